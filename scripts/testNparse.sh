@@ -23,6 +23,13 @@ else
   echo "::error ::❌ Tests failed!" | tee -a "$LOG_FILE"
 fi
 
+# The scheduler core must keep building and passing without gRPC/protoc.
+if cargo test -p timpani-o --no-default-features --features core >>"$LOG_FILE" 2>&1; then
+  echo "✅ Core-only tests passed" | tee -a "$LOG_FILE"
+else
+  echo "::error ::❌ Core-only (--no-default-features --features core) tests failed!" | tee -a "$LOG_FILE"
+fi
+
 echo "🔍 Debug: Test output file size: $(wc -l < "$TMP_FILE" 2>/dev/null || echo 0) lines" | tee -a "$LOG_FILE"
 
 if [[ -f "$TMP_FILE" ]]; then
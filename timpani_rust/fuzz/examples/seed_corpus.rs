@@ -0,0 +1,23 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Regenerate `corpus/sched_info` from the test-tools workload files.
+//!
+//! ```text
+//! cargo run -p timpani-fuzz --example seed_corpus
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+fn main() -> std::io::Result<()> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus/sched_info");
+    fs::create_dir_all(&dir)?;
+    for (name, bytes) in timpani_fuzz::seed_corpus() {
+        fs::write(dir.join(&name), bytes)?;
+        println!("{name}");
+    }
+    Ok(())
+}
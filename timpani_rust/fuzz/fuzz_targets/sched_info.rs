@@ -0,0 +1,20 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Arbitrary bytes as an `AddSchedInfo` request (see the `timpani-fuzz`
+//! crate docs).
+
+#![no_main]
+
+use std::sync::OnceLock;
+
+use libfuzzer_sys::fuzz_target;
+use timpani_fuzz::Pipeline;
+
+static PIPELINE: OnceLock<Pipeline> = OnceLock::new();
+
+fuzz_target!(|data: &[u8]| {
+    PIPELINE.get_or_init(Pipeline::new).run(data);
+});
@@ -0,0 +1,166 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Whole-pipeline fuzzing of the `AddSchedInfo` path.
+//!
+//! [`Pipeline::run`] treats its input as a protobuf-encoded `SchedInfo` and
+//! takes it through every step a real request goes through up to the
+//! placement:
+//!
+//! 1. `prost` decoding — undecodable input stops here;
+//! 2. [`SchedInfoServiceImpl::validate_request`] — names, metadata and
+//!    option overrides, exactly as the service checks them;
+//! 3. [`task_from_proto`] for every task;
+//! 4. [`GlobalScheduler::schedule_with_options`] against [`NODE_CONFIG`],
+//!    with strict simulation capped at [`SIMULATION_BUDGET`] so no input
+//!    can make a run unbounded;
+//! 5. on success, [`check_schedule`] — a violated invariant panics.
+//!
+//! Every other error is an expected outcome.  The `sched_info` fuzz target
+//! only feeds bytes to [`Pipeline::run`]; `tests/corpus.rs` replays the seed
+//! corpus through it on every `cargo test`.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use prost::Message;
+use timpani_o::config::NodeConfigManager;
+use timpani_o::fault::{FaultError, FaultNotification, FaultNotifier};
+use timpani_o::grpc::new_workload_store;
+use timpani_o::grpc::schedinfo_service::{task_from_proto, SchedInfoServiceImpl};
+use timpani_o::proto::schedinfo_v1::SchedInfo;
+use timpani_o::scheduler::{
+    check_schedule, GlobalScheduler, SchedAlgorithm, SchedulerError, SimulationCheck,
+};
+use timpani_o::task::{Micros, NodeSchedMap, Task};
+
+/// The fixed cluster every input is scheduled on: the three nodes of
+/// `timpani-o/examples/node_configurations.yaml`.
+pub const NODE_CONFIG: &str = include_str!("../../timpani-o/examples/node_configurations.yaml");
+
+/// Longest hyperperiod the simulation gate will walk (the budget guard).
+pub const SIMULATION_BUDGET: Micros = Micros(100_000);
+
+/// The workload files under `test-tools/workloads`, by name.
+const WORKLOADS: [(&str, &str); 1] = [(
+    "example_workload",
+    include_str!("../../test-tools/workloads/example_workload.yaml"),
+)];
+
+// ── Pipeline ──────────────────────────────────────────────────────────────────
+
+/// How far an input got.
+#[derive(Debug)]
+pub enum Outcome {
+    /// Not a `SchedInfo` message.
+    Undecodable,
+    /// Refused by request validation.
+    Invalid(SchedulerError),
+    /// Valid, but the scheduler refused it.
+    Rejected(SchedulerError),
+    /// Placed; the invariants hold.
+    Scheduled(NodeSchedMap),
+}
+
+/// The service and scheduler an input runs through (see the crate docs).
+pub struct Pipeline {
+    nodes: Arc<NodeConfigManager>,
+    service: SchedInfoServiceImpl,
+    scheduler: GlobalScheduler,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        let mut nodes = NodeConfigManager::new();
+        nodes
+            .load_from_str(NODE_CONFIG, Path::new("node_configurations.yaml"))
+            .expect("fuzz node configuration is valid");
+        let nodes = Arc::new(nodes);
+        Self {
+            service: SchedInfoServiceImpl::new(
+                Arc::clone(&nodes),
+                new_workload_store(),
+                Arc::new(Discard),
+            ),
+            scheduler: GlobalScheduler::new(Arc::clone(&nodes)),
+            nodes,
+        }
+    }
+
+    /// Decode, validate, convert and schedule `data`.
+    ///
+    /// # Panics
+    /// If the scheduler accepts the request but its schedule breaks an
+    /// invariant.
+    pub fn run(&self, data: &[u8]) -> Outcome {
+        let Ok(req) = SchedInfo::decode(data) else {
+            return Outcome::Undecodable;
+        };
+        let opts = match self.service.validate_request(&req) {
+            Ok(opts) => opts
+                .with_simulation_check(SimulationCheck::Strict)
+                .with_simulation_hyperperiod_limit(SIMULATION_BUDGET),
+            Err(e) => return Outcome::Invalid(e),
+        };
+        let tasks: Vec<Task> = req
+            .tasks
+            .iter()
+            .map(|t| task_from_proto(t, &req.workload_id))
+            .collect();
+        match self.scheduler.schedule_with_options(tasks.clone(), &opts) {
+            Ok(map) => {
+                if let Err(e) = check_schedule(&map, &tasks, &self.nodes, &opts) {
+                    panic!("schedule breaks an invariant: {e}\n{map:#?}");
+                }
+                Outcome::Scheduled(map)
+            }
+            Err(e) => Outcome::Rejected(e),
+        }
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Drops every fault; nothing in the pipeline should raise one.
+struct Discard;
+
+#[tonic::async_trait]
+impl FaultNotifier for Discard {
+    async fn notify_fault(&self, _info: FaultNotification) -> Result<(), FaultError> {
+        Ok(())
+    }
+}
+
+// ── Seed corpus ───────────────────────────────────────────────────────────────
+
+/// Valid requests to seed the corpus with: each workload file once per
+/// algorithm, named `<workload>-<algorithm>`.
+pub fn seed_requests() -> Vec<(String, SchedInfo)> {
+    let mut seeds = Vec::new();
+    for (name, yaml) in WORKLOADS {
+        let req: SchedInfo = serde_yaml::from_str(yaml).expect("workload file parses");
+        for alg in SchedAlgorithm::ALL {
+            let mut req = req.clone();
+            req.algorithm = Some(alg.as_str().to_string());
+            if alg == SchedAlgorithm::RandomizedSpread {
+                req.seed = Some(42);
+            }
+            seeds.push((format!("{name}-{alg}"), req));
+        }
+    }
+    seeds
+}
+
+/// [`seed_requests`] as corpus files: `(file name, protobuf bytes)`.
+pub fn seed_corpus() -> Vec<(String, Vec<u8>)> {
+    seed_requests()
+        .into_iter()
+        .map(|(name, req)| (name, req.encode_to_vec()))
+        .collect()
+}
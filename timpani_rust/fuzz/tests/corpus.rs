@@ -0,0 +1,47 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Replays the `sched_info` corpus through the fuzz pipeline.
+
+use std::fs;
+use std::path::Path;
+
+use timpani_fuzz::{seed_corpus, Outcome, Pipeline};
+
+fn corpus_dir() -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus/sched_info")
+}
+
+#[test]
+fn seed_corpus_is_up_to_date() {
+    for (name, bytes) in seed_corpus() {
+        let on_disk = fs::read(corpus_dir().join(&name)).unwrap_or_default();
+        assert!(
+            on_disk == bytes,
+            "corpus/sched_info/{name} is stale; run `cargo run -p timpani-fuzz --example seed_corpus`"
+        );
+    }
+}
+
+#[test]
+fn every_seed_is_scheduled() {
+    let pipeline = Pipeline::new();
+    for (name, bytes) in seed_corpus() {
+        let outcome = pipeline.run(&bytes);
+        assert!(
+            matches!(outcome, Outcome::Scheduled(_)),
+            "{name}: {outcome:?}"
+        );
+    }
+}
+
+#[test]
+fn corpus_replays_without_panicking() {
+    let pipeline = Pipeline::new();
+    for entry in fs::read_dir(corpus_dir()).unwrap() {
+        pipeline.run(&fs::read(entry.unwrap().path()).unwrap());
+    }
+    assert!(matches!(pipeline.run(b"\xff\xff"), Outcome::Undecodable));
+}
@@ -0,0 +1,149 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! codec-bench — size and timing comparison of the snapshot formats.
+//!
+//! Builds a synthetic `NodeSchedMap`, and a `ClusterState` around it, and
+//! encodes/decodes each repeatedly with every `timpani_o::codec::Format`,
+//! printing bytes and mean times.
+//!
+//! # Usage
+//! ```text
+//! cargo run --release --bin codec-bench -- --tasks 5000 --nodes 16
+//! ```
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use clap::Parser;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use timpani_o::admission::ClusterState;
+use timpani_o::codec::{self, Format};
+use timpani_o::task::{Nanos, NodeSchedMap, SchedPolicy, SchedTask};
+
+// ── CLI ───────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "codec-bench",
+    about = "Compare JSON and CBOR snapshot size and speed"
+)]
+struct Cli {
+    /// Total number of tasks in the synthetic schedule.
+    #[arg(long, default_value_t = 5000)]
+    tasks: usize,
+
+    /// Number of nodes the tasks are spread across.
+    #[arg(long, default_value_t = 16)]
+    nodes: usize,
+
+    /// Encode/decode repetitions per format.
+    #[arg(long, default_value_t = 20)]
+    iterations: u32,
+}
+
+// ── Main ──────────────────────────────────────────────────────────────────────
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let map = synthetic_schedule(cli.tasks, cli.nodes.max(1));
+    let iterations = cli.iterations.max(1);
+
+    println!(
+        "{} task(s) on {} node(s), {} iteration(s)",
+        cli.tasks,
+        map.len(),
+        iterations
+    );
+    compare("schedule", &map, iterations)?;
+    compare("cluster state", &cluster_state(map), iterations)?;
+    Ok(())
+}
+
+/// Print the size and mean encode / decode time of `value` in each format.
+fn compare<T>(label: &str, value: &T, iterations: u32) -> Result<()>
+where
+    T: Serialize + DeserializeOwned + PartialEq,
+{
+    println!();
+    println!(
+        "{:<14} {:<6} {:>12} {:>14} {:>14}",
+        "snapshot", "format", "bytes", "encode (mean)", "decode (mean)"
+    );
+    for format in [Format::Json, Format::Cbor] {
+        let mut bytes = Vec::new();
+        let encode = mean(iterations, || {
+            bytes = codec::encode(value, format)?;
+            Ok(())
+        })?;
+        let decode = mean(iterations, || {
+            let back: T = codec::decode(&bytes)?;
+            anyhow::ensure!(back == *value, "{format} round trip changed the {label}");
+            Ok(())
+        })?;
+        println!(
+            "{:<14} {:<6} {:>12} {:>14.2?} {:>14.2?}",
+            label,
+            format,
+            bytes.len(),
+            encode,
+            decode
+        );
+    }
+    Ok(())
+}
+
+/// Mean wall-clock time of `iterations` calls to `f`.
+fn mean(iterations: u32, mut f: impl FnMut() -> Result<()>) -> Result<Duration> {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f()?;
+    }
+    Ok(start.elapsed() / iterations)
+}
+
+/// Round-robin `tasks` representative tasks over `nodes` nodes.
+fn synthetic_schedule(tasks: usize, nodes: usize) -> NodeSchedMap {
+    let mut map = NodeSchedMap::new();
+    for i in 0..tasks {
+        let node = format!("node{:02}", i % nodes);
+        let period_us = 1_000 * (1 + (i as u64 % 50));
+        map.entry(node.clone()).or_default().push(SchedTask {
+            name: format!("task_{i:05}"),
+            assigned_node: node,
+            assigned_cpu: (i % 8) as u32,
+            policy: SchedPolicy::Fifo,
+            priority: (i % 99) as i32 + 1,
+            period_ns: Nanos(period_us * 1_000),
+            runtime_ns: Nanos(period_us * 100),
+            deadline_ns: Nanos(period_us * 1_000),
+            release_time_us: 0,
+            max_dmiss: 3,
+            shared_resources: Vec::new(),
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        });
+    }
+    map
+}
+
+/// `schedule` with every other node cordoned and one CPU of each node
+/// offline, as a running instance would snapshot it.
+fn cluster_state(schedule: NodeSchedMap) -> ClusterState {
+    let nodes: Vec<String> = schedule.keys().cloned().collect();
+    ClusterState {
+        cordoned: nodes.iter().step_by(2).cloned().collect(),
+        rt_incapable: Default::default(),
+        offline_cpus: nodes.iter().map(|n| (n.clone(), vec![7])).collect(),
+        schedule,
+    }
+}
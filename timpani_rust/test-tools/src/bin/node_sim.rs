@@ -66,6 +66,11 @@ struct Cli {
     /// Simulates the task running for a while before missing its deadline.
     #[arg(long, default_value_t = 2000)]
     dmiss_delay_ms: u64,
+
+    /// Free memory (MB) every node reports with GetSchedInfo.  Omitted from
+    /// the request when unset.
+    #[arg(long)]
+    free_memory_mb: Option<u64>,
 }
 
 // ── main ──────────────────────────────────────────────────────────────────────
@@ -128,9 +133,11 @@ async fn main() -> Result<()> {
         let addr = addr.clone();
         let dmiss = dmiss_target.clone();
         let dmiss_delay = cli.dmiss_delay_ms;
+        let free_memory_mb = cli.free_memory_mb;
 
         let handle = tokio::spawn(async move {
-            if let Err(e) = simulate_node(&node_id, &addr, dmiss, dmiss_delay).await {
+            if let Err(e) = simulate_node(&node_id, &addr, dmiss, dmiss_delay, free_memory_mb).await
+            {
                 error!("[{node_id}] simulation error: {e}");
             }
         });
@@ -152,6 +159,7 @@ async fn simulate_node(
     addr: &str,
     dmiss: Option<(String, String)>,
     dmiss_delay_ms: u64,
+    free_memory_mb: Option<u64>,
 ) -> Result<()> {
     let mut client = NodeServiceClient::connect(addr.to_string())
         .await
@@ -172,14 +180,18 @@ async fn simulate_node(
     let resp = client
         .get_sched_info(NodeSchedRequest {
             node_id: node_id.to_string(),
+            free_memory_mb,
+            ..Default::default()
         })
         .await
         .map_err(|s| anyhow::anyhow!("[{node_id}] GetSchedInfo failed: {s}", node_id = node_id))?
         .into_inner();
 
     info!(
-        "[{node_id}] ← GetSchedInfo: workload='{}' hyperperiod={}µs tasks={}",
+        "[{node_id}] ← GetSchedInfo: workload='{}' generation={} full={} hyperperiod={}µs tasks={}",
         resp.workload_id,
+        resp.generation,
+        resp.full,
         resp.hyperperiod_us,
         resp.tasks.len()
     );
@@ -258,6 +270,7 @@ async fn simulate_node(
                 .report_d_miss(DeadlineMissInfo {
                     node_id: node_id.to_string(),
                     task_name: dmiss_task.clone(),
+                    drift: None,
                 })
                 .await
                 .map_err(|s| {
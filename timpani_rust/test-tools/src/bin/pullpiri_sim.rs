@@ -36,11 +36,13 @@ use tonic::transport::Server;
 use tonic::{Request, Response, Status};
 use tracing::{error, info};
 
+use timpani_o::limits::{InputLimits, LimitHits};
 use timpani_o::proto::schedinfo_v1::{
     fault_service_server::{FaultService, FaultServiceServer},
     sched_info_service_client::SchedInfoServiceClient,
-    FaultInfo, Response as ProtoResponse, SchedInfo,
+    FaultInfo, Response as ProtoResponse,
 };
+use timpani_o::taskfile;
 
 // ── CLI ───────────────────────────────────────────────────────────────────────
 
@@ -83,8 +85,27 @@ impl FaultService for PullpiriFaultService {
         let fault_type = match info.r#type {
             0 => "UNKNOWN",
             1 => "DMISS",
+            2 => "FEASIBILITY",
             _ => "INVALID",
         };
+        let severity = match info.severity {
+            0 => "CRITICAL",
+            1 => "ADVISORY",
+            _ => "INVALID",
+        };
+        let detail = info
+            .feasibility
+            .as_ref()
+            .map(|f| {
+                format!(
+                    "\tdetail    : cpu {} at {:.1}% > {} bound {:.1}%\n",
+                    f.cpu,
+                    f.utilization * 100.0,
+                    f.analysis,
+                    f.bound * 100.0
+                )
+            })
+            .unwrap_or_default();
         // Use eprintln directly so the notification stands out regardless of
         // log level.
         eprintln!(
@@ -92,17 +113,21 @@ impl FaultService for PullpiriFaultService {
              \tworkload  : {}\n\
              \tnode      : {}\n\
              \ttask      : {}\n\
-             \ttype      : {}\n",
-            info.workload_id, info.node_id, info.task_name, fault_type
+             \ttype      : {} ({})\n{}",
+            info.workload_id, info.node_id, info.task_name, fault_type, severity, detail
         );
         info!(
             workload = %info.workload_id,
             node     = %info.node_id,
             task     = %info.task_name,
             fault    = %fault_type,
+            severity = %severity,
             "FaultService: NotifyFault received"
         );
-        Ok(Response::new(ProtoResponse { status: 0 }))
+        Ok(Response::new(ProtoResponse {
+            status: 0,
+            ..Default::default()
+        }))
     }
 }
 
@@ -146,14 +171,15 @@ async fn main() -> Result<()> {
 
     // ── Step 2: read workload YAML ────────────────────────────────────────────
     info!("Reading workload from: {}", cli.workload.display());
-    let file = std::fs::File::open(&cli.workload)
+    let yaml = std::fs::read_to_string(&cli.workload)
         .map_err(|e| anyhow::anyhow!("cannot open workload file: {e}"))?;
-    let sched_info: SchedInfo = serde_yaml::from_reader(file)
+    let sched_info = taskfile::parse(&yaml, &InputLimits::default(), &LimitHits::default())
         .map_err(|e| anyhow::anyhow!("failed to parse workload YAML: {e}"))?;
 
     info!(
-        workload_id = %sched_info.workload_id,
-        task_count  = sched_info.tasks.len(),
+        workload_id    = %sched_info.workload_id,
+        priority_class = sched_info.priority_class.as_deref().unwrap_or("platform"),
+        task_count     = sched_info.tasks.len(),
         "Workload loaded"
     );
     for (i, t) in sched_info.tasks.iter().enumerate() {
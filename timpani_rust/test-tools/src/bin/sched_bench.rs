@@ -0,0 +1,183 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! sched-bench — placement quality of every algorithm against the optimum.
+//!
+//! Schedules many small random workloads with every `SchedAlgorithm` and
+//! measures each result with `timpani_o::scheduler::quality`.  The
+//! workloads are small enough for the exhaustive solver, so the table shows
+//! how far each heuristic strays from the fewest CPUs possible — on average
+//! and at worst.
+//!
+//! # Usage
+//! ```text
+//! cargo run --release --bin sched-bench -- --runs 500 --tasks 10 --nodes 2 --cpus 4
+//! ```
+
+use std::sync::Arc;
+
+use anyhow::{ensure, Result};
+use clap::Parser;
+
+use timpani_o::config::{NodeConfig, NodeConfigManager};
+use timpani_o::scheduler::quality::{
+    evaluate, optimal_cpus, QualityConfig, MAX_OPTIMAL_CPUS, MAX_OPTIMAL_TASKS,
+};
+use timpani_o::scheduler::spread::SplitMix64;
+use timpani_o::scheduler::{GlobalScheduler, SchedAlgorithm, ScheduleOptions};
+use timpani_o::task::{Micros, Task};
+
+/// Period of every synthetic task; runtimes are drawn against it.
+const PERIOD_US: u64 = 10_000;
+
+// ── CLI ───────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "sched-bench",
+    about = "Compare the scheduling algorithms' placements with the optimum"
+)]
+struct Cli {
+    /// Random workloads to schedule.
+    #[arg(long, default_value_t = 200)]
+    runs: u32,
+
+    /// Tasks per workload.
+    #[arg(long, default_value_t = 8)]
+    tasks: usize,
+
+    /// Nodes in the synthetic cluster.
+    #[arg(long, default_value_t = 2)]
+    nodes: usize,
+
+    /// CPUs per node.
+    #[arg(long, default_value_t = 4)]
+    cpus: usize,
+
+    /// Seed of the first workload; run `i` uses `seed + i`.
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+}
+
+// ── Main ──────────────────────────────────────────────────────────────────────
+
+/// Totals of one algorithm over every run.
+#[derive(Debug, Default)]
+struct Tally {
+    placed: u32,
+    failed: u32,
+    cpus_used: usize,
+    excess_over_bound: usize,
+    excess_over_optimum: usize,
+    worst_over_optimum: usize,
+    variance: f64,
+    near_threshold: usize,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let total_cpus = cli.nodes * cli.cpus;
+    ensure!(
+        (1..=MAX_OPTIMAL_TASKS).contains(&cli.tasks),
+        "--tasks must be 1..={MAX_OPTIMAL_TASKS} for the exhaustive optimum"
+    );
+    ensure!(
+        (1..=MAX_OPTIMAL_CPUS).contains(&total_cpus),
+        "--nodes × --cpus must be 1..={MAX_OPTIMAL_CPUS} for the exhaustive optimum"
+    );
+
+    let nodes: Vec<String> = (0..cli.nodes).map(|i| format!("node{i:02}")).collect();
+    let scheduler = GlobalScheduler::new(Arc::new(cluster(&nodes, cli.cpus)));
+    let quality = QualityConfig::default();
+    let mut tallies: Vec<(SchedAlgorithm, Tally)> = SchedAlgorithm::ALL
+        .into_iter()
+        .map(|a| (a, Tally::default()))
+        .collect();
+    let mut infeasible = 0;
+
+    for run in 0..cli.runs {
+        let seed = cli.seed.wrapping_add(u64::from(run));
+        let tasks = workload(seed, cli.tasks, &nodes);
+        let utilizations: Vec<_> = tasks.iter().map(Task::exact_utilization).collect();
+        let Some(optimum) =
+            optimal_cpus(&utilizations, total_cpus, quality.cpu_utilization_threshold)
+        else {
+            infeasible += 1;
+            continue;
+        };
+        for (algorithm, tally) in &mut tallies {
+            let opts = ScheduleOptions::default()
+                .with_algorithm(*algorithm)
+                .with_seed(seed);
+            let Ok(map) = scheduler.schedule_with_options(tasks.clone(), &opts) else {
+                tally.failed += 1;
+                continue;
+            };
+            let m = evaluate(&map, &quality);
+            let over = m.cpus_used.saturating_sub(optimum);
+            tally.placed += 1;
+            tally.cpus_used += m.cpus_used;
+            tally.excess_over_bound += m.excess_cpus();
+            tally.excess_over_optimum += over;
+            tally.worst_over_optimum = tally.worst_over_optimum.max(over);
+            tally.variance += m.utilization_variance;
+            tally.near_threshold += m.near_threshold;
+        }
+    }
+
+    println!(
+        "{} run(s) of {} task(s) on {} node(s) × {} CPU(s); {} without a placement skipped",
+        cli.runs, cli.tasks, cli.nodes, cli.cpus, infeasible
+    );
+    println!(
+        "{:<22} {:>6} {:>6} {:>9} {:>9} {:>9} {:>10} {:>9} {:>6}",
+        "algorithm", "placed", "failed", "cpus", ">bound", ">optimum", "worst", "variance", "near"
+    );
+    for (algorithm, t) in &tallies {
+        let n = f64::from(t.placed.max(1));
+        println!(
+            "{:<22} {:>6} {:>6} {:>9.2} {:>9.2} {:>9.2} {:>10} {:>9.4} {:>6.2}",
+            algorithm.as_str(),
+            t.placed,
+            t.failed,
+            t.cpus_used as f64 / n,
+            t.excess_over_bound as f64 / n,
+            t.excess_over_optimum as f64 / n,
+            t.worst_over_optimum,
+            t.variance / n,
+            t.near_threshold as f64 / n,
+        );
+    }
+    Ok(())
+}
+
+/// `nodes` with CPUs `0..cpus` each.
+fn cluster(nodes: &[String], cpus: usize) -> NodeConfigManager {
+    NodeConfigManager::from_nodes(
+        nodes
+            .iter()
+            .map(|name| NodeConfig {
+                available_cpus: (0..cpus as u32).collect(),
+                ..NodeConfig::default_config(name.as_str())
+            })
+            .collect(),
+    )
+}
+
+/// `count` tasks of 5–60 % utilisation, each targeting a random node.
+fn workload(seed: u64, count: usize, nodes: &[String]) -> Vec<Task> {
+    let mut rng = SplitMix64::new(seed);
+    (0..count)
+        .map(|i| Task {
+            name: format!("task_{i:02}"),
+            workload_id: "bench".into(),
+            target_node: nodes[rng.below(nodes.len())].clone(),
+            period_us: Micros(PERIOD_US),
+            runtime_us: Micros(PERIOD_US * (5 + rng.below(56) as u64) / 100),
+            deadline_us: Micros(PERIOD_US),
+            ..Default::default()
+        })
+        .collect()
+}
@@ -0,0 +1,27 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+/// Build script – compiles the NodeService protocol into Rust source code.
+///
+/// Timpani-N speaks only `node_service.proto`, which Timpani-O keeps
+/// self-contained for this purpose; it is compiled from the Timpani-O tree
+/// so both sides always build against the same file.  Only the client is
+/// generated.  The generated file is written to `OUT_DIR` and pulled into
+/// the crate via `tonic::include_proto!` in `src/proto.rs`.
+///
+/// `protoc` must be on `$PATH`, or its path set in the `PROTOC` environment
+/// variable (see the Timpani-O build script).
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let proto_root = "../timpani-o/proto";
+    let proto_file = format!("{}/node_service.proto", proto_root);
+    println!("cargo:rerun-if-changed={}", proto_file);
+
+    tonic_build::configure()
+        .build_server(false)
+        .build_client(true)
+        .compile_protos(&[proto_file.as_str()], &[proto_root])?;
+
+    Ok(())
+}
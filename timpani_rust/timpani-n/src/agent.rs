@@ -0,0 +1,898 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! The node's schedule cycle against Timpani-O.
+//!
+//! Nodes poll: every `--poll-interval-ms` the [`NodeAgent`] makes one
+//! round trip through its [`Upstream`] ([`NodeAgent::poll`]):
+//!
+//! 1. `GetSchedInfo`, sending what the node runs (`known_generation`,
+//!    `known_instance_epoch`, `known_tasks` from the [`ScheduleStore`]),
+//!    its free memory, the CPUs online right now (read afresh each cycle
+//!    by the [`OnlineCpuWatcher`], which logs every hotplug transition),
+//!    what its time source says about the clock ([`ClockStatus`]), when a
+//!    scan is due the RT load of other threads on the CPUs the schedule
+//!    uses ([`ForeignLoadScanner`]), and the features it understands (deltas and placeholders; it cannot
+//!    stage a transactional push);
+//! 2. the answer goes into the store, which decides its [`PushStatus`];
+//! 3. an `Applied` push is applied task by task, each PID resolved afresh
+//!    with the `--resolver` rules, and the per-task [`ApplyReport`] goes
+//!    back with `ReportApply`; a `Stale` one is acknowledged with
+//!    [`ApplyReport::ack`].  A `Duplicate` — the answer to every poll once
+//!    the node is up to date — needs no answer.
+//!
+//! A report that could not be sent is sent again before the next request.
+//!
+//! After each round trip the [`DriftMonitor`] reads the applied tasks
+//! back, right after an apply and then every `--verify-interval-secs`,
+//! re-applying up to `--max-reapply` times.  Each drift goes upstream as a
+//! `ReportDMiss` carrying a `SchedDrift`; one that cannot be sent is only
+//! logged, and is found again at the next check if it persists.
+//!
+//! [`NodeAgent::serve`] is the main loop.  Like the C node it gives up if
+//! Timpani-O cannot be reached within `--connect-attempts` tries, one
+//! [`RETRY_INTERVAL_MS`](crate::config::defaults::RETRY_INTERVAL_MS) apart;
+//! once Timpani-O has answered, a failed poll is simply retried at the next
+//! cycle.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info, warn};
+
+use crate::apply::{Applier, ApplyReport, NodeApplyInfo, SchedBackend};
+use crate::capability::Capabilities;
+use crate::clock::{ClockProbe, ClockStatus, SystemClockProbe};
+use crate::config::{defaults, Config};
+use crate::error::{TimpaniError, TimpaniResult};
+use crate::foreign::{scheduled_pids, ForeignLoadScanner, ThreadSample};
+use crate::hotplug::{online_mask, OnlineCpuWatcher};
+use crate::memory::free_memory_mb;
+use crate::proto::node_v1::{
+    self, CpuSet, DeadlineMissInfo, ForeignLoadReport, NodeFeature, NodeSchedRequest,
+};
+use crate::resolve::{read_processes, ProcessInfo, ResolveRule, Resolver, SystemdUnits};
+use crate::schedule::{PushStatus, SchedulePush, ScheduleStore};
+use crate::upstream::Upstream;
+use crate::verify::DriftMonitor;
+
+/// What this build announces in `NodeSchedRequest.features`.
+pub const FEATURES: u32 = NodeFeature::Delta as u32 | NodeFeature::Placeholder as u32;
+
+/// Runs the node's side of the NodeService protocol (see the module docs).
+pub struct NodeAgent<'a> {
+    node_id: String,
+    backend: &'a dyn SchedBackend,
+    units: &'a dyn SystemdUnits,
+    clock: &'a dyn ClockProbe,
+    rules: Vec<ResolveRule>,
+    caps: Capabilities,
+    local_fault_sink: bool,
+    /// Lists the candidate processes at each apply.
+    processes: fn() -> Vec<ProcessInfo>,
+    cpus: OnlineCpuWatcher,
+    store: ScheduleStore,
+    drift: DriftMonitor,
+    /// `None` with `--foreign-scan-interval-secs 0`.
+    foreign: Option<ForeignLoadScanner>,
+    /// The report of the last push applied.
+    applied: Option<ApplyReport>,
+    /// A report whose `ReportApply` failed.
+    unsent: Option<ApplyReport>,
+}
+
+impl<'a> NodeAgent<'a> {
+    /// An agent for the node `config` describes, applying through `backend`
+    /// and asking `units` for `SystemdUnit` rules.
+    pub fn new(
+        config: &Config,
+        caps: Capabilities,
+        backend: &'a dyn SchedBackend,
+        units: &'a dyn SystemdUnits,
+    ) -> Self {
+        Self {
+            node_id: config.node_id.clone(),
+            backend,
+            units,
+            clock: &SystemClockProbe,
+            rules: config.resolvers.clone(),
+            caps,
+            local_fault_sink: config.local_fault_sink.is_some(),
+            processes: read_processes,
+            cpus: OnlineCpuWatcher::new(),
+            store: ScheduleStore::new(),
+            drift: DriftMonitor::new(Duration::from_secs(config.verify_interval_secs))
+                .with_max_reapply(config.max_reapply),
+            foreign: (config.foreign_scan_interval_secs > 0).then(|| {
+                ForeignLoadScanner::new(0, Duration::from_secs(config.foreign_scan_interval_secs))
+            }),
+            applied: None,
+            unsent: None,
+        }
+    }
+
+    /// List candidate processes with `processes` instead of reading `/proc`.
+    pub fn with_processes(mut self, processes: fn() -> Vec<ProcessInfo>) -> Self {
+        self.processes = processes;
+        self
+    }
+
+    /// Ask `clock` about the clock instead of chrony and the PHC.
+    pub fn with_clock_probe(mut self, clock: &'a dyn ClockProbe) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Read the online CPU list with `source` instead of sysfs.
+    pub fn with_online_cpus(mut self, source: fn() -> Option<Vec<u32>>) -> Self {
+        self.cpus = self.cpus.with_source(source);
+        self
+    }
+
+    /// List threads for the foreign-load scan with `threads` instead of
+    /// reading `/proc`.
+    pub fn with_threads(mut self, threads: fn() -> Vec<ThreadSample>) -> Self {
+        self.foreign = self.foreign.map(|f| f.with_source(threads));
+        self
+    }
+
+    /// The schedule the node runs.
+    pub fn store(&self) -> &ScheduleStore {
+        &self.store
+    }
+
+    /// The report of the last push applied, whether or not it was sent.
+    pub fn applied(&self) -> Option<&ApplyReport> {
+        self.applied.as_ref()
+    }
+
+    /// The `GetSchedInfo` request for the node's current state.
+    pub fn request(&self) -> NodeSchedRequest {
+        let generation = self.store.generation();
+        let epoch = self.store.instance_epoch();
+        NodeSchedRequest {
+            node_id: self.node_id.clone(),
+            known_generation: (generation > 0).then_some(generation),
+            known_instance_epoch: (epoch > 0).then_some(epoch),
+            known_tasks: self.store.tasks().iter().map(|t| t.name.clone()).collect(),
+            free_memory_mb: free_memory_mb(),
+            online_cpus: self.cpus.online().map(|cpus| CpuSet {
+                cpus: cpus.to_vec(),
+            }),
+            clock: ClockStatus::probe(self.clock).map(Into::into),
+            features: FEATURES,
+            ..Default::default()
+        }
+    }
+
+    /// One cycle (see the module docs).  `Ok` once Timpani-O has answered,
+    /// even with no schedule yet.
+    pub fn poll(&mut self, upstream: &mut dyn Upstream) -> TimpaniResult<()> {
+        self.poll_at(upstream, Instant::now())
+    }
+
+    /// [`poll`](Self::poll) at `now`.
+    pub fn poll_at(&mut self, upstream: &mut dyn Upstream, now: Instant) -> TimpaniResult<()> {
+        let synced = self.sync(upstream, now);
+        self.verify(upstream, now);
+        synced
+    }
+
+    /// The round trip: fetch, apply and report.
+    fn sync(&mut self, upstream: &mut dyn Upstream, now: Instant) -> TimpaniResult<()> {
+        if let Some(report) = self.unsent.take() {
+            self.send(upstream, report)?;
+        }
+        self.cpus.poll();
+        let mut request = self.request();
+        request.foreign_load = self.scan_foreign(now);
+        let Some(resp) = upstream.get_sched_info(request)? else {
+            debug!(node_id = %self.node_id, "no workload scheduled yet");
+            return Ok(());
+        };
+        let push = SchedulePush::from(resp);
+        let generation = push.generation;
+        let report = match self.store.push(push) {
+            PushStatus::Applied => {
+                let report = self.apply(generation);
+                self.drift.track(&report, self.store.tasks(), now);
+                self.applied = Some(report.clone());
+                report
+            }
+            PushStatus::Duplicate => return Ok(()),
+            status => ApplyReport::ack(&self.node_id, generation, status),
+        };
+        self.send(upstream, report)
+    }
+
+    /// Apply the stored schedule of `generation`.
+    fn apply(&self, generation: u64) -> ApplyReport {
+        let processes = (self.processes)();
+        let resolver = Resolver {
+            rules: &self.rules,
+            processes: &processes,
+            units: self.units,
+        };
+        let report = Applier::new(self.backend, self.caps)
+            .with_resolver(&resolver)
+            .with_local_fault_sink(self.local_fault_sink)
+            .apply_all(
+                &self.node_id,
+                generation,
+                self.store.tasks(),
+                NodeApplyInfo::read(&self.caps),
+            );
+        info!(
+            node_id    = %self.node_id,
+            generation,
+            tasks      = report.tasks.len(),
+            failed     = report.failed(),
+            "schedule applied"
+        );
+        report
+    }
+
+    /// The foreign load on the CPUs the schedule uses, if a scan is due.
+    /// No scan before there is a schedule.
+    fn scan_foreign(&mut self, now: Instant) -> Option<ForeignLoadReport> {
+        let scanner = self.foreign.as_mut()?;
+        let managed = self.store.tasks().iter().fold(0, |mask, t| {
+            // Affinity 0 = any CPU.
+            mask | if t.cpu_affinity == 0 {
+                online_mask()
+            } else {
+                t.cpu_affinity
+            }
+        });
+        if managed == 0 || !scanner.due(now) {
+            return None;
+        }
+        scanner.set_managed(managed);
+        let scheduled = self
+            .applied
+            .as_ref()
+            .map(scheduled_pids)
+            .unwrap_or_default();
+        let loads = scanner.scan(&scheduled, now);
+        Some(ForeignLoadReport {
+            cpus: loads.iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Check for drift if due, and report each one.
+    fn verify(&mut self, upstream: &mut dyn Upstream, now: Instant) {
+        if !self.drift.due(now) {
+            return;
+        }
+        for drift in self.drift.check(self.backend, now) {
+            let info = DeadlineMissInfo {
+                node_id: self.node_id.clone(),
+                task_name: drift.task_name.clone(),
+                drift: Some((&drift).into()),
+            };
+            if upstream.report_dmiss(info).is_err() {
+                debug!(task = %drift.task_name, "drift not reported");
+            }
+        }
+    }
+
+    /// `ReportApply`; kept for the next poll if it fails.
+    fn send(&mut self, upstream: &mut dyn Upstream, report: ApplyReport) -> TimpaniResult<()> {
+        upstream
+            .report_apply(node_v1::ApplyReport::from(&report))
+            .inspect_err(|_| self.unsent = Some(report))
+    }
+
+    /// Poll every `poll_interval` until `shutdown` is set (see the module
+    /// docs).  `Err(Network)` if Timpani-O never answers within
+    /// `connect_attempts` tries.
+    pub fn serve(
+        &mut self,
+        upstream: &mut dyn Upstream,
+        connect_attempts: u32,
+        poll_interval: Duration,
+        shutdown: &AtomicBool,
+    ) -> TimpaniResult<()> {
+        let retry_interval = Duration::from_millis(defaults::RETRY_INTERVAL_MS);
+        let mut attempt = 0;
+        while !shutdown.load(Ordering::SeqCst) {
+            match self.poll(upstream) {
+                Ok(()) => break,
+                Err(_) => {
+                    attempt += 1;
+                    if attempt >= connect_attempts {
+                        warn!(attempts = attempt, "cannot reach Timpani-O, giving up");
+                        return Err(TimpaniError::Network);
+                    }
+                    info!(
+                        attempt,
+                        connect_attempts, "Timpani-O not reachable, retrying"
+                    );
+                    thread::sleep(retry_interval);
+                }
+            }
+        }
+        while !shutdown.load(Ordering::SeqCst) {
+            thread::sleep(poll_interval);
+            if self.poll(upstream).is_err() {
+                debug!("poll failed, retrying at the next cycle");
+            }
+        }
+        info!("shutdown requested");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::proto::node_v1::NodeSchedResponse;
+
+    /// Answers `GetSchedInfo` from a queue (empty = `NOT_FOUND`) and records
+    /// every call.  `down` fails every call; `stop_after` sets `shutdown`
+    /// once that many `GetSchedInfo` calls were made.
+    #[derive(Default)]
+    pub struct MockUpstream {
+        pub answers: VecDeque<NodeSchedResponse>,
+        pub requests: Vec<NodeSchedRequest>,
+        pub reports: Vec<node_v1::ApplyReport>,
+        pub dmisses: Vec<DeadlineMissInfo>,
+        pub down: bool,
+        pub stop_after: Option<(usize, &'static AtomicBool)>,
+    }
+
+    impl MockUpstream {
+        pub fn answering(answers: impl IntoIterator<Item = NodeSchedResponse>) -> Self {
+            Self {
+                answers: answers.into_iter().collect(),
+                ..Default::default()
+            }
+        }
+    }
+
+    impl Upstream for MockUpstream {
+        fn get_sched_info(
+            &mut self,
+            request: NodeSchedRequest,
+        ) -> TimpaniResult<Option<NodeSchedResponse>> {
+            self.requests.push(request);
+            if let Some((n, shutdown)) = self.stop_after {
+                if self.requests.len() >= n {
+                    shutdown.store(true, Ordering::SeqCst);
+                }
+            }
+            if self.down {
+                return Err(TimpaniError::Network);
+            }
+            Ok(self.answers.pop_front())
+        }
+
+        fn report_apply(&mut self, report: node_v1::ApplyReport) -> TimpaniResult<()> {
+            if self.down {
+                return Err(TimpaniError::Network);
+            }
+            self.reports.push(report);
+            Ok(())
+        }
+
+        fn report_dmiss(&mut self, info: DeadlineMissInfo) -> TimpaniResult<()> {
+            if self.down {
+                return Err(TimpaniError::Network);
+            }
+            self.dmisses.push(info);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::MockUpstream;
+    use super::*;
+    use crate::apply::{errno, policy};
+    use crate::capability::test_support::MockProbe;
+    use crate::clock::test_support::MockProbe as MockClock;
+    use crate::proto::node_v1::{ApplyStatus, NodeSchedResponse, ScheduledTask};
+    use crate::resolve::NoSystemd;
+    use std::cell::{Cell, RefCell};
+    use std::collections::BTreeMap;
+    use std::sync::atomic::AtomicU64;
+
+    /// Records the attributes set per PID; unknown PIDs are gone.  With
+    /// `frozen`, writes are dropped (a process that keeps resetting its
+    /// own policy).
+    #[derive(Default)]
+    struct RecordingBackend {
+        set: RefCell<BTreeMap<i32, (i32, i32, u64)>>,
+        frozen: Cell<bool>,
+    }
+
+    impl SchedBackend for RecordingBackend {
+        fn join_cpuset(&self, _pid: i32, _cpus: u64) -> Result<(), i32> {
+            Ok(())
+        }
+        fn set_affinity(&self, pid: i32, cpus: u64) -> Result<(), i32> {
+            if self.frozen.get() {
+                return Ok(());
+            }
+            self.set.borrow_mut().entry(pid).or_default().2 = cpus;
+            Ok(())
+        }
+        fn set_scheduler(&self, pid: i32, policy: i32, priority: i32) -> Result<(), i32> {
+            if self.frozen.get() {
+                return Ok(());
+            }
+            let mut set = self.set.borrow_mut();
+            let attrs = set.entry(pid).or_default();
+            (attrs.0, attrs.1) = (policy, priority);
+            Ok(())
+        }
+        fn get_affinity(&self, pid: i32) -> Result<u64, i32> {
+            self.set.borrow().get(&pid).map(|a| a.2).ok_or(errno::ESRCH)
+        }
+        fn get_scheduler(&self, pid: i32) -> Result<(i32, i32), i32> {
+            self.set
+                .borrow()
+                .get(&pid)
+                .map(|a| (a.0, a.1))
+                .ok_or(errno::ESRCH)
+        }
+    }
+
+    fn processes() -> Vec<ProcessInfo> {
+        vec![
+            ProcessInfo {
+                pid: 100,
+                comm: "cam".into(),
+                ..Default::default()
+            },
+            ProcessInfo {
+                pid: 200,
+                comm: "lidar".into(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn privileged() -> Capabilities {
+        Capabilities::probe(&MockProbe::PRIVILEGED, -1)
+    }
+
+    fn task(name: &str, priority: i32) -> ScheduledTask {
+        ScheduledTask {
+            name: name.into(),
+            sched_policy: policy::SCHED_FIFO,
+            sched_priority: priority,
+            cpu_affinity: 0b10,
+            ..Default::default()
+        }
+    }
+
+    fn full(epoch: u64, generation: u64, tasks: Vec<ScheduledTask>) -> NodeSchedResponse {
+        NodeSchedResponse {
+            instance_epoch: epoch,
+            generation,
+            full: true,
+            tasks,
+            ..Default::default()
+        }
+    }
+
+    /// Neither chrony nor a PHC: no clock report.
+    static NO_CLOCK: MockClock = MockClock {
+        chrony: None,
+        phc: None,
+    };
+
+    fn agent<'a>(backend: &'a RecordingBackend, config: &Config) -> NodeAgent<'a> {
+        NodeAgent::new(config, privileged(), backend, &NoSystemd)
+            .with_processes(processes)
+            .with_clock_probe(&NO_CLOCK)
+    }
+
+    fn node_config() -> Config {
+        Config {
+            node_id: "n1".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_applied_push_is_reported_task_by_task() {
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config());
+        let mut upstream =
+            MockUpstream::answering([full(7, 1, vec![task("cam", 60), task("radar", 50)])]);
+
+        agent.poll(&mut upstream).unwrap();
+
+        let first = &upstream.requests[0];
+        assert_eq!(first.node_id, "n1");
+        assert_eq!(first.known_generation, None);
+        assert_eq!(first.features, FEATURES);
+        assert_eq!(
+            backend.set.borrow()[&100],
+            (policy::SCHED_FIFO, 60, 0b10),
+            "cam is resolved by name and applied"
+        );
+        let [report] = &upstream.reports[..] else {
+            panic!("one report expected: {:?}", upstream.reports);
+        };
+        assert_eq!((report.node_id.as_str(), report.generation), ("n1", 1));
+        let statuses: Vec<(&str, i32)> = report
+            .tasks
+            .iter()
+            .map(|t| (t.task_name.as_str(), t.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("cam", ApplyStatus::Applied as i32),
+                ("radar", ApplyStatus::PidNotFound as i32),
+            ]
+        );
+        assert_eq!(report.tasks[0].pid, 100);
+        assert!(report.node.is_some());
+    }
+
+    #[test]
+    fn test_next_request_carries_what_the_node_runs() {
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config());
+        let mut upstream = MockUpstream::answering([full(7, 3, vec![task("cam", 60)])]);
+
+        agent.poll(&mut upstream).unwrap();
+        agent.poll(&mut upstream).unwrap();
+
+        let second = &upstream.requests[1];
+        assert_eq!(second.known_generation, Some(3));
+        assert_eq!(second.known_instance_epoch, Some(7));
+        assert_eq!(second.known_tasks, ["cam"]);
+    }
+
+    #[test]
+    fn test_delta_is_applied_on_top_of_the_stored_schedule() {
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config());
+        let delta = NodeSchedResponse {
+            instance_epoch: 7,
+            generation: 2,
+            tasks: vec![task("lidar", 40)],
+            modified_tasks: vec![task("cam", 70)],
+            ..Default::default()
+        };
+        let mut upstream = MockUpstream::answering([full(7, 1, vec![task("cam", 60)]), delta]);
+
+        agent.poll(&mut upstream).unwrap();
+        agent.poll(&mut upstream).unwrap();
+
+        assert_eq!(backend.set.borrow()[&100].1, 70);
+        assert_eq!(backend.set.borrow()[&200].1, 40);
+        let names: Vec<&str> = upstream.reports[1]
+            .tasks
+            .iter()
+            .map(|t| t.task_name.as_str())
+            .collect();
+        assert_eq!(names, ["cam", "lidar"]);
+    }
+
+    #[test]
+    fn test_stale_push_is_acknowledged_and_duplicate_is_not() {
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config());
+        let mut upstream = MockUpstream::answering([
+            full(7, 2, vec![task("cam", 60)]),
+            // The answer to a poll once up to date: the same generation.
+            NodeSchedResponse {
+                instance_epoch: 7,
+                generation: 2,
+                ..Default::default()
+            },
+            // A retried push that arrived late.
+            full(7, 1, vec![task("cam", 50)]),
+        ]);
+
+        for _ in 0..3 {
+            agent.poll(&mut upstream).unwrap();
+        }
+
+        let statuses: Vec<(u64, i32)> = upstream
+            .reports
+            .iter()
+            .map(|r| (r.generation, r.push_status))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                (2, node_v1::PushStatus::Applied as i32),
+                (1, node_v1::PushStatus::Stale as i32),
+            ]
+        );
+        assert!(upstream.reports[1].tasks.is_empty());
+        assert_eq!(backend.set.borrow()[&100].1, 60);
+    }
+
+    #[test]
+    fn test_each_request_carries_the_cpus_online_then() {
+        static CPU3_ONLINE: AtomicBool = AtomicBool::new(true);
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config()).with_online_cpus(|| {
+            Some(if CPU3_ONLINE.load(Ordering::SeqCst) {
+                vec![0, 1, 2, 3]
+            } else {
+                vec![0, 1, 2]
+            })
+        });
+        let mut upstream = MockUpstream::default();
+
+        agent.poll(&mut upstream).unwrap();
+        CPU3_ONLINE.store(false, Ordering::SeqCst);
+        agent.poll(&mut upstream).unwrap();
+
+        let online: Vec<Vec<u32>> = upstream
+            .requests
+            .iter()
+            .map(|r| r.online_cpus.clone().unwrap().cpus)
+            .collect();
+        assert_eq!(online, [vec![0, 1, 2, 3], vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn test_request_carries_the_clock_report() {
+        let backend = RecordingBackend::default();
+        let phc = MockClock {
+            phc: Some("offset from CLOCK_REALTIME is 37000000200ns".into()),
+            ..Default::default()
+        };
+        let mut agent = agent(&backend, &node_config()).with_clock_probe(&phc);
+        let mut upstream = MockUpstream::default();
+
+        agent.poll(&mut upstream).unwrap();
+
+        assert_eq!(
+            upstream.requests[0].clock,
+            Some(node_v1::ClockSync {
+                synchronized: true,
+                offset_ns: 200,
+                source: "phc".into(),
+            })
+        );
+        assert_eq!(agent.with_clock_probe(&NO_CLOCK).request().clock, None);
+    }
+
+    #[test]
+    fn test_unreadable_cpu_list_is_not_reported() {
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config()).with_online_cpus(|| None);
+        let mut upstream = MockUpstream::default();
+
+        agent.poll(&mut upstream).unwrap();
+
+        assert_eq!(upstream.requests[0].online_cpus, None);
+    }
+
+    #[test]
+    fn test_drift_is_reported_after_the_apply_and_every_interval() {
+        let backend = RecordingBackend::default();
+        let config = Config {
+            verify_interval_secs: 10,
+            max_reapply: 0,
+            ..node_config()
+        };
+        let mut agent = agent(&backend, &config);
+        let mut upstream = MockUpstream::answering([full(7, 1, vec![task("cam", 60)])]);
+        let t0 = Instant::now();
+
+        agent.poll_at(&mut upstream, t0).unwrap();
+        assert!(
+            upstream.dmisses.is_empty(),
+            "verified right after the apply"
+        );
+
+        // Something resets cam to SCHED_OTHER on every CPU.
+        backend
+            .set
+            .borrow_mut()
+            .insert(100, (policy::SCHED_OTHER, 0, 0xf));
+        agent
+            .poll_at(&mut upstream, t0 + Duration::from_secs(5))
+            .unwrap();
+        assert!(upstream.dmisses.is_empty(), "not due yet");
+        agent
+            .poll_at(&mut upstream, t0 + Duration::from_secs(10))
+            .unwrap();
+
+        let [miss] = &upstream.dmisses[..] else {
+            panic!("one drift expected: {:?}", upstream.dmisses);
+        };
+        assert_eq!(
+            (miss.node_id.as_str(), miss.task_name.as_str()),
+            ("n1", "cam")
+        );
+        let drift = miss.drift.as_ref().unwrap();
+        assert_eq!(
+            (
+                drift.expected_policy,
+                drift.expected_priority,
+                drift.expected_cpu_affinity
+            ),
+            (policy::SCHED_FIFO, 60, 0b10)
+        );
+        assert_eq!(
+            (
+                drift.observed_policy,
+                drift.observed_priority,
+                drift.observed_cpu_affinity
+            ),
+            (policy::SCHED_OTHER, 0, 0xf)
+        );
+        assert_eq!((drift.reapplied, drift.restored), (0, false));
+    }
+
+    #[test]
+    fn test_drift_is_reapplied_within_the_bound() {
+        let backend = RecordingBackend::default();
+        let config = Config {
+            max_reapply: 2,
+            ..node_config()
+        };
+        let mut agent = agent(&backend, &config);
+        let mut upstream = MockUpstream::answering([full(7, 1, vec![task("cam", 60)])]);
+        let t0 = Instant::now();
+        agent.poll_at(&mut upstream, t0).unwrap();
+
+        backend
+            .set
+            .borrow_mut()
+            .insert(100, (policy::SCHED_OTHER, 0, 0xf));
+        backend.frozen.set(true);
+        let later = t0 + Duration::from_secs(config.verify_interval_secs);
+        agent.poll_at(&mut upstream, later).unwrap();
+
+        let drift = upstream.dmisses[0].drift.unwrap();
+        assert_eq!((drift.reapplied, drift.restored), (2, false));
+    }
+
+    #[test]
+    fn test_foreign_load_on_scheduled_cpus_is_reported() {
+        static RUNTIME_MS: AtomicU64 = AtomicU64::new(0);
+        fn threads() -> Vec<ThreadSample> {
+            let rt = |pid, comm: &str, affinity| ThreadSample {
+                pid,
+                tid: pid,
+                comm: comm.into(),
+                policy: policy::SCHED_FIFO,
+                affinity,
+                runtime_ns: RUNTIME_MS.load(Ordering::SeqCst) * 1_000_000,
+            };
+            // cam (PID 100) is scheduled; irq/9 shares its CPU; other is
+            // pinned elsewhere.
+            vec![
+                rt(100, "cam", 0b10),
+                rt(900, "irq/9", 0b10),
+                rt(901, "other", 0b100),
+            ]
+        }
+        let backend = RecordingBackend::default();
+        let config = Config {
+            foreign_scan_interval_secs: 10,
+            ..node_config()
+        };
+        let mut agent = agent(&backend, &config).with_threads(threads);
+        let mut upstream = MockUpstream::answering([full(7, 1, vec![task("cam", 60)])]);
+        let t0 = Instant::now();
+
+        // No schedule yet: nothing to scan.
+        agent.poll_at(&mut upstream, t0).unwrap();
+        // The baseline scan, then one 10 s later with 2.5 s more run time.
+        agent.poll_at(&mut upstream, t0).unwrap();
+        agent
+            .poll_at(&mut upstream, t0 + Duration::from_secs(5))
+            .unwrap();
+        RUNTIME_MS.store(2_500, Ordering::SeqCst);
+        agent
+            .poll_at(&mut upstream, t0 + Duration::from_secs(10))
+            .unwrap();
+
+        let reports: Vec<Option<&ForeignLoadReport>> = upstream
+            .requests
+            .iter()
+            .map(|r| r.foreign_load.as_ref())
+            .collect();
+        assert_eq!(reports[0], None);
+        assert_eq!(reports[1].map(|r| r.cpus.len()), Some(0));
+        assert_eq!(reports[2], None, "not due yet");
+        let [load] = &reports[3].unwrap().cpus[..] else {
+            panic!("one CPU expected: {:?}", reports[3]);
+        };
+        assert_eq!(load.cpu, 1);
+        assert!((load.utilization - 0.25).abs() < 1e-9);
+        assert_eq!(load.threads, ["irq/9/900"]);
+    }
+
+    #[test]
+    fn test_no_workload_is_an_answer() {
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config());
+        let mut upstream = MockUpstream::default();
+
+        assert_eq!(agent.poll(&mut upstream), Ok(()));
+        assert!(upstream.reports.is_empty());
+        assert_eq!(agent.store().generation(), 0);
+    }
+
+    #[test]
+    fn test_unsent_report_goes_out_before_the_next_request() {
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config());
+        let mut upstream = MockUpstream::answering([full(7, 1, vec![task("cam", 60)])]);
+
+        // GetSchedInfo succeeds, then Timpani-O goes away before the report.
+        let resp = upstream.get_sched_info(agent.request()).unwrap().unwrap();
+        agent.store.push(SchedulePush::from(resp));
+        let report = agent.apply(1);
+        upstream.down = true;
+        assert_eq!(
+            agent.send(&mut upstream, report),
+            Err(TimpaniError::Network)
+        );
+
+        upstream.down = false;
+        agent.poll(&mut upstream).unwrap();
+
+        assert_eq!(upstream.reports.len(), 1);
+        assert_eq!(upstream.reports[0].generation, 1);
+    }
+
+    #[test]
+    fn test_serve_gives_up_when_timpani_o_never_answers() {
+        static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config());
+        let mut upstream = MockUpstream {
+            down: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            agent.serve(&mut upstream, 1, Duration::ZERO, &SHUTDOWN),
+            Err(TimpaniError::Network)
+        );
+        assert_eq!(upstream.requests.len(), 1);
+    }
+
+    #[test]
+    fn test_serve_polls_until_shutdown() {
+        static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config());
+        let mut upstream = MockUpstream::answering([full(7, 1, vec![task("cam", 60)])]);
+        upstream.stop_after = Some((3, &SHUTDOWN));
+
+        assert_eq!(
+            agent.serve(&mut upstream, 1, Duration::ZERO, &SHUTDOWN),
+            Ok(())
+        );
+        assert_eq!(upstream.requests.len(), 3);
+        assert_eq!(upstream.reports.len(), 1);
+    }
+
+    #[test]
+    fn test_serve_returns_at_once_on_shutdown() {
+        let backend = RecordingBackend::default();
+        let mut agent = agent(&backend, &node_config());
+        let mut upstream = MockUpstream::default();
+
+        let shutdown = AtomicBool::new(true);
+        assert_eq!(
+            agent.serve(&mut upstream, 1, Duration::ZERO, &shutdown),
+            Ok(())
+        );
+        assert!(upstream.requests.is_empty());
+    }
+}
@@ -0,0 +1,840 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Applying a delivered schedule to local tasks, and the per-task report
+//! sent back to Timpani-O with `ReportApply`.
+//!
+//! Each task is applied in three steps through a [`SchedBackend`]: join the
+//! cpuset cgroup, set the CPU affinity, set the scheduling policy.  The
+//! first failing step decides the task's [`ApplyStatus`]:
+//!
+//! | Step / errno                            | Status                           |
+//! |-----------------------------------------|----------------------------------|
+//! | any step, `ESRCH`                       | `PidNotFound`                    |
+//! | cpuset cgroup, other                    | `CgroupError`                    |
+//! | affinity or policy, `EPERM`             | `PermissionDenied`               |
+//! | affinity or policy, other               | `InvalidCpu`                     |
+//! | RT task on a node without RT privileges | `PermissionDenied`, no call made |
+//! | priority outside 0–99                   | `InvalidAttributes`, no call made |
+//! | no process resolved                     | `PidNotFound`, no call made      |
+//! | placeholder task                        | `Reserved`, nothing resolved     |
+//!
+//! With a [`Resolver`] the PID is looked up at apply time instead of taken
+//! from the delivery (see [`crate::resolve`]).  If a step then fails with
+//! `ESRCH` the task is resolved again and, if that finds a different PID
+//! (a restarted systemd unit has a new `MainPID`), applied once more.
+//! Every result carries the PID used and the unit state seen.
+//!
+//! Applied attributes are read back and re-verified by
+//! [`crate::verify::DriftMonitor`].
+//!
+//! A placeholder ([`TaskApply::placeholder`]) only reserves capacity on
+//! Timpani-O: it is stored with the schedule but never applied, so no
+//! process is looked up and the drift monitor never watches it.
+//!
+//! Each result also carries the task's [`FaultSink`] in effect: the one
+//! delivered, or `Upstream` on a node without a local fault sink (see
+//! [`crate::fault`]).
+//!
+//! In dry-run mode nothing is changed and every task reports `DryRun`.
+//! The node-level [`NodeApplyInfo`] comes from the start-up
+//! [`Capabilities`] plus the isolated CPU list and the kernel RT throttle.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use tracing::{debug, warn};
+
+use crate::capability::Capabilities;
+use crate::fault::FaultSink;
+use crate::resolve::{Resolution, Resolver};
+use crate::schedule::PushStatus;
+
+// =============================================================================
+// CONSTANTS
+// =============================================================================
+
+/// Sysfs / procfs paths used by [`NodeApplyInfo::read`]
+pub mod paths {
+    pub const ISOLATED_CPUS: &str = "/sys/devices/system/cpu/isolated";
+    pub const RT_RUNTIME_US: &str = "/proc/sys/kernel/sched_rt_runtime_us";
+    pub const RT_PERIOD_US: &str = "/proc/sys/kernel/sched_rt_period_us";
+}
+
+/// errno values the status mapping distinguishes (asm-generic/errno-base.h)
+pub mod errno {
+    pub const EPERM: i32 = 1;
+    pub const ESRCH: i32 = 3;
+    pub const EINVAL: i32 = 22;
+}
+
+/// Linux scheduling policy numbers, as carried in `ScheduledTask.policy`.
+pub mod policy {
+    pub const SCHED_OTHER: i32 = 0;
+    pub const SCHED_FIFO: i32 = 1;
+    pub const SCHED_RR: i32 = 2;
+    /// Highest priority a delivered task may carry.
+    pub const MAX_PRIORITY: i32 = 99;
+}
+
+// =============================================================================
+// REPORT
+// =============================================================================
+
+/// Outcome of applying one task; mirrors the `ApplyStatus` proto enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplyStatus {
+    Applied,
+    PidNotFound,
+    PermissionDenied,
+    InvalidCpu,
+    CgroupError,
+    DryRun,
+    /// A placeholder task; stored, not applied.
+    Reserved,
+    /// Attributes the kernel must not be given; nothing was changed.
+    InvalidAttributes,
+}
+
+impl ApplyStatus {
+    /// The proto enum number (`APPLY_STATUS_UNSPECIFIED` = 0 is never sent).
+    pub fn wire_value(self) -> i32 {
+        match self {
+            ApplyStatus::Applied => 1,
+            ApplyStatus::PidNotFound => 2,
+            ApplyStatus::PermissionDenied => 3,
+            ApplyStatus::InvalidCpu => 4,
+            ApplyStatus::CgroupError => 5,
+            ApplyStatus::DryRun => 6,
+            ApplyStatus::Reserved => 7,
+            ApplyStatus::InvalidAttributes => 8,
+        }
+    }
+
+    /// `true` for the statuses Timpani-O counts as applied.
+    pub fn is_success(self) -> bool {
+        matches!(
+            self,
+            ApplyStatus::Applied | ApplyStatus::DryRun | ApplyStatus::Reserved
+        )
+    }
+}
+
+/// One task's entry in an [`ApplyReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskApplyResult {
+    pub task_name: String,
+    pub status: ApplyStatus,
+    /// errno of the failing call; 0 on success or when no call was made.
+    pub errno: i32,
+    pub detail: String,
+    /// PID the task was applied to; 0 when none was found.
+    pub pid: i32,
+    /// State of the task's systemd unit; empty when none was consulted.
+    pub unit_state: String,
+    /// Where the task's deadline misses go on this node.
+    pub fault_sink: FaultSink,
+}
+
+/// What the node could do when it applied the schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NodeApplyInfo {
+    pub cap_sys_nice: bool,
+    pub rtprio_limit: u64,
+    pub cpuset_writable: bool,
+    pub isolated_cpus: Vec<u32>,
+    /// `sched_rt_runtime_us`; -1 = RT throttling disabled.
+    pub rt_runtime_us: i64,
+    pub rt_period_us: i64,
+}
+
+impl NodeApplyInfo {
+    /// `caps` plus the isolated CPUs and RT throttle read from the kernel.
+    /// Unreadable files leave the defaults (no isolated CPUs, 0).
+    pub fn read(caps: &Capabilities) -> Self {
+        let read_i64 = |path| {
+            fs::read_to_string(path)
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(0)
+        };
+        Self {
+            isolated_cpus: fs::read_to_string(paths::ISOLATED_CPUS)
+                .map(|s| parse_cpu_list(&s))
+                .unwrap_or_default(),
+            rt_runtime_us: read_i64(paths::RT_RUNTIME_US),
+            rt_period_us: read_i64(paths::RT_PERIOD_US),
+            ..Self::from_capabilities(caps)
+        }
+    }
+
+    /// The capability part only.
+    pub fn from_capabilities(caps: &Capabilities) -> Self {
+        Self {
+            cap_sys_nice: caps.cap_sys_nice,
+            rtprio_limit: caps.rtprio_limit,
+            cpuset_writable: caps.cpuset_writable,
+            ..Default::default()
+        }
+    }
+}
+
+/// Parse a kernel CPU list such as `2-3,6` (empty → no CPUs).
+pub(crate) fn parse_cpu_list(list: &str) -> Vec<u32> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((lo, hi)) => {
+                if let (Ok(lo), Ok(hi)) = (lo.parse::<u32>(), hi.parse::<u32>()) {
+                    cpus.extend(lo..=hi);
+                }
+            }
+            None => cpus.extend(part.parse::<u32>().ok()),
+        }
+    }
+    cpus
+}
+
+/// Everything sent upstream after applying one generation.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ApplyReport {
+    pub node_id: String,
+    pub generation: u64,
+    pub tasks: Vec<TaskApplyResult>,
+    pub node: NodeApplyInfo,
+    /// What the [`ScheduleStore`](crate::schedule::ScheduleStore) did with
+    /// the push of `generation`.
+    pub push_status: PushStatus,
+}
+
+impl ApplyReport {
+    /// The report for a push the store did not take: its status, no tasks.
+    pub fn ack(node_id: &str, generation: u64, push_status: PushStatus) -> Self {
+        Self {
+            node_id: node_id.to_string(),
+            generation,
+            push_status,
+            ..Default::default()
+        }
+    }
+
+    /// Number of tasks that failed to apply.
+    pub fn failed(&self) -> usize {
+        self.tasks.iter().filter(|t| !t.status.is_success()).count()
+    }
+}
+
+// =============================================================================
+// APPLY
+// =============================================================================
+
+/// One task to apply, as delivered by Timpani-O.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskApply {
+    pub name: String,
+    pub pid: i32,
+    /// See [`policy`].
+    pub policy: i32,
+    pub priority: i32,
+    /// Bit `n` = CPU `n`.
+    pub cpu_affinity: u64,
+    /// The task's metadata, for [`ResolveRule::ByLabel`](crate::resolve::ResolveRule::ByLabel).
+    pub metadata: BTreeMap<String, String>,
+    /// Capacity reserved on Timpani-O for a task that arrives later.
+    pub placeholder: bool,
+    /// Where the task's deadline misses should go.
+    pub fault_sink: FaultSink,
+}
+
+impl TaskApply {
+    pub(crate) fn is_rt(&self) -> bool {
+        matches!(self.policy, policy::SCHED_FIFO | policy::SCHED_RR)
+    }
+
+    /// Why the task's attributes must not reach the kernel, if they must
+    /// not: a priority outside `0..=MAX_PRIORITY` would be rejected or,
+    /// narrowed to the kernel's type, silently changed.
+    pub(crate) fn invalid_attributes(&self) -> Option<String> {
+        (!(0..=policy::MAX_PRIORITY).contains(&self.priority)).then(|| {
+            format!(
+                "priority {} outside 0-{}",
+                self.priority,
+                policy::MAX_PRIORITY
+            )
+        })
+    }
+}
+
+/// The system calls applying a task.  Each returns the errno on failure.
+/// Mocked in tests.
+pub trait SchedBackend {
+    /// Move `pid` into a cpuset cgroup limited to `cpus`.
+    fn join_cpuset(&self, pid: i32, cpus: u64) -> Result<(), i32>;
+
+    /// `sched_setaffinity(pid, cpus)`.
+    fn set_affinity(&self, pid: i32, cpus: u64) -> Result<(), i32>;
+
+    /// `sched_setattr(pid, policy, priority)`.
+    fn set_scheduler(&self, pid: i32, policy: i32, priority: i32) -> Result<(), i32>;
+
+    /// `sched_getaffinity(pid)`.
+    fn get_affinity(&self, pid: i32) -> Result<u64, i32>;
+
+    /// `sched_getattr(pid)`: `(policy, priority)`.
+    fn get_scheduler(&self, pid: i32) -> Result<(i32, i32), i32>;
+}
+
+/// Applies tasks through a [`SchedBackend`], or only reports in dry-run
+/// mode.
+pub struct Applier<'a> {
+    backend: &'a dyn SchedBackend,
+    caps: Capabilities,
+    dry_run: bool,
+    resolver: Option<&'a Resolver<'a>>,
+    local_fault_sink: bool,
+}
+
+impl<'a> Applier<'a> {
+    pub fn new(backend: &'a dyn SchedBackend, caps: Capabilities) -> Self {
+        Self {
+            backend,
+            caps,
+            dry_run: false,
+            resolver: None,
+            local_fault_sink: false,
+        }
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Resolve each task's PID with `resolver` instead of using
+    /// [`TaskApply::pid`].
+    pub fn with_resolver(mut self, resolver: &'a Resolver<'a>) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    /// Whether the node has a local fault sink (`--local-fault-sink`).
+    pub fn with_local_fault_sink(mut self, available: bool) -> Self {
+        self.local_fault_sink = available;
+        self
+    }
+
+    /// Apply every task and build the report for `generation`.
+    pub fn apply_all(
+        &self,
+        node_id: &str,
+        generation: u64,
+        tasks: &[TaskApply],
+        node: NodeApplyInfo,
+    ) -> ApplyReport {
+        let report = ApplyReport {
+            node_id: node_id.to_string(),
+            generation,
+            tasks: tasks.iter().map(|t| self.apply(t)).collect(),
+            node,
+            push_status: PushStatus::Applied,
+        };
+        if report.failed() > 0 {
+            warn!(
+                generation,
+                failed = report.failed(),
+                total = tasks.len(),
+                "some tasks could not be applied"
+            );
+        }
+        let downgraded = tasks
+            .iter()
+            .zip(&report.tasks)
+            .filter(|(t, r)| t.fault_sink != r.fault_sink)
+            .count();
+        if downgraded > 0 {
+            warn!(
+                generation,
+                tasks = downgraded,
+                "no local fault sink configured; local deadline-miss reporting falls back to upstream"
+            );
+        }
+        report
+    }
+
+    /// Apply one task (see the module docs for the status mapping and
+    /// retry).
+    pub fn apply(&self, task: &TaskApply) -> TaskApplyResult {
+        if task.placeholder {
+            return TaskApplyResult {
+                task_name: task.name.clone(),
+                status: ApplyStatus::Reserved,
+                errno: 0,
+                detail: String::new(),
+                pid: 0,
+                unit_state: String::new(),
+                fault_sink: self.fault_sink(task),
+            };
+        }
+        let first = self.resolve(task);
+        let result = self.apply_resolved(task, &first);
+        if result.errno != errno::ESRCH || self.resolver.is_none() {
+            return result;
+        }
+        let again = self.resolve(task);
+        if again.pid.is_none() || again.pid == first.pid {
+            return result;
+        }
+        debug!(
+            task = %task.name,
+            old = ?first.pid,
+            new = ?again.pid,
+            "process gone, retrying with the re-resolved PID"
+        );
+        self.apply_resolved(task, &again)
+    }
+
+    fn fault_sink(&self, task: &TaskApply) -> FaultSink {
+        task.fault_sink.effective(self.local_fault_sink)
+    }
+
+    fn resolve(&self, task: &TaskApply) -> Resolution {
+        match self.resolver {
+            Some(resolver) => resolver.resolve(&task.name, &task.metadata),
+            None => Resolution {
+                pid: Some(task.pid),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn apply_resolved(&self, task: &TaskApply, resolution: &Resolution) -> TaskApplyResult {
+        let result = |status, errno, detail: String| TaskApplyResult {
+            task_name: task.name.clone(),
+            status,
+            errno,
+            detail,
+            pid: resolution.pid.unwrap_or(0),
+            unit_state: resolution.unit_state.clone(),
+            fault_sink: self.fault_sink(task),
+        };
+        if let Some(detail) = task.invalid_attributes() {
+            return result(ApplyStatus::InvalidAttributes, 0, detail);
+        }
+        if self.dry_run {
+            return result(ApplyStatus::DryRun, 0, String::new());
+        }
+        let Some(pid) = resolution.pid else {
+            let detail = if resolution.unit.is_empty() {
+                "no matching process".to_string()
+            } else {
+                format!(
+                    "no matching process (unit {} is {})",
+                    resolution.unit, resolution.unit_state
+                )
+            };
+            return result(ApplyStatus::PidNotFound, 0, detail);
+        };
+        if task.is_rt() && !self.caps.can_set_rt_priority() {
+            return result(
+                ApplyStatus::PermissionDenied,
+                0,
+                format!("node lacks {}", self.caps.missing().join("; ")),
+            );
+        }
+
+        if let Err(e) = self.backend.join_cpuset(pid, task.cpu_affinity) {
+            let status = match e {
+                errno::ESRCH => ApplyStatus::PidNotFound,
+                _ => ApplyStatus::CgroupError,
+            };
+            return result(status, e, "joining the cpuset cgroup failed".into());
+        }
+        if let Err(e) = self.backend.set_affinity(pid, task.cpu_affinity) {
+            return result(
+                syscall_status(e),
+                e,
+                format!("sched_setaffinity({:#x}) failed", task.cpu_affinity),
+            );
+        }
+        if let Err(e) = self.backend.set_scheduler(pid, task.policy, task.priority) {
+            return result(
+                syscall_status(e),
+                e,
+                format!(
+                    "sched_setattr(policy {}, priority {}) failed",
+                    task.policy, task.priority
+                ),
+            );
+        }
+        result(ApplyStatus::Applied, 0, String::new())
+    }
+}
+
+/// Status for a failed affinity or policy call.
+fn syscall_status(e: i32) -> ApplyStatus {
+    match e {
+        errno::ESRCH => ApplyStatus::PidNotFound,
+        errno::EPERM => ApplyStatus::PermissionDenied,
+        _ => ApplyStatus::InvalidCpu,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capability::test_support::MockProbe;
+    use crate::resolve::test_support::MockUnits;
+    use crate::resolve::{ResolveRule, SystemdUnits, UnitStatus, UNIT_NOT_FOUND};
+    use std::cell::Cell;
+
+    /// Fails the given step with the given errno and counts calls.
+    /// `dead_pid` fails every step for that PID with `ESRCH`.
+    #[derive(Default)]
+    struct MockBackend {
+        cpuset: Option<i32>,
+        affinity: Option<i32>,
+        scheduler: Option<i32>,
+        dead_pid: Option<i32>,
+        calls: Cell<u32>,
+    }
+
+    impl MockBackend {
+        fn step(&self, fail: Option<i32>) -> Result<(), i32> {
+            self.calls.set(self.calls.get() + 1);
+            fail.map_or(Ok(()), Err)
+        }
+    }
+
+    impl SchedBackend for MockBackend {
+        fn join_cpuset(&self, pid: i32, _cpus: u64) -> Result<(), i32> {
+            if self.dead_pid == Some(pid) {
+                return self.step(Some(errno::ESRCH));
+            }
+            self.step(self.cpuset)
+        }
+        fn set_affinity(&self, _pid: i32, _cpus: u64) -> Result<(), i32> {
+            self.step(self.affinity)
+        }
+        fn set_scheduler(&self, _pid: i32, _policy: i32, _priority: i32) -> Result<(), i32> {
+            self.step(self.scheduler)
+        }
+        fn get_affinity(&self, _pid: i32) -> Result<u64, i32> {
+            unreachable!("apply never reads back")
+        }
+        fn get_scheduler(&self, _pid: i32) -> Result<(i32, i32), i32> {
+            unreachable!("apply never reads back")
+        }
+    }
+
+    fn task(policy: i32) -> TaskApply {
+        TaskApply {
+            name: "t1".into(),
+            pid: 100,
+            policy,
+            priority: 50,
+            cpu_affinity: 0b10,
+            metadata: BTreeMap::new(),
+            placeholder: false,
+            fault_sink: FaultSink::Upstream,
+        }
+    }
+
+    /// An active unit whose main process is replaced after every query:
+    /// PID 200, then 201, ...
+    #[derive(Default)]
+    struct RestartingUnit(Cell<u32>);
+
+    impl SystemdUnits for RestartingUnit {
+        fn unit(&self, _unit: &str) -> Option<UnitStatus> {
+            let restarts = self.0.get();
+            self.0.set(restarts + 1);
+            Some(UnitStatus {
+                active_state: "active".into(),
+                main_pid: 200 + restarts,
+            })
+        }
+    }
+
+    fn privileged() -> Capabilities {
+        Capabilities::probe(&MockProbe::PRIVILEGED, 50)
+    }
+
+    #[test]
+    fn test_each_status_from_the_backend() {
+        let cases = [
+            (MockBackend::default(), ApplyStatus::Applied, 0),
+            (
+                MockBackend {
+                    cpuset: Some(errno::ESRCH),
+                    ..Default::default()
+                },
+                ApplyStatus::PidNotFound,
+                errno::ESRCH,
+            ),
+            (
+                MockBackend {
+                    cpuset: Some(errno::EINVAL),
+                    ..Default::default()
+                },
+                ApplyStatus::CgroupError,
+                errno::EINVAL,
+            ),
+            (
+                MockBackend {
+                    affinity: Some(errno::EINVAL),
+                    ..Default::default()
+                },
+                ApplyStatus::InvalidCpu,
+                errno::EINVAL,
+            ),
+            (
+                MockBackend {
+                    scheduler: Some(errno::EPERM),
+                    ..Default::default()
+                },
+                ApplyStatus::PermissionDenied,
+                errno::EPERM,
+            ),
+            (
+                MockBackend {
+                    scheduler: Some(errno::ESRCH),
+                    ..Default::default()
+                },
+                ApplyStatus::PidNotFound,
+                errno::ESRCH,
+            ),
+        ];
+        for (backend, status, e) in cases {
+            let r = Applier::new(&backend, privileged()).apply(&task(policy::SCHED_FIFO));
+            assert_eq!((r.status, r.errno), (status, e), "{r:?}");
+            assert_eq!(r.detail.is_empty(), status == ApplyStatus::Applied);
+        }
+    }
+
+    #[test]
+    fn test_dry_run_and_missing_privileges_skip_the_backend() {
+        let backend = MockBackend::default();
+        let dry = Applier::new(&backend, privileged()).with_dry_run(true);
+        assert_eq!(
+            dry.apply(&task(policy::SCHED_FIFO)).status,
+            ApplyStatus::DryRun
+        );
+
+        let caps = Capabilities::probe(&MockProbe::UNPRIVILEGED, 50);
+        let unprivileged = Applier::new(&backend, caps);
+        let r = unprivileged.apply(&task(policy::SCHED_RR));
+        assert_eq!((r.status, r.errno), (ApplyStatus::PermissionDenied, 0));
+        assert!(r.detail.contains("CAP_SYS_NICE"));
+        assert_eq!(backend.calls.get(), 0);
+
+        // Normal tasks need no RT privileges.
+        let r = unprivileged.apply(&task(policy::SCHED_OTHER));
+        assert_eq!(r.status, ApplyStatus::Applied);
+        assert_eq!(backend.calls.get(), 3);
+    }
+
+    #[test]
+    fn test_out_of_range_priority_is_reported_not_applied() {
+        let backend = MockBackend::default();
+        let applier = Applier::new(&backend, privileged());
+        for priority in [-1, policy::MAX_PRIORITY + 1, i32::MAX] {
+            let r = applier.apply(&TaskApply {
+                priority,
+                ..task(policy::SCHED_FIFO)
+            });
+            assert_eq!((r.status, r.errno), (ApplyStatus::InvalidAttributes, 0));
+            assert_eq!(r.detail, format!("priority {priority} outside 0-99"));
+            assert!(!r.status.is_success());
+        }
+        // Also in dry-run mode, which validates.
+        let dry = Applier::new(&backend, privileged()).with_dry_run(true);
+        let r = dry.apply(&TaskApply {
+            priority: 100,
+            ..task(policy::SCHED_FIFO)
+        });
+        assert_eq!(r.status, ApplyStatus::InvalidAttributes);
+        assert_eq!(backend.calls.get(), 0);
+
+        // Both ends of the range go through.
+        for priority in [0, policy::MAX_PRIORITY] {
+            let r = applier.apply(&TaskApply {
+                priority,
+                ..task(policy::SCHED_FIFO)
+            });
+            assert_eq!(r.status, ApplyStatus::Applied);
+        }
+    }
+
+    #[test]
+    fn test_placeholder_is_stored_not_applied() {
+        let backend = MockBackend::default();
+        let rules = [ResolveRule::SystemdUnit {
+            template: "{task}.service".into(),
+        }];
+        let units = MockUnits::default().with("t1.service", "active", 42);
+        let resolver = Resolver {
+            rules: &rules,
+            processes: &[],
+            units: &units,
+        };
+        let applier = Applier::new(&backend, privileged()).with_resolver(&resolver);
+        let reservation = TaskApply {
+            placeholder: true,
+            ..task(policy::SCHED_FIFO)
+        };
+        let report = applier.apply_all("n1", 3, &[reservation], NodeApplyInfo::default());
+        let r = &report.tasks[0];
+        assert_eq!((r.status, r.errno, r.pid), (ApplyStatus::Reserved, 0, 0));
+        assert_eq!(report.failed(), 0);
+        assert_eq!(backend.calls.get(), 0);
+    }
+
+    #[test]
+    fn test_systemd_unit_resolution_at_apply_time() {
+        let rules = [ResolveRule::SystemdUnit {
+            template: "{task}.service".into(),
+        }];
+        let backend = MockBackend::default();
+        let apply = |units: &dyn SystemdUnits, name: &str| {
+            let resolver = Resolver {
+                rules: &rules,
+                processes: &[],
+                units,
+            };
+            let applier = Applier::new(&backend, privileged()).with_resolver(&resolver);
+            applier.apply(&TaskApply {
+                name: name.into(),
+                ..task(policy::SCHED_FIFO)
+            })
+        };
+        let units = MockUnits::default().with("cam.service", "active", 42).with(
+            "lidar.service",
+            "inactive",
+            0,
+        );
+
+        let r = apply(&units, "cam");
+        assert_eq!((r.status, r.pid), (ApplyStatus::Applied, 42));
+        assert_eq!(r.unit_state, "active");
+        assert_eq!(backend.calls.get(), 3);
+
+        let r = apply(&units, "lidar");
+        assert_eq!((r.status, r.errno, r.pid), (ApplyStatus::PidNotFound, 0, 0));
+        assert_eq!(r.unit_state, "inactive");
+        assert_eq!(
+            r.detail,
+            "no matching process (unit lidar.service is inactive)"
+        );
+
+        let r = apply(&units, "radar");
+        assert_eq!(r.status, ApplyStatus::PidNotFound);
+        assert_eq!(r.unit_state, UNIT_NOT_FOUND);
+        assert_eq!(backend.calls.get(), 3);
+    }
+
+    #[test]
+    fn test_restarted_unit_is_resolved_again_on_esrch() {
+        let rules = [ResolveRule::SystemdUnit {
+            template: "{task}.service".into(),
+        }];
+        let units = RestartingUnit::default();
+        let resolver = Resolver {
+            rules: &rules,
+            processes: &[],
+            units: &units,
+        };
+        let backend = MockBackend {
+            dead_pid: Some(200),
+            ..Default::default()
+        };
+        let r = Applier::new(&backend, privileged())
+            .with_resolver(&resolver)
+            .apply(&task(policy::SCHED_FIFO));
+        assert_eq!((r.status, r.errno, r.pid), (ApplyStatus::Applied, 0, 201));
+        assert_eq!(units.0.get(), 2);
+
+        // Without a resolver the delivered PID is final.
+        let backend = MockBackend {
+            dead_pid: Some(100),
+            ..Default::default()
+        };
+        let r = Applier::new(&backend, privileged()).apply(&task(policy::SCHED_FIFO));
+        assert_eq!((r.status, r.pid), (ApplyStatus::PidNotFound, 100));
+        assert!(r.unit_state.is_empty());
+    }
+
+    #[test]
+    fn test_report_counts_failures_and_carries_node_info() {
+        let backend = MockBackend {
+            affinity: Some(errno::EINVAL),
+            ..Default::default()
+        };
+        let caps = privileged();
+        let node = NodeApplyInfo {
+            isolated_cpus: vec![2, 3],
+            rt_runtime_us: -1,
+            ..NodeApplyInfo::from_capabilities(&caps)
+        };
+        let report = Applier::new(&backend, caps).apply_all(
+            "node01",
+            4,
+            &[task(policy::SCHED_FIFO)],
+            node.clone(),
+        );
+        assert_eq!(report.generation, 4);
+        assert_eq!(report.failed(), 1);
+        assert_eq!(report.node, node);
+        assert!(report.node.cap_sys_nice);
+    }
+
+    #[test]
+    fn test_fault_sink_in_effect_is_reported() {
+        let backend = MockBackend::default();
+        let tasks =
+            [FaultSink::Upstream, FaultSink::LocalOnly, FaultSink::Both].map(|sink| TaskApply {
+                fault_sink: sink,
+                ..task(policy::SCHED_FIFO)
+            });
+        let sinks = |local| {
+            Applier::new(&backend, privileged())
+                .with_local_fault_sink(local)
+                .apply_all("n1", 1, &tasks, NodeApplyInfo::default())
+                .tasks
+                .iter()
+                .map(|r| r.fault_sink)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            sinks(true),
+            [FaultSink::Upstream, FaultSink::LocalOnly, FaultSink::Both]
+        );
+        assert_eq!(sinks(false), [FaultSink::Upstream; 3]);
+    }
+
+    #[test]
+    fn test_wire_values_match_the_proto() {
+        use ApplyStatus::*;
+        let values: Vec<i32> = [
+            Applied,
+            PidNotFound,
+            PermissionDenied,
+            InvalidCpu,
+            CgroupError,
+            DryRun,
+            Reserved,
+            InvalidAttributes,
+        ]
+        .map(ApplyStatus::wire_value)
+        .to_vec();
+        assert_eq!(values, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("2-3,6\n"), [2, 3, 6]);
+        assert_eq!(parse_cpu_list("\n"), Vec::<u32>::new());
+        assert_eq!(parse_cpu_list("0"), [0]);
+    }
+}
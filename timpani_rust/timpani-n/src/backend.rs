@@ -0,0 +1,228 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! The production [`SchedBackend`]: Linux system calls and the cpuset
+//! cgroup.
+//!
+//! | Call            | Implementation                                        |
+//! |-----------------|-------------------------------------------------------|
+//! | `join_cpuset`   | write the PID to `timpani/cpus-<mask>/cgroup.procs`   |
+//! | `set_affinity`  | `sched_setaffinity`                                   |
+//! | `set_scheduler` | `sched_setscheduler`                                  |
+//! | `get_affinity`  | `sched_getaffinity`                                   |
+//! | `get_scheduler` | `sched_getscheduler` + `sched_getparam`               |
+//!
+//! Each distinct CPU set gets its own child of a `timpani` cgroup under the
+//! cpuset hierarchy (the v1 cpuset mount if there is one, else the unified
+//! v2 root), created on first use.  An affinity of 0 means any CPU: the
+//! task is left in its cgroup and allowed every online CPU.
+
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use crate::apply::{errno, SchedBackend};
+use crate::capability::paths as cgroup_paths;
+use crate::hotplug::online_mask;
+
+/// Name of the cgroup holding the per-CPU-set children.
+pub const CGROUP_NAME: &str = "timpani";
+
+/// `SCHED_RESET_ON_FORK`, or-ed into the policy `sched_getscheduler`
+/// returns.
+const SCHED_RESET_ON_FORK: i32 = 0x4000_0000;
+
+/// [`SchedBackend`] on the running kernel (see the module docs).
+#[derive(Debug, Clone)]
+pub struct LinuxBackend {
+    /// The `timpani` cgroup.
+    cgroup: PathBuf,
+    /// Cgroup v1: `cpuset.mems` must be set before tasks can join.
+    v1: bool,
+}
+
+impl Default for LinuxBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinuxBackend {
+    pub fn new() -> Self {
+        let v1 = Path::new(cgroup_paths::CGROUP_V1_CPUSET).is_dir();
+        let root = if v1 {
+            cgroup_paths::CGROUP_V1_CPUSET
+        } else {
+            cgroup_paths::CGROUP_V2_ROOT
+        };
+        Self {
+            cgroup: Path::new(root).join(CGROUP_NAME),
+            v1,
+        }
+    }
+
+    /// The cgroup for `cpus`, created and limited to them if needed.
+    fn cpuset_for(&self, cpus: u64) -> io::Result<PathBuf> {
+        let dir = self.cgroup.join(format!("cpus-{cpus:x}"));
+        if dir.is_dir() {
+            return Ok(dir);
+        }
+        if !self.cgroup.is_dir() {
+            fs::create_dir(&self.cgroup)?;
+        }
+        if self.v1 {
+            copy_mems(self.cgroup.parent().unwrap_or(&self.cgroup), &self.cgroup)?;
+        } else {
+            // v2: children only get a cpuset controller if each parent
+            // delegates it.
+            for parent in [self.cgroup.parent(), Some(self.cgroup.as_path())]
+                .into_iter()
+                .flatten()
+            {
+                fs::write(parent.join("cgroup.subtree_control"), "+cpuset")?;
+            }
+        }
+        fs::create_dir(&dir)?;
+        if self.v1 {
+            copy_mems(&self.cgroup, &dir)?;
+        }
+        fs::write(dir.join("cpuset.cpus"), cpu_list(cpus))?;
+        debug!(cgroup = %dir.display(), "cpuset cgroup created");
+        Ok(dir)
+    }
+}
+
+impl SchedBackend for LinuxBackend {
+    fn join_cpuset(&self, pid: i32, cpus: u64) -> Result<(), i32> {
+        if cpus == 0 {
+            return Ok(());
+        }
+        let dir = self.cpuset_for(cpus).map_err(|e| os_errno(&e))?;
+        fs::write(dir.join("cgroup.procs"), pid.to_string()).map_err(|e| os_errno(&e))
+    }
+
+    fn set_affinity(&self, pid: i32, cpus: u64) -> Result<(), i32> {
+        let cpus = if cpus == 0 { online_mask() } else { cpus };
+        // SAFETY: cpu_set_t is plain data; an all-zero value is the empty
+        // set, and the kernel only reads `size_of::<cpu_set_t>()` bytes.
+        let rc = unsafe {
+            let mut set: libc::cpu_set_t = mem::zeroed();
+            for cpu in (0..u64::BITS as usize).filter(|&c| cpus & 1 << c != 0) {
+                libc::CPU_SET(cpu, &mut set);
+            }
+            libc::sched_setaffinity(pid, mem::size_of::<libc::cpu_set_t>(), &set)
+        };
+        syscall(rc).map(drop)
+    }
+
+    fn set_scheduler(&self, pid: i32, policy: i32, priority: i32) -> Result<(), i32> {
+        let param = libc::sched_param {
+            sched_priority: priority,
+        };
+        // SAFETY: `param` outlives the call, which only reads it.
+        syscall(unsafe { libc::sched_setscheduler(pid, policy, &param) }).map(drop)
+    }
+
+    fn get_affinity(&self, pid: i32) -> Result<u64, i32> {
+        // SAFETY: as in `set_affinity`; the kernel writes at most
+        // `size_of::<cpu_set_t>()` bytes into `set`.
+        unsafe {
+            let mut set: libc::cpu_set_t = mem::zeroed();
+            syscall(libc::sched_getaffinity(
+                pid,
+                mem::size_of::<libc::cpu_set_t>(),
+                &mut set,
+            ))?;
+            Ok((0..u64::BITS as usize)
+                .filter(|&c| libc::CPU_ISSET(c, &set))
+                .fold(0, |mask, c| mask | 1 << c))
+        }
+    }
+
+    fn get_scheduler(&self, pid: i32) -> Result<(i32, i32), i32> {
+        // SAFETY: `param` is plain data the kernel fills in.
+        unsafe {
+            let policy = syscall(libc::sched_getscheduler(pid))? & !SCHED_RESET_ON_FORK;
+            let mut param: libc::sched_param = mem::zeroed();
+            syscall(libc::sched_getparam(pid, &mut param))?;
+            Ok((policy, param.sched_priority))
+        }
+    }
+}
+
+/// `rc`, or the errno of the failed call.
+fn syscall(rc: i32) -> Result<i32, i32> {
+    if rc < 0 {
+        Err(os_errno(&io::Error::last_os_error()))
+    } else {
+        Ok(rc)
+    }
+}
+
+/// The errno behind `e`; `EINVAL` for errors that have none.
+fn os_errno(e: &io::Error) -> i32 {
+    e.raw_os_error().unwrap_or(errno::EINVAL)
+}
+
+/// `0b1101` → `0,2-3`.
+fn cpu_list(cpus: u64) -> String {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for cpu in (0..u64::BITS).filter(|&c| cpus & 1 << c != 0) {
+        match ranges.last_mut() {
+            Some((_, hi)) if *hi + 1 == cpu => *hi = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(lo, hi)| {
+            if lo == hi {
+                lo.to_string()
+            } else {
+                format!("{lo}-{hi}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Cgroup v1: give `child` the memory nodes of `parent`.
+fn copy_mems(parent: &Path, child: &Path) -> io::Result<()> {
+    let mems = fs::read_to_string(parent.join("cpuset.mems"))?;
+    fs::write(child.join("cpuset.mems"), mems.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply::policy;
+
+    #[test]
+    fn test_cpu_list_joins_runs() {
+        assert_eq!(cpu_list(0b1101), "0,2-3");
+        assert_eq!(cpu_list(0b1), "0");
+        assert_eq!(cpu_list(0xf0), "4-7");
+        assert_eq!(cpu_list(1 << 63), "63");
+    }
+
+    #[test]
+    fn test_reads_back_the_calling_thread() {
+        let backend = LinuxBackend::new();
+        let (policy, _) = backend.get_scheduler(0).unwrap();
+        assert_eq!(policy, policy::SCHED_OTHER);
+        assert_ne!(backend.get_affinity(0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_missing_process_is_esrch() {
+        // PIDs are capped well below i32::MAX (pid_max <= 2^22).
+        let backend = LinuxBackend::new();
+        assert_eq!(backend.get_scheduler(i32::MAX), Err(errno::ESRCH));
+        assert_eq!(backend.set_affinity(i32::MAX, 0b1), Err(errno::ESRCH));
+    }
+}
@@ -0,0 +1,286 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Start-up privilege pre-flight for real-time scheduling.
+//!
+//! Applying SCHED_FIFO/SCHED_RR without privileges fails with EPERM for every
+//! task, late and noisily.  Instead the node probes once at start-up:
+//!
+//! * `CAP_SYS_NICE` in the effective capability set, or a soft
+//!   `RLIMIT_RTPRIO` high enough for the configured priority;
+//! * write access to the cpuset cgroup controller.
+//!
+//! The result is stored in the [`Context`](crate::context::Context) and
+//! exported as a [`Capabilities`] section so Timpani-O can keep RT tasks off
+//! the node.
+
+use std::fs;
+use std::path::Path;
+
+use tracing::{info, warn};
+
+use crate::error::{TimpaniError, TimpaniResult};
+
+// =============================================================================
+// CONSTANTS
+// =============================================================================
+
+/// Capability bit number of `CAP_SYS_NICE` (linux/capability.h).
+pub const CAP_SYS_NICE_BIT: u32 = 23;
+
+/// Procfs / cgroupfs paths used by [`ProcfsProbe`]
+pub mod paths {
+    pub const PROC_STATUS: &str = "/proc/self/status";
+    pub const PROC_LIMITS: &str = "/proc/self/limits";
+    /// cgroup v2 unified hierarchy root
+    pub const CGROUP_V2_ROOT: &str = "/sys/fs/cgroup";
+    /// cgroup v1 cpuset controller mount
+    pub const CGROUP_V1_CPUSET: &str = "/sys/fs/cgroup/cpuset";
+}
+
+// =============================================================================
+// PROBE
+// =============================================================================
+
+/// Source of the raw privilege facts.  Mocked in tests.
+pub trait CapabilityProbe {
+    /// `true` if `CAP_SYS_NICE` is in the effective set.
+    fn has_cap_sys_nice(&self) -> bool;
+
+    /// Soft `RLIMIT_RTPRIO` (0 when unknown).
+    fn rtprio_limit(&self) -> u64;
+
+    /// `true` if cpuset cgroups can be created/written.
+    fn cpuset_writable(&self) -> bool;
+}
+
+/// Probe backed by `/proc/self` and the cgroup filesystem.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ProcfsProbe;
+
+impl CapabilityProbe for ProcfsProbe {
+    fn has_cap_sys_nice(&self) -> bool {
+        fs::read_to_string(paths::PROC_STATUS)
+            .ok()
+            .and_then(|s| parse_cap_eff(&s))
+            .is_some_and(|mask| mask & (1u64 << CAP_SYS_NICE_BIT) != 0)
+    }
+
+    fn rtprio_limit(&self) -> u64 {
+        fs::read_to_string(paths::PROC_LIMITS)
+            .ok()
+            .and_then(|s| parse_rtprio_limit(&s))
+            .unwrap_or(0)
+    }
+
+    fn cpuset_writable(&self) -> bool {
+        // v1: the cpuset mount itself; v2: the unified root, provided the
+        // cpuset controller is available there.
+        let v1 = Path::new(paths::CGROUP_V1_CPUSET);
+        if v1.is_dir() {
+            return dir_writable(v1);
+        }
+        let v2 = Path::new(paths::CGROUP_V2_ROOT);
+        let has_cpuset = fs::read_to_string(v2.join("cgroup.controllers"))
+            .map(|c| c.split_whitespace().any(|w| w == "cpuset"))
+            .unwrap_or(false);
+        has_cpuset && dir_writable(v2)
+    }
+}
+
+/// Parse the `CapEff:` hex mask from `/proc/self/status`.
+fn parse_cap_eff(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|l| l.strip_prefix("CapEff:"))
+        .and_then(|v| u64::from_str_radix(v.trim(), 16).ok())
+}
+
+/// Parse the soft limit of `Max realtime priority` from `/proc/self/limits`.
+/// `unlimited` maps to `u64::MAX`.
+fn parse_rtprio_limit(limits: &str) -> Option<u64> {
+    let line = limits
+        .lines()
+        .find(|l| l.starts_with("Max realtime priority"))?;
+    let soft = line
+        .trim_start_matches("Max realtime priority")
+        .split_whitespace()
+        .next()?;
+    if soft == "unlimited" {
+        Some(u64::MAX)
+    } else {
+        soft.parse().ok()
+    }
+}
+
+/// Writable-directory check without touching the directory contents.
+fn dir_writable(dir: &Path) -> bool {
+    fs::metadata(dir)
+        .map(|m| !m.permissions().readonly())
+        .unwrap_or(false)
+        && fs::OpenOptions::new()
+            .write(true)
+            .open(dir.join("cgroup.procs"))
+            .is_ok()
+}
+
+// =============================================================================
+// CAPABILITIES
+// =============================================================================
+
+/// Recorded pre-flight result — the `capabilities` section reported to
+/// Timpani-O.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Capabilities {
+    pub cap_sys_nice: bool,
+    pub rtprio_limit: u64,
+    pub cpuset_writable: bool,
+    /// Priority the checks were evaluated against.
+    pub required_prio: i32,
+}
+
+impl Capabilities {
+    /// Run `probe` and evaluate it for `required_prio` (the configured
+    /// priority; values below 1 are treated as 1).
+    pub fn probe(probe: &dyn CapabilityProbe, required_prio: i32) -> Self {
+        let caps = Capabilities {
+            cap_sys_nice: probe.has_cap_sys_nice(),
+            rtprio_limit: probe.rtprio_limit(),
+            cpuset_writable: probe.cpuset_writable(),
+            required_prio: required_prio.max(1),
+        };
+        if caps.rt_ready() {
+            info!(?caps, "RT pre-flight passed");
+        } else {
+            warn!(missing = ?caps.missing(), "RT pre-flight failed — node not RT-ready");
+        }
+        caps
+    }
+
+    /// `true` if RT priorities can be set (capability or rlimit).
+    pub fn can_set_rt_priority(&self) -> bool {
+        self.cap_sys_nice || self.rtprio_limit >= self.required_prio as u64
+    }
+
+    /// `true` if the node may report itself ready for RT placements.
+    pub fn rt_ready(&self) -> bool {
+        self.can_set_rt_priority() && self.cpuset_writable
+    }
+
+    /// Human-readable list of everything that is missing.
+    pub fn missing(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        if !self.can_set_rt_priority() {
+            out.push(format!(
+                "CAP_SYS_NICE or RLIMIT_RTPRIO >= {} (have {})",
+                self.required_prio, self.rtprio_limit
+            ));
+        }
+        if !self.cpuset_writable {
+            out.push("write access to cpuset cgroup".to_string());
+        }
+        out
+    }
+
+    /// Gate for applying RT attributes: one aggregated error instead of an
+    /// EPERM per task.
+    pub fn ensure_rt_ready(&self) -> TimpaniResult<()> {
+        if self.rt_ready() {
+            return Ok(());
+        }
+        tracing::error!(
+            missing = %self.missing().join("; "),
+            "refusing to apply RT scheduling: pre-flight failed"
+        );
+        Err(TimpaniError::Permission)
+    }
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use super::CapabilityProbe;
+
+    /// Fixed-answer probe
+    #[derive(Debug, Clone, Copy)]
+    pub struct MockProbe {
+        pub cap_sys_nice: bool,
+        pub rtprio_limit: u64,
+        pub cpuset_writable: bool,
+    }
+
+    impl MockProbe {
+        pub const PRIVILEGED: MockProbe = MockProbe {
+            cap_sys_nice: true,
+            rtprio_limit: 0,
+            cpuset_writable: true,
+        };
+        pub const UNPRIVILEGED: MockProbe = MockProbe {
+            cap_sys_nice: false,
+            rtprio_limit: 0,
+            cpuset_writable: false,
+        };
+    }
+
+    impl CapabilityProbe for MockProbe {
+        fn has_cap_sys_nice(&self) -> bool {
+            self.cap_sys_nice
+        }
+        fn rtprio_limit(&self) -> u64 {
+            self.rtprio_limit
+        }
+        fn cpuset_writable(&self) -> bool {
+            self.cpuset_writable
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::MockProbe;
+    use super::*;
+
+    #[test]
+    fn test_privileged_probe_is_rt_ready() {
+        let caps = Capabilities::probe(&MockProbe::PRIVILEGED, 50);
+        assert!(caps.rt_ready());
+        assert!(caps.missing().is_empty());
+        assert!(caps.ensure_rt_ready().is_ok());
+    }
+
+    #[test]
+    fn test_unprivileged_probe_aggregates_missing() {
+        let caps = Capabilities::probe(&MockProbe::UNPRIVILEGED, 50);
+        assert!(!caps.rt_ready());
+        assert_eq!(caps.missing().len(), 2);
+        assert_eq!(caps.ensure_rt_ready(), Err(TimpaniError::Permission));
+    }
+
+    #[test]
+    fn test_rlimit_substitutes_for_capability() {
+        let probe = MockProbe {
+            cap_sys_nice: false,
+            rtprio_limit: 50,
+            cpuset_writable: true,
+        };
+        assert!(Capabilities::probe(&probe, 50).rt_ready());
+        assert!(!Capabilities::probe(&probe, 51).rt_ready());
+    }
+
+    #[test]
+    fn test_parse_cap_eff() {
+        let status = "Name:\tx\nCapEff:\t0000000000800000\n";
+        assert_eq!(parse_cap_eff(status), Some(1 << CAP_SYS_NICE_BIT));
+        assert_eq!(parse_cap_eff("Name:\tx\n"), None);
+    }
+
+    #[test]
+    fn test_parse_rtprio_limit() {
+        let limits = "Limit                     Soft Limit           Hard Limit           Units\n\
+                      Max realtime priority     0                    0                    \n";
+        assert_eq!(parse_rtprio_limit(limits), Some(0));
+        let unlimited = "Max realtime priority     unlimited            unlimited            \n";
+        assert_eq!(parse_rtprio_limit(unlimited), Some(u64::MAX));
+    }
+}
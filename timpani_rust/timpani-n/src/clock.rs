@@ -0,0 +1,253 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Clock synchronisation probe for the clock report sent to Timpani-O.
+//!
+//! Release offsets are relative to a hyperperiod start shared by every
+//! node, so a node whose clock has drifted fires its tasks at the wrong
+//! time.  The node reports what its time source says with every schedule
+//! request, and Timpani-O holds back offset-dependent schedules from nodes
+//! that are out of sync:
+//!
+//! * chrony, if `chronyd` is running: `chronyc -c tracking` gives the leap
+//!   status (unsynchronised or not) and the system clock offset;
+//! * otherwise the PTP hardware clock: `phc_ctl <dev> cmp` gives the offset
+//!   of the system clock from the PHC.  A PHC normally runs on TAI, so only
+//!   the sub-second residual counts; the PHC has no notion of "locked", so
+//!   the report is always synchronised and only the offset is judged.
+//!
+//! Commands rather than syscalls keep the probe free of FFI.
+
+use std::path::Path;
+use std::process::Command;
+
+use tracing::debug;
+
+// =============================================================================
+// CONSTANTS
+// =============================================================================
+
+/// Paths used by [`SystemClockProbe`]
+pub mod paths {
+    /// chronyd's runtime directory (holds the `chronyc` command socket)
+    pub const CHRONY_RUN_DIR: &str = "/run/chrony";
+    /// PTP hardware clock compared against when chrony is absent
+    pub const PHC_DEVICE: &str = "/dev/ptp0";
+}
+
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+/// Field positions in `chronyc -c tracking` output.
+const CHRONY_STRATUM: usize = 2;
+const CHRONY_SYSTEM_OFFSET: usize = 4;
+const CHRONY_LEAP_STATUS: usize = 13;
+
+// =============================================================================
+// PROBE
+// =============================================================================
+
+/// Source of the raw time-source output.  Mocked in tests.
+pub trait ClockProbe {
+    /// Output of `chronyc -c tracking`; `None` if chrony is not running.
+    fn chrony_tracking(&self) -> Option<String>;
+
+    /// Output of `phc_ctl <dev> cmp`; `None` if there is no PHC.
+    fn phc_compare(&self) -> Option<String>;
+}
+
+/// Probe backed by `chronyc` and `phc_ctl`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClockProbe;
+
+impl ClockProbe for SystemClockProbe {
+    fn chrony_tracking(&self) -> Option<String> {
+        if !Path::new(paths::CHRONY_RUN_DIR).is_dir() {
+            return None;
+        }
+        run(Command::new("chronyc").args(["-c", "tracking"]))
+    }
+
+    fn phc_compare(&self) -> Option<String> {
+        if !Path::new(paths::PHC_DEVICE).exists() {
+            return None;
+        }
+        run(Command::new("phc_ctl").args([paths::PHC_DEVICE, "cmp"]))
+    }
+}
+
+/// Stdout of `cmd` if it ran and succeeded.
+fn run(cmd: &mut Command) -> Option<String> {
+    match cmd.output() {
+        Ok(out) if out.status.success() => String::from_utf8(out.stdout).ok(),
+        Ok(out) => {
+            debug!(?cmd, status = %out.status, "clock probe command failed");
+            None
+        }
+        Err(e) => {
+            debug!(?cmd, error = %e, "clock probe command unavailable");
+            None
+        }
+    }
+}
+
+/// Parse `chronyc -c tracking`: `(synchronised, offset_ns)`.
+///
+/// Unsynchronised means leap status `Not synchronised` or stratum 0.
+fn parse_chrony_tracking(csv: &str) -> Option<(bool, i64)> {
+    let fields: Vec<&str> = csv.trim().split(',').collect();
+    let stratum: u32 = fields.get(CHRONY_STRATUM)?.parse().ok()?;
+    let offset_s: f64 = fields.get(CHRONY_SYSTEM_OFFSET)?.parse().ok()?;
+    let leap = fields.get(CHRONY_LEAP_STATUS)?;
+    let synchronized = stratum != 0 && *leap != "Not synchronised";
+    Some((
+        synchronized,
+        (offset_s * NANOS_PER_SEC as f64).round() as i64,
+    ))
+}
+
+/// Parse `phc_ctl ... cmp` (`offset from CLOCK_REALTIME is <n>ns`): the
+/// offset with whole seconds (the TAI−UTC difference) removed, in
+/// `(-0.5 s, 0.5 s]`.
+fn parse_phc_offset(out: &str) -> Option<i64> {
+    let (_, rest) = out.split_once("offset from CLOCK_REALTIME is ")?;
+    let ns: i64 = rest.split("ns").next()?.trim().parse().ok()?;
+    let residual = ns.rem_euclid(NANOS_PER_SEC);
+    Some(if residual > NANOS_PER_SEC / 2 {
+        residual - NANOS_PER_SEC
+    } else {
+        residual
+    })
+}
+
+// =============================================================================
+// CLOCK STATUS
+// =============================================================================
+
+/// Time source a [`ClockStatus`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockSource {
+    Chrony,
+    Phc,
+}
+
+impl ClockSource {
+    /// Name reported to Timpani-O.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ClockSource::Chrony => "chrony",
+            ClockSource::Phc => "phc",
+        }
+    }
+}
+
+/// The clock report sent to Timpani-O.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockStatus {
+    pub synchronized: bool,
+    /// Offset of the system clock from the reference (signed).
+    pub offset_ns: i64,
+    pub source: ClockSource,
+}
+
+impl ClockStatus {
+    /// Ask chrony, then the PHC.  `None` if neither answers (the report is
+    /// then omitted and Timpani-O trusts the clock).
+    pub fn probe(probe: &dyn ClockProbe) -> Option<Self> {
+        if let Some((synchronized, offset_ns)) = probe
+            .chrony_tracking()
+            .as_deref()
+            .and_then(parse_chrony_tracking)
+        {
+            return Some(ClockStatus {
+                synchronized,
+                offset_ns,
+                source: ClockSource::Chrony,
+            });
+        }
+        let offset_ns = probe.phc_compare().as_deref().and_then(parse_phc_offset)?;
+        Some(ClockStatus {
+            synchronized: true,
+            offset_ns,
+            source: ClockSource::Phc,
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use super::ClockProbe;
+
+    /// Fixed-output probe
+    #[derive(Debug, Clone, Default)]
+    pub struct MockProbe {
+        pub chrony: Option<String>,
+        pub phc: Option<String>,
+    }
+
+    impl ClockProbe for MockProbe {
+        fn chrony_tracking(&self) -> Option<String> {
+            self.chrony.clone()
+        }
+        fn phc_compare(&self) -> Option<String> {
+            self.phc.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::MockProbe;
+    use super::*;
+
+    const TRACKING_SYNCED: &str = "A9FEA9FE,169.254.169.254,3,1760600000.123456789,\
+        -0.000012345,-0.000001000,0.000020000,-12.345,-0.001,0.050,0.000500000,\
+        0.000250000,64.2,Normal\n";
+    const TRACKING_UNSYNCED: &str = "00000000,,0,0.000000000,0.000000000,0.000000000,\
+        0.000000000,0.000,0.000,0.000,1.000000000,1.000000000,0.0,Not synchronised\n";
+
+    #[test]
+    fn test_parse_chrony_tracking() {
+        assert_eq!(
+            parse_chrony_tracking(TRACKING_SYNCED),
+            Some((true, -12_345))
+        );
+        assert_eq!(parse_chrony_tracking(TRACKING_UNSYNCED), Some((false, 0)));
+        assert_eq!(parse_chrony_tracking("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_phc_offset_drops_whole_seconds() {
+        let out = "phc_ctl[1234.567]: offset from CLOCK_REALTIME is 37000001500ns\n";
+        assert_eq!(parse_phc_offset(out), Some(1_500));
+        let behind = "phc_ctl[1234.567]: offset from CLOCK_REALTIME is 36999999000ns\n";
+        assert_eq!(parse_phc_offset(behind), Some(-1_000));
+        assert_eq!(parse_phc_offset("phc_ctl: failed"), None);
+    }
+
+    #[test]
+    fn test_probe_prefers_chrony_then_phc() {
+        let both = MockProbe {
+            chrony: Some(TRACKING_UNSYNCED.into()),
+            phc: Some("offset from CLOCK_REALTIME is 37000000200ns".into()),
+        };
+        let status = ClockStatus::probe(&both).unwrap();
+        assert_eq!(status.source, ClockSource::Chrony);
+        assert!(!status.synchronized);
+
+        let phc_only = MockProbe {
+            chrony: None,
+            ..both
+        };
+        assert_eq!(
+            ClockStatus::probe(&phc_only),
+            Some(ClockStatus {
+                synchronized: true,
+                offset_ns: 200,
+                source: ClockSource::Phc,
+            })
+        );
+        assert_eq!(ClockStatus::probe(&MockProbe::default()), None);
+    }
+}
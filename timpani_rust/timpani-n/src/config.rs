@@ -4,6 +4,8 @@
  */
 
 use crate::error::{TimpaniError, TimpaniResult};
+use crate::fault::LocalSinkTarget;
+use crate::resolve::ResolveRule;
 use clap::Parser;
 use tracing::info;
 
@@ -29,6 +31,12 @@ pub mod defaults {
     pub const ADDRESS: &str = "127.0.0.1";
     pub const NODE_ID: &str = "1";
     pub const LOG_LEVEL: u8 = super::log_level::INFO;
+    pub const VERIFY_INTERVAL_SECS: u64 = 10;
+    pub const MAX_REAPPLY: u32 = 0;
+    pub const FOREIGN_SCAN_INTERVAL_SECS: u64 = 30;
+    pub const POLL_INTERVAL_MS: u64 = 1000;
+    pub const CONNECT_ATTEMPTS: u32 = 300;
+    pub const RETRY_INTERVAL_MS: u64 = 1000;
 }
 
 /// Validation range constants
@@ -144,6 +152,32 @@ pub struct Config {
 
     /// Log level
     pub log_level: LogLevel,
+
+    /// Rules matching a scheduled task to its process, tried in order
+    pub resolvers: Vec<ResolveRule>,
+
+    /// Seconds between read-backs of applied scheduling attributes
+    /// (0 = only right after applying)
+    pub verify_interval_secs: u64,
+
+    /// Re-applies per task and generation when its attributes drift
+    /// (0 = report only)
+    pub max_reapply: u32,
+
+    /// Seconds between scans for foreign RT threads on managed CPUs
+    /// (0 = never scan)
+    pub foreign_scan_interval_secs: u64,
+
+    /// Where deadline misses of tasks delivered with a local fault sink go
+    /// (None = nowhere; they are reported upstream)
+    pub local_fault_sink: Option<LocalSinkTarget>,
+
+    /// Milliseconds between schedule requests to Timpani-O
+    pub poll_interval_ms: u64,
+
+    /// Tries to reach Timpani-O at startup, defaults::RETRY_INTERVAL_MS
+    /// apart, before giving up
+    pub connect_attempts: u32,
 }
 
 impl Default for Config {
@@ -159,6 +193,13 @@ impl Default for Config {
             enable_apex: false,
             clockid: ClockType::Realtime,
             log_level: LogLevel::Info,
+            resolvers: vec![ResolveRule::ByName],
+            verify_interval_secs: defaults::VERIFY_INTERVAL_SECS,
+            max_reapply: defaults::MAX_REAPPLY,
+            foreign_scan_interval_secs: defaults::FOREIGN_SCAN_INTERVAL_SECS,
+            local_fault_sink: None,
+            poll_interval_ms: defaults::POLL_INTERVAL_MS,
+            connect_attempts: defaults::CONNECT_ATTEMPTS,
         }
     }
 }
@@ -200,6 +241,41 @@ pub struct CliArgs {
     #[arg(short = 'a', long)]
     pub enable_apex: bool,
 
+    /// Task-to-process rule, tried in the order given: name,
+    /// label:<label>=<key> or systemd_unit:<template> ({task} = task name)
+    #[arg(short = 'r', long = "resolver", value_name = "RULE")]
+    pub resolvers: Vec<ResolveRule>,
+
+    /// Seconds between read-backs of applied scheduling attributes
+    /// (0 = only right after applying)
+    #[arg(long, value_name = "SECS", default_value_t = defaults::VERIFY_INTERVAL_SECS)]
+    pub verify_interval_secs: u64,
+
+    /// Re-apply drifted scheduling attributes up to N times per task and
+    /// generation (0 = report drift only)
+    #[arg(long, value_name = "N", default_value_t = defaults::MAX_REAPPLY)]
+    pub max_reapply: u32,
+
+    /// Scan for RT threads Timpani did not place on managed CPUs every SECS
+    /// seconds and report their load (0 = never scan)
+    #[arg(long, value_name = "SECS", default_value_t = defaults::FOREIGN_SCAN_INTERVAL_SECS)]
+    pub foreign_scan_interval_secs: u64,
+
+    /// Local sink for the deadline misses of tasks delivered with a local
+    /// fault sink: a journal file (<path> or file:<path>) or a datagram
+    /// socket (unix:<path>).  Without one they are reported upstream.
+    #[arg(long, value_name = "TARGET")]
+    pub local_fault_sink: Option<LocalSinkTarget>,
+
+    /// Milliseconds between schedule requests to Timpani-O
+    #[arg(long, value_name = "MS", default_value_t = defaults::POLL_INTERVAL_MS)]
+    pub poll_interval_ms: u64,
+
+    /// Tries to reach Timpani-O at startup, one second apart, before giving
+    /// up
+    #[arg(long, value_name = "N", default_value_t = defaults::CONNECT_ATTEMPTS)]
+    pub connect_attempts: u32,
+
     /// Server host address
     #[arg(value_name = "HOST")]
     pub host: Option<String>,
@@ -240,6 +316,21 @@ impl Config {
         config.enable_plot = args.enable_plot;
         config.enable_apex = args.enable_apex;
 
+        // Parse resolution rules (by name unless given)
+        if !args.resolvers.is_empty() {
+            config.resolvers = args.resolvers;
+        }
+
+        // Parse drift verification
+        config.verify_interval_secs = args.verify_interval_secs;
+        config.max_reapply = args.max_reapply;
+        config.foreign_scan_interval_secs = args.foreign_scan_interval_secs;
+        config.local_fault_sink = args.local_fault_sink;
+
+        // Parse the connection to Timpani-O
+        config.poll_interval_ms = args.poll_interval_ms;
+        config.connect_attempts = args.connect_attempts;
+
         // Parse host address
         if let Some(host) = args.host {
             config.addr = host;
@@ -288,6 +379,12 @@ impl Config {
             return Err(TimpaniError::Config);
         }
 
+        // Validate connection attempts
+        if self.connect_attempts == 0 {
+            eprintln!("[ERROR] Connection attempts must be at least 1");
+            return Err(TimpaniError::Config);
+        }
+
         Ok(())
     }
 
@@ -311,6 +408,16 @@ impl Config {
             "  Apex.OS test mode: {}",
             if self.enable_apex { "yes" } else { "no" }
         );
+        info!("  Resolvers: {:?}", self.resolvers);
+        info!("  Verify interval: {}s", self.verify_interval_secs);
+        info!("  Max re-apply: {}", self.max_reapply);
+        info!(
+            "  Foreign load scan interval: {}s",
+            self.foreign_scan_interval_secs
+        );
+        info!("  Local fault sink: {:?}", self.local_fault_sink);
+        info!("  Poll interval: {}ms", self.poll_interval_ms);
+        info!("  Connection attempts: {}", self.connect_attempts);
     }
 }
 
@@ -582,6 +689,74 @@ mod tests {
         assert_eq!(config.node_id, "1");
         assert_eq!(config.log_level, LogLevel::Info);
         assert!(!config.enable_sync);
+        assert_eq!(config.verify_interval_secs, defaults::VERIFY_INTERVAL_SECS);
+        assert_eq!(config.max_reapply, 0);
+        assert_eq!(
+            config.foreign_scan_interval_secs,
+            defaults::FOREIGN_SCAN_INTERVAL_SECS
+        );
+    }
+
+    #[test]
+    fn test_from_cli_args_drift_verification() {
+        use clap::Parser;
+
+        let args = CliArgs::try_parse_from([
+            "timpani-n",
+            "--verify-interval-secs",
+            "0",
+            "--max-reapply",
+            "3",
+        ])
+        .unwrap();
+        let config = Config::from_cli_args(args).unwrap();
+        assert_eq!(config.verify_interval_secs, 0);
+        assert_eq!(config.max_reapply, 3);
+    }
+
+    #[test]
+    fn test_from_cli_args_connection() {
+        use clap::Parser;
+
+        let args = CliArgs::try_parse_from(["timpani-n"]).unwrap();
+        let config = Config::from_cli_args(args).unwrap();
+        assert_eq!(config.poll_interval_ms, defaults::POLL_INTERVAL_MS);
+        assert_eq!(config.connect_attempts, defaults::CONNECT_ATTEMPTS);
+
+        let args = CliArgs::try_parse_from([
+            "timpani-n",
+            "--poll-interval-ms",
+            "250",
+            "--connect-attempts",
+            "5",
+        ])
+        .unwrap();
+        let config = Config::from_cli_args(args).unwrap();
+        assert_eq!(config.poll_interval_ms, 250);
+        assert_eq!(config.connect_attempts, 5);
+
+        let args = CliArgs::try_parse_from(["timpani-n", "--connect-attempts", "0"]).unwrap();
+        assert_eq!(
+            Config::from_cli_args(args).err(),
+            Some(TimpaniError::Config)
+        );
+    }
+
+    #[test]
+    fn test_from_cli_args_local_fault_sink() {
+        use clap::Parser;
+
+        let args = CliArgs::try_parse_from(["timpani-n"]).unwrap();
+        assert_eq!(Config::from_cli_args(args).unwrap().local_fault_sink, None);
+
+        let args =
+            CliArgs::try_parse_from(["timpani-n", "--local-fault-sink", "unix:/run/safety.sock"])
+                .unwrap();
+        assert_eq!(
+            Config::from_cli_args(args).unwrap().local_fault_sink,
+            Some(LocalSinkTarget::Socket("/run/safety.sock".into()))
+        );
+        assert!(CliArgs::try_parse_from(["timpani-n", "--local-fault-sink", "unix:"]).is_err());
     }
 
     #[test]
@@ -678,6 +853,36 @@ mod tests {
         assert!(args.enable_apex);
     }
 
+    #[test]
+    fn test_resolver_options() {
+        use clap::Parser;
+
+        let args = CliArgs::try_parse_from(["timpani-n"]).unwrap();
+        let config = Config::from_cli_args(args).unwrap();
+        assert_eq!(config.resolvers, [ResolveRule::ByName]);
+
+        let args = CliArgs::try_parse_from([
+            "timpani-n",
+            "--resolver",
+            "systemd_unit:{task}.service",
+            "-r",
+            "name",
+        ])
+        .unwrap();
+        let config = Config::from_cli_args(args).unwrap();
+        assert_eq!(
+            config.resolvers,
+            [
+                ResolveRule::SystemdUnit {
+                    template: "{task}.service".into()
+                },
+                ResolveRule::ByName
+            ]
+        );
+
+        assert!(CliArgs::try_parse_from(["timpani-n", "-r", "pidfile"]).is_err());
+    }
+
     #[test]
     fn test_validate_all_log_levels() {
         for level_num in 0..=5 {
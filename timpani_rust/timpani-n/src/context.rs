@@ -3,17 +3,22 @@
  * SPDX-License-Identifier: MIT
  */
 
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use crate::capability::{Capabilities, CapabilityProbe, ProcfsProbe};
 use crate::config::Config;
 
 /// Runtime state structure
 /// Maps to context.runtime from C implementation
 #[derive(Debug, Default)]
 pub struct RuntimeState {
-    /// Shutdown request flag
-    pub shutdown_requested: bool,
+    /// Shutdown request flag; the main loop ends once it is set
+    pub shutdown_requested: Arc<AtomicBool>,
+    /// RT privilege pre-flight result (None until initialize)
+    pub capabilities: Option<Capabilities>,
     // TODO: Add fields as we port more modules:
     // - tt_list (time trigger task list)
-    // - sched_info (scheduling information)
     // - starttimer_ts (start timer timestamp)
     // - apex_list (Apex.OS task list)
 }
@@ -70,6 +75,19 @@ impl Context {
 
     /// Initialize the context (placeholder for future initialization logic)
     pub fn initialize(&mut self) -> crate::error::TimpaniResult<()> {
+        self.initialize_with_probe(&ProcfsProbe)
+    }
+
+    /// Initialize using the given capability probe
+    ///
+    /// A failed pre-flight is recorded, not returned: the node still starts,
+    /// but does not report itself RT-ready.
+    pub fn initialize_with_probe(
+        &mut self,
+        probe: &dyn CapabilityProbe,
+    ) -> crate::error::TimpaniResult<()> {
+        self.runtime.capabilities = Some(Capabilities::probe(probe, self.config.prio));
+
         // TODO: Add initialization logic as we port more modules:
         // - setup_signal_handlers
         // - set_affinity
@@ -82,6 +100,11 @@ impl Context {
         Ok(())
     }
 
+    /// Whether this node may accept RT placements
+    pub fn rt_ready(&self) -> bool {
+        self.runtime.capabilities.is_some_and(|c| c.rt_ready())
+    }
+
     /// Cleanup resources (placeholder for future cleanup logic)
     pub fn cleanup(&mut self) {
         // TODO: Add cleanup logic as we port more modules:
@@ -95,18 +118,20 @@ impl Context {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::capability::test_support::MockProbe;
+    use std::sync::atomic::Ordering;
 
     #[test]
     fn test_context_creation() {
         let config = Config::default();
         let ctx = Context::new(config);
-        assert!(!ctx.runtime.shutdown_requested);
+        assert!(!ctx.runtime.shutdown_requested.load(Ordering::SeqCst));
     }
 
     #[test]
     fn test_runtime_default() {
         let runtime = RuntimeState::default();
-        assert!(!runtime.shutdown_requested);
+        assert!(!runtime.shutdown_requested.load(Ordering::SeqCst));
     }
 
     #[test]
@@ -137,6 +162,27 @@ mod tests {
         let _ = format!("{:?}", hp_mgr);
     }
 
+    #[test]
+    fn test_context_not_rt_ready_before_initialize() {
+        let ctx = Context::new(Config::default());
+        assert!(!ctx.rt_ready());
+    }
+
+    #[test]
+    fn test_context_rt_ready_with_privileged_probe() {
+        let mut ctx = Context::new(Config::default());
+        ctx.initialize_with_probe(&MockProbe::PRIVILEGED).unwrap();
+        assert!(ctx.rt_ready());
+    }
+
+    #[test]
+    fn test_context_unprivileged_probe_initializes_but_not_ready() {
+        let mut ctx = Context::new(Config::default());
+        assert!(ctx.initialize_with_probe(&MockProbe::UNPRIVILEGED).is_ok());
+        assert!(!ctx.rt_ready());
+        assert!(ctx.runtime.capabilities.is_some());
+    }
+
     #[test]
     fn test_context_with_custom_config() {
         let mut config = Config::default();
@@ -0,0 +1,388 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Routing of deadline-miss events to Timpani-O, a local sink, or both.
+//!
+//! Every miss normally goes upstream with `ReportDMiss`, and Timpani-O
+//! forwards it to Piccolo.  A task whose misses should reach a
+//! vehicle-local safety monitor directly is delivered with a
+//! [`FaultSink`] in `ScheduledTask.fault_sink`:
+//!
+//! | Sink        | `ReportDMiss` | Local sink |
+//! |-------------|---------------|------------|
+//! | `Upstream`  | yes           | no         |
+//! | `LocalOnly` | no            | yes        |
+//! | `Both`      | yes           | yes        |
+//!
+//! The local sink is `--local-fault-sink`: a journal file each miss is
+//! appended to as one line, or `unix:<path>`, a datagram socket each miss
+//! is sent to (see [`LocalSinkTarget`]).  A node without one cannot honour
+//! `LocalOnly` or `Both`, so those tasks fall back to `Upstream` and no
+//! miss is lost; the sink in effect is reported per task in the
+//! [`ApplyReport`](crate::apply::ApplyReport).  A local delivery that fails
+//! is logged and, for `LocalOnly`, sent upstream instead.
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::os::unix::net::UnixDatagram;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use tracing::warn;
+
+// =============================================================================
+// SINK
+// =============================================================================
+
+/// Where a task's deadline misses go; mirrors the `FaultSink` proto enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FaultSink {
+    #[default]
+    Upstream,
+    LocalOnly,
+    Both,
+}
+
+impl FaultSink {
+    /// The proto enum number.
+    pub fn wire_value(self) -> i32 {
+        match self {
+            FaultSink::Upstream => 0,
+            FaultSink::LocalOnly => 1,
+            FaultSink::Both => 2,
+        }
+    }
+
+    /// Parse `ScheduledTask.fault_sink`.  Unknown values map to `Upstream`,
+    /// which never loses a miss.
+    pub fn from_wire(v: i32) -> Self {
+        match v {
+            1 => FaultSink::LocalOnly,
+            2 => FaultSink::Both,
+            _ => FaultSink::Upstream,
+        }
+    }
+
+    /// The sink in effect on a node that has a local sink or not.
+    pub fn effective(self, local_available: bool) -> Self {
+        if local_available {
+            self
+        } else {
+            FaultSink::Upstream
+        }
+    }
+
+    /// `true` if misses are reported with `ReportDMiss`.
+    pub fn upstream(self) -> bool {
+        matches!(self, FaultSink::Upstream | FaultSink::Both)
+    }
+
+    /// `true` if misses go to the local sink.
+    pub fn local(self) -> bool {
+        matches!(self, FaultSink::LocalOnly | FaultSink::Both)
+    }
+}
+
+// =============================================================================
+// EVENTS
+// =============================================================================
+
+/// One deadline miss.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissEvent {
+    pub node_id: String,
+    pub task_name: String,
+    /// Wall-clock time of the miss, in ns since the Unix epoch.
+    pub time_ns: u64,
+}
+
+impl fmt::Display for MissEvent {
+    /// The journal line: `dmiss node=<node> task=<task> time_ns=<ns>`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dmiss node={} task={} time_ns={}",
+            self.node_id, self.task_name, self.time_ns
+        )
+    }
+}
+
+/// Somewhere a miss can be delivered.  Mocked in tests.
+pub trait MissSink {
+    fn deliver(&self, event: &MissEvent) -> io::Result<()>;
+}
+
+// =============================================================================
+// LOCAL SINK
+// =============================================================================
+
+/// The node's local sink, as given with `--local-fault-sink`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalSinkTarget {
+    /// A file each miss is appended to as one line.
+    Journal(PathBuf),
+    /// A Unix datagram socket each miss is sent to as one datagram.
+    Socket(PathBuf),
+}
+
+impl FromStr for LocalSinkTarget {
+    type Err = String;
+
+    /// `unix:<path>` for a socket; `file:<path>` or a bare path for a
+    /// journal.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let target = match s.split_once(':') {
+            Some(("unix", path)) => LocalSinkTarget::Socket(path.into()),
+            Some(("file", path)) => LocalSinkTarget::Journal(path.into()),
+            _ => LocalSinkTarget::Journal(s.into()),
+        };
+        match &target {
+            LocalSinkTarget::Journal(p) | LocalSinkTarget::Socket(p)
+                if p.as_os_str().is_empty() =>
+            {
+                Err(format!(
+                    "expected <path>, file:<path> or unix:<path>, got '{s}'"
+                ))
+            }
+            _ => Ok(target),
+        }
+    }
+}
+
+/// Delivers misses to a [`LocalSinkTarget`].
+#[derive(Debug, Clone)]
+pub struct LocalSink {
+    target: LocalSinkTarget,
+}
+
+impl LocalSink {
+    pub fn new(target: LocalSinkTarget) -> Self {
+        Self { target }
+    }
+}
+
+impl MissSink for LocalSink {
+    fn deliver(&self, event: &MissEvent) -> io::Result<()> {
+        match &self.target {
+            LocalSinkTarget::Journal(path) => {
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{event}")
+            }
+            LocalSinkTarget::Socket(path) => {
+                UnixDatagram::unbound()?.send_to(event.to_string().as_bytes(), path)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+// =============================================================================
+// ROUTER
+// =============================================================================
+
+/// Where a miss was delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Routed {
+    pub upstream: bool,
+    pub local: bool,
+}
+
+/// Sends each miss where its task's [`FaultSink`] says (see the module
+/// docs).
+pub struct MissRouter<'a> {
+    upstream: &'a dyn MissSink,
+    local: Option<&'a dyn MissSink>,
+}
+
+impl<'a> MissRouter<'a> {
+    pub fn new(upstream: &'a dyn MissSink) -> Self {
+        Self {
+            upstream,
+            local: None,
+        }
+    }
+
+    pub fn with_local(mut self, local: &'a dyn MissSink) -> Self {
+        self.local = Some(local);
+        self
+    }
+
+    /// Deliver `event` for a task delivered with `sink`.
+    pub fn route(&self, sink: FaultSink, event: &MissEvent) -> Routed {
+        let sink = sink.effective(self.local.is_some());
+        let mut routed = Routed::default();
+        if let Some(local) = self.local.filter(|_| sink.local()) {
+            match local.deliver(event) {
+                Ok(()) => routed.local = true,
+                Err(e) => warn!(
+                    task = %event.task_name,
+                    error = %e,
+                    "local fault sink failed"
+                ),
+            }
+        }
+        if sink.upstream() || !routed.local {
+            match self.upstream.deliver(event) {
+                Ok(()) => routed.upstream = true,
+                Err(e) => warn!(
+                    task = %event.task_name,
+                    error = %e,
+                    "reporting the deadline miss upstream failed"
+                ),
+            }
+        }
+        routed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Records every delivery, or fails each one.
+    #[derive(Default)]
+    struct MockSink {
+        delivered: RefCell<Vec<String>>,
+        broken: bool,
+    }
+
+    impl MissSink for MockSink {
+        fn deliver(&self, event: &MissEvent) -> io::Result<()> {
+            if self.broken {
+                return Err(io::Error::other("sink down"));
+            }
+            self.delivered.borrow_mut().push(event.task_name.clone());
+            Ok(())
+        }
+    }
+
+    fn miss(task: &str) -> MissEvent {
+        MissEvent {
+            node_id: "n1".into(),
+            task_name: task.into(),
+            time_ns: 1_000,
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("timpani-n-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_each_sink_routes_where_it_says() {
+        let upstream = MockSink::default();
+        let local = MockSink::default();
+        let router = MissRouter::new(&upstream).with_local(&local);
+
+        let cases = [
+            (FaultSink::Upstream, "up", true, false),
+            (FaultSink::LocalOnly, "local", false, true),
+            (FaultSink::Both, "both", true, true),
+        ];
+        for (sink, task, to_upstream, to_local) in cases {
+            let routed = router.route(sink, &miss(task));
+            assert_eq!(
+                routed,
+                Routed {
+                    upstream: to_upstream,
+                    local: to_local
+                },
+                "{sink:?}"
+            );
+        }
+        assert_eq!(*upstream.delivered.borrow(), ["up", "both"]);
+        assert_eq!(*local.delivered.borrow(), ["local", "both"]);
+    }
+
+    #[test]
+    fn test_without_a_local_sink_everything_goes_upstream() {
+        let upstream = MockSink::default();
+        let router = MissRouter::new(&upstream);
+        for sink in [FaultSink::Upstream, FaultSink::LocalOnly, FaultSink::Both] {
+            let routed = router.route(sink, &miss("t"));
+            assert!(routed.upstream && !routed.local, "{sink:?}");
+        }
+        assert_eq!(upstream.delivered.borrow().len(), 3);
+    }
+
+    #[test]
+    fn test_failed_local_delivery_falls_back_upstream() {
+        let upstream = MockSink::default();
+        let local = MockSink {
+            broken: true,
+            ..Default::default()
+        };
+        let routed = MissRouter::new(&upstream)
+            .with_local(&local)
+            .route(FaultSink::LocalOnly, &miss("t"));
+        assert_eq!(
+            routed,
+            Routed {
+                upstream: true,
+                local: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_wire_values_match_the_proto() {
+        for sink in [FaultSink::Upstream, FaultSink::LocalOnly, FaultSink::Both] {
+            assert_eq!(FaultSink::from_wire(sink.wire_value()), sink);
+        }
+        assert_eq!(FaultSink::Both.wire_value(), 2);
+        assert_eq!(FaultSink::from_wire(9), FaultSink::Upstream);
+        assert_eq!(FaultSink::Both.effective(false), FaultSink::Upstream);
+    }
+
+    #[test]
+    fn test_parse_local_sink_target() {
+        let parse = |s: &str| s.parse::<LocalSinkTarget>();
+        assert_eq!(
+            parse("unix:/run/safety.sock"),
+            Ok(LocalSinkTarget::Socket("/run/safety.sock".into()))
+        );
+        assert_eq!(
+            parse("file:/var/log/dmiss"),
+            Ok(LocalSinkTarget::Journal("/var/log/dmiss".into()))
+        );
+        assert_eq!(
+            parse("/var/log/dmiss"),
+            Ok(LocalSinkTarget::Journal("/var/log/dmiss".into()))
+        );
+        assert!(parse("unix:").is_err());
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn test_journal_appends_one_line_per_miss() {
+        let path = temp_path("journal");
+        let sink = LocalSink::new(LocalSinkTarget::Journal(path.clone()));
+        sink.deliver(&miss("cam")).unwrap();
+        sink.deliver(&miss("lidar")).unwrap();
+        let journal = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            journal,
+            "dmiss node=n1 task=cam time_ns=1000\ndmiss node=n1 task=lidar time_ns=1000\n"
+        );
+    }
+
+    #[test]
+    fn test_socket_sends_one_datagram_per_miss() {
+        let path = temp_path("sock");
+        let monitor = UnixDatagram::bind(&path).unwrap();
+        LocalSink::new(LocalSinkTarget::Socket(path.clone()))
+            .deliver(&miss("cam"))
+            .unwrap();
+        let mut buf = [0u8; 128];
+        let n = monitor.recv(&mut buf).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(&buf[..n], b"dmiss node=n1 task=cam time_ns=1000");
+    }
+}
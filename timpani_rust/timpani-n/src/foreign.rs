@@ -0,0 +1,404 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Foreign load: RT threads on managed CPUs that Timpani did not place.
+//!
+//! Another agent on the node may pin its own RT threads to CPUs Timpani-O
+//! schedules, and Timpani-O's utilisation model does not see them.  The
+//! [`ForeignLoadScanner`] lists every thread under `/proc` every
+//! `--foreign-scan-interval-secs` seconds and keeps those that
+//!
+//! - run an RT policy (`SCHED_FIFO`, `SCHED_RR` or `SCHED_DEADLINE`),
+//! - are allowed on at least one managed CPU, and
+//! - belong to no process of the applied schedule (the PIDs of the last
+//!   [`ApplyReport`]) nor to Timpani-N itself.
+//!
+//! A thread's utilisation is the growth of its `schedstat` run time over
+//! the wall time between two scans, so a thread is first counted at the
+//! scan after it appears.  `schedstat` does not say where the thread ran:
+//! one allowed on several CPUs is spread evenly over them, and only the
+//! share on managed CPUs is reported.  The result goes upstream as one
+//! [`ForeignLoad`] per CPU, mirroring the `ForeignLoad` proto message;
+//! CPUs below [`MIN_REPORTED_LOAD`] are left out as noise.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, warn};
+
+use crate::apply::{parse_cpu_list, policy, ApplyReport};
+
+// =============================================================================
+// CONSTANTS
+// =============================================================================
+
+/// Procfs path used by [`read_threads`]
+pub mod paths {
+    pub const PROC: &str = "/proc";
+}
+
+/// `SCHED_DEADLINE`, which [`policy`] does not list because Timpani-O never
+/// assigns it.
+pub const SCHED_DEADLINE: i32 = 6;
+
+/// Per-CPU foreign utilisation below this is not reported.
+pub const MIN_REPORTED_LOAD: f64 = 0.01;
+
+/// Field of `/proc/<pid>/task/<tid>/stat` holding the policy (1-based, see
+/// proc(5)).
+const STAT_POLICY_FIELD: usize = 41;
+
+// =============================================================================
+// SAMPLES
+// =============================================================================
+
+/// One thread as read from `/proc`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ThreadSample {
+    /// Thread-group (process) id.
+    pub pid: i32,
+    pub tid: i32,
+    pub comm: String,
+    /// See [`policy`] and [`SCHED_DEADLINE`].
+    pub policy: i32,
+    /// Bit `n` = CPU `n`.
+    pub affinity: u64,
+    /// Time spent on a CPU so far (`schedstat`, first field).
+    pub runtime_ns: u64,
+}
+
+impl ThreadSample {
+    fn is_rt(&self) -> bool {
+        matches!(
+            self.policy,
+            policy::SCHED_FIFO | policy::SCHED_RR | SCHED_DEADLINE
+        )
+    }
+
+    /// Parse one thread from the contents of its `stat`, `status` and
+    /// `schedstat` files.  `None` if any is malformed.
+    pub fn parse(pid: i32, tid: i32, stat: &str, status: &str, schedstat: &str) -> Option<Self> {
+        let (comm, policy) = parse_stat(stat)?;
+        Some(Self {
+            pid,
+            tid,
+            comm,
+            policy,
+            affinity: parse_cpus_allowed(status)?,
+            runtime_ns: schedstat.split_whitespace().next()?.parse().ok()?,
+        })
+    }
+}
+
+/// `(comm, policy)` from a `stat` line.  The comm is parenthesised and may
+/// itself contain spaces and parentheses, so fields are counted from the
+/// last `)`.
+fn parse_stat(stat: &str) -> Option<(String, i32)> {
+    let open = stat.find('(')?;
+    let close = stat.rfind(')')?;
+    let comm = stat.get(open + 1..close)?.to_string();
+    // The field after the comm is field 3.
+    let policy = stat[close + 1..]
+        .split_whitespace()
+        .nth(STAT_POLICY_FIELD - 3)?
+        .parse()
+        .ok()?;
+    Some((comm, policy))
+}
+
+/// The affinity mask from `Cpus_allowed_list:` in `status`.  CPUs beyond
+/// the mask are dropped.
+fn parse_cpus_allowed(status: &str) -> Option<u64> {
+    let list = status
+        .lines()
+        .find_map(|l| l.strip_prefix("Cpus_allowed_list:"))?;
+    Some(
+        parse_cpu_list(list)
+            .into_iter()
+            .filter(|&c| c < u64::BITS)
+            .fold(0, |mask, c| mask | 1 << c),
+    )
+}
+
+/// Every thread of every process under `/proc`.  Threads that exit while
+/// being read are skipped.
+pub fn read_threads() -> Vec<ThreadSample> {
+    let mut threads = Vec::new();
+    let Ok(procs) = fs::read_dir(paths::PROC) else {
+        warn!(path = paths::PROC, "cannot list processes");
+        return threads;
+    };
+    let id = |name: &std::ffi::OsStr| name.to_str()?.parse::<i32>().ok();
+    for proc_entry in procs.flatten() {
+        let Some(pid) = id(&proc_entry.file_name()) else {
+            continue;
+        };
+        let Ok(tasks) = fs::read_dir(proc_entry.path().join("task")) else {
+            continue;
+        };
+        for task in tasks.flatten() {
+            let Some(tid) = id(&task.file_name()) else {
+                continue;
+            };
+            let dir = task.path();
+            let read = |file: &str| fs::read_to_string(Path::new(&dir).join(file));
+            if let (Ok(stat), Ok(status), Ok(schedstat)) =
+                (read("stat"), read("status"), read("schedstat"))
+            {
+                threads.extend(ThreadSample::parse(pid, tid, &stat, &status, &schedstat));
+            }
+        }
+    }
+    threads
+}
+
+// =============================================================================
+// REPORT
+// =============================================================================
+
+/// Estimated foreign RT load on one CPU; mirrors the `ForeignLoad` proto
+/// message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignLoad {
+    pub cpu: u32,
+    /// Fraction of the CPU, `0..=1`.
+    pub utilization: f64,
+    /// The contributing threads as `comm/tid`, highest load first.
+    pub threads: Vec<String>,
+}
+
+/// PIDs of the applied schedule: every task `report` applied to a process.
+pub fn scheduled_pids(report: &ApplyReport) -> BTreeSet<i32> {
+    report
+        .tasks
+        .iter()
+        .filter(|t| t.status.is_success() && t.pid != 0)
+        .map(|t| t.pid)
+        .collect()
+}
+
+// =============================================================================
+// SCANNER
+// =============================================================================
+
+/// Turns successive thread listings into [`ForeignLoad`] reports (see the
+/// module docs).
+#[derive(Debug)]
+pub struct ForeignLoadScanner {
+    /// CPUs Timpani schedules on this node; bit `n` = CPU `n`.
+    managed: u64,
+    interval: Duration,
+    /// Lists the threads; [`read_threads`] unless replaced in tests.
+    source: fn() -> Vec<ThreadSample>,
+    /// Run time per tid at the previous scan, and when it was taken.
+    last: Option<(Instant, BTreeMap<i32, u64>)>,
+}
+
+impl ForeignLoadScanner {
+    /// Watch the CPUs in `managed`, scanning every `interval`.
+    pub fn new(managed: u64, interval: Duration) -> Self {
+        Self {
+            managed,
+            interval,
+            source: read_threads,
+            last: None,
+        }
+    }
+
+    /// List threads with `source` instead of reading `/proc`.
+    pub fn with_source(mut self, source: fn() -> Vec<ThreadSample>) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Watch `managed` from now on, e.g. after a new schedule.
+    pub fn set_managed(&mut self, managed: u64) {
+        self.managed = managed;
+    }
+
+    /// `true` if a scan is due at `now`.
+    pub fn due(&self, now: Instant) -> bool {
+        self.last
+            .as_ref()
+            .is_none_or(|(at, _)| now.saturating_duration_since(*at) >= self.interval)
+    }
+
+    /// Scan `/proc` at `now`, with `scheduled` the PIDs of the applied
+    /// schedule.
+    pub fn scan(&mut self, scheduled: &BTreeSet<i32>, now: Instant) -> Vec<ForeignLoad> {
+        let mut own = scheduled.clone();
+        own.insert(std::process::id() as i32);
+        self.observe(&(self.source)(), &own, now)
+    }
+
+    /// Record `threads`, listed at `now`, and return the foreign load since
+    /// the previous observation, by CPU.  The first observation is the
+    /// baseline and reports nothing.
+    pub fn observe(
+        &mut self,
+        threads: &[ThreadSample],
+        scheduled: &BTreeSet<i32>,
+        now: Instant,
+    ) -> Vec<ForeignLoad> {
+        let foreign: Vec<&ThreadSample> = threads
+            .iter()
+            .filter(|t| t.is_rt() && t.affinity & self.managed != 0)
+            .filter(|t| !scheduled.contains(&t.pid) && !scheduled.contains(&t.tid))
+            .collect();
+        let runtimes = foreign.iter().map(|t| (t.tid, t.runtime_ns)).collect();
+        let Some((then, before)) = self.last.replace((now, runtimes)) else {
+            return Vec::new();
+        };
+        let elapsed_ns = now.saturating_duration_since(then).as_nanos() as f64;
+        if elapsed_ns == 0.0 {
+            return Vec::new();
+        }
+
+        let mut by_cpu: BTreeMap<u32, Vec<(f64, &ThreadSample)>> = BTreeMap::new();
+        for t in foreign {
+            // A tid seen for the first time, or reused, has no baseline.
+            let Some(&prev) = before.get(&t.tid).filter(|&&p| p <= t.runtime_ns) else {
+                continue;
+            };
+            let util = (t.runtime_ns - prev) as f64 / elapsed_ns;
+            let allowed = t.affinity.count_ones() as f64;
+            for cpu in (0..u64::BITS).filter(|&c| t.affinity & self.managed & 1 << c != 0) {
+                by_cpu.entry(cpu).or_default().push((util / allowed, t));
+            }
+        }
+
+        by_cpu
+            .into_iter()
+            .filter_map(|(cpu, mut threads)| {
+                let utilization = threads.iter().map(|(u, _)| u).sum::<f64>().min(1.0);
+                if utilization < MIN_REPORTED_LOAD {
+                    return None;
+                }
+                threads.sort_by(|a, b| b.0.total_cmp(&a.0));
+                let threads = threads
+                    .into_iter()
+                    .map(|(_, t)| format!("{}/{}", t.comm, t.tid))
+                    .collect();
+                debug!(cpu, utilization, ?threads, "foreign RT load");
+                Some(ForeignLoad {
+                    cpu,
+                    utilization,
+                    threads,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply::{ApplyStatus, TaskApplyResult};
+
+    /// A `stat` line for a thread named `comm` under `policy`: fields 3 to
+    /// 40 are zero, then the policy.
+    fn stat(tid: i32, comm: &str, policy: i32) -> String {
+        format!("{tid} ({comm}) S{} {policy} 0 0 0", " 0".repeat(37))
+    }
+
+    fn thread(
+        pid: i32,
+        tid: i32,
+        comm: &str,
+        policy: i32,
+        cpus: &str,
+        runtime_ms: u64,
+    ) -> ThreadSample {
+        ThreadSample::parse(
+            pid,
+            tid,
+            &stat(tid, comm, policy),
+            &format!("Name:\t{comm}\nCpus_allowed:\tff\nCpus_allowed_list:\t{cpus}\n"),
+            &format!("{} 0 0\n", runtime_ms * 1_000_000),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_proc_samples() {
+        let t = thread(10, 11, "rt agent (x)", policy::SCHED_FIFO, "2-3,6", 5);
+        assert_eq!(t.comm, "rt agent (x)");
+        assert_eq!(t.policy, policy::SCHED_FIFO);
+        assert_eq!(t.affinity, 0b100_1100);
+        assert_eq!(t.runtime_ns, 5_000_000);
+        assert_eq!(parse_stat("1 (init) S 0"), None);
+        assert_eq!(
+            ThreadSample::parse(1, 1, &stat(1, "x", 0), "Name: x\n", "0"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_foreign_rt_threads_are_attributed_per_cpu() {
+        // CPUs 2 and 3 are managed.
+        let mut scanner = ForeignLoadScanner::new(0b1100, Duration::from_secs(10));
+        let scheduled = BTreeSet::from([100]);
+        let t0 = Instant::now();
+        let listing = |ms: [u64; 5]| {
+            vec![
+                // Foreign, pinned to CPU 2.
+                thread(50, 50, "agent", policy::SCHED_FIFO, "2", ms[0]),
+                // Foreign, allowed on CPUs 1-3: a third per CPU.
+                thread(60, 61, "spread", policy::SCHED_RR, "1-3", ms[1]),
+                // A thread of a scheduled process.
+                thread(100, 101, "cam", policy::SCHED_FIFO, "2", ms[2]),
+                // Not RT.
+                thread(70, 70, "batch", policy::SCHED_OTHER, "2", ms[3]),
+                // RT, but on an unmanaged CPU.
+                thread(80, 80, "irq", policy::SCHED_FIFO, "0", ms[4]),
+            ]
+        };
+        assert!(scanner.due(t0));
+        assert!(scanner.observe(&listing([0; 5]), &scheduled, t0).is_empty());
+
+        let t1 = t0 + Duration::from_secs(10);
+        assert!(!scanner.due(t1 - Duration::from_secs(1)));
+        let loads = scanner.observe(
+            &listing([3_000, 1_500, 9_000, 9_000, 9_000]),
+            &scheduled,
+            t1,
+        );
+        let by_cpu: BTreeMap<u32, &ForeignLoad> = loads.iter().map(|l| (l.cpu, l)).collect();
+        assert_eq!(by_cpu.keys().copied().collect::<Vec<_>>(), [2, 3]);
+        assert!((by_cpu[&2].utilization - 0.35).abs() < 1e-9, "{loads:?}");
+        assert_eq!(by_cpu[&2].threads, ["agent/50", "spread/61"]);
+        assert!((by_cpu[&3].utilization - 0.05).abs() < 1e-9, "{loads:?}");
+
+        // An idle scan reports nothing; so does a thread seen only once.
+        let mut quiet = listing([3_000, 1_500, 0, 0, 0]);
+        quiet.push(thread(90, 90, "new", policy::SCHED_FIFO, "3", 5_000));
+        let t2 = t1 + Duration::from_secs(10);
+        assert!(scanner.observe(&quiet, &scheduled, t2).is_empty());
+    }
+
+    #[test]
+    fn test_scheduled_pids_come_from_the_apply_report() {
+        let result = |pid, status| TaskApplyResult {
+            task_name: "t".into(),
+            status,
+            errno: 0,
+            detail: String::new(),
+            pid,
+            unit_state: String::new(),
+            fault_sink: Default::default(),
+        };
+        let report = ApplyReport {
+            tasks: vec![
+                result(10, ApplyStatus::Applied),
+                result(11, ApplyStatus::PermissionDenied),
+                result(0, ApplyStatus::Reserved),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(scheduled_pids(&report), BTreeSet::from([10]));
+    }
+}
@@ -0,0 +1,170 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Online-CPU tracking for CPU hotplug.
+//!
+//! Thermal management may take a core offline at runtime.  The node reads
+//! the kernel's online list before every schedule request, sends it to
+//! Timpani-O as `online_cpus`, and logs each transition.  Timpani-O then
+//! stops placing tasks on the offline CPUs and moves (or reports) the
+//! tasks already there.
+//!
+//! sysfs does not notify on changes to the online list, so it is polled:
+//! a transition is seen at the next request at the latest.
+
+use std::fs;
+
+use tracing::{info, warn};
+
+use crate::apply::parse_cpu_list;
+
+// =============================================================================
+// CONSTANTS
+// =============================================================================
+
+/// Sysfs path used by [`online_cpus`]
+pub mod paths {
+    pub const CPU_ONLINE: &str = "/sys/devices/system/cpu/online";
+}
+
+// =============================================================================
+// PROBE
+// =============================================================================
+
+/// CPUs the kernel has online, ascending.  `None` if the list cannot be
+/// read (the report is then omitted and Timpani-O assumes every configured
+/// CPU is online).
+pub fn online_cpus() -> Option<Vec<u32>> {
+    let list = fs::read_to_string(paths::CPU_ONLINE).ok()?;
+    let mut cpus = parse_cpu_list(&list);
+    cpus.sort_unstable();
+    cpus.dedup();
+    Some(cpus)
+}
+
+/// [`online_cpus`] as a mask (bit `n` = CPU `n`); all 64 if the list
+/// cannot be read.
+pub fn online_mask() -> u64 {
+    online_cpus().map_or(u64::MAX, |cpus| {
+        cpus.into_iter()
+            .filter(|&c| c < u64::BITS)
+            .fold(0, |mask, c| mask | 1 << c)
+    })
+}
+
+// =============================================================================
+// WATCHER
+// =============================================================================
+
+/// A change in the online CPU list between two observations.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CpuChange {
+    /// The new online list.
+    pub online: Vec<u32>,
+    pub went_offline: Vec<u32>,
+    pub came_online: Vec<u32>,
+}
+
+/// Remembers the last online list and reports transitions.
+#[derive(Debug)]
+pub struct OnlineCpuWatcher {
+    /// Reads the online list; [`online_cpus`] unless replaced in tests.
+    source: fn() -> Option<Vec<u32>>,
+    last: Option<Vec<u32>>,
+}
+
+impl Default for OnlineCpuWatcher {
+    fn default() -> Self {
+        Self {
+            source: online_cpus,
+            last: None,
+        }
+    }
+}
+
+impl OnlineCpuWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the online list with `source` instead of sysfs.
+    pub fn with_source(mut self, source: fn() -> Option<Vec<u32>>) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// The last observed online list, as sent to Timpani-O.
+    pub fn online(&self) -> Option<&[u32]> {
+        self.last.as_deref()
+    }
+
+    /// Read the kernel's online list and report a change since the last
+    /// call.  An unreadable list keeps the previous one.
+    pub fn poll(&mut self) -> Option<CpuChange> {
+        match (self.source)() {
+            Some(online) => self.observe(online),
+            None => {
+                warn!(path = paths::CPU_ONLINE, "cannot read online CPU list");
+                None
+            }
+        }
+    }
+
+    /// Record `online` (ascending) and report a change since the last
+    /// observation.  The first observation is the baseline, not a change.
+    pub fn observe(&mut self, online: Vec<u32>) -> Option<CpuChange> {
+        let previous = self.last.replace(online.clone())?;
+        let went_offline: Vec<u32> = previous
+            .iter()
+            .copied()
+            .filter(|c| online.binary_search(c).is_err())
+            .collect();
+        let came_online: Vec<u32> = online
+            .iter()
+            .copied()
+            .filter(|c| previous.binary_search(c).is_err())
+            .collect();
+        if went_offline.is_empty() && came_online.is_empty() {
+            return None;
+        }
+        info!(?went_offline, ?came_online, "online CPUs changed");
+        Some(CpuChange {
+            online,
+            went_offline,
+            came_online,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_observation_is_the_baseline() {
+        let mut watcher = OnlineCpuWatcher::new();
+        assert_eq!(watcher.online(), None);
+        assert_eq!(watcher.observe(vec![0, 1, 2, 3]), None);
+        assert_eq!(watcher.online(), Some(&[0, 1, 2, 3][..]));
+        assert_eq!(watcher.observe(vec![0, 1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_offline_and_online_transitions() {
+        let mut watcher = OnlineCpuWatcher::new();
+        watcher.observe(vec![0, 1, 2, 3]);
+        assert_eq!(
+            watcher.observe(vec![0, 1, 3]),
+            Some(CpuChange {
+                online: vec![0, 1, 3],
+                went_offline: vec![2],
+                came_online: vec![],
+            })
+        );
+        let change = watcher.observe(vec![0, 1, 2, 3]).unwrap();
+        assert_eq!(change.went_offline, Vec::<u32>::new());
+        assert_eq!(change.came_online, vec![2]);
+    }
+}
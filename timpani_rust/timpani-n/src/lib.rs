@@ -3,15 +3,35 @@
  * SPDX-License-Identifier: MIT
  */
 
+pub mod agent;
+pub mod apply;
+pub mod backend;
+pub mod capability;
+pub mod clock;
 pub mod config;
 pub mod context;
 pub mod error;
-
+pub mod fault;
+pub mod foreign;
+pub mod hotplug;
+pub mod memory;
+pub mod proto;
+pub mod resolve;
+pub mod schedule;
+pub mod upstream;
+pub mod verify;
+
+use std::time::Duration;
+
+use agent::NodeAgent;
+use backend::LinuxBackend;
 use config::Config;
 use context::Context;
 use error::TimpaniResult;
-use tracing::info;
+use resolve::{NoSystemd, ResolveRule, SystemdBus, SystemdUnits};
+use tracing::{info, warn};
 use tracing_subscriber::fmt::SubscriberBuilder;
+use upstream::GrpcUpstream;
 
 /// Initialize logging with the specified log level
 pub fn init_logging(log_level: config::LogLevel) {
@@ -38,10 +58,39 @@ pub fn initialize(ctx: &mut Context) -> TimpaniResult<()> {
     ctx.initialize()
 }
 
-/// Run the main loop
-pub fn run(_ctx: &mut Context) -> TimpaniResult<()> {
-    info!("Runtime loop not yet implemented");
-    Ok(())
+/// Run the main loop: poll Timpani-O and apply what it serves until
+/// shutdown is requested (see [`agent`])
+pub fn run(ctx: &mut Context) -> TimpaniResult<()> {
+    let config = &ctx.config;
+    let mut upstream = GrpcUpstream::new(&config.addr, config.port)?;
+    let backend = LinuxBackend::new();
+    let units = systemd_units(&config.resolvers);
+    let caps = ctx.runtime.capabilities.unwrap_or_default();
+    let mut agent = NodeAgent::new(config, caps, &backend, units.as_ref());
+    info!(server = %format!("{}:{}", config.addr, config.port), "connecting to Timpani-O");
+    agent.serve(
+        &mut upstream,
+        config.connect_attempts,
+        Duration::from_millis(config.poll_interval_ms),
+        &ctx.runtime.shutdown_requested,
+    )
+}
+
+/// The system bus if a `SystemdUnit` rule needs it and it can be reached
+fn systemd_units(rules: &[ResolveRule]) -> Box<dyn SystemdUnits> {
+    if !rules
+        .iter()
+        .any(|r| matches!(r, ResolveRule::SystemdUnit { .. }))
+    {
+        return Box::new(NoSystemd);
+    }
+    match SystemdBus::connect() {
+        Ok(bus) => Box::new(bus),
+        Err(e) => {
+            warn!(error = %e, "cannot reach systemd; systemd_unit rules resolve nothing");
+            Box::new(NoSystemd)
+        }
+    }
 }
 
 /// Main application logic, extracted for testability
@@ -57,6 +106,24 @@ pub fn run_app(config: Config) -> TimpaniResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use error::TimpaniError;
+    use std::sync::atomic::Ordering;
+
+    /// `config` pointed at a closed port, giving up after one try.
+    fn unreachable(config: Config) -> Config {
+        Config {
+            port: 1,
+            connect_attempts: 1,
+            ..config
+        }
+    }
+
+    /// A context whose main loop returns at once.
+    fn stopped(config: Config) -> Context {
+        let ctx = Context::new(config);
+        ctx.runtime.shutdown_requested.store(true, Ordering::SeqCst);
+        ctx
+    }
 
     #[test]
     fn test_context_initialization() {
@@ -66,16 +133,15 @@ mod tests {
     }
 
     #[test]
-    fn test_run_with_default_context() {
-        let config = Config::default();
-        let mut ctx = Context::new(config);
+    fn test_run_returns_when_shutdown_requested() {
+        let mut ctx = stopped(Config::default());
         assert!(run(&mut ctx).is_ok());
     }
 
     #[test]
-    fn test_run_app_success() {
-        let config = Config::default();
-        assert!(run_app(config).is_ok());
+    fn test_run_app_fails_without_timpani_o() {
+        let config = unreachable(Config::default());
+        assert_eq!(run_app(config), Err(TimpaniError::Network));
     }
 
     #[test]
@@ -88,7 +154,7 @@ mod tests {
             ..Default::default()
         };
 
-        assert!(run_app(config).is_ok());
+        assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
     }
 
     #[test]
@@ -104,7 +170,7 @@ mod tests {
     #[test]
     fn test_context_lifecycle() {
         let config = Config::default();
-        let mut ctx = Context::new(config);
+        let mut ctx = stopped(config);
 
         // Full lifecycle
         assert!(initialize(&mut ctx).is_ok());
@@ -114,9 +180,10 @@ mod tests {
 
     #[test]
     fn test_run_app_lifecycle() {
-        // Test the full run_app function which includes all lifecycle steps
+        // Test the full run_app function which includes all lifecycle steps;
+        // without a Timpani-O to talk to, every one ends in a network error
         let config = Config::default();
-        assert!(run_app(config).is_ok());
+        assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
 
         // Test with various configurations
         let config = Config {
@@ -124,14 +191,14 @@ mod tests {
             prio: config::test_values::TEST_PRIORITY_LOW,
             ..Default::default()
         };
-        assert!(run_app(config).is_ok());
+        assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
 
         let config = Config {
             enable_sync: true,
             enable_plot: true,
             ..Default::default()
         };
-        assert!(run_app(config).is_ok());
+        assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
     }
 
     #[test]
@@ -207,7 +274,7 @@ mod tests {
         ];
 
         for config in configs {
-            let mut ctx = Context::new(config);
+            let mut ctx = stopped(config);
             assert!(initialize(&mut ctx).is_ok());
             assert!(run(&mut ctx).is_ok());
             ctx.cleanup();
@@ -216,24 +283,25 @@ mod tests {
 
     #[test]
     fn test_error_handling_in_run_app() {
-        // Test run_app with valid configurations
+        // Test run_app with valid configurations: the error is the
+        // unreachable Timpani-O, not the configuration
         let config = Config {
             log_level: config::LogLevel::Debug,
             ..Default::default()
         };
-        assert!(run_app(config).is_ok());
+        assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
 
         let config = Config {
             log_level: config::LogLevel::Silent,
             ..Default::default()
         };
-        assert!(run_app(config).is_ok());
+        assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
 
         let config = Config {
             log_level: config::LogLevel::Verbose,
             ..Default::default()
         };
-        assert!(run_app(config).is_ok());
+        assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
     }
 
     #[test]
@@ -0,0 +1,91 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Free-memory probe for the live memory report sent to Timpani-O.
+//!
+//! Free memory is `MemAvailable` from `/proc/meminfo`, capped by the
+//! headroom of this process's cgroup (`memory.max − memory.current`, cgroup
+//! v2) when a limit is set.  The value goes out as `free_memory_mb` with
+//! every schedule request; Timpani-O admits task memory against it.
+
+use std::fs;
+use std::path::Path;
+
+// =============================================================================
+// CONSTANTS
+// =============================================================================
+
+/// Procfs / cgroupfs paths used by [`free_memory_mb`]
+pub mod paths {
+    pub const PROC_MEMINFO: &str = "/proc/meminfo";
+    /// cgroup v2 unified hierarchy root
+    pub const CGROUP_V2_ROOT: &str = "/sys/fs/cgroup";
+}
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+// =============================================================================
+// PROBE
+// =============================================================================
+
+/// Memory this node can give to new tasks right now, in MB.
+///
+/// `None` if `/proc/meminfo` cannot be read (the report is then omitted and
+/// Timpani-O keeps using the configured maximum).
+pub fn free_memory_mb() -> Option<u64> {
+    let meminfo = fs::read_to_string(paths::PROC_MEMINFO).ok()?;
+    let available_mb = parse_mem_available_kb(&meminfo)? / 1024;
+    let root = Path::new(paths::CGROUP_V2_ROOT);
+    let cgroup_mb = cgroup_headroom_bytes(
+        &fs::read_to_string(root.join("memory.max")).unwrap_or_default(),
+        &fs::read_to_string(root.join("memory.current")).unwrap_or_default(),
+    )
+    .map(|b| b / BYTES_PER_MB);
+    Some(cgroup_mb.map_or(available_mb, |c| c.min(available_mb)))
+}
+
+/// Parse `MemAvailable:` (kB) from `/proc/meminfo`.
+fn parse_mem_available_kb(meminfo: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|l| l.strip_prefix("MemAvailable:"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// `memory.max − memory.current` in bytes.  `None` without a limit (`max`)
+/// or when either file is missing.
+fn cgroup_headroom_bytes(max: &str, current: &str) -> Option<u64> {
+    let max: u64 = max.trim().parse().ok()?;
+    let current: u64 = current.trim().parse().ok()?;
+    Some(max.saturating_sub(current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mem_available() {
+        let meminfo = "MemTotal:       16314328 kB\n\
+                       MemFree:          812344 kB\n\
+                       MemAvailable:    9437184 kB\n";
+        assert_eq!(parse_mem_available_kb(meminfo), Some(9_437_184));
+        assert_eq!(parse_mem_available_kb("MemTotal: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn test_cgroup_headroom() {
+        assert_eq!(
+            cgroup_headroom_bytes("2147483648\n", "536870912\n"),
+            Some(1536 * BYTES_PER_MB)
+        );
+        assert_eq!(cgroup_headroom_bytes("max\n", "536870912\n"), None);
+        assert_eq!(cgroup_headroom_bytes("100\n", "200\n"), Some(0));
+        assert_eq!(cgroup_headroom_bytes("", ""), None);
+    }
+}
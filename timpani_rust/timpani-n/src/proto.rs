@@ -0,0 +1,222 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! The NodeService wire types, and their conversion to and from the node's
+//! own.
+//!
+//! [`node_v1`] is generated from Timpani-O's `node_service.proto` by the
+//! build script.  The rest of the node works on its own types; only
+//! [`crate::upstream`] and [`crate::agent`] see the generated ones:
+//!
+//! | Wire                | Node                                          |
+//! |---------------------|-----------------------------------------------|
+//! | `NodeSchedResponse` | [`SchedulePush`] (into)                       |
+//! | `ScheduledTask`     | [`TaskApply`] (into; no PID, it is resolved)  |
+//! | `ApplyReport`       | [`ApplyReport`](apply::ApplyReport) (from)    |
+//! | `ClockSync`         | [`ClockStatus`] (from)                        |
+//! | `SchedDrift`        | [`Drift`] (from)                              |
+//! | `ForeignLoad`       | [`ForeignLoad`] (from)                        |
+
+use crate::apply::{self, TaskApply};
+use crate::clock::ClockStatus;
+use crate::fault::FaultSink;
+use crate::foreign::ForeignLoad;
+use crate::schedule::SchedulePush;
+use crate::verify::Drift;
+
+pub mod node_v1 {
+    // Package name declared in node_service.proto is `schedinfo.v1`.
+    tonic::include_proto!("schedinfo.v1");
+}
+
+use node_v1::{NodeSchedResponse, ScheduledTask};
+
+impl From<ScheduledTask> for TaskApply {
+    fn from(t: ScheduledTask) -> Self {
+        Self {
+            name: t.name,
+            pid: 0,
+            policy: t.sched_policy,
+            priority: t.sched_priority,
+            cpu_affinity: t.cpu_affinity,
+            metadata: t.metadata.into_iter().collect(),
+            placeholder: t.placeholder,
+            fault_sink: FaultSink::from_wire(t.fault_sink),
+        }
+    }
+}
+
+impl From<NodeSchedResponse> for SchedulePush {
+    fn from(r: NodeSchedResponse) -> Self {
+        Self {
+            instance_epoch: r.instance_epoch,
+            generation: r.generation,
+            full: r.full || r.resync,
+            tasks: r.tasks.into_iter().map(TaskApply::from).collect(),
+            modified_tasks: r.modified_tasks.into_iter().map(TaskApply::from).collect(),
+            removed_tasks: r.removed_tasks,
+        }
+    }
+}
+
+impl From<&apply::ApplyReport> for node_v1::ApplyReport {
+    fn from(r: &apply::ApplyReport) -> Self {
+        Self {
+            node_id: r.node_id.clone(),
+            generation: r.generation,
+            tasks: r
+                .tasks
+                .iter()
+                .map(|t| node_v1::TaskApplyResult {
+                    task_name: t.task_name.clone(),
+                    status: t.status.wire_value(),
+                    errno: t.errno,
+                    detail: t.detail.clone(),
+                    pid: t.pid,
+                    unit_state: t.unit_state.clone(),
+                    fault_sink: t.fault_sink.wire_value(),
+                })
+                .collect(),
+            node: Some(node_v1::NodeApplyInfo {
+                cap_sys_nice: r.node.cap_sys_nice,
+                rtprio_limit: r.node.rtprio_limit,
+                cpuset_writable: r.node.cpuset_writable,
+                isolated_cpus: r.node.isolated_cpus.clone(),
+                rt_runtime_us: r.node.rt_runtime_us,
+                rt_period_us: r.node.rt_period_us,
+            }),
+            push_status: r.push_status.wire_value(),
+        }
+    }
+}
+
+impl From<ClockStatus> for node_v1::ClockSync {
+    fn from(c: ClockStatus) -> Self {
+        Self {
+            synchronized: c.synchronized,
+            offset_ns: c.offset_ns,
+            source: c.source.as_str().to_string(),
+        }
+    }
+}
+
+impl From<&Drift> for node_v1::SchedDrift {
+    fn from(d: &Drift) -> Self {
+        Self {
+            expected_policy: d.expected.policy,
+            expected_priority: d.expected.priority,
+            expected_cpu_affinity: d.expected.cpu_affinity,
+            observed_policy: d.observed.policy,
+            observed_priority: d.observed.priority,
+            observed_cpu_affinity: d.observed.cpu_affinity,
+            reapplied: d.reapplied,
+            restored: d.restored,
+        }
+    }
+}
+
+impl From<&ForeignLoad> for node_v1::ForeignLoad {
+    fn from(l: &ForeignLoad) -> Self {
+        Self {
+            cpu: l.cpu,
+            utilization: l.utilization,
+            threads: l.threads.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply::{ApplyStatus, NodeApplyInfo, TaskApplyResult};
+    use crate::schedule::PushStatus;
+
+    fn scheduled(name: &str) -> ScheduledTask {
+        ScheduledTask {
+            name: name.into(),
+            sched_priority: 60,
+            sched_policy: apply::policy::SCHED_FIFO,
+            cpu_affinity: 0b10,
+            metadata: [("container_id".to_string(), "abc".to_string())].into(),
+            fault_sink: FaultSink::Both.wire_value(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_response_becomes_a_push() {
+        let push = SchedulePush::from(NodeSchedResponse {
+            instance_epoch: 7,
+            generation: 3,
+            full: false,
+            tasks: vec![scheduled("cam")],
+            modified_tasks: vec![scheduled("lidar")],
+            removed_tasks: vec!["radar".into()],
+            ..Default::default()
+        });
+
+        assert_eq!((push.instance_epoch, push.generation), (7, 3));
+        assert!(!push.full);
+        assert_eq!(push.removed_tasks, ["radar"]);
+        let cam = &push.tasks[0];
+        assert_eq!(cam.name, "cam");
+        assert_eq!((cam.policy, cam.priority), (apply::policy::SCHED_FIFO, 60));
+        assert_eq!(cam.cpu_affinity, 0b10);
+        assert_eq!(cam.metadata["container_id"], "abc");
+        assert_eq!(cam.fault_sink, FaultSink::Both);
+        assert_eq!(cam.pid, 0);
+        assert_eq!(push.modified_tasks[0].name, "lidar");
+    }
+
+    #[test]
+    fn test_resync_is_taken_as_a_full_push() {
+        let push = SchedulePush::from(NodeSchedResponse {
+            generation: 1,
+            resync: true,
+            ..Default::default()
+        });
+        assert!(push.full);
+    }
+
+    #[test]
+    fn test_report_keeps_every_field() {
+        let report = apply::ApplyReport {
+            node_id: "n1".into(),
+            generation: 4,
+            tasks: vec![TaskApplyResult {
+                task_name: "cam".into(),
+                status: ApplyStatus::PermissionDenied,
+                errno: 1,
+                detail: "sched_setattr failed".into(),
+                pid: 42,
+                unit_state: "active".into(),
+                fault_sink: FaultSink::LocalOnly,
+            }],
+            node: NodeApplyInfo {
+                cap_sys_nice: true,
+                rtprio_limit: 99,
+                cpuset_writable: false,
+                isolated_cpus: vec![2, 3],
+                rt_runtime_us: -1,
+                rt_period_us: 1_000_000,
+            },
+            push_status: PushStatus::Applied,
+        };
+
+        let wire = node_v1::ApplyReport::from(&report);
+
+        assert_eq!((wire.node_id.as_str(), wire.generation), ("n1", 4));
+        let t = &wire.tasks[0];
+        assert_eq!(t.status, node_v1::ApplyStatus::PermissionDenied as i32);
+        assert_eq!((t.errno, t.pid), (1, 42));
+        assert_eq!(t.unit_state, "active");
+        assert_eq!(t.fault_sink, FaultSink::LocalOnly.wire_value());
+        let node = wire.node.unwrap();
+        assert!(node.cap_sys_nice && !node.cpuset_writable);
+        assert_eq!(node.isolated_cpus, [2, 3]);
+        assert_eq!((node.rt_runtime_us, node.rt_period_us), (-1, 1_000_000));
+        assert_eq!(wire.push_status, node_v1::PushStatus::Applied as i32);
+    }
+}
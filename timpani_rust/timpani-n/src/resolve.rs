@@ -0,0 +1,560 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Finding the local process behind a scheduled task.
+//!
+//! A `ScheduledTask` names a task and carries the opaque metadata Piccolo
+//! attached to it.  The node tries its [`ResolveRule`]s in order and the
+//! first one that matches exactly one process wins:
+//!
+//! | Rule          | Matches a process whose                                        |
+//! |---------------|----------------------------------------------------------------|
+//! | `ByLabel`     | container label `label` equals the task's `metadata_key` value |
+//! | `ByName`      | `comm` equals the task name                                    |
+//! | `SystemdUnit` | PID is the `MainPID` of the active unit named by `template`    |
+//!
+//! A rule that matches several processes is ambiguous and resolves nothing,
+//! so the next rule is tried.  A `SystemdUnit` rule asks systemd through
+//! [`SystemdUnits`] ([`SystemdBus`]: the manager API on the system bus)
+//! rather than the process list, and resolves nothing
+//! unless the unit is `active`; the unit's state is kept in the
+//! [`Resolution`] either way.  With no match the task reports
+//! `PidNotFound` (see [`crate::apply`]).
+//!
+//! On the command line a rule is written `name`, `label:<label>=<key>` or
+//! `systemd_unit:<template>`, where `{task}` in the template stands for the
+//! task name (`systemd_unit:{task}.service`).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::str::FromStr;
+
+use tracing::debug;
+use zbus::blocking::{proxy::Builder, Connection, Proxy};
+use zbus::proxy::CacheProperties;
+use zbus::zvariant::OwnedObjectPath;
+
+// =============================================================================
+// CONSTANTS
+// =============================================================================
+
+/// Procfs path used by [`read_processes`]
+pub mod paths {
+    pub const PROC: &str = "/proc";
+}
+
+/// Placeholder for the task name in a `SystemdUnit` template.
+pub const TASK_PLACEHOLDER: &str = "{task}";
+
+/// [`Resolution::unit_state`] of a unit systemd does not know
+/// (its `LoadState`).
+pub const UNIT_NOT_FOUND: &str = "not-found";
+
+/// systemd's bus name and manager object.
+const SYSTEMD_SERVICE: &str = "org.freedesktop.systemd1";
+const SYSTEMD_MANAGER_PATH: &str = "/org/freedesktop/systemd1";
+const SYSTEMD_MANAGER: &str = "org.freedesktop.systemd1.Manager";
+
+/// Interfaces of a unit object: every unit has `Unit`; only services have
+/// `MainPID` (on `Service`).
+const SYSTEMD_UNIT: &str = "org.freedesktop.systemd1.Unit";
+const SYSTEMD_SERVICE_UNIT: &str = "org.freedesktop.systemd1.Service";
+
+// =============================================================================
+// TYPES
+// =============================================================================
+
+/// One candidate process, as listed by the node.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProcessInfo {
+    pub pid: i32,
+    /// `/proc/<pid>/comm`
+    pub comm: String,
+    /// Labels of the container the process runs in; empty outside one.
+    pub labels: BTreeMap<String, String>,
+}
+
+/// How a task is matched to a process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveRule {
+    /// The process `comm` equals the task name.
+    ByName,
+    /// The container label `label` equals the task's `metadata_key` value.
+    ByLabel { label: String, metadata_key: String },
+    /// The `MainPID` of the active systemd unit `template` names.
+    SystemdUnit { template: String },
+}
+
+impl ResolveRule {
+    fn matches(
+        &self,
+        task_name: &str,
+        metadata: &BTreeMap<String, String>,
+        process: &ProcessInfo,
+    ) -> bool {
+        match self {
+            ResolveRule::ByName => process.comm == task_name,
+            ResolveRule::ByLabel {
+                label,
+                metadata_key,
+            } => match (metadata.get(metadata_key), process.labels.get(label)) {
+                (Some(want), Some(have)) => want == have,
+                _ => false,
+            },
+            ResolveRule::SystemdUnit { .. } => false,
+        }
+    }
+}
+
+impl FromStr for ResolveRule {
+    type Err = String;
+
+    /// Parse the command-line form (see the module docs).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, arg) = s.split_once(':').unwrap_or((s, ""));
+        match (kind, arg) {
+            ("name", "") => Ok(ResolveRule::ByName),
+            ("label", arg) => match arg.split_once('=') {
+                Some((label, key)) if !label.is_empty() && !key.is_empty() => {
+                    Ok(ResolveRule::ByLabel {
+                        label: label.to_string(),
+                        metadata_key: key.to_string(),
+                    })
+                }
+                _ => Err(format!("expected label:<label>=<key>, got '{s}'")),
+            },
+            ("systemd_unit", template) if !template.is_empty() => Ok(ResolveRule::SystemdUnit {
+                template: template.to_string(),
+            }),
+            _ => Err(format!(
+                "unknown resolver '{s}' (expected name, label:<label>=<key> \
+                 or systemd_unit:<template>)"
+            )),
+        }
+    }
+}
+
+/// State of one systemd unit, as far as resolution cares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitStatus {
+    /// `ActiveState`: `active`, `inactive`, `failed`, `activating`, ...
+    pub active_state: String,
+    /// `MainPID`; 0 while the unit has no main process.
+    pub main_pid: u32,
+}
+
+impl UnitStatus {
+    /// The main process, if the unit is active and has one.
+    fn pid(&self) -> Option<i32> {
+        if self.active_state != "active" || self.main_pid == 0 {
+            return None;
+        }
+        i32::try_from(self.main_pid).ok()
+    }
+}
+
+/// The part of systemd's manager API (`org.freedesktop.systemd1`) the
+/// `SystemdUnit` rule uses.  Mocked in tests.
+pub trait SystemdUnits {
+    /// State of `unit`, or `None` if systemd has no such unit.
+    fn unit(&self, unit: &str) -> Option<UnitStatus>;
+}
+
+/// Queries units on the system bus, one method call and a few property
+/// reads per unit.
+pub struct SystemdBus {
+    connection: Connection,
+}
+
+impl SystemdBus {
+    /// Connect to the system bus.
+    pub fn connect() -> zbus::Result<Self> {
+        Ok(Self::with_connection(Connection::system()?))
+    }
+
+    /// Ask whatever answers on `connection` instead.
+    pub fn with_connection(connection: Connection) -> Self {
+        Self { connection }
+    }
+
+    /// An uncached proxy for `interface` on systemd's object `path`.
+    fn proxy<'p>(&self, path: &'p str, interface: &'p str) -> zbus::Result<Proxy<'p>> {
+        Builder::new(&self.connection)
+            .destination(SYSTEMD_SERVICE)?
+            .path(path)?
+            .interface(interface)?
+            .cache_properties(CacheProperties::No)
+            .build()
+    }
+
+    fn query(&self, unit: &str) -> zbus::Result<Option<UnitStatus>> {
+        // `LoadUnit`, unlike `GetUnit`, also answers for units that are not
+        // loaded, with `LoadState` saying why.
+        let path: OwnedObjectPath = self
+            .proxy(SYSTEMD_MANAGER_PATH, SYSTEMD_MANAGER)?
+            .call("LoadUnit", &(unit,))?;
+        let object = self.proxy(path.as_str(), SYSTEMD_UNIT)?;
+        let load_state: String = object.get_property("LoadState")?;
+        let active_state: String = object.get_property("ActiveState")?;
+        // Not a service (a target, a mount): no main process.
+        let main_pid = self
+            .proxy(path.as_str(), SYSTEMD_SERVICE_UNIT)?
+            .get_property("MainPID")
+            .unwrap_or(0);
+        Ok(unit_status(&load_state, active_state, main_pid))
+    }
+}
+
+impl SystemdUnits for SystemdBus {
+    fn unit(&self, unit: &str) -> Option<UnitStatus> {
+        self.query(unit)
+            .inspect_err(|e| debug!(unit, error = %e, "systemd unit query failed"))
+            .ok()
+            .flatten()
+    }
+}
+
+/// Unit source for a node without a system bus: knows no unit.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoSystemd;
+
+impl SystemdUnits for NoSystemd {
+    fn unit(&self, _unit: &str) -> Option<UnitStatus> {
+        None
+    }
+}
+
+/// The status of a unit with `load_state`; `None` for a unit that is not
+/// loaded because it does not exist.
+fn unit_status(load_state: &str, active_state: String, main_pid: u32) -> Option<UnitStatus> {
+    if load_state == UNIT_NOT_FOUND {
+        return None;
+    }
+    Some(UnitStatus {
+        active_state,
+        main_pid,
+    })
+}
+
+/// Every process under `/proc`, with its `comm`.  Container labels are not
+/// read from `/proc`, so `labels` is empty and `ByLabel` rules only match
+/// processes listed by a caller that knows them.
+pub fn read_processes() -> Vec<ProcessInfo> {
+    let Ok(entries) = fs::read_dir(paths::PROC) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|e| e.file_name().to_str()?.parse::<i32>().ok())
+        .filter_map(|pid| {
+            let comm = fs::read_to_string(format!("{}/{pid}/comm", paths::PROC)).ok()?;
+            Some(ProcessInfo {
+                pid,
+                comm: comm.trim_end().to_string(),
+                labels: BTreeMap::new(),
+            })
+        })
+        .collect()
+}
+
+/// Where [`resolve`] found a task's process, or why it did not.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Resolution {
+    pub pid: Option<i32>,
+    /// The unit the last `SystemdUnit` rule tried asked about; empty if no
+    /// such rule ran.
+    pub unit: String,
+    /// That unit's `ActiveState`, or [`UNIT_NOT_FOUND`].
+    pub unit_state: String,
+}
+
+// =============================================================================
+// RESOLUTION
+// =============================================================================
+
+/// The process behind `task_name` (see the module docs).
+pub fn resolve(
+    task_name: &str,
+    metadata: &BTreeMap<String, String>,
+    rules: &[ResolveRule],
+    processes: &[ProcessInfo],
+    units: &dyn SystemdUnits,
+) -> Resolution {
+    let mut resolution = Resolution::default();
+    for rule in rules {
+        if let ResolveRule::SystemdUnit { template } = rule {
+            resolution.unit = template.replace(TASK_PLACEHOLDER, task_name);
+            let status = units.unit(&resolution.unit);
+            resolution.unit_state = status
+                .as_ref()
+                .map_or(UNIT_NOT_FOUND, |s| &s.active_state)
+                .to_string();
+            if let Some(pid) = status.as_ref().and_then(UnitStatus::pid) {
+                resolution.pid = Some(pid);
+                return resolution;
+            }
+            debug!(task = task_name, unit = %resolution.unit, state = %resolution.unit_state, "unit has no main process");
+            continue;
+        }
+        let mut found = processes
+            .iter()
+            .filter(|p| rule.matches(task_name, metadata, p));
+        match (found.next(), found.next()) {
+            (Some(p), None) => {
+                resolution.pid = Some(p.pid);
+                return resolution;
+            }
+            (Some(_), Some(_)) => debug!(task = task_name, ?rule, "ambiguous match"),
+            _ => {}
+        }
+    }
+    resolution
+}
+
+/// [`resolve`] with fixed rules, process list and unit source, for
+/// resolving tasks again at apply time (see [`crate::apply::Applier`]).
+/// Units are queried afresh on every call; the process list is the one
+/// given.
+pub struct Resolver<'a> {
+    pub rules: &'a [ResolveRule],
+    pub processes: &'a [ProcessInfo],
+    pub units: &'a dyn SystemdUnits,
+}
+
+impl Resolver<'_> {
+    pub fn resolve(&self, task_name: &str, metadata: &BTreeMap<String, String>) -> Resolution {
+        resolve(task_name, metadata, self.rules, self.processes, self.units)
+    }
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use std::collections::BTreeMap;
+
+    use super::{SystemdUnits, UnitStatus};
+
+    /// Fixed unit states; unknown units are missing.
+    #[derive(Default)]
+    pub struct MockUnits(pub BTreeMap<String, UnitStatus>);
+
+    impl MockUnits {
+        pub fn with(mut self, unit: &str, state: &str, main_pid: u32) -> Self {
+            let status = UnitStatus {
+                active_state: state.to_string(),
+                main_pid,
+            };
+            self.0.insert(unit.to_string(), status);
+            self
+        }
+    }
+
+    impl SystemdUnits for MockUnits {
+        fn unit(&self, unit: &str) -> Option<UnitStatus> {
+            self.0.get(unit).cloned()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::MockUnits;
+    use super::*;
+
+    fn process(pid: i32, comm: &str, labels: &[(&str, &str)]) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            comm: comm.to_string(),
+            labels: labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    fn by_image() -> ResolveRule {
+        ResolveRule::ByLabel {
+            label: "image".into(),
+            metadata_key: "container_image".into(),
+        }
+    }
+
+    #[test]
+    fn test_label_rule_uses_task_metadata() {
+        let processes = [
+            process(10, "worker", &[("image", "cam:1")]),
+            process(11, "worker", &[("image", "lidar:2")]),
+        ];
+        let metadata = [("container_image".to_string(), "lidar:2".to_string())].into();
+        let rules = [by_image(), ResolveRule::ByName];
+        let units = MockUnits::default();
+        let found = resolve("worker", &metadata, &rules, &processes, &units);
+        assert_eq!(found.pid, Some(11));
+    }
+
+    #[test]
+    fn test_ambiguous_or_missing_falls_through_to_next_rule() {
+        let processes = [
+            process(10, "cam", &[("image", "img")]),
+            process(11, "other", &[("image", "img")]),
+        ];
+        let metadata = [("container_image".to_string(), "img".to_string())].into();
+        let rules = [by_image(), ResolveRule::ByName];
+        let units = MockUnits::default();
+        let pid = |name, metadata: &BTreeMap<String, String>| {
+            resolve(name, metadata, &rules, &processes, &units).pid
+        };
+        assert_eq!(pid("cam", &metadata), Some(10));
+        assert_eq!(pid("cam", &BTreeMap::new()), Some(10));
+        assert_eq!(pid("gone", &metadata), None);
+    }
+
+    #[test]
+    fn test_systemd_unit_rule_reports_the_unit_state() {
+        let units = MockUnits::default().with("cam.service", "active", 42).with(
+            "lidar.service",
+            "inactive",
+            0,
+        );
+        let processes = [process(10, "lidar", &[])];
+        let rules = [
+            "systemd_unit:{task}.service".parse().unwrap(),
+            ResolveRule::ByName,
+        ];
+        let metadata = BTreeMap::new();
+        let resolve = |name| resolve(name, &metadata, &rules, &processes, &units);
+
+        let active = resolve("cam");
+        assert_eq!(active.pid, Some(42));
+        assert_eq!(
+            (active.unit.as_str(), active.unit_state.as_str()),
+            ("cam.service", "active")
+        );
+
+        // Inactive: no MainPID, but a process of the same name still matches.
+        let inactive = resolve("lidar");
+        assert_eq!(inactive.pid, Some(10));
+        assert_eq!(inactive.unit_state, "inactive");
+
+        let missing = resolve("radar");
+        assert_eq!(missing.pid, None);
+        assert_eq!(missing.unit, "radar.service");
+        assert_eq!(missing.unit_state, UNIT_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_rule_syntax_and_unit_load_state() {
+        assert_eq!("name".parse(), Ok(ResolveRule::ByName));
+        assert_eq!("label:image=container_image".parse(), Ok(by_image()));
+        assert!("label:image".parse::<ResolveRule>().is_err());
+        assert!("systemd_unit:".parse::<ResolveRule>().is_err());
+        assert!("comm".parse::<ResolveRule>().is_err());
+
+        let active = unit_status("loaded", "active".into(), 42);
+        assert_eq!(active.and_then(|u| u.pid()), Some(42));
+        assert_eq!(unit_status(UNIT_NOT_FOUND, "inactive".into(), 0), None);
+    }
+
+    // ── SystemdBus against a fake systemd ─────────────────────────────────────
+
+    use zbus::zvariant::ObjectPath;
+
+    /// `org.freedesktop.systemd1.Manager`: units under `/unit/<name>`.
+    struct FakeManager;
+
+    #[zbus::interface(name = "org.freedesktop.systemd1.Manager")]
+    impl FakeManager {
+        fn load_unit(&self, name: &str) -> OwnedObjectPath {
+            let path = format!("/unit/{}", name.replace(['.', '-'], "_"));
+            ObjectPath::try_from(path).unwrap().into()
+        }
+    }
+
+    struct FakeUnit {
+        load_state: &'static str,
+        active_state: &'static str,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.systemd1.Unit")]
+    impl FakeUnit {
+        #[zbus(property)]
+        fn load_state(&self) -> String {
+            self.load_state.to_string()
+        }
+
+        #[zbus(property)]
+        fn active_state(&self) -> String {
+            self.active_state.to_string()
+        }
+    }
+
+    struct FakeService {
+        main_pid: u32,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.systemd1.Service")]
+    impl FakeService {
+        #[zbus(property, name = "MainPID")]
+        fn main_pid(&self) -> u32 {
+            self.main_pid
+        }
+    }
+
+    /// A [`SystemdBus`] connected peer-to-peer to a fake systemd (the
+    /// other connection) with an active `cam.service`, a `boot.target` and
+    /// no other units.
+    fn fake_systemd() -> (SystemdBus, Connection) {
+        use zbus::blocking::connection::Builder;
+
+        let (server, client) = std::os::unix::net::UnixStream::pair().unwrap();
+        let unit = |load_state, active_state| FakeUnit {
+            load_state,
+            active_state,
+        };
+        let server = std::thread::spawn(move || {
+            Builder::async_io_unix_stream(server)
+                .server(zbus::Guid::generate())
+                .unwrap()
+                .p2p()
+                .serve_at(SYSTEMD_MANAGER_PATH, FakeManager)
+                .unwrap()
+                .serve_at("/unit/cam_service", unit("loaded", "active"))
+                .unwrap()
+                .serve_at("/unit/cam_service", FakeService { main_pid: 42 })
+                .unwrap()
+                .serve_at("/unit/boot_target", unit("loaded", "active"))
+                .unwrap()
+                .serve_at("/unit/radar_service", unit(UNIT_NOT_FOUND, "inactive"))
+                .unwrap()
+                .build()
+                .unwrap()
+        });
+        let client = Builder::async_io_unix_stream(client).p2p().build().unwrap();
+        (SystemdBus::with_connection(client), server.join().unwrap())
+    }
+
+    #[test]
+    fn test_systemd_bus_reads_the_unit_properties() {
+        let (bus, _systemd) = fake_systemd();
+        assert_eq!(
+            bus.unit("cam.service"),
+            Some(UnitStatus {
+                active_state: "active".into(),
+                main_pid: 42,
+            })
+        );
+        // A target has no Service interface, so no main process.
+        assert_eq!(bus.unit("boot.target").map(|u| u.main_pid), Some(0));
+        assert_eq!(bus.unit("radar.service"), None);
+    }
+
+    #[test]
+    fn test_read_processes_lists_this_process() {
+        let me = std::process::id() as i32;
+        let processes = read_processes();
+        let own = processes.iter().find(|p| p.pid == me).unwrap();
+        assert!(!own.comm.is_empty());
+        assert!(!own.comm.ends_with('\n'));
+    }
+}
@@ -0,0 +1,321 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! The node's copy of its schedule, and the order pushes are taken in.
+//!
+//! Timpani-O numbers every schedule it serves with a generation, but a
+//! retried push can land after a newer one (generation 5 again after 6).
+//! Generations restart at 1 when Timpani-O does, so each push also carries
+//! the instance epoch of the Timpani-O that numbered it (its start time, so
+//! a restarted instance has a larger one).  The [`ScheduleStore`] therefore
+//! only ever moves forward, by epoch first:
+//!
+//! | Push                            | [`PushStatus`] | Stored schedule                      |
+//! |---------------------------------|----------------|--------------------------------------|
+//! | from a newer instance           | `Applied`      | replaced ([`ScheduleStore::resync`]) |
+//! | from an older instance          | `Stale`        | unchanged                            |
+//! | same instance, generation above | `Applied`      | replaced (full) or patched (delta)   |
+//! | same instance, generation equal | `Duplicate`    | unchanged                            |
+//! | same instance, generation below | `Stale`        | unchanged                            |
+//!
+//! The status goes upstream as `ApplyReport.push_status`.  Only an
+//! `Applied` push is applied and reported task by task; the others are
+//! acknowledged with [`ApplyReport::ack`](crate::apply::ApplyReport::ack),
+//! which Timpani-O takes as success for the old push.
+//!
+//! A push from a newer instance is a resync: it takes the generation it is
+//! given as the new baseline, lower or not.  The node sends
+//! [`ScheduleStore::instance_epoch`] and [`ScheduleStore::generation`] back
+//! as `known_instance_epoch` and `known_generation`, and the stored task
+//! names as `known_tasks`.
+
+use tracing::{debug, info};
+
+use crate::apply::TaskApply;
+
+// =============================================================================
+// PUSH
+// =============================================================================
+
+/// What the store did with a push; mirrors the `PushStatus` proto enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PushStatus {
+    /// A newer generation; the stored schedule is now this one.
+    #[default]
+    Applied,
+    /// The generation already stored; nothing changed.
+    Duplicate,
+    /// Older than the generation stored; nothing changed.
+    Stale,
+}
+
+impl PushStatus {
+    /// The proto enum number.
+    pub fn wire_value(self) -> i32 {
+        match self {
+            PushStatus::Applied => 0,
+            PushStatus::Duplicate => 1,
+            PushStatus::Stale => 2,
+        }
+    }
+}
+
+/// One schedule delivery, as in `NodeSchedResponse`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SchedulePush {
+    /// Timpani-O instance that numbered `generation`; 0 = not sent.
+    pub instance_epoch: u64,
+    pub generation: u64,
+    /// `true` = `tasks` is the whole schedule; `false` = a delta against
+    /// the stored one.
+    pub full: bool,
+    /// Full: every task.  Delta: the tasks added.
+    pub tasks: Vec<TaskApply>,
+    /// Delta only: tasks whose parameters changed.
+    pub modified_tasks: Vec<TaskApply>,
+    /// Delta only: tasks to stop.
+    pub removed_tasks: Vec<String>,
+}
+
+// =============================================================================
+// STORE
+// =============================================================================
+
+/// The generation the node runs and its tasks (see the module docs).
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleStore {
+    /// 0 = nothing delivered yet, or from a Timpani-O without epochs.
+    instance_epoch: u64,
+    /// 0 = nothing delivered yet; Timpani-O starts at 1.
+    generation: u64,
+    tasks: Vec<TaskApply>,
+}
+
+impl ScheduleStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Instance epoch of the stored schedule; 0 before the first push.
+    pub fn instance_epoch(&self) -> u64 {
+        self.instance_epoch
+    }
+
+    /// Generation of the stored schedule; 0 before the first push.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The stored tasks, in delivery order.
+    pub fn tasks(&self) -> &[TaskApply] {
+        &self.tasks
+    }
+
+    /// Take `push` if it is newer than the stored schedule.  A push from a
+    /// newer Timpani-O instance goes through [`resync`](Self::resync).
+    pub fn push(&mut self, push: SchedulePush) -> PushStatus {
+        if push.instance_epoch > self.instance_epoch {
+            self.resync(push);
+            return PushStatus::Applied;
+        }
+        if push.instance_epoch < self.instance_epoch {
+            info!(
+                instance_epoch = push.instance_epoch,
+                stored = self.instance_epoch,
+                "schedule push from a replaced Timpani-O ignored"
+            );
+            return PushStatus::Stale;
+        }
+        if push.generation < self.generation {
+            info!(
+                generation = push.generation,
+                stored = self.generation,
+                "stale schedule push ignored"
+            );
+            return PushStatus::Stale;
+        }
+        if push.generation == self.generation {
+            debug!(generation = push.generation, "schedule push repeated");
+            return PushStatus::Duplicate;
+        }
+        self.take(push);
+        PushStatus::Applied
+    }
+
+    /// Replace the stored schedule with `push`, whatever its generation.
+    /// A delta is taken as the whole schedule.
+    pub fn resync(&mut self, push: SchedulePush) {
+        info!(
+            instance_epoch = push.instance_epoch,
+            generation = push.generation,
+            stored = self.generation,
+            "schedule resynchronised"
+        );
+        self.take(SchedulePush { full: true, ..push });
+    }
+
+    fn take(&mut self, push: SchedulePush) {
+        self.instance_epoch = push.instance_epoch;
+        self.generation = push.generation;
+        if push.full {
+            self.tasks = push.tasks;
+            return;
+        }
+        self.tasks.retain(|t| !push.removed_tasks.contains(&t.name));
+        for task in push.modified_tasks {
+            match self.tasks.iter_mut().find(|t| t.name == task.name) {
+                Some(stored) => *stored = task,
+                None => self.tasks.push(task),
+            }
+        }
+        self.tasks.extend(push.tasks);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply::ApplyReport;
+    use crate::fault::FaultSink;
+
+    fn task(name: &str, priority: i32) -> TaskApply {
+        TaskApply {
+            name: name.into(),
+            pid: 100,
+            policy: crate::apply::policy::SCHED_FIFO,
+            priority,
+            cpu_affinity: 0b1,
+            metadata: Default::default(),
+            placeholder: false,
+            fault_sink: FaultSink::Upstream,
+        }
+    }
+
+    fn full(generation: u64, tasks: &[(&str, i32)]) -> SchedulePush {
+        SchedulePush {
+            generation,
+            full: true,
+            tasks: tasks.iter().map(|&(n, p)| task(n, p)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn stored(store: &ScheduleStore) -> Vec<(&str, i32)> {
+        store
+            .tasks()
+            .iter()
+            .map(|t| (t.name.as_str(), t.priority))
+            .collect()
+    }
+
+    #[test]
+    fn test_out_of_order_pushes_keep_the_highest_generation() {
+        let mut store = ScheduleStore::new();
+        let g5 = full(5, &[("cam", 50)]);
+        let g6 = full(6, &[("cam", 60), ("lidar", 40)]);
+
+        let statuses = [
+            store.push(g5.clone()),
+            store.push(g6.clone()),
+            // The retry of 5 lands after 6, then 6 is pushed again.
+            store.push(g5),
+            store.push(g6),
+        ];
+
+        assert_eq!(
+            statuses,
+            [
+                PushStatus::Applied,
+                PushStatus::Applied,
+                PushStatus::Stale,
+                PushStatus::Duplicate,
+            ]
+        );
+        assert_eq!(store.generation(), 6);
+        assert_eq!(stored(&store), [("cam", 60), ("lidar", 40)]);
+    }
+
+    #[test]
+    fn test_delta_patches_the_stored_schedule() {
+        let mut store = ScheduleStore::new();
+        store.push(full(1, &[("cam", 50), ("log", 10), ("old", 5)]));
+        let delta = SchedulePush {
+            generation: 2,
+            full: false,
+            tasks: vec![task("lidar", 40)],
+            modified_tasks: vec![task("cam", 70)],
+            removed_tasks: vec!["old".into()],
+            ..Default::default()
+        };
+
+        assert_eq!(store.push(delta.clone()), PushStatus::Applied);
+        assert_eq!(stored(&store), [("cam", 70), ("log", 10), ("lidar", 40)]);
+        // Applying the same delta twice would add lidar twice.
+        assert_eq!(store.push(delta), PushStatus::Duplicate);
+        assert_eq!(stored(&store).len(), 3);
+    }
+
+    #[test]
+    fn test_resync_resets_the_baseline() {
+        let mut store = ScheduleStore::new();
+        store.push(full(6, &[("cam", 60)]));
+
+        // Timpani-O restarted and counts from 1 again.
+        store.resync(SchedulePush {
+            generation: 1,
+            full: false,
+            tasks: vec![task("log", 10)],
+            ..Default::default()
+        });
+        assert_eq!(store.generation(), 1);
+        assert_eq!(stored(&store), [("log", 10)]);
+        assert_eq!(store.push(full(2, &[("cam", 20)])), PushStatus::Applied);
+    }
+
+    #[test]
+    fn test_restarted_timpani_o_resyncs_even_at_the_stored_generation() {
+        let before = |generation, tasks| SchedulePush {
+            instance_epoch: 100,
+            ..full(generation, tasks)
+        };
+        let after = |generation, tasks| SchedulePush {
+            instance_epoch: 200,
+            ..full(generation, tasks)
+        };
+        let mut store = ScheduleStore::new();
+        store.push(before(1, &[("cam", 50)]));
+        store.push(before(2, &[("cam", 60), ("lidar", 40)]));
+
+        let statuses = [
+            // The restarted instance is at generation 2 as well.
+            store.push(after(2, &[("cam", 70)])),
+            // A late push of the replaced instance.
+            store.push(before(3, &[("old", 10)])),
+            store.push(after(2, &[("cam", 70)])),
+            store.push(after(3, &[("cam", 80)])),
+        ];
+
+        assert_eq!(
+            statuses,
+            [
+                PushStatus::Applied,
+                PushStatus::Stale,
+                PushStatus::Duplicate,
+                PushStatus::Applied,
+            ]
+        );
+        assert_eq!((store.instance_epoch(), store.generation()), (200, 3));
+        assert_eq!(stored(&store), [("cam", 80)]);
+    }
+
+    #[test]
+    fn test_a_push_not_taken_is_acked_without_tasks() {
+        let ack = ApplyReport::ack("n1", 5, PushStatus::Stale);
+        assert_eq!(ack.generation, 5);
+        assert_eq!(ack.push_status.wire_value(), 2);
+        assert!(ack.tasks.is_empty());
+        assert_eq!(ApplyReport::default().push_status, PushStatus::Applied);
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! The node's calls to Timpani-O's NodeService.
+//!
+//! [`Upstream`] is what the [`NodeAgent`](crate::agent::NodeAgent) needs
+//! from Timpani-O, one method per RPC.  [`GrpcUpstream`] is the production
+//! client: the node's main loop is synchronous, so it owns a
+//! current-thread Tokio runtime and blocks on each call.  The channel
+//! connects lazily and reconnects on its own: an unreachable Timpani-O
+//! fails the call, which the caller retries at its next cycle.
+//!
+//! Errors are logged here with the gRPC status and returned as
+//! [`TimpaniError::Network`].
+
+use std::time::Duration;
+
+use tokio::runtime::{Builder, Runtime};
+use tonic::transport::{Channel, Endpoint};
+use tonic::Code;
+use tracing::{debug, warn};
+
+use crate::error::{TimpaniError, TimpaniResult};
+use crate::proto::node_v1::{
+    node_service_client::NodeServiceClient, ApplyReport, DeadlineMissInfo, NodeResponse,
+    NodeSchedRequest, NodeSchedResponse,
+};
+
+/// Time allowed to establish the connection.
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Time allowed for one call.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The NodeService calls the node makes.  Mocked in tests.
+pub trait Upstream {
+    /// `GetSchedInfo`.  `None` while Timpani-O has no workload (`NOT_FOUND`).
+    fn get_sched_info(
+        &mut self,
+        request: NodeSchedRequest,
+    ) -> TimpaniResult<Option<NodeSchedResponse>>;
+
+    /// `ReportApply`.
+    fn report_apply(&mut self, report: ApplyReport) -> TimpaniResult<()>;
+
+    /// `ReportDMiss`.
+    fn report_dmiss(&mut self, info: DeadlineMissInfo) -> TimpaniResult<()>;
+}
+
+/// [`Upstream`] over gRPC (see the module docs).
+pub struct GrpcUpstream {
+    runtime: Runtime,
+    client: NodeServiceClient<Channel>,
+}
+
+impl GrpcUpstream {
+    /// A client for the Timpani-O at `addr:port`.  Nothing is sent until
+    /// the first call.
+    pub fn new(addr: &str, port: u16) -> TimpaniResult<Self> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                warn!(error = %e, "cannot start the gRPC runtime");
+                TimpaniError::Network
+            })?;
+        let endpoint = Endpoint::from_shared(format!("http://{addr}:{port}"))
+            .map_err(|e| {
+                warn!(addr, port, error = %e, "invalid Timpani-O address");
+                TimpaniError::Config
+            })?
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT);
+        // The lazy channel spawns its worker on the current runtime.
+        let channel = {
+            let _guard = runtime.enter();
+            endpoint.connect_lazy()
+        };
+        Ok(Self {
+            runtime,
+            client: NodeServiceClient::new(channel),
+        })
+    }
+}
+
+impl Upstream for GrpcUpstream {
+    fn get_sched_info(
+        &mut self,
+        request: NodeSchedRequest,
+    ) -> TimpaniResult<Option<NodeSchedResponse>> {
+        match self.runtime.block_on(self.client.get_sched_info(request)) {
+            Ok(resp) => Ok(Some(resp.into_inner())),
+            Err(status) if status.code() == Code::NotFound => {
+                debug!(message = status.message(), "no schedule yet");
+                Ok(None)
+            }
+            Err(status) => Err(failed("GetSchedInfo", &status)),
+        }
+    }
+
+    fn report_apply(&mut self, report: ApplyReport) -> TimpaniResult<()> {
+        let resp = self
+            .runtime
+            .block_on(self.client.report_apply(report))
+            .map_err(|status| failed("ReportApply", &status))?;
+        check("ReportApply", resp.into_inner())
+    }
+
+    fn report_dmiss(&mut self, info: DeadlineMissInfo) -> TimpaniResult<()> {
+        let resp = self
+            .runtime
+            .block_on(self.client.report_d_miss(info))
+            .map_err(|status| failed("ReportDMiss", &status))?;
+        check("ReportDMiss", resp.into_inner())
+    }
+}
+
+/// Log a failed call.
+fn failed(rpc: &str, status: &tonic::Status) -> TimpaniError {
+    warn!(
+        rpc,
+        code = ?status.code(),
+        message = status.message(),
+        "call to Timpani-O failed"
+    );
+    TimpaniError::Network
+}
+
+/// A `NodeResponse` with a non-zero status is a refusal: logged, and not
+/// worth sending again.
+fn check(rpc: &str, resp: NodeResponse) -> TimpaniResult<()> {
+    if resp.status != 0 {
+        warn!(
+            rpc,
+            status = resp.status,
+            error = %resp.error_message,
+            "Timpani-O refused the report"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unreachable_server_fails_the_call_not_the_client() {
+        // Port 1 (tcpmux) is closed on any test host.
+        let mut upstream = GrpcUpstream::new("127.0.0.1", 1).unwrap();
+        let request = NodeSchedRequest {
+            node_id: "n1".into(),
+            ..Default::default()
+        };
+        assert_eq!(upstream.get_sched_info(request), Err(TimpaniError::Network));
+    }
+
+    #[test]
+    fn test_malformed_address_is_a_config_error() {
+        assert_eq!(
+            GrpcUpstream::new("not a host", 7777).err(),
+            Some(TimpaniError::Config)
+        );
+    }
+}
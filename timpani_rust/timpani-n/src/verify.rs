@@ -0,0 +1,460 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Read-back verification of applied scheduling attributes.
+//!
+//! A successful `sched_setattr` does not keep the attributes in place: a
+//! systemd restart brings the unit back with its own policy, and a process
+//! may change its own.  The [`DriftMonitor`] reads every applied task's
+//! policy, priority and affinity back through the [`SchedBackend`] right
+//! after the apply and then every `--verify-interval-secs`.  A mismatch is
+//! a [`Drift`], reported upstream with `ReportDMiss` like a deadline miss
+//! and carrying the observed values.
+//!
+//! With `--max-reapply N` the intended attributes are set again on drift,
+//! at most N times per task and generation; after that the drift is only
+//! reported.  A task whose process is gone (`ESRCH`) is not drift: the next
+//! delivery resolves and applies it again.
+
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info, warn};
+
+use crate::apply::{errno, ApplyReport, ApplyStatus, SchedBackend, TaskApply};
+
+// =============================================================================
+// ATTRIBUTES
+// =============================================================================
+
+/// The attributes verified for a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchedAttrs {
+    /// See [`crate::apply::policy`].
+    pub policy: i32,
+    pub priority: i32,
+    /// Bit `n` = CPU `n`.
+    pub cpu_affinity: u64,
+}
+
+impl SchedAttrs {
+    /// What `task` asks for.
+    pub fn intended(task: &TaskApply) -> Self {
+        Self {
+            policy: task.policy,
+            priority: task.priority,
+            cpu_affinity: task.cpu_affinity,
+        }
+    }
+
+    /// What `pid` has right now.
+    fn read(backend: &dyn SchedBackend, pid: i32) -> Result<Self, i32> {
+        let (policy, priority) = backend.get_scheduler(pid)?;
+        Ok(Self {
+            policy,
+            priority,
+            cpu_affinity: backend.get_affinity(pid)?,
+        })
+    }
+
+    /// `true` if `observed` satisfies these attributes.  The kernel reports
+    /// priority 0 for normal policies, so priority only counts for RT ones.
+    fn satisfied_by(&self, observed: &SchedAttrs, rt: bool) -> bool {
+        self.policy == observed.policy
+            && self.cpu_affinity == observed.cpu_affinity
+            && (!rt || self.priority == observed.priority)
+    }
+}
+
+// =============================================================================
+// DRIFT
+// =============================================================================
+
+/// A task found running with other attributes than applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drift {
+    pub task_name: String,
+    pub pid: i32,
+    pub expected: SchedAttrs,
+    /// Attributes found, before any re-apply.
+    pub observed: SchedAttrs,
+    /// Re-applies made in this check.
+    pub reapplied: u32,
+    /// A re-apply brought the intended attributes back.
+    pub restored: bool,
+}
+
+impl Drift {
+    /// The differing attributes, e.g. `policy 1 -> 0, affinity 0x2 -> 0xf`.
+    pub fn detail(&self) -> String {
+        let (e, o) = (&self.expected, &self.observed);
+        let mut parts = Vec::new();
+        if e.policy != o.policy {
+            parts.push(format!("policy {} -> {}", e.policy, o.policy));
+        }
+        if e.priority != o.priority {
+            parts.push(format!("priority {} -> {}", e.priority, o.priority));
+        }
+        if e.cpu_affinity != o.cpu_affinity {
+            parts.push(format!(
+                "affinity {:#x} -> {:#x}",
+                e.cpu_affinity, o.cpu_affinity
+            ));
+        }
+        parts.join(", ")
+    }
+}
+
+// =============================================================================
+// MONITOR
+// =============================================================================
+
+/// A successfully applied task under watch.
+#[derive(Debug, Clone)]
+struct Tracked {
+    task: TaskApply,
+    pid: i32,
+    /// Re-applies used in this generation.
+    reapplies: u32,
+}
+
+/// Re-verifies the tasks of the last applied generation (see the module
+/// docs).
+#[derive(Debug, Default)]
+pub struct DriftMonitor {
+    /// `None` = verify once after each apply only.
+    interval: Option<Duration>,
+    max_reapply: u32,
+    tracked: Vec<Tracked>,
+    next_check: Option<Instant>,
+}
+
+impl DriftMonitor {
+    /// Verify every `interval`; zero = only right after an apply.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval: (!interval.is_zero()).then_some(interval),
+            ..Default::default()
+        }
+    }
+
+    /// Re-apply drifted tasks up to `max` times per task and generation.
+    pub fn with_max_reapply(mut self, max: u32) -> Self {
+        self.max_reapply = max;
+        self
+    }
+
+    /// Watch the tasks `report` says were applied, replacing the previous
+    /// generation's, and verify them at the next [`check`](Self::check).
+    pub fn track(&mut self, report: &ApplyReport, tasks: &[TaskApply], now: Instant) {
+        self.tracked = tasks
+            .iter()
+            .zip(&report.tasks)
+            .filter(|(_, r)| r.status == ApplyStatus::Applied && r.pid != 0)
+            .map(|(task, r)| Tracked {
+                task: task.clone(),
+                pid: r.pid,
+                reapplies: 0,
+            })
+            .collect();
+        self.next_check = Some(now);
+    }
+
+    /// `true` if [`check`](Self::check) should run at `now`.
+    pub fn due(&self, now: Instant) -> bool {
+        self.next_check.is_some_and(|at| now >= at)
+    }
+
+    /// Read every watched task back, re-applying within the bound, and
+    /// return the drifted ones.
+    pub fn check(&mut self, backend: &dyn SchedBackend, now: Instant) -> Vec<Drift> {
+        self.next_check = self.interval.map(|i| now + i);
+        let max_reapply = self.max_reapply;
+        let drifts: Vec<Drift> = self
+            .tracked
+            .iter_mut()
+            .filter_map(|t| verify(backend, t, max_reapply))
+            .collect();
+        for d in &drifts {
+            warn!(
+                task = %d.task_name,
+                pid = d.pid,
+                drift = %d.detail(),
+                reapplied = d.reapplied,
+                restored = d.restored,
+                "scheduling attributes drifted"
+            );
+        }
+        drifts
+    }
+}
+
+/// Verify one task; `None` if it is as applied or cannot be read.
+fn verify(backend: &dyn SchedBackend, t: &mut Tracked, max_reapply: u32) -> Option<Drift> {
+    let expected = SchedAttrs::intended(&t.task);
+    let rt = t.task.is_rt();
+    let observed = match SchedAttrs::read(backend, t.pid) {
+        Ok(attrs) => attrs,
+        Err(errno::ESRCH) => {
+            debug!(task = %t.task.name, pid = t.pid, "process gone, not verified");
+            return None;
+        }
+        Err(e) => {
+            warn!(
+                task = %t.task.name,
+                pid = t.pid,
+                errno = e,
+                "reading scheduling attributes failed"
+            );
+            return None;
+        }
+    };
+    if expected.satisfied_by(&observed, rt) {
+        return None;
+    }
+
+    let mut drift = Drift {
+        task_name: t.task.name.clone(),
+        pid: t.pid,
+        expected,
+        observed,
+        reapplied: 0,
+        restored: false,
+    };
+    while !drift.restored && t.reapplies < max_reapply {
+        t.reapplies += 1;
+        drift.reapplied += 1;
+        let reapply = backend
+            .set_affinity(t.pid, expected.cpu_affinity)
+            .and_then(|()| backend.set_scheduler(t.pid, expected.policy, expected.priority))
+            .and_then(|()| SchedAttrs::read(backend, t.pid));
+        match reapply {
+            Ok(now) => drift.restored = expected.satisfied_by(&now, rt),
+            Err(e) => debug!(task = %t.task.name, errno = e, "re-apply failed"),
+        }
+    }
+    if drift.restored {
+        info!(
+            task = %t.task.name,
+            reapplied = drift.reapplied,
+            "scheduling attributes restored"
+        );
+    }
+    Some(drift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply::{policy, TaskApplyResult};
+    use crate::fault::FaultSink;
+    use std::cell::{Cell, RefCell};
+    use std::collections::BTreeMap;
+
+    /// Holds one process's attributes.  `set_*` calls update them unless
+    /// `sticky_drift` is set, in which case every write is reverted (a unit
+    /// that keeps restarting with its own policy).
+    struct DriftingBackend {
+        attrs: RefCell<SchedAttrs>,
+        sticky_drift: bool,
+        writes: Cell<u32>,
+        gone: bool,
+    }
+
+    impl DriftingBackend {
+        fn new(attrs: SchedAttrs) -> Self {
+            Self {
+                attrs: RefCell::new(attrs),
+                sticky_drift: false,
+                writes: Cell::new(0),
+                gone: false,
+            }
+        }
+    }
+
+    impl SchedBackend for DriftingBackend {
+        fn join_cpuset(&self, _pid: i32, _cpus: u64) -> Result<(), i32> {
+            Ok(())
+        }
+        fn set_affinity(&self, _pid: i32, cpus: u64) -> Result<(), i32> {
+            self.writes.set(self.writes.get() + 1);
+            if !self.sticky_drift {
+                self.attrs.borrow_mut().cpu_affinity = cpus;
+            }
+            Ok(())
+        }
+        fn set_scheduler(&self, _pid: i32, policy: i32, priority: i32) -> Result<(), i32> {
+            self.writes.set(self.writes.get() + 1);
+            if !self.sticky_drift {
+                let mut attrs = self.attrs.borrow_mut();
+                attrs.policy = policy;
+                attrs.priority = priority;
+            }
+            Ok(())
+        }
+        fn get_affinity(&self, _pid: i32) -> Result<u64, i32> {
+            Ok(self.attrs.borrow().cpu_affinity)
+        }
+        fn get_scheduler(&self, _pid: i32) -> Result<(i32, i32), i32> {
+            if self.gone {
+                return Err(errno::ESRCH);
+            }
+            let attrs = self.attrs.borrow();
+            Ok((attrs.policy, attrs.priority))
+        }
+    }
+
+    fn task() -> TaskApply {
+        TaskApply {
+            name: "cam".into(),
+            pid: 42,
+            policy: policy::SCHED_FIFO,
+            priority: 50,
+            cpu_affinity: 0b10,
+            metadata: BTreeMap::new(),
+            placeholder: false,
+            fault_sink: FaultSink::Upstream,
+        }
+    }
+
+    /// Attributes a restarted unit comes back with.
+    const REVERTED: SchedAttrs = SchedAttrs {
+        policy: policy::SCHED_OTHER,
+        priority: 0,
+        cpu_affinity: 0xf,
+    };
+
+    fn applied(tasks: &[TaskApply]) -> ApplyReport {
+        ApplyReport {
+            tasks: tasks
+                .iter()
+                .map(|t| TaskApplyResult {
+                    task_name: t.name.clone(),
+                    status: ApplyStatus::Applied,
+                    errno: 0,
+                    detail: String::new(),
+                    pid: t.pid,
+                    unit_state: String::new(),
+                    fault_sink: t.fault_sink,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn monitor(max_reapply: u32, now: Instant) -> DriftMonitor {
+        let mut m = DriftMonitor::new(Duration::from_secs(10)).with_max_reapply(max_reapply);
+        m.track(&applied(&[task()]), &[task()], now);
+        m
+    }
+
+    #[test]
+    fn test_drift_is_detected_and_described() {
+        let now = Instant::now();
+        let mut m = monitor(0, now);
+        let backend = DriftingBackend::new(SchedAttrs::intended(&task()));
+        assert!(m.due(now));
+        assert!(m.check(&backend, now).is_empty());
+        assert!(!m.due(now + Duration::from_secs(9)));
+        assert!(m.due(now + Duration::from_secs(10)));
+
+        *backend.attrs.borrow_mut() = REVERTED;
+        let drifts = m.check(&backend, now + Duration::from_secs(10));
+        assert_eq!(
+            drifts,
+            [Drift {
+                task_name: "cam".into(),
+                pid: 42,
+                expected: SchedAttrs::intended(&task()),
+                observed: REVERTED,
+                reapplied: 0,
+                restored: false,
+            }]
+        );
+        assert_eq!(
+            drifts[0].detail(),
+            "policy 1 -> 0, priority 50 -> 0, affinity 0x2 -> 0xf"
+        );
+        assert_eq!(backend.writes.get(), 0);
+    }
+
+    #[test]
+    fn test_reapply_restores_within_the_bound() {
+        let now = Instant::now();
+        let mut m = monitor(2, now);
+        let backend = DriftingBackend::new(REVERTED);
+        let drifts = m.check(&backend, now);
+        assert_eq!((drifts[0].reapplied, drifts[0].restored), (1, true));
+        assert_eq!(*backend.attrs.borrow(), SchedAttrs::intended(&task()));
+        assert!(m.check(&backend, now).is_empty());
+    }
+
+    #[test]
+    fn test_reapply_bound_holds_across_checks() {
+        let now = Instant::now();
+        let mut m = monitor(3, now);
+        let backend = DriftingBackend {
+            sticky_drift: true,
+            ..DriftingBackend::new(REVERTED)
+        };
+        let first = m.check(&backend, now);
+        assert_eq!((first[0].reapplied, first[0].restored), (3, false));
+        assert_eq!(first[0].observed, REVERTED);
+        assert_eq!(backend.writes.get(), 6);
+
+        // Bound used up: reported, not re-applied, until the next generation.
+        let second = m.check(&backend, now);
+        assert_eq!(second[0].reapplied, 0);
+        assert_eq!(backend.writes.get(), 6);
+
+        m.track(&applied(&[task()]), &[task()], now);
+        assert_eq!(m.check(&backend, now)[0].reapplied, 3);
+    }
+
+    #[test]
+    fn test_gone_processes_and_failed_tasks_are_not_drift() {
+        let now = Instant::now();
+        let mut m = monitor(1, now);
+        let backend = DriftingBackend {
+            gone: true,
+            ..DriftingBackend::new(REVERTED)
+        };
+        assert!(m.check(&backend, now).is_empty());
+
+        let mut report = applied(&[task()]);
+        report.tasks[0].status = ApplyStatus::PermissionDenied;
+        m.track(&report, &[task()], now);
+        let backend = DriftingBackend::new(REVERTED);
+        assert!(m.check(&backend, now).is_empty());
+
+        // Nor are placeholders, which were never applied.
+        let reservation = TaskApply {
+            placeholder: true,
+            ..task()
+        };
+        report.tasks[0].status = ApplyStatus::Reserved;
+        m.track(&report, &[reservation], now);
+        assert!(m.check(&backend, now).is_empty());
+    }
+
+    #[test]
+    fn test_normal_policy_priority_is_not_compared() {
+        let normal = TaskApply {
+            policy: policy::SCHED_OTHER,
+            priority: 10,
+            ..task()
+        };
+        let now = Instant::now();
+        let mut m = DriftMonitor::new(Duration::ZERO);
+        let tasks = [normal.clone()];
+        m.track(&applied(&tasks), &tasks, now);
+        let backend = DriftingBackend::new(SchedAttrs {
+            priority: 0,
+            ..SchedAttrs::intended(&normal)
+        });
+        assert!(m.check(&backend, now).is_empty());
+        // Interval zero: verified after the apply only.
+        assert!(!m.due(now + Duration::from_secs(3600)));
+    }
+}
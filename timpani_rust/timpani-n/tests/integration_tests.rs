@@ -7,33 +7,46 @@
 
 use timpani_n::config::Config;
 use timpani_n::context::Context;
+use timpani_n::error::TimpaniError;
 use timpani_n::run_app;
 
+/// `config` pointed at a closed port, giving up after one try.
+fn unreachable(config: Config) -> Config {
+    Config {
+        port: 1,
+        connect_attempts: 1,
+        ..config
+    }
+}
+
 #[test]
 fn test_run_app_integration() {
-    let config = Config::default();
-    assert!(run_app(config).is_ok());
+    let config = unreachable(Config::default());
+    assert_eq!(run_app(config), Err(TimpaniError::Network));
 }
 
 #[test]
 fn test_full_lifecycle_with_various_configs() {
+    // Every configuration gets as far as contacting Timpani-O, which is
+    // not there
+
     // Test with default config
     let config = Config::default();
-    assert!(run_app(config).is_ok());
+    assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
 
     // Test with CPU affinity
     let config = Config {
         cpu: 2,
         ..Default::default()
     };
-    assert!(run_app(config).is_ok());
+    assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
 
     // Test with priority
     let config = Config {
         prio: 50,
         ..Default::default()
     };
-    assert!(run_app(config).is_ok());
+    assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
 
     // Test with all flags enabled
     let config = Config {
@@ -42,13 +55,13 @@ fn test_full_lifecycle_with_various_configs() {
         enable_apex: true,
         ..Default::default()
     };
-    assert!(run_app(config).is_ok());
+    assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
 
     // Test with different log levels
     for level in 0..=5 {
         let mut config = Config::default();
         config.log_level = timpani_n::config::LogLevel::from_u8(level).unwrap();
-        assert!(run_app(config).is_ok());
+        assert_eq!(run_app(unreachable(config)), Err(TimpaniError::Network));
     }
 }
 
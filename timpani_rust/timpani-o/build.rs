@@ -15,7 +15,17 @@ SPDX-License-Identifier: MIT
 /// must be set in the `PROTOC` environment variable before running `cargo build`.
 /// Install on Ubuntu/Debian: `sudo apt install -y protobuf-compiler`
 /// Install on macOS:          `brew install protobuf`
+///
+/// Only with the `grpc` feature: a `core`-only build generates nothing and
+/// needs no `protoc`.
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "grpc")]
+    compile_protos()?;
+    Ok(())
+}
+
+#[cfg(feature = "grpc")]
+fn compile_protos() -> Result<(), Box<dyn std::error::Error>> {
     // Path to the proto source relative to this crate's root.
     // Both proto files now live inside the Rust project itself so that the
     // crate can be built without the C++ tree present alongside it.
@@ -24,9 +34,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // All proto files to compile.
     //   schedinfo.proto    — SchedInfoService (Pullpiri → Timpani-O) + FaultService
     //   node_service.proto — NodeService (Timpani-N → Timpani-O)
+    //   admin.proto        — AdminService (operator → Timpani-O)
     let proto_files = [
         format!("{}/schedinfo.proto", proto_root),
         format!("{}/node_service.proto", proto_root),
+        format!("{}/admin.proto", proto_root),
     ];
 
     // Tell Cargo to re-run this build script when any proto file changes.
@@ -38,13 +50,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tonic_build::configure()
         // Generate both server and client stubs for every service.
-        // Servers: SchedInfoService, NodeService (Timpani-O serves these).
-        // Client:  FaultService (Timpani-O calls Pullpiri).
+        // Servers: SchedInfoService, NodeService, AdminService (Timpani-O
+        //          serves these).
+        // Client:  FaultService (Timpani-O calls Pullpiri), AdminService
+        //          (`timpani-o node`).
         .build_server(true)
         .build_client(true)
         // Derive serde Serialize/Deserialize on every generated message so we can
         // (de)serialise them easily in tests and logging.
         .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]")
+        // Proto3 semantics: a field missing from YAML/JSON takes its default,
+        // so workload files need not list fields added after they were written.
+        .message_attribute(".", "#[serde(default)]")
         .compile_protos(
             &proto_refs,   // proto files to compile
             &[proto_root], // directories to search for imports
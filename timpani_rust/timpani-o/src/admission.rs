@@ -0,0 +1,415 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Per-node admission, shared by the scheduler and embedded pre-checks.
+//!
+//! Whether a node can take a task is decided by [`node_verdict`]: the
+//! admission gate ([`check_admission`]) followed by the CPU-headroom check.
+//! Every scheduling algorithm asks it before choosing a node, and
+//! [`quick_check`] asks it for each node of a cluster snapshot, so a
+//! pre-check linked into Piccolo (`core` feature only) gives the verdict
+//! the scheduler would:
+//!
+//! ```
+//! use timpani_o::admission::{quick_check, ClusterState};
+//! use timpani_o::prelude::*;
+//!
+//! let nodes = [NodeConfig::default_config("node01")];
+//! let task = Task {
+//!     name: "sensor".into(),
+//!     workload_id: "plan".into(),
+//!     period_us: Micros(10_000),
+//!     runtime_us: Micros(2_000),
+//!     deadline_us: Micros(10_000),
+//!     ..Default::default()
+//! };
+//! let verdicts = quick_check(&task, &nodes, Some(&ClusterState::default()));
+//! assert_eq!(verdicts, [("node01".to_string(), Ok(()))]);
+//! ```
+//!
+//! A pre-check places nothing and only looks at one task on one node at a
+//! time, so a full run can still refuse a task every node admits here: the
+//! aggregate capacity check, `allowed_nodes` and the other tasks of the same
+//! workload are not considered.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{NodeConfig, NodeConfigManager};
+use crate::scheduler::pinned::PinnedDemand;
+use crate::scheduler::workloads::NodeWorkloads;
+use crate::scheduler::{
+    AdmissionOverride, AdmissionReason, AvailCpus, CpuUtil, GlobalScheduler, ScheduleOptions,
+};
+use crate::task::{CpuAffinity, NodeSchedMap, Task};
+
+// ── ClusterState ──────────────────────────────────────────────────────────────
+
+/// The last-known state of a cluster, as far as admission depends on it.
+///
+/// Serialisable, so a snapshot can be exported through [`crate::codec`] and
+/// checked against elsewhere.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClusterState {
+    /// Placements already running; new tasks share CPUs with them, except
+    /// those held exclusively ([`SchedTask::exclusive_cpus`](crate::task::SchedTask::exclusive_cpus)).
+    pub schedule: NodeSchedMap,
+    /// Nodes closed to new placements.
+    pub cordoned: BTreeSet<String>,
+    /// Nodes that reported missing RT privileges.
+    pub rt_incapable: BTreeSet<String>,
+    /// Configured CPUs each node last reported offline.
+    pub offline_cpus: BTreeMap<String, Vec<u32>>,
+}
+
+impl ClusterState {
+    /// Snapshot `config`'s runtime reports alongside `schedule`.
+    pub fn capture(config: &NodeConfigManager, schedule: NodeSchedMap) -> Self {
+        let nodes = config.get_all_nodes().keys();
+        Self {
+            schedule,
+            cordoned: config.cordoned_nodes(),
+            rt_incapable: nodes
+                .clone()
+                .filter(|n| !config.is_rt_capable(n))
+                .cloned()
+                .collect(),
+            offline_cpus: nodes
+                .map(|n| (n.clone(), config.offline_cpus(n)))
+                .filter(|(_, cpus)| !cpus.is_empty())
+                .collect(),
+        }
+    }
+}
+
+// ── Verdicts ──────────────────────────────────────────────────────────────────
+
+/// Admission control gate: check whether `task` is eligible to run on
+/// `node_id`.
+///
+/// Checks (in order):
+/// 1. Node exists in config and is not cordoned.
+/// 2. Memory budget (`task.memory_mb == 0` → skip; dormant until proto
+///    carries the field): `max_memory_mb`, lowered by the node's free
+///    memory report when live memory is enabled.  Skipped under
+///    [`AdmissionOverride::SkipMemory`] in `overrides`.
+/// 3. If `CpuAffinity::Pinned`, the pinned CPU must be in the node's set.
+/// 4. Real-time tasks only go to nodes that have not reported missing RT
+///    privileges.
+/// 5. A node at its `max_workloads` only takes tasks of workloads it
+///    already hosts (see [`crate::scheduler::workloads`]).
+pub fn check_admission(
+    config: &NodeConfigManager,
+    task: &Task,
+    node_id: &str,
+    avail: &AvailCpus,
+    workloads: &NodeWorkloads,
+    overrides: &BTreeSet<AdmissionOverride>,
+) -> Result<(), AdmissionReason> {
+    // 1. Node must exist in config
+    let node_cfg =
+        config
+            .get_node_config(node_id)
+            .ok_or_else(|| AdmissionReason::NodeNotFound {
+                node: node_id.to_string(),
+            })?;
+    if config.is_cordoned(node_id) {
+        return Err(AdmissionReason::NodeCordoned);
+    }
+
+    // 2. Memory (dormant while task.memory_mb == 0)
+    if task.memory_mb > 0 && !overrides.contains(&AdmissionOverride::SkipMemory) {
+        let available_mb = config
+            .memory_budget(node_id)
+            .map_or(node_cfg.max_memory_mb, |b| b.ceiling_mb());
+        if task.memory_mb > available_mb {
+            return Err(AdmissionReason::InsufficientMemory {
+                required_mb: task.memory_mb,
+                available_mb,
+            });
+        }
+    }
+
+    // 3. Pinned CPU affinity must be in this node's CPU set
+    if let CpuAffinity::Pinned(mask) = task.affinity {
+        let required_cpu = mask.trailing_zeros();
+        let node_cpus = avail.get(node_id).map(|v| v.as_slice()).unwrap_or(&[]);
+        if !node_cpus.contains(&required_cpu) {
+            return Err(AdmissionReason::CpuAffinityUnavailable {
+                requested_cpu: required_cpu,
+            });
+        }
+    }
+
+    // 4. RT privileges reported by the node's pre-flight
+    if task.policy.is_realtime() && !config.is_rt_capable(node_id) {
+        return Err(AdmissionReason::RtPrivilegesMissing);
+    }
+
+    // 5. Distinct workloads on the node
+    if !workloads.admits(node_id, &task.workload_id, node_cfg.max_workloads) {
+        return Err(AdmissionReason::WorkloadCountExceeded {
+            limit: node_cfg.max_workloads.unwrap_or_default(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Whether `node_id` can take `task` now: [`check_admission`], then a CPU
+/// with room for the task under `opts`' threshold among those CPU
+/// exclusivity leaves it (see [`crate::scheduler::workloads`]).  Fails
+/// with [`AdmissionReason::CpuExclusivelyReserved`] if only excluded CPUs
+/// have room, [`AdmissionReason::NoAvailableCpu`] if none has.
+///
+/// The check every algorithm makes before choosing a node.
+pub fn node_verdict(
+    config: &NodeConfigManager,
+    task: &Task,
+    node_id: &str,
+    avail: &AvailCpus,
+    util: &CpuUtil,
+    workloads: &NodeWorkloads,
+    opts: &ScheduleOptions,
+) -> Result<(), AdmissionReason> {
+    check_admission(
+        config,
+        task,
+        node_id,
+        avail,
+        workloads,
+        &opts.admission_overrides,
+    )?;
+    let best_cpu = |avail: &AvailCpus| {
+        GlobalScheduler::find_best_cpu_for_task(
+            config,
+            task,
+            node_id,
+            avail,
+            util,
+            &PinnedDemand::NONE,
+            opts.effective_threshold(),
+        )
+    };
+    if best_cpu(&workloads.open_cpus(avail, task)).is_some() {
+        return Ok(());
+    }
+    match best_cpu(avail).and_then(|cpu| workloads.conflict(node_id, cpu, task)) {
+        Some(workload) => Err(AdmissionReason::CpuExclusivelyReserved {
+            workload: workload.to_string(),
+        }),
+        None => Err(AdmissionReason::NoAvailableCpu),
+    }
+}
+
+/// [`node_verdict`] for `task` on each of `nodes`, in name order, against
+/// `snapshot` (none = an idle cluster) and the default
+/// [`ScheduleOptions`].  Nothing is placed.
+pub fn quick_check(
+    task: &Task,
+    nodes: &[NodeConfig],
+    snapshot: Option<&ClusterState>,
+) -> Vec<(String, Result<(), AdmissionReason>)> {
+    let empty = ClusterState::default();
+    let snapshot = snapshot.unwrap_or(&empty);
+    let config = NodeConfigManager::from_nodes(nodes.to_vec());
+    for node in nodes {
+        config.set_cordoned(&node.name, snapshot.cordoned.contains(&node.name));
+    }
+    for node in &snapshot.rt_incapable {
+        config.set_rt_capable(node, false);
+    }
+
+    let avail: AvailCpus = nodes
+        .iter()
+        .map(|node| {
+            let offline = snapshot.offline_cpus.get(&node.name);
+            let cpus = node
+                .available_cpus
+                .iter()
+                .copied()
+                .filter(|cpu| offline.is_none_or(|off| !off.contains(cpu)))
+                .collect();
+            (node.name.clone(), cpus)
+        })
+        .collect();
+    let mut util = GlobalScheduler::build_cpu_utilization(&avail);
+    GlobalScheduler::seed_cpu_utilization(&mut util, &snapshot.schedule);
+    let workloads = NodeWorkloads::from_schedule(&snapshot.schedule);
+    let opts = ScheduleOptions::default();
+
+    avail
+        .keys()
+        .map(|node| {
+            let verdict = node_verdict(&config, task, node, &avail, &util, &workloads, &opts);
+            (node.clone(), verdict)
+        })
+        .collect()
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::codec;
+    use crate::scheduler::SchedAlgorithm;
+    use crate::task::{Micros, SchedPolicy, TargetNodePolicy};
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    fn task(name: &str, workload: &str, runtime_us: u64) -> Task {
+        Task {
+            name: name.into(),
+            workload_id: workload.into(),
+            period_us: Micros(10_000),
+            runtime_us: Micros(runtime_us),
+            deadline_us: Micros(10_000),
+            ..Default::default()
+        }
+    }
+
+    /// The outcome of scheduling `task` alone with a hard target on `node`,
+    /// as an admission verdict.
+    fn scheduled(
+        config: &Arc<NodeConfigManager>,
+        state: &ClusterState,
+        task: &Task,
+        node: &str,
+    ) -> Result<(), AdmissionReason> {
+        let probe = Task {
+            target_node: node.into(),
+            target_node_policy: Some(TargetNodePolicy::Hard),
+            ..task.clone()
+        };
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::TargetNodePriority);
+        match GlobalScheduler::new(Arc::clone(config)).schedule_with_occupancy(
+            &state.schedule,
+            vec![probe],
+            &opts,
+        ) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e
+                .reason()
+                .unwrap_or_else(|| panic!("{} on {node}: not an admission error: {e}", task.name))
+                .clone()),
+        }
+    }
+
+    /// The impact fixture cluster (front01, rear01 and control01 with CPUs 2
+    /// and 3, and the placements of `impact_state.json`) under a few runtime
+    /// states.
+    fn snapshots() -> Vec<(&'static str, ClusterState)> {
+        let schedule: NodeSchedMap = codec::load(&fixture("impact_state.json")).unwrap();
+        let base = ClusterState {
+            schedule: schedule.clone(),
+            ..Default::default()
+        };
+        vec![
+            ("idle", ClusterState::default()),
+            ("loaded", base.clone()),
+            (
+                "cordoned",
+                ClusterState {
+                    cordoned: ["rear01".to_string()].into(),
+                    ..base.clone()
+                },
+            ),
+            (
+                "degraded",
+                ClusterState {
+                    rt_incapable: ["control01".to_string()].into(),
+                    offline_cpus: [("front01".to_string(), vec![3])].into(),
+                    ..base
+                },
+            ),
+        ]
+    }
+
+    fn tasks() -> Vec<Task> {
+        vec![
+            task("small", "probe", 500),
+            task("large", "probe", 6_000),
+            task("oversized", "probe", 9_500),
+            Task {
+                policy: SchedPolicy::Fifo,
+                ..task("rt", "probe", 1_000)
+            },
+            Task {
+                affinity: CpuAffinity::Pinned(1 << 3),
+                ..task("pinned_cpu3", "probe", 1_000)
+            },
+            Task {
+                affinity: CpuAffinity::Pinned(1 << 7),
+                ..task("pinned_cpu7", "probe", 1_000)
+            },
+            Task {
+                memory_mb: 1 << 40,
+                ..task("hungry", "probe", 1_000)
+            },
+        ]
+    }
+
+    #[test]
+    fn quick_check_matches_the_scheduler_on_every_node() {
+        let mut loaded = NodeConfigManager::new();
+        loaded
+            .load_from_file(&fixture("impact_current.yaml"))
+            .unwrap();
+        let mut nodes: Vec<NodeConfig> = loaded.get_all_nodes().values().cloned().collect();
+        for node in &mut nodes {
+            if node.name == "control01" {
+                node.max_workloads = Some(1);
+            }
+        }
+
+        let mut rejected = BTreeSet::new();
+        for (label, state) in snapshots() {
+            let config = Arc::new(NodeConfigManager::from_nodes(nodes.clone()));
+            for node in &state.cordoned {
+                config.set_cordoned(node, true);
+            }
+            for node in &state.rt_incapable {
+                config.set_rt_capable(node, false);
+            }
+            for (node, offline) in &state.offline_cpus {
+                let online = config.usable_cpus(node).unwrap();
+                config
+                    .report_online_cpus(node, online.into_iter().filter(|c| !offline.contains(c)));
+            }
+            assert_eq!(
+                ClusterState::capture(&config, state.schedule.clone()),
+                state
+            );
+
+            for task in tasks() {
+                let verdicts = quick_check(&task, &nodes, Some(&state));
+                assert_eq!(verdicts.len(), nodes.len());
+                for (node, verdict) in verdicts {
+                    assert_eq!(
+                        verdict,
+                        scheduled(&config, &state, &task, &node),
+                        "{label}: {} on {node}",
+                        task.name
+                    );
+                    if let Err(reason) = verdict {
+                        rejected.insert(reason.kind());
+                    }
+                }
+            }
+        }
+        // The matrix exercises every reason a configured node can give.
+        assert_eq!(rejected.len(), 6, "{rejected:?}");
+    }
+}
@@ -0,0 +1,438 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Snapshot serialisation — JSON or CBOR.
+//!
+//! Everything Timpani-O writes to or reads from disk as data — status
+//! snapshots (`status --save` / `--from`), schedule exports (`schedule
+//! --save`, read by `report` and `config impact --state`), repro bundles and
+//! [`ClusterState`](crate::admission::ClusterState) snapshots — goes through
+//! [`encode`] / [`decode`], so a single loader reads either format without
+//! being told which one it is:
+//!
+//! | Format | Selected by                      | Leading bytes                              |
+//! |--------|----------------------------------|--------------------------------------------|
+//! | JSON   | default, `.json`                 | `{` or `[` (after optional whitespace)     |
+//! | CBOR   | `.cbor`, or an explicit format   | `d9 d9 f7` — self-described CBOR tag 55799 |
+//!
+//! JSON stays the default because it is human-readable.  CBOR is the
+//! compact option for large cluster states on slow flash; `codec-bench` in
+//! `test-tools` prints the size and timing difference of a schedule and a
+//! cluster state for a given task count.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use thiserror::Error;
+
+/// Self-described CBOR tag 55799 (RFC 8949 §3.4.6).  Prefixed to every CBOR
+/// snapshot so [`Format::detect`] can recognise it.
+pub const CBOR_MAGIC: [u8; 3] = [0xd9, 0xd9, 0xf7];
+
+// ── Format ────────────────────────────────────────────────────────────────────
+
+/// On-disk snapshot format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Format {
+    /// Pretty-printed JSON.
+    #[default]
+    Json,
+    /// CBOR with the self-describe tag.
+    Cbor,
+}
+
+impl Format {
+    /// Canonical string form (also the file extension).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Cbor => "cbor",
+        }
+    }
+
+    /// Format implied by `path`'s extension; `None` if it is neither
+    /// `.json` nor `.cbor`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(Format::Json),
+            "cbor" => Some(Format::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Recognise the format of an encoded snapshot from its leading bytes.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&CBOR_MAGIC) {
+            return Some(Format::Cbor);
+        }
+        match bytes.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'{' | b'[') => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ── Errors ────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum CodecError {
+    #[error("I/O error on '{path}': {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("CBOR encode error: {0}")]
+    CborEncode(String),
+
+    #[error("CBOR decode error: {0}")]
+    CborDecode(String),
+
+    #[error("unrecognised snapshot format (neither JSON nor self-described CBOR)")]
+    UnknownFormat,
+}
+
+// ── Encoding / decoding ───────────────────────────────────────────────────────
+
+/// Serialise `value` in `format`.
+pub fn encode<T: Serialize>(value: &T, format: Format) -> Result<Vec<u8>, CodecError> {
+    match format {
+        Format::Json => {
+            let mut buf = serde_json::to_vec_pretty(value)?;
+            buf.push(b'\n');
+            Ok(buf)
+        }
+        Format::Cbor => {
+            let mut buf = CBOR_MAGIC.to_vec();
+            ciborium::into_writer(value, &mut buf)
+                .map_err(|e| CodecError::CborEncode(e.to_string()))?;
+            Ok(buf)
+        }
+    }
+}
+
+/// Deserialise a snapshot produced by [`encode`], detecting its format.
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError> {
+    match Format::detect(bytes).ok_or(CodecError::UnknownFormat)? {
+        Format::Json => Ok(serde_json::from_slice(bytes)?),
+        Format::Cbor => ciborium::from_reader(&bytes[CBOR_MAGIC.len()..])
+            .map_err(|e| CodecError::CborDecode(e.to_string())),
+    }
+}
+
+// ── Files ─────────────────────────────────────────────────────────────────────
+
+/// Write `value` to `path`.
+///
+/// The format is `format` if given, else implied by the extension, else
+/// JSON.  The file is written to a sibling temporary and renamed into place
+/// so a reader never sees a half-written snapshot.  Returns the format used.
+pub fn save<T: Serialize>(
+    path: &Path,
+    value: &T,
+    format: Option<Format>,
+) -> Result<Format, CodecError> {
+    let format = format
+        .or_else(|| Format::from_path(path))
+        .unwrap_or_default();
+    let bytes = encode(value, format)?;
+
+    let io_err = |source| CodecError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, bytes).map_err(io_err)?;
+    fs::rename(&tmp, path).map_err(io_err)?;
+    Ok(format)
+}
+
+/// Read a snapshot written by [`save`] (either format).
+pub fn load<T: DeserializeOwned>(path: &Path) -> Result<T, CodecError> {
+    let bytes = fs::read(path).map_err(|source| CodecError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    decode(&bytes)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+// The fixtures are proto status messages.
+#[cfg(all(test, feature = "grpc"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::admission::ClusterState;
+    use crate::proto::schedinfo_v1::{
+        ClockSync, ClusterStatus, NodeApplyInfo, NodeStatus, OrphanedNode, TaskApplyResult,
+        TaskStatus, WorkloadStatus,
+    };
+    use crate::scheduler::spread::SplitMix64;
+    use crate::task::{
+        FaultSink, Micros, Nanos, NodeSchedMap, SchedPolicy, SchedTask, SharedResource,
+    };
+
+    const FORMATS: [Format; 2] = [Format::Json, Format::Cbor];
+
+    fn name(rng: &mut SplitMix64, prefix: &str) -> String {
+        format!("{prefix}{}", rng.below(100))
+    }
+
+    /// Dyadic fractions survive a JSON round trip bit-for-bit.
+    fn fraction(rng: &mut SplitMix64) -> f64 {
+        rng.below(1 << 10) as f64 / 256.0
+    }
+
+    fn random_sched_task(rng: &mut SplitMix64, node: &str) -> SchedTask {
+        SchedTask {
+            name: name(rng, "task"),
+            assigned_node: node.to_string(),
+            assigned_cpu: rng.below(64) as u32,
+            policy: [
+                SchedPolicy::Normal,
+                SchedPolicy::Fifo,
+                SchedPolicy::RoundRobin,
+            ][rng.below(3)],
+            priority: rng.below(99) as i32,
+            period_ns: Nanos(rng.next_u64()),
+            runtime_ns: Nanos(rng.next_u64()),
+            deadline_ns: Nanos(rng.next_u64()),
+            release_time_us: rng.next_u64() as i32,
+            max_dmiss: rng.below(10) as i32,
+            shared_resources: (0..rng.below(3))
+                .map(|_| SharedResource {
+                    name: name(rng, "lock"),
+                    max_cs_us: Micros(rng.next_u64()),
+                })
+                .collect(),
+            workload_id: name(rng, "wl"),
+            fallback_from: (rng.below(2) == 0).then(|| name(rng, "node")),
+            metadata: (0..rng.below(3))
+                .map(|_| (name(rng, "key"), name(rng, "value")))
+                .collect(),
+            placeholder: false,
+            exclusive_cpus: rng.below(2) == 0,
+            fault_sink: [FaultSink::Upstream, FaultSink::LocalOnly, FaultSink::Both][rng.below(3)],
+            cache_affinity_group: (rng.below(2) == 0).then(|| name(rng, "group")),
+        }
+    }
+
+    fn random_sched_map(rng: &mut SplitMix64) -> NodeSchedMap {
+        (0..rng.below(5))
+            .map(|_| {
+                let node = name(rng, "node");
+                let tasks = (0..rng.below(8))
+                    .map(|_| random_sched_task(rng, &node))
+                    .collect();
+                (node, tasks)
+            })
+            .collect()
+    }
+
+    fn random_cluster_status(rng: &mut SplitMix64) -> ClusterStatus {
+        ClusterStatus {
+            tenant: name(rng, "tenant"),
+            nodes: (0..rng.below(5))
+                .map(|_| NodeStatus {
+                    node: name(rng, "node"),
+                    cpu_count: rng.below(64) as u32,
+                    total_utilization: fraction(rng),
+                    total_free: fraction(rng),
+                    largest_placeable: fraction(rng),
+                    fragmentation_ratio: fraction(rng),
+                    task_count: rng.below(100) as u32,
+                    endpoint: format!("{}:{}", name(rng, "host"), rng.below(65535) + 1),
+                    configured_memory_mb: rng.next_u64(),
+                    live_memory_mb: (rng.below(2) == 0).then(|| rng.below(1 << 20) as u64),
+                    apply_info: (rng.below(2) == 0).then(|| NodeApplyInfo {
+                        cap_sys_nice: rng.below(2) == 0,
+                        rtprio_limit: rng.below(100) as u64,
+                        cpuset_writable: rng.below(2) == 0,
+                        isolated_cpus: (0..rng.below(4)).map(|c| c as u32).collect(),
+                        rt_runtime_us: 950_000,
+                        rt_period_us: 1_000_000,
+                    }),
+                    offline_cpus: (0..rng.below(3)).map(|c| c as u32).collect(),
+                    workload_count: rng.below(8) as u32,
+                    max_workloads: (rng.below(2) == 0).then(|| rng.below(8) as u32),
+                    config_fingerprint: format!("{:016x}", rng.next_u64()),
+                    clock: (rng.below(2) == 0).then(|| ClockSync {
+                        synchronized: rng.below(2) == 0,
+                        offset_ns: rng.next_u64() as i64,
+                        source: "chrony".into(),
+                    }),
+                    utilization_high_water_mark: fraction(rng),
+                    protocol: ["", "legacy", "delta,prepare,placeholder"][rng.below(3)].into(),
+                    exclusive_cpus: (0..rng.below(3))
+                        .map(|c| (c as u32, name(rng, "wl")))
+                        .collect(),
+                    cordoned: rng.below(2) == 0,
+                })
+                .collect(),
+            workloads: (0..rng.below(3))
+                .map(|_| WorkloadStatus {
+                    workload_id: name(rng, "wl"),
+                    priority_class: "platform".into(),
+                    ttl_remaining_ms: (rng.below(2) == 0).then(|| rng.below(1 << 30) as u64),
+                    generation: rng.next_u64(),
+                    task_count: rng.below(100) as u32,
+                    tasks_per_node: (0..rng.below(4))
+                        .map(|_| (name(rng, "node"), rng.below(10) as u32))
+                        .collect::<HashMap<_, _>>(),
+                    tasks: (0..rng.below(6))
+                        .map(|_| TaskStatus {
+                            node: name(rng, "node"),
+                            name: name(rng, "task"),
+                            state: "running".into(),
+                            metadata: Default::default(),
+                            placeholder: rng.below(4) == 0,
+                            apply: (rng.below(2) == 0).then(|| TaskApplyResult {
+                                task_name: name(rng, "task"),
+                                status: rng.below(8) as i32,
+                                errno: rng.below(40) as i32,
+                                detail: name(rng, "detail"),
+                                pid: rng.below(1 << 16) as i32,
+                                unit_state: name(rng, "state"),
+                                fault_sink: rng.below(3) as i32,
+                            }),
+                        })
+                        .collect(),
+                    illegal_transitions: rng.next_u64(),
+                })
+                .collect(),
+            pending: None,
+            config_generation: rng.next_u64(),
+            workloads_per_class: [(name(rng, "class"), rng.below(8) as u32)].into(),
+            compaction: None,
+            log_filter: ["", "info", "debug,h2=warn"][rng.below(3)].into(),
+            limit_hits: [(name(rng, "limit"), rng.next_u64())].into(),
+            orphaned: (0..rng.below(2))
+                .map(|_| OrphanedNode {
+                    node: name(rng, "node"),
+                    task_count: 1,
+                    total_utilization: fraction(rng),
+                    tasks: vec![name(rng, "task")],
+                    cpu: (rng.below(2) == 0).then(|| rng.below(64) as u32),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn random_sched_maps_round_trip_in_both_formats() {
+        let mut rng = SplitMix64::new(1909);
+        for _ in 0..200 {
+            let map = random_sched_map(&mut rng);
+            for format in FORMATS {
+                let bytes = encode(&map, format).unwrap();
+                assert_eq!(Format::detect(&bytes), Some(format));
+                assert_eq!(decode::<NodeSchedMap>(&bytes).unwrap(), map, "{format}");
+            }
+        }
+    }
+
+    #[test]
+    fn random_cluster_statuses_round_trip_in_both_formats() {
+        let mut rng = SplitMix64::new(55799);
+        for _ in 0..200 {
+            let status = random_cluster_status(&mut rng);
+            for format in FORMATS {
+                let bytes = encode(&status, format).unwrap();
+                assert_eq!(decode::<ClusterStatus>(&bytes).unwrap(), status, "{format}");
+            }
+        }
+    }
+
+    fn random_cluster_state(rng: &mut SplitMix64) -> ClusterState {
+        ClusterState {
+            schedule: random_sched_map(rng),
+            cordoned: (0..rng.below(3)).map(|_| name(rng, "node")).collect(),
+            rt_incapable: (0..rng.below(3)).map(|_| name(rng, "node")).collect(),
+            offline_cpus: (0..rng.below(3))
+                .map(|_| {
+                    let cpus = (0..rng.below(4)).map(|c| c as u32).collect();
+                    (name(rng, "node"), cpus)
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn random_cluster_states_round_trip_in_both_formats() {
+        let mut rng = SplitMix64::new(8949);
+        for _ in 0..200 {
+            let state = random_cluster_state(&mut rng);
+            for format in FORMATS {
+                let bytes = encode(&state, format).unwrap();
+                assert_eq!(decode::<ClusterState>(&bytes).unwrap(), state, "{format}");
+            }
+        }
+    }
+
+    #[test]
+    fn cbor_is_smaller_than_json() {
+        let mut rng = SplitMix64::new(7);
+        let node = "node01";
+        let map: NodeSchedMap = [(
+            node.to_string(),
+            (0..100)
+                .map(|_| random_sched_task(&mut rng, node))
+                .collect(),
+        )]
+        .into();
+        let json = encode(&map, Format::Json).unwrap();
+        let cbor = encode(&map, Format::Cbor).unwrap();
+        assert!(cbor.len() < json.len(), "{} vs {}", cbor.len(), json.len());
+    }
+
+    #[test]
+    fn detect_rejects_unknown_bytes() {
+        assert_eq!(Format::detect(b"  \n{}"), Some(Format::Json));
+        assert_eq!(Format::detect(b""), None);
+        assert_eq!(Format::detect(b"workload: x"), None);
+        assert!(matches!(
+            decode::<NodeSchedMap>(b"\x00\x01"),
+            Err(CodecError::UnknownFormat)
+        ));
+    }
+
+    #[test]
+    fn save_picks_format_from_flag_then_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let map = random_sched_map(&mut SplitMix64::new(3));
+
+        let cases = [
+            ("a.cbor", None, Format::Cbor),
+            ("b.json", None, Format::Json),
+            ("c.snapshot", None, Format::Json),
+            ("d.json", Some(Format::Cbor), Format::Cbor),
+        ];
+        for (file, flag, expected) in cases {
+            let path = dir.path().join(file);
+            assert_eq!(save(&path, &map, flag).unwrap(), expected, "{file}");
+            assert_eq!(Format::detect(&fs::read(&path).unwrap()), Some(expected));
+            assert_eq!(load::<NodeSchedMap>(&path).unwrap(), map, "{file}");
+        }
+    }
+}
@@ -0,0 +1,399 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! The schedule payload of the legacy C++ Timpani-N.
+//!
+//! [`encode`] produces byte for byte what the C++ Timpani-O's
+//! `DBusServer::SerializeSchedInfo` returns for libtrpc's `schedinfo` call,
+//! and `deserialize_sched_info` in the C Timpani-N reads.  libtrpc packs
+//! integers big-endian and a string as its bytes followed by their `u32`
+//! count; the reader unpacks from the end, so the fields go out in reverse:
+//!
+//! | Field              | Wire                | From                           |
+//! |--------------------|---------------------|--------------------------------|
+//! | hyperperiod        | `i64`               | `hyperperiod_us`               |
+//! | workload ID        | string, ≤ 63 bytes  | `workload_id`                  |
+//! | *per task:*        |                     |                                |
+//! | `task_name`        | string, ≤ 15 bytes  | [`SchedTask::name`]            |
+//! | `sched_priority`   | `i32`               | `priority`                     |
+//! | `sched_policy`     | `i32`               | `policy`                       |
+//! | period             | `i32` µs            | `period_ns`                    |
+//! | `release_time`     | `i32` µs            | `release_time_us`              |
+//! | runtime            | `i32` µs            | `runtime_ns`                   |
+//! | deadline           | `i32` µs            | `deadline_ns`                  |
+//! | `cpu_affinity`     | `i64`, a CPU *id*   | `assigned_cpu`                 |
+//! | `max_dmiss`        | `i32`               | `max_dmiss`                    |
+//! | `assigned_node`    | string, ≤ 63 bytes  | `assigned_node`                |
+//! | task count         | `i32`               |                                |
+//!
+//! Tasks go out node by node in name order, as from the C++ `std::map`,
+//! each node's in schedule order.  Longer strings are cut to the
+//! `sched_task_t` array sizes, as `strncpy` would; every cut is logged.  A
+//! cut that makes two task or node names equal is an error instead: the
+//! legacy node matches deadline misses and its own tasks by those names.
+//!
+//! This port has no D-Bus server, so nothing here is sent: a bridge serving
+//! libtrpc's `schedinfo` call hands the node [`encode`]'s bytes unchanged.
+//! `tests/fixtures/cxx_compat` holds payloads captured from the C++ code
+//! and the harness that captured them.
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+use tracing::warn;
+
+use crate::task::{Nanos, NodeSchedMap, SchedTask, TimingField};
+
+/// Longest task name the legacy node takes (`char task_name[16]`).
+pub const MAX_TASK_NAME: usize = 15;
+
+/// Longest node name or workload ID the legacy node takes (`char [64]`).
+pub const MAX_NODE_NAME: usize = 63;
+
+/// Why a schedule has no legacy payload.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EncodeError {
+    #[error("task names '{first}' and '{second}' are both sent as '{sent}' to a legacy Timpani-N")]
+    TaskNameCollision {
+        sent: String,
+        first: String,
+        second: String,
+    },
+    #[error("node names '{first}' and '{second}' are both sent as '{sent}' to a legacy Timpani-N")]
+    NodeNameCollision {
+        sent: String,
+        first: String,
+        second: String,
+    },
+    #[error("task '{task}': {field} does not fit the legacy Timpani-N's int32 microseconds")]
+    TooLong { task: String, field: TimingField },
+}
+
+/// The legacy payload of a workload's `schedule` (see the module docs).
+pub fn encode(
+    workload_id: &str,
+    hyperperiod_us: u64,
+    schedule: &NodeSchedMap,
+) -> Result<Vec<u8>, EncodeError> {
+    let mut nodes: Vec<_> = schedule.iter().collect();
+    nodes.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut task_names = Names::default();
+    let mut node_names = Names::default();
+    let mut buf = Vec::new();
+    put_i64(&mut buf, hyperperiod_us as i64);
+    put_str(&mut buf, cut("workload ID", workload_id, MAX_NODE_NAME));
+
+    let mut nr_tasks: i32 = 0;
+    for (_, tasks) in nodes {
+        for t in tasks {
+            let name = cut("task name", &t.name, MAX_TASK_NAME);
+            let node = cut("node name", &t.assigned_node, MAX_NODE_NAME);
+            task_names.insert(name, &t.name).map_err(|(sent, first)| {
+                EncodeError::TaskNameCollision {
+                    sent,
+                    first,
+                    second: t.name.clone(),
+                }
+            })?;
+            node_names
+                .insert(node, &t.assigned_node)
+                .map_err(|(sent, first)| EncodeError::NodeNameCollision {
+                    sent,
+                    first,
+                    second: t.assigned_node.clone(),
+                })?;
+
+            put_str(&mut buf, name);
+            put_i32(&mut buf, t.priority);
+            put_i32(&mut buf, t.policy.to_linux_int());
+            put_i32(&mut buf, micros(t, TimingField::Period, t.period_ns)?);
+            put_i32(&mut buf, t.release_time_us);
+            put_i32(&mut buf, micros(t, TimingField::Runtime, t.runtime_ns)?);
+            put_i32(&mut buf, micros(t, TimingField::Deadline, t.deadline_ns)?);
+            put_i64(&mut buf, i64::from(t.assigned_cpu));
+            put_i32(&mut buf, t.max_dmiss);
+            put_str(&mut buf, node);
+            nr_tasks += 1;
+        }
+    }
+    put_i32(&mut buf, nr_tasks);
+    Ok(buf)
+}
+
+/// Sent name → original name, to catch names a cut made equal.
+#[derive(Default)]
+struct Names(BTreeMap<Vec<u8>, String>);
+
+impl Names {
+    /// Note `original` went out as `sent`; `Err((sent, other))` if another
+    /// original already did.
+    fn insert(&mut self, sent: &[u8], original: &str) -> Result<(), (String, String)> {
+        match self.0.entry(sent.to_vec()) {
+            Entry::Vacant(e) => {
+                e.insert(original.to_string());
+                Ok(())
+            }
+            Entry::Occupied(e) if e.get() == original => Ok(()),
+            Entry::Occupied(e) => {
+                Err((String::from_utf8_lossy(sent).into_owned(), e.get().clone()))
+            }
+        }
+    }
+}
+
+/// `s` as `strncpy` into a `char [max + 1]` leaves it, logging a cut.
+fn cut<'a>(what: &str, s: &'a str, max: usize) -> &'a [u8] {
+    let bytes = s.as_bytes();
+    let bytes = bytes.split(|&b| b == 0).next().unwrap_or(bytes);
+    if bytes.len() <= max {
+        return bytes;
+    }
+    let sent = &bytes[..max];
+    warn!(
+        name = %s,
+        sent = %String::from_utf8_lossy(sent),
+        max_bytes = max,
+        "{what} truncated for a legacy Timpani-N"
+    );
+    sent
+}
+
+fn micros(task: &SchedTask, field: TimingField, value: Nanos) -> Result<i32, EncodeError> {
+    i32::try_from(value.to_micros().as_u64()).map_err(|_| EncodeError::TooLong {
+        task: task.name.clone(),
+        field,
+    })
+}
+
+fn put_i32(buf: &mut Vec<u8>, v: i32) {
+    buf.extend_from_slice(&v.to_be_bytes());
+}
+
+fn put_i64(buf: &mut Vec<u8>, v: i64) {
+    buf.extend_from_slice(&v.to_be_bytes());
+}
+
+fn put_str(buf: &mut Vec<u8>, s: &[u8]) {
+    buf.extend_from_slice(s);
+    buf.extend_from_slice(&(s.len() as u32).to_be_bytes());
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::payload_for;
+    use crate::config::{NodeConfig, NodeProtocol};
+    use crate::task::{SchedPolicy, Task};
+    use crate::testing::{fake_task, placed};
+
+    /// The schedules `capture.cpp` serialises; keep the two in step.
+    mod fixtures {
+        use super::*;
+
+        pub const LONG_NODE: &str =
+            "ecu_front_perception_and_sensor_fusion_compute_node_with_a_long_hostname";
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn task(
+            name: &str,
+            node: &str,
+            cpu: u32,
+            policy: SchedPolicy,
+            priority: i32,
+            (period_us, runtime_us, deadline_us): (u64, u64, u64),
+            release_time_us: u32,
+            max_dmiss: i32,
+        ) -> SchedTask {
+            let task = Task {
+                policy,
+                priority,
+                deadline_us: crate::task::Micros(deadline_us),
+                release_time_us,
+                max_dmiss,
+                ..fake_task(name, node, period_us, runtime_us)
+            };
+            placed(&task, node, cpu)
+        }
+
+        pub fn schedule(tasks: Vec<SchedTask>) -> NodeSchedMap {
+            let mut map = NodeSchedMap::new();
+            for t in tasks {
+                map.entry(t.assigned_node.clone()).or_default().push(t);
+            }
+            map
+        }
+
+        pub fn basic() -> NodeSchedMap {
+            use SchedPolicy::*;
+            schedule(vec![
+                task(
+                    "lidar",
+                    "node01",
+                    3,
+                    RoundRobin,
+                    70,
+                    (20_000, 5_000, 20_000),
+                    500,
+                    0,
+                ),
+                task(
+                    "planner",
+                    "node02",
+                    1,
+                    Normal,
+                    0,
+                    (5_000, 1_000, 5_000),
+                    0,
+                    3,
+                ),
+                task(
+                    "cam_front",
+                    "node01",
+                    2,
+                    Fifo,
+                    80,
+                    (10_000, 2_000, 8_000),
+                    0,
+                    1,
+                ),
+            ])
+        }
+
+        pub fn truncated() -> NodeSchedMap {
+            use SchedPolicy::*;
+            schedule(vec![
+                task(
+                    "sensor_fusion_main",
+                    LONG_NODE,
+                    4,
+                    Fifo,
+                    90,
+                    (50_000, 10_000, 40_000),
+                    0,
+                    2,
+                ),
+                task(
+                    "object_tracker_v2",
+                    LONG_NODE,
+                    5,
+                    Fifo,
+                    85,
+                    (100_000, 20_000, 100_000),
+                    250,
+                    0,
+                ),
+                task(
+                    "ctl",
+                    "node01",
+                    0,
+                    RoundRobin,
+                    60,
+                    (10_000, 1_000, 10_000),
+                    0,
+                    0,
+                ),
+            ])
+        }
+    }
+
+    #[test]
+    fn fixture_schedules_encode_as_the_cxx_captures() {
+        let basic = encode("wl_basic", 20_000, &fixtures::basic()).unwrap();
+        assert_eq!(
+            basic,
+            include_bytes!("../../tests/fixtures/cxx_compat/basic.bin")
+        );
+
+        let truncated = encode(
+            "vehicle_perception_stack_with_a_really_long_workload_identifier_v2",
+            100_000,
+            &fixtures::truncated(),
+        )
+        .unwrap();
+        assert_eq!(
+            truncated,
+            include_bytes!("../../tests/fixtures/cxx_compat/truncated.bin")
+        );
+
+        let empty = encode("wl_empty", 0, &NodeSchedMap::new()).unwrap();
+        assert_eq!(
+            empty,
+            include_bytes!("../../tests/fixtures/cxx_compat/empty.bin")
+        );
+    }
+
+    #[test]
+    fn names_a_cut_makes_equal_are_rejected() {
+        // Both are sent as "sensor_fusion_m".
+        let map = fixtures::schedule(vec![
+            placed(
+                &fake_task("sensor_fusion_main", "n1", 10_000, 1_000),
+                "n1",
+                0,
+            ),
+            placed(
+                &fake_task("sensor_fusion_mirror", "n1", 10_000, 1_000),
+                "n1",
+                1,
+            ),
+        ]);
+        assert_eq!(
+            encode("wl", 10_000, &map).unwrap_err(),
+            EncodeError::TaskNameCollision {
+                sent: "sensor_fusion_m".into(),
+                first: "sensor_fusion_main".into(),
+                second: "sensor_fusion_mirror".into(),
+            }
+        );
+
+        // A name cut to exactly another task's full name collides too.
+        let map = fixtures::schedule(vec![
+            placed(&fake_task("sensor_fusion_m", "n1", 10_000, 1_000), "n1", 0),
+            placed(
+                &fake_task("sensor_fusion_main", "n1", 10_000, 1_000),
+                "n1",
+                1,
+            ),
+        ]);
+        assert!(matches!(
+            encode("wl", 10_000, &map),
+            Err(EncodeError::TaskNameCollision { .. })
+        ));
+
+        let a = format!("{}_a", fixtures::LONG_NODE);
+        let b = format!("{}_b", fixtures::LONG_NODE);
+        let map = fixtures::schedule(vec![
+            placed(&fake_task("x", &a, 10_000, 1_000), &a, 0),
+            placed(&fake_task("y", &b, 10_000, 1_000), &b, 0),
+        ]);
+        assert!(matches!(
+            encode("wl", 10_000, &map),
+            Err(EncodeError::NodeNameCollision { .. })
+        ));
+    }
+
+    #[test]
+    fn only_legacy_nodes_get_a_payload() {
+        let schedule = fixtures::basic();
+        let modern = NodeConfig::default_config("node01");
+        let legacy = NodeConfig {
+            protocol: NodeProtocol::LegacyCxx,
+            ..NodeConfig::default_config("node02")
+        };
+        assert_eq!(
+            payload_for(&modern, "wl_basic", 20_000, &schedule).unwrap(),
+            None
+        );
+        assert_eq!(
+            payload_for(&legacy, "wl_basic", 20_000, &schedule).unwrap(),
+            Some(include_bytes!("../../tests/fixtures/cxx_compat/basic.bin").to_vec())
+        );
+    }
+}
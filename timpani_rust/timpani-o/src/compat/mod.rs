@@ -0,0 +1,36 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Compatibility with the C++ implementation.
+//!
+//! | Module  | Talks to                                                       |
+//! |---------|----------------------------------------------------------------|
+//! | [`cxx`] | legacy C++ Timpani-N nodes (`protocol: legacy_cxx`, see config) |
+//!
+//! Which nodes get which format is decided per node by
+//! [`NodeConfig::protocol`]; [`payload_for`] is the one place that reads it.
+
+pub mod cxx;
+
+use crate::config::{NodeConfig, NodeProtocol};
+use crate::task::NodeSchedMap;
+
+/// The legacy payload of a workload's `schedule` for `node`, or `None` when
+/// the node speaks the modern protocol and is served by `NodeService`.
+///
+/// Like the C++ Timpani-O, every legacy node of the workload gets the same
+/// payload, holding all of the workload's tasks; Timpani-N keeps those whose
+/// `assigned_node` is its own.
+pub fn payload_for(
+    node: &NodeConfig,
+    workload_id: &str,
+    hyperperiod_us: u64,
+    schedule: &NodeSchedMap,
+) -> Result<Option<Vec<u8>>, cxx::EncodeError> {
+    match node.protocol {
+        NodeProtocol::Grpc => Ok(None),
+        NodeProtocol::LegacyCxx => cxx::encode(workload_id, hyperperiod_us, schedule).map(Some),
+    }
+}
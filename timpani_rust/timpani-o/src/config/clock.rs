@@ -0,0 +1,119 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Node clocks: the synchronisation state reported by Timpani-N.
+//!
+//! Release offsets only mean something if every node shares a time base.
+//! Nodes report what chrony or their PTP hardware clock says about the
+//! system clock with every `GetSchedInfo` / `StreamSchedInfo` poll; the
+//! last report is kept here and checked before a schedule with release
+//! offsets is pushed (see [`crate::grpc::clock`]).
+//!
+//! A node that never reported is trusted.  Like online-CPU reports, clock
+//! reports do not go stale: a clock stays unsynchronised until the node
+//! says otherwise.
+
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use super::NodeConfigManager;
+
+/// Last clock report from a node (runtime state, not YAML).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockReport {
+    /// The time source reports the clock locked to its reference.
+    pub synchronized: bool,
+    /// Estimated offset of the system clock from the reference (signed).
+    pub offset_ns: i64,
+    /// Time source, e.g. `chrony` or `phc`.
+    pub source: String,
+}
+
+impl ClockReport {
+    /// Why the clock cannot be trusted to within `max_offset`; `None` if it
+    /// can.
+    pub fn problem(&self, max_offset: Duration) -> Option<String> {
+        if !self.synchronized {
+            return Some(format!("{} reports the clock unsynchronised", self.source));
+        }
+        let offset = Duration::from_nanos(self.offset_ns.unsigned_abs());
+        (offset > max_offset).then(|| {
+            format!(
+                "{} offset {} ns exceeds {} ns",
+                self.source,
+                self.offset_ns,
+                max_offset.as_nanos()
+            )
+        })
+    }
+}
+
+impl NodeConfigManager {
+    /// Record `name`'s clock report.  Unconfigured nodes are ignored.
+    pub fn report_clock(&self, name: &str, report: ClockReport) {
+        if !self.nodes.contains_key(name) {
+            return;
+        }
+        let mut reports = self.clock_reports.write().unwrap();
+        let was = reports.get(name).is_none_or(|r| r.synchronized);
+        match (was, report.synchronized) {
+            (true, false) => {
+                warn!(node = %name, source = %report.source, "node clock lost synchronisation")
+            }
+            (false, true) => {
+                info!(node = %name, source = %report.source, "node clock synchronised again")
+            }
+            _ => {}
+        }
+        reports.insert(name.to_string(), report);
+    }
+
+    /// `name`'s last clock report; `None` if it never sent one.
+    pub fn clock_report(&self, name: &str) -> Option<ClockReport> {
+        self.clock_reports.read().unwrap().get(name).cloned()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+
+    fn report(synchronized: bool, offset_ns: i64) -> ClockReport {
+        ClockReport {
+            synchronized,
+            offset_ns,
+            source: "chrony".into(),
+        }
+    }
+
+    #[test]
+    fn problem_covers_sync_state_and_offset_bound() {
+        let bound = Duration::from_micros(100);
+        assert_eq!(report(true, -100_000).problem(bound), None);
+        assert_eq!(
+            report(true, -100_001).problem(bound).unwrap(),
+            "chrony offset -100001 ns exceeds 100000 ns"
+        );
+        assert_eq!(
+            report(false, 0).problem(bound).unwrap(),
+            "chrony reports the clock unsynchronised"
+        );
+    }
+
+    #[test]
+    fn reports_are_kept_per_configured_node() {
+        let mgr = NodeConfigManager::from_nodes(vec![NodeConfig::default_config("n1")]);
+        assert_eq!(mgr.clock_report("n1"), None);
+        mgr.report_clock("n1", report(false, 0));
+        mgr.report_clock("ghost", report(true, 0));
+        assert_eq!(mgr.clock_report("n1"), Some(report(false, 0)));
+        assert_eq!(mgr.clock_report("ghost"), None);
+        assert_eq!(mgr.successor().clock_report("n1"), Some(report(false, 0)));
+    }
+}
@@ -0,0 +1,144 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Cordoned nodes: configured, but closed to new placements.
+//!
+//! The YAML `enabled` flag is the baseline: `enabled: false` starts a node
+//! cordoned.  Operators change it at runtime through the admin service
+//! (`timpani-o node cordon|uncordon`), which records an *override* on top
+//! of the baseline:
+//!
+//! ```text
+//! cordoned = override, if any, else !enabled
+//! ```
+//!
+//! Setting a node back to its YAML state drops the override.  Overrides
+//! outlive configuration reloads ([`NodeConfigManager::successor`],
+//! [`NodeConfigManager::adopt_cordons`]), so a SIGHUP does not undo an
+//! operator's cordon; an override for a node the new configuration lacks is
+//! kept in case the node comes back.
+//!
+//! Tasks already placed on a cordoned node stay until it is drained.
+
+use std::collections::BTreeSet;
+
+use tracing::info;
+
+use super::NodeConfigManager;
+
+impl NodeConfigManager {
+    /// Whether `name` is closed to new placements.  `false` for unknown
+    /// nodes.
+    pub fn is_cordoned(&self, name: &str) -> bool {
+        let Some(node) = self.nodes.get(name) else {
+            return false;
+        };
+        self.cordons
+            .read()
+            .unwrap()
+            .get(name)
+            .copied()
+            .unwrap_or(!node.enabled)
+    }
+
+    /// Cordon (`true`) or uncordon `name`.  Returns whether the state
+    /// changed, or `None` if the node is not configured.
+    pub fn set_cordoned(&self, name: &str, cordoned: bool) -> Option<bool> {
+        let node = self.nodes.get(name)?;
+        let was = self.is_cordoned(name);
+        let mut cordons = self.cordons.write().unwrap();
+        if cordoned != node.enabled {
+            // The YAML state: no override needed.
+            cordons.remove(name);
+        } else {
+            cordons.insert(name.to_string(), cordoned);
+        }
+        if was != cordoned {
+            info!(node = %name, cordoned, "node cordon state changed");
+        }
+        Some(was != cordoned)
+    }
+
+    /// Configured nodes that are cordoned, sorted.
+    pub fn cordoned_nodes(&self) -> BTreeSet<String> {
+        self.nodes
+            .keys()
+            .filter(|name| self.is_cordoned(name))
+            .cloned()
+            .collect()
+    }
+
+    /// Take over `previous`'s cordon overrides, for a configuration loaded
+    /// without [`successor`](Self::successor).
+    pub fn adopt_cordons(&self, previous: &NodeConfigManager) {
+        let theirs = previous.cordons.read().unwrap().clone();
+        let mut ours = self.cordons.write().unwrap();
+        for (name, cordoned) in theirs {
+            match self.nodes.get(&name) {
+                // Moot: the new YAML state already matches.
+                Some(node) if cordoned != node.enabled => {}
+                _ => {
+                    ours.insert(name, cordoned);
+                }
+            }
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+
+    fn nodes() -> NodeConfigManager {
+        NodeConfigManager::from_nodes(vec![
+            NodeConfig::default_config("n1"),
+            NodeConfig {
+                enabled: false,
+                ..NodeConfig::default_config("n2")
+            },
+        ])
+    }
+
+    #[test]
+    fn overrides_apply_on_top_of_the_yaml_flag() {
+        let mgr = nodes();
+        assert_eq!(mgr.cordoned_nodes(), BTreeSet::from(["n2".to_string()]));
+
+        assert_eq!(mgr.set_cordoned("n1", true), Some(true));
+        assert_eq!(mgr.set_cordoned("n1", true), Some(false));
+        assert_eq!(mgr.set_cordoned("n2", false), Some(true));
+        assert!(mgr.is_cordoned("n1"));
+        assert!(!mgr.is_cordoned("n2"));
+        assert_eq!(mgr.set_cordoned("n9", true), None);
+        assert!(!mgr.is_cordoned("n9"));
+
+        // Back to the YAML state drops the override.
+        mgr.set_cordoned("n2", true);
+        assert_eq!(mgr.cordons.read().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn overrides_survive_a_reload() {
+        let old = nodes();
+        old.set_cordoned("n1", true);
+        old.set_cordoned("n2", false);
+
+        let successor = old.successor();
+        assert!(successor.cordons.read().unwrap()["n1"]);
+
+        // n2 is now enabled in the YAML: the uncordon override is moot.
+        let reloaded = NodeConfigManager::from_nodes(vec![
+            NodeConfig::default_config("n1"),
+            NodeConfig::default_config("n2"),
+        ]);
+        reloaded.adopt_cordons(&old);
+        assert!(reloaded.is_cordoned("n1"));
+        assert!(!reloaded.is_cordoned("n2"));
+        assert!(!reloaded.cordons.read().unwrap().contains_key("n2"));
+    }
+}
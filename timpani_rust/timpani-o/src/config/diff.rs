@@ -0,0 +1,179 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Difference between two node configurations.
+//!
+//! [`diff`] compares the YAML-derived fields that affect placement:
+//!
+//! | Field             | Reported as                         |
+//! |-------------------|-------------------------------------|
+//! | node presence     | `added` / `removed` node names      |
+//! | `available_cpus`  | `cpus_added` / `cpus_removed`       |
+//! | `max_memory_mb`   | `memory_mb: Some((old, new))`       |
+//! | `max_workloads`   | `max_workloads: Some((old, new))`   |
+//! | `enabled`         | `enabled: Some((old, new))`         |
+//! | `time_partitions` | `time_partitions: Some((old, new))` |
+//!
+//! Descriptive fields (`architecture`, `location`, `description`,
+//! `endpoint`), the `cpu_clusters` placement hint, the delivery `protocol`
+//! and runtime state (cordon overrides, online CPUs, memory reports) are not
+//! compared.  What a diff would do to existing placements
+//! is [`GlobalScheduler::impact_of`](crate::scheduler::GlobalScheduler::impact_of).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{NodeConfig, NodeConfigManager, TimePartitions};
+
+/// Placement-relevant changes to one node present in both configurations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeConfigChange {
+    /// CPUs only the new configuration lists, sorted.
+    pub cpus_added: Vec<u32>,
+    /// CPUs only the old configuration lists, sorted.
+    pub cpus_removed: Vec<u32>,
+    /// `(old, new)` `max_memory_mb` if it changed.
+    pub memory_mb: Option<(u64, u64)>,
+    /// `(old, new)` `max_workloads` if it changed.
+    pub max_workloads: Option<(Option<usize>, Option<usize>)>,
+    /// `(old, new)` `enabled` if it changed.
+    pub enabled: Option<(bool, bool)>,
+    /// `(old, new)` partition windows if they changed.
+    pub time_partitions: Option<(Option<TimePartitions>, Option<TimePartitions>)>,
+}
+
+impl NodeConfigChange {
+    fn between(old: &NodeConfig, new: &NodeConfig) -> Self {
+        let before: BTreeSet<u32> = old.available_cpus.iter().copied().collect();
+        let after: BTreeSet<u32> = new.available_cpus.iter().copied().collect();
+        Self {
+            cpus_added: after.difference(&before).copied().collect(),
+            cpus_removed: before.difference(&after).copied().collect(),
+            memory_mb: changed(old.max_memory_mb, new.max_memory_mb),
+            max_workloads: changed(old.max_workloads, new.max_workloads),
+            enabled: changed(old.enabled, new.enabled),
+            time_partitions: changed(old.time_partitions.clone(), new.time_partitions.clone()),
+        }
+    }
+
+    /// `true` when nothing placement-relevant changed.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `Some((old, new))` if they differ.
+fn changed<T: PartialEq>(old: T, new: T) -> Option<(T, T)> {
+    (old != new).then_some((old, new))
+}
+
+/// What changes between two node configurations (see the module docs).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    /// Nodes only the new configuration has, sorted.
+    pub added: Vec<String>,
+    /// Nodes only the old configuration has, sorted.
+    pub removed: Vec<String>,
+    /// Nodes in both whose placement-relevant fields changed.  Unchanged
+    /// nodes are omitted.
+    pub changed: BTreeMap<String, NodeConfigChange>,
+}
+
+impl ConfigDiff {
+    /// `true` when the configurations are equivalent for placement.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Whether `node`'s `cpu` is gone in the new configuration, either
+    /// because the node was removed or because the CPU was.
+    pub fn drops_cpu(&self, node: &str, cpu: u32) -> bool {
+        self.removed.iter().any(|n| n == node)
+            || self
+                .changed
+                .get(node)
+                .is_some_and(|c| c.cpus_removed.contains(&cpu))
+    }
+}
+
+/// Compare `old` with `new` (see the module docs).
+pub fn diff(old: &NodeConfigManager, new: &NodeConfigManager) -> ConfigDiff {
+    let old_nodes = old.get_all_nodes();
+    let new_nodes = new.get_all_nodes();
+
+    let mut added: Vec<String> = new_nodes
+        .keys()
+        .filter(|n| !old_nodes.contains_key(*n))
+        .cloned()
+        .collect();
+    added.sort();
+    let mut removed: Vec<String> = old_nodes
+        .keys()
+        .filter(|n| !new_nodes.contains_key(*n))
+        .cloned()
+        .collect();
+    removed.sort();
+    let changed = old_nodes
+        .iter()
+        .filter_map(|(name, before)| {
+            let change = NodeConfigChange::between(before, new_nodes.get(name)?);
+            (!change.is_empty()).then(|| (name.clone(), change))
+        })
+        .collect();
+
+    ConfigDiff {
+        added,
+        removed,
+        changed,
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_node_and_field_changes_only() {
+        let old = NodeConfigManager::from_nodes(vec![
+            NodeConfig::default_config("a"),
+            NodeConfig::default_config("b"),
+            NodeConfig::default_config("c"),
+        ]);
+        let new = NodeConfigManager::from_nodes(vec![
+            NodeConfig {
+                available_cpus: vec![0, 1, 4],
+                max_memory_mb: 2048,
+                description: "ignored".into(),
+                ..NodeConfig::default_config("a")
+            },
+            NodeConfig {
+                max_workloads: Some(2),
+                enabled: false,
+                ..NodeConfig::default_config("b")
+            },
+            NodeConfig::default_config("d"),
+        ]);
+
+        let d = diff(&old, &new);
+        assert_eq!(d.added, ["d"]);
+        assert_eq!(d.removed, ["c"]);
+        assert_eq!(
+            d.changed["a"],
+            NodeConfigChange {
+                cpus_added: vec![4],
+                cpus_removed: vec![2, 3],
+                memory_mb: Some((4096, 2048)),
+                ..Default::default()
+            }
+        );
+        assert_eq!(d.changed["b"].max_workloads, Some((None, Some(2))));
+        assert_eq!(d.changed["b"].enabled, Some((true, false)));
+        assert!(d.drops_cpu("a", 3) && d.drops_cpu("c", 0));
+        assert!(!d.drops_cpu("a", 0) && !d.drops_cpu("b", 3));
+
+        assert!(diff(&new, &new).is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Error type of [`NodeConfigManager::load_from_file`].
+//!
+//! | Variant      | Cause                                            |
+//! |--------------|--------------------------------------------------|
+//! | `Io`         | the file could not be read (or written)          |
+//! | `Parse`      | not YAML, or not the node-configuration layout   |
+//! | `Validation` | parsed, but one or more nodes are invalid        |
+//! | `Injected`   | a failure-injection hook fired (tests only)      |
+//!
+//! [`NodeConfigManager::load_from_file`]: super::NodeConfigManager::load_from_file
+
+use std::fmt;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::inject::InjectedFailure;
+
+/// One invalid setting of one node.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Node '{node}': {message}")]
+pub struct ValidationIssue {
+    pub node: String,
+    pub message: String,
+}
+
+/// Why a node configuration file could not be loaded.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ConfigError {
+    #[error("Cannot open configuration file: {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to parse YAML file: {}", path.display())]
+    Parse {
+        path: PathBuf,
+        /// 1-based line of the error, when serde_yaml reports one.
+        line: Option<usize>,
+        /// 1-based column of the error, when serde_yaml reports one.
+        column: Option<usize>,
+        #[source]
+        source: serde_yaml::Error,
+    },
+
+    #[error("Invalid node configuration in {}: {}", path.display(), IssueList(issues))]
+    Validation {
+        path: PathBuf,
+        /// Every invalid setting found, sorted by node.
+        issues: Vec<ValidationIssue>,
+    },
+
+    #[error(transparent)]
+    Injected(#[from] InjectedFailure),
+}
+
+impl ConfigError {
+    /// [`ConfigError::Parse`] with the location taken from `source`.
+    pub(super) fn parse(path: PathBuf, source: serde_yaml::Error) -> Self {
+        let location = source.location();
+        ConfigError::Parse {
+            path,
+            line: location.as_ref().map(|l| l.line()),
+            column: location.as_ref().map(|l| l.column()),
+            source,
+        }
+    }
+}
+
+/// `issue; issue; …` for [`ConfigError::Validation`].
+struct IssueList<'a>(&'a [ValidationIssue]);
+
+impl fmt::Display for IssueList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, issue) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Node configuration fingerprints.
+//!
+//! [`NodeConfig::fingerprint`] hashes a node's body — everything but its
+//! name — after normalising it:
+//!
+//! | Field                             | Normalised as                         |
+//! |-----------------------------------|---------------------------------------|
+//! | `available_cpus`, `reserved_cpus` | sorted, duplicates dropped            |
+//! | strings                           | surrounding space trimmed             |
+//! | `endpoint`, `max_workloads`       | absent ≠ any value                    |
+//! | `time_partitions`                 | sorted by offset; absent adds nothing |
+//! | `cpu_clusters`                    | each sorted; absent adds nothing      |
+//! | `protocol`                        | `grpc` adds nothing                   |
+//!
+//! The hash is 64-bit FNV-1a over a fixed encoding, so it is stable across
+//! builds and hosts: fleet tooling can compare the fingerprints two
+//! vehicles report (`NodeStatus.config_fingerprint`) to spot a stanza
+//! copied where it should have been edited.  Within one file, nodes that
+//! share a fingerprint are [`NodeConfigManager::duplicate_nodes`] and are
+//! warned about on load.  [`NodeConfigManager::config_fingerprint`] hashes
+//! a whole file the same way, node names included.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{NodeConfig, NodeConfigManager, NodeProtocol};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a over length-prefixed fields, so no two field lists encode alike.
+struct Fnv(u64);
+
+/// 64-bit FNV-1a over length-prefixed `fields`, as the fingerprints are
+/// computed: stable across builds and hosts.
+pub(crate) fn fnv1a<'a>(fields: impl IntoIterator<Item = &'a [u8]>) -> u64 {
+    let mut h = Fnv(FNV_OFFSET);
+    for field in fields {
+        h.bytes(field);
+    }
+    h.0
+}
+
+impl Fnv {
+    fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        for &b in (bytes.len() as u64).to_le_bytes().iter().chain(bytes) {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+        self
+    }
+
+    fn str(&mut self, s: &str) -> &mut Self {
+        self.bytes(s.trim().as_bytes())
+    }
+
+    fn cpus(&mut self, cpus: &[u32]) -> &mut Self {
+        let cpus: BTreeSet<u32> = cpus.iter().copied().collect();
+        let bytes: Vec<u8> = cpus.iter().flat_map(|c| c.to_le_bytes()).collect();
+        self.bytes(&bytes)
+    }
+
+    fn opt(&mut self, value: Option<&[u8]>) -> &mut Self {
+        match value {
+            Some(v) => self.bytes(&[1]).bytes(v),
+            None => self.bytes(&[0]),
+        }
+    }
+}
+
+impl NodeConfig {
+    /// Hash of this node's normalised body (see the module docs).
+    pub fn fingerprint(&self) -> u64 {
+        let max_workloads = self.max_workloads.map(|m| (m as u64).to_le_bytes());
+        let mut h = Fnv(FNV_OFFSET);
+        h.cpus(&self.available_cpus)
+            .cpus(&self.reserved_cpus)
+            .bytes(&self.max_memory_mb.to_le_bytes())
+            .str(&self.architecture)
+            .str(&self.location)
+            .str(&self.description)
+            .opt(self.endpoint.as_deref().map(|e| e.trim().as_bytes()))
+            .opt(max_workloads.as_ref().map(|m| m.as_slice()))
+            .bytes(&[u8::from(self.enabled)]);
+        // Only hashed when set, so unpartitioned fingerprints are unchanged.
+        if let Some(p) = &self.time_partitions {
+            let windows: Vec<u8> = p
+                .windows
+                .iter()
+                .flat_map(|w| [w.offset_us, w.duration_us])
+                .flat_map(u64::to_le_bytes)
+                .collect();
+            h.bytes(&p.major_frame_us.to_le_bytes()).bytes(&windows);
+        }
+        for cluster in &self.cpu_clusters {
+            h.cpus(cluster);
+        }
+        if self.protocol != NodeProtocol::Grpc {
+            h.str(self.protocol.as_str());
+        }
+        h.0
+    }
+}
+
+impl NodeConfigManager {
+    /// [`NodeConfig::fingerprint`] of `name`; `None` if it is not
+    /// configured.
+    pub fn fingerprint(&self, name: &str) -> Option<u64> {
+        self.nodes.get(name).map(NodeConfig::fingerprint)
+    }
+
+    /// Hash of every node's name and [`NodeConfig::fingerprint`], in name
+    /// order: equal for two files that configure the same nodes alike,
+    /// whatever their order or formatting.
+    pub fn config_fingerprint(&self) -> u64 {
+        let nodes: BTreeMap<&str, [u8; 8]> = self
+            .nodes
+            .iter()
+            .map(|(name, node)| (name.as_str(), node.fingerprint().to_le_bytes()))
+            .collect();
+        fnv1a(
+            nodes
+                .iter()
+                .flat_map(|(name, body)| [name.as_bytes(), body.as_slice()]),
+        )
+    }
+
+    /// Groups of two or more nodes with identical bodies, each sorted by
+    /// name, in name order.
+    pub fn duplicate_nodes(&self) -> Vec<Vec<String>> {
+        let mut groups: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+        for (name, node) in &self.nodes {
+            groups
+                .entry(node.fingerprint())
+                .or_default()
+                .push(name.clone());
+        }
+        let mut dups: Vec<Vec<String>> = groups
+            .into_values()
+            .filter(|g| g.len() > 1)
+            .map(|mut g| {
+                g.sort();
+                g
+            })
+            .collect();
+        dups.sort();
+        dups
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_name_and_list_order_but_not_content() {
+        let a = NodeConfig {
+            available_cpus: vec![3, 2, 2],
+            location: " front ".into(),
+            ..NodeConfig::default_config("a")
+        };
+        let b = NodeConfig {
+            available_cpus: vec![2, 3],
+            location: "front".into(),
+            ..NodeConfig::default_config("b")
+        };
+        assert_eq!(a.fingerprint(), b.fingerprint());
+
+        let changed = [
+            NodeConfig {
+                reserved_cpus: vec![0],
+                ..b.clone()
+            },
+            NodeConfig {
+                endpoint: Some("10.0.0.1:50054".into()),
+                ..b.clone()
+            },
+            NodeConfig {
+                max_workloads: Some(1),
+                ..b.clone()
+            },
+            NodeConfig {
+                description: String::new(),
+                ..b.clone()
+            },
+        ];
+        for c in &changed {
+            assert_ne!(c.fingerprint(), b.fingerprint(), "{c:?}");
+        }
+        // A field's content never leaks into its neighbour.
+        let shifted = NodeConfig {
+            architecture: "aarch64front".into(),
+            location: String::new(),
+            ..b.clone()
+        };
+        assert_ne!(shifted.fingerprint(), b.fingerprint());
+    }
+
+    #[test]
+    fn groups_nodes_with_identical_bodies() {
+        let mgr = NodeConfigManager::from_nodes(vec![
+            NodeConfig::default_config("c"),
+            NodeConfig::default_config("a"),
+            NodeConfig {
+                location: "rear".into(),
+                ..NodeConfig::default_config("b")
+            },
+        ]);
+        assert_eq!(mgr.duplicate_nodes(), [["a", "c"]]);
+        assert_eq!(mgr.fingerprint("a"), mgr.fingerprint("c"));
+        assert_ne!(mgr.fingerprint("a"), mgr.fingerprint("b"));
+        assert_eq!(mgr.fingerprint("missing"), None);
+    }
+
+    #[test]
+    fn config_fingerprint_covers_names_but_not_order() {
+        let nodes = || {
+            vec![
+                NodeConfig::default_config("a"),
+                NodeConfig {
+                    location: "rear".into(),
+                    ..NodeConfig::default_config("b")
+                },
+            ]
+        };
+        let mgr = NodeConfigManager::from_nodes(nodes());
+        let mut reversed = nodes();
+        reversed.reverse();
+        assert_eq!(
+            mgr.config_fingerprint(),
+            NodeConfigManager::from_nodes(reversed).config_fingerprint()
+        );
+
+        let mut renamed = nodes();
+        renamed[0].name = "c".into();
+        let renamed = NodeConfigManager::from_nodes(renamed);
+        assert_ne!(mgr.config_fingerprint(), renamed.config_fingerprint());
+    }
+}
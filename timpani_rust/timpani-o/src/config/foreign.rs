@@ -0,0 +1,93 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Foreign load: RT threads on a node that Timpani-O did not place.
+//!
+//! Other agents may pin their own RT threads to CPUs Timpani-O schedules.
+//! Timpani-N finds them by scanning `/proc` and reports their estimated
+//! load per CPU with its `GetSchedInfo` / `StreamSchedInfo` polls; the last
+//! report is kept here.  Each report replaces the previous one, so a CPU
+//! missing from it has no foreign load.  A poll without a report (no scan
+//! since the last one) changes nothing.
+//!
+//! With [`NodeConfigManager::with_respect_foreign_load`] the scheduler
+//! counts the reported load as already placed on its CPU, shrinking the
+//! headroom new tasks can use.  Without it the load is only reported.
+
+use std::collections::BTreeMap;
+
+use tracing::{info, warn};
+
+use super::NodeConfigManager;
+
+impl NodeConfigManager {
+    /// Count reported foreign load against CPU headroom when placing tasks
+    /// (`--respect-foreign-load`).
+    pub fn with_respect_foreign_load(mut self, respect: bool) -> Self {
+        self.respect_foreign_load = respect;
+        self
+    }
+
+    /// `true` if [`with_respect_foreign_load`](Self::with_respect_foreign_load)
+    /// was set.
+    pub fn respects_foreign_load(&self) -> bool {
+        self.respect_foreign_load
+    }
+
+    /// Record `name`'s foreign load, CPU → fraction of the CPU, and return
+    /// the previous report.  Unconfigured nodes are ignored.
+    pub fn report_foreign_load(
+        &self,
+        name: &str,
+        load: BTreeMap<u32, f64>,
+    ) -> Option<BTreeMap<u32, f64>> {
+        if !self.nodes.contains_key(name) {
+            return None;
+        }
+        let mut reports = self.foreign_load.write().unwrap();
+        let was_loaded = reports.get(name).is_some_and(|l| !l.is_empty());
+        match (was_loaded, load.is_empty()) {
+            (false, false) => warn!(node = %name, cpus = ?load, "foreign RT load on managed CPUs"),
+            (true, true) => info!(node = %name, "foreign RT load gone"),
+            _ => {}
+        }
+        reports.insert(name.to_string(), load)
+    }
+
+    /// `name`'s last foreign-load report; empty if it never sent one.
+    pub fn foreign_load(&self, name: &str) -> BTreeMap<u32, f64> {
+        self.foreign_load
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+
+    #[test]
+    fn each_report_replaces_the_last() {
+        let mgr = NodeConfigManager::from_nodes(vec![NodeConfig::default_config("n1")]);
+        assert!(!mgr.respects_foreign_load());
+        assert!(mgr.foreign_load("n1").is_empty());
+
+        let load = BTreeMap::from([(2, 0.3), (3, 0.05)]);
+        assert_eq!(mgr.report_foreign_load("n1", load.clone()), None);
+        assert_eq!(mgr.report_foreign_load("ghost", load.clone()), None);
+        assert_eq!(mgr.foreign_load("n1"), load);
+        assert!(mgr.foreign_load("ghost").is_empty());
+        assert_eq!(mgr.successor().foreign_load("n1"), load);
+
+        assert_eq!(mgr.report_foreign_load("n1", BTreeMap::new()), Some(load));
+        assert!(mgr.foreign_load("n1").is_empty());
+    }
+}
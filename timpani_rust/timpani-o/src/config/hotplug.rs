@@ -0,0 +1,142 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! CPU hotplug: the online CPUs reported by Timpani-N.
+//!
+//! `available_cpus` in the YAML is what a node *may* use; thermal
+//! management can take one of those cores offline at runtime.  Nodes report
+//! the kernel's online list with every `GetSchedInfo` / `StreamSchedInfo`
+//! poll, and the scheduler only places tasks on
+//!
+//! ```text
+//! available_cpus ∩ last reported online set
+//! ```
+//!
+//! A node that never reported is assumed to have every configured CPU
+//! online.  Unlike memory reports, online reports do not go stale: a CPU
+//! stays offline until the node says otherwise.
+
+use std::collections::BTreeSet;
+
+use tracing::{info, warn};
+
+use super::NodeConfigManager;
+
+/// How a node's configured CPUs changed with one online report.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuTransition {
+    /// Configured CPUs online before the report and offline after it.
+    pub went_offline: Vec<u32>,
+    /// Configured CPUs offline before the report and online after it.
+    pub came_online: Vec<u32>,
+}
+
+impl CpuTransition {
+    pub fn is_empty(&self) -> bool {
+        self.went_offline.is_empty() && self.came_online.is_empty()
+    }
+}
+
+impl NodeConfigManager {
+    /// Record the CPUs `name` reports online and return how its configured
+    /// CPUs changed.  Unconfigured nodes are ignored.
+    pub fn report_online_cpus(
+        &self,
+        name: &str,
+        online: impl IntoIterator<Item = u32>,
+    ) -> CpuTransition {
+        let Some(node) = self.nodes.get(name) else {
+            return CpuTransition::default();
+        };
+        let online: BTreeSet<u32> = online.into_iter().collect();
+        let before = self.offline_cpus(name);
+        self.online_cpus
+            .write()
+            .unwrap()
+            .insert(name.to_string(), online.clone());
+
+        let mut transition = CpuTransition::default();
+        for &cpu in &node.available_cpus {
+            match (before.contains(&cpu), online.contains(&cpu)) {
+                (false, false) => transition.went_offline.push(cpu),
+                (true, true) => transition.came_online.push(cpu),
+                _ => {}
+            }
+        }
+        if !transition.went_offline.is_empty() {
+            warn!(node = %name, cpus = ?transition.went_offline, "configured CPUs went offline");
+        }
+        if !transition.came_online.is_empty() {
+            info!(node = %name, cpus = ?transition.came_online, "configured CPUs back online");
+        }
+        transition
+    }
+
+    /// Configured CPUs of `name` that its last report lists as offline,
+    /// ascending.
+    pub fn offline_cpus(&self, name: &str) -> Vec<u32> {
+        let Some(node) = self.nodes.get(name) else {
+            return Vec::new();
+        };
+        let reports = self.online_cpus.read().unwrap();
+        let Some(online) = reports.get(name) else {
+            return Vec::new();
+        };
+        let mut offline: Vec<u32> = node
+            .available_cpus
+            .iter()
+            .copied()
+            .filter(|c| !online.contains(c))
+            .collect();
+        offline.sort_unstable();
+        offline
+    }
+
+    /// Configured CPUs of `name` the scheduler may use now, in configured
+    /// order.  `None` if the node is not configured.
+    pub fn usable_cpus(&self, name: &str) -> Option<Vec<u32>> {
+        let node = self.nodes.get(name)?;
+        let offline = self.offline_cpus(name);
+        Some(
+            node.available_cpus
+                .iter()
+                .copied()
+                .filter(|c| !offline.contains(c))
+                .collect(),
+        )
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+
+    #[test]
+    fn online_report_shrinks_and_grows_the_usable_set() {
+        let mgr = NodeConfigManager::from_nodes(vec![NodeConfig {
+            available_cpus: vec![2, 3, 4],
+            ..NodeConfig::default_config("n1")
+        }]);
+        assert_eq!(mgr.usable_cpus("n1"), Some(vec![2, 3, 4]));
+
+        // CPU 3 goes offline; CPUs outside the configured pool are ignored.
+        let t = mgr.report_online_cpus("n1", [0, 1, 2, 4]);
+        assert_eq!(t.went_offline, [3]);
+        assert!(t.came_online.is_empty());
+        assert_eq!(mgr.usable_cpus("n1"), Some(vec![2, 4]));
+        assert_eq!(mgr.offline_cpus("n1"), [3]);
+        assert!(mgr.report_online_cpus("n1", [2, 4]).is_empty());
+
+        let t = mgr.report_online_cpus("n1", [2, 3, 4]);
+        assert_eq!(t.came_online, [3]);
+        assert_eq!(mgr.usable_cpus("n1"), Some(vec![2, 3, 4]));
+
+        assert!(mgr.report_online_cpus("n9", []).is_empty());
+        assert_eq!(mgr.usable_cpus("n9"), None);
+    }
+}
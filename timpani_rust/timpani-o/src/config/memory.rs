@@ -0,0 +1,171 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Live node memory: free memory reported by Timpani-N.
+//!
+//! `max_memory_mb` in the YAML is what a node *may* give to tasks; what it
+//! *can* give right now depends on everything else running there.  Nodes
+//! report their free memory on every `GetSchedInfo` / `StreamSchedInfo`
+//! poll.  With live memory enabled
+//! ([`NodeConfigManager::with_live_memory`]) the admission ceiling becomes
+//!
+//! ```text
+//! min(max_memory_mb, reported free + tracked placements)
+//! ```
+//!
+//! where *tracked placements* is the memory of the tasks Timpani-O itself
+//! has placed on the node at report time — already in use there, but
+//! available again to the workload that replaces them.
+//!
+//! A report older than the liveness window is stale: the configured value
+//! is used alone and a warning is logged.  A node that never reported is
+//! treated the same way.
+
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+use super::NodeConfigManager;
+
+/// Liveness window used when none is configured.
+pub const DEFAULT_MEMORY_REPORT_WINDOW: Duration = Duration::from_secs(30);
+
+/// Last free-memory report from a node (runtime state, not YAML).
+#[derive(Debug, Clone, Copy)]
+pub(super) struct MemoryReport {
+    free_mb: u64,
+    tracked_mb: u64,
+    at: Instant,
+}
+
+/// Memory a node can give to tasks: configured and, when fresh, reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    /// `max_memory_mb` from the node configuration.
+    pub configured_mb: u64,
+
+    /// Reported free memory plus tracked placements.  `None` when live
+    /// memory is disabled or the node has no fresh report.
+    pub live_mb: Option<u64>,
+}
+
+impl MemoryBudget {
+    /// The admission ceiling: the smaller of the two values.
+    pub fn ceiling_mb(&self) -> u64 {
+        self.live_mb
+            .map_or(self.configured_mb, |live| live.min(self.configured_mb))
+    }
+}
+
+impl NodeConfigManager {
+    /// Admit against reported free memory; reports older than `window` are
+    /// ignored (see the [module docs](self)).
+    pub fn with_live_memory(mut self, window: Duration) -> Self {
+        self.live_memory_window = Some(window);
+        self
+    }
+
+    /// `true` if [`with_live_memory`](Self::with_live_memory) was set.
+    pub fn uses_live_memory(&self) -> bool {
+        self.live_memory_window.is_some()
+    }
+
+    /// Record `free_mb` reported by `name`, `tracked_mb` of which Timpani-O's
+    /// own placements do not yet account for.
+    pub fn report_free_memory(&self, name: &str, free_mb: u64, tracked_mb: u64) {
+        self.report_free_memory_at(name, free_mb, tracked_mb, Instant::now());
+    }
+
+    /// [`report_free_memory`](Self::report_free_memory) received at `at`.
+    pub fn report_free_memory_at(&self, name: &str, free_mb: u64, tracked_mb: u64, at: Instant) {
+        self.memory_reports.write().unwrap().insert(
+            name.to_string(),
+            MemoryReport {
+                free_mb,
+                tracked_mb,
+                at,
+            },
+        );
+    }
+
+    /// Memory budget of `name` now.  `None` if the node is not configured.
+    pub fn memory_budget(&self, name: &str) -> Option<MemoryBudget> {
+        self.memory_budget_at(name, Instant::now())
+    }
+
+    /// Memory budget of `name` as of `now`.
+    pub fn memory_budget_at(&self, name: &str, now: Instant) -> Option<MemoryBudget> {
+        let configured_mb = self.nodes.get(name)?.max_memory_mb;
+        let live_mb = self.live_memory_window.and_then(|window| {
+            let report = self.memory_reports.read().unwrap().get(name).copied();
+            match report {
+                Some(r) if now.saturating_duration_since(r.at) <= window => {
+                    Some(r.free_mb.saturating_add(r.tracked_mb))
+                }
+                Some(r) => {
+                    warn!(
+                        node = %name,
+                        age_secs = now.saturating_duration_since(r.at).as_secs(),
+                        "stale memory report — using configured max_memory_mb"
+                    );
+                    None
+                }
+                None => None,
+            }
+        });
+        Some(MemoryBudget {
+            configured_mb,
+            live_mb,
+        })
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+
+    fn manager() -> NodeConfigManager {
+        NodeConfigManager::from_nodes(vec![NodeConfig {
+            max_memory_mb: 4096,
+            ..NodeConfig::default_config("n1")
+        }])
+        .with_live_memory(Duration::from_secs(10))
+    }
+
+    #[test]
+    fn fresh_report_lowers_the_ceiling() {
+        let mgr = manager();
+        let t0 = Instant::now();
+        assert_eq!(mgr.memory_budget_at("n1", t0).unwrap().ceiling_mb(), 4096);
+
+        mgr.report_free_memory_at("n1", 1000, 500, t0);
+        let budget = mgr.memory_budget_at("n1", t0).unwrap();
+        assert_eq!(budget.live_mb, Some(1500));
+        assert_eq!(budget.ceiling_mb(), 1500);
+
+        // Plenty free: the configured maximum still caps admission.
+        mgr.report_free_memory_at("n1", 64_000, 0, t0);
+        assert_eq!(mgr.memory_budget_at("n1", t0).unwrap().ceiling_mb(), 4096);
+        assert!(mgr.memory_budget_at("n9", t0).is_none());
+    }
+
+    #[test]
+    fn stale_or_disabled_reports_fall_back_to_the_configured_value() {
+        let mgr = manager();
+        let t0 = Instant::now();
+        mgr.report_free_memory_at("n1", 100, 0, t0);
+        let later = t0 + Duration::from_secs(11);
+        let budget = mgr.memory_budget_at("n1", later).unwrap();
+        assert_eq!(budget.live_mb, None);
+        assert_eq!(budget.ceiling_mb(), 4096);
+
+        let off = NodeConfigManager::from_nodes(vec![NodeConfig::default_config("n1")]);
+        off.report_free_memory_at("n1", 100, 0, t0);
+        assert_eq!(off.memory_budget_at("n1", t0).unwrap().live_mb, None);
+    }
+}
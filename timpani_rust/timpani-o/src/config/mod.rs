@@ -13,19 +13,63 @@ SPDX-License-Identifier: MIT
 //! nodes:
 //!   node01:
 //!     available_cpus: [2, 3]
+//!     reserved_cpus: [0]            # optional, never used for placement
 //!     max_memory_mb: 4096
 //!     architecture: "aarch64"
 //!     location: "front_sensor_unit"
 //!     description: "Perception and sensor fusion node"
+//!     endpoint: "10.0.0.11:50054"   # optional, host:port of this node's Timpani-N
+//!     max_workloads: 2              # optional, distinct workloads the node may host
+//!     enabled: true                 # optional, false = cordoned (see `cordon`)
+//!     major_frame_us: 10000         # optional, with time_partitions
+//!     time_partitions:              # optional, see `partition`
+//!       - { offset_us: 0, duration_us: 5000 }
+//!     cpu_clusters: [[2, 3]]        # optional, CPUs sharing a cache, see `topology`
+//!     protocol: legacy_cxx          # optional, grpc (default) or legacy_cxx
 //! ```
+//!
+//! Nodes without an `endpoint` resolve to `<node name>:<default node port>`
+//! (the `--nodeport` value), i.e. the node name is used as the hostname.
+//!
+//! A CPU listed in both `available_cpus` and `reserved_cpus` is reserved:
+//! with [strict validation](NodeConfigManager::with_strict_validation) the
+//! file is rejected, otherwise the CPU is dropped from `available_cpus` with
+//! a warning.  Nodes whose bodies are identical (see
+//! [`NodeConfig::fingerprint`]) are warned about as a likely copy-pasted
+//! stanza.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Write;
+use std::net::SocketAddr;
 use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
-use anyhow::{Context, Result};
 use serde::Deserialize;
 use tracing::{debug, info, warn};
 
+use crate::inject::{FailureInjector, InjectionPoint};
+
+mod clock;
+mod cordon;
+mod diff;
+mod error;
+mod fingerprint;
+mod foreign;
+mod hotplug;
+mod memory;
+mod partition;
+mod topology;
+
+pub use clock::ClockReport;
+pub use diff::{diff, ConfigDiff, NodeConfigChange};
+pub use error::{ConfigError, ValidationIssue};
+pub(crate) use fingerprint::fnv1a;
+pub use hotplug::CpuTransition;
+pub use memory::{MemoryBudget, DEFAULT_MEMORY_REPORT_WINDOW};
+pub use partition::{TimePartitions, TimeWindow};
+
 // ── Private YAML deserialization types ────────────────────────────────────────
 
 /// Top-level wrapper that maps directly onto the YAML file layout.
@@ -45,6 +89,8 @@ struct NodeConfigFile {
 struct NodeConfigEntry {
     #[serde(default)]
     available_cpus: Vec<u32>,
+    #[serde(default)]
+    reserved_cpus: Vec<u32>,
     /// Maximum memory this node can allocate to tasks, in MB.
     /// Defaults to `u64::MAX` (unconstrained) when absent from YAML.
     #[serde(default = "default_max_memory_mb")]
@@ -52,6 +98,30 @@ struct NodeConfigEntry {
     architecture: Option<String>,
     location: Option<String>,
     description: Option<String>,
+    endpoint: Option<String>,
+    max_workloads: Option<usize>,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+    major_frame_us: Option<u64>,
+    #[serde(default)]
+    time_partitions: Vec<TimeWindow>,
+    #[serde(default)]
+    cpu_clusters: Vec<Vec<u32>>,
+    #[serde(default)]
+    protocol: NodeProtocol,
+}
+
+impl NodeConfigEntry {
+    /// The entry's partition windows; `Ok(None)` when it declares none.
+    fn partitions(&self) -> Result<Option<TimePartitions>, String> {
+        match (self.major_frame_us, self.time_partitions.is_empty()) {
+            (None, true) => Ok(None),
+            (Some(_), true) => Err("major_frame_us is set without time_partitions".into()),
+            (frame, false) => {
+                TimePartitions::new(frame.unwrap_or(0), self.time_partitions.clone()).map(Some)
+            }
+        }
+    }
 }
 
 /// Serde default for `max_memory_mb`: `u64::MAX` means "no constraint".
@@ -59,8 +129,34 @@ fn default_max_memory_mb() -> u64 {
     u64::MAX
 }
 
+/// Serde default for `enabled`: nodes take placements unless told not to.
+fn default_enabled() -> bool {
+    true
+}
+
 // ── Public data structures ────────────────────────────────────────────────────
 
+/// How a node's Timpani-N receives its schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeProtocol {
+    /// The gRPC `NodeService` of this port.
+    #[default]
+    Grpc,
+    /// The C++ Timpani-N's libtrpc payload (see [`crate::compat::cxx`]).
+    LegacyCxx,
+}
+
+impl NodeProtocol {
+    /// Canonical string form, as written in the YAML.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NodeProtocol::Grpc => "grpc",
+            NodeProtocol::LegacyCxx => "legacy_cxx",
+        }
+    }
+}
+
 /// Hardware specification and available resources for a single compute node.
 ///
 /// Mirrors the C++ `NodeConfig` struct in `node_config.h`.
@@ -68,12 +164,31 @@ fn default_max_memory_mb() -> u64 {
 pub struct NodeConfig {
     pub name: String,
     pub available_cpus: Vec<u32>,
+    /// CPUs held back from placement (e.g. for housekeeping).  Never in
+    /// `available_cpus` once loaded.
+    pub reserved_cpus: Vec<u32>,
     /// Maximum memory this node can allocate to tasks, in MB.
     /// `u64::MAX` means unconstrained (no YAML value supplied).
     pub max_memory_mb: u64,
     pub architecture: String,
     pub location: String,
     pub description: String,
+    /// `host:port` of this node's Timpani-N.  `None` = resolve from the node
+    /// name and the default node port (see
+    /// [`NodeConfigManager::resolve_endpoint`]).
+    pub endpoint: Option<String>,
+    /// Distinct workloads this node may host at once.  `None` = no limit.
+    pub max_workloads: Option<usize>,
+    /// `false` = cordoned unless an operator uncordons it (see
+    /// [`NodeConfigManager::is_cordoned`]).
+    pub enabled: bool,
+    /// Windows the node's CPUs are ours in.  `None` = all the time.
+    pub time_partitions: Option<TimePartitions>,
+    /// CPUs sharing a cache, one list per cluster (see [`topology`]).
+    /// Empty = topology unknown.
+    pub cpu_clusters: Vec<Vec<u32>>,
+    /// How the node's Timpani-N receives its schedule.
+    pub protocol: NodeProtocol,
 }
 
 impl NodeConfig {
@@ -85,10 +200,17 @@ impl NodeConfig {
         Self {
             name: name.into(),
             available_cpus: vec![0, 1, 2, 3],
+            reserved_cpus: Vec::new(),
             max_memory_mb: 4096_u64,
             architecture: String::from("aarch64"),
             location: String::from("default_location"),
             description: String::from("Default node configuration"),
+            endpoint: None,
+            max_workloads: None,
+            enabled: true,
+            time_partitions: None,
+            cpu_clusters: Vec::new(),
+            protocol: NodeProtocol::Grpc,
         }
     }
 
@@ -96,6 +218,86 @@ impl NodeConfig {
     pub fn cpu_count(&self) -> usize {
         self.available_cpus.len()
     }
+
+    /// Share of each CPU's time the node's tasks get: the
+    /// [duty cycle](TimePartitions::duty_cycle) of its partition windows,
+    /// 1.0 without them.
+    pub fn cpu_share(&self) -> f64 {
+        self.time_partitions
+            .as_ref()
+            .map_or(1.0, TimePartitions::duty_cycle)
+    }
+
+    /// The cluster `cpu` is in; `None` if it is in none.
+    pub fn cluster_of(&self, cpu: u32) -> Option<&[u32]> {
+        self.cpu_clusters
+            .iter()
+            .find(|c| c.contains(&cpu))
+            .map(Vec::as_slice)
+    }
+}
+
+/// CPU ids must be below this: affinity masks on the wire are `uint64`.
+pub const MAX_CPUS: u32 = 64;
+
+/// Port used for nodes without an explicit `endpoint` (the C++ default).
+pub const DEFAULT_NODE_PORT: u16 = 50054;
+
+/// Check that `endpoint` is `host:port` with an IP address or a valid DNS
+/// hostname and a non-zero port.
+fn validate_endpoint(endpoint: &str) -> Result<(), String> {
+    if let Ok(addr) = endpoint.parse::<SocketAddr>() {
+        if addr.port() == 0 {
+            return Err("port must be 1-65535".into());
+        }
+        return Ok(());
+    }
+    let Some((host, port)) = endpoint.rsplit_once(':') else {
+        return Err("expected host:port".into());
+    };
+    match port.parse::<u16>() {
+        Ok(p) if p != 0 => {}
+        _ => return Err(format!("port must be 1-65535, got '{port}'")),
+    }
+    let valid_label = |l: &str| {
+        !l.is_empty()
+            && l.len() <= 63
+            && !l.starts_with('-')
+            && !l.ends_with('-')
+            && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if host.len() > 253 || !host.split('.').all(valid_label) {
+        return Err(format!("'{host}' is not a valid hostname or IP address"));
+    }
+    Ok(())
+}
+
+/// CPUs `entry` lists as both available and reserved, sorted.
+fn reserved_overlap(entry: &NodeConfigEntry) -> Vec<u32> {
+    let reserved: BTreeSet<u32> = entry.reserved_cpus.iter().copied().collect();
+    let overlap: BTreeSet<u32> = entry
+        .available_cpus
+        .iter()
+        .copied()
+        .filter(|c| reserved.contains(c))
+        .collect();
+    overlap.into_iter().collect()
+}
+
+/// Replace the configuration file at `path` with `yaml` so that a crash
+/// leaves either the old or the new file: the content goes to a sibling
+/// temporary, is flushed to disk, then renamed into place.
+pub fn write_config_file(path: &Path, yaml: &str) -> Result<(), ConfigError> {
+    let io_err = |source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let mut file = File::create(&tmp).map_err(io_err)?;
+    file.write_all(yaml.as_bytes()).map_err(io_err)?;
+    file.sync_all().map_err(io_err)?;
+    fs::rename(&tmp, path).map_err(io_err)
 }
 
 // ── NodeConfigManager ─────────────────────────────────────────────────────────
@@ -108,6 +310,45 @@ pub struct NodeConfigManager {
 
     /// Set to `true` after a successful [`load_from_file`](Self::load_from_file).
     loaded: bool,
+
+    /// Nodes that reported missing RT privileges (runtime state, not YAML).
+    ///
+    /// Interior mutability because the manager is shared as
+    /// `Arc<NodeConfigManager>` and node capability reports arrive at runtime.
+    rt_excluded: RwLock<HashSet<String>>,
+
+    /// Last free-memory report per node (runtime state, see [`memory`]).
+    memory_reports: RwLock<HashMap<String, memory::MemoryReport>>,
+
+    /// Liveness window for memory reports.  `None` = live memory disabled.
+    live_memory_window: Option<Duration>,
+
+    /// Last online-CPU report per node (runtime state, see [`hotplug`]).
+    online_cpus: RwLock<HashMap<String, BTreeSet<u32>>>,
+
+    /// Last clock report per node (runtime state, see [`clock`]).
+    clock_reports: RwLock<HashMap<String, ClockReport>>,
+
+    /// Operator cordon overrides, node → cordoned (runtime state, see
+    /// [`cordon`]).
+    cordons: RwLock<HashMap<String, bool>>,
+
+    /// Last foreign-load report per node, CPU → utilisation (runtime
+    /// state, see [`foreign`]).
+    foreign_load: RwLock<HashMap<String, BTreeMap<u32, f64>>>,
+
+    /// Count foreign load against CPU headroom.
+    respect_foreign_load: bool,
+
+    /// Failure-injection hooks (no-op without the `testing` feature).
+    injector: Arc<FailureInjector>,
+
+    /// Port for nodes without an `endpoint`.  `None` = [`DEFAULT_NODE_PORT`].
+    default_node_port: Option<u16>,
+
+    /// Reject CPUs listed as both available and reserved instead of
+    /// reserving them with a warning.
+    strict_validation: bool,
 }
 
 impl NodeConfigManager {
@@ -116,6 +357,47 @@ impl NodeConfigManager {
         Self::default()
     }
 
+    /// Share `injector` (see [`crate::inject`]).
+    pub fn with_failure_injector(mut self, injector: Arc<FailureInjector>) -> Self {
+        self.injector = injector;
+        self
+    }
+
+    /// Port used to resolve nodes that have no `endpoint` (`--nodeport`).
+    pub fn with_default_node_port(mut self, port: u16) -> Self {
+        self.default_node_port = Some(port);
+        self
+    }
+
+    /// Reject a CPU listed in both `available_cpus` and `reserved_cpus`
+    /// (see the module docs) instead of reserving it with a warning.
+    pub fn with_strict_validation(mut self, strict: bool) -> Self {
+        self.strict_validation = strict;
+        self
+    }
+
+    /// An unloaded manager with this one's settings (default port, live
+    /// memory, strict validation, failure injector), runtime reports (RT
+    /// capability, free memory, online CPUs, clocks) and cordon overrides,
+    /// to load a replacement configuration into.
+    pub fn successor(&self) -> Self {
+        Self {
+            nodes: HashMap::new(),
+            loaded: false,
+            rt_excluded: RwLock::new(self.rt_excluded.read().unwrap().clone()),
+            memory_reports: RwLock::new(self.memory_reports.read().unwrap().clone()),
+            live_memory_window: self.live_memory_window,
+            online_cpus: RwLock::new(self.online_cpus.read().unwrap().clone()),
+            clock_reports: RwLock::new(self.clock_reports.read().unwrap().clone()),
+            cordons: RwLock::new(self.cordons.read().unwrap().clone()),
+            foreign_load: RwLock::new(self.foreign_load.read().unwrap().clone()),
+            respect_foreign_load: self.respect_foreign_load,
+            injector: Arc::clone(&self.injector),
+            default_node_port: self.default_node_port,
+            strict_validation: self.strict_validation,
+        }
+    }
+
     /// Parses `path` and populates the internal node map.
     ///
     /// * If the file contains no nodes a single `"default_node"` is inserted,
@@ -123,29 +405,126 @@ impl NodeConfigManager {
     /// * Calling this method a second time replaces all previously loaded nodes.
     ///
     /// # Errors
-    /// Returns an error if the file cannot be opened or if the YAML is
-    /// structurally invalid.
-    pub fn load_from_file(&mut self, path: &Path) -> Result<()> {
+    /// [`ConfigError::Io`] if the file cannot be read,
+    /// [`ConfigError::Parse`] if the YAML is structurally invalid, and
+    /// [`ConfigError::Validation`] listing every invalid node setting.  The
+    /// manager is left unloaded on error.
+    pub fn load_from_file(&mut self, path: &Path) -> Result<(), ConfigError> {
         info!("Loading node configuration from: {}", path.display());
 
         // Reset state before (re-)loading
         self.nodes.clear();
         self.loaded = false;
 
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Cannot open configuration file: {}", path.display()))?;
+        let content = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        self.injector.hit_blocking(InjectionPoint::ConfigReload)?;
+        self.load_from_str(&content, path)
+    }
+
+    /// Like [`load_from_file`](Self::load_from_file), for YAML already in
+    /// memory (e.g. pushed over gRPC).  `path` only names it in errors.
+    pub fn load_from_str(&mut self, content: &str, path: &Path) -> Result<(), ConfigError> {
+        self.nodes.clear();
+        self.loaded = false;
+
+        let file: NodeConfigFile =
+            serde_yaml::from_str(content).map_err(|e| ConfigError::parse(path.to_path_buf(), e))?;
 
-        let file: NodeConfigFile = serde_yaml::from_str(&content)
-            .with_context(|| format!("Failed to parse YAML file: {}", path.display()))?;
+        let mut issues: Vec<ValidationIssue> = file
+            .nodes
+            .iter()
+            .flat_map(|(name, entry)| {
+                let endpoint = entry.endpoint.as_deref().and_then(|ep| {
+                    let reason = validate_endpoint(ep).err()?;
+                    Some(format!("invalid endpoint '{ep}': {reason}"))
+                });
+                let max_workloads = (entry.max_workloads == Some(0))
+                    .then(|| "max_workloads must be at least 1".to_string());
+                let cpus = entry
+                    .available_cpus
+                    .iter()
+                    .find(|&&cpu| cpu >= MAX_CPUS)
+                    .map(|cpu| format!("CPU {cpu} is outside the {MAX_CPUS}-CPU affinity mask"));
+                let overlap = reserved_overlap(entry);
+                let reserved = (self.strict_validation && !overlap.is_empty()).then(|| {
+                    format!("CPUs {overlap:?} are listed in both available_cpus and reserved_cpus")
+                });
+                let partitions = entry.partitions().err();
+                let usable: Vec<u32> = entry
+                    .available_cpus
+                    .iter()
+                    .copied()
+                    .filter(|c| !entry.reserved_cpus.contains(c))
+                    .collect();
+                let clusters = topology::check_clusters(&entry.cpu_clusters, &usable).err();
+                [
+                    endpoint,
+                    max_workloads,
+                    cpus,
+                    reserved,
+                    partitions,
+                    clusters,
+                ]
+                .into_iter()
+                .flatten()
+                .map(|message| ValidationIssue {
+                    node: name.clone(),
+                    message,
+                })
+            })
+            .collect();
+        if !issues.is_empty() {
+            issues.sort_by(|a, b| a.node.cmp(&b.node));
+            return Err(ConfigError::Validation {
+                path: path.to_path_buf(),
+                issues,
+            });
+        }
 
-        for (name, entry) in file.nodes {
+        for (name, mut entry) in file.nodes {
+            let overlap = reserved_overlap(&entry);
+            if !overlap.is_empty() {
+                warn!(
+                    node = %name,
+                    cpus = ?overlap,
+                    "CPUs listed in both available_cpus and reserved_cpus — treating them as reserved"
+                );
+                entry.available_cpus.retain(|c| !overlap.contains(c));
+            }
+            if entry.endpoint.is_none() {
+                info!(
+                    node = %name,
+                    port = self.default_node_port.unwrap_or(DEFAULT_NODE_PORT),
+                    "no endpoint configured — using node name as hostname"
+                );
+            }
+            let time_partitions = entry.partitions().ok().flatten();
+            if let Some(p) = &time_partitions {
+                info!(
+                    node = %name,
+                    major_frame_us = p.major_frame_us,
+                    windows = p.windows.len(),
+                    cpu_share = p.duty_cycle(),
+                    "time-partitioned node — per-CPU capacity scaled to its windows"
+                );
+            }
             let node = NodeConfig {
                 name: name.clone(),
                 available_cpus: entry.available_cpus,
+                reserved_cpus: entry.reserved_cpus,
                 max_memory_mb: entry.max_memory_mb,
                 architecture: entry.architecture.unwrap_or_default(),
                 location: entry.location.unwrap_or_default(),
                 description: entry.description.unwrap_or_default(),
+                endpoint: entry.endpoint,
+                max_workloads: entry.max_workloads,
+                enabled: entry.enabled,
+                time_partitions,
+                cpu_clusters: entry.cpu_clusters,
+                protocol: entry.protocol,
             };
 
             debug!(
@@ -169,6 +548,13 @@ impl NodeConfigManager {
 
         self.loaded = true;
 
+        for group in self.duplicate_nodes() {
+            warn!(
+                nodes = ?group,
+                "nodes have identical configurations — likely a copy-pasted stanza"
+            );
+        }
+
         info!(
             "Successfully loaded {} node configuration(s):",
             self.nodes.len()
@@ -212,28 +598,75 @@ impl NodeConfigManager {
             .unwrap_or_else(|| vec![0, 1, 2, 3])
     }
 
+    /// [`NodeConfig::cpu_share`] of `name`; 1.0 if it is not configured.
+    pub fn cpu_share(&self, name: &str) -> f64 {
+        self.nodes.get(name).map_or(1.0, NodeConfig::cpu_share)
+    }
+
+    /// `host:port` for reaching node `name`: its configured `endpoint`, or
+    /// `<name>:<default node port>` when absent.  `None` if the node is not
+    /// configured.
+    pub fn resolve_endpoint(&self, name: &str) -> Option<String> {
+        let node = self.nodes.get(name)?;
+        Some(node.endpoint.clone().unwrap_or_else(|| {
+            format!(
+                "{name}:{}",
+                self.default_node_port.unwrap_or(DEFAULT_NODE_PORT)
+            )
+        }))
+    }
+
     /// Returns `true` after a successful call to [`load_from_file`](Self::load_from_file).
     ///
     /// Mirrors `NodeConfigManager::IsLoaded()`.
     pub fn is_loaded(&self) -> bool {
         self.loaded
     }
+
+    /// Record whether `name` can run SCHED_FIFO / SCHED_RR tasks, as reported
+    /// by the node's capability pre-flight.  Nodes are RT-capable until told
+    /// otherwise.
+    pub fn set_rt_capable(&self, name: &str, capable: bool) {
+        let mut excluded = self.rt_excluded.write().unwrap();
+        if capable {
+            excluded.remove(name);
+        } else {
+            warn!(node = %name, "node lacks RT privileges — excluded from RT placements");
+            excluded.insert(name.to_string());
+        }
+    }
+
+    /// `false` if the node reported missing RT privileges.
+    pub fn is_rt_capable(&self, name: &str) -> bool {
+        !self.rt_excluded.read().unwrap().contains(name)
+    }
 }
 
-// ── Test helpers ──────────────────────────────────────────────────────────────
+// ── Direct construction ───────────────────────────────────────────────────────
 
-#[cfg(test)]
 impl NodeConfigManager {
     /// Construct a `NodeConfigManager` directly from a list of `NodeConfig` values.
     ///
-    /// Only available in test builds.  Use [`load_from_file`](Self::load_from_file)
-    /// in production.  This avoids the need for a temp file in unit tests that
-    /// require a populated node configuration.
+    /// The nodes are taken as they are, without the validation of
+    /// [`load_from_file`](Self::load_from_file), which the daemon uses.  For
+    /// nodes that are already validated (e.g. [`crate::admission::quick_check`])
+    /// and for tests that need a populated configuration without a temp file.
     pub fn from_nodes(nodes: Vec<NodeConfig>) -> Self {
         let nodes_map = nodes.into_iter().map(|n| (n.name.clone(), n)).collect();
         Self {
             nodes: nodes_map,
             loaded: true,
+            rt_excluded: RwLock::default(),
+            memory_reports: RwLock::default(),
+            live_memory_window: None,
+            online_cpus: RwLock::default(),
+            clock_reports: RwLock::default(),
+            cordons: RwLock::default(),
+            foreign_load: RwLock::default(),
+            respect_foreign_load: false,
+            injector: Arc::default(),
+            default_node_port: None,
+            strict_validation: false,
         }
     }
 }
@@ -335,6 +768,7 @@ nodes:
         assert_eq!(node.max_memory_mb, u64::MAX); // default = unconstrained
         assert_eq!(node.architecture, ""); // default (empty)
         assert_eq!(node.location, ""); // default (empty)
+        assert!(node.enabled);
     }
 
     #[test]
@@ -354,6 +788,13 @@ nodes:
         let result = mgr.load_from_file(Path::new("/nonexistent/path/config.yaml"));
         assert!(result.is_err());
         assert!(!mgr.is_loaded());
+        match result.unwrap_err() {
+            ConfigError::Io { path, source } => {
+                assert_eq!(path, Path::new("/nonexistent/path/config.yaml"));
+                assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+            }
+            other => panic!("expected Io, got {other:?}"),
+        }
     }
 
     #[test]
@@ -363,6 +804,94 @@ nodes:
         let result = mgr.load_from_file(f.path());
         assert!(result.is_err());
         assert!(!mgr.is_loaded());
+        let err = result.unwrap_err();
+        assert!(matches!(err, ConfigError::Parse { .. }), "{err:?}");
+        assert!(err.to_string().starts_with("Failed to parse YAML file: "));
+    }
+
+    #[test]
+    fn parse_error_carries_the_line_number() {
+        let f =
+            yaml_tempfile("nodes:\n  n1:\n    available_cpus: [0, 1]\n    max_memory_mb: lots\n");
+        let err = NodeConfigManager::new()
+            .load_from_file(f.path())
+            .unwrap_err();
+        match err {
+            ConfigError::Parse { line, column, .. } => {
+                assert_eq!(line, Some(4));
+                assert!(column.is_some());
+            }
+            other => panic!("expected Parse, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn every_invalid_node_is_reported() {
+        let yaml = "nodes:\n\
+                    \x20 b:\n    available_cpus: [0]\n    endpoint: \"b:0\"\n\
+                    \x20 ok:\n    available_cpus: [0]\n    endpoint: \"ok:50054\"\n\
+                    \x20 a:\n    available_cpus: [0]\n    endpoint: \"a\"\n";
+        let f = yaml_tempfile(yaml);
+        let mut mgr = NodeConfigManager::new();
+        let err = mgr.load_from_file(f.path()).unwrap_err();
+        assert!(!mgr.is_loaded());
+        match &err {
+            ConfigError::Validation { issues, .. } => {
+                let nodes: Vec<&str> = issues.iter().map(|i| i.node.as_str()).collect();
+                assert_eq!(nodes, ["a", "b"]);
+            }
+            other => panic!("expected Validation, got {other:?}"),
+        }
+        assert!(err
+            .to_string()
+            .contains("Node 'a': invalid endpoint 'a': expected host:port"));
+    }
+
+    #[test]
+    fn cpu_ids_beyond_the_affinity_mask_are_rejected() {
+        let f = yaml_tempfile("nodes:\n  n1:\n    available_cpus: [2, 64]\n");
+        let mut mgr = NodeConfigManager::new();
+        let err = mgr.load_from_file(f.path()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("CPU 64 is outside the 64-CPU affinity mask"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn cpus_both_available_and_reserved_are_reserved_unless_strict() {
+        let yaml = "nodes:\n  n1:\n    available_cpus: [0, 1, 2, 3]\n    reserved_cpus: [3, 0]\n";
+        let f = yaml_tempfile(yaml);
+
+        let mut lenient = NodeConfigManager::new();
+        lenient.load_from_file(f.path()).unwrap();
+        let n1 = lenient.get_node_config("n1").unwrap();
+        assert_eq!(n1.available_cpus, [1, 2]);
+        assert_eq!(n1.reserved_cpus, [3, 0]);
+
+        let mut strict = NodeConfigManager::new().with_strict_validation(true);
+        let err = strict.load_from_file(f.path()).unwrap_err();
+        assert!(!strict.is_loaded());
+        assert!(
+            err.to_string().contains(
+                "Node 'n1': CPUs [0, 3] are listed in both available_cpus and reserved_cpus"
+            ),
+            "{err}"
+        );
+        // Strictness survives a reload.
+        let mut reloaded = strict.successor();
+        assert!(reloaded.load_from_file(f.path()).is_err());
+    }
+
+    #[test]
+    fn copy_pasted_stanzas_load_but_are_detected() {
+        let stanza = "    available_cpus: [2, 3]\n    location: \"front\"\n";
+        let yaml = format!("nodes:\n  a:\n{stanza}  b:\n{stanza}  c:\n    available_cpus: [2]\n");
+        let f = yaml_tempfile(&yaml);
+        let mut mgr = NodeConfigManager::new().with_strict_validation(true);
+        mgr.load_from_file(f.path()).unwrap();
+        assert_eq!(mgr.duplicate_nodes(), [["a", "b"]]);
     }
 
     // ── NodeConfigManager: get_available_cpus ─────────────────────────────────
@@ -406,4 +935,208 @@ nodes:
         assert!(mgr.get_node_config("n1").is_none(), "old node must be gone");
         assert!(mgr.get_node_config("n2").is_some());
     }
+
+    #[test]
+    fn rt_capability_defaults_to_true_and_can_be_toggled() {
+        let mgr = NodeConfigManager::from_nodes(vec![NodeConfig::default_config("n1")]);
+        assert!(mgr.is_rt_capable("n1"));
+        mgr.set_rt_capable("n1", false);
+        assert!(!mgr.is_rt_capable("n1"));
+        mgr.set_rt_capable("n1", true);
+        assert!(mgr.is_rt_capable("n1"));
+    }
+
+    // ── NodeConfigManager: endpoints ──────────────────────────────────────────
+
+    #[test]
+    fn endpoint_is_parsed_and_missing_one_falls_back_to_node_name() {
+        let yaml = r#"
+nodes:
+  ecu_front:
+    available_cpus: [0]
+    endpoint: "10.0.0.11:6000"
+  ecu_rear:
+    available_cpus: [0]
+    endpoint: "ecu-rear.local:6001"
+  ecu_v6:
+    available_cpus: [0]
+    endpoint: "[fd00::2]:6002"
+  ecu_plain:
+    available_cpus: [0]
+"#;
+        let f = yaml_tempfile(yaml);
+        let mut mgr = NodeConfigManager::new().with_default_node_port(7000);
+        mgr.load_from_file(f.path()).unwrap();
+
+        let ep = |n| mgr.resolve_endpoint(n).unwrap();
+        assert_eq!(ep("ecu_front"), "10.0.0.11:6000");
+        assert_eq!(ep("ecu_rear"), "ecu-rear.local:6001");
+        assert_eq!(ep("ecu_v6"), "[fd00::2]:6002");
+        assert_eq!(ep("ecu_plain"), "ecu_plain:7000");
+        assert_eq!(mgr.resolve_endpoint("unknown"), None);
+    }
+
+    #[test]
+    fn fallback_uses_default_node_port_when_not_set() {
+        let mgr = NodeConfigManager::from_nodes(vec![NodeConfig::default_config("n1")]);
+        assert_eq!(
+            mgr.resolve_endpoint("n1").unwrap(),
+            format!("n1:{DEFAULT_NODE_PORT}")
+        );
+    }
+
+    #[test]
+    fn each_node_reaches_its_own_server_and_the_rest_the_default_port() {
+        use std::io::{Read, Write};
+        use std::net::{TcpListener, TcpStream};
+
+        // Two node-specific servers and one on the default port.
+        let server = || TcpListener::bind("127.0.0.1:0").unwrap();
+        let (front, rear, default) = (server(), server(), server());
+        let port = |l: &TcpListener| l.local_addr().unwrap().port();
+        let yaml = format!(
+            "nodes:\n\
+             \x20 ecu_front:\n    available_cpus: [0]\n    endpoint: \"127.0.0.1:{}\"\n\
+             \x20 ecu_rear:\n    available_cpus: [0]\n    endpoint: \"127.0.0.1:{}\"\n\
+             \x20 localhost:\n    available_cpus: [0]\n",
+            port(&front),
+            port(&rear)
+        );
+        let f = yaml_tempfile(&yaml);
+        let mut mgr = NodeConfigManager::new().with_default_node_port(port(&default));
+        mgr.load_from_file(f.path()).unwrap();
+
+        for node in ["ecu_front", "ecu_rear", "localhost"] {
+            let mut conn = TcpStream::connect(mgr.resolve_endpoint(node).unwrap()).unwrap();
+            conn.write_all(node.as_bytes()).unwrap();
+        }
+        let received = |l: &TcpListener| {
+            let mut name = String::new();
+            l.accept().unwrap().0.read_to_string(&mut name).unwrap();
+            name
+        };
+        assert_eq!(received(&front), "ecu_front");
+        assert_eq!(received(&rear), "ecu_rear");
+        assert_eq!(received(&default), "localhost");
+    }
+
+    #[test]
+    fn invalid_endpoints_are_rejected_at_load() {
+        for bad in [
+            "10.0.0.1",
+            "10.0.0.1:0",
+            "host:99999",
+            "bad_host:50054",
+            "-host:50054",
+            ":50054",
+        ] {
+            let yaml = format!("nodes:\n  n1:\n    available_cpus: [0]\n    endpoint: \"{bad}\"\n");
+            let f = yaml_tempfile(&yaml);
+            let err = NodeConfigManager::new()
+                .load_from_file(f.path())
+                .unwrap_err();
+            assert!(
+                format!("{err:#}").contains("invalid endpoint"),
+                "{bad}: {err:#}"
+            );
+        }
+    }
+
+    #[test]
+    fn zero_max_workloads_is_rejected_at_load() {
+        let f = yaml_tempfile("nodes:\n  n1:\n    available_cpus: [0]\n    max_workloads: 0\n");
+        let err = NodeConfigManager::new()
+            .load_from_file(f.path())
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("Node 'n1': max_workloads must be at least 1"));
+    }
+
+    #[test]
+    fn time_partitions_load_and_scale_the_cpu_share() {
+        let yaml = "nodes:\n  p:\n    available_cpus: [0]\n    major_frame_us: 10000\n\
+                    \x20   time_partitions:\n\
+                    \x20     - { offset_us: 5000, duration_us: 2500 }\n\
+                    \x20     - { offset_us: 0, duration_us: 2500 }\n\
+                    \x20 plain:\n    available_cpus: [0]\n";
+        let f = yaml_tempfile(yaml);
+        let mut mgr = NodeConfigManager::new();
+        mgr.load_from_file(f.path()).unwrap();
+        let p = mgr.get_node_config("p").unwrap();
+        let partitions = p.time_partitions.as_ref().unwrap();
+        assert_eq!(partitions.major_frame_us, 10_000);
+        assert_eq!(partitions.windows[0].offset_us, 0);
+        assert_eq!(mgr.cpu_share("p"), 0.5);
+        assert_eq!(mgr.cpu_share("plain"), 1.0);
+        assert_ne!(
+            p.fingerprint(),
+            mgr.get_node_config("plain").unwrap().fingerprint()
+        );
+    }
+
+    #[test]
+    fn cpu_clusters_load_and_are_checked_against_available_cpus() {
+        let yaml = "nodes:\n  n1:\n    available_cpus: [2, 3, 4, 5]\n\
+                    \x20   cpu_clusters: [[2, 3], [4, 5]]\n";
+        let f = yaml_tempfile(yaml);
+        let mut mgr = NodeConfigManager::new();
+        mgr.load_from_file(f.path()).unwrap();
+        let n1 = mgr.get_node_config("n1").unwrap();
+        assert_eq!(n1.cluster_of(5), Some(&[4, 5][..]));
+        assert_eq!(n1.cluster_of(0), None);
+
+        let f = yaml_tempfile(
+            "nodes:\n  n1:\n    available_cpus: [2, 3]\n    reserved_cpus: [3]\n\
+             \x20   cpu_clusters: [[2, 3]]\n",
+        );
+        let err = NodeConfigManager::new()
+            .load_from_file(f.path())
+            .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("Node 'n1': CPU 3 in cpu_clusters is not an available CPU"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn protocol_defaults_to_grpc_and_loads_legacy_cxx() {
+        let yaml = "nodes:\n  old:\n    available_cpus: [0]\n    protocol: legacy_cxx\n\
+                    \x20 new:\n    available_cpus: [0]\n";
+        let f = yaml_tempfile(yaml);
+        let mut mgr = NodeConfigManager::new();
+        mgr.load_from_file(f.path()).unwrap();
+        let old = mgr.get_node_config("old").unwrap();
+        let new = mgr.get_node_config("new").unwrap();
+        assert_eq!(old.protocol, NodeProtocol::LegacyCxx);
+        assert_eq!(new.protocol, NodeProtocol::Grpc);
+        assert_ne!(old.fingerprint(), new.fingerprint());
+
+        let f = yaml_tempfile("nodes:\n  n1:\n    available_cpus: [0]\n    protocol: dbus\n");
+        assert!(NodeConfigManager::new().load_from_file(f.path()).is_err());
+    }
+
+    #[test]
+    fn invalid_time_partitions_are_rejected_at_load() {
+        for (body, expected) in [
+            (
+                "    time_partitions:\n      - { offset_us: 0, duration_us: 10 }\n",
+                "time_partitions need a non-zero major_frame_us",
+            ),
+            (
+                "    major_frame_us: 1000\n",
+                "major_frame_us is set without time_partitions",
+            ),
+            (
+                "    major_frame_us: 1000\n    time_partitions:\n\
+                 \x20     - { offset_us: 0, duration_us: 600 }\n\
+                 \x20     - { offset_us: 500, duration_us: 100 }\n",
+                "time partitions at 0 µs and 500 µs overlap",
+            ),
+        ] {
+            let f = yaml_tempfile(&format!("nodes:\n  n1:\n    available_cpus: [0]\n{body}"));
+            let err = NodeConfigManager::new()
+                .load_from_file(f.path())
+                .unwrap_err();
+            assert!(format!("{err:#}").contains(expected), "{err:#}");
+        }
+    }
 }
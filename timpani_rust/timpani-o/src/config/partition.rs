@@ -0,0 +1,132 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Time partitioning: nodes whose CPUs Timpani-O's tasks only get in
+//! declared windows.
+//!
+//! A node behind a partitioned (ARINC 653-style) hypervisor runs our tasks
+//! only during fixed windows that repeat every major frame:
+//!
+//! ```yaml
+//! nodes:
+//!   supplier01:
+//!     available_cpus: [0, 1]
+//!     major_frame_us: 10000
+//!     time_partitions:
+//!       - { offset_us: 0, duration_us: 3000 }
+//!       - { offset_us: 5000, duration_us: 2000 }
+//! ```
+//!
+//! Windows must be non-empty, end within the major frame and not overlap.
+//! The share of the frame they cover — the [duty
+//! cycle](TimePartitions::duty_cycle), 50 % above — scales every per-CPU
+//! limit on the node: admission, the aggregate capacity pre-check, the
+//! capacity report and the Liu & Layland bound.  A 90 % threshold on that
+//! node admits at most 45 % per CPU.
+//!
+//! The windows go to the node with its schedule
+//! (`NodeSchedResponse.time_partitions`) so that releases can be aligned
+//! with them.  Response-time analysis and simulation do not model the
+//! windows.
+
+use serde::{Deserialize, Serialize};
+
+/// One window of CPU time within the major frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    /// Start, from the beginning of the major frame.
+    pub offset_us: u64,
+    pub duration_us: u64,
+}
+
+impl TimeWindow {
+    /// First microsecond after the window.
+    pub fn end_us(&self) -> u64 {
+        self.offset_us.saturating_add(self.duration_us)
+    }
+}
+
+/// A node's partition windows and the frame they repeat in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimePartitions {
+    pub major_frame_us: u64,
+    /// Sorted by `offset_us`.
+    pub windows: Vec<TimeWindow>,
+}
+
+impl TimePartitions {
+    /// Sort `windows` and check them against `major_frame_us` (see the
+    /// module docs).  The error names the first problem found.
+    pub fn new(major_frame_us: u64, mut windows: Vec<TimeWindow>) -> Result<Self, String> {
+        if major_frame_us == 0 {
+            return Err("time_partitions need a non-zero major_frame_us".into());
+        }
+        windows.sort_by_key(|w| w.offset_us);
+        for w in &windows {
+            if w.duration_us == 0 {
+                return Err(format!("time partition at {} µs is empty", w.offset_us));
+            }
+            if w.end_us() > major_frame_us {
+                return Err(format!(
+                    "time partition at {} µs ends at {} µs, after the {major_frame_us} µs major frame",
+                    w.offset_us,
+                    w.end_us()
+                ));
+            }
+        }
+        if let Some(pair) = windows.windows(2).find(|p| p[0].end_us() > p[1].offset_us) {
+            return Err(format!(
+                "time partitions at {} µs and {} µs overlap",
+                pair[0].offset_us, pair[1].offset_us
+            ));
+        }
+        Ok(Self {
+            major_frame_us,
+            windows,
+        })
+    }
+
+    /// Share of the major frame the windows cover, in `(0, 1]`.
+    pub fn duty_cycle(&self) -> f64 {
+        let busy: u64 = self.windows.iter().map(|w| w.duration_us).sum();
+        busy as f64 / self.major_frame_us as f64
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn w(offset_us: u64, duration_us: u64) -> TimeWindow {
+        TimeWindow {
+            offset_us,
+            duration_us,
+        }
+    }
+
+    #[test]
+    fn windows_are_sorted_and_their_share_is_the_duty_cycle() {
+        let p = TimePartitions::new(10_000, vec![w(5_000, 2_000), w(0, 3_000)]).unwrap();
+        assert_eq!(p.windows, [w(0, 3_000), w(5_000, 2_000)]);
+        assert_eq!(p.duty_cycle(), 0.5);
+        // Back to back is not an overlap, and the frame may be filled.
+        let full = TimePartitions::new(10_000, vec![w(0, 4_000), w(4_000, 6_000)]).unwrap();
+        assert_eq!(full.duty_cycle(), 1.0);
+    }
+
+    #[test]
+    fn invalid_windows_are_rejected() {
+        let err = |frame, windows| TimePartitions::new(frame, windows).unwrap_err();
+        assert!(err(0, vec![w(0, 1)]).contains("major_frame_us"));
+        assert!(err(10_000, vec![w(2_000, 0)]).contains("empty"));
+        assert!(err(10_000, vec![w(8_000, 3_000)]).contains("after the 10000 µs major frame"));
+        assert_eq!(
+            err(10_000, vec![w(4_000, 2_000), w(0, 5_000)]),
+            "time partitions at 0 µs and 4000 µs overlap"
+        );
+    }
+}
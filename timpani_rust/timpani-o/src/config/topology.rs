@@ -0,0 +1,72 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! CPU topology: which of a node's CPUs share a cache.
+//!
+//! ```yaml
+//! nodes:
+//!   node01:
+//!     available_cpus: [2, 3, 4, 5]
+//!     cpu_clusters: [[2, 3], [4, 5]]
+//! ```
+//!
+//! Each cluster lists CPUs that share an L2 cache.  Its CPUs must be
+//! available (listed in `available_cpus` and not reserved), and no CPU may
+//! be in two clusters; a CPU in none is fine.  The clusters only steer
+//! where tasks of one cache affinity group go on the node (see
+//! [`crate::scheduler::cache_group`]): a node without them places such
+//! tasks as if they had no group.
+
+use std::collections::BTreeSet;
+
+/// Check `clusters` against the node's `available` CPUs (see the module
+/// docs).  The error names the first problem found.
+pub(super) fn check_clusters(clusters: &[Vec<u32>], available: &[u32]) -> Result<(), String> {
+    let mut seen = BTreeSet::new();
+    for cluster in clusters {
+        if cluster.is_empty() {
+            return Err("cpu_clusters lists an empty cluster".into());
+        }
+        for &cpu in cluster {
+            if !available.contains(&cpu) {
+                return Err(format!("CPU {cpu} in cpu_clusters is not an available CPU"));
+            }
+            if !seen.insert(cpu) {
+                return Err(format!("CPU {cpu} is in more than one of cpu_clusters"));
+            }
+        }
+    }
+    Ok(())
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clusters_must_be_disjoint_sets_of_available_cpus() {
+        let available = [2, 3, 4, 5];
+        assert!(check_clusters(&[vec![2, 3], vec![4, 5]], &available).is_ok());
+        // A CPU outside every cluster is fine, as is no topology at all.
+        assert!(check_clusters(&[vec![2, 3]], &available).is_ok());
+        assert!(check_clusters(&[], &available).is_ok());
+
+        let err = |clusters: &[Vec<u32>]| check_clusters(clusters, &available).unwrap_err();
+        assert_eq!(
+            err(&[vec![2, 3], vec![]]),
+            "cpu_clusters lists an empty cluster"
+        );
+        assert_eq!(
+            err(&[vec![2, 6]]),
+            "CPU 6 in cpu_clusters is not an available CPU"
+        );
+        assert_eq!(
+            err(&[vec![2, 3], vec![3, 4]]),
+            "CPU 3 is in more than one of cpu_clusters"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Time-window suppression of repeated notifications.
+//!
+//! Advisory events are cheap to generate but noisy: re-submitting the same
+//! marginal workload would otherwise send Pullpiri the same feasibility
+//! advisory every time.  [`Debouncer`] lets the first event for a key through
+//! and drops repeats until `window` has elapsed.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default suppression window for feasibility advisories.
+pub const DEFAULT_ADVISORY_WINDOW: Duration = Duration::from_secs(60);
+
+/// Suppresses repeats of the same key within a fixed time window.
+///
+/// Thread-safe; the internal lock is only held for a map lookup/insert.
+#[derive(Debug)]
+pub struct Debouncer {
+    window: Duration,
+    last_sent: Mutex<HashMap<String, Instant>>,
+}
+
+impl Debouncer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_sent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns `true` if an event for `key` should be sent now, and records
+    /// it.  Returns `false` if one was sent less than `window` ago.
+    ///
+    /// Expired keys are pruned on every call, so the map only ever holds keys
+    /// seen within the last `window`.
+    pub fn should_send(&self, key: &str) -> bool {
+        self.should_send_at(key, Instant::now())
+    }
+
+    /// [`should_send`](Self::should_send) at `now`.
+    pub fn should_send_at(&self, key: &str, now: Instant) -> bool {
+        let mut last = self.last_sent.lock().unwrap();
+        last.retain(|_, t| now.duration_since(*t) < self.window);
+
+        if last.contains_key(key) {
+            return false;
+        }
+        last.insert(key.to_string(), now);
+        true
+    }
+
+    /// Drop every key starting with `prefix`, e.g. those of a removed
+    /// workload, so its successor is not suppressed.  Returns how many were
+    /// dropped.
+    pub fn forget_prefix(&self, prefix: &str) -> usize {
+        let mut last = self.last_sent.lock().unwrap();
+        let before = last.len();
+        last.retain(|key, _| !key.starts_with(prefix));
+        before - last.len()
+    }
+
+    /// Number of keys currently suppressed.
+    pub fn len(&self) -> usize {
+        self.last_sent.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for Debouncer {
+    fn default() -> Self {
+        Self::new(DEFAULT_ADVISORY_WINDOW)
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockClock;
+
+    #[test]
+    fn first_event_passes_repeat_is_suppressed() {
+        let d = Debouncer::new(Duration::from_secs(60));
+        assert!(d.should_send("wl/n1/0"));
+        assert!(!d.should_send("wl/n1/0"));
+        assert!(d.should_send("wl/n1/1"), "different key is independent");
+    }
+
+    #[test]
+    fn forgotten_keys_pass_again() {
+        let d = Debouncer::new(Duration::from_secs(60));
+        assert!(d.should_send("t/wl/n1/0"));
+        assert!(d.should_send("t/wl/n1/1"));
+        assert!(d.should_send("t/wl2/n1/0"));
+        assert_eq!(d.forget_prefix("t/wl/"), 2);
+        assert_eq!(d.len(), 1);
+        assert!(d.should_send("t/wl/n1/0"));
+        assert!(!d.should_send("t/wl2/n1/0"));
+    }
+
+    #[test]
+    fn zero_window_never_suppresses() {
+        let d = Debouncer::new(Duration::ZERO);
+        assert!(d.should_send("k"));
+        assert!(d.should_send("k"));
+    }
+
+    #[test]
+    fn key_passes_again_after_window() {
+        let clock = MockClock::new();
+        let d = Debouncer::new(Duration::from_secs(60));
+        assert!(d.should_send_at("k", clock.now()));
+        assert!(!d.should_send_at("k", clock.advance(Duration::from_secs(59))));
+        assert!(d.should_send_at("k", clock.advance(Duration::from_secs(1))));
+    }
+}
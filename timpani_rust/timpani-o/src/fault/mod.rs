@@ -15,6 +15,14 @@ SPDX-License-Identifier: MIT
 //! so the singleton pattern is unnecessary.  `FaultClient` is injected as
 //! `Arc<dyn FaultNotifier>` wherever it is needed.  This makes the component
 //! testable without a live Pullpiri server.
+//!
+//! # Severity
+//!
+//! Deadline misses are sent as `CRITICAL`.  Post-schedule feasibility
+//! warnings are sent as `ADVISORY` with a [`FeasibilityInfo`] payload, and are
+//! rate-limited per key by [`debounce::Debouncer`].
+
+pub mod debounce;
 
 use std::sync::Arc;
 
@@ -22,9 +30,11 @@ use thiserror::Error;
 use tonic::transport::Channel;
 use tracing::info;
 
+use crate::metadata::Metadata;
 use crate::proto::schedinfo_v1::{
     fault_service_client::FaultServiceClient as ProtoFaultClient, FaultInfo, FaultType,
 };
+pub use crate::proto::schedinfo_v1::{FaultSeverity, FeasibilityInfo};
 
 // ── FaultNotification ─────────────────────────────────────────────────────────
 
@@ -35,12 +45,18 @@ pub struct FaultNotification {
     pub node_id: String,
     pub task_name: String,
     pub fault_type: FaultType,
+    pub severity: FaultSeverity,
+    /// Present only for `FaultType::Feasibility`.
+    pub feasibility: Option<FeasibilityInfo>,
+    /// The task's forwarded metadata keys (see [`crate::metadata`]).
+    pub metadata: Metadata,
 }
 
 // ── FaultError ────────────────────────────────────────────────────────────────
 
 /// Errors that can occur when notifying Pullpiri of a fault.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum FaultError {
     /// tonic channel / endpoint construction failure.
     #[error("transport error: {0}")]
@@ -100,12 +116,17 @@ impl FaultNotifier for FaultClient {
             node_id: info.node_id.clone(),
             task_name: info.task_name.clone(),
             r#type: info.fault_type as i32,
+            severity: info.severity as i32,
+            feasibility: info.feasibility.clone(),
+            metadata: info.metadata.clone().into_iter().collect(),
         };
 
         info!(
             workload_id = %info.workload_id,
             node_id     = %info.node_id,
             task_name   = %info.task_name,
+            fault_type  = ?info.fault_type,
+            severity    = ?info.severity,
             "Notifying Pullpiri of fault"
         );
 
@@ -176,6 +197,9 @@ mod tests {
             node_id: "node01".into(),
             task_name: "task_safety".into(),
             fault_type: FaultType::Dmiss,
+            severity: FaultSeverity::Critical,
+            feasibility: None,
+            metadata: Default::default(),
         }
     }
 
@@ -0,0 +1,341 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Client side of `AdminService`, used by `timpani-o node` and
+//! `timpani-o log`.
+
+use std::time::Duration;
+
+use thiserror::Error;
+use tonic::transport::{Channel, Endpoint};
+
+use crate::proto::schedinfo_v1::{
+    admin_service_client::AdminServiceClient, CompactionRef, CompactionResult, CordonResult,
+    DrainRequest, DrainResult, LogFilterQuery, LogFilterResult, LogFilterUpdate, NodeRef,
+    WhatIfResult,
+};
+
+/// Connect timeout — an operator at a shell should not wait for TCP retries.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Tasks `timpani-o node drain` moves per `DrainNode` call by default.
+pub const DEFAULT_DRAIN_BATCH_SIZE: u32 = 8;
+
+/// Why an admin call failed.  `Display` is a single line suitable for a CLI.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum AdminError {
+    #[error("invalid address '{0}'")]
+    InvalidAddress(String),
+
+    #[error("cannot connect to Timpani-O at {addr}: {source}")]
+    Connect {
+        addr: String,
+        #[source]
+        source: tonic::transport::Error,
+    },
+
+    #[error("{}", .0.message())]
+    Rpc(#[from] tonic::Status),
+}
+
+/// A connection to the AdminService at one address.
+#[derive(Debug, Clone)]
+pub struct AdminClient {
+    inner: AdminServiceClient<Channel>,
+}
+
+impl AdminClient {
+    /// Connect to the AdminService at `addr` (e.g. `http://127.0.0.1:50055`).
+    pub async fn connect(addr: &str) -> Result<Self, AdminError> {
+        let endpoint = Endpoint::from_shared(addr.to_string())
+            .map_err(|_| AdminError::InvalidAddress(addr.to_string()))?
+            .connect_timeout(CONNECT_TIMEOUT);
+        let channel = endpoint
+            .connect()
+            .await
+            .map_err(|source| AdminError::Connect {
+                addr: addr.to_string(),
+                source,
+            })?;
+        Ok(Self {
+            inner: AdminServiceClient::new(channel),
+        })
+    }
+
+    pub async fn cordon(&mut self, node: &str) -> Result<CordonResult, AdminError> {
+        let req = NodeRef { node: node.into() };
+        Ok(self.inner.cordon_node(req).await?.into_inner())
+    }
+
+    pub async fn uncordon(&mut self, node: &str) -> Result<CordonResult, AdminError> {
+        let req = NodeRef { node: node.into() };
+        Ok(self.inner.uncordon_node(req).await?.into_inner())
+    }
+
+    /// One `DrainNode` step; repeat until `remaining` is 0.
+    pub async fn drain_step(
+        &mut self,
+        node: &str,
+        batch_size: u32,
+    ) -> Result<DrainResult, AdminError> {
+        let req = DrainRequest {
+            node: node.into(),
+            batch_size,
+        };
+        Ok(self.inner.drain_node(req).await?.into_inner())
+    }
+
+    pub async fn apply_compaction(
+        &mut self,
+        proposal_id: u64,
+    ) -> Result<CompactionResult, AdminError> {
+        let req = CompactionRef { proposal_id };
+        Ok(self.inner.apply_compaction(req).await?.into_inner())
+    }
+
+    /// Read-only: what losing `node` would do.
+    pub async fn what_if_node_loss(&mut self, node: &str) -> Result<WhatIfResult, AdminError> {
+        let req = NodeRef { node: node.into() };
+        Ok(self.inner.what_if_node_loss(req).await?.into_inner())
+    }
+
+    pub async fn log_filter(&mut self) -> Result<LogFilterResult, AdminError> {
+        Ok(self
+            .inner
+            .get_log_filter(LogFilterQuery {})
+            .await?
+            .into_inner())
+    }
+
+    pub async fn set_log_filter(&mut self, filter: &str) -> Result<LogFilterResult, AdminError> {
+        let req = LogFilterUpdate {
+            filter: filter.into(),
+        };
+        Ok(self.inner.set_log_filter(req).await?.into_inner())
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::net::TcpListener;
+    use tonic::transport::server::TcpIncoming;
+    use tonic::transport::Server;
+    use tonic::{Code, Request};
+
+    use super::*;
+    use crate::config::{NodeConfig, NodeConfigManager};
+    use crate::fault::{test_support::MockFaultNotifier, FaultNotifier};
+    use crate::grpc::admin_service::AdminServiceImpl;
+    use crate::grpc::{new_workload_store, schedinfo_service::SchedInfoServiceImpl};
+    use crate::proto::schedinfo_v1::{
+        admin_service_server::AdminServiceServer, sched_info_service_server::SchedInfoService,
+        SchedInfo, TaskInfo,
+    };
+    use crate::scheduler::ErrorCode;
+
+    fn service() -> SchedInfoServiceImpl {
+        let nodes = NodeConfigManager::from_nodes(vec![
+            NodeConfig {
+                available_cpus: vec![0, 1],
+                ..NodeConfig::default_config("n1")
+            },
+            NodeConfig {
+                available_cpus: vec![0, 1],
+                ..NodeConfig::default_config("n2")
+            },
+        ]);
+        SchedInfoServiceImpl::new(
+            Arc::new(nodes),
+            new_workload_store(),
+            MockFaultNotifier::arc() as Arc<dyn FaultNotifier>,
+        )
+    }
+
+    /// Start an AdminService over `svc` on an ephemeral port; returns a
+    /// client connected to it.
+    async fn start_admin(svc: &SchedInfoServiceImpl) -> AdminClient {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(AdminServiceServer::new(AdminServiceImpl::new(svc.clone())))
+                .serve_with_incoming(incoming),
+        );
+        AdminClient::connect(&format!("http://{addr}"))
+            .await
+            .unwrap()
+    }
+
+    fn workload(id: &str, count: usize) -> Request<SchedInfo> {
+        heavy_workload(id, count, 1_000)
+    }
+
+    /// `count` tasks of `runtime` µs every 10 ms.
+    fn heavy_workload(id: &str, count: usize, runtime: i32) -> Request<SchedInfo> {
+        Request::new(SchedInfo {
+            workload_id: id.into(),
+            algorithm: Some("least_loaded".into()),
+            tasks: (0..count)
+                .map(|i| TaskInfo {
+                    name: format!("{id}_{i}"),
+                    priority: 50,
+                    policy: 1,
+                    period: 10_000,
+                    runtime,
+                    deadline: 10_000,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    /// Nodes `svc`'s default-tenant workload uses.
+    async fn nodes_used(svc: &SchedInfoServiceImpl) -> Vec<String> {
+        let status = svc
+            .get_cluster_status(Request::new(Default::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        status
+            .nodes
+            .into_iter()
+            .filter(|n| n.task_count > 0)
+            .map(|n| n.node)
+            .collect()
+    }
+
+    /// Nodes `svc` reports cordoned.
+    async fn nodes_cordoned(svc: &SchedInfoServiceImpl) -> Vec<String> {
+        let status = svc
+            .get_cluster_status(Request::new(Default::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        status
+            .nodes
+            .into_iter()
+            .filter(|n| n.cordoned)
+            .map(|n| n.node)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn cordon_takes_effect_on_the_next_placement() {
+        let svc = service();
+        let mut admin = start_admin(&svc).await;
+
+        let r = admin.cordon("n1").await.unwrap();
+        assert!(r.cordoned && r.changed);
+        assert!(!admin.cordon("n1").await.unwrap().changed);
+        assert_eq!(nodes_cordoned(&svc).await, ["n1"]);
+        svc.add_sched_info(workload("a", 4)).await.unwrap();
+        assert_eq!(nodes_used(&svc).await, ["n2"]);
+
+        let r = admin.uncordon("n1").await.unwrap();
+        assert!(!r.cordoned && r.changed);
+        assert!(nodes_cordoned(&svc).await.is_empty());
+        svc.add_sched_info(workload("b", 4)).await.unwrap();
+        assert_eq!(nodes_used(&svc).await, ["n1", "n2"]);
+
+        let err = admin.cordon("n9").await.unwrap_err();
+        assert!(matches!(err, AdminError::Rpc(ref s) if s.code() == Code::NotFound));
+    }
+
+    #[tokio::test]
+    async fn drain_cordons_and_empties_the_node_in_batches() {
+        let svc = service();
+        let mut admin = start_admin(&svc).await;
+        svc.add_sched_info(workload("a", 4)).await.unwrap();
+        assert_eq!(nodes_used(&svc).await, ["n1", "n2"]);
+
+        let first = admin.drain_step("n1", 1).await.unwrap();
+        assert_eq!(first.moved.len(), 1);
+        assert_eq!(first.moved[0].node, "n2");
+        assert_eq!(first.remaining, 1);
+        // Cordoned by the drain: the next workload avoids n1.
+        assert!(!admin.cordon("n1").await.unwrap().changed);
+
+        let last = admin.drain_step("n1", 8).await.unwrap();
+        assert_eq!(last.remaining, 0);
+        assert_eq!(nodes_used(&svc).await, ["n2"]);
+
+        let err = admin.drain_step("n1", 0).await.unwrap_err();
+        assert!(matches!(err, AdminError::Rpc(ref s) if s.code() == Code::InvalidArgument));
+    }
+
+    #[tokio::test]
+    async fn cordon_survives_a_config_reload() {
+        let svc = service();
+        let mut admin = start_admin(&svc).await;
+        admin.cordon("n1").await.unwrap();
+
+        let mut reloaded = NodeConfigManager::new();
+        reloaded
+            .load_from_str(
+                "nodes:\n  n1:\n    available_cpus: [0, 1]\n  n2:\n    available_cpus: [0, 1]\n",
+                std::path::Path::new("reload.yaml"),
+            )
+            .unwrap();
+        svc.reload_config(Arc::new(reloaded)).await;
+
+        svc.add_sched_info(workload("a", 4)).await.unwrap();
+        assert_eq!(nodes_used(&svc).await, ["n2"]);
+    }
+
+    #[tokio::test]
+    async fn what_if_loss_reports_without_moving_anything() {
+        let svc = service();
+        let mut admin = start_admin(&svc).await;
+        svc.add_sched_info(workload("a", 4)).await.unwrap();
+
+        let r = admin.what_if_node_loss("n1").await.unwrap();
+        assert!(r.survivable && r.unplaced.is_empty());
+        assert_eq!(r.moved.len(), 2);
+        assert!(r
+            .moved
+            .iter()
+            .all(|m| m.from_node == "n1" && m.to_node == "n2"));
+        let n2 = &r.peaks[1];
+        assert_eq!((r.peaks[0].node.as_str(), r.peaks[0].after), ("n1", 0.0));
+        assert!(n2.node == "n2" && n2.after > n2.before, "{n2:?}");
+        // Nothing moved and n1 was not cordoned.
+        assert_eq!(nodes_used(&svc).await, ["n1", "n2"]);
+        assert!(admin.cordon("n1").await.unwrap().changed);
+
+        let err = admin.what_if_node_loss("n9").await.unwrap_err();
+        assert!(matches!(err, AdminError::Rpc(ref s) if s.code() == Code::NotFound));
+    }
+
+    #[tokio::test]
+    async fn what_if_loss_names_each_nodes_reason_for_stranded_tasks() {
+        let svc = service();
+        let mut admin = start_admin(&svc).await;
+        // 60 % each: one task per CPU, all four CPUs taken.
+        svc.add_sched_info(heavy_workload("a", 4, 6_000))
+            .await
+            .unwrap();
+
+        let r = admin.what_if_node_loss("n1").await.unwrap();
+        assert!(!r.survivable);
+        assert!(r.moved.is_empty());
+        assert_eq!(r.unplaced.len(), 2);
+        for u in &r.unplaced {
+            assert_eq!(u.rejections.len(), 1);
+            assert_eq!(u.rejections[0].node, "n2");
+            assert_eq!(
+                u.rejections[0].error_code,
+                ErrorCode::NoAvailableCpu.as_u32()
+            );
+        }
+        assert_eq!(nodes_used(&svc).await, ["n1", "n2"]);
+    }
+}
@@ -0,0 +1,259 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! `AdminService`: operator actions on nodes and on the log filter.
+//!
+//! | RPC               | Does                                                      |
+//! |-------------------|-----------------------------------------------------------|
+//! | `CordonNode`      | no new placements on the node; its tasks stay             |
+//! | `UncordonNode`    | the node takes new placements again                       |
+//! | `DrainNode`       | cordon, then move one batch of the node's tasks elsewhere |
+//! | `ApplyCompaction` | move every workload to the current compaction proposal    |
+//! | `WhatIfNodeLoss`  | report what would fit if the node went away; read-only    |
+//! | `GetLogFilter`    | the [log filter](super::log_filter) in force              |
+//! | `SetLogFilter`    | replace it; an unparsable filter changes nothing          |
+//!
+//! Each call acts on the [`SchedInfoServiceImpl`] it wraps, so the next
+//! scheduling run already sees the change.  The service is cluster-wide and
+//! ignores the tenant metadata; `timpani-o` serves it on its own address
+//! (`--admin-addr`) so access can be restricted apart from Pullpiri's
+//! `SchedInfoService`.  The client side is [`super::admin_client`].
+
+use tonic::metadata::MetadataMap;
+use tonic::{Code, Request, Response, Status};
+use tracing::{info, warn};
+
+use crate::proto::schedinfo_v1::{
+    admin_service_server::AdminService, CompactionMove, CompactionRef, CompactionResult,
+    CordonResult, DrainRequest, DrainResult, LogFilterQuery, LogFilterResult, LogFilterUpdate,
+    NodePeak, NodeRef, NodeRejection, PinnedTask, UnplacedTask, WhatIfResult,
+};
+use crate::scheduler::{SchedulerError, WhatIfReport};
+
+use super::compaction::CompactionError;
+use super::schedinfo_service::{insert_error_metadata, SchedInfoServiceImpl};
+
+/// Where `timpani-o` serves the AdminService unless `--admin-addr` says
+/// otherwise: loopback only.
+pub const DEFAULT_ADMIN_ADDR: &str = "127.0.0.1:50055";
+
+/// `AdminService` over a [`SchedInfoServiceImpl`] (see the module docs).
+#[derive(Clone)]
+pub struct AdminServiceImpl {
+    sched_info: SchedInfoServiceImpl,
+}
+
+impl AdminServiceImpl {
+    /// Act on `sched_info`'s nodes and workloads; pass a clone of the
+    /// instance that serves `SchedInfoService`.
+    pub fn new(sched_info: SchedInfoServiceImpl) -> Self {
+        Self { sched_info }
+    }
+
+    /// `None` if `node` is not configured.
+    fn set_cordoned(&self, node: &str, cordoned: bool) -> Option<CordonResult> {
+        let changed = self.sched_info.set_node_cordoned(node, cordoned)?;
+        Some(CordonResult {
+            node: node.to_string(),
+            cordoned,
+            changed,
+        })
+    }
+}
+
+fn not_configured(node: &str) -> Status {
+    Status::not_found(format!("node '{node}' is not configured"))
+}
+
+fn log_filter_not_reloadable() -> Status {
+    Status::unimplemented("the log filter is not reloadable")
+}
+
+/// `ResourceExhausted` carrying `err`'s message and codes.
+fn drain_failed(err: &SchedulerError) -> Status {
+    let mut md = MetadataMap::new();
+    insert_error_metadata(&mut md, err);
+    Status::with_metadata(Code::ResourceExhausted, err.to_string(), md)
+}
+
+fn compaction_failed(err: &CompactionError) -> Status {
+    match err {
+        CompactionError::Expired(_) => Status::failed_precondition(err.to_string()),
+        _ => Status::not_found(err.to_string()),
+    }
+}
+
+impl From<WhatIfReport> for WhatIfResult {
+    fn from(r: WhatIfReport) -> Self {
+        let survivable = r.survivable();
+        WhatIfResult {
+            moved: r
+                .moved
+                .into_iter()
+                .map(|m| CompactionMove {
+                    tenant: m.tenant,
+                    task: m.task,
+                    from_node: r.node.clone(),
+                    from_cpu: m.from_cpu,
+                    to_node: m.to_node,
+                    to_cpu: m.to_cpu,
+                })
+                .collect(),
+            unplaced: r
+                .unplaced
+                .into_iter()
+                .map(|u| UnplacedTask {
+                    tenant: u.tenant,
+                    task: u.task,
+                    reason: u.reason,
+                    rejections: u
+                        .rejections
+                        .into_iter()
+                        .map(|rej| NodeRejection {
+                            node: rej.node,
+                            error_code: rej.reason.code().as_u32(),
+                            reason: rej.reason.to_string(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            peaks: r
+                .peaks
+                .into_iter()
+                .map(|p| NodePeak {
+                    node: p.node,
+                    before: p.before,
+                    after: p.after,
+                })
+                .collect(),
+            node: r.node,
+            survivable,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl AdminService for AdminServiceImpl {
+    async fn cordon_node(
+        &self,
+        request: Request<NodeRef>,
+    ) -> Result<Response<CordonResult>, Status> {
+        let node = request.into_inner().node;
+        info!(node = %node, "CordonNode received");
+        self.set_cordoned(&node, true)
+            .map(Response::new)
+            .ok_or_else(|| not_configured(&node))
+    }
+
+    async fn uncordon_node(
+        &self,
+        request: Request<NodeRef>,
+    ) -> Result<Response<CordonResult>, Status> {
+        let node = request.into_inner().node;
+        info!(node = %node, "UncordonNode received");
+        self.set_cordoned(&node, false)
+            .map(Response::new)
+            .ok_or_else(|| not_configured(&node))
+    }
+
+    async fn drain_node(
+        &self,
+        request: Request<DrainRequest>,
+    ) -> Result<Response<DrainResult>, Status> {
+        let DrainRequest { node, batch_size } = request.into_inner();
+        info!(node = %node, batch_size, "DrainNode received");
+        if batch_size == 0 {
+            return Err(Status::invalid_argument("batch_size must be at least 1"));
+        }
+        self.set_cordoned(&node, true)
+            .ok_or_else(|| not_configured(&node))?;
+
+        let progress = self
+            .sched_info
+            .drain_node(&node, batch_size as usize)
+            .await
+            .map_err(|e| {
+                warn!(node = %node, error = %e, "DrainNode: batch could not be moved");
+                drain_failed(&e)
+            })?;
+        Ok(Response::new(DrainResult {
+            node,
+            moved: progress.moved,
+            pinned: progress
+                .pinned
+                .into_iter()
+                .map(|(tenant, task)| PinnedTask { tenant, task })
+                .collect(),
+            remaining: progress.remaining as u32,
+        }))
+    }
+
+    async fn apply_compaction(
+        &self,
+        request: Request<CompactionRef>,
+    ) -> Result<Response<CompactionResult>, Status> {
+        let proposal_id = request.into_inner().proposal_id;
+        info!(proposal_id, "ApplyCompaction received");
+        let applied = self
+            .sched_info
+            .apply_compaction(proposal_id)
+            .await
+            .map_err(|e| {
+                warn!(proposal_id, error = %e, "ApplyCompaction: not applied");
+                compaction_failed(&e)
+            })?;
+        Ok(Response::new(CompactionResult {
+            proposal_id,
+            moved: applied.moves.clone(),
+            nodes_freed: applied.nodes_freed.clone(),
+        }))
+    }
+
+    async fn what_if_node_loss(
+        &self,
+        request: Request<NodeRef>,
+    ) -> Result<Response<WhatIfResult>, Status> {
+        let node = request.into_inner().node;
+        info!(node = %node, "WhatIfNodeLoss received");
+        let report = self
+            .sched_info
+            .what_if_node_loss(&node)
+            .await
+            .ok_or_else(|| not_configured(&node))?;
+        Ok(Response::new(report.into()))
+    }
+
+    async fn get_log_filter(
+        &self,
+        _request: Request<LogFilterQuery>,
+    ) -> Result<Response<LogFilterResult>, Status> {
+        Ok(Response::new(LogFilterResult {
+            filter: self
+                .sched_info
+                .log_filter()
+                .ok_or_else(log_filter_not_reloadable)?
+                .current(),
+            previous: String::new(),
+        }))
+    }
+
+    async fn set_log_filter(
+        &self,
+        request: Request<LogFilterUpdate>,
+    ) -> Result<Response<LogFilterResult>, Status> {
+        let filter = request.into_inner().filter;
+        let log_filter = self
+            .sched_info
+            .log_filter()
+            .ok_or_else(log_filter_not_reloadable)?;
+        let previous = log_filter.set(&filter).map_err(|e| {
+            warn!(filter = %filter, error = %e, "SetLogFilter: rejected");
+            Status::invalid_argument(e.to_string())
+        })?;
+        let filter = filter.trim().to_string();
+        info!(filter = %filter, previous = %previous, "SetLogFilter: log filter replaced");
+        Ok(Response::new(LogFilterResult { filter, previous }))
+    }
+}
@@ -0,0 +1,217 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Clock gate: hold back offset-dependent schedules from nodes whose clock
+//! cannot be trusted.
+//!
+//! A task with a non-zero `release_time_us` fires at an offset from the
+//! shared hyperperiod start; on a node whose clock is off, it fires at the
+//! wrong time without anyone noticing.  With `--clock-check` set,
+//! `NodeService` checks the node's last [`ClockReport`] before serving a
+//! schedule that delivers such a task:
+//!
+//! | Clock                                 | `warn`          | `strict`                       |
+//! |---------------------------------------|-----------------|--------------------------------|
+//! | never reported, or within the bound   | served          | served                         |
+//! | unsynchronised, or offset above bound | served, warning | refused, `FAILED_PRECONDITION` |
+//!
+//! Either way Pullpiri gets one `CLOCK_UNSYNCHRONIZED` advisory per tenant,
+//! node and generation.  Schedules without release offsets are always
+//! served: tasks released at the hyperperiod start only depend on the
+//! `SyncTimer` barrier.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::config::ClockReport;
+use crate::proto::schedinfo_v1::{ClockSync, NodeSchedResponse};
+
+/// Offset bound used when `--max-clock-offset-us` is not given.
+pub const DEFAULT_MAX_CLOCK_OFFSET: Duration = Duration::from_millis(1);
+
+/// What an untrusted clock does to a schedule push.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockCheck {
+    /// Refuse the push.
+    Strict,
+    /// Serve the push and log a warning.
+    Warn,
+}
+
+impl ClockCheck {
+    /// Canonical string form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ClockCheck::Strict => "strict",
+            ClockCheck::Warn => "warn",
+        }
+    }
+}
+
+impl fmt::Display for ClockCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ClockCheck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(ClockCheck::Strict),
+            "warn" => Ok(ClockCheck::Warn),
+            other => Err(format!("unknown clock check '{other}'")),
+        }
+    }
+}
+
+/// Outcome of [`ClockGate::verdict`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClockVerdict {
+    /// Nothing to object to.
+    Serve,
+    /// Serve, but the clock has this problem.
+    Warn(String),
+    /// Do not serve: the clock has this problem.
+    Refuse(String),
+}
+
+/// The gate (see the module docs).
+///
+/// Thread-safe; the internal lock is only held for map updates.
+#[derive(Debug)]
+pub struct ClockGate {
+    check: ClockCheck,
+    max_offset: Duration,
+    /// Generation last advised per `(tenant, node)`.
+    advised: Mutex<BTreeMap<(String, String), u64>>,
+}
+
+impl ClockGate {
+    pub fn new(check: ClockCheck, max_offset: Duration) -> Self {
+        Self {
+            check,
+            max_offset,
+            advised: Mutex::default(),
+        }
+    }
+
+    pub fn check(&self) -> ClockCheck {
+        self.check
+    }
+
+    pub fn max_offset(&self) -> Duration {
+        self.max_offset
+    }
+
+    /// Whether `resp` may go to a node whose last clock report is `clock`.
+    pub fn verdict(&self, clock: Option<&ClockReport>, resp: &NodeSchedResponse) -> ClockVerdict {
+        let problem = clock
+            .filter(|_| offset_dependent(resp))
+            .and_then(|c| c.problem(self.max_offset));
+        match (problem, self.check) {
+            (None, _) => ClockVerdict::Serve,
+            (Some(p), ClockCheck::Warn) => ClockVerdict::Warn(p),
+            (Some(p), ClockCheck::Strict) => ClockVerdict::Refuse(p),
+        }
+    }
+
+    /// `true` the first time it is called for `tenant`, `node` and
+    /// `generation`: whether to send an advisory.
+    pub fn first_advisory(&self, tenant: &str, node: &str, generation: u64) -> bool {
+        let key = (tenant.to_string(), node.to_string());
+        self.advised.lock().unwrap().insert(key, generation) != Some(generation)
+    }
+}
+
+/// Whether `resp` delivers a task with a release offset.
+fn offset_dependent(resp: &NodeSchedResponse) -> bool {
+    resp.tasks
+        .iter()
+        .chain(&resp.modified_tasks)
+        .any(|t| t.release_time_us != 0)
+}
+
+impl From<ClockSync> for ClockReport {
+    fn from(c: ClockSync) -> Self {
+        ClockReport {
+            synchronized: c.synchronized,
+            offset_ns: c.offset_ns,
+            source: c.source,
+        }
+    }
+}
+
+impl From<ClockReport> for ClockSync {
+    fn from(c: ClockReport) -> Self {
+        ClockSync {
+            synchronized: c.synchronized,
+            offset_ns: c.offset_ns,
+            source: c.source,
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::schedinfo_v1::ScheduledTask;
+
+    fn resp(release_time_us: i32) -> NodeSchedResponse {
+        NodeSchedResponse {
+            tasks: vec![ScheduledTask {
+                name: "t".into(),
+                release_time_us,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn clock(synchronized: bool, offset_ns: i64) -> ClockReport {
+        ClockReport {
+            synchronized,
+            offset_ns,
+            source: "phc".into(),
+        }
+    }
+
+    #[test]
+    fn only_offset_schedules_on_untrusted_clocks_are_held() {
+        let strict = ClockGate::new(ClockCheck::Strict, DEFAULT_MAX_CLOCK_OFFSET);
+        let bad = clock(true, -2_000_000);
+        assert!(matches!(
+            strict.verdict(Some(&bad), &resp(500)),
+            ClockVerdict::Refuse(p) if p == "phc offset -2000000 ns exceeds 1000000 ns"
+        ));
+        assert_eq!(strict.verdict(Some(&bad), &resp(0)), ClockVerdict::Serve);
+        assert_eq!(strict.verdict(None, &resp(500)), ClockVerdict::Serve);
+        assert_eq!(
+            strict.verdict(Some(&clock(true, 999_999)), &resp(500)),
+            ClockVerdict::Serve
+        );
+
+        let warn = ClockGate::new(ClockCheck::Warn, DEFAULT_MAX_CLOCK_OFFSET);
+        assert!(matches!(
+            warn.verdict(Some(&clock(false, 0)), &resp(500)),
+            ClockVerdict::Warn(_)
+        ));
+    }
+
+    #[test]
+    fn one_advisory_per_generation() {
+        let gate = ClockGate::new(ClockCheck::Warn, DEFAULT_MAX_CLOCK_OFFSET);
+        assert!(gate.first_advisory("t", "n1", 1));
+        assert!(!gate.first_advisory("t", "n1", 1));
+        assert!(gate.first_advisory("t", "n2", 1));
+        assert!(gate.first_advisory("t", "n1", 2));
+    }
+}
@@ -0,0 +1,359 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Idle-time compaction proposals.
+//!
+//! Every workload is placed around whatever the other tenants hold when it
+//! arrives, so a long-running cluster drifts into a fragmented layout.  Once
+//! the placements have not changed for the idle period, [`plan`] re-places
+//! every stored workload from an empty cluster — highest priority class
+//! first, then highest importance, then tenant — and compares the result
+//! with the current layout:
+//!
+//! | Metric               | Better when                        |
+//! |----------------------|------------------------------------|
+//! | nodes in use         | fewer nodes carry a task           |
+//! | peak CPU (tie-break) | the most loaded CPU is less loaded |
+//!
+//! A [`Compaction`] that improves on the layout becomes the current proposal
+//! of the [`Compactor`].  It is never applied on its own, and it is only
+//! valid for the [`Basis`] it was computed against: any change of placements,
+//! node configuration or cordons expires it.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+use crate::proto::schedinfo_v1::{CompactionMove, CompactionProposal};
+use crate::scheduler::{GlobalScheduler, ScheduleOptions, SchedulerError, Utilization};
+use crate::task::NodeSchedMap;
+
+use super::WorkloadState;
+
+/// How often `timpani-o` looks for an idle cluster to compact.
+pub const COMPACTION_TICK: Duration = Duration::from_secs(5);
+
+/// Why a proposal could not be applied.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum CompactionError {
+    #[error("compaction proposal {0} is not the current proposal")]
+    Unknown(u64),
+
+    #[error("compaction proposal {0} has expired: the placements changed since it was made")]
+    Expired(u64),
+}
+
+// ── Basis ─────────────────────────────────────────────────────────────────────
+
+/// The cluster state a proposal is computed against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Basis {
+    pub config_generation: u64,
+    pub cordoned: BTreeSet<String>,
+    /// Tenant → stored schedule.
+    pub schedules: BTreeMap<String, NodeSchedMap>,
+}
+
+impl Basis {
+    pub fn of(
+        workloads: &HashMap<String, WorkloadState>,
+        config_generation: u64,
+        cordoned: BTreeSet<String>,
+    ) -> Self {
+        Self {
+            config_generation,
+            cordoned,
+            schedules: workloads
+                .iter()
+                .map(|(tenant, ws)| (tenant.clone(), ws.schedule.clone()))
+                .collect(),
+        }
+    }
+}
+
+// ── Compaction ────────────────────────────────────────────────────────────────
+
+/// A re-placement of every stored workload (see the module docs).
+#[derive(Debug, Clone)]
+pub struct Compaction {
+    /// Assigned by the [`Compactor`]; 0 until then.
+    pub id: u64,
+    pub basis: Basis,
+    /// Tenant → proposed schedule, for every tenant in the basis.
+    pub proposed: BTreeMap<String, NodeSchedMap>,
+    /// Tasks that change node or CPU, sorted by tenant and task.
+    pub moves: Vec<CompactionMove>,
+    /// Nodes in use now that the proposal leaves empty, sorted.
+    pub nodes_freed: Vec<String>,
+    pub nodes_in_use_before: usize,
+    pub nodes_in_use_after: usize,
+    pub peak_before: Utilization,
+    pub peak_after: Utilization,
+    pub created_at: Instant,
+}
+
+impl Compaction {
+    /// Fewer nodes in use, or as many with a lower peak CPU.
+    pub fn improves(&self) -> bool {
+        (self.nodes_in_use_after, self.peak_after) < (self.nodes_in_use_before, self.peak_before)
+    }
+
+    /// The proposal as reported to `tenant` (its own moves only), or with
+    /// every move for `None`.
+    pub fn to_proto(&self, tenant: Option<&str>, now: Instant) -> CompactionProposal {
+        CompactionProposal {
+            proposal_id: self.id,
+            moves: self
+                .moves
+                .iter()
+                .filter(|m| tenant.is_none_or(|t| m.tenant == t))
+                .cloned()
+                .collect(),
+            nodes_freed: self.nodes_freed.clone(),
+            nodes_in_use_before: self.nodes_in_use_before as u32,
+            nodes_in_use_after: self.nodes_in_use_after as u32,
+            peak_cpu_utilization_before: self.peak_before.as_f64(),
+            peak_cpu_utilization_after: self.peak_after.as_f64(),
+            age_ms: now.saturating_duration_since(self.created_at).as_millis() as u64,
+        }
+    }
+}
+
+/// Re-place every workload of `basis` from an empty cluster with `opts`
+/// (see the module docs).  Fails if any workload no longer fits.
+pub fn plan(
+    scheduler: &GlobalScheduler,
+    workloads: &HashMap<String, WorkloadState>,
+    basis: Basis,
+    opts: &ScheduleOptions,
+    now: Instant,
+) -> Result<Compaction, SchedulerError> {
+    let mut order: Vec<(&String, &WorkloadState)> = workloads.iter().collect();
+    order.sort_by_key(|(tenant, ws)| (Reverse(ws.priority_class), Reverse(ws.importance), *tenant));
+
+    let mut occupied = NodeSchedMap::new();
+    let mut proposed = BTreeMap::new();
+    for (tenant, ws) in order {
+        let placed = scheduler.schedule_with_occupancy(&occupied, ws.tasks.clone(), opts)?;
+        for (node, tasks) in &placed {
+            occupied
+                .entry(node.clone())
+                .or_default()
+                .extend(tasks.iter().cloned());
+        }
+        proposed.insert(tenant.clone(), placed);
+    }
+
+    let before = nodes_in_use(basis.schedules.values());
+    let after = nodes_in_use(proposed.values());
+    Ok(Compaction {
+        id: 0,
+        moves: moves(&basis.schedules, &proposed),
+        nodes_freed: before.difference(&after).cloned().collect(),
+        nodes_in_use_before: before.len(),
+        nodes_in_use_after: after.len(),
+        peak_before: peak_cpu_utilization(basis.schedules.values()),
+        peak_after: peak_cpu_utilization(proposed.values()),
+        basis,
+        proposed,
+        created_at: now,
+    })
+}
+
+fn nodes_in_use<'a>(schedules: impl Iterator<Item = &'a NodeSchedMap>) -> BTreeSet<String> {
+    schedules
+        .flat_map(|s| s.iter())
+        .filter(|(_, tasks)| !tasks.is_empty())
+        .map(|(node, _)| node.clone())
+        .collect()
+}
+
+/// Highest per-CPU utilisation over all `schedules` together.
+fn peak_cpu_utilization<'a>(schedules: impl Iterator<Item = &'a NodeSchedMap>) -> Utilization {
+    let mut per_cpu: BTreeMap<(&str, u32), Utilization> = BTreeMap::new();
+    for (node, tasks) in schedules.flat_map(|s| s.iter()) {
+        for t in tasks {
+            *per_cpu.entry((node, t.assigned_cpu)).or_default() += t.exact_utilization();
+        }
+    }
+    per_cpu.values().copied().max().unwrap_or_default()
+}
+
+fn moves(
+    current: &BTreeMap<String, NodeSchedMap>,
+    proposed: &BTreeMap<String, NodeSchedMap>,
+) -> Vec<CompactionMove> {
+    let places = |schedule: &NodeSchedMap| -> BTreeMap<String, (String, u32)> {
+        schedule
+            .iter()
+            .flat_map(|(node, tasks)| {
+                tasks
+                    .iter()
+                    .map(move |t| (t.name.clone(), (node.clone(), t.assigned_cpu)))
+            })
+            .collect()
+    };
+    let mut moves = Vec::new();
+    for (tenant, schedule) in proposed {
+        let from = current.get(tenant).map(places).unwrap_or_default();
+        for (task, (to_node, to_cpu)) in places(schedule) {
+            let Some((from_node, from_cpu)) = from.get(&task).cloned() else {
+                continue;
+            };
+            if (&from_node, from_cpu) != (&to_node, to_cpu) {
+                moves.push(CompactionMove {
+                    tenant: tenant.clone(),
+                    task,
+                    from_node,
+                    from_cpu,
+                    to_node,
+                    to_cpu,
+                });
+            }
+        }
+    }
+    moves
+}
+
+// ── Compactor ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Default)]
+struct CompactorState {
+    /// Placements last observed, and since when they have not changed.
+    seen: Option<(Basis, Instant)>,
+    /// [`plan`] already ran for `seen`.
+    planned: bool,
+    current: Option<Arc<Compaction>>,
+    next_id: u64,
+}
+
+/// Idle detection and the current proposal.
+#[derive(Debug)]
+pub struct Compactor {
+    idle: Duration,
+    state: Mutex<CompactorState>,
+}
+
+impl Compactor {
+    /// Plan once the placements have not changed for `idle`.
+    pub fn new(idle: Duration) -> Self {
+        Self {
+            idle,
+            state: Mutex::default(),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, CompactorState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Note the cluster's `basis` at `now`.  True once it has been unchanged
+    /// for the idle period and not planned yet; the caller then plans and
+    /// reports the outcome to [`planned`](Self::planned).
+    pub fn observe(&self, basis: &Basis, now: Instant) -> bool {
+        let mut state = self.state();
+        let since = match &state.seen {
+            Some((seen, since)) if seen == basis => *since,
+            _ => {
+                state.seen = Some((basis.clone(), now));
+                state.planned = false;
+                now
+            }
+        };
+        !state.planned && now.saturating_duration_since(since) >= self.idle
+    }
+
+    /// Record the outcome of a [`plan`]; a proposal becomes the current one
+    /// under a new id.
+    pub fn planned(&self, proposal: Option<Compaction>) -> Option<Arc<Compaction>> {
+        let mut state = self.state();
+        state.planned = true;
+        let mut proposal = proposal?;
+        state.next_id += 1;
+        proposal.id = state.next_id;
+        let proposal = Arc::new(proposal);
+        state.current = Some(Arc::clone(&proposal));
+        Some(proposal)
+    }
+
+    /// The current proposal, if it was computed against `basis`.
+    pub fn current(&self, basis: &Basis) -> Option<Arc<Compaction>> {
+        self.state().current.clone().filter(|c| c.basis == *basis)
+    }
+
+    /// Take proposal `id` for applying, if it is current and still valid for
+    /// `basis`.
+    pub fn take(&self, id: u64, basis: &Basis) -> Result<Arc<Compaction>, CompactionError> {
+        let mut state = self.state();
+        match &state.current {
+            Some(c) if c.id == id && c.basis == *basis => {}
+            Some(c) if c.id == id => return Err(CompactionError::Expired(id)),
+            _ => return Err(CompactionError::Unknown(id)),
+        }
+        Ok(state.current.take().expect("checked above"))
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn basis(generation: u64) -> Basis {
+        Basis {
+            config_generation: generation,
+            cordoned: BTreeSet::new(),
+            schedules: BTreeMap::new(),
+        }
+    }
+
+    fn proposal(basis: Basis, now: Instant) -> Compaction {
+        Compaction {
+            id: 0,
+            basis,
+            proposed: BTreeMap::new(),
+            moves: Vec::new(),
+            nodes_freed: Vec::new(),
+            nodes_in_use_before: 2,
+            nodes_in_use_after: 1,
+            peak_before: Utilization::default(),
+            peak_after: Utilization::default(),
+            created_at: now,
+        }
+    }
+
+    #[test]
+    fn plans_once_per_idle_period_and_expires_on_change() {
+        let compactor = Compactor::new(Duration::from_secs(10));
+        let t0 = Instant::now();
+        assert!(!compactor.observe(&basis(0), t0));
+        assert!(!compactor.observe(&basis(0), t0 + Duration::from_secs(9)));
+        assert!(compactor.observe(&basis(0), t0 + Duration::from_secs(10)));
+
+        let c = compactor.planned(Some(proposal(basis(0), t0))).unwrap();
+        assert_eq!(c.id, 1);
+        assert!(!compactor.observe(&basis(0), t0 + Duration::from_secs(60)));
+        assert_eq!(compactor.current(&basis(0)).unwrap().id, 1);
+
+        // A change restarts the idle period and expires the proposal.
+        assert!(!compactor.observe(&basis(1), t0 + Duration::from_secs(61)));
+        assert!(compactor.current(&basis(1)).is_none());
+        assert_eq!(
+            compactor.take(1, &basis(1)).unwrap_err(),
+            CompactionError::Expired(1)
+        );
+        assert_eq!(
+            compactor.take(2, &basis(0)).unwrap_err(),
+            CompactionError::Unknown(2)
+        );
+        assert_eq!(compactor.take(1, &basis(0)).unwrap().id, 1);
+        assert!(compactor.current(&basis(0)).is_none());
+    }
+}
@@ -0,0 +1,395 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Install self-check behind `timpani-o doctor`.
+//!
+//! | Check          | Mandatory | Passes when                                      |
+//! |----------------|-----------|--------------------------------------------------|
+//! | `node config`  | yes       | `--nodeconfig` loads and validates (or is unset) |
+//! | `listen <svc>` | yes       | the service port can be bound (then released)    |
+//! | `FaultService` | yes       | a gRPC channel to Pullpiri opens                 |
+//! | `node <name>`  | no        | a gRPC channel to the node's Timpani-N opens     |
+//!
+//! Nodes pull their schedules from Timpani-O, so an unreachable node is
+//! reported but does not make the install unhealthy.
+//!
+//! The node configuration is read first (it names the nodes); every other
+//! check runs concurrently, and any still running when the overall timeout
+//! expires fails as timed out.
+
+use std::error::Error as StdError;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::time::Duration;
+
+use tokio::net::TcpListener;
+use tokio::task::JoinSet;
+use tokio::time::{timeout_at, Instant};
+use tonic::transport::Endpoint;
+
+use crate::config::{NodeConfigManager, DEFAULT_NODE_PORT};
+
+/// Overall time limit when none is configured.
+pub const DEFAULT_DOCTOR_TIMEOUT: Duration = Duration::from_secs(5);
+
+// ── Results ───────────────────────────────────────────────────────────────────
+
+/// How one check ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckOutcome {
+    Pass,
+    Fail,
+    /// Not applicable to this install (e.g. no `--nodeconfig`).
+    Skipped,
+}
+
+/// One line of the report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: String,
+    /// A failed mandatory check makes the install unhealthy.
+    pub mandatory: bool,
+    pub outcome: CheckOutcome,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &str, mandatory: bool, result: Result<String, String>) -> Self {
+        let (outcome, detail) = match result {
+            Ok(detail) => (CheckOutcome::Pass, detail),
+            Err(detail) => (CheckOutcome::Fail, detail),
+        };
+        Self {
+            name: name.to_string(),
+            mandatory,
+            outcome,
+            detail,
+        }
+    }
+}
+
+/// Every check, in the order of the module docs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DoctorReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl DoctorReport {
+    /// Mandatory checks that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks
+            .iter()
+            .filter(|c| c.mandatory && c.outcome == CheckOutcome::Fail)
+    }
+
+    /// No mandatory check failed.
+    pub fn is_healthy(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// One line per check and a verdict, for the terminal.
+    pub fn render(&self) -> String {
+        let width = self.checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+        let mut out = String::new();
+        for c in &self.checks {
+            let tag = match (&c.outcome, c.mandatory) {
+                (CheckOutcome::Pass, _) => "ok",
+                (CheckOutcome::Fail, true) => "FAIL",
+                (CheckOutcome::Fail, false) => "warn",
+                (CheckOutcome::Skipped, _) => "skip",
+            };
+            let _ = writeln!(out, "[{tag:>4}] {:<width$}  {}", c.name, c.detail);
+        }
+        match self.failures().count() {
+            0 => out.push_str("healthy\n"),
+            n => {
+                let _ = writeln!(out, "unhealthy: {n} mandatory check(s) failed");
+            }
+        }
+        out
+    }
+}
+
+// ── Doctor ────────────────────────────────────────────────────────────────────
+
+/// What to check (see the module docs).
+#[derive(Debug, Clone)]
+pub struct Doctor {
+    node_config: Option<PathBuf>,
+    default_node_port: u16,
+    listen_ports: Vec<(String, u16)>,
+    fault_addr: String,
+    timeout: Duration,
+}
+
+impl Doctor {
+    /// Check the FaultService at `fault_addr` (e.g. `http://localhost:50053`).
+    pub fn new(fault_addr: impl Into<String>) -> Self {
+        Self {
+            node_config: None,
+            default_node_port: DEFAULT_NODE_PORT,
+            listen_ports: Vec::new(),
+            fault_addr: fault_addr.into(),
+            timeout: DEFAULT_DOCTOR_TIMEOUT,
+        }
+    }
+
+    pub fn with_node_config(mut self, path: impl Into<PathBuf>) -> Self {
+        self.node_config = Some(path.into());
+        self
+    }
+
+    /// Port of nodes without an `endpoint`, as `--nodeport`.
+    pub fn with_default_node_port(mut self, port: u16) -> Self {
+        self.default_node_port = port;
+        self
+    }
+
+    /// Check that `port` can be bound on all interfaces for `service`.
+    pub fn with_listen_port(mut self, service: impl Into<String>, port: u16) -> Self {
+        self.listen_ports.push((service.into(), port));
+        self
+    }
+
+    /// Limit on the whole run, and on each connection attempt.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Run every check.
+    pub async fn run(&self) -> DoctorReport {
+        let deadline = Instant::now() + self.timeout;
+        let mut checks = Vec::new();
+
+        let mut endpoints = Vec::new();
+        match &self.node_config {
+            None => checks.push(CheckResult {
+                name: "node config".into(),
+                mandatory: true,
+                outcome: CheckOutcome::Skipped,
+                detail: "no --nodeconfig; default node settings are used".into(),
+            }),
+            Some(path) => {
+                let mut config =
+                    NodeConfigManager::new().with_default_node_port(self.default_node_port);
+                let result = config
+                    .load_from_file(path)
+                    .map(|()| {
+                        format!(
+                            "{} node(s) from {}",
+                            config.get_all_nodes().len(),
+                            path.display()
+                        )
+                    })
+                    .map_err(|e| error_chain(&e));
+                checks.push(CheckResult::new("node config", true, result));
+                let mut names: Vec<_> = config.get_all_nodes().keys().cloned().collect();
+                names.sort();
+                endpoints = names
+                    .into_iter()
+                    .filter_map(|n| config.resolve_endpoint(&n).map(|ep| (n, ep)))
+                    .collect();
+            }
+        }
+
+        let limit = self.timeout;
+        let mut names = Vec::new();
+        let mut set = JoinSet::new();
+        let mut spawn = |name: String, mandatory: bool, check: Check| {
+            let index = names.len();
+            names.push((name, mandatory));
+            set.spawn(async move {
+                let result = timeout_at(deadline, check).await;
+                (
+                    index,
+                    result.unwrap_or_else(|_| Err(format!("timed out after {limit:?}"))),
+                )
+            });
+        };
+        for (service, port) in &self.listen_ports {
+            spawn(format!("listen {service}"), true, Box::pin(can_bind(*port)));
+        }
+        spawn(
+            "FaultService".into(),
+            true,
+            Box::pin(can_connect(self.fault_addr.clone(), self.timeout)),
+        );
+        for (node, endpoint) in endpoints {
+            let url = format!("http://{endpoint}");
+            spawn(
+                format!("node {node}"),
+                false,
+                Box::pin(can_connect(url, self.timeout)),
+            );
+        }
+
+        let mut results = vec![None; names.len()];
+        while let Some(joined) = set.join_next().await {
+            if let Ok((index, result)) = joined {
+                results[index] = Some(result);
+            }
+        }
+        for ((name, mandatory), result) in names.into_iter().zip(results) {
+            let result = result.unwrap_or_else(|| Err("check panicked".into()));
+            checks.push(CheckResult::new(&name, mandatory, result));
+        }
+        DoctorReport { checks }
+    }
+}
+
+/// One spawned check: a detail line on success or failure.
+type Check = Pin<Box<dyn Future<Output = Result<String, String>> + Send>>;
+
+/// Bind `port` on all interfaces and release it.
+async fn can_bind(port: u16) -> Result<String, String> {
+    let addr = format!("0.0.0.0:{port}");
+    match TcpListener::bind(&addr).await {
+        Ok(_) => Ok(format!("{addr} is free")),
+        Err(e) => Err(format!("cannot bind {addr}: {e}")),
+    }
+}
+
+/// Open a gRPC channel to `url`.
+async fn can_connect(url: String, timeout: Duration) -> Result<String, String> {
+    let endpoint = Endpoint::from_shared(url.clone())
+        .map_err(|_| format!("invalid address '{url}'"))?
+        .connect_timeout(timeout);
+    match endpoint.connect().await {
+        Ok(_) => Ok(format!("{url} reachable")),
+        Err(e) => Err(format!("{url} unreachable: {}", error_chain(&e))),
+    }
+}
+
+/// `error: source: source…` on one line; a source that repeats the
+/// message before it (hyper's wrappers do) is shown once.
+fn error_chain(e: &dyn StdError) -> String {
+    let mut out = e.to_string();
+    let mut last = out.clone();
+    let mut source = e.source();
+    while let Some(s) = source {
+        let message = s.to_string();
+        if message != last {
+            let _ = write!(out, ": {message}");
+        }
+        last = message;
+        source = s.source();
+    }
+    out
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::net::SocketAddr;
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    /// A listener that accepts and holds connections, standing in for a
+    /// gRPC peer.
+    async fn reachable() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                held.push(socket);
+            }
+        });
+        addr
+    }
+
+    /// A port nothing listens on.
+    async fn unreachable() -> SocketAddr {
+        TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+    }
+
+    fn node_config(endpoints: &[(&str, SocketAddr)]) -> NamedTempFile {
+        let mut yaml = String::from("nodes:\n");
+        for (name, addr) in endpoints {
+            let _ = write!(
+                yaml,
+                "  {name}:\n    available_cpus: [0, 1]\n    endpoint: \"{addr}\"\n"
+            );
+        }
+        let mut f = NamedTempFile::new().unwrap();
+        f.write_all(yaml.as_bytes()).unwrap();
+        f
+    }
+
+    fn outcome<'a>(report: &'a DoctorReport, name: &str) -> &'a CheckResult {
+        report.checks.iter().find(|c| c.name == name).unwrap()
+    }
+
+    #[tokio::test]
+    async fn unreachable_node_is_reported_but_healthy() {
+        let fault = reachable().await;
+        let config = node_config(&[("up", reachable().await), ("down", unreachable().await)]);
+        let free_port = unreachable().await.port();
+
+        let report = Doctor::new(format!("http://{fault}"))
+            .with_node_config(config.path())
+            .with_listen_port("sinfo", free_port)
+            .with_timeout(Duration::from_secs(2))
+            .run()
+            .await;
+
+        let names: Vec<_> = report.checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "node config",
+                "listen sinfo",
+                "FaultService",
+                "node down",
+                "node up"
+            ]
+        );
+        assert_eq!(outcome(&report, "node config").outcome, CheckOutcome::Pass);
+        assert_eq!(outcome(&report, "listen sinfo").outcome, CheckOutcome::Pass);
+        assert_eq!(outcome(&report, "FaultService").outcome, CheckOutcome::Pass);
+        assert_eq!(outcome(&report, "node up").outcome, CheckOutcome::Pass);
+        let down = outcome(&report, "node down");
+        assert_eq!(down.outcome, CheckOutcome::Fail);
+        assert!(down.detail.contains("unreachable"), "{}", down.detail);
+        assert!(report.is_healthy());
+        let table = report.render();
+        assert!(table.contains("[warn] node down"), "{table}");
+        assert!(table.ends_with("healthy\n"), "{table}");
+    }
+
+    #[tokio::test]
+    async fn mandatory_failures_make_the_install_unhealthy() {
+        let busy = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+        let mut config = NamedTempFile::new().unwrap();
+        config
+            .write_all(b"nodes:\n  n1:\n    available_cpus: [zero]\n")
+            .unwrap();
+
+        let report = Doctor::new(format!("http://{}", unreachable().await))
+            .with_node_config(config.path())
+            .with_listen_port("node", busy.local_addr().unwrap().port())
+            .with_timeout(Duration::from_secs(2))
+            .run()
+            .await;
+
+        assert!(!report.is_healthy());
+        let failed: Vec<_> = report.failures().map(|c| c.name.as_str()).collect();
+        assert_eq!(failed, ["node config", "listen node", "FaultService"]);
+        assert!(report
+            .render()
+            .ends_with("unhealthy: 3 mandatory check(s) failed\n"));
+    }
+}
@@ -0,0 +1,108 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Push epochs: coalesce workload updates into one delta per node.
+//!
+//! Nodes normally see a new generation as soon as `AddSchedInfo` commits
+//! it.  With `--push-epoch-ms` set, an update of a workload that is already
+//! published is *held*: nodes keep being served the last published
+//! [`Publication`] while further updates within the epoch replace the
+//! pending schedule in place.  At the next epoch boundary (a multiple of the
+//! epoch since the Unix epoch, so several Timpani-O instances flush
+//! together) the pending schedule is published as the one generation after
+//! the held one, and a node that applied the held generation gets a single
+//! delta covering every update of the epoch:
+//!
+//! ```text
+//! g5 published ── update ── update ── update ──┤ boundary: g6 = g5 + all three
+//! nodes served g5 ──────────────────────────────┘
+//! ```
+//!
+//! | Update                                    | Published                       |
+//! |-------------------------------------------|---------------------------------|
+//! | first workload of a tenant                | immediately                     |
+//! | workload of class `safety`                | immediately, with anything held |
+//! | node drain, failover, evacuation, hotplug | immediately, with anything held |
+//! | any other                                 | at the next epoch boundary      |
+//!
+//! Everything node-facing follows the publication: `GetSchedInfo` and
+//! `StreamSchedInfo` answers, the delivery ledger
+//! ([`WorkloadState::deliveries`](super::WorkloadState::deliveries)),
+//! `DELIVERY_CONFIRMED` events, apply reports and apply deadlines.
+//! Pullpiri-facing state (`GetClusterStatus`, `WORKLOAD_UPDATED` events)
+//! shows the pending generation.  Held updates are flushed on shutdown.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::task::NodeSchedMap;
+
+/// A generation as nodes are served it, kept while newer updates wait for
+/// the next epoch boundary.
+#[derive(Debug, Clone)]
+pub struct Publication {
+    pub workload_id: String,
+    pub hyperperiod_us: u64,
+    pub generation: u64,
+    pub schedule: NodeSchedMap,
+    /// Schedule of `generation - 1`, the delta base.
+    pub previous: Option<NodeSchedMap>,
+}
+
+impl Publication {
+    pub fn view(&self) -> Published<'_> {
+        Published {
+            workload_id: &self.workload_id,
+            hyperperiod_us: self.hyperperiod_us,
+            generation: self.generation,
+            schedule: &self.schedule,
+            previous: self.previous.as_ref(),
+        }
+    }
+}
+
+/// Borrowed form of a [`Publication`]: what nodes are served right now.
+#[derive(Debug, Clone, Copy)]
+pub struct Published<'a> {
+    pub workload_id: &'a str,
+    pub hyperperiod_us: u64,
+    pub generation: u64,
+    pub schedule: &'a NodeSchedMap,
+    pub previous: Option<&'a NodeSchedMap>,
+}
+
+/// Time from `now` to the next multiple of `epoch` since the Unix epoch.
+/// A zero `epoch` is treated as one millisecond.
+pub fn until_boundary(epoch: Duration, now: SystemTime) -> Duration {
+    let epoch_ns = epoch.as_nanos().max(1_000_000);
+    let since = now
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let left = epoch_ns - since % epoch_ns;
+    Duration::from_nanos(left as u64)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boundaries_are_multiples_of_the_epoch() {
+        let second = Duration::from_secs(1);
+        let at = |ms| UNIX_EPOCH + Duration::from_millis(ms);
+        assert_eq!(
+            until_boundary(second, at(1_760_000_000_250)),
+            Duration::from_millis(750)
+        );
+        // On a boundary the next one is a full epoch away.
+        assert_eq!(until_boundary(second, at(1_760_000_000_000)), second);
+        assert_eq!(
+            until_boundary(Duration::ZERO, at(5)),
+            Duration::from_millis(1)
+        );
+    }
+}
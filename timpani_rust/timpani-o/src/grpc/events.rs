@@ -0,0 +1,250 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Schedule change events for `SchedInfoService::WatchScheduleEvents`.
+//!
+//! Both services [`record`](EventLog::record) an event wherever they change
+//! the workload store, a task's lifecycle state or a node's delivery
+//! progress:
+//!
+//! | Event                   | Recorded by                                                |
+//! |-------------------------|------------------------------------------------------------|
+//! | `WORKLOAD_SCHEDULED`    | `AddSchedInfo` or a pending retry, tenant's first workload |
+//! | `WORKLOAD_UPDATED`      | replacement, drain step, orphan evacuation, compaction     |
+//! | `WORKLOAD_REMOVED`      | `RemoveWorkload`                                           |
+//! | `WORKLOAD_EXPIRED`      | workload removed at the end of its `ttl_seconds`           |
+//! | `NODE_CORDONED`         | `CordonNode`, `DrainNode` of an uncordoned node            |
+//! | `NODE_UNCORDONED`       | `UncordonNode` of a cordoned node                          |
+//! | `NODE_DRAINED`          | drain step that leaves no movable task                     |
+//! | `DELIVERY_CONFIRMED`    | `GetSchedInfo` for a new generation, stream commit         |
+//! | `DELIVERY_FAILED`       | stream that lost a batch or its node                       |
+//! | `FAULT_RAISED`          | `ReportDMiss` for a placed task                            |
+//! | `FAULT_CLEARED`         | faulted task removed, replaced or moved                    |
+//! | `APPLY_OVERDUE`         | missed apply deadline (see `watchdog`)                     |
+//! | `COMPACTION_PROPOSED`   | idle-time re-placement found a better layout               |
+//! | `TRANSACTION_COMMITTED` | every node staged a transactional push (see `txn`)         |
+//! | `TRANSACTION_ABORTED`   | transactional push failed on a node or timed out           |
+//! | `NODES_RECONCILED`      | end of the window after startup (see `reconcile`)          |
+//!
+//! Every event gets the next sequence number and is kept in a bounded
+//! in-memory log, so a late subscriber can replay the recent past before
+//! switching to live events.  Live events go out on a bounded
+//! `tokio::sync::broadcast` channel: recording never waits for a
+//! subscriber.  A subscriber that falls more than the channel capacity
+//! behind loses the oldest events and receives one `EVENTS_DROPPED` event
+//! with the number it missed.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::warn;
+
+use crate::proto::schedinfo_v1::{ScheduleEvent, ScheduleEventKind};
+
+/// Events kept for replay when no capacity is configured.
+pub const DEFAULT_EVENT_LOG_CAPACITY: usize = 256;
+
+/// Live events a subscriber may fall behind by before it loses some.
+pub const DEFAULT_EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// An event of `kind` with every other field empty.
+pub fn event(kind: ScheduleEventKind) -> ScheduleEvent {
+    ScheduleEvent {
+        kind: kind as i32,
+        ..Default::default()
+    }
+}
+
+// ── EventLog ──────────────────────────────────────────────────────────────────
+
+struct LogState {
+    next_sequence: u64,
+    recent: VecDeque<ScheduleEvent>,
+}
+
+/// Sequenced, bounded event log with live fan-out (see the module docs).
+pub struct EventLog {
+    state: Mutex<LogState>,
+    tx: broadcast::Sender<ScheduleEvent>,
+    capacity: usize,
+    /// Events lost by lagging subscribers, summed over all of them.
+    dropped: Arc<AtomicU64>,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_LOG_CAPACITY, DEFAULT_EVENT_CHANNEL_CAPACITY)
+    }
+}
+
+impl EventLog {
+    /// Keep the last `capacity` events for replay; let a subscriber lag
+    /// `channel_capacity` (at least one) live events behind.
+    pub fn new(capacity: usize, channel_capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(channel_capacity.max(1));
+        Self {
+            state: Mutex::new(LogState {
+                next_sequence: 1,
+                recent: VecDeque::new(),
+            }),
+            tx,
+            capacity,
+            dropped: Arc::default(),
+        }
+    }
+
+    /// Stamp `event` with the next sequence number and the current time,
+    /// keep it and send it to the subscribers.  Returns the sequence number.
+    pub fn record(&self, mut event: ScheduleEvent) -> u64 {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        event.sequence = state.next_sequence;
+        event.timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        state.next_sequence += 1;
+
+        if state.recent.len() >= self.capacity {
+            state.recent.pop_front();
+        }
+        if self.capacity > 0 {
+            state.recent.push_back(event.clone());
+        }
+        // No subscribers is not an error.
+        let _ = self.tx.send(event);
+        state.next_sequence - 1
+    }
+
+    /// Subscribe to the events `keep` accepts, starting with the last
+    /// `backlog` retained ones (all retained if `None`).
+    ///
+    /// Replay and live events join without a gap or a duplicate.
+    pub fn subscribe(
+        &self,
+        backlog: Option<usize>,
+        keep: impl Fn(&ScheduleEvent) -> bool + Send + 'static,
+    ) -> Subscription {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut replay: VecDeque<ScheduleEvent> =
+            state.recent.iter().filter(|e| keep(e)).cloned().collect();
+        if let Some(n) = backlog {
+            let skip = replay.len().saturating_sub(n);
+            replay.drain(..skip);
+        }
+        Subscription {
+            replay,
+            rx: self.tx.subscribe(),
+            keep: Box::new(keep),
+            dropped: Arc::clone(&self.dropped),
+        }
+    }
+
+    /// Events lost by lagging subscribers so far.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+// ── Subscription ──────────────────────────────────────────────────────────────
+
+/// One subscriber's view of an [`EventLog`].
+pub struct Subscription {
+    replay: VecDeque<ScheduleEvent>,
+    rx: broadcast::Receiver<ScheduleEvent>,
+    keep: Box<dyn Fn(&ScheduleEvent) -> bool + Send>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl Subscription {
+    /// The next replayed or live event, or an `EVENTS_DROPPED` event after
+    /// falling behind.  `None` once the log is gone.
+    pub async fn next(&mut self) -> Option<ScheduleEvent> {
+        if let Some(e) = self.replay.pop_front() {
+            return Some(e);
+        }
+        loop {
+            match self.rx.recv().await {
+                Ok(e) if (self.keep)(&e) => return Some(e),
+                Ok(_) => {}
+                Err(RecvError::Lagged(n)) => {
+                    self.dropped.fetch_add(n, Ordering::Relaxed);
+                    warn!(dropped = n, "schedule event subscriber fell behind");
+                    return Some(ScheduleEvent {
+                        dropped: n,
+                        ..event(ScheduleEventKind::EventsDropped)
+                    });
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn removed(tenant: &str) -> ScheduleEvent {
+        ScheduleEvent {
+            tenant: tenant.into(),
+            ..event(ScheduleEventKind::WorkloadRemoved)
+        }
+    }
+
+    #[tokio::test]
+    async fn late_subscriber_replays_the_retained_backlog_then_live_events() {
+        let log = EventLog::new(3, 8);
+        for _ in 0..5 {
+            log.record(removed("a"));
+        }
+
+        let mut all = log.subscribe(None, |_| true);
+        let mut last = log.subscribe(Some(1), |_| true);
+        let mut none = log.subscribe(Some(0), |_| true);
+        assert_eq!(log.record(removed("a")), 6);
+
+        let mut seqs = Vec::new();
+        for _ in 0..4 {
+            seqs.push(all.next().await.unwrap().sequence);
+        }
+        assert_eq!(seqs, [3, 4, 5, 6]);
+        assert_eq!(last.next().await.unwrap().sequence, 5);
+        assert_eq!(last.next().await.unwrap().sequence, 6);
+        assert_eq!(none.next().await.unwrap().sequence, 6);
+    }
+
+    #[tokio::test]
+    async fn filter_applies_to_replay_and_live_events() {
+        let log = EventLog::default();
+        log.record(removed("a"));
+        log.record(removed("b"));
+        let mut sub = log.subscribe(None, |e| e.tenant == "b");
+        log.record(removed("a"));
+        log.record(removed("b"));
+
+        assert_eq!(sub.next().await.unwrap().sequence, 2);
+        assert_eq!(sub.next().await.unwrap().sequence, 4);
+    }
+
+    #[tokio::test]
+    async fn slow_subscriber_is_told_how_many_events_it_lost() {
+        let log = EventLog::new(0, 2);
+        let mut sub = log.subscribe(None, |_| true);
+        for _ in 0..5 {
+            log.record(removed("a"));
+        }
+
+        let lag = sub.next().await.unwrap();
+        assert_eq!(lag.kind(), ScheduleEventKind::EventsDropped);
+        assert_eq!(lag.dropped, 3);
+        assert_eq!(log.dropped(), 3);
+        assert_eq!(sub.next().await.unwrap().sequence, 4);
+        assert_eq!(sub.next().await.unwrap().sequence, 5);
+    }
+}
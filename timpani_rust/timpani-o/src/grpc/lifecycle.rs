@@ -0,0 +1,294 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Per-task lifecycle tracking for the active workload.
+//!
+//! ```text
+//!  Pending ──deliver──► Delivered ──apply──► Applied ──start──► Running
+//!     │                    │                   ▲
+//!     └───────expire───────┴──► Degraded ──apply┘
+//!  (any) ──fault──► Faulted
+//!  (any) ──remove──► Removed
+//! ```
+//!
+//! | Event    | Raised by                                                     |
+//! |----------|---------------------------------------------------------------|
+//! | deliver  | `GetSchedInfo` response containing the task                   |
+//! | apply    | `SyncTimer` from the task's node (it applied and is ready)    |
+//! | start    | SyncTimer barrier released (the RT loop starts)               |
+//! | expire   | the node's apply deadline passed (see [`super::watchdog`])    |
+//! | fault    | `ReportDMiss` — the node reports once `max_dmiss` is exceeded |
+//! | remove   | `RemoveWorkload`                                              |
+//!
+//! An event that is not legal in the current state is **not** applied: it is
+//! logged and counted in [`TaskStates::illegal_transitions`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use tracing::warn;
+
+use crate::task::NodeSchedMap;
+
+// ── TaskState ─────────────────────────────────────────────────────────────────
+
+/// Where a placed task is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TaskState {
+    Pending,
+    Delivered,
+    Applied,
+    Running,
+    /// Not applied by its node within the apply deadline.
+    Degraded,
+    Faulted,
+    Removed,
+}
+
+/// Something that happened to a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskEvent {
+    Deliver,
+    Apply,
+    Start,
+    Expire,
+    Fault,
+    Remove,
+}
+
+impl TaskState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskState::Pending => "pending",
+            TaskState::Delivered => "delivered",
+            TaskState::Applied => "applied",
+            TaskState::Running => "running",
+            TaskState::Degraded => "degraded",
+            TaskState::Faulted => "faulted",
+            TaskState::Removed => "removed",
+        }
+    }
+
+    /// The transition table.  `None` means the event is illegal here.
+    ///
+    /// Re-delivery and repeated start/fault events are idempotent so that a
+    /// node retrying an RPC is not counted as an error.  A degraded task
+    /// stays degraded when delivered again and recovers when applied.
+    pub fn on(self, event: TaskEvent) -> Option<TaskState> {
+        use TaskEvent as E;
+        use TaskState as S;
+        match (self, event) {
+            (S::Removed, _) => None,
+            (_, E::Remove) => Some(S::Removed),
+            (_, E::Fault) => Some(S::Faulted),
+            (S::Pending | S::Delivered, E::Deliver) => Some(S::Delivered),
+            (S::Delivered | S::Applied | S::Degraded, E::Apply) => Some(S::Applied),
+            (S::Pending | S::Delivered | S::Degraded, E::Expire) => Some(S::Degraded),
+            (S::Degraded, E::Deliver) => Some(S::Degraded),
+            (S::Applied | S::Running, E::Start) => Some(S::Running),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TaskState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ── TaskStates ────────────────────────────────────────────────────────────────
+
+/// Lifecycle state of every task in one workload, keyed by `(node, task)`.
+#[derive(Debug, Clone, Default)]
+pub struct TaskStates {
+    states: BTreeMap<(String, String), TaskState>,
+    illegal: u64,
+}
+
+impl TaskStates {
+    /// Every task in `schedule` starts [`TaskState::Pending`].
+    pub fn from_schedule(schedule: &NodeSchedMap) -> Self {
+        let states = schedule
+            .iter()
+            .flat_map(|(node, tasks)| {
+                tasks
+                    .iter()
+                    .map(|t| ((node.clone(), t.name.clone()), TaskState::Pending))
+            })
+            .collect();
+        Self { states, illegal: 0 }
+    }
+
+    /// States for `schedule`: tasks still on the same node keep their state,
+    /// tasks new to a node start [`TaskState::Pending`].
+    pub fn rebased(&self, schedule: &NodeSchedMap) -> Self {
+        let mut next = Self::from_schedule(schedule);
+        for (key, state) in next.states.iter_mut() {
+            if let Some(prev) = self.states.get(key) {
+                *state = *prev;
+            }
+        }
+        next.illegal = self.illegal;
+        next
+    }
+
+    pub fn get(&self, node: &str, task: &str) -> Option<TaskState> {
+        self.states
+            .get(&(node.to_string(), task.to_string()))
+            .copied()
+    }
+
+    /// Apply `event` to one task.  Returns `false` (and counts it) if the
+    /// task is unknown or the transition is illegal.
+    pub fn apply(&mut self, node: &str, task: &str, event: TaskEvent) -> bool {
+        let Some(state) = self.states.get_mut(&(node.to_string(), task.to_string())) else {
+            self.illegal += 1;
+            warn!(node, task, ?event, "task lifecycle: event for unknown task");
+            return false;
+        };
+        match state.on(event) {
+            Some(next) => {
+                *state = next;
+                true
+            }
+            None => {
+                self.illegal += 1;
+                warn!(node, task, ?event, from = %state, "task lifecycle: illegal transition ignored");
+                false
+            }
+        }
+    }
+
+    /// Apply `event` to every task on `node`.
+    pub fn apply_node(&mut self, node: &str, event: TaskEvent) {
+        let tasks: Vec<String> = self
+            .states
+            .keys()
+            .filter(|(n, _)| n == node)
+            .map(|(_, t)| t.clone())
+            .collect();
+        for t in tasks {
+            self.apply(node, &t, event);
+        }
+    }
+
+    /// Apply `event` to every task.
+    pub fn apply_all(&mut self, event: TaskEvent) {
+        let keys: Vec<(String, String)> = self.states.keys().cloned().collect();
+        for (n, t) in keys {
+            self.apply(&n, &t, event);
+        }
+    }
+
+    /// `((node, task), state)` in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = (&(String, String), &TaskState)> {
+        self.states.iter()
+    }
+
+    /// `(node, task)` of every [`TaskState::Faulted`] task, sorted.
+    pub fn faulted(&self) -> impl Iterator<Item = &(String, String)> {
+        self.states
+            .iter()
+            .filter(|(_, s)| **s == TaskState::Faulted)
+            .map(|(k, _)| k)
+    }
+
+    /// Number of rejected events so far.
+    pub fn illegal_transitions(&self) -> u64 {
+        self.illegal
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TaskEvent as E;
+    use TaskState as S;
+
+    #[test]
+    fn happy_path() {
+        let mut s = S::Pending;
+        for (e, expect) in [
+            (E::Deliver, S::Delivered),
+            (E::Apply, S::Applied),
+            (E::Start, S::Running),
+            (E::Fault, S::Faulted),
+            (E::Remove, S::Removed),
+        ] {
+            s = s.on(e).unwrap();
+            assert_eq!(s, expect);
+        }
+    }
+
+    #[test]
+    fn transition_table() {
+        let all_states = [
+            S::Pending,
+            S::Delivered,
+            S::Applied,
+            S::Running,
+            S::Degraded,
+            S::Faulted,
+            S::Removed,
+        ];
+        let all_events = [
+            E::Deliver,
+            E::Apply,
+            E::Start,
+            E::Expire,
+            E::Fault,
+            E::Remove,
+        ];
+        let legal = [
+            (S::Pending, E::Deliver, S::Delivered),
+            (S::Delivered, E::Deliver, S::Delivered),
+            (S::Delivered, E::Apply, S::Applied),
+            (S::Applied, E::Apply, S::Applied),
+            (S::Applied, E::Start, S::Running),
+            (S::Running, E::Start, S::Running),
+            (S::Pending, E::Expire, S::Degraded),
+            (S::Delivered, E::Expire, S::Degraded),
+            (S::Degraded, E::Expire, S::Degraded),
+            (S::Degraded, E::Deliver, S::Degraded),
+            (S::Degraded, E::Apply, S::Applied),
+        ];
+        for s in all_states {
+            for e in all_events {
+                let expected = if s == S::Removed {
+                    None
+                } else if e == E::Remove {
+                    Some(S::Removed)
+                } else if e == E::Fault {
+                    Some(S::Faulted)
+                } else {
+                    legal
+                        .iter()
+                        .find(|(from, ev, _)| *from == s && *ev == e)
+                        .map(|(_, _, to)| *to)
+                };
+                assert_eq!(s.on(e), expected, "{s} --{e:?}-->");
+            }
+        }
+    }
+
+    #[test]
+    fn illegal_transitions_are_counted_not_applied() {
+        let schedule: NodeSchedMap = [("n1".to_string(), vec![])].into();
+        let mut states = TaskStates::from_schedule(&schedule);
+        assert!(!states.apply("n1", "ghost", E::Deliver));
+
+        let mut states2 = TaskStates {
+            states: [(("n1".into(), "t".into()), S::Pending)].into(),
+            illegal: 0,
+        };
+        assert!(!states2.apply("n1", "t", E::Start));
+        assert_eq!(states2.get("n1", "t"), Some(S::Pending));
+        assert_eq!(states2.illegal_transitions(), 1);
+        assert_eq!(states.illegal_transitions(), 1);
+    }
+}
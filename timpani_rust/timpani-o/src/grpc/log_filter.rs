@@ -0,0 +1,213 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! The log filter in force, changeable without a restart.
+//!
+//! `timpani-o` logs through a `tracing_subscriber::reload` layer holding an
+//! `EnvFilter`, initially from `RUST_LOG`.  [`LogFilter`] pairs the function
+//! that parses and swaps that filter with the directives now in force, so
+//! the services need no `tracing-subscriber` themselves:
+//!
+//! | Where                        | What                                  |
+//! |------------------------------|---------------------------------------|
+//! | `AdminService.GetLogFilter`  | [`LogFilter::current`]                |
+//! | `AdminService.SetLogFilter`  | [`LogFilter::set`]                    |
+//! | `ClusterStatus.log_filter`   | [`LogFilter::current`]; empty without |
+//! | `timpani-o log set-level`    | `SetLogFilter`                        |
+//!
+//! A filter that does not parse is rejected and the one in force stays.
+//! The change is not persisted: a restart starts from `RUST_LOG` again.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use thiserror::Error;
+
+/// Parses `RUST_LOG`-style directives and makes them the filter in force;
+/// `Err` with the parser's message if they do not parse.
+pub type Reload = dyn Fn(&str) -> Result<(), String> + Send + Sync;
+
+/// Why a new filter was not applied.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LogFilterError {
+    #[error("log filter is empty")]
+    Empty,
+
+    #[error("invalid log filter '{filter}': {reason}")]
+    Invalid { filter: String, reason: String },
+}
+
+/// The process's log filter (see the module docs).  Clones share it.
+#[derive(Clone)]
+pub struct LogFilter {
+    /// Held across a reload, so `current` always names the filter applied.
+    current: Arc<Mutex<String>>,
+    reload: Arc<Reload>,
+}
+
+impl LogFilter {
+    /// `initial` is in force; `reload` applies a new filter.
+    pub fn new(
+        initial: impl Into<String>,
+        reload: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            current: Arc::new(Mutex::new(initial.into())),
+            reload: Arc::new(reload),
+        }
+    }
+
+    /// The directives in force.
+    pub fn current(&self) -> String {
+        self.current
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Apply `filter`; returns the directives it replaced.  On error the
+    /// filter in force is unchanged.
+    pub fn set(&self, filter: &str) -> Result<String, LogFilterError> {
+        let filter = filter.trim();
+        if filter.is_empty() {
+            return Err(LogFilterError::Empty);
+        }
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        (self.reload)(filter).map_err(|reason| LogFilterError::Invalid {
+            filter: filter.to_string(),
+            reason,
+        })?;
+        Ok(std::mem::replace(&mut current, filter.to_string()))
+    }
+}
+
+impl fmt::Debug for LogFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogFilter")
+            .field("current", &self.current())
+            .finish_non_exhaustive()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use tonic::{Code, Request};
+    use tracing::debug;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::{fmt as fmt_layer, reload, EnvFilter};
+
+    use super::*;
+    use crate::config::{NodeConfig, NodeConfigManager};
+    use crate::fault::{test_support::MockFaultNotifier, FaultNotifier};
+    use crate::grpc::admin_service::AdminServiceImpl;
+    use crate::grpc::{new_workload_store, schedinfo_service::SchedInfoServiceImpl};
+    use crate::proto::schedinfo_v1::{
+        admin_service_server::AdminService, sched_info_service_server::SchedInfoService,
+        LogFilterQuery, LogFilterUpdate,
+    };
+
+    /// Shared buffer the test subscriber writes to.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn set_log_filter_lets_suppressed_debug_events_through() {
+        // What main.rs installs, writing to `capture`.
+        let (layer, handle) = reload::Layer::new(EnvFilter::new("info"));
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::registry().with(layer).with(
+            fmt_layer::layer()
+                .with_ansi(false)
+                .with_writer(move || writer.clone()),
+        );
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let filter = LogFilter::new("info", move |directives| {
+            let filter = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+            handle.reload(filter).map_err(|e| e.to_string())
+        });
+
+        let svc = SchedInfoServiceImpl::new(
+            Arc::new(NodeConfigManager::from_nodes(vec![
+                NodeConfig::default_config("n1"),
+            ])),
+            new_workload_store(),
+            MockFaultNotifier::arc() as Arc<dyn FaultNotifier>,
+        )
+        .with_log_filter(filter);
+        let admin = AdminServiceImpl::new(svc.clone());
+
+        debug!("probe before");
+        let r = admin
+            .set_log_filter(Request::new(LogFilterUpdate {
+                filter: "debug".into(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!((r.previous.as_str(), r.filter.as_str()), ("info", "debug"));
+        debug!("probe after");
+
+        // Rejected, and debug events keep coming.
+        let err = admin
+            .set_log_filter(Request::new(LogFilterUpdate {
+                filter: "timpani_o=loudest".into(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        let now = admin
+            .get_log_filter(Request::new(LogFilterQuery {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(now.filter, "debug");
+        debug!("probe still");
+
+        let status = svc
+            .get_cluster_status(Request::new(Default::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(status.log_filter, "debug");
+
+        let log = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        assert!(!log.contains("probe before"), "{log}");
+        assert!(log.contains("probe after"), "{log}");
+        assert!(log.contains("probe still"), "{log}");
+    }
+
+    #[test]
+    fn empty_or_unparsable_filters_change_nothing() {
+        let filter = LogFilter::new("info", |d| {
+            if d.contains('!') {
+                Err("bad".into())
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(filter.set("  "), Err(LogFilterError::Empty));
+        assert!(matches!(
+            filter.set("debug!"),
+            Err(LogFilterError::Invalid { .. })
+        ));
+        assert_eq!(filter.current(), "info");
+        assert_eq!(filter.set(" warn ").unwrap(), "info");
+        assert_eq!(filter.current(), "warn");
+    }
+}
@@ -17,25 +17,101 @@ SPDX-License-Identifier: MIT
 //!                             WorkloadStore  (Arc<Mutex<Option<WorkloadState>>>)
 //!                                     │  reads
 //!                                     ▼
-//!   Timpani-N ──GetSchedInfo──► NodeServiceImpl
+//!   Timpani-N ──GetSchedInfo──► NodeServiceImpl  (or StreamSchedInfo)
 //!   Timpani-N ──SyncTimer    ──► NodeServiceImpl  (holds watch::Receiver)
 //!   Timpani-N ──ReportDMiss  ──► NodeServiceImpl
+//!   Timpani-N ──ReportApply  ──► NodeServiceImpl
+//!   Timpani-N ──PrepareSchedInfo► NodeServiceImpl  (holds watch::Receiver)
 //! ```
 //!
 //! The `Mutex` is held briefly: only while reading/writing `WorkloadState`.
 //! `SyncTimer` acquires the lock to register the node and obtain a
 //! `watch::Receiver`, then releases it before awaiting the barrier.
+//!
+//! # Tenants
+//!
+//! Several Pullpiri instances (or a test and a production pipeline) may share
+//! one Timpani-O.  Every RPC carries its tenant in the [`TENANT_METADATA_KEY`]
+//! metadata entry; callers that omit it use [`DEFAULT_TENANT`], which keeps the
+//! single-tenant behaviour.  The store holds one active workload **per
+//! tenant**, so equal `workload_id`s from different tenants never collide,
+//! and nodes only see the workload of the tenant they announce.
+//!
+//! Tenants share the physical CPUs: a new workload is placed on the headroom
+//! left by the other tenants' active schedules.  Workloads that only fail
+//! for lack of that headroom may wait in the [`pending`] queue.
+//!
+//! # Events
+//!
+//! Both services record schedule changes in a shared [`events::EventLog`],
+//! which `SchedInfoService::WatchScheduleEvents` streams to subscribers.
+//!
+//! # Administration
+//!
+//! [`admin_service`] wraps the `SchedInfoServiceImpl` for operator actions
+//! on nodes (cordon, uncordon, drain) and for applying [`compaction`]
+//! proposals, and is served on a separate address.  It also reads and
+//! replaces the process's [`log_filter`].
 
+pub mod admin_client;
+pub mod admin_service;
+pub mod clock;
+pub mod compaction;
+pub mod doctor;
+pub mod epoch;
+pub mod events;
+pub mod lifecycle;
+pub mod log_filter;
 pub mod node_service;
+pub mod pending;
+pub mod protocol;
+pub mod reconcile;
+pub mod repro;
+pub mod revision;
 pub mod schedinfo_service;
+pub mod status_client;
+pub mod stream;
+pub mod txn;
+pub mod watchdog;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use tokio::sync::{watch, Mutex};
+use tonic::metadata::MetadataMap;
 
 use crate::hyperperiod::HyperperiodInfo;
-use crate::task::NodeSchedMap;
+use crate::metadata::Metadata;
+use crate::proto::schedinfo_v1::ApplyReport;
+use crate::report::ScheduleDiff;
+use crate::scheduler::PriorityClass;
+use crate::task::{FaultSink, NodeSchedMap, Task};
+use epoch::{Publication, Published};
+use lifecycle::TaskStates;
+use protocol::NodeFeatures;
+use repro::ReproBundle;
+use stream::DeliveryProgress;
+use txn::Transaction;
+
+// ── Tenants ───────────────────────────────────────────────────────────────────
+
+/// Request metadata key naming the caller's tenant.
+pub const TENANT_METADATA_KEY: &str = "x-timpani-tenant";
+
+/// Tenant used when a request carries no (or an empty) tenant.
+pub const DEFAULT_TENANT: &str = "default";
+
+/// Resolve the caller's tenant from request metadata.
+pub(crate) fn tenant_from_metadata(metadata: &MetadataMap) -> String {
+    metadata
+        .get(TENANT_METADATA_KEY)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .unwrap_or(DEFAULT_TENANT)
+        .to_string()
+}
 
 // ── BarrierStatus ─────────────────────────────────────────────────────────────
 
@@ -98,6 +174,55 @@ pub struct WorkloadState {
     /// `NodeService::sync_timer` subscribes to this sender while holding the
     /// `WorkloadStore` lock, then awaits the receiver after releasing the lock.
     pub barrier_tx: watch::Sender<BarrierStatus>,
+
+    /// Monotonic schedule generation — 1 for the first workload, incremented
+    /// each time a new schedule replaces the stored one.
+    pub generation: u64,
+
+    /// Schedule of generation `generation - 1`, kept so `GetSchedInfo` can
+    /// answer a node one generation behind with a delta.
+    pub previous: Option<NodeSchedMap>,
+
+    /// Lifecycle state of each placed task (all `Pending` at construction).
+    pub task_states: TaskStates,
+
+    /// Tasks as submitted, with `target_node_policy` resolved against the
+    /// algorithm used.  Lets a node drain tell pinned tasks from movable ones.
+    pub tasks: Vec<Task>,
+
+    /// `SchedInfo.priority_class` of the request; lower classes drain first.
+    pub priority_class: PriorityClass,
+
+    /// `SchedInfo.importance` of the request; within a class, lower drains
+    /// first.
+    pub importance: i32,
+
+    /// When the workload expires (`SchedInfo.ttl_seconds`); `None` = never.
+    pub expires_at: Option<Instant>,
+
+    /// Per-node progress of the latest `StreamSchedInfo` delivery.
+    pub deliveries: BTreeMap<String, DeliveryProgress>,
+
+    /// Per-node features announced with the latest `GetSchedInfo` /
+    /// `StreamSchedInfo` call (see [`protocol`]).  Carried over to the
+    /// successor generation.
+    pub protocols: BTreeMap<String, NodeFeatures>,
+
+    /// Per-node latest `ReportApply`: how each task's attributes were
+    /// applied, and what the node could do at the time.
+    pub apply_reports: BTreeMap<String, ApplyReport>,
+
+    /// What nodes are served while a newer schedule waits for the next push
+    /// epoch (see [`epoch`]); `None` = the current schedule is published.
+    pub held: Option<Publication>,
+
+    /// Inputs and result of the admission that produced this workload (see
+    /// [`repro`]).  Kept across drains and other reschedules.
+    pub repro: Option<Arc<ReproBundle>>,
+
+    /// Open transaction of the current generation (see [`txn`]); `None` =
+    /// not being prepared.  Nodes run [`held`](Self::held) meanwhile.
+    pub txn: Option<Transaction>,
 }
 
 impl WorkloadState {
@@ -107,6 +232,7 @@ impl WorkloadState {
     /// that actually received tasks must participate in the sync barrier.
     pub fn new(workload_id: String, schedule: NodeSchedMap, hyperperiod: HyperperiodInfo) -> Self {
         let active_nodes: BTreeSet<String> = schedule.keys().cloned().collect();
+        let task_states = TaskStates::from_schedule(&schedule);
         let (barrier_tx, _) = watch::channel(BarrierStatus::Waiting);
         Self {
             workload_id,
@@ -115,25 +241,255 @@ impl WorkloadState {
             active_nodes,
             synced_nodes: BTreeSet::new(),
             barrier_tx,
+            generation: 1,
+            previous: None,
+            task_states,
+            tasks: Vec::new(),
+            priority_class: PriorityClass::default(),
+            importance: 0,
+            expires_at: None,
+            deliveries: BTreeMap::new(),
+            protocols: BTreeMap::new(),
+            apply_reports: BTreeMap::new(),
+            held: None,
+            repro: None,
+            txn: None,
+        }
+    }
+
+    /// Metadata of `task` if it is placed on `node`.
+    pub fn task_metadata(&self, node: &str, task: &str) -> Option<&Metadata> {
+        self.schedule
+            .get(node)?
+            .iter()
+            .find(|t| t.name == task)
+            .map(|t| &t.metadata)
+    }
+
+    /// Whether `task` is placed on `node` as a placeholder
+    /// ([`Task::placeholder`](crate::task::Task::placeholder)).
+    pub fn is_placeholder(&self, node: &str, task: &str) -> bool {
+        self.schedule
+            .get(node)
+            .is_some_and(|ts| ts.iter().any(|t| t.name == task && t.placeholder))
+    }
+
+    /// Where `task`'s deadline misses go on `node`: the sink in effect
+    /// according to the node's latest [`ApplyReport`], else the one it was
+    /// scheduled with.  A node without a local sink runs every task
+    /// [`FaultSink::Upstream`].
+    pub fn fault_sink(&self, node: &str, task: &str) -> FaultSink {
+        let reported = self
+            .apply_reports
+            .get(node)
+            .and_then(|r| r.tasks.iter().find(|t| t.task_name == task));
+        if let Some(t) = reported {
+            return FaultSink::from_proto_int(t.fault_sink);
+        }
+        self.schedule
+            .get(node)
+            .and_then(|ts| ts.iter().find(|t| t.name == task))
+            .map_or(FaultSink::Upstream, |t| t.fault_sink)
+    }
+
+    /// Memory (`Task::memory_mb`) of the tasks placed on `node`.
+    pub fn placed_memory_mb(&self, node: &str) -> u64 {
+        let placed: BTreeSet<&str> = self
+            .schedule
+            .get(node)
+            .into_iter()
+            .flatten()
+            .map(|t| t.name.as_str())
+            .collect();
+        self.tasks
+            .iter()
+            .filter(|t| placed.contains(t.name.as_str()))
+            .map(|t| t.memory_mb)
+            .sum()
+    }
+
+    /// Record the submitted tasks (see [`tasks`](Self::tasks)).
+    pub fn with_tasks(mut self, tasks: Vec<Task>) -> Self {
+        self.tasks = tasks;
+        self
+    }
+
+    pub fn with_repro(mut self, bundle: ReproBundle) -> Self {
+        self.repro = Some(Arc::new(bundle));
+        self
+    }
+
+    pub fn with_priority_class(mut self, class: PriorityClass) -> Self {
+        self.priority_class = class;
+        self
+    }
+
+    pub fn with_importance(mut self, importance: i32) -> Self {
+        self.importance = importance;
+        self
+    }
+
+    pub fn with_expiry(mut self, expires_at: Option<Instant>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
+    /// Time left at `now` before the workload expires; zero once it has.
+    pub fn ttl_remaining_at(&self, now: Instant) -> Option<Duration> {
+        self.expires_at.map(|at| at.saturating_duration_since(now))
+    }
+
+    /// Replace the schedule in place as the next generation, keeping the old
+    /// one as the delta base.
+    ///
+    /// Unlike a replacement workload the barrier is left alone: tasks that
+    /// stay where they were keep their lifecycle state, and nodes pick up the
+    /// change with their next `GetSchedInfo`.
+    ///
+    /// Published at once, together with any update held for a push epoch,
+    /// ending an open transaction.
+    pub fn reschedule(&mut self, schedule: NodeSchedMap) {
+        self.txn = None;
+        self.task_states = self.task_states.rebased(&schedule);
+        self.active_nodes = schedule.keys().cloned().collect();
+        let replaced = std::mem::replace(&mut self.schedule, schedule);
+        match self.held.take() {
+            Some(held) => {
+                self.previous = Some(held.schedule);
+                self.generation = held.generation + 1;
+            }
+            None => {
+                self.previous = Some(replaced);
+                self.generation += 1;
+            }
+        }
+    }
+
+    /// Nodes with something new to apply in this generation: every active
+    /// node of a first generation, else those whose task list changed.
+    pub fn nodes_to_apply(&self) -> Vec<&str> {
+        let diff = self
+            .previous
+            .as_ref()
+            .map(|prev| ScheduleDiff::between(prev, &self.schedule));
+        self.active_nodes
+            .iter()
+            .filter(|n| diff.as_ref().is_none_or(|d| d.node(n).is_some()))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Make this state the successor of `prev`: one generation later, with
+    /// `prev`'s schedule retained as the delta base.
+    ///
+    /// If `prev` is [held](Self::hold), so is the result: it stays the one
+    /// generation after the held publication, with that as the delta base.
+    pub fn succeeding(mut self, prev: WorkloadState) -> Self {
+        self.protocols = prev.protocols;
+        match prev.held {
+            Some(held) => {
+                self.generation = held.generation + 1;
+                self.previous = Some(held.schedule.clone());
+                self.held = Some(held);
+            }
+            None => {
+                self.generation = prev.generation + 1;
+                self.previous = Some(prev.schedule);
+            }
+        }
+        self
+    }
+
+    /// Keep serving nodes the current generation until [`publish`]
+    /// (see [`epoch`]).  No-op if something is already held.
+    ///
+    /// [`publish`]: Self::publish
+    pub fn hold(&mut self) {
+        if self.held.is_none() {
+            self.held = Some(Publication {
+                workload_id: self.workload_id.clone(),
+                hyperperiod_us: self.hyperperiod.hyperperiod_us.as_u64(),
+                generation: self.generation,
+                schedule: self.schedule.clone(),
+                previous: self.previous.clone(),
+            });
+        }
+    }
+
+    /// Prepare this state as the successor of `prior` in a transaction (see
+    /// [`txn`]): nodes keep running `prior`'s published generation, which
+    /// comes back on abort.  An open transaction of `prior` is abandoned.
+    pub fn preparing(mut self, prior: Option<WorkloadState>) -> Self {
+        let prior = prior.and_then(WorkloadState::settled);
+        if let Some(prior) = prior.as_ref() {
+            self.protocols = prior.protocols.clone();
+        }
+        if let Some(running) = prior.as_ref().map(WorkloadState::published) {
+            self.generation = running.generation + 1;
+            self.previous = Some(running.schedule.clone());
+            self.held = Some(Publication {
+                workload_id: running.workload_id.to_string(),
+                hyperperiod_us: running.hyperperiod_us,
+                generation: running.generation,
+                schedule: running.schedule.clone(),
+                previous: running.previous.cloned(),
+            });
+        }
+        let nodes = txn::changed_nodes(self.previous.as_ref(), &self.schedule);
+        self.txn = Some(Transaction::new(nodes.iter().map(String::as_str), prior));
+        self
+    }
+
+    /// This state without an open transaction: the one it would roll back
+    /// to, if it is preparing (`None` if there is none), else itself.
+    pub fn settled(mut self) -> Option<WorkloadState> {
+        match self.txn.take() {
+            Some(txn) => txn.into_prior(),
+            None => Some(self),
+        }
+    }
+
+    /// Serve nodes the current generation; `true` if one was held back.
+    pub fn publish(&mut self) -> bool {
+        self.held.take().is_some()
+    }
+
+    /// What nodes are served: the held publication, else the current
+    /// schedule.
+    pub fn published(&self) -> Published<'_> {
+        match &self.held {
+            Some(held) => held.view(),
+            None => self.pending(),
+        }
+    }
+
+    /// The current generation, published or not.
+    pub fn pending(&self) -> Published<'_> {
+        Published {
+            workload_id: &self.workload_id,
+            hyperperiod_us: self.hyperperiod.hyperperiod_us.as_u64(),
+            generation: self.generation,
+            schedule: &self.schedule,
+            previous: self.previous.as_ref(),
         }
     }
 }
 
 // ── WorkloadStore ─────────────────────────────────────────────────────────────
 
-/// The single shared mutable state.
+/// The single shared mutable state: the active workload of each tenant.
 ///
 /// ```text
-/// Arc<Mutex<Option<WorkloadState>>>
-///  │    │      └─ None = no workload submitted yet
+/// Arc<Mutex<HashMap<tenant, WorkloadState>>>
+///  │    │      └─ no entry = tenant has not submitted a workload yet
 ///  │    └─ tokio async Mutex: held across .await only when strictly needed
 ///  └─ shared by SchedInfoService, NodeService, and main
 /// ```
-pub type WorkloadStore = Arc<Mutex<Option<WorkloadState>>>;
+pub type WorkloadStore = Arc<Mutex<HashMap<String, WorkloadState>>>;
 
 /// Construct an empty `WorkloadStore`.
 pub fn new_workload_store() -> WorkloadStore {
-    Arc::new(Mutex::new(None))
+    Arc::new(Mutex::new(HashMap::new()))
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -141,21 +497,31 @@ pub fn new_workload_store() -> WorkloadStore {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::task::NodeSchedMap;
+    use crate::task::{Micros, NodeSchedMap};
 
     fn dummy_hyperperiod() -> HyperperiodInfo {
         HyperperiodInfo {
             workload_id: "wl".into(),
-            hyperperiod_us: 10_000,
-            unique_periods: vec![10_000],
+            hyperperiod_us: Micros(10_000),
+            unique_periods: vec![Micros(10_000)],
             task_count: 1,
         }
     }
 
     #[tokio::test]
-    async fn new_workload_store_is_initially_none() {
+    async fn new_workload_store_is_initially_empty() {
         let store = new_workload_store();
-        assert!(store.lock().await.is_none());
+        assert!(store.lock().await.is_empty());
+    }
+
+    #[test]
+    fn tenant_defaults_when_metadata_missing_or_empty() {
+        let mut md = MetadataMap::new();
+        assert_eq!(tenant_from_metadata(&md), DEFAULT_TENANT);
+        md.insert(TENANT_METADATA_KEY, "  ".parse().unwrap());
+        assert_eq!(tenant_from_metadata(&md), DEFAULT_TENANT);
+        md.insert(TENANT_METADATA_KEY, "staging".parse().unwrap());
+        assert_eq!(tenant_from_metadata(&md), "staging");
     }
 
     #[test]
@@ -5,13 +5,17 @@ SPDX-License-Identifier: MIT
 
 //! `NodeService` gRPC server — serves Timpani-N nodes.
 //!
-//! Three RPCs (mirroring the D-Bus / libtrpc interface from the C++ port):
+//! Three RPCs mirror the D-Bus / libtrpc interface from the C++ port, plus a
+//! streaming variant of `GetSchedInfo` and the node-side RPCs added since:
 //!
 //! | RPC           | C++ equivalent              | Purpose                              |
 //! |---------------|-----------------------------|--------------------------------------|
 //! | `GetSchedInfo`  | `trpc_client_schedinfo`   | Timpani-N pulls its task list        |
+//! | `StreamSchedInfo` | —                       | Same, in batches (large nodes)       |
 //! | `SyncTimer`     | `trpc_client_sync`        | Barrier — all nodes start together   |
 //! | `ReportDMiss`   | `trpc_client_dmiss`       | Deadline miss forwarded to Pullpiri  |
+//! | `ReportApply`   | —                         | Per-task apply outcome from the node |
+//! | `PrepareSchedInfo` | —                      | Vote on a transactional push         |
 //!
 //! # SyncTimer barrier design
 //!
@@ -27,20 +31,213 @@ SPDX-License-Identifier: MIT
 //!
 //! The lock is **not** held during the `changed().await` wait, so it does not
 //! block concurrent `GetSchedInfo` or `ReportDMiss` calls.
-
+//!
+//! # Delta delivery
+//!
+//! `GetSchedInfo` answers a node that reports `known_generation =
+//! generation - 1` with a [`NodeDiff`] against the previous schedule, so
+//! unchanged tasks are not re-applied.  Anything else — first contact, a gap
+//! of more than one generation, or `--full-push` — gets the full list with
+//! `full = true`.
+//!
+//! Nodes are served the *published* generation: with a push epoch, updates
+//! are held back and published together at the epoch boundary, so a node
+//! gets one delta for all of them (see [`super::epoch`]).
+//!
+//! # Streamed delivery
+//!
+//! `StreamSchedInfo` sends the same answer in batches of
+//! `--stream-batch-size` entries followed by a checksummed commit (see
+//! [`super::stream`]).  Tasks are marked delivered, and the node's entry in
+//! [`WorkloadState::deliveries`] advanced, as each batch is handed to the
+//! transport.
+//!
+//! # Memory reports
+//!
+//! A node may send `free_memory_mb` with either request.  Given the shared
+//! [`NodeConfigManager`] (`with_node_config`), the report is recorded before
+//! the schedule lookup, together with the memory of the tasks placed on the
+//! node across all tenants (see [`crate::config::MemoryBudget`]).
+//!
+//! # CPU hotplug
+//!
+//! A node may also send `online_cpus`, recorded the same way (see
+//! [`NodeConfigManager::report_online_cpus`]); new placements then avoid
+//! its offline CPUs.  Tasks of any tenant placed on a CPU that just went
+//! offline are dealt with before the schedule lookup:
+//!
+//! | `with_offline_cpu_repair` | Task with room on another online CPU of the node | Task without    |
+//! |---------------------------|--------------------------------------------------|-----------------|
+//! | set                       | moved there; new generation, `WORKLOAD_UPDATED`  | orphaned        |
+//! | not set                   | orphaned                                         | orphaned        |
+//!
+//! Only the affected tasks move (see [`crate::scheduler::hotplug`]).  An
+//! orphaned task stays where it is, is logged on the `audit` target, sent
+//! to Pullpiri as a `CPU_OFFLINE` advisory and listed under
+//! `ClusterStatus.orphaned` until the CPU comes back.
+//!
+//! # Clock synchronisation
+//!
+//! A node may also send `clock`, recorded the same way (see
+//! [`NodeConfigManager::report_clock`]).  Given a [`ClockGate`]
+//! (`with_clock_check`), a schedule that delivers a task with a release
+//! offset is checked against the node's last report before it is served:
+//! `warn` serves it with a warning, `strict` refuses the request with
+//! `FAILED_PRECONDITION`, and either sends Pullpiri a
+//! `CLOCK_UNSYNCHRONIZED` advisory once per generation (see
+//! [`super::clock`]).
+//!
+//! # Foreign load
+//!
+//! A node may also send `foreign_load`: RT threads Timpani did not place,
+//! found on its managed CPUs, with their estimated load per CPU.  It is
+//! recorded the same way (see [`NodeConfigManager::report_foreign_load`])
+//! and, with `--respect-foreign-load`, charged to the CPU's headroom by the
+//! next scheduling run.  A CPU whose placed load (all tenants) plus foreign
+//! load goes past the threshold (`with_foreign_load_threshold`, 0.9 by
+//! default) is logged on the `audit` target and sent to Pullpiri as a
+//! `FOREIGN_LOAD` advisory for the node, with the load under
+//! [`FOREIGN_LOAD_METADATA_KEY`].  It is sent once per crossing: again only
+//! after a report puts the CPU back under the threshold.
+//!
+//! # Events
+//!
+//! Delivery outcomes and deadline misses are recorded in the
+//! [`EventLog`] shared with `SchedInfoService` (`with_event_log`):
+//! `DELIVERY_CONFIRMED` when `GetSchedInfo` serves a generation the node
+//! did not announce or a stream commit is sent, `DELIVERY_FAILED` when a
+//! stream loses a batch or its node, `FAULT_RAISED` when `ReportDMiss`
+//! or `ReportApply` faults a placed task.
+//!
+//! # Apply reports
+//!
+//! After applying a generation a node sends an `ApplyReport`: one
+//! [`ApplyStatus`] (plus errno and detail) per task, and what the node
+//! could do (RT privileges, isolated CPUs, RT throttling).  A report for
+//! the active generation is kept in [`WorkloadState::apply_reports`] and
+//! shown by `GetClusterStatus`; a stale one is refused.  Each task moves
+//! with its status:
+//!
+//! | Status                            | Task becomes | Sent to Pullpiri     |
+//! |-----------------------------------|--------------|----------------------|
+//! | `APPLIED`, `DRY_RUN`, `RESERVED`  | `applied`    | —                    |
+//! | `UNSPECIFIED`                     | unchanged    | —                    |
+//! | any other (a failure)             | `faulted`    | `APPLY_FAILED` fault |
+//!
+//! A report for the active generation, or a `SyncTimer` call, also
+//! acknowledges the node's apply deadline in the shared
+//! [`ApplyWatchdog`] (`with_apply_watchdog`), whatever the statuses.
+//!
+//! A node's schedule only moves forward.  A push it already runs, or one
+//! older than that (a retry that arrived late), is not applied but
+//! acknowledged with `push_status` `DUPLICATE` or `STALE` and no tasks.
+//! Such a report succeeds and changes nothing, so it never faults a task
+//! nor replaces the kept report; a `DUPLICATE` of the active generation
+//! still acknowledges the apply deadline.
+//!
+//! # Transactional push
+//!
+//! While a generation is prepared (see [`super::txn`]), each node it
+//! changes is served it with `prepare = true`; every other node is served
+//! the generation the nodes run.  `PrepareSchedInfo` records the node's
+//! vote, commits or aborts the transaction when the vote decides it, and
+//! otherwise waits for the outcome like `SyncTimer` waits for its barrier.
+//!
+//! # Protocol negotiation
+//!
+//! Every schedule request announces the protocol features of the node's
+//! Timpani-N build.  The answer is downgraded to them — full pushes instead
+//! of deltas, no `prepare`, placeholder tasks left out — and a node that
+//! cannot prepare is left out of an open transaction, which may commit it
+//! (see [`super::protocol`]).  A node that announces nothing is served as
+//! the oldest build.
+//!
+//! # Drift reports
+//!
+//! A node that reads a task's attributes back and finds them changed (a
+//! restarted unit, say) sends `ReportDMiss` with `drift` set.  It takes the
+//! deadline-miss path, but reaches Pullpiri as a `SCHED_DRIFT` fault with
+//! the change under [`SCHED_DRIFT_METADATA_KEY`]; `Advisory` if the node's
+//! re-apply restored the attributes, `Critical` otherwise.
+//!
+//! # Placeholders
+//!
+//! A placeholder task (`ScheduledTask.placeholder`) only reserves capacity:
+//! the node stores it without applying it and reports it `RESERVED`.  A
+//! `ReportDMiss` naming one is acknowledged and dropped.
+//!
+//! # Fault sinks
+//!
+//! A task's `fault_sink` says where its deadline misses go.  A
+//! `LOCAL_ONLY` miss is meant for a safety monitor on the node: should the
+//! node report it anyway, the task is marked faulted but Pullpiri is not
+//! told.  The sink in effect is the one the node's latest `ReportApply`
+//! gives for the task — a node without a local sink runs every task
+//! `UPSTREAM` — else the one it was scheduled with.  Drift reports are
+//! always forwarded.
+//!
+//! # Time partitions
+//!
+//! A time-partitioned node (see [`crate::config::TimePartitions`]) gets its
+//! partition windows and major frame with every answer, full or delta, so
+//! it can align releases with them.  They are read from the shared
+//! [`NodeConfigManager`] (`with_node_config`) when the answer is built, so
+//! a configuration reload reaches the node at its next call.
+//!
+//! # Restarts
+//!
+//! Only the node configuration outlives a Timpani-O restart; workloads,
+//! generations and the delivery ledger start empty, a node is answered
+//! `NOT_FOUND` until Pullpiri resubmits its workloads, and generations are
+//! numbered afresh from 1.  Every answer therefore carries this instance's
+//! `instance_epoch`, which the node sends back with `known_generation`.  A
+//! `known_generation` without this instance's epoch (from before the
+//! restart, or from a node that does not track epochs) is never answered
+//! with a delta or an empty answer, even when the numbers happen to match:
+//! the node gets a full push marked `resync`.
+//!
+//! Until a node has taken that push, the task names it reports are compared
+//! with the ledger, and once the window after startup closes
+//! (`with_reconcile_window`, polled by [`NodeServiceImpl::finish_reconciliation`])
+//! a `NODES_RECONCILED` event reports the nodes in sync, the diverged
+//! (re-pushed) ones and the unreachable ones, whose undelivered tasks are
+//! degraded (see [`super::reconcile`]).
+
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
-use crate::fault::{FaultNotification, FaultNotifier};
+use crate::config::NodeConfigManager;
+use crate::fault::{FaultNotification, FaultNotifier, FaultSeverity};
+use crate::inject::{FailureInjector, InjectionPoint};
+use crate::metadata::{Metadata, MetadataPolicy};
+use crate::naming::sanitize;
 use crate::proto::schedinfo_v1::{
-    node_service_server::NodeService, DeadlineMissInfo, FaultType, NodeResponse, NodeSchedRequest,
-    NodeSchedResponse, ScheduledTask, SyncRequest, SyncResponse,
+    node_service_server::NodeService, ApplyReport, ApplyStatus, ClockSync, CpuSet,
+    DeadlineMissInfo, FaultType, ForeignLoadReport, NodeFeature, NodeResponse, NodeSchedRequest,
+    NodeSchedResponse, PrepareDecision, PrepareVote, PushStatus, ReconcileReport, SchedChunk,
+    SchedDrift, ScheduleEvent, ScheduleEventKind, ScheduledTask, SyncRequest, SyncResponse,
+    TimePartition,
 };
-
-use super::{BarrierStatus, WorkloadStore};
+use crate::report::NodeDiff;
+use crate::scheduler::hotplug::repair_offline_placements;
+use crate::scheduler::ScheduleOptions;
+use crate::task::{CpuAffinity, SchedTask};
+
+use super::clock::{ClockCheck, ClockGate, ClockVerdict};
+use super::events::{event, EventLog};
+use super::lifecycle::{TaskEvent, TaskState};
+use super::protocol::{self, NodeFeatures};
+use super::reconcile::{Reconciler, DEFAULT_RECONCILE_WINDOW_SECS};
+use super::stream::{self, DeliveryProgress, DEFAULT_STREAM_BATCH_SIZE};
+use super::txn::{self, Decided, Verdict};
+use super::watchdog::ApplyWatchdog;
+use super::{tenant_from_metadata, BarrierStatus, WorkloadState, WorkloadStore};
 
 // ── Constants ─────────────────────────────────────────────────────────────────
 
@@ -62,6 +259,19 @@ const SYNC_START_OFFSET_NS: i64 = 1_000_000_000; // 1 second
 /// Configurable via `--sync-timeout-secs` on the CLI.
 pub const DEFAULT_SYNC_TIMEOUT_SECS: u64 = 30;
 
+/// Fault metadata key describing a `SCHED_DRIFT` fault, e.g.
+/// `policy 1 -> 0, affinity 0x2 -> 0xf; not restored`.
+pub const SCHED_DRIFT_METADATA_KEY: &str = "sched_drift";
+
+/// Fault metadata key describing a `FOREIGN_LOAD` advisory, e.g.
+/// `cpu 2: foreign 0.35 (agent/50, spread/61), placed 0.60`.
+pub const FOREIGN_LOAD_METADATA_KEY: &str = "foreign_load";
+
+/// Chunks buffered between the `StreamSchedInfo` sender task and tonic.
+/// Small, so a slow node back-pressures the sender instead of Timpani-O
+/// queueing the whole schedule in memory.
+const STREAM_CHANNEL_DEPTH: usize = 2;
+
 // ── Service struct ────────────────────────────────────────────────────────────
 
 /// tonic implementation of `NodeService`.
@@ -72,6 +282,23 @@ pub struct NodeServiceImpl {
     workload_store: WorkloadStore,
     fault_notifier: Arc<dyn FaultNotifier>,
     sync_timeout: Duration,
+    full_push: bool,
+    stream_batch_size: usize,
+    injector: Arc<FailureInjector>,
+    node_config: Option<Arc<NodeConfigManager>>,
+    events: Arc<EventLog>,
+    metadata: MetadataPolicy,
+    /// Utilisation threshold for moving tasks off offline CPUs; `None` =
+    /// report them only.
+    offline_repair: Option<f64>,
+    /// Apply deadlines, shared with `SchedInfoService`.
+    apply_watchdog: Option<Arc<ApplyWatchdog>>,
+    /// Check for offset-dependent pushes; `None` = clocks are trusted.
+    clock_gate: Option<Arc<ClockGate>>,
+    /// Utilisation past which foreign load raises an advisory.
+    foreign_load_threshold: f64,
+    /// This instance's epoch and the nodes heard from since startup.
+    reconciler: Arc<Reconciler>,
 }
 
 impl NodeServiceImpl {
@@ -84,8 +311,601 @@ impl NodeServiceImpl {
             workload_store,
             fault_notifier,
             sync_timeout,
+            full_push: false,
+            stream_batch_size: DEFAULT_STREAM_BATCH_SIZE,
+            injector: Arc::default(),
+            node_config: None,
+            events: Arc::default(),
+            metadata: MetadataPolicy::default(),
+            offline_repair: None,
+            apply_watchdog: None,
+            clock_gate: None,
+            foreign_load_threshold: ScheduleOptions::default().cpu_utilization_threshold,
+            reconciler: Arc::new(Reconciler::new(Duration::from_secs(
+                DEFAULT_RECONCILE_WINDOW_SECS,
+            ))),
+        }
+    }
+
+    /// Always send the full task list, ignoring `known_generation`.
+    pub fn with_full_push(mut self, full_push: bool) -> Self {
+        self.full_push = full_push;
+        self
+    }
+
+    /// Entries per `StreamSchedInfo` batch (at least one).
+    pub fn with_stream_batch_size(mut self, batch_size: usize) -> Self {
+        self.stream_batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Share `injector` (see [`crate::inject`]).
+    pub fn with_failure_injector(mut self, injector: Arc<FailureInjector>) -> Self {
+        self.injector = injector;
+        self
+    }
+
+    /// Record nodes' free-memory reports in `node_config`.  Without it,
+    /// reports are ignored.
+    pub fn with_node_config(mut self, node_config: Arc<NodeConfigManager>) -> Self {
+        self.node_config = Some(node_config);
+        self
+    }
+
+    /// Record delivery outcomes and faults in `events` (see the module docs).
+    pub fn with_event_log(mut self, events: Arc<EventLog>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Copy `policy`'s forwarded metadata keys into faults.
+    pub fn with_metadata_policy(mut self, policy: MetadataPolicy) -> Self {
+        self.metadata = policy;
+        self
+    }
+
+    /// Move tasks off CPUs a node reports offline, onto its other online
+    /// CPUs up to `threshold` utilisation each (see the module docs).
+    pub fn with_offline_cpu_repair(mut self, threshold: f64) -> Self {
+        self.offline_repair = Some(threshold);
+        self
+    }
+
+    /// Acknowledge nodes' apply deadlines in `watchdog` (see the module
+    /// docs).
+    pub fn with_apply_watchdog(mut self, watchdog: Arc<ApplyWatchdog>) -> Self {
+        self.apply_watchdog = Some(watchdog);
+        self
+    }
+
+    /// Check nodes' clocks before serving schedules with release offsets,
+    /// allowing `max_offset` from the reference (see the module docs).
+    pub fn with_clock_check(mut self, check: ClockCheck, max_offset: Duration) -> Self {
+        self.clock_gate = Some(Arc::new(ClockGate::new(check, max_offset)));
+        self
+    }
+
+    /// Raise a `FOREIGN_LOAD` advisory when foreign load takes a CPU past
+    /// `threshold` utilisation (see the module docs).
+    pub fn with_foreign_load_threshold(mut self, threshold: f64) -> Self {
+        self.foreign_load_threshold = threshold;
+        self
+    }
+
+    /// Give nodes `window` after startup to call before the reconciliation
+    /// report calls them unreachable (see the module docs).
+    pub fn with_reconcile_window(mut self, window: Duration) -> Self {
+        self.reconciler = Arc::new(Reconciler::new(window));
+        self
+    }
+
+    /// Epoch of this instance, sent with every answer.
+    pub fn instance_epoch(&self) -> u64 {
+        self.reconciler.instance_epoch()
+    }
+
+    /// Once the reconciliation window has closed: report every node of the
+    /// node configuration or the ledger that never called as unreachable,
+    /// degrade its undelivered tasks and record the report as a
+    /// `NODES_RECONCILED` event.  `None` before the window closes and
+    /// after the report.
+    pub async fn finish_reconciliation(&self) -> Option<ReconcileReport> {
+        self.finish_reconciliation_at(Instant::now()).await
+    }
+
+    /// [`finish_reconciliation`](Self::finish_reconciliation) at `now`.
+    pub async fn finish_reconciliation_at(&self, now: Instant) -> Option<ReconcileReport> {
+        if self.reconciler.is_finished() {
+            return None;
+        }
+        let mut guard = self.workload_store.lock().await;
+        let mut expected: Vec<String> = guard
+            .values()
+            .flat_map(|ws| ws.active_nodes.iter().cloned())
+            .collect();
+        if let Some(cfg) = &self.node_config {
+            expected.extend(cfg.get_all_nodes().keys().cloned());
+        }
+        let report = self.reconciler.finish_at(now, expected)?;
+
+        for (tenant, ws) in guard.iter_mut() {
+            let undelivered: Vec<(String, String)> = ws
+                .task_states
+                .iter()
+                .filter(|((node, _), state)| {
+                    report.unreachable.contains(node)
+                        && matches!(state, TaskState::Pending | TaskState::Delivered)
+                })
+                .map(|(key, _)| key.clone())
+                .collect();
+            for (node, task) in &undelivered {
+                ws.task_states.apply(node, task, TaskEvent::Expire);
+            }
+            if !undelivered.is_empty() {
+                warn!(
+                    tenant      = %tenant,
+                    workload_id = %ws.workload_id,
+                    degraded    = undelivered.len(),
+                    "tasks of nodes unreachable since startup degraded"
+                );
+            }
+        }
+        info!(
+            target: "audit",
+            instance_epoch = report.instance_epoch,
+            in_sync        = ?report.in_sync,
+            diverged       = ?report.diverged,
+            unreachable    = ?report.unreachable,
+            "nodes reconciled after startup"
+        );
+        self.events.record(ScheduleEvent {
+            reconcile: Some(report.clone()),
+            ..event(ScheduleEventKind::NodesReconciled)
+        });
+        Some(report)
+    }
+
+    /// Compare the tasks `node_id` reports with what `tenant`'s published
+    /// schedule places on it, if the node is not on this instance's
+    /// schedule yet.
+    fn reconcile(
+        &self,
+        workloads: &HashMap<String, WorkloadState>,
+        tenant: &str,
+        node_id: &str,
+        known_instance_epoch: Option<u64>,
+        known_tasks: &[String],
+    ) {
+        let placed = workloads
+            .get(tenant)
+            .and_then(|ws| ws.published().schedule.get(node_id));
+        self.reconciler.observe(
+            node_id,
+            known_instance_epoch,
+            known_tasks,
+            placed.into_iter().flatten().map(|t| t.name.as_str()),
+        );
+    }
+
+    /// Record, forward and act on a decided transaction.
+    fn report_transaction(&self, decided: Decided) {
+        txn::report(
+            decided,
+            &self.events,
+            self.apply_watchdog.as_deref(),
+            &self.fault_notifier,
+        );
+    }
+
+    /// `node_id` applied `tenant`'s `generation`.
+    fn ack_apply(&self, tenant: &str, node_id: &str, generation: u64) {
+        if let Some(watchdog) = &self.apply_watchdog {
+            watchdog.ack(tenant, node_id, generation);
+        }
+    }
+
+    /// Record `node_id`'s free-memory report, if it sent one.
+    fn record_free_memory(
+        &self,
+        store: &HashMap<String, WorkloadState>,
+        node_id: &str,
+        free_memory_mb: Option<u64>,
+    ) {
+        let (Some(cfg), Some(free_mb)) = (&self.node_config, free_memory_mb) else {
+            return;
+        };
+        let tracked_mb = store.values().map(|ws| ws.placed_memory_mb(node_id)).sum();
+        cfg.report_free_memory(node_id, free_mb, tracked_mb);
+    }
+
+    /// Record `node_id`'s clock report, if it sent one.
+    fn record_clock(&self, node_id: &str, clock: Option<&ClockSync>) {
+        if let (Some(cfg), Some(clock)) = (&self.node_config, clock) {
+            cfg.report_clock(node_id, clock.clone().into());
+        }
+    }
+
+    /// Record `node_id`'s foreign-load report, if it sent one, and raise an
+    /// advisory for each CPU it newly takes past the threshold (see the
+    /// module docs).
+    fn record_foreign_load(
+        &self,
+        store: &HashMap<String, WorkloadState>,
+        node_id: &str,
+        report: Option<&ForeignLoadReport>,
+    ) {
+        let (Some(cfg), Some(report)) = (&self.node_config, report) else {
+            return;
+        };
+        let mut load: BTreeMap<u32, f64> = BTreeMap::new();
+        for l in &report.cpus {
+            *load.entry(l.cpu).or_default() += l.utilization.clamp(0.0, 1.0);
+        }
+        let previous = cfg
+            .report_foreign_load(node_id, load.clone())
+            .unwrap_or_default();
+
+        let mut placed: BTreeMap<u32, f64> = BTreeMap::new();
+        for t in store
+            .values()
+            .flat_map(|ws| ws.schedule.get(node_id))
+            .flatten()
+        {
+            *placed.entry(t.assigned_cpu).or_default() += t.exact_utilization().as_f64();
+        }
+        for l in &report.cpus {
+            let (foreign, base) = (load[&l.cpu], placed.get(&l.cpu).copied().unwrap_or(0.0));
+            let before = previous.get(&l.cpu).copied().unwrap_or(0.0);
+            if base + foreign <= self.foreign_load_threshold
+                || base + before > self.foreign_load_threshold
+            {
+                continue;
+            }
+            let detail = format!(
+                "cpu {}: foreign {foreign:.2} ({}), placed {base:.2}",
+                l.cpu,
+                l.threads.join(", ")
+            );
+            warn!(
+                target: "audit",
+                node      = %node_id,
+                cpu       = l.cpu,
+                foreign   = foreign,
+                placed    = base,
+                threshold = self.foreign_load_threshold,
+                threads   = ?l.threads,
+                "foreign RT load takes CPU past its threshold"
+            );
+            self.spawn_advisory(FaultNotification {
+                workload_id: String::new(),
+                node_id: node_id.to_string(),
+                task_name: String::new(),
+                fault_type: FaultType::ForeignLoad,
+                severity: FaultSeverity::Advisory,
+                feasibility: None,
+                metadata: Metadata::from([(FOREIGN_LOAD_METADATA_KEY.to_string(), detail)]),
+            });
+        }
+    }
+
+    /// Run `resp` for `node_id` through the clock gate (see the module
+    /// docs).  Returns why it must not be served, if it must not.
+    fn check_clock(
+        &self,
+        tenant: &str,
+        ws: &WorkloadState,
+        node_id: &str,
+        resp: &NodeSchedResponse,
+    ) -> Option<String> {
+        let (Some(gate), Some(cfg)) = (&self.clock_gate, &self.node_config) else {
+            return None;
+        };
+        let (problem, refused) = match gate.verdict(cfg.clock_report(node_id).as_ref(), resp) {
+            ClockVerdict::Serve => return None,
+            ClockVerdict::Warn(p) => (p, false),
+            ClockVerdict::Refuse(p) => (p, true),
+        };
+        warn!(
+            target: "audit",
+            tenant      = %tenant,
+            workload_id = %ws.workload_id,
+            node        = %node_id,
+            generation  = ws.generation,
+            problem     = %problem,
+            refused,
+            "schedule with release offsets for a node with an untrusted clock"
+        );
+        if gate.first_advisory(tenant, node_id, ws.generation) {
+            self.spawn_advisory(FaultNotification {
+                workload_id: ws.workload_id.clone(),
+                node_id: node_id.to_string(),
+                task_name: String::new(),
+                fault_type: FaultType::ClockUnsynchronized,
+                severity: FaultSeverity::Advisory,
+                feasibility: None,
+                metadata: Metadata::default(),
+            });
+        }
+        refused.then_some(problem)
+    }
+
+    /// Record `node_id`'s online-CPU report, if it sent one, then move or
+    /// orphan the tasks on the CPUs that went offline (see the module docs).
+    fn record_online_cpus(
+        &self,
+        store: &mut HashMap<String, WorkloadState>,
+        node_id: &str,
+        online: Option<&CpuSet>,
+    ) {
+        let (Some(cfg), Some(online)) = (&self.node_config, online) else {
+            return;
+        };
+        let offline = cfg
+            .report_online_cpus(node_id, online.cpus.iter().copied())
+            .went_offline;
+        if offline.is_empty() {
+            return;
+        }
+
+        // Affected placements as (tenant, index in the node's task list).
+        let mut tenants: Vec<&String> = store.keys().collect();
+        tenants.sort();
+        let mut affected = Vec::new();
+        let mut staying = Vec::new();
+        for tenant in tenants {
+            let node_tasks = store[tenant].schedule.get(node_id).into_iter().flatten();
+            for (i, t) in node_tasks.enumerate() {
+                if offline.contains(&t.assigned_cpu) {
+                    affected.push((tenant.clone(), i));
+                } else {
+                    staying.push(t);
+                }
+            }
+        }
+        let targets = match self.offline_repair {
+            Some(threshold) => {
+                let moving: Vec<(&SchedTask, CpuAffinity)> = affected
+                    .iter()
+                    .map(|(tenant, i)| {
+                        let ws = &store[tenant];
+                        let task = &ws.schedule[node_id][*i];
+                        let affinity = ws
+                            .tasks
+                            .iter()
+                            .find(|t| t.name == task.name)
+                            .map_or(CpuAffinity::Any, |t| t.affinity);
+                        (task, affinity)
+                    })
+                    .collect();
+                let usable = cfg.usable_cpus(node_id).unwrap_or_default();
+                repair_offline_placements(&usable, &staying, &moving, threshold)
+            }
+            None => vec![None; affected.len()],
+        };
+
+        let mut moves: BTreeMap<String, Vec<(usize, u32)>> = BTreeMap::new();
+        for ((tenant, i), target) in affected.into_iter().zip(targets) {
+            let ws = &store[&tenant];
+            let task = &ws.schedule[node_id][i];
+            match target {
+                Some(cpu) => {
+                    info!(
+                        target: "audit",
+                        tenant      = %tenant,
+                        workload_id = %ws.workload_id,
+                        node        = %node_id,
+                        task        = %task.name,
+                        from_cpu    = task.assigned_cpu,
+                        to_cpu      = cpu,
+                        "task moved off offline CPU"
+                    );
+                    moves.entry(tenant).or_default().push((i, cpu));
+                }
+                None => {
+                    warn!(
+                        target: "audit",
+                        tenant      = %tenant,
+                        workload_id = %ws.workload_id,
+                        node        = %node_id,
+                        task        = %task.name,
+                        cpu         = task.assigned_cpu,
+                        "task orphaned: CPU is offline"
+                    );
+                    self.spawn_advisory(FaultNotification {
+                        workload_id: ws.workload_id.clone(),
+                        node_id: node_id.to_string(),
+                        task_name: task.name.clone(),
+                        fault_type: FaultType::CpuOffline,
+                        severity: FaultSeverity::Advisory,
+                        feasibility: None,
+                        metadata: self.metadata.forwarded(&task.metadata),
+                    });
+                }
+            }
+        }
+
+        for (tenant, moved) in moves {
+            let ws = store.get_mut(&tenant).expect("affected tenant is stored");
+            let mut schedule = ws.schedule.clone();
+            let node_tasks = schedule
+                .get_mut(node_id)
+                .expect("affected node is scheduled");
+            for (i, cpu) in moved {
+                node_tasks[i].assigned_cpu = cpu;
+            }
+            ws.reschedule(schedule);
+            if let Some(watchdog) = &self.apply_watchdog {
+                watchdog.arm(&tenant, ws.generation, ws.nodes_to_apply());
+            }
+            self.events.record(ScheduleEvent {
+                tenant,
+                workload_id: ws.workload_id.clone(),
+                generation: ws.generation,
+                ..event(ScheduleEventKind::WorkloadUpdated)
+            });
+        }
+    }
+
+    /// Send an advisory in the background.
+    fn spawn_advisory(&self, notification: FaultNotification) {
+        let notifier = Arc::clone(&self.fault_notifier);
+        tokio::spawn(async move {
+            let (node, task) = (notification.node_id.clone(), notification.task_name.clone());
+            let fault_type = notification.fault_type;
+            if let Err(e) = notifier.notify_fault(notification).await {
+                warn!(node = %node, task = %task, fault_type = ?fault_type, error = %e,
+                      "Failed to send advisory");
+            }
+        });
+    }
+
+    /// Record the `features` `node_id` announced in `tenant`'s workload
+    /// and, if the node cannot prepare, leave it out of an open
+    /// transaction.  Returns the transaction if that decided it.
+    fn negotiate(
+        &self,
+        workloads: &mut HashMap<String, WorkloadState>,
+        tenant: &str,
+        node_id: &str,
+        features: NodeFeatures,
+    ) -> Option<Decided> {
+        let ws = workloads.get_mut(tenant)?;
+        let previous = ws.protocols.insert(node_id.to_string(), features);
+        if previous != Some(features) {
+            info!(
+                tenant   = %tenant,
+                node_id  = %node_id,
+                features = %features,
+                "node protocol negotiated"
+            );
+        }
+        let generation = ws.generation;
+        let txn = ws
+            .txn
+            .as_mut()
+            .filter(|t| t.nodes.contains(node_id) && !features.supports(NodeFeature::Prepare))?;
+        warn!(
+            tenant     = %tenant,
+            node_id    = %node_id,
+            generation = generation,
+            "node cannot stage a transactional push; left out of the transaction"
+        );
+        match txn.excuse(node_id)? {
+            Verdict::Commit => txn::commit(workloads, tenant),
+            _ => None,
         }
     }
+
+    /// What `GetSchedInfo` answers `node_id`: a delta against
+    /// `known_generation` when possible, else the full list, downgraded to
+    /// `features`.  A `known_generation` of another instance gets a full
+    /// resync (see the module docs).
+    fn node_response(
+        &self,
+        ws: &WorkloadState,
+        node_id: &str,
+        known_generation: Option<u64>,
+        known_instance_epoch: Option<u64>,
+        features: NodeFeatures,
+    ) -> NodeSchedResponse {
+        let prepare = ws.txn.as_ref().is_some_and(|t| t.nodes.contains(node_id));
+        let published = if prepare {
+            ws.pending()
+        } else {
+            ws.published()
+        };
+        // This node's task list.  If the node received no tasks it is empty
+        // (not an error — the node can legitimately idle).
+        let current = published
+            .schedule
+            .get(node_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+
+        let full_push = self.full_push || !features.supports(NodeFeature::Delta);
+        let resync =
+            known_generation.is_some() && !self.reconciler.is_current(known_instance_epoch);
+        let delta = if full_push || resync {
+            None
+        } else {
+            match (known_generation, published.previous) {
+                (Some(g), _) if g == published.generation => Some(NodeDiff::default()),
+                (Some(g), Some(prev)) if g + 1 == published.generation => Some(NodeDiff::between(
+                    prev.get(node_id).map(Vec::as_slice).unwrap_or(&[]),
+                    current,
+                )),
+                _ => None,
+            }
+        };
+
+        let mut resp = NodeSchedResponse {
+            workload_id: published.workload_id.to_string(),
+            hyperperiod_us: published.hyperperiod_us,
+            generation: published.generation,
+            prepare,
+            instance_epoch: self.reconciler.instance_epoch(),
+            resync,
+            ..Default::default()
+        };
+        match delta {
+            Some(diff) => {
+                resp.tasks = diff.added.iter().map(to_proto_task).collect();
+                resp.modified_tasks = diff.modified.iter().map(to_proto_task).collect();
+                resp.removed_tasks = diff.removed;
+            }
+            None => {
+                if resync {
+                    warn!(
+                        node_id              = %node_id,
+                        known_generation     = ?known_generation,
+                        known_instance_epoch = ?known_instance_epoch,
+                        instance_epoch       = self.reconciler.instance_epoch(),
+                        generation           = published.generation,
+                        "GetSchedInfo: generation of another instance, sending full resync"
+                    );
+                } else if known_generation.is_some_and(|g| g != published.generation) && !full_push
+                {
+                    warn!(
+                        node_id          = %node_id,
+                        known_generation = ?known_generation,
+                        generation       = published.generation,
+                        "GetSchedInfo: generation gap, sending full resync"
+                    );
+                }
+                resp.full = true;
+                resp.tasks = current.iter().map(to_proto_task).collect();
+            }
+        }
+        let partitions = self
+            .node_config
+            .as_ref()
+            .and_then(|cfg| cfg.get_node_config(node_id))
+            .and_then(|node| node.time_partitions.as_ref());
+        if let Some(p) = partitions {
+            resp.major_frame_us = p.major_frame_us;
+            resp.time_partitions = p
+                .windows
+                .iter()
+                .map(|w| TimePartition {
+                    offset_us: w.offset_us,
+                    duration_us: w.duration_us,
+                })
+                .collect();
+        }
+        if !features.supports(NodeFeature::Placeholder) {
+            let stripped = protocol::strip_placeholders(&mut resp);
+            if !stripped.is_empty() {
+                warn!(
+                    node_id    = %node_id,
+                    generation = resp.generation,
+                    tasks      = ?stripped,
+                    "node does not understand placeholder tasks; left out"
+                );
+            }
+        }
+
+        resp
+    }
 }
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
@@ -101,26 +921,97 @@ fn compute_start_time() -> (i64, i32) {
     (start_ns / NANOS_PER_SEC, (start_ns % NANOS_PER_SEC) as i32)
 }
 
+/// A node-scoped event of `tenant`'s workload.
+fn node_event(
+    kind: ScheduleEventKind,
+    tenant: &str,
+    workload_id: &str,
+    node_id: &str,
+    generation: u64,
+) -> ScheduleEvent {
+    ScheduleEvent {
+        tenant: tenant.to_string(),
+        workload_id: workload_id.to_string(),
+        node: node_id.to_string(),
+        generation,
+        ..event(kind)
+    }
+}
+
+/// The lifecycle event a task's apply status raises, if any.
+fn apply_event(status: ApplyStatus) -> Option<TaskEvent> {
+    match status {
+        ApplyStatus::Applied | ApplyStatus::DryRun | ApplyStatus::Reserved => {
+            Some(TaskEvent::Apply)
+        }
+        ApplyStatus::PidNotFound
+        | ApplyStatus::PermissionDenied
+        | ApplyStatus::InvalidCpu
+        | ApplyStatus::CgroupError
+        | ApplyStatus::InvalidAttributes => Some(TaskEvent::Fault),
+        ApplyStatus::Unspecified => None,
+    }
+}
+
+/// The [`SCHED_DRIFT_METADATA_KEY`] value for `d`: the attributes that
+/// differ, then whether a re-apply restored them.
+fn drift_detail(d: &SchedDrift) -> String {
+    let mut parts = Vec::new();
+    if d.expected_policy != d.observed_policy {
+        parts.push(format!(
+            "policy {} -> {}",
+            d.expected_policy, d.observed_policy
+        ));
+    }
+    if d.expected_priority != d.observed_priority {
+        parts.push(format!(
+            "priority {} -> {}",
+            d.expected_priority, d.observed_priority
+        ));
+    }
+    if d.expected_cpu_affinity != d.observed_cpu_affinity {
+        parts.push(format!(
+            "affinity {:#x} -> {:#x}",
+            d.expected_cpu_affinity, d.observed_cpu_affinity
+        ));
+    }
+    let outcome = match (d.restored, d.reapplied) {
+        (true, n) => format!("restored after {n} re-apply(s)"),
+        (false, 0) => "not restored".to_string(),
+        (false, n) => format!("not restored after {n} re-apply(s)"),
+    };
+    format!("{}; {outcome}", parts.join(", "))
+}
+
 /// Convert an internal `SchedTask` to the proto wire type `ScheduledTask`.
 ///
-/// `period_ns / 1_000` converts back to microseconds because `ScheduledTask`
-/// carries µs (matching `task_info.period` in Timpani-N's C headers).
+/// `Nanos::to_micros` converts back to microseconds because `ScheduledTask`
+/// carries µs (matching `task_info.period` in Timpani-N's C headers); values
+/// beyond `i32::MAX` µs saturate rather than wrap.
 ///
 /// `cpu_affinity` is encoded as a single-bit mask (`1 << assigned_cpu`)
 /// because the scheduler picked a specific CPU; Timpani-N calls
-/// `set_affinity_cpumask` with this value.
-fn to_proto_task(t: &crate::task::SchedTask) -> ScheduledTask {
+/// `set_affinity_cpumask` with this value.  A CPU beyond the mask (which
+/// configuration loading refuses) is sent as `0`, "any CPU".
+fn to_proto_task(t: &SchedTask) -> ScheduledTask {
     ScheduledTask {
         name: t.name.clone(),
         sched_priority: t.priority,
         sched_policy: t.policy.to_linux_int(),
-        period_us: (t.period_ns / 1_000) as i32,
+        period_us: t.period_ns.to_micros().to_proto(),
         release_time_us: t.release_time_us,
-        runtime_us: (t.runtime_ns / 1_000) as i32,
-        deadline_us: (t.deadline_ns / 1_000) as i32,
-        cpu_affinity: 1u64 << t.assigned_cpu,
+        runtime_us: t.runtime_ns.to_micros().to_proto(),
+        deadline_us: t.deadline_ns.to_micros().to_proto(),
+        cpu_affinity: 1u64.checked_shl(t.assigned_cpu).unwrap_or(0),
         max_dmiss: t.max_dmiss,
         assigned_node: t.assigned_node.clone(),
+        metadata: t
+            .metadata
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+        placeholder: t.placeholder,
+        fault_sink: t.fault_sink.to_proto_int(),
     }
 }
 
@@ -134,35 +1025,230 @@ impl NodeService for NodeServiceImpl {
         &self,
         request: Request<NodeSchedRequest>,
     ) -> Result<Response<NodeSchedResponse>, Status> {
-        let node_id = request.into_inner().node_id;
-        info!(node_id = %node_id, "GetSchedInfo request");
-
-        let guard = self.workload_store.lock().await;
-        let ws = guard.as_ref().ok_or_else(|| {
+        let tenant = tenant_from_metadata(request.metadata());
+        let req = request.into_inner();
+        let node_id = req.node_id;
+        info!(
+            tenant           = %tenant,
+            node_id          = %node_id,
+            known_generation = ?req.known_generation,
+            known_epoch      = ?req.known_instance_epoch,
+            free_memory_mb   = ?req.free_memory_mb,
+            online_cpus      = ?req.online_cpus.as_ref().map(|s| &s.cpus),
+            clock            = ?req.clock,
+            foreign_load     = ?req.foreign_load.as_ref().map(|r| r.cpus.len()),
+            features         = req.features,
+            "GetSchedInfo request"
+        );
+        self.injector
+            .hit(InjectionPoint::NodeServe)
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+
+        let features = NodeFeatures::from_bits(req.features);
+        let mut guard = self.workload_store.lock().await;
+        self.record_free_memory(&guard, &node_id, req.free_memory_mb);
+        self.record_online_cpus(&mut guard, &node_id, req.online_cpus.as_ref());
+        self.record_clock(&node_id, req.clock.as_ref());
+        self.record_foreign_load(&guard, &node_id, req.foreign_load.as_ref());
+        if let Some(decided) = self.negotiate(&mut guard, &tenant, &node_id, features) {
+            self.report_transaction(decided);
+        }
+        self.reconcile(
+            &guard,
+            &tenant,
+            &node_id,
+            req.known_instance_epoch,
+            &req.known_tasks,
+        );
+        let ws = guard.get_mut(&tenant).ok_or_else(|| {
             warn!(node_id = %node_id, "GetSchedInfo: no workload scheduled yet");
             Status::not_found("no workload has been scheduled yet")
         })?;
+        let resp = self.node_response(
+            ws,
+            &node_id,
+            req.known_generation,
+            req.known_instance_epoch,
+            features,
+        );
+        if let Some(problem) = self.check_clock(&tenant, ws, &node_id, &resp) {
+            return Err(Status::failed_precondition(format!(
+                "schedule has release offsets but the node clock is untrusted: {problem}"
+            )));
+        }
 
-        // Return this node's task list.  If the node received no tasks, return
-        // an empty list (not an error — the node can legitimately idle).
-        let tasks: Vec<ScheduledTask> = ws
-            .schedule
-            .get(&node_id)
-            .map(|v| v.iter().map(to_proto_task).collect())
-            .unwrap_or_default();
+        for t in resp.tasks.iter().chain(&resp.modified_tasks) {
+            ws.task_states.apply(&node_id, &t.name, TaskEvent::Deliver);
+        }
+        if resp.resync || req.known_generation != Some(resp.generation) {
+            self.events.record(node_event(
+                ScheduleEventKind::DeliveryConfirmed,
+                &tenant,
+                &resp.workload_id,
+                &node_id,
+                resp.generation,
+            ));
+        }
 
         info!(
             node_id     = %node_id,
-            workload_id = %ws.workload_id,
-            task_count  = tasks.len(),
+            workload_id = %resp.workload_id,
+            generation  = resp.generation,
+            full        = resp.full,
+            resync      = resp.resync,
+            added       = resp.tasks.len(),
+            modified    = resp.modified_tasks.len(),
+            removed     = resp.removed_tasks.len(),
             "GetSchedInfo: serving schedule"
         );
 
-        Ok(Response::new(NodeSchedResponse {
-            workload_id: ws.workload_id.clone(),
-            hyperperiod_us: ws.hyperperiod.hyperperiod_us,
-            tasks,
-        }))
+        Ok(Response::new(resp))
+    }
+
+    // ── StreamSchedInfo ───────────────────────────────────────────────────────
+
+    type StreamSchedInfoStream = ReceiverStream<Result<SchedChunk, Status>>;
+
+    async fn stream_sched_info(
+        &self,
+        request: Request<NodeSchedRequest>,
+    ) -> Result<Response<Self::StreamSchedInfoStream>, Status> {
+        let tenant = tenant_from_metadata(request.metadata());
+        let req = request.into_inner();
+        let node_id = req.node_id;
+        info!(
+            tenant           = %tenant,
+            node_id          = %node_id,
+            known_generation = ?req.known_generation,
+            known_epoch      = ?req.known_instance_epoch,
+            free_memory_mb   = ?req.free_memory_mb,
+            online_cpus      = ?req.online_cpus.as_ref().map(|s| &s.cpus),
+            clock            = ?req.clock,
+            foreign_load     = ?req.foreign_load.as_ref().map(|r| r.cpus.len()),
+            features         = req.features,
+            "StreamSchedInfo request"
+        );
+        self.injector
+            .hit(InjectionPoint::NodeServe)
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+
+        let features = NodeFeatures::from_bits(req.features);
+        let (batches, commit) = {
+            let mut guard = self.workload_store.lock().await;
+            self.record_free_memory(&guard, &node_id, req.free_memory_mb);
+            self.record_online_cpus(&mut guard, &node_id, req.online_cpus.as_ref());
+            self.record_clock(&node_id, req.clock.as_ref());
+            self.record_foreign_load(&guard, &node_id, req.foreign_load.as_ref());
+            if let Some(decided) = self.negotiate(&mut guard, &tenant, &node_id, features) {
+                self.report_transaction(decided);
+            }
+            self.reconcile(
+                &guard,
+                &tenant,
+                &node_id,
+                req.known_instance_epoch,
+                &req.known_tasks,
+            );
+            let ws = guard.get_mut(&tenant).ok_or_else(|| {
+                warn!(node_id = %node_id, "StreamSchedInfo: no workload scheduled yet");
+                Status::not_found("no workload has been scheduled yet")
+            })?;
+            let resp = self.node_response(
+                ws,
+                &node_id,
+                req.known_generation,
+                req.known_instance_epoch,
+                features,
+            );
+            if let Some(problem) = self.check_clock(&tenant, ws, &node_id, &resp) {
+                return Err(Status::failed_precondition(format!(
+                    "schedule has release offsets but the node clock is untrusted: {problem}"
+                )));
+            }
+            let (batches, commit) = stream::split(resp, self.stream_batch_size);
+            ws.deliveries.insert(
+                node_id.clone(),
+                DeliveryProgress::new(commit.generation, commit.batch_count),
+            );
+            info!(
+                node_id     = %node_id,
+                workload_id = %commit.workload_id,
+                generation  = commit.generation,
+                full        = commit.full,
+                batches     = commit.batch_count,
+                "StreamSchedInfo: streaming schedule"
+            );
+            (batches, commit)
+        };
+
+        // The channel is the flow-control window: `send` waits while tonic
+        // has not yet written the previous chunks to the HTTP/2 stream.
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_DEPTH);
+        let store = self.workload_store.clone();
+        let injector = Arc::clone(&self.injector);
+        let events = Arc::clone(&self.events);
+        tokio::spawn(async move {
+            let generation = commit.generation;
+            let outcome =
+                |kind| node_event(kind, &tenant, &commit.workload_id, &node_id, generation);
+            let mut lost_batch = false;
+            for batch in batches {
+                let index = batch.index;
+                let delivered: Vec<String> = batch
+                    .tasks
+                    .iter()
+                    .chain(&batch.modified_tasks)
+                    .map(|t| t.name.clone())
+                    .collect();
+                if let Err(e) = injector.hit(InjectionPoint::NodeStreamBatch).await {
+                    warn!(node_id = %node_id, index, "StreamSchedInfo: dropping batch: {e}");
+                    lost_batch = true;
+                    continue;
+                }
+                if tx.send(Ok(batch.into())).await.is_err() {
+                    warn!(node_id = %node_id, index, "StreamSchedInfo: node went away");
+                    events.record(outcome(ScheduleEventKind::DeliveryFailed));
+                    return;
+                }
+
+                let mut guard = store.lock().await;
+                let Some(ws) = guard
+                    .get_mut(&tenant)
+                    .filter(|ws| [ws.published().generation, ws.generation].contains(&generation))
+                else {
+                    continue;
+                };
+                for name in &delivered {
+                    ws.task_states.apply(&node_id, name, TaskEvent::Deliver);
+                }
+                if let Some(p) = ws.deliveries.get_mut(&node_id) {
+                    if p.generation == generation {
+                        p.batches_sent = index + 1;
+                    }
+                }
+            }
+
+            let failed = outcome(ScheduleEventKind::DeliveryFailed);
+            let confirmed = outcome(ScheduleEventKind::DeliveryConfirmed);
+            if tx.send(Ok(commit.into())).await.is_err() {
+                events.record(failed);
+                return;
+            }
+            let mut guard = store.lock().await;
+            if let Some(p) = guard
+                .get_mut(&tenant)
+                .and_then(|ws| ws.deliveries.get_mut(&node_id))
+                .filter(|p| p.generation == generation)
+            {
+                p.committed = true;
+            }
+            // The node discards a stream with a missing batch.
+            events.record(if lost_batch { failed } else { confirmed });
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
     }
 
     // ── SyncTimer ─────────────────────────────────────────────────────────────
@@ -171,8 +1257,9 @@ impl NodeService for NodeServiceImpl {
         &self,
         request: Request<SyncRequest>,
     ) -> Result<Response<SyncResponse>, Status> {
+        let tenant = tenant_from_metadata(request.metadata());
         let node_id = request.into_inner().node_id;
-        info!(node_id = %node_id, "SyncTimer: node checking in");
+        info!(tenant = %tenant, node_id = %node_id, "SyncTimer: node checking in");
 
         // ── Phase 1: register the node and obtain a barrier receiver ──────────
         //
@@ -182,7 +1269,7 @@ impl NodeService for NodeServiceImpl {
         let mut barrier_rx = {
             let mut guard = self.workload_store.lock().await;
             let ws = guard
-                .as_mut()
+                .get_mut(&tenant)
                 .ok_or_else(|| Status::not_found("no workload has been scheduled yet"))?;
 
             if ws.active_nodes.is_empty() {
@@ -203,6 +1290,8 @@ impl NodeService for NodeServiceImpl {
             let rx = ws.barrier_tx.subscribe();
 
             ws.synced_nodes.insert(node_id.clone());
+            ws.task_states.apply_node(&node_id, TaskEvent::Apply);
+            self.ack_apply(&tenant, &node_id, ws.published().generation);
 
             let all_synced = ws.active_nodes.iter().all(|n| ws.synced_nodes.contains(n));
 
@@ -212,6 +1301,7 @@ impl NodeService for NodeServiceImpl {
                     start_time_sec: sec,
                     start_time_nsec: nsec,
                 });
+                ws.task_states.apply_all(TaskEvent::Start);
                 info!(
                     workload_id = %ws.workload_id,
                     node_count  = ws.active_nodes.len(),
@@ -306,7 +1396,7 @@ impl NodeService for NodeServiceImpl {
                     // Wake all other handlers that are waiting on this barrier.
                     {
                         let guard = self.workload_store.lock().await;
-                        if let Some(ws) = guard.as_ref() {
+                        if let Some(ws) = guard.get(&tenant) {
                             let _ = ws.barrier_tx.send(BarrierStatus::TimedOut);
                         }
                     }
@@ -325,56 +1415,119 @@ impl NodeService for NodeServiceImpl {
         &self,
         request: Request<DeadlineMissInfo>,
     ) -> Result<Response<NodeResponse>, Status> {
+        let tenant = tenant_from_metadata(request.metadata());
         let info = request.into_inner();
         let node_id = info.node_id.clone();
         let task_name = info.task_name.clone();
-
-        warn!(
-            node_id   = %node_id,
-            task_name = %task_name,
-            "DeadlineMiss reported"
-        );
+        let drift = info.drift.as_ref().map(|d| (drift_detail(d), d.restored));
+
+        match &drift {
+            None => warn!(
+                node_id   = %sanitize(&node_id),
+                task_name = %sanitize(&task_name),
+                "DeadlineMiss reported"
+            ),
+            Some((detail, _)) => warn!(
+                node_id   = %sanitize(&node_id),
+                task_name = %sanitize(&task_name),
+                drift     = %detail,
+                "Scheduling attribute drift reported"
+            ),
+        }
 
         // Resolve workload_id from the active schedule.
         // If the task is not found (race with workload replacement), fall back
         // to the current workload_id — mirrors the C++ DMissCallback fallback.
-        let workload_id = {
-            let guard = self.workload_store.lock().await;
-            match guard.as_ref() {
+        let (workload_id, metadata, sink) = {
+            let mut guard = self.workload_store.lock().await;
+            match guard.get_mut(&tenant) {
                 None => {
-                    warn!("ReportDMiss: no active workload");
+                    warn!(tenant = %tenant, "ReportDMiss: no active workload");
                     return Ok(Response::new(NodeResponse {
                         status: -1,
                         error_message: "no active workload".into(),
                     }));
                 }
+                Some(ws) if ws.is_placeholder(&node_id, &task_name) => {
+                    // Nothing runs behind a placeholder; a report is stale.
+                    debug!(
+                        node_id   = %sanitize(&node_id),
+                        task_name = %sanitize(&task_name),
+                        "ReportDMiss: placeholder task ignored"
+                    );
+                    return Ok(Response::new(NodeResponse {
+                        status: 0,
+                        error_message: String::new(),
+                    }));
+                }
                 Some(ws) => {
-                    let found = ws
-                        .schedule
-                        .get(&node_id)
-                        .and_then(|tasks| tasks.iter().find(|t| t.name == task_name))
-                        .is_some();
-
-                    if !found {
+                    let metadata = ws
+                        .task_metadata(&node_id, &task_name)
+                        .map(|m| self.metadata.forwarded(m));
+
+                    if metadata.is_some() {
+                        ws.task_states.apply(&node_id, &task_name, TaskEvent::Fault);
+                        self.events.record(ScheduleEvent {
+                            task: task_name.clone(),
+                            ..node_event(
+                                ScheduleEventKind::FaultRaised,
+                                &tenant,
+                                &ws.workload_id,
+                                &node_id,
+                                ws.generation,
+                            )
+                        });
+                    } else {
                         warn!(
-                            node_id   = %node_id,
-                            task_name = %task_name,
+                            node_id   = %sanitize(&node_id),
+                            task_name = %sanitize(&task_name),
                             "ReportDMiss: task not found in schedule; \
                              using current workload_id as fallback"
                         );
                     }
-                    ws.workload_id.clone()
+                    (
+                        ws.workload_id.clone(),
+                        metadata.unwrap_or_default(),
+                        ws.fault_sink(&node_id, &task_name),
+                    )
                 }
             }
         };
 
-        // Forward the fault to Pullpiri.
-        let notification = FaultNotification {
+        // A LocalOnly miss went to the node's own sink; Pullpiri is not told.
+        if drift.is_none() && !sink.forwards_upstream() {
+            debug!(
+                node_id    = %sanitize(&node_id),
+                task_name  = %sanitize(&task_name),
+                fault_sink = sink.as_str(),
+                "ReportDMiss: not forwarded"
+            );
+            return Ok(Response::new(NodeResponse {
+                status: 0,
+                error_message: String::new(),
+            }));
+        }
+
+        // Forward the fault to Pullpiri.  The node names the task itself, so
+        // the names were never validated.
+        let mut notification = FaultNotification {
             workload_id,
-            node_id,
-            task_name,
+            node_id: sanitize(&node_id),
+            task_name: sanitize(&task_name),
             fault_type: FaultType::Dmiss,
+            severity: FaultSeverity::Critical,
+            feasibility: None,
+            metadata,
         };
+        if let Some((detail, restored)) = drift {
+            notification.fault_type = FaultType::SchedDrift;
+            if restored {
+                notification.severity = FaultSeverity::Advisory;
+            }
+            notification
+                .metadata
+                .insert(SCHED_DRIFT_METADATA_KEY.to_string(), detail);
+        }
 
         if let Err(e) = self.fault_notifier.notify_fault(notification).await {
             error!(error = %e, "Failed to notify Pullpiri of deadline miss");
@@ -389,6 +1542,225 @@ impl NodeService for NodeServiceImpl {
             error_message: String::new(),
         }))
     }
+
+    // ── ReportApply ───────────────────────────────────────────────────────────
+
+    async fn report_apply(
+        &self,
+        request: Request<ApplyReport>,
+    ) -> Result<Response<NodeResponse>, Status> {
+        let tenant = tenant_from_metadata(request.metadata());
+        let report = request.into_inner();
+        let node_id = report.node_id.clone();
+        let failed: Vec<String> = report
+            .tasks
+            .iter()
+            .filter(|t| apply_event(t.status()) == Some(TaskEvent::Fault))
+            .map(|t| t.task_name.clone())
+            .collect();
+
+        info!(
+            node_id    = %sanitize(&node_id),
+            generation = report.generation,
+            tasks      = report.tasks.len(),
+            failed     = failed.len(),
+            "ApplyReport received"
+        );
+
+        let (workload_id, failed) = {
+            let mut guard = self.workload_store.lock().await;
+            let Some(ws) = guard.get_mut(&tenant) else {
+                warn!(tenant = %tenant, "ReportApply: no active workload");
+                return Ok(Response::new(NodeResponse {
+                    status: -1,
+                    error_message: "no active workload".into(),
+                }));
+            };
+            let active = ws.published().generation;
+            let push = report.push_status();
+            if push != PushStatus::Applied {
+                info!(
+                    node_id    = %sanitize(&node_id),
+                    generation = report.generation,
+                    active,
+                    push       = push.as_str_name(),
+                    "ReportApply: old push acknowledged"
+                );
+                if push == PushStatus::Duplicate && report.generation == active {
+                    self.ack_apply(&tenant, &node_id, active);
+                }
+                return Ok(Response::new(NodeResponse {
+                    status: 0,
+                    error_message: String::new(),
+                }));
+            }
+            if report.generation != active {
+                warn!(
+                    node_id    = %sanitize(&node_id),
+                    generation = report.generation,
+                    active,
+                    "ReportApply: stale generation ignored"
+                );
+                return Ok(Response::new(NodeResponse {
+                    status: -1,
+                    error_message: format!(
+                        "generation {} is not the active generation {active}",
+                        report.generation
+                    ),
+                }));
+            }
+            self.ack_apply(&tenant, &node_id, report.generation);
+
+            for t in &report.tasks {
+                let Some(event) = apply_event(t.status()) else {
+                    continue;
+                };
+                let moved = ws.task_states.apply(&node_id, &t.task_name, event);
+                if moved && event == TaskEvent::Fault {
+                    self.events.record(ScheduleEvent {
+                        task: t.task_name.clone(),
+                        ..node_event(
+                            ScheduleEventKind::FaultRaised,
+                            &tenant,
+                            &ws.workload_id,
+                            &node_id,
+                            ws.generation,
+                        )
+                    });
+                }
+                if event == TaskEvent::Fault {
+                    warn!(
+                        node_id = %sanitize(&node_id),
+                        task    = %sanitize(&t.task_name),
+                        status  = t.status().as_str_name(),
+                        errno   = t.errno,
+                        detail  = %t.detail,
+                        "task could not be applied"
+                    );
+                }
+            }
+            ws.apply_reports.insert(node_id.clone(), report);
+            let failed: Vec<(String, Metadata)> = failed
+                .into_iter()
+                .map(|task| {
+                    let metadata = ws
+                        .task_metadata(&node_id, &task)
+                        .map(|m| self.metadata.forwarded(m))
+                        .unwrap_or_default();
+                    (task, metadata)
+                })
+                .collect();
+            (ws.workload_id.clone(), failed)
+        };
+
+        // The report is already recorded; a lost notification is not the
+        // node's to retry.
+        for (task_name, metadata) in failed {
+            let notification = FaultNotification {
+                workload_id: workload_id.clone(),
+                node_id: sanitize(&node_id),
+                task_name: sanitize(&task_name),
+                fault_type: FaultType::ApplyFailed,
+                severity: FaultSeverity::Critical,
+                feasibility: None,
+                metadata,
+            };
+            if let Err(e) = self.fault_notifier.notify_fault(notification).await {
+                error!(error = %e, "Failed to notify Pullpiri of an apply failure");
+            }
+        }
+
+        Ok(Response::new(NodeResponse {
+            status: 0,
+            error_message: String::new(),
+        }))
+    }
+
+    // ── PrepareSchedInfo ──────────────────────────────────────────────────────
+
+    async fn prepare_sched_info(
+        &self,
+        request: Request<PrepareVote>,
+    ) -> Result<Response<PrepareDecision>, Status> {
+        let tenant = tenant_from_metadata(request.metadata());
+        let vote = request.into_inner();
+        info!(
+            tenant     = %tenant,
+            node_id    = %vote.node_id,
+            generation = vote.generation,
+            staged     = vote.staged,
+            error      = %vote.error_message,
+            "PrepareSchedInfo: vote"
+        );
+
+        // Subscribe and vote under one lock hold, so a verdict this vote
+        // (or a concurrent one) decides cannot be missed.
+        let (mut verdict_rx, decided) = {
+            let mut guard = self.workload_store.lock().await;
+            let ws = guard
+                .get_mut(&tenant)
+                .ok_or_else(|| Status::not_found("no workload has been scheduled yet"))?;
+            let generation = ws.generation;
+            let Some(txn) = ws.txn.as_mut().filter(|_| generation == vote.generation) else {
+                // Decided (or abandoned) before this vote arrived.
+                let verdict = if ws.published().generation == vote.generation {
+                    Verdict::Commit
+                } else {
+                    Verdict::Abort(format!(
+                        "generation {} is not being prepared",
+                        vote.generation
+                    ))
+                };
+                return Ok(Response::new(
+                    verdict.decision(vote.generation).unwrap_or_default(),
+                ));
+            };
+            if !txn.nodes.contains(&vote.node_id) {
+                return Err(Status::failed_precondition(format!(
+                    "node '{}' is not part of the transaction of generation {}",
+                    vote.node_id, vote.generation
+                )));
+            }
+            let rx = txn.subscribe();
+            let decided = match txn.vote(
+                &vote.node_id,
+                vote.generation,
+                vote.staged,
+                &vote.error_message,
+            ) {
+                Some(Verdict::Commit) => txn::commit(&mut guard, &tenant),
+                Some(Verdict::Abort(reason)) => txn::abort(&mut guard, &tenant, reason),
+                _ => None,
+            };
+            (rx, decided)
+        };
+        if let Some(decided) = decided {
+            self.report_transaction(decided);
+        }
+
+        loop {
+            let verdict = verdict_rx.borrow_and_update().clone();
+            if let Some(decision) = verdict.decision(vote.generation) {
+                info!(
+                    node_id    = %vote.node_id,
+                    generation = vote.generation,
+                    verdict    = ?verdict,
+                    "PrepareSchedInfo: decided"
+                );
+                return Ok(Response::new(decision));
+            }
+            if verdict_rx.changed().await.is_err() {
+                // The transaction ended without an outcome.
+                let superseded = Verdict::Abort(format!(
+                    "generation {} was superseded while preparing",
+                    vote.generation
+                ));
+                return Ok(Response::new(
+                    superseded.decision(vote.generation).unwrap_or_default(),
+                ));
+            }
+        }
+    }
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -397,41 +1769,35 @@ impl NodeService for NodeServiceImpl {
 mod tests {
     use std::sync::Arc;
     use std::time::Duration;
-    use tonic::Request;
+    use tonic::{Request, Response, Status};
 
     use crate::config::{NodeConfig, NodeConfigManager};
     use crate::fault::{
         test_support::MockFaultNotifier, FaultError, FaultNotification, FaultNotifier,
+        FaultSeverity,
     };
+    use crate::grpc::protocol::NodeFeatures;
+    use crate::grpc::txn::TRANSACTION_METADATA_KEY;
     use crate::grpc::{new_workload_store, schedinfo_service::SchedInfoServiceImpl};
     use crate::proto::schedinfo_v1::{
-        node_service_server::NodeService, sched_info_service_server::SchedInfoService,
-        DeadlineMissInfo, NodeSchedRequest, SchedInfo, SyncRequest, TaskInfo,
+        node_service_server::NodeService, sched_info_service_server::SchedInfoService, ClockSync,
+        CpuSet, DeadlineMissInfo, FaultType, NodeSchedRequest, NodeSchedResponse, PrepareDecision,
+        PrepareOutcome, PrepareVote, SchedDrift, SchedInfo, ScheduledTask, SyncRequest, TaskInfo,
+        TimePartition,
     };
 
-    use super::{NodeServiceImpl, DEFAULT_SYNC_TIMEOUT_SECS};
+    use super::{
+        to_proto_task, ClockCheck, NodeServiceImpl, DEFAULT_SYNC_TIMEOUT_SECS,
+        FOREIGN_LOAD_METADATA_KEY, SCHED_DRIFT_METADATA_KEY,
+    };
+    use crate::scheduler::ErrorCode;
+    use crate::task::{FaultSink, Nanos, SchedPolicy, SchedTask};
+    use crate::testing::fake_nodes;
 
     // ── Helpers ───────────────────────────────────────────────────────────────
 
     fn two_node_config() -> Arc<NodeConfigManager> {
-        Arc::new(NodeConfigManager::from_nodes(vec![
-            NodeConfig {
-                name: "n1".into(),
-                available_cpus: vec![0, 1],
-                max_memory_mb: 4096,
-                architecture: "x86_64".into(),
-                location: "test".into(),
-                description: "".into(),
-            },
-            NodeConfig {
-                name: "n2".into(),
-                available_cpus: vec![0, 1],
-                max_memory_mb: 4096,
-                architecture: "x86_64".into(),
-                location: "test".into(),
-                description: "".into(),
-            },
-        ]))
+        Arc::new(fake_nodes(&[("n1", &[0, 1], 4096), ("n2", &[0, 1], 4096)]))
     }
 
     fn task_for(name: &str, node: &str) -> TaskInfo {
@@ -446,6 +1812,15 @@ mod tests {
             deadline: 10_000,
             release_time: 0,
             max_dmiss: 3,
+            shared_resources: vec![],
+            target_node_policy: None,
+            wcet_scaling: Default::default(),
+            metadata: Default::default(),
+            preferred_location: String::new(),
+            placeholder: false,
+            fault_sink: 0,
+            cache_affinity_group: String::new(),
+            memory_mb: 0,
         }
     }
 
@@ -478,6 +1853,7 @@ mod tests {
         let err = node_svc
             .get_sched_info(Request::new(NodeSchedRequest {
                 node_id: "n1".into(),
+                ..Default::default()
             }))
             .await
             .unwrap_err();
@@ -490,6 +1866,7 @@ mod tests {
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl".into(),
             tasks: vec![task_for("t1", "n1"), task_for("t2", "n2")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -497,6 +1874,7 @@ mod tests {
         let resp = node_svc
             .get_sched_info(Request::new(NodeSchedRequest {
                 node_id: "n1".into(),
+                ..Default::default()
             }))
             .await
             .unwrap()
@@ -508,12 +1886,88 @@ mod tests {
         assert!(resp.hyperperiod_us > 0);
     }
 
+    #[tokio::test]
+    async fn get_sched_info_records_the_free_memory_report() {
+        let cfg = Arc::new(
+            NodeConfigManager::from_nodes(vec![NodeConfig::default_config("n1")])
+                .with_live_memory(Duration::from_secs(30)),
+        );
+        let node_svc = NodeServiceImpl::new(
+            new_workload_store(),
+            MockFaultNotifier::arc() as Arc<dyn FaultNotifier>,
+            Duration::from_secs(DEFAULT_SYNC_TIMEOUT_SECS),
+        )
+        .with_node_config(Arc::clone(&cfg));
+
+        // Recorded even before any workload exists.
+        let err = node_svc
+            .get_sched_info(Request::new(NodeSchedRequest {
+                node_id: "n1".into(),
+                free_memory_mb: Some(1_234),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+        assert_eq!(cfg.memory_budget("n1").unwrap().live_mb, Some(1_234));
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_rejects_memory_beyond_the_reported_free_memory() {
+        let cfg = Arc::new(
+            fake_nodes(&[("n1", &[0, 1], 4096)]).with_live_memory(Duration::from_secs(30)),
+        );
+        let store = new_workload_store();
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            Arc::clone(&cfg),
+            Arc::clone(&store),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+        let node_svc = NodeServiceImpl::new(
+            store,
+            mock as Arc<dyn FaultNotifier>,
+            Duration::from_secs(DEFAULT_SYNC_TIMEOUT_SECS),
+        )
+        .with_node_config(Arc::clone(&cfg));
+        node_svc
+            .get_sched_info(Request::new(NodeSchedRequest {
+                node_id: "n1".into(),
+                free_memory_mb: Some(1_000),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        let add = |memory_mb| {
+            svc.add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl".into(),
+                tasks: vec![TaskInfo {
+                    memory_mb,
+                    ..task_for("t1", "n1")
+                }],
+                ..Default::default()
+            }))
+        };
+
+        // Within the configured 4096 MB, but not the 1000 MB the node has.
+        let resp = add(2_000).await.unwrap().into_inner();
+        assert_eq!(resp.status, -1);
+        assert_eq!(
+            resp.placements[0].error_code,
+            ErrorCode::InsufficientMemory.as_u32()
+        );
+
+        let resp = add(800).await.unwrap().into_inner();
+        assert_eq!(resp.status, 0);
+    }
+
     #[tokio::test]
     async fn get_sched_info_unknown_node_returns_empty_task_list() {
         let (svc, node_svc, _) = test_services();
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl".into(),
             tasks: vec![task_for("t1", "n1")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -521,6 +1975,7 @@ mod tests {
         let resp = node_svc
             .get_sched_info(Request::new(NodeSchedRequest {
                 node_id: "no_such_node".into(),
+                ..Default::default()
             }))
             .await
             .unwrap()
@@ -530,6 +1985,293 @@ mod tests {
         assert!(resp.tasks.is_empty());
     }
 
+    async fn submit(svc: &SchedInfoServiceImpl, tasks: Vec<TaskInfo>) {
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl".into(),
+            tasks,
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    }
+
+    async fn fetch(node_svc: &NodeServiceImpl, known: Option<u64>) -> NodeSchedResponse {
+        node_svc
+            .get_sched_info(Request::new(NodeSchedRequest {
+                node_id: "n1".into(),
+                known_generation: known,
+                known_instance_epoch: known.map(|_| node_svc.instance_epoch()),
+                features: NodeFeatures::ALL.bits(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+    }
+
+    #[tokio::test]
+    async fn get_sched_info_sends_delta_for_next_generation() {
+        let (svc, node_svc, _) = test_services();
+        submit(&svc, vec![task_for("t1", "n1"), task_for("t2", "n1")]).await;
+        let first = fetch(&node_svc, None).await;
+        assert!(first.full);
+        assert_eq!(first.generation, 1);
+        assert_eq!(first.tasks.len(), 2);
+
+        let mut changed = task_for("t2", "n1");
+        changed.runtime = 2_000;
+        submit(&svc, vec![task_for("t1", "n1"), changed]).await;
+
+        let resp = fetch(&node_svc, Some(first.generation)).await;
+        assert!(!resp.full);
+        assert_eq!(resp.generation, 2);
+        assert!(resp.tasks.is_empty());
+        assert!(resp.removed_tasks.is_empty());
+        assert_eq!(resp.modified_tasks.len(), 1);
+        assert_eq!(resp.modified_tasks[0].name, "t2");
+        assert_eq!(resp.modified_tasks[0].runtime_us, 2_000);
+
+        // Already current: empty delta.
+        let resp = fetch(&node_svc, Some(2)).await;
+        assert!(!resp.full);
+        assert!(resp.tasks.is_empty() && resp.modified_tasks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_sched_info_generation_gap_forces_full_resync() {
+        let (svc, node_svc, _) = test_services();
+        submit(&svc, vec![task_for("t1", "n1")]).await;
+        submit(&svc, vec![task_for("t1", "n1"), task_for("t2", "n1")]).await;
+        submit(&svc, vec![task_for("t2", "n1")]).await;
+
+        // Node last applied generation 1; the store is at 3.
+        let resp = fetch(&node_svc, Some(1)).await;
+        assert!(resp.full);
+        assert_eq!(resp.generation, 3);
+        assert_eq!(resp.tasks.len(), 1);
+        assert_eq!(resp.tasks[0].name, "t2");
+    }
+
+    #[tokio::test]
+    async fn get_sched_info_full_push_ignores_known_generation() {
+        let (svc, node_svc, _) = test_services();
+        let node_svc = node_svc.with_full_push(true);
+        submit(&svc, vec![task_for("t1", "n1")]).await;
+        submit(&svc, vec![task_for("t1", "n1"), task_for("t2", "n1")]).await;
+
+        let resp = fetch(&node_svc, Some(1)).await;
+        assert!(resp.full);
+        assert_eq!(resp.tasks.len(), 2);
+    }
+
+    // ── Restarts ──────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn restart_answers_an_old_generation_with_a_full_resync() {
+        let (svc, node_svc, _) = test_services();
+        submit(&svc, vec![task_for("t1", "n1")]).await;
+        submit(&svc, vec![task_for("t1", "n1"), task_for("t2", "n1")]).await;
+        let before = fetch(&node_svc, None).await;
+        assert_eq!(before.generation, 2);
+        assert!(!before.resync);
+
+        // Restarted: Pullpiri resubmits other tasks, again up to generation 2.
+        let (svc, node_svc, _) = test_services();
+        submit(&svc, vec![task_for("t3", "n1")]).await;
+        submit(&svc, vec![task_for("t3", "n1"), task_for("t4", "n1")]).await;
+        let old_node = |known_instance_epoch| NodeSchedRequest {
+            node_id: "n1".into(),
+            known_generation: Some(2),
+            known_instance_epoch,
+            known_tasks: vec!["t1".into(), "t2".into()],
+            features: NodeFeatures::ALL.bits(),
+            ..Default::default()
+        };
+        for known_instance_epoch in [Some(before.instance_epoch), None] {
+            let resp = node_svc
+                .get_sched_info(Request::new(old_node(known_instance_epoch)))
+                .await
+                .unwrap()
+                .into_inner();
+            assert!(resp.resync && resp.full, "{known_instance_epoch:?}");
+            assert_eq!(resp.generation, 2);
+            assert!(resp.instance_epoch > before.instance_epoch);
+            let names: Vec<_> = resp.tasks.iter().map(|t| t.name.as_str()).collect();
+            assert_eq!(names, ["t3", "t4"]);
+        }
+
+        // On this instance's schedule: deltas again.
+        let resp = fetch(&node_svc, Some(2)).await;
+        assert!(!resp.resync && !resp.full);
+        assert!(resp.tasks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reconciliation_reports_every_configured_node() {
+        use crate::grpc::events::EventLog;
+        use crate::grpc::lifecycle::TaskState;
+        use crate::proto::schedinfo_v1::ScheduleEventKind as K;
+        use std::time::Instant;
+
+        // n4 is configured but has no tasks; it is still expected to call.
+        let cfg = Arc::new(fake_nodes(&[
+            ("n1", &[0, 1], 4096),
+            ("n2", &[0, 1], 4096),
+            ("n3", &[0, 1], 4096),
+            ("n4", &[0, 1], 4096),
+        ]));
+        let store = new_workload_store();
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            Arc::clone(&cfg),
+            Arc::clone(&store),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+        let events = Arc::new(EventLog::default());
+        let node_svc = NodeServiceImpl::new(
+            store,
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+            Duration::from_secs(DEFAULT_SYNC_TIMEOUT_SECS),
+        )
+        .with_node_config(cfg)
+        .with_reconcile_window(Duration::from_secs(30))
+        .with_event_log(Arc::clone(&events));
+        let started = Instant::now();
+        let mut sub = events.subscribe(Some(0), |e| e.kind() == K::NodesReconciled);
+        submit(
+            &svc,
+            vec![
+                task_for("t1", "n1"),
+                task_for("t2", "n2"),
+                task_for("t3", "n3"),
+            ],
+        )
+        .await;
+
+        // n1 runs what the ledger places on it; n2 still runs a task of its
+        // schedule from before the restart.
+        for (node, known_tasks) in [("n1", vec!["t1"]), ("n2", vec!["t2", "old"])] {
+            node_svc
+                .get_sched_info(Request::new(NodeSchedRequest {
+                    node_id: node.into(),
+                    known_generation: Some(7),
+                    known_instance_epoch: Some(1),
+                    known_tasks: known_tasks.into_iter().map(String::from).collect(),
+                    features: NodeFeatures::ALL.bits(),
+                    ..Default::default()
+                }))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(node_svc.finish_reconciliation().await, None);
+        let report = node_svc
+            .finish_reconciliation_at(started + Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert_eq!(report.instance_epoch, node_svc.instance_epoch());
+        assert_eq!(report.in_sync, ["n1"]);
+        assert_eq!(report.diverged, ["n2"]);
+        assert_eq!(report.unreachable, ["n3", "n4"]);
+
+        let store = node_svc.workload_store.lock().await;
+        let states = &store[crate::grpc::DEFAULT_TENANT].task_states;
+        assert_eq!(states.get("n1", "t1"), Some(TaskState::Delivered));
+        assert_eq!(states.get("n2", "t2"), Some(TaskState::Delivered));
+        assert_eq!(states.get("n3", "t3"), Some(TaskState::Degraded));
+        drop(store);
+        assert_eq!(sub.next().await.unwrap().reconcile, Some(report));
+        assert_eq!(
+            node_svc
+                .finish_reconciliation_at(started + Duration::from_secs(60))
+                .await,
+            None
+        );
+    }
+
+    // ── StreamSchedInfo ───────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn stream_sched_info_batches_a_large_schedule() {
+        use tokio_stream::StreamExt;
+
+        use crate::grpc::lifecycle::TaskState;
+        use crate::grpc::stream::{DeliveryProgress, ScheduleAssembler};
+        use crate::grpc::DEFAULT_TENANT;
+
+        let store = new_workload_store();
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            Arc::clone(&store),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+        let node_svc = NodeServiceImpl::new(
+            Arc::clone(&store),
+            mock as Arc<dyn FaultNotifier>,
+            Duration::from_secs(DEFAULT_SYNC_TIMEOUT_SECS),
+        )
+        .with_stream_batch_size(200);
+        let tasks = (0..1_000)
+            .map(|i| TaskInfo {
+                runtime: 1,
+                ..task_for(&format!("t{i:04}"), "n1")
+            })
+            .collect();
+        submit(&svc, tasks).await;
+
+        let mut stream = node_svc
+            .stream_sched_info(Request::new(NodeSchedRequest {
+                node_id: "n1".into(),
+                known_generation: None,
+                features: NodeFeatures::ALL.bits(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let mut assembler = ScheduleAssembler::new();
+        let (mut chunks, mut streamed) = (0, None);
+        while let Some(chunk) = stream.next().await {
+            chunks += 1;
+            streamed = assembler.push(chunk.unwrap()).unwrap();
+        }
+
+        // 5 batches + commit, equal to the unary answer.
+        assert_eq!(chunks, 6);
+        assert_eq!(streamed.unwrap(), fetch(&node_svc, None).await);
+
+        let guard = store.lock().await;
+        let ws = &guard[DEFAULT_TENANT];
+        assert_eq!(
+            ws.deliveries["n1"],
+            DeliveryProgress {
+                generation: 1,
+                batches_sent: 5,
+                batch_count: 5,
+                committed: true,
+            }
+        );
+        assert_eq!(
+            ws.task_states.get("n1", "t0999"),
+            Some(TaskState::Delivered)
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_sched_info_no_workload_returns_not_found() {
+        let (_, node_svc, _) = test_services();
+        let err = node_svc
+            .stream_sched_info(Request::new(NodeSchedRequest {
+                node_id: "n1".into(),
+                known_generation: None,
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
     // ── SyncTimer ─────────────────────────────────────────────────────────────
 
     #[tokio::test]
@@ -550,6 +2292,7 @@ mod tests {
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl".into(),
             tasks: vec![task_for("t1", "n1")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -569,6 +2312,7 @@ mod tests {
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl".into(),
             tasks: vec![task_for("t1", "n1")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -594,6 +2338,7 @@ mod tests {
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl".into(),
             tasks: vec![task_for("t1", "n1"), task_for("t2", "n2")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -649,6 +2394,7 @@ mod tests {
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl".into(),
             tasks: vec![task_for("t1", "n1"), task_for("t2", "n2")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -672,62 +2418,13 @@ mod tests {
     #[tokio::test]
     async fn sync_timer_timeout_wakes_all_waiting_nodes() {
         // Three-node workload; n1 and n2 join but n3 never does.
-        let ncm = NodeConfigManager::from_nodes(vec![
-            NodeConfig {
-                name: "n1".into(),
-                available_cpus: vec![0],
-                max_memory_mb: 1024,
-                architecture: "x86_64".into(),
-                location: "test".into(),
-                description: "".into(),
-            },
-            NodeConfig {
-                name: "n2".into(),
-                available_cpus: vec![0],
-                max_memory_mb: 1024,
-                architecture: "x86_64".into(),
-                location: "test".into(),
-                description: "".into(),
-            },
-            NodeConfig {
-                name: "n3".into(),
-                available_cpus: vec![0],
-                max_memory_mb: 1024,
-                architecture: "x86_64".into(),
-                location: "test".into(),
-                description: "".into(),
-            },
-        ]);
-        let _ = ncm; // suppress unused warning
-
         let store = new_workload_store();
         let mock = MockFaultNotifier::arc();
         let svc = SchedInfoServiceImpl::new(
-            Arc::new(NodeConfigManager::from_nodes(vec![
-                NodeConfig {
-                    name: "n1".into(),
-                    available_cpus: vec![0, 1],
-                    max_memory_mb: 4096,
-                    architecture: "x86_64".into(),
-                    location: "test".into(),
-                    description: "".into(),
-                },
-                NodeConfig {
-                    name: "n2".into(),
-                    available_cpus: vec![0, 1],
-                    max_memory_mb: 4096,
-                    architecture: "x86_64".into(),
-                    location: "test".into(),
-                    description: "".into(),
-                },
-                NodeConfig {
-                    name: "n3".into(),
-                    available_cpus: vec![0, 1],
-                    max_memory_mb: 4096,
-                    architecture: "x86_64".into(),
-                    location: "test".into(),
-                    description: "".into(),
-                },
+            Arc::new(fake_nodes(&[
+                ("n1", &[0, 1], 4096),
+                ("n2", &[0, 1], 4096),
+                ("n3", &[0, 1], 4096),
             ])),
             Arc::clone(&store),
             Arc::clone(&mock) as Arc<dyn FaultNotifier>,
@@ -745,6 +2442,7 @@ mod tests {
                 task_for("t2", "n2"),
                 task_for("t3", "n3"),
             ],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -795,6 +2493,7 @@ mod tests {
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl1".into(),
             tasks: vec![task_for("t1", "n1"), task_for("t2", "n2")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -814,6 +2513,7 @@ mod tests {
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl2".into(),
             tasks: vec![task_for("t3", "n1")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -835,6 +2535,7 @@ mod tests {
             .report_d_miss(Request::new(DeadlineMissInfo {
                 node_id: "n1".into(),
                 task_name: "t1".into(),
+                drift: None,
             }))
             .await
             .unwrap()
@@ -848,6 +2549,7 @@ mod tests {
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl".into(),
             tasks: vec![task_for("t1", "n1")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -856,6 +2558,7 @@ mod tests {
             .report_d_miss(Request::new(DeadlineMissInfo {
                 node_id: "n1".into(),
                 task_name: "t1".into(),
+                drift: None,
             }))
             .await
             .unwrap()
@@ -875,6 +2578,7 @@ mod tests {
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl_fallback".into(),
             tasks: vec![task_for("t1", "n1")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -883,7 +2587,8 @@ mod tests {
         let resp = node_svc
             .report_d_miss(Request::new(DeadlineMissInfo {
                 node_id: "n1".into(),
-                task_name: "task_that_does_not_exist".into(),
+                task_name: "no such/task".into(),
+                drift: None,
             }))
             .await
             .unwrap()
@@ -894,6 +2599,7 @@ mod tests {
         let calls = mock.calls.lock().unwrap();
         assert_eq!(calls.len(), 1);
         assert_eq!(calls[0].workload_id, "wl_fallback");
+        assert_eq!(calls[0].task_name, "no%20such%2Ftask");
     }
 
     #[tokio::test]
@@ -918,6 +2624,7 @@ mod tests {
         svc.add_sched_info(Request::new(SchedInfo {
             workload_id: "wl".into(),
             tasks: vec![task_for("t1", "n1")],
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -933,6 +2640,7 @@ mod tests {
             .report_d_miss(Request::new(DeadlineMissInfo {
                 node_id: "n1".into(),
                 task_name: "t1".into(),
+                drift: None,
             }))
             .await
             .unwrap()
@@ -941,4 +2649,1175 @@ mod tests {
         assert_ne!(resp.status, 0);
         assert!(!resp.error_message.is_empty());
     }
+
+    #[tokio::test]
+    async fn report_d_miss_with_drift_is_a_sched_drift_fault() {
+        let (svc, node_svc, mock) = test_services();
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl".into(),
+            tasks: vec![task_for("t1", "n1")],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+
+        let drift = SchedDrift {
+            expected_policy: 1,
+            expected_priority: 50,
+            expected_cpu_affinity: 0x2,
+            observed_policy: 0,
+            observed_priority: 0,
+            observed_cpu_affinity: 0x2,
+            reapplied: 2,
+            restored: false,
+        };
+        for restored in [false, true] {
+            let resp = node_svc
+                .report_d_miss(Request::new(DeadlineMissInfo {
+                    node_id: "n1".into(),
+                    task_name: "t1".into(),
+                    drift: Some(SchedDrift { restored, ..drift }),
+                }))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(resp.status, 0);
+        }
+
+        let calls = mock.calls.lock().unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].fault_type, FaultType::SchedDrift);
+        assert_eq!(calls[0].severity, FaultSeverity::Critical);
+        assert_eq!(
+            calls[0].metadata[SCHED_DRIFT_METADATA_KEY],
+            "policy 1 -> 0, priority 50 -> 0; not restored after 2 re-apply(s)"
+        );
+        assert_eq!(calls[1].severity, FaultSeverity::Advisory);
+        assert_eq!(
+            calls[1].metadata[SCHED_DRIFT_METADATA_KEY],
+            "policy 1 -> 0, priority 50 -> 0; restored after 2 re-apply(s)"
+        );
+    }
+
+    fn with_sink(name: &str, sink: FaultSink) -> TaskInfo {
+        TaskInfo {
+            fault_sink: sink.to_proto_int(),
+            ..task_for(name, "n1")
+        }
+    }
+
+    async fn miss(node_svc: &NodeServiceImpl, task: &str) {
+        let resp = node_svc
+            .report_d_miss(Request::new(DeadlineMissInfo {
+                node_id: "n1".into(),
+                task_name: task.into(),
+                drift: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, 0);
+    }
+
+    #[tokio::test]
+    async fn report_d_miss_local_only_is_not_forwarded() {
+        use crate::grpc::lifecycle::TaskState;
+        use crate::grpc::DEFAULT_TENANT;
+
+        let (svc, node_svc, mock) = test_services();
+        submit(
+            &svc,
+            vec![
+                with_sink("local", FaultSink::LocalOnly),
+                with_sink("both", FaultSink::Both),
+                with_sink("up", FaultSink::Upstream),
+            ],
+        )
+        .await;
+        let resp = fetch(&node_svc, None).await;
+        let local = resp.tasks.iter().find(|t| t.name == "local").unwrap();
+        assert_eq!(local.fault_sink, FaultSink::LocalOnly.to_proto_int());
+
+        for task in ["local", "both", "up"] {
+            miss(&node_svc, task).await;
+        }
+
+        let notified: Vec<_> = mock
+            .calls
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|c| c.task_name.clone())
+            .collect();
+        assert_eq!(notified, ["both", "up"]);
+        let ws = &node_svc.workload_store.lock().await[DEFAULT_TENANT];
+        assert_eq!(ws.task_states.get("n1", "local"), Some(TaskState::Faulted));
+    }
+
+    #[tokio::test]
+    async fn report_d_miss_follows_the_sink_the_node_reported() {
+        use crate::proto::schedinfo_v1::{ApplyReport, ApplyStatus, TaskApplyResult};
+
+        let (svc, node_svc, mock) = test_services();
+        submit(&svc, vec![with_sink("local", FaultSink::LocalOnly)]).await;
+        fetch(&node_svc, None).await;
+
+        // The node has no local sink, so it runs the task upstream.
+        node_svc
+            .report_apply(Request::new(ApplyReport {
+                node_id: "n1".into(),
+                generation: 1,
+                tasks: vec![TaskApplyResult {
+                    task_name: "local".into(),
+                    status: ApplyStatus::Applied as i32,
+                    fault_sink: FaultSink::Upstream.to_proto_int(),
+                    ..Default::default()
+                }],
+                node: None,
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        miss(&node_svc, "local").await;
+
+        assert_eq!(mock.calls.lock().unwrap().len(), 1);
+    }
+
+    // ── Task lifecycle ────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn task_lifecycle_follows_rpc_flow() {
+        use crate::grpc::lifecycle::TaskState;
+        use crate::grpc::DEFAULT_TENANT;
+
+        let store = new_workload_store();
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            Arc::clone(&store),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+        let node_svc = NodeServiceImpl::new(
+            Arc::clone(&store),
+            mock as Arc<dyn FaultNotifier>,
+            Duration::from_secs(DEFAULT_SYNC_TIMEOUT_SECS),
+        );
+        let state = |store: &crate::grpc::WorkloadStore| {
+            let store = Arc::clone(store);
+            async move {
+                store.lock().await[DEFAULT_TENANT]
+                    .task_states
+                    .get("n1", "t1")
+                    .unwrap()
+            }
+        };
+
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl".into(),
+            tasks: vec![task_for("t1", "n1")],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+        assert_eq!(state(&store).await, TaskState::Pending);
+
+        // Apply before delivery is illegal: counted, state unchanged.
+        {
+            let mut guard = store.lock().await;
+            let ws = guard.get_mut(DEFAULT_TENANT).unwrap();
+            assert!(!ws
+                .task_states
+                .apply("n1", "t1", crate::grpc::lifecycle::TaskEvent::Apply));
+            assert_eq!(ws.task_states.illegal_transitions(), 1);
+        }
+
+        node_svc
+            .get_sched_info(Request::new(NodeSchedRequest {
+                node_id: "n1".into(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(state(&store).await, TaskState::Delivered);
+
+        // n1 is the only active node, so its SyncTimer releases the barrier.
+        node_svc
+            .sync_timer(Request::new(SyncRequest {
+                node_id: "n1".into(),
+            }))
+            .await
+            .unwrap();
+        assert_eq!(state(&store).await, TaskState::Running);
+
+        node_svc
+            .report_d_miss(Request::new(DeadlineMissInfo {
+                node_id: "n1".into(),
+                task_name: "t1".into(),
+                drift: None,
+            }))
+            .await
+            .unwrap();
+        assert_eq!(state(&store).await, TaskState::Faulted);
+    }
+
+    // ── Push epochs ───────────────────────────────────────────────────────────
+
+    async fn fetch_node(node_svc: &NodeServiceImpl, node: &str, known: u64) -> NodeSchedResponse {
+        node_svc
+            .get_sched_info(Request::new(NodeSchedRequest {
+                node_id: node.into(),
+                known_generation: Some(known),
+                known_instance_epoch: Some(node_svc.instance_epoch()),
+                features: NodeFeatures::ALL.bits(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn push_epoch_coalesces_updates_into_one_delta_per_node() {
+        use tokio::sync::watch;
+
+        use crate::grpc::events::EventLog;
+        use crate::proto::schedinfo_v1::ScheduleEventKind as K;
+
+        let events = Arc::new(EventLog::default());
+        let (svc, node_svc, _) = test_services();
+        let svc = svc
+            .with_push_epoch(Duration::from_secs(1))
+            .with_event_log(Arc::clone(&events));
+        let node_svc = node_svc.with_event_log(Arc::clone(&events));
+        let mut deliveries = events.subscribe(Some(0), |e| e.kind() == K::DeliveryConfirmed);
+
+        // The first workload is published at once.
+        submit(&svc, vec![task_for("a1", "n1"), task_for("b1", "n2")]).await;
+        assert_eq!(fetch(&node_svc, None).await.generation, 1);
+        let (shutdown, rx) = watch::channel(false);
+        let flusher = tokio::spawn({
+            let svc = svc.clone();
+            async move { svc.run_push_epochs(rx).await }
+        });
+
+        // Three updates within one epoch.
+        let mut a1 = task_for("a1", "n1");
+        a1.runtime = 2_000;
+        let mut b1 = task_for("b1", "n2");
+        submit(&svc, vec![a1.clone(), task_for("b1", "n2")]).await;
+        submit(&svc, vec![a1.clone(), task_for("a2", "n1"), b1.clone()]).await;
+        b1.runtime = 3_000;
+        submit(&svc, vec![a1, task_for("a2", "n1"), b1]).await;
+
+        // Held: the nodes are still current at generation 1.
+        let held = fetch_node(&node_svc, "n1", 1).await;
+        assert_eq!(held.generation, 1);
+        assert!(held.tasks.is_empty() && held.modified_tasks.is_empty());
+
+        tokio::time::sleep(Duration::from_millis(1_010)).await;
+        let n1 = fetch_node(&node_svc, "n1", 1).await;
+        assert_eq!(n1.generation, 2);
+        assert!(!n1.full);
+        let names = |ts: &[ScheduledTask]| ts.iter().map(|t| t.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&n1.tasks), ["a2"]);
+        assert_eq!(names(&n1.modified_tasks), ["a1"]);
+        let n2 = fetch_node(&node_svc, "n2", 1).await;
+        assert_eq!(n2.generation, 2);
+        assert_eq!(names(&n2.modified_tasks), ["b1"]);
+        assert!(n2.tasks.is_empty());
+
+        // One delivery per node and generation, none for the update steps.
+        let mut got = Vec::new();
+        for _ in 0..3 {
+            let e = deliveries.next().await.unwrap();
+            got.push((e.node, e.generation));
+        }
+        assert_eq!(got, [("n1".into(), 1), ("n1".into(), 2), ("n2".into(), 2)]);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(1), deliveries.next())
+                .await
+                .is_err()
+        );
+
+        shutdown.send(true).unwrap();
+        flusher.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn push_epoch_publishes_safety_updates_and_flushes_on_shutdown() {
+        use tokio::sync::watch;
+
+        let (svc, node_svc, _) = test_services();
+        let svc = svc.with_push_epoch(Duration::from_secs(60));
+        submit(&svc, vec![task_for("t1", "n1")]).await;
+
+        // Held, then published with the safety update that follows it.
+        submit(&svc, vec![task_for("t1", "n1"), task_for("t2", "n1")]).await;
+        assert_eq!(fetch(&node_svc, Some(1)).await.generation, 1);
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl".into(),
+            tasks: vec![
+                task_for("t1", "n1"),
+                task_for("t2", "n1"),
+                task_for("t3", "n1"),
+            ],
+            priority_class: Some("safety".into()),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+        let resp = fetch(&node_svc, Some(1)).await;
+        assert_eq!(resp.generation, 2);
+        assert_eq!(resp.tasks.len(), 2, "t2 and t3 in one delta");
+
+        submit(&svc, vec![task_for("t1", "n1")]).await;
+        assert_eq!(fetch(&node_svc, Some(2)).await.generation, 2);
+        let (shutdown, rx) = watch::channel(false);
+        let flusher = tokio::spawn({
+            let svc = svc.clone();
+            async move { svc.run_push_epochs(rx).await }
+        });
+        shutdown.send(true).unwrap();
+        flusher.await.unwrap();
+        assert_eq!(fetch(&node_svc, Some(2)).await.removed_tasks.len(), 2);
+    }
+
+    // ── Transactional push ────────────────────────────────────────────────────
+
+    async fn vote(
+        node_svc: &NodeServiceImpl,
+        node: &str,
+        generation: u64,
+        staged: bool,
+    ) -> PrepareDecision {
+        node_svc
+            .prepare_sched_info(Request::new(PrepareVote {
+                node_id: node.into(),
+                generation,
+                staged,
+                error_message: if staged { "" } else { "cgroup missing" }.into(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+    }
+
+    /// Vote for `node` in the background.
+    fn spawn_vote(
+        node_svc: &NodeServiceImpl,
+        node: &str,
+        generation: u64,
+    ) -> tokio::task::JoinHandle<PrepareDecision> {
+        let node_svc = node_svc.clone();
+        let node = node.to_string();
+        tokio::spawn(async move { vote(&node_svc, &node, generation, true).await })
+    }
+
+    #[tokio::test]
+    async fn transactional_push_commits_once_every_node_staged() {
+        use crate::grpc::events::EventLog;
+        use crate::proto::schedinfo_v1::ScheduleEventKind as K;
+
+        let events = Arc::new(EventLog::default());
+        let (svc, node_svc, _) = test_services();
+        let svc = svc
+            .with_transactional_push(Duration::from_secs(30))
+            .with_event_log(Arc::clone(&events));
+        let node_svc = node_svc.with_event_log(Arc::clone(&events));
+        let mut outcomes = events.subscribe(Some(0), |e| {
+            matches!(e.kind(), K::TransactionCommitted | K::TransactionAborted)
+        });
+
+        submit(&svc, vec![task_for("a1", "n1"), task_for("b1", "n2")]).await;
+        let n1 = fetch_node(&node_svc, "n1", 0).await;
+        assert!(n1.prepare && n1.full);
+        assert_eq!(n1.generation, 1);
+
+        // n1 waits until n2 has staged the generation too.
+        let n1_vote = spawn_vote(&node_svc, "n1", 1);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!n1_vote.is_finished());
+        let n2 = vote(&node_svc, "n2", 1, true).await;
+        assert_eq!(n2.outcome(), PrepareOutcome::Commit);
+        assert_eq!(n1_vote.await.unwrap().outcome(), PrepareOutcome::Commit);
+
+        let e = outcomes.next().await.unwrap();
+        assert_eq!((e.kind(), e.generation), (K::TransactionCommitted, 1));
+        // Published: served without prepare, and a late vote learns the
+        // outcome.
+        let n1 = fetch_node(&node_svc, "n1", 1).await;
+        assert!(!n1.prepare);
+        assert_eq!(n1.generation, 1);
+        let late = vote(&node_svc, "n1", 1, true).await;
+        assert_eq!(late.outcome(), PrepareOutcome::Commit);
+    }
+
+    #[tokio::test]
+    async fn transactional_push_rolls_back_when_a_node_fails_to_stage() {
+        let (svc, node_svc, mock) = test_services();
+        let svc = svc.with_transactional_push(Duration::from_secs(30));
+        submit(&svc, vec![task_for("a1", "n1"), task_for("b1", "n2")]).await;
+        let n1_vote = spawn_vote(&node_svc, "n1", 1);
+        vote(&node_svc, "n2", 1, true).await;
+        assert_eq!(n1_vote.await.unwrap().outcome(), PrepareOutcome::Commit);
+
+        // A replacement changing both nodes.
+        let mut a1 = task_for("a1", "n1");
+        a1.runtime = 2_000;
+        let mut b1 = task_for("b1", "n2");
+        b1.runtime = 2_000;
+        submit(&svc, vec![a1, b1]).await;
+        let n1 = fetch_node(&node_svc, "n1", 1).await;
+        assert!(n1.prepare && !n1.full);
+        assert_eq!(n1.generation, 2);
+        assert_eq!(n1.modified_tasks[0].runtime_us, 2_000);
+
+        let n1_vote = spawn_vote(&node_svc, "n1", 2);
+        let n2 = vote(&node_svc, "n2", 2, false).await;
+        assert_eq!(n2.outcome(), PrepareOutcome::Abort);
+        assert_eq!(
+            n2.reason,
+            "node n2 failed to stage generation 2: cgroup missing"
+        );
+        assert_eq!(n1_vote.await.unwrap().outcome(), PrepareOutcome::Abort);
+
+        // Back at generation 1, which the nodes still run.
+        let n1 = fetch_node(&node_svc, "n1", 1).await;
+        assert!(!n1.prepare);
+        assert_eq!(n1.generation, 1);
+        assert!(n1.tasks.is_empty() && n1.modified_tasks.is_empty());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let calls = mock.calls.lock().unwrap();
+        let abort = calls
+            .iter()
+            .find(|c| c.fault_type == FaultType::TransactionAborted)
+            .unwrap();
+        assert_eq!(abort.workload_id, "wl");
+        assert_eq!(
+            abort.metadata[TRANSACTION_METADATA_KEY],
+            "node n2 failed to stage generation 2: cgroup missing"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn transactional_push_aborts_when_prepare_times_out() {
+        let (svc, node_svc, _) = test_services();
+        let svc = svc.with_transactional_push(Duration::from_millis(500));
+        submit(&svc, vec![task_for("a1", "n1"), task_for("b1", "n2")]).await;
+
+        // n2 never votes.
+        let n1 = vote(&node_svc, "n1", 1, true).await;
+        assert_eq!(n1.outcome(), PrepareOutcome::Abort);
+        assert_eq!(n1.reason, "prepare timed out after 500 ms waiting for n2");
+
+        // A first workload has nothing to roll back to.
+        let err = node_svc
+            .get_sched_info(Request::new(NodeSchedRequest {
+                node_id: "n1".into(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    // ── Protocol negotiation ──────────────────────────────────────────────────
+
+    /// `GetSchedInfo` for a node that announces no features.
+    async fn fetch_legacy(node_svc: &NodeServiceImpl, node: &str, known: u64) -> NodeSchedResponse {
+        node_svc
+            .get_sched_info(Request::new(NodeSchedRequest {
+                node_id: node.into(),
+                known_generation: Some(known),
+                known_instance_epoch: Some(node_svc.instance_epoch()),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+    }
+
+    #[tokio::test]
+    async fn legacy_node_is_left_out_of_a_transaction() {
+        use crate::proto::schedinfo_v1::ClusterStatusRequest;
+
+        let (svc, node_svc, _) = test_services();
+        let svc = svc.with_transactional_push(Duration::from_secs(30));
+        submit(&svc, vec![task_for("a1", "n1"), task_for("b1", "n2")]).await;
+        let n1_vote = spawn_vote(&node_svc, "n1", 1);
+        vote(&node_svc, "n2", 1, true).await;
+        assert_eq!(n1_vote.await.unwrap().outcome(), PrepareOutcome::Commit);
+
+        // Both nodes change; only n1 can stage.
+        let mut a1 = task_for("a1", "n1");
+        a1.runtime = 2_000;
+        let mut b1 = task_for("b1", "n2");
+        b1.runtime = 2_000;
+        submit(&svc, vec![a1, b1]).await;
+        let n1 = fetch_node(&node_svc, "n1", 1).await;
+        assert!(n1.prepare && !n1.full);
+        assert_eq!(n1.generation, 2);
+        let n2 = fetch_legacy(&node_svc, "n2", 1).await;
+        assert!(!n2.prepare && n2.full);
+        assert_eq!(n2.generation, 1);
+        assert_eq!(n2.tasks[0].runtime_us, 1_000);
+
+        // n1's vote alone commits; n2 then gets generation 2 in full.
+        assert_eq!(
+            vote(&node_svc, "n1", 2, true).await.outcome(),
+            PrepareOutcome::Commit
+        );
+        let n2 = fetch_legacy(&node_svc, "n2", 1).await;
+        assert!(!n2.prepare && n2.full);
+        assert_eq!(n2.generation, 2);
+        assert_eq!(n2.tasks[0].runtime_us, 2_000);
+
+        let status = svc
+            .get_cluster_status(Request::new(ClusterStatusRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        let protocol = |node: &str| {
+            status
+                .nodes
+                .iter()
+                .find(|n| n.node == node)
+                .unwrap()
+                .protocol
+                .clone()
+        };
+        assert_eq!(protocol("n1"), "delta,prepare,placeholder");
+        assert_eq!(protocol("n2"), "legacy");
+    }
+
+    // ── Events ────────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn delivery_and_faults_are_recorded_as_events() {
+        use crate::grpc::events::EventLog;
+        use crate::proto::schedinfo_v1::ScheduleEventKind as K;
+
+        let events = Arc::new(EventLog::default());
+        let (svc, node_svc, _) = test_services();
+        let svc = svc.with_event_log(Arc::clone(&events));
+        let node_svc = node_svc.with_event_log(Arc::clone(&events));
+        let mut sub = events.subscribe(Some(0), |_| true);
+
+        submit(&svc, vec![task_for("t1", "n1")]).await;
+        fetch(&node_svc, None).await;
+        // Already current: nothing new was delivered.
+        fetch(&node_svc, Some(1)).await;
+        node_svc
+            .report_d_miss(Request::new(DeadlineMissInfo {
+                node_id: "n1".into(),
+                task_name: "t1".into(),
+                drift: None,
+            }))
+            .await
+            .unwrap();
+        // The replacement clears the fault.
+        submit(&svc, vec![task_for("t1", "n1")]).await;
+
+        let mut got = Vec::new();
+        for _ in 0..5 {
+            let e = sub.next().await.unwrap();
+            got.push((e.sequence, e.kind(), e.node, e.task, e.generation));
+        }
+        let none = String::new;
+        assert_eq!(
+            got,
+            [
+                (1, K::WorkloadScheduled, none(), none(), 1),
+                (2, K::DeliveryConfirmed, "n1".into(), none(), 1),
+                (3, K::FaultRaised, "n1".into(), "t1".into(), 1),
+                (4, K::WorkloadUpdated, none(), none(), 2),
+                (5, K::FaultCleared, "n1".into(), "t1".into(), 2),
+            ]
+        );
+    }
+
+    // ── ReportApply ───────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn apply_report_moves_each_task_by_its_status() {
+        use crate::proto::schedinfo_v1::{
+            ApplyReport, ApplyStatus as A, ClusterStatusRequest, FaultType, NodeApplyInfo,
+            TaskApplyResult,
+        };
+
+        let (svc, node_svc, mock) = test_services();
+        let cases = [
+            ("ok", A::Applied),
+            ("dry", A::DryRun),
+            ("gone", A::PidNotFound),
+            ("eperm", A::PermissionDenied),
+            ("cpu", A::InvalidCpu),
+            ("cg", A::CgroupError),
+            ("attrs", A::InvalidAttributes),
+            ("unknown", A::Unspecified),
+        ];
+        submit(&svc, cases.iter().map(|(t, _)| task_for(t, "n1")).collect()).await;
+        fetch(&node_svc, None).await;
+
+        let report = |generation| ApplyReport {
+            node_id: "n1".into(),
+            generation,
+            tasks: cases
+                .iter()
+                .map(|&(t, status)| TaskApplyResult {
+                    task_name: t.into(),
+                    status: status as i32,
+                    errno: if status == A::PidNotFound { 3 } else { 0 },
+                    ..Default::default()
+                })
+                .collect(),
+            node: Some(NodeApplyInfo {
+                cap_sys_nice: true,
+                rtprio_limit: 99,
+                isolated_cpus: vec![1],
+                rt_runtime_us: -1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let stale = node_svc
+            .report_apply(Request::new(report(7)))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(stale.status, -1);
+        assert!(stale.error_message.contains("generation 7"));
+        assert!(mock.calls.lock().unwrap().is_empty());
+
+        let resp = node_svc
+            .report_apply(Request::new(report(1)))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, 0);
+
+        let calls = mock.calls.lock().unwrap().clone();
+        let mut notified: Vec<_> = calls.iter().map(|c| c.task_name.as_str()).collect();
+        notified.sort();
+        assert_eq!(notified, ["attrs", "cg", "cpu", "eperm", "gone"]);
+        assert!(calls.iter().all(|c| c.fault_type == FaultType::ApplyFailed));
+
+        let status = svc
+            .get_cluster_status(Request::new(ClusterStatusRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        let n1 = status.nodes.iter().find(|n| n.node == "n1").unwrap();
+        assert_eq!(n1.apply_info.as_ref().unwrap().isolated_cpus, [1]);
+        let states: Vec<_> = status.workloads[0]
+            .tasks
+            .iter()
+            .map(|t| {
+                (
+                    t.name.as_str(),
+                    t.state.as_str(),
+                    t.apply.as_ref().map(|a| a.status()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            states,
+            [
+                ("attrs", "faulted", Some(A::InvalidAttributes)),
+                ("cg", "faulted", Some(A::CgroupError)),
+                ("cpu", "faulted", Some(A::InvalidCpu)),
+                ("dry", "applied", Some(A::DryRun)),
+                ("eperm", "faulted", Some(A::PermissionDenied)),
+                ("gone", "faulted", Some(A::PidNotFound)),
+                ("ok", "applied", Some(A::Applied)),
+                ("unknown", "delivered", Some(A::Unspecified)),
+            ]
+        );
+    }
+
+    // ── CPU hotplug ───────────────────────────────────────────────────────────
+
+    /// Both services sharing a one-node, three-CPU configuration, with `t1`
+    /// and `t2` scheduled and delivered.  Returns `t1`'s CPU last.
+    async fn hotplug_services(
+        repair: bool,
+    ) -> (
+        SchedInfoServiceImpl,
+        NodeServiceImpl,
+        Arc<MockFaultNotifier>,
+        u32,
+    ) {
+        let cfg = Arc::new(NodeConfigManager::from_nodes(vec![NodeConfig {
+            available_cpus: vec![0, 1, 2],
+            ..NodeConfig::default_config("n1")
+        }]));
+        let store = new_workload_store();
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            Arc::clone(&cfg),
+            Arc::clone(&store),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+        let mut node_svc = NodeServiceImpl::new(
+            store,
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+            Duration::from_secs(DEFAULT_SYNC_TIMEOUT_SECS),
+        )
+        .with_node_config(cfg);
+        if repair {
+            node_svc = node_svc.with_offline_cpu_repair(0.9);
+        }
+        let mut t1 = task_for("t1", "n1");
+        t1.metadata = [("trace_id".to_string(), "abc".to_string())].into();
+        submit(&svc, vec![t1, task_for("t2", "n1")]).await;
+        let resp = fetch(&node_svc, None).await;
+        let cpu = resp
+            .tasks
+            .iter()
+            .find(|t| t.name == "t1")
+            .unwrap()
+            .cpu_affinity;
+        (svc, node_svc, mock, cpu.trailing_zeros())
+    }
+
+    /// `GetSchedInfo` for n1 at generation 1, reporting `online`.
+    async fn report_online(node_svc: &NodeServiceImpl, online: Vec<u32>) -> NodeSchedResponse {
+        node_svc
+            .get_sched_info(Request::new(NodeSchedRequest {
+                node_id: "n1".into(),
+                known_generation: Some(1),
+                known_instance_epoch: Some(node_svc.instance_epoch()),
+                features: NodeFeatures::ALL.bits(),
+                online_cpus: Some(CpuSet { cpus: online }),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+    }
+
+    #[tokio::test]
+    async fn apply_report_acks_an_old_push_without_changing_anything() {
+        use crate::grpc::lifecycle::TaskState;
+        use crate::grpc::DEFAULT_TENANT;
+        use crate::proto::schedinfo_v1::{ApplyReport, ApplyStatus, PushStatus, TaskApplyResult};
+
+        let (svc, node_svc, mock) = test_services();
+        submit(&svc, vec![task_for("t1", "n1")]).await;
+        submit(&svc, vec![task_for("t1", "n1"), task_for("t2", "n1")]).await;
+        fetch(&node_svc, None).await;
+
+        let report = |generation, push: PushStatus, tasks: &[&str]| ApplyReport {
+            node_id: "n1".into(),
+            generation,
+            tasks: tasks
+                .iter()
+                .map(|&t| TaskApplyResult {
+                    task_name: t.into(),
+                    status: ApplyStatus::Applied as i32,
+                    ..Default::default()
+                })
+                .collect(),
+            node: None,
+            push_status: push as i32,
+        };
+        let send = |report: ApplyReport| {
+            let node_svc = &node_svc;
+            async move {
+                node_svc
+                    .report_apply(Request::new(report))
+                    .await
+                    .unwrap()
+                    .into_inner()
+            }
+        };
+
+        // Generation 2 is applied, then the retried push of 1 and a repeat
+        // of 2 are acknowledged by the node.
+        let acks = [
+            send(report(2, PushStatus::Applied, &["t1", "t2"])).await,
+            send(report(1, PushStatus::Stale, &[])).await,
+            send(report(2, PushStatus::Duplicate, &[])).await,
+        ];
+        assert!(acks.iter().all(|r| r.status == 0), "{acks:?}");
+        // A node that does not say is still refused an old generation.
+        let legacy = send(report(1, PushStatus::Applied, &["t1"])).await;
+        assert_eq!(legacy.status, -1);
+
+        assert!(mock.calls.lock().unwrap().is_empty());
+        let ws = &node_svc.workload_store.lock().await[DEFAULT_TENANT];
+        let kept = &ws.apply_reports["n1"];
+        assert_eq!((kept.generation, kept.tasks.len()), (2, 2));
+        assert_eq!(ws.task_states.get("n1", "t2"), Some(TaskState::Applied));
+    }
+    #[tokio::test]
+    async fn offline_cpu_tasks_move_to_another_cpu_of_the_node() {
+        let (_svc, node_svc, mock, cpu) = hotplug_services(true).await;
+        let online: Vec<u32> = (0..3).filter(|&c| c != cpu).collect();
+
+        let resp = report_online(&node_svc, online).await;
+        assert_eq!(resp.generation, 2);
+        assert!(!resp.full);
+        assert!(resp.removed_tasks.is_empty());
+        let moved: Vec<&str> = resp
+            .modified_tasks
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert!(moved.contains(&"t1"), "{moved:?}");
+        for t in &resp.modified_tasks {
+            assert_ne!(t.cpu_affinity, 1 << cpu, "{} still on CPU {cpu}", t.name);
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(mock.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn offline_cpu_without_repair_orphans_its_tasks() {
+        use crate::proto::schedinfo_v1::{ClusterStatusRequest, FaultType};
+
+        let (svc, node_svc, mock, cpu) = hotplug_services(false).await;
+        let online: Vec<u32> = (0..3).filter(|&c| c != cpu).collect();
+
+        let resp = report_online(&node_svc, online).await;
+        assert_eq!(resp.generation, 1);
+        assert!(resp.modified_tasks.is_empty());
+
+        for _ in 0..100 {
+            if !mock.calls.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let t1 = mock
+            .calls
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|c| c.task_name == "t1")
+            .cloned()
+            .expect("advisory for t1");
+        assert_eq!(t1.fault_type, FaultType::CpuOffline);
+        assert_eq!(t1.severity, FaultSeverity::Advisory);
+        assert_eq!(t1.node_id, "n1");
+        assert_eq!(t1.metadata["trace_id"], "abc");
+
+        let status = svc
+            .get_cluster_status(Request::new(ClusterStatusRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(status.nodes[0].offline_cpus, [cpu]);
+        assert_eq!(status.nodes[0].cpu_count, 2);
+        assert_eq!(status.orphaned.len(), 1);
+        assert_eq!(status.orphaned[0].cpu, Some(cpu));
+        assert!(status.orphaned[0].tasks.contains(&"t1".to_string()));
+
+        // The CPU comes back: nothing is orphaned any more.
+        report_online(&node_svc, vec![0, 1, 2]).await;
+        let status = svc
+            .get_cluster_status(Request::new(ClusterStatusRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(status.nodes[0].offline_cpus.is_empty());
+        assert!(status.orphaned.is_empty());
+    }
+
+    // ── Clock synchronisation ─────────────────────────────────────────────────
+
+    /// Both services sharing a one-node configuration, checking clocks with
+    /// `check`, and `tasks` scheduled on n1.
+    async fn clock_services(
+        check: ClockCheck,
+        tasks: Vec<TaskInfo>,
+    ) -> (NodeServiceImpl, Arc<MockFaultNotifier>) {
+        let cfg = Arc::new(NodeConfigManager::from_nodes(vec![
+            NodeConfig::default_config("n1"),
+        ]));
+        let store = new_workload_store();
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            Arc::clone(&cfg),
+            Arc::clone(&store),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+        let node_svc = NodeServiceImpl::new(
+            store,
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+            Duration::from_secs(DEFAULT_SYNC_TIMEOUT_SECS),
+        )
+        .with_node_config(cfg)
+        .with_clock_check(check, Duration::from_micros(100));
+        submit(&svc, tasks).await;
+        (node_svc, mock)
+    }
+
+    /// `GetSchedInfo` for n1, reporting its clock.
+    async fn fetch_with_clock(
+        node_svc: &NodeServiceImpl,
+        synchronized: bool,
+        offset_ns: i64,
+    ) -> Result<NodeSchedResponse, Status> {
+        node_svc
+            .get_sched_info(Request::new(NodeSchedRequest {
+                node_id: "n1".into(),
+                clock: Some(ClockSync {
+                    synchronized,
+                    offset_ns,
+                    source: "chrony".into(),
+                }),
+                ..Default::default()
+            }))
+            .await
+            .map(Response::into_inner)
+    }
+
+    fn with_offset(name: &str, release_time: i32) -> TaskInfo {
+        TaskInfo {
+            release_time,
+            ..task_for(name, "n1")
+        }
+    }
+
+    async fn clock_advisories(mock: &MockFaultNotifier) -> usize {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        mock.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| c.fault_type == FaultType::ClockUnsynchronized)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn strict_clock_check_refuses_offsets_to_an_unsynchronised_node() {
+        let tasks = vec![with_offset("t1", 500), task_for("t2", "n1")];
+        let (node_svc, mock) = clock_services(ClockCheck::Strict, tasks).await;
+
+        for _ in 0..2 {
+            let err = fetch_with_clock(&node_svc, false, 0).await.unwrap_err();
+            assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+            assert!(err
+                .message()
+                .contains("chrony reports the clock unsynchronised"));
+        }
+        let err = fetch_with_clock(&node_svc, true, 250_000)
+            .await
+            .unwrap_err();
+        assert!(err.message().contains("offset 250000 ns exceeds 100000 ns"));
+        assert_eq!(clock_advisories(&mock).await, 1);
+
+        let resp = fetch_with_clock(&node_svc, true, -80_000).await.unwrap();
+        assert_eq!(resp.tasks.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn clock_check_ignores_schedules_without_offsets() {
+        let tasks = vec![task_for("t1", "n1")];
+        let (node_svc, mock) = clock_services(ClockCheck::Strict, tasks).await;
+        let resp = fetch_with_clock(&node_svc, false, 0).await.unwrap();
+        assert_eq!(resp.tasks.len(), 1);
+        assert_eq!(clock_advisories(&mock).await, 0);
+    }
+
+    #[tokio::test]
+    async fn warn_clock_check_serves_with_an_advisory() {
+        let tasks = vec![with_offset("t1", 500)];
+        let (node_svc, mock) = clock_services(ClockCheck::Warn, tasks).await;
+        let resp = fetch_with_clock(&node_svc, false, 0).await.unwrap();
+        assert_eq!(resp.tasks.len(), 1);
+        assert_eq!(clock_advisories(&mock).await, 1);
+    }
+
+    // ── Foreign load ──────────────────────────────────────────────────────────
+
+    /// `GetSchedInfo` for n1 at generation 1, reporting `load` per CPU.
+    async fn report_foreign(node_svc: &NodeServiceImpl, load: &[(u32, f64)]) {
+        use crate::proto::schedinfo_v1::{ForeignLoad, ForeignLoadReport};
+
+        let cpus = load
+            .iter()
+            .map(|&(cpu, utilization)| ForeignLoad {
+                cpu,
+                utilization,
+                threads: vec!["agent/77".into()],
+            })
+            .collect();
+        node_svc
+            .get_sched_info(Request::new(NodeSchedRequest {
+                node_id: "n1".into(),
+                known_generation: Some(1),
+                foreign_load: Some(ForeignLoadReport { cpus }),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+    }
+
+    async fn foreign_advisories(mock: &MockFaultNotifier) -> Vec<FaultNotification> {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        mock.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| c.fault_type == FaultType::ForeignLoad)
+            .cloned()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn foreign_load_past_the_threshold_raises_one_advisory_per_crossing() {
+        // `cpu` holds t1, and maybe t2: 0.1 or 0.2 placed.
+        let (_svc, node_svc, mock, cpu) = hotplug_services(false).await;
+        let other = (0..3).find(|&c| c != cpu).unwrap();
+
+        report_foreign(&node_svc, &[(cpu, 0.65), (other, 0.5)]).await;
+        assert!(foreign_advisories(&mock).await.is_empty());
+
+        report_foreign(&node_svc, &[(cpu, 0.85), (other, 0.5)]).await;
+        let calls = foreign_advisories(&mock).await;
+        assert_eq!(calls.len(), 1, "{calls:?}");
+        assert_eq!(calls[0].severity, FaultSeverity::Advisory);
+        assert_eq!(calls[0].node_id, "n1");
+        let detail = &calls[0].metadata[FOREIGN_LOAD_METADATA_KEY];
+        let expected = format!("cpu {cpu}: foreign 0.85 (agent/77), placed 0.");
+        assert!(detail.starts_with(&expected), "{detail}");
+
+        // Still past it: no repeat.  Back under and past again: a new one.
+        report_foreign(&node_svc, &[(cpu, 0.9)]).await;
+        assert_eq!(foreign_advisories(&mock).await.len(), 1);
+        report_foreign(&node_svc, &[]).await;
+        report_foreign(&node_svc, &[(cpu, 0.9)]).await;
+        assert_eq!(foreign_advisories(&mock).await.len(), 2);
+    }
+
+    // ── Time partitions ───────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn time_partitions_are_pushed_with_every_answer() {
+        use crate::config::{TimePartitions, TimeWindow};
+
+        let window = |offset_us, duration_us| TimeWindow {
+            offset_us,
+            duration_us,
+        };
+        let partitions =
+            TimePartitions::new(10_000, vec![window(5_000, 2_000), window(0, 3_000)]).unwrap();
+        let cfg = Arc::new(NodeConfigManager::from_nodes(vec![
+            NodeConfig {
+                time_partitions: Some(partitions),
+                ..NodeConfig::default_config("n1")
+            },
+            NodeConfig::default_config("n2"),
+        ]));
+        let store = new_workload_store();
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            Arc::clone(&cfg),
+            Arc::clone(&store),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+        let node_svc = NodeServiceImpl::new(
+            store,
+            mock as Arc<dyn FaultNotifier>,
+            Duration::from_secs(DEFAULT_SYNC_TIMEOUT_SECS),
+        )
+        .with_node_config(cfg);
+        submit(&svc, vec![task_for("t1", "n1"), task_for("t2", "n2")]).await;
+
+        let expected = [
+            TimePartition {
+                offset_us: 0,
+                duration_us: 3_000,
+            },
+            TimePartition {
+                offset_us: 5_000,
+                duration_us: 2_000,
+            },
+        ];
+        let full = fetch(&node_svc, None).await;
+        assert!(full.full);
+        assert_eq!(full.major_frame_us, 10_000);
+        assert_eq!(full.time_partitions, expected);
+        let delta = fetch(&node_svc, Some(1)).await;
+        assert!(!delta.full);
+        assert_eq!(delta.time_partitions, expected);
+
+        let n2 = fetch_node(&node_svc, "n2", 0).await;
+        assert_eq!(n2.major_frame_us, 0);
+        assert!(n2.time_partitions.is_empty());
+    }
+
+    // ── Metadata ──────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn metadata_reaches_node_status_and_faults() {
+        use crate::proto::schedinfo_v1::ClusterStatusRequest;
+
+        let (svc, node_svc, mock) = test_services();
+        let mut task = task_for("t1", "n1");
+        task.metadata = [("trace_id", "abc"), ("image", "cam:1")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let all = task.metadata.clone();
+        submit(&svc, vec![task]).await;
+
+        let resp = fetch(&node_svc, None).await;
+        assert_eq!(resp.tasks[0].metadata, all);
+
+        let status = svc
+            .get_cluster_status(Request::new(ClusterStatusRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(status.workloads[0].tasks[0].metadata, all);
+
+        node_svc
+            .report_d_miss(Request::new(DeadlineMissInfo {
+                node_id: "n1".into(),
+                task_name: "t1".into(),
+                drift: None,
+            }))
+            .await
+            .unwrap();
+        let calls = mock.calls.lock().unwrap();
+        assert_eq!(
+            calls[0].metadata,
+            [("trace_id".to_string(), "abc".to_string())].into()
+        );
+    }
+
+    // ── to_proto_task ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn to_proto_task_converts_ns_to_us_and_saturates() {
+        let st = SchedTask {
+            name: "t".into(),
+            assigned_node: "n1".into(),
+            assigned_cpu: 2,
+            policy: SchedPolicy::Fifo,
+            priority: 10,
+            period_ns: Nanos(10_000_999), // sub-µs remainder truncated
+            runtime_ns: Nanos(1_000_000),
+            deadline_ns: Nanos(u64::MAX), // does not fit in int32 µs
+            release_time_us: 0,
+            max_dmiss: 0,
+            shared_resources: Vec::new(),
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        };
+        let p = to_proto_task(&st);
+        assert_eq!(p.period_us, 10_000);
+        assert_eq!(p.runtime_us, 1_000);
+        assert_eq!(p.deadline_us, i32::MAX);
+        assert_eq!(p.cpu_affinity, 1 << 2);
+    }
 }
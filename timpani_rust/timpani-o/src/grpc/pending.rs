@@ -0,0 +1,244 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Bounded queue of workloads waiting for cluster capacity.
+//!
+//! An `AddSchedInfo` with `queue_if_full` whose tasks would fit on an empty
+//! cluster, but not around the other tenants' active workloads, is parked
+//! here instead of being rejected.  Whenever capacity is released the
+//! service retries the queue in order:
+//!
+//! 1. higher [`PriorityClass`] first;
+//! 2. then higher `importance`;
+//! 3. then earlier arrival.
+//!
+//! Like the workload store, the queue holds at most one workload per tenant
+//! — a newer submission from the same tenant replaces the queued one.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+use crate::proto::schedinfo_v1::SchedInfo;
+use crate::scheduler::PriorityClass;
+
+/// Queue bound used when none is configured.
+pub const DEFAULT_PENDING_CAPACITY: usize = 16;
+
+/// Returned by [`PendingQueue::push`] when the queue is at capacity.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("pending queue is full ({capacity} workload(s))")]
+pub struct QueueFull {
+    pub capacity: usize,
+}
+
+// ── PendingWorkload ───────────────────────────────────────────────────────────
+
+/// A parked `AddSchedInfo` request.
+#[derive(Debug, Clone)]
+pub struct PendingWorkload {
+    pub tenant: String,
+    pub request: SchedInfo,
+    pub enqueued_at: Instant,
+    /// Arrival order (tie-break after class and importance).
+    seq: u64,
+}
+
+impl PendingWorkload {
+    pub fn workload_id(&self) -> &str {
+        &self.request.workload_id
+    }
+
+    pub fn importance(&self) -> i32 {
+        self.request.importance
+    }
+
+    /// The request's priority class.  Requests are validated before they
+    /// are queued, so an unparsable class does not occur here.
+    pub fn priority_class(&self) -> PriorityClass {
+        PriorityClass::from_proto(self.request.priority_class.as_deref()).unwrap_or_default()
+    }
+
+    /// Time spent in the queue so far.
+    pub fn age(&self) -> Duration {
+        self.enqueued_at.elapsed()
+    }
+
+    fn order_key(&self) -> (Reverse<PriorityClass>, Reverse<i32>, u64) {
+        (
+            Reverse(self.priority_class()),
+            Reverse(self.importance()),
+            self.seq,
+        )
+    }
+}
+
+// ── PendingQueue ──────────────────────────────────────────────────────────────
+
+/// Class-, importance-, then arrival-ordered bounded queue (see the module docs).
+#[derive(Debug)]
+pub struct PendingQueue {
+    /// Kept sorted by [`PendingWorkload::order_key`].
+    entries: Vec<PendingWorkload>,
+    capacity: usize,
+    next_seq: u64,
+}
+
+impl Default for PendingQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_PENDING_CAPACITY)
+    }
+}
+
+impl PendingQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+            next_seq: 0,
+        }
+    }
+
+    /// Most workloads the queue holds; `0` refuses every `queue_if_full`.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Park `request` for `tenant`, replacing the tenant's queued workload if
+    /// any.  A replacement never overflows the queue.
+    pub fn push(&mut self, tenant: &str, request: SchedInfo) -> Result<(), QueueFull> {
+        let replaced = self.remove_tenant(tenant).is_some();
+        if !replaced && self.entries.len() >= self.capacity {
+            return Err(QueueFull {
+                capacity: self.capacity,
+            });
+        }
+        let entry = PendingWorkload {
+            tenant: tenant.to_string(),
+            request,
+            enqueued_at: Instant::now(),
+            seq: self.next_seq,
+        };
+        self.next_seq += 1;
+        let at = self
+            .entries
+            .partition_point(|e| e.order_key() <= entry.order_key());
+        self.entries.insert(at, entry);
+        Ok(())
+    }
+
+    /// Remove and return `tenant`'s queued workload.
+    pub fn remove_tenant(&mut self, tenant: &str) -> Option<PendingWorkload> {
+        let idx = self.entries.iter().position(|e| e.tenant == tenant)?;
+        Some(self.entries.remove(idx))
+    }
+
+    /// `tenant`'s queued workload, if any.
+    pub fn get(&self, tenant: &str) -> Option<&PendingWorkload> {
+        self.entries.iter().find(|e| e.tenant == tenant)
+    }
+
+    /// Queued workloads in retry order.
+    pub fn iter(&self) -> impl Iterator<Item = &PendingWorkload> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Queued workloads per priority class; classes with none are absent.
+    pub fn depth_per_class(&self) -> BTreeMap<PriorityClass, usize> {
+        let mut depth = BTreeMap::new();
+        for e in &self.entries {
+            *depth.entry(e.priority_class()).or_default() += 1;
+        }
+        depth
+    }
+
+    /// Age of the longest-waiting entry (`None` when empty).
+    pub fn oldest_age(&self) -> Option<Duration> {
+        self.entries.iter().map(PendingWorkload::age).max()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(workload_id: &str, importance: i32) -> SchedInfo {
+        SchedInfo {
+            workload_id: workload_id.into(),
+            importance,
+            ..Default::default()
+        }
+    }
+
+    fn order(q: &PendingQueue) -> Vec<&str> {
+        q.iter().map(PendingWorkload::workload_id).collect()
+    }
+
+    #[test]
+    fn ordered_by_importance_then_arrival() {
+        let mut q = PendingQueue::new(8);
+        q.push("a", req("low", 0)).unwrap();
+        q.push("b", req("high", 5)).unwrap();
+        q.push("c", req("low2", 0)).unwrap();
+        q.push("d", req("high2", 5)).unwrap();
+        assert_eq!(order(&q), ["high", "high2", "low", "low2"]);
+    }
+
+    #[test]
+    fn class_outranks_importance() {
+        let mut q = PendingQueue::new(8);
+        let classed = |id: &str, importance, class: &str| SchedInfo {
+            priority_class: Some(class.into()),
+            ..req(id, importance)
+        };
+        q.push("a", classed("be", 9, "best_effort")).unwrap();
+        q.push("b", req("plat", 0)).unwrap();
+        q.push("c", classed("safe", -5, "safety")).unwrap();
+        q.push("d", classed("plat_hi", 3, "platform")).unwrap();
+        assert_eq!(order(&q), ["safe", "plat_hi", "plat", "be"]);
+        assert_eq!(
+            q.depth_per_class(),
+            BTreeMap::from([
+                (PriorityClass::BestEffort, 1),
+                (PriorityClass::Platform, 2),
+                (PriorityClass::Safety, 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn same_tenant_replaces_and_goes_to_the_back_of_its_class() {
+        let mut q = PendingQueue::new(2);
+        q.push("a", req("a1", 0)).unwrap();
+        q.push("b", req("b1", 0)).unwrap();
+        // Full, but a replacement is allowed.
+        q.push("a", req("a2", 0)).unwrap();
+        assert_eq!(order(&q), ["b1", "a2"]);
+        assert_eq!(q.get("a").unwrap().workload_id(), "a2");
+    }
+
+    #[test]
+    fn overflow_is_rejected() {
+        let mut q = PendingQueue::new(1);
+        q.push("a", req("a1", 0)).unwrap();
+        assert_eq!(q.push("b", req("b1", 9)), Err(QueueFull { capacity: 1 }));
+        assert_eq!(q.len(), 1);
+        assert!(q.remove_tenant("a").is_some());
+        assert!(q.is_empty());
+        assert_eq!(q.oldest_age(), None);
+    }
+}
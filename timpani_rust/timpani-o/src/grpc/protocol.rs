@@ -0,0 +1,143 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Protocol negotiation: serve every Timpani-N build what it understands.
+//!
+//! Timpani-N builds in the field are not upgraded together with Timpani-O.
+//! A node announces the [`NodeFeature`]s it understands in
+//! `NodeSchedRequest.features` with every `GetSchedInfo` /
+//! `StreamSchedInfo` call, and the answer is downgraded to them:
+//!
+//! | Not announced | Answer                                                        |
+//! |---------------|---------------------------------------------------------------|
+//! | `DELTA`       | always the full task list (`full = true`)                     |
+//! | `PREPARE`     | never `prepare = true`; the node is left out of an open transaction and is served the generation the other nodes run |
+//! | `PLACEHOLDER` | placeholder tasks left out, with a warning                    |
+//!
+//! A node that announces nothing — every build older than the field — gets
+//! all three.  What a node announced at its last call is kept in
+//! [`WorkloadState::protocols`](super::WorkloadState::protocols) and shown
+//! as `NodeStatus.protocol`.
+
+use std::fmt;
+
+use crate::proto::schedinfo_v1::{NodeFeature, NodeSchedResponse};
+
+/// Every feature, in bit order.
+const FEATURES: [NodeFeature; 3] = [
+    NodeFeature::Delta,
+    NodeFeature::Prepare,
+    NodeFeature::Placeholder,
+];
+
+/// The [`NodeFeature`] bits a node announced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeFeatures(u32);
+
+impl NodeFeatures {
+    /// What a node that announces nothing is assumed to understand.
+    pub const LEGACY: Self = Self(0);
+
+    /// Everything this Timpani-O can serve.
+    pub const ALL: Self = Self(
+        NodeFeature::Delta as u32 | NodeFeature::Prepare as u32 | NodeFeature::Placeholder as u32,
+    );
+
+    /// `bits` as announced; bits this Timpani-O does not know are dropped.
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub fn supports(self, feature: NodeFeature) -> bool {
+        self.0 & feature as u32 != 0
+    }
+
+    /// These features plus `feature`.
+    pub fn with(self, feature: NodeFeature) -> Self {
+        Self(self.0 | feature as u32)
+    }
+}
+
+impl fmt::Display for NodeFeatures {
+    /// `delta,prepare,placeholder`, or `legacy` for none.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == Self::LEGACY {
+            return f.write_str("legacy");
+        }
+        let names: Vec<&str> = FEATURES
+            .into_iter()
+            .filter(|&feature| self.supports(feature))
+            .map(feature_name)
+            .collect();
+        f.write_str(&names.join(","))
+    }
+}
+
+fn feature_name(feature: NodeFeature) -> &'static str {
+    match feature {
+        NodeFeature::None => "none",
+        NodeFeature::Delta => "delta",
+        NodeFeature::Prepare => "prepare",
+        NodeFeature::Placeholder => "placeholder",
+    }
+}
+
+/// Remove placeholder tasks from `resp`, for a node without
+/// `PLACEHOLDER`.  Returns their names.
+pub(crate) fn strip_placeholders(resp: &mut NodeSchedResponse) -> Vec<String> {
+    let mut stripped = Vec::new();
+    for tasks in [&mut resp.tasks, &mut resp.modified_tasks] {
+        tasks.retain(|t| {
+            if t.placeholder {
+                stripped.push(t.name.clone());
+            }
+            !t.placeholder
+        });
+    }
+    stripped
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::schedinfo_v1::ScheduledTask;
+
+    #[test]
+    fn unknown_bits_are_dropped_and_names_follow_bit_order() {
+        assert_eq!(NodeFeatures::from_bits(0).to_string(), "legacy");
+        assert_eq!(NodeFeatures::from_bits(u32::MAX), NodeFeatures::ALL);
+        assert_eq!(NodeFeatures::ALL.to_string(), "delta,prepare,placeholder");
+        let delta = NodeFeatures::LEGACY.with(NodeFeature::Delta);
+        assert!(delta.supports(NodeFeature::Delta));
+        assert!(!delta.supports(NodeFeature::Prepare));
+        assert_eq!(
+            delta.with(NodeFeature::Placeholder).to_string(),
+            "delta,placeholder"
+        );
+    }
+
+    #[test]
+    fn placeholders_are_stripped_from_added_and_modified_tasks() {
+        let task = |name: &str, placeholder| ScheduledTask {
+            name: name.into(),
+            placeholder,
+            ..Default::default()
+        };
+        let mut resp = NodeSchedResponse {
+            tasks: vec![task("a", false), task("spare", true)],
+            modified_tasks: vec![task("b", false), task("spare2", true)],
+            ..Default::default()
+        };
+        assert_eq!(strip_placeholders(&mut resp), ["spare", "spare2"]);
+        assert_eq!(resp.tasks.len(), 1);
+        assert_eq!(resp.modified_tasks[0].name, "b");
+    }
+}
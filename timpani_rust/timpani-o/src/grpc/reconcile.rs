@@ -0,0 +1,232 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Reconciling nodes with a freshly started Timpani-O.
+//!
+//! Only the node configuration outlives a restart: workloads, generations
+//! and the delivery ledger start empty until Pullpiri resubmits, and
+//! generations are numbered from 1 again.  Every instance therefore has an
+//! *instance epoch* ([`new_instance_epoch`]), its start time in nanoseconds,
+//! sent with every answer.  A node's `known_generation` only means
+//! something together with the epoch it came from; a node whose
+//! `known_instance_epoch` is not this instance's gets a full push marked
+//! `resync`, never a delta or an empty answer.
+//!
+//! Nodes poll, so they are reconciled as they call
+//! ([`Reconciler::observe`]): until a node takes this instance's schedule,
+//! the task names it reports (`known_tasks`) are compared with what the
+//! ledger places on it.  When the window after startup closes
+//! ([`Reconciler::finish_at`]) the owner gets one [`ReconcileReport`]:
+//!
+//! | Node                                       | Report        | Action                         |
+//! |--------------------------------------------|---------------|--------------------------------|
+//! | ran the tasks the ledger places on it      | `in_sync`     | full push (same tasks)         |
+//! | ran other tasks, or none                   | `diverged`    | full push                      |
+//! | configured or placed, but never called     | `unreachable` | undelivered tasks `DEGRADED`   |
+//!
+//! In-sync nodes are re-pushed too: names cannot show changed parameters,
+//! and a full push of an unchanged schedule changes nothing on the node.
+//!
+//! Nothing runs on its own: the owner polls and acts on the report (see
+//! [`NodeServiceImpl::finish_reconciliation`](super::node_service::NodeServiceImpl::finish_reconciliation)).
+//! Methods that read the clock have an `_at` form taking the current time,
+//! so tests drive the clock themselves.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use tracing::info;
+
+use crate::proto::schedinfo_v1::ReconcileReport;
+
+/// Time nodes have after startup to call before they are reported
+/// unreachable.
+pub const DEFAULT_RECONCILE_WINDOW_SECS: u64 = 30;
+
+/// How often `timpani-o` polls for the end of the window.
+pub const RECONCILE_TICK: Duration = Duration::from_secs(1);
+
+/// The last epoch issued in this process.
+static LAST_INSTANCE_EPOCH: AtomicU64 = AtomicU64::new(0);
+
+/// A new instance epoch: the current time in nanoseconds since the Unix
+/// epoch, and above every epoch issued before in this process.
+pub fn new_instance_epoch() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    let last = LAST_INSTANCE_EPOCH
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+            Some(now.max(last + 1))
+        })
+        .unwrap_or_default();
+    now.max(last + 1)
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Node → whether its tasks matched the ledger when it last called.
+    observed: BTreeMap<String, bool>,
+    reported: bool,
+}
+
+/// This instance's epoch and the nodes heard from since startup (see the
+/// module docs).
+///
+/// Thread-safe; the internal lock is only held for map updates.
+#[derive(Debug)]
+pub struct Reconciler {
+    instance_epoch: u64,
+    due: Instant,
+    state: Mutex<State>,
+}
+
+impl Reconciler {
+    /// A new instance whose window closes `window` from now.
+    pub fn new(window: Duration) -> Self {
+        Self::new_at(window, Instant::now())
+    }
+
+    /// [`new`](Self::new) at `now`.
+    pub fn new_at(window: Duration, now: Instant) -> Self {
+        Self {
+            instance_epoch: new_instance_epoch(),
+            due: now + window,
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn instance_epoch(&self) -> u64 {
+        self.instance_epoch
+    }
+
+    /// `known_instance_epoch` names this instance: the node's
+    /// `known_generation` was numbered here.
+    pub fn is_current(&self, known_instance_epoch: Option<u64>) -> bool {
+        known_instance_epoch == Some(self.instance_epoch)
+    }
+
+    /// `node` called with `known_tasks` while the ledger places `placed`
+    /// on it.  Nodes already on this instance's schedule, and calls after
+    /// the report, change nothing.
+    pub fn observe<'a>(
+        &self,
+        node: &str,
+        known_instance_epoch: Option<u64>,
+        known_tasks: &[String],
+        placed: impl IntoIterator<Item = &'a str>,
+    ) {
+        if self.is_current(known_instance_epoch) {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        if state.reported {
+            return;
+        }
+        let known: BTreeSet<&str> = known_tasks.iter().map(String::as_str).collect();
+        let in_sync = known == placed.into_iter().collect();
+        if state.observed.insert(node.to_string(), in_sync) != Some(in_sync) {
+            info!(
+                node           = %node,
+                instance_epoch = self.instance_epoch,
+                known_epoch    = ?known_instance_epoch,
+                in_sync,
+                "node reconciled against the ledger"
+            );
+        }
+    }
+
+    /// The report, once the window has closed at `now`; `None` before and
+    /// after.  `nodes` are those expected to call (configured or placed);
+    /// the ones never observed are unreachable.
+    pub fn finish_at(
+        &self,
+        now: Instant,
+        nodes: impl IntoIterator<Item = String>,
+    ) -> Option<ReconcileReport> {
+        if now < self.due {
+            return None;
+        }
+        let mut state = self.state.lock().unwrap();
+        if std::mem::replace(&mut state.reported, true) {
+            return None;
+        }
+        let mut report = ReconcileReport {
+            instance_epoch: self.instance_epoch,
+            ..Default::default()
+        };
+        for (node, &in_sync) in &state.observed {
+            if in_sync {
+                report.in_sync.push(node.clone());
+            } else {
+                report.diverged.push(node.clone());
+            }
+        }
+        let unreachable: BTreeSet<String> = nodes
+            .into_iter()
+            .filter(|n| !state.observed.contains_key(n))
+            .collect();
+        report.unreachable = unreachable.into_iter().collect();
+        Some(report)
+    }
+
+    /// The report has been produced.
+    pub fn is_finished(&self) -> bool {
+        self.state.lock().unwrap().reported
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(tasks: &[&str]) -> Vec<String> {
+        tasks.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn instance_epochs_only_increase() {
+        let a = Reconciler::new(Duration::ZERO);
+        let b = Reconciler::new(Duration::ZERO);
+        assert!(b.instance_epoch() > a.instance_epoch());
+        assert!(b.is_current(Some(b.instance_epoch())));
+        assert!(!b.is_current(Some(a.instance_epoch())));
+        assert!(!b.is_current(None));
+    }
+
+    #[test]
+    fn nodes_are_classified_by_their_last_call_before_the_resync() {
+        let t0 = Instant::now();
+        let rec = Reconciler::new_at(Duration::from_secs(30), t0);
+        let old = Some(rec.instance_epoch() - 1);
+
+        // n1 calls before Pullpiri resubmitted (nothing placed), then after.
+        rec.observe("n1", old, &names(&["cam"]), []);
+        rec.observe("n1", old, &names(&["cam"]), ["cam"]);
+        // n2 runs a task the ledger no longer places; then takes the resync.
+        rec.observe("n2", old, &names(&["cam", "old"]), ["cam"]);
+        rec.observe("n2", Some(rec.instance_epoch()), &names(&["cam"]), ["cam"]);
+        // n3 never ran anything.
+        rec.observe("n3", None, &[], ["lidar"]);
+
+        let nodes = || names(&["n1", "n2", "n3", "n4"]);
+        assert_eq!(rec.finish_at(t0 + Duration::from_secs(29), nodes()), None);
+        let report = rec
+            .finish_at(t0 + Duration::from_secs(30), nodes())
+            .unwrap();
+        assert_eq!(report.instance_epoch, rec.instance_epoch());
+        assert_eq!(report.in_sync, ["n1"]);
+        assert_eq!(report.diverged, ["n2", "n3"]);
+        assert_eq!(report.unreachable, ["n4"]);
+
+        // Once only.
+        assert!(rec.is_finished());
+        assert_eq!(rec.finish_at(t0 + Duration::from_secs(60), nodes()), None);
+    }
+}
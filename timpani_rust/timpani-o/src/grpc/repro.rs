@@ -0,0 +1,547 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Repro bundles: the exact inputs of one admission, for bug reports.
+//!
+//! Every admitted workload keeps a [`ReproBundle`] of what its scheduling run
+//! saw; [`SchedInfoServiceImpl::capture_repro`] hands out a redacted copy and
+//! `timpani-o repro run <bundle>` replays it offline:
+//!
+//! | Field       | Contents                                                        |
+//! |-------------|-----------------------------------------------------------------|
+//! | `version`   | crate version of the instance that admitted the workload        |
+//! | `nodes`     | node configuration, cordons, RT capability and offline CPUs     |
+//! | `request`   | the `AddSchedInfo` request as submitted                         |
+//! | `settings`  | the scheduling options of the run, request overrides applied    |
+//! | `occupied`  | the other tenants' placements the workload was scheduled around |
+//! | `placement` | the schedule the run produced                                   |
+//!
+//! Bundles are written with [`codec`](crate::codec), so JSON or CBOR.  A
+//! replay runs the request through `tasks_from_proto` and
+//! `GlobalScheduler::schedule_with_occupancy`, as admission does, and diffs
+//! the result against `placement`.
+//!
+//! # Redaction
+//!
+//! `--repro-sensitive-key` names label keys whose values are replaced by
+//! [`REDACTED`]: task metadata keys (in `request`, `occupied` and
+//! `placement`) and the node fields `architecture`, `location` and
+//! `description`.  A sensitive `endpoint` is dropped.  Redacting `location`
+//! changes what a proximity table sees, so such a replay may differ.  Live
+//! memory reports are not captured.
+//!
+//! [`SchedInfoServiceImpl::capture_repro`]: super::schedinfo_service::SchedInfoServiceImpl::capture_repro
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::schedinfo_service::tasks_from_proto;
+use crate::config::{ConfigError, NodeConfigManager, TimeWindow};
+use crate::limits::InputLimits;
+use crate::proto::schedinfo_v1::SchedInfo;
+use crate::report::ScheduleDiff;
+use crate::scheduler::{
+    AdmissionOverride, GlobalScheduler, MarginAnalysis, PriorityOrdering, ProximityTable,
+    SchedAlgorithm, ScheduleOptions, SchedulerError, SimulationCheck, StaggerStrategy,
+};
+use crate::task::{Micros, Nanos, NodeSchedMap};
+
+/// What a sensitive label's value is replaced with.
+pub const REDACTED: &str = "<redacted>";
+
+// ── Errors ────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ReproError {
+    #[error("bundle node configuration: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("bundle node configuration: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("bundle setting {setting} has an invalid value '{value}'")]
+    InvalidSetting {
+        setting: &'static str,
+        value: String,
+    },
+}
+
+// ── Bundle contents ───────────────────────────────────────────────────────────
+
+/// A configured node as the scheduler saw it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReproNode {
+    pub name: String,
+    pub available_cpus: Vec<u32>,
+    pub reserved_cpus: Vec<u32>,
+    pub max_memory_mb: u64,
+    pub architecture: String,
+    pub location: String,
+    pub description: String,
+    pub endpoint: Option<String>,
+    pub max_workloads: Option<usize>,
+    pub enabled: bool,
+    /// Absent from bundles of nodes without time partitions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub major_frame_us: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub time_partitions: Vec<TimeWindow>,
+    /// Absent from bundles of nodes without a CPU topology.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cpu_clusters: Vec<Vec<u32>>,
+    pub cordoned: bool,
+    pub rt_capable: bool,
+    pub offline_cpus: Vec<u32>,
+}
+
+/// [`ScheduleOptions`] in serialisable form.  Enums are kept as their string
+/// forms; the log policy and the chains do not affect placement and are
+/// left out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReproSettings {
+    pub algorithm: String,
+    pub cpu_utilization_threshold: f64,
+    pub seed: u64,
+    pub utilization_epsilon: f64,
+    pub allowed_nodes: Option<BTreeSet<String>>,
+    pub reserve_pinned_cpus: bool,
+    pub release_stagger: Option<String>,
+    pub verify_with_simulation: Option<String>,
+    pub simulation_hyperperiod_limit_us: u64,
+    pub simulation_max_jobs: u64,
+    pub max_task_duration_ns: u64,
+    pub admission_overrides: Vec<String>,
+    pub proximity: Option<ProximityTable>,
+    pub margin_analysis: String,
+    pub priority_ordering: String,
+    /// Absent from bundles captured before the caps moved into the options;
+    /// those replay with the defaults.
+    #[serde(default)]
+    pub limits: InputLimits,
+}
+
+impl From<&ScheduleOptions> for ReproSettings {
+    fn from(opts: &ScheduleOptions) -> Self {
+        Self {
+            algorithm: opts.algorithm.as_str().to_string(),
+            cpu_utilization_threshold: opts.cpu_utilization_threshold,
+            seed: opts.seed,
+            utilization_epsilon: opts.utilization_epsilon,
+            allowed_nodes: opts.allowed_nodes.clone(),
+            reserve_pinned_cpus: opts.reserve_pinned_cpus,
+            release_stagger: opts.release_stagger.map(|s| s.as_str().to_string()),
+            verify_with_simulation: opts.verify_with_simulation.map(|c| c.as_str().to_string()),
+            simulation_hyperperiod_limit_us: opts.simulation_hyperperiod_limit.as_u64(),
+            simulation_max_jobs: opts.simulation_max_jobs,
+            max_task_duration_ns: opts.max_task_duration.as_u64(),
+            admission_overrides: opts
+                .admission_overrides
+                .iter()
+                .map(|o| o.as_str().to_string())
+                .collect(),
+            proximity: opts.proximity.as_deref().cloned(),
+            margin_analysis: opts.margin_analysis.as_str().to_string(),
+            priority_ordering: opts.priority_ordering.as_str().to_string(),
+            limits: opts.limits,
+        }
+    }
+}
+
+impl ReproSettings {
+    /// The options these settings were taken from.
+    pub fn to_options(&self) -> Result<ScheduleOptions, ReproError> {
+        fn parse<T: std::str::FromStr>(
+            setting: &'static str,
+            value: &str,
+        ) -> Result<T, ReproError> {
+            value.parse().map_err(|_| ReproError::InvalidSetting {
+                setting,
+                value: value.to_string(),
+            })
+        }
+
+        let mut opts = ScheduleOptions::default()
+            .with_algorithm(parse::<SchedAlgorithm>("algorithm", &self.algorithm)?)
+            .with_cpu_utilization_threshold(self.cpu_utilization_threshold)
+            .with_seed(self.seed)
+            .with_utilization_epsilon(self.utilization_epsilon)
+            .with_reserve_pinned_cpus(self.reserve_pinned_cpus)
+            .with_margin_analysis(parse::<MarginAnalysis>(
+                "margin_analysis",
+                &self.margin_analysis,
+            )?)
+            .with_priority_ordering(parse::<PriorityOrdering>(
+                "priority_ordering",
+                &self.priority_ordering,
+            )?);
+        opts.allowed_nodes = self.allowed_nodes.clone();
+        opts.release_stagger = self
+            .release_stagger
+            .as_deref()
+            .map(|s| parse::<StaggerStrategy>("release_stagger", s))
+            .transpose()?;
+        opts.verify_with_simulation = self
+            .verify_with_simulation
+            .as_deref()
+            .map(|s| parse::<SimulationCheck>("verify_with_simulation", s))
+            .transpose()?;
+        opts.simulation_hyperperiod_limit = Micros(self.simulation_hyperperiod_limit_us);
+        opts.simulation_max_jobs = self.simulation_max_jobs;
+        opts.max_task_duration = Nanos(self.max_task_duration_ns);
+        for o in &self.admission_overrides {
+            opts.admission_overrides
+                .insert(parse::<AdmissionOverride>("admission_overrides", o)?);
+        }
+        opts.proximity = self.proximity.clone().map(Arc::new);
+        opts.limits = self.limits;
+        Ok(opts)
+    }
+}
+
+// ── ReproBundle ───────────────────────────────────────────────────────────────
+
+/// The inputs and result of one admission (see the module docs).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReproBundle {
+    pub version: String,
+    pub tenant: String,
+    /// Sorted by name.
+    pub nodes: Vec<ReproNode>,
+    pub request: SchedInfo,
+    pub settings: ReproSettings,
+    pub occupied: NodeSchedMap,
+    pub placement: NodeSchedMap,
+    /// Sensitive keys applied by [`redacted`](Self::redacted), sorted.
+    pub redacted_keys: Vec<String>,
+}
+
+/// What [`ReproBundle::replay`] found.
+#[derive(Debug)]
+pub enum ReproOutcome {
+    /// The replay produced the recorded placement.
+    Match,
+    /// The replay placed the workload differently; recorded → replayed.
+    Mismatch(ScheduleDiff),
+    /// The replay failed to place the workload at all.
+    Failed(SchedulerError),
+}
+
+impl ReproBundle {
+    /// Record an admission of `request` for `tenant` with `opts` against
+    /// `config` around `occupied`, which produced `placement`.
+    pub fn capture(
+        tenant: &str,
+        request: &SchedInfo,
+        opts: &ScheduleOptions,
+        config: &NodeConfigManager,
+        occupied: NodeSchedMap,
+        placement: NodeSchedMap,
+    ) -> Self {
+        let mut nodes: Vec<ReproNode> = config
+            .get_all_nodes()
+            .values()
+            .map(|n| ReproNode {
+                name: n.name.clone(),
+                available_cpus: n.available_cpus.clone(),
+                reserved_cpus: n.reserved_cpus.clone(),
+                max_memory_mb: n.max_memory_mb,
+                architecture: n.architecture.clone(),
+                location: n.location.clone(),
+                description: n.description.clone(),
+                endpoint: n.endpoint.clone(),
+                max_workloads: n.max_workloads,
+                enabled: n.enabled,
+                major_frame_us: n.time_partitions.as_ref().map(|p| p.major_frame_us),
+                time_partitions: n
+                    .time_partitions
+                    .as_ref()
+                    .map(|p| p.windows.clone())
+                    .unwrap_or_default(),
+                cpu_clusters: n.cpu_clusters.clone(),
+                cordoned: config.is_cordoned(&n.name),
+                rt_capable: config.is_rt_capable(&n.name),
+                offline_cpus: config.offline_cpus(&n.name),
+            })
+            .collect();
+        nodes.sort_by(|a, b| a.name.cmp(&b.name));
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            tenant: tenant.to_string(),
+            nodes,
+            request: request.clone(),
+            settings: ReproSettings::from(opts),
+            occupied,
+            placement,
+            redacted_keys: Vec::new(),
+        }
+    }
+
+    /// A copy with the values of `sensitive` label keys replaced (see the
+    /// module docs).
+    pub fn redacted(&self, sensitive: &BTreeSet<String>) -> Self {
+        let mut bundle = self.clone();
+        let is_sensitive = |key: &str| sensitive.contains(key);
+        for node in &mut bundle.nodes {
+            for (key, value) in [
+                ("architecture", &mut node.architecture),
+                ("location", &mut node.location),
+                ("description", &mut node.description),
+            ] {
+                if is_sensitive(key) {
+                    *value = REDACTED.to_string();
+                }
+            }
+            if is_sensitive("endpoint") {
+                node.endpoint = None;
+            }
+        }
+        for task in &mut bundle.request.tasks {
+            for (key, value) in task.metadata.iter_mut() {
+                if is_sensitive(key) {
+                    *value = REDACTED.to_string();
+                }
+            }
+        }
+        for schedule in [&mut bundle.occupied, &mut bundle.placement] {
+            for task in schedule.values_mut().flatten() {
+                for (key, value) in task.metadata.iter_mut() {
+                    if is_sensitive(key) {
+                        *value = REDACTED.to_string();
+                    }
+                }
+            }
+        }
+        bundle.redacted_keys = sensitive.iter().cloned().collect();
+        bundle
+    }
+
+    /// The node configuration of the bundle, loaded the way `--nodeconfig`
+    /// is, with the recorded cordons, RT capability and offline CPUs.
+    pub fn node_config(&self) -> Result<NodeConfigManager, ReproError> {
+        #[derive(Serialize)]
+        struct Entry<'a> {
+            available_cpus: &'a [u32],
+            reserved_cpus: &'a [u32],
+            max_memory_mb: u64,
+            architecture: &'a str,
+            location: &'a str,
+            description: &'a str,
+            endpoint: Option<&'a str>,
+            max_workloads: Option<usize>,
+            enabled: bool,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            major_frame_us: Option<u64>,
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            time_partitions: &'a Vec<TimeWindow>,
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            cpu_clusters: &'a Vec<Vec<u32>>,
+        }
+        #[derive(Serialize)]
+        struct File<'a> {
+            nodes: std::collections::BTreeMap<&'a str, Entry<'a>>,
+        }
+
+        let file = File {
+            nodes: self
+                .nodes
+                .iter()
+                .map(|n| {
+                    let entry = Entry {
+                        available_cpus: &n.available_cpus,
+                        reserved_cpus: &n.reserved_cpus,
+                        max_memory_mb: n.max_memory_mb,
+                        architecture: &n.architecture,
+                        location: &n.location,
+                        description: &n.description,
+                        endpoint: n.endpoint.as_deref(),
+                        max_workloads: n.max_workloads,
+                        enabled: n.enabled,
+                        major_frame_us: n.major_frame_us,
+                        time_partitions: &n.time_partitions,
+                        cpu_clusters: &n.cpu_clusters,
+                    };
+                    (n.name.as_str(), entry)
+                })
+                .collect(),
+        };
+        let mut config = NodeConfigManager::new();
+        config.load_from_str(&serde_yaml::to_string(&file)?, Path::new("<bundle>"))?;
+        for n in &self.nodes {
+            config.set_cordoned(&n.name, n.cordoned);
+            config.set_rt_capable(&n.name, n.rt_capable);
+            if !n.offline_cpus.is_empty() {
+                let online = n
+                    .available_cpus
+                    .iter()
+                    .copied()
+                    .filter(|c| !n.offline_cpus.contains(c));
+                config.report_online_cpus(&n.name, online);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Schedule the recorded request again and compare with `placement`.
+    pub fn replay(&self) -> Result<ReproOutcome, ReproError> {
+        let scheduler = GlobalScheduler::new(Arc::new(self.node_config()?));
+        let opts = self.settings.to_options()?;
+        let tasks = tasks_from_proto(&self.request);
+        Ok(
+            match scheduler.schedule_with_occupancy(&self.occupied, tasks, &opts) {
+                Ok(replayed) => {
+                    let diff = ScheduleDiff::between(&self.placement, &replayed);
+                    if diff.is_empty() {
+                        ReproOutcome::Match
+                    } else {
+                        ReproOutcome::Mismatch(diff)
+                    }
+                }
+                Err(e) => ReproOutcome::Failed(e),
+            },
+        )
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{self, Format};
+    use crate::config::NodeConfig;
+    use crate::grpc::schedinfo_service::task_from_proto;
+    use crate::proto::schedinfo_v1::TaskInfo;
+
+    fn config() -> NodeConfigManager {
+        let node = |name: &str, location: &str| NodeConfig {
+            location: location.into(),
+            endpoint: Some(format!("{name}.internal:50054")),
+            ..NodeConfig::default_config(name)
+        };
+        let config = NodeConfigManager::from_nodes(vec![node("n1", "bay-7"), node("n2", "bay-9")]);
+        config.set_cordoned("n2", true);
+        config
+    }
+
+    fn task(name: &str, runtime: i32) -> TaskInfo {
+        TaskInfo {
+            name: name.into(),
+            period: 10_000,
+            runtime,
+            deadline: 10_000,
+            priority: 50,
+            metadata: [("customer".to_string(), "acme".to_string())].into(),
+            ..Default::default()
+        }
+    }
+
+    /// `w1` scheduled with `least_loaded` around one task of another tenant.
+    fn bundle() -> ReproBundle {
+        let config = config();
+        let request = SchedInfo {
+            workload_id: "w1".into(),
+            tasks: vec![task("a", 3_000), task("b", 4_000)],
+            ..Default::default()
+        };
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        let scheduler = GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(
+            config.get_all_nodes().values().cloned().collect(),
+        )));
+        let other: Vec<_> = [task("x", 5_000)]
+            .iter()
+            .map(|t| task_from_proto(t, "w0"))
+            .collect();
+        let occupied = scheduler
+            .schedule_with_options(other, &opts.clone().with_allowed_nodes(["n1"]))
+            .unwrap();
+        // Schedule against the cordon, as the service would.
+        let scheduler = GlobalScheduler::new(Arc::new(config));
+        let tasks = request
+            .tasks
+            .iter()
+            .map(|t| task_from_proto(t, "w1"))
+            .collect();
+        let placement = scheduler
+            .schedule_with_occupancy(&occupied, tasks, &opts)
+            .unwrap();
+        ReproBundle::capture(
+            "t1",
+            &request,
+            &opts,
+            scheduler.node_config_manager(),
+            occupied,
+            placement,
+        )
+    }
+
+    #[test]
+    fn replay_of_a_captured_bundle_matches() {
+        let bundle = bundle();
+        assert_eq!(bundle.version, env!("CARGO_PKG_VERSION"));
+        assert!(bundle.nodes.iter().any(|n| n.name == "n2" && n.cordoned));
+        for format in [Format::Json, Format::Cbor] {
+            let bytes = codec::encode(&bundle, format).unwrap();
+            let decoded: ReproBundle = codec::decode(&bytes).unwrap();
+            assert_eq!(decoded, bundle);
+            assert!(matches!(decoded.replay().unwrap(), ReproOutcome::Match));
+        }
+    }
+
+    #[test]
+    fn mutated_bundles_are_detected() {
+        // Uncordoned, the idle n2 is the least loaded node.
+        let mut bundle = bundle();
+        bundle.nodes[1].cordoned = false;
+        let ReproOutcome::Mismatch(diff) = bundle.replay().unwrap() else {
+            panic!("expected a mismatch");
+        };
+        assert!(!diff.is_empty());
+
+        // A task that no longer fits fails the replay.
+        let mut bundle = self::bundle();
+        bundle.request.tasks[1].runtime = 9_500;
+        assert!(matches!(bundle.replay().unwrap(), ReproOutcome::Failed(_)));
+
+        let mut bundle = self::bundle();
+        bundle.settings.algorithm = "fastest".into();
+        assert!(matches!(
+            bundle.replay(),
+            Err(ReproError::InvalidSetting {
+                setting: "algorithm",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn redaction_replaces_sensitive_labels_and_still_replays() {
+        let sensitive: BTreeSet<String> = ["customer", "location", "endpoint"]
+            .map(String::from)
+            .into();
+        let bundle = bundle().redacted(&sensitive);
+        assert_eq!(bundle.redacted_keys, ["customer", "endpoint", "location"]);
+        assert!(bundle
+            .nodes
+            .iter()
+            .all(|n| n.location == REDACTED && n.endpoint.is_none()));
+        assert!(bundle
+            .request
+            .tasks
+            .iter()
+            .all(|t| t.metadata["customer"] == REDACTED));
+        assert!(bundle
+            .placement
+            .values()
+            .chain(bundle.occupied.values())
+            .flatten()
+            .all(|t| t.metadata["customer"] == REDACTED));
+        assert!(matches!(bundle.replay().unwrap(), ReproOutcome::Match));
+    }
+}
@@ -0,0 +1,125 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Guard against drastic changes in a workload revision.
+//!
+//! An `AddSchedInfo` for the `workload_id` a tenant already has stored is a
+//! revision of it.  A task whose period or runtime moved by more than the
+//! configured factor in either direction (10 ms → 10 µs is 1000×) is more
+//! likely a typo than an intent, so the revision is refused with
+//! `FailedPrecondition` listing those tasks unless the request sets
+//! `force`.  Tasks that are new or gone are not compared.
+
+use std::fmt;
+
+use crate::task::{Micros, Task};
+use crate::units::fmt_duration_us;
+
+/// Largest accepted change factor when none is configured.
+pub const DEFAULT_REVISION_CHANGE_FACTOR: f64 = 10.0;
+
+/// The parameter that changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskParameter {
+    Period,
+    Runtime,
+}
+
+/// One task parameter that changed by more than the factor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuspiciousChange {
+    pub task: String,
+    pub parameter: TaskParameter,
+    pub old: Micros,
+    pub new: Micros,
+}
+
+impl fmt::Display for SuspiciousChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parameter = match self.parameter {
+            TaskParameter::Period => "period",
+            TaskParameter::Runtime => "runtime",
+        };
+        write!(
+            f,
+            "{} {parameter} {} → {}",
+            self.task,
+            fmt_duration_us(self.old.as_u64()),
+            fmt_duration_us(self.new.as_u64())
+        )
+    }
+}
+
+/// The changes from `old` to `new` larger than `factor`, in `new` order.
+pub fn suspicious_changes(old: &[Task], new: &[Task], factor: f64) -> Vec<SuspiciousChange> {
+    let mut changes = Vec::new();
+    for task in new {
+        let Some(prev) = old.iter().find(|t| t.name == task.name) else {
+            continue;
+        };
+        for (parameter, old, new) in [
+            (TaskParameter::Period, prev.period_us, task.period_us),
+            (TaskParameter::Runtime, prev.runtime_us, task.runtime_us),
+        ] {
+            if exceeds(old, new, factor) {
+                changes.push(SuspiciousChange {
+                    task: task.name.clone(),
+                    parameter,
+                    old,
+                    new,
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Whether `old` and `new` differ by more than `factor` either way.  A
+/// change from or to zero always does.
+fn exceeds(old: Micros, new: Micros, factor: f64) -> bool {
+    let (lo, hi) = if old <= new { (old, new) } else { (new, old) };
+    if lo == hi {
+        return false;
+    }
+    lo.as_u64() == 0 || hi.as_u64() as f64 > lo.as_u64() as f64 * factor
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(name: &str, period_us: u64, runtime_us: u64) -> Task {
+        Task {
+            name: name.into(),
+            period_us: Micros(period_us),
+            runtime_us: Micros(runtime_us),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn only_changes_beyond_the_factor_either_way_count() {
+        let old = [task("a", 10_000, 1_000), task("b", 10_000, 1_000)];
+        let new = [
+            task("a", 100_000, 100), // exactly 10× both ways: allowed
+            task("b", 10, 1_000),    // 1000× shorter period
+            task("c", 1, 1),         // new task
+        ];
+        let changes = suspicious_changes(&old, &new, DEFAULT_REVISION_CHANGE_FACTOR);
+        assert_eq!(
+            changes,
+            vec![SuspiciousChange {
+                task: "b".into(),
+                parameter: TaskParameter::Period,
+                old: Micros(10_000),
+                new: Micros(10),
+            }]
+        );
+        assert!(!exceeds(Micros(0), Micros(0), 10.0));
+        assert!(exceeds(Micros(0), Micros(1), 10.0));
+    }
+}
@@ -8,25 +8,408 @@ SPDX-License-Identifier: MIT
 //! Implements the `AddSchedInfo` RPC:
 //!   1. Convert proto `TaskInfo` list → internal `Vec<Task>`.
 //!   2. Calculate hyperperiod (LCM of all task periods).
-//!   3. Run `GlobalScheduler` to assign tasks to nodes and CPUs.
-//!   4. Acquire `WorkloadStore` lock briefly, cancel previous workload's
-//!      sync barrier, store the new `WorkloadState`, release lock.
+//!   3. Run `GlobalScheduler` to assign tasks to nodes and CPUs, around the
+//!      other tenants' active workloads.
+//!   4. Acquire `WorkloadStore` lock briefly, cancel the caller tenant's
+//!      previous sync barrier, store the new `WorkloadState` one generation
+//!      after the old one (whose schedule is kept as the delta base), release
+//!      lock.
+//!   5. Reply with per-task placements and a [`WorkloadSummary`] (task and
+//!      node counts, hyperperiod, total and peak CPU utilisation, warnings).
+//!   6. Spawn a background task that forwards per-CPU feasibility warnings
+//!      to Pullpiri as `ADVISORY` faults.  The RPC returns without waiting
+//!      for it; repeats of the same (workload, node, cpu) are debounced.
+//!
+//! `RemoveWorkload` clears the caller tenant's workload.  Naming a workload
+//! that only another tenant holds yields `PermissionDenied`.
+//!
+//! `GetClusterStatus` reports node capacity as seen by the caller's tenant
+//! (i.e. with only that tenant's workload placed) plus a workload summary.
+//!
+//! `GetCapabilities` reports what this instance accepts — algorithms,
+//! policies, request size, queueing, shadow and simulation settings — from
+//! the service's own configuration.
+//!
+//! # Pending queue
+//!
+//! A request with `queue_if_full` that fails only because other tenants hold
+//! the capacity is parked in a bounded [`PendingQueue`] and answered with
+//! [`STATUS_QUEUED`].  Each `RemoveWorkload` or replacement retries the queue
+//! (priority class first, then importance, then arrival); a workload that gets placed is reported
+//! to Pullpiri as a `WORKLOAD_SCHEDULED` advisory.  A full queue yields
+//! `ResourceExhausted`.
+//!
+//! # Error codes
+//!
+//! A failed `AddSchedInfo` carries the stable [`ErrorCode`] of the scheduler
+//! error in `Response.error_code` and in the [`ERROR_CODE_METADATA_KEY`]
+//! metadata (also on an `InvalidArgument` status); an admission rejection
+//! adds the reason's code under [`REASON_CODE_METADATA_KEY`].  The task the
+//! scheduler gave up on is listed in `placements` with its own code.
+//!
+//! # Per-request scheduling options
+//!
+//! `SchedInfo.algorithm`, `SchedInfo.cpu_utilization_threshold` and
+//! `SchedInfo.seed` override the service defaults (see
+//! [`SchedInfoServiceImpl::with_schedule_defaults`]) for one request.  An
+//! unparseable algorithm or a threshold outside `(0, 1]` is rejected with
+//! `InvalidArgument` before any scheduling work is done, as is a
+//! `workload_id` or task name outside the [naming policy](crate::naming), or
+//! a task bound to its target node but pinned only to CPUs that node does
+//! not have (every such task is listed in one error).  A request with more
+//! tasks than the [input limits](crate::limits) allow is refused with
+//! `TooManyTasks` before any other check.  The values actually
+//! used are echoed back in the response metadata ([`ALGORITHM_METADATA_KEY`],
+//! [`THRESHOLD_METADATA_KEY`], and [`SEED_METADATA_KEY`] for
+//! `randomized_spread`) and recorded on the `audit` tracing target, so a
+//! randomised placement can always be reproduced.
+//!
+//! # Field ranges
+//!
+//! A task's `priority` must be 0–99 and its `max_dmiss` not negative
+//! (see [`RangedField`](crate::task::RangedField)).  By default a request
+//! with a value outside that is rejected with `InvalidArgument` and
+//! `InvalidTasks`, which lists it with every other task problem (see
+//! [`validate_tasks`]).  With [`RangeCheck::Clamp`] (see
+//! [`SchedInfoServiceImpl::with_range_check`]) the value is moved to the
+//! nearest end of its range, with a warning, and the clamped value is what
+//! gets stored, scheduled and sent to the nodes.
+//!
+//! # Algorithm chains
+//!
+//! `SchedInfo.algorithm_chain` — or, for a request that names no algorithm,
+//! the configured [`ScheduleOptions::algorithm_chain`] — retries a workload
+//! that one algorithm cannot place with the next (see
+//! [`crate::scheduler::fallback`]).  Every link runs against the same
+//! snapshot of the other tenants' workloads, under the store lock, and the
+//! first that succeeds is admitted as if its algorithm had been requested:
+//! [`ALGORITHM_METADATA_KEY`] names it, [`CHAIN_LINK_METADATA_KEY`] gives its
+//! index, and `Response.chain_attempts` lists it after the links that
+//! failed.  When every link fails, the response carries the last link's
+//! error and `chain_attempts` the error of each.
+//!
+//! - With `queue_if_full`, a workload is queued only if the whole chain
+//!   fails around the other tenants but succeeds on an idle cluster; a
+//!   retry from the queue runs the chain again.
+//! - Shadow algorithms are compared with the link that placed the workload.
+//! - `timpani-o schedule`, the offline dry run, runs the chain the same way
+//!   and reports which link placed the workload.
+//!
+//! # Revisions
+//!
+//! A request for the `workload_id` the tenant already has stored revises
+//! it.  A revision that changes a task's period or runtime by more than the
+//! [revision factor](SchedInfoServiceImpl::with_revision_change_factor) is
+//! refused with `FailedPrecondition` naming each such task with its old and
+//! new value, unless `SchedInfo.force` is set (see [`super::revision`]).
+//!
+//! [`SchedInfoServiceImpl::update_task`] revises the timing of a single
+//! task without revalidating the rest of the workload (see
+//! [`crate::scheduler::task_update`]): the task stays on its CPU if it
+//! still fits there, else it alone moves, and only if that fails too is
+//! the workload placed again.  Either way the workload moves to its next
+//! generation, so a node that ran the previous one gets a delta — one task
+//! unless the workload was placed again.  The level needed is returned and
+//! logged on the `audit` target.
+//!
+//! A non-empty `SchedInfo.allowed_nodes` confines the workload to those
+//! nodes ([`ScheduleOptions::allowed_nodes`]).
+//!
+//! Real-time tasks sent with priority 0 are given one by
+//! [`assign_priorities`], ranked by `SchedInfo.priority_ordering`
+//! (rate-monotonic when unset; see [`crate::scheduler::priority`]).  The
+//! ordering used is echoed in `WorkloadSummary.priority_ordering`, and the
+//! feasibility warnings assume it.
+//!
+//! A placeholder task (`TaskInfo.placeholder`) reserves capacity for a task
+//! that arrives later.  A revision that submits a task under a stored
+//! placeholder's name warm-starts it on the placeholder's node and CPU
+//! while it still fits ([`ScheduleOptions::warm_start`]).  Status flags
+//! placeholders in `TaskStatus.placeholder`.
+//!
+//! `SchedInfo.exclusive_cpus` dedicates every CPU the workload's tasks go to:
+//! other tenants' workloads stay off those CPUs, in this and later
+//! admissions, and the workload's tasks stay off CPUs others use (see
+//! [`crate::scheduler::workloads`]).  Status lists the held CPUs in
+//! `NodeStatus.exclusive_cpus`.
+//!
+//! # Admission overrides
+//!
+//! `SchedInfo.admission_overrides` switches off admission checks for a lab
+//! experiment ([`AdmissionOverride`]).  Unless the service
+//! [allows overrides](SchedInfoServiceImpl::with_admission_overrides) a
+//! request that sets any is rejected with `InvalidArgument`.  An admitted
+//! workload never overcommits silently: the skipped checks are listed in
+//! `WorkloadSummary.admission_overrides`, logged on the `audit` target and
+//! reported to Pullpiri as an `ADMISSION_OVERRIDE` advisory.
+//!
+//! # Shadow scheduling
+//!
+//! With [`SchedInfoServiceImpl::with_shadow_algorithms`] every admitted
+//! workload is scheduled again by each shadow algorithm against the same
+//! snapshot, on a blocking thread after the primary result is stored.  The
+//! comparison (see [`crate::report::shadow`]) is logged on the `audit`
+//! target and kept in memory; shadow schedules never reach a node.
+//!
+//! # Node drain
+//!
+//! [`SchedInfoServiceImpl::drain_node`] empties a node for maintenance a
+//! batch at a time, across all tenants: lowest workload priority class
+//! first, then lowest workload importance, then lowest task priority.  Each step re-places its batch on the other
+//! nodes (`least_loaded`, around everything else that is placed) and moves
+//! the affected workloads to a new generation, so their nodes receive a
+//! delta on the next `GetSchedInfo`.  Tasks with a hard `target_node` on the
+//! draining node stay and are reported as pinned.
+//!
+//! [`SchedInfoServiceImpl::set_node_cordoned`] closes a node to new
+//! placements (or opens it again) without moving anything; the admin
+//! service cordons a node before draining it.  The state is logged on the
+//! `audit` target, recorded as a `NODE_CORDONED` / `NODE_UNCORDONED` event,
+//! and carried over configuration reloads (see [`crate::config`]'s
+//! `cordon` docs).
+//!
+//! [`SchedInfoServiceImpl::what_if_node_loss`] runs the same re-placement
+//! for every task of a node, in drain order, against a snapshot, and
+//! reports what would fit and what would not (see
+//! [`crate::scheduler::what_if`]).  Nothing changes.
+//!
+//! # Node configuration reload
+//!
+//! [`SchedInfoServiceImpl::reload_config`] swaps in a new node configuration
+//! (`timpani-o` does so on `SIGHUP`) and reconciles the stored workloads
+//! against it.  Placements on nodes the new configuration lacks are
+//! *orphaned*: they are logged on the `audit` target, reported to Pullpiri as
+//! `NODE_ORPHANED` advisories and listed under `ClusterStatus.orphaned`
+//! instead of being counted as capacity.  With
+//! [`with_orphan_evacuation`](SchedInfoServiceImpl::with_orphan_evacuation)
+//! they are first re-placed on the configured nodes the same way a drain
+//! moves tasks; only what cannot move (a hard `target_node` on the removed
+//! node, or no room) stays orphaned.
+//!
+//! `ApplyNodeConfig` does the same for YAML pushed by Pullpiri, atomically:
+//! the YAML is loaded into a candidate configuration first, and a document
+//! that does not parse or validate is answered with its findings while the
+//! running configuration stays untouched.  An accepted one is written to
+//! the [configuration file](SchedInfoServiceImpl::with_node_config_path)
+//! (temporary file, `fsync`, rename) before it is swapped in, so a restart
+//! comes up with the same nodes; if that write fails nothing is swapped.
+//! Every swap, pushed or reloaded, advances the configuration generation
+//! reported in `ClusterStatus.config_generation`.
+//!
+//! # Apply watchdog
+//!
+//! With an [`ApplyWatchdog`]
+//! ([`with_apply_watchdog`](SchedInfoServiceImpl::with_apply_watchdog),
+//! shared with `NodeService`), every new generation gives each node with
+//! something new to apply a deadline to acknowledge it (see
+//! [`super::watchdog`]).  [`SchedInfoServiceImpl::check_apply_deadlines`],
+//! which `timpani-o` runs every second, handles each missed deadline:
+//!
+//! 1. the node's tasks that are not applied yet become `degraded`;
+//! 2. the miss is logged on the `audit` target, recorded as an
+//!    `APPLY_OVERDUE` event and sent to Pullpiri as a `CRITICAL`
+//!    `APPLY_TIMEOUT` fault;
+//! 3. with [`with_apply_failover`](SchedInfoServiceImpl::with_apply_failover)
+//!    the workload's tasks on that node are re-placed on the other nodes the
+//!    way a drain moves them (tasks with a hard `target_node` there stay).
+//!
+//! `RemoveWorkload` cancels the tenant's deadlines.
+//!
+//! # Workload expiry
+//!
+//! A workload admitted with `ttl_seconds` expires that long after it is
+//! stored; resubmitting it with a new `ttl_seconds` renews the lease (and
+//! without one, makes it permanent).  [`SchedInfoServiceImpl::expire_workloads`],
+//! which `timpani-o` runs every [`WORKLOAD_EXPIRY_TICK`], removes expired
+//! workloads the way `RemoveWorkload` does — their nodes find no workload on
+//! the next `GetSchedInfo`, waiting `SyncTimer` calls are cancelled and the
+//! pending queue is retried — records a `WORKLOAD_EXPIRED` event and sends
+//! Pullpiri a `WORKLOAD_EXPIRED` advisory.  `ClusterStatus` reports the time
+//! left.
+//!
+//! # Compaction
+//!
+//! With [`with_compaction_idle`](SchedInfoServiceImpl::with_compaction_idle),
+//! [`SchedInfoServiceImpl::propose_compaction`] (run every
+//! [`COMPACTION_TICK`](super::compaction::COMPACTION_TICK)) waits until no placement, node configuration or
+//! cordon has changed for the idle period, then re-places every stored
+//! workload from scratch with the default algorithm (see
+//! [`super::compaction`]).  A layout that frees nodes, or lowers the peak
+//! CPU load on as many nodes, becomes the current proposal: it is logged on
+//! the `audit` target, recorded as a `COMPACTION_PROPOSED` event and
+//! reported in `ClusterStatus.compaction`.  Nothing moves until an operator
+//! calls `AdminService.ApplyCompaction`
+//! ([`apply_compaction`](SchedInfoServiceImpl::apply_compaction)), which
+//! moves each affected workload to a new generation the way a drain does,
+//! so nodes receive deltas.  Any change to what the proposal was based on
+//! expires it.
+//!
+//! # Push epochs
+//!
+//! With [`with_push_epoch`](SchedInfoServiceImpl::with_push_epoch), an
+//! update of a stored workload below class `safety` is held from the nodes
+//! until the next epoch boundary, where
+//! [`SchedInfoServiceImpl::run_push_epochs`] publishes it together with
+//! the other updates of the epoch as one generation (see [`super::epoch`]).
+//! Apply deadlines start when the generation is published.
+//!
+//! # Transactional push
+//!
+//! With a prepare timeout
+//! ([`with_transactional_push`](SchedInfoServiceImpl::with_transactional_push)),
+//! a replacement that changes more than one node is prepared on those nodes
+//! before it is published, and rolled back if one of them fails to stage it
+//! or the timeout passes first (see [`super::txn`]).  It is never held for
+//! a push epoch.
+//!
+//! # Repro bundles
+//!
+//! Each admission records its inputs and result.
+//! [`SchedInfoServiceImpl::capture_repro`] returns them for a bug report,
+//! with the values of the
+//! [sensitive label keys](SchedInfoServiceImpl::with_sensitive_label_keys)
+//! redacted (see [`super::repro`]).
+//!
+//! # Utilisation and timing metrics
+//!
+//! Each admission also feeds the per-CPU utilisation it leaves behind into
+//! [`SchedInfoServiceImpl::utilization_metrics`] (see
+//! [`crate::report::metrics`]).  `GetClusterStatus` reports each node's
+//! high-water mark, which never decreases while the process runs.
+//!
+//! How long each phase of the admission's scheduling run took (see
+//! [`crate::scheduler::timing`]) goes to the `audit` log and into
+//! [`SchedInfoServiceImpl::phase_metrics`].
+//!
+//! # Retention
+//!
+//! Per-workload state outside the store — the advisory debouncer's keys —
+//! is dropped when the workload leaves: `RemoveWorkload`, TTL expiry, or a
+//! replacement under another `workload_id`.  A workload that comes back is
+//! treated as new.  [`SchedInfoServiceImpl::reclaimed_entries`] counts what
+//! was dropped.  The delivery ledger
+//! ([`WorkloadState::deliveries`](super::WorkloadState::deliveries)) is part
+//! of the stored state and leaves with it; hyperperiods are computed per
+//! request and not kept at all.
+//!
+//! # Schedule events
+//!
+//! Every change above is also recorded in the shared [`EventLog`]
+//! ([`with_event_log`](SchedInfoServiceImpl::with_event_log)) while the
+//! store lock is held, so sequence numbers follow the order of the changes.
+//! `WatchScheduleEvents` streams the caller tenant's events and the
+//! cluster-wide node events, after replaying up to `backlog` retained ones.
+//!
+//! # Audit log
+//!
+//! What this page calls the `audit` target is the default
+//! [`TracingSink`]: each line is reported as an [`AuditEvent`] to the
+//! service's [`ScheduleEventSink`], which its schedulers share.  Install
+//! another with [`with_event_sink`](SchedInfoServiceImpl::with_event_sink).
 
-use std::sync::Arc;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime};
 
+use thiserror::Error;
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::time::{self, MissedTickBehavior};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::metadata::{MetadataMap, MetadataValue};
 use tonic::{Request, Response, Status};
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
-use crate::config::NodeConfigManager;
-use crate::fault::FaultNotifier;
-use crate::hyperperiod::HyperperiodManager;
+use crate::config::{write_config_file, ConfigError, NodeConfigManager};
+use crate::fault::debounce::Debouncer;
+use crate::fault::{FaultNotification, FaultNotifier, FaultSeverity, FeasibilityInfo};
+use crate::hyperperiod::{HyperperiodError, HyperperiodManager};
+use crate::limits::{Limit, LimitHits};
+use crate::metadata::{Metadata, MetadataPolicy};
+use crate::naming::{sanitize, NameKind, NamingPolicy};
 use crate::proto::schedinfo_v1::{
-    sched_info_service_server::SchedInfoService, Response as ProtoResponse, SchedInfo, TaskInfo,
+    sched_info_service_server::SchedInfoService, Capabilities, CapabilitiesRequest,
+    ChainAttempt as ProtoChainAttempt, ClusterStatus, ClusterStatusRequest, ConfigFinding,
+    FaultType, NodeConfigResult, NodeConfigUpdate, PendingStatus, QueuedWorkload,
+    Response as ProtoResponse, SchedInfo, SchedPolicy as ProtoSchedPolicy, ScheduleEvent,
+    ScheduleEventKind, TaskInfo, TaskPlacement, TaskStatus, WatchScheduleEventsRequest,
+    WorkloadRef,
+};
+use crate::report::metrics::{
+    MetricsSnapshot, PhaseMetrics, PhaseMetricsSnapshot, UtilizationMetrics,
 };
-use crate::scheduler::GlobalScheduler;
-use crate::task::{CpuAffinity, SchedPolicy, Task};
+use crate::report::shadow::{ScheduleStats, ShadowComparison, ShadowLog, ShadowOutcome};
+use crate::report::status::{node_statuses, orphaned_nodes, workload_status};
+use crate::report::summary::{chain_latencies, render_summary, workload_summary, WorkloadSummary};
+use crate::scheduler::feasibility::{check_schedule, FeasibilityWarning};
+use crate::scheduler::simulate::{verify_schedule, SimulatedMiss};
+use crate::scheduler::{
+    analyse_chains, assign_priorities, honoured_hints, run_chain, runtime_margins, validate_tasks,
+    AdmissionOverride, AuditEvent, ChainAttempt, ErrorCode, GlobalScheduler, LostTask,
+    PhaseTimings, PriorPlacement, PriorityClass, PriorityOrdering, SchedAlgorithm,
+    ScheduleEventSink, ScheduleOptions, SchedulerError, SimulationCheck, TaskChain, TaskTiming,
+    TracingSink, UpdateLevel, WhatIfReport,
+};
+use crate::task::{
+    CpuAffinity, FaultSink, Micros, NodeSchedMap, RangeCheck, SchedPolicy, SharedResource,
+    TargetNodePolicy, Task,
+};
+use crate::units::{fmt_duration_us, DurationStyle};
+
+use super::compaction::{self, Basis, Compaction, CompactionError, Compactor};
+use super::epoch::until_boundary;
+use super::events::{event, EventLog};
+use super::lifecycle::{TaskEvent, TaskState};
+use super::log_filter::LogFilter;
+use super::pending::{PendingQueue, PendingWorkload};
+use super::repro::ReproBundle;
+use super::revision::{suspicious_changes, SuspiciousChange, DEFAULT_REVISION_CHANGE_FACTOR};
+use super::txn;
+use super::watchdog::{ApplyExpiry, ApplyWatchdog};
+use super::{tenant_from_metadata, BarrierStatus, WorkloadState, WorkloadStore};
+
+// ── Constants ─────────────────────────────────────────────────────────────────
+
+/// Response metadata key carrying the algorithm used for the request.
+pub const ALGORITHM_METADATA_KEY: &str = "x-timpani-algorithm";
+
+/// Response metadata key carrying the index of the algorithm chain link that
+/// placed the workload (see [`ScheduleOptions::algorithm_chain`]).
+pub const CHAIN_LINK_METADATA_KEY: &str = "x-timpani-chain-link";
+
+/// Response metadata key carrying the per-CPU threshold used for the request.
+pub const THRESHOLD_METADATA_KEY: &str = "x-timpani-cpu-threshold";
+
+/// Response metadata key carrying the RNG seed (`randomized_spread` only).
+pub const SEED_METADATA_KEY: &str = "x-timpani-seed";
 
-use super::{BarrierStatus, WorkloadState, WorkloadStore};
+/// Response / `Status` metadata key carrying the numeric [`ErrorCode`] of a
+/// failed request.
+pub const ERROR_CODE_METADATA_KEY: &str = "x-timpani-error-code";
+
+/// Metadata key carrying the admission reason's [`ErrorCode`] when the
+/// error is `TIMPANI_E_ADMISSION_REJECTED`.
+pub const REASON_CODE_METADATA_KEY: &str = "x-timpani-reason-code";
+
+/// Fault metadata key listing the skipped checks of an `ADMISSION_OVERRIDE`
+/// advisory, comma-separated.
+pub const ADMISSION_OVERRIDES_METADATA_KEY: &str = "admission_overrides";
+
+/// How often `timpani-o` checks for expired workloads.
+pub const WORKLOAD_EXPIRY_TICK: Duration = Duration::from_secs(1);
+
+/// Largest `AddSchedInfo` message when none is configured — tonic's own
+/// decoding limit.
+pub const DEFAULT_MAX_REQUEST_BYTES: usize = 4 * 1024 * 1024;
+
+/// `Response.status` for a workload parked in the pending queue.
+pub const STATUS_QUEUED: i32 = 1;
+
+/// Events buffered between a `WatchScheduleEvents` subscription and tonic.
+/// A subscriber slower than that lags on the event channel instead.
+const EVENT_STREAM_DEPTH: usize = 16;
 
 // ── Service struct ────────────────────────────────────────────────────────────
 
@@ -36,12 +419,173 @@ use super::{BarrierStatus, WorkloadState, WorkloadStore};
 /// All fields are `Arc`-wrapped so cloning is cheap.
 #[derive(Clone)]
 pub struct SchedInfoServiceImpl {
-    scheduler: Arc<GlobalScheduler>,
+    /// Replaced as a whole by [`reload_config`](Self::reload_config).
+    scheduler: Arc<RwLock<Arc<GlobalScheduler>>>,
     workload_store: WorkloadStore,
-    /// Injected fault notifier — used for future scheduler-error forwarding.
-    /// Not yet called in the port; present so the injection pipeline exists.
-    #[allow(dead_code)]
+    /// Injected fault notifier — carries feasibility advisories to Pullpiri.
     fault_notifier: Arc<dyn FaultNotifier>,
+    /// Rate-limits advisories per (workload, node, cpu).
+    advisory_debouncer: Arc<Debouncer>,
+    /// Algorithm and threshold used when the request does not override them.
+    defaults: ScheduleOptions,
+    /// Workloads waiting for capacity (`queue_if_full`).
+    pending: Arc<Mutex<PendingQueue>>,
+    /// Secondary algorithms run after every admission for comparison only.
+    shadows: Vec<SchedAlgorithm>,
+    /// Recent shadow comparisons.
+    shadow_log: Arc<ShadowLog>,
+    /// Re-place orphaned tasks after a config reload.
+    evacuate_orphans: bool,
+    /// Schedule change events, shared with `NodeService`.
+    events: Arc<EventLog>,
+    /// Rule for `workload_id` and task names.
+    naming: NamingPolicy,
+    /// Limits on task metadata and the keys forwarded to audit and faults.
+    metadata: MetadataPolicy,
+    /// What to do with a priority or `max_dmiss` outside its range.
+    range_check: RangeCheck,
+    /// Largest period/runtime change a revision may make without `force`.
+    revision_factor: f64,
+    /// Decoding limit of the server this service is mounted in.
+    max_request_bytes: usize,
+    /// Honour `SchedInfo.admission_overrides`.
+    allow_admission_overrides: bool,
+    /// Where an applied node configuration is persisted.
+    node_config_path: Option<PathBuf>,
+    /// Node configurations swapped in since startup.
+    config_generation: Arc<AtomicU64>,
+    /// Serialises `ApplyNodeConfig` calls.
+    config_apply: Arc<Mutex<()>>,
+    /// Apply deadlines, shared with `NodeService`; `None` = no watchdog.
+    apply_watchdog: Option<Arc<ApplyWatchdog>>,
+    /// Move a workload's tasks off a node that missed its apply deadline.
+    apply_failover: bool,
+    /// Idle-time compaction proposals; `None` = off.
+    compaction: Option<Arc<Compactor>>,
+    /// Hold updates for the next epoch boundary; `None` = publish at once.
+    push_epoch: Option<Duration>,
+    /// Per-workload entries dropped when their workload left.
+    reclaimed: Arc<AtomicU64>,
+    /// Label keys redacted from repro bundles.
+    sensitive_label_keys: BTreeSet<String>,
+    /// Per-CPU utilisation seen by admissions.
+    utilization: Arc<UtilizationMetrics>,
+    /// Scheduling phase timings of admissions.
+    phase_metrics: Arc<PhaseMetrics>,
+    /// Prepare multi-node replacements on their nodes first, aborting
+    /// after this long; `None` = publish at once.
+    prepare_timeout: Option<Duration>,
+    /// The process's reloadable log filter; `None` = not reloadable.
+    log_filter: Option<LogFilter>,
+    /// Hits of the input limits by this service's requests, shared with its
+    /// scheduler.
+    limit_hits: Arc<LimitHits>,
+    /// Receiver of the schedulers' per-task events and of this service's
+    /// audit events; [`TracingSink`] unless replaced with
+    /// [`with_event_sink`](Self::with_event_sink).
+    sink: Arc<dyn ScheduleEventSink>,
+}
+
+/// Result of one [`SchedInfoServiceImpl::drain_node`] step.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DrainProgress {
+    /// Tasks moved off the node in this step, at their new placement.
+    pub moved: Vec<TaskPlacement>,
+    /// `(tenant, task)` pairs that cannot leave the node (hard target).
+    pub pinned: Vec<(String, String)>,
+    /// Movable tasks still on the node.  Callers repeat until this is zero.
+    pub remaining: usize,
+}
+
+/// Result of [`SchedInfoServiceImpl::update_task`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskUpdateOutcome {
+    /// How far the update had to go.
+    pub level: UpdateLevel,
+    /// The updated task at its placement.
+    pub placement: TaskPlacement,
+    /// The workload's generation with the update.
+    pub generation: u64,
+}
+
+/// Why [`SchedInfoServiceImpl::update_task`] changed nothing.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum TaskUpdateError {
+    #[error("no workload '{0}' is stored for the tenant")]
+    UnknownWorkload(String),
+
+    #[error("workload '{workload}' has no task '{task}'")]
+    UnknownTask { workload: String, task: String },
+
+    #[error("hyperperiod calculation failed: {0}")]
+    Hyperperiod(#[from] HyperperiodError),
+
+    #[error(transparent)]
+    Rejected(#[from] SchedulerError),
+}
+
+/// A task placed on a node that the node configuration no longer has.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OrphanedPlacement {
+    pub tenant: String,
+    pub workload_id: String,
+    pub node: String,
+    pub task: String,
+}
+
+/// Result of [`SchedInfoServiceImpl::reload_config`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReconcileReport {
+    /// Configuration generation the reload started.
+    pub generation: u64,
+    /// Tasks evacuated to a configured node, at their new placement.
+    pub evacuated: Vec<TaskPlacement>,
+    /// Placements still on removed nodes, sorted.
+    pub orphaned: Vec<OrphanedPlacement>,
+}
+
+/// Input of a primary scheduling run, replayed by the shadows.
+struct ShadowSnapshot {
+    occupied: NodeSchedMap,
+    tasks: Vec<Task>,
+    primary: NodeSchedMap,
+    timings: PhaseTimings,
+}
+
+/// What [`SchedInfoServiceImpl::admit`] reports for a placed workload.
+struct Admitted {
+    placements: Vec<TaskPlacement>,
+    summary: WorkloadSummary,
+    /// Algorithms tried; the last one placed the workload.
+    attempts: Vec<ChainAttempt>,
+}
+
+/// Why [`SchedInfoServiceImpl::admit`] did not place a workload.  Each
+/// variant also carries the algorithms tried, empty if it failed before
+/// scheduling.
+enum AdmitError {
+    /// Would fit on an empty cluster, but not around the other tenants'
+    /// workloads.  Eligible for the pending queue.
+    CapacityLimited(SchedulerError, Vec<ChainAttempt>),
+    /// Invalid or unplaceable regardless of load (already logged).  `None`
+    /// for failures outside the scheduler (hyperperiod).
+    Rejected(Option<SchedulerError>, Vec<ChainAttempt>),
+}
+
+impl AdmitError {
+    fn scheduler_error(&self) -> Option<&SchedulerError> {
+        match self {
+            AdmitError::CapacityLimited(e, _) => Some(e),
+            AdmitError::Rejected(e, _) => e.as_ref(),
+        }
+    }
+
+    fn attempts(&self) -> &[ChainAttempt] {
+        match self {
+            AdmitError::CapacityLimited(_, a) | AdmitError::Rejected(_, a) => a,
+        }
+    }
 }
 
 impl SchedInfoServiceImpl {
@@ -50,83 +594,326 @@ impl SchedInfoServiceImpl {
         workload_store: WorkloadStore,
         fault_notifier: Arc<dyn FaultNotifier>,
     ) -> Self {
+        let limit_hits = Arc::new(LimitHits::default());
+        let sink: Arc<dyn ScheduleEventSink> = Arc::new(TracingSink);
         Self {
-            scheduler: Arc::new(GlobalScheduler::new(node_config_manager)),
+            scheduler: Arc::new(RwLock::new(Arc::new(
+                GlobalScheduler::new(node_config_manager)
+                    .with_limit_hits(Arc::clone(&limit_hits))
+                    .with_sink(Arc::clone(&sink)),
+            ))),
             workload_store,
             fault_notifier,
+            defaults: ScheduleOptions::default(),
+            advisory_debouncer: Arc::new(Debouncer::default()),
+            pending: Arc::default(),
+            shadows: Vec::new(),
+            shadow_log: Arc::default(),
+            evacuate_orphans: false,
+            events: Arc::default(),
+            naming: NamingPolicy::default(),
+            metadata: MetadataPolicy::default(),
+            range_check: RangeCheck::default(),
+            revision_factor: DEFAULT_REVISION_CHANGE_FACTOR,
+            max_request_bytes: DEFAULT_MAX_REQUEST_BYTES,
+            allow_admission_overrides: false,
+            node_config_path: None,
+            config_generation: Arc::default(),
+            config_apply: Arc::default(),
+            apply_watchdog: None,
+            apply_failover: false,
+            compaction: None,
+            push_epoch: None,
+            reclaimed: Arc::default(),
+            sensitive_label_keys: BTreeSet::new(),
+            utilization: Arc::default(),
+            phase_metrics: Arc::default(),
+            prepare_timeout: None,
+            log_filter: None,
+            limit_hits,
+            sink,
         }
     }
-}
 
-// ── Proto → Task conversion ───────────────────────────────────────────────────
+    /// The scheduler for the current node configuration.
+    fn scheduler(&self) -> Arc<GlobalScheduler> {
+        Arc::clone(&self.scheduler.read().unwrap_or_else(|e| e.into_inner()))
+    }
 
-/// Convert a proto `TaskInfo` into an internal `Task`.
-///
-/// `workload_id` comes from the enclosing `SchedInfo` message; every task in
-/// one RPC call shares the same value.
-fn task_from_proto(t: &TaskInfo, workload_id: &str) -> Task {
-    Task {
-        name: t.name.clone(),
-        workload_id: workload_id.to_owned(),
-        // node_id in the proto is the preferred/required target node.
-        target_node: t.node_id.clone(),
-        policy: SchedPolicy::from_proto_int(t.policy),
-        priority: t.priority,
-        affinity: CpuAffinity::from_proto(t.cpu_affinity),
-        period_us: t.period.max(0) as u64,
-        runtime_us: t.runtime.max(0) as u64,
-        deadline_us: t.deadline.max(0) as u64,
-        release_time_us: t.release_time.max(0) as u32,
-        max_dmiss: t.max_dmiss,
-        memory_mb: 0, // not in proto yet — dormant (D-003)
-        ..Task::default()
+    /// Report the schedulers' per-task events and this service's audit
+    /// events to `sink` instead of logging them.  Wrap [`TracingSink`] and
+    /// `sink` in a [`FanOut`](crate::scheduler::FanOut) to keep the log.
+    pub fn with_event_sink(mut self, sink: Arc<dyn ScheduleEventSink>) -> Self {
+        let scheduler = GlobalScheduler::clone(&self.scheduler()).with_sink(Arc::clone(&sink));
+        self.scheduler = Arc::new(RwLock::new(Arc::new(scheduler)));
+        self.sink = sink;
+        self
     }
-}
 
-// ── SchedInfoService implementation ──────────────────────────────────────────
+    fn audit(&self, event: AuditEvent<'_>) {
+        self.sink.audit(&event);
+    }
 
-#[tonic::async_trait]
-impl SchedInfoService for SchedInfoServiceImpl {
-    async fn add_sched_info(
-        &self,
-        request: Request<SchedInfo>,
-    ) -> Result<Response<ProtoResponse>, Status> {
-        let req = request.into_inner();
-        let workload_id = req.workload_id.clone();
+    /// Re-place orphaned tasks on the configured nodes after a reload (see
+    /// the module docs).
+    pub fn with_orphan_evacuation(mut self, enabled: bool) -> Self {
+        self.evacuate_orphans = enabled;
+        self
+    }
 
-        info!(
-            workload_id = %workload_id,
-            task_count  = req.tasks.len(),
-            "AddSchedInfo received"
-        );
+    /// Shadow-schedule every admitted workload with `algorithms` (see
+    /// [`crate::report::shadow`]).  A shadow equal to the request's own
+    /// algorithm is skipped.
+    pub fn with_shadow_algorithms(
+        mut self,
+        algorithms: impl IntoIterator<Item = SchedAlgorithm>,
+    ) -> Self {
+        self.shadows = algorithms.into_iter().collect();
+        self.shadows.sort_by_key(|a| a.as_str());
+        self.shadows.dedup();
+        self
+    }
 
-        // Log per-task details at debug level (mirrors C++ TLOG_DEBUG block).
-        for (i, t) in req.tasks.iter().enumerate() {
-            tracing::debug!(
-                idx          = i,
-                name         = %t.name,
-                node_id      = %t.node_id,
-                priority     = t.priority,
-                cpu_affinity = %format!("0x{:016x}", t.cpu_affinity),
-                period_us    = t.period,
-                runtime_us   = t.runtime,
-                deadline_us  = t.deadline,
-                "task"
-            );
+    /// Recent shadow comparisons, oldest first.
+    pub fn shadow_comparisons(&self) -> Vec<ShadowComparison> {
+        self.shadow_log.snapshot()
+    }
+
+    /// Report `filter` in `ClusterStatus.log_filter` and let the
+    /// AdminService change it (see [`super::log_filter`]).
+    pub fn with_log_filter(mut self, filter: LogFilter) -> Self {
+        self.log_filter = Some(filter);
+        self
+    }
+
+    /// The log filter passed to [`with_log_filter`](Self::with_log_filter).
+    pub fn log_filter(&self) -> Option<&LogFilter> {
+        self.log_filter.as_ref()
+    }
+
+    /// Record schedule changes in `events` (see the module docs).  Share it
+    /// with `NodeService` so one stream carries both services' events.
+    pub fn with_event_log(mut self, events: Arc<EventLog>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Check `workload_id` and task names against `policy` instead of the
+    /// default `[A-Za-z0-9_.-]{1,64}`.
+    pub fn with_naming_policy(mut self, policy: NamingPolicy) -> Self {
+        self.naming = policy;
+        self
+    }
+
+    /// Check task metadata against `policy`'s limits and forward its keys
+    /// to the audit log and faults.
+    pub fn with_metadata_policy(mut self, policy: MetadataPolicy) -> Self {
+        self.metadata = policy;
+        self
+    }
+
+    /// Reject or clamp a priority or `max_dmiss` outside its range (see the
+    /// module docs); rejected by default.
+    pub fn with_range_check(mut self, check: RangeCheck) -> Self {
+        self.range_check = check;
+        self
+    }
+
+    /// Refuse revisions that change a task's period or runtime by more than
+    /// `factor` without `force` (see the module docs).  Values below 1 are
+    /// treated as 1.
+    pub fn with_revision_change_factor(mut self, factor: f64) -> Self {
+        self.revision_factor = factor.max(1.0);
+        self
+    }
+
+    /// Report `bytes` as the largest accepted request in `GetCapabilities`.
+    /// The caller applies the same limit to the server
+    /// (`max_decoding_message_size`).
+    pub fn with_max_request_bytes(mut self, bytes: usize) -> Self {
+        self.max_request_bytes = bytes;
+        self
+    }
+
+    /// Honour `SchedInfo.admission_overrides` (see the module docs).  Off by
+    /// default, which rejects any request that sets one.
+    pub fn with_admission_overrides(mut self, allowed: bool) -> Self {
+        self.allow_admission_overrides = allowed;
+        self
+    }
+
+    /// Persist configurations applied by `ApplyNodeConfig` to `path` (the
+    /// `--node-config` file).  Without one they last until restart.
+    pub fn with_node_config_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.node_config_path = Some(path.into());
+        self
+    }
+
+    /// Give nodes `watchdog`'s deadline to apply each new generation (see
+    /// the module docs).  Share it with `NodeService`, which acknowledges.
+    pub fn with_apply_watchdog(mut self, watchdog: Arc<ApplyWatchdog>) -> Self {
+        self.apply_watchdog = Some(watchdog);
+        self
+    }
+
+    /// Re-place a workload's tasks off a node that missed its apply
+    /// deadline (see the module docs).  Needs an apply watchdog.
+    pub fn with_apply_failover(mut self, enabled: bool) -> Self {
+        self.apply_failover = enabled;
+        self
+    }
+
+    /// Start the apply deadlines of `tenant`'s new generation, except on
+    /// `excluded` nodes, which are being emptied.
+    fn arm_apply_deadlines(&self, tenant: &str, ws: &WorkloadState, excluded: &BTreeSet<String>) {
+        if let Some(watchdog) = &self.apply_watchdog {
+            let nodes = ws
+                .nodes_to_apply()
+                .into_iter()
+                .filter(|n| !excluded.contains(*n));
+            watchdog.arm(tenant, ws.generation, nodes);
         }
+    }
 
-        // ── 1. Convert proto tasks to internal representation ─────────────────
-        let tasks: Vec<Task> = req
-            .tasks
+    /// Bound the pending queue to `capacity` workloads.
+    pub fn with_pending_capacity(mut self, capacity: usize) -> Self {
+        self.pending = Arc::new(Mutex::new(PendingQueue::new(capacity)));
+        self
+    }
+
+    /// Set the window within which repeated feasibility advisories for the
+    /// same (workload, node, cpu) are suppressed.
+    pub fn with_advisory_window(mut self, window: Duration) -> Self {
+        self.advisory_debouncer = Arc::new(Debouncer::new(window));
+        self
+    }
+
+    /// Send `warnings` and simulated `misses` to Pullpiri in the background,
+    /// skipping any that the debouncer has seen recently.
+    ///
+    /// Debouncing happens synchronously so back-to-back requests are
+    /// deduplicated deterministically; only the RPCs are deferred.
+    fn spawn_feasibility_advisories(
+        &self,
+        tenant: &str,
+        workload_id: &str,
+        warnings: Vec<FeasibilityWarning>,
+        misses: Vec<SimulatedMiss>,
+    ) {
+        let fresh: Vec<FaultNotification> = warnings
+            .into_iter()
+            .filter(|w| {
+                let key = format!("{tenant}/{workload_id}/{}/{}", w.node, w.cpu);
+                self.advisory_debouncer.should_send(&key)
+            })
+            .map(|w| advisory_from_warning(workload_id, w))
+            .chain(
+                misses
+                    .into_iter()
+                    .filter(|m| {
+                        let key = format!("{tenant}/{workload_id}/{}/{}/simulation", m.node, m.cpu);
+                        self.advisory_debouncer.should_send(&key)
+                    })
+                    .map(|m| advisory_from_miss(workload_id, m)),
+            )
+            .collect();
+        if fresh.is_empty() {
+            return;
+        }
+
+        let notifier = Arc::clone(&self.fault_notifier);
+        tokio::spawn(async move {
+            for n in fresh {
+                let (node, wl) = (n.node_id.clone(), n.workload_id.clone());
+                if let Err(e) = notifier.notify_fault(n).await {
+                    warn!(workload_id = %wl, node = %node, error = %e,
+                          "Failed to send feasibility advisory");
+                }
+            }
+        });
+    }
+
+    /// Replay `snapshot` with each shadow algorithm on a blocking thread and
+    /// record the comparisons.  Nothing is stored or sent to nodes.
+    fn spawn_shadow_runs(
+        &self,
+        tenant: &str,
+        workload_id: &str,
+        opts: &ScheduleOptions,
+        snapshot: ShadowSnapshot,
+    ) {
+        let shadows: Vec<SchedAlgorithm> = self
+            .shadows
             .iter()
-            .map(|t| task_from_proto(t, &workload_id))
+            .copied()
+            .filter(|&a| a != opts.algorithm)
             .collect();
+        if shadows.is_empty() {
+            return;
+        }
+
+        let scheduler = self.scheduler();
+        let log = Arc::clone(&self.shadow_log);
+        let (tenant, workload_id, opts) =
+            (tenant.to_string(), workload_id.to_string(), opts.clone());
+        tokio::task::spawn_blocking(move || {
+            let primary_stats =
+                ScheduleStats::of(&snapshot.primary, &opts).with_phase_timings(snapshot.timings);
+            for shadow in shadows {
+                let (result, timings) = match scheduler.schedule_profiled(
+                    Some(&snapshot.occupied),
+                    snapshot.tasks.clone(),
+                    &opts.clone().with_algorithm(shadow),
+                ) {
+                    Ok((schedule, timings)) => (Ok(schedule), timings),
+                    Err(e) => (Err(e), PhaseTimings::default()),
+                };
+                let mut outcome = ShadowOutcome::compare(&snapshot.primary, result, &opts);
+                if let ShadowOutcome::Placed { stats, .. } = &mut outcome {
+                    stats.phase_timings = timings;
+                }
+                let comparison = ShadowComparison {
+                    tenant: tenant.clone(),
+                    workload_id: workload_id.clone(),
+                    primary: opts.algorithm,
+                    shadow,
+                    primary_stats,
+                    outcome,
+                };
+                comparison.log();
+                log.push(comparison);
+            }
+        });
+    }
+
+    /// Schedule `req` for `tenant` around the other tenants' workloads and,
+    /// on success, make it the tenant's active workload.
+    ///
+    /// The store lock is held from the occupancy snapshot to the insert, so
+    /// concurrent admissions cannot double-book a CPU.
+    async fn admit(
+        &self,
+        tenant: &str,
+        req: &SchedInfo,
+        opts: &ScheduleOptions,
+    ) -> Result<Admitted, AdmitError> {
+        let workload_id = req.workload_id.clone();
+        let class = PriorityClass::from_proto(req.priority_class.as_deref())
+            .map_err(|e| AdmitError::Rejected(Some(e), Vec::new()))?;
+
+        // ── 1. Convert proto tasks to internal representation ─────────────────
+        let mut tasks = tasks_from_proto(req);
+        // Ranked over the whole workload here, so the stored tasks keep
+        // these priorities when a drain re-places only some of them.
+        assign_priorities(&mut tasks, opts.priority_ordering);
 
         // ── 2. Calculate hyperperiod ──────────────────────────────────────────
         // Create a fresh HyperperiodManager per call — we only need the result
         // once and storing it in WorkloadState.  The clone gives us ownership.
         let hyperperiod_info = {
-            let mut hp_mgr = HyperperiodManager::new();
+            let mut hp_mgr = HyperperiodManager::new()
+                .with_max_unique_periods(opts.limits.max_unique_periods)
+                .with_limit_hits(Arc::clone(&self.limit_hits));
             match hp_mgr.calculate_hyperperiod(&workload_id, &tasks) {
                 Ok(info) => info.clone(),
                 Err(e) => {
@@ -135,30 +922,97 @@ impl SchedInfoService for SchedInfoServiceImpl {
                         error = %e,
                         "Hyperperiod calculation failed"
                     );
-                    return Ok(Response::new(ProtoResponse { status: -1 }));
+                    return Err(AdmitError::Rejected(None, Vec::new()));
                 }
             }
         };
 
         info!(
             workload_id    = %workload_id,
-            hyperperiod_ms = hyperperiod_info.hyperperiod_us / 1_000,
+            hyperperiod    = %fmt_duration_us(hyperperiod_info.hyperperiod_us.as_u64()),
             task_count     = hyperperiod_info.task_count,
             "Hyperperiod calculated"
         );
 
-        // ── 3. Run GlobalScheduler ────────────────────────────────────────────
-        let schedule = match self.scheduler.schedule(tasks, "target_node_priority") {
-            Ok(s) => s,
+        let mut guard = self.workload_store.lock().await;
+        let scheduler = self.scheduler();
+
+        // ── 3. Run GlobalScheduler around the other tenants ───────────────────
+        let mut occupied = NodeSchedMap::new();
+        for (_, ws) in guard.iter().filter(|(t, _)| t.as_str() != tenant) {
+            for (node, node_tasks) in &ws.schedule {
+                occupied
+                    .entry(node.clone())
+                    .or_default()
+                    .extend(node_tasks.iter().cloned());
+            }
+        }
+        // A revision replacing the tenant's placeholders keeps their
+        // placement while it still fits (see `scheduler::warm_start`).
+        let warm: Vec<(String, PriorPlacement)> = guard
+            .get(tenant)
+            .into_iter()
+            .flat_map(|ws| ws.schedule.values().flatten())
+            .filter(|t| t.placeholder && tasks.iter().any(|n| n.name == t.name))
+            .map(|t| {
+                let prior = PriorPlacement {
+                    node: t.assigned_node.clone(),
+                    cpu: t.assigned_cpu,
+                };
+                (t.name.clone(), prior)
+            })
+            .collect();
+        let warm_opts;
+        let opts = if warm.is_empty() {
+            opts
+        } else {
+            warm_opts = opts.clone().with_warm_start(warm);
+            &warm_opts
+        };
+        // Each link of an algorithm chain starts from the same occupancy.
+        let run = run_chain(opts, |link| {
+            scheduler.schedule_profiled(Some(&occupied), tasks.clone(), link)
+        });
+        if !opts.algorithm_chain.is_empty() {
+            for a in run.attempts.iter().filter(|a| a.error_code.is_some()) {
+                warn!(
+                    workload_id = %workload_id,
+                    algorithm   = %a.algorithm,
+                    error       = %a.error,
+                    "algorithm chain link failed"
+                );
+            }
+        }
+        let attempts = run.attempts;
+        let link_opts;
+        let (schedule, timings) = match run.result {
+            Ok((s, used)) => {
+                link_opts = used;
+                s
+            }
             Err(e) => {
+                // Capacity-limited iff the same request fits an idle cluster.
+                let capacity_limited = !occupied.is_empty()
+                    && run_chain(opts, |link| {
+                        scheduler.schedule_with_options(tasks.clone(), link)
+                    })
+                    .result
+                    .is_ok();
                 error!(
                     workload_id = %workload_id,
                     error = %e,
+                    capacity_limited,
                     "GlobalScheduler::schedule() failed"
                 );
-                return Ok(Response::new(ProtoResponse { status: -1 }));
+                return Err(if capacity_limited {
+                    AdmitError::CapacityLimited(e, attempts)
+                } else {
+                    AdmitError::Rejected(Some(e), attempts)
+                });
             }
         };
+        // The rest of the admission is the successful link's.
+        let opts = &link_opts;
 
         info!(
             workload_id = %workload_id,
@@ -168,179 +1022,4901 @@ impl SchedInfoService for SchedInfoServiceImpl {
         for (node, tasks) in &schedule {
             info!("  node '{node}': {} task(s)", tasks.len());
         }
+        for cap in scheduler.capacity_report_with(&schedule, opts).nodes {
+            info!(
+                node                = %cap.node,
+                total_free_pct      = cap.total_free * 100.0,
+                largest_slot_pct    = cap.largest_placeable * 100.0,
+                fragmentation_ratio = cap.fragmentation_ratio,
+                "Node capacity"
+            );
+        }
+        self.utilization
+            .record_admission(&occupied, &schedule, opts.cpu_utilization_threshold);
+        self.phase_metrics.record(&timings);
+        self.audit(AuditEvent::PhaseTimings {
+            tenant,
+            workload_id: &workload_id,
+            timings: &timings,
+        });
 
-        // ── 4. Store workload (brief lock) ────────────────────────────────────
-        {
-            let mut guard = self.workload_store.lock().await;
-
-            if let Some(prev) = guard.as_ref() {
+        // Strict simulation already ran inside the scheduler; warn mode is
+        // reported here, around the other tenants' placements.
+        let misses = if opts.verify_with_simulation == Some(SimulationCheck::Warn) {
+            let mut combined = occupied.clone();
+            for (node, node_tasks) in &schedule {
+                combined
+                    .entry(node.clone())
+                    .or_default()
+                    .extend(node_tasks.iter().cloned());
+            }
+            let misses = match verify_schedule(
+                &combined,
+                opts.utilization_epsilon,
+                opts.priority_ordering,
+                opts.simulation_hyperperiod_limit,
+                opts.simulation_max_jobs,
+            ) {
+                Ok(misses) => misses,
+                Err(e) => {
+                    // Too many jobs to simulate: nothing was checked, so
+                    // there is no advisory to admit the workload under.
+                    error!(
+                        workload_id = %workload_id,
+                        error = %e,
+                        "simulation not attempted"
+                    );
+                    return Err(AdmitError::Rejected(Some(e), attempts));
+                }
+            };
+            for m in &misses {
                 warn!(
-                    prev_workload = %prev.workload_id,
-                    new_workload  = %workload_id,
-                    "Replacing existing workload \
-                     (single-workload limitation — see DEVELOPER_NOTES D-016)"
+                    workload_id = %workload_id,
+                    node        = %m.node,
+                    cpu         = m.cpu,
+                    task        = %m.task,
+                    time        = %fmt_duration_us(m.time_us),
+                    "simulated deadline miss — admitted with an advisory"
                 );
-                // Wake all SyncTimer handlers waiting on the previous barrier.
+            }
+            misses
+        } else {
+            Vec::new()
+        };
+
+        let repro = ReproBundle::capture(
+            tenant,
+            req,
+            opts,
+            scheduler.node_config_manager(),
+            occupied.clone(),
+            schedule.clone(),
+        );
+        let margins = runtime_margins(&schedule, &occupied, opts);
+        let snapshot = (!self.shadows.is_empty()).then(|| ShadowSnapshot {
+            occupied,
+            tasks: tasks.clone(),
+            primary: schedule.clone(),
+            timings,
+        });
+
+        let warnings = check_schedule(&schedule, opts.utilization_epsilon, opts.priority_ordering);
+        let hints = honoured_hints(&schedule, scheduler.node_config_manager());
+        let placements = placements_of(&schedule, &margins, &hints);
+        let chains = chain_latencies(&analyse_chains(&schedule, &opts.chains));
+        let chain_misses = chains.iter().filter(|c| !c.met).count();
+        let mut summary =
+            workload_summary(&hyperperiod_info, &schedule, warnings.len() + chain_misses);
+        summary.chains = chains;
+        summary.admission_overrides = opts
+            .admission_overrides
+            .iter()
+            .map(|o| o.as_str().to_string())
+            .collect();
+        summary.min_runtime_margin = min_runtime_margin(&placements);
+        summary.priority_ordering = opts.priority_ordering.as_str().to_string();
+        let admitted = Admitted {
+            placements,
+            summary,
+            attempts,
+        };
+        info!(
+            summary = %render_summary(&admitted.summary, DurationStyle::default()),
+            "Workload summary"
+        );
+
+        // ── 4. Store workload ─────────────────────────────────────────────────
+        let prev = guard.remove(tenant);
+        let transactional = self.prepare_timeout.is_some()
+            && txn::changed_nodes(prev.as_ref().map(|p| p.published().schedule), &schedule).len()
+                > 1;
+        let hold = !transactional && self.holds_push(class);
+        if let Some(prev) = prev.as_ref() {
+            warn!(
+                tenant        = %tenant,
+                prev_workload = %prev.workload_id,
+                new_workload  = %workload_id,
+                "Replacing existing workload \
+                 (single-workload limitation — see DEVELOPER_NOTES D-016)"
+            );
+            // Wake all SyncTimer handlers waiting on the previous barrier;
+            // a transaction does so when it commits.
+            if !transactional {
                 let _ = prev.barrier_tx.send(BarrierStatus::Cancelled);
             }
+            if prev.workload_id != workload_id {
+                self.forget_workload(tenant, &prev.workload_id);
+            }
+        }
+
+        let (kind, cleared) = match prev.as_ref() {
+            Some(prev) => (
+                ScheduleEventKind::WorkloadUpdated,
+                prev.task_states.faulted().cloned().collect(),
+            ),
+            None => (ScheduleEventKind::WorkloadScheduled, Vec::new()),
+        };
 
-            *guard = Some(WorkloadState::new(
-                workload_id.clone(),
-                schedule,
-                hyperperiod_info,
-            ));
-        } // lock released here
+        let default_policy = opts.algorithm.default_target_policy();
+        let tasks = tasks
+            .into_iter()
+            .map(|t| Task {
+                target_node_policy: t.target_node_policy.or(default_policy),
+                ..t
+            })
+            .collect();
+        let ws = WorkloadState::new(workload_id.clone(), schedule, hyperperiod_info)
+            .with_tasks(tasks)
+            .with_priority_class(class)
+            .with_importance(req.importance)
+            .with_repro(repro)
+            .with_expiry(
+                req.ttl_seconds
+                    .and_then(|ttl| Instant::now().checked_add(Duration::from_secs(ttl))),
+            );
+        let mut ws = match prev {
+            _ if transactional => ws.preparing(prev),
+            Some(mut prev) => {
+                if hold {
+                    prev.hold();
+                }
+                ws.succeeding(prev)
+            }
+            None => ws,
+        };
+        if !hold && !transactional {
+            ws.publish();
+        }
+        guard.insert(tenant.to_string(), ws);
+        record_workload_change(&self.events, kind, tenant, &guard[tenant], cleared);
+        match &guard[tenant].txn {
+            Some(txn) => self.spawn_prepare_timeout(tenant, txn.id),
+            None if guard[tenant].held.is_none() => {
+                self.arm_apply_deadlines(tenant, &guard[tenant], &BTreeSet::new());
+            }
+            None => {}
+        }
+        let forwarded: BTreeMap<&str, Metadata> = guard[tenant]
+            .schedule
+            .values()
+            .flatten()
+            .map(|t| (t.name.as_str(), self.metadata.forwarded(&t.metadata)))
+            .filter(|(_, m)| !m.is_empty())
+            .collect();
+        if !forwarded.is_empty() {
+            self.audit(AuditEvent::TaskMetadata {
+                tenant,
+                workload_id: &workload_id,
+                metadata: &forwarded,
+            });
+        }
+        drop(guard);
 
         info!(workload_id = %workload_id, "Workload stored, awaiting node sync");
-        Ok(Response::new(ProtoResponse { status: 0 }))
-    }
-}
 
-// ── Tests ─────────────────────────────────────────────────────────────────────
+        // ── 5. Advisories (after the response is decided) ─────────────────────
+        if !opts.admission_overrides.is_empty() {
+            let skipped = admitted.summary.admission_overrides.join(",");
+            self.audit(AuditEvent::OverridesApplied {
+                tenant,
+                workload_id: &workload_id,
+                skipped: &skipped,
+            });
+            self.spawn_override_advisory(&workload_id, skipped);
+        }
+        self.spawn_feasibility_advisories(tenant, &workload_id, warnings, misses);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tonic::Request;
+        // ── 6. Shadow algorithms, compared and discarded ──────────────────────
+        if let Some(snapshot) = snapshot {
+            self.spawn_shadow_runs(tenant, &workload_id, opts, snapshot);
+        }
 
-    use crate::config::{NodeConfig, NodeConfigManager};
-    use crate::fault::{test_support::MockFaultNotifier, FaultNotifier};
-    use crate::grpc::{new_workload_store, BarrierStatus};
-    use crate::proto::schedinfo_v1::{
-        sched_info_service_server::SchedInfoService, SchedInfo, TaskInfo,
-    };
+        Ok(admitted)
+    }
 
-    // ── Helpers ───────────────────────────────────────────────────────────────
+    /// Retry queued workloads in order after capacity may have been released.
+    ///
+    /// Scheduled workloads leave the queue and Pullpiri receives a
+    /// `WORKLOAD_SCHEDULED` advisory; ones that are still capacity-limited
+    /// stay; ones that can no longer be placed at all are dropped.
+    async fn retry_pending(&self) {
+        let mut pending = self.pending.lock().await;
+        let queued: Vec<PendingWorkload> = pending.iter().cloned().collect();
+        for entry in queued {
+            let (tenant, workload_id) = (&entry.tenant, entry.workload_id());
+            let outcome = match self.resolve_options(&entry.request) {
+                Ok(opts) => self.admit(tenant, &entry.request, &opts).await,
+                Err(e) => Err(AdmitError::Rejected(Some(e), Vec::new())),
+            };
+            match outcome {
+                Ok(_) => {
+                    pending.remove_tenant(tenant);
+                    self.audit(AuditEvent::QueuedScheduled {
+                        tenant,
+                        workload_id,
+                        waited_ms: entry.age().as_millis() as u64,
+                    });
+                    self.spawn_scheduled_notice(workload_id);
+                }
+                Err(AdmitError::CapacityLimited(..)) => {}
+                Err(AdmitError::Rejected(..)) => {
+                    pending.remove_tenant(tenant);
+                    warn!(
+                        tenant      = %tenant,
+                        workload_id = %workload_id,
+                        "queued workload can no longer be scheduled — dropped"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Tell Pullpiri in the background that a queued workload is now active.
+    fn spawn_scheduled_notice(&self, workload_id: &str) {
+        let notification = FaultNotification {
+            workload_id: workload_id.to_string(),
+            node_id: String::new(),
+            task_name: String::new(),
+            fault_type: FaultType::WorkloadScheduled,
+            severity: FaultSeverity::Advisory,
+            feasibility: None,
+            metadata: Metadata::new(),
+        };
+        let notifier = Arc::clone(&self.fault_notifier);
+        tokio::spawn(async move {
+            let wl = notification.workload_id.clone();
+            if let Err(e) = notifier.notify_fault(notification).await {
+                warn!(workload_id = %wl, error = %e, "Failed to send scheduled notice");
+            }
+        });
+    }
+
+    /// Tell Pullpiri in the background that `workload_id` was admitted with
+    /// the `skipped` admission checks off.  Never debounced.
+    fn spawn_override_advisory(&self, workload_id: &str, skipped: String) {
+        let notification = FaultNotification {
+            workload_id: workload_id.to_string(),
+            node_id: String::new(),
+            task_name: String::new(),
+            fault_type: FaultType::AdmissionOverride,
+            severity: FaultSeverity::Advisory,
+            feasibility: None,
+            metadata: Metadata::from([(ADMISSION_OVERRIDES_METADATA_KEY.to_string(), skipped)]),
+        };
+        let notifier = Arc::clone(&self.fault_notifier);
+        tokio::spawn(async move {
+            let wl = notification.workload_id.clone();
+            if let Err(e) = notifier.notify_fault(notification).await {
+                warn!(workload_id = %wl, error = %e, "Failed to send admission override advisory");
+            }
+        });
+    }
+
+    /// Cordon (`true`) or uncordon `node` (see the module docs).  Returns
+    /// whether the state changed, or `None` if the node is not configured.
+    pub fn set_node_cordoned(&self, node: &str, cordoned: bool) -> Option<bool> {
+        // Under the read lock so a concurrent reload sees the change.
+        let scheduler = self.scheduler.read().unwrap_or_else(|e| e.into_inner());
+        let changed = scheduler
+            .node_config_manager()
+            .set_cordoned(node, cordoned)?;
+        drop(scheduler);
+        if changed {
+            self.audit(AuditEvent::CordonChanged { node, cordoned });
+            let kind = if cordoned {
+                ScheduleEventKind::NodeCordoned
+            } else {
+                ScheduleEventKind::NodeUncordoned
+            };
+            self.events.record(ScheduleEvent {
+                node: node.to_string(),
+                ..event(kind)
+            });
+        }
+        Some(changed)
+    }
+
+    /// Move up to `batch_size` tasks off `node` (see the module docs).
+    ///
+    /// A step is all-or-nothing: if the batch cannot be placed elsewhere the
+    /// error is returned and no workload changes.
+    pub async fn drain_node(
+        &self,
+        node: &str,
+        batch_size: usize,
+    ) -> Result<DrainProgress, SchedulerError> {
+        let mut guard = self.workload_store.lock().await;
+
+        // ── 1. Pick the batch ─────────────────────────────────────────────────
+        let mut pinned = Vec::new();
+        let mut movable: Vec<((PriorityClass, i32, i32), &str, &Task)> = Vec::new();
+        for (tenant, ws) in guard.iter() {
+            for placed in ws.schedule.get(node).into_iter().flatten() {
+                match ws.tasks.iter().find(|t| t.name == placed.name) {
+                    Some(t)
+                        if !(t.target_node == node
+                            && t.target_node_policy == Some(TargetNodePolicy::Hard)) =>
+                    {
+                        let rank = (ws.priority_class, ws.importance, t.priority);
+                        movable.push((rank, tenant, t));
+                    }
+                    _ => pinned.push((tenant.clone(), placed.name.clone())),
+                }
+            }
+        }
+        pinned.sort();
+        movable.sort_by(|a, b| (a.0, a.1, &a.2.name).cmp(&(b.0, b.1, &b.2.name)));
+        let remaining = movable.len().saturating_sub(batch_size);
+
+        let mut batch: BTreeMap<String, Vec<Task>> = BTreeMap::new();
+        for (_, tenant, t) in movable.into_iter().take(batch_size) {
+            let mut task = t.clone();
+            if task.target_node == node {
+                task.target_node.clear();
+            }
+            batch.entry(tenant.to_string()).or_default().push(task);
+        }
+        if batch.is_empty() {
+            return Ok(DrainProgress {
+                pinned,
+                ..Default::default()
+            });
+        }
+
+        // ── 2. Re-place it elsewhere and commit ───────────────────────────────
+        let tenants: Vec<String> = batch.keys().cloned().collect();
+        let moved = self
+            .reschedule_excluding(&mut guard, batch, &BTreeSet::from([node.to_string()]))
+            .inspect_err(|e| error!(node = %node, error = %e, "drain step failed"))?;
+        for tenant in tenants {
+            let ws = &guard[&tenant];
+            self.audit(AuditEvent::TasksDrained {
+                tenant: &tenant,
+                workload_id: &ws.workload_id,
+                node,
+                generation: ws.generation,
+            });
+        }
+        if remaining == 0 {
+            self.events.record(ScheduleEvent {
+                node: node.to_string(),
+                ..event(ScheduleEventKind::NodeDrained)
+            });
+        }
+
+        Ok(DrainProgress {
+            moved,
+            pinned,
+            remaining,
+        })
+    }
+
+    /// Give `task` of `tenant`'s workload `workload_id` a new `timing`,
+    /// moving as little as possible (see the module docs).  On error
+    /// nothing changes.
+    pub async fn update_task(
+        &self,
+        tenant: &str,
+        workload_id: &str,
+        task: &str,
+        timing: TaskTiming,
+    ) -> Result<TaskUpdateOutcome, TaskUpdateError> {
+        let scheduler = self.scheduler();
+        let mut guard = self.workload_store.lock().await;
+        let mut others = NodeSchedMap::new();
+        for (_, ws) in guard.iter().filter(|(t, _)| *t != tenant) {
+            for (n, node_tasks) in &ws.schedule {
+                others
+                    .entry(n.clone())
+                    .or_default()
+                    .extend(node_tasks.iter().cloned());
+            }
+        }
+        let ws = guard
+            .get_mut(tenant)
+            .filter(|ws| ws.workload_id == workload_id)
+            .ok_or_else(|| TaskUpdateError::UnknownWorkload(workload_id.to_string()))?;
+        let updated = ws
+            .tasks
+            .iter()
+            .find(|t| t.name == task)
+            .map(|t| timing.applied_to(t))
+            .ok_or_else(|| TaskUpdateError::UnknownTask {
+                workload: workload_id.to_string(),
+                task: task.to_string(),
+            })?;
+        let tasks: Vec<Task> = ws
+            .tasks
+            .iter()
+            .map(|t| {
+                if t.name == task {
+                    updated.clone()
+                } else {
+                    t.clone()
+                }
+            })
+            .collect();
+        let hyperperiod = HyperperiodManager::new()
+            .with_max_unique_periods(self.defaults.limits.max_unique_periods)
+            .with_limit_hits(Arc::clone(&self.limit_hits))
+            .calculate_hyperperiod(workload_id, &tasks)?
+            .clone();
+        let update = scheduler
+            .update_task(&others, &ws.schedule, &ws.tasks, &updated, &self.defaults)
+            .inspect_err(|e| {
+                warn!(workload_id = %workload_id, task = %task, error = %e, "task update failed");
+            })?;
+
+        let hints = honoured_hints(&update.schedule, scheduler.node_config_manager());
+        let margins = runtime_margins(&update.schedule, &others, &self.defaults);
+        let placement = placements_of(&update.schedule, &margins, &hints)
+            .into_iter()
+            .find(|p| p.task == task)
+            .expect("the updated task is placed");
+        ws.tasks = tasks;
+        ws.hyperperiod = hyperperiod;
+        self.commit_reschedule(tenant, ws, update.schedule, &BTreeSet::new());
+        self.audit(AuditEvent::TaskUpdated {
+            tenant,
+            workload_id,
+            task,
+            level: update.level,
+            node: &placement.node,
+            cpu: placement.cpu,
+            generation: ws.generation,
+        });
+        Ok(TaskUpdateOutcome {
+            level: update.level,
+            placement,
+            generation: ws.generation,
+        })
+    }
+
+    /// The repro bundle of the stored workload `workload_id`, redacted, or
+    /// `None` if no tenant has it (see the module docs).
+    pub async fn capture_repro(&self, workload_id: &str) -> Option<ReproBundle> {
+        let guard = self.workload_store.lock().await;
+        let bundle = guard
+            .values()
+            .find(|ws| ws.workload_id == workload_id)?
+            .repro
+            .clone()?;
+        drop(guard);
+        Some(bundle.redacted(&self.sensitive_label_keys))
+    }
+
+    /// Utilisation metrics of the admissions so far.
+    pub fn utilization_metrics(&self) -> MetricsSnapshot {
+        self.utilization.snapshot()
+    }
+
+    /// Where the admissions so far spent their scheduling time.
+    pub fn phase_metrics(&self) -> PhaseMetricsSnapshot {
+        self.phase_metrics.snapshot()
+    }
+
+    /// What losing `node` would do to every tenant's placements, or `None`
+    /// if the node is not configured (see the module docs).  Read-only.
+    pub async fn what_if_node_loss(&self, node: &str) -> Option<WhatIfReport> {
+        let scheduler = self.scheduler();
+        scheduler.node_config_manager().get_node_config(node)?;
+        let guard = self.workload_store.lock().await;
+
+        let mut placements = NodeSchedMap::new();
+        let mut lost: Vec<((PriorityClass, i32, i32), LostTask)> = Vec::new();
+        for (tenant, ws) in guard.iter() {
+            for (n, tasks) in &ws.schedule {
+                placements
+                    .entry(n.clone())
+                    .or_default()
+                    .extend(tasks.iter().cloned());
+            }
+            for placed in ws.schedule.get(node).into_iter().flatten() {
+                let task = match ws.tasks.iter().find(|t| t.name == placed.name) {
+                    Some(t) => t.clone(),
+                    None => Task {
+                        workload_id: ws.workload_id.clone(),
+                        ..GlobalScheduler::replacement_task(placed)
+                    },
+                };
+                let rank = (ws.priority_class, ws.importance, task.priority);
+                lost.push((
+                    rank,
+                    LostTask {
+                        tenant: tenant.clone(),
+                        task,
+                        cpu: placed.assigned_cpu,
+                    },
+                ));
+            }
+        }
+        drop(guard);
+        lost.sort_by(|a, b| (a.0, &a.1.task.name).cmp(&(b.0, &b.1.task.name)));
+
+        let lost = lost.into_iter().map(|(_, t)| t).collect();
+        Some(scheduler.what_if_node_loss(node, &placements, lost, &self.defaults))
+    }
+
+    /// Re-place `batch` (tenant → tasks of its workload) on the configured
+    /// nodes outside `excluded`, around everything else that is placed, and
+    /// commit each affected workload as its next generation.
+    ///
+    /// The batch tasks leave whichever node they are on now.  All-or-nothing:
+    /// on error no workload changes.
+    fn reschedule_excluding(
+        &self,
+        workloads: &mut HashMap<String, WorkloadState>,
+        batch: BTreeMap<String, Vec<Task>>,
+        excluded: &BTreeSet<String>,
+    ) -> Result<Vec<TaskPlacement>, SchedulerError> {
+        let scheduler = self.scheduler();
+        let mut schedules: BTreeMap<String, NodeSchedMap> = workloads
+            .iter()
+            .map(|(tenant, ws)| (tenant.clone(), ws.schedule.clone()))
+            .collect();
+        for (tenant, tasks) in &batch {
+            let schedule = schedules
+                .get_mut(tenant)
+                .expect("batch tenant has a schedule");
+            for on_node in schedule.values_mut() {
+                on_node.retain(|p| !tasks.iter().any(|t| t.name == p.name));
+            }
+            schedule.retain(|_, on_node| !on_node.is_empty());
+        }
+
+        let opts = self
+            .defaults
+            .clone()
+            .with_algorithm(SchedAlgorithm::LeastLoaded)
+            .with_allowed_nodes(
+                scheduler
+                    .node_ids()
+                    .into_iter()
+                    .filter(|n| !excluded.contains(n)),
+            );
+        let mut moved = Vec::new();
+        let tenants: Vec<String> = batch.keys().cloned().collect();
+        for (tenant, tasks) in batch {
+            let mut occupied = NodeSchedMap::new();
+            for schedule in schedules.values() {
+                for (n, node_tasks) in schedule {
+                    occupied
+                        .entry(n.clone())
+                        .or_default()
+                        .extend(node_tasks.iter().cloned());
+                }
+            }
+            let placed = scheduler.schedule_with_occupancy(&occupied, tasks, &opts)?;
+            let margins = runtime_margins(&placed, &occupied, &opts);
+            let schedule = schedules
+                .get_mut(&tenant)
+                .expect("batch tenant has a schedule");
+            for (n, node_tasks) in &placed {
+                schedule
+                    .entry(n.clone())
+                    .or_default()
+                    .extend(node_tasks.iter().cloned());
+            }
+            // Cache mates that stayed put count too.
+            let hints = honoured_hints(schedule, scheduler.node_config_manager());
+            moved.extend(placements_of(&placed, &margins, &hints));
+        }
+
+        for tenant in tenants {
+            let ws = workloads
+                .get_mut(&tenant)
+                .expect("batch tenant has a workload");
+            let schedule = schedules.remove(&tenant).unwrap_or_default();
+            self.commit_reschedule(&tenant, ws, schedule, excluded);
+        }
+        Ok(moved)
+    }
+
+    /// Move `ws` to its next generation with `schedule`, record the change
+    /// and arm apply deadlines outside `excluded`.
+    fn commit_reschedule(
+        &self,
+        tenant: &str,
+        ws: &mut WorkloadState,
+        schedule: NodeSchedMap,
+        excluded: &BTreeSet<String>,
+    ) {
+        let faulted: Vec<(String, String)> = ws.task_states.faulted().cloned().collect();
+        ws.reschedule(schedule);
+        let cleared = faulted
+            .into_iter()
+            .filter(|(n, t)| ws.task_states.get(n, t) != Some(TaskState::Faulted))
+            .collect();
+        record_workload_change(
+            &self.events,
+            ScheduleEventKind::WorkloadUpdated,
+            tenant,
+            ws,
+            cleared,
+        );
+        self.arm_apply_deadlines(tenant, ws, excluded);
+    }
+
+    /// Propose a compaction once placements have not changed for `idle`
+    /// (see the module docs).
+    pub fn with_compaction_idle(mut self, idle: Duration) -> Self {
+        self.compaction = Some(Arc::new(Compactor::new(idle)));
+        self
+    }
+
+    /// Publish workload updates to nodes at multiples of `epoch` (see
+    /// [`super::epoch`]); run [`run_push_epochs`](Self::run_push_epochs)
+    /// to flush them.
+    pub fn with_push_epoch(mut self, epoch: Duration) -> Self {
+        self.push_epoch = Some(epoch);
+        self
+    }
+
+    /// Redact the values of these label keys from repro bundles (see
+    /// [`super::repro`]).
+    pub fn with_sensitive_label_keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.sensitive_label_keys = keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Prepare replacements that change more than one node on those nodes
+    /// before publishing them, aborting if they have not all staged it
+    /// within `timeout` (see [`super::txn`]).
+    pub fn with_transactional_push(mut self, timeout: Duration) -> Self {
+        self.prepare_timeout = Some(timeout);
+        self
+    }
+
+    /// Abort `tenant`'s transaction `id` if it is still preparing once the
+    /// prepare timeout has passed.
+    fn spawn_prepare_timeout(&self, tenant: &str, id: u64) {
+        let Some(timeout) = self.prepare_timeout else {
+            return;
+        };
+        let svc = self.clone();
+        let tenant = tenant.to_string();
+        tokio::spawn(async move {
+            time::sleep(timeout).await;
+            let mut guard = svc.workload_store.lock().await;
+            let Some(txn) = guard
+                .get(&tenant)
+                .and_then(|ws| ws.txn.as_ref())
+                .filter(|txn| txn.id == id)
+            else {
+                return;
+            };
+            let waiting: Vec<&str> = txn.waiting().collect();
+            let reason = format!(
+                "prepare timed out after {} ms waiting for {}",
+                timeout.as_millis(),
+                waiting.join(", ")
+            );
+            let decided = txn::abort(&mut guard, &tenant, reason);
+            drop(guard);
+            if let Some(decided) = decided {
+                txn::report(
+                    decided,
+                    &svc.events,
+                    svc.apply_watchdog.as_deref(),
+                    &svc.fault_notifier,
+                );
+            }
+        });
+    }
+
+    /// Whether an update of a `class` workload waits for the next push
+    /// epoch.
+    fn holds_push(&self, class: PriorityClass) -> bool {
+        self.push_epoch.is_some() && class < PriorityClass::Safety
+    }
+
+    /// Publish every held update (see [`super::epoch`]) and start its apply
+    /// deadlines.  Returns the `(tenant, generation)` pairs published.
+    pub async fn flush_pushes(&self) -> Vec<(String, u64)> {
+        let mut guard = self.workload_store.lock().await;
+        let mut published = Vec::new();
+        for (tenant, ws) in guard.iter_mut() {
+            // A transaction publishes when it commits.
+            if ws.txn.is_some() || !ws.publish() {
+                continue;
+            }
+            info!(
+                tenant      = %tenant,
+                workload_id = %ws.workload_id,
+                generation  = ws.generation,
+                "held update published"
+            );
+            self.arm_apply_deadlines(tenant, ws, &BTreeSet::new());
+            published.push((tenant.clone(), ws.generation));
+        }
+        published.sort();
+        published
+    }
+
+    /// Flush held updates at every push-epoch boundary until `shutdown`
+    /// turns `true`, then once more.  Returns at once without a push epoch.
+    pub async fn run_push_epochs(&self, mut shutdown: watch::Receiver<bool>) {
+        let Some(epoch) = self.push_epoch else {
+            return;
+        };
+        let start = time::Instant::now() + until_boundary(epoch, SystemTime::now());
+        let mut tick = time::interval_at(start, epoch);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        while !*shutdown.borrow() {
+            tokio::select! {
+                _ = tick.tick() => {
+                    self.flush_pushes().await;
+                }
+                _ = shutdown.changed() => {}
+            }
+        }
+        self.flush_pushes().await;
+    }
+
+    /// What a compaction proposal made now would be based on.
+    fn compaction_basis(&self, workloads: &HashMap<String, WorkloadState>) -> Basis {
+        Basis::of(
+            workloads,
+            self.config_generation.load(Ordering::SeqCst),
+            self.scheduler().node_config_manager().cordoned_nodes(),
+        )
+    }
+
+    /// Look for a better layout if the cluster has been idle long enough
+    /// (see the module docs).  Returns the new proposal, if any.
+    pub async fn propose_compaction(&self) -> Option<Arc<Compaction>> {
+        self.propose_compaction_at(Instant::now()).await
+    }
+
+    /// [`propose_compaction`](Self::propose_compaction) at `now`.
+    pub async fn propose_compaction_at(&self, now: Instant) -> Option<Arc<Compaction>> {
+        let compactor = self.compaction.as_ref()?;
+        let guard = self.workload_store.lock().await;
+        let basis = self.compaction_basis(&guard);
+        if !compactor.observe(&basis, now) {
+            return None;
+        }
+        let plan = compaction::plan(&self.scheduler(), &guard, basis, &self.defaults, now);
+        let proposal = match plan {
+            Ok(c) if c.improves() => Some(c),
+            Ok(_) => None,
+            Err(e) => {
+                info!(error = %e, "compaction: stored workloads cannot all be re-placed");
+                None
+            }
+        };
+        let proposal = compactor.planned(proposal)?;
+        self.audit(AuditEvent::CompactionProposed {
+            proposal_id: proposal.id,
+            moves: proposal.moves.len(),
+            nodes_freed: &proposal.nodes_freed,
+            nodes_before: proposal.nodes_in_use_before,
+            nodes_after: proposal.nodes_in_use_after,
+            peak_before_pct: proposal.peak_before.as_f64() * 100.0,
+            peak_after_pct: proposal.peak_after.as_f64() * 100.0,
+        });
+        self.events.record(ScheduleEvent {
+            proposal_id: proposal.id,
+            ..event(ScheduleEventKind::CompactionProposed)
+        });
+        Some(proposal)
+    }
+
+    /// Apply the current compaction proposal `proposal_id` (see the module
+    /// docs).  Returns the applied proposal.
+    pub async fn apply_compaction(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Arc<Compaction>, CompactionError> {
+        let compactor = self
+            .compaction
+            .as_ref()
+            .ok_or(CompactionError::Unknown(proposal_id))?;
+        let mut guard = self.workload_store.lock().await;
+        let basis = self.compaction_basis(&guard);
+        let proposal = compactor.take(proposal_id, &basis)?;
+        for (tenant, schedule) in &proposal.proposed {
+            let ws = guard
+                .get_mut(tenant)
+                .expect("proposal tenants match the store");
+            if ws.schedule == *schedule {
+                continue;
+            }
+            self.commit_reschedule(tenant, ws, schedule.clone(), &BTreeSet::new());
+            self.audit(AuditEvent::WorkloadCompacted {
+                tenant,
+                workload_id: &ws.workload_id,
+                generation: ws.generation,
+                proposal_id,
+            });
+        }
+        Ok(proposal)
+    }
+
+    /// Switch to `config` and reconcile the stored workloads with it (see
+    /// the module docs).  Cordon overrides carry over.
+    pub async fn reload_config(&self, config: Arc<NodeConfigManager>) -> ReconcileReport {
+        let scheduler = Arc::new(
+            GlobalScheduler::new(config)
+                .with_limit_hits(Arc::clone(&self.limit_hits))
+                .with_sink(Arc::clone(&self.sink)),
+        );
+        let configured = scheduler.node_ids();
+        {
+            let mut current = self.scheduler.write().unwrap_or_else(|e| e.into_inner());
+            scheduler
+                .node_config_manager()
+                .adopt_cordons(current.node_config_manager());
+            *current = scheduler;
+        }
+        let generation = self.config_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.audit(AuditEvent::ConfigReloaded {
+            nodes: &configured,
+            generation,
+        });
+
+        let mut guard = self.workload_store.lock().await;
+        let mut report = ReconcileReport {
+            generation,
+            ..Default::default()
+        };
+
+        // ── 1. Evacuate what can move, one tenant at a time ───────────────────
+        if self.evacuate_orphans {
+            let mut tenants: Vec<String> = guard.keys().cloned().collect();
+            tenants.sort();
+            for tenant in tenants {
+                let ws = &guard[&tenant];
+                let movable: Vec<Task> = orphans_of(ws, &configured)
+                    .filter_map(|(node, name)| {
+                        let task = ws.tasks.iter().find(|t| t.name == name)?;
+                        movable_off(task, node)
+                    })
+                    .collect();
+                if movable.is_empty() {
+                    continue;
+                }
+                let batch = BTreeMap::from([(tenant.clone(), movable)]);
+                match self.reschedule_excluding(&mut guard, batch, &BTreeSet::new()) {
+                    Ok(moved) => {
+                        let ws = &guard[&tenant];
+                        self.audit(AuditEvent::OrphansEvacuated {
+                            tenant: &tenant,
+                            workload_id: &ws.workload_id,
+                            generation: ws.generation,
+                            moved: moved.len(),
+                        });
+                        report.evacuated.extend(moved);
+                    }
+                    Err(e) => warn!(
+                        tenant = %tenant,
+                        error  = %e,
+                        "orphaned tasks could not be evacuated — left in place"
+                    ),
+                }
+            }
+        }
+
+        // ── 2. Report what is still orphaned ──────────────────────────────────
+        for (tenant, ws) in guard.iter() {
+            report
+                .orphaned
+                .extend(
+                    orphans_of(ws, &configured).map(|(node, task)| OrphanedPlacement {
+                        tenant: tenant.clone(),
+                        workload_id: ws.workload_id.clone(),
+                        node: node.to_string(),
+                        task: task.to_string(),
+                    }),
+                );
+        }
+        drop(guard);
+        report.orphaned.sort();
+
+        let mut advised = BTreeSet::new();
+        for o in &report.orphaned {
+            self.audit(AuditEvent::TaskOrphaned {
+                tenant: &o.tenant,
+                workload_id: &o.workload_id,
+                node: &o.node,
+                task: &o.task,
+            });
+            if advised.insert((o.workload_id.as_str(), o.node.as_str())) {
+                self.spawn_orphan_advisory(&o.workload_id, &o.node);
+            }
+        }
+        report
+    }
+
+    /// Load `yaml` as the node configuration, persist it and reconcile the
+    /// stored workloads with it (see the module docs).
+    ///
+    /// # Errors
+    /// The [`ConfigError`] that refused `yaml`, or [`ConfigError::Io`] if it
+    /// could not be persisted.  Either way the running configuration is
+    /// unchanged.
+    pub async fn apply_config_yaml(&self, yaml: &str) -> Result<ReconcileReport, ConfigError> {
+        let _apply = self.config_apply.lock().await;
+        let source = self
+            .node_config_path
+            .as_deref()
+            .unwrap_or(Path::new("ApplyNodeConfig"));
+        let mut candidate = self.scheduler().node_config_manager().successor();
+        if let Err(e) = candidate.load_from_str(yaml, source) {
+            self.audit(AuditEvent::ConfigRefused { error: &e });
+            return Err(e);
+        }
+        match &self.node_config_path {
+            Some(path) => write_config_file(path, yaml)?,
+            None => warn!("no node configuration file — pushed configuration lasts until restart"),
+        }
+        Ok(self.reload_config(Arc::new(candidate)).await)
+    }
+
+    /// Act on the apply deadlines missed by now (see the module docs).
+    /// Returns the misses acted on.
+    pub async fn check_apply_deadlines(&self) -> Vec<ApplyExpiry> {
+        self.check_apply_deadlines_at(Instant::now()).await
+    }
+
+    /// [`check_apply_deadlines`](Self::check_apply_deadlines) at `now`.
+    ///
+    /// A miss for a node the workload no longer uses is dropped.
+    pub async fn check_apply_deadlines_at(&self, now: Instant) -> Vec<ApplyExpiry> {
+        let Some(watchdog) = &self.apply_watchdog else {
+            return Vec::new();
+        };
+        let expired = watchdog.expired_at(now);
+        if expired.is_empty() {
+            return expired;
+        }
+
+        let mut guard = self.workload_store.lock().await;
+        let mut missed = Vec::new();
+        for expiry in expired {
+            let Some(ws) = guard.get_mut(&expiry.tenant) else {
+                continue;
+            };
+            if !ws.active_nodes.contains(&expiry.node) {
+                continue;
+            }
+
+            // ── 1. Degrade what the node has not applied ──────────────────────
+            let unapplied: Vec<String> = ws
+                .task_states
+                .iter()
+                .filter(|((node, _), state)| {
+                    *node == expiry.node
+                        && matches!(state, TaskState::Pending | TaskState::Delivered)
+                })
+                .map(|((_, task), _)| task.clone())
+                .collect();
+            for task in &unapplied {
+                ws.task_states.apply(&expiry.node, task, TaskEvent::Expire);
+            }
+
+            // ── 2. Report ─────────────────────────────────────────────────────
+            self.audit(AuditEvent::ApplyOverdue {
+                tenant: &expiry.tenant,
+                workload_id: &ws.workload_id,
+                node: &expiry.node,
+                generation: expiry.generation,
+                deadline_ms: watchdog.deadline().as_millis() as u64,
+                degraded: unapplied.len(),
+            });
+            self.events.record(ScheduleEvent {
+                tenant: expiry.tenant.clone(),
+                workload_id: ws.workload_id.clone(),
+                node: expiry.node.clone(),
+                generation: ws.generation,
+                ..event(ScheduleEventKind::ApplyOverdue)
+            });
+            missed.push((ws.workload_id.clone(), expiry));
+        }
+
+        // ── 3. Fail over ──────────────────────────────────────────────────────
+        if self.apply_failover {
+            for (_, expiry) in &missed {
+                let ws = &guard[&expiry.tenant];
+                let movable: Vec<Task> = ws
+                    .schedule
+                    .get(&expiry.node)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|placed| {
+                        let task = ws.tasks.iter().find(|t| t.name == placed.name)?;
+                        movable_off(task, &expiry.node)
+                    })
+                    .collect();
+                if movable.is_empty() {
+                    continue;
+                }
+                let batch = BTreeMap::from([(expiry.tenant.clone(), movable)]);
+                let excluded = BTreeSet::from([expiry.node.clone()]);
+                match self.reschedule_excluding(&mut guard, batch, &excluded) {
+                    Ok(moved) => self.audit(AuditEvent::FailedOver {
+                        tenant: &expiry.tenant,
+                        workload_id: &guard[&expiry.tenant].workload_id,
+                        node: &expiry.node,
+                        generation: guard[&expiry.tenant].generation,
+                        moved: moved.len(),
+                    }),
+                    Err(e) => warn!(
+                        tenant = %expiry.tenant,
+                        node   = %expiry.node,
+                        error  = %e,
+                        "tasks could not be failed over — left in place"
+                    ),
+                }
+            }
+        }
+        drop(guard);
+
+        for (workload_id, expiry) in &missed {
+            let notification = FaultNotification {
+                workload_id: workload_id.clone(),
+                node_id: expiry.node.clone(),
+                task_name: String::new(),
+                fault_type: FaultType::ApplyTimeout,
+                severity: FaultSeverity::Critical,
+                feasibility: None,
+                metadata: Metadata::new(),
+            };
+            if let Err(e) = self.fault_notifier.notify_fault(notification).await {
+                warn!(workload_id = %workload_id, node = %expiry.node, error = %e,
+                      "Failed to send apply timeout fault");
+            }
+        }
+        missed.into_iter().map(|(_, expiry)| expiry).collect()
+    }
+
+    /// Drop the per-workload state kept outside the store for `tenant`'s
+    /// `workload_id` (see the module docs).
+    fn forget_workload(&self, tenant: &str, workload_id: &str) {
+        let dropped = self
+            .advisory_debouncer
+            .forget_prefix(&format!("{tenant}/{workload_id}/"));
+        if dropped > 0 {
+            self.reclaimed.fetch_add(dropped as u64, Ordering::Relaxed);
+            debug!(tenant = %tenant, workload_id = %workload_id, dropped,
+                   "per-workload entries reclaimed");
+        }
+    }
+
+    /// Per-workload entries reclaimed from departed workloads so far.
+    pub fn reclaimed_entries(&self) -> u64 {
+        self.reclaimed.load(Ordering::Relaxed)
+    }
+
+    /// Take `tenant`'s workload out of the store and retire it: deadlines
+    /// cancelled, barrier cancelled, tasks removed, `kind` recorded.  The
+    /// caller retries the pending queue once the lock is released.
+    fn remove_stored(
+        &self,
+        guard: &mut HashMap<String, WorkloadState>,
+        tenant: &str,
+        kind: ScheduleEventKind,
+    ) -> Option<WorkloadState> {
+        let mut ws = guard.remove(tenant)?;
+        if let Some(watchdog) = &self.apply_watchdog {
+            watchdog.cancel(tenant);
+        }
+        self.forget_workload(tenant, &ws.workload_id);
+        let _ = ws.barrier_tx.send(BarrierStatus::Cancelled);
+        let cleared = ws.task_states.faulted().cloned().collect();
+        ws.task_states.apply_all(TaskEvent::Remove);
+        record_workload_change(&self.events, kind, tenant, &ws, cleared);
+        Some(ws)
+    }
+
+    /// Remove every workload whose TTL has run out (see the module docs).
+    /// Returns the `(tenant, workload_id)` pairs removed.
+    pub async fn expire_workloads(&self) -> Vec<(String, String)> {
+        self.expire_workloads_at(Instant::now()).await
+    }
+
+    /// [`expire_workloads`](Self::expire_workloads) at `now`.
+    pub async fn expire_workloads_at(&self, now: Instant) -> Vec<(String, String)> {
+        let mut guard = self.workload_store.lock().await;
+        let mut due: Vec<String> = guard
+            .iter()
+            .filter(|(_, ws)| ws.expires_at.is_some_and(|at| at <= now))
+            .map(|(tenant, _)| tenant.clone())
+            .collect();
+        if due.is_empty() {
+            return Vec::new();
+        }
+        due.sort();
+
+        let mut expired = Vec::new();
+        for tenant in due {
+            let Some(ws) =
+                self.remove_stored(&mut guard, &tenant, ScheduleEventKind::WorkloadExpired)
+            else {
+                continue;
+            };
+            self.audit(AuditEvent::WorkloadExpired {
+                tenant: &tenant,
+                workload_id: &ws.workload_id,
+                generation: ws.generation,
+            });
+            expired.push((tenant, ws.workload_id));
+        }
+        drop(guard);
+
+        for (_, workload_id) in &expired {
+            let notification = FaultNotification {
+                workload_id: workload_id.clone(),
+                node_id: String::new(),
+                task_name: String::new(),
+                fault_type: FaultType::WorkloadExpired,
+                severity: FaultSeverity::Advisory,
+                feasibility: None,
+                metadata: Metadata::new(),
+            };
+            if let Err(e) = self.fault_notifier.notify_fault(notification).await {
+                warn!(workload_id = %workload_id, error = %e,
+                      "Failed to send workload expiry advisory");
+            }
+        }
+        self.retry_pending().await;
+        expired
+    }
+
+    /// Tell Pullpiri in the background that `workload_id` still has tasks on
+    /// the removed `node`.
+    fn spawn_orphan_advisory(&self, workload_id: &str, node: &str) {
+        let notification = FaultNotification {
+            workload_id: workload_id.to_string(),
+            node_id: node.to_string(),
+            task_name: String::new(),
+            fault_type: FaultType::NodeOrphaned,
+            severity: FaultSeverity::Advisory,
+            feasibility: None,
+            metadata: Metadata::new(),
+        };
+        let notifier = Arc::clone(&self.fault_notifier);
+        tokio::spawn(async move {
+            let (wl, node) = (
+                notification.workload_id.clone(),
+                notification.node_id.clone(),
+            );
+            if let Err(e) = notifier.notify_fault(notification).await {
+                warn!(workload_id = %wl, node = %node, error = %e,
+                      "Failed to send orphan advisory");
+            }
+        });
+    }
+
+    /// Replace the service-wide default scheduling options.
+    pub fn with_schedule_defaults(mut self, defaults: ScheduleOptions) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Refuse a request with more tasks than the configured
+    /// [cap](crate::limits), before any per-task work.
+    fn validate_task_count(&self, req: &SchedInfo) -> Result<(), SchedulerError> {
+        let max = self.defaults.limits.max_tasks;
+        if self.limit_hits.exceeded(Limit::Tasks, req.tasks.len(), max) {
+            return Err(SchedulerError::TooManyTasks {
+                count: req.tasks.len(),
+                max,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check the request's `workload_id` and every task name against the
+    /// naming policy.  Fails with `InvalidName` for the first bad one.
+    fn validate_names(&self, req: &SchedInfo) -> Result<(), SchedulerError> {
+        self.naming.check(NameKind::WorkloadId, &req.workload_id)?;
+        for t in &req.tasks {
+            self.naming.check(NameKind::TaskName, &t.name)?;
+        }
+        Ok(())
+    }
+
+    /// Check every task's metadata against the metadata limits.  Fails with
+    /// `InvalidMetadata` for the first bad task.
+    fn validate_metadata(&self, req: &SchedInfo) -> Result<(), SchedulerError> {
+        for t in &req.tasks {
+            self.metadata
+                .check_key_count(&t.name, t.metadata.len(), &self.limit_hits)?;
+            let metadata: Metadata = t
+                .metadata
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            self.metadata.check(&t.name, &metadata, &self.limit_hits)?;
+        }
+        Ok(())
+    }
+
+    /// Apply the range check to every task's priority and `max_dmiss`:
+    /// under `Clamp`, clamp each one in `req` (see the module docs).  Under
+    /// `Strict` values outside their range are left for
+    /// [`validate_task_structure`](Self::validate_task_structure) to report
+    /// with every other task problem.
+    fn enforce_ranges(&self, req: &mut SchedInfo) -> Result<(), SchedulerError> {
+        if self.range_check == RangeCheck::Strict {
+            return Ok(());
+        }
+        for t in &mut req.tasks {
+            let mut task = Task {
+                name: t.name.clone(),
+                priority: t.priority,
+                max_dmiss: t.max_dmiss,
+                ..Task::default()
+            };
+            for (field, value) in task.enforce_ranges(self.range_check)? {
+                warn!(
+                    workload_id = %sanitize(&req.workload_id),
+                    task        = %sanitize(&t.name),
+                    field       = %field,
+                    value,
+                    "task field out of range, clamped"
+                );
+            }
+            t.priority = task.priority;
+            t.max_dmiss = task.max_dmiss;
+        }
+        Ok(())
+    }
+
+    /// Changes beyond the revision factor from the tenant's stored revision
+    /// of `req.workload_id`; empty if it has none.
+    async fn drastic_changes(&self, tenant: &str, req: &SchedInfo) -> Vec<SuspiciousChange> {
+        let guard = self.workload_store.lock().await;
+        let Some(ws) = guard
+            .get(tenant)
+            .filter(|ws| ws.workload_id == req.workload_id)
+        else {
+            return Vec::new();
+        };
+        let tasks = tasks_from_proto(req);
+        suspicious_changes(&ws.tasks, &tasks, self.revision_factor)
+    }
+
+    /// Merge the request's optional overrides onto the service defaults.
+    /// Each was added after revision 1 of the schema (see [`crate::proto`]),
+    /// so a request from a build that predates it leaves it unset, or
+    /// empty, and gets the default.
+    ///
+    /// Fails with `UnknownAlgorithm`, `InvalidThreshold`,
+    /// `UnknownAdmissionOverride`, `AdmissionOverridesDisabled`,
+    /// `UnknownPriorityClass`, `InvalidTtl` (class and TTL are not
+    /// options, but are checked with them) or `UnknownPriorityOrdering`.
+    fn resolve_options(&self, req: &SchedInfo) -> Result<ScheduleOptions, SchedulerError> {
+        let mut opts = self.defaults.clone();
+        if let Some(name) = req.algorithm.as_deref() {
+            opts.algorithm = name.parse::<SchedAlgorithm>()?;
+            opts.algorithm_chain.clear();
+        }
+        if !req.algorithm_chain.is_empty() {
+            opts.algorithm_chain = req
+                .algorithm_chain
+                .iter()
+                .map(|name| name.parse::<SchedAlgorithm>())
+                .collect::<Result<_, _>>()?;
+            opts.algorithm = opts.algorithm_chain[0];
+        }
+        if let Some(threshold) = req.cpu_utilization_threshold {
+            opts.cpu_utilization_threshold = threshold;
+        }
+        if let Some(seed) = req.seed {
+            opts.seed = seed;
+        }
+        if !req.allowed_nodes.is_empty() {
+            opts = opts.with_allowed_nodes(req.allowed_nodes.iter().cloned());
+        }
+        if !req.admission_overrides.is_empty() && !self.allow_admission_overrides {
+            return Err(SchedulerError::AdmissionOverridesDisabled);
+        }
+        for name in &req.admission_overrides {
+            opts = opts.with_admission_override(name.parse::<AdmissionOverride>()?);
+        }
+        PriorityClass::from_proto(req.priority_class.as_deref())?;
+        if req.ttl_seconds == Some(0) {
+            return Err(SchedulerError::InvalidTtl);
+        }
+        if let Some(name) = req.priority_ordering.as_deref() {
+            opts.priority_ordering = name.parse::<PriorityOrdering>()?;
+        }
+        opts = opts.with_chains(chains_from_proto(req));
+        opts.validate()?;
+        Ok(opts)
+    }
+
+    /// Check tasks bound to their target node against that node's CPUs.
+    /// Fails with `PinnedCpusUnavailable` listing every conflict.
+    fn validate_pinned_cpus(
+        &self,
+        req: &SchedInfo,
+        opts: &ScheduleOptions,
+    ) -> Result<(), SchedulerError> {
+        let tasks = tasks_from_proto(req);
+        let conflicts = self.scheduler().pinned_cpu_conflicts(&tasks, opts);
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(SchedulerError::PinnedCpusUnavailable(conflicts))
+        }
+    }
+
+    /// Check every task with [`validate_tasks`] for each link of `opts`.
+    /// Like a chained run, fails only if every link would: with
+    /// `InvalidTasks` listing every problem the last link found.
+    fn validate_task_structure(
+        &self,
+        req: &SchedInfo,
+        opts: &ScheduleOptions,
+    ) -> Result<(), SchedulerError> {
+        let tasks = tasks_from_proto(req);
+        let mut result = Ok(());
+        for algorithm in opts.links() {
+            result = validate_tasks(
+                &tasks,
+                algorithm,
+                opts.limits.max_task_problems,
+                &self.limit_hits,
+            );
+            if result.is_ok() {
+                break;
+            }
+        }
+        result.map_err(SchedulerError::InvalidTasks)
+    }
+
+    /// The checks `AddSchedInfo` runs before it touches any state: names,
+    /// metadata, field ranges, the option overrides, every task's
+    /// structure, then pinned CPUs on bound target nodes.  Returns the
+    /// options the request would be scheduled with.
+    pub fn validate_request(&self, req: &SchedInfo) -> Result<ScheduleOptions, SchedulerError> {
+        self.validate_task_count(req)?;
+        self.validate_names(req)?;
+        self.validate_metadata(req)?;
+        let mut clamped = req.clone();
+        self.enforce_ranges(&mut clamped)?;
+        let opts = self.resolve_options(req)?;
+        self.validate_task_structure(&clamped, &opts)?;
+        self.validate_pinned_cpus(req, &opts)?;
+        Ok(opts)
+    }
+}
+
+// ── Proto → Task conversion ───────────────────────────────────────────────────
+
+/// Convert a proto `TaskInfo` into an internal `Task`.
+///
+/// `workload_id` comes from the enclosing `SchedInfo` message; every task in
+/// one RPC call shares the same value.
+///
+/// A Pullpiri build older than a field sends none, so it decodes to its
+/// proto3 default; each field added after revision 1 (see
+/// [`crate::proto`]'s schema revisions) maps that default to what the task
+/// meant before the field existed.
+pub fn task_from_proto(t: &TaskInfo, workload_id: &str) -> Task {
+    Task {
+        name: t.name.clone(),
+        workload_id: workload_id.to_owned(),
+        // node_id in the proto is the preferred/required target node.
+        target_node: t.node_id.clone(),
+        policy: SchedPolicy::from_proto_int(t.policy),
+        priority: t.priority,
+        affinity: CpuAffinity::from_proto(t.cpu_affinity),
+        period_us: Micros::from_proto(t.period),
+        runtime_us: Micros::from_proto(t.runtime),
+        deadline_us: Micros::from_proto(t.deadline),
+        release_time_us: t.release_time.max(0).unsigned_abs(),
+        max_dmiss: t.max_dmiss,
+        // Rev 5: unset = the algorithm's own default, as before.
+        target_node_policy: t.target_node_policy.map(TargetNodePolicy::from_proto_int),
+        // Rev 16: empty = no preference.
+        preferred_location: t.preferred_location.clone(),
+        // Rev 4: none = an independent task, no blocking.
+        shared_resources: t
+            .shared_resources
+            .iter()
+            .map(|r| SharedResource {
+                name: r.name.clone(),
+                max_cs_us: Micros::from_proto(r.max_cs_us),
+            })
+            .collect(),
+        // Rev 10: none = runtime unscaled on every architecture.
+        wcet_scaling: t
+            .wcet_scaling
+            .iter()
+            .map(|(arch, &f)| (arch.clone(), f))
+            .collect(),
+        // Rev 11: none = no metadata.
+        metadata: t
+            .metadata
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+        // Rev 18: false = a real task, applied and monitored.
+        placeholder: t.placeholder,
+        // Rev 20: upstream = every miss reported to Timpani-O, as before.
+        fault_sink: FaultSink::from_proto_int(t.fault_sink),
+        // Rev 23: empty = no group, placed as before.
+        cache_affinity_group: (!t.cache_affinity_group.is_empty())
+            .then(|| t.cache_affinity_group.clone()),
+        // Rev 24: zero = no memory requirement, as before.
+        memory_mb: t.memory_mb,
+        ..Task::default()
+    }
+}
+
+/// Every task of `req` ([`task_from_proto`]), with the request's
+/// workload-wide settings applied: rev 21 `exclusive_cpus` (unset = CPUs
+/// shared, as before).
+pub fn tasks_from_proto(req: &SchedInfo) -> Vec<Task> {
+    let exclusive_cpus = req.exclusive_cpus.unwrap_or(false);
+    req.tasks
+        .iter()
+        .map(|t| Task {
+            exclusive_cpus,
+            ..task_from_proto(t, &req.workload_id)
+        })
+        .collect()
+}
+
+/// The rev 25 `chains` of `req` (empty = none, as before).
+pub fn chains_from_proto(req: &SchedInfo) -> Vec<TaskChain> {
+    req.chains
+        .iter()
+        .map(|c| TaskChain {
+            name: c.name.clone(),
+            tasks: c.tasks.clone(),
+            end_to_end_deadline: Micros(c.end_to_end_deadline_us),
+        })
+        .collect()
+}
+
+/// `(node, task)` for every placement of `ws` outside `configured`.
+fn orphans_of<'a>(
+    ws: &'a WorkloadState,
+    configured: &'a BTreeSet<String>,
+) -> impl Iterator<Item = (&'a str, &'a str)> {
+    ws.schedule
+        .iter()
+        .filter(|(node, _)| !configured.contains(*node))
+        .flat_map(|(node, tasks)| tasks.iter().map(move |t| (node.as_str(), t.name.as_str())))
+}
+
+/// A copy of `task` to re-place off `node`, with `node` no longer its
+/// target; `None` if the task is pinned there by a hard `target_node`.
+fn movable_off(task: &Task, node: &str) -> Option<Task> {
+    if task.target_node == node && task.target_node_policy == Some(TargetNodePolicy::Hard) {
+        return None;
+    }
+    let mut task = task.clone();
+    if task.target_node == node {
+        task.target_node.clear();
+    }
+    Some(task)
+}
+
+/// `ApplyNodeConfig` findings for a refused configuration: one per invalid
+/// node setting, else one for the whole document.
+fn findings_of(err: &ConfigError) -> Vec<ConfigFinding> {
+    if let ConfigError::Validation { issues, .. } = err {
+        return issues
+            .iter()
+            .map(|i| ConfigFinding {
+                node: i.node.clone(),
+                message: i.message.clone(),
+            })
+            .collect();
+    }
+    let mut message = err.to_string();
+    if let Some(source) = err.source() {
+        message = format!("{message}: {source}");
+    }
+    vec![ConfigFinding {
+        node: String::new(),
+        message,
+    }]
+}
+
+/// Record `kind` for `tenant`'s workload `ws`, then `FAULT_CLEARED` for each
+/// `(node, task)` in `cleared`.
+fn record_workload_change(
+    events: &EventLog,
+    kind: ScheduleEventKind,
+    tenant: &str,
+    ws: &WorkloadState,
+    cleared: Vec<(String, String)>,
+) {
+    let base = ScheduleEvent {
+        tenant: tenant.to_string(),
+        workload_id: ws.workload_id.clone(),
+        generation: ws.generation,
+        ..Default::default()
+    };
+    events.record(ScheduleEvent {
+        kind: kind as i32,
+        ..base.clone()
+    });
+    for (node, task) in cleared {
+        events.record(ScheduleEvent {
+            kind: ScheduleEventKind::FaultCleared as i32,
+            node,
+            task,
+            ..base.clone()
+        });
+    }
+}
+
+/// Flatten lifecycle states for `GetClusterStatus`.
+fn task_statuses(ws: &WorkloadState) -> Vec<TaskStatus> {
+    ws.task_states
+        .iter()
+        .map(|((node, name), state)| TaskStatus {
+            node: node.clone(),
+            name: name.clone(),
+            state: state.as_str().to_string(),
+            apply: ws
+                .apply_reports
+                .get(node)
+                .and_then(|r| r.tasks.iter().find(|t| &t.task_name == name))
+                .cloned(),
+            metadata: ws
+                .task_metadata(node, name)
+                .into_iter()
+                .flatten()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            placeholder: ws.is_placeholder(node, name),
+        })
+        .collect()
+}
+
+/// Wrap a [`FeasibilityWarning`] as a low-severity fault notification.
+///
+/// Advisories describe a CPU, not a task, so `task_name` is left empty.
+fn advisory_from_warning(workload_id: &str, w: FeasibilityWarning) -> FaultNotification {
+    FaultNotification {
+        workload_id: workload_id.to_string(),
+        node_id: w.node,
+        task_name: String::new(),
+        fault_type: FaultType::Feasibility,
+        severity: FaultSeverity::Advisory,
+        feasibility: Some(FeasibilityInfo {
+            cpu: w.cpu,
+            utilization: w.utilization,
+            bound: w.bound,
+            analysis: w.analysis.to_string(),
+        }),
+        metadata: Metadata::new(),
+    }
+}
+
+/// Wrap a [`SimulatedMiss`] as a low-severity fault notification naming the
+/// task that missed first.
+fn advisory_from_miss(workload_id: &str, m: SimulatedMiss) -> FaultNotification {
+    FaultNotification {
+        workload_id: workload_id.to_string(),
+        node_id: m.node,
+        task_name: m.task,
+        fault_type: FaultType::Feasibility,
+        severity: FaultSeverity::Advisory,
+        feasibility: Some(FeasibilityInfo {
+            cpu: m.cpu,
+            utilization: m.utilization,
+            bound: m.bound,
+            analysis: "simulation".to_string(),
+        }),
+        metadata: Metadata::new(),
+    }
+}
+
+/// Per-task placement summary for the `AddSchedInfo` response, in
+/// node/task order.  `hints` are the workload's
+/// [`honoured_hints`].
+fn placements_of(
+    schedule: &NodeSchedMap,
+    margins: &BTreeMap<String, Vec<Option<f64>>>,
+    hints: &BTreeMap<String, bool>,
+) -> Vec<TaskPlacement> {
+    schedule
+        .iter()
+        .flat_map(|(node, tasks)| {
+            let node_margins = margins.get(node);
+            tasks.iter().enumerate().map(move |(i, t)| TaskPlacement {
+                task: t.name.clone(),
+                node: node.clone(),
+                cpu: t.assigned_cpu,
+                target_fallback: t.fallback_from.is_some(),
+                requested_node: t.fallback_from.clone().unwrap_or_default(),
+                error_code: 0,
+                runtime_margin: node_margins.and_then(|m| m.get(i).copied().flatten()),
+                cache_group_honoured: hints.get(&t.name).copied(),
+            })
+        })
+        .collect()
+}
+
+/// Smallest runtime margin among `placements`, for the workload summary.
+fn min_runtime_margin(placements: &[TaskPlacement]) -> Option<f64> {
+    placements
+        .iter()
+        .filter_map(|p| p.runtime_margin)
+        .min_by(f64::total_cmp)
+}
+
+/// Build a `Response` whose metadata echoes the scheduling options used:
+/// the algorithm is the last of `attempts`, if any.  With an algorithm
+/// chain, `attempts` also go into `chain_attempts`, and the index of the
+/// link that placed the workload under [`CHAIN_LINK_METADATA_KEY`].
+fn response_with_options(
+    status: i32,
+    opts: &ScheduleOptions,
+    attempts: &[ChainAttempt],
+) -> Response<ProtoResponse> {
+    let mut resp = Response::new(ProtoResponse {
+        status,
+        ..Default::default()
+    });
+    let algorithm = attempts.last().map_or(opts.algorithm, |a| a.algorithm);
+    let md = resp.metadata_mut();
+    md.insert(
+        ALGORITHM_METADATA_KEY,
+        MetadataValue::from_static(algorithm.as_str()),
+    );
+    if let Ok(v) = opts.cpu_utilization_threshold.to_string().parse() {
+        md.insert(THRESHOLD_METADATA_KEY, v);
+    }
+    if algorithm == SchedAlgorithm::RandomizedSpread {
+        md.insert(SEED_METADATA_KEY, opts.seed.into());
+    }
+    if opts.algorithm_chain.is_empty() {
+        return resp;
+    }
+    if attempts.last().is_some_and(|a| a.error_code.is_none()) {
+        let link = attempts.len() as u64 - 1;
+        resp.metadata_mut()
+            .insert(CHAIN_LINK_METADATA_KEY, link.into());
+    }
+    resp.get_mut().chain_attempts = attempts
+        .iter()
+        .map(|a| ProtoChainAttempt {
+            algorithm: a.algorithm.as_str().to_string(),
+            error_code: a.error_code.map_or(0, ErrorCode::as_u32),
+            error: a.error.clone(),
+        })
+        .collect();
+    resp
+}
+
+/// Add `err`'s [`ErrorCode`] (and the admission reason's) to `md`.
+pub(super) fn insert_error_metadata(md: &mut MetadataMap, err: &SchedulerError) {
+    md.insert(ERROR_CODE_METADATA_KEY, err.code().as_u32().into());
+    if let Some(reason) = err.reason() {
+        md.insert(REASON_CODE_METADATA_KEY, reason.code().as_u32().into());
+    }
+}
+
+/// `InvalidArgument` carrying `err`'s message and codes.
+fn invalid_argument(err: &SchedulerError) -> Status {
+    let mut md = MetadataMap::new();
+    insert_error_metadata(&mut md, err);
+    Status::with_metadata(tonic::Code::InvalidArgument, err.to_string(), md)
+}
+
+/// Build the `status: -1` response for a failed `AddSchedInfo`.
+///
+/// When the scheduler named the offending task it is reported as a
+/// placement with a non-zero `error_code`: the admission reason's code if
+/// there is one, else the error's.
+fn error_response(
+    err: Option<&SchedulerError>,
+    opts: &ScheduleOptions,
+    attempts: &[ChainAttempt],
+) -> Response<ProtoResponse> {
+    let mut resp = response_with_options(-1, opts, attempts);
+    let Some(err) = err else {
+        return resp;
+    };
+    insert_error_metadata(resp.metadata_mut(), err);
+    let body = resp.get_mut();
+    body.error_code = err.code().as_u32();
+    if let Some(task) = err.task() {
+        let code: ErrorCode = err.reason().map_or(err.code(), |r| r.code());
+        body.placements.push(TaskPlacement {
+            task: task.to_string(),
+            node: match err {
+                SchedulerError::AdmissionRejected { node, .. } => node.clone(),
+                _ => String::new(),
+            },
+            error_code: code.as_u32(),
+            ..Default::default()
+        });
+    }
+    resp
+}
+
+// ── SchedInfoService implementation ──────────────────────────────────────────
+
+#[tonic::async_trait]
+impl SchedInfoService for SchedInfoServiceImpl {
+    async fn add_sched_info(
+        &self,
+        request: Request<SchedInfo>,
+    ) -> Result<Response<ProtoResponse>, Status> {
+        let tenant = tenant_from_metadata(request.metadata());
+        let mut req = request.into_inner();
+        let workload_id = req.workload_id.clone();
+
+        info!(
+            tenant      = %tenant,
+            workload_id = %sanitize(&workload_id),
+            task_count  = req.tasks.len(),
+            "AddSchedInfo received"
+        );
+
+        if let Err(e) = self
+            .validate_task_count(&req)
+            .and_then(|()| self.validate_names(&req))
+            .and_then(|()| self.validate_metadata(&req))
+            .and_then(|()| self.enforce_ranges(&mut req))
+        {
+            warn!(
+                workload_id = %sanitize(&workload_id),
+                error = %e,
+                "AddSchedInfo rejected: too many tasks, invalid name or metadata"
+            );
+            return Err(invalid_argument(&e));
+        }
+
+        let opts = match self.resolve_options(&req) {
+            Ok(o) => o,
+            Err(e) => {
+                warn!(
+                    workload_id = %workload_id,
+                    error = %e,
+                    "AddSchedInfo rejected: invalid scheduling options"
+                );
+                return Err(invalid_argument(&e));
+            }
+        };
+        if let Err(e) = self.validate_task_structure(&req, &opts) {
+            warn!(
+                workload_id = %workload_id,
+                error = %e,
+                "AddSchedInfo rejected: invalid tasks"
+            );
+            return Err(invalid_argument(&e));
+        }
+        if let Err(e) = self.validate_pinned_cpus(&req, &opts) {
+            warn!(
+                workload_id = %workload_id,
+                error = %e,
+                "AddSchedInfo rejected: pinned CPUs missing on target node"
+            );
+            return Err(invalid_argument(&e));
+        }
+        self.audit(AuditEvent::SchedulingOptions {
+            tenant: &tenant,
+            workload_id: &workload_id,
+            opts: &opts,
+            overridden: req.algorithm.is_some()
+                || !req.algorithm_chain.is_empty()
+                || req.cpu_utilization_threshold.is_some()
+                || req.seed.is_some(),
+        });
+
+        let changes = self.drastic_changes(&tenant, &req).await;
+        if !changes.is_empty() {
+            let list = changes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; ");
+            if !req.force.unwrap_or(false) {
+                warn!(
+                    tenant      = %tenant,
+                    workload_id = %workload_id,
+                    changes     = %list,
+                    "AddSchedInfo rejected: drastic revision without force"
+                );
+                return Err(Status::failed_precondition(format!(
+                    "revision of workload '{workload_id}' changes tasks by more than \
+                     {}x: {list}; set force to apply it",
+                    self.revision_factor
+                )));
+            }
+            self.audit(AuditEvent::RevisionForced {
+                tenant: &tenant,
+                workload_id: &workload_id,
+                changes: &list,
+            });
+        }
+
+        // Log per-task details at debug level (mirrors C++ TLOG_DEBUG block).
+        for (i, t) in req.tasks.iter().enumerate() {
+            tracing::debug!(
+                idx          = i,
+                name         = %t.name,
+                node_id      = %t.node_id,
+                priority     = t.priority,
+                cpu_affinity = %format!("0x{:016x}", t.cpu_affinity),
+                period       = %fmt_duration_us(Micros::from_proto(t.period).as_u64()),
+                runtime      = %fmt_duration_us(Micros::from_proto(t.runtime).as_u64()),
+                deadline     = %fmt_duration_us(Micros::from_proto(t.deadline).as_u64()),
+                "task"
+            );
+        }
+
+        match self.admit(&tenant, &req, &opts).await {
+            Ok(admitted) => {
+                // A direct success supersedes anything the tenant had queued.
+                self.pending.lock().await.remove_tenant(&tenant);
+                // Replacing a workload may have released capacity.
+                self.retry_pending().await;
+                let mut resp = response_with_options(0, &opts, &admitted.attempts);
+                resp.get_mut().placements = admitted.placements;
+                resp.get_mut().summary = Some(admitted.summary);
+                Ok(resp)
+            }
+            Err(AdmitError::CapacityLimited(_, attempts)) if req.queue_if_full.unwrap_or(false) => {
+                let mut pending = self.pending.lock().await;
+                if let Err(e) = pending.push(&tenant, req) {
+                    warn!(tenant = %tenant, workload_id = %workload_id, error = %e,
+                          "AddSchedInfo rejected: pending queue full");
+                    return Err(Status::resource_exhausted(e.to_string()));
+                }
+                self.audit(AuditEvent::WorkloadQueued {
+                    tenant: &tenant,
+                    workload_id: &workload_id,
+                    depth: pending.len(),
+                });
+                let mut resp = response_with_options(STATUS_QUEUED, &opts, &attempts);
+                resp.get_mut().queued = true;
+                Ok(resp)
+            }
+            Err(e) => Ok(error_response(e.scheduler_error(), &opts, e.attempts())),
+        }
+    }
+
+    async fn remove_workload(
+        &self,
+        request: Request<WorkloadRef>,
+    ) -> Result<Response<ProtoResponse>, Status> {
+        let tenant = tenant_from_metadata(request.metadata());
+        let workload_id = request.into_inner().workload_id;
+
+        // A workload that never left the queue is simply dequeued.
+        {
+            let mut pending = self.pending.lock().await;
+            if pending
+                .get(&tenant)
+                .is_some_and(|p| p.workload_id() == workload_id)
+            {
+                pending.remove_tenant(&tenant);
+                self.audit(AuditEvent::QueuedRemoved {
+                    tenant: &tenant,
+                    workload_id: &workload_id,
+                });
+                return Ok(Response::new(ProtoResponse {
+                    status: 0,
+                    ..Default::default()
+                }));
+            }
+        }
+
+        let mut guard = self.workload_store.lock().await;
+        let owned = guard
+            .get(&tenant)
+            .is_some_and(|ws| ws.workload_id == workload_id);
+        if !owned {
+            let foreign = guard.values().any(|ws| ws.workload_id == workload_id);
+            warn!(
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                foreign,
+                "RemoveWorkload rejected"
+            );
+            return Err(if foreign {
+                Status::permission_denied(format!(
+                    "workload '{workload_id}' belongs to another tenant"
+                ))
+            } else {
+                Status::not_found(format!("workload '{workload_id}' not found"))
+            });
+        }
+
+        self.remove_stored(&mut guard, &tenant, ScheduleEventKind::WorkloadRemoved);
+        drop(guard);
+        self.audit(AuditEvent::WorkloadRemoved {
+            tenant: &tenant,
+            workload_id: &workload_id,
+        });
+        self.retry_pending().await;
+        Ok(Response::new(ProtoResponse {
+            status: 0,
+            ..Default::default()
+        }))
+    }
+
+    async fn get_cluster_status(
+        &self,
+        request: Request<ClusterStatusRequest>,
+    ) -> Result<Response<ClusterStatus>, Status> {
+        let tenant = tenant_from_metadata(request.metadata());
+        let pending = {
+            let queue = self.pending.lock().await;
+            PendingStatus {
+                depth: queue.len() as u32,
+                oldest_age_ms: queue.oldest_age().map_or(0, |a| a.as_millis() as u64),
+                workloads: queue
+                    .get(&tenant)
+                    .map(|p| QueuedWorkload {
+                        workload_id: p.workload_id().to_string(),
+                        importance: p.importance(),
+                        age_ms: p.age().as_millis() as u64,
+                        priority_class: p.priority_class().to_string(),
+                    })
+                    .into_iter()
+                    .collect(),
+                depth_per_class: queue
+                    .depth_per_class()
+                    .into_iter()
+                    .map(|(class, n)| (class.to_string(), n as u32))
+                    .collect(),
+            }
+        };
+        let guard = self.workload_store.lock().await;
+        let compaction = self.compaction.as_ref().and_then(|c| {
+            c.current(&self.compaction_basis(&guard))
+                .map(|c| c.to_proto(Some(&tenant), Instant::now()))
+        });
+        let ws = guard.get(&tenant);
+        let mut workloads_per_class = HashMap::new();
+        for other in guard.values() {
+            *workloads_per_class
+                .entry(other.priority_class.to_string())
+                .or_default() += 1;
+        }
+
+        let empty = NodeSchedMap::new();
+        let schedule = ws.map_or(&empty, |ws| &ws.schedule);
+        let capacity = self
+            .scheduler()
+            .capacity_report_with(schedule, &self.defaults);
+
+        let mut nodes = node_statuses(&capacity, schedule);
+        for n in &mut nodes {
+            n.utilization_high_water_mark = self
+                .utilization
+                .high_water_mark(&n.node)
+                .unwrap_or_default();
+        }
+        if let Some(ws) = ws {
+            for n in &mut nodes {
+                n.apply_info = ws.apply_reports.get(&n.node).and_then(|r| r.node.clone());
+                n.protocol = ws
+                    .protocols
+                    .get(&n.node)
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+            }
+        }
+
+        Ok(Response::new(ClusterStatus {
+            nodes,
+            orphaned: orphaned_nodes(&capacity),
+            workloads: ws
+                .map(|ws| {
+                    let mut w = workload_status(&ws.workload_id, ws.generation, &ws.schedule);
+                    w.tasks = task_statuses(ws);
+                    w.illegal_transitions = ws.task_states.illegal_transitions();
+                    w.priority_class = ws.priority_class.to_string();
+                    w.ttl_remaining_ms = ws
+                        .ttl_remaining_at(Instant::now())
+                        .map(|left| left.as_millis() as u64);
+                    w
+                })
+                .into_iter()
+                .collect(),
+            tenant,
+            pending: Some(pending),
+            config_generation: self.config_generation.load(Ordering::SeqCst),
+            workloads_per_class,
+            compaction,
+            log_filter: self
+                .log_filter
+                .as_ref()
+                .map(LogFilter::current)
+                .unwrap_or_default(),
+            limit_hits: self.limit_hits.snapshot().into_iter().collect(),
+        }))
+    }
+
+    async fn get_capabilities(
+        &self,
+        _request: Request<CapabilitiesRequest>,
+    ) -> Result<Response<Capabilities>, Status> {
+        let pending_capacity = self.pending.lock().await.capacity();
+        Ok(Response::new(Capabilities {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            algorithms: SchedAlgorithm::ALL
+                .iter()
+                .map(|a| a.as_str().to_string())
+                .collect(),
+            default_algorithm: self.defaults.algorithm.as_str().to_string(),
+            policies: vec![
+                ProtoSchedPolicy::Normal as i32,
+                ProtoSchedPolicy::Fifo as i32,
+                ProtoSchedPolicy::Rr as i32,
+            ],
+            max_request_bytes: self.max_request_bytes as u64,
+            queueing: pending_capacity > 0,
+            pending_capacity: u32::try_from(pending_capacity).unwrap_or(u32::MAX),
+            shadow_algorithms: self
+                .shadows
+                .iter()
+                .map(|a| a.as_str().to_string())
+                .collect(),
+            simulation_check: self
+                .defaults
+                .verify_with_simulation
+                .map_or_else(String::new, |c| c.as_str().to_string()),
+            revision_change_factor: self.revision_factor,
+            admission_overrides: if self.allow_admission_overrides {
+                AdmissionOverride::ALL
+                    .iter()
+                    .map(|o| o.as_str().to_string())
+                    .collect()
+            } else {
+                Vec::new()
+            },
+            priority_classes: PriorityClass::ALL
+                .iter()
+                .map(|c| c.as_str().to_string())
+                .collect(),
+            default_algorithm_chain: self
+                .defaults
+                .algorithm_chain
+                .iter()
+                .map(|a| a.as_str().to_string())
+                .collect(),
+            // Neither mode exists in this build.
+            best_effort: false,
+            dry_run: false,
+        }))
+    }
+
+    async fn apply_node_config(
+        &self,
+        request: Request<NodeConfigUpdate>,
+    ) -> Result<Response<NodeConfigResult>, Status> {
+        let tenant = tenant_from_metadata(request.metadata());
+        let yaml = request.into_inner().yaml;
+        info!(tenant = %tenant, bytes = yaml.len(), "ApplyNodeConfig received");
+
+        match self.apply_config_yaml(&yaml).await {
+            Ok(report) => Ok(Response::new(NodeConfigResult {
+                applied: true,
+                generation: report.generation,
+                findings: Vec::new(),
+                evacuated: report.evacuated.len() as u32,
+                orphaned: report.orphaned.len() as u32,
+            })),
+            Err(e @ ConfigError::Io { .. }) => {
+                error!(error = %e, "pushed node configuration could not be persisted");
+                Err(Status::internal(format!(
+                    "node configuration not applied: {e}"
+                )))
+            }
+            Err(e) => Ok(Response::new(NodeConfigResult {
+                applied: false,
+                generation: self.config_generation.load(Ordering::SeqCst),
+                findings: findings_of(&e),
+                ..Default::default()
+            })),
+        }
+    }
+
+    type WatchScheduleEventsStream = ReceiverStream<Result<ScheduleEvent, Status>>;
+
+    async fn watch_schedule_events(
+        &self,
+        request: Request<WatchScheduleEventsRequest>,
+    ) -> Result<Response<Self::WatchScheduleEventsStream>, Status> {
+        let tenant = tenant_from_metadata(request.metadata());
+        let backlog = request.into_inner().backlog.map(|n| n as usize);
+        info!(tenant = %tenant, backlog = ?backlog, "WatchScheduleEvents: subscriber joined");
+
+        let own = tenant.clone();
+        let mut sub = self
+            .events
+            .subscribe(backlog, move |e| e.tenant.is_empty() || e.tenant == own);
+        let (tx, rx) = mpsc::channel(EVENT_STREAM_DEPTH);
+        tokio::spawn(async move {
+            loop {
+                let next = tokio::select! {
+                    next = sub.next() => next,
+                    _ = tx.closed() => None,
+                };
+                let Some(e) = next else { break };
+                if tx.send(Ok(e)).await.is_err() {
+                    break;
+                }
+            }
+            info!(tenant = %tenant, "WatchScheduleEvents: subscriber left");
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Request;
+
+    use crate::config::NodeConfigManager;
+    use crate::fault::{test_support::MockFaultNotifier, FaultNotifier};
+    use crate::grpc::repro::{ReproOutcome, REDACTED};
+    use crate::grpc::{new_workload_store, BarrierStatus, DEFAULT_TENANT, TENANT_METADATA_KEY};
+    use crate::limits::{self, InputLimits};
+    use crate::proto::schedinfo_v1::{
+        sched_info_service_server::SchedInfoService, SchedInfo,
+        TargetNodePolicy as ProtoTargetNodePolicy, TaskChain as ProtoTaskChain, TaskInfo,
+    };
+    use crate::testing::fake_nodes;
+
+    // ── Helpers ───────────────────────────────────────────────────────────────
+
+    fn two_node_config() -> Arc<NodeConfigManager> {
+        Arc::new(fake_nodes(&[("n1", &[0, 1], 4096), ("n2", &[0, 1], 4096)]))
+    }
+
+    fn task_for(name: &str, node: &str) -> TaskInfo {
+        TaskInfo {
+            name: name.into(),
+            node_id: node.into(),
+            priority: 50,
+            policy: 1,
+            cpu_affinity: 0,
+            period: 10_000,
+            runtime: 1_000,
+            deadline: 10_000,
+            release_time: 0,
+            max_dmiss: 3,
+            shared_resources: vec![],
+            target_node_policy: None,
+            wcet_scaling: Default::default(),
+            metadata: Default::default(),
+            preferred_location: String::new(),
+            placeholder: false,
+            fault_sink: 0,
+            cache_affinity_group: String::new(),
+            memory_mb: 0,
+        }
+    }
+
+    fn make_svc_with_store(store: WorkloadStore) -> SchedInfoServiceImpl {
+        let mock = MockFaultNotifier::arc();
+        SchedInfoServiceImpl::new(two_node_config(), store, mock as Arc<dyn FaultNotifier>)
+    }
+
+    // ── Tests ─────────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn add_sched_info_two_nodes_returns_ok_status() {
+        let svc = make_svc_with_store(new_workload_store());
+        let si = SchedInfo {
+            workload_id: "wl_ok".into(),
+            tasks: vec![task_for("t1", "n1"), task_for("t2", "n2")],
+            ..Default::default()
+        };
+        let resp = svc.add_sched_info(Request::new(si)).await.unwrap();
+        assert_eq!(resp.into_inner().status, 0);
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_empty_tasks_returns_error_status() {
+        let svc = make_svc_with_store(new_workload_store());
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_empty".into(),
+                tasks: vec![],
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_ne!(resp.into_inner().status, 0);
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_returns_workload_summary() {
+        let svc = make_svc_with_store(new_workload_store());
+        let slow = TaskInfo {
+            period: 20_000,
+            runtime: 5_000,
+            deadline: 20_000,
+            ..task_for("t3", "n2")
+        };
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_sum".into(),
+                tasks: vec![task_for("t1", "n1"), task_for("t2", "n1"), slow],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // 0.1 + 0.1 + 0.25 = 0.45; n1 packs both 10 % tasks onto one CPU.
+        let s = resp.summary.unwrap();
+        assert_eq!(s.workload_id, "wl_sum");
+        assert_eq!(s.task_count, 3);
+        assert_eq!(s.nodes, ["n1", "n2"]);
+        assert!((s.total_utilization - 0.45).abs() < 1e-12);
+        assert!((s.peak_cpu_utilization - 0.25).abs() < 1e-12);
+        assert_eq!(s.hyperperiod_us, 20_000);
+        assert_eq!(s.warning_count, 0);
+
+        // Against the 90 % threshold: the 10 % tasks share a CPU at 20 %, so
+        // each could grow 8×; the lone 25 % task 3.6×.  Rounded down.
+        let margin = |task: &str| {
+            resp.placements
+                .iter()
+                .find(|p| p.task == task)
+                .and_then(|p| p.runtime_margin)
+                .unwrap()
+        };
+        assert!((7.99..=8.0).contains(&margin("t1")), "{}", margin("t1"));
+        assert!((3.59..=3.6).contains(&margin("t3")), "{}", margin("t3"));
+        assert_eq!(s.min_runtime_margin, Some(margin("t3")));
+        assert_eq!(s.priority_ordering, "rate_monotonic");
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_reports_a_chain_over_its_end_to_end_deadline() {
+        let svc = make_svc_with_store(new_workload_store());
+        // Both meet their own 10 ms deadline (R = 2 and 5 ms), but fusion is
+        // released with sensor, misses its output and waits a period:
+        // 10 + 5 = 15 ms end to end.
+        let sensor = TaskInfo {
+            priority: 60,
+            runtime: 2_000,
+            ..task_for("sensor", "n1")
+        };
+        let fusion = TaskInfo {
+            runtime: 3_000,
+            ..task_for("fusion", "n1")
+        };
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_chain".into(),
+                tasks: vec![sensor, fusion],
+                chains: vec![ProtoTaskChain {
+                    name: "perception".into(),
+                    tasks: vec!["sensor".into(), "fusion".into()],
+                    end_to_end_deadline_us: 12_000,
+                }],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.status, 0);
+        let s = resp.summary.unwrap();
+        assert_eq!(s.warning_count, 1);
+        let [chain] = s.chains.as_slice() else {
+            panic!("{:?}", s.chains)
+        };
+        assert_eq!(chain.name, "perception");
+        assert_eq!(chain.node, "n1");
+        assert_eq!(chain.latency_us, Some(15_000));
+        assert_eq!(chain.deadline_us, 12_000);
+        assert!(!chain.met);
+        assert!(chain.error.is_empty());
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_assigns_priorities_by_requested_ordering() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        // `ctl` has the longer period but the shorter deadline.
+        let auto = |name: &str, period, deadline| TaskInfo {
+            priority: 0,
+            period,
+            deadline,
+            ..task_for(name, "n1")
+        };
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_dm".into(),
+                tasks: vec![auto("ctl", 20_000, 4_000), auto("io", 10_000, 10_000)],
+                priority_ordering: Some("deadline_monotonic".into()),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            resp.summary.unwrap().priority_ordering,
+            "deadline_monotonic"
+        );
+
+        let guard = store.lock().await;
+        let prio = |name: &str| {
+            guard["default"]
+                .tasks
+                .iter()
+                .find(|t| t.name == name)
+                .unwrap()
+                .priority
+        };
+        assert!(prio("ctl") > prio("io"));
+        drop(guard);
+
+        let err = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_dm".into(),
+                tasks: vec![auto("ctl", 20_000, 4_000)],
+                priority_ordering: Some("edf".into()),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1023");
+    }
+
+    #[tokio::test]
+    async fn captured_repro_is_redacted_and_replays() {
+        let svc = make_svc_with_store(new_workload_store()).with_sensitive_label_keys(["owner"]);
+        let tagged = TaskInfo {
+            metadata: [("owner".to_string(), "team-x".to_string())].into(),
+            ..task_for("t1", "n1")
+        };
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl_repro".into(),
+            tasks: vec![tagged, task_for("t2", "n2")],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+
+        assert!(svc.capture_repro("wl_other").await.is_none());
+        let bundle = svc.capture_repro("wl_repro").await.unwrap();
+        assert_eq!(bundle.request.tasks[0].metadata["owner"], REDACTED);
+        assert_eq!(bundle.placement["n1"][0].metadata["owner"], REDACTED);
+        assert_eq!(bundle.settings.algorithm, "target_node_priority");
+        assert!(matches!(bundle.replay().unwrap(), ReproOutcome::Match));
+    }
+
+    #[tokio::test]
+    async fn cluster_status_reports_monotonic_high_water_marks() {
+        let svc = make_svc_with_store(new_workload_store());
+        let peak = || async {
+            let status = svc
+                .get_cluster_status(Request::new(ClusterStatusRequest::default()))
+                .await
+                .unwrap()
+                .into_inner();
+            let n1 = status.nodes.iter().find(|n| n.node == "n1").unwrap();
+            n1.utilization_high_water_mark
+        };
+        assert_eq!(peak().await, 0.0);
+
+        let mut marks = Vec::new();
+        for (tenant, runtime) in [("a", 2_000), ("b", 5_000), ("c", 3_000)] {
+            let req = SchedInfo {
+                workload_id: format!("wl_{tenant}"),
+                tasks: vec![TaskInfo {
+                    runtime,
+                    ..task_for(&format!("t_{tenant}"), "n1")
+                }],
+                ..Default::default()
+            };
+            svc.add_sched_info(as_tenant(tenant, req)).await.unwrap();
+            marks.push(peak().await);
+        }
+        assert!((marks[0] - 0.2).abs() < 1e-9);
+        assert!(marks.windows(2).all(|w| w[0] <= w[1]), "{marks:?}");
+
+        svc.remove_workload(as_tenant(
+            "b",
+            WorkloadRef {
+                workload_id: "wl_b".into(),
+            },
+        ))
+        .await
+        .unwrap();
+        assert_eq!(peak().await, marks[2]);
+
+        let m = svc.utilization_metrics();
+        assert_eq!(m.admissions, 3);
+        assert!(m.histogram.iter().sum::<u64>() >= 3);
+        assert_eq!(m.high_water_marks["n1"], marks[2]);
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_honours_allowed_nodes() {
+        let svc = make_svc_with_store(new_workload_store());
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_zone".into(),
+                tasks: vec![task_for("t1", ""), task_for("t2", "")],
+                algorithm: Some("least_loaded".into()),
+                allowed_nodes: vec!["n2".into()],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, 0);
+        assert!(resp.placements.iter().all(|p| p.node == "n2"));
+
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_zone".into(),
+                tasks: vec![task_for("t1", "n1")],
+                allowed_nodes: vec!["n2".into()],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.error_code, ErrorCode::TargetNodeNotAllowed.as_u32());
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_failure_carries_error_codes() {
+        let svc = make_svc_with_store(new_workload_store());
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_bad".into(),
+                tasks: vec![task_for("t1", "n1"), task_for("t2", "node_not_in_config")],
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+
+        let md = resp.metadata();
+        assert_eq!(md.get(ERROR_CODE_METADATA_KEY).unwrap(), "1007");
+        assert_eq!(md.get(REASON_CODE_METADATA_KEY).unwrap(), "1101");
+        let body = resp.into_inner();
+        assert_eq!(body.status, -1);
+        assert_eq!(body.error_code, ErrorCode::AdmissionRejected.as_u32());
+        assert_eq!(body.placements.len(), 1);
+        let failed = &body.placements[0];
+        assert_eq!(failed.task, "t2");
+        assert_eq!(failed.node, "node_not_in_config");
+        assert_eq!(failed.error_code, ErrorCode::NodeNotFound.as_u32());
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_unknown_node_returns_error_status() {
+        let svc = make_svc_with_store(new_workload_store());
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_bad".into(),
+                tasks: vec![task_for("t1", "node_not_in_config")],
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_ne!(resp.into_inner().status, 0);
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_reports_placements_and_fallbacks() {
+        let svc = make_svc_with_store(new_workload_store());
+        let preferred = TaskInfo {
+            target_node_policy: Some(ProtoTargetNodePolicy::Preferred as i32),
+            ..task_for("t2", "node_not_in_config")
+        };
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_place".into(),
+                tasks: vec![task_for("t1", "n2"), preferred],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, 0);
+
+        let by_task = |name: &str| resp.placements.iter().find(|p| p.task == name).unwrap();
+        assert_eq!(by_task("t1").node, "n2");
+        assert!(!by_task("t1").target_fallback);
+        assert!(by_task("t2").target_fallback);
+        assert_eq!(by_task("t2").requested_node, "node_not_in_config");
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_stores_workload_in_workload_store() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl_stored".into(),
+            tasks: vec![task_for("t1", "n1")],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+
+        let guard = store.lock().await;
+        let ws = guard
+            .get(DEFAULT_TENANT)
+            .expect("workload should be in the store");
+        assert_eq!(ws.workload_id, "wl_stored");
+        assert!(ws.active_nodes.contains("n1"));
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_replaces_previous_workload_and_cancels_barrier() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+
+        // First workload — subscribe to its barrier before replacing
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl_first".into(),
+            tasks: vec![task_for("t1", "n1")],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+
+        let barrier_rx = {
+            let guard = store.lock().await;
+            guard.get(DEFAULT_TENANT).unwrap().barrier_tx.subscribe()
+        };
+
+        // Replace with second workload
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl_second".into(),
+            tasks: vec![task_for("t2", "n2")],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+
+        // First barrier should now be Cancelled
+        assert_eq!(*barrier_rx.borrow(), BarrierStatus::Cancelled);
+
+        // Active workload should be the second one
+        let guard = store.lock().await;
+        assert_eq!(guard.get(DEFAULT_TENANT).unwrap().workload_id, "wl_second");
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_per_request_algorithm_override() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+
+        // No node_id: the default target_node_priority would reject this.
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_ll".into(),
+                tasks: vec![task_for("t1", ""), task_for("t2", "")],
+                algorithm: Some("least_loaded".into()),
+                cpu_utilization_threshold: Some(0.5),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            resp.metadata().get(ALGORITHM_METADATA_KEY).unwrap(),
+            "least_loaded"
+        );
+        assert_eq!(resp.metadata().get(THRESHOLD_METADATA_KEY).unwrap(), "0.5");
+        assert_eq!(resp.into_inner().status, 0);
+
+        // least_loaded spreads the two tasks across both nodes.
+        let guard = store.lock().await;
+        assert_eq!(guard.get(DEFAULT_TENANT).unwrap().active_nodes.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_randomized_spread_echoes_seed() {
+        let svc = make_svc_with_store(new_workload_store());
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_rs".into(),
+                tasks: vec![task_for("t1", ""), task_for("t2", "")],
+                algorithm: Some("randomized_spread".into()),
+                seed: Some(1234),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(resp.metadata().get(SEED_METADATA_KEY).unwrap(), "1234");
+        assert_eq!(resp.into_inner().status, 0);
+
+        // Deterministic algorithms do not echo a seed.
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_tnp".into(),
+                tasks: vec![task_for("t1", "n1")],
+                seed: Some(1234),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert!(resp.metadata().get(SEED_METADATA_KEY).is_none());
+    }
+
+    fn as_tenant<T>(tenant: &str, msg: T) -> Request<T> {
+        let mut req = Request::new(msg);
+        req.metadata_mut()
+            .insert(TENANT_METADATA_KEY, tenant.parse().unwrap());
+        req
+    }
+
+    fn shared_wl(task: &str, node: &str) -> SchedInfo {
+        SchedInfo {
+            workload_id: "wl_shared".into(),
+            tasks: vec![task_for(task, node)],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn tenants_with_same_workload_id_do_not_interfere() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+
+        svc.add_sched_info(as_tenant("prod", shared_wl("t_prod", "n1")))
+            .await
+            .unwrap();
+        let prod_rx = store.lock().await["prod"].barrier_tx.subscribe();
+        svc.add_sched_info(as_tenant("test", shared_wl("t_test", "n2")))
+            .await
+            .unwrap();
+
+        // The test tenant's submission neither replaced nor cancelled prod.
+        assert_eq!(*prod_rx.borrow(), BarrierStatus::Waiting);
+        let guard = store.lock().await;
+        assert_eq!(guard.len(), 2);
+        assert!(guard["prod"].active_nodes.contains("n1"));
+        assert!(guard["test"].active_nodes.contains("n2"));
+        assert_eq!(guard["prod"].generation, 1);
+        assert_eq!(guard["test"].generation, 1);
+    }
+
+    #[tokio::test]
+    async fn remove_workload_cross_tenant_is_permission_denied() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        svc.add_sched_info(as_tenant("prod", shared_wl("t1", "n1")))
+            .await
+            .unwrap();
+
+        let err = svc
+            .remove_workload(as_tenant(
+                "test",
+                WorkloadRef {
+                    workload_id: "wl_shared".into(),
+                },
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        assert!(store.lock().await.contains_key("prod"));
+
+        let err = svc
+            .remove_workload(as_tenant(
+                "prod",
+                WorkloadRef {
+                    workload_id: "wl_missing".into(),
+                },
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+
+        let resp = svc
+            .remove_workload(as_tenant(
+                "prod",
+                WorkloadRef {
+                    workload_id: "wl_shared".into(),
+                },
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.into_inner().status, 0);
+        assert!(store.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn untagged_requests_use_default_tenant() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        svc.add_sched_info(Request::new(shared_wl("t1", "n1")))
+            .await
+            .unwrap();
+        assert!(store.lock().await.contains_key(DEFAULT_TENANT));
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_echoes_defaults_when_not_overridden() {
+        let svc = make_svc_with_store(new_workload_store()).with_schedule_defaults(
+            ScheduleOptions::default().with_algorithm(SchedAlgorithm::BestFitDecreasing),
+        );
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_def".into(),
+                tasks: vec![task_for("t1", "n1")],
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.metadata().get(ALGORITHM_METADATA_KEY).unwrap(),
+            "best_fit_decreasing"
+        );
+        assert_eq!(resp.metadata().get(THRESHOLD_METADATA_KEY).unwrap(), "0.9");
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_invalid_threshold_is_invalid_argument() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        for bad in [0.0, 1.5, -0.1] {
+            let err = svc
+                .add_sched_info(Request::new(SchedInfo {
+                    workload_id: "wl_bad_thr".into(),
+                    tasks: vec![task_for("t1", "n1")],
+                    cpu_utilization_threshold: Some(bad),
+                    ..Default::default()
+                }))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), tonic::Code::InvalidArgument, "threshold {bad}");
+            assert_eq!(
+                err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(),
+                "1004",
+                "threshold {bad}"
+            );
+        }
+        assert!(store.lock().await.is_empty(), "nothing must be stored");
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_unknown_algorithm_is_invalid_argument() {
+        let svc = make_svc_with_store(new_workload_store());
+        let err = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_bad_alg".into(),
+                tasks: vec![task_for("t1", "n1")],
+                algorithm: Some("round_robin".into()),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    // ── Algorithm chains ──────────────────────────────────────────────────────
+
+    fn chain(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn attempt_codes(resp: &ProtoResponse) -> Vec<(&str, u32)> {
+        resp.chain_attempts
+            .iter()
+            .map(|a| (a.algorithm.as_str(), a.error_code))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn algorithm_chain_falls_back_to_the_next_link() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+
+        // No node_id: target_node_priority fails, least_loaded places it.
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_chain".into(),
+                tasks: vec![task_for("t1", ""), task_for("t2", "")],
+                algorithm_chain: chain(&["target_node_priority", "least_loaded"]),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        let md = resp.metadata();
+        assert_eq!(md.get(ALGORITHM_METADATA_KEY).unwrap(), "least_loaded");
+        assert_eq!(md.get(CHAIN_LINK_METADATA_KEY).unwrap(), "1");
+        let body = resp.into_inner();
+        assert_eq!(body.status, 0);
+        assert_eq!(
+            attempt_codes(&body),
+            [
+                ("target_node_priority", ErrorCode::InvalidTasks.as_u32()),
+                ("least_loaded", 0),
+            ]
+        );
+        assert!(body.chain_attempts[0].error.contains("no target_node"));
+        assert_eq!(store.lock().await[DEFAULT_TENANT].active_nodes.len(), 2);
+
+        // Without a chain nothing is reported.
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_plain".into(),
+                tasks: vec![task_for("t1", "n1")],
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert!(resp.metadata().get(CHAIN_LINK_METADATA_KEY).is_none());
+        assert!(resp.into_inner().chain_attempts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn configured_chain_applies_unless_the_request_names_an_algorithm() {
+        let defaults = ScheduleOptions::default().with_algorithm_chain([
+            SchedAlgorithm::TargetNodePriority,
+            SchedAlgorithm::BestFitDecreasing,
+        ]);
+        let svc = make_svc_with_store(new_workload_store()).with_schedule_defaults(defaults);
+        let request = |algorithm: Option<&str>| {
+            Request::new(SchedInfo {
+                workload_id: "wl_chain".into(),
+                tasks: vec![task_for("t1", "")],
+                algorithm: algorithm.map(Into::into),
+                ..Default::default()
+            })
+        };
+
+        let caps = svc
+            .get_capabilities(Request::new(CapabilitiesRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            caps.default_algorithm_chain,
+            ["target_node_priority", "best_fit_decreasing"]
+        );
+
+        let resp = svc.add_sched_info(request(None)).await.unwrap();
+        assert_eq!(
+            resp.metadata().get(ALGORITHM_METADATA_KEY).unwrap(),
+            "best_fit_decreasing"
+        );
+        assert_eq!(resp.into_inner().status, 0);
+
+        // An explicit algorithm replaces the configured chain, so the task
+        // without a target node is rejected up front.
+        let err = svc
+            .add_sched_info(request(Some("target_node_priority")))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1025");
+        assert!(
+            err.message().contains("no target_node"),
+            "{}",
+            err.message()
+        );
+    }
+
+    #[tokio::test]
+    async fn algorithm_chain_failing_everywhere_reports_every_link() {
+        let svc = make_svc_with_store(new_workload_store());
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_chain".into(),
+                // Five CPUs' worth of 80 % tasks on four CPUs.
+                tasks: (1..=5).map(|i| heavy(&format!("t{i}"), "")).collect(),
+                algorithm_chain: chain(&["least_loaded", "best_fit_decreasing"]),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        let md = resp.metadata();
+        assert_eq!(
+            md.get(ALGORITHM_METADATA_KEY).unwrap(),
+            "best_fit_decreasing"
+        );
+        assert!(md.get(CHAIN_LINK_METADATA_KEY).is_none());
+        let body = resp.into_inner();
+        assert_eq!(body.status, -1);
+        let attempts = attempt_codes(&body);
+        assert_eq!(attempts.len(), 2);
+        assert!(attempts.iter().all(|&(_, code)| code != 0));
+        // The response's error is the last link's.
+        assert_eq!(body.error_code, attempts[1].1);
+
+        let err = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_chain".into(),
+                tasks: vec![task_for("t1", "n1")],
+                algorithm_chain: chain(&["least_loaded", "round_robin"]),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn chained_workload_is_queued_and_retried_through_the_chain() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        svc.add_sched_info(as_tenant("a", filling_workload()))
+            .await
+            .unwrap();
+
+        // target_node_priority can never place it; least_loaded could on an
+        // idle cluster, so the workload waits for capacity.
+        let waiting = SchedInfo {
+            tasks: vec![heavy("b1", "")],
+            algorithm_chain: chain(&["target_node_priority", "least_loaded"]),
+            ..queued_workload("")
+        };
+        let resp = svc
+            .add_sched_info(as_tenant("b", waiting))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, STATUS_QUEUED);
+        assert_eq!(resp.chain_attempts.len(), 2);
+
+        svc.remove_workload(as_tenant(
+            "a",
+            WorkloadRef {
+                workload_id: "wl_big".into(),
+            },
+        ))
+        .await
+        .unwrap();
+        assert_eq!(store.lock().await["b"].workload_id, "wl_wait");
+    }
+
+    #[tokio::test]
+    async fn priority_class_is_validated_and_reported() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        let request = |class: &str| {
+            Request::new(SchedInfo {
+                workload_id: "wl_class".into(),
+                tasks: vec![task_for("t1", "n1")],
+                priority_class: Some(class.into()),
+                ..Default::default()
+            })
+        };
+        let err = svc.add_sched_info(request("critical")).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1020");
+        assert!(store.lock().await.is_empty(), "nothing must be stored");
+
+        svc.add_sched_info(request("safety")).await.unwrap();
+        assert_eq!(
+            store.lock().await[DEFAULT_TENANT].priority_class,
+            PriorityClass::Safety
+        );
+        let status = svc
+            .get_cluster_status(Request::new(ClusterStatusRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(status.workloads[0].priority_class, "safety");
+        assert_eq!(status.workloads_per_class["safety"], 1);
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_bad_name_is_invalid_argument() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        for (workload_id, task, expected) in [
+            (
+                "wl/1",
+                "t1",
+                "workload_id 'wl%2F1' contains '/' at position 3",
+            ),
+            (
+                "wl1",
+                "my task",
+                "task name 'my%20task' contains ' ' at position 3",
+            ),
+        ] {
+            let err = svc
+                .add_sched_info(Request::new(SchedInfo {
+                    workload_id: workload_id.into(),
+                    tasks: vec![task_for(task, "n1")],
+                    ..Default::default()
+                }))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), tonic::Code::InvalidArgument);
+            assert_eq!(err.message(), expected);
+            assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1015");
+        }
+        assert!(store.lock().await.is_empty(), "nothing must be stored");
+
+        // A custom pattern replaces the default rule.
+        let svc = make_svc_with_store(new_workload_store())
+            .with_naming_policy(NamingPolicy::with_pattern("[a-z/ ]+[0-9]?").unwrap());
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl/1".into(),
+                tasks: vec![task_for("my task", "n1")],
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(resp.get_ref().status, 0);
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_rejects_oversized_metadata() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store))
+            .with_metadata_policy(MetadataPolicy::default().with_max_keys(1));
+        let mut task = task_for("t1", "n1");
+        task.metadata = [("image", "cam:1"), ("version", "2")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let err = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl".into(),
+                tasks: vec![task],
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.message(), "task 't1' has 2 metadata keys, more than 1");
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1016");
+        assert!(store.lock().await.is_empty(), "nothing must be stored");
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_rejects_or_clamps_out_of_range_fields() {
+        let request = || {
+            let mut hot = task_for("hot", "n1");
+            hot.priority = 150;
+            let mut lax = task_for("lax", "n1");
+            lax.max_dmiss = -1;
+            SchedInfo {
+                workload_id: "wl".into(),
+                tasks: vec![task_for("ok", "n1"), hot, lax],
+                ..Default::default()
+            }
+        };
+
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        assert!(svc.validate_request(&request()).is_err());
+        let err = svc
+            .add_sched_info(Request::new(request()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(
+            err.message(),
+            "2 task problem(s): task 'hot' has priority 150, outside 0–99; \
+             task 'lax' has max_dmiss -1, outside 0 or more"
+        );
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1025");
+        assert!(store.lock().await.is_empty(), "nothing must be stored");
+
+        let svc = make_svc_with_store(Arc::clone(&store)).with_range_check(RangeCheck::Clamp);
+        assert!(svc.validate_request(&request()).is_ok());
+        let resp = svc.add_sched_info(Request::new(request())).await.unwrap();
+        assert_eq!(resp.get_ref().status, 0);
+        let guard = store.lock().await;
+        let fields: Vec<_> = guard[DEFAULT_TENANT]
+            .schedule
+            .values()
+            .flatten()
+            .map(|t| (t.name.as_str(), t.priority, t.max_dmiss))
+            .collect();
+        assert!(fields.contains(&("hot", 99, 3)), "{fields:?}");
+        assert!(fields.contains(&("lax", 50, 0)), "{fields:?}");
+        assert!(fields.contains(&("ok", 50, 3)), "{fields:?}");
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_reports_every_task_problem_at_once() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        let mut scaled = task_for("scaled", "n1");
+        scaled.wcet_scaling = [("x86_64".to_string(), -1.0)].into();
+        let mut hot = task_for("hot", "n2");
+        hot.priority = 150;
+        let request = SchedInfo {
+            workload_id: "wl_broken".into(),
+            tasks: vec![
+                task_for("lost", ""),
+                scaled,
+                task_for("twin", "n1"),
+                hot,
+                task_for("twin", "n2"),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            svc.validate_request(&request).unwrap_err().code(),
+            ErrorCode::InvalidTasks
+        );
+        let err = svc.add_sched_info(Request::new(request)).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1025");
+        assert!(
+            err.message().starts_with("4 task problem(s)"),
+            "{}",
+            err.message()
+        );
+        for name in ["'lost'", "'scaled'", "'hot'", "'twin'"] {
+            assert!(err.message().contains(name), "{}", err.message());
+        }
+        assert!(store.lock().await.is_empty(), "nothing must be stored");
+    }
+
+    /// Requests well under the message size limit that would otherwise
+    /// make Timpani-O hold a large task, metadata or problem list.
+    #[tokio::test]
+    async fn adversarially_large_requests_stay_within_the_input_limits() {
+        use prost::Message as _;
+
+        let store = new_workload_store();
+        let svc = &make_svc_with_store(Arc::clone(&store));
+        let submit = move |tasks: Vec<TaskInfo>| {
+            let request = SchedInfo {
+                workload_id: "wl_huge".into(),
+                tasks,
+                ..Default::default()
+            };
+            assert!(request.encoded_len() < DEFAULT_MAX_REQUEST_BYTES);
+            svc.add_sched_info(Request::new(request))
+        };
+
+        // One task past the cap, refused on the count alone.
+        let max = limits::DEFAULT_MAX_TASKS;
+        let err = submit(vec![task_for("t", "n1"); max + 1])
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1027");
+
+        // 100 000 metadata keys on one task, refused before they are copied.
+        let mut flooded = task_for("flooded", "n1");
+        flooded.metadata = (0..100_000)
+            .map(|i| (format!("k{i}"), String::new()))
+            .collect();
+        let err = submit(vec![flooded]).await.unwrap_err();
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1016");
+
+        // A problem in every task: only the first ones are listed.
+        let broken: Vec<_> = (0..max)
+            .map(|i| TaskInfo {
+                priority: 150,
+                ..task_for(&format!("t{i}"), "n1")
+            })
+            .collect();
+        let err = submit(broken).await.unwrap_err();
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1025");
+        let omitted = max - limits::DEFAULT_MAX_TASK_PROBLEMS;
+        assert!(
+            err.message().ends_with(&format!("; and {omitted} more")),
+            "{}",
+            err.message()
+        );
+        assert!(err.message().len() < 64 * 1024);
+        assert!(store.lock().await.is_empty(), "nothing must be stored");
+
+        let status = svc
+            .get_cluster_status(Request::new(ClusterStatusRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        let expected: HashMap<String, u64> = [
+            ("tasks", 1),
+            ("unique_periods", 0),
+            ("task_problems", 1),
+            ("templates", 0),
+            ("metadata_keys", 1),
+        ]
+        .map(|(limit, hits)| (limit.to_string(), hits))
+        .into();
+        assert_eq!(status.limit_hits, expected);
+
+        // The counts are this service's own.
+        let other = make_svc_with_store(new_workload_store())
+            .get_cluster_status(Request::new(ClusterStatusRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(other.limit_hits.values().all(|&hits| hits == 0));
+    }
+
+    #[tokio::test]
+    async fn configured_input_limits_apply_to_requests() {
+        let svc = make_svc_with_store(new_workload_store()).with_schedule_defaults(
+            ScheduleOptions::default().with_limits(
+                InputLimits::default()
+                    .with_max_task_problems(1)
+                    .with_max_tasks(3),
+            ),
+        );
+        let request = |tasks| {
+            Request::new(SchedInfo {
+                workload_id: "wl_capped".into(),
+                tasks,
+                ..Default::default()
+            })
+        };
+
+        let names = ["a", "b", "c", "d"];
+        let tasks = names.iter().map(|n| task_for(n, "n1")).collect();
+        let err = svc.add_sched_info(request(tasks)).await.unwrap_err();
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1027");
+
+        let broken = names[..3]
+            .iter()
+            .map(|n| TaskInfo {
+                priority: 150,
+                ..task_for(n, "n1")
+            })
+            .collect();
+        let err = svc.add_sched_info(request(broken)).await.unwrap_err();
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1025");
+        assert!(err.message().ends_with("; and 2 more"), "{}", err.message());
+    }
+
+    #[tokio::test]
+    async fn add_sched_info_reports_every_pinned_cpu_conflict() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        let pinned = |name: &str, mask: u64| TaskInfo {
+            cpu_affinity: mask,
+            ..task_for(name, "n1")
+        };
+        let err = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_pinned".into(),
+                tasks: vec![
+                    pinned("t1", 0b100),
+                    pinned("t2", 0b11),
+                    pinned("t3", 0b1100),
+                ],
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(
+            err.message(),
+            "2 pinned task(s) cannot run on their target node: \
+             task 't1' is pinned to CPU(s) [2] but node 'n1' has CPUs [0, 1]; \
+             task 't3' is pinned to CPU(s) [2, 3] but node 'n1' has CPUs [0, 1]"
+        );
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1022");
+        assert!(store.lock().await.is_empty(), "nothing must be stored");
+    }
+
+    // ── Capabilities ──────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn capabilities_follow_the_configuration() {
+        let caps = |svc: SchedInfoServiceImpl| async move {
+            svc.get_capabilities(Request::new(CapabilitiesRequest {}))
+                .await
+                .unwrap()
+                .into_inner()
+        };
+
+        let default = caps(make_svc_with_store(new_workload_store())).await;
+        assert_eq!(default.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            default.algorithms,
+            [
+                "target_node_priority",
+                "least_loaded",
+                "best_fit_decreasing",
+                "randomized_spread"
+            ]
+        );
+        assert_eq!(default.default_algorithm, "target_node_priority");
+        assert_eq!(default.policies, [0, 1, 2]);
+        assert_eq!(default.max_request_bytes, DEFAULT_MAX_REQUEST_BYTES as u64);
+        assert!(default.queueing);
+        assert!(default.shadow_algorithms.is_empty());
+        assert_eq!(default.simulation_check, "");
+        assert!(default.admission_overrides.is_empty());
+        assert_eq!(
+            default.priority_classes,
+            ["safety", "platform", "best_effort"]
+        );
+        assert!(!default.best_effort);
+        assert!(!default.dry_run);
+
+        let mut defaults = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        defaults.verify_with_simulation = Some(SimulationCheck::Warn);
+        let tuned = caps(
+            make_svc_with_store(new_workload_store())
+                .with_schedule_defaults(defaults)
+                .with_pending_capacity(0)
+                .with_shadow_algorithms([SchedAlgorithm::BestFitDecreasing])
+                .with_max_request_bytes(1024)
+                .with_revision_change_factor(4.0)
+                .with_admission_overrides(true),
+        )
+        .await;
+        assert_eq!(tuned.default_algorithm, "least_loaded");
+        assert!(!tuned.queueing);
+        assert_eq!(tuned.shadow_algorithms, ["best_fit_decreasing"]);
+        assert_eq!(tuned.simulation_check, "warn");
+        assert_eq!(tuned.max_request_bytes, 1024);
+        assert_eq!(tuned.revision_change_factor, 4.0);
+        assert_eq!(tuned.admission_overrides, ["skip_memory", "skip_threshold"]);
+        // Overrides are not best-effort mode, which is unsupported.
+        assert!(!tuned.best_effort);
+    }
+
+    // ── Admission overrides ───────────────────────────────────────────────────
+
+    /// Shared buffer the test subscriber writes to.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// One task at 95 % of a CPU, above the default 90 % threshold.
+    fn overcommit(overrides: &[&str]) -> Request<SchedInfo> {
+        let mut heavy = task_for("heavy", "n1");
+        heavy.runtime = 9_500;
+        Request::new(SchedInfo {
+            workload_id: "wl_lab".into(),
+            tasks: vec![heavy],
+            admission_overrides: overrides.iter().map(|o| o.to_string()).collect(),
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn admission_overrides_need_the_global_switch_and_are_audited() {
+        let mock = MockFaultNotifier::arc();
+        let svc = |allowed| {
+            SchedInfoServiceImpl::new(
+                two_node_config(),
+                new_workload_store(),
+                Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+            )
+            .with_admission_overrides(allowed)
+        };
+        let code = |err: &Status| {
+            let code = err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap();
+            code.to_str().unwrap().to_string()
+        };
+
+        // Not allowed: rejected outright rather than ignored.
+        let err = svc(false)
+            .add_sched_info(overcommit(&["skip_threshold"]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(code(&err), "1018");
+
+        let enabled = svc(true);
+        let err = enabled
+            .add_sched_info(overcommit(&["skip_safety_level"]))
+            .await
+            .unwrap_err();
+        assert_eq!(code(&err), "1017");
+        let resp = enabled.add_sched_info(overcommit(&[])).await.unwrap();
+        assert_ne!(resp.into_inner().status, 0, "95 % needs the override");
+        assert!(mock.calls.lock().unwrap().is_empty());
+
+        // Allowed: admitted, and the overcommit shows up everywhere.
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let resp = {
+            let _guard = tracing::subscriber::set_default(subscriber);
+            enabled
+                .add_sched_info(overcommit(&["skip_threshold"]))
+                .await
+                .unwrap()
+                .into_inner()
+        };
+        assert_eq!(resp.status, 0);
+        assert_eq!(
+            resp.summary.unwrap().admission_overrides,
+            ["skip_threshold"]
+        );
+
+        let log = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let audit = log
+            .lines()
+            .find(|l| l.contains("audit:") && l.contains("admission checks skipped"))
+            .expect("audit line");
+        assert!(audit.contains("WARN"), "{audit}");
+        assert!(audit.contains("workload_id=wl_lab"), "{audit}");
+        assert!(audit.contains("skipped=skip_threshold"), "{audit}");
+
+        wait_for_calls(&mock, 1).await;
+        let calls = mock.calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].fault_type, FaultType::AdmissionOverride);
+        assert_eq!(calls[0].severity, FaultSeverity::Advisory);
+        assert_eq!(calls[0].workload_id, "wl_lab");
+        assert_eq!(
+            calls[0].metadata[ADMISSION_OVERRIDES_METADATA_KEY],
+            "skip_threshold"
+        );
+    }
+
+    #[tokio::test]
+    async fn event_sink_receives_placements_and_audit_events() {
+        /// Records placements and the variant name of each audit event.
+        #[derive(Default)]
+        struct Recorder(std::sync::Mutex<Vec<String>>);
+
+        impl ScheduleEventSink for Recorder {
+            fn task_placed(&self, task: &Task, node: &str, _cpu: u32, _verbose: bool) {
+                let event = format!("placed {} {node}", task.name);
+                self.0.lock().unwrap().push(event);
+            }
+
+            fn audit(&self, event: &AuditEvent<'_>) {
+                let debug = format!("{event:?}");
+                let name = debug.split([' ', '{']).next().unwrap().to_string();
+                self.0.lock().unwrap().push(name);
+            }
+        }
+
+        let recorder = Arc::new(Recorder::default());
+        let svc = make_svc_with_store(new_workload_store())
+            .with_event_sink(Arc::clone(&recorder) as Arc<dyn ScheduleEventSink>);
+        let add = |task| {
+            let req = SchedInfo {
+                workload_id: "wl".into(),
+                tasks: vec![task_for(task, "n1")],
+                ..Default::default()
+            };
+            svc.add_sched_info(Request::new(req))
+        };
+        assert_eq!(add("t1").await.unwrap().into_inner().status, 0);
+        svc.remove_workload(Request::new(WorkloadRef {
+            workload_id: "wl".into(),
+        }))
+        .await
+        .unwrap();
+        // The scheduler built for a reloaded configuration keeps the sink.
+        svc.reload_config(two_node_config()).await;
+        assert_eq!(add("t2").await.unwrap().into_inner().status, 0);
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                "SchedulingOptions",
+                "placed t1 n1",
+                "PhaseTimings",
+                "WorkloadRemoved",
+                "ConfigReloaded",
+                "SchedulingOptions",
+                "placed t2 n1",
+                "PhaseTimings",
+            ]
+        );
+    }
+
+    // ── Revisions ─────────────────────────────────────────────────────────────
+
+    fn revision(period: i32, runtime: i32, force: bool) -> Request<SchedInfo> {
+        let mut task = task_for("t1", "n1");
+        task.period = period;
+        task.runtime = runtime;
+        task.deadline = period;
+        Request::new(SchedInfo {
+            workload_id: "wl".into(),
+            tasks: vec![task, task_for("t2", "n2")],
+            force: force.then_some(true),
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn drastic_revision_needs_force() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        let status = |resp: Result<Response<ProtoResponse>, Status>| resp.unwrap().get_ref().status;
+        assert_eq!(
+            status(svc.add_sched_info(revision(10_000, 1_000, false)).await),
+            0
+        );
+
+        // 2× is an ordinary revision.
+        assert_eq!(
+            status(svc.add_sched_info(revision(20_000, 2_000, false)).await),
+            0
+        );
+
+        // 100× shorter period, 200× shorter runtime: refused, nothing stored.
+        let err = svc
+            .add_sched_info(revision(200, 10, false))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+        assert!(
+            err.message().contains("t1 period 20 ms → 200 µs"),
+            "{}",
+            err.message()
+        );
+        assert!(
+            err.message().contains("t1 runtime 2 ms → 10 µs"),
+            "{}",
+            err.message()
+        );
+        assert!(!err.message().contains("t2"), "{}", err.message());
+        assert_eq!(
+            store.lock().await["default"].tasks[0].period_us,
+            Micros(20_000)
+        );
+
+        // Forced, the same revision goes through.
+        assert_eq!(status(svc.add_sched_info(revision(200, 10, true)).await), 0);
+        assert_eq!(
+            store.lock().await["default"].tasks[0].period_us,
+            Micros(200)
+        );
+
+        // A different workload_id is not a revision.
+        let mut other = revision(200_000, 10_000, false);
+        other.get_mut().workload_id = "wl2".into();
+        assert_eq!(status(svc.add_sched_info(other).await), 0);
+    }
+
+    #[tokio::test]
+    async fn placeholder_reserves_capacity_and_conversion_keeps_its_cpu() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        let cam = |placeholder| TaskInfo {
+            runtime: 4_000,
+            placeholder,
+            ..task_for("cam", "n1")
+        };
+        let submit = |tasks| {
+            Request::new(SchedInfo {
+                workload_id: "wl".into(),
+                tasks,
+                ..Default::default()
+            })
+        };
+        let placed = |schedule: &NodeSchedMap| {
+            schedule["n1"]
+                .iter()
+                .find(|t| t.name == "cam")
+                .map(|t| (t.assigned_cpu, t.placeholder))
+        };
+
+        let resp = svc.add_sched_info(submit(vec![cam(true)])).await.unwrap();
+        assert_eq!(resp.get_ref().status, 0);
+        assert_eq!(
+            placed(&store.lock().await[DEFAULT_TENANT].schedule),
+            Some((1, true))
+        );
+        let status = svc
+            .get_cluster_status(Request::new(ClusterStatusRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(status.workloads[0].tasks[0].placeholder);
+        let n1 = status.nodes.iter().find(|n| n.node == "n1").unwrap();
+        assert!(n1.total_utilization > 0.0, "{n1:?}");
+
+        // Listed first, a 60 % task would take cpu1 and push cam to cpu0;
+        // the converted cam keeps the CPU it reserved instead.
+        let big = TaskInfo {
+            runtime: 6_000,
+            ..task_for("big", "n1")
+        };
+        let resp = svc
+            .add_sched_info(submit(vec![big, cam(false)]))
+            .await
+            .unwrap();
+        assert_eq!(resp.get_ref().status, 0);
+        let guard = store.lock().await;
+        let schedule = &guard[DEFAULT_TENANT].schedule;
+        assert_eq!(placed(schedule), Some((1, false)));
+        assert!(schedule["n1"]
+            .iter()
+            .any(|t| t.name == "big" && t.assigned_cpu == 0));
+    }
+
+    // ── Shadow scheduling ─────────────────────────────────────────────────────
+
+    async fn wait_for_shadows(svc: &SchedInfoServiceImpl, n: usize) -> Vec<ShadowComparison> {
+        for _ in 0..100 {
+            let comparisons = svc.shadow_comparisons();
+            if comparisons.len() >= n {
+                return comparisons;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        svc.shadow_comparisons()
+    }
+
+    #[tokio::test]
+    async fn shadow_algorithms_are_compared_but_never_stored() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store)).with_shadow_algorithms([
+            SchedAlgorithm::LeastLoaded,
+            // Same as the primary: skipped.
+            SchedAlgorithm::TargetNodePriority,
+        ]);
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_shadow".into(),
+                tasks: vec![task_for("t1", "n1"), task_for("t2", "n1")],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, 0);
+        assert!(resp.placements.iter().all(|p| p.node == "n1"));
+
+        let comparisons = wait_for_shadows(&svc, 1).await;
+        assert_eq!(comparisons.len(), 1);
+        let c = &comparisons[0];
+        assert_eq!(c.tenant, DEFAULT_TENANT);
+        assert_eq!(c.workload_id, "wl_shadow");
+        assert_eq!(c.primary, SchedAlgorithm::TargetNodePriority);
+        assert_eq!(c.shadow, SchedAlgorithm::LeastLoaded);
+        assert_eq!(c.primary_stats.nodes_used, 1);
+        assert_eq!(c.primary_stats.warning_count, 0);
+        match &c.outcome {
+            ShadowOutcome::Placed {
+                stats,
+                diff,
+                tasks_differing,
+            } => {
+                // least_loaded spreads the pair across both nodes.
+                assert_eq!(stats.nodes_used, 2);
+                assert!((stats.peak_cpu_utilization - 0.1).abs() < 1e-12);
+                assert!(diff.nodes.contains_key("n2"));
+                assert!(*tasks_differing >= 1);
+            }
+            other => panic!("unexpected shadow outcome {other:?}"),
+        }
+
+        // Only the primary schedule is live.
+        let guard = store.lock().await;
+        let ws = &guard[DEFAULT_TENANT];
+        assert_eq!(ws.generation, 1);
+        assert_eq!(ws.schedule.keys().collect::<Vec<_>>(), ["n1"]);
+        assert_eq!(ws.active_nodes.iter().collect::<Vec<_>>(), ["n1"]);
+    }
+
+    #[tokio::test]
+    async fn no_shadows_configured_records_nothing() {
+        let svc = make_svc_with_store(new_workload_store());
+        svc.add_sched_info(Request::new(shared_wl("t1", "n1")))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(svc.shadow_comparisons().is_empty());
+    }
+
+    /// Two tasks of 42 % each land on the same CPU of n1: 84 % exceeds the
+    /// two-task Liu & Layland bound (≈ 82.8 %) but fits under the 90 % gate.
+    fn marginal_workload() -> SchedInfo {
+        let mut t1 = task_for("t1", "n1");
+        let mut t2 = task_for("t2", "n1");
+        t1.runtime = 4_200;
+        t2.runtime = 4_200;
+        SchedInfo {
+            workload_id: "wl_marginal".into(),
+            tasks: vec![t1, t2],
+            ..Default::default()
+        }
+    }
+
+    async fn wait_for_calls(mock: &MockFaultNotifier, n: usize) {
+        for _ in 0..100 {
+            if mock.calls.lock().unwrap().len() >= n {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn simulated_miss_in_warn_mode_is_admitted_with_an_advisory() {
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            new_workload_store(),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        )
+        .with_schedule_defaults(
+            ScheduleOptions::default().with_simulation_check(SimulationCheck::Warn),
+        );
+        // 90 % on one CPU; the short task has the lower priority.
+        let mut long = task_for("long", "n1");
+        (long.period, long.deadline, long.runtime, long.priority) = (20_000, 20_000, 10_000, 90);
+        let mut short = task_for("short", "n1");
+        (short.period, short.deadline, short.runtime, short.priority) = (5_000, 5_000, 2_000, 10);
+
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_sim".into(),
+                tasks: vec![long, short],
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(resp.into_inner().status, 0);
+        wait_for_calls(&mock, 2).await;
+
+        let calls = mock.calls.lock().unwrap();
+        let sim = calls
+            .iter()
+            .find(|c| c.feasibility.as_ref().unwrap().analysis == "simulation")
+            .expect("simulation advisory");
+        assert_eq!(sim.task_name, "short");
+        assert_eq!(sim.severity, FaultSeverity::Advisory);
+        assert!(calls
+            .iter()
+            .any(|c| c.feasibility.as_ref().unwrap().analysis == "liu_layland"));
+    }
+
+    #[tokio::test]
+    async fn marginal_set_sends_one_advisory_when_scheduled_twice() {
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            new_workload_store(),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+
+        for _ in 0..2 {
+            let resp = svc
+                .add_sched_info(Request::new(marginal_workload()))
+                .await
+                .unwrap();
+            assert_eq!(resp.into_inner().status, 0);
+        }
+        wait_for_calls(&mock, 1).await;
+        // Give a spurious second send a chance to show up.
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let calls = mock.calls.lock().unwrap();
+        assert_eq!(calls.len(), 1, "duplicate advisory must be debounced");
+        let c = &calls[0];
+        assert_eq!(c.workload_id, "wl_marginal");
+        assert_eq!(c.node_id, "n1");
+        assert_eq!(c.fault_type, FaultType::Feasibility);
+        assert_eq!(c.severity, FaultSeverity::Advisory);
+        let f = c.feasibility.as_ref().unwrap();
+        assert_eq!(f.cpu, 1);
+        assert!((f.utilization - 0.84).abs() < 1e-9);
+        assert_eq!(f.analysis, "liu_layland");
+    }
+
+    #[tokio::test]
+    async fn zero_advisory_window_does_not_debounce() {
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            new_workload_store(),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        )
+        .with_advisory_window(Duration::ZERO);
+
+        for _ in 0..2 {
+            svc.add_sched_info(Request::new(marginal_workload()))
+                .await
+                .unwrap();
+        }
+        wait_for_calls(&mock, 2).await;
+        assert_eq!(mock.calls.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn workload_churn_leaves_no_per_workload_entries_behind() {
+        let store = new_workload_store();
+        let watchdog = Arc::new(ApplyWatchdog::new(Duration::from_secs(60)));
+        let svc =
+            make_svc_with_store(Arc::clone(&store)).with_apply_watchdog(Arc::clone(&watchdog));
+        let baseline = svc.advisory_debouncer.len();
+
+        for i in 0..1_000 {
+            let workload_id = format!("wl_churn_{i}");
+            svc.add_sched_info(Request::new(SchedInfo {
+                workload_id: workload_id.clone(),
+                ..marginal_workload()
+            }))
+            .await
+            .unwrap();
+            svc.remove_workload(Request::new(WorkloadRef { workload_id }))
+                .await
+                .unwrap();
+        }
+        assert_eq!(svc.advisory_debouncer.len(), baseline);
+        assert_eq!(svc.reclaimed_entries(), 1_000);
+        assert!(watchdog.is_empty());
+        assert!(store.lock().await.is_empty());
+
+        // Replaced under another workload_id: the old one's entry goes too.
+        svc.add_sched_info(Request::new(marginal_workload()))
+            .await
+            .unwrap();
+        assert_eq!(svc.advisory_debouncer.len(), baseline + 1);
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl_other".into(),
+            ..marginal_workload()
+        }))
+        .await
+        .unwrap();
+        assert_eq!(svc.advisory_debouncer.len(), baseline + 1);
+        assert_eq!(svc.reclaimed_entries(), 1_001);
+    }
+
+    #[tokio::test]
+    async fn feasible_set_sends_no_advisory() {
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            new_workload_store(),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl_ok".into(),
+            tasks: vec![task_for("t1", "n1"), task_for("t2", "n1")],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(mock.calls.lock().unwrap().is_empty());
+    }
+
+    // ── Pending queue ─────────────────────────────────────────────────────────
+
+    /// 80 % utilisation: one per CPU.
+    fn heavy(name: &str, node: &str) -> TaskInfo {
+        TaskInfo {
+            runtime: 8_000,
+            ..task_for(name, node)
+        }
+    }
+
+    /// Occupies every CPU of `two_node_config`.
+    fn filling_workload() -> SchedInfo {
+        SchedInfo {
+            workload_id: "wl_big".into(),
+            tasks: vec![
+                heavy("a1", "n1"),
+                heavy("a2", "n1"),
+                heavy("a3", "n2"),
+                heavy("a4", "n2"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn queued_workload(node: &str) -> SchedInfo {
+        SchedInfo {
+            workload_id: "wl_wait".into(),
+            tasks: vec![heavy("b1", node)],
+            queue_if_full: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn capacity_limited_workload_is_queued_then_scheduled_on_release() {
+        let store = new_workload_store();
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            Arc::clone(&store),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+        let resp = svc
+            .add_sched_info(as_tenant("a", filling_workload()))
+            .await
+            .unwrap();
+        assert_eq!(resp.into_inner().status, 0);
+
+        let resp = svc
+            .add_sched_info(as_tenant("b", queued_workload("n1")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, STATUS_QUEUED);
+        assert!(resp.queued);
+        assert!(!store.lock().await.contains_key("b"));
+
+        let status = svc
+            .get_cluster_status(as_tenant("b", ClusterStatusRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        let pending = status.pending.unwrap();
+        assert_eq!(pending.depth, 1);
+        assert_eq!(pending.workloads[0].workload_id, "wl_wait");
+
+        svc.remove_workload(as_tenant(
+            "a",
+            WorkloadRef {
+                workload_id: "wl_big".into(),
+            },
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(store.lock().await["b"].workload_id, "wl_wait");
+        assert!(svc.pending.lock().await.is_empty());
+        wait_for_calls(&mock, 1).await;
+        let calls = mock.calls.lock().unwrap();
+        assert!(calls
+            .iter()
+            .any(|c| c.fault_type == FaultType::WorkloadScheduled && c.workload_id == "wl_wait"));
+    }
+
+    #[tokio::test]
+    async fn queued_workload_can_be_removed_before_it_runs() {
+        let svc = make_svc_with_store(new_workload_store());
+        svc.add_sched_info(as_tenant("a", filling_workload()))
+            .await
+            .unwrap();
+        svc.add_sched_info(as_tenant("b", queued_workload("n1")))
+            .await
+            .unwrap();
+
+        let resp = svc
+            .remove_workload(as_tenant(
+                "b",
+                WorkloadRef {
+                    workload_id: "wl_wait".into(),
+                },
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.into_inner().status, 0);
+        assert!(svc.pending.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn full_pending_queue_is_resource_exhausted() {
+        let svc = make_svc_with_store(new_workload_store()).with_pending_capacity(1);
+        svc.add_sched_info(as_tenant("a", filling_workload()))
+            .await
+            .unwrap();
+        svc.add_sched_info(as_tenant("b", queued_workload("n1")))
+            .await
+            .unwrap();
+
+        let err = svc
+            .add_sched_info(as_tenant("c", queued_workload("n2")))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+    }
+
+    #[tokio::test]
+    async fn unplaceable_workload_is_not_queued() {
+        let svc = make_svc_with_store(new_workload_store());
+        svc.add_sched_info(as_tenant("a", filling_workload()))
+            .await
+            .unwrap();
+
+        // Unknown node: no amount of waiting would help.
+        let resp = svc
+            .add_sched_info(as_tenant("b", queued_workload("n9")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, -1);
+        assert!(!resp.queued);
+        assert!(svc.pending.lock().await.is_empty());
+    }
+
+    // ── Node drain ────────────────────────────────────────────────────────────
+
+    fn movable(name: &str, node: &str, priority: i32) -> TaskInfo {
+        TaskInfo {
+            priority,
+            target_node_policy: Some(ProtoTargetNodePolicy::Preferred as i32),
+            ..task_for(name, node)
+        }
+    }
+
+    fn names(placements: &[TaskPlacement]) -> Vec<&str> {
+        placements.iter().map(|p| p.task.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn drain_moves_lowest_priority_tasks_in_batches() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        let tasks = (1..=6)
+            .map(|i| movable(&format!("t{i}"), "n1", 70 - 10 * i))
+            .collect();
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_drain".into(),
+                tasks,
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(resp.placements.iter().all(|p| p.node == "n1"));
+
+        for (step, expected, remaining) in [
+            (1, ["t6", "t5"], 4),
+            (2, ["t4", "t3"], 2),
+            (3, ["t2", "t1"], 0),
+        ] {
+            let progress = svc.drain_node("n1", 2).await.unwrap();
+            assert_eq!(names(&progress.moved), expected, "step {step}");
+            assert!(progress.moved.iter().all(|p| p.node == "n2"));
+            assert_eq!(progress.remaining, remaining);
+            assert!(progress.pinned.is_empty());
+
+            let guard = store.lock().await;
+            let ws = &guard[DEFAULT_TENANT];
+            assert_eq!(ws.generation, 1 + step);
+            assert_eq!(ws.schedule["n2"].len(), 2 * step as usize);
+            assert!(ws.previous.is_some());
+        }
 
-    fn two_node_config() -> Arc<NodeConfigManager> {
-        Arc::new(NodeConfigManager::from_nodes(vec![
-            NodeConfig {
-                name: "n1".into(),
-                available_cpus: vec![0, 1],
-                max_memory_mb: 4096,
-                architecture: "x86_64".into(),
-                location: "test".into(),
-                description: "test node 1".into(),
+        let guard = store.lock().await;
+        let ws = &guard[DEFAULT_TENANT];
+        assert!(!ws.schedule.contains_key("n1"));
+        assert_eq!(ws.active_nodes.iter().collect::<Vec<_>>(), ["n2"]);
+        drop(guard);
+
+        let idle = svc.drain_node("n1", 2).await.unwrap();
+        assert_eq!(idle, DrainProgress::default());
+    }
+
+    #[tokio::test]
+    async fn update_task_changes_one_task_in_a_new_generation() {
+        use crate::report::diff::NodeDiff;
+
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl_upd".into(),
+            tasks: vec![task_for("t1", "n1"), task_for("t2", "n1")],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+        let timing = TaskTiming {
+            period_us: Micros(10_000),
+            runtime_us: Micros(1_050),
+            deadline_us: Micros(10_000),
+        };
+
+        let outcome = svc
+            .update_task(DEFAULT_TENANT, "wl_upd", "t1", timing)
+            .await
+            .unwrap();
+        assert_eq!(outcome.level, UpdateLevel::InPlace);
+        assert_eq!(outcome.generation, 2);
+        assert_eq!(outcome.placement.node, "n1");
+
+        let guard = store.lock().await;
+        let ws = &guard[DEFAULT_TENANT];
+        let prev = &ws.previous.as_ref().unwrap()["n1"];
+        let diff = NodeDiff::between(prev, &ws.schedule["n1"]);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].name, "t1");
+        let stored = ws.tasks.iter().find(|t| t.name == "t1").unwrap();
+        assert_eq!(stored.runtime_us, Micros(1_050));
+        drop(guard);
+
+        let err = svc
+            .update_task(DEFAULT_TENANT, "wl_upd", "t9", timing)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TaskUpdateError::UnknownTask { .. }), "{err}");
+        let err = svc
+            .update_task("other", "wl_upd", "t1", timing)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TaskUpdateError::UnknownWorkload(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn drain_orders_by_importance_and_reports_pinned_tasks() {
+        let store = new_workload_store();
+        let svc = make_svc_with_store(Arc::clone(&store));
+        svc.add_sched_info(as_tenant(
+            "a",
+            SchedInfo {
+                workload_id: "wl_a".into(),
+                // No policy under target_node_priority: a hard target.
+                tasks: vec![task_for("a_hard", "n1"), movable("a_soft", "n1", 10)],
+                importance: 1,
+                ..Default::default()
             },
-            NodeConfig {
-                name: "n2".into(),
-                available_cpus: vec![0, 1],
-                max_memory_mb: 4096,
-                architecture: "x86_64".into(),
-                location: "test".into(),
-                description: "test node 2".into(),
+        ))
+        .await
+        .unwrap();
+        svc.add_sched_info(as_tenant(
+            "b",
+            SchedInfo {
+                workload_id: "wl_b".into(),
+                tasks: vec![movable("b_soft", "n1", 90)],
+                ..Default::default()
             },
-        ]))
+        ))
+        .await
+        .unwrap();
+
+        let first = svc.drain_node("n1", 1).await.unwrap();
+        assert_eq!(names(&first.moved), ["b_soft"]);
+        assert_eq!(first.remaining, 1);
+        assert_eq!(first.pinned, [("a".to_string(), "a_hard".to_string())]);
+        assert_eq!(store.lock().await["a"].generation, 1);
+
+        let second = svc.drain_node("n1", 1).await.unwrap();
+        assert_eq!(names(&second.moved), ["a_soft"]);
+        assert_eq!(second.remaining, 0);
+        assert_eq!(second.pinned.len(), 1);
+
+        let guard = store.lock().await;
+        let names_on_n1: Vec<&str> = guard["a"].schedule["n1"]
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names_on_n1, ["a_hard"]);
+        assert_eq!(guard["a"].generation, 2);
     }
 
-    fn task_for(name: &str, node: &str) -> TaskInfo {
-        TaskInfo {
-            name: name.into(),
-            node_id: node.into(),
-            priority: 50,
-            policy: 1,
-            cpu_affinity: 0,
-            period: 10_000,
-            runtime: 1_000,
-            deadline: 10_000,
-            release_time: 0,
-            max_dmiss: 3,
+    #[tokio::test]
+    async fn drain_moves_lower_classes_first_whatever_their_importance() {
+        let svc = make_svc_with_store(new_workload_store());
+        for (tenant, class, importance) in [("a", "platform", 0), ("b", "best_effort", 9)] {
+            svc.add_sched_info(as_tenant(
+                tenant,
+                SchedInfo {
+                    workload_id: format!("wl_{tenant}"),
+                    tasks: vec![movable(&format!("{tenant}_soft"), "n1", 50)],
+                    priority_class: Some(class.into()),
+                    importance,
+                    ..Default::default()
+                },
+            ))
+            .await
+            .unwrap();
         }
+
+        let first = svc.drain_node("n1", 1).await.unwrap();
+        assert_eq!(names(&first.moved), ["b_soft"]);
+        let second = svc.drain_node("n1", 1).await.unwrap();
+        assert_eq!(names(&second.moved), ["a_soft"]);
     }
 
-    fn make_svc_with_store(store: WorkloadStore) -> SchedInfoServiceImpl {
-        let mock = MockFaultNotifier::arc();
-        SchedInfoServiceImpl::new(two_node_config(), store, mock as Arc<dyn FaultNotifier>)
+    // ── Config reload ─────────────────────────────────────────────────────────
+
+    fn n1_only_config() -> Arc<NodeConfigManager> {
+        let mut n1 = two_node_config().get_node_config("n1").unwrap().clone();
+        n1.description = "reloaded".into();
+        Arc::new(NodeConfigManager::from_nodes(vec![n1]))
     }
 
-    // ── Tests ─────────────────────────────────────────────────────────────────
+    /// One workload with a task on each node: `keep` on n1, `soft` and a
+    /// hard-targeted `hard` on n2.
+    async fn placed_on_both_nodes(
+        evacuate: bool,
+    ) -> (SchedInfoServiceImpl, Arc<MockFaultNotifier>) {
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            new_workload_store(),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        )
+        .with_orphan_evacuation(evacuate);
+        let resp = svc
+            .add_sched_info(Request::new(SchedInfo {
+                workload_id: "wl_reload".into(),
+                tasks: vec![
+                    task_for("keep", "n1"),
+                    movable("soft", "n2", 10),
+                    task_for("hard", "n2"),
+                ],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, 0, "{resp:?}");
+        (svc, mock)
+    }
 
     #[tokio::test]
-    async fn add_sched_info_two_nodes_returns_ok_status() {
-        let svc = make_svc_with_store(new_workload_store());
-        let si = SchedInfo {
-            workload_id: "wl_ok".into(),
-            tasks: vec![task_for("t1", "n1"), task_for("t2", "n2")],
-        };
-        let resp = svc.add_sched_info(Request::new(si)).await.unwrap();
-        assert_eq!(resp.into_inner().status, 0);
+    async fn reload_marks_placements_on_removed_nodes_orphaned() {
+        let (svc, mock) = placed_on_both_nodes(false).await;
+        let report = svc.reload_config(n1_only_config()).await;
+
+        assert!(report.evacuated.is_empty());
+        let orphaned: Vec<(&str, &str)> = report
+            .orphaned
+            .iter()
+            .map(|o| (o.node.as_str(), o.task.as_str()))
+            .collect();
+        assert_eq!(orphaned, [("n2", "hard"), ("n2", "soft")]);
+        assert_eq!(report.orphaned[0].workload_id, "wl_reload");
+
+        // Status: n2 is gone from the capacity section and listed as orphaned.
+        let status = svc
+            .get_cluster_status(Request::new(ClusterStatusRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        let nodes: Vec<&str> = status.nodes.iter().map(|n| n.node.as_str()).collect();
+        assert_eq!(nodes, ["n1"]);
+        assert_eq!(status.orphaned.len(), 1);
+        assert_eq!(status.orphaned[0].node, "n2");
+        assert_eq!(status.orphaned[0].tasks, ["hard", "soft"]);
+        assert!((status.orphaned[0].total_utilization - 0.2).abs() < 1e-9);
+
+        // One advisory per (workload, node).
+        wait_for_calls(&mock, 1).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let calls = mock.calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].fault_type, FaultType::NodeOrphaned);
+        assert_eq!(calls[0].severity, FaultSeverity::Advisory);
+        assert_eq!(calls[0].node_id, "n2");
+        assert_eq!(calls[0].workload_id, "wl_reload");
     }
 
     #[tokio::test]
-    async fn add_sched_info_empty_tasks_returns_error_status() {
-        let svc = make_svc_with_store(new_workload_store());
+    async fn reload_with_evacuation_moves_what_it_can() {
+        let (svc, mock) = placed_on_both_nodes(true).await;
+        let report = svc.reload_config(n1_only_config()).await;
+
+        assert_eq!(names(&report.evacuated), ["soft"]);
+        assert_eq!(report.evacuated[0].node, "n1");
+        assert_eq!(report.orphaned.len(), 1);
+        assert_eq!(report.orphaned[0].task, "hard");
+
+        let guard = svc.workload_store.lock().await;
+        let ws = &guard[DEFAULT_TENANT];
+        assert_eq!(ws.generation, 2);
+        let on_n1: Vec<&str> = ws.schedule["n1"].iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(on_n1, ["keep", "soft"]);
+        drop(guard);
+
+        wait_for_calls(&mock, 1).await;
+        assert_eq!(
+            mock.calls.lock().unwrap()[0].fault_type,
+            FaultType::NodeOrphaned
+        );
+
+        // Placing a new workload no longer trips over the removed node.
         let resp = svc
             .add_sched_info(Request::new(SchedInfo {
-                workload_id: "wl_empty".into(),
-                tasks: vec![],
+                workload_id: "wl_next".into(),
+                tasks: vec![task_for("next", "n1")],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, 0, "{resp:?}");
+    }
+
+    const N1_ONLY_YAML: &str = "nodes:\n  n1:\n    available_cpus: [0, 1]\n";
+
+    #[tokio::test]
+    async fn pushed_config_is_swapped_in_only_when_valid_and_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nodes.yaml");
+        let (svc, _mock) = placed_on_both_nodes(false).await;
+        let svc = svc.with_node_config_path(&path);
+        let push = |yaml: &str| {
+            svc.apply_node_config(Request::new(NodeConfigUpdate { yaml: yaml.into() }))
+        };
+        let status = || svc.get_cluster_status(Request::new(ClusterStatusRequest::default()));
+
+        let result = push(N1_ONLY_YAML).await.unwrap().into_inner();
+        assert!(result.applied, "{result:?}");
+        assert!(result.findings.is_empty());
+        assert_eq!((result.generation, result.orphaned), (1, 2));
+        let now = status().await.unwrap().into_inner();
+        assert_eq!(now.config_generation, 1);
+        assert_eq!(now.nodes.len(), 1);
+
+        // Persisted: a restart would load the same nodes.
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), N1_ONLY_YAML);
+        let mut restarted = NodeConfigManager::new();
+        restarted.load_from_file(&path).unwrap();
+        assert!(restarted.get_node_config("n1").is_some());
+        assert!(restarted.get_node_config("n2").is_none());
+
+        // Invalid: findings, no swap, no write.
+        let invalid = "nodes:\n  n1:\n    available_cpus: [0]\n    max_workloads: 0\n\
+                       \x20 n3:\n    available_cpus: [0]\n    endpoint: \"n3\"\n";
+        for (yaml, node) in [(invalid, "n1"), ("nodes: [", "")] {
+            let result = push(yaml).await.unwrap().into_inner();
+            assert!(!result.applied);
+            assert_eq!(result.generation, 1);
+            assert_eq!(result.findings[0].node, node, "{:?}", result.findings);
+        }
+        let result = push(invalid).await.unwrap().into_inner();
+        let findings: Vec<&str> = result.findings.iter().map(|f| f.node.as_str()).collect();
+        assert_eq!(findings, ["n1", "n3"]);
+        let now = status().await.unwrap().into_inner();
+        assert_eq!(now.config_generation, 1);
+        let nodes: Vec<&str> = now.nodes.iter().map(|n| n.node.as_str()).collect();
+        assert_eq!(nodes, ["n1"]);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), N1_ONLY_YAML);
+    }
+
+    #[tokio::test]
+    async fn pushed_config_that_cannot_be_persisted_is_not_applied() {
+        let dir = tempfile::tempdir().unwrap();
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            new_workload_store(),
+            MockFaultNotifier::arc() as Arc<dyn FaultNotifier>,
+        )
+        .with_node_config_path(dir.path().join("missing").join("nodes.yaml"));
+
+        let err = svc
+            .apply_node_config(Request::new(NodeConfigUpdate {
+                yaml: N1_ONLY_YAML.into(),
             }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Internal);
+        assert_eq!(svc.scheduler().node_ids().len(), 2);
+        assert_eq!(svc.config_generation.load(Ordering::SeqCst), 0);
+    }
+
+    // ── Schedule events ───────────────────────────────────────────────────────
+
+    async fn watch(
+        svc: &SchedInfoServiceImpl,
+        tenant: &str,
+        backlog: Option<u32>,
+    ) -> ReceiverStream<Result<ScheduleEvent, Status>> {
+        svc.watch_schedule_events(as_tenant(tenant, WatchScheduleEventsRequest { backlog }))
+            .await
+            .unwrap()
+            .into_inner()
+    }
+
+    async fn next_events(
+        stream: &mut ReceiverStream<Result<ScheduleEvent, Status>>,
+        n: usize,
+    ) -> Vec<(u64, ScheduleEventKind, String)> {
+        use tokio_stream::StreamExt;
+        let mut got = Vec::new();
+        for _ in 0..n {
+            let e = tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await
+                .expect("event within 5 s")
+                .unwrap()
+                .unwrap();
+            got.push((e.sequence, e.kind(), e.workload_id));
+        }
+        got
+    }
+
+    #[tokio::test]
+    async fn watch_streams_schedule_then_removal_in_order() {
+        let svc = make_svc_with_store(new_workload_store());
+        let mut stream = watch(&svc, "prod", None).await;
+
+        svc.add_sched_info(as_tenant("prod", shared_wl("t1", "n1")))
             .await
             .unwrap();
-        assert_ne!(resp.into_inner().status, 0);
+        // Another tenant's change takes a sequence number but is not sent.
+        svc.add_sched_info(as_tenant("test", shared_wl("t2", "n2")))
+            .await
+            .unwrap();
+        svc.remove_workload(as_tenant(
+            "prod",
+            WorkloadRef {
+                workload_id: "wl_shared".into(),
+            },
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(
+            next_events(&mut stream, 2).await,
+            [
+                (1, ScheduleEventKind::WorkloadScheduled, "wl_shared".into()),
+                (3, ScheduleEventKind::WorkloadRemoved, "wl_shared".into()),
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn add_sched_info_unknown_node_returns_error_status() {
+    async fn late_subscriber_replays_the_requested_backlog() {
         let svc = make_svc_with_store(new_workload_store());
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl_drain".into(),
+            tasks: vec![movable("t1", "n1", 10)],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+        assert_eq!(svc.drain_node("n1", 1).await.unwrap().remaining, 0);
+
+        // scheduled (1), updated by the drain (2), node drained (3).
+        let mut stream = watch(&svc, DEFAULT_TENANT, Some(2)).await;
+        assert_eq!(
+            next_events(&mut stream, 2).await,
+            [
+                (2, ScheduleEventKind::WorkloadUpdated, "wl_drain".into()),
+                (3, ScheduleEventKind::NodeDrained, String::new()),
+            ]
+        );
+
+        svc.remove_workload(Request::new(WorkloadRef {
+            workload_id: "wl_drain".into(),
+        }))
+        .await
+        .unwrap();
+        assert_eq!(
+            next_events(&mut stream, 1).await,
+            [(4, ScheduleEventKind::WorkloadRemoved, "wl_drain".into())]
+        );
+    }
+
+    // ── Apply watchdog ────────────────────────────────────────────────────────
+
+    const APPLY_DEADLINE: Duration = Duration::from_secs(5);
+
+    /// The [`placed_on_both_nodes`] workload under an apply watchdog; n1 acknowledges in
+    /// time through `ReportApply`, n2 never does.
+    async fn n2_wedged(
+        failover: bool,
+    ) -> (
+        SchedInfoServiceImpl,
+        Arc<MockFaultNotifier>,
+        Arc<ApplyWatchdog>,
+    ) {
+        use crate::grpc::node_service::NodeServiceImpl;
+        use crate::proto::schedinfo_v1::{
+            node_service_server::NodeService, ApplyReport, ApplyStatus, TaskApplyResult,
+        };
+
+        let mock = MockFaultNotifier::arc();
+        let watchdog = Arc::new(ApplyWatchdog::new(APPLY_DEADLINE));
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            new_workload_store(),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        )
+        .with_apply_watchdog(Arc::clone(&watchdog))
+        .with_apply_failover(failover);
         let resp = svc
             .add_sched_info(Request::new(SchedInfo {
-                workload_id: "wl_bad".into(),
-                tasks: vec![task_for("t1", "node_not_in_config")],
+                workload_id: "wl_reload".into(),
+                tasks: vec![
+                    task_for("keep", "n1"),
+                    movable("soft", "n2", 10),
+                    task_for("hard", "n2"),
+                ],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.status, 0, "{resp:?}");
+        assert_eq!(watchdog.len(), 2);
+
+        let node_svc = NodeServiceImpl::new(
+            Arc::clone(&svc.workload_store),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+            Duration::from_secs(1),
+        )
+        .with_apply_watchdog(Arc::clone(&watchdog));
+        let generation = svc.workload_store.lock().await[DEFAULT_TENANT].generation;
+        node_svc
+            .report_apply(Request::new(ApplyReport {
+                node_id: "n1".into(),
+                generation,
+                tasks: vec![TaskApplyResult {
+                    task_name: "keep".into(),
+                    status: ApplyStatus::Applied as i32,
+                    ..Default::default()
+                }],
+                node: None,
+                ..Default::default()
             }))
             .await
             .unwrap();
-        assert_ne!(resp.into_inner().status, 0);
+        assert_eq!(watchdog.len(), 1);
+        (svc, mock, watchdog)
+    }
+
+    async fn state_of(svc: &SchedInfoServiceImpl, node: &str, task: &str) -> Option<TaskState> {
+        svc.workload_store.lock().await[DEFAULT_TENANT]
+            .task_states
+            .get(node, task)
     }
 
     #[tokio::test]
-    async fn add_sched_info_stores_workload_in_workload_store() {
-        let store = new_workload_store();
-        let svc = make_svc_with_store(Arc::clone(&store));
+    async fn missed_apply_deadline_degrades_the_node_and_raises_a_fault() {
+        let (svc, mock, _) = n2_wedged(false).await;
+        mock.calls.lock().unwrap().clear();
+        let armed_at = Instant::now();
 
-        svc.add_sched_info(Request::new(SchedInfo {
-            workload_id: "wl_stored".into(),
-            tasks: vec![task_for("t1", "n1")],
-        }))
-        .await
-        .unwrap();
+        assert!(svc
+            .check_apply_deadlines_at(armed_at + APPLY_DEADLINE / 2)
+            .await
+            .is_empty());
+        let missed = svc
+            .check_apply_deadlines_at(armed_at + APPLY_DEADLINE * 2)
+            .await;
+        assert_eq!(missed.len(), 1);
+        assert_eq!((missed[0].node.as_str(), missed[0].generation), ("n2", 1));
 
-        let guard = store.lock().await;
-        let ws = guard.as_ref().expect("workload should be in the store");
-        assert_eq!(ws.workload_id, "wl_stored");
-        assert!(ws.active_nodes.contains("n1"));
+        assert_eq!(state_of(&svc, "n1", "keep").await, Some(TaskState::Pending));
+        for task in ["soft", "hard"] {
+            assert_eq!(state_of(&svc, "n2", task).await, Some(TaskState::Degraded));
+        }
+        assert_eq!(
+            svc.workload_store.lock().await[DEFAULT_TENANT].generation,
+            1
+        );
+
+        let calls = mock.calls.lock().unwrap().clone();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].fault_type, FaultType::ApplyTimeout);
+        assert_eq!(calls[0].severity, FaultSeverity::Critical);
+        assert_eq!(
+            (calls[0].workload_id.as_str(), calls[0].node_id.as_str()),
+            ("wl_reload", "n2")
+        );
+
+        // Reported once.
+        assert!(svc
+            .check_apply_deadlines_at(armed_at + APPLY_DEADLINE * 4)
+            .await
+            .is_empty());
     }
 
     #[tokio::test]
-    async fn add_sched_info_replaces_previous_workload_and_cancels_barrier() {
-        let store = new_workload_store();
-        let svc = make_svc_with_store(Arc::clone(&store));
+    async fn missed_apply_deadline_with_failover_moves_unpinned_tasks() {
+        let (svc, mock, watchdog) = n2_wedged(true).await;
+        mock.calls.lock().unwrap().clear();
 
-        // First workload — subscribe to its barrier before replacing
-        svc.add_sched_info(Request::new(SchedInfo {
-            workload_id: "wl_first".into(),
-            tasks: vec![task_for("t1", "n1")],
+        let missed = svc
+            .check_apply_deadlines_at(Instant::now() + APPLY_DEADLINE * 2)
+            .await;
+        assert_eq!(missed.len(), 1);
+
+        let guard = svc.workload_store.lock().await;
+        let ws = &guard[DEFAULT_TENANT];
+        assert_eq!(ws.generation, 2);
+        let on_n1: Vec<&str> = ws.schedule["n1"].iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(on_n1, ["keep", "soft"]);
+        assert_eq!(ws.task_states.get("n1", "soft"), Some(TaskState::Pending));
+        assert_eq!(ws.task_states.get("n2", "hard"), Some(TaskState::Degraded));
+        drop(guard);
+
+        // n1 now owes the new generation; the wedged node is not re-armed.
+        assert_eq!(watchdog.len(), 1);
+        assert!(!watchdog.ack(DEFAULT_TENANT, "n2", 2));
+        assert!(watchdog.ack(DEFAULT_TENANT, "n1", 2));
+        assert_eq!(
+            mock.calls.lock().unwrap()[0].fault_type,
+            FaultType::ApplyTimeout
+        );
+    }
+
+    #[tokio::test]
+    async fn removing_the_workload_cancels_its_apply_deadlines() {
+        let (svc, mock, watchdog) = n2_wedged(true).await;
+        mock.calls.lock().unwrap().clear();
+        svc.remove_workload(Request::new(WorkloadRef {
+            workload_id: "wl_reload".into(),
         }))
         .await
         .unwrap();
+        assert!(watchdog.is_empty());
+        assert!(svc
+            .check_apply_deadlines_at(Instant::now() + APPLY_DEADLINE * 2)
+            .await
+            .is_empty());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(mock
+            .calls
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|c| c.fault_type != FaultType::ApplyTimeout));
+    }
 
-        let barrier_rx = {
-            let guard = store.lock().await;
-            guard.as_ref().unwrap().barrier_tx.subscribe()
-        };
+    // ── Workload expiry ───────────────────────────────────────────────────────
 
-        // Replace with second workload
-        svc.add_sched_info(Request::new(SchedInfo {
-            workload_id: "wl_second".into(),
-            tasks: vec![task_for("t2", "n2")],
-        }))
+    #[tokio::test]
+    async fn expired_workload_is_retracted_and_frees_its_capacity() {
+        use crate::grpc::node_service::NodeServiceImpl;
+        use crate::proto::schedinfo_v1::{node_service_server::NodeService, NodeSchedRequest};
+
+        let mock = MockFaultNotifier::arc();
+        let svc = SchedInfoServiceImpl::new(
+            two_node_config(),
+            new_workload_store(),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+        );
+        let node_svc = NodeServiceImpl::new(
+            Arc::clone(&svc.workload_store),
+            Arc::clone(&mock) as Arc<dyn FaultNotifier>,
+            Duration::from_secs(1),
+        );
+        let admitted_at = Instant::now();
+        svc.add_sched_info(as_tenant(
+            "a",
+            SchedInfo {
+                ttl_seconds: Some(60),
+                ..filling_workload()
+            },
+        ))
         .await
         .unwrap();
+        svc.add_sched_info(as_tenant("b", queued_workload("n1")))
+            .await
+            .unwrap();
+        let mut stream = watch(&svc, "a", Some(0)).await;
 
-        // First barrier should now be Cancelled
-        assert_eq!(*barrier_rx.borrow(), BarrierStatus::Cancelled);
+        let status = svc
+            .get_cluster_status(as_tenant("a", ClusterStatusRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        let left = status.workloads[0].ttl_remaining_ms.unwrap();
+        assert!(left > 59_000 && left <= 60_000, "{left}");
 
-        // Active workload should be the second one
-        let guard = store.lock().await;
-        assert_eq!(guard.as_ref().unwrap().workload_id, "wl_second");
+        assert!(svc
+            .expire_workloads_at(admitted_at + Duration::from_secs(30))
+            .await
+            .is_empty());
+        let expired = svc
+            .expire_workloads_at(admitted_at + Duration::from_secs(61))
+            .await;
+        assert_eq!(expired, [("a".to_string(), "wl_big".to_string())]);
+
+        // Retracted from the nodes, and the capacity went to the queue.
+        let err = node_svc
+            .get_sched_info(as_tenant(
+                "a",
+                NodeSchedRequest {
+                    node_id: "n1".into(),
+                    ..Default::default()
+                },
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+        assert_eq!(svc.workload_store.lock().await["b"].workload_id, "wl_wait");
+        assert_eq!(
+            next_events(&mut stream, 1).await,
+            [(2, ScheduleEventKind::WorkloadExpired, "wl_big".into())]
+        );
+        wait_for_calls(&mock, 2).await;
+        assert!(mock
+            .calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|c| c.fault_type == FaultType::WorkloadExpired
+                && c.severity == FaultSeverity::Advisory
+                && c.workload_id == "wl_big"));
+    }
+
+    #[tokio::test]
+    async fn resubmitting_renews_or_clears_the_ttl() {
+        let svc = make_svc_with_store(new_workload_store());
+        let submit = |ttl_seconds| {
+            svc.add_sched_info(Request::new(SchedInfo {
+                ttl_seconds,
+                ..shared_wl("t1", "n1")
+            }))
+        };
+        let err = submit(Some(0)).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(), "1021");
+
+        let first = Instant::now();
+        submit(Some(10)).await.unwrap();
+        submit(Some(100)).await.unwrap();
+        let expires_at = svc.workload_store.lock().await[DEFAULT_TENANT].expires_at;
+        assert!(expires_at.unwrap() >= first + Duration::from_secs(100));
+        assert!(svc
+            .expire_workloads_at(first + Duration::from_secs(50))
+            .await
+            .is_empty());
+
+        submit(None).await.unwrap();
+        assert!(svc
+            .expire_workloads_at(first + Duration::from_secs(1_000))
+            .await
+            .is_empty());
+        assert!(svc.workload_store.lock().await.contains_key(DEFAULT_TENANT));
+    }
+
+    // ── Compaction ────────────────────────────────────────────────────────────
+
+    /// Tenants `a` and `b` spread over both nodes by `least_loaded`, on a
+    /// service that compacts with `best_fit_decreasing` after a minute idle.
+    async fn fragmented(store: &WorkloadStore) -> SchedInfoServiceImpl {
+        let svc = make_svc_with_store(Arc::clone(store))
+            .with_schedule_defaults(
+                ScheduleOptions::default().with_algorithm(SchedAlgorithm::BestFitDecreasing),
+            )
+            .with_compaction_idle(Duration::from_secs(60));
+        for (tenant, node) in [("a", "n1"), ("b", "n2")] {
+            let resp = svc
+                .add_sched_info(as_tenant(
+                    tenant,
+                    SchedInfo {
+                        workload_id: format!("wl_{tenant}"),
+                        algorithm: Some("least_loaded".into()),
+                        tasks: vec![task_for(&format!("{tenant}_1"), "")],
+                        ..Default::default()
+                    },
+                ))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(resp.placements[0].node, node);
+        }
+        svc
+    }
+
+    async fn compaction_seen_by(
+        svc: &SchedInfoServiceImpl,
+        tenant: &str,
+    ) -> Option<crate::proto::schedinfo_v1::CompactionProposal> {
+        svc.get_cluster_status(as_tenant(tenant, ClusterStatusRequest::default()))
+            .await
+            .unwrap()
+            .into_inner()
+            .compaction
+    }
+
+    #[tokio::test]
+    async fn fragmented_cluster_gets_a_compaction_proposal_that_applies_as_deltas() {
+        let store = new_workload_store();
+        let svc = fragmented(&store).await;
+        let t0 = Instant::now();
+        assert!(svc.propose_compaction_at(t0).await.is_none());
+        assert!(svc
+            .propose_compaction_at(t0 + Duration::from_secs(59))
+            .await
+            .is_none());
+
+        let p = svc
+            .propose_compaction_at(t0 + Duration::from_secs(60))
+            .await
+            .expect("idle fragmented cluster gets a proposal");
+        assert_eq!((p.nodes_in_use_before, p.nodes_in_use_after), (2, 1));
+        assert_eq!(p.nodes_freed, ["n2"]);
+        assert_eq!(p.moves.len(), 1);
+        assert_eq!(
+            (p.moves[0].task.as_str(), p.moves[0].to_node.as_str()),
+            ("b_1", "n1")
+        );
+        assert!(p.peak_after >= p.peak_before);
+        // Proposed once per idle period.
+        assert!(svc
+            .propose_compaction_at(t0 + Duration::from_secs(600))
+            .await
+            .is_none());
+
+        let mut sub = svc.events.subscribe(None, |e| {
+            e.kind == ScheduleEventKind::CompactionProposed as i32
+        });
+        assert_eq!(sub.next().await.unwrap().proposal_id, p.id);
+        let seen_by_b = compaction_seen_by(&svc, "b").await.unwrap();
+        assert_eq!(seen_by_b.proposal_id, p.id);
+        assert_eq!(seen_by_b.moves.len(), 1);
+        assert!(compaction_seen_by(&svc, "a")
+            .await
+            .unwrap()
+            .moves
+            .is_empty());
+
+        let applied = svc.apply_compaction(p.id).await.unwrap();
+        assert_eq!(applied.id, p.id);
+        {
+            let guard = store.lock().await;
+            assert_eq!(guard["a"].generation, 1);
+            let b = &guard["b"];
+            assert_eq!(b.generation, 2);
+            assert_eq!(b.active_nodes.iter().collect::<Vec<_>>(), ["n1"]);
+            assert!(b.previous.as_ref().unwrap().contains_key("n2"));
+        }
+        assert!(compaction_seen_by(&svc, "b").await.is_none());
+        assert_eq!(
+            svc.apply_compaction(p.id).await.unwrap_err(),
+            CompactionError::Unknown(p.id)
+        );
+
+        // Already compact: the next idle period finds nothing better.
+        let t1 = t0 + Duration::from_secs(700);
+        svc.propose_compaction_at(t1).await;
+        assert!(svc
+            .propose_compaction_at(t1 + Duration::from_secs(60))
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn compaction_proposal_expires_when_the_cluster_changes() {
+        let store = new_workload_store();
+        let svc = fragmented(&store).await;
+        let t0 = Instant::now();
+        svc.propose_compaction_at(t0).await;
+        let p = svc
+            .propose_compaction_at(t0 + Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        svc.set_node_cordoned("n2", true);
+        assert!(compaction_seen_by(&svc, "b").await.is_none());
+        assert_eq!(
+            svc.apply_compaction(p.id).await.unwrap_err(),
+            CompactionError::Expired(p.id)
+        );
+        assert_eq!(store.lock().await["b"].generation, 1);
+        // The change restarts the idle period.
+        assert!(svc
+            .propose_compaction_at(t0 + Duration::from_secs(61))
+            .await
+            .is_none());
     }
 }
@@ -0,0 +1,188 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Client side of `GetClusterStatus`, used by `timpani-o status`.
+
+use std::time::Duration;
+
+use thiserror::Error;
+use tonic::transport::Endpoint;
+use tonic::Request;
+
+use crate::proto::schedinfo_v1::{
+    sched_info_service_client::SchedInfoServiceClient, ClusterStatus, ClusterStatusRequest,
+};
+
+use super::TENANT_METADATA_KEY;
+
+/// Connect timeout — an operator at a shell should not wait for TCP retries.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Why a status query failed.  `Display` is a single line suitable for a CLI.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum StatusQueryError {
+    #[error("invalid address '{0}'")]
+    InvalidAddress(String),
+
+    #[error("cannot connect to Timpani-O at {addr}: {source}")]
+    Connect {
+        addr: String,
+        #[source]
+        source: tonic::transport::Error,
+    },
+
+    #[error("invalid tenant '{0}'")]
+    InvalidTenant(String),
+
+    #[error("GetClusterStatus failed: {}", .0.message())]
+    Rpc(#[from] tonic::Status),
+}
+
+/// Fetch a [`ClusterStatus`] snapshot from the SchedInfoService at `addr`
+/// (e.g. `http://localhost:50052`) on behalf of `tenant`.
+pub async fn fetch_cluster_status(
+    addr: &str,
+    tenant: &str,
+) -> Result<ClusterStatus, StatusQueryError> {
+    let endpoint = Endpoint::from_shared(addr.to_string())
+        .map_err(|_| StatusQueryError::InvalidAddress(addr.to_string()))?
+        .connect_timeout(CONNECT_TIMEOUT);
+    let channel = endpoint
+        .connect()
+        .await
+        .map_err(|source| StatusQueryError::Connect {
+            addr: addr.to_string(),
+            source,
+        })?;
+
+    let mut request = Request::new(ClusterStatusRequest {});
+    request.metadata_mut().insert(
+        TENANT_METADATA_KEY,
+        tenant
+            .parse()
+            .map_err(|_| StatusQueryError::InvalidTenant(tenant.to_string()))?,
+    );
+    let status = SchedInfoServiceClient::new(channel)
+        .get_cluster_status(request)
+        .await?
+        .into_inner();
+    Ok(status)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::net::TcpListener;
+    use tonic::transport::server::TcpIncoming;
+    use tonic::transport::Server;
+
+    use super::*;
+    use crate::config::{NodeConfig, NodeConfigManager, NodeProtocol};
+    use crate::fault::{test_support::MockFaultNotifier, FaultNotifier};
+    use crate::grpc::{new_workload_store, schedinfo_service::SchedInfoServiceImpl};
+    use crate::proto::schedinfo_v1::{
+        sched_info_service_server::{SchedInfoService, SchedInfoServiceServer},
+        SchedInfo, TaskInfo,
+    };
+    use crate::report::{status::render, OutputFormat};
+    use crate::units::DurationStyle;
+
+    /// Start a SchedInfoService on an ephemeral port; returns its URL.
+    async fn start_server(svc: SchedInfoServiceImpl) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(SchedInfoServiceServer::new(svc))
+                .serve_with_incoming(incoming),
+        );
+        format!("http://{addr}")
+    }
+
+    fn service() -> SchedInfoServiceImpl {
+        let nodes = NodeConfigManager::from_nodes(vec![NodeConfig {
+            name: "n1".into(),
+            available_cpus: vec![0, 1],
+            reserved_cpus: Vec::new(),
+            max_memory_mb: 4096,
+            architecture: "x86_64".into(),
+            location: "test".into(),
+            description: "".into(),
+            endpoint: None,
+            max_workloads: None,
+            enabled: true,
+            time_partitions: None,
+            cpu_clusters: Vec::new(),
+            protocol: NodeProtocol::Grpc,
+        }]);
+        SchedInfoServiceImpl::new(
+            Arc::new(nodes),
+            new_workload_store(),
+            MockFaultNotifier::arc() as Arc<dyn FaultNotifier>,
+        )
+    }
+
+    #[tokio::test]
+    async fn status_against_in_process_server() {
+        let svc = service();
+        svc.add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl".into(),
+            tasks: vec![TaskInfo {
+                name: "t1".into(),
+                node_id: "n1".into(),
+                priority: 50,
+                policy: 1,
+                period: 10_000,
+                runtime: 2_500,
+                deadline: 10_000,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+        let url = start_server(svc).await;
+
+        let status = fetch_cluster_status(&url, "default").await.unwrap();
+        assert_eq!(status.nodes.len(), 1);
+        assert_eq!(status.nodes[0].task_count, 1);
+        assert!((status.nodes[0].total_utilization - 0.25).abs() < 1e-9);
+        assert_eq!(status.workloads[0].workload_id, "wl");
+        assert!(
+            render(&status, OutputFormat::Table, DurationStyle::default()).contains("workload wl")
+        );
+
+        // Another tenant sees the nodes but no workload.
+        let other = fetch_cluster_status(&url, "staging").await.unwrap();
+        assert!(other.workloads.is_empty());
+        assert_eq!(other.nodes[0].task_count, 0);
+    }
+
+    #[tokio::test]
+    async fn unreachable_server_is_a_concise_error() {
+        // Bind then drop to get a port nothing listens on.
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let err = fetch_cluster_status(&format!("http://{addr}"), "default")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StatusQueryError::Connect { .. }));
+        assert!(err.to_string().starts_with("cannot connect to Timpani-O"));
+        assert!(!err.to_string().contains('\n'));
+
+        let err = fetch_cluster_status("not a url", "default")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StatusQueryError::InvalidAddress(_)));
+    }
+}
@@ -0,0 +1,379 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Batched schedule delivery for `NodeService::StreamSchedInfo`.
+//!
+//! A node hosting thousands of tasks should not receive them in a single
+//! message.  The server [`split`]s the `NodeSchedResponse` it would have
+//! returned from `GetSchedInfo` into [`SchedBatch`]es of at most
+//! `batch_size` entries, then sends a [`SchedCommit`] trailer:
+//!
+//! ```text
+//! batch 0 ─ batch 1 ─ … ─ batch n-1 ─ commit { generation, batch_count = n, checksum }
+//! ```
+//!
+//! The node feeds every chunk to a [`ScheduleAssembler`], which buffers the
+//! batches and yields the reassembled response only when the commit matches
+//! what arrived — batch count and a 64-bit FNV-1a [`checksum`] over the
+//! protobuf encoding of each batch.  A lost, reordered or corrupted batch,
+//! or a stream that ends (or times out) before its commit, leaves the node
+//! on its previous schedule.
+//!
+//! Each batch counts removed, modified and added entries alike toward
+//! `batch_size`; removals go first, so a node never briefly holds both the
+//! old and the new copy of a task.
+
+use prost::Message;
+use thiserror::Error;
+
+use crate::proto::schedinfo_v1::{
+    sched_chunk::Chunk, NodeSchedResponse, SchedBatch, SchedChunk, SchedCommit,
+};
+
+/// Entries per batch when none is configured (`--stream-batch-size`).
+pub const DEFAULT_STREAM_BATCH_SIZE: usize = 200;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// ── Errors ────────────────────────────────────────────────────────────────────
+
+/// Why a streamed schedule was discarded.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum StreamError {
+    #[error("chunk carries neither a batch nor a commit")]
+    EmptyChunk,
+
+    #[error("batch {got} arrived where batch {expected} was expected")]
+    OutOfOrder { expected: u32, got: u32 },
+
+    #[error("commit announces {expected} batch(es) but {received} arrived")]
+    BatchCount { expected: u32, received: u32 },
+
+    #[error("checksum mismatch: commit says {expected:#018x}, batches hash to {actual:#018x}")]
+    ChecksumMismatch { expected: u64, actual: u64 },
+
+    #[error("stream ended after {received} batch(es) without a commit")]
+    Incomplete { received: u32 },
+}
+
+// ── Server side ───────────────────────────────────────────────────────────────
+
+/// Split `resp` into batches of at most `batch_size` entries plus the commit
+/// that closes them.  A `batch_size` of zero is treated as one.
+pub(crate) fn split(resp: NodeSchedResponse, batch_size: usize) -> (Vec<SchedBatch>, SchedCommit) {
+    let batch_size = batch_size.max(1);
+    let mut batches: Vec<SchedBatch> = Vec::new();
+    let mut room = 0;
+    let mut next = |batches: &mut Vec<SchedBatch>| -> usize {
+        if room == 0 {
+            batches.push(SchedBatch {
+                index: batches.len() as u32,
+                ..Default::default()
+            });
+            room = batch_size;
+        }
+        room -= 1;
+        batches.len() - 1
+    };
+
+    for name in resp.removed_tasks {
+        let i = next(&mut batches);
+        batches[i].removed_tasks.push(name);
+    }
+    for t in resp.modified_tasks {
+        let i = next(&mut batches);
+        batches[i].modified_tasks.push(t);
+    }
+    for t in resp.tasks {
+        let i = next(&mut batches);
+        batches[i].tasks.push(t);
+    }
+
+    let commit = SchedCommit {
+        workload_id: resp.workload_id,
+        hyperperiod_us: resp.hyperperiod_us,
+        generation: resp.generation,
+        full: resp.full,
+        batch_count: batches.len() as u32,
+        checksum: checksum(&batches),
+        prepare: resp.prepare,
+        major_frame_us: resp.major_frame_us,
+        time_partitions: resp.time_partitions,
+        instance_epoch: resp.instance_epoch,
+        resync: resp.resync,
+    };
+    (batches, commit)
+}
+
+/// 64-bit FNV-1a over the protobuf encoding of `batches`, in order.
+pub fn checksum<'a>(batches: impl IntoIterator<Item = &'a SchedBatch>) -> u64 {
+    batches
+        .into_iter()
+        .fold(FNV_OFFSET, |h, b| fnv1a(h, &b.encode_to_vec()))
+}
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &b in bytes {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+impl From<SchedBatch> for SchedChunk {
+    fn from(batch: SchedBatch) -> Self {
+        SchedChunk {
+            chunk: Some(Chunk::Batch(batch)),
+        }
+    }
+}
+
+impl From<SchedCommit> for SchedChunk {
+    fn from(commit: SchedCommit) -> Self {
+        SchedChunk {
+            chunk: Some(Chunk::Commit(commit)),
+        }
+    }
+}
+
+// ── Node side ─────────────────────────────────────────────────────────────────
+
+/// Buffers a streamed schedule until its commit (see the module docs).
+///
+/// Nothing is applied until [`push`](Self::push) returns `Ok(Some(_))`; on
+/// any error the assembler should be dropped along with its buffer.
+#[derive(Debug, Default)]
+pub struct ScheduleAssembler {
+    batches: Vec<SchedBatch>,
+}
+
+impl ScheduleAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Batches buffered so far.
+    pub fn received(&self) -> u32 {
+        self.batches.len() as u32
+    }
+
+    /// Accept the next chunk.  Returns the reassembled response once a valid
+    /// commit arrives.
+    pub fn push(&mut self, chunk: SchedChunk) -> Result<Option<NodeSchedResponse>, StreamError> {
+        match chunk.chunk.ok_or(StreamError::EmptyChunk)? {
+            Chunk::Batch(batch) => {
+                if batch.index != self.received() {
+                    return Err(StreamError::OutOfOrder {
+                        expected: self.received(),
+                        got: batch.index,
+                    });
+                }
+                self.batches.push(batch);
+                Ok(None)
+            }
+            Chunk::Commit(commit) => self.commit(commit).map(Some),
+        }
+    }
+
+    /// Error for a stream that ended before its commit.
+    pub fn incomplete(&self) -> StreamError {
+        StreamError::Incomplete {
+            received: self.received(),
+        }
+    }
+
+    fn commit(&mut self, commit: SchedCommit) -> Result<NodeSchedResponse, StreamError> {
+        if commit.batch_count != self.received() {
+            return Err(StreamError::BatchCount {
+                expected: commit.batch_count,
+                received: self.received(),
+            });
+        }
+        let actual = checksum(&self.batches);
+        if actual != commit.checksum {
+            return Err(StreamError::ChecksumMismatch {
+                expected: commit.checksum,
+                actual,
+            });
+        }
+
+        let mut resp = NodeSchedResponse {
+            workload_id: commit.workload_id,
+            hyperperiod_us: commit.hyperperiod_us,
+            generation: commit.generation,
+            full: commit.full,
+            major_frame_us: commit.major_frame_us,
+            time_partitions: commit.time_partitions,
+            instance_epoch: commit.instance_epoch,
+            resync: commit.resync,
+            ..Default::default()
+        };
+        for b in self.batches.drain(..) {
+            resp.removed_tasks.extend(b.removed_tasks);
+            resp.modified_tasks.extend(b.modified_tasks);
+            resp.tasks.extend(b.tasks);
+        }
+        Ok(resp)
+    }
+}
+
+// ── Delivery progress ─────────────────────────────────────────────────────────
+
+/// How far a node's current stream has got, kept per node in
+/// [`WorkloadState::deliveries`](super::WorkloadState::deliveries).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeliveryProgress {
+    /// Generation being streamed.
+    pub generation: u64,
+    /// Batches handed to the transport so far.
+    pub batches_sent: u32,
+    /// Batches in the stream (excluding the commit).
+    pub batch_count: u32,
+    /// The commit has been handed to the transport.
+    pub committed: bool,
+}
+
+impl DeliveryProgress {
+    pub fn new(generation: u64, batch_count: u32) -> Self {
+        Self {
+            generation,
+            batches_sent: 0,
+            batch_count,
+            committed: false,
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::schedinfo_v1::ScheduledTask;
+
+    fn synthetic(n: usize) -> NodeSchedResponse {
+        NodeSchedResponse {
+            workload_id: "wl".into(),
+            hyperperiod_us: 10_000,
+            generation: 7,
+            full: true,
+            tasks: (0..n)
+                .map(|i| ScheduledTask {
+                    name: format!("t{i:04}"),
+                    sched_priority: (i % 99) as i32 + 1,
+                    sched_policy: 1,
+                    period_us: 10_000,
+                    runtime_us: 1,
+                    deadline_us: 10_000,
+                    cpu_affinity: 1 << (i % 4),
+                    assigned_node: "n1".into(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn assemble(
+        chunks: impl IntoIterator<Item = SchedChunk>,
+    ) -> Result<NodeSchedResponse, StreamError> {
+        let mut asm = ScheduleAssembler::new();
+        for chunk in chunks {
+            if let Some(resp) = asm.push(chunk)? {
+                return Ok(resp);
+            }
+        }
+        Err(asm.incomplete())
+    }
+
+    fn chunks(batches: Vec<SchedBatch>, commit: SchedCommit) -> Vec<SchedChunk> {
+        batches
+            .into_iter()
+            .map(SchedChunk::from)
+            .chain([commit.into()])
+            .collect()
+    }
+
+    #[test]
+    fn thousand_tasks_round_trip_in_batches() {
+        let resp = synthetic(1_000);
+        let (batches, commit) = split(resp.clone(), DEFAULT_STREAM_BATCH_SIZE);
+        assert_eq!(batches.len(), 5);
+        assert!(batches.iter().all(|b| b.tasks.len() == 200));
+        assert_eq!(commit.batch_count, 5);
+        assert_eq!(assemble(chunks(batches, commit)).unwrap(), resp);
+    }
+
+    #[test]
+    fn delta_entries_share_batches_with_removals_first() {
+        let mut resp = synthetic(3);
+        resp.full = false;
+        resp.removed_tasks = vec!["old".into()];
+        resp.modified_tasks = resp.tasks.split_off(2);
+        let (batches, commit) = split(resp.clone(), 2);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].removed_tasks, ["old"]);
+        assert_eq!(batches[0].modified_tasks.len(), 1);
+        assert_eq!(batches[1].tasks.len(), 2);
+        assert_eq!(assemble(chunks(batches, commit)).unwrap(), resp);
+    }
+
+    #[test]
+    fn empty_schedule_is_a_bare_commit() {
+        let (batches, commit) = split(synthetic(0), 0);
+        assert!(batches.is_empty());
+        assert_eq!(assemble([commit.into()]).unwrap(), synthetic(0));
+    }
+
+    #[test]
+    fn dropped_batch_is_detected() {
+        let (mut batches, commit) = split(synthetic(1_000), 200);
+        batches.remove(2);
+        assert_eq!(
+            assemble(chunks(batches, commit)),
+            Err(StreamError::OutOfOrder {
+                expected: 2,
+                got: 3
+            })
+        );
+
+        // The last batch lost: only the commit can tell.
+        let (mut batches, commit) = split(synthetic(1_000), 200);
+        batches.pop();
+        assert_eq!(
+            assemble(chunks(batches, commit)),
+            Err(StreamError::BatchCount {
+                expected: 5,
+                received: 4
+            })
+        );
+    }
+
+    #[test]
+    fn corrupted_batch_fails_the_checksum() {
+        let (mut batches, commit) = split(synthetic(10), 4);
+        batches[1].tasks[0].runtime_us += 1;
+        assert!(matches!(
+            assemble(chunks(batches, commit)),
+            Err(StreamError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn stream_without_commit_is_incomplete() {
+        let (batches, _) = split(synthetic(10), 4);
+        let only_batches = batches.into_iter().map(SchedChunk::from);
+        assert_eq!(
+            assemble(only_batches),
+            Err(StreamError::Incomplete { received: 3 })
+        );
+        assert_eq!(
+            assemble([SchedChunk { chunk: None }]),
+            Err(StreamError::EmptyChunk)
+        );
+    }
+}
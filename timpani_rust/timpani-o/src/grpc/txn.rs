@@ -0,0 +1,364 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Transactional delivery: every affected node switches to a generation, or
+//! none does.
+//!
+//! A workload spread over several nodes is dangerous to run on only some of
+//! them.  With a prepare timeout set ([`with_transactional_push`]), a
+//! replacement that changes more than one node is stored as a
+//! [`Transaction`] instead of being published:
+//!
+//! ```text
+//! AddSchedInfo ── g6 stored, preparing ──┬── commit: g6 served to every node
+//!   nodes served g5 (running)            │
+//!   changed nodes served g6, prepare ──► └── abort: workload back at g5
+//! ```
+//!
+//! 1. Each changed node is served the new generation with `prepare = true`,
+//!    stages it without activating it and calls `PrepareSchedInfo`; every
+//!    other caller is served the generation the nodes run.
+//! 2. When the last changed node has staged it, the transaction commits:
+//!    the generation is published, apply deadlines start and every waiting
+//!    `PrepareSchedInfo` returns `COMMIT`.
+//! 3. When a node fails to stage it, or the prepare timeout passes first,
+//!    it aborts: the workload goes back to the state it replaced (or is
+//!    removed if it had none) and every waiting call returns `ABORT`.
+//!
+//! Either outcome is recorded as a `TRANSACTION_COMMITTED` or
+//! `TRANSACTION_ABORTED` event; an abort also reaches Pullpiri as a
+//! `TRANSACTION_ABORTED` fault with the reason under
+//! [`TRANSACTION_METADATA_KEY`].
+//!
+//! A transaction ends without an outcome — its waiting calls return
+//! `ABORT` — when the workload is replaced again, removed or rescheduled
+//! (drain, failover, hotplug) while it prepares.  Updates that change a
+//! single node, and every reschedule, are published as before.
+//!
+//! A changed node that does not announce the `PREPARE` feature (see
+//! [`super::protocol`]) cannot stage anything: it is left out of the
+//! transaction at its first call during it, served like a node the update
+//! does not change, and switches once the generation is published.
+//!
+//! [`with_transactional_push`]: super::schedinfo_service::SchedInfoServiceImpl::with_transactional_push
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::fault::{FaultNotification, FaultNotifier, FaultSeverity};
+use crate::metadata::Metadata;
+use crate::proto::schedinfo_v1::{
+    FaultType, PrepareDecision, PrepareOutcome, ScheduleEvent, ScheduleEventKind,
+};
+use crate::report::ScheduleDiff;
+use crate::task::NodeSchedMap;
+
+use super::events::{event, EventLog};
+use super::watchdog::ApplyWatchdog;
+use super::{BarrierStatus, WorkloadState};
+
+/// Fault metadata key carrying why a transaction aborted, e.g.
+/// `node n2 failed to stage generation 6: cgroup missing`.
+pub const TRANSACTION_METADATA_KEY: &str = "transaction";
+
+static NEXT_TRANSACTION_ID: AtomicU64 = AtomicU64::new(1);
+
+// ── Verdict ───────────────────────────────────────────────────────────────────
+
+/// State of a transaction, broadcast to the waiting `PrepareSchedInfo`
+/// calls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// Waiting for the remaining nodes.
+    Preparing,
+    Commit,
+    /// Aborted, with the reason.
+    Abort(String),
+}
+
+impl Verdict {
+    /// The `PrepareSchedInfo` answer for `generation`; `None` while
+    /// preparing.
+    pub fn decision(&self, generation: u64) -> Option<PrepareDecision> {
+        let (outcome, reason) = match self {
+            Verdict::Preparing => return None,
+            Verdict::Commit => (PrepareOutcome::Commit, String::new()),
+            Verdict::Abort(reason) => (PrepareOutcome::Abort, reason.clone()),
+        };
+        Some(PrepareDecision {
+            outcome: outcome as i32,
+            generation,
+            reason,
+        })
+    }
+}
+
+// ── Transaction ───────────────────────────────────────────────────────────────
+
+/// A generation waiting for every changed node to stage it (see the module
+/// docs).  Dropping it ends the transaction without an outcome.
+pub struct Transaction {
+    /// Process-wide identifier, so a timer can tell its transaction from a
+    /// later one of the same generation.
+    pub id: u64,
+    /// Nodes that must stage the generation.
+    pub nodes: BTreeSet<String>,
+    /// Nodes that have staged it.
+    pub prepared: BTreeSet<String>,
+    verdict_tx: watch::Sender<Verdict>,
+    /// The state the workload returns to on abort; `None` = it is removed.
+    prior: Option<Box<WorkloadState>>,
+}
+
+impl Transaction {
+    pub fn new<'a>(nodes: impl IntoIterator<Item = &'a str>, prior: Option<WorkloadState>) -> Self {
+        let (verdict_tx, _) = watch::channel(Verdict::Preparing);
+        Self {
+            id: NEXT_TRANSACTION_ID.fetch_add(1, Ordering::Relaxed),
+            nodes: nodes.into_iter().map(str::to_string).collect(),
+            prepared: BTreeSet::new(),
+            verdict_tx,
+            prior: prior.map(Box::new),
+        }
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<Verdict> {
+        self.verdict_tx.subscribe()
+    }
+
+    /// The state the workload returns to on abort.
+    pub fn prior(&self) -> Option<&WorkloadState> {
+        self.prior.as_deref()
+    }
+
+    /// Take the prior state out, ending the transaction.
+    pub fn into_prior(self) -> Option<WorkloadState> {
+        self.prior.map(|p| *p)
+    }
+
+    /// Nodes that have not staged the generation yet.
+    pub fn waiting(&self) -> impl Iterator<Item = &str> {
+        self.nodes.difference(&self.prepared).map(String::as_str)
+    }
+
+    /// Record `node`'s vote on `generation`.  Returns the verdict it
+    /// decides, if any.
+    pub fn vote(
+        &mut self,
+        node: &str,
+        generation: u64,
+        staged: bool,
+        error: &str,
+    ) -> Option<Verdict> {
+        if !staged {
+            return Some(Verdict::Abort(format!(
+                "node {node} failed to stage generation {generation}: {error}"
+            )));
+        }
+        self.prepared.insert(node.to_string());
+        self.waiting().next().is_none().then_some(Verdict::Commit)
+    }
+
+    /// Leave `node` out: it cannot stage a generation (see
+    /// [`super::protocol`]) and switches once it is published.  Returns the
+    /// verdict this decides, if any.
+    pub fn excuse(&mut self, node: &str) -> Option<Verdict> {
+        if !self.nodes.remove(node) {
+            return None;
+        }
+        self.prepared.remove(node);
+        self.waiting().next().is_none().then_some(Verdict::Commit)
+    }
+}
+
+/// Nodes whose task list differs between `previous` (none = empty) and
+/// `schedule`, including nodes that lose every task.
+pub fn changed_nodes(previous: Option<&NodeSchedMap>, schedule: &NodeSchedMap) -> Vec<String> {
+    let empty = NodeSchedMap::new();
+    ScheduleDiff::between(previous.unwrap_or(&empty), schedule)
+        .nodes
+        .into_keys()
+        .collect()
+}
+
+// ── Outcomes ──────────────────────────────────────────────────────────────────
+
+/// A decided transaction, for the caller to [`report`].
+#[derive(Debug, Clone)]
+pub struct Decided {
+    pub tenant: String,
+    pub workload_id: String,
+    /// Generation the transaction prepared.
+    pub generation: u64,
+    pub verdict: Verdict,
+    /// Commit: nodes with something new to apply.
+    pub apply_nodes: Vec<String>,
+    /// Abort: generation the workload went back to; `None` = removed.
+    pub restored: Option<u64>,
+}
+
+impl Decided {
+    fn event(&self) -> ScheduleEvent {
+        let kind = match self.verdict {
+            Verdict::Commit => ScheduleEventKind::TransactionCommitted,
+            _ => ScheduleEventKind::TransactionAborted,
+        };
+        ScheduleEvent {
+            tenant: self.tenant.clone(),
+            workload_id: self.workload_id.clone(),
+            generation: self.generation,
+            ..event(kind)
+        }
+    }
+}
+
+/// Commit `tenant`'s open transaction: publish its generation and release
+/// the waiting calls.  `None` if the tenant has none.
+pub fn commit(workloads: &mut HashMap<String, WorkloadState>, tenant: &str) -> Option<Decided> {
+    let ws = workloads.get_mut(tenant)?;
+    let txn = ws.txn.take()?;
+    ws.publish();
+    if let Some(prior) = txn.prior() {
+        // Nodes still waiting on the replaced workload's barrier restart.
+        let _ = prior.barrier_tx.send(BarrierStatus::Cancelled);
+    }
+    let _ = txn.verdict_tx.send(Verdict::Commit);
+    Some(Decided {
+        tenant: tenant.to_string(),
+        workload_id: ws.workload_id.clone(),
+        generation: ws.generation,
+        verdict: Verdict::Commit,
+        apply_nodes: ws
+            .nodes_to_apply()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        restored: None,
+    })
+}
+
+/// Abort `tenant`'s open transaction: put the prior state back (or remove
+/// the workload) and release the waiting calls.  `None` if the tenant has
+/// none.
+pub fn abort(
+    workloads: &mut HashMap<String, WorkloadState>,
+    tenant: &str,
+    reason: String,
+) -> Option<Decided> {
+    workloads.get(tenant)?.txn.as_ref()?;
+    let mut ws = workloads.remove(tenant)?;
+    let txn = ws.txn.take()?;
+    let _ = ws.barrier_tx.send(BarrierStatus::Cancelled);
+    let _ = txn.verdict_tx.send(Verdict::Abort(reason.clone()));
+    let restored = txn.into_prior().map(|prior| {
+        let generation = prior.published().generation;
+        workloads.insert(tenant.to_string(), prior);
+        generation
+    });
+    Some(Decided {
+        tenant: tenant.to_string(),
+        workload_id: ws.workload_id,
+        generation: ws.generation,
+        verdict: Verdict::Abort(reason),
+        apply_nodes: Vec::new(),
+        restored,
+    })
+}
+
+/// Log, record and forward the outcome of `decided`; on commit, start the
+/// apply deadlines in `watchdog`.
+pub(crate) fn report(
+    decided: Decided,
+    events: &EventLog,
+    watchdog: Option<&ApplyWatchdog>,
+    notifier: &Arc<dyn FaultNotifier>,
+) {
+    events.record(decided.event());
+    let reason = match decided.verdict {
+        Verdict::Commit => {
+            info!(
+                target: "audit",
+                tenant      = %decided.tenant,
+                workload_id = %decided.workload_id,
+                generation  = decided.generation,
+                "transaction committed"
+            );
+            if let Some(watchdog) = watchdog {
+                watchdog.arm(
+                    &decided.tenant,
+                    decided.generation,
+                    decided.apply_nodes.iter().map(String::as_str),
+                );
+            }
+            return;
+        }
+        Verdict::Abort(reason) => reason,
+        Verdict::Preparing => return,
+    };
+    warn!(
+        target: "audit",
+        tenant      = %decided.tenant,
+        workload_id = %decided.workload_id,
+        generation  = decided.generation,
+        restored    = ?decided.restored,
+        reason      = %reason,
+        "transaction aborted"
+    );
+    let notification = FaultNotification {
+        workload_id: decided.workload_id,
+        node_id: String::new(),
+        task_name: String::new(),
+        fault_type: FaultType::TransactionAborted,
+        severity: FaultSeverity::Critical,
+        feasibility: None,
+        metadata: Metadata::from([(TRANSACTION_METADATA_KEY.to_string(), reason)]),
+    };
+    let notifier = Arc::clone(notifier);
+    tokio::spawn(async move {
+        let wl = notification.workload_id.clone();
+        if let Err(e) = notifier.notify_fault(notification).await {
+            warn!(workload_id = %wl, error = %e, "Failed to send transaction abort");
+        }
+    });
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn votes_commit_once_every_node_staged_and_abort_on_a_failure() {
+        let mut txn = Transaction::new(["n1", "n2"], None);
+        assert_eq!(txn.vote("n1", 6, true, ""), None);
+        assert_eq!(txn.waiting().collect::<Vec<_>>(), ["n2"]);
+        // A repeated vote changes nothing.
+        assert_eq!(txn.vote("n1", 6, true, ""), None);
+        assert_eq!(txn.vote("n2", 6, true, ""), Some(Verdict::Commit));
+
+        let mut txn = Transaction::new(["n1", "n2"], None);
+        assert_eq!(
+            txn.vote("n2", 6, false, "cgroup missing"),
+            Some(Verdict::Abort(
+                "node n2 failed to stage generation 6: cgroup missing".into()
+            ))
+        );
+        assert!(Transaction::new(["n1"], None).id > txn.id);
+    }
+
+    #[test]
+    fn excusing_the_last_waiting_node_commits() {
+        let mut txn = Transaction::new(["n1", "n2"], None);
+        assert_eq!(txn.vote("n1", 6, true, ""), None);
+        assert_eq!(txn.excuse("n3"), None);
+        assert_eq!(txn.excuse("n2"), Some(Verdict::Commit));
+        assert!(!txn.nodes.contains("n2"));
+    }
+}
@@ -0,0 +1,165 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Deadline from pushing a schedule to a node until the node applies it.
+//!
+//! A wedged node never fetches or never acknowledges its schedule, and the
+//! delivery path alone cannot tell.  When a workload moves to a new
+//! generation, every node with something new to apply gets a deadline per
+//! tenant ([`ApplyWatchdog::arm`]).  The node acknowledges with a
+//! `ReportApply` for that generation (or a later one) or by calling
+//! `SyncTimer` ([`ApplyWatchdog::ack`]); removing the workload cancels its
+//! deadlines ([`ApplyWatchdog::cancel`]).
+//!
+//! Nothing runs on its own: the owner polls [`ApplyWatchdog::expired_at`]
+//! and acts on what it returns (see
+//! [`SchedInfoServiceImpl::check_apply_deadlines`](super::schedinfo_service::SchedInfoServiceImpl::check_apply_deadlines)).
+//! Methods that read the clock have an `_at` form taking the current time,
+//! so tests drive the clock themselves.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often `timpani-o` polls for expired deadlines.
+pub const APPLY_WATCHDOG_TICK: Duration = Duration::from_secs(1);
+
+/// A deadline that passed without an acknowledgement.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ApplyExpiry {
+    pub tenant: String,
+    pub node: String,
+    /// Generation the node was expected to apply.
+    pub generation: u64,
+}
+
+/// One running deadline.
+#[derive(Debug, Clone, Copy)]
+struct Armed {
+    generation: u64,
+    due: Instant,
+}
+
+/// Apply deadlines per `(tenant, node)` (see the module docs).
+///
+/// Thread-safe; the internal lock is only held for map updates.
+#[derive(Debug)]
+pub struct ApplyWatchdog {
+    deadline: Duration,
+    armed: Mutex<BTreeMap<(String, String), Armed>>,
+}
+
+impl ApplyWatchdog {
+    pub fn new(deadline: Duration) -> Self {
+        Self {
+            deadline,
+            armed: Mutex::default(),
+        }
+    }
+
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+
+    /// Start the clock for `nodes` to apply `tenant`'s `generation`.
+    pub fn arm<'a>(&self, tenant: &str, generation: u64, nodes: impl IntoIterator<Item = &'a str>) {
+        self.arm_at(tenant, generation, nodes, Instant::now());
+    }
+
+    /// [`arm`](Self::arm) at `now`.  A node's earlier deadline for the
+    /// tenant is replaced.
+    pub fn arm_at<'a>(
+        &self,
+        tenant: &str,
+        generation: u64,
+        nodes: impl IntoIterator<Item = &'a str>,
+        now: Instant,
+    ) {
+        let due = now + self.deadline;
+        let mut armed = self.armed.lock().unwrap();
+        for node in nodes {
+            armed.insert(
+                (tenant.to_string(), node.to_string()),
+                Armed { generation, due },
+            );
+        }
+    }
+
+    /// `node` applied `tenant`'s `generation`.  Returns `true` if that
+    /// cancelled a deadline (one for `generation` or an earlier one).
+    pub fn ack(&self, tenant: &str, node: &str, generation: u64) -> bool {
+        let mut armed = self.armed.lock().unwrap();
+        let key = (tenant.to_string(), node.to_string());
+        match armed.get(&key) {
+            Some(a) if a.generation <= generation => armed.remove(&key).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Drop every deadline of `tenant` (its workload is gone).
+    pub fn cancel(&self, tenant: &str) {
+        self.armed.lock().unwrap().retain(|(t, _), _| t != tenant);
+    }
+
+    /// Remove and return the deadlines past at `now`, sorted.
+    pub fn expired_at(&self, now: Instant) -> Vec<ApplyExpiry> {
+        let mut armed = self.armed.lock().unwrap();
+        let mut expired = Vec::new();
+        armed.retain(|(tenant, node), a| {
+            if a.due > now {
+                return true;
+            }
+            expired.push(ApplyExpiry {
+                tenant: tenant.clone(),
+                node: node.clone(),
+                generation: a.generation,
+            });
+            false
+        });
+        expired
+    }
+
+    /// Number of running deadlines.
+    pub fn len(&self) -> usize {
+        self.armed.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadlines_expire_once_unless_acked_or_cancelled() {
+        let dog = ApplyWatchdog::new(Duration::from_secs(5));
+        let t0 = Instant::now();
+        dog.arm_at("a", 2, ["n1", "n2"], t0);
+        dog.arm_at("b", 7, ["n1"], t0);
+
+        // An ack for an older generation does not count.
+        assert!(!dog.ack("a", "n1", 1));
+        assert!(dog.ack("a", "n1", 2));
+        dog.cancel("b");
+
+        assert!(dog.expired_at(t0 + Duration::from_secs(4)).is_empty());
+        let expired = dog.expired_at(t0 + Duration::from_secs(5));
+        assert_eq!(
+            expired,
+            [ApplyExpiry {
+                tenant: "a".into(),
+                node: "n2".into(),
+                generation: 2,
+            }]
+        );
+        assert!(dog.is_empty());
+        assert!(dog.expired_at(t0 + Duration::from_secs(60)).is_empty());
+    }
+}
@@ -21,10 +21,13 @@ SPDX-License-Identifier: MIT
 pub mod math;
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use tracing::{debug, info, warn};
 
-use crate::task::Task;
+use crate::limits::{Limit, LimitHits, DEFAULT_MAX_UNIQUE_PERIODS};
+use crate::task::{Micros, Task};
+use crate::units::fmt_duration_us;
 use math::lcm_of_slice;
 
 // ── Constants ─────────────────────────────────────────────────────────────────
@@ -33,12 +36,13 @@ use math::lcm_of_slice;
 ///
 /// Matches the C++ warning threshold.  Callers that want a different limit can
 /// pass their own value to [`HyperperiodManager::with_limit`].
-pub const DEFAULT_HYPERPERIOD_LIMIT_US: u64 = 3_600_000_000; // 1 h
+pub const DEFAULT_HYPERPERIOD_LIMIT_US: Micros = Micros(3_600_000_000); // 1 h
 
 // ── Error type ────────────────────────────────────────────────────────────────
 
 /// Errors that can occur during hyperperiod calculation.
 #[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum HyperperiodError {
     /// The task slice was empty (or all tasks had `period_us == 0`).
     NoValidPeriods,
@@ -53,7 +57,11 @@ pub enum HyperperiodError {
     ///
     /// This is not necessarily a hard error — the caller can choose to warn
     /// and continue, or reject the workload.
-    TooLarge { value_us: u64, limit_us: u64 },
+    TooLarge { value_us: Micros, limit_us: Micros },
+
+    /// The workload has more distinct periods than the
+    /// [cap](crate::limits); no LCM was attempted.
+    TooManyPeriods { count: usize, max: usize },
 }
 
 impl std::fmt::Display for HyperperiodError {
@@ -67,10 +75,16 @@ impl std::fmt::Display for HyperperiodError {
             }
             HyperperiodError::TooLarge { value_us, limit_us } => write!(
                 f,
-                "hyperperiod {value_us}µs ({:.1}s) exceeds limit {limit_us}µs ({:.1}s)",
-                *value_us as f64 / 1_000_000.0,
-                *limit_us as f64 / 1_000_000.0
+                "hyperperiod {} exceeds limit {}",
+                fmt_duration_us(value_us.as_u64()),
+                fmt_duration_us(limit_us.as_u64())
             ),
+            HyperperiodError::TooManyPeriods { count, max } => {
+                write!(
+                    f,
+                    "{count} distinct task periods, more than the limit of {max}"
+                )
+            }
         }
     }
 }
@@ -88,10 +102,10 @@ pub struct HyperperiodInfo {
     pub workload_id: String,
 
     /// Hyperperiod in microseconds (LCM of all unique task periods).
-    pub hyperperiod_us: u64,
+    pub hyperperiod_us: Micros,
 
     /// Unique periods present in the workload (sorted, deduplicated).
-    pub unique_periods: Vec<u64>,
+    pub unique_periods: Vec<Micros>,
 
     /// Number of tasks in the workload that contributed to this hyperperiod.
     pub task_count: usize,
@@ -106,17 +120,17 @@ pub struct HyperperiodInfo {
 /// # Example
 /// ```rust
 /// use timpani_o::hyperperiod::HyperperiodManager;
-/// use timpani_o::task::{Task, SchedPolicy, CpuAffinity};
+/// use timpani_o::task::{Micros, Task};
 ///
 /// let mut mgr = HyperperiodManager::new();
 ///
 /// let tasks = vec![
-///     Task { workload_id: "w1".into(), period_us: 1_000, ..Default::default() },
-///     Task { workload_id: "w1".into(), period_us: 2_000, ..Default::default() },
+///     Task { workload_id: "w1".into(), period_us: Micros(1_000), ..Default::default() },
+///     Task { workload_id: "w1".into(), period_us: Micros(2_000), ..Default::default() },
 /// ];
 ///
 /// let info = mgr.calculate_hyperperiod("w1", &tasks).unwrap();
-/// assert_eq!(info.hyperperiod_us, 2_000);
+/// assert_eq!(info.hyperperiod_us, Micros(2_000));
 /// ```
 #[derive(Debug)]
 pub struct HyperperiodManager {
@@ -125,26 +139,46 @@ pub struct HyperperiodManager {
 
     /// Upper bound on the hyperperiod.  A calculated value above this limit
     /// causes [`HyperperiodError::TooLarge`] to be returned.
-    limit_us: u64,
+    limit_us: Micros,
+
+    /// Most distinct periods a workload may have; more causes
+    /// [`HyperperiodError::TooManyPeriods`].
+    max_unique_periods: usize,
+
+    /// Where going over `max_unique_periods` is counted.
+    limit_hits: Arc<LimitHits>,
 }
 
 impl HyperperiodManager {
-    /// Create a manager with the default 1-hour limit.
+    /// Create a manager with the default 1-hour limit and the default cap
+    /// on distinct periods.
     pub fn new() -> Self {
-        Self {
-            map: HashMap::new(),
-            limit_us: DEFAULT_HYPERPERIOD_LIMIT_US,
-        }
+        Self::with_limit(DEFAULT_HYPERPERIOD_LIMIT_US)
     }
 
     /// Create a manager with a custom hyperperiod limit (in microseconds).
-    pub fn with_limit(limit_us: u64) -> Self {
+    pub fn with_limit(limit_us: Micros) -> Self {
         Self {
             map: HashMap::new(),
             limit_us,
+            max_unique_periods: DEFAULT_MAX_UNIQUE_PERIODS,
+            limit_hits: Arc::default(),
         }
     }
 
+    /// Refuse workloads with more than `max` distinct periods instead of
+    /// [`DEFAULT_MAX_UNIQUE_PERIODS`].
+    pub fn with_max_unique_periods(mut self, max: usize) -> Self {
+        self.max_unique_periods = max;
+        self
+    }
+
+    /// Count input limit hits in `hits`, typically its owner's.
+    pub fn with_limit_hits(mut self, hits: Arc<LimitHits>) -> Self {
+        self.limit_hits = hits;
+        self
+    }
+
     /// Calculate and store the hyperperiod for `workload_id`.
     ///
     /// # Arguments
@@ -159,6 +193,8 @@ impl HyperperiodManager {
     ///   periods were zero.
     /// * [`HyperperiodError::Overflow`] – LCM computation exceeded `u64`.
     /// * [`HyperperiodError::TooLarge`] – result exceeds the configured limit.
+    /// * [`HyperperiodError::TooManyPeriods`] – more distinct periods than
+    ///   the configured cap.
     pub fn calculate_hyperperiod(
         &mut self,
         workload_id: &str,
@@ -167,7 +203,7 @@ impl HyperperiodManager {
         // Filter to tasks belonging to this workload with a non-zero period
         let matching: Vec<&Task> = tasks
             .iter()
-            .filter(|t| t.workload_id == workload_id && t.period_us > 0)
+            .filter(|t| t.workload_id == workload_id && !t.period_us.is_zero())
             .collect();
 
         if matching.is_empty() {
@@ -176,20 +212,31 @@ impl HyperperiodManager {
         }
 
         // Collect unique periods (sorted for deterministic output)
-        let unique_periods: Vec<u64> = {
-            let mut v: Vec<u64> = matching.iter().map(|t| t.period_us).collect();
+        let unique_periods: Vec<Micros> = {
+            let mut v: Vec<Micros> = matching.iter().map(|t| t.period_us).collect();
             v.sort_unstable();
             v.dedup();
             v
         };
+        if self.limit_hits.exceeded(
+            Limit::UniquePeriods,
+            unique_periods.len(),
+            self.max_unique_periods,
+        ) {
+            return Err(HyperperiodError::TooManyPeriods {
+                count: unique_periods.len(),
+                max: self.max_unique_periods,
+            });
+        }
 
-        let hyperperiod_us = lcm_of_slice(&unique_periods)?;
+        let raw: Vec<u64> = unique_periods.iter().map(|p| p.as_u64()).collect();
+        let hyperperiod_us = Micros(lcm_of_slice(&raw)?);
 
         // Sanity-check: too-large hyperperiod — return Err so caller decides
         if hyperperiod_us > self.limit_us {
             warn!(
-                hyperperiod_us,
-                limit_us = self.limit_us,
+                hyperperiod_us = hyperperiod_us.as_u64(),
+                limit_us = self.limit_us.as_u64(),
                 workload_id,
                 "Hyperperiod exceeds configured limit"
             );
@@ -203,11 +250,14 @@ impl HyperperiodManager {
             workload_id,
             task_count = matching.len(),
             unique_count = unique_periods.len(),
-            hyperperiod_ms = hyperperiod_us / 1_000,
+            hyperperiod = %fmt_duration_us(hyperperiod_us.as_u64()),
             "Calculated hyperperiod"
         );
         for p in &unique_periods {
-            debug!(period_us = p, period_ms = p / 1_000, "  unique period");
+            debug!(
+                period = %fmt_duration_us(p.as_u64()),
+                "  unique period"
+            );
         }
 
         let info = HyperperiodInfo {
@@ -275,12 +325,12 @@ impl Default for HyperperiodManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::task::Task;
+    use crate::task::{Micros, Task};
 
     fn make_task(workload_id: &str, period_us: u64) -> Task {
         Task {
             workload_id: workload_id.into(),
-            period_us,
+            period_us: Micros(period_us),
             ..Default::default()
         }
     }
@@ -292,7 +342,7 @@ mod tests {
         let tasks = vec![make_task("w1", 1_000), make_task("w1", 2_000)];
         let mut mgr = HyperperiodManager::new();
         let info = mgr.calculate_hyperperiod("w1", &tasks).unwrap();
-        assert_eq!(info.hyperperiod_us, 2_000);
+        assert_eq!(info.hyperperiod_us, Micros(2_000));
         assert_eq!(info.task_count, 2);
     }
 
@@ -306,7 +356,7 @@ mod tests {
         ];
         let mut mgr = HyperperiodManager::new();
         let info = mgr.calculate_hyperperiod("w1", &tasks).unwrap();
-        assert_eq!(info.hyperperiod_us, 10_000);
+        assert_eq!(info.hyperperiod_us, Micros(10_000));
     }
 
     #[test]
@@ -318,7 +368,7 @@ mod tests {
         ];
         let mut mgr = HyperperiodManager::new();
         let info = mgr.calculate_hyperperiod("w1", &tasks).unwrap();
-        assert_eq!(info.hyperperiod_us, 5_000);
+        assert_eq!(info.hyperperiod_us, Micros(5_000));
         // Three tasks but only one unique period
         assert_eq!(info.unique_periods.len(), 1);
         assert_eq!(info.task_count, 3);
@@ -329,7 +379,7 @@ mod tests {
         let tasks = vec![make_task("w1", 4_000)];
         let mut mgr = HyperperiodManager::new();
         let info = mgr.calculate_hyperperiod("w1", &tasks).unwrap();
-        assert_eq!(info.hyperperiod_us, 4_000);
+        assert_eq!(info.hyperperiod_us, Micros(4_000));
     }
 
     // ── workload_id filter ────────────────────────────────────────────────────
@@ -345,7 +395,7 @@ mod tests {
         let mut mgr = HyperperiodManager::new();
         let info = mgr.calculate_hyperperiod("w1", &tasks).unwrap();
         // LCM(1000, 2000) = 2000, NOT LCM(1000, 2000, 3000) = 6000
-        assert_eq!(info.hyperperiod_us, 2_000);
+        assert_eq!(info.hyperperiod_us, Micros(2_000));
         assert_eq!(info.task_count, 2);
     }
 
@@ -381,23 +431,47 @@ mod tests {
             make_task("w1", 7_000_000), // 7 s  → LCM = 7 s
         ];
         // Set limit to 5 seconds
-        let mut mgr = HyperperiodManager::with_limit(5_000_000);
+        let mut mgr = HyperperiodManager::with_limit(Micros(5_000_000));
         let result = mgr.calculate_hyperperiod("w1", &tasks);
         assert!(matches!(
             result,
             Err(HyperperiodError::TooLarge {
-                value_us: 7_000_000,
+                value_us: Micros(7_000_000),
                 ..
             })
         ));
     }
 
+    #[test]
+    fn too_many_distinct_periods_are_refused_before_the_lcm() {
+        // Pairwise coprime enough to overflow, were the LCM attempted.
+        let tasks: Vec<Task> = (0..40).map(|i| make_task("w1", 1_000 + i)).collect();
+        let hits = Arc::new(LimitHits::default());
+        let mut mgr = HyperperiodManager::new()
+            .with_max_unique_periods(39)
+            .with_limit_hits(Arc::clone(&hits));
+        assert_eq!(
+            mgr.calculate_hyperperiod("w1", &tasks).unwrap_err(),
+            HyperperiodError::TooManyPeriods { count: 40, max: 39 }
+        );
+        assert!(!mgr.has("w1"));
+        assert_eq!(hits.get(Limit::UniquePeriods), 1);
+
+        // Duplicates do not count against the cap.
+        let mut tasks = tasks[..39].to_vec();
+        tasks.push(make_task("w1", 1_000));
+        assert!(matches!(
+            mgr.calculate_hyperperiod("w1", &tasks),
+            Err(HyperperiodError::Overflow { .. })
+        ));
+    }
+
     #[test]
     fn hyperperiod_at_exactly_the_limit_is_accepted() {
         let tasks = vec![make_task("w1", 5_000_000)];
-        let mut mgr = HyperperiodManager::with_limit(5_000_000);
+        let mut mgr = HyperperiodManager::with_limit(Micros(5_000_000));
         let info = mgr.calculate_hyperperiod("w1", &tasks).unwrap();
-        assert_eq!(info.hyperperiod_us, 5_000_000);
+        assert_eq!(info.hyperperiod_us, Micros(5_000_000));
     }
 
     // ── get / has ─────────────────────────────────────────────────────────────
@@ -408,7 +482,7 @@ mod tests {
         let mut mgr = HyperperiodManager::new();
         mgr.calculate_hyperperiod("w1", &tasks).unwrap();
         assert!(mgr.has("w1"));
-        assert_eq!(mgr.get("w1").unwrap().hyperperiod_us, 1_000);
+        assert_eq!(mgr.get("w1").unwrap().hyperperiod_us, Micros(1_000));
     }
 
     #[test]
@@ -458,10 +532,10 @@ mod tests {
 
         let mut mgr = HyperperiodManager::new();
         mgr.calculate_hyperperiod("w1", &tasks_v1).unwrap();
-        assert_eq!(mgr.get("w1").unwrap().hyperperiod_us, 1_000);
+        assert_eq!(mgr.get("w1").unwrap().hyperperiod_us, Micros(1_000));
 
         mgr.calculate_hyperperiod("w1", &tasks_v2).unwrap();
-        assert_eq!(mgr.get("w1").unwrap().hyperperiod_us, 3_000);
+        assert_eq!(mgr.get("w1").unwrap().hyperperiod_us, Micros(3_000));
     }
 
     // ── unique_periods are sorted and deduplicated ────────────────────────────
@@ -476,6 +550,9 @@ mod tests {
         ];
         let mut mgr = HyperperiodManager::new();
         let info = mgr.calculate_hyperperiod("w1", &tasks).unwrap();
-        assert_eq!(info.unique_periods, vec![1_000, 2_000, 5_000]);
+        assert_eq!(
+            info.unique_periods,
+            vec![Micros(1_000), Micros(2_000), Micros(5_000)]
+        );
     }
 }
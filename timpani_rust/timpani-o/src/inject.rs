@@ -0,0 +1,282 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Failure injection for integration tests.
+//!
+//! Components that talk to the outside world consult a shared
+//! [`FailureInjector`] at fixed [`InjectionPoint`]s.  With the `testing`
+//! feature the injector can be programmed to fail or delay the Nth call at a
+//! point; without it [`FailureInjector`] is a zero-sized type whose hooks are
+//! empty inline functions, so production builds carry no cost.
+//!
+//! | Point             | Consulted in                                            |
+//! |-------------------|---------------------------------------------------------|
+//! | `NodeServe`       | `NodeService::get_sched_info`, once per node request    |
+//! | `NodeStreamBatch` | `NodeService::stream_sched_info`; failing drops a batch |
+//! | `FaultSend`       | [`InjectingNotifier`] in front of any `FaultNotifier`   |
+//! | `ConfigReload`    | `NodeConfigManager::load_from_file`, after the read     |
+//!
+//! ```ignore
+//! let injector = Arc::new(FailureInjector::new());
+//! injector.delay_nth(InjectionPoint::NodeServe, 1, Duration::from_millis(500));
+//! let node_svc = NodeServiceImpl::new(store, notifier, timeout)
+//!     .with_failure_injector(Arc::clone(&injector));
+//! ```
+
+use std::fmt;
+
+use thiserror::Error;
+
+// ── Points and outcomes ───────────────────────────────────────────────────────
+
+/// A place in the pipeline where a failure can be injected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InjectionPoint {
+    /// Before a node's schedule is served.
+    NodeServe,
+    /// Before each batch of a streamed schedule is sent.
+    NodeStreamBatch,
+    /// Before a fault notification is sent to Pullpiri.
+    FaultSend,
+    /// Inside a node configuration (re)load, after the file has been read.
+    ConfigReload,
+}
+
+impl fmt::Display for InjectionPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            InjectionPoint::NodeServe => "node_serve",
+            InjectionPoint::NodeStreamBatch => "node_stream_batch",
+            InjectionPoint::FaultSend => "fault_send",
+            InjectionPoint::ConfigReload => "config_reload",
+        })
+    }
+}
+
+/// Error returned by a hook programmed to fail.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("injected failure at {point} (call {call})")]
+pub struct InjectedFailure {
+    pub point: InjectionPoint,
+    /// 1-based call number at `point`.
+    pub call: u64,
+}
+
+// ── FailureInjector (testing) ─────────────────────────────────────────────────
+
+#[cfg(feature = "testing")]
+mod enabled {
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use tracing::warn;
+
+    use super::{InjectedFailure, InjectionPoint};
+
+    /// What to do on a programmed call.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Injection {
+        /// Return [`InjectedFailure`].
+        Fail,
+        /// Sleep, then proceed normally.
+        Delay(Duration),
+    }
+
+    #[derive(Debug, Default)]
+    struct PointState {
+        calls: u64,
+        /// 1-based call number → injection.
+        program: BTreeMap<u64, Injection>,
+    }
+
+    /// Programmable failure injector (see the [module docs](super)).
+    #[derive(Debug, Default)]
+    pub struct FailureInjector {
+        points: Mutex<HashMap<InjectionPoint, PointState>>,
+    }
+
+    impl FailureInjector {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Apply `injection` to the `nth` (1-based) call at `point`.
+        pub fn program(&self, point: InjectionPoint, nth: u64, injection: Injection) {
+            self.lock()
+                .entry(point)
+                .or_default()
+                .program
+                .insert(nth, injection);
+        }
+
+        /// Make the `nth` call at `point` fail.
+        pub fn fail_nth(&self, point: InjectionPoint, nth: u64) {
+            self.program(point, nth, Injection::Fail);
+        }
+
+        /// Delay the `nth` call at `point` by `delay`.
+        pub fn delay_nth(&self, point: InjectionPoint, nth: u64, delay: Duration) {
+            self.program(point, nth, Injection::Delay(delay));
+        }
+
+        /// Number of times `point` has been reached.
+        pub fn calls(&self, point: InjectionPoint) -> u64 {
+            self.lock().get(&point).map_or(0, |s| s.calls)
+        }
+
+        /// Async hook: count the call and apply its injection, if any.
+        pub async fn hit(&self, point: InjectionPoint) -> Result<(), InjectedFailure> {
+            match self.next(point) {
+                (_, None) => Ok(()),
+                (_, Some(Injection::Delay(d))) => {
+                    tokio::time::sleep(d).await;
+                    Ok(())
+                }
+                (call, Some(Injection::Fail)) => Err(InjectedFailure { point, call }),
+            }
+        }
+
+        /// Blocking hook for synchronous call sites.
+        pub fn hit_blocking(&self, point: InjectionPoint) -> Result<(), InjectedFailure> {
+            match self.next(point) {
+                (_, None) => Ok(()),
+                (_, Some(Injection::Delay(d))) => {
+                    std::thread::sleep(d);
+                    Ok(())
+                }
+                (call, Some(Injection::Fail)) => Err(InjectedFailure { point, call }),
+            }
+        }
+
+        fn next(&self, point: InjectionPoint) -> (u64, Option<Injection>) {
+            let mut points = self.lock();
+            let state = points.entry(point).or_default();
+            state.calls += 1;
+            let injection = state.program.remove(&state.calls);
+            if let Some(injection) = injection {
+                warn!(%point, call = state.calls, ?injection, "injecting failure");
+            }
+            (state.calls, injection)
+        }
+
+        fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<InjectionPoint, PointState>> {
+            // A panicking test thread must not wedge the others.
+            self.points.lock().unwrap_or_else(|e| e.into_inner())
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+pub use enabled::{FailureInjector, Injection};
+
+// ── FailureInjector (production) ──────────────────────────────────────────────
+
+#[cfg(not(feature = "testing"))]
+mod disabled {
+    use super::{InjectedFailure, InjectionPoint};
+
+    /// No-op injector: every hook succeeds immediately.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct FailureInjector;
+
+    impl FailureInjector {
+        pub fn new() -> Self {
+            Self
+        }
+
+        #[inline(always)]
+        pub async fn hit(&self, _point: InjectionPoint) -> Result<(), InjectedFailure> {
+            Ok(())
+        }
+
+        #[inline(always)]
+        pub fn hit_blocking(&self, _point: InjectionPoint) -> Result<(), InjectedFailure> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(feature = "testing"))]
+pub use disabled::FailureInjector;
+
+// ── InjectingNotifier ─────────────────────────────────────────────────────────
+
+#[cfg(feature = "testing")]
+pub use notifier::InjectingNotifier;
+
+#[cfg(feature = "testing")]
+mod notifier {
+    use std::sync::Arc;
+
+    use super::{FailureInjector, InjectionPoint};
+    use crate::fault::{FaultError, FaultNotification, FaultNotifier};
+
+    /// `FaultNotifier` decorator consulting [`InjectionPoint::FaultSend`]
+    /// before forwarding.  An injected failure surfaces as
+    /// `FaultError::Rpc(Status::unavailable)`, as if Pullpiri were down.
+    pub struct InjectingNotifier {
+        inner: Arc<dyn FaultNotifier>,
+        injector: Arc<FailureInjector>,
+    }
+
+    impl InjectingNotifier {
+        pub fn wrap(
+            inner: Arc<dyn FaultNotifier>,
+            injector: Arc<FailureInjector>,
+        ) -> Arc<dyn FaultNotifier> {
+            Arc::new(Self { inner, injector })
+        }
+    }
+
+    #[tonic::async_trait]
+    impl FaultNotifier for InjectingNotifier {
+        async fn notify_fault(&self, info: FaultNotification) -> Result<(), FaultError> {
+            self.injector
+                .hit(InjectionPoint::FaultSend)
+                .await
+                .map_err(|e| FaultError::Rpc(tonic::Status::unavailable(e.to_string())))?;
+            self.inner.notify_fault(info).await
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn only_the_programmed_call_is_affected() {
+        let inj = FailureInjector::new();
+        inj.fail_nth(InjectionPoint::FaultSend, 2);
+
+        assert!(inj.hit(InjectionPoint::FaultSend).await.is_ok());
+        assert_eq!(
+            inj.hit(InjectionPoint::FaultSend).await,
+            Err(InjectedFailure {
+                point: InjectionPoint::FaultSend,
+                call: 2
+            })
+        );
+        assert!(inj.hit(InjectionPoint::FaultSend).await.is_ok());
+        // Other points are counted independently.
+        assert!(inj.hit_blocking(InjectionPoint::ConfigReload).is_ok());
+        assert_eq!(inj.calls(InjectionPoint::FaultSend), 3);
+        assert_eq!(inj.calls(InjectionPoint::ConfigReload), 1);
+    }
+
+    #[tokio::test]
+    async fn delay_sleeps_then_succeeds() {
+        let inj = FailureInjector::new();
+        inj.delay_nth(InjectionPoint::NodeServe, 1, Duration::from_millis(50));
+        let start = Instant::now();
+        assert!(inj.hit(InjectionPoint::NodeServe).await.is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
@@ -9,18 +9,79 @@ SPDX-License-Identifier: MIT
 //!
 //! ```text
 //! lib.rs
+//! ├── admission.rs    – per-node admission and embedded pre-checks
 //! ├── proto/          – generated gRPC/protobuf types & stubs
 //! ├── config/         – YAML node configuration
 //! ├── scheduler/      – three scheduling algorithms
 //! ├── hyperperiod/    – LCM / GCD helpers
+//! ├── limits.rs       – caps on collections grown by external input
 //! ├── grpc/           – gRPC server + client wiring
+//! ├── report/         – schedule diffs and other derived reports
+//! ├── codec.rs        – JSON / CBOR snapshot serialisation
+//! ├── compat/         – payloads for the legacy C++ Timpani-N
+//! ├── metadata.rs     – opaque per-task key/value metadata
+//! ├── naming.rs       – workload ID / task name policy
+//! ├── taskfile.rs     – workload task files with templates
+//! ├── prelude.rs      – one-`use` re-exports for library users
+//! ├── units.rs        – human-readable durations (`--raw-units`)
+//! ├── inject.rs       – failure injection hooks (`testing` feature)
+//! ├── testkit.rs      – in-process end-to-end harness (`testing` feature)
+//! ├── testing.rs      – fake nodes, tasks and clock for tests (`testing` feature)
 //! └── fault/          – fault reporting to Pullpiri
 //! ```
+//!
+//! # Features
+//!
+//! | Feature       | Default | Adds                                                    |
+//! |---------------|---------|---------------------------------------------------------|
+//! | `core`        | yes     | scheduler, task, config, hyperperiod, reports, codec    |
+//! | `grpc`        | yes     | `proto`, `grpc`, `fault`, proto-backed reports (protoc) |
+//! | `cli`         | yes     | the `timpani-o` binary and its `clap` value enums       |
+//! | `testing`     | no      | failure injection, `testkit` and the `testing` fixtures |
+//! | `alloc-stats` | no      | per-phase allocation counts in scheduler timings        |
+//! | `bench`       | no      | exhaustive optimal placement for tiny inputs            |
+//!
+//! `--no-default-features --features core` builds without protoc, tonic or
+//! tokio, e.g. for reuse of [`scheduler::GlobalScheduler`] in a planning tool;
+//! [`prelude`] brings in what such a tool needs.
 
+#[cfg(feature = "core")]
+pub mod admission;
+#[cfg(feature = "core")]
+pub mod codec;
+#[cfg(feature = "core")]
+pub mod compat;
+#[cfg(feature = "core")]
 pub mod config;
+#[cfg(feature = "grpc")]
 pub mod fault;
+#[cfg(feature = "grpc")]
 pub mod grpc;
+#[cfg(feature = "core")]
 pub mod hyperperiod;
+#[cfg(feature = "core")]
+pub mod inject;
+#[cfg(feature = "core")]
+pub mod limits;
+#[cfg(feature = "core")]
+pub mod metadata;
+#[cfg(feature = "core")]
+pub mod naming;
+#[cfg(feature = "core")]
+pub mod prelude;
+#[cfg(feature = "grpc")]
 pub mod proto;
+#[cfg(feature = "core")]
+pub mod report;
+#[cfg(feature = "core")]
 pub mod scheduler;
+#[cfg(feature = "core")]
 pub mod task;
+#[cfg(feature = "grpc")]
+pub mod taskfile;
+#[cfg(all(feature = "core", any(test, feature = "testing")))]
+pub mod testing;
+#[cfg(feature = "testing")]
+pub mod testkit;
+#[cfg(feature = "core")]
+pub mod units;
@@ -0,0 +1,204 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Caps on collections that grow with external input.
+//!
+//! A request, a task file or a workload handed to the library decides how
+//! many tasks, periods, templates and problems Timpani-O holds in memory.
+//! Each such collection is capped, and going over the cap either fails the
+//! input with a typed error or truncates the collection and says so:
+//!
+//! | [`Limit`]        | Default              | Applied by                       | Over the cap                          |
+//! |------------------|----------------------|----------------------------------|---------------------------------------|
+//! | `tasks`          | 10 000               | `AddSchedInfo`, `schedule*`      | `SchedulerError::TooManyTasks`        |
+//! |                  |                      | task files                       | `TaskfileError::TooManyTasks`         |
+//! | `unique_periods` | 256                  | [`HyperperiodManager`]           | `HyperperiodError::TooManyPeriods`    |
+//! | `task_problems`  | 100                  | [`validate_tasks`]               | truncated, `TaskProblems::omitted` set |
+//! | `templates`      | 1024                 | task files                       | `TaskfileError::TooManyTemplates`     |
+//! | `metadata_keys`  | [`MetadataPolicy`]'s | `AddSchedInfo`, workload files   | `MetadataViolation::TooManyKeys`      |
+//!
+//! The per-node task vectors of a schedule, the scheduler's statistics and
+//! the per-node rejections of a what-if placement hold at most one entry
+//! per task or per configured node, so the task cap bounds them too.
+//!
+//! The caps travel with the input: an [`InputLimits`] in
+//! [`ScheduleOptions::limits`](crate::scheduler::ScheduleOptions::limits)
+//! for requests and scheduling runs, and as an argument to the task file
+//! parser, [`validate_tasks`] and
+//! [`HyperperiodManager::with_max_unique_periods`].  `timpani-o
+//! --max-tasks`, `--max-unique-periods`, `--max-task-problems` and
+//! `--max-templates` change the defaults.  Every time a cap is hit it is
+//! logged and counted in the [`LimitHits`] passed along with the caps; a
+//! `SchedInfoService` owns one, and `GetClusterStatus` returns its counts
+//! as `limit_hits`.
+//!
+//! [`HyperperiodManager`]: crate::hyperperiod::HyperperiodManager
+//! [`HyperperiodManager::with_max_unique_periods`]: crate::hyperperiod::HyperperiodManager::with_max_unique_periods
+//! [`validate_tasks`]: crate::scheduler::validate_tasks
+//! [`MetadataPolicy`]: crate::metadata::MetadataPolicy
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Tasks per workload when no limit is configured.
+pub const DEFAULT_MAX_TASKS: usize = 10_000;
+
+/// Distinct task periods per workload when no limit is configured.
+pub const DEFAULT_MAX_UNIQUE_PERIODS: usize = 256;
+
+/// Task problems listed per workload when no limit is configured.
+pub const DEFAULT_MAX_TASK_PROBLEMS: usize = 100;
+
+/// Templates per task file when no limit is configured.
+pub const DEFAULT_MAX_TEMPLATES: usize = 1024;
+
+/// A capped collection (see the module docs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Limit {
+    Tasks,
+    UniquePeriods,
+    TaskProblems,
+    Templates,
+    MetadataKeys,
+}
+
+impl Limit {
+    pub const ALL: [Limit; 5] = [
+        Limit::Tasks,
+        Limit::UniquePeriods,
+        Limit::TaskProblems,
+        Limit::Templates,
+        Limit::MetadataKeys,
+    ];
+
+    /// Name in logs and in `ClusterStatus.limit_hits`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Limit::Tasks => "tasks",
+            Limit::UniquePeriods => "unique_periods",
+            Limit::TaskProblems => "task_problems",
+            Limit::Templates => "templates",
+            Limit::MetadataKeys => "metadata_keys",
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The configurable caps.  Metadata keys are capped per
+/// [`MetadataPolicy`](crate::metadata::MetadataPolicy) instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputLimits {
+    pub max_tasks: usize,
+    pub max_unique_periods: usize,
+    pub max_task_problems: usize,
+    pub max_templates: usize,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        Self {
+            max_tasks: DEFAULT_MAX_TASKS,
+            max_unique_periods: DEFAULT_MAX_UNIQUE_PERIODS,
+            max_task_problems: DEFAULT_MAX_TASK_PROBLEMS,
+            max_templates: DEFAULT_MAX_TEMPLATES,
+        }
+    }
+}
+
+impl InputLimits {
+    pub fn with_max_tasks(mut self, max: usize) -> Self {
+        self.max_tasks = max;
+        self
+    }
+
+    pub fn with_max_unique_periods(mut self, max: usize) -> Self {
+        self.max_unique_periods = max;
+        self
+    }
+
+    pub fn with_max_task_problems(mut self, max: usize) -> Self {
+        self.max_task_problems = max;
+        self
+    }
+
+    pub fn with_max_templates(mut self, max: usize) -> Self {
+        self.max_templates = max;
+        self
+    }
+}
+
+/// How often each [`Limit`] was hit, for one owner (see the module docs).
+///
+/// Thread-safe; counts only grow.
+#[derive(Debug, Default)]
+pub struct LimitHits([AtomicU64; Limit::ALL.len()]);
+
+impl LimitHits {
+    /// Whether `count` items are over `limit`'s cap of `max`; if so, the
+    /// hit is logged and counted.
+    pub fn exceeded(&self, limit: Limit, count: usize, max: usize) -> bool {
+        if count <= max {
+            return false;
+        }
+        self.0[limit.index()].fetch_add(1, Ordering::Relaxed);
+        warn!(limit = %limit, count, max, "input limit hit");
+        true
+    }
+
+    /// Hits of `limit` so far.
+    pub fn get(&self, limit: Limit) -> u64 {
+        self.0[limit.index()].load(Ordering::Relaxed)
+    }
+
+    /// Hits of every limit so far, by [`Limit::as_str`].
+    pub fn snapshot(&self) -> BTreeMap<String, u64> {
+        Limit::ALL
+            .iter()
+            .map(|&limit| (limit.as_str().to_string(), self.get(limit)))
+            .collect()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_limit_is_counted_under_its_own_name() {
+        for (i, limit) in Limit::ALL.into_iter().enumerate() {
+            assert_eq!(limit.index(), i);
+        }
+        let hits = LimitHits::default();
+        assert!(!hits.exceeded(Limit::Templates, 1024, 1024));
+        assert!(hits.exceeded(Limit::Templates, 1025, 1024));
+        assert!(hits.exceeded(Limit::Tasks, 2, 1));
+        assert!(hits.exceeded(Limit::Tasks, 3, 1));
+        assert_eq!(hits.get(Limit::Templates), 1);
+        let all = hits.snapshot();
+        assert_eq!(all.len(), Limit::ALL.len());
+        assert_eq!(all["templates"], 1);
+        assert_eq!(all["tasks"], 2);
+        assert_eq!(all["unique_periods"], 0);
+
+        // Counts belong to their owner.
+        assert_eq!(LimitHits::default().get(Limit::Tasks), 0);
+    }
+}
@@ -3,25 +3,80 @@ SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
 SPDX-License-Identifier: MIT
 */
 
-use std::path::PathBuf;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::process;
 use std::sync::Arc;
 
-use clap::Parser;
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand};
 use tonic::transport::Server;
 use tracing::{error, info, warn};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter};
 
-use timpani_o::config::NodeConfigManager;
-use timpani_o::fault::{FaultClient, FaultNotification};
+use timpani_o::codec::{self, Format};
+use timpani_o::config::{self, NodeConfigManager, DEFAULT_MEMORY_REPORT_WINDOW, DEFAULT_NODE_PORT};
+use timpani_o::fault::debounce::DEFAULT_ADVISORY_WINDOW;
+use timpani_o::fault::{FaultClient, FaultNotification, FaultSeverity};
 use timpani_o::grpc::{
+    admin_client::{AdminClient, AdminError, DEFAULT_DRAIN_BATCH_SIZE},
+    admin_service::{AdminServiceImpl, DEFAULT_ADMIN_ADDR},
+    clock::{ClockCheck, DEFAULT_MAX_CLOCK_OFFSET},
+    compaction::COMPACTION_TICK,
+    doctor::{Doctor, DEFAULT_DOCTOR_TIMEOUT},
+    events::{EventLog, DEFAULT_EVENT_CHANNEL_CAPACITY, DEFAULT_EVENT_LOG_CAPACITY},
+    log_filter::LogFilter,
     new_workload_store,
     node_service::{NodeServiceImpl, DEFAULT_SYNC_TIMEOUT_SECS},
-    schedinfo_service::SchedInfoServiceImpl,
+    pending::DEFAULT_PENDING_CAPACITY,
+    reconcile::{DEFAULT_RECONCILE_WINDOW_SECS, RECONCILE_TICK},
+    repro::{ReproBundle, ReproOutcome},
+    revision::DEFAULT_REVISION_CHANGE_FACTOR,
+    schedinfo_service::{
+        chains_from_proto, tasks_from_proto, SchedInfoServiceImpl, DEFAULT_MAX_REQUEST_BYTES,
+        WORKLOAD_EXPIRY_TICK,
+    },
+    status_client::fetch_cluster_status,
+    stream::DEFAULT_STREAM_BATCH_SIZE,
+    watchdog::{ApplyWatchdog, APPLY_WATCHDOG_TICK},
+    DEFAULT_TENANT,
 };
+use timpani_o::hyperperiod::HyperperiodManager;
+use timpani_o::limits::{
+    InputLimits, LimitHits, DEFAULT_MAX_TASKS, DEFAULT_MAX_TASK_PROBLEMS, DEFAULT_MAX_TEMPLATES,
+    DEFAULT_MAX_UNIQUE_PERIODS,
+};
+use timpani_o::metadata::{
+    MetadataPolicy, DEFAULT_FORWARDED_KEYS, DEFAULT_MAX_KEYS, DEFAULT_MAX_VALUE_LEN,
+};
+use timpani_o::naming::NamingPolicy;
 use timpani_o::proto::schedinfo_v1::{
-    node_service_server::NodeServiceServer, sched_info_service_server::SchedInfoServiceServer,
-    FaultType,
+    admin_service_server::AdminServiceServer, node_service_server::NodeServiceServer,
+    sched_info_service_server::SchedInfoServiceServer, ClusterStatus, CordonResult, FaultType,
+    LogFilterResult, SchedInfo, WhatIfResult,
+};
+use timpani_o::report::{
+    chain_latencies, render_summary, signoff_report, status::render, to_dot, workload_summary,
+    OutputFormat, ReportFormat,
+};
+use timpani_o::scheduler::feasibility::check_schedule;
+use timpani_o::scheduler::log_policy::{
+    LogPolicy, DEFAULT_PROGRESS_INTERVAL, DEFAULT_VERBOSE_TASK_LIMIT,
+};
+use timpani_o::scheduler::simulate::{
+    DEFAULT_MAX_SIMULATED_JOBS, DEFAULT_SIMULATION_HYPERPERIOD_LIMIT,
+};
+use timpani_o::scheduler::sweep::{chart, to_csv};
+use timpani_o::scheduler::{
+    analyse_chains, run_chain, runtime_margins, threshold_steps, GlobalScheduler, ImpactReport,
+    MarginAnalysis, PriorityOrdering, ProximityTable, SchedAlgorithm, ScheduleOptions,
+    SimulationCheck, StaggerStrategy, SweepFormat,
 };
+use timpani_o::task::{Micros, NodeSchedMap, RangeCheck, Task, DEFAULT_MAX_TASK_DURATION};
+use timpani_o::taskfile;
+use timpani_o::units::{DurationStyle, DEFAULT_PRECISION};
 
 // ── CLI argument definition ───────────────────────────────────────────────────
 
@@ -37,6 +92,9 @@ use timpani_o::proto::schedinfo_v1::{
     long_about = None,
 )]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Port for the upstream SchedInfoService gRPC server (receives workloads from Pullpiri).
     #[arg(short = 's', long = "sinfoport", default_value_t = 50052)]
     sinfo_port: u16,
@@ -50,9 +108,14 @@ struct Cli {
     fault_port: u16,
 
     /// Port for the downstream node gRPC service (Timpani-N endpoint).
-    #[arg(short = 'd', long = "nodeport", default_value_t = 50054)]
+    #[arg(short = 'd', long = "nodeport", default_value_t = DEFAULT_NODE_PORT)]
     node_port: u16,
 
+    /// Address of the AdminService (node cordon / drain, used by
+    /// `timpani-o node`).  Loopback by default.
+    #[arg(long = "admin-addr", default_value = DEFAULT_ADMIN_ADDR)]
+    admin_addr: SocketAddr,
+
     /// Enable the NotifyFault demo (sends one fault notification then clears).
     #[arg(short = 'n', long = "notifyfault", default_value_t = false)]
     notify_fault: bool,
@@ -65,47 +128,1432 @@ struct Cli {
     #[arg(short = 't', long = "sync-timeout-secs", default_value_t = DEFAULT_SYNC_TIMEOUT_SECS)]
     sync_timeout_secs: u64,
 
-    /// Path to the YAML node configuration file.
+    /// Path to the YAML node configuration file.  Re-read on SIGHUP.
     #[arg(short = 'c', long = "nodeconfig")]
     node_config: Option<PathBuf>,
+
+    /// Reject a node configuration that lists a CPU in both
+    /// `available_cpus` and `reserved_cpus` instead of reserving the CPU
+    /// with a warning.
+    #[arg(long = "strict-config")]
+    strict_config: bool,
+
+    /// After a node configuration reload, move tasks off nodes that were
+    /// removed instead of only reporting them as orphaned.
+    #[arg(long = "evacuate-orphans")]
+    evacuate_orphans: bool,
+
+    /// Seconds a node has to apply a new schedule before the miss is
+    /// reported as a fault and its unapplied tasks are degraded.  0 disables
+    /// the apply watchdog.
+    #[arg(long = "apply-deadline-secs", default_value_t = 0)]
+    apply_deadline_secs: u64,
+
+    /// Seconds after startup nodes have to call before the reconciliation
+    /// report calls them unreachable and degrades their undelivered tasks.
+    /// 0 disables the report; restarted nodes are still resynced.
+    #[arg(long = "reconcile-window-secs", default_value_t = DEFAULT_RECONCILE_WINDOW_SECS)]
+    reconcile_window_secs: u64,
+
+    /// Move a workload's tasks off a node that missed its apply deadline.
+    /// Needs --apply-deadline-secs.
+    #[arg(long = "apply-failover")]
+    apply_failover: bool,
+
+    /// Seconds without a placement change after which every workload is
+    /// re-placed offline and a better layout, if found, is proposed (see
+    /// `timpani-o compact`).  0 disables compaction proposals.
+    #[arg(long = "compaction-idle-secs", default_value_t = 0)]
+    compaction_idle_secs: u64,
+
+    /// Default scheduling algorithm (target_node_priority, least_loaded,
+    /// best_fit_decreasing, randomized_spread).  A workload may override it per request.
+    #[arg(short = 'a', long = "algorithm", default_value_t = SchedAlgorithm::default())]
+    algorithm: SchedAlgorithm,
+
+    /// Default algorithm chain (comma-separated or repeated): a workload the
+    /// first algorithm cannot place is retried whole with the next.  Takes
+    /// the place of --algorithm; a workload naming its own algorithm or
+    /// chain overrides it.
+    #[arg(long = "algorithm-chain", value_delimiter = ',')]
+    algorithm_chain: Vec<SchedAlgorithm>,
+
+    /// Default per-CPU utilisation threshold, in (0, 1].  A workload may
+    /// override it per request.
+    #[arg(long = "cpu-threshold", default_value_t = ScheduleOptions::default().cpu_utilization_threshold)]
+    cpu_threshold: f64,
+
+    /// Tolerance for utilisation-versus-limit comparisons, in [0, 1e-3].
+    #[arg(long = "utilization-epsilon", default_value_t = ScheduleOptions::default().utilization_epsilon)]
+    utilization_epsilon: f64,
+
+    /// Default RNG seed for `randomized_spread`.  A workload may override it
+    /// per request.
+    #[arg(long = "seed", default_value_t = 0)]
+    seed: u64,
+
+    /// Keep unpinned tasks off CPUs that pinned tasks later in the same
+    /// workload will need.
+    #[arg(long = "reserve-pinned-cpus")]
+    reserve_pinned_cpus: bool,
+
+    /// Spread task release offsets on each CPU after placement
+    /// (even_within_period, golden_ratio).  Tasks with a non-zero
+    /// `release_time` keep it.
+    #[arg(long = "stagger-releases")]
+    stagger_releases: Option<StaggerStrategy>,
+
+    /// Admit memory against the free memory nodes report instead of
+    /// `max_memory_mb` alone.
+    #[arg(long = "use-live-memory")]
+    use_live_memory: bool,
+
+    /// Age (seconds) after which a node's memory report is stale and
+    /// `max_memory_mb` is used instead.
+    #[arg(long = "memory-report-window-secs", default_value_t = DEFAULT_MEMORY_REPORT_WINDOW.as_secs())]
+    memory_report_window_secs: u64,
+
+    /// Count the load of RT threads nodes report on their managed CPUs,
+    /// but which Timpani did not place, against CPU headroom.
+    #[arg(long = "respect-foreign-load")]
+    respect_foreign_load: bool,
+
+    /// When a node reports a CPU offline, move its tasks to the node's other
+    /// online CPUs (up to --cpu-threshold) instead of only reporting them
+    /// as orphaned.
+    #[arg(long = "repair-offline-cpus")]
+    repair_offline_cpus: bool,
+
+    /// Check node clocks before pushing schedules with release offsets:
+    /// `warn` serves them with a warning, `strict` refuses them while the
+    /// node's clock is unsynchronised or off by more than
+    /// --max-clock-offset-us.  Unset = no check.
+    #[arg(long = "clock-check")]
+    clock_check: Option<ClockCheck>,
+
+    /// Largest clock offset (µs) --clock-check accepts.
+    #[arg(long = "max-clock-offset-us", default_value_t = DEFAULT_MAX_CLOCK_OFFSET.as_micros() as u64)]
+    max_clock_offset_us: u64,
+
+    /// Simulate CPUs above their Liu & Layland bound after placement:
+    /// `strict` rejects a workload with a simulated deadline miss, `warn`
+    /// admits it and sends a feasibility advisory.
+    #[arg(long = "verify-with-simulation")]
+    verify_with_simulation: Option<SimulationCheck>,
+
+    /// CPUs whose hyperperiod (µs) exceeds this are not simulated.
+    #[arg(long = "simulation-hyperperiod-limit-us", default_value_t = DEFAULT_SIMULATION_HYPERPERIOD_LIMIT.as_u64())]
+    simulation_hyperperiod_limit_us: u64,
+
+    /// Most jobs one CPU's simulation window may release.  A workload
+    /// whose periods need more is rejected rather than simulated; harmonise
+    /// its periods (make them divide one another) or raise this.
+    #[arg(long = "simulation-max-jobs", default_value_t = DEFAULT_MAX_SIMULATED_JOBS)]
+    simulation_max_jobs: u64,
+
+    /// Analysis each task's runtime margin (how far its runtime could grow
+    /// before its CPU fails) is computed against: `threshold`,
+    /// `liu_layland` or `rta`.
+    #[arg(long = "margin-analysis", default_value_t = MarginAnalysis::Threshold)]
+    margin_analysis: MarginAnalysis,
+
+    /// How real-time tasks submitted with priority 0 are ranked when a
+    /// request does not say: `rate_monotonic` or `deadline_monotonic`.
+    #[arg(long = "priority-ordering", default_value_t = PriorityOrdering::RateMonotonic)]
+    priority_ordering: PriorityOrdering,
+
+    /// Longest period, runtime, deadline or release time (µs) a placed task
+    /// may have; longer ones are rejected.  Capped at the wire limit of
+    /// `i32::MAX` µs.
+    #[arg(long = "max-task-duration-us", default_value_t = DEFAULT_MAX_TASK_DURATION.to_micros().as_u64())]
+    max_task_duration_us: u64,
+
+    /// Scheduling runs with more tasks than this log each placement at
+    /// debug level and report progress instead.
+    #[arg(long = "log-verbose-task-limit", default_value_t = DEFAULT_VERBOSE_TASK_LIMIT)]
+    log_verbose_task_limit: usize,
+
+    /// Placements between progress lines in such runs (0 = none).
+    #[arg(long = "log-progress-interval", default_value_t = DEFAULT_PROGRESS_INTERVAL)]
+    log_progress_interval: usize,
+
+    /// Window (seconds) within which repeated feasibility advisories for the
+    /// same workload/node/CPU are not re-sent to Pullpiri.
+    #[arg(long = "advisory-window-secs", default_value_t = DEFAULT_ADVISORY_WINDOW.as_secs())]
+    advisory_window_secs: u64,
+
+    /// Always send nodes their full task list instead of a delta against the
+    /// generation they last applied.
+    #[arg(long = "full-push")]
+    full_push: bool,
+
+    /// Publish workload updates to nodes only at multiples of this many
+    /// milliseconds, so updates within an epoch reach each node as one
+    /// delta.  Workloads of class `safety` are published at once.  0
+    /// publishes every update at once.
+    #[arg(long = "push-epoch-ms", default_value_t = 0)]
+    push_epoch_ms: u64,
+
+    /// Deliver a workload replacement that changes more than one node as a
+    /// transaction: the nodes stage it first, and it is rolled back unless
+    /// every one of them has staged it within this many milliseconds.  0
+    /// publishes replacements at once.
+    #[arg(long = "transactional-push-ms", default_value_t = 0)]
+    transactional_push_ms: u64,
+
+    /// Tasks per batch when a node pulls its schedule with StreamSchedInfo.
+    #[arg(long = "stream-batch-size", default_value_t = DEFAULT_STREAM_BATCH_SIZE)]
+    stream_batch_size: usize,
+
+    /// Secondary algorithms to shadow-schedule every workload with, for
+    /// comparison in the audit log only (comma-separated or repeated).
+    #[arg(long = "shadow-algorithm", value_delimiter = ',')]
+    shadow_algorithms: Vec<SchedAlgorithm>,
+
+    /// Refuse a workload revision that changes a task's period or runtime by
+    /// more than this factor unless the request sets `force`.
+    #[arg(long = "revision-change-factor", default_value_t = DEFAULT_REVISION_CHANGE_FACTOR)]
+    revision_change_factor: f64,
+
+    /// Honour `admission_overrides` in AddSchedInfo (skip_memory,
+    /// skip_threshold) for lab experiments.  Each use is audited and
+    /// reported to Pullpiri.
+    #[arg(long = "allow-admission-overrides")]
+    allow_admission_overrides: bool,
+
+    /// Largest AddSchedInfo message accepted, in bytes.
+    #[arg(long = "max-request-bytes", default_value_t = DEFAULT_MAX_REQUEST_BYTES)]
+    max_request_bytes: usize,
+
+    /// Maximum number of workloads parked (`queue_if_full`) while waiting
+    /// for capacity.  Further queued submissions get RESOURCE_EXHAUSTED.
+    #[arg(long = "pending-capacity", default_value_t = DEFAULT_PENDING_CAPACITY)]
+    pending_capacity: usize,
+
+    /// Print durations in the subcommands' output and reports as integer
+    /// microseconds (no unit) instead of e.g. `1.5 ms`, for scripts.  Logs
+    /// and error messages keep the unit.
+    #[arg(long = "raw-units")]
+    raw_units: bool,
+
+    /// Fraction digits of human-readable durations in the subcommands'
+    /// output and reports.
+    #[arg(long = "duration-precision", default_value_t = DEFAULT_PRECISION)]
+    duration_precision: usize,
+
+    /// Regex every workload_id and task name must match in full, replacing
+    /// the default `[A-Za-z0-9_.-]{1,64}`.
+    #[arg(long = "name-pattern")]
+    name_pattern: Option<String>,
+
+    /// What to do with a task priority outside 0–99 or a negative
+    /// max_dmiss: `strict` rejects the workload, `clamp` moves the value
+    /// into range with a warning.
+    #[arg(long = "range-check", default_value_t = RangeCheck::Strict)]
+    range_check: RangeCheck,
+
+    /// Most metadata keys a task may carry.
+    #[arg(long = "metadata-max-keys", default_value_t = DEFAULT_MAX_KEYS)]
+    metadata_max_keys: usize,
+
+    /// Longest metadata value, in characters.
+    #[arg(long = "metadata-max-value-len", default_value_t = DEFAULT_MAX_VALUE_LEN)]
+    metadata_max_value_len: usize,
+
+    /// Most tasks in one workload (request, task file or `schedule` run).
+    #[arg(long = "max-tasks", default_value_t = DEFAULT_MAX_TASKS)]
+    max_tasks: usize,
+
+    /// Most distinct task periods in one workload.
+    #[arg(long = "max-unique-periods", default_value_t = DEFAULT_MAX_UNIQUE_PERIODS)]
+    max_unique_periods: usize,
+
+    /// Most task problems listed in one error; the rest are counted.
+    #[arg(long = "max-task-problems", default_value_t = DEFAULT_MAX_TASK_PROBLEMS)]
+    max_task_problems: usize,
+
+    /// Most templates in one task file.
+    #[arg(long = "max-templates", default_value_t = DEFAULT_MAX_TEMPLATES)]
+    max_templates: usize,
+
+    /// Metadata key copied into faults sent to Pullpiri and the audit log
+    /// (repeatable; default `trace_id`).
+    #[arg(long = "metadata-forward-key")]
+    metadata_forward_keys: Vec<String>,
+
+    /// Label key whose values are redacted from repro bundles: a task
+    /// metadata key, or the node field `architecture`, `location`,
+    /// `description` or `endpoint` (repeatable).
+    #[arg(long = "repro-sensitive-key")]
+    repro_sensitive_keys: Vec<String>,
+
+    /// Schedule events kept for replay to new WatchScheduleEvents
+    /// subscribers.
+    #[arg(long = "event-log-capacity", default_value_t = DEFAULT_EVENT_LOG_CAPACITY)]
+    event_log_capacity: usize,
+
+    /// Live events a WatchScheduleEvents subscriber may fall behind by
+    /// before it loses some (it is told how many).
+    #[arg(long = "event-channel-capacity", default_value_t = DEFAULT_EVENT_CHANNEL_CAPACITY)]
+    event_channel_capacity: usize,
+
+    /// YAML table of zone -> node location -> cost; least_loaded and
+    /// best_fit_decreasing place a task with a `preferred_location` on the
+    /// cheapest of nearly equally loaded nodes.
+    #[arg(long = "proximity-table", value_name = "PATH")]
+    proximity_table: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Query a running Timpani-O and print node capacity and workload status.
+    Status(StatusArgs),
+    /// Schedule a workload file offline against --nodeconfig and print the
+    /// placements and workload summary.
+    Schedule(ScheduleArgs),
+    /// Schedule a workload file offline at a range of CPU utilisation
+    /// thresholds and summarise how placement density changes.
+    Sweep(SweepArgs),
+    /// Write the sign-off report of a saved schedule on --nodeconfig:
+    /// per-CPU tasks, utilisation, feasibility, hyperperiods, constraints.
+    Report(ReportArgs),
+    /// Check this install: node configuration, listen ports, and whether
+    /// the FaultService and each node endpoint are reachable.
+    Doctor(DoctorArgs),
+    /// Cordon, uncordon or drain a node of a running Timpani-O.
+    Node(NodeArgs),
+    /// Apply the compaction proposal `timpani-o status` reports.
+    Compact(CompactArgs),
+    /// Inspect node configuration files offline.
+    Config(ConfigArgs),
+    /// Work with repro bundles captured from a running instance.
+    Repro(ReproArgs),
+    /// Show or change the log filter of a running Timpani-O.
+    Log(LogArgs),
+}
+
+#[derive(Debug, Args)]
+struct LogArgs {
+    #[command(subcommand)]
+    action: LogAction,
+
+    /// AdminService URL of the running instance
+    /// [default: http://<admin-addr>].
+    #[arg(long = "addr", global = true)]
+    addr: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum LogAction {
+    /// Print the filter in force.
+    Get,
+    /// Replace the filter until the next restart, e.g.
+    /// `info,timpani_o::scheduler=debug` (RUST_LOG syntax).  An invalid
+    /// filter is rejected and the current one stays.
+    SetLevel { filter: String },
+}
+
+#[derive(Debug, Args)]
+struct ReproArgs {
+    #[command(subcommand)]
+    action: ReproAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum ReproAction {
+    /// Replay a bundle offline and report whether the placement matches
+    /// the recorded one.
+    Run {
+        /// Bundle file (JSON or CBOR).
+        bundle: PathBuf,
+    },
+}
+
+#[derive(Debug, Args)]
+struct ConfigArgs {
+    #[command(subcommand)]
+    action: ConfigAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum ConfigAction {
+    /// Show what rolling out a new node configuration would change and
+    /// which placements it would break, without applying anything.
+    Impact {
+        /// Node configuration in service.
+        #[arg(long = "current")]
+        current: PathBuf,
+
+        /// Node configuration to roll out.
+        #[arg(long = "proposed")]
+        proposed: PathBuf,
+
+        /// Placements to check (as written by `timpani-o schedule --save`);
+        /// without it only the configuration changes and empty-cluster
+        /// capacity are shown.
+        #[arg(long = "state")]
+        state: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Args)]
+struct CompactArgs {
+    /// Id of the proposal to apply.
+    proposal_id: u64,
+
+    /// AdminService URL of the running instance
+    /// [default: http://<admin-addr>].
+    #[arg(long = "addr")]
+    addr: Option<String>,
+}
+
+#[derive(Debug, Args)]
+struct NodeArgs {
+    #[command(subcommand)]
+    action: NodeAction,
+
+    /// AdminService URL of the running instance
+    /// [default: http://<admin-addr>].
+    #[arg(long = "addr", global = true)]
+    addr: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum NodeAction {
+    /// Stop placing new tasks on the node; its tasks stay.
+    Cordon { node: String },
+    /// Let the node take new tasks again.
+    Uncordon { node: String },
+    /// Cordon the node and move its tasks to other nodes, a batch at a time.
+    Drain {
+        node: String,
+
+        /// Tasks moved per step.
+        #[arg(long = "batch-size", default_value_t = DEFAULT_DRAIN_BATCH_SIZE)]
+        batch_size: u32,
+    },
+    /// Report what would happen to the node's tasks if it went away,
+    /// without changing anything.  Exits 1 if some task would fit nowhere.
+    WhatIfLoss { node: String },
+}
+
+#[derive(Debug, Args)]
+struct ScheduleArgs {
+    /// Workload YAML (a `SchedInfo`, as used by pullpiri-sim; task
+    /// `templates:` are expanded).
+    #[arg(short = 'w', long = "workload")]
+    workload: PathBuf,
+
+    /// Output format (`json` prints the summary only).
+    #[arg(long = "format", value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Also write the placement as a Graphviz DOT graph to this path.
+    #[arg(long = "output-dot")]
+    output_dot: Option<PathBuf>,
+
+    /// Also write the placement (node -> tasks) to this path, e.g. for
+    /// `timpani-o config impact --state` (format from --save-format, else
+    /// the .json / .cbor extension, else JSON).
+    #[arg(long = "save")]
+    save: Option<PathBuf>,
+
+    /// Placement format for --save.
+    #[arg(long = "save-format", value_enum)]
+    save_format: Option<Format>,
+}
+
+#[derive(Debug, Args)]
+struct SweepArgs {
+    /// Workload YAML, as for `timpani-o schedule --workload`.
+    #[arg(long = "tasks")]
+    tasks: PathBuf,
+
+    /// Lowest threshold.
+    #[arg(long = "from", default_value_t = 0.70)]
+    from: f64,
+
+    /// Highest threshold (included).
+    #[arg(long = "to", default_value_t = 0.95)]
+    to: f64,
+
+    /// Threshold increment.
+    #[arg(long = "step", default_value_t = 0.05)]
+    step: f64,
+
+    /// Summary format, on stdout.
+    #[arg(long = "format", value_enum, default_value_t = SweepFormat::Csv)]
+    format: SweepFormat,
+
+    /// Also draw tasks placed per threshold as a text chart, on stderr.
+    #[arg(long = "chart")]
+    chart: bool,
+}
+
+#[derive(Debug, Args)]
+struct ReportArgs {
+    /// Placements to report (as written by `timpani-o schedule --save`).
+    #[arg(long = "state")]
+    state: PathBuf,
+
+    /// Report file; stdout if not given.
+    #[arg(long = "out")]
+    out: Option<PathBuf>,
+
+    /// Report format [default: html for a .html / .htm --out, else
+    /// markdown].
+    #[arg(long = "format", value_enum)]
+    format: Option<ReportFormat>,
+}
+
+#[derive(Debug, Args)]
+struct DoctorArgs {
+    /// Overall time limit for all checks, in seconds.
+    #[arg(long = "timeout-secs", default_value_t = DEFAULT_DOCTOR_TIMEOUT.as_secs())]
+    timeout_secs: u64,
+}
+
+#[derive(Debug, Args)]
+struct StatusArgs {
+    /// SchedInfoService URL of the running instance
+    /// [default: http://localhost:<sinfoport>].
+    #[arg(long = "addr")]
+    addr: Option<String>,
+
+    /// Tenant whose workload to show.
+    #[arg(long = "tenant", default_value = DEFAULT_TENANT)]
+    tenant: String,
+
+    /// Output format.
+    #[arg(long = "format", value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Refresh every SECS seconds until interrupted.
+    #[arg(long = "watch", value_name = "SECS")]
+    watch: Option<u64>,
+
+    /// Also write each snapshot to PATH (format from --save-format, else
+    /// the .json / .cbor extension, else JSON).
+    #[arg(long = "save", value_name = "PATH")]
+    save: Option<PathBuf>,
+
+    /// Snapshot format for --save.
+    #[arg(long = "save-format", value_enum)]
+    save_format: Option<Format>,
+
+    /// Render a snapshot saved with --save instead of querying a server.
+    #[arg(long = "from", value_name = "PATH", conflicts_with_all = ["addr", "watch", "save"])]
+    from: Option<PathBuf>,
+}
+
+// ── status subcommand ─────────────────────────────────────────────────────────
+
+/// Run `timpani-o status`; returns the process exit code.
+///
+/// Without `--watch` a failed query exits 1.  With `--watch` errors are
+/// printed and the loop keeps polling, so a restarting server is tolerated.
+async fn run_status(args: &StatusArgs, sinfo_port: u16, style: DurationStyle) -> i32 {
+    if let Some(path) = &args.from {
+        return match codec::load::<ClusterStatus>(path) {
+            Ok(status) => {
+                print!("{}", render(&status, args.format, style));
+                0
+            }
+            Err(e) => {
+                eprintln!("timpani-o status: {e}");
+                1
+            }
+        };
+    }
+
+    let addr = args
+        .addr
+        .clone()
+        .unwrap_or_else(|| format!("http://localhost:{sinfo_port}"));
+    loop {
+        match fetch_cluster_status(&addr, &args.tenant).await {
+            Ok(status) => {
+                if args.watch.is_some() {
+                    // Clear screen and home the cursor between refreshes.
+                    print!("\x1b[2J\x1b[H");
+                }
+                print!("{}", render(&status, args.format, style));
+                if let Some(path) = &args.save {
+                    if let Err(e) = codec::save(path, &status, args.save_format) {
+                        eprintln!("timpani-o status: {e}");
+                        if args.watch.is_none() {
+                            return 1;
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("timpani-o status: {e}");
+                if args.watch.is_none() {
+                    return 1;
+                }
+            }
+        }
+        let Some(secs) = args.watch else {
+            return 0;
+        };
+        tokio::time::sleep(std::time::Duration::from_secs(secs.max(1))).await;
+    }
+}
+
+// ── schedule subcommand ───────────────────────────────────────────────────────
+
+/// The naming policy selected by `--name-pattern`.
+fn naming_policy(cli: &Cli) -> Result<NamingPolicy, regex::Error> {
+    match &cli.name_pattern {
+        Some(pattern) => NamingPolicy::with_pattern(pattern),
+        None => Ok(NamingPolicy::default()),
+    }
+}
+
+/// The per-task logging policy selected by `--log-verbose-task-limit` and
+/// `--log-progress-interval`.
+fn log_policy(cli: &Cli) -> LogPolicy {
+    LogPolicy::default()
+        .with_verbose_task_limit(cli.log_verbose_task_limit)
+        .with_progress_interval(cli.log_progress_interval)
+}
+
+/// The metadata policy selected by the `--metadata-*` flags.
+fn metadata_policy(cli: &Cli) -> MetadataPolicy {
+    let policy = MetadataPolicy::default()
+        .with_max_keys(cli.metadata_max_keys)
+        .with_max_value_len(cli.metadata_max_value_len);
+    if cli.metadata_forward_keys.is_empty() {
+        return policy.with_forwarded_keys(DEFAULT_FORWARDED_KEYS.iter().copied());
+    }
+    policy.with_forwarded_keys(cli.metadata_forward_keys.iter().cloned())
+}
+
+/// Run `timpani-o schedule`; returns the process exit code.
+///
+/// Uses the global `--nodeconfig`, `--algorithm`, `--algorithm-chain`,
+/// `--cpu-threshold` and `--seed`; options set in the workload file take
+/// precedence, as they do for `AddSchedInfo`.  Each link of a chain is
+/// reported on stderr.
+fn run_schedule(args: &ScheduleArgs, cli: &Cli) -> i32 {
+    match schedule_offline(args, cli) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("timpani-o schedule: {e:#}");
+            1
+        }
+    }
+}
+
+/// The proximity table named by `--proximity-table`, if any.
+fn proximity_table(cli: &Cli) -> anyhow::Result<Option<ProximityTable>> {
+    let Some(path) = &cli.proximity_table else {
+        return Ok(None);
+    };
+    let yaml =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let table =
+        ProximityTable::from_yaml(&yaml).with_context(|| format!("parsing {}", path.display()))?;
+    Ok(Some(table))
+}
+
+/// The scheduling options selected by the global flags, without the
+/// proximity table (see [`proximity_table`]).
+/// How the subcommands write durations (`--raw-units`,
+/// `--duration-precision`).
+fn duration_style(cli: &Cli) -> DurationStyle {
+    DurationStyle::default()
+        .with_precision(cli.duration_precision)
+        .with_raw(cli.raw_units)
+}
+
+fn schedule_options(cli: &Cli) -> ScheduleOptions {
+    let mut opts = ScheduleOptions::default()
+        .with_algorithm(
+            cli.algorithm_chain
+                .first()
+                .copied()
+                .unwrap_or(cli.algorithm),
+        )
+        .with_algorithm_chain(cli.algorithm_chain.iter().copied())
+        .with_cpu_utilization_threshold(cli.cpu_threshold)
+        .with_utilization_epsilon(cli.utilization_epsilon)
+        .with_seed(cli.seed)
+        .with_reserve_pinned_cpus(cli.reserve_pinned_cpus);
+    opts.release_stagger = cli.stagger_releases;
+    opts.verify_with_simulation = cli.verify_with_simulation;
+    opts.simulation_hyperperiod_limit = Micros(cli.simulation_hyperperiod_limit_us);
+    opts.simulation_max_jobs = cli.simulation_max_jobs;
+    opts.margin_analysis = cli.margin_analysis;
+    opts.priority_ordering = cli.priority_ordering;
+    opts.max_task_duration = Micros(cli.max_task_duration_us).saturating_to_nanos();
+    opts.log_policy = log_policy(cli);
+    opts.limits = input_limits(cli);
+    opts
+}
+
+/// The input caps set by `--max-tasks`, `--max-unique-periods`,
+/// `--max-task-problems` and `--max-templates`.
+fn input_limits(cli: &Cli) -> InputLimits {
+    InputLimits::default()
+        .with_max_tasks(cli.max_tasks)
+        .with_max_unique_periods(cli.max_unique_periods)
+        .with_max_task_problems(cli.max_task_problems)
+        .with_max_templates(cli.max_templates)
+}
+
+/// The node configuration named by the global `--nodeconfig`.
+fn offline_node_config(cli: &Cli) -> anyhow::Result<NodeConfigManager> {
+    let config_path = cli
+        .node_config
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("--nodeconfig is required"))?;
+    let mut config = NodeConfigManager::new().with_strict_validation(cli.strict_config);
+    config.load_from_file(config_path)?;
+    Ok(config)
+}
+
+/// The workload file at `path`, the options it is scheduled with (the
+/// global flags, overridden by the file) and its tasks, range-checked and
+/// validated like `AddSchedInfo` does.
+fn offline_workload(
+    path: &Path,
+    cli: &Cli,
+) -> anyhow::Result<(SchedInfo, ScheduleOptions, Vec<Task>)> {
+    let yaml =
+        std::fs::read_to_string(path).with_context(|| format!("opening {}", path.display()))?;
+    // An offline run reports its limit hits as errors; nobody reads counts.
+    let hits = LimitHits::default();
+    let req = taskfile::parse(&yaml, &input_limits(cli), &hits)
+        .with_context(|| format!("parsing {}", path.display()))?;
+
+    let mut opts = schedule_options(cli);
+    opts.proximity = proximity_table(cli)?.map(Arc::new);
+    if let Some(name) = req.algorithm.as_deref() {
+        opts.algorithm = name.parse()?;
+        opts.algorithm_chain.clear();
+    }
+    if !req.algorithm_chain.is_empty() {
+        opts.algorithm_chain = req
+            .algorithm_chain
+            .iter()
+            .map(|name| name.parse())
+            .collect::<Result<_, _>>()?;
+        opts.algorithm = opts.algorithm_chain[0];
+    }
+    if let Some(threshold) = req.cpu_utilization_threshold {
+        opts.cpu_utilization_threshold = threshold;
+    }
+    if let Some(seed) = req.seed {
+        opts.seed = seed;
+    }
+    if !req.allowed_nodes.is_empty() {
+        opts = opts.with_allowed_nodes(req.allowed_nodes.iter().cloned());
+    }
+    if let Some(name) = req.priority_ordering.as_deref() {
+        opts.priority_ordering = name.parse()?;
+    }
+    opts = opts.with_chains(chains_from_proto(&req));
+
+    let mut tasks = tasks_from_proto(&req);
+    let naming = naming_policy(cli).context("invalid --name-pattern")?;
+    let metadata = metadata_policy(cli);
+    for t in &mut tasks {
+        let clamped = t
+            .enforce_ranges(cli.range_check)
+            .with_context(|| format!("in {}", path.display()))?;
+        for (field, value) in clamped {
+            eprintln!("task '{}': {field} {value} clamped into range", t.name);
+        }
+        t.validate(&naming)
+            .with_context(|| format!("in {}", path.display()))?;
+        metadata
+            .check(&t.name, &t.metadata, &hits)
+            .with_context(|| format!("in {}", path.display()))?;
+    }
+    Ok((req, opts, tasks))
+}
+
+fn schedule_offline(args: &ScheduleArgs, cli: &Cli) -> anyhow::Result<()> {
+    let config = offline_node_config(cli)?;
+    let (req, opts, tasks) = offline_workload(&args.workload, cli)?;
+    let hyperperiod = HyperperiodManager::new()
+        .with_max_unique_periods(opts.limits.max_unique_periods)
+        .calculate_hyperperiod(&req.workload_id, &tasks)?
+        .clone();
+    let config = Arc::new(config);
+    let scheduler = GlobalScheduler::new(Arc::clone(&config));
+    let run = run_chain(&opts, |link| {
+        scheduler.schedule_with_options(tasks.clone(), link)
+    });
+    if !opts.algorithm_chain.is_empty() {
+        for a in &run.attempts {
+            match a.error_code {
+                Some(_) => eprintln!("{}: {}", a.algorithm, a.error),
+                None => eprintln!("{}: placed the workload", a.algorithm),
+            }
+        }
+    }
+    let (schedule, opts) = run.result?;
+    let warnings = check_schedule(&schedule, opts.utilization_epsilon, opts.priority_ordering);
+    let margins = runtime_margins(&schedule, &NodeSchedMap::new(), &opts);
+    let chains = chain_latencies(&analyse_chains(&schedule, &opts.chains));
+    let chain_misses = chains.iter().filter(|c| !c.met).count();
+    let mut summary = workload_summary(&hyperperiod, &schedule, warnings.len() + chain_misses);
+    summary.chains = chains;
+    summary.min_runtime_margin = margins
+        .values()
+        .flatten()
+        .flatten()
+        .copied()
+        .min_by(f64::total_cmp);
+    summary.priority_ordering = opts.priority_ordering.as_str().to_string();
+    let style = duration_style(cli);
+    if let Some(path) = &args.output_dot {
+        std::fs::write(path, to_dot(&schedule, &config, style))
+            .with_context(|| format!("writing {}", path.display()))?;
+    }
+    if let Some(path) = &args.save {
+        codec::save(path, &schedule, args.save_format)?;
+    }
+
+    match args.format {
+        OutputFormat::Table => {
+            println!(
+                "{:<16} {:<16} {:>4} {:>10} {:>10} {:>7}",
+                "TASK", "NODE", "CPU", "PERIOD", "RUNTIME", "MARGIN"
+            );
+            for (node, node_tasks) in &schedule {
+                for (t, margin) in node_tasks.iter().zip(&margins[node]) {
+                    println!(
+                        "{:<16} {:<16} {:>4} {:>10} {:>10} {:>7}",
+                        t.name,
+                        node,
+                        t.assigned_cpu,
+                        style.format_ns(t.period_ns.as_u64()),
+                        style.format_ns(t.runtime_ns.as_u64()),
+                        margin.map_or_else(|| "-".to_string(), |m| format!("{m:.2}x"))
+                    );
+                }
+            }
+            for w in &warnings {
+                println!(
+                    "warning: {}/cpu{} at {:.1}% exceeds the {} bound {:.1}%",
+                    w.node,
+                    w.cpu,
+                    w.utilization * 100.0,
+                    w.analysis,
+                    w.bound * 100.0
+                );
+            }
+            println!();
+            println!("{}", render_summary(&summary, style));
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summary)?),
+    }
+    Ok(())
+}
+
+// ── sweep subcommand ──────────────────────────────────────────────────────────
+
+/// Run `timpani-o sweep`; returns the process exit code.
+///
+/// Like `schedule`, uses the global `--nodeconfig`, `--algorithm` and the
+/// other scheduling flags, and the options in the workload file; only the
+/// threshold is swept (see [`timpani_o::scheduler::sweep`]).
+fn run_sweep(args: &SweepArgs, cli: &Cli) -> i32 {
+    match sweep_offline(args, cli) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("timpani-o sweep: {e:#}");
+            1
+        }
+    }
+}
+
+fn sweep_offline(args: &SweepArgs, cli: &Cli) -> anyhow::Result<()> {
+    let thresholds = threshold_steps(args.from, args.to, args.step);
+    if thresholds.is_empty() {
+        anyhow::bail!(
+            "no thresholds from {} to {} by {} — --step must be positive and --from at most --to",
+            args.from,
+            args.to,
+            args.step
+        );
+    }
+    let config = offline_node_config(cli)?;
+    let (_, opts, tasks) = offline_workload(&args.tasks, cli)?;
+    let scheduler = GlobalScheduler::new(Arc::new(config));
+    let points = scheduler.sweep_thresholds(&tasks, &opts, &thresholds)?;
+
+    match args.format {
+        SweepFormat::Csv => print!("{}", to_csv(&points)),
+        SweepFormat::Json => println!("{}", serde_json::to_string_pretty(&points)?),
+    }
+    if args.chart {
+        eprint!("{}", chart(&points));
+    }
+    Ok(())
+}
+
+// ── report subcommand ─────────────────────────────────────────────────────────
+
+/// Run `timpani-o report`; returns the process exit code.
+///
+/// Uses the global `--nodeconfig` and the priority ordering, threshold and
+/// epsilon flags (see [`timpani_o::report::signoff`]).
+fn run_report(args: &ReportArgs, cli: &Cli) -> i32 {
+    match report_offline(args, cli) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("timpani-o report: {e:#}");
+            1
+        }
+    }
+}
+
+fn report_offline(args: &ReportArgs, cli: &Cli) -> anyhow::Result<()> {
+    let config = offline_node_config(cli)?;
+    let schedule: NodeSchedMap = codec::load(&args.state)?;
+    let format = args.format.unwrap_or_else(|| {
+        args.out
+            .as_deref()
+            .map_or(ReportFormat::Markdown, ReportFormat::from_path)
+    });
+    let report = signoff_report(
+        &schedule,
+        &config,
+        &schedule_options(cli),
+        format,
+        duration_style(cli),
+    );
+    match &args.out {
+        Some(path) => {
+            std::fs::write(path, report).with_context(|| format!("writing {}", path.display()))?
+        }
+        None => print!("{report}"),
+    }
+    Ok(())
+}
+
+// ── doctor subcommand ─────────────────────────────────────────────────────────
+
+/// Run `timpani-o doctor` against the global flags; returns the process
+/// exit code (1 if a mandatory check failed).
+async fn run_doctor(args: &DoctorArgs, cli: &Cli) -> i32 {
+    let mut doctor = Doctor::new(format!("http://{}:{}", cli.fault_host, cli.fault_port))
+        .with_default_node_port(cli.node_port)
+        .with_listen_port("SchedInfoService", cli.sinfo_port)
+        .with_listen_port("NodeService", cli.node_port)
+        .with_listen_port("AdminService", cli.admin_addr.port())
+        .with_timeout(std::time::Duration::from_secs(args.timeout_secs.max(1)));
+    if let Some(path) = &cli.node_config {
+        doctor = doctor.with_node_config(path);
+    }
+    let report = doctor.run().await;
+    print!("{}", report.render());
+    i32::from(!report.is_healthy())
+}
+
+// ── node subcommand ───────────────────────────────────────────────────────────
+
+/// `addr`, else the AdminService URL for `--admin-addr`.
+fn admin_url(addr: Option<&String>, admin_addr: SocketAddr) -> String {
+    addr.cloned().unwrap_or_else(|| {
+        let mut local = admin_addr;
+        if local.ip().is_unspecified() {
+            local.set_ip([127, 0, 0, 1].into());
+        }
+        format!("http://{local}")
+    })
+}
+
+/// Run `timpani-o node`; returns the process exit code.
+async fn run_node(args: &NodeArgs, admin_addr: SocketAddr) -> i32 {
+    let addr = admin_url(args.addr.as_ref(), admin_addr);
+    match node_action(&args.action, &addr).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("timpani-o node: {e}");
+            1
+        }
+    }
+}
+
+/// Returns the process exit code.
+async fn node_action(action: &NodeAction, addr: &str) -> Result<i32, AdminError> {
+    let mut admin = AdminClient::connect(addr).await?;
+    match action {
+        NodeAction::Cordon { node } => println!("{}", cordon_line(&admin.cordon(node).await?)),
+        NodeAction::Uncordon { node } => println!("{}", cordon_line(&admin.uncordon(node).await?)),
+        NodeAction::Drain { node, batch_size } => loop {
+            let step = admin.drain_step(node, *batch_size).await?;
+            for p in &step.moved {
+                println!("moved {} to {}:{}", p.task, p.node, p.cpu);
+            }
+            if step.remaining == 0 {
+                for p in &step.pinned {
+                    println!("pinned {} (tenant {})", p.task, p.tenant);
+                }
+                println!("node {node} drained");
+                break;
+            }
+        },
+        NodeAction::WhatIfLoss { node } => {
+            let r = admin.what_if_node_loss(node).await?;
+            print_what_if(&r);
+            return Ok(i32::from(!r.survivable));
+        }
+    }
+    Ok(0)
+}
+
+/// Print a `timpani-o node what-if-loss` result.
+fn print_what_if(r: &WhatIfResult) {
+    for m in &r.moved {
+        println!(
+            "would move {} (tenant {}) to {}:{}",
+            m.task, m.tenant, m.to_node, m.to_cpu
+        );
+    }
+    for u in &r.unplaced {
+        println!(
+            "cannot place {} (tenant {}): {}",
+            u.task, u.tenant, u.reason
+        );
+        for rej in &u.rejections {
+            println!("  {:<16} {}", rej.node, rej.reason);
+        }
+    }
+    println!();
+    println!("{:<16} {:>15}", "NODE", "PEAK_CPU");
+    for p in &r.peaks {
+        println!(
+            "{:<16} {:>15}",
+            p.node,
+            format!("{:.0}% -> {:.0}%", p.before * 100.0, p.after * 100.0)
+        );
+    }
+    let verdict = if r.survivable {
+        "survivable"
+    } else {
+        "not survivable"
+    };
+    println!("loss of node {}: {verdict}", r.node);
+}
+
+/// `node n1 cordoned`, `node n1 already uncordoned`, …
+fn cordon_line(r: &CordonResult) -> String {
+    let state = if r.cordoned { "cordoned" } else { "uncordoned" };
+    let already = if r.changed { "" } else { "already " };
+    format!("node {} {already}{state}", r.node)
+}
+
+// ── log subcommand ────────────────────────────────────────────────────────────
+
+/// Run `timpani-o log`; returns the process exit code.
+async fn run_log(args: &LogArgs, admin_addr: SocketAddr) -> i32 {
+    let addr = admin_url(args.addr.as_ref(), admin_addr);
+    match log_action(&args.action, &addr).await {
+        Ok(r) => {
+            println!("{}", log_filter_line(&r));
+            0
+        }
+        Err(e) => {
+            eprintln!("timpani-o log: {e}");
+            1
+        }
+    }
+}
+
+async fn log_action(action: &LogAction, addr: &str) -> Result<LogFilterResult, AdminError> {
+    let mut admin = AdminClient::connect(addr).await?;
+    match action {
+        LogAction::Get => admin.log_filter().await,
+        LogAction::SetLevel { filter } => admin.set_log_filter(filter).await,
+    }
+}
+
+/// `log filter: debug`, `log filter: info -> debug`
+fn log_filter_line(r: &LogFilterResult) -> String {
+    if r.previous.is_empty() {
+        format!("log filter: {}", r.filter)
+    } else {
+        format!("log filter: {} -> {}", r.previous, r.filter)
+    }
+}
+
+// ── compact subcommand ────────────────────────────────────────────────────────
+
+/// Run `timpani-o compact`; returns the process exit code.
+async fn run_compact(args: &CompactArgs, admin_addr: SocketAddr) -> i32 {
+    let addr = admin_url(args.addr.as_ref(), admin_addr);
+    let result = async {
+        let mut admin = AdminClient::connect(&addr).await?;
+        admin.apply_compaction(args.proposal_id).await
+    };
+    match result.await {
+        Ok(r) => {
+            for m in &r.moved {
+                println!(
+                    "moved {} (tenant {}) to {}:{}",
+                    m.task, m.tenant, m.to_node, m.to_cpu
+                );
+            }
+            if !r.nodes_freed.is_empty() {
+                println!("freed {}", r.nodes_freed.join(", "));
+            }
+            println!("compaction proposal {} applied", r.proposal_id);
+            0
+        }
+        Err(e) => {
+            eprintln!("timpani-o compact: {e}");
+            1
+        }
+    }
+}
+
+// ── config subcommand ─────────────────────────────────────────────────────────
+
+/// Run `timpani-o config`; returns the process exit code (1 on error, or if
+/// the proposed configuration would leave a placed task unadmitted).
+fn run_config(args: &ConfigArgs, cli: &Cli) -> i32 {
+    let ConfigAction::Impact {
+        current,
+        proposed,
+        state,
+    } = &args.action;
+    match config_impact(current, proposed, state.as_deref(), cli) {
+        Ok(report) => i32::from(!report.failing.is_empty()),
+        Err(e) => {
+            eprintln!("timpani-o config impact: {e:#}");
+            1
+        }
+    }
+}
+
+fn config_impact(
+    current: &Path,
+    proposed: &Path,
+    state: Option<&Path>,
+    cli: &Cli,
+) -> anyhow::Result<ImpactReport> {
+    let load = |path: &Path| -> anyhow::Result<Arc<NodeConfigManager>> {
+        let mut config = NodeConfigManager::new().with_strict_validation(cli.strict_config);
+        config.load_from_file(path)?;
+        Ok(Arc::new(config))
+    };
+    let (current, proposed) = (load(current)?, load(proposed)?);
+    let placements: NodeSchedMap = match state {
+        Some(path) => codec::load(path)?,
+        None => NodeSchedMap::new(),
+    };
+
+    let diff = config::diff(&current, &proposed);
+    let report = GlobalScheduler::new(current).impact_of(
+        &GlobalScheduler::new(proposed),
+        &diff,
+        &placements,
+        &schedule_options(cli),
+    );
+
+    if diff.is_empty() {
+        println!("no placement-relevant configuration changes");
+    }
+    for node in &diff.added {
+        println!("+ node {node}");
+    }
+    for node in &diff.removed {
+        println!("- node {node}");
+    }
+    for (node, c) in &diff.changed {
+        let mut parts = Vec::new();
+        if !c.cpus_added.is_empty() {
+            parts.push(format!("+cpus {:?}", c.cpus_added));
+        }
+        if !c.cpus_removed.is_empty() {
+            parts.push(format!("-cpus {:?}", c.cpus_removed));
+        }
+        if let Some((old, new)) = c.memory_mb {
+            parts.push(format!("memory {old} -> {new} MB"));
+        }
+        if let Some((old, new)) = c.max_workloads {
+            let fmt = |v: Option<usize>| v.map_or("unlimited".to_string(), |n| n.to_string());
+            parts.push(format!("max_workloads {} -> {}", fmt(old), fmt(new)));
+        }
+        if let Some((_, enabled)) = c.enabled {
+            parts.push(if enabled { "enabled" } else { "disabled" }.to_string());
+        }
+        println!("~ node {node}: {}", parts.join(", "));
+    }
+
+    println!();
+    println!(
+        "{:<16} {:>9} {:>13} {:>13}",
+        "NODE", "CPUS", "FREE", "MEMORY_MB"
+    );
+    let mem = |m: Option<u64>| match m {
+        None => "-".to_string(),
+        Some(u64::MAX) => "unlimited".to_string(),
+        Some(mb) => mb.to_string(),
+    };
+    for c in &report.capacity {
+        println!(
+            "{:<16} {:>9} {:>13} {:>13}",
+            c.node,
+            format!("{} -> {}", c.cpus_before, c.cpus_after),
+            format!(
+                "{:.0}% -> {:.0}%",
+                c.free_before * 100.0,
+                c.free_after * 100.0
+            ),
+            if c.memory_mb_before == c.memory_mb_after {
+                mem(c.memory_mb_after)
+            } else {
+                format!("{} -> {}", mem(c.memory_mb_before), mem(c.memory_mb_after))
+            },
+        );
+    }
+    println!(
+        "cluster: {:+} CPUs, {:+.0}% free",
+        report.cpu_delta(),
+        report.free_delta() * 100.0
+    );
+
+    if state.is_some() {
+        println!();
+        for o in &report.orphaned {
+            println!(
+                "orphaned {} ({}) on {}:{} ({:.1}%)",
+                o.task,
+                o.workload_id,
+                o.node,
+                o.cpu,
+                o.utilization * 100.0
+            );
+        }
+        for t in report.rehomed.values().flatten() {
+            println!(
+                "  would move {} to {}:{}",
+                t.name, t.assigned_node, t.assigned_cpu
+            );
+        }
+        for f in &report.failing {
+            println!("FAILS {} ({}): {}", f.task, f.workload_id, f.reason);
+        }
+        println!(
+            "{} placement(s) orphaned, {} would no longer be admitted",
+            report.orphaned.len(),
+            report.failing.len()
+        );
+    }
+    Ok(report)
+}
+
+// ── repro subcommand ──────────────────────────────────────────────────────────
+
+/// Run `timpani-o repro`; returns the process exit code (1 unless the
+/// replay matches).
+fn run_repro(args: &ReproArgs) -> i32 {
+    let ReproAction::Run { bundle } = &args.action;
+    match replay_bundle(bundle) {
+        Ok(matched) => i32::from(!matched),
+        Err(e) => {
+            eprintln!("timpani-o repro run: {e:#}");
+            1
+        }
+    }
+}
+
+/// Replay `path` and print the outcome; `true` if the placement matches.
+fn replay_bundle(path: &Path) -> anyhow::Result<bool> {
+    let bundle: ReproBundle = codec::load(path)?;
+    println!(
+        "workload {} of tenant {}, captured by timpani-o {}",
+        bundle.request.workload_id, bundle.tenant, bundle.version
+    );
+    if bundle.version != env!("CARGO_PKG_VERSION") {
+        println!(
+            "note: replaying with timpani-o {}; results may differ across versions",
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+    if !bundle.redacted_keys.is_empty() {
+        println!("redacted: {}", bundle.redacted_keys.join(", "));
+    }
+    match bundle.replay()? {
+        ReproOutcome::Match => {
+            println!("match: the replay reproduces the recorded placement");
+            Ok(true)
+        }
+        ReproOutcome::Mismatch(diff) => {
+            println!("MISMATCH: the replay placed the workload differently");
+            for (node, d) in &diff.nodes {
+                for t in &d.removed {
+                    println!("  - {node}: {t} (recorded only)");
+                }
+                for t in &d.added {
+                    println!(
+                        "  + {node}: {} on CPU {} (replay only)",
+                        t.name, t.assigned_cpu
+                    );
+                }
+                for t in &d.modified {
+                    println!("  ~ {node}: {} now on CPU {}", t.name, t.assigned_cpu);
+                }
+            }
+            Ok(false)
+        }
+        ReproOutcome::Failed(e) => {
+            println!("MISMATCH: the replay failed to place the workload: {e}");
+            Ok(false)
+        }
+    }
 }
 
 // ── Entry point ───────────────────────────────────────────────────────────────
 
 #[tokio::main]
 async fn main() {
+    let cli = Cli::parse();
+
+    // The subcommands print to stdout only; no server logging.
+    match &cli.command {
+        Some(Command::Status(args)) => {
+            process::exit(run_status(args, cli.sinfo_port, duration_style(&cli)).await)
+        }
+        Some(Command::Schedule(args)) => process::exit(run_schedule(args, &cli)),
+        Some(Command::Sweep(args)) => process::exit(run_sweep(args, &cli)),
+        Some(Command::Report(args)) => process::exit(run_report(args, &cli)),
+        Some(Command::Doctor(args)) => process::exit(run_doctor(args, &cli).await),
+        Some(Command::Node(args)) => process::exit(run_node(args, cli.admin_addr).await),
+        Some(Command::Compact(args)) => process::exit(run_compact(args, cli.admin_addr).await),
+        Some(Command::Config(args)) => process::exit(run_config(args, &cli)),
+        Some(Command::Repro(args)) => process::exit(run_repro(args)),
+        Some(Command::Log(args)) => process::exit(run_log(args, cli.admin_addr).await),
+        None => {}
+    }
+
     // Initialise structured logging.
-    // Level is controlled by the RUST_LOG env-var (e.g. RUST_LOG=debug).
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("debug")),
-        )
+    // Level is controlled by the RUST_LOG env-var (e.g. RUST_LOG=debug) and
+    // can be changed at runtime with `timpani-o log set-level`.
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"));
+    let initial_filter = filter.to_string();
+    let (filter, filter_handle) = reload::Layer::new(filter);
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
         .init();
+    let log_filter = LogFilter::new(initial_filter, move |directives| {
+        let filter = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+        filter_handle.reload(filter).map_err(|e| e.to_string())
+    });
 
     info!("Timpani-O starting up...");
 
-    // ── Parse CLI arguments ───────────────────────────────────────────────────
-    let cli = Cli::parse();
-
     info!(
         sinfo_port        = cli.sinfo_port,
         fault_host        = %cli.fault_host,
         fault_port        = cli.fault_port,
         node_port         = cli.node_port,
+        admin_addr        = %cli.admin_addr,
         notify_fault      = cli.notify_fault,
         sync_timeout_secs = cli.sync_timeout_secs,
         node_config       = ?cli.node_config,
+        algorithm         = %cli.algorithm,
+        algorithm_chain   = ?cli.algorithm_chain,
+        cpu_threshold     = cli.cpu_threshold,
+        utilization_epsilon = cli.utilization_epsilon,
+        seed              = cli.seed,
+        reserve_pinned_cpus = cli.reserve_pinned_cpus,
+        stagger_releases  = ?cli.stagger_releases,
+        verify_with_simulation = ?cli.verify_with_simulation,
+        simulation_hyperperiod_limit_us = cli.simulation_hyperperiod_limit_us,
+        simulation_max_jobs = cli.simulation_max_jobs,
+        margin_analysis   = %cli.margin_analysis,
+        priority_ordering = %cli.priority_ordering,
+        max_task_duration_us = cli.max_task_duration_us,
+        log_verbose_task_limit = cli.log_verbose_task_limit,
+        log_progress_interval = cli.log_progress_interval,
+        use_live_memory   = cli.use_live_memory,
+        memory_report_window_secs = cli.memory_report_window_secs,
+        respect_foreign_load = cli.respect_foreign_load,
+        repair_offline_cpus = cli.repair_offline_cpus,
+        clock_check       = ?cli.clock_check,
+        max_clock_offset_us = cli.max_clock_offset_us,
+        advisory_window_secs = cli.advisory_window_secs,
+        full_push         = cli.full_push,
+        push_epoch_ms     = cli.push_epoch_ms,
+        transactional_push_ms = cli.transactional_push_ms,
+        stream_batch_size = cli.stream_batch_size,
+        pending_capacity  = cli.pending_capacity,
+        revision_change_factor = cli.revision_change_factor,
+        allow_admission_overrides = cli.allow_admission_overrides,
+        max_request_bytes = cli.max_request_bytes,
+        raw_units         = cli.raw_units,
+        duration_precision = cli.duration_precision,
+        event_log_capacity = cli.event_log_capacity,
+        event_channel_capacity = cli.event_channel_capacity,
+        name_pattern      = ?cli.name_pattern,
+        range_check       = %cli.range_check,
+        metadata_max_keys = cli.metadata_max_keys,
+        max_tasks         = cli.max_tasks,
+        max_unique_periods = cli.max_unique_periods,
+        max_task_problems = cli.max_task_problems,
+        max_templates     = cli.max_templates,
+        metadata_max_value_len = cli.metadata_max_value_len,
+        metadata_forward_keys = ?cli.metadata_forward_keys,
+        repro_sensitive_keys = ?cli.repro_sensitive_keys,
+        shadow_algorithms = ?cli.shadow_algorithms,
+        evacuate_orphans  = cli.evacuate_orphans,
+        strict_config     = cli.strict_config,
+        apply_deadline_secs = cli.apply_deadline_secs,
+        reconcile_window_secs = cli.reconcile_window_secs,
+        apply_failover    = cli.apply_failover,
+        compaction_idle_secs = cli.compaction_idle_secs,
+        proximity_table   = ?cli.proximity_table,
         "Configuration"
     );
 
+    let mut schedule_defaults = schedule_options(&cli);
+    match proximity_table(&cli) {
+        Ok(table) => schedule_defaults.proximity = table.map(Arc::new),
+        Err(e) => {
+            error!("Invalid --proximity-table: {e:#}");
+            process::exit(1);
+        }
+    }
+    if let Err(e) = schedule_defaults.validate() {
+        error!("Invalid scheduling defaults: {e}");
+        process::exit(1);
+    }
+    let naming = match naming_policy(&cli) {
+        Ok(p) => p,
+        Err(e) => {
+            error!("Invalid --name-pattern: {e}");
+            process::exit(1);
+        }
+    };
+
     // ── Load node configuration ───────────────────────────────────────────────
-    let mut node_config_manager = NodeConfigManager::new();
+    let mut node_config_manager = NodeConfigManager::new()
+        .with_default_node_port(cli.node_port)
+        .with_strict_validation(cli.strict_config)
+        .with_respect_foreign_load(cli.respect_foreign_load);
+    if cli.use_live_memory {
+        node_config_manager = node_config_manager.with_live_memory(std::time::Duration::from_secs(
+            cli.memory_report_window_secs,
+        ));
+    }
 
     match &cli.node_config {
         Some(path) => {
             info!("Loading node configuration from: {}", path.display());
-            if let Err(e) = node_config_manager.load_from_file(path) {
+            if let Err(e) = node_config_manager
+                .load_from_file(path)
+                .map_err(anyhow::Error::from)
+            {
                 error!("Failed to load node configuration: {:#}", e);
                 process::exit(1);
             }
@@ -124,8 +1572,11 @@ async fn main() {
         sorted.sort_by_key(|n| &n.name);
         for node in sorted {
             info!(
-                "  [{name}]  cpus={cpus:?}  memory={mem}MB  arch={arch}  location={loc}",
+                "  [{name}]  cpus={cpus:?}  memory={mem}MB  arch={arch}  location={loc}  endpoint={ep}",
                 name = node.name,
+                ep = node_config_manager
+                    .resolve_endpoint(&node.name)
+                    .unwrap_or_default(),
                 cpus = node.available_cpus,
                 mem = node.max_memory_mb,
                 arch = node.architecture,
@@ -150,16 +1601,124 @@ async fn main() {
     info!(addr = %pullpiri_addr, "FaultClient ready (lazy connect)");
 
     // ── gRPC service instances ────────────────────────────────────────────────
+    let events = Arc::new(EventLog::new(
+        cli.event_log_capacity,
+        cli.event_channel_capacity,
+    ));
     let sched_info_svc = SchedInfoServiceImpl::new(
         Arc::clone(&node_config_manager),
         Arc::clone(&workload_store),
         Arc::clone(&fault_notifier),
-    );
-    let node_svc = NodeServiceImpl::new(
+    )
+    .with_schedule_defaults(schedule_defaults)
+    .with_advisory_window(std::time::Duration::from_secs(cli.advisory_window_secs))
+    .with_pending_capacity(cli.pending_capacity)
+    .with_revision_change_factor(cli.revision_change_factor)
+    .with_admission_overrides(cli.allow_admission_overrides)
+    .with_max_request_bytes(cli.max_request_bytes)
+    .with_shadow_algorithms(cli.shadow_algorithms.iter().copied())
+    .with_orphan_evacuation(cli.evacuate_orphans)
+    .with_event_log(Arc::clone(&events))
+    .with_naming_policy(naming)
+    .with_range_check(cli.range_check)
+    .with_metadata_policy(metadata_policy(&cli))
+    .with_sensitive_label_keys(cli.repro_sensitive_keys.iter().cloned())
+    .with_log_filter(log_filter);
+    let sched_info_svc = match &cli.node_config {
+        Some(path) => sched_info_svc.with_node_config_path(path),
+        None => sched_info_svc,
+    };
+    let apply_watchdog = (cli.apply_deadline_secs > 0).then(|| {
+        Arc::new(ApplyWatchdog::new(std::time::Duration::from_secs(
+            cli.apply_deadline_secs,
+        )))
+    });
+    let sched_info_svc = match &apply_watchdog {
+        Some(watchdog) => sched_info_svc
+            .with_apply_watchdog(Arc::clone(watchdog))
+            .with_apply_failover(cli.apply_failover),
+        None => sched_info_svc,
+    };
+    let sched_info_svc = match cli.compaction_idle_secs {
+        0 => sched_info_svc,
+        secs => sched_info_svc.with_compaction_idle(std::time::Duration::from_secs(secs)),
+    };
+    let sched_info_svc = match cli.push_epoch_ms {
+        0 => sched_info_svc,
+        ms => sched_info_svc.with_push_epoch(std::time::Duration::from_millis(ms)),
+    };
+    let sched_info_svc = match cli.transactional_push_ms {
+        0 => sched_info_svc,
+        ms => sched_info_svc.with_transactional_push(std::time::Duration::from_millis(ms)),
+    };
+    let mut node_svc = NodeServiceImpl::new(
         Arc::clone(&workload_store),
         Arc::clone(&fault_notifier),
         std::time::Duration::from_secs(cli.sync_timeout_secs),
-    );
+    )
+    .with_full_push(cli.full_push)
+    .with_stream_batch_size(cli.stream_batch_size)
+    .with_node_config(Arc::clone(&node_config_manager))
+    .with_event_log(events)
+    .with_metadata_policy(metadata_policy(&cli))
+    .with_foreign_load_threshold(cli.cpu_threshold)
+    .with_reconcile_window(std::time::Duration::from_secs(cli.reconcile_window_secs));
+    if cli.repair_offline_cpus {
+        node_svc = node_svc.with_offline_cpu_repair(cli.cpu_threshold);
+    }
+    if let Some(check) = cli.clock_check {
+        node_svc = node_svc.with_clock_check(
+            check,
+            std::time::Duration::from_micros(cli.max_clock_offset_us),
+        );
+    }
+    if let Some(watchdog) = apply_watchdog {
+        node_svc = node_svc.with_apply_watchdog(watchdog);
+
+        let svc = sched_info_svc.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(APPLY_WATCHDOG_TICK);
+            loop {
+                tick.tick().await;
+                svc.check_apply_deadlines().await;
+            }
+        });
+    }
+    // Report how the nodes compared with the ledger once the window after
+    // startup has closed.
+    if cli.reconcile_window_secs > 0 {
+        let svc = node_svc.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(RECONCILE_TICK);
+            loop {
+                tick.tick().await;
+                if svc.finish_reconciliation().await.is_some() {
+                    break;
+                }
+            }
+        });
+    }
+    // Remove workloads whose ttl_seconds has run out.
+    let expiry_svc = sched_info_svc.clone();
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(WORKLOAD_EXPIRY_TICK);
+        loop {
+            tick.tick().await;
+            expiry_svc.expire_workloads().await;
+        }
+    });
+
+    // Propose a better layout once the placements have been idle.
+    if cli.compaction_idle_secs > 0 {
+        let compaction_svc = sched_info_svc.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(COMPACTION_TICK);
+            loop {
+                tick.tick().await;
+                compaction_svc.propose_compaction().await;
+            }
+        });
+    }
 
     // ── Server addresses ──────────────────────────────────────────────────────
     let sinfo_addr = format!("0.0.0.0:{}", cli.sinfo_port)
@@ -171,6 +1730,7 @@ async fn main() {
 
     info!(addr = %sinfo_addr, "SchedInfoService starting (upstream — Pullpiri)");
     info!(addr = %node_addr,  "NodeService starting      (downstream — Timpani-N)");
+    info!(addr = %cli.admin_addr, "AdminService starting     (operators)");
 
     // ── Graceful shutdown — shared watch channel ──────────────────────────────
     let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
@@ -209,6 +1769,51 @@ async fn main() {
             shutdown_rx_node.changed().await.ok();
         }
     };
+    let admin_shutdown = {
+        let mut rx = shutdown_rx.clone();
+        async move {
+            while !*rx.borrow() {
+                rx.changed().await.ok();
+            }
+        }
+    };
+
+    // Publish held updates at push-epoch boundaries, and once more on
+    // shutdown.
+    if cli.push_epoch_ms > 0 {
+        let svc = sched_info_svc.clone();
+        let rx = shutdown_rx.clone();
+        tokio::spawn(async move { svc.run_push_epochs(rx).await });
+    }
+
+    // ── Node configuration reload on SIGHUP ───────────────────────────────────
+    #[cfg(unix)]
+    if let Some(path) = cli.node_config.clone() {
+        let svc = sched_info_svc.clone();
+        let node_port = cli.node_port;
+        let strict_config = cli.strict_config;
+        tokio::spawn(async move {
+            use tokio::signal::unix::{signal, SignalKind};
+            let mut sighup =
+                signal(SignalKind::hangup()).expect("failed to install SIGHUP handler");
+            while sighup.recv().await.is_some() {
+                info!("SIGHUP received — reloading {}", path.display());
+                let mut config = NodeConfigManager::new()
+                    .with_default_node_port(node_port)
+                    .with_strict_validation(strict_config);
+                if let Err(e) = config.load_from_file(&path).map_err(anyhow::Error::from) {
+                    error!("Node configuration reload failed, keeping the old one: {e:#}");
+                    continue;
+                }
+                let report = svc.reload_config(Arc::new(config)).await;
+                info!(
+                    evacuated = report.evacuated.len(),
+                    orphaned = report.orphaned.len(),
+                    "Node configuration reconciled"
+                );
+            }
+        });
+    }
 
     // ── Optional NotifyFault demo ─────────────────────────────────────────────
     //
@@ -228,6 +1833,9 @@ async fn main() {
                     node_id: "node_demo".into(),
                     task_name: "task_demo".into(),
                     fault_type: FaultType::Dmiss,
+                    severity: FaultSeverity::Critical,
+                    feasibility: None,
+                    metadata: Default::default(),
                 })
                 .await;
             match result {
@@ -237,16 +1845,25 @@ async fn main() {
         });
     }
 
-    // ── Start both servers concurrently ──────────────────────────────────────
+    // ── Start the servers concurrently ────────────────────────────────────────
+    let admin_server = Server::builder()
+        .add_service(AdminServiceServer::new(AdminServiceImpl::new(
+            sched_info_svc.clone(),
+        )))
+        .serve_with_shutdown(cli.admin_addr, admin_shutdown);
+
     let sinfo_server = Server::builder()
-        .add_service(SchedInfoServiceServer::new(sched_info_svc))
+        .add_service(
+            SchedInfoServiceServer::new(sched_info_svc)
+                .max_decoding_message_size(cli.max_request_bytes),
+        )
         .serve_with_shutdown(sinfo_addr, sinfo_shutdown);
 
     let node_server = Server::builder()
         .add_service(NodeServiceServer::new(node_svc))
         .serve_with_shutdown(node_addr, node_shutdown);
 
-    match tokio::try_join!(sinfo_server, node_server) {
+    match tokio::try_join!(sinfo_server, node_server, admin_server) {
         Ok(_) => info!("Servers stopped cleanly"),
         Err(e) => {
             error!("Server error: {e}");
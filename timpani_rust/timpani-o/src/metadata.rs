@@ -0,0 +1,273 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Opaque per-task metadata (container image, service version, trace ids).
+//!
+//! Piccolo attaches key/value pairs to a task; Timpani-O never interprets
+//! them, only bounds their size and carries them along:
+//!
+//! | Where                        | Metadata                                        |
+//! |------------------------------|-------------------------------------------------|
+//! | `TaskInfo` → `Task`          | all of it, checked by [`MetadataPolicy`]        |
+//! | `SchedTask`, `ScheduledTask` | all of it (Timpani-N resolves PIDs by it)       |
+//! | `GetClusterStatus` tasks     | all of it                                       |
+//! | faults sent to Pullpiri      | the [forwarded](MetadataPolicy::forwarded) keys |
+//! | the workload's audit line    | the forwarded keys                              |
+//! | `timpani-o report`           | `safety_level`, as the task's safety level      |
+//!
+//! Limits are checked once, at the boundary (`AddSchedInfo` and the
+//! `timpani-o schedule` workload file).  `timpani-o --metadata-max-keys`,
+//! `--metadata-max-value-len` and `--metadata-forward-key` change the
+//! defaults.
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::limits::{Limit, LimitHits};
+use crate::naming::sanitize;
+
+/// Task metadata: key → value, sorted by key.
+pub type Metadata = BTreeMap<String, String>;
+
+/// Keys per task when no limit is configured.
+pub const DEFAULT_MAX_KEYS: usize = 16;
+
+/// Longest value, in characters, when no limit is configured.
+pub const DEFAULT_MAX_VALUE_LEN: usize = 256;
+
+/// Longest key, in characters.
+pub const MAX_KEY_LEN: usize = 63;
+
+/// Keys copied into faults and the audit log when none are configured.
+pub const DEFAULT_FORWARDED_KEYS: &[&str] = &["trace_id"];
+
+// ── MetadataError ─────────────────────────────────────────────────────────────
+
+/// What is wrong with a task's metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum MetadataViolation {
+    #[error("has {count} metadata keys, more than {max}")]
+    TooManyKeys { count: usize, max: usize },
+
+    /// Empty, longer than [`MAX_KEY_LEN`], or a character outside
+    /// `[A-Za-z0-9_.-/]`.
+    #[error("has an invalid metadata key '{}'", sanitize(key))]
+    InvalidKey { key: String },
+
+    #[error("has a {len}-character value for metadata key '{key}', more than {max}")]
+    ValueTooLong { key: String, len: usize, max: usize },
+}
+
+/// Metadata rejected by a [`MetadataPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("task '{}' {violation}", sanitize(task))]
+pub struct MetadataError {
+    pub task: String,
+    pub violation: MetadataViolation,
+}
+
+// ── MetadataPolicy ────────────────────────────────────────────────────────────
+
+/// Size limits and forwarded keys (see the module docs).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataPolicy {
+    pub max_keys: usize,
+    pub max_value_len: usize,
+    /// Keys copied into faults and the audit log.
+    pub forwarded_keys: Vec<String>,
+}
+
+impl Default for MetadataPolicy {
+    fn default() -> Self {
+        Self {
+            max_keys: DEFAULT_MAX_KEYS,
+            max_value_len: DEFAULT_MAX_VALUE_LEN,
+            forwarded_keys: DEFAULT_FORWARDED_KEYS
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl MetadataPolicy {
+    pub fn with_max_keys(mut self, max: usize) -> Self {
+        self.max_keys = max;
+        self
+    }
+
+    pub fn with_max_value_len(mut self, max: usize) -> Self {
+        self.max_value_len = max;
+        self
+    }
+
+    pub fn with_forwarded_keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.forwarded_keys = keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Check that `task`'s `count` metadata keys are within the limit, before
+    /// anything copies them.  Every refusal counts as a
+    /// [`Limit::MetadataKeys`] hit in `hits`.
+    pub fn check_key_count(
+        &self,
+        task: &str,
+        count: usize,
+        hits: &LimitHits,
+    ) -> Result<(), MetadataError> {
+        if hits.exceeded(Limit::MetadataKeys, count, self.max_keys) {
+            return Err(MetadataError {
+                task: task.to_string(),
+                violation: MetadataViolation::TooManyKeys {
+                    count,
+                    max: self.max_keys,
+                },
+            });
+        }
+        Ok(())
+    }
+
+    /// Check the metadata of `task`: key count first (counted in `hits`),
+    /// then each key and value in key order.
+    pub fn check(
+        &self,
+        task: &str,
+        metadata: &Metadata,
+        hits: &LimitHits,
+    ) -> Result<(), MetadataError> {
+        let fail = |violation| {
+            Err(MetadataError {
+                task: task.to_string(),
+                violation,
+            })
+        };
+        self.check_key_count(task, metadata.len(), hits)?;
+        for (key, value) in metadata {
+            if !is_valid_key(key) {
+                return fail(MetadataViolation::InvalidKey { key: key.clone() });
+            }
+            let len = value.chars().count();
+            if len > self.max_value_len {
+                return fail(MetadataViolation::ValueTooLong {
+                    key: key.clone(),
+                    len,
+                    max: self.max_value_len,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The forwarded keys present in `metadata`.
+    pub fn forwarded(&self, metadata: &Metadata) -> Metadata {
+        self.forwarded_keys
+            .iter()
+            .filter_map(|k| metadata.get_key_value(k))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.chars().count() <= MAX_KEY_LEN
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '/'))
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn md(pairs: &[(&str, &str)]) -> Metadata {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn violation(policy: &MetadataPolicy, metadata: &Metadata) -> Option<MetadataViolation> {
+        violation_counted(policy, metadata, &LimitHits::default())
+    }
+
+    fn violation_counted(
+        policy: &MetadataPolicy,
+        metadata: &Metadata,
+        hits: &LimitHits,
+    ) -> Option<MetadataViolation> {
+        policy
+            .check("t1", metadata, hits)
+            .err()
+            .map(|e| e.violation)
+    }
+
+    #[test]
+    fn limits_are_enforced_in_order() {
+        let policy = MetadataPolicy::default()
+            .with_max_keys(2)
+            .with_max_value_len(4);
+        assert_eq!(violation(&policy, &Metadata::new()), None);
+        assert_eq!(
+            violation(&policy, &md(&[("app.io/name", "abcd"), ("x", "")])),
+            None
+        );
+        let hits = LimitHits::default();
+        assert_eq!(
+            violation_counted(&policy, &md(&[("a", ""), ("b", ""), ("c", "")]), &hits),
+            Some(MetadataViolation::TooManyKeys { count: 3, max: 2 })
+        );
+        assert_eq!(hits.get(Limit::MetadataKeys), 1);
+        // Only the key count is a limit hit.
+        assert!(violation_counted(&policy, &md(&[("image", "abcde")]), &hits).is_some());
+        assert_eq!(hits.get(Limit::MetadataKeys), 1);
+        assert_eq!(
+            violation(&policy, &md(&[("image", "abcde")])),
+            Some(MetadataViolation::ValueTooLong {
+                key: "image".into(),
+                len: 5,
+                max: 4
+            })
+        );
+        for key in ["", "a b", &"k".repeat(MAX_KEY_LEN + 1)] {
+            assert_eq!(
+                violation(&policy, &md(&[(key, "")])),
+                Some(MetadataViolation::InvalidKey { key: key.into() }),
+                "{key:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn error_names_the_task_and_key() {
+        let err = MetadataPolicy::default()
+            .with_max_value_len(1)
+            .check("t/1", &md(&[("image", "ab")]), &LimitHits::default())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "task 't%2F1' has a 2-character value for metadata key 'image', more than 1"
+        );
+    }
+
+    #[test]
+    fn only_forwarded_keys_are_selected() {
+        let metadata = md(&[("trace_id", "abc"), ("image", "img:1"), ("ver", "2")]);
+        assert_eq!(
+            MetadataPolicy::default().forwarded(&metadata),
+            md(&[("trace_id", "abc")])
+        );
+        let policy = MetadataPolicy::default().with_forwarded_keys(["ver", "missing"]);
+        assert_eq!(policy.forwarded(&metadata), md(&[("ver", "2")]));
+    }
+}
@@ -0,0 +1,270 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Naming policy for workload IDs and task names.
+//!
+//! Names end up in cgroup names, audit-log file paths and metrics labels,
+//! none of which tolerate `/`, whitespace or control characters.  Every
+//! name entering Timpani-O is checked against a [`NamingPolicy`]:
+//!
+//! | Checked by                         | Names                       |
+//! |------------------------------------|-----------------------------|
+//! | `AddSchedInfo`                     | `workload_id`, task names   |
+//! | `timpani-o schedule` workload file | `workload_id`, task names   |
+//! | [`Task::validate`]                 | the task's own two names    |
+//!
+//! The default policy is `[A-Za-z0-9_.-]{1,64}`.  `timpani-o --name-pattern`
+//! replaces it with any regex, which must match the whole name.
+//!
+//! Names that come from elsewhere and are only rendered (a node reporting a
+//! deadline miss for a task it names itself) go through [`sanitize`]
+//! instead.
+//!
+//! [`Task::validate`]: crate::task::Task::validate
+
+use std::fmt;
+
+use regex::Regex;
+use thiserror::Error;
+
+/// Longest name the default policy accepts, in characters.
+pub const MAX_NAME_LEN: usize = 64;
+
+/// Whether the default policy allows `c` anywhere in a name.
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')
+}
+
+// ── NameError ─────────────────────────────────────────────────────────────────
+
+/// Which name was checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameKind {
+    WorkloadId,
+    TaskName,
+}
+
+impl NameKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NameKind::WorkloadId => "workload_id",
+            NameKind::TaskName => "task name",
+        }
+    }
+}
+
+impl fmt::Display for NameKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What is wrong with a name.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum NameViolation {
+    #[error("is empty")]
+    Empty,
+
+    /// `position` is the 1-based character position of `ch`.
+    #[error("contains {ch:?} at position {position}")]
+    InvalidChar { ch: char, position: usize },
+
+    #[error("is {len} characters long, more than {max}")]
+    TooLong { len: usize, max: usize },
+
+    /// A custom pattern rejected the name.
+    #[error("does not match the naming pattern '{pattern}'")]
+    PatternMismatch { pattern: String },
+}
+
+/// A name rejected by a [`NamingPolicy`].
+///
+/// The name is [`sanitize`]d in the message, so the error can be logged
+/// and returned to the client as is.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{kind} '{}' {violation}", sanitize(name))]
+pub struct NameError {
+    pub kind: NameKind,
+    pub name: String,
+    pub violation: NameViolation,
+}
+
+// ── NamingPolicy ──────────────────────────────────────────────────────────────
+
+/// The rule names must follow (see the module docs).
+#[derive(Debug, Clone, Default)]
+pub struct NamingPolicy {
+    /// `None` = the default character set and length.
+    pattern: Option<(String, Regex)>,
+}
+
+impl NamingPolicy {
+    /// A policy accepting exactly the names `pattern` matches in full.
+    pub fn with_pattern(pattern: &str) -> Result<Self, regex::Error> {
+        let regex = Regex::new(&format!("^(?:{pattern})$"))?;
+        Ok(Self {
+            pattern: Some((pattern.to_string(), regex)),
+        })
+    }
+
+    /// The custom pattern, if one is set.
+    pub fn pattern(&self) -> Option<&str> {
+        self.pattern.as_ref().map(|(p, _)| p.as_str())
+    }
+
+    /// Check one name.  The default policy reports the first offending
+    /// character before the length.
+    pub fn check(&self, kind: NameKind, name: &str) -> Result<(), NameError> {
+        let violation = match &self.pattern {
+            Some((pattern, regex)) => {
+                (!regex.is_match(name)).then(|| NameViolation::PatternMismatch {
+                    pattern: pattern.clone(),
+                })
+            }
+            None => default_violation(name),
+        };
+        match violation {
+            None => Ok(()),
+            Some(violation) => Err(NameError {
+                kind,
+                name: name.to_string(),
+                violation,
+            }),
+        }
+    }
+}
+
+fn default_violation(name: &str) -> Option<NameViolation> {
+    if name.is_empty() {
+        return Some(NameViolation::Empty);
+    }
+    if let Some((i, ch)) = name.chars().enumerate().find(|(_, c)| !is_name_char(*c)) {
+        return Some(NameViolation::InvalidChar {
+            ch,
+            position: i + 1,
+        });
+    }
+    let len = name.chars().count();
+    (len > MAX_NAME_LEN).then_some(NameViolation::TooLong {
+        len,
+        max: MAX_NAME_LEN,
+    })
+}
+
+// ── Sanitising ────────────────────────────────────────────────────────────────
+
+/// `name` with every character outside `[A-Za-z0-9_.-]` percent-encoded as
+/// its UTF-8 bytes (`a/b` → `a%2Fb`), for rendering names that were never
+/// validated.
+///
+/// The result is safe in paths, metric labels and single-line logs; it
+/// is not shortened, so it may still break the length limit.
+pub fn sanitize(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        if is_name_char(c) {
+            out.push(c);
+        } else {
+            let mut buf = [0u8; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                out.push_str(&format!("%{b:02X}"));
+            }
+        }
+    }
+    out
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(policy: &NamingPolicy, name: &str) -> Option<NameViolation> {
+        policy
+            .check(NameKind::TaskName, name)
+            .err()
+            .map(|e| e.violation)
+    }
+
+    #[test]
+    fn default_policy_accepts_the_safe_character_set() {
+        let policy = NamingPolicy::default();
+        for name in [
+            "a",
+            "task_safety",
+            "wl-01.v2",
+            "A9",
+            &"x".repeat(MAX_NAME_LEN),
+        ] {
+            assert_eq!(violation(&policy, name), None, "{name}");
+        }
+    }
+
+    #[test]
+    fn default_policy_rejects_each_class_with_its_reason() {
+        let policy = NamingPolicy::default();
+        assert_eq!(violation(&policy, ""), Some(NameViolation::Empty));
+        for (name, ch, position) in [
+            ("a/b", '/', 2),
+            ("two words", ' ', 4),
+            ("tab\t", '\t', 4),
+            ("nul\0", '\0', 4),
+            ("café", 'é', 4),
+            ("ключ", 'к', 1),
+        ] {
+            assert_eq!(
+                violation(&policy, name),
+                Some(NameViolation::InvalidChar { ch, position }),
+                "{name:?}"
+            );
+        }
+        assert_eq!(
+            violation(&policy, &"x".repeat(MAX_NAME_LEN + 1)),
+            Some(NameViolation::TooLong {
+                len: MAX_NAME_LEN + 1,
+                max: MAX_NAME_LEN
+            })
+        );
+    }
+
+    #[test]
+    fn error_names_the_kind_character_and_position() {
+        let err = NamingPolicy::default()
+            .check(NameKind::WorkloadId, "wl/1")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "workload_id 'wl%2F1' contains '/' at position 3"
+        );
+    }
+
+    #[test]
+    fn custom_pattern_must_match_the_whole_name() {
+        let policy = NamingPolicy::with_pattern("[a-z]+").unwrap();
+        assert_eq!(policy.pattern(), Some("[a-z]+"));
+        assert_eq!(violation(&policy, "abc"), None);
+        for name in ["", "abc1", "1abc", "a b"] {
+            assert_eq!(
+                violation(&policy, name),
+                Some(NameViolation::PatternMismatch {
+                    pattern: "[a-z]+".into()
+                }),
+                "{name:?}"
+            );
+        }
+        assert!(NamingPolicy::with_pattern("(").is_err());
+    }
+
+    #[test]
+    fn sanitize_escapes_everything_outside_the_safe_set() {
+        assert_eq!(sanitize("task_1.a-b"), "task_1.a-b");
+        assert_eq!(sanitize("a/b c"), "a%2Fb%20c");
+        assert_eq!(sanitize("100%"), "100%25");
+        assert_eq!(sanitize("é\n"), "%C3%A9%0A");
+        assert_eq!(sanitize(""), "");
+    }
+}
@@ -0,0 +1,50 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! The types a library user needs for a scheduling call, in one `use`.
+//!
+//! ```
+//! use std::path::Path;
+//! use std::sync::Arc;
+//!
+//! use timpani_o::prelude::*;
+//!
+//! let mut nodes = NodeConfigManager::new();
+//! nodes.load_from_file(Path::new(concat!(
+//!     env!("CARGO_MANIFEST_DIR"),
+//!     "/examples/node_configurations.yaml"
+//! )))?;
+//! let tasks = vec![Task {
+//!     name: "sensor".into(),
+//!     workload_id: "plan".into(),
+//!     period_us: Micros(10_000),
+//!     runtime_us: Micros(2_000),
+//!     deadline_us: Micros(10_000),
+//!     ..Default::default()
+//! }];
+//!
+//! let mut hyper = HyperperiodManager::new();
+//! let info = hyper.calculate_hyperperiod("plan", &tasks)?;
+//! assert_eq!(info.hyperperiod_us, Micros(10_000));
+//!
+//! let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+//! let scheduler = GlobalScheduler::new(Arc::new(nodes));
+//! let map: NodeSchedMap = scheduler.schedule_with_options(tasks, &opts)?;
+//! let placed: Vec<&SchedTask> = map.values().flatten().collect();
+//! assert_eq!(placed[0].name, "sensor");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! Everything here is also reachable under its own module; the prelude only
+//! re-exports.  The error enums are `#[non_exhaustive]`, so a `match` on
+//! them needs a wildcard arm.
+
+pub use crate::config::{ConfigError, NodeConfig, NodeConfigManager};
+pub use crate::hyperperiod::{HyperperiodError, HyperperiodInfo, HyperperiodManager};
+pub use crate::scheduler::{
+    AdmissionReason, AvailCpus, CpuUtil, ErrorCode, GlobalScheduler, SchedAlgorithm,
+    ScheduleOptions, SchedulerError,
+};
+pub use crate::task::{CpuAffinity, Micros, NodeSchedMap, SchedPolicy, SchedTask, Task};
@@ -3,10 +3,64 @@ SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
 SPDX-License-Identifier: MIT
 */
 
-/// Proto-generated modules.
-///
-/// `tonic::include_proto!` expands to an `include!` of the file that
-/// prost/tonic-build wrote into `OUT_DIR` during the build script.
+//! Proto-generated modules.
+//!
+//! `tonic::include_proto!` expands to an `include!` of the file that
+//! prost/tonic-build wrote into `OUT_DIR` during the build script.
+//!
+//! # Schema revisions
+//!
+//! Pullpiri builds are not upgraded in lockstep with Timpani-O, so every
+//! `AddSchedInfo` request and response an older build sends or expects must
+//! still decode.  [`SCHEMA_REVISION`] numbers the changes to those messages
+//! (`SchedInfo`, `TaskInfo`, `SharedResource`, `TaskChain`, `Response`,
+//! `TaskPlacement`, `WorkloadSummary`, `ChainLatency` and `ChainAttempt`);
+//! fields are only ever added, never renumbered or reused:
+//!
+//! | Rev | Added                                                                  |
+//! |-----|------------------------------------------------------------------------|
+//! | 1   | `SchedInfo` 1–2, `TaskInfo` 1–10, `Response.status`                    |
+//! | 2   | `SchedInfo.algorithm`, `.cpu_utilization_threshold`                    |
+//! | 3   | `SchedInfo.seed`                                                       |
+//! | 4   | `TaskInfo.shared_resources`                                            |
+//! | 5   | `TaskInfo.target_node_policy`, `Response.placements`                   |
+//! | 6   | `SchedInfo.queue_if_full`, `.importance`, `Response.queued`            |
+//! | 7   | `Response.error_code`, `TaskPlacement.error_code`                      |
+//! | 8   | `Response.summary`                                                     |
+//! | 9   | `SchedInfo.allowed_nodes`                                              |
+//! | 10  | `TaskInfo.wcet_scaling`                                                |
+//! | 11  | `TaskInfo.metadata`                                                    |
+//! | 12  | `SchedInfo.force`                                                      |
+//! | 13  | `SchedInfo.admission_overrides`, `WorkloadSummary.admission_overrides` |
+//! | 14  | `SchedInfo.priority_class`                                             |
+//! | 15  | `SchedInfo.ttl_seconds`                                                |
+//! | 16  | `TaskInfo.preferred_location`                                          |
+//! | 17  | `TaskPlacement.runtime_margin`, `WorkloadSummary.min_runtime_margin`   |
+//! | 18  | `TaskInfo.placeholder`                                                 |
+//! | 19  | `SchedInfo.priority_ordering`, `WorkloadSummary.priority_ordering`     |
+//! | 20  | `TaskInfo.fault_sink`                                                  |
+//! | 21  | `SchedInfo.exclusive_cpus`                                             |
+//! | 22  | `SchedInfo.algorithm_chain`, `Response.chain_attempts`                 |
+//! | 23  | `TaskInfo.cache_affinity_group`, `TaskPlacement.cache_group_honoured`  |
+//! | 24  | `TaskInfo.memory_mb`                                                   |
+//! | 25  | `SchedInfo.chains`, `WorkloadSummary.chains`                           |
+//!
+//! A field missing from an older message decodes to its proto3 default, and
+//! the conversion layer gives every such default the meaning the older
+//! revision had (see
+//! [`task_from_proto`](crate::grpc::schedinfo_service::task_from_proto)).
+//!
+//! `tests/proto_compat/` keeps a request and a response encoded at every
+//! revision, and a copy of the code generated for the previous revision;
+//! `tests/proto_compat.rs` decodes the former with the current code and the
+//! current messages with the latter.  Changing one of the messages above
+//! means bumping [`SCHEMA_REVISION`], adding a row here, recording the new
+//! goldens (`TIMPANI_BLESS_PROTO_COMPAT=1 cargo test --test proto_compat`)
+//! and re-vendoring the previous revision's code.
+
+/// Revision of the `AddSchedInfo` wire schema (see the module docs).
+pub const SCHEMA_REVISION: u32 = 25;
+
 pub mod schedinfo_v1 {
     // Package name declared in schedinfo.proto is `schedinfo.v1`.
     // tonic-build turns the dots into underscores for the file name, so the
@@ -0,0 +1,169 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Task-level difference between two schedules.
+//!
+//! Tasks are matched by name within a node.  A task that moves between nodes
+//! therefore shows up as *removed* on the old node and *added* on the new one,
+//! which is exactly what each Timpani-N has to do about it.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::task::{NodeSchedMap, SchedTask};
+
+// ── NodeDiff ──────────────────────────────────────────────────────────────────
+
+/// Changes to one node's task list.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeDiff {
+    /// Tasks present only in the new schedule.
+    pub added: Vec<SchedTask>,
+    /// Names of tasks present only in the old schedule.
+    pub removed: Vec<String>,
+    /// Tasks present in both whose parameters changed (new values).
+    pub modified: Vec<SchedTask>,
+}
+
+impl NodeDiff {
+    /// Diff two task lists for the same node.
+    ///
+    /// `added` and `modified` keep the order of `new`; `removed` keeps the
+    /// order of `old`.
+    pub fn between(old: &[SchedTask], new: &[SchedTask]) -> Self {
+        let old_by_name: HashMap<&str, &SchedTask> =
+            old.iter().map(|t| (t.name.as_str(), t)).collect();
+        let new_names: BTreeSet<&str> = new.iter().map(|t| t.name.as_str()).collect();
+
+        let mut diff = NodeDiff::default();
+        for task in new {
+            match old_by_name.get(task.name.as_str()) {
+                None => diff.added.push(task.clone()),
+                Some(prev) if *prev != task => diff.modified.push(task.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .iter()
+            .filter(|t| !new_names.contains(t.name.as_str()))
+            .map(|t| t.name.clone())
+            .collect();
+        diff
+    }
+
+    /// `true` when the node's task list is unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Total number of changed entries.
+    pub fn len(&self) -> usize {
+        self.added.len() + self.removed.len() + self.modified.len()
+    }
+}
+
+// ── ScheduleDiff ──────────────────────────────────────────────────────────────
+
+/// Per-node changes between two schedules.  Unchanged nodes are omitted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScheduleDiff {
+    pub nodes: BTreeMap<String, NodeDiff>,
+}
+
+impl ScheduleDiff {
+    /// Diff every node that appears in either schedule.
+    pub fn between(old: &NodeSchedMap, new: &NodeSchedMap) -> Self {
+        let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+        let nodes = names
+            .into_iter()
+            .filter_map(|name| {
+                let diff = NodeDiff::between(
+                    old.get(name).map(Vec::as_slice).unwrap_or_default(),
+                    new.get(name).map(Vec::as_slice).unwrap_or_default(),
+                );
+                (!diff.is_empty()).then(|| (name.clone(), diff))
+            })
+            .collect();
+        Self { nodes }
+    }
+
+    /// Changes for `node`, if any.
+    pub fn node(&self, node: &str) -> Option<&NodeDiff> {
+        self.nodes.get(node)
+    }
+
+    /// `true` when no node changed.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Nanos, SchedPolicy};
+
+    fn st(name: &str, node: &str, runtime_us: u64) -> SchedTask {
+        SchedTask {
+            name: name.into(),
+            assigned_node: node.into(),
+            assigned_cpu: 0,
+            policy: SchedPolicy::Fifo,
+            priority: 50,
+            period_ns: Nanos(10_000_000),
+            runtime_ns: Nanos(runtime_us * 1_000),
+            deadline_ns: Nanos(10_000_000),
+            release_time_us: 0,
+            max_dmiss: 3,
+            shared_resources: Vec::new(),
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    #[test]
+    fn identical_schedules_have_empty_diff() {
+        let map: NodeSchedMap = [("n1".to_string(), vec![st("a", "n1", 100)])].into();
+        assert!(ScheduleDiff::between(&map, &map).is_empty());
+    }
+
+    #[test]
+    fn classifies_added_removed_and_modified() {
+        let old = vec![
+            st("keep", "n1", 100),
+            st("gone", "n1", 100),
+            st("chg", "n1", 100),
+        ];
+        let new = vec![
+            st("keep", "n1", 100),
+            st("chg", "n1", 200),
+            st("new", "n1", 100),
+        ];
+        let diff = NodeDiff::between(&old, &new);
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "new");
+        assert_eq!(diff.removed, vec!["gone".to_string()]);
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].runtime_ns, Nanos(200_000));
+        assert_eq!(diff.len(), 3);
+    }
+
+    #[test]
+    fn task_moving_nodes_is_remove_plus_add() {
+        let old: NodeSchedMap = [("n1".to_string(), vec![st("t", "n1", 100)])].into();
+        let new: NodeSchedMap = [("n2".to_string(), vec![st("t", "n2", 100)])].into();
+        let diff = ScheduleDiff::between(&old, &new);
+
+        assert_eq!(diff.node("n1").unwrap().removed, vec!["t".to_string()]);
+        assert_eq!(diff.node("n2").unwrap().added.len(), 1);
+    }
+}
@@ -0,0 +1,252 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Graphviz (DOT) rendering of a schedule's topology.
+//!
+//! [`to_dot`] draws one `cluster_<node>` subgraph per node, one record per
+//! configured CPU (labelled with its utilisation) and one box per task,
+//! with a `cpu → task` edge for every placement:
+//!
+//! | Element         | DOT id           | Shape / style                         |
+//! |-----------------|------------------|---------------------------------------|
+//! | node            | `cluster_<node>` | subgraph, `<node> (<n> CPUs, <arch>)` |
+//! | CPU             | `<node>/cpu<n>`  | `record`, `{cpu<n>\|<util>}`          |
+//! | task            | `<node>/<task>`  | `box`, period / runtime / priority    |
+//! | placement       | cpu → task       | solid                                 |
+//! | shared resource | task — task      | dashed, undirected, resource name     |
+//!
+//! Output is deterministic — nodes, CPUs and tasks are emitted in sorted
+//! order — so it can be diffed and checked in.  Render with e.g.
+//! `dot -Tsvg schedule.dot -o schedule.svg`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::config::NodeConfigManager;
+use crate::scheduler::Utilization;
+use crate::task::{NodeSchedMap, SchedTask};
+use crate::units::DurationStyle;
+
+/// Render `schedule` as a DOT digraph (see the [module docs](self)).
+///
+/// Every node in `config` gets a cluster — idle ones included — as does any
+/// node that appears only in `schedule`.  Durations are written in `style`.
+pub fn to_dot(schedule: &NodeSchedMap, config: &NodeConfigManager, style: DurationStyle) -> String {
+    let nodes: BTreeSet<&str> = config
+        .get_all_nodes()
+        .keys()
+        .chain(schedule.keys())
+        .map(String::as_str)
+        .collect();
+
+    let mut out = String::new();
+    out.push_str("digraph timpani {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [fontname=\"monospace\"];\n");
+
+    for node in nodes {
+        let mut tasks: Vec<&SchedTask> = schedule.get(node).into_iter().flatten().collect();
+        tasks.sort_by(|a, b| (a.assigned_cpu, &a.name).cmp(&(b.assigned_cpu, &b.name)));
+        write_node(&mut out, node, &tasks, config, style);
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn write_node(
+    out: &mut String,
+    node: &str,
+    tasks: &[&SchedTask],
+    config: &NodeConfigManager,
+    style: DurationStyle,
+) {
+    let mut per_cpu: BTreeMap<u32, Utilization> = BTreeMap::new();
+    let label = match config.get_node_config(node) {
+        Some(nc) => {
+            per_cpu.extend(nc.available_cpus.iter().map(|&c| (c, Utilization::ZERO)));
+            format!(
+                "{node} ({} CPUs, {})",
+                nc.available_cpus.len(),
+                nc.architecture
+            )
+        }
+        None => format!("{node} (not configured)"),
+    };
+    for t in tasks {
+        *per_cpu.entry(t.assigned_cpu).or_default() += t.exact_utilization();
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "  subgraph {} {{", quote(&format!("cluster_{node}")));
+    let _ = writeln!(out, "    label={};", quote(&label));
+    for (cpu, util) in &per_cpu {
+        let _ = writeln!(
+            out,
+            "    {} [shape=record, label=\"{{cpu{cpu}|{util}}}\"];",
+            quote(&format!("{node}/cpu{cpu}"))
+        );
+    }
+    for t in tasks {
+        let mut label = format!(
+            "{}\nperiod {} runtime {}\nprio {} ({})",
+            t.name,
+            style.format_ns(t.period_ns.as_u64()),
+            style.format_ns(t.runtime_ns.as_u64()),
+            t.priority,
+            t.exact_utilization()
+        );
+        if let Some(from) = &t.fallback_from {
+            let _ = write!(label, "\nfallback from {from}");
+        }
+        let _ = writeln!(
+            out,
+            "    {} [shape=box, label={}];",
+            task_id(node, &t.name),
+            quote(&label)
+        );
+    }
+    let _ = writeln!(out, "  }}");
+
+    for t in tasks {
+        let _ = writeln!(
+            out,
+            "  {} -> {};",
+            quote(&format!("{node}/cpu{}", t.assigned_cpu)),
+            task_id(node, &t.name)
+        );
+    }
+
+    // Tasks on this node that declare the same shared resource, pairwise.
+    let mut holders: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for t in tasks {
+        for r in &t.shared_resources {
+            holders.entry(&r.name).or_default().push(&t.name);
+        }
+    }
+    for (resource, names) in &mut holders {
+        names.sort_unstable();
+        names.dedup();
+        for (i, a) in names.iter().enumerate() {
+            for b in &names[i + 1..] {
+                let _ = writeln!(
+                    out,
+                    "  {} -> {} [dir=none, style=dashed, label={}];",
+                    task_id(node, a),
+                    task_id(node, b),
+                    quote(resource)
+                );
+            }
+        }
+    }
+}
+
+fn task_id(node: &str, task: &str) -> String {
+    quote(&format!("{node}/{task}"))
+}
+
+/// A DOT double-quoted string; `"` and `\` are escaped, newlines become `\n`.
+fn quote(s: &str) -> String {
+    let mut q = String::with_capacity(s.len() + 2);
+    q.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => q.push_str("\\\""),
+            '\\' => q.push_str("\\\\"),
+            '\n' => q.push_str("\\n"),
+            c => q.push(c),
+        }
+    }
+    q.push('"');
+    q
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+    use crate::task::{Micros, Nanos, SchedPolicy, SharedResource};
+
+    fn st(name: &str, node: &str, cpu: u32, period_us: u64, runtime_us: u64) -> SchedTask {
+        SchedTask {
+            name: name.into(),
+            assigned_node: node.into(),
+            assigned_cpu: cpu,
+            period_ns: Nanos(period_us * 1_000),
+            runtime_ns: Nanos(runtime_us * 1_000),
+            deadline_ns: Nanos(period_us * 1_000),
+            policy: SchedPolicy::Fifo,
+            priority: 50,
+            release_time_us: 0,
+            max_dmiss: 0,
+            shared_resources: vec![],
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    fn lock(name: &str) -> SharedResource {
+        SharedResource {
+            name: name.into(),
+            max_cs_us: Micros(100),
+        }
+    }
+
+    /// n1 carries three tasks, two of which share `bus`; n2 is idle and
+    /// `ghost` is scheduled but not configured.
+    fn fixture() -> (NodeSchedMap, NodeConfigManager) {
+        let mut a = st("sensor", "n1", 0, 10_000, 2_500);
+        a.shared_resources = vec![lock("bus")];
+        let mut b = st("fusion", "n1", 1, 20_000, 5_000);
+        b.shared_resources = vec![lock("bus"), lock("map")];
+        b.priority = 70;
+        let c = st("logger", "n1", 0, 100_000, 10_000);
+        let mut d = st("nav \"v2\"", "ghost", 0, 10_000, 1_000);
+        d.fallback_from = Some("n2".into());
+
+        let mut n2 = NodeConfig::default_config("n2");
+        n2.available_cpus = vec![0, 1];
+        n2.architecture = "x86_64".into();
+        let config = NodeConfigManager::from_nodes(vec![NodeConfig::default_config("n1"), n2]);
+
+        let schedule = [
+            ("n1".to_string(), vec![b, a, c]),
+            ("ghost".to_string(), vec![d]),
+        ]
+        .into();
+        (schedule, config)
+    }
+
+    #[test]
+    fn matches_checked_in_snapshot() {
+        let (schedule, config) = fixture();
+        let expected = include_str!("../../tests/fixtures/topology.dot");
+        assert_eq!(
+            to_dot(&schedule, &config, DurationStyle::default()),
+            expected
+        );
+    }
+
+    #[test]
+    fn output_is_independent_of_input_order() {
+        let (mut schedule, config) = fixture();
+        let first = to_dot(&schedule, &config, DurationStyle::default());
+        schedule.get_mut("n1").unwrap().reverse();
+        assert_eq!(to_dot(&schedule, &config, DurationStyle::default()), first);
+    }
+
+    #[test]
+    fn quoting_escapes_dot_metacharacters() {
+        assert_eq!(quote(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(quote("x\ny"), r#""x\ny""#);
+    }
+}
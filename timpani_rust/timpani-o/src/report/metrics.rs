@@ -0,0 +1,256 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Utilisation metrics accumulated over the admissions of one process.
+//!
+//! Average utilisation hides transient overload, so every admission feeds
+//! the per-CPU utilisation it leaves behind (all tenants, from
+//! [`ScheduleStats::per_cpu`]) into a [`UtilizationMetrics`]:
+//!
+//! | Metric           | Kind           | Meaning                                                 |
+//! |------------------|----------------|---------------------------------------------------------|
+//! | histogram        | 11 buckets     | CPUs per 10 % utilisation band; the last is above 100 % |
+//! | high-water marks | per-node gauge | highest per-CPU utilisation the node ever reached       |
+//! | near-misses      | counter        | admissions leaving a CPU within 5 % of the threshold    |
+//! | admissions       | counter        | admissions recorded                                     |
+//!
+//! Only CPUs carrying a task are observed.  Nothing is reset while the
+//! process runs, so the high-water marks never decrease (node configuration
+//! reloads included).
+//!
+//! [`PhaseMetrics`] does the same for where scheduling runs spend their time
+//! (see [`crate::scheduler::timing`]): per phase, the total and the slowest
+//! run.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::scheduler::{Phase, PhaseTimings};
+use crate::task::NodeSchedMap;
+
+use super::ScheduleStats;
+
+/// Histogram buckets: ten 10 % bands, then everything above 100 %.
+pub const UTILIZATION_BUCKETS: usize = 11;
+
+/// How close to the threshold (in utilisation) an admission counts as a
+/// near-miss.
+pub const NEAR_MISS_MARGIN: f64 = 0.05;
+
+/// Slack for float noise at bucket edges.
+const EDGE_EPSILON: f64 = 1e-9;
+
+/// Bucket of a per-CPU utilisation: `⌊u × 10⌋` up to 9, 10 above 100 %.
+pub fn bucket_of(utilization: f64) -> usize {
+    if utilization > 1.0 + EDGE_EPSILON {
+        return UTILIZATION_BUCKETS - 1;
+    }
+    ((utilization + EDGE_EPSILON) * 10.0)
+        .floor()
+        .clamp(0.0, (UTILIZATION_BUCKETS - 2) as f64) as usize
+}
+
+/// A copy of the metrics at one point in time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// CPUs observed per bucket (see [`bucket_of`]).
+    pub histogram: [u64; UTILIZATION_BUCKETS],
+    /// Node → highest per-CPU utilisation seen.
+    pub high_water_marks: BTreeMap<String, f64>,
+    pub near_misses: u64,
+    pub admissions: u64,
+}
+
+/// Process-lifetime utilisation metrics (see the module docs).
+#[derive(Debug, Default)]
+pub struct UtilizationMetrics {
+    inner: Mutex<MetricsSnapshot>,
+}
+
+impl UtilizationMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an admission that placed `schedule` around `occupied` (the
+    /// other tenants' placements) under utilisation threshold `threshold`.
+    pub fn record_admission(
+        &self,
+        occupied: &NodeSchedMap,
+        schedule: &NodeSchedMap,
+        threshold: f64,
+    ) {
+        let placed = ScheduleStats::per_cpu(schedule);
+        let mut cluster = ScheduleStats::per_cpu(occupied);
+        for (&cpu, &u) in &placed {
+            *cluster.entry(cpu).or_default() += u;
+        }
+
+        let mut m = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        m.admissions += 1;
+        for (&(node, _), u) in &cluster {
+            let u = u.as_f64();
+            m.histogram[bucket_of(u)] += 1;
+            let mark = m.high_water_marks.entry(node.to_string()).or_default();
+            *mark = mark.max(u);
+        }
+        let near_miss = placed
+            .keys()
+            .any(|cpu| cluster[cpu].as_f64() >= threshold - NEAR_MISS_MARGIN);
+        if near_miss {
+            m.near_misses += 1;
+        }
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Highest per-CPU utilisation `node` has reached, if it ever had a task.
+    pub fn high_water_mark(&self, node: &str) -> Option<f64> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .high_water_marks
+            .get(node)
+            .copied()
+    }
+}
+
+// ── Phase timings ─────────────────────────────────────────────────────────────
+
+/// A copy of the phase metrics at one point in time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhaseMetricsSnapshot {
+    /// Scheduling runs recorded.
+    pub runs: u64,
+    /// Phase → time spent in it over every run.
+    pub total: BTreeMap<Phase, Duration>,
+    /// Phase → its longest single run.
+    pub max: BTreeMap<Phase, Duration>,
+}
+
+/// Process-lifetime scheduling phase timings (see the module docs).
+#[derive(Debug, Default)]
+pub struct PhaseMetrics {
+    inner: Mutex<PhaseMetricsSnapshot>,
+}
+
+impl PhaseMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, timings: &PhaseTimings) {
+        let mut m = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        m.runs += 1;
+        for (phase, elapsed) in timings.iter() {
+            *m.total.entry(phase).or_default() += elapsed;
+            let max = m.max.entry(phase).or_default();
+            *max = (*max).max(elapsed);
+        }
+    }
+
+    pub fn snapshot(&self) -> PhaseMetricsSnapshot {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Nanos, SchedPolicy, SchedTask};
+
+    fn st(name: &str, cpu: u32, runtime_us: u64) -> SchedTask {
+        SchedTask {
+            name: name.into(),
+            assigned_node: "n1".into(),
+            assigned_cpu: cpu,
+            period_ns: Nanos(10_000_000),
+            runtime_ns: Nanos(runtime_us * 1_000),
+            deadline_ns: Nanos(10_000_000),
+            policy: SchedPolicy::Fifo,
+            priority: 50,
+            release_time_us: 0,
+            max_dmiss: 0,
+            shared_resources: vec![],
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    fn on(node: &str, tasks: Vec<SchedTask>) -> NodeSchedMap {
+        [(node.to_string(), tasks)].into()
+    }
+
+    #[test]
+    fn buckets_are_ten_percent_wide() {
+        assert_eq!(bucket_of(0.0), 0);
+        assert_eq!(bucket_of(0.099), 0);
+        assert_eq!(bucket_of(0.1), 1);
+        assert_eq!(bucket_of(0.3 + 0.6), 9);
+        assert_eq!(bucket_of(1.0), 9);
+        assert_eq!(bucket_of(1.2), 10);
+    }
+
+    #[test]
+    fn increasing_load_fills_buckets_and_raises_the_high_water_mark() {
+        let metrics = UtilizationMetrics::new();
+        let mut marks = Vec::new();
+        let mut occupied = NodeSchedMap::new();
+        // Each run adds 20 % to CPU 0 of n1 and 10 % to CPU 1 of n2.
+        for run in 0..4 {
+            let schedule: NodeSchedMap = [
+                ("n1".to_string(), vec![st(&format!("a{run}"), 0, 2_000)]),
+                ("n2".to_string(), vec![st(&format!("b{run}"), 1, 1_000)]),
+            ]
+            .into();
+            metrics.record_admission(&occupied, &schedule, 0.9);
+            marks.push(metrics.high_water_mark("n1").unwrap());
+            for (node, tasks) in schedule {
+                occupied.entry(node).or_default().extend(tasks);
+            }
+        }
+
+        let m = metrics.snapshot();
+        assert_eq!(m.admissions, 4);
+        // n1/cpu0 at 20, 40, 60, 80 %; n2/cpu1 at 10, 20, 30, 40 %.
+        assert_eq!(m.histogram, [0, 1, 2, 1, 2, 0, 1, 0, 1, 0, 0]);
+        assert_eq!(marks, [0.2, 0.4, 0.6, 0.8]);
+        assert!((m.high_water_marks["n2"] - 0.4).abs() < 1e-12);
+        // 80 % is not within 5 % of 90 %.
+        assert_eq!(m.near_misses, 0);
+
+        // A lighter run afterwards leaves the mark where it was.
+        metrics.record_admission(
+            &NodeSchedMap::new(),
+            &on("n1", vec![st("c", 0, 1_000)]),
+            0.9,
+        );
+        assert!((metrics.high_water_mark("n1").unwrap() - 0.8).abs() < 1e-12);
+        assert_eq!(metrics.high_water_mark("n3"), None);
+    }
+
+    #[test]
+    fn near_misses_count_only_cpus_the_workload_uses() {
+        let metrics = UtilizationMetrics::new();
+        let busy = on("n1", vec![st("busy", 0, 8_800)]);
+        // The new task lands on another CPU: not a near-miss.
+        metrics.record_admission(&busy, &on("n1", vec![st("x", 1, 1_000)]), 0.9);
+        assert_eq!(metrics.snapshot().near_misses, 0);
+        // 80 % + 6 % = 86 % ≥ 85 %.
+        let base = on("n1", vec![st("base", 0, 8_000)]);
+        metrics.record_admission(&base, &on("n1", vec![st("y", 0, 600)]), 0.9);
+        assert_eq!(metrics.snapshot().near_misses, 1);
+    }
+}
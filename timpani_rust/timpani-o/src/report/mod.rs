@@ -0,0 +1,30 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Reports derived from scheduling results.
+//!
+//! Nothing here feeds back into placement — these types describe a
+//! `NodeSchedMap` (or the change between two of them) for delivery to nodes,
+//! logs and operators.
+
+pub mod diff;
+pub mod dot;
+pub mod metrics;
+pub mod shadow;
+pub mod signoff;
+#[cfg(feature = "grpc")]
+pub mod status;
+#[cfg(feature = "grpc")]
+pub mod summary;
+
+pub use diff::{NodeDiff, ScheduleDiff};
+pub use dot::to_dot;
+pub use metrics::{MetricsSnapshot, UtilizationMetrics};
+pub use shadow::{ScheduleStats, ShadowComparison, ShadowLog, ShadowOutcome};
+pub use signoff::{signoff_report, ReportFormat};
+#[cfg(feature = "grpc")]
+pub use status::OutputFormat;
+#[cfg(feature = "grpc")]
+pub use summary::{chain_latencies, render_summary, workload_summary, WorkloadSummary};
@@ -0,0 +1,352 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Shadow scheduling: what a secondary algorithm would have done.
+//!
+//! With shadow algorithms configured, every admitted workload is scheduled
+//! again by each shadow against the same snapshot (the same tasks around
+//! the same occupancy) once the primary result has been stored.  The shadow
+//! schedule is compared with the primary and thrown away — it is never
+//! stored or sent to a node.  Each comparison records:
+//!
+//! | Field                  | Meaning                                          |
+//! |------------------------|--------------------------------------------------|
+//! | `nodes_used`           | nodes that received at least one task            |
+//! | `peak_cpu_utilization` | highest per-CPU utilisation of the workload      |
+//! | `warning_count`        | feasibility warnings (`check_schedule`)          |
+//! | `phase_timings`        | where the run spent its time ([`timing`])        |
+//! | `quality`              | CPUs used against the lower bound ([`quality`])  |
+//! | `diff`                 | [`ScheduleDiff`] from the primary to the shadow  |
+//! | `tasks_differing`      | tasks the shadow places on another node or CPU   |
+//!
+//! Comparisons go to the `audit` tracing target and into a bounded
+//! [`ShadowLog`] kept by the service.
+//!
+//! [`timing`]: crate::scheduler::timing
+//! [`quality`]: crate::scheduler::quality
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::Mutex;
+
+use tracing::info;
+
+use crate::scheduler::feasibility::check_schedule;
+use crate::scheduler::priority::PriorityOrdering;
+use crate::scheduler::{
+    evaluate, ErrorCode, Phase, PhaseTimings, QualityConfig, QualityMetrics, SchedAlgorithm,
+    ScheduleOptions, SchedulerError, Utilization,
+};
+use crate::task::NodeSchedMap;
+
+use super::ScheduleDiff;
+
+/// Comparisons kept when no capacity is configured.
+pub const DEFAULT_SHADOW_LOG_CAPACITY: usize = 64;
+
+// ── ScheduleStats ─────────────────────────────────────────────────────────────
+
+/// Headline numbers compared between primary and shadow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduleStats {
+    pub nodes_used: usize,
+    pub peak_cpu_utilization: f64,
+    pub warning_count: usize,
+    /// Zero unless set with [`with_phase_timings`](Self::with_phase_timings).
+    pub phase_timings: PhaseTimings,
+    pub quality: QualityMetrics,
+}
+
+impl ScheduleStats {
+    /// Stats for `schedule` as run with `opts`; warnings use its epsilon
+    /// like admission does, and the rate-monotonic bound whatever the
+    /// workloads asked for.
+    pub fn of(schedule: &NodeSchedMap, opts: &ScheduleOptions) -> Self {
+        let per_cpu = Self::per_cpu(schedule);
+        let epsilon = opts.utilization_epsilon;
+        Self {
+            nodes_used: schedule.values().filter(|t| !t.is_empty()).count(),
+            peak_cpu_utilization: per_cpu.values().copied().max().unwrap_or_default().as_f64(),
+            warning_count: check_schedule(schedule, epsilon, PriorityOrdering::default()).len(),
+            phase_timings: PhaseTimings::default(),
+            quality: evaluate(schedule, &QualityConfig::from_options(opts)),
+        }
+    }
+
+    /// Record how long the run that produced the schedule took.
+    pub fn with_phase_timings(mut self, timings: PhaseTimings) -> Self {
+        self.phase_timings = timings;
+        self
+    }
+
+    /// Exact utilisation of every `(node, cpu)` with a task in `schedule`.
+    pub fn per_cpu(schedule: &NodeSchedMap) -> BTreeMap<(&str, u32), Utilization> {
+        let mut per_cpu: BTreeMap<(&str, u32), Utilization> = BTreeMap::new();
+        for (node, tasks) in schedule {
+            for t in tasks {
+                *per_cpu.entry((node, t.assigned_cpu)).or_default() += t.exact_utilization();
+            }
+        }
+        per_cpu
+    }
+}
+
+// ── ShadowComparison ──────────────────────────────────────────────────────────
+
+/// What the shadow produced.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShadowOutcome {
+    Placed {
+        stats: ScheduleStats,
+        diff: ScheduleDiff,
+        tasks_differing: usize,
+    },
+    /// The shadow could not place the workload at all.
+    Failed { code: ErrorCode, error: String },
+}
+
+impl ShadowOutcome {
+    /// Compare a shadow run with the `primary` schedule.
+    pub fn compare(
+        primary: &NodeSchedMap,
+        shadow: Result<NodeSchedMap, SchedulerError>,
+        opts: &ScheduleOptions,
+    ) -> Self {
+        let shadow = match shadow {
+            Ok(s) => s,
+            Err(e) => {
+                return ShadowOutcome::Failed {
+                    code: e.code(),
+                    error: e.to_string(),
+                }
+            }
+        };
+        let diff = ScheduleDiff::between(primary, &shadow);
+        let tasks_differing = diff
+            .nodes
+            .values()
+            .flat_map(|d| d.added.iter().chain(&d.modified))
+            .map(|t| t.name.as_str())
+            .collect::<BTreeSet<_>>()
+            .len();
+        ShadowOutcome::Placed {
+            stats: ScheduleStats::of(&shadow, opts),
+            diff,
+            tasks_differing,
+        }
+    }
+}
+
+/// One primary-versus-shadow comparison (see the module docs).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowComparison {
+    pub tenant: String,
+    pub workload_id: String,
+    pub primary: SchedAlgorithm,
+    pub shadow: SchedAlgorithm,
+    pub primary_stats: ScheduleStats,
+    pub outcome: ShadowOutcome,
+}
+
+impl ShadowComparison {
+    /// Record on the `audit` tracing target.
+    pub fn log(&self) {
+        let p = &self.primary_stats;
+        match &self.outcome {
+            ShadowOutcome::Placed {
+                stats,
+                diff,
+                tasks_differing,
+            } => info!(
+                target: "audit",
+                tenant              = %self.tenant,
+                workload_id         = %self.workload_id,
+                primary             = %self.primary,
+                shadow              = %self.shadow,
+                primary_nodes       = p.nodes_used,
+                shadow_nodes        = stats.nodes_used,
+                primary_peak_pct    = p.peak_cpu_utilization * 100.0,
+                shadow_peak_pct     = stats.peak_cpu_utilization * 100.0,
+                primary_warnings    = p.warning_count,
+                shadow_warnings     = stats.warning_count,
+                primary_algorithm_us= p.phase_timings.get(Phase::Algorithm).as_micros() as u64,
+                shadow_algorithm_us = stats.phase_timings.get(Phase::Algorithm).as_micros() as u64,
+                primary_cpus        = p.quality.cpus_used,
+                shadow_cpus         = stats.quality.cpus_used,
+                cpus_lower_bound    = p.quality.lower_bound,
+                primary_variance    = p.quality.utilization_variance,
+                shadow_variance     = stats.quality.utilization_variance,
+                primary_near_threshold = p.quality.near_threshold,
+                shadow_near_threshold  = stats.quality.near_threshold,
+                nodes_differing     = diff.nodes.len(),
+                tasks_differing,
+                "shadow comparison"
+            ),
+            ShadowOutcome::Failed { code, error } => info!(
+                target: "audit",
+                tenant      = %self.tenant,
+                workload_id = %self.workload_id,
+                primary     = %self.primary,
+                shadow      = %self.shadow,
+                code        = %code,
+                error       = %error,
+                "shadow comparison: shadow failed"
+            ),
+        }
+    }
+}
+
+// ── ShadowLog ─────────────────────────────────────────────────────────────────
+
+/// Most recent comparisons, oldest dropped first.
+#[derive(Debug)]
+pub struct ShadowLog {
+    entries: Mutex<VecDeque<ShadowComparison>>,
+    capacity: usize,
+}
+
+impl Default for ShadowLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_SHADOW_LOG_CAPACITY)
+    }
+}
+
+impl ShadowLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::default(),
+            capacity,
+        }
+    }
+
+    pub fn push(&self, comparison: ShadowComparison) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        if self.capacity > 0 {
+            entries.push_back(comparison);
+        }
+    }
+
+    /// Kept comparisons, oldest first.
+    pub fn snapshot(&self) -> Vec<ShadowComparison> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().cloned().collect()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Nanos, SchedPolicy, SchedTask};
+
+    fn st(name: &str, node: &str, cpu: u32, runtime_us: u64) -> SchedTask {
+        SchedTask {
+            name: name.into(),
+            assigned_node: node.into(),
+            assigned_cpu: cpu,
+            period_ns: Nanos(10_000_000),
+            runtime_ns: Nanos(runtime_us * 1_000),
+            deadline_ns: Nanos(10_000_000),
+            policy: SchedPolicy::Fifo,
+            priority: 50,
+            release_time_us: 0,
+            max_dmiss: 0,
+            shared_resources: vec![],
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    #[test]
+    fn packed_versus_spread() {
+        // Primary packs both tasks on n1/cpu0; the shadow spreads them.
+        let primary: NodeSchedMap = [(
+            "n1".to_string(),
+            vec![st("a", "n1", 0, 3_000), st("b", "n1", 0, 2_000)],
+        )]
+        .into();
+        let shadow: NodeSchedMap = [
+            ("n1".to_string(), vec![st("a", "n1", 0, 3_000)]),
+            ("n2".to_string(), vec![st("b", "n2", 1, 2_000)]),
+        ]
+        .into();
+
+        let opts = ScheduleOptions::default().with_utilization_epsilon(0.0);
+        let p = ScheduleStats::of(&primary, &opts);
+        assert_eq!(p.nodes_used, 1);
+        assert!((p.peak_cpu_utilization - 0.5).abs() < 1e-12);
+        assert_eq!((p.quality.cpus_used, p.quality.lower_bound), (1, 1));
+
+        match ShadowOutcome::compare(&primary, Ok(shadow), &opts) {
+            ShadowOutcome::Placed {
+                stats,
+                diff,
+                tasks_differing,
+            } => {
+                assert_eq!(stats.nodes_used, 2);
+                assert!((stats.peak_cpu_utilization - 0.3).abs() < 1e-12);
+                assert_eq!(stats.warning_count, 0);
+                assert_eq!(stats.quality.excess_cpus(), 1);
+                assert_eq!(tasks_differing, 1);
+                assert_eq!(diff.nodes["n1"].removed, ["b"]);
+                assert_eq!(diff.nodes["n2"].added[0].name, "b");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn identical_schedules_do_not_differ() {
+        let primary: NodeSchedMap = [("n1".to_string(), vec![st("a", "n1", 1, 1_000)])].into();
+        let opts = ScheduleOptions::default();
+        let outcome = ShadowOutcome::compare(&primary, Ok(primary.clone()), &opts);
+        assert!(matches!(
+            outcome,
+            ShadowOutcome::Placed { tasks_differing: 0, ref diff, .. } if diff.nodes.is_empty()
+        ));
+    }
+
+    #[test]
+    fn failed_shadow_keeps_its_error_code() {
+        let opts = ScheduleOptions::default();
+        let outcome =
+            ShadowOutcome::compare(&NodeSchedMap::new(), Err(SchedulerError::NoTasks), &opts);
+        assert!(matches!(
+            outcome,
+            ShadowOutcome::Failed {
+                code: ErrorCode::NoTasks,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn log_is_bounded() {
+        let log = ShadowLog::new(2);
+        for id in ["w1", "w2", "w3"] {
+            log.push(ShadowComparison {
+                tenant: "t".into(),
+                workload_id: id.into(),
+                primary: SchedAlgorithm::TargetNodePriority,
+                shadow: SchedAlgorithm::LeastLoaded,
+                primary_stats: ScheduleStats::of(&NodeSchedMap::new(), &ScheduleOptions::default()),
+                outcome: ShadowOutcome::Failed {
+                    code: ErrorCode::NoTasks,
+                    error: String::new(),
+                },
+            });
+        }
+        let ids: Vec<String> = log.snapshot().into_iter().map(|c| c.workload_id).collect();
+        assert_eq!(ids, ["w2", "w3"]);
+    }
+}
@@ -0,0 +1,822 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Cluster-wide sign-off report of a finished schedule.
+//!
+//! [`signoff_report`] renders one self-contained document — Markdown or
+//! HTML — from a saved schedule (`timpani-o schedule --save`) and the node
+//! configuration it was made for.  It is what `timpani-o report` writes:
+//!
+//! | Section       | Contents                                                  |
+//! |---------------|-----------------------------------------------------------|
+//! | Summary       | verdict, config and schedule fingerprints, counts, options |
+//! | Utilisation   | one bar per CPU: ASCII in Markdown, SVG in HTML            |
+//! | Feasibility   | per CPU: load, Liu & Layland bound, method used, result   |
+//! | Nodes         | per node, a task table by CPU with each task's WCRT       |
+//! | Hyperperiods  | per workload: tasks, distinct periods, their LCM          |
+//! | Constraints   | pinned CPUs, colocation, safety levels, fallbacks         |
+//!
+//! A CPU is decided by the Liu & Layland bound of [`cpu_loads`] when its
+//! load is within it and none of its tasks can be blocked; otherwise by
+//! response time analysis ([`analyse_schedule`]).  The verdict passes when
+//! every task's worst-case response time is within its deadline.
+//!
+//! Safety levels are the tasks' [`SAFETY_LEVEL_KEY`] metadata; tasks
+//! without one are `unrated`.
+//!
+//! Output is deterministic — no timestamps, everything in sorted order —
+//! so the artifacts of two releases can be diffed.  The fingerprints are
+//! 64-bit FNV-1a: the config one is
+//! [`NodeConfigManager::config_fingerprint`], the schedule one covers every
+//! placement and timing field of every task (see [`schedule_fingerprint`]).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::config::{fnv1a, NodeConfigManager};
+use crate::hyperperiod::math::lcm_of_slice;
+use crate::scheduler::feasibility::cpu_loads;
+use crate::scheduler::rta::{analyse_schedule, RtaResult};
+use crate::scheduler::{PriorityOrdering, ScheduleOptions};
+use crate::task::{NodeSchedMap, SchedPolicy, SchedTask};
+use crate::units::DurationStyle;
+
+/// Metadata key holding a task's safety level (e.g. `ASIL-B`).
+pub const SAFETY_LEVEL_KEY: &str = "safety_level";
+
+/// Width of a full utilisation bar, in characters or SVG units / 10.
+const BAR_WIDTH: usize = 40;
+
+/// Output format of `timpani-o report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum ReportFormat {
+    /// GitHub-flavoured Markdown, charts as text.
+    #[default]
+    Markdown,
+    /// One HTML page, charts as inline SVG.
+    Html,
+}
+
+impl ReportFormat {
+    /// `Html` for a `.html` or `.htm` path (any case), `Markdown` for any
+    /// other.
+    pub fn from_path(path: &Path) -> Self {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm") {
+            ReportFormat::Html
+        } else {
+            ReportFormat::Markdown
+        }
+    }
+}
+
+/// Render the sign-off report of `schedule` on `config` (see the
+/// [module docs](self)).  `opts` supplies the priority ordering, the
+/// utilisation threshold and the Liu & Layland epsilon; durations are
+/// written in `style`.
+pub fn signoff_report(
+    schedule: &NodeSchedMap,
+    config: &NodeConfigManager,
+    opts: &ScheduleOptions,
+    format: ReportFormat,
+    style: DurationStyle,
+) -> String {
+    let doc = Report::new(schedule, config, opts, style).blocks();
+    match format {
+        ReportFormat::Markdown => to_markdown(&doc),
+        ReportFormat::Html => to_html(&doc),
+    }
+}
+
+/// Hash of every task's node, CPU, name, workload, policy, priority,
+/// timing, `max_dmiss`, exclusivity and placeholder flag, by node, CPU
+/// and name: equal for two schedules that place the same tasks alike.
+pub fn schedule_fingerprint(schedule: &NodeSchedMap) -> u64 {
+    let tasks = sorted_tasks(schedule);
+    let fields: Vec<Vec<u8>> = tasks
+        .iter()
+        .flat_map(|t| {
+            [
+                t.assigned_node.as_bytes().to_vec(),
+                t.assigned_cpu.to_le_bytes().to_vec(),
+                t.name.as_bytes().to_vec(),
+                t.workload_id.as_bytes().to_vec(),
+                t.policy.to_linux_int().to_le_bytes().to_vec(),
+                t.priority.to_le_bytes().to_vec(),
+                t.period_ns.as_u64().to_le_bytes().to_vec(),
+                t.runtime_ns.as_u64().to_le_bytes().to_vec(),
+                t.deadline_ns.as_u64().to_le_bytes().to_vec(),
+                t.release_time_us.to_le_bytes().to_vec(),
+                t.max_dmiss.to_le_bytes().to_vec(),
+                vec![u8::from(t.exclusive_cpus), u8::from(t.placeholder)],
+            ]
+        })
+        .collect();
+    fnv1a(fields.iter().map(Vec::as_slice))
+}
+
+/// Every task, by node, CPU, then name.
+fn sorted_tasks(schedule: &NodeSchedMap) -> Vec<&SchedTask> {
+    let mut tasks: Vec<&SchedTask> = schedule.values().flatten().collect();
+    tasks.sort_by(|a, b| {
+        (&a.assigned_node, a.assigned_cpu, &a.name).cmp(&(
+            &b.assigned_node,
+            b.assigned_cpu,
+            &b.name,
+        ))
+    });
+    tasks
+}
+
+// ── Document model ────────────────────────────────────────────────────────────
+
+/// Format-neutral building blocks of the report.
+#[derive(Debug)]
+enum Block {
+    Heading(u8, String),
+    Para(String),
+    Table {
+        header: Vec<&'static str>,
+        rows: Vec<Vec<String>>,
+    },
+    Bars(Vec<Bar>),
+}
+
+#[derive(Debug)]
+struct Bar {
+    label: String,
+    /// Utilisation, `0.0..`; drawn capped at 1.
+    value: f64,
+    note: String,
+}
+
+/// Everything the blocks are built from.
+struct Report<'a> {
+    config: &'a NodeConfigManager,
+    opts: &'a ScheduleOptions,
+    tasks: Vec<&'a SchedTask>,
+    /// Configured and scheduled nodes.
+    nodes: BTreeSet<&'a str>,
+    /// WCRT results by `(node, task)`.
+    rta: BTreeMap<(String, String), RtaResult>,
+    schedule: &'a NodeSchedMap,
+    style: DurationStyle,
+}
+
+impl<'a> Report<'a> {
+    fn new(
+        schedule: &'a NodeSchedMap,
+        config: &'a NodeConfigManager,
+        opts: &'a ScheduleOptions,
+        style: DurationStyle,
+    ) -> Self {
+        let nodes = config
+            .get_all_nodes()
+            .keys()
+            .chain(schedule.keys())
+            .map(String::as_str)
+            .collect();
+        let rta = analyse_schedule(schedule)
+            .results
+            .into_iter()
+            .map(|r| ((r.node.clone(), r.task.clone()), r))
+            .collect();
+        Self {
+            config,
+            opts,
+            tasks: sorted_tasks(schedule),
+            nodes,
+            rta,
+            schedule,
+            style,
+        }
+    }
+
+    fn blocks(&self) -> Vec<Block> {
+        let mut doc = vec![Block::Heading(1, "Timpani-O schedule sign-off".into())];
+        self.summary(&mut doc);
+        self.utilisation(&mut doc);
+        self.feasibility(&mut doc);
+        self.nodes(&mut doc);
+        self.hyperperiods(&mut doc);
+        self.constraints(&mut doc);
+        doc
+    }
+
+    fn summary(&self, doc: &mut Vec<Block>) {
+        let misses = self.rta.values().filter(|r| !r.schedulable()).count();
+        let verdict = if misses == 0 {
+            "PASS — every task meets its deadline".to_string()
+        } else {
+            format!("FAIL — {misses} task(s) can miss their deadline")
+        };
+        let workloads: BTreeSet<&str> = self.tasks.iter().map(|t| t.workload_id.as_str()).collect();
+        let rows = [
+            ("Verdict", verdict),
+            (
+                "Config fingerprint",
+                format!("{:016x}", self.config.config_fingerprint()),
+            ),
+            (
+                "Schedule fingerprint",
+                format!("{:016x}", schedule_fingerprint(self.schedule)),
+            ),
+            (
+                "Nodes",
+                format!(
+                    "{} configured, {} with tasks",
+                    self.config.get_all_nodes().len(),
+                    self.schedule.values().filter(|t| !t.is_empty()).count()
+                ),
+            ),
+            ("Workloads", workloads.len().to_string()),
+            ("Tasks", self.tasks.len().to_string()),
+            ("Priority ordering", self.opts.priority_ordering.to_string()),
+            (
+                "Utilisation threshold",
+                percent(self.opts.cpu_utilization_threshold),
+            ),
+        ];
+        doc.push(Block::Heading(2, "Summary".into()));
+        doc.push(Block::Table {
+            header: vec!["Item", "Value"],
+            rows: rows
+                .into_iter()
+                .map(|(k, v)| vec![k.to_string(), v])
+                .collect(),
+        });
+    }
+
+    /// Utilisation of every configured or used CPU, by node then CPU.
+    fn cpu_utilisation(&self) -> BTreeMap<(&'a str, u32), f64> {
+        let mut per_cpu = BTreeMap::new();
+        for &node in &self.nodes {
+            if let Some(nc) = self.config.get_node_config(node) {
+                per_cpu.extend(nc.available_cpus.iter().map(|&c| ((node, c), 0.0)));
+            }
+        }
+        for t in &self.tasks {
+            *per_cpu
+                .entry((t.assigned_node.as_str(), t.assigned_cpu))
+                .or_default() += t.utilization();
+        }
+        per_cpu
+    }
+
+    fn utilisation(&self, doc: &mut Vec<Block>) {
+        let threshold = self.opts.cpu_utilization_threshold;
+        let bars = self
+            .cpu_utilisation()
+            .into_iter()
+            .map(|((node, cpu), value)| Bar {
+                label: format!("{node}/cpu{cpu}"),
+                value,
+                note: if value > threshold {
+                    format!("{} over threshold", percent(value))
+                } else {
+                    percent(value)
+                },
+            })
+            .collect::<Vec<_>>();
+        doc.push(Block::Heading(2, "Utilisation".into()));
+        if bars.is_empty() {
+            doc.push(Block::Para("No CPUs.".into()));
+        } else {
+            doc.push(Block::Bars(bars));
+        }
+    }
+
+    fn feasibility(&self, doc: &mut Vec<Block>) {
+        let ordering = self.opts.priority_ordering;
+        let bound_method = match ordering {
+            PriorityOrdering::RateMonotonic => "Liu & Layland (utilisation)",
+            PriorityOrdering::DeadlineMonotonic => "Liu & Layland (density)",
+        };
+        let mut rows = Vec::new();
+        for c in cpu_loads(self.schedule, ordering) {
+            let results: Vec<&RtaResult> = self
+                .rta
+                .values()
+                .filter(|r| r.node == c.node && r.cpu == c.cpu)
+                .collect();
+            let blocked = results.iter().any(|r| !r.blocking.is_zero());
+            let (method, result) = if c.load <= c.bound + self.opts.utilization_epsilon && !blocked
+            {
+                (bound_method, "schedulable".to_string())
+            } else {
+                let misses: Vec<&str> = results
+                    .iter()
+                    .filter(|r| !r.schedulable())
+                    .map(|r| r.task.as_str())
+                    .collect();
+                let result = if misses.is_empty() {
+                    "schedulable".to_string()
+                } else {
+                    format!("misses: {}", misses.join(", "))
+                };
+                ("response time analysis", result)
+            };
+            rows.push(vec![
+                c.node,
+                c.cpu.to_string(),
+                c.task_count.to_string(),
+                percent(c.load),
+                percent(c.bound),
+                method.to_string(),
+                result,
+            ]);
+        }
+        doc.push(Block::Heading(2, "Feasibility".into()));
+        if rows.is_empty() {
+            doc.push(Block::Para("No periodic tasks.".into()));
+        } else {
+            doc.push(Block::Table {
+                header: vec!["Node", "CPU", "Tasks", "Load", "Bound", "Method", "Result"],
+                rows,
+            });
+        }
+    }
+
+    fn nodes(&self, doc: &mut Vec<Block>) {
+        doc.push(Block::Heading(2, "Nodes".into()));
+        for &node in &self.nodes {
+            let tasks: Vec<&SchedTask> = self
+                .tasks
+                .iter()
+                .copied()
+                .filter(|t| t.assigned_node == node)
+                .collect();
+            let mut idle: BTreeSet<u32> = BTreeSet::new();
+            let title = match self.config.get_node_config(node) {
+                Some(nc) => {
+                    idle.extend(&nc.available_cpus);
+                    format!(
+                        "{node} ({} CPUs, {})",
+                        nc.available_cpus.len(),
+                        nc.architecture
+                    )
+                }
+                None => format!("{node} (not configured)"),
+            };
+            for t in &tasks {
+                idle.remove(&t.assigned_cpu);
+            }
+            doc.push(Block::Heading(3, title));
+            if tasks.is_empty() {
+                doc.push(Block::Para("No tasks.".into()));
+            } else {
+                doc.push(Block::Table {
+                    header: vec![
+                        "CPU", "Task", "Workload", "Policy", "Priority", "Period", "Runtime",
+                        "Deadline", "Util", "WCRT",
+                    ],
+                    rows: tasks.iter().map(|t| self.task_row(t)).collect(),
+                });
+            }
+            if !tasks.is_empty() && !idle.is_empty() {
+                let idle: Vec<String> = idle.iter().map(u32::to_string).collect();
+                doc.push(Block::Para(format!("Idle CPUs: {}.", idle.join(", "))));
+            }
+        }
+    }
+
+    fn task_row(&self, t: &SchedTask) -> Vec<String> {
+        let wcrt = match self.rta.get(&(t.assigned_node.clone(), t.name.clone())) {
+            Some(RtaResult {
+                response: Some(r), ..
+            }) => self.style.format_ns(r.as_u64()),
+            Some(_) => "miss".into(),
+            None => "—".into(),
+        };
+        vec![
+            t.assigned_cpu.to_string(),
+            t.name.clone(),
+            workload(&t.workload_id).to_string(),
+            policy(t.policy).to_string(),
+            t.priority.to_string(),
+            self.style.format_ns(t.period_ns.as_u64()),
+            self.style.format_ns(t.runtime_ns.as_u64()),
+            self.style.format_ns(t.deadline_ns.as_u64()),
+            percent(t.utilization()),
+            wcrt,
+        ]
+    }
+
+    fn hyperperiods(&self, doc: &mut Vec<Block>) {
+        let mut by_workload: BTreeMap<&str, (usize, BTreeSet<u64>)> = BTreeMap::new();
+        for t in &self.tasks {
+            let e = by_workload.entry(&t.workload_id).or_default();
+            e.0 += 1;
+            if !t.period_ns.is_zero() {
+                e.1.insert(t.period_ns.as_u64());
+            }
+        }
+        let rows = by_workload
+            .into_iter()
+            .map(|(wl, (count, periods))| {
+                let periods: Vec<u64> = periods.into_iter().collect();
+                let hyperperiod = match lcm_of_slice(&periods) {
+                    Ok(0) => "—".to_string(),
+                    Ok(h) => self.style.format_ns(h),
+                    Err(_) => "overflow".to_string(),
+                };
+                vec![
+                    workload(wl).to_string(),
+                    count.to_string(),
+                    periods.len().to_string(),
+                    hyperperiod,
+                ]
+            })
+            .collect::<Vec<_>>();
+        doc.push(Block::Heading(2, "Hyperperiods".into()));
+        table_or_none(
+            doc,
+            vec!["Workload", "Tasks", "Periods", "Hyperperiod"],
+            rows,
+        );
+    }
+
+    fn constraints(&self, doc: &mut Vec<Block>) {
+        doc.push(Block::Heading(2, "Constraints".into()));
+
+        // Pinned: CPUs a workload holds exclusively.
+        let mut pinned: BTreeMap<(&str, u32), BTreeSet<&str>> = BTreeMap::new();
+        for t in self.tasks.iter().filter(|t| t.exclusive_cpus) {
+            pinned
+                .entry((&t.assigned_node, t.assigned_cpu))
+                .or_default()
+                .insert(workload(&t.workload_id));
+        }
+        let rows = pinned
+            .into_iter()
+            .map(|((node, cpu), workloads)| {
+                vec![node.to_string(), cpu.to_string(), join(workloads)]
+            })
+            .collect();
+        doc.push(Block::Heading(3, "Pinned CPUs".into()));
+        table_or_none(doc, vec!["Node", "CPU", "Held by"], rows);
+
+        // Colocation: cache affinity groups and shared resources.
+        let mut groups: BTreeMap<&str, Vec<&SchedTask>> = BTreeMap::new();
+        let mut resources: BTreeMap<&str, Vec<&SchedTask>> = BTreeMap::new();
+        for &t in &self.tasks {
+            if let Some(g) = &t.cache_affinity_group {
+                groups.entry(g).or_default().push(t);
+            }
+            for r in &t.shared_resources {
+                resources.entry(&r.name).or_default().push(t);
+            }
+        }
+        let colocation = |members: &[&SchedTask], same: fn(&SchedTask, &SchedTask) -> bool| {
+            let names = join(members.iter().map(|t| t.name.as_str()));
+            let placements = join(
+                members
+                    .iter()
+                    .map(|t| format!("{}/cpu{}", t.assigned_node, t.assigned_cpu))
+                    .collect::<BTreeSet<_>>(),
+            );
+            let kept = members.windows(2).all(|w| same(w[0], w[1]));
+            vec![names, placements, yes_no(kept).to_string()]
+        };
+        let rows = groups
+            .iter()
+            .map(|(g, m)| {
+                let mut row = vec![format!("cache group {g}")];
+                row.extend(colocation(m, |a, b| a.assigned_node == b.assigned_node));
+                row
+            })
+            .chain(resources.iter().map(|(r, m)| {
+                let mut row = vec![format!("resource {r}")];
+                row.extend(colocation(m, |a, b| {
+                    (&a.assigned_node, a.assigned_cpu) == (&b.assigned_node, b.assigned_cpu)
+                }));
+                row
+            }))
+            .collect();
+        doc.push(Block::Heading(3, "Colocation".into()));
+        table_or_none(
+            doc,
+            vec!["Constraint", "Tasks", "Placed on", "Co-located"],
+            rows,
+        );
+
+        let mut levels: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for t in &self.tasks {
+            let level = t
+                .metadata
+                .get(SAFETY_LEVEL_KEY)
+                .map_or("unrated", String::as_str);
+            levels.entry(level).or_default().push(&t.name);
+        }
+        let rows = levels
+            .into_iter()
+            .map(|(level, names)| {
+                vec![
+                    level.to_string(),
+                    names.len().to_string(),
+                    join(names.into_iter().collect::<BTreeSet<_>>()),
+                ]
+            })
+            .collect();
+        doc.push(Block::Heading(3, "Safety levels".into()));
+        table_or_none(doc, vec!["Level", "Tasks", "Names"], rows);
+
+        let rows = self
+            .tasks
+            .iter()
+            .filter_map(|t| {
+                let note = match (&t.fallback_from, t.placeholder) {
+                    (Some(from), true) => format!("placeholder, fallback from {from}"),
+                    (Some(from), false) => format!("fallback from {from}"),
+                    (None, true) => "placeholder".to_string(),
+                    (None, false) => return None,
+                };
+                Some(vec![t.name.clone(), t.assigned_node.clone(), note])
+            })
+            .collect();
+        doc.push(Block::Heading(3, "Fallbacks and placeholders".into()));
+        table_or_none(doc, vec!["Task", "Node", "Note"], rows);
+    }
+}
+
+fn table_or_none(doc: &mut Vec<Block>, header: Vec<&'static str>, rows: Vec<Vec<String>>) {
+    if rows.is_empty() {
+        doc.push(Block::Para("None.".into()));
+    } else {
+        doc.push(Block::Table { header, rows });
+    }
+}
+
+fn percent(value: f64) -> String {
+    format!("{:.1}%", value * 100.0)
+}
+
+fn workload(id: &str) -> &str {
+    if id.is_empty() {
+        "(none)"
+    } else {
+        id
+    }
+}
+
+fn policy(policy: SchedPolicy) -> &'static str {
+    match policy {
+        SchedPolicy::Normal => "normal",
+        SchedPolicy::Fifo => "fifo",
+        SchedPolicy::RoundRobin => "rr",
+    }
+}
+
+fn yes_no(b: bool) -> &'static str {
+    if b {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+fn join<S: AsRef<str>>(items: impl IntoIterator<Item = S>) -> String {
+    items
+        .into_iter()
+        .map(|s| s.as_ref().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Filled part of a bar of `width` for `value`, capped at `width`.
+fn filled(value: f64, width: usize) -> usize {
+    ((value.clamp(0.0, 1.0) * width as f64).round() as usize).min(width)
+}
+
+// ── Markdown ──────────────────────────────────────────────────────────────────
+
+fn to_markdown(doc: &[Block]) -> String {
+    let mut out = String::new();
+    for (i, block) in doc.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        match block {
+            Block::Heading(level, text) => {
+                let _ = writeln!(out, "{} {text}", "#".repeat(usize::from(*level)));
+            }
+            Block::Para(text) => {
+                let _ = writeln!(out, "{text}");
+            }
+            Block::Table { header, rows } => {
+                let _ = writeln!(out, "| {} |", header.join(" | "));
+                let _ = writeln!(out, "|{}", "---|".repeat(header.len()));
+                for row in rows {
+                    let cells: Vec<String> = row.iter().map(|c| md_cell(c)).collect();
+                    let _ = writeln!(out, "| {} |", cells.join(" | "));
+                }
+            }
+            Block::Bars(bars) => {
+                let width = bars.iter().map(|b| b.label.len()).max().unwrap_or(0);
+                out.push_str("```text\n");
+                for b in bars {
+                    let n = filled(b.value, BAR_WIDTH);
+                    let _ = writeln!(
+                        out,
+                        "{:<width$} {}{} {}",
+                        b.label,
+                        "#".repeat(n),
+                        ".".repeat(BAR_WIDTH - n),
+                        b.note
+                    );
+                }
+                out.push_str("```\n");
+            }
+        }
+    }
+    out
+}
+
+/// A Markdown table cell: `|` escaped, line breaks as spaces.
+fn md_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+// ── HTML ──────────────────────────────────────────────────────────────────────
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em}\
+table{border-collapse:collapse;margin:0.5em 0}\
+th,td{border:1px solid #999;padding:2px 8px;text-align:left}\
+th{background:#eee}\
+svg text{font-family:monospace;font-size:12px}";
+
+fn to_html(doc: &[Block]) -> String {
+    let mut out = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(out, "<title>Timpani-O schedule sign-off</title>");
+    let _ = writeln!(out, "<style>{STYLE}</style>\n</head>\n<body>");
+    for block in doc {
+        match block {
+            Block::Heading(level, text) => {
+                let _ = writeln!(out, "<h{level}>{}</h{level}>", escape(text));
+            }
+            Block::Para(text) => {
+                let _ = writeln!(out, "<p>{}</p>", escape(text));
+            }
+            Block::Table { header, rows } => {
+                out.push_str("<table>\n<tr>");
+                for h in header {
+                    let _ = write!(out, "<th>{}</th>", escape(h));
+                }
+                out.push_str("</tr>\n");
+                for row in rows {
+                    out.push_str("<tr>");
+                    for c in row {
+                        let _ = write!(out, "<td>{}</td>", escape(c));
+                    }
+                    out.push_str("</tr>\n");
+                }
+                out.push_str("</table>\n");
+            }
+            Block::Bars(bars) => write_svg(&mut out, bars),
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Horizontal bars: label, a grey track with the filled part, then the note.
+fn write_svg(out: &mut String, bars: &[Bar]) {
+    const ROW: usize = 20;
+    const LABEL: usize = 8 * 24;
+    let track = BAR_WIDTH * 10;
+    let width = LABEL + track + 8 * 24;
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{}\">",
+        bars.len() * ROW
+    );
+    for (i, b) in bars.iter().enumerate() {
+        let y = i * ROW;
+        let fill = if b.value > 1.0 { "#c33" } else { "#36c" };
+        let _ = writeln!(
+            out,
+            "<text x=\"0\" y=\"{}\">{}</text>\
+             <rect x=\"{LABEL}\" y=\"{}\" width=\"{track}\" height=\"14\" fill=\"#ddd\"/>\
+             <rect x=\"{LABEL}\" y=\"{}\" width=\"{}\" height=\"14\" fill=\"{fill}\"/>\
+             <text x=\"{}\" y=\"{}\">{}</text>",
+            y + 14,
+            escape(&b.label),
+            y + 3,
+            y + 3,
+            filled(b.value, track),
+            LABEL + track + 8,
+            y + 14,
+            escape(&b.note)
+        );
+    }
+    out.push_str("</svg>\n");
+}
+
+/// `s` with `& < > "` as HTML entities.
+fn escape(s: &str) -> String {
+    let mut e = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => e.push_str("&amp;"),
+            '<' => e.push_str("&lt;"),
+            '>' => e.push_str("&gt;"),
+            '"' => e.push_str("&quot;"),
+            c => e.push(c),
+        }
+    }
+    e
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec;
+
+    fn fixture() -> (NodeSchedMap, NodeConfigManager) {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let schedule: NodeSchedMap = codec::load(&dir.join("signoff_state.json")).unwrap();
+        let mut config = NodeConfigManager::new();
+        config
+            .load_from_file(&dir.join("signoff_nodes.yaml"))
+            .unwrap();
+        (schedule, config)
+    }
+
+    #[test]
+    fn markdown_report_matches_snapshot() {
+        let (schedule, config) = fixture();
+        let md = signoff_report(
+            &schedule,
+            &config,
+            &ScheduleOptions::default(),
+            ReportFormat::Markdown,
+            DurationStyle::default(),
+        );
+        assert_eq!(
+            md,
+            include_str!("../../tests/fixtures/signoff_report.md"),
+            "{md}"
+        );
+    }
+
+    #[test]
+    fn report_does_not_depend_on_input_order() {
+        let (schedule, config) = fixture();
+        let mut shuffled = schedule.clone();
+        for tasks in shuffled.values_mut() {
+            tasks.reverse();
+        }
+        let opts = ScheduleOptions::default();
+        let style = DurationStyle::default();
+        for format in [ReportFormat::Markdown, ReportFormat::Html] {
+            assert_eq!(
+                signoff_report(&schedule, &config, &opts, format, style),
+                signoff_report(&shuffled, &config, &opts, format, style)
+            );
+        }
+        assert_eq!(
+            schedule_fingerprint(&schedule),
+            schedule_fingerprint(&shuffled)
+        );
+        let mut moved = schedule;
+        moved.values_mut().next().unwrap()[0].assigned_cpu += 1;
+        assert_ne!(
+            schedule_fingerprint(&moved),
+            schedule_fingerprint(&shuffled)
+        );
+    }
+
+    #[test]
+    fn html_report_is_escaped_and_self_contained() {
+        let (mut schedule, config) = fixture();
+        let task = schedule.values_mut().next().unwrap().first_mut().unwrap();
+        task.name = "<script>&\"x\"".into();
+        let html = signoff_report(
+            &schedule,
+            &config,
+            &ScheduleOptions::default(),
+            ReportFormat::Html,
+            DurationStyle::default(),
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("&lt;script&gt;&amp;&quot;x&quot;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<svg "));
+        assert!(!html.contains(" src="), "nothing loaded from elsewhere");
+
+        assert_eq!(
+            ReportFormat::from_path(Path::new("out/report.HTM")),
+            ReportFormat::Html
+        );
+        assert_eq!(
+            ReportFormat::from_path(Path::new("report.md")),
+            ReportFormat::Markdown
+        );
+    }
+}
@@ -0,0 +1,700 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Cluster status snapshot — building and rendering.
+//!
+//! The same [`ClusterStatus`] message is produced by the `GetClusterStatus`
+//! RPC and can be built offline from a [`CapacityReport`] and a
+//! [`NodeSchedMap`], so `timpani-o status` and offline tooling print
+//! identical output.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+use crate::proto::schedinfo_v1::{
+    ClusterStatus, NodeApplyInfo, NodeStatus, OrphanedNode, TaskApplyResult, TaskStatus,
+    WorkloadStatus,
+};
+use crate::scheduler::{CapacityReport, PriorityClass};
+use crate::task::NodeSchedMap;
+use crate::units::DurationStyle;
+
+// ── Building ──────────────────────────────────────────────────────────────────
+
+/// One [`NodeStatus`] per node in `capacity`, with task counts from
+/// `schedule`.
+pub fn node_statuses(capacity: &CapacityReport, schedule: &NodeSchedMap) -> Vec<NodeStatus> {
+    capacity
+        .nodes
+        .iter()
+        .map(|c| NodeStatus {
+            node: c.node.clone(),
+            cpu_count: c.cpu_count as u32,
+            total_utilization: c.total_utilization,
+            total_free: c.total_free,
+            largest_placeable: c.largest_placeable,
+            fragmentation_ratio: c.fragmentation_ratio,
+            task_count: schedule.get(&c.node).map_or(0, |t| t.len() as u32),
+            endpoint: c.endpoint.clone(),
+            configured_memory_mb: c.configured_memory_mb,
+            live_memory_mb: c.live_memory_mb,
+            apply_info: None,
+            offline_cpus: c.offline_cpus.clone(),
+            workload_count: c.workloads as u32,
+            max_workloads: c.max_workloads.map(|m| m as u32),
+            config_fingerprint: c
+                .fingerprint
+                .map_or_else(String::new, |f| format!("{f:016x}")),
+            clock: c.clock.clone().map(Into::into),
+            utilization_high_water_mark: 0.0,
+            protocol: String::new(),
+            exclusive_cpus: c
+                .exclusive_cpus
+                .iter()
+                .map(|(&cpu, workload)| (cpu, workload.clone()))
+                .collect(),
+            cordoned: c.cordoned,
+        })
+        .collect()
+}
+
+/// One [`OrphanedNode`] per entry in `capacity.orphaned`.
+pub fn orphaned_nodes(capacity: &CapacityReport) -> Vec<OrphanedNode> {
+    capacity
+        .orphaned
+        .iter()
+        .map(|o| OrphanedNode {
+            node: o.node.clone(),
+            task_count: o.tasks.len() as u32,
+            total_utilization: o.total_utilization,
+            tasks: o.tasks.clone(),
+            cpu: o.cpu,
+        })
+        .collect()
+}
+
+/// Summary of one workload's placement.
+pub fn workload_status(
+    workload_id: &str,
+    generation: u64,
+    schedule: &NodeSchedMap,
+) -> WorkloadStatus {
+    let tasks_per_node: HashMap<String, u32> = schedule
+        .iter()
+        .map(|(node, tasks)| (node.clone(), tasks.len() as u32))
+        .collect();
+    WorkloadStatus {
+        workload_id: workload_id.to_string(),
+        generation,
+        task_count: tasks_per_node.values().sum(),
+        tasks_per_node,
+        ..Default::default()
+    }
+}
+
+// ── Rendering ─────────────────────────────────────────────────────────────────
+
+/// Output format for [`render`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+/// Render `status` in `format`, durations in `style`.  Always ends with a
+/// newline.
+pub fn render(status: &ClusterStatus, format: OutputFormat, style: DurationStyle) -> String {
+    match format {
+        OutputFormat::Table => render_table(status, style),
+        OutputFormat::Json => {
+            // Generated types are plain data; serialisation cannot fail.
+            let mut s = serde_json::to_string_pretty(status).unwrap_or_default();
+            s.push('\n');
+            s
+        }
+    }
+}
+
+/// Human-readable node table followed by the workload summary, durations in
+/// `style`.
+pub fn render_table(status: &ClusterStatus, style: DurationStyle) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "tenant: {}", status.tenant);
+    let _ = writeln!(out, "config generation: {}", status.config_generation);
+    if !status.log_filter.is_empty() {
+        let _ = writeln!(out, "log filter: {}", status.log_filter);
+    }
+    let mut hits: Vec<_> = status
+        .limit_hits
+        .iter()
+        .filter(|(_, &n)| n > 0)
+        .map(|(limit, n)| format!("{limit}={n}"))
+        .collect();
+    if !hits.is_empty() {
+        hits.sort();
+        let _ = writeln!(out, "input limit hits: {}", hits.join(", "));
+    }
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "{:<16} {:<8} {:>4} {:>7} {:>7} {:>7} {:>8} {:>6} {:>5} {:>9} {:>13}  ENDPOINT",
+        "NODE",
+        "STATE",
+        "CPUS",
+        "UTIL%",
+        "PEAK%",
+        "FREE%",
+        "LARGEST%",
+        "FRAG",
+        "TASKS",
+        "WLS U/L",
+        "MEM_MB L/C"
+    );
+    for n in &status.nodes {
+        let _ = writeln!(
+            out,
+            "{:<16} {:<8} {:>4} {:>7.1} {:>7.1} {:>7.1} {:>8.1} {:>6.2} {:>5} {:>9} {:>13}  {}",
+            n.node,
+            if n.cordoned { "cordoned" } else { "open" },
+            n.cpu_count,
+            n.total_utilization * 100.0,
+            n.utilization_high_water_mark * 100.0,
+            n.total_free * 100.0,
+            n.largest_placeable * 100.0,
+            n.fragmentation_ratio,
+            n.task_count,
+            workloads_cell(n),
+            memory_cell(n),
+            n.endpoint
+        );
+    }
+    let applied: Vec<_> = status
+        .nodes
+        .iter()
+        .filter_map(|n| Some((&n.node, n.apply_info.as_ref()?)))
+        .collect();
+    if !applied.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "as of the last apply report:");
+        for (node, info) in applied {
+            let _ = writeln!(out, "  {:<16} {}", node, node_apply_cell(info, style));
+        }
+    }
+    let offline: Vec<_> = status
+        .nodes
+        .iter()
+        .filter(|n| !n.offline_cpus.is_empty())
+        .collect();
+    if !offline.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "offline CPUs:");
+        for n in offline {
+            let cpus: Vec<String> = n.offline_cpus.iter().map(u32::to_string).collect();
+            let _ = writeln!(out, "  {:<16} [{}]", n.node, cpus.join(","));
+        }
+    }
+    let exclusive: Vec<_> = status
+        .nodes
+        .iter()
+        .filter(|n| !n.exclusive_cpus.is_empty())
+        .collect();
+    if !exclusive.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "exclusive CPUs:");
+        for n in exclusive {
+            let mut held: Vec<_> = n.exclusive_cpus.iter().collect();
+            held.sort();
+            let cpus: Vec<String> = held.iter().map(|(c, w)| format!("{c}={w}")).collect();
+            let _ = writeln!(out, "  {:<16} [{}]", n.node, cpus.join(","));
+        }
+    }
+    let clocks: Vec<_> = status
+        .nodes
+        .iter()
+        .filter_map(|n| Some((&n.node, n.clock.as_ref()?)))
+        .collect();
+    if !clocks.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "clock sync:");
+        for (node, c) in clocks {
+            let state = if c.synchronized {
+                "synced"
+            } else {
+                "UNSYNCHRONISED"
+            };
+            let _ = writeln!(
+                out,
+                "  {:<16} {:<8} {:<14} {:>+} ns",
+                node, c.source, state, c.offset_ns
+            );
+        }
+    }
+    let protocols: Vec<_> = status
+        .nodes
+        .iter()
+        .filter(|n| !n.protocol.is_empty())
+        .collect();
+    if !protocols.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "node protocol:");
+        for n in protocols {
+            let _ = writeln!(out, "  {:<16} {}", n.node, n.protocol);
+        }
+    }
+    let mut by_fingerprint: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for n in status
+        .nodes
+        .iter()
+        .filter(|n| !n.config_fingerprint.is_empty())
+    {
+        by_fingerprint
+            .entry(&n.config_fingerprint)
+            .or_default()
+            .push(&n.node);
+    }
+    by_fingerprint.retain(|_, nodes| nodes.len() > 1);
+    if !by_fingerprint.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "identical node configurations:");
+        for (fingerprint, nodes) in by_fingerprint {
+            let _ = writeln!(out, "  {fingerprint} {}", nodes.join(", "));
+        }
+    }
+    let (cpu_orphans, node_orphans): (Vec<_>, Vec<_>) =
+        status.orphaned.iter().partition(|o| o.cpu.is_some());
+    for (heading, orphans) in [
+        ("orphaned (node no longer configured):", node_orphans),
+        ("orphaned (CPU offline):", cpu_orphans),
+    ] {
+        if orphans.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{heading}");
+        for o in orphans {
+            let place = match o.cpu {
+                Some(cpu) => format!("{} cpu{cpu}", o.node),
+                None => o.node.clone(),
+            };
+            let _ = writeln!(
+                out,
+                "  {:<16} {:>5} task(s) {:>7.1}%  {}",
+                place,
+                o.task_count,
+                o.total_utilization * 100.0,
+                o.tasks.join(", ")
+            );
+        }
+    }
+    let _ = writeln!(out);
+    if status.workloads.is_empty() {
+        let _ = writeln!(out, "no active workload");
+    }
+    for w in &status.workloads {
+        let mut per_node: Vec<_> = w.tasks_per_node.iter().collect();
+        per_node.sort();
+        let per_node: Vec<String> = per_node.iter().map(|(n, c)| format!("{n}={c}")).collect();
+        let mut details = match w.priority_class.as_str() {
+            "" => String::new(),
+            c => format!(", class {c}"),
+        };
+        if let Some(ms) = w.ttl_remaining_ms {
+            let _ = write!(
+                details,
+                ", expires in {}",
+                style.format_us(ms.saturating_mul(1_000))
+            );
+        }
+        let _ = writeln!(
+            out,
+            "workload {} (generation {}{}): {} task(s) [{}]",
+            w.workload_id,
+            w.generation,
+            details,
+            w.task_count,
+            per_node.join(", ")
+        );
+        if !w.tasks.is_empty() {
+            let _ = writeln!(out, "  {:<16} {:<16} {:<9} APPLY", "NODE", "TASK", "STATE");
+            for t in &w.tasks {
+                let _ = writeln!(
+                    out,
+                    "  {:<16} {:<16} {:<9} {}",
+                    t.node,
+                    t.name,
+                    t.state,
+                    task_apply_cell(t)
+                );
+            }
+        }
+        if w.illegal_transitions > 0 {
+            let _ = writeln!(
+                out,
+                "  illegal lifecycle transitions: {}",
+                w.illegal_transitions
+            );
+        }
+    }
+    if !status.workloads_per_class.is_empty() {
+        let _ = writeln!(
+            out,
+            "active workloads per class: {}",
+            per_class_cell(&status.workloads_per_class)
+        );
+    }
+    if let Some(p) = status.pending.as_ref().filter(|p| p.depth > 0) {
+        let _ = writeln!(
+            out,
+            "pending: {} workload(s), oldest queued {}",
+            p.depth,
+            style.format_us(p.oldest_age_ms.saturating_mul(1_000))
+        );
+        if !p.depth_per_class.is_empty() {
+            let _ = writeln!(out, "  per class: {}", per_class_cell(&p.depth_per_class));
+        }
+        for q in &p.workloads {
+            let class = match q.priority_class.as_str() {
+                "" => String::new(),
+                c => format!("class {c}, "),
+            };
+            let _ = writeln!(
+                out,
+                "  queued {} ({}importance {}): {}",
+                q.workload_id,
+                class,
+                q.importance,
+                style.format_us(q.age_ms.saturating_mul(1_000))
+            );
+        }
+    }
+    if let Some(c) = &status.compaction {
+        let freed = match c.nodes_freed.as_slice() {
+            [] => String::new(),
+            nodes => format!(", frees {}", nodes.join(", ")),
+        };
+        let _ = writeln!(
+            out,
+            "compaction proposal {}: nodes {} -> {}{}, peak CPU {:.1}% -> {:.1}%",
+            c.proposal_id,
+            c.nodes_in_use_before,
+            c.nodes_in_use_after,
+            freed,
+            c.peak_cpu_utilization_before * 100.0,
+            c.peak_cpu_utilization_after * 100.0
+        );
+        for m in &c.moves {
+            let _ = writeln!(
+                out,
+                "  move {:<16} {} cpu{} -> {} cpu{}",
+                m.task, m.from_node, m.from_cpu, m.to_node, m.to_cpu
+            );
+        }
+    }
+    out
+}
+
+/// `class=count` pairs, highest class first (unknown names last, sorted).
+fn per_class_cell(counts: &HashMap<String, u32>) -> String {
+    let rank = |name: &str| {
+        PriorityClass::ALL
+            .iter()
+            .position(|c| c.as_str() == name)
+            .unwrap_or(PriorityClass::ALL.len())
+    };
+    let mut counts: Vec<_> = counts.iter().collect();
+    counts.sort_by_key(|(name, _)| (rank(name), name.as_str()));
+    counts
+        .iter()
+        .map(|(name, n)| format!("{name}={n}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A task's apply outcome: the status, and for a failure the errno and
+/// detail; `-` before the node reported.
+fn apply_cell(apply: Option<&TaskApplyResult>) -> String {
+    let Some(a) = apply else {
+        return "-".to_string();
+    };
+    let status = a.status().as_str_name();
+    let status = status
+        .strip_prefix("APPLY_STATUS_")
+        .unwrap_or(status)
+        .to_lowercase();
+    if a.errno == 0 && a.detail.is_empty() {
+        return status;
+    }
+    format!("{status} (errno {}) {}", a.errno, a.detail)
+        .trim_end()
+        .to_string()
+}
+
+/// [`apply_cell`] for `t`, marking a placeholder as reserved capacity.
+fn task_apply_cell(t: &TaskStatus) -> String {
+    let cell = apply_cell(t.apply.as_ref());
+    if t.placeholder {
+        format!("{cell} (placeholder)")
+    } else {
+        cell
+    }
+}
+
+/// RT readiness, isolated CPUs and RT throttling from an apply report.
+fn node_apply_cell(info: &NodeApplyInfo, style: DurationStyle) -> String {
+    let privileges = if info.cap_sys_nice {
+        "CAP_SYS_NICE".to_string()
+    } else {
+        format!("rtprio<={}", info.rtprio_limit)
+    };
+    let cpuset = if info.cpuset_writable {
+        "cpuset"
+    } else {
+        "no cpuset"
+    };
+    let isolated: Vec<String> = info.isolated_cpus.iter().map(u32::to_string).collect();
+    let throttle = if info.rt_runtime_us < 0 {
+        "off".to_string()
+    } else {
+        format!(
+            "{} per {}",
+            style.format_us(info.rt_runtime_us as u64),
+            style.format_us(info.rt_period_us.max(0) as u64)
+        )
+    };
+    format!(
+        "{privileges}, {cpuset}, isolated [{}], rt throttle {throttle}",
+        isolated.join(",")
+    )
+}
+
+/// `used/limit` workloads; `-` for no limit.
+fn workloads_cell(n: &NodeStatus) -> String {
+    let limit = n.max_workloads.map_or("-".to_string(), |m| m.to_string());
+    format!("{}/{limit}", n.workload_count)
+}
+
+/// `live/configured` memory in MB; `-` for an absent or unconstrained value.
+fn memory_cell(n: &NodeStatus) -> String {
+    let live = n.live_memory_mb.map_or("-".to_string(), |m| m.to_string());
+    let configured = match n.configured_memory_mb {
+        0 | u64::MAX => "-".to_string(),
+        m => m.to_string(),
+    };
+    format!("{live}/{configured}")
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::schedinfo_v1::{
+        ApplyStatus, ClockSync, CompactionMove, CompactionProposal, PendingStatus, QueuedWorkload,
+    };
+
+    fn sample() -> ClusterStatus {
+        ClusterStatus {
+            tenant: "default".into(),
+            nodes: vec![NodeStatus {
+                node: "n1".into(),
+                cpu_count: 2,
+                total_utilization: 0.25,
+                total_free: 1.55,
+                largest_placeable: 0.8,
+                fragmentation_ratio: 0.52,
+                task_count: 1,
+                endpoint: "10.0.0.1:50054".into(),
+                configured_memory_mb: 4096,
+                live_memory_mb: Some(1024),
+                apply_info: Some(NodeApplyInfo {
+                    cap_sys_nice: false,
+                    rtprio_limit: 50,
+                    cpuset_writable: true,
+                    isolated_cpus: vec![2, 3],
+                    rt_runtime_us: 950_000,
+                    rt_period_us: 1_000_000,
+                }),
+                offline_cpus: vec![3],
+                workload_count: 1,
+                max_workloads: Some(2),
+                config_fingerprint: "00c0ffee00c0ffee".into(),
+                clock: Some(ClockSync {
+                    synchronized: false,
+                    offset_ns: -1500,
+                    source: "phc".into(),
+                }),
+                utilization_high_water_mark: 0.75,
+                protocol: "legacy".into(),
+                exclusive_cpus: [(2, "cert".to_string())].into(),
+                cordoned: true,
+            }],
+            orphaned: vec![
+                OrphanedNode {
+                    node: "n9".into(),
+                    task_count: 2,
+                    total_utilization: 0.3,
+                    tasks: vec!["t8".into(), "t9".into()],
+                    cpu: None,
+                },
+                OrphanedNode {
+                    node: "n1".into(),
+                    task_count: 1,
+                    total_utilization: 0.1,
+                    tasks: vec!["t7".into()],
+                    cpu: Some(3),
+                },
+            ],
+            workloads: vec![WorkloadStatus {
+                workload_id: "wl".into(),
+                generation: 3,
+                task_count: 1,
+                tasks_per_node: [("n1".to_string(), 1)].into(),
+                tasks: vec![
+                    TaskStatus {
+                        node: "n1".into(),
+                        name: "t1".into(),
+                        state: "running".into(),
+                        metadata: Default::default(),
+                        placeholder: false,
+                        apply: Some(TaskApplyResult {
+                            task_name: "t1".into(),
+                            status: ApplyStatus::Applied as i32,
+                            ..Default::default()
+                        }),
+                    },
+                    TaskStatus {
+                        node: "n1".into(),
+                        name: "t2".into(),
+                        state: "faulted".into(),
+                        metadata: Default::default(),
+                        placeholder: false,
+                        apply: Some(TaskApplyResult {
+                            task_name: "t2".into(),
+                            status: ApplyStatus::PidNotFound as i32,
+                            errno: 3,
+                            detail: "sched_setaffinity(4242)".into(),
+                            ..Default::default()
+                        }),
+                    },
+                ],
+                illegal_transitions: 0,
+                priority_class: "safety".into(),
+                ttl_remaining_ms: Some(30_000),
+            }],
+            config_generation: 4,
+            log_filter: "info,timpani_o::scheduler=debug".into(),
+            limit_hits: [("tasks".to_string(), 2), ("templates".to_string(), 0)].into(),
+            workloads_per_class: [("platform".to_string(), 2), ("safety".to_string(), 1)].into(),
+            pending: Some(PendingStatus {
+                depth: 2,
+                oldest_age_ms: 1500,
+                workloads: vec![QueuedWorkload {
+                    workload_id: "wl2".into(),
+                    importance: 1,
+                    age_ms: 900,
+                    priority_class: "best_effort".into(),
+                }],
+                depth_per_class: [("best_effort".to_string(), 2)].into(),
+            }),
+            compaction: Some(CompactionProposal {
+                proposal_id: 7,
+                moves: vec![CompactionMove {
+                    tenant: "default".into(),
+                    task: "t1".into(),
+                    from_node: "n2".into(),
+                    from_cpu: 1,
+                    to_node: "n1".into(),
+                    to_cpu: 0,
+                }],
+                nodes_freed: vec!["n2".into()],
+                nodes_in_use_before: 2,
+                nodes_in_use_after: 1,
+                peak_cpu_utilization_before: 0.5,
+                peak_cpu_utilization_after: 0.75,
+                age_ms: 2_000,
+            }),
+        }
+    }
+
+    #[test]
+    fn table_lists_nodes_and_workload() {
+        let out = render(&sample(), OutputFormat::Table, DurationStyle::default());
+        assert!(out.contains("\nn1               cordoned    2 "), "{out}");
+        assert!(out.contains("config generation: 4"));
+        assert!(out.contains("log filter: info,timpani_o::scheduler=debug"));
+        assert!(out.contains("input limit hits: tasks=2\n"), "{out}");
+        assert!(out.contains("25.0"));
+        assert!(out.contains("PEAK%"));
+        assert!(out.contains("75.0"));
+        assert!(out.contains(
+            "workload wl (generation 3, class safety, expires in 30 s): 1 task(s) [n1=1]"
+        ));
+        assert!(out.contains("active workloads per class: safety=1, platform=2"));
+        assert!(out.contains("10.0.0.1:50054"));
+        assert!(out.contains("1024/4096"));
+        assert!(out.contains(" 1/2 "));
+        assert!(out.contains("running"));
+        assert!(out.contains("applied"));
+        assert!(out.contains("pid_not_found (errno 3) sched_setaffinity(4242)"));
+        assert!(out.contains("rtprio<=50, cpuset, isolated [2,3], rt throttle 950 ms per 1 s"));
+        assert!(out.contains("pending: 2 workload(s), oldest queued 1.5 s"));
+        assert!(out.contains("  per class: best_effort=2"));
+        assert!(out.contains("queued wl2 (class best_effort, importance 1): 900 ms"));
+        assert!(
+            out.contains("compaction proposal 7: nodes 2 -> 1, frees n2, peak CPU 50.0% -> 75.0%")
+        );
+        assert!(out.contains("  move t1               n2 cpu1 -> n1 cpu0"));
+        assert!(out.contains("orphaned (node no longer configured):"));
+        assert!(out.contains("n9"));
+        assert!(out.contains("t8, t9"));
+        assert!(out.contains("offline CPUs:\n  n1               [3]"));
+        assert!(out.contains("exclusive CPUs:\n  n1               [2=cert]"));
+        assert!(out.contains("orphaned (CPU offline):\n  n1 cpu3"));
+        assert!(out.contains("node protocol:\n  n1               legacy\n"));
+        assert!(
+            out.contains("clock sync:\n  n1               phc      UNSYNCHRONISED -1500 ns\n"),
+            "{out}"
+        );
+        assert!(!out.contains("identical node configurations"));
+    }
+
+    #[test]
+    fn table_writes_durations_in_the_given_style() {
+        let raw = DurationStyle::default().with_raw(true);
+        let out = render(&sample(), OutputFormat::Table, raw);
+        assert!(out.contains("expires in 30000000)"), "{out}");
+        assert!(out.contains("rt throttle 950000 per 1000000"));
+        assert!(out.contains("oldest queued 1500000"));
+    }
+
+    #[test]
+    fn table_groups_nodes_sharing_a_fingerprint() {
+        let mut status = sample();
+        let n1 = status.nodes[0].clone();
+        status.nodes.push(NodeStatus {
+            node: "n2".into(),
+            ..n1.clone()
+        });
+        status.nodes.push(NodeStatus {
+            node: "n3".into(),
+            config_fingerprint: String::new(),
+            ..n1
+        });
+        let out = render(&status, OutputFormat::Table, DurationStyle::default());
+        assert!(
+            out.contains("identical node configurations:\n  00c0ffee00c0ffee n1, n2\n"),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let out = render(&sample(), OutputFormat::Json, DurationStyle::default());
+        let back: ClusterStatus = serde_json::from_str(&out).unwrap();
+        assert_eq!(back, sample());
+    }
+}
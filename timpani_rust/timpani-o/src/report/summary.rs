@@ -0,0 +1,258 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Workload-level summary of a finished schedule.
+//!
+//! One [`WorkloadSummary`] per accepted `AddSchedInfo`, so Pullpiri can show
+//! e.g.
+//!
+//! ```text
+//! workload nav-stack: 14 task(s) across 3 node(s), hyperperiod 100 ms,
+//! 2.30 CPU-equivalents (peak CPU 85.0%), 1 feasibility warning(s)
+//! ```
+//!
+//! without recomputing anything.  The offline `timpani-o schedule`
+//! subcommand prints the same summary.
+
+use std::collections::BTreeMap;
+
+use crate::hyperperiod::HyperperiodInfo;
+use crate::scheduler::{ChainReport, PriorityOrdering, Utilization};
+use crate::task::NodeSchedMap;
+use crate::units::DurationStyle;
+
+pub use crate::proto::schedinfo_v1::{ChainLatency, WorkloadSummary};
+
+/// Summarise `schedule` for the workload described by `hyperperiod`.
+///
+/// Utilisation sums are exact ([`Utilization`]) and only converted to `f64`
+/// at the end.  `warning_count` is the number of feasibility warnings
+/// raised for the schedule.  `admission_overrides`, `min_runtime_margin`,
+/// `priority_ordering` and `chains` are left for the caller.
+pub fn workload_summary(
+    hyperperiod: &HyperperiodInfo,
+    schedule: &NodeSchedMap,
+    warning_count: usize,
+) -> WorkloadSummary {
+    let mut per_cpu: BTreeMap<(&str, u32), Utilization> = BTreeMap::new();
+    for (node, tasks) in schedule {
+        for t in tasks {
+            *per_cpu.entry((node, t.assigned_cpu)).or_default() += t.exact_utilization();
+        }
+    }
+
+    let mut nodes: Vec<String> = schedule
+        .iter()
+        .filter(|(_, tasks)| !tasks.is_empty())
+        .map(|(node, _)| node.clone())
+        .collect();
+    nodes.sort();
+
+    WorkloadSummary {
+        workload_id: hyperperiod.workload_id.clone(),
+        task_count: schedule.values().map(|t| t.len() as u32).sum(),
+        nodes,
+        total_utilization: per_cpu.values().copied().sum::<Utilization>().as_f64(),
+        peak_cpu_utilization: per_cpu.values().copied().max().unwrap_or_default().as_f64(),
+        hyperperiod_us: hyperperiod.hyperperiod_us.as_u64(),
+        warning_count: warning_count as u32,
+        admission_overrides: Vec::new(),
+        min_runtime_margin: None,
+        priority_ordering: String::new(),
+        chains: Vec::new(),
+    }
+}
+
+/// The `WorkloadSummary.chains` entries for `reports`, latencies rounded up
+/// to the next microsecond.
+pub fn chain_latencies(reports: &[ChainReport]) -> Vec<ChainLatency> {
+    reports
+        .iter()
+        .map(|r| {
+            let mut c = ChainLatency {
+                name: r.name.clone(),
+                deadline_us: r.deadline.as_u64(),
+                met: r.meets_deadline(),
+                ..Default::default()
+            };
+            match &r.outcome {
+                Ok(result) => {
+                    c.node = result.node.clone();
+                    c.cpu = result.cpu;
+                    c.latency_us = result.latency.map(|l| l.as_u64().div_ceil(1_000));
+                }
+                Err(e) => c.error = e.to_string(),
+            }
+            c
+        })
+        .collect()
+}
+
+/// One-line human-readable form (no trailing newline), durations in
+/// `style`.
+pub fn render_summary(s: &WorkloadSummary, style: DurationStyle) -> String {
+    let mut line = format!(
+        "workload {}: {} task(s) across {} node(s), hyperperiod {}, \
+         {:.2} CPU-equivalents (peak CPU {:.1}%), {} feasibility warning(s)",
+        s.workload_id,
+        s.task_count,
+        s.nodes.len(),
+        style.format_us(s.hyperperiod_us),
+        s.total_utilization,
+        s.peak_cpu_utilization * 100.0,
+        s.warning_count
+    );
+    if let Some(margin) = s.min_runtime_margin {
+        line += &format!(", tightest runtime margin {margin:.2}x");
+    }
+    if s.priority_ordering == PriorityOrdering::DeadlineMonotonic.as_str() {
+        line += ", deadline-monotonic priorities";
+    }
+    let missed = s.chains.iter().filter(|c| !c.met).count();
+    if missed > 0 {
+        line += &format!(
+            ", {missed} of {} chain(s) over their end-to-end deadline",
+            s.chains.len()
+        );
+    }
+    if !s.admission_overrides.is_empty() {
+        line += &format!(
+            ", admission checks skipped: {}",
+            s.admission_overrides.join(", ")
+        );
+    }
+    line
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{analyse_chains, TaskChain};
+    use crate::task::{Micros, Nanos, SchedPolicy, SchedTask};
+
+    fn st(name: &str, node: &str, cpu: u32, period_us: u64, runtime_us: u64) -> SchedTask {
+        SchedTask {
+            name: name.into(),
+            assigned_node: node.into(),
+            assigned_cpu: cpu,
+            period_ns: Nanos(period_us * 1_000),
+            runtime_ns: Nanos(runtime_us * 1_000),
+            deadline_ns: Nanos(period_us * 1_000),
+            policy: SchedPolicy::Fifo,
+            priority: 50,
+            release_time_us: 0,
+            max_dmiss: 0,
+            shared_resources: vec![],
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    /// Hand-computed:
+    ///
+    /// | node | cpu | tasks (runtime / period)      | util |
+    /// |------|-----|-------------------------------|------|
+    /// | n1   | 0   | 5/10 ms + 7/20 ms             | 0.85 |
+    /// | n1   | 1   | 10/100 ms                     | 0.10 |
+    /// | n2   | 3   | 25/50 ms                      | 0.50 |
+    ///
+    /// Total 1.45, peak 0.85, hyperperiod lcm(10, 20, 50, 100) = 100 ms.
+    fn fixture() -> (HyperperiodInfo, NodeSchedMap) {
+        let schedule: NodeSchedMap = [
+            (
+                "n1".to_string(),
+                vec![
+                    st("a", "n1", 0, 10_000, 5_000),
+                    st("b", "n1", 0, 20_000, 7_000),
+                    st("c", "n1", 1, 100_000, 10_000),
+                ],
+            ),
+            ("n2".to_string(), vec![st("d", "n2", 3, 50_000, 25_000)]),
+            ("n3".to_string(), vec![]),
+        ]
+        .into();
+        let hp = HyperperiodInfo {
+            workload_id: "nav-stack".into(),
+            hyperperiod_us: Micros(100_000),
+            unique_periods: [10_000, 20_000, 50_000, 100_000].map(Micros).to_vec(),
+            task_count: 4,
+        };
+        (hp, schedule)
+    }
+
+    #[test]
+    fn summary_matches_hand_computed_fixture() {
+        let (hp, schedule) = fixture();
+        let s = workload_summary(&hp, &schedule, 1);
+        assert_eq!(s.workload_id, "nav-stack");
+        assert_eq!(s.task_count, 4);
+        assert_eq!(s.nodes, ["n1", "n2"]);
+        assert!((s.total_utilization - 1.45).abs() < 1e-12);
+        assert!((s.peak_cpu_utilization - 0.85).abs() < 1e-12);
+        assert_eq!(s.hyperperiod_us, 100_000);
+        assert_eq!(s.warning_count, 1);
+    }
+
+    #[test]
+    fn render_is_one_line() {
+        let (hp, schedule) = fixture();
+        assert_eq!(
+            render_summary(
+                &workload_summary(&hp, &schedule, 1),
+                DurationStyle::default()
+            ),
+            "workload nav-stack: 4 task(s) across 2 node(s), hyperperiod 100 ms, \
+             1.45 CPU-equivalents (peak CPU 85.0%), 1 feasibility warning(s)"
+        );
+
+        let mut s = workload_summary(&hp, &schedule, 1);
+        s.admission_overrides = vec!["skip_memory".into(), "skip_threshold".into()];
+        assert!(render_summary(&s, DurationStyle::default())
+            .ends_with("warning(s), admission checks skipped: skip_memory, skip_threshold"));
+    }
+
+    #[test]
+    fn chains_report_latency_or_why_they_were_not_analysed() {
+        let (_, schedule) = fixture();
+        let chain = |name: &str, tasks: &[&str], deadline_us| TaskChain {
+            name: name.into(),
+            tasks: tasks.iter().map(|t| t.to_string()).collect(),
+            end_to_end_deadline: Micros(deadline_us),
+        };
+        // b below a: R_b = 7 + 2 × 5 = 17 ms.  Released with a, b misses
+        // a's output and waits its 20 ms period: 20 + 17 = 37 ms.
+        let mut schedule = schedule;
+        schedule.get_mut("n1").unwrap()[1].priority = 40;
+        let chains = chain_latencies(&analyse_chains(
+            &schedule,
+            &[
+                chain("ab", &["a", "b"], 10_000),
+                chain("ac", &["a", "c"], 10_000),
+            ],
+        ));
+        assert_eq!(chains[0].node, "n1");
+        assert_eq!(chains[0].cpu, 0);
+        assert_eq!(chains[0].latency_us, Some(37_000));
+        assert!(!chains[0].met);
+        assert_eq!(chains[1].latency_us, None);
+        assert_eq!(
+            chains[1].error,
+            "chain members are on different CPUs (a and c)"
+        );
+
+        let (hp, _) = fixture();
+        let mut s = workload_summary(&hp, &schedule, 1);
+        s.chains = chains;
+        assert!(render_summary(&s, DurationStyle::default())
+            .ends_with("warning(s), 2 of 2 chain(s) over their end-to-end deadline"));
+    }
+}
@@ -0,0 +1,262 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Cache affinity groups: keep tasks that stream data between them in one
+//! cache cluster, on different CPUs.
+//!
+//! Tasks of one workload naming the same
+//! [`cache_affinity_group`](Task::cache_affinity_group) are *mates*.  When a
+//! task is placed on a node with
+//! [`cpu_clusters`](crate::config::NodeConfig::cpu_clusters) where mates
+//! already run, CPU selection first tries only the CPUs that share a
+//! cluster with a mate but run none, in the usual packing order.  If none of
+//! them has room it picks among all CPUs as if the task had no group, so the
+//! hint never fails a placement.  Node selection ignores groups, and a task
+//! pinned to a CPU keeps its pin.
+//!
+//! Whether the hint was honoured is reported for each placement next to
+//! mates through
+//! [`ScheduleEventSink::cache_hint`](super::ScheduleEventSink::cache_hint),
+//! and for each grouped task of a finished schedule by [`honoured_hints`]
+//! (`TaskPlacement.cache_group_honoured`).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::AvailCpus;
+use crate::config::NodeConfigManager;
+use crate::task::{CpuAffinity, NodeSchedMap, Task};
+
+/// Node → workload → group → CPUs running a member.
+type Members = BTreeMap<String, BTreeMap<String, BTreeMap<String, BTreeSet<u32>>>>;
+
+/// Node, CPU and name of a placed member.
+type Placed<'a> = (&'a str, u32, &'a str);
+
+/// The mates placed so far in one scheduling run, and the clusters of every
+/// node that has them.
+#[derive(Debug, Clone, Default)]
+pub(super) struct CacheGroups {
+    clusters: BTreeMap<String, Vec<Vec<u32>>>,
+    placed: Members,
+}
+
+impl CacheGroups {
+    /// No mates placed yet, on `config`'s topology.
+    pub(super) fn new(config: &NodeConfigManager) -> Self {
+        Self {
+            clusters: config
+                .get_all_nodes()
+                .iter()
+                .filter(|(_, n)| !n.cpu_clusters.is_empty())
+                .map(|(name, n)| (name.clone(), n.cpu_clusters.clone()))
+                .collect(),
+            placed: Members::new(),
+        }
+    }
+
+    /// Note that `task` went to `cpu` of `node`.
+    pub(super) fn record(&mut self, task: &Task, node: &str, cpu: u32) {
+        let Some(group) = &task.cache_affinity_group else {
+            return;
+        };
+        self.placed
+            .entry(node.to_string())
+            .or_default()
+            .entry(task.workload_id.clone())
+            .or_default()
+            .entry(group.clone())
+            .or_default()
+            .insert(cpu);
+    }
+
+    /// `find` among the CPUs of `open` the hint prefers for `task` on
+    /// `node`, then among all of `open` (see the module docs).
+    pub(super) fn find_cpu(
+        &self,
+        task: &Task,
+        node: &str,
+        open: &AvailCpus,
+        find: impl Fn(&AvailCpus) -> Option<u32>,
+    ) -> Option<u32> {
+        if let (Some(preferred), Some(cpus)) = (self.preferred(task, node), open.get(node)) {
+            let narrowed: AvailCpus = [(
+                node.to_string(),
+                cpus.iter()
+                    .copied()
+                    .filter(|c| preferred.contains(c))
+                    .collect(),
+            )]
+            .into();
+            if let Some(cpu) = find(&narrowed) {
+                return Some(cpu);
+            }
+        }
+        find(open)
+    }
+
+    /// Whether placing `task` on `cpu` of `node` honours the hint; `None`
+    /// when the hint does not apply there.
+    pub(super) fn honoured(&self, task: &Task, node: &str, cpu: u32) -> Option<bool> {
+        self.preferred(task, node).map(|p| p.contains(&cpu))
+    }
+
+    /// The CPUs of `node` sharing a cluster with one of `task`'s mates and
+    /// running none; `None` when the hint does not apply: no group, a
+    /// pinned task, no topology or no mates on the node.
+    fn preferred(&self, task: &Task, node: &str) -> Option<BTreeSet<u32>> {
+        let group = task.cache_affinity_group.as_ref()?;
+        if matches!(task.affinity, CpuAffinity::Pinned(_)) {
+            return None;
+        }
+        let clusters = self.clusters.get(node)?;
+        let mates = self.placed.get(node)?.get(&task.workload_id)?.get(group)?;
+        Some(
+            clusters
+                .iter()
+                .filter(|c| c.iter().any(|cpu| mates.contains(cpu)))
+                .flatten()
+                .copied()
+                .filter(|cpu| !mates.contains(cpu))
+                .collect(),
+        )
+    }
+}
+
+/// Whether each grouped task of `schedule` with a mate in it shares a
+/// cluster with a mate on another CPU and its own CPU with none, by task
+/// name.  A task on a node without `cpu_clusters` is never honoured.
+pub fn honoured_hints(
+    schedule: &NodeSchedMap,
+    config: &NodeConfigManager,
+) -> BTreeMap<String, bool> {
+    // (workload, group) → every member.
+    let mut groups: BTreeMap<(&str, &str), Vec<Placed>> = BTreeMap::new();
+    for (node, tasks) in schedule {
+        for t in tasks {
+            if let Some(group) = &t.cache_affinity_group {
+                groups
+                    .entry((t.workload_id.as_str(), group.as_str()))
+                    .or_default()
+                    .push((node, t.assigned_cpu, &t.name));
+            }
+        }
+    }
+    let mut honoured = BTreeMap::new();
+    for members in groups.values().filter(|m| m.len() > 1) {
+        for (i, &(node, cpu, name)) in members.iter().enumerate() {
+            let cluster = config
+                .get_node_config(node)
+                .and_then(|n| n.cluster_of(cpu))
+                .unwrap_or_default();
+            let mates = members
+                .iter()
+                .enumerate()
+                .filter(|&(j, &(n, ..))| j != i && n == node);
+            let mut shared = false;
+            let mut beside = false;
+            for (_, &(_, mate_cpu, _)) in mates {
+                shared |= mate_cpu == cpu;
+                beside |= mate_cpu != cpu && cluster.contains(&mate_cpu);
+            }
+            honoured.insert(name.to_string(), beside && !shared);
+        }
+    }
+    honoured
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::config::NodeConfig;
+    use crate::scheduler::{GlobalScheduler, SchedAlgorithm, ScheduleOptions};
+    use crate::testing::fake_task;
+
+    fn clustered(name: &str) -> NodeConfig {
+        NodeConfig {
+            available_cpus: vec![2, 3, 4, 5],
+            cpu_clusters: vec![vec![2, 3], vec![4, 5]],
+            ..NodeConfig::default_config(name)
+        }
+    }
+
+    fn grouped(name: &str, runtime_us: u64) -> Task {
+        Task {
+            workload_id: "wl".into(),
+            cache_affinity_group: Some("pipe".into()),
+            ..fake_task(name, "node01", 10_000, runtime_us)
+        }
+    }
+
+    fn schedule(
+        config: NodeConfigManager,
+        tasks: Vec<Task>,
+    ) -> (NodeSchedMap, Arc<NodeConfigManager>) {
+        let config = Arc::new(config);
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::TargetNodePriority);
+        let map = GlobalScheduler::new(Arc::clone(&config))
+            .schedule_with_options(tasks, &opts)
+            .unwrap();
+        (map, config)
+    }
+
+    fn cpus(map: &NodeSchedMap) -> BTreeMap<&str, u32> {
+        map.values()
+            .flatten()
+            .map(|t| (t.name.as_str(), t.assigned_cpu))
+            .collect()
+    }
+
+    #[test]
+    fn grouped_tasks_share_a_cluster_on_different_cpus() {
+        // Packing alone would stack both on CPU 5.
+        let (map, config) = schedule(
+            NodeConfigManager::from_nodes(vec![clustered("node01")]),
+            vec![grouped("producer", 3_000), grouped("consumer", 3_000)],
+        );
+        assert_eq!(cpus(&map), [("consumer", 4), ("producer", 5)].into());
+        assert_eq!(
+            honoured_hints(&map, &config),
+            [("consumer".into(), true), ("producer".into(), true)].into()
+        );
+    }
+
+    #[test]
+    fn hint_falls_back_without_room_or_topology() {
+        // CPU 4 is full, so the consumer joins the producer on CPU 5.
+        let filler = Task {
+            affinity: CpuAffinity::Pinned(1 << 4),
+            ..fake_task("filler", "node01", 10_000, 8_000)
+        };
+        let (map, config) = schedule(
+            NodeConfigManager::from_nodes(vec![clustered("node01")]),
+            vec![
+                filler,
+                grouped("producer", 3_000),
+                grouped("consumer", 3_000),
+            ],
+        );
+        assert_eq!(cpus(&map)["consumer"], 5);
+        assert_eq!(cpus(&map)["producer"], 5);
+        let hints = honoured_hints(&map, &config);
+        assert!(!hints["consumer"]);
+        assert!(!hints.contains_key("filler"));
+
+        // Without clusters the group is placed as plain packing would.
+        let flat = NodeConfig {
+            cpu_clusters: Vec::new(),
+            ..clustered("node01")
+        };
+        let (map, config) = schedule(
+            NodeConfigManager::from_nodes(vec![flat]),
+            vec![grouped("producer", 3_000), grouped("consumer", 3_000)],
+        );
+        assert_eq!(cpus(&map), [("consumer", 5), ("producer", 5)].into());
+        assert!(!honoured_hints(&map, &config)["producer"]);
+    }
+}
@@ -0,0 +1,689 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Capacity and fragmentation reporting.
+//!
+//! Total free utilisation alone is misleading: a node with 8 CPUs each at
+//! 85 % has 40 % free in total, yet no task above 5 % can be admitted because
+//! a task never spans CPUs.  [`CapacityReport`] therefore reports, per node:
+//!
+//! | Field | Meaning |
+//! |---|---|
+//! | `total_free` | Σ over CPUs of `max(threshold − current, 0)` |
+//! | `largest_placeable` | max over CPUs of `max(threshold − current, 0)` — the biggest single task that still fits |
+//! | `fragmentation_ratio` | `largest_placeable / total_free` — `1.0` means all headroom is on one CPU |
+//! | `configured_memory_mb` | `max_memory_mb` from the node configuration |
+//! | `live_memory_mb` | reported free memory plus tracked placements, if live memory is on and the report is fresh |
+//! | `workloads` / `max_workloads` | distinct workloads placed on the node, and its configured limit |
+//! | `exclusive_cpus` | CPUs held exclusively by a workload, with the holder; their headroom only takes that workload's tasks |
+//! | `fingerprint` | [`NodeConfig::fingerprint`](crate::config::NodeConfig::fingerprint) of the node's configuration |
+//! | `clock` | the node's last clock report, if it sent one |
+//!
+//! On a time-partitioned node the threshold is scaled by its
+//! [`cpu_share`](crate::config::NodeConfig::cpu_share).
+//!
+//! Tasks recorded on a node the configuration no longer has (e.g. after a
+//! reload), or on a CPU the node reports offline (see
+//! [`crate::config::NodeConfigManager::report_online_cpus`]), are listed
+//! separately in [`CapacityReport::orphaned`] and never counted as capacity.
+//!
+//! [`GlobalScheduler::schedule_incremental`] can additionally return a
+//! [`DefragSuggestion`] when re-placing every task from scratch would open up
+//! a larger slot than the configured target.
+
+use std::collections::BTreeMap;
+
+use tracing::info;
+
+use super::workloads::NodeWorkloads;
+use super::{GlobalScheduler, SchedAlgorithm, ScheduleOptions, Utilization};
+use crate::config::ClockReport;
+use crate::task::{CpuAffinity, NodeSchedMap, SchedTask, Task};
+
+// ── NodeCapacity ──────────────────────────────────────────────────────────────
+
+/// Headroom summary for one node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeCapacity {
+    /// Node identifier.
+    pub node: String,
+
+    /// Number of CPUs in the node's configured pool that are online.
+    pub cpu_count: usize,
+
+    /// Sum of per-CPU utilisation (0.0 – `cpu_count`).
+    pub total_utilization: f64,
+
+    /// Sum of per-CPU headroom below the admission threshold.
+    pub total_free: f64,
+
+    /// Largest single-task utilisation that could still be admitted.
+    pub largest_placeable: f64,
+
+    /// `largest_placeable / total_free`.
+    ///
+    /// Defined as `1.0` when `total_free` is zero — a full node is not
+    /// fragmented, it is just full.
+    pub fragmentation_ratio: f64,
+
+    /// Resolved `host:port` of the node's Timpani-N (empty if unknown).
+    pub endpoint: String,
+
+    /// `max_memory_mb` from the node configuration.
+    pub configured_memory_mb: u64,
+
+    /// Memory ceiling from the node's last fresh report (see
+    /// [`MemoryBudget`](crate::config::MemoryBudget)).
+    pub live_memory_mb: Option<u64>,
+
+    /// Configured CPUs the node last reported offline.
+    pub offline_cpus: Vec<u32>,
+
+    /// Distinct workloads with a task on the node.
+    pub workloads: usize,
+
+    /// `max_workloads` from the node configuration; `None` = no limit.
+    pub max_workloads: Option<usize>,
+
+    /// CPUs held exclusively, with the workload holding each (see
+    /// [`crate::scheduler::workloads`]).
+    pub exclusive_cpus: BTreeMap<u32, String>,
+
+    /// Hash of the node's configuration (see
+    /// [`NodeConfigManager::fingerprint`](crate::config::NodeConfigManager::fingerprint)).
+    pub fingerprint: Option<u64>,
+
+    /// Last clock report from the node (see
+    /// [`NodeConfigManager::clock_report`](crate::config::NodeConfigManager::clock_report)).
+    pub clock: Option<ClockReport>,
+
+    /// Whether the node is cordoned (closed to new placements; see
+    /// [`NodeConfigManager::is_cordoned`](crate::config::NodeConfigManager::is_cordoned)).
+    pub cordoned: bool,
+}
+
+impl NodeCapacity {
+    /// Build a summary from per-CPU utilisation values, each CPU admitting
+    /// up to `threshold`.
+    fn from_cpu_util(node: &str, cpus: &BTreeMap<u32, f64>, threshold: f64) -> Self {
+        let mut total_utilization = 0.0;
+        let mut total_free = 0.0;
+        let mut largest_placeable: f64 = 0.0;
+
+        for &u in cpus.values() {
+            let free = (threshold - u).max(0.0);
+            total_utilization += u;
+            total_free += free;
+            largest_placeable = largest_placeable.max(free);
+        }
+
+        let fragmentation_ratio = if total_free > 0.0 {
+            largest_placeable / total_free
+        } else {
+            1.0
+        };
+
+        Self {
+            node: node.to_string(),
+            cpu_count: cpus.len(),
+            total_utilization,
+            total_free,
+            largest_placeable,
+            fragmentation_ratio,
+            endpoint: String::new(),
+            configured_memory_mb: u64::MAX,
+            live_memory_mb: None,
+            offline_cpus: Vec::new(),
+            workloads: 0,
+            max_workloads: None,
+            exclusive_cpus: BTreeMap::new(),
+            fingerprint: None,
+            clock: None,
+            cordoned: false,
+        }
+    }
+}
+
+// ── OrphanedNode ──────────────────────────────────────────────────────────────
+
+/// Tasks placed on a node that is not in the node configuration, or on a
+/// CPU of a configured node that is not usable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrphanedNode {
+    /// Node identifier.
+    pub node: String,
+
+    /// The unusable CPU (offline or no longer configured); `None` when the
+    /// whole node is gone.
+    pub cpu: Option<u32>,
+
+    /// Names of the tasks still placed there, sorted.
+    pub tasks: Vec<String>,
+
+    /// Sum of their utilisation.
+    pub total_utilization: f64,
+}
+
+// ── CapacityReport ────────────────────────────────────────────────────────────
+
+/// Per-node headroom for a schedule, sorted by node name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CapacityReport {
+    pub nodes: Vec<NodeCapacity>,
+
+    /// Nodes in the schedule but not in the configuration, then CPUs of
+    /// configured nodes that are not usable, sorted by node and CPU.
+    /// Excluded from `nodes` and from every headroom figure.
+    pub orphaned: Vec<OrphanedNode>,
+}
+
+impl CapacityReport {
+    /// Look up a single node's entry.
+    pub fn node(&self, node: &str) -> Option<&NodeCapacity> {
+        self.nodes.iter().find(|n| n.node == node)
+    }
+
+    /// Largest slot available on any node — the biggest task that the
+    /// cluster as a whole could still admit.
+    pub fn largest_placeable(&self) -> f64 {
+        self.nodes
+            .iter()
+            .map(|n| n.largest_placeable)
+            .fold(0.0, f64::max)
+    }
+}
+
+// ── Incremental scheduling ────────────────────────────────────────────────────
+
+/// Proposal to re-place every task from scratch.
+///
+/// Only a suggestion: the caller decides whether moving running tasks is
+/// worth the disruption.
+#[derive(Debug, Clone)]
+pub struct DefragSuggestion {
+    /// Cluster-wide largest placeable slot with the incremental schedule.
+    pub current_largest_placeable: f64,
+
+    /// Cluster-wide largest placeable slot after re-optimisation.
+    pub optimised_largest_placeable: f64,
+
+    /// The configured target that triggered the suggestion.
+    pub target: f64,
+
+    /// The re-optimised schedule.
+    pub proposed: NodeSchedMap,
+}
+
+/// Result of [`GlobalScheduler::schedule_incremental`].
+#[derive(Debug, Clone)]
+pub struct IncrementalSchedule {
+    /// `existing` plus the newly placed tasks.  Existing tasks never move.
+    pub schedule: NodeSchedMap,
+
+    /// Present when re-optimisation would lift the largest placeable slot
+    /// above the configured defragmentation target.
+    pub defrag: Option<DefragSuggestion>,
+}
+
+impl GlobalScheduler {
+    /// Compute per-node headroom for `schedule`.
+    ///
+    /// Every configured node appears in the report, including nodes with no
+    /// tasks.  Scheduled nodes that are not configured go to
+    /// [`orphaned`](CapacityReport::orphaned).
+    pub fn capacity_report(&self, schedule: &NodeSchedMap) -> CapacityReport {
+        self.capacity_report_with(schedule, &ScheduleOptions::default())
+    }
+
+    /// [`capacity_report`](Self::capacity_report) against `opts`' per-CPU
+    /// utilisation threshold.
+    pub fn capacity_report_with(
+        &self,
+        schedule: &NodeSchedMap,
+        opts: &ScheduleOptions,
+    ) -> CapacityReport {
+        let avail = self.build_available_cpus();
+        let mut util = Self::build_cpu_utilization(&avail);
+        Self::seed_cpu_utilization(&mut util, schedule);
+        self.seed_foreign_load(&mut util);
+        let workloads = NodeWorkloads::from_schedule(schedule);
+
+        // Only configured CPUs can host new work, so only they count.
+        let nodes = avail
+            .iter()
+            .map(|(node, cpus)| {
+                let node_util = util.get(node);
+                let per_cpu: BTreeMap<u32, f64> = cpus
+                    .iter()
+                    .map(|&c| {
+                        let u = node_util
+                            .and_then(|m| m.get(&c))
+                            .copied()
+                            .unwrap_or_default();
+                        (c, u.as_f64())
+                    })
+                    .collect();
+                let memory = self.node_config_manager.memory_budget(node);
+                NodeCapacity {
+                    endpoint: self
+                        .node_config_manager
+                        .resolve_endpoint(node)
+                        .unwrap_or_default(),
+                    configured_memory_mb: memory.map_or(u64::MAX, |m| m.configured_mb),
+                    live_memory_mb: memory.and_then(|m| m.live_mb),
+                    offline_cpus: self.node_config_manager.offline_cpus(node),
+                    workloads: workloads.count(node),
+                    max_workloads: self
+                        .node_config_manager
+                        .get_node_config(node)
+                        .and_then(|cfg| cfg.max_workloads),
+                    exclusive_cpus: workloads.exclusive_cpus(node),
+                    fingerprint: self.node_config_manager.fingerprint(node),
+                    clock: self.node_config_manager.clock_report(node),
+                    cordoned: self.node_config_manager.is_cordoned(node),
+                    ..NodeCapacity::from_cpu_util(
+                        node,
+                        &per_cpu,
+                        opts.cpu_utilization_threshold * self.node_config_manager.cpu_share(node),
+                    )
+                }
+            })
+            .collect();
+
+        let mut orphaned: Vec<OrphanedNode> = schedule
+            .iter()
+            .filter(|(node, tasks)| !tasks.is_empty() && !avail.contains_key(*node))
+            .map(|(node, tasks)| {
+                let mut names: Vec<String> = tasks.iter().map(|t| t.name.clone()).collect();
+                names.sort();
+                OrphanedNode {
+                    node: node.clone(),
+                    cpu: None,
+                    tasks: names,
+                    total_utilization: Self::calculate_node_utilization(&util, node).as_f64(),
+                }
+            })
+            .collect();
+        for (node, cpus) in &avail {
+            let mut stranded: BTreeMap<u32, Vec<&SchedTask>> = BTreeMap::new();
+            for t in schedule.get(node).into_iter().flatten() {
+                if !cpus.contains(&t.assigned_cpu) {
+                    stranded.entry(t.assigned_cpu).or_default().push(t);
+                }
+            }
+            orphaned.extend(stranded.into_iter().map(|(cpu, tasks)| {
+                let mut names: Vec<String> = tasks.iter().map(|t| t.name.clone()).collect();
+                names.sort();
+                OrphanedNode {
+                    node: node.clone(),
+                    cpu: Some(cpu),
+                    tasks: names,
+                    total_utilization: tasks
+                        .iter()
+                        .map(|t| t.exact_utilization())
+                        .sum::<Utilization>()
+                        .as_f64(),
+                }
+            }));
+        }
+        orphaned.sort_by(|a, b| (&a.node, a.cpu).cmp(&(&b.node, b.cpu)));
+
+        CapacityReport { nodes, orphaned }
+    }
+
+    /// An unplaced [`Task`] with `st`'s timing and CPU exclusivity, free to
+    /// go to any CPU on any node.
+    pub(crate) fn replacement_task(st: &SchedTask) -> Task {
+        Task {
+            name: st.name.clone(),
+            policy: st.policy,
+            priority: st.priority,
+            affinity: CpuAffinity::Any,
+            period_us: st.period_ns.to_micros(),
+            runtime_us: st.runtime_ns.to_micros(),
+            deadline_us: st.deadline_ns.to_micros(),
+            release_time_us: st.release_time_us.max(0) as u32,
+            max_dmiss: st.max_dmiss,
+            shared_resources: st.shared_resources.clone(),
+            exclusive_cpus: st.exclusive_cpus,
+            ..Default::default()
+        }
+    }
+
+    /// Re-place every task in `schedule` with `best_fit_decreasing` under
+    /// `opts` and return the result if it opens a slot larger than
+    /// `target`.
+    ///
+    /// Each placed task is re-placed from its source in `sources` (same
+    /// workload and name), so it keeps its affinity and target node.
+    pub(super) fn suggest_defragmentation(
+        &self,
+        schedule: &NodeSchedMap,
+        sources: &[Task],
+        opts: &ScheduleOptions,
+        target: f64,
+    ) -> Option<DefragSuggestion> {
+        let current = self
+            .capacity_report_with(schedule, opts)
+            .largest_placeable();
+        if current > target {
+            return None;
+        }
+
+        let tasks: Vec<Task> = schedule
+            .values()
+            .flatten()
+            .map(|st| {
+                sources
+                    .iter()
+                    .find(|t| t.workload_id == st.workload_id && t.name == st.name)
+                    .map(|t| Task {
+                        assigned_node: String::new(),
+                        assigned_cpu: None,
+                        target_fallback: false,
+                        ..t.clone()
+                    })
+                    .unwrap_or_else(|| Self::replacement_task(st))
+            })
+            .collect();
+
+        let opts = opts
+            .clone()
+            .with_algorithm(SchedAlgorithm::BestFitDecreasing);
+        let proposed = self.schedule_with_options(tasks, &opts).ok()?;
+        let optimised = self
+            .capacity_report_with(&proposed, &opts)
+            .largest_placeable();
+        if optimised <= target {
+            return None;
+        }
+
+        info!(
+            current_largest_pct = current * 100.0,
+            optimised_largest_pct = optimised * 100.0,
+            target_pct = target * 100.0,
+            "defragmentation would enlarge the largest placeable slot"
+        );
+
+        Some(DefragSuggestion {
+            current_largest_placeable: current,
+            optimised_largest_placeable: optimised,
+            target,
+            proposed,
+        })
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{NodeConfig, NodeConfigManager};
+    use crate::scheduler::{SchedAlgorithm, ScheduleOptions};
+    use crate::task::{Micros, Nanos, SchedPolicy, SchedTask};
+    use std::sync::Arc;
+
+    fn scheduler_with(nodes: &[(&str, Vec<u32>)]) -> GlobalScheduler {
+        let cfgs = nodes
+            .iter()
+            .map(|(name, cpus)| NodeConfig {
+                available_cpus: cpus.clone(),
+                ..NodeConfig::default_config(*name)
+            })
+            .collect();
+        GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(cfgs)))
+    }
+
+    /// A placed task using `util` of one CPU (period fixed at 100 ms).
+    fn placed(name: &str, node: &str, cpu: u32, util: f64) -> SchedTask {
+        SchedTask {
+            name: name.to_string(),
+            assigned_node: node.to_string(),
+            assigned_cpu: cpu,
+            policy: SchedPolicy::Fifo,
+            priority: 50,
+            period_ns: Nanos(100_000_000),
+            runtime_ns: Nanos((util * 100_000_000.0).round() as u64),
+            deadline_ns: Nanos(100_000_000),
+            release_time_us: 0,
+            max_dmiss: 0,
+            shared_resources: Vec::new(),
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    fn unplaced(name: &str, period_us: u64, runtime_us: u64) -> Task {
+        Task {
+            name: name.to_string(),
+            workload_id: "wl1".to_string(),
+            period_us: Micros(period_us),
+            runtime_us: Micros(runtime_us),
+            deadline_us: Micros(period_us),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fragmented_node_reports_small_largest_slot() {
+        let sched = scheduler_with(&[("node01", (0..8).collect())]);
+        let mut map = NodeSchedMap::new();
+        map.insert(
+            "node01".into(),
+            (0..8)
+                .map(|c| placed(&format!("t{c}"), "node01", c, 0.85))
+                .collect(),
+        );
+
+        let report = sched.capacity_report(&map);
+        let n = report.node("node01").unwrap();
+        assert_eq!(n.cpu_count, 8);
+        assert!((n.total_free - 0.40).abs() < 1e-9, "got {}", n.total_free);
+        assert!((n.largest_placeable - 0.05).abs() < 1e-9);
+        assert!((n.fragmentation_ratio - 0.125).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_and_full_nodes_have_ratio_one() {
+        let sched = scheduler_with(&[("empty", vec![0, 1]), ("full", vec![0])]);
+        let mut map = NodeSchedMap::new();
+        map.insert("full".into(), vec![placed("t", "full", 0, 0.9)]);
+
+        let report = sched.capacity_report(&map);
+        let empty = report.node("empty").unwrap();
+        assert!((empty.largest_placeable - 0.9).abs() < 1e-9);
+        assert!((empty.fragmentation_ratio - 0.5).abs() < 1e-9);
+
+        let full = report.node("full").unwrap();
+        assert_eq!(full.largest_placeable, 0.0);
+        assert_eq!(full.fragmentation_ratio, 1.0);
+    }
+
+    #[test]
+    fn respected_foreign_load_shrinks_headroom() {
+        for respect in [false, true] {
+            let mgr = NodeConfigManager::from_nodes(vec![NodeConfig {
+                available_cpus: vec![0, 1],
+                ..NodeConfig::default_config("n1")
+            }])
+            .with_respect_foreign_load(respect);
+            mgr.report_foreign_load("n1", BTreeMap::from([(1, 0.7), (5, 1.0)]));
+            let sched = GlobalScheduler::new(Arc::new(mgr));
+
+            let report = sched.capacity_report(&NodeSchedMap::new());
+            let n = report.node("n1").unwrap();
+            let (free, largest) = if respect { (1.1, 0.9) } else { (1.8, 0.9) };
+            assert!((n.total_free - free).abs() < 1e-9, "got {}", n.total_free);
+            assert!((n.largest_placeable - largest).abs() < 1e-9);
+
+            // 0.5 fits next to the foreign 0.7 only when it is ignored.
+            let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+            let tasks = vec![unplaced("a", 10_000, 5_000), unplaced("b", 10_000, 5_000)];
+            let result = sched.schedule_with_options(tasks, &opts);
+            assert_eq!(result.is_ok(), !respect, "respect = {respect}");
+        }
+    }
+
+    #[test]
+    fn report_carries_resolved_endpoints() {
+        let cfgs = vec![
+            NodeConfig {
+                endpoint: Some("10.0.0.11:6000".into()),
+                ..NodeConfig::default_config("front")
+            },
+            NodeConfig::default_config("rear"),
+        ];
+        let mgr = NodeConfigManager::from_nodes(cfgs).with_default_node_port(7000);
+        let report = GlobalScheduler::new(Arc::new(mgr)).capacity_report(&NodeSchedMap::new());
+        assert_eq!(report.node("front").unwrap().endpoint, "10.0.0.11:6000");
+        assert_eq!(report.node("rear").unwrap().endpoint, "rear:7000");
+    }
+
+    #[test]
+    fn report_shows_configured_and_live_memory() {
+        let mgr = NodeConfigManager::from_nodes(vec![
+            NodeConfig::default_config("front"),
+            NodeConfig::default_config("rear"),
+        ])
+        .with_live_memory(std::time::Duration::from_secs(30));
+        mgr.report_free_memory("front", 1_000, 24);
+        let report = GlobalScheduler::new(Arc::new(mgr)).capacity_report(&NodeSchedMap::new());
+
+        let front = report.node("front").unwrap();
+        assert_eq!(front.configured_memory_mb, 4096);
+        assert_eq!(front.live_memory_mb, Some(1_024));
+        let rear = report.node("rear").unwrap();
+        assert_eq!(rear.configured_memory_mb, 4096);
+        assert_eq!(rear.live_memory_mb, None);
+    }
+
+    #[test]
+    fn unconfigured_node_is_reported_as_orphaned() {
+        let sched = scheduler_with(&[("node01", vec![0, 1])]);
+        let mut map = NodeSchedMap::new();
+        map.insert("node01".into(), vec![placed("a", "node01", 0, 0.2)]);
+        map.insert(
+            "node02".into(),
+            vec![placed("c", "node02", 1, 0.3), placed("b", "node02", 0, 0.1)],
+        );
+
+        let report = sched.capacity_report(&map);
+        assert_eq!(report.nodes.len(), 1);
+        assert!((report.node("node01").unwrap().total_utilization - 0.2).abs() < 1e-9);
+        assert_eq!(report.orphaned.len(), 1);
+        let orphan = &report.orphaned[0];
+        assert_eq!(orphan.node, "node02");
+        assert_eq!(orphan.tasks, ["b", "c"]);
+        assert!((orphan.total_utilization - 0.4).abs() < 1e-9);
+    }
+
+    /// node01 has CPUs [2, 3]; `t1` (40 %) already sits on CPU 2.  Packing
+    /// puts the new 40 % task on CPU 3, leaving two 50 % slivers.  A full
+    /// re-optimisation stacks both on one CPU and frees a 90 % slot.
+    fn fragmented_incremental(sched: &GlobalScheduler) -> IncrementalSchedule {
+        fragmented_incremental_with(sched, [CpuAffinity::Any; 2], &bfd())
+    }
+
+    fn bfd() -> ScheduleOptions {
+        ScheduleOptions::default().with_algorithm(SchedAlgorithm::BestFitDecreasing)
+    }
+
+    /// [`fragmented_incremental`] with `t1` and `t2` pinned by `affinity`.
+    fn fragmented_incremental_with(
+        sched: &GlobalScheduler,
+        affinity: [CpuAffinity; 2],
+        opts: &ScheduleOptions,
+    ) -> IncrementalSchedule {
+        let mut existing = NodeSchedMap::new();
+        existing.insert("node01".into(), vec![placed("t1", "node01", 2, 0.4)]);
+        let t1 = Task {
+            workload_id: String::new(),
+            affinity: affinity[0],
+            ..unplaced("t1", 100_000, 40_000)
+        };
+        let t2 = Task {
+            affinity: affinity[1],
+            ..unplaced("t2", 100_000, 40_000)
+        };
+        sched
+            .schedule_incremental(&existing, &[t1], vec![t2], opts)
+            .unwrap()
+    }
+
+    #[test]
+    fn incremental_keeps_existing_and_seeds_utilisation() {
+        let sched = scheduler_with(&[("node01", vec![2, 3])]);
+        let result = fragmented_incremental(&sched);
+
+        let tasks = &result.schedule["node01"];
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(
+            tasks.iter().find(|t| t.name == "t1").unwrap().assigned_cpu,
+            2
+        );
+        assert_eq!(
+            tasks.iter().find(|t| t.name == "t2").unwrap().assigned_cpu,
+            3
+        );
+
+        let largest = sched.capacity_report(&result.schedule).largest_placeable();
+        assert!((largest - 0.5).abs() < 1e-9);
+        assert!(result.defrag.is_none(), "no target configured");
+    }
+
+    #[test]
+    fn defrag_suggested_when_reoptimisation_beats_target() {
+        let sched = scheduler_with(&[("node01", vec![2, 3])]).with_defrag_target(0.6);
+        let result = fragmented_incremental(&sched);
+
+        let s = result.defrag.expect("suggestion expected");
+        assert!((s.current_largest_placeable - 0.5).abs() < 1e-9);
+        assert!((s.optimised_largest_placeable - 0.9).abs() < 1e-9);
+        assert_eq!(s.proposed["node01"].len(), 2);
+    }
+
+    #[test]
+    fn no_defrag_when_target_unreachable() {
+        let sched = scheduler_with(&[("node01", vec![2, 3])]).with_defrag_target(0.95);
+        assert!(fragmented_incremental(&sched).defrag.is_none());
+    }
+
+    #[test]
+    fn defrag_keeps_each_tasks_affinity() {
+        let sched = scheduler_with(&[("node01", vec![2, 3])]).with_defrag_target(0.6);
+        let pinned = [CpuAffinity::Pinned(1 << 2), CpuAffinity::Pinned(1 << 3)];
+        let result = fragmented_incremental_with(&sched, pinned, &bfd());
+        assert!(result.defrag.is_none(), "pinned tasks cannot be stacked");
+
+        // Only t2 pinned: t1 moves onto t2's CPU.
+        let pinned = [CpuAffinity::Any, CpuAffinity::Pinned(1 << 3)];
+        let result = fragmented_incremental_with(&sched, pinned, &bfd());
+        let s = result.defrag.expect("suggestion expected");
+        assert!(s.proposed["node01"].iter().all(|t| t.assigned_cpu == 3));
+    }
+
+    #[test]
+    fn defrag_uses_the_options_threshold() {
+        let sched = scheduler_with(&[("node01", vec![2, 3])]).with_defrag_target(0.6);
+        let opts = bfd().with_cpu_utilization_threshold(0.95);
+        let result = fragmented_incremental_with(&sched, [CpuAffinity::Any; 2], &opts);
+
+        let s = result.defrag.expect("suggestion expected");
+        assert!((s.current_largest_placeable - 0.55).abs() < 1e-9);
+        assert!((s.optimised_largest_placeable - 0.95).abs() < 1e-9);
+    }
+
+    #[test]
+    fn no_defrag_when_current_already_meets_target() {
+        let sched = scheduler_with(&[("node01", vec![2, 3])]).with_defrag_target(0.4);
+        assert!(fragmented_incremental(&sched).defrag.is_none());
+    }
+}
@@ -0,0 +1,311 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! End-to-end latency of a processing chain on one CPU.
+//!
+//! [`rta`](super::rta) checks each task against its own deadline.  When
+//! tasks form a chain (sensor → fusion → planner), what matters is the time
+//! from the first member's release to the last member's completion.
+//!
+//! # Model
+//!
+//! Members run on one `(node, cpu)` and each reads its predecessor's output
+//! when it is released (time-triggered, as Timpani-N arms one timer per
+//! task).  Starting at the first member's release offset `O_1`:
+//!
+//! $$t_1 = O_1 + R_1, \qquad
+//!   t_i = \min\{\, O_i + kT_i \ge t_{i-1} : k \in \mathbb{N} \,\} + R_i$$
+//!
+//! and the latency is `t_n − O_1`, with `R_i` the worst-case response time
+//! from RTA (blocking included) and `O_i` the release offset, staggered or
+//! not.  A member without a response time (it misses its own deadline)
+//! leaves the chain without a bound.
+//!
+//! Chains are declared with the workload ([`TaskChain`], `SchedInfo.chains`).
+//! Each run analyses them after RTA ([`analyse_chains`]) and warns about any
+//! over its deadline or not analysable; the workload summary reports them.
+
+use thiserror::Error;
+use tracing::warn;
+
+use super::rta::{blocking_time, response_time};
+use crate::task::{Micros, Nanos, NodeSchedMap, SchedTask};
+use crate::units::fmt_duration_ns;
+
+// ── Declaration ───────────────────────────────────────────────────────────────
+
+/// A processing chain declared with a workload.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TaskChain {
+    pub name: String,
+    /// Member task names, in precedence order.
+    pub tasks: Vec<String>,
+    /// Longest allowed release-to-completion latency.
+    pub end_to_end_deadline: Micros,
+}
+
+// ── Results ───────────────────────────────────────────────────────────────────
+
+/// End-to-end outcome of one chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainResult {
+    pub node: String,
+    pub cpu: u32,
+    /// Worst-case release-to-completion latency, or `None` if a member has
+    /// no response-time bound.
+    pub latency: Option<Nanos>,
+    pub deadline: Nanos,
+}
+
+impl ChainResult {
+    pub fn meets_deadline(&self) -> bool {
+        self.latency.is_some_and(|l| l <= self.deadline)
+    }
+}
+
+/// Why a chain could not be analysed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ChainError {
+    #[error("chain is empty")]
+    Empty,
+
+    #[error("chain member '{0}' is not in the schedule")]
+    UnknownTask(String),
+
+    #[error("chain members are on different CPUs ({first} and {other})")]
+    SplitAcrossCpus { first: String, other: String },
+}
+
+/// Outcome of one declared chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainReport {
+    pub name: String,
+    pub deadline: Micros,
+    pub outcome: Result<ChainResult, ChainError>,
+}
+
+impl ChainReport {
+    /// Whether the chain was analysed and meets its deadline.
+    pub fn meets_deadline(&self) -> bool {
+        self.outcome.as_ref().is_ok_and(ChainResult::meets_deadline)
+    }
+}
+
+// ── Analysis ──────────────────────────────────────────────────────────────────
+
+/// [`chain_latency`] of every chain in `chains`, in order.
+pub fn analyse_chains(schedule: &NodeSchedMap, chains: &[TaskChain]) -> Vec<ChainReport> {
+    chains
+        .iter()
+        .map(|c| {
+            let members: Vec<&str> = c.tasks.iter().map(String::as_str).collect();
+            ChainReport {
+                name: c.name.clone(),
+                deadline: c.end_to_end_deadline,
+                outcome: chain_latency(schedule, &members, c.end_to_end_deadline),
+            }
+        })
+        .collect()
+}
+
+/// Log a warning for each chain in `reports` that misses its deadline or
+/// could not be analysed.
+pub(super) fn log_report(reports: &[ChainReport]) {
+    for r in reports {
+        match &r.outcome {
+            Ok(c) if c.meets_deadline() => {}
+            Ok(c) => warn!(
+                chain    = %r.name,
+                node     = %c.node,
+                cpu      = c.cpu,
+                latency  = %c.latency.map_or("unbounded".into(), |l| fmt_duration_ns(l.as_u64())),
+                deadline = %fmt_duration_ns(c.deadline.as_u64()),
+                "chain: worst-case end-to-end latency exceeds deadline"
+            ),
+            Err(e) => warn!(chain = %r.name, error = %e, "chain: cannot be analysed"),
+        }
+    }
+}
+
+/// Worst-case latency of `members`, in precedence order, against
+/// `deadline` (see the module docs).
+pub fn chain_latency(
+    schedule: &NodeSchedMap,
+    members: &[&str],
+    deadline: Micros,
+) -> Result<ChainResult, ChainError> {
+    let mut placed = Vec::with_capacity(members.len());
+    for name in members {
+        let (node, task) = schedule
+            .iter()
+            .find_map(|(node, tasks)| tasks.iter().find(|t| t.name == *name).map(|t| (node, t)))
+            .ok_or_else(|| ChainError::UnknownTask(name.to_string()))?;
+        placed.push((node, task));
+    }
+    let &(node, first) = placed.first().ok_or(ChainError::Empty)?;
+    if let Some((_, other)) = placed
+        .iter()
+        .find(|(n, t)| *n != node || t.assigned_cpu != first.assigned_cpu)
+    {
+        return Err(ChainError::SplitAcrossCpus {
+            first: first.name.clone(),
+            other: other.name.clone(),
+        });
+    }
+
+    let cpu_tasks: Vec<&SchedTask> = schedule[node]
+        .iter()
+        .filter(|t| t.assigned_cpu == first.assigned_cpu && !t.period_ns.is_zero())
+        .collect();
+    let response = |t: &SchedTask| response_time(t, &cpu_tasks, blocking_time(t, &cpu_tasks));
+
+    let start = offset(first);
+    let mut done = Some(start);
+    for (i, (_, task)) in placed.iter().enumerate() {
+        done = done.and_then(|at| {
+            let release = if i == 0 {
+                start
+            } else {
+                next_release(task, at)
+            };
+            response(task).map(|r| release.saturating_add(r.as_u64()))
+        });
+    }
+    let latency = done.map(|done| Nanos(done - start));
+
+    Ok(ChainResult {
+        node: node.clone(),
+        cpu: first.assigned_cpu,
+        latency,
+        deadline: deadline.saturating_to_nanos(),
+    })
+}
+
+/// Release offset of `t`, in nanoseconds.
+fn offset(t: &SchedTask) -> u64 {
+    Micros(u64::try_from(t.release_time_us).unwrap_or(0))
+        .saturating_to_nanos()
+        .as_u64()
+}
+
+/// First release of `t` at or after `at`.
+fn next_release(t: &SchedTask, at: u64) -> u64 {
+    let first = offset(t);
+    let period = t.period_ns.as_u64();
+    if at <= first || period == 0 {
+        return first;
+    }
+    first.saturating_add((at - first).div_ceil(period).saturating_mul(period))
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::SchedPolicy;
+
+    fn task(name: &str, prio: i32, runtime_us: u64, release_us: i32) -> SchedTask {
+        SchedTask {
+            name: name.into(),
+            assigned_node: "n1".into(),
+            assigned_cpu: 0,
+            policy: SchedPolicy::Fifo,
+            priority: prio,
+            period_ns: Micros(10_000).saturating_to_nanos(),
+            runtime_ns: Micros(runtime_us).saturating_to_nanos(),
+            deadline_ns: Micros(10_000).saturating_to_nanos(),
+            release_time_us: release_us,
+            max_dmiss: 3,
+            shared_resources: Vec::new(),
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    #[test]
+    fn per_task_deadlines_met_but_chain_too_slow() {
+        // All released together: fusion reads before sensor finishes and
+        // waits a whole period.  R = 2, 5 ms → 10 + 5 = 15 ms end to end.
+        let map: NodeSchedMap = [(
+            "n1".to_string(),
+            vec![task("sensor", 50, 2_000, 0), task("fusion", 40, 3_000, 0)],
+        )]
+        .into();
+        let result = chain_latency(&map, &["sensor", "fusion"], Micros(12_000)).unwrap();
+        assert_eq!(result.latency, Some(Micros(15_000).saturating_to_nanos()));
+        assert!(!result.meets_deadline());
+
+        // Staggered so fusion is released after sensor's worst case: 3 + 5.
+        let map: NodeSchedMap = [(
+            "n1".to_string(),
+            vec![
+                task("sensor", 50, 2_000, 0),
+                task("fusion", 40, 3_000, 3_000),
+            ],
+        )]
+        .into();
+        let result = chain_latency(&map, &["sensor", "fusion"], Micros(12_000)).unwrap();
+        assert_eq!(result.latency, Some(Micros(8_000).saturating_to_nanos()));
+        assert!(result.meets_deadline());
+    }
+
+    #[test]
+    fn members_must_share_a_cpu() {
+        let mut other = task("fusion", 40, 3_000, 0);
+        other.assigned_cpu = 1;
+        let map: NodeSchedMap =
+            [("n1".to_string(), vec![task("sensor", 50, 2_000, 0), other])].into();
+        assert_eq!(
+            chain_latency(&map, &["sensor", "fusion"], Micros(1)),
+            Err(ChainError::SplitAcrossCpus {
+                first: "sensor".into(),
+                other: "fusion".into()
+            })
+        );
+        assert_eq!(
+            chain_latency(&map, &["sensor", "gone"], Micros(1)),
+            Err(ChainError::UnknownTask("gone".into()))
+        );
+        assert_eq!(chain_latency(&map, &[], Micros(1)), Err(ChainError::Empty));
+    }
+
+    #[test]
+    fn declared_chains_are_analysed_in_order() {
+        let map: NodeSchedMap = [(
+            "n1".to_string(),
+            vec![task("sensor", 50, 2_000, 0), task("fusion", 40, 3_000, 0)],
+        )]
+        .into();
+        let chain = |name: &str, tasks: &[&str], deadline_us| TaskChain {
+            name: name.into(),
+            tasks: tasks.iter().map(|t| t.to_string()).collect(),
+            end_to_end_deadline: Micros(deadline_us),
+        };
+        let reports = analyse_chains(
+            &map,
+            &[
+                chain("slow", &["sensor", "fusion"], 12_000),
+                chain("fast", &["sensor"], 12_000),
+                chain("broken", &["sensor", "gone"], 12_000),
+            ],
+        );
+        let names: Vec<&str> = reports.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["slow", "fast", "broken"]);
+        assert!(!reports[0].meets_deadline());
+        assert!(reports[1].meets_deadline());
+        assert!(!reports[2].meets_deadline());
+        assert_eq!(
+            reports[2].outcome,
+            Err(ChainError::UnknownTask("gone".into()))
+        );
+    }
+}
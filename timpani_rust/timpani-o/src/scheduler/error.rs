@@ -21,16 +21,148 @@ SPDX-License-Identifier: MIT
 //!
 //! **Do not** replace these with `anyhow::Error` in production paths — the
 //! structured variants are intentional.
+//!
+//! Every variant of both enums also maps to a stable numeric [`ErrorCode`]
+//! so Pullpiri can branch on the error kind without parsing messages.
+
+use std::fmt;
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::metadata::MetadataError;
+use crate::naming::{NameError, NameKind};
+use crate::task::SchedTaskConversionError;
+use crate::units::fmt_duration_us;
+
+// ── Error codes ───────────────────────────────────────────────────────────────
+
+/// Stable, machine-readable error code.
+///
+/// A number, once assigned, **never changes meaning** — retired variants
+/// leave a gap.  New variants take the next free number in their range.
+///
+/// | Range     | Owner                                  |
+/// |-----------|----------------------------------------|
+/// | 1000–1099 | [`SchedulerError`], [`TaskProblem`] (Timpani-O) |
+/// | 1100–1199 | [`AdmissionReason`] (Timpani-O)        |
+/// | 1200–1999 | reserved for future Timpani-O errors   |
+/// | 2000–2999 | reserved for Timpani-N errors          |
+///
+/// `0` is never used: on the wire it means "no error".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u32)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    NoTasks = 1001,
+    ConfigNotLoaded = 1002,
+    UnknownAlgorithm = 1003,
+    InvalidThreshold = 1004,
+    MissingWorkloadId = 1005,
+    MissingTargetNode = 1006,
+    AdmissionRejected = 1007,
+    NoSchedulableNode = 1008,
+    ClusterCapacityExceeded = 1009,
+    NoAllowedNodes = 1010,
+    TargetNodeNotAllowed = 1011,
+    InvalidEpsilon = 1012,
+    InvalidWcetScaling = 1013,
+    SimulatedDeadlineMiss = 1014,
+    InvalidName = 1015,
+    InvalidMetadata = 1016,
+    UnknownAdmissionOverride = 1017,
+    AdmissionOverridesDisabled = 1018,
+    InvalidTask = 1019,
+    UnknownPriorityClass = 1020,
+    InvalidTtl = 1021,
+    PinnedCpusUnavailable = 1022,
+    UnknownPriorityOrdering = 1023,
+    TooManyJobs = 1024,
+    InvalidTasks = 1025,
+    /// Only a [`TaskProblem`]; no `SchedulerError` has it.
+    DuplicateTaskName = 1026,
+    TooManyTasks = 1027,
+
+    NodeNotFound = 1101,
+    InsufficientMemory = 1102,
+    CpuAffinityUnavailable = 1103,
+    CpuUtilizationExceeded = 1104,
+    NoAvailableCpu = 1105,
+    RtPrivilegesMissing = 1106,
+    WorkloadCountExceeded = 1107,
+    NodeCordoned = 1108,
+    CpuExclusivelyReserved = 1109,
+}
+
+impl ErrorCode {
+    /// Numeric value, as sent on the wire.
+    pub const fn as_u32(self) -> u32 {
+        self as u32
+    }
+
+    /// Symbolic name (`TIMPANI_E_…`).
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::NoTasks => "TIMPANI_E_NO_TASKS",
+            ErrorCode::ConfigNotLoaded => "TIMPANI_E_CONFIG_NOT_LOADED",
+            ErrorCode::UnknownAlgorithm => "TIMPANI_E_UNKNOWN_ALGORITHM",
+            ErrorCode::InvalidThreshold => "TIMPANI_E_INVALID_THRESHOLD",
+            ErrorCode::MissingWorkloadId => "TIMPANI_E_MISSING_WORKLOAD_ID",
+            ErrorCode::MissingTargetNode => "TIMPANI_E_MISSING_TARGET_NODE",
+            ErrorCode::AdmissionRejected => "TIMPANI_E_ADMISSION_REJECTED",
+            ErrorCode::NoSchedulableNode => "TIMPANI_E_NO_SCHEDULABLE_NODE",
+            ErrorCode::ClusterCapacityExceeded => "TIMPANI_E_CLUSTER_CAPACITY_EXCEEDED",
+            ErrorCode::NoAllowedNodes => "TIMPANI_E_NO_ALLOWED_NODES",
+            ErrorCode::TargetNodeNotAllowed => "TIMPANI_E_TARGET_NODE_NOT_ALLOWED",
+            ErrorCode::InvalidEpsilon => "TIMPANI_E_INVALID_EPSILON",
+            ErrorCode::InvalidWcetScaling => "TIMPANI_E_INVALID_WCET_SCALING",
+            ErrorCode::SimulatedDeadlineMiss => "TIMPANI_E_SIMULATED_DEADLINE_MISS",
+            ErrorCode::InvalidName => "TIMPANI_E_INVALID_NAME",
+            ErrorCode::InvalidMetadata => "TIMPANI_E_INVALID_METADATA",
+            ErrorCode::UnknownAdmissionOverride => "TIMPANI_E_UNKNOWN_ADMISSION_OVERRIDE",
+            ErrorCode::AdmissionOverridesDisabled => "TIMPANI_E_ADMISSION_OVERRIDES_DISABLED",
+            ErrorCode::InvalidTask => "TIMPANI_E_INVALID_TASK",
+            ErrorCode::UnknownPriorityClass => "TIMPANI_E_UNKNOWN_PRIORITY_CLASS",
+            ErrorCode::InvalidTtl => "TIMPANI_E_INVALID_TTL",
+            ErrorCode::PinnedCpusUnavailable => "TIMPANI_E_PINNED_CPUS_UNAVAILABLE",
+            ErrorCode::UnknownPriorityOrdering => "TIMPANI_E_UNKNOWN_PRIORITY_ORDERING",
+            ErrorCode::TooManyJobs => "TIMPANI_E_TOO_MANY_JOBS",
+            ErrorCode::InvalidTasks => "TIMPANI_E_INVALID_TASKS",
+            ErrorCode::DuplicateTaskName => "TIMPANI_E_DUPLICATE_TASK_NAME",
+            ErrorCode::TooManyTasks => "TIMPANI_E_TOO_MANY_TASKS",
+            ErrorCode::NodeNotFound => "TIMPANI_E_NODE_NOT_FOUND",
+            ErrorCode::InsufficientMemory => "TIMPANI_E_INSUFFICIENT_MEMORY",
+            ErrorCode::CpuAffinityUnavailable => "TIMPANI_E_CPU_AFFINITY_UNAVAILABLE",
+            ErrorCode::CpuUtilizationExceeded => "TIMPANI_E_CPU_UTILIZATION_EXCEEDED",
+            ErrorCode::NoAvailableCpu => "TIMPANI_E_NO_AVAILABLE_CPU",
+            ErrorCode::RtPrivilegesMissing => "TIMPANI_E_RT_PRIVILEGES_MISSING",
+            ErrorCode::WorkloadCountExceeded => "TIMPANI_E_WORKLOAD_COUNT_EXCEEDED",
+            ErrorCode::NodeCordoned => "TIMPANI_E_NODE_CORDONED",
+            ErrorCode::CpuExclusivelyReserved => "TIMPANI_E_CPU_EXCLUSIVELY_RESERVED",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.as_str(), self.as_u32())
+    }
+}
+
 // ── Admission control ─────────────────────────────────────────────────────────
 
 /// Detailed reason why a task was rejected during admission control.
 ///
 /// Carried inside [`SchedulerError::AdmissionRejected`] so the caller always
 /// knows both *which* task/node pair failed and *why*.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Utilisations are stored in fixed-point permille (`1000` = one full CPU)
+/// rather than `f64`, so a reason is `Eq + Hash` — usable as a map key for
+/// rejection counts — and serialises to the same JSON every time
+/// (`{"kind": "cpu_utilization_exceeded", "cpu": 2, ...}`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum AdmissionReason {
     /// The node name is not present in the loaded [`NodeConfigManager`].
     ///
@@ -48,17 +180,137 @@ pub enum AdmissionReason {
     CpuAffinityUnavailable { requested_cpu: u32 },
 
     /// Assigning the task to this CPU would push its utilisation above the
-    /// `CPU_UTILIZATION_THRESHOLD`.
+    /// `CPU_UTILIZATION_THRESHOLD`.  Build it with
+    /// [`cpu_utilization_exceeded`](AdmissionReason::cpu_utilization_exceeded).
     CpuUtilizationExceeded {
         cpu: u32,
-        current: f64,
-        added: f64,
-        threshold: f64,
+        current_permille: u32,
+        added_permille: u32,
+        threshold_permille: u32,
     },
 
     /// The node has no CPU with enough headroom to accommodate the task, even
     /// after considering all CPUs.
     NoAvailableCpu,
+
+    /// The task uses a real-time policy but the node reported that it lacks
+    /// the privileges to apply one (`CAP_SYS_NICE` / `RLIMIT_RTPRIO`).
+    RtPrivilegesMissing,
+
+    /// The node already hosts its configured `max_workloads` distinct
+    /// workloads and the task belongs to none of them.
+    WorkloadCountExceeded { limit: usize },
+
+    /// The node is cordoned: it keeps its tasks but takes no new ones.
+    NodeCordoned,
+
+    /// The only CPUs with room for the task are held exclusively by
+    /// `workload` — or, for a task of an exclusive workload, are shared
+    /// with `workload` (see [`crate::scheduler::workloads`]).
+    CpuExclusivelyReserved { workload: String },
+}
+
+/// Fieldless [`AdmissionReason`] discriminant, for counting rejections by
+/// kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum AdmissionReasonKind {
+    NodeNotFound,
+    InsufficientMemory,
+    CpuAffinityUnavailable,
+    CpuUtilizationExceeded,
+    NoAvailableCpu,
+    RtPrivilegesMissing,
+    WorkloadCountExceeded,
+    NodeCordoned,
+    CpuExclusivelyReserved,
+}
+
+impl AdmissionReasonKind {
+    /// Snake-case name, as in the serialised [`AdmissionReason`].
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AdmissionReasonKind::NodeNotFound => "node_not_found",
+            AdmissionReasonKind::InsufficientMemory => "insufficient_memory",
+            AdmissionReasonKind::CpuAffinityUnavailable => "cpu_affinity_unavailable",
+            AdmissionReasonKind::CpuUtilizationExceeded => "cpu_utilization_exceeded",
+            AdmissionReasonKind::NoAvailableCpu => "no_available_cpu",
+            AdmissionReasonKind::RtPrivilegesMissing => "rt_privileges_missing",
+            AdmissionReasonKind::WorkloadCountExceeded => "workload_count_exceeded",
+            AdmissionReasonKind::NodeCordoned => "node_cordoned",
+            AdmissionReasonKind::CpuExclusivelyReserved => "cpu_exclusively_reserved",
+        }
+    }
+}
+
+impl fmt::Display for AdmissionReasonKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `fraction` of a CPU in permille, rounded to the nearest; negative and
+/// NaN give 0, overlarge values saturate.
+pub fn to_permille(fraction: f64) -> u32 {
+    // `as` saturates and maps NaN to 0.
+    (fraction * 1000.0).round() as u32
+}
+
+/// `permille` as a percentage with one decimal, e.g. `85.5%`.
+fn fmt_permille(permille: u32) -> String {
+    format!("{}.{}%", permille / 10, permille % 10)
+}
+
+impl AdmissionReason {
+    /// [`CpuUtilizationExceeded`](AdmissionReason::CpuUtilizationExceeded)
+    /// from utilisations given as CPU fractions.
+    pub fn cpu_utilization_exceeded(cpu: u32, current: f64, added: f64, threshold: f64) -> Self {
+        AdmissionReason::CpuUtilizationExceeded {
+            cpu,
+            current_permille: to_permille(current),
+            added_permille: to_permille(added),
+            threshold_permille: to_permille(threshold),
+        }
+    }
+
+    /// The reason's variant without its details.
+    pub fn kind(&self) -> AdmissionReasonKind {
+        match self {
+            AdmissionReason::NodeNotFound { .. } => AdmissionReasonKind::NodeNotFound,
+            AdmissionReason::InsufficientMemory { .. } => AdmissionReasonKind::InsufficientMemory,
+            AdmissionReason::CpuAffinityUnavailable { .. } => {
+                AdmissionReasonKind::CpuAffinityUnavailable
+            }
+            AdmissionReason::CpuUtilizationExceeded { .. } => {
+                AdmissionReasonKind::CpuUtilizationExceeded
+            }
+            AdmissionReason::NoAvailableCpu => AdmissionReasonKind::NoAvailableCpu,
+            AdmissionReason::RtPrivilegesMissing => AdmissionReasonKind::RtPrivilegesMissing,
+            AdmissionReason::WorkloadCountExceeded { .. } => {
+                AdmissionReasonKind::WorkloadCountExceeded
+            }
+            AdmissionReason::NodeCordoned => AdmissionReasonKind::NodeCordoned,
+            AdmissionReason::CpuExclusivelyReserved { .. } => {
+                AdmissionReasonKind::CpuExclusivelyReserved
+            }
+        }
+    }
+
+    /// Stable code for this reason.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AdmissionReason::NodeNotFound { .. } => ErrorCode::NodeNotFound,
+            AdmissionReason::InsufficientMemory { .. } => ErrorCode::InsufficientMemory,
+            AdmissionReason::CpuAffinityUnavailable { .. } => ErrorCode::CpuAffinityUnavailable,
+            AdmissionReason::CpuUtilizationExceeded { .. } => ErrorCode::CpuUtilizationExceeded,
+            AdmissionReason::NoAvailableCpu => ErrorCode::NoAvailableCpu,
+            AdmissionReason::RtPrivilegesMissing => ErrorCode::RtPrivilegesMissing,
+            AdmissionReason::WorkloadCountExceeded { .. } => ErrorCode::WorkloadCountExceeded,
+            AdmissionReason::NodeCordoned => ErrorCode::NodeCordoned,
+            AdmissionReason::CpuExclusivelyReserved { .. } => ErrorCode::CpuExclusivelyReserved,
+        }
+    }
 }
 
 impl std::fmt::Display for AdmissionReason {
@@ -85,27 +337,184 @@ impl std::fmt::Display for AdmissionReason {
 
             AdmissionReason::CpuUtilizationExceeded {
                 cpu,
-                current,
-                added,
-                threshold,
+                current_permille,
+                added_permille,
+                threshold_permille,
             } => write!(
                 f,
-                "CPU {} utilization would be {:.1}% + {:.1}% = {:.1}% (threshold {:.0}%)",
+                "CPU {} utilization would be {} + {} = {} (threshold {})",
                 cpu,
-                current * 100.0,
-                added * 100.0,
-                (current + added) * 100.0,
-                threshold * 100.0,
+                fmt_permille(*current_permille),
+                fmt_permille(*added_permille),
+                fmt_permille(current_permille.saturating_add(*added_permille)),
+                fmt_permille(*threshold_permille),
             ),
 
             AdmissionReason::NoAvailableCpu => write!(
                 f,
                 "no CPU on this node can accommodate the task utilization"
             ),
+
+            AdmissionReason::RtPrivilegesMissing => {
+                write!(f, "node lacks privileges for real-time scheduling policies")
+            }
+
+            AdmissionReason::WorkloadCountExceeded { limit } => {
+                write!(f, "node already hosts its limit of {} workloads", limit)
+            }
+
+            AdmissionReason::NodeCordoned => write!(f, "node is cordoned"),
+
+            AdmissionReason::CpuExclusivelyReserved { workload } => write!(
+                f,
+                "CPU exclusivity conflicts with workload '{}' on every CPU with room",
+                workload
+            ),
         }
     }
 }
 
+/// A task bound to its `target_node` whose pinned CPUs that node does not
+/// have, found by request validation (see [`super::pinned`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedCpuConflict {
+    pub task: String,
+    pub node: String,
+    /// CPUs of the task's affinity mask, ascending.
+    pub requested: Vec<u32>,
+    /// The node's configured CPUs.
+    pub node_cpus: Vec<u32>,
+}
+
+impl fmt::Display for PinnedCpuConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "task '{}' is pinned to CPU(s) {:?} but node '{}' has CPUs {:?}",
+            self.task, self.requested, self.node, self.node_cpus
+        )
+    }
+}
+
+fn fmt_conflicts(conflicts: &[PinnedCpuConflict]) -> String {
+    conflicts
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// One structural problem of one task, found by
+/// [`validate_tasks`](super::validate::validate_tasks) before any placement.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum TaskProblem {
+    /// No `workload_id`, which `target_node_priority` requires.
+    #[error("task '{task}' has no workload_id — required by target_node_priority algorithm")]
+    MissingWorkloadId { task: String },
+
+    /// No `target_node`, which `target_node_priority` requires.
+    #[error("task '{task}' has no target_node — required by target_node_priority algorithm")]
+    MissingTargetNode { task: String },
+
+    /// A `wcet_scaling` factor is zero, negative or not finite.
+    #[error("task '{task}' has invalid wcet_scaling {factor} for '{architecture}' — must be > 0")]
+    InvalidWcetScaling {
+        task: String,
+        architecture: String,
+        factor: f64,
+    },
+
+    /// A field is outside its range.
+    #[error(transparent)]
+    Invalid(SchedTaskConversionError),
+
+    /// `count` tasks of the workload share the name `task`.
+    #[error("task name '{task}' is used by {count} tasks — names must be unique")]
+    DuplicateName { task: String, count: usize },
+}
+
+impl TaskProblem {
+    /// The task the problem is about.
+    pub fn task(&self) -> &str {
+        match self {
+            TaskProblem::MissingWorkloadId { task }
+            | TaskProblem::MissingTargetNode { task }
+            | TaskProblem::InvalidWcetScaling { task, .. }
+            | TaskProblem::DuplicateName { task, .. } => task,
+            TaskProblem::Invalid(e) => e.task(),
+        }
+    }
+
+    /// Stable code for this problem: that of the `SchedulerError` it would
+    /// have been on its own.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            TaskProblem::MissingWorkloadId { .. } => ErrorCode::MissingWorkloadId,
+            TaskProblem::MissingTargetNode { .. } => ErrorCode::MissingTargetNode,
+            TaskProblem::InvalidWcetScaling { .. } => ErrorCode::InvalidWcetScaling,
+            TaskProblem::Invalid(_) => ErrorCode::InvalidTask,
+            TaskProblem::DuplicateName { .. } => ErrorCode::DuplicateTaskName,
+        }
+    }
+}
+
+/// The problems [`validate_tasks`](super::validate::validate_tasks) found:
+/// at most [`max_task_problems`](crate::limits::InputLimits::max_task_problems)
+/// of them listed, the rest only counted.  Derefs to the listed ones.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskProblems {
+    pub problems: Vec<TaskProblem>,
+    /// Problems found beyond the cap and not listed.
+    pub omitted: usize,
+}
+
+impl TaskProblems {
+    /// Problems found, listed or not.
+    pub fn total(&self) -> usize {
+        self.problems.len() + self.omitted
+    }
+
+    /// Some problems were found but not listed.
+    pub fn is_truncated(&self) -> bool {
+        self.omitted > 0
+    }
+}
+
+impl std::ops::Deref for TaskProblems {
+    type Target = [TaskProblem];
+
+    fn deref(&self) -> &[TaskProblem] {
+        &self.problems
+    }
+}
+
+impl From<Vec<TaskProblem>> for TaskProblems {
+    fn from(problems: Vec<TaskProblem>) -> Self {
+        Self {
+            problems,
+            omitted: 0,
+        }
+    }
+}
+
+/// The listed problems joined by `; `, then `and N more` if truncated.
+impl fmt::Display for TaskProblems {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let listed = self
+            .problems
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        f.write_str(&listed)?;
+        if self.is_truncated() {
+            write!(f, "; and {} more", self.omitted)?;
+        }
+        Ok(())
+    }
+}
+
 // ── Top-level scheduler errors ────────────────────────────────────────────────
 
 /// Top-level error type returned by
@@ -118,11 +527,24 @@ impl std::fmt::Display for AdmissionReason {
 /// |---|---|
 /// | `NoTasks` | `InvalidArgument` |
 /// | `ConfigNotLoaded` | `FailedPrecondition` |
-/// | `UnknownAlgorithm` | `InvalidArgument` |
+/// | `UnknownAlgorithm` / `InvalidThreshold` / `InvalidEpsilon` | `InvalidArgument` |
 /// | `MissingWorkloadId` / `MissingTargetNode` | `InvalidArgument` |
+/// | `InvalidWcetScaling` | `InvalidArgument` |
+/// | `InvalidName` / `InvalidMetadata` | `InvalidArgument` |
+/// | `InvalidTask` | `InvalidArgument` |
+/// | `NoAllowedNodes` / `TargetNodeNotAllowed` | `InvalidArgument` |
+/// | `UnknownAdmissionOverride` / `AdmissionOverridesDisabled` | `InvalidArgument` |
+/// | `UnknownPriorityClass` / `InvalidTtl` | `InvalidArgument` |
+/// | `UnknownPriorityOrdering` | `InvalidArgument` |
+/// | `PinnedCpusUnavailable` | `InvalidArgument` |
+/// | `InvalidTasks` / `TooManyTasks` | `InvalidArgument` |
+/// | `ClusterCapacityExceeded` | `ResourceExhausted` |
 /// | `AdmissionRejected` | `ResourceExhausted` |
 /// | `NoSchedulableNode` | `ResourceExhausted` |
+/// | `SimulatedDeadlineMiss` | `ResourceExhausted` |
+/// | `TooManyJobs` | `ResourceExhausted` |
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum SchedulerError {
     /// `schedule()` was called with an empty task list.
     #[error("no tasks provided — task list is empty")]
@@ -135,9 +557,58 @@ pub enum SchedulerError {
     ConfigNotLoaded,
 
     /// The `algorithm` string passed to `schedule()` is not recognised.
-    #[error("unknown scheduling algorithm: '{0}' (valid: target_node_priority, least_loaded, best_fit_decreasing)")]
+    #[error("unknown scheduling algorithm: '{0}' (valid: target_node_priority, least_loaded, best_fit_decreasing, randomized_spread)")]
     UnknownAlgorithm(String),
 
+    /// The per-CPU utilisation threshold is outside `(0, 1]`.
+    #[error("invalid CPU utilization threshold {0} — must be in (0, 1]")]
+    InvalidThreshold(f64),
+
+    /// The utilisation comparison epsilon is negative, non-finite, or so
+    /// large it would mask a real overload.
+    #[error("invalid utilization epsilon {0} — must be in [0, 1e-3]")]
+    InvalidEpsilon(f64),
+
+    /// A request named an admission override that does not exist.
+    #[error("unknown admission override: '{0}' (valid: skip_memory, skip_threshold)")]
+    UnknownAdmissionOverride(String),
+
+    /// A request set admission overrides on an instance that does not allow
+    /// them.
+    #[error("admission overrides are not allowed on this instance")]
+    AdmissionOverridesDisabled,
+
+    /// A request named a priority class that does not exist.
+    #[error("unknown priority class: '{0}' (valid: safety, platform, best_effort)")]
+    UnknownPriorityClass(String),
+
+    /// A request named a priority ordering that does not exist.
+    #[error("unknown priority ordering: '{0}' (valid: rate_monotonic, deadline_monotonic)")]
+    UnknownPriorityOrdering(String),
+
+    /// A request set `ttl_seconds` to zero.
+    #[error("invalid workload ttl 0 s — must be at least 1 s")]
+    InvalidTtl,
+
+    /// Tasks bound to their target node are pinned to CPUs that node does
+    /// not have; every such task is listed.
+    #[error(
+        "{} pinned task(s) cannot run on their target node: {}",
+        .0.len(),
+        fmt_conflicts(.0)
+    )]
+    PinnedCpusUnavailable(Vec<PinnedCpuConflict>),
+
+    /// Tasks have structural problems (missing fields, values outside their
+    /// range, duplicate names); every problem of every task is listed, up
+    /// to the [task problem cap](crate::limits).
+    #[error("{} task problem(s): {}", .0.total(), .0)]
+    InvalidTasks(TaskProblems),
+
+    /// The workload has more tasks than the [task cap](crate::limits).
+    #[error("{count} tasks in one workload, more than the limit of {max}")]
+    TooManyTasks { count: usize, max: usize },
+
     /// A task arrived without a `workload_id` field set.
     ///
     /// Every task must carry a workload identifier — it is required by the
@@ -145,11 +616,32 @@ pub enum SchedulerError {
     #[error("task '{task}' has no workload_id — all tasks must carry a workload identifier")]
     MissingWorkloadId { task: String },
 
+    /// A workload ID or task name breaks the naming policy.
+    #[error(transparent)]
+    InvalidName(#[from] NameError),
+
+    /// A task's metadata breaks the metadata limits.
+    #[error(transparent)]
+    InvalidMetadata(#[from] MetadataError),
+
+    /// A task's timing cannot be sent to its node (too long), or it needs
+    /// more than one whole CPU.
+    #[error(transparent)]
+    InvalidTask(#[from] SchedTaskConversionError),
+
     /// A task arrived without a `target_node` field set, which is required by
     /// the `target_node_priority` algorithm.
     #[error("task '{task}' has no target_node — required by target_node_priority algorithm")]
     MissingTargetNode { task: String },
 
+    /// A task's `wcet_scaling` factor is zero, negative or not finite.
+    #[error("task '{task}' has invalid wcet_scaling {factor} for '{architecture}' — must be > 0")]
+    InvalidWcetScaling {
+        task: String,
+        architecture: String,
+        factor: f64,
+    },
+
     /// Admission control rejected a task for a specific node with a detailed
     /// reason.
     ///
@@ -163,16 +655,133 @@ pub enum SchedulerError {
         reason: AdmissionReason,
     },
 
+    /// The workload's total utilisation exceeds the headroom of every
+    /// configured CPU combined, so no placement can exist.
+    ///
+    /// Both values are in CPUs (`1.0` = one fully used CPU); `available` is
+    /// the sum of per-CPU headroom below the threshold.
+    #[error(
+        "workload needs {required:.2} CPU(s) of utilization but the cluster \
+         has only {available:.2} available"
+    )]
+    ClusterCapacityExceeded { required: f64, available: f64 },
+
+    /// None of the request's `allowed_nodes` is a configured node.
+    #[error("none of the allowed nodes {allowed:?} is configured")]
+    NoAllowedNodes { allowed: Vec<String> },
+
+    /// A task's `target_node` lies outside the request's `allowed_nodes`.
+    #[error("task '{task}' targets node '{node}', which is not in the allowed nodes {allowed:?}")]
+    TargetNodeNotAllowed {
+        task: String,
+        node: String,
+        allowed: Vec<String>,
+    },
+
     /// No node in the configuration could accept the task (all nodes either
     /// failed admission or had no headroom).
     #[error("no schedulable node found for task '{task}'")]
     NoSchedulableNode { task: String },
+
+    /// Simulating a CPU above its Liu & Layland bound found a deadline miss
+    /// (strict [`verify_with_simulation`](super::ScheduleOptions::verify_with_simulation)).
+    ///
+    /// `time_us` is the missed absolute deadline, from simulation start.
+    #[error(
+        "task '{task}' misses its deadline at {} when {node}:{cpu} is simulated",
+        fmt_duration_us(*time_us)
+    )]
+    SimulatedDeadlineMiss {
+        node: String,
+        cpu: u32,
+        task: String,
+        time_us: u64,
+    },
+
+    /// Simulating a CPU would release more jobs than the configured limit
+    /// (see [`simulate::estimate_jobs`](super::simulate::estimate_jobs)),
+    /// so it was not attempted.
+    #[error(
+        "simulating {node}:{cpu} would release {estimated} jobs, over the limit of {limit} — \
+         harmonise the task periods (make them divide one another) to shorten the \
+         hyperperiod, or raise --simulation-max-jobs"
+    )]
+    TooManyJobs {
+        node: String,
+        cpu: u32,
+        estimated: u64,
+        limit: u64,
+    },
+}
+
+impl SchedulerError {
+    /// Stable code for this error.  For `AdmissionRejected` the reason's own
+    /// code is available via [`reason`](Self::reason).
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SchedulerError::NoTasks => ErrorCode::NoTasks,
+            SchedulerError::ConfigNotLoaded => ErrorCode::ConfigNotLoaded,
+            SchedulerError::UnknownAlgorithm(_) => ErrorCode::UnknownAlgorithm,
+            SchedulerError::InvalidThreshold(_) => ErrorCode::InvalidThreshold,
+            SchedulerError::InvalidEpsilon(_) => ErrorCode::InvalidEpsilon,
+            SchedulerError::UnknownAdmissionOverride(_) => ErrorCode::UnknownAdmissionOverride,
+            SchedulerError::AdmissionOverridesDisabled => ErrorCode::AdmissionOverridesDisabled,
+            SchedulerError::UnknownPriorityClass(_) => ErrorCode::UnknownPriorityClass,
+            SchedulerError::InvalidTtl => ErrorCode::InvalidTtl,
+            SchedulerError::UnknownPriorityOrdering(_) => ErrorCode::UnknownPriorityOrdering,
+            SchedulerError::PinnedCpusUnavailable(_) => ErrorCode::PinnedCpusUnavailable,
+            SchedulerError::InvalidTasks(_) => ErrorCode::InvalidTasks,
+            SchedulerError::TooManyTasks { .. } => ErrorCode::TooManyTasks,
+            SchedulerError::MissingWorkloadId { .. } => ErrorCode::MissingWorkloadId,
+            SchedulerError::MissingTargetNode { .. } => ErrorCode::MissingTargetNode,
+            SchedulerError::InvalidWcetScaling { .. } => ErrorCode::InvalidWcetScaling,
+            SchedulerError::AdmissionRejected { .. } => ErrorCode::AdmissionRejected,
+            SchedulerError::NoSchedulableNode { .. } => ErrorCode::NoSchedulableNode,
+            SchedulerError::ClusterCapacityExceeded { .. } => ErrorCode::ClusterCapacityExceeded,
+            SchedulerError::NoAllowedNodes { .. } => ErrorCode::NoAllowedNodes,
+            SchedulerError::TargetNodeNotAllowed { .. } => ErrorCode::TargetNodeNotAllowed,
+            SchedulerError::SimulatedDeadlineMiss { .. } => ErrorCode::SimulatedDeadlineMiss,
+            SchedulerError::TooManyJobs { .. } => ErrorCode::TooManyJobs,
+            SchedulerError::InvalidName(_) => ErrorCode::InvalidName,
+            SchedulerError::InvalidMetadata(_) => ErrorCode::InvalidMetadata,
+            SchedulerError::InvalidTask(_) => ErrorCode::InvalidTask,
+        }
+    }
+
+    /// The admission reason, for `AdmissionRejected`.
+    pub fn reason(&self) -> Option<&AdmissionReason> {
+        match self {
+            SchedulerError::AdmissionRejected { reason, .. } => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// The task the error is about, if it names one.
+    pub fn task(&self) -> Option<&str> {
+        match self {
+            SchedulerError::MissingWorkloadId { task }
+            | SchedulerError::MissingTargetNode { task }
+            | SchedulerError::InvalidWcetScaling { task, .. }
+            | SchedulerError::AdmissionRejected { task, .. }
+            | SchedulerError::TargetNodeNotAllowed { task, .. }
+            | SchedulerError::SimulatedDeadlineMiss { task, .. }
+            | SchedulerError::NoSchedulableNode { task } => Some(task),
+            SchedulerError::InvalidName(e) if e.kind == NameKind::TaskName => Some(&e.name),
+            SchedulerError::InvalidMetadata(e) => Some(&e.task),
+            SchedulerError::InvalidTask(e) => Some(e.task()),
+            SchedulerError::PinnedCpusUnavailable(c) => c.first().map(|c| c.task.as_str()),
+            SchedulerError::InvalidTasks(p) => p.first().map(TaskProblem::task),
+            _ => None,
+        }
+    }
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
     use super::*;
 
     // ── AdmissionReason Display ───────────────────────────────────────────────
@@ -204,15 +813,27 @@ mod tests {
 
     #[test]
     fn admission_cpu_utilization_exceeded_display() {
-        let r = AdmissionReason::CpuUtilizationExceeded {
-            cpu: 2,
-            current: 0.85,
-            added: 0.10,
-            threshold: 0.90,
-        };
-        let s = r.to_string();
-        assert!(s.contains("CPU 2"));
-        assert!(s.contains("90")); // threshold percentage
+        let r = AdmissionReason::cpu_utilization_exceeded(2, 0.85, 0.10, 0.90);
+        assert_eq!(
+            r.to_string(),
+            "CPU 2 utilization would be 85.0% + 10.0% = 95.0% (threshold 90.0%)"
+        );
+    }
+
+    #[test]
+    fn permille_rounds_and_clamps() {
+        assert_eq!(to_permille(0.8555), 856);
+        assert_eq!(to_permille(1.0), 1000);
+        assert_eq!(to_permille(-0.2), 0);
+        assert_eq!(to_permille(f64::NAN), 0);
+        assert_eq!(to_permille(f64::INFINITY), u32::MAX);
+    }
+
+    #[test]
+    fn admission_rt_privileges_missing_display() {
+        assert!(AdmissionReason::RtPrivilegesMissing
+            .to_string()
+            .contains("real-time"));
     }
 
     #[test]
@@ -220,8 +841,263 @@ mod tests {
         assert!(!AdmissionReason::NoAvailableCpu.to_string().is_empty());
     }
 
+    // ── Error codes ───────────────────────────────────────────────────────────
+
+    /// Pins every variant to its number.  If this fails, a code changed
+    /// meaning: add a new code instead of renumbering.
+    #[test]
+    fn error_codes_are_stable() {
+        let task = || "t".to_string();
+        let scheduler: [(SchedulerError, u32); 26] = [
+            (SchedulerError::NoTasks, 1001),
+            (SchedulerError::ConfigNotLoaded, 1002),
+            (SchedulerError::UnknownAlgorithm("x".into()), 1003),
+            (SchedulerError::InvalidThreshold(2.0), 1004),
+            (SchedulerError::MissingWorkloadId { task: task() }, 1005),
+            (SchedulerError::MissingTargetNode { task: task() }, 1006),
+            (
+                SchedulerError::AdmissionRejected {
+                    task: task(),
+                    node: "n".into(),
+                    reason: AdmissionReason::NoAvailableCpu,
+                },
+                1007,
+            ),
+            (SchedulerError::NoSchedulableNode { task: task() }, 1008),
+            (
+                SchedulerError::ClusterCapacityExceeded {
+                    required: 2.0,
+                    available: 1.0,
+                },
+                1009,
+            ),
+            (SchedulerError::NoAllowedNodes { allowed: vec![] }, 1010),
+            (
+                SchedulerError::TargetNodeNotAllowed {
+                    task: task(),
+                    node: "n".into(),
+                    allowed: vec![],
+                },
+                1011,
+            ),
+            (SchedulerError::InvalidEpsilon(-1.0), 1012),
+            (
+                SchedulerError::InvalidWcetScaling {
+                    task: task(),
+                    architecture: "x86_64".into(),
+                    factor: 0.0,
+                },
+                1013,
+            ),
+            (
+                SchedulerError::SimulatedDeadlineMiss {
+                    node: "n".into(),
+                    cpu: 0,
+                    task: task(),
+                    time_us: 1,
+                },
+                1014,
+            ),
+            (
+                SchedulerError::InvalidName(NameError {
+                    kind: NameKind::TaskName,
+                    name: task(),
+                    violation: crate::naming::NameViolation::Empty,
+                }),
+                1015,
+            ),
+            (
+                SchedulerError::InvalidMetadata(MetadataError {
+                    task: task(),
+                    violation: crate::metadata::MetadataViolation::TooManyKeys { count: 2, max: 1 },
+                }),
+                1016,
+            ),
+            (SchedulerError::UnknownAdmissionOverride("x".into()), 1017),
+            (SchedulerError::AdmissionOverridesDisabled, 1018),
+            (
+                SchedulerError::InvalidTask(SchedTaskConversionError::TooLong {
+                    task: task(),
+                    field: crate::task::TimingField::Period,
+                    value: crate::task::Micros(u64::MAX),
+                    max: crate::task::DEFAULT_MAX_TASK_DURATION,
+                }),
+                1019,
+            ),
+            (SchedulerError::UnknownPriorityClass("x".into()), 1020),
+            (SchedulerError::InvalidTtl, 1021),
+            (SchedulerError::PinnedCpusUnavailable(vec![]), 1022),
+            (SchedulerError::UnknownPriorityOrdering("x".into()), 1023),
+            (
+                SchedulerError::TooManyJobs {
+                    node: "n".into(),
+                    cpu: 0,
+                    estimated: 2,
+                    limit: 1,
+                },
+                1024,
+            ),
+            (SchedulerError::InvalidTasks(TaskProblems::default()), 1025),
+            (SchedulerError::TooManyTasks { count: 2, max: 1 }, 1027),
+        ];
+        for (err, code) in scheduler {
+            assert_eq!(err.code().as_u32(), code, "{err}");
+        }
+
+        let admission: [(AdmissionReason, u32); 9] = [
+            (AdmissionReason::NodeNotFound { node: "n".into() }, 1101),
+            (
+                AdmissionReason::InsufficientMemory {
+                    required_mb: 2,
+                    available_mb: 1,
+                },
+                1102,
+            ),
+            (
+                AdmissionReason::CpuAffinityUnavailable { requested_cpu: 0 },
+                1103,
+            ),
+            (
+                AdmissionReason::cpu_utilization_exceeded(0, 0.5, 0.5, 0.9),
+                1104,
+            ),
+            (AdmissionReason::NoAvailableCpu, 1105),
+            (AdmissionReason::RtPrivilegesMissing, 1106),
+            (AdmissionReason::WorkloadCountExceeded { limit: 2 }, 1107),
+            (AdmissionReason::NodeCordoned, 1108),
+            (
+                AdmissionReason::CpuExclusivelyReserved {
+                    workload: "w".into(),
+                },
+                1109,
+            ),
+        ];
+        for (reason, code) in admission {
+            assert_eq!(reason.code().as_u32(), code, "{reason}");
+        }
+
+        assert_eq!(
+            TaskProblem::DuplicateName {
+                task: task(),
+                count: 2
+            }
+            .code()
+            .as_u32(),
+            1026
+        );
+        assert_eq!(ErrorCode::NoTasks.as_str(), "TIMPANI_E_NO_TASKS");
+        assert_eq!(
+            ErrorCode::NoAvailableCpu.to_string(),
+            "TIMPANI_E_NO_AVAILABLE_CPU (1105)"
+        );
+    }
+
+    /// One of each variant.
+    fn every_reason() -> [AdmissionReason; 9] {
+        [
+            AdmissionReason::NodeNotFound { node: "n".into() },
+            AdmissionReason::InsufficientMemory {
+                required_mb: 2,
+                available_mb: 1,
+            },
+            AdmissionReason::CpuAffinityUnavailable { requested_cpu: 3 },
+            AdmissionReason::cpu_utilization_exceeded(1, 0.6, 0.35, 0.9),
+            AdmissionReason::NoAvailableCpu,
+            AdmissionReason::RtPrivilegesMissing,
+            AdmissionReason::WorkloadCountExceeded { limit: 2 },
+            AdmissionReason::NodeCordoned,
+            AdmissionReason::CpuExclusivelyReserved {
+                workload: "w".into(),
+            },
+        ]
+    }
+
+    // ── Serialisation and keys ────────────────────────────────────────────────
+
+    #[test]
+    fn reasons_round_trip_through_json() {
+        for reason in every_reason() {
+            let json = serde_json::to_string(&reason).unwrap();
+            assert!(
+                json.contains(&format!(r#""kind":"{}""#, reason.kind())),
+                "{json}"
+            );
+            let back: AdmissionReason = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, reason);
+            // Stable: the same reason always serialises the same way.
+            assert_eq!(serde_json::to_string(&back).unwrap(), json);
+        }
+        assert_eq!(
+            serde_json::to_string(&every_reason()[3]).unwrap(),
+            r#"{"kind":"cpu_utilization_exceeded","cpu":1,"current_permille":600,"added_permille":350,"threshold_permille":900}"#
+        );
+    }
+
+    #[test]
+    fn reasons_and_kinds_work_as_map_keys() {
+        let rejections = [
+            AdmissionReason::NoAvailableCpu,
+            AdmissionReason::cpu_utilization_exceeded(0, 0.5, 0.5, 0.9),
+            AdmissionReason::NoAvailableCpu,
+            AdmissionReason::cpu_utilization_exceeded(0, 0.5, 0.5, 0.9),
+            AdmissionReason::cpu_utilization_exceeded(1, 0.5, 0.5, 0.9),
+        ];
+        let mut by_reason: HashMap<&AdmissionReason, usize> = HashMap::new();
+        let mut by_kind: BTreeMap<AdmissionReasonKind, usize> = BTreeMap::new();
+        for r in &rejections {
+            *by_reason.entry(r).or_default() += 1;
+            *by_kind.entry(r.kind()).or_default() += 1;
+        }
+        assert_eq!(by_reason.len(), 3);
+        assert_eq!(by_reason[&AdmissionReason::NoAvailableCpu], 2);
+        assert_eq!(
+            by_reason[&AdmissionReason::cpu_utilization_exceeded(0, 0.5, 0.5, 0.9)],
+            2
+        );
+        assert_eq!(
+            by_kind,
+            BTreeMap::from([
+                (AdmissionReasonKind::CpuUtilizationExceeded, 3),
+                (AdmissionReasonKind::NoAvailableCpu, 2),
+            ])
+        );
+        let kinds: Vec<&str> = every_reason().iter().map(|r| r.kind().as_str()).collect();
+        assert_eq!(kinds.len(), 9);
+        assert!(kinds.windows(2).all(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    fn task_and_reason_accessors() {
+        let e = SchedulerError::AdmissionRejected {
+            task: "t3".into(),
+            node: "n1".into(),
+            reason: AdmissionReason::RtPrivilegesMissing,
+        };
+        assert_eq!(e.task(), Some("t3"));
+        assert_eq!(e.reason(), Some(&AdmissionReason::RtPrivilegesMissing));
+        assert_eq!(SchedulerError::NoTasks.task(), None);
+    }
+
     // ── SchedulerError Display ────────────────────────────────────────────────
 
+    #[test]
+    fn error_invalid_threshold_display() {
+        assert!(SchedulerError::InvalidThreshold(1.5)
+            .to_string()
+            .contains("1.5"));
+    }
+
+    #[test]
+    fn error_cluster_capacity_exceeded_display() {
+        let s = SchedulerError::ClusterCapacityExceeded {
+            required: 12.0,
+            available: 7.2,
+        }
+        .to_string();
+        assert!(s.contains("12.00"));
+        assert!(s.contains("7.20"));
+    }
+
     #[test]
     fn error_no_tasks_display() {
         assert!(SchedulerError::NoTasks.to_string().contains("empty"));
@@ -0,0 +1,162 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Algorithm chains: retry a whole workload with the next algorithm.
+//!
+//! [`ScheduleOptions::algorithm_chain`] lists algorithms to try in order,
+//! e.g. `target_node_priority` then `best_fit_decreasing`.  [`run_chain`]
+//! runs each link with the options' `algorithm` set to it and stops at the
+//! first that places the workload.  Every link starts from the caller's
+//! inputs: a failed link leaves nothing behind for the next one.
+//!
+//! The result records every link tried, so a caller can report which one
+//! succeeded or, when all failed, why each did.  The error returned is the
+//! last link's.  Options without a chain run `algorithm` alone.
+
+use super::{ErrorCode, SchedAlgorithm, ScheduleOptions, SchedulerError};
+
+/// One link of a chained run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainAttempt {
+    pub algorithm: SchedAlgorithm,
+    /// Code of the link's error; `None` for the link that succeeded.
+    pub error_code: Option<ErrorCode>,
+    /// The link's error message; empty for the link that succeeded.
+    pub error: String,
+}
+
+/// What [`run_chain`] did.
+#[derive(Debug)]
+pub struct ChainRun<T> {
+    /// Every link tried, in order; the last one succeeded if `result` is
+    /// `Ok`.
+    pub attempts: Vec<ChainAttempt>,
+    /// The successful link's output and the options it ran with, or the
+    /// last link's error.
+    pub result: Result<(T, ScheduleOptions), SchedulerError>,
+}
+
+impl<T> ChainRun<T> {
+    /// Index of the successful link in [`ScheduleOptions::links`].
+    pub fn link(&self) -> Option<usize> {
+        self.result.as_ref().ok().map(|_| self.attempts.len() - 1)
+    }
+}
+
+/// Call `run` with `opts` set to each of [`ScheduleOptions::links`] in turn
+/// until one succeeds (see the module docs).
+pub fn run_chain<T>(
+    opts: &ScheduleOptions,
+    mut run: impl FnMut(&ScheduleOptions) -> Result<T, SchedulerError>,
+) -> ChainRun<T> {
+    let mut attempts = Vec::new();
+    let mut last = None;
+    for algorithm in opts.links() {
+        let link = opts.clone().with_algorithm(algorithm);
+        match run(&link) {
+            Ok(out) => {
+                attempts.push(ChainAttempt {
+                    algorithm,
+                    error_code: None,
+                    error: String::new(),
+                });
+                return ChainRun {
+                    attempts,
+                    result: Ok((out, link)),
+                };
+            }
+            Err(e) => {
+                attempts.push(ChainAttempt {
+                    algorithm,
+                    error_code: Some(e.code()),
+                    error: e.to_string(),
+                });
+                last = Some(e);
+            }
+        }
+    }
+    ChainRun {
+        attempts,
+        result: Err(last.expect("links() is never empty")),
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::config::NodeConfigManager;
+    use crate::scheduler::GlobalScheduler;
+    use crate::testing::{fake_node, fake_task};
+
+    fn scheduler() -> GlobalScheduler {
+        GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(vec![fake_node(
+            "node01",
+            &[0, 1],
+            4096,
+        )])))
+    }
+
+    #[test]
+    fn workload_failing_the_first_link_is_placed_by_the_second() {
+        let sched = scheduler();
+        // No target node: target_node_priority cannot place it.
+        let tasks = vec![fake_task("t1", "", 10_000, 1_000)];
+        let opts = ScheduleOptions::default().with_algorithm_chain([
+            SchedAlgorithm::TargetNodePriority,
+            SchedAlgorithm::BestFitDecreasing,
+            SchedAlgorithm::LeastLoaded,
+        ]);
+
+        let run = run_chain(&opts, |o| sched.schedule_with_options(tasks.clone(), o));
+        assert_eq!(run.link(), Some(1));
+        let (map, used) = run.result.unwrap();
+        assert_eq!(map["node01"][0].name, "t1");
+        assert_eq!(used.algorithm, SchedAlgorithm::BestFitDecreasing);
+        assert_eq!(
+            run.attempts
+                .iter()
+                .map(|a| (a.algorithm, a.error_code))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    SchedAlgorithm::TargetNodePriority,
+                    Some(ErrorCode::InvalidTasks)
+                ),
+                (SchedAlgorithm::BestFitDecreasing, None),
+            ]
+        );
+        assert!(run.attempts[0].error.contains("t1"));
+    }
+
+    #[test]
+    fn every_link_failing_returns_the_last_error() {
+        let sched = scheduler();
+        // Two CPUs at 90 % cannot take three tasks of 70 %.
+        let tasks: Vec<_> = ["a", "b", "c"]
+            .map(|n| fake_task(n, "node01", 10_000, 7_000))
+            .into();
+        let opts = ScheduleOptions::default().with_algorithm_chain([
+            SchedAlgorithm::LeastLoaded,
+            SchedAlgorithm::TargetNodePriority,
+        ]);
+
+        let run = run_chain(&opts, |o| sched.schedule_with_options(tasks.clone(), o));
+        assert_eq!(run.link(), None);
+        assert_eq!(run.attempts.len(), 2);
+        assert!(run.attempts.iter().all(|a| a.error_code.is_some()));
+        let err = run.result.unwrap_err();
+        assert_eq!(Some(err.code()), run.attempts[1].error_code);
+
+        // Without a chain only `algorithm` runs.
+        let plain = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        let run = run_chain(&plain, |o| sched.schedule_with_options(tasks.clone(), o));
+        assert_eq!(run.attempts.len(), 1);
+        assert_eq!(run.attempts[0].algorithm, SchedAlgorithm::LeastLoaded);
+    }
+}
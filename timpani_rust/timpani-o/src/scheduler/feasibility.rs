@@ -37,7 +37,10 @@ SPDX-License-Identifier: MIT
 //! If `U` is between the L&L bound and 1.0, the task set **may or may not** be
 //! schedulable — deeper Response Time Analysis (RTA) is required.
 
-use crate::task::Task;
+use std::collections::BTreeMap;
+
+use super::priority::PriorityOrdering;
+use crate::task::{NodeSchedMap, SchedTask, Task};
 
 // ── Public API ────────────────────────────────────────────────────────────────
 
@@ -59,49 +62,162 @@ pub fn liu_layland_bound(n: usize) -> f64 {
 /// Layland schedulability bound.
 ///
 /// Returns `None` if the task set is **provably schedulable** (total
-/// utilisation ≤ L&L bound).
+/// utilisation ≤ L&L bound + `epsilon`; see
+/// [`DEFAULT_UTILIZATION_EPSILON`](super::DEFAULT_UTILIZATION_EPSILON)).
 ///
 /// Returns `Some(total_utilisation)` if the bound is **exceeded** — the
 /// caller should emit a warning; the schedule is not automatically invalidated.
 ///
 /// Tasks with `period_us == 0` are excluded from the utilisation sum (they
 /// contribute zero utilisation by definition).
-pub fn check_liu_layland(tasks_on_node: &[&Task]) -> Option<f64> {
+pub fn check_liu_layland(tasks_on_node: &[&Task], epsilon: f64) -> Option<f64> {
+    check_liu_layland_scaled(tasks_on_node, 1.0, epsilon)
+}
+
+/// [`check_liu_layland`] against the bound scaled by `cpu_share`, the share
+/// of the CPU the tasks get (below 1.0 on a time-partitioned node; see
+/// [`NodeConfig::cpu_share`](crate::config::NodeConfig::cpu_share)).
+pub fn check_liu_layland_scaled(
+    tasks_on_node: &[&Task],
+    cpu_share: f64,
+    epsilon: f64,
+) -> Option<f64> {
     let feasible: Vec<&Task> = tasks_on_node
         .iter()
         .copied()
-        .filter(|t| t.period_us > 0)
+        .filter(|t| !t.period_us.is_zero())
         .collect();
 
     if feasible.is_empty() {
         return None;
     }
 
-    let total_u: f64 = feasible
-        .iter()
-        .map(|t| t.runtime_us as f64 / t.period_us as f64)
-        .sum();
+    let total_u: f64 = feasible.iter().map(|t| t.utilization()).sum();
 
-    let bound = liu_layland_bound(feasible.len());
+    let bound = liu_layland_bound(feasible.len()) * cpu_share;
 
-    if total_u > bound {
+    if total_u > bound + epsilon {
         Some(total_u)
     } else {
         None
     }
 }
 
+/// A per-CPU task set whose utilisation exceeds its schedulability bound.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeasibilityWarning {
+    pub node: String,
+    pub cpu: u32,
+    /// Total utilisation of the tasks on `node:cpu` (density for
+    /// `"liu_layland_density"`).
+    pub utilization: f64,
+    /// Bound `utilization` was compared against.
+    pub bound: f64,
+    pub task_count: usize,
+    /// Name of the analysis that produced `bound`.
+    pub analysis: &'static str,
+}
+
+/// One `(node, cpu)` task set of a finished schedule, measured against its
+/// Liu & Layland bound (see [`check_schedule`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpuLoad {
+    pub node: String,
+    pub cpu: u32,
+    /// Total utilisation of the tasks (density for
+    /// `"liu_layland_density"`).
+    pub load: f64,
+    /// `liu_layland_bound(task_count)`.
+    pub bound: f64,
+    /// Tasks with a period; the others add nothing.
+    pub task_count: usize,
+    /// Name of the analysis `load` and `bound` belong to.
+    pub analysis: &'static str,
+}
+
+/// [`CpuLoad`] of every `(node, cpu)` with a periodic task, sorted by
+/// node, then CPU; `ordering` picks the analysis as for [`check_schedule`].
+pub fn cpu_loads(schedule: &NodeSchedMap, ordering: PriorityOrdering) -> Vec<CpuLoad> {
+    let mut by_cpu: BTreeMap<(&str, u32), (f64, usize)> = BTreeMap::new();
+    for (node, tasks) in schedule {
+        for t in tasks.iter().filter(|t| !t.period_ns.is_zero()) {
+            let e = by_cpu.entry((node, t.assigned_cpu)).or_default();
+            e.0 += match ordering {
+                PriorityOrdering::RateMonotonic => t.utilization(),
+                PriorityOrdering::DeadlineMonotonic => density(t),
+            };
+            e.1 += 1;
+        }
+    }
+    let analysis = match ordering {
+        PriorityOrdering::RateMonotonic => "liu_layland",
+        PriorityOrdering::DeadlineMonotonic => "liu_layland_density",
+    };
+
+    by_cpu
+        .into_iter()
+        .map(|((node, cpu), (load, task_count))| CpuLoad {
+            node: node.to_string(),
+            cpu,
+            load,
+            bound: liu_layland_bound(task_count),
+            task_count,
+            analysis,
+        })
+        .collect()
+}
+
+/// Run the Liu & Layland check on every `(node, cpu)` task set in a finished
+/// schedule, flagging sets above the bound by more than `epsilon`.
+///
+/// Unlike the per-node log emitted during `schedule()`, this groups by CPU —
+/// the unit RM scheduling actually runs on.  The bound assumes the
+/// `ordering` the priorities were assigned by: rate-monotonic compares
+/// utilisation (`analysis: "liu_layland"`); deadline-monotonic compares
+/// density, `Σ C / min(D, T)` (`"liu_layland_density"`), which stays
+/// sufficient for deadlines below the period.  Results are sorted by node,
+/// then CPU.
+pub fn check_schedule(
+    schedule: &NodeSchedMap,
+    epsilon: f64,
+    ordering: PriorityOrdering,
+) -> Vec<FeasibilityWarning> {
+    cpu_loads(schedule, ordering)
+        .into_iter()
+        .filter(|c| c.load > c.bound + epsilon)
+        .map(|c| FeasibilityWarning {
+            node: c.node,
+            cpu: c.cpu,
+            utilization: c.load,
+            bound: c.bound,
+            task_count: c.task_count,
+            analysis: c.analysis,
+        })
+        .collect()
+}
+
+/// `runtime / min(deadline, period)`; the deadline counts only when set.
+fn density(t: &SchedTask) -> f64 {
+    let window = if t.deadline_ns.is_zero() {
+        t.period_ns
+    } else {
+        t.deadline_ns.min(t.period_ns)
+    };
+    t.runtime_ns.ratio(window)
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::task::Task;
+    use crate::scheduler::DEFAULT_UTILIZATION_EPSILON;
+    use crate::task::{Micros, Task};
 
     fn task_with_timing(period_us: u64, runtime_us: u64) -> Task {
         Task {
-            period_us,
-            runtime_us,
+            period_us: Micros(period_us),
+            runtime_us: Micros(runtime_us),
             ..Default::default()
         }
     }
@@ -143,7 +259,7 @@ mod tests {
         let a = task_with_timing(10_000, 3_000);
         let b = task_with_timing(20_000, 5_000);
         let c = task_with_timing(50_000, 8_000);
-        let result = check_liu_layland(&[&a, &b, &c]);
+        let result = check_liu_layland(&[&a, &b, &c], DEFAULT_UTILIZATION_EPSILON);
         assert!(
             result.is_none(),
             "classic 3-task set should be feasible, got utilization = {:?}",
@@ -157,7 +273,7 @@ mod tests {
         let a = task_with_timing(10_000, 3_500);
         let b = task_with_timing(10_000, 3_500);
         let c = task_with_timing(10_000, 3_500);
-        let result = check_liu_layland(&[&a, &b, &c]);
+        let result = check_liu_layland(&[&a, &b, &c], DEFAULT_UTILIZATION_EPSILON);
         assert!(result.is_some(), "overloaded set should exceed bound");
         let u = result.unwrap();
         assert!(
@@ -172,7 +288,7 @@ mod tests {
         // bound(1) = 1.0, so U=0.5 is feasible
         let zero = task_with_timing(0, 100);
         let valid = task_with_timing(10_000, 5_000);
-        let result = check_liu_layland(&[&zero, &valid]);
+        let result = check_liu_layland(&[&zero, &valid], DEFAULT_UTILIZATION_EPSILON);
         assert!(
             result.is_none(),
             "zero-period task should be excluded, set should be feasible"
@@ -181,7 +297,7 @@ mod tests {
 
     #[test]
     fn empty_task_set_is_feasible() {
-        let result = check_liu_layland(&[]);
+        let result = check_liu_layland(&[], DEFAULT_UTILIZATION_EPSILON);
         assert!(result.is_none(), "empty set is trivially feasible");
     }
 
@@ -190,10 +306,78 @@ mod tests {
         // Construct one task with utilization exactly equal to bound(1) = 1.0
         // period=1000, runtime=1000 → U=1.0 exactly
         let t = task_with_timing(1_000, 1_000);
-        let result = check_liu_layland(&[&t]);
+        let result = check_liu_layland(&[&t], DEFAULT_UTILIZATION_EPSILON);
         assert!(
             result.is_none(),
             "utilization == bound should be feasible (≤, not <)"
         );
     }
+
+    #[test]
+    fn epsilon_absorbs_overshoot_below_its_size() {
+        // 0.5 + 0.328427124747 sits 2e-13 above bound(2) = 0.82842712474619.
+        let half = task_with_timing(2, 1);
+        let rest = task_with_timing(1_000_000_000_000, 328_427_124_747);
+        assert!(half.utilization() + rest.utilization() > liu_layland_bound(2));
+
+        for _ in 0..3 {
+            assert!(check_liu_layland(&[&half, &rest], DEFAULT_UTILIZATION_EPSILON).is_none());
+            assert!(check_liu_layland(&[&rest, &half], DEFAULT_UTILIZATION_EPSILON).is_none());
+        }
+        assert!(check_liu_layland(&[&half, &rest], 0.0).is_some());
+    }
+
+    // ── check_schedule ────────────────────────────────────────────────────────
+
+    fn placed(node: &str, cpu: u32, period_us: u64, runtime_us: u64) -> crate::task::SchedTask {
+        crate::task::SchedTask::from_task(
+            &Task {
+                name: format!("{node}_{cpu}_{runtime_us}"),
+                assigned_node: node.into(),
+                assigned_cpu: Some(cpu),
+                period_us: Micros(period_us),
+                runtime_us: Micros(runtime_us),
+                ..Default::default()
+            },
+            crate::task::DEFAULT_MAX_TASK_DURATION,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn check_schedule_groups_by_cpu() {
+        let mut map = NodeSchedMap::new();
+        // CPU 0: 0.42 + 0.42 = 0.84 > 0.828 → warning.
+        // CPU 1: a single 0.84 task is below bound(1) = 1.0 → no warning.
+        map.insert(
+            "n1".into(),
+            vec![
+                placed("n1", 0, 10_000, 4_200),
+                placed("n1", 0, 10_000, 4_200),
+                placed("n1", 1, 10_000, 8_400),
+            ],
+        );
+
+        let warnings = check_schedule(
+            &map,
+            DEFAULT_UTILIZATION_EPSILON,
+            PriorityOrdering::default(),
+        );
+        assert_eq!(warnings.len(), 1);
+        let w = &warnings[0];
+        assert_eq!((w.node.as_str(), w.cpu, w.task_count), ("n1", 0, 2));
+        assert!((w.utilization - 0.84).abs() < 1e-9);
+        assert!((w.bound - liu_layland_bound(2)).abs() < 1e-12);
+        assert_eq!(w.analysis, "liu_layland");
+    }
+
+    #[test]
+    fn check_schedule_empty_map_has_no_warnings() {
+        assert!(check_schedule(
+            &NodeSchedMap::new(),
+            DEFAULT_UTILIZATION_EPSILON,
+            PriorityOrdering::default()
+        )
+        .is_empty());
+    }
 }
@@ -0,0 +1,131 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Repair after a CPU goes offline: move the tasks placed on it to other
+//! online CPUs of the same node.
+//!
+//! This is deliberately narrower than a reschedule.  Tasks on online CPUs
+//! never move, the affected tasks never leave their node, and each one goes
+//! to the least-loaded online CPU its affinity allows that stays within the
+//! utilisation threshold — largest task first, so the big ones are not
+//! left without room by the small ones.  A task with no such CPU stays where
+//! it is and is reported as orphaned by the caller.
+
+use std::collections::BTreeMap;
+
+use super::Utilization;
+use crate::task::{CpuAffinity, SchedTask};
+
+/// New CPU for each task in `moving`, in order; `None` where no CPU in
+/// `online` has room.
+///
+/// `staying` are the node's other tasks (all tenants); only those on an
+/// `online` CPU count towards its load.
+pub fn repair_offline_placements(
+    online: &[u32],
+    staying: &[&SchedTask],
+    moving: &[(&SchedTask, CpuAffinity)],
+    threshold: f64,
+) -> Vec<Option<u32>> {
+    let limit = Utilization::from_f64(threshold);
+    let mut load: BTreeMap<u32, Utilization> =
+        online.iter().map(|&c| (c, Utilization::ZERO)).collect();
+    for t in staying {
+        if let Some(u) = load.get_mut(&t.assigned_cpu) {
+            *u += t.exact_utilization();
+        }
+    }
+
+    let mut order: Vec<usize> = (0..moving.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (ta, tb) = (moving[a].0, moving[b].0);
+        tb.exact_utilization()
+            .cmp(&ta.exact_utilization())
+            .then_with(|| ta.name.cmp(&tb.name))
+    });
+
+    let mut placed = vec![None; moving.len()];
+    for i in order {
+        let (task, affinity) = &moving[i];
+        let need = task.exact_utilization();
+        let best = load
+            .iter()
+            .filter(|(&cpu, &used)| affinity.allows_cpu(cpu) && used + need <= limit)
+            .min_by_key(|(_, &used)| used)
+            .map(|(&cpu, _)| cpu);
+        if let Some(cpu) = best {
+            *load.get_mut(&cpu).expect("candidate is online") += need;
+            placed[i] = Some(cpu);
+        }
+    }
+    placed
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Nanos, SchedPolicy};
+
+    fn task(name: &str, cpu: u32, runtime_ms: u64) -> SchedTask {
+        SchedTask {
+            name: name.into(),
+            assigned_node: "n1".into(),
+            assigned_cpu: cpu,
+            period_ns: Nanos(10_000_000),
+            runtime_ns: Nanos(runtime_ms * 1_000_000),
+            deadline_ns: Nanos(10_000_000),
+            policy: SchedPolicy::Fifo,
+            priority: 50,
+            release_time_us: 0,
+            max_dmiss: 0,
+            shared_resources: Vec::new(),
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    #[test]
+    fn moves_to_least_loaded_cpu_within_threshold() {
+        let busy = task("busy", 0, 6);
+        let light = task("light", 1, 1);
+        let gone = task("gone", 2, 3);
+        let big = task("big", 2, 5);
+        let staying = [&busy, &light];
+        let moving = [(&gone, CpuAffinity::Any), (&big, CpuAffinity::Any)];
+
+        // big (0.5) goes first, to CPU 1; gone (0.3) then fits only on 0.
+        assert_eq!(
+            repair_offline_placements(&[0, 1], &staying, &moving, 0.9),
+            [Some(0), Some(1)]
+        );
+        // At 0.8 nothing fits next to both: gone stays orphaned.
+        assert_eq!(
+            repair_offline_placements(&[0, 1], &staying, &moving, 0.8),
+            [None, Some(1)]
+        );
+    }
+
+    #[test]
+    fn affinity_and_offline_tasks_are_respected() {
+        let stale = task("stale", 1, 9); // on another offline CPU: no load
+        let pinned = task("pinned", 2, 1);
+        let moving = [(&pinned, CpuAffinity::Pinned(1 << 2 | 1 << 3))];
+        assert_eq!(
+            repair_offline_placements(&[0, 3], &[&stale], &moving, 0.9),
+            [Some(3)]
+        );
+        assert_eq!(
+            repair_offline_placements(&[0, 1], &[&stale], &moving, 0.9),
+            [None]
+        );
+    }
+}
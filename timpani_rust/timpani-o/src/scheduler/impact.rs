@@ -0,0 +1,322 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Impact of a node-configuration change on existing placements.
+//!
+//! [`GlobalScheduler::impact_of`] answers "what breaks if I roll out this
+//! `node_configurations.yaml`?" without applying anything:
+//!
+//! | Section      | Contents                                                         |
+//! |--------------|------------------------------------------------------------------|
+//! | `orphaned`   | placements on a node or CPU the proposed configuration drops     |
+//! | `rehomed`    | where the orphaned tasks would be placed instead                 |
+//! | `failing`    | orphaned tasks that would no longer be admitted anywhere         |
+//! | `capacity`   | per-node CPU count, free headroom and memory, before and after   |
+//!
+//! Orphaned tasks are re-placed one at a time, largest utilisation first,
+//! on the headroom the surviving placements leave.  Like
+//! [defragmentation](super::capacity), this works from the placed
+//! [`SchedTask`]s, which no longer carry their affinity or target node: a
+//! rehomed task may land on any CPU of any node, so `failing` is a lower
+//! bound.  For the same reason `target_node_priority` rehomes with
+//! `best_fit_decreasing`.  Placements already orphaned under the current configuration are
+//! not reported again.
+
+use std::collections::BTreeSet;
+
+use super::{GlobalScheduler, SchedAlgorithm, ScheduleOptions};
+use crate::config::ConfigDiff;
+use crate::task::{NodeSchedMap, SchedTask, Task};
+
+/// A placement the proposed configuration no longer has room for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrphanedPlacement {
+    pub workload_id: String,
+    pub task: String,
+    pub node: String,
+    pub cpu: u32,
+    /// The task's utilisation of its CPU.
+    pub utilization: f64,
+}
+
+/// An orphaned task the proposed configuration would not admit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedAdmission {
+    pub workload_id: String,
+    pub task: String,
+    /// The scheduler's error for the re-placement.
+    pub reason: String,
+}
+
+/// One node's capacity under the current and the proposed configuration.
+/// A node absent from one side has zero CPUs and headroom there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapacityDelta {
+    pub node: String,
+    pub cpus_before: usize,
+    pub cpus_after: usize,
+    /// Free headroom (Σ per-CPU, as in [`NodeCapacity::total_free`]) with
+    /// the current placements.
+    ///
+    /// [`NodeCapacity::total_free`]: super::NodeCapacity::total_free
+    pub free_before: f64,
+    /// Free headroom with the surviving and rehomed placements.
+    pub free_after: f64,
+    /// `max_memory_mb`; `None` where the node is not configured.
+    pub memory_mb_before: Option<u64>,
+    pub memory_mb_after: Option<u64>,
+}
+
+impl CapacityDelta {
+    /// Change in free headroom (negative = less room).
+    pub fn free_delta(&self) -> f64 {
+        self.free_after - self.free_before
+    }
+
+    /// Change in CPU count.
+    pub fn cpu_delta(&self) -> i64 {
+        self.cpus_after as i64 - self.cpus_before as i64
+    }
+}
+
+/// Result of [`GlobalScheduler::impact_of`] (see the module docs).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImpactReport {
+    /// Sorted by node, CPU, then task.
+    pub orphaned: Vec<OrphanedPlacement>,
+    /// New placements for the orphaned tasks that still fit.
+    pub rehomed: NodeSchedMap,
+    /// Sorted by workload, then task.
+    pub failing: Vec<FailedAdmission>,
+    /// Every node of either configuration, sorted.
+    pub capacity: Vec<CapacityDelta>,
+}
+
+impl ImpactReport {
+    /// `true` when no placement is affected.
+    pub fn is_harmless(&self) -> bool {
+        self.orphaned.is_empty()
+    }
+
+    /// Cluster-wide change in CPU count.
+    pub fn cpu_delta(&self) -> i64 {
+        self.capacity.iter().map(CapacityDelta::cpu_delta).sum()
+    }
+
+    /// Cluster-wide change in free headroom.
+    pub fn free_delta(&self) -> f64 {
+        self.capacity.iter().map(CapacityDelta::free_delta).sum()
+    }
+}
+
+impl GlobalScheduler {
+    /// What rolling out `proposed` would do to `placements`, all of which
+    /// were made under this scheduler's configuration.  `diff` is
+    /// [`config::diff`](crate::config::diff) of the two configurations;
+    /// rehomed tasks are placed with `opts` (see the module docs).  Nothing
+    /// is applied.
+    pub fn impact_of(
+        &self,
+        proposed: &GlobalScheduler,
+        diff: &ConfigDiff,
+        placements: &NodeSchedMap,
+        opts: &ScheduleOptions,
+    ) -> ImpactReport {
+        let mut survivors = NodeSchedMap::new();
+        let mut dropped: Vec<&SchedTask> = Vec::new();
+        for (node, tasks) in placements {
+            for t in tasks {
+                if diff.drops_cpu(node, t.assigned_cpu) {
+                    dropped.push(t);
+                } else {
+                    survivors.entry(node.clone()).or_default().push(t.clone());
+                }
+            }
+        }
+
+        let mut orphaned: Vec<OrphanedPlacement> = dropped
+            .iter()
+            .map(|t| OrphanedPlacement {
+                workload_id: t.workload_id.clone(),
+                task: t.name.clone(),
+                node: t.assigned_node.clone(),
+                cpu: t.assigned_cpu,
+                utilization: t.utilization(),
+            })
+            .collect();
+        orphaned.sort_by(|a, b| (&a.node, a.cpu, &a.task).cmp(&(&b.node, b.cpu, &b.task)));
+
+        // Largest first, so small tasks do not take the only slot a large
+        // one would fit.
+        dropped.sort_by(|a, b| {
+            b.exact_utilization()
+                .cmp(&a.exact_utilization())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        let rehome_opts = match opts.algorithm {
+            SchedAlgorithm::TargetNodePriority => opts
+                .clone()
+                .with_algorithm(SchedAlgorithm::BestFitDecreasing),
+            _ => opts.clone(),
+        };
+        let mut occupied = survivors;
+        let mut rehomed = NodeSchedMap::new();
+        let mut failing = Vec::new();
+        for t in dropped {
+            let task = Task {
+                workload_id: t.workload_id.clone(),
+                ..Self::replacement_task(t)
+            };
+            match proposed.schedule_with_occupancy(&occupied, vec![task], &rehome_opts) {
+                Ok(placed) => {
+                    for (node, tasks) in placed {
+                        occupied
+                            .entry(node.clone())
+                            .or_default()
+                            .extend(tasks.clone());
+                        rehomed.entry(node).or_default().extend(tasks);
+                    }
+                }
+                Err(e) => failing.push(FailedAdmission {
+                    workload_id: t.workload_id.clone(),
+                    task: t.name.clone(),
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        failing.sort_by(|a, b| (&a.workload_id, &a.task).cmp(&(&b.workload_id, &b.task)));
+
+        let before = self.capacity_report_with(placements, opts);
+        let after = proposed.capacity_report_with(&occupied, opts);
+        let nodes: BTreeSet<&String> = before
+            .nodes
+            .iter()
+            .chain(&after.nodes)
+            .map(|n| &n.node)
+            .collect();
+        let capacity = nodes
+            .into_iter()
+            .map(|node| {
+                let old = before.node(node);
+                let new = after.node(node);
+                let memory = |s: &GlobalScheduler| {
+                    s.node_config_manager
+                        .get_node_config(node)
+                        .map(|c| c.max_memory_mb)
+                };
+                CapacityDelta {
+                    node: node.clone(),
+                    cpus_before: old.map_or(0, |n| n.cpu_count),
+                    cpus_after: new.map_or(0, |n| n.cpu_count),
+                    free_before: old.map_or(0.0, |n| n.total_free),
+                    free_after: new.map_or(0.0, |n| n.total_free),
+                    memory_mb_before: memory(self),
+                    memory_mb_after: memory(proposed),
+                }
+            })
+            .collect();
+
+        ImpactReport {
+            orphaned,
+            rehomed,
+            failing,
+            capacity,
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::codec;
+    use crate::config::{self, NodeConfigManager};
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    fn load(name: &str) -> Arc<NodeConfigManager> {
+        let mut mgr = NodeConfigManager::new();
+        mgr.load_from_file(&fixture(name)).unwrap();
+        Arc::new(mgr)
+    }
+
+    #[test]
+    fn proposed_config_orphans_and_shrinks_capacity() {
+        let current = load("impact_current.yaml");
+        let proposed = load("impact_proposed.yaml");
+        let state: NodeSchedMap = codec::load(&fixture("impact_state.json")).unwrap();
+        let diff = config::diff(&current, &proposed);
+        assert_eq!(diff.removed, ["rear01"]);
+        assert_eq!(diff.changed["front01"].cpus_removed, [3]);
+
+        let report = GlobalScheduler::new(Arc::clone(&current)).impact_of(
+            &GlobalScheduler::new(Arc::clone(&proposed)),
+            &diff,
+            &state,
+            &ScheduleOptions::default(),
+        );
+
+        let orphans: Vec<_> = report
+            .orphaned
+            .iter()
+            .map(|o| (o.node.as_str(), o.cpu, o.task.as_str()))
+            .collect();
+        assert_eq!(
+            orphans,
+            [
+                ("front01", 3, "lidar"),
+                ("rear01", 2, "park_assist"),
+                ("rear01", 3, "rear_cam"),
+            ]
+        );
+        // Left after the orphans go: front01 CPU 2 with 40 %, control01 60 %
+        // on each CPU.  Both parking tasks fit on control01; lidar (85 %)
+        // fits nowhere.
+        let mut rehomed: Vec<(&str, &str)> = report
+            .rehomed
+            .values()
+            .flatten()
+            .map(|t| (t.name.as_str(), t.assigned_node.as_str()))
+            .collect();
+        rehomed.sort();
+        assert_eq!(
+            rehomed,
+            [("park_assist", "control01"), ("rear_cam", "control01")]
+        );
+        assert_eq!(report.failing.len(), 1);
+        assert_eq!(report.failing[0].task, "lidar");
+        assert_eq!(report.failing[0].workload_id, "perception");
+
+        let front = report
+            .capacity
+            .iter()
+            .find(|c| c.node == "front01")
+            .unwrap();
+        assert_eq!((front.cpus_before, front.cpus_after), (2, 1));
+        assert_eq!(
+            (front.memory_mb_before, front.memory_mb_after),
+            (Some(4096), Some(2048))
+        );
+        let rear = report.capacity.iter().find(|c| c.node == "rear01").unwrap();
+        assert_eq!((rear.cpus_after, rear.memory_mb_after), (0, None));
+        assert_eq!(report.cpu_delta(), -3);
+        // Free before: front 0.40 + 0.05, rear 0.40 + 0.30, control 0.60 +
+        // 0.60 = 2.35.  After: front 0.40, control 0.90 - 0.30 - 0.60 = 0
+        // and 0.90 - 0.30 - 0.50 = 0.10 → 0.50.
+        assert!(
+            (report.free_delta() - (0.50 - 2.35)).abs() < 1e-9,
+            "{}",
+            report.free_delta()
+        );
+    }
+}
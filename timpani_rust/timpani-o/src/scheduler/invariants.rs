@@ -0,0 +1,213 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Structural checks on a finished schedule.
+//!
+//! Whatever the algorithm, a [`NodeSchedMap`] returned by
+//! [`GlobalScheduler::schedule_with_options`](super::GlobalScheduler::schedule_with_options)
+//! must satisfy:
+//!
+//! | Invariant                                                   | Violation                    |
+//! |-------------------------------------------------------------|------------------------------|
+//! | every placement comes from an input task, at most once each | `UnknownTask`, `PlacedTwice` |
+//! | a task sits under the node it was assigned                  | `WrongNode`                  |
+//! | the node is configured and allowed by the options           | `NodeNotAllowed`             |
+//! | the CPU is one of the node's usable CPUs                    | `CpuNotUsable`               |
+//! | no CPU exceeds the effective threshold (unless overridden)  | `CpuOverloaded`              |
+//!
+//! Input tasks left unplaced are not a violation.  The checks only use the
+//! schedule itself, so they hold for a fresh run, not for a map merged with
+//! existing occupancy.
+//!
+//! [`check_schedule`] is what the fuzz target asserts after every
+//! successful run; the scheduler's own tests use it too.
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use super::options::{AdmissionOverride, ScheduleOptions};
+use super::utilization::Utilization;
+use crate::config::NodeConfigManager;
+use crate::task::{NodeSchedMap, Task};
+
+/// A broken invariant (see the module docs).
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum InvariantViolation {
+    #[error("task '{0}' was placed but not submitted")]
+    UnknownTask(String),
+
+    #[error("task '{0}' was placed more often than submitted")]
+    PlacedTwice(String),
+
+    #[error("task '{task}' is listed under node '{node}' but assigned to '{assigned}'")]
+    WrongNode {
+        task: String,
+        node: String,
+        assigned: String,
+    },
+
+    #[error("node '{0}' is not configured or not allowed")]
+    NodeNotAllowed(String),
+
+    #[error("task '{task}' is on {node}/cpu{cpu}, which is not usable")]
+    CpuNotUsable {
+        task: String,
+        node: String,
+        cpu: u32,
+    },
+
+    #[error("{node}/cpu{cpu} is at {utilization:.6}, above the threshold {threshold}")]
+    CpuOverloaded {
+        node: String,
+        cpu: u32,
+        utilization: f64,
+        threshold: f64,
+    },
+}
+
+/// Check `schedule`, produced from `tasks` on `nodes` under `opts`, against
+/// every invariant in the module docs.  Fails with the first violation.
+pub fn check_schedule(
+    schedule: &NodeSchedMap,
+    tasks: &[Task],
+    nodes: &NodeConfigManager,
+    opts: &ScheduleOptions,
+) -> Result<(), InvariantViolation> {
+    let mut submitted: BTreeMap<&str, usize> = BTreeMap::new();
+    for t in tasks {
+        *submitted.entry(t.name.as_str()).or_default() += 1;
+    }
+
+    let mut per_cpu: BTreeMap<(&str, u32), Utilization> = BTreeMap::new();
+    for (node, placed) in schedule {
+        let usable = nodes
+            .usable_cpus(node)
+            .filter(|_| opts.allows_node(node))
+            .ok_or_else(|| InvariantViolation::NodeNotAllowed(node.clone()))?;
+        for t in placed {
+            let left = submitted
+                .get_mut(t.name.as_str())
+                .ok_or_else(|| InvariantViolation::UnknownTask(t.name.clone()))?;
+            *left = left
+                .checked_sub(1)
+                .ok_or_else(|| InvariantViolation::PlacedTwice(t.name.clone()))?;
+            if &t.assigned_node != node {
+                return Err(InvariantViolation::WrongNode {
+                    task: t.name.clone(),
+                    node: node.clone(),
+                    assigned: t.assigned_node.clone(),
+                });
+            }
+            if !usable.contains(&t.assigned_cpu) {
+                return Err(InvariantViolation::CpuNotUsable {
+                    task: t.name.clone(),
+                    node: node.clone(),
+                    cpu: t.assigned_cpu,
+                });
+            }
+            *per_cpu.entry((node, t.assigned_cpu)).or_default() += t.exact_utilization();
+        }
+    }
+
+    if opts.overrides(AdmissionOverride::SkipThreshold) {
+        return Ok(());
+    }
+    let threshold = opts.effective_threshold();
+    let limit = Utilization::from_f64(threshold);
+    match per_cpu.into_iter().find(|(_, u)| *u > limit) {
+        Some(((node, cpu), u)) => Err(InvariantViolation::CpuOverloaded {
+            node: node.to_string(),
+            cpu,
+            utilization: u.as_f64(),
+            threshold,
+        }),
+        None => Ok(()),
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+    use crate::task::{Micros, SchedTask, DEFAULT_MAX_TASK_DURATION};
+
+    fn nodes() -> NodeConfigManager {
+        let mut n1 = NodeConfig::default_config("n1");
+        n1.available_cpus = vec![0, 1];
+        NodeConfigManager::from_nodes(vec![n1])
+    }
+
+    fn task(name: &str, runtime_us: u64) -> Task {
+        Task {
+            name: name.into(),
+            period_us: Micros(10_000),
+            runtime_us: Micros(runtime_us),
+            deadline_us: Micros(10_000),
+            ..Default::default()
+        }
+    }
+
+    fn placed(t: &Task, node: &str, cpu: u32) -> SchedTask {
+        let mut t = t.clone();
+        t.assigned_node = node.into();
+        t.assigned_cpu = Some(cpu);
+        SchedTask::from_task(&t, DEFAULT_MAX_TASK_DURATION).unwrap()
+    }
+
+    #[test]
+    fn each_invariant_is_checked() {
+        let (a, b) = (task("a", 5_000), task("b", 5_000));
+        let opts = ScheduleOptions::default();
+        let check = |placements: Vec<SchedTask>| {
+            let map: NodeSchedMap = [("n1".to_string(), placements)].into();
+            check_schedule(&map, &[a.clone(), b.clone()], &nodes(), &opts)
+        };
+
+        assert_eq!(
+            check(vec![placed(&a, "n1", 0), placed(&b, "n1", 1)]),
+            Ok(())
+        );
+        // Unplaced tasks are fine.
+        assert_eq!(check(vec![placed(&a, "n1", 0)]), Ok(()));
+
+        assert_eq!(
+            check(vec![placed(&a, "n1", 0), placed(&a, "n1", 1)]),
+            Err(InvariantViolation::PlacedTwice("a".into()))
+        );
+        assert!(matches!(
+            check(vec![placed(&task("c", 1), "n1", 0)]),
+            Err(InvariantViolation::UnknownTask(_))
+        ));
+        assert!(matches!(
+            check(vec![placed(&a, "n2", 0)]),
+            Err(InvariantViolation::WrongNode { .. })
+        ));
+        assert!(matches!(
+            check(vec![placed(&a, "n1", 7)]),
+            Err(InvariantViolation::CpuNotUsable { cpu: 7, .. })
+        ));
+        assert!(matches!(
+            check(vec![placed(&a, "n1", 0), placed(&b, "n1", 0)]),
+            Err(InvariantViolation::CpuOverloaded { cpu: 0, .. })
+        ));
+
+        let map: NodeSchedMap = [("n2".to_string(), Vec::new())].into();
+        assert_eq!(
+            check_schedule(&map, &[], &nodes(), &opts),
+            Err(InvariantViolation::NodeNotAllowed("n2".into()))
+        );
+        let skip = opts.with_admission_override(AdmissionOverride::SkipThreshold);
+        let map: NodeSchedMap = [(
+            "n1".to_string(),
+            vec![placed(&a, "n1", 0), placed(&b, "n1", 0)],
+        )]
+        .into();
+        assert_eq!(check_schedule(&map, &[a, b], &nodes(), &skip), Ok(()));
+    }
+}
@@ -0,0 +1,257 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Bounded log volume for large schedules.
+//!
+//! Every algorithm reports each placement through a [`PlacementLog`], which
+//! hands it to the scheduler's [`ScheduleEventSink`].  With the default
+//! [`TracingSink`](super::TracingSink), up to
+//! [`LogPolicy::verbose_task_limit`] tasks per run, each placement is an
+//! `info!` line ("✓ scheduled"); above it they drop to `debug!` and a
+//! progress line ("placed 1000/5000") is logged every
+//! [`LogPolicy::progress_interval`] placements instead:
+//!
+//! | Tasks in the run | Per task | Progress                | Per node summary |
+//! |------------------|----------|-------------------------|------------------|
+//! | ≤ limit          | `info!`  | —                       | `info!`          |
+//! | > limit          | `debug!` | `info!` every interval  | `info!`          |
+//!
+//! Warnings and errors are never affected.  A 5 000-task run on the
+//! defaults logs 5 progress lines where it used to log 5 000.
+
+use tracing::info;
+
+use super::cache_group::CacheGroups;
+use super::sink::ScheduleEventSink;
+use super::workloads::NodeWorkloads;
+use crate::task::Task;
+
+/// Runs with more tasks than this log placements at `debug!`.
+pub const DEFAULT_VERBOSE_TASK_LIMIT: usize = 100;
+
+/// Placements between two progress lines in a quiet run.
+pub const DEFAULT_PROGRESS_INTERVAL: usize = 1_000;
+
+// ── LogPolicy ─────────────────────────────────────────────────────────────────
+
+/// How much the scheduler logs per task (see the module docs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogPolicy {
+    /// Largest run that still logs every placement at `info!`.
+    pub verbose_task_limit: usize,
+    /// Placements per progress line above the limit; `0` = none.
+    pub progress_interval: usize,
+}
+
+impl Default for LogPolicy {
+    fn default() -> Self {
+        Self {
+            verbose_task_limit: DEFAULT_VERBOSE_TASK_LIMIT,
+            progress_interval: DEFAULT_PROGRESS_INTERVAL,
+        }
+    }
+}
+
+impl LogPolicy {
+    pub fn with_verbose_task_limit(mut self, limit: usize) -> Self {
+        self.verbose_task_limit = limit;
+        self
+    }
+
+    pub fn with_progress_interval(mut self, interval: usize) -> Self {
+        self.progress_interval = interval;
+        self
+    }
+
+    /// Whether a run of `total` tasks logs every placement at `info!`.
+    pub fn is_verbose(&self, total: usize) -> bool {
+        total <= self.verbose_task_limit
+    }
+}
+
+// ── PlacementLog ──────────────────────────────────────────────────────────────
+
+/// Placement counter for one scheduling run.
+///
+/// Also keeps the run's [`NodeWorkloads`] and [`CacheGroups`], since every
+/// placement passes through [`record`](Self::record).
+pub(super) struct PlacementLog<'a> {
+    policy: LogPolicy,
+    total: usize,
+    placed: usize,
+    sink: &'a dyn ScheduleEventSink,
+    workloads: NodeWorkloads,
+    cache_groups: CacheGroups,
+}
+
+impl<'a> PlacementLog<'a> {
+    pub(super) fn new(
+        policy: LogPolicy,
+        total: usize,
+        sink: &'a dyn ScheduleEventSink,
+        workloads: NodeWorkloads,
+        cache_groups: CacheGroups,
+    ) -> Self {
+        Self {
+            policy,
+            total,
+            placed: 0,
+            sink,
+            workloads,
+            cache_groups,
+        }
+    }
+
+    /// Placements so far.
+    pub(super) fn placed(&self) -> usize {
+        self.placed
+    }
+
+    /// Workloads per node, including this run's placements so far.
+    pub(super) fn workloads(&self) -> &NodeWorkloads {
+        &self.workloads
+    }
+
+    /// Cache affinity group members placed so far.
+    pub(super) fn cache_groups(&self) -> &CacheGroups {
+        &self.cache_groups
+    }
+
+    /// Record that `task` went to `cpu` on `node`, reporting it to the sink.
+    pub(super) fn record(&mut self, task: &Task, node: &str, cpu: u32) {
+        self.placed += 1;
+        self.workloads
+            .record(node, cpu, &task.workload_id, task.exclusive_cpus);
+        let hint = self.cache_groups.honoured(task, node, cpu);
+        self.cache_groups.record(task, node, cpu);
+        let verbose = self.policy.is_verbose(self.total);
+        self.sink.task_placed(task, node, cpu, verbose);
+        if let Some(honoured) = hint {
+            self.sink.cache_hint(task, node, cpu, honoured);
+        }
+        if verbose {
+            return;
+        }
+        let interval = self.policy.progress_interval;
+        if interval > 0 && self.placed.is_multiple_of(interval) {
+            info!(
+                placed = self.placed,
+                total = self.total,
+                "placed {}/{}",
+                self.placed,
+                self.total
+            );
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::config::{NodeConfig, NodeConfigManager, NodeProtocol};
+    use crate::scheduler::{GlobalScheduler, ScheduleOptions};
+    use crate::task::Micros;
+
+    /// Shared buffer the test subscriber writes to.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Schedule `count` light tasks across four 16-CPU nodes and return the
+    /// log lines, `debug!` included.
+    fn captured_run(count: usize, policy: LogPolicy) -> Vec<String> {
+        let nodes = (0..4)
+            .map(|i| NodeConfig {
+                name: format!("node{i}"),
+                available_cpus: (0..16).collect(),
+                reserved_cpus: Vec::new(),
+                max_memory_mb: 4096,
+                architecture: "x86_64".into(),
+                location: String::new(),
+                description: String::new(),
+                endpoint: None,
+                max_workloads: None,
+                enabled: true,
+                time_partitions: None,
+                cpu_clusters: Vec::new(),
+                protocol: NodeProtocol::Grpc,
+            })
+            .collect();
+        let sched = GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(nodes)));
+        let tasks = (0..count)
+            .map(|i| Task {
+                name: format!("t{i}"),
+                workload_id: "wl".into(),
+                target_node: format!("node{}", i % 4),
+                period_us: Micros(100_000),
+                runtime_us: Micros(100),
+                deadline_us: Micros(100_000),
+                ..Default::default()
+            })
+            .collect();
+
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            sched
+                .schedule_with_options(tasks, &ScheduleOptions::default().with_log_policy(policy))
+                .unwrap();
+        });
+        let out = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        out.lines().map(str::to_string).collect()
+    }
+
+    fn count(lines: &[String], level: &str, text: &str) -> usize {
+        lines
+            .iter()
+            .filter(|l| l.contains(level) && l.contains(text))
+            .count()
+    }
+
+    #[test]
+    fn large_run_keeps_info_lines_bounded() {
+        let lines = captured_run(5_000, LogPolicy::default());
+        let info = count(&lines, " INFO ", "");
+        assert!(info < 30, "{info} info lines");
+        assert_eq!(count(&lines, " INFO ", "✓ scheduled"), 0);
+        assert_eq!(count(&lines, "DEBUG", "✓ scheduled"), 5_000);
+        assert_eq!(count(&lines, " INFO ", "placed "), 5);
+        assert!(lines.iter().any(|l| l.contains("placed 5000/5000")));
+        assert_eq!(count(&lines, " INFO ", "node summary"), 4);
+    }
+
+    #[test]
+    fn small_run_logs_every_placement() {
+        let lines = captured_run(8, LogPolicy::default());
+        assert_eq!(count(&lines, " INFO ", "✓ scheduled"), 8);
+        assert_eq!(count(&lines, " INFO ", "placed "), 0);
+
+        let quiet = LogPolicy::default()
+            .with_verbose_task_limit(4)
+            .with_progress_interval(2);
+        let lines = captured_run(8, quiet);
+        assert_eq!(count(&lines, " INFO ", "✓ scheduled"), 0);
+        assert_eq!(count(&lines, " INFO ", "placed "), 4);
+    }
+}
@@ -0,0 +1,339 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Per-task runtime margin: how far a task's WCET could grow before its CPU
+//! fails analysis.
+//!
+//! The margin is the largest factor `k` such that the CPU still passes when
+//! the task's runtime is multiplied by `k` and every other task is left as
+//! placed.  `1.8` means the task could run 80 % longer; below `1.0` means the
+//! CPU already fails (possible under an admission override).  Which analysis
+//! decides is [`ScheduleOptions::margin_analysis`]:
+//!
+//! | Analysis      | The CPU passes while                                          |
+//! |---------------|---------------------------------------------------------------|
+//! | `threshold`   | utilisation ≤ threshold + epsilon (capped at one CPU)         |
+//! | `liu_layland` | utilisation ≤ `n(2^(1/n) − 1)` + epsilon                      |
+//! | `rta`         | every task's response time, with blocking, meets its deadline |
+//!
+//! `k` is found by bisection with a fixed [`MARGIN_BISECTION_STEPS`], then
+//! rounded down to [`MARGIN_RESOLUTION`], so the reported figure always
+//! passes.  Aperiodic tasks (and tasks with a zero runtime) get no margin.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::feasibility::liu_layland_bound;
+use super::rta::{blocking_time, response_time};
+use super::ScheduleOptions;
+use crate::task::{Nanos, NodeSchedMap, SchedTask};
+
+/// Bisection steps per task.
+pub const MARGIN_BISECTION_STEPS: u32 = 32;
+
+/// Granularity margins are rounded down to.
+pub const MARGIN_RESOLUTION: f64 = 0.01;
+
+/// The analysis a runtime margin is computed against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarginAnalysis {
+    /// The per-CPU utilisation threshold admission enforces.
+    #[default]
+    Threshold,
+    /// The Liu & Layland bound for the CPU's task count.
+    LiuLayland,
+    /// Response time analysis with priority-ceiling blocking.
+    Rta,
+}
+
+impl MarginAnalysis {
+    /// Canonical string form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MarginAnalysis::Threshold => "threshold",
+            MarginAnalysis::LiuLayland => "liu_layland",
+            MarginAnalysis::Rta => "rta",
+        }
+    }
+}
+
+impl fmt::Display for MarginAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MarginAnalysis {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "threshold" => Ok(MarginAnalysis::Threshold),
+            "liu_layland" => Ok(MarginAnalysis::LiuLayland),
+            "rta" => Ok(MarginAnalysis::Rta),
+            other => Err(format!("unknown margin analysis '{other}'")),
+        }
+    }
+}
+
+/// Margins of the tasks in `schedule`, per node and in the same order as
+/// `schedule`'s task lists.  `occupied` holds the other tasks sharing the
+/// nodes (e.g. other workloads); they count towards each CPU but get no
+/// margin of their own.
+pub fn runtime_margins(
+    schedule: &NodeSchedMap,
+    occupied: &NodeSchedMap,
+    opts: &ScheduleOptions,
+) -> BTreeMap<String, Vec<Option<f64>>> {
+    schedule
+        .iter()
+        .map(|(node, tasks)| {
+            let margins = tasks
+                .iter()
+                .map(|t| {
+                    let cpu_tasks: Vec<&SchedTask> = occupied
+                        .get(node)
+                        .into_iter()
+                        .chain([tasks])
+                        .flatten()
+                        .filter(|o| o.assigned_cpu == t.assigned_cpu && !o.period_ns.is_zero())
+                        .collect();
+                    let index = cpu_tasks.iter().position(|o| std::ptr::eq(*o, t))?;
+                    runtime_margin(&cpu_tasks, index, opts)
+                })
+                .collect();
+            (node.clone(), margins)
+        })
+        .collect()
+}
+
+/// Margin of `cpu_tasks[index]` among the periodic tasks `cpu_tasks` of one
+/// CPU.  `None` for an aperiodic task or a zero runtime.
+pub fn runtime_margin(
+    cpu_tasks: &[&SchedTask],
+    index: usize,
+    opts: &ScheduleOptions,
+) -> Option<f64> {
+    let task = cpu_tasks[index];
+    if task.period_ns.is_zero() || task.runtime_ns.is_zero() {
+        return None;
+    }
+    let passes = |k: f64| passes_inflated(cpu_tasks, index, k, opts);
+
+    // No analysis passes a task longer than both its period and deadline.
+    let runtime = task.runtime_ns.as_u64() as f64;
+    let mut hi = task.period_ns.max(task.deadline_ns).as_u64() as f64 / runtime;
+    if passes(hi) {
+        return Some(round_down(hi));
+    }
+    let mut lo = 0.0;
+    if !passes(lo) {
+        return Some(0.0);
+    }
+    for _ in 0..MARGIN_BISECTION_STEPS {
+        let mid = (lo + hi) / 2.0;
+        if passes(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(round_down(lo))
+}
+
+fn round_down(k: f64) -> f64 {
+    (k / MARGIN_RESOLUTION).floor() * MARGIN_RESOLUTION
+}
+
+/// Whether the CPU passes [`ScheduleOptions::margin_analysis`] with the
+/// runtime of `cpu_tasks[index]` multiplied by `k` (rounded up to whole
+/// nanoseconds for RTA).
+fn passes_inflated(cpu_tasks: &[&SchedTask], index: usize, k: f64, opts: &ScheduleOptions) -> bool {
+    let utilization = || -> f64 {
+        cpu_tasks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                if i == index {
+                    t.utilization() * k
+                } else {
+                    t.utilization()
+                }
+            })
+            .sum()
+    };
+    match opts.margin_analysis {
+        MarginAnalysis::Threshold => {
+            let limit = opts
+                .effective_threshold()
+                .min(1.0 + opts.utilization_epsilon);
+            utilization() <= limit
+        }
+        MarginAnalysis::LiuLayland => {
+            utilization() <= liu_layland_bound(cpu_tasks.len()) + opts.utilization_epsilon
+        }
+        MarginAnalysis::Rta => {
+            let inflated = SchedTask {
+                runtime_ns: Nanos((cpu_tasks[index].runtime_ns.as_u64() as f64 * k).ceil() as u64),
+                ..cpu_tasks[index].clone()
+            };
+            let mut tasks = cpu_tasks.to_vec();
+            tasks[index] = &inflated;
+            tasks
+                .iter()
+                .all(|t| response_time(t, &tasks, blocking_time(t, &tasks)).is_some())
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::rta::analyse_schedule;
+    use crate::task::{SchedPolicy, SharedResource};
+
+    fn st(name: &str, cpu: u32, period_us: u64, runtime_us: u64, priority: i32) -> SchedTask {
+        SchedTask {
+            name: name.into(),
+            assigned_node: "n1".into(),
+            assigned_cpu: cpu,
+            period_ns: Nanos(period_us * 1_000),
+            runtime_ns: Nanos(runtime_us * 1_000),
+            deadline_ns: Nanos(period_us * 1_000),
+            policy: SchedPolicy::Fifo,
+            priority,
+            release_time_us: 0,
+            max_dmiss: 0,
+            shared_resources: vec![],
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    /// CPU 0 holds three rate-monotonic tasks, `b` and `c` sharing a lock;
+    /// CPU 1 holds `d` alone; `e` is aperiodic.
+    fn fixture() -> NodeSchedMap {
+        let mut b = st("b", 0, 20_000, 4_000, 80);
+        let mut c = st("c", 0, 50_000, 6_000, 70);
+        let lock = SharedResource {
+            name: "bus".into(),
+            max_cs_us: crate::task::Micros(1_000),
+        };
+        b.shared_resources = vec![lock.clone()];
+        c.shared_resources = vec![lock];
+        let mut e = st("e", 1, 0, 1_000, 10);
+        e.period_ns = Nanos::ZERO;
+        [(
+            "n1".to_string(),
+            vec![
+                st("a", 0, 10_000, 2_000, 90),
+                b,
+                c,
+                st("d", 1, 100_000, 30_000, 50),
+                e,
+            ],
+        )]
+        .into()
+    }
+
+    /// Whether CPU `cpu` of `schedule` passes `analysis`, judged with the
+    /// whole-schedule checks rather than the margin code.
+    fn cpu_passes(schedule: &NodeSchedMap, cpu: u32, opts: &ScheduleOptions) -> bool {
+        let tasks: Vec<&SchedTask> = schedule["n1"]
+            .iter()
+            .filter(|t| t.assigned_cpu == cpu && !t.period_ns.is_zero())
+            .collect();
+        let u: f64 = tasks.iter().map(|t| t.utilization()).sum();
+        match opts.margin_analysis {
+            MarginAnalysis::Threshold => u <= opts.effective_threshold(),
+            MarginAnalysis::LiuLayland => {
+                u <= liu_layland_bound(tasks.len()) + opts.utilization_epsilon
+            }
+            MarginAnalysis::Rta => analyse_schedule(schedule)
+                .results
+                .iter()
+                .filter(|r| r.cpu == cpu)
+                .all(|r| r.schedulable()),
+        }
+    }
+
+    /// Largest multiple of the resolution that passes, found by stepping.
+    fn brute_force(schedule: &NodeSchedMap, index: usize, opts: &ScheduleOptions) -> f64 {
+        let task = &schedule["n1"][index];
+        let mut best = 0.0;
+        for step in 1..=2_000 {
+            let k = step as f64 * MARGIN_RESOLUTION;
+            let mut inflated = schedule.clone();
+            let t = &mut inflated.get_mut("n1").unwrap()[index];
+            t.runtime_ns = Nanos((task.runtime_ns.as_u64() as f64 * k).ceil() as u64);
+            if t.runtime_ns > t.period_ns.max(t.deadline_ns) {
+                break;
+            }
+            if cpu_passes(&inflated, task.assigned_cpu, opts) {
+                best = k;
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn margins_match_brute_force_inflation() {
+        let schedule = fixture();
+        for analysis in [
+            MarginAnalysis::Threshold,
+            MarginAnalysis::LiuLayland,
+            MarginAnalysis::Rta,
+        ] {
+            let opts = ScheduleOptions::default().with_margin_analysis(analysis);
+            let margins = runtime_margins(&schedule, &NodeSchedMap::new(), &opts);
+            for (i, t) in schedule["n1"].iter().enumerate() {
+                let margin = margins["n1"][i];
+                if t.period_ns.is_zero() {
+                    assert_eq!(margin, None, "{analysis}: {}", t.name);
+                    continue;
+                }
+                let margin = margin.unwrap();
+                let expected = brute_force(&schedule, i, &opts);
+                // Bisection stops just below an exact grid point, so it may
+                // round one step lower — never higher.
+                assert!(
+                    margin <= expected + 1e-9 && expected - margin <= MARGIN_RESOLUTION + 1e-9,
+                    "{analysis}: {} margin {margin}, brute force {expected}",
+                    t.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn threshold_margin_of_a_lone_task() {
+        // 30 % alone on CPU 1 against the 90 % threshold: exactly 3×.
+        let schedule = fixture();
+        let margins = runtime_margins(&schedule, &NodeSchedMap::new(), &ScheduleOptions::default());
+        let d = margins["n1"][3].unwrap();
+        assert!((2.99..=3.0).contains(&d), "{d}");
+    }
+
+    #[test]
+    fn occupied_tasks_shrink_the_margin_but_get_none() {
+        let schedule = fixture();
+        let mut occupied = NodeSchedMap::new();
+        occupied.insert("n1".into(), vec![st("other", 1, 100_000, 30_000, 40)]);
+        let margins = runtime_margins(&schedule, &occupied, &ScheduleOptions::default());
+        assert_eq!(margins.len(), 1);
+        assert_eq!(margins["n1"].len(), schedule["n1"].len());
+        // (0.9 − 0.3) / 0.3 = 2×.
+        let d = margins["n1"][3].unwrap();
+        assert!((1.99..=2.0).contains(&d), "{d}");
+    }
+}
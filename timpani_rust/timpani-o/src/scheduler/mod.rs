@@ -5,7 +5,7 @@ SPDX-License-Identifier: MIT
 
 //! Global task scheduler for Timpani-O.
 //!
-//! [`GlobalScheduler`] implements three scheduling algorithms that distribute
+//! [`GlobalScheduler`] implements four scheduling algorithms that distribute
 //! a set of real-time [`Task`]s across compute nodes, assigning each task a
 //! node and a CPU.  The result is a [`NodeSchedMap`] — one
 //! `Vec<`[`SchedTask`]`>` per node — ready to be forwarded to Timpani-N over
@@ -20,7 +20,16 @@ SPDX-License-Identifier: MIT
 //! | CPU model | Algorithms 2 & 3 dequeue CPUs; algorithm 1 uses util tracking | All three use per-CPU utilisation tracking |
 //! | Error returns | `bool` + silent `continue` | `Result<NodeSchedMap, SchedulerError>` with typed variants |
 //! | Thread safety | Shared mutable state | `Send + Sync` (no interior mutability) |
-//! | Feasibility check | 90 % hard-coded heuristic | 90 % heuristic + post-schedule Liu & Layland warning |
+//! | Feasibility check | 90 % hard-coded heuristic | 90 % heuristic + post-schedule Liu & Layland and RTA (with PCP blocking) warnings |
+//! | Utilisation sums | `double` accumulation | Exact integer fractions ([`Utilization`]) — admission is order-independent |
+//!
+//! # Architecture scaling
+//!
+//! A task's `runtime_us` is its WCET on a reference board.  Wherever the
+//! scheduler sizes a task against a node — admission, CPU fit, the
+//! post-schedule feasibility checks and the `runtime_ns` the node receives —
+//! it uses [`Task::runtime_on`] for that node's `architecture`, so a task
+//! with `wcet_scaling: {x86_64: 0.7}` costs 70 % of its budget there.
 //!
 //! # Example
 //! ```rust,ignore
@@ -29,20 +38,86 @@ SPDX-License-Identifier: MIT
 //! let result: NodeSchedMap = scheduler.schedule(tasks, "target_node_priority")?;
 //! ```
 
+pub mod cache_group;
+pub mod capacity;
+pub mod chain;
 pub mod error;
+pub mod fallback;
 pub mod feasibility;
-
-pub use error::{AdmissionReason, SchedulerError};
-
-use std::collections::BTreeMap;
+pub mod hotplug;
+pub mod impact;
+pub mod invariants;
+pub mod log_policy;
+pub mod margin;
+pub mod options;
+pub mod pinned;
+pub mod priority;
+pub mod priority_class;
+pub mod proximity;
+pub mod quality;
+pub mod rta;
+pub mod simulate;
+pub mod sink;
+pub mod spread;
+pub mod stagger;
+pub mod sweep;
+pub mod task_update;
+pub mod timing;
+pub mod utilization;
+pub mod validate;
+pub mod warm_start;
+pub mod what_if;
+pub mod workloads;
+
+pub use cache_group::honoured_hints;
+pub use capacity::{CapacityReport, DefragSuggestion, IncrementalSchedule, NodeCapacity};
+pub use chain::{analyse_chains, ChainReport, TaskChain};
+pub use error::{
+    AdmissionReason, AdmissionReasonKind, ErrorCode, PinnedCpuConflict, SchedulerError,
+    TaskProblem, TaskProblems,
+};
+pub use fallback::{run_chain, ChainAttempt, ChainRun};
+pub use impact::{CapacityDelta, FailedAdmission, ImpactReport, OrphanedPlacement};
+pub use invariants::{check_schedule, InvariantViolation};
+pub use log_policy::LogPolicy;
+pub use margin::{runtime_margins, MarginAnalysis};
+pub use options::{AdmissionOverride, SchedAlgorithm, ScheduleOptions};
+pub use priority::{assign_priorities, PriorityOrdering};
+pub use priority_class::PriorityClass;
+pub use proximity::{ProximityTable, DEFAULT_PROXIMITY_TOLERANCE};
+pub use quality::{evaluate, QualityConfig, QualityMetrics};
+pub use simulate::SimulationCheck;
+pub use sink::{AuditEvent, FanOut, ScheduleEventSink, TracingSink};
+pub use stagger::{stagger_releases, StaggerStrategy};
+pub use sweep::{threshold_steps, SweepFormat, SweepPoint};
+pub use task_update::{TaskTiming, TaskUpdate, UpdateLevel};
+pub use timing::{Phase, PhaseTimings};
+pub use utilization::Utilization;
+pub use validate::validate_tasks;
+pub use warm_start::PriorPlacement;
+pub use what_if::{
+    LostTask, NodeRejection, PeakUtilization, UnplacedTask, WhatIfMove, WhatIfReport,
+};
+
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use tracing::{debug, info, warn};
 
+use crate::admission;
 use crate::config::NodeConfigManager;
-use crate::task::{CpuAffinity, NodeSchedMap, SchedTask, Task};
-
-use feasibility::{check_liu_layland, liu_layland_bound};
+use crate::limits::{Limit, LimitHits};
+use crate::task::{
+    CpuAffinity, Nanos, NodeSchedMap, SchedTask, SchedTaskConversionError, TargetNodePolicy, Task,
+};
+
+use cache_group::CacheGroups;
+use feasibility::{check_liu_layland_scaled, liu_layland_bound};
+use log_policy::PlacementLog;
+use pinned::PinnedDemand;
+use proximity::Prefer;
+use timing::PhaseClock;
+use workloads::NodeWorkloads;
 
 // ── Constants ─────────────────────────────────────────────────────────────────
 
@@ -53,18 +128,45 @@ use feasibility::{check_liu_layland, liu_layland_bound};
 /// theoretical bound that contextualises this value.
 const CPU_UTILIZATION_THRESHOLD: f64 = 0.90;
 
-// ── Internal state types ──────────────────────────────────────────────────────
+/// Default tolerance for utilisation-versus-limit comparisons.
+///
+/// Every "does it fit" check accepts up to `limit + epsilon`, so a value a
+/// rounding error above its limit is treated as on it — and a tie is always
+/// accepted:
+///
+/// | Check                                  | Accepts when                       |
+/// |----------------------------------------|------------------------------------|
+/// | per-CPU admission (`find_best_cpu_for_task`) | `cpu + task ≤ threshold + ε`  |
+/// | aggregate pre-check                    | `required + used ≤ cpus × (threshold + ε)` |
+/// | `best_fit_decreasing` node cap         | `node + task ≤ cpu_share × cpus + ε` |
+/// | Liu & Layland ([`feasibility`])        | `U ≤ bound + ε` (no warning)       |
+///
+/// Admission sums are exact ([`Utilization`]), so there ε only absorbs
+/// rounding in an `f64` threshold; the Liu & Layland checks sum `f64`s, so
+/// there it keeps the verdict independent of summation order and FP
+/// contraction.  The limits are quantised to 10⁻⁹, so a smaller non-zero ε
+/// only matters for the Liu & Layland checks.
+///
+/// Ties between candidates are broken deterministically: nodes in name
+/// order (the first node wins an equal `least_loaded` or
+/// `best_fit_decreasing` score, unless a [`proximity`] table prefers a
+/// near-tie) and CPUs highest-numbered first.
+///
+/// Configurable per run with [`ScheduleOptions::with_utilization_epsilon`].
+pub const DEFAULT_UTILIZATION_EPSILON: f64 = 1e-9;
+
+// ── Per-call state types ──────────────────────────────────────────────────────
 
 /// Per-call CPU pool: node_id → sorted list of available CPU ids.
 ///
 /// `BTreeMap` (not `HashMap`) so iteration order is always alphabetical by
 /// node name — required for deterministic scheduling.
-type AvailCpus = BTreeMap<String, Vec<u32>>;
+pub type AvailCpus = BTreeMap<String, Vec<u32>>;
 
-/// Per-call utilisation tracker: node_id → (cpu_id → utilisation fraction).
+/// Per-call utilisation tracker: node_id → (cpu_id → exact utilisation).
 ///
 /// Both levels use `BTreeMap` for deterministic iteration.
-type CpuUtil = BTreeMap<String, BTreeMap<u32, f64>>;
+pub type CpuUtil = BTreeMap<String, BTreeMap<u32, Utilization>>;
 
 // ── GlobalScheduler ───────────────────────────────────────────────────────────
 
@@ -74,8 +176,24 @@ type CpuUtil = BTreeMap<String, BTreeMap<u32, f64>>;
 /// (available CPUs, utilisation tracking) is allocated inside `schedule()`
 /// and dropped at the end of the call, making this struct `Send + Sync` and
 /// eliminating the need for `clear()`.
+#[derive(Clone)]
 pub struct GlobalScheduler {
     node_config_manager: Arc<NodeConfigManager>,
+
+    /// Largest-placeable-slot target for defragmentation suggestions.
+    ///
+    /// `None` disables the re-optimisation pass in
+    /// [`schedule_incremental`](Self::schedule_incremental).
+    defrag_target: Option<f64>,
+
+    /// Receiver of per-task events; [`TracingSink`] unless replaced with
+    /// [`with_sink`](Self::with_sink).
+    sink: Arc<dyn ScheduleEventSink>,
+
+    /// Where hits of the [input limits](crate::limits) are counted; this
+    /// scheduler's own unless shared with
+    /// [`with_limit_hits`](Self::with_limit_hits).
+    limit_hits: Arc<LimitHits>,
 }
 
 impl GlobalScheduler {
@@ -83,9 +201,57 @@ impl GlobalScheduler {
     pub fn new(node_config_manager: Arc<NodeConfigManager>) -> Self {
         Self {
             node_config_manager,
+            defrag_target: None,
+            sink: Arc::new(TracingSink),
+            limit_hits: Arc::default(),
         }
     }
 
+    /// Enable defragmentation suggestions from
+    /// [`schedule_incremental`](Self::schedule_incremental).
+    ///
+    /// A suggestion is produced when the largest placeable slot (see
+    /// [`NodeCapacity::largest_placeable`]) is at or below `target` after the
+    /// incremental run, but a full re-optimisation would raise it above
+    /// `target`.
+    pub fn with_defrag_target(mut self, target: f64) -> Self {
+        self.defrag_target = Some(target);
+        self
+    }
+
+    /// Report per-task events to `sink` instead of logging them.
+    ///
+    /// Wrap [`TracingSink`] and `sink` in a [`FanOut`] to keep the log.
+    pub fn with_sink(mut self, sink: Arc<dyn ScheduleEventSink>) -> Self {
+        self.sink = sink;
+        self
+    }
+
+    /// Count input limit hits in `hits`, typically its owner's.
+    pub fn with_limit_hits(mut self, hits: Arc<LimitHits>) -> Self {
+        self.limit_hits = hits;
+        self
+    }
+
+    /// Where this scheduler counts input limit hits.
+    pub fn limit_hits(&self) -> &LimitHits {
+        &self.limit_hits
+    }
+
+    /// The node configuration this scheduler places against.
+    pub fn node_config_manager(&self) -> &NodeConfigManager {
+        &self.node_config_manager
+    }
+
+    /// Names of all configured nodes.
+    pub fn node_ids(&self) -> BTreeSet<String> {
+        self.node_config_manager
+            .get_all_nodes()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
     // ── Public entry point ────────────────────────────────────────────────────
 
     /// Schedule `tasks` using the named `algorithm` and return a per-node map
@@ -100,56 +266,309 @@ impl GlobalScheduler {
     ///   assigns each to the node that will be most tightly packed (highest
     ///   post-assignment utilisation that still stays ≤ 1.0).
     ///
+    /// A task's `target_node_policy` overrides how its `target_node` is
+    /// treated (see [`SchedAlgorithm::default_target_policy`]); a `Preferred`
+    /// target that cannot take the task is recorded in
+    /// [`SchedTask::fallback_from`].
+    ///
     /// # Errors
     /// Returns a [`SchedulerError`] variant that describes exactly what went
     /// wrong so the gRPC handler can map it to an appropriate `tonic::Status`.
     pub fn schedule(
         &self,
-        mut tasks: Vec<Task>,
+        tasks: Vec<Task>,
         algorithm: &str,
     ) -> Result<NodeSchedMap, SchedulerError> {
+        let opts = ScheduleOptions::default().with_algorithm(algorithm.parse()?);
+        self.schedule_with_options(tasks, &opts)
+    }
+
+    /// Like [`schedule`](Self::schedule), but with an explicit algorithm and
+    /// per-CPU utilisation threshold.
+    ///
+    /// # Errors
+    /// [`SchedulerError::InvalidThreshold`] if `opts` fails
+    /// [`ScheduleOptions::validate`]; otherwise as for `schedule`.
+    pub fn schedule_with_options(
+        &self,
+        tasks: Vec<Task>,
+        opts: &ScheduleOptions,
+    ) -> Result<NodeSchedMap, SchedulerError> {
+        self.schedule_on(tasks, opts, None)
+    }
+
+    /// Place `tasks` on CPU headroom left by `occupied`, returning only the
+    /// new placements.
+    ///
+    /// Used to share the cluster between tenants: `occupied` holds the
+    /// other tenants' active schedules.
+    pub fn schedule_with_occupancy(
+        &self,
+        occupied: &NodeSchedMap,
+        tasks: Vec<Task>,
+        opts: &ScheduleOptions,
+    ) -> Result<NodeSchedMap, SchedulerError> {
+        self.schedule_on(tasks, opts, Some(occupied))
+    }
+
+    /// Place `tasks` on top of an `existing` schedule without moving any of
+    /// the tasks already placed there.
+    ///
+    /// The per-CPU utilisation tracker is seeded from `existing` before the
+    /// algorithm runs, so new tasks only consume the remaining headroom.  The
+    /// returned [`IncrementalSchedule`] holds the merged map and — when
+    /// [`with_defrag_target`](Self::with_defrag_target) is set — an optional
+    /// [`DefragSuggestion`].  `existing_tasks` are the tasks `existing` was
+    /// placed from; the suggestion re-places them with their constraints.
+    ///
+    /// # Errors
+    /// Same as [`schedule_with_options`](Self::schedule_with_options).  On
+    /// error `existing` is untouched.
+    pub fn schedule_incremental(
+        &self,
+        existing: &NodeSchedMap,
+        existing_tasks: &[Task],
+        tasks: Vec<Task>,
+        opts: &ScheduleOptions,
+    ) -> Result<IncrementalSchedule, SchedulerError> {
+        let mut sources = existing_tasks.to_vec();
+        sources.extend(tasks.iter().cloned());
+        let added = self.schedule_on(tasks, opts, Some(existing))?;
+
+        let mut schedule = existing.clone();
+        for (node, node_tasks) in added {
+            schedule.entry(node).or_default().extend(node_tasks);
+        }
+
+        let defrag = self
+            .defrag_target
+            .and_then(|target| self.suggest_defragmentation(&schedule, &sources, opts, target));
+
+        Ok(IncrementalSchedule { schedule, defrag })
+    }
+
+    /// Like [`schedule_with_occupancy`](Self::schedule_with_occupancy)
+    /// (`occupied` = `None`: [`schedule_with_options`](Self::schedule_with_options)),
+    /// also returning where the run spent its time (see [`timing`]).
+    pub fn schedule_profiled(
+        &self,
+        occupied: Option<&NodeSchedMap>,
+        tasks: Vec<Task>,
+        opts: &ScheduleOptions,
+    ) -> Result<(NodeSchedMap, PhaseTimings), SchedulerError> {
+        let mut clock = PhaseClock::start();
+        let map = self.schedule_timed(tasks, opts, occupied, &mut clock)?;
+        Ok((map, clock.finish()))
+    }
+
+    /// Shared body of the public `schedule*` entry points.
+    fn schedule_on(
+        &self,
+        tasks: Vec<Task>,
+        opts: &ScheduleOptions,
+        existing: Option<&NodeSchedMap>,
+    ) -> Result<NodeSchedMap, SchedulerError> {
+        self.schedule_timed(tasks, opts, existing, &mut PhaseClock::start())
+    }
+
+    /// [`schedule_on`](Self::schedule_on), lapping `clock` at the end of
+    /// each phase.
+    fn schedule_timed(
+        &self,
+        mut tasks: Vec<Task>,
+        opts: &ScheduleOptions,
+        existing: Option<&NodeSchedMap>,
+        clock: &mut PhaseClock,
+    ) -> Result<NodeSchedMap, SchedulerError> {
+        opts.validate()?;
+
         // ── Preconditions ─────────────────────────────────────────────────────
         if tasks.is_empty() {
             return Err(SchedulerError::NoTasks);
         }
+        let max_tasks = opts.limits.max_tasks;
+        if self
+            .limit_hits
+            .exceeded(Limit::Tasks, tasks.len(), max_tasks)
+        {
+            return Err(SchedulerError::TooManyTasks {
+                count: tasks.len(),
+                max: max_tasks,
+            });
+        }
         if !self.node_config_manager.is_loaded() {
             return Err(SchedulerError::ConfigNotLoaded);
         }
+        validate_tasks(
+            &tasks,
+            opts.algorithm,
+            opts.limits.max_task_problems,
+            &self.limit_hits,
+        )
+        .map_err(SchedulerError::InvalidTasks)?;
+        for task in &tasks {
+            Self::check_whole_cpu(task, "", task.exact_utilization(), opts.utilization_epsilon)?;
+        }
+        let assigned = assign_priorities(&mut tasks, opts.priority_ordering);
+        if assigned > 0 {
+            info!(
+                ordering = %opts.priority_ordering,
+                tasks = assigned,
+                "assigned priorities to priority-0 real-time tasks"
+            );
+        }
+
+        clock.lap(Phase::Preconditions);
 
         // ── Per-call state ────────────────────────────────────────────────────
-        let avail = self.build_available_cpus();
+        let mut avail = self.build_available_cpus();
+        if let Some(allowed) = &opts.allowed_nodes {
+            avail.retain(|node, _| allowed.contains(node));
+            let allowed_list = || allowed.iter().cloned().collect::<Vec<_>>();
+            if avail.is_empty() {
+                return Err(SchedulerError::NoAllowedNodes {
+                    allowed: allowed_list(),
+                });
+            }
+            if let Some(task) = tasks
+                .iter()
+                .find(|t| !t.target_node.is_empty() && !opts.allows_node(&t.target_node))
+            {
+                return Err(SchedulerError::TargetNodeNotAllowed {
+                    task: task.name.clone(),
+                    node: task.target_node.clone(),
+                    allowed: allowed_list(),
+                });
+            }
+            info!(allowed = ?allowed, nodes = avail.len(), "placement restricted to allowed nodes");
+        }
+        let cordoned = self.node_config_manager.cordoned_nodes();
+        if !cordoned.is_empty() {
+            avail.retain(|node, _| !cordoned.contains(node));
+            info!(cordoned = ?cordoned, "cordoned nodes take no new placements");
+        }
         let mut util = Self::build_cpu_utilization(&avail);
+        if let Some(existing) = existing {
+            Self::seed_cpu_utilization(&mut util, existing);
+        }
+        self.seed_foreign_load(&mut util);
 
         info!(
-            algorithm = algorithm,
+            algorithm = %opts.algorithm,
+            threshold = opts.cpu_utilization_threshold,
+            seed = opts.seed,
             task_count = tasks.len(),
             node_count = avail.len(),
             "=== GlobalScheduler::schedule() ==="
         );
 
-        // ── Algorithm dispatch ────────────────────────────────────────────────
-        match algorithm {
-            "target_node_priority" => {
-                self.schedule_target_node_priority(&mut tasks, &avail, &mut util)?
-            }
-            "least_loaded" => self.schedule_least_loaded(&mut tasks, &avail, &mut util)?,
-            "best_fit_decreasing" => {
-                self.schedule_best_fit_decreasing(&mut tasks, &avail, &mut util)?
-            }
-            other => return Err(SchedulerError::UnknownAlgorithm(other.to_string())),
+        clock.lap(Phase::AvailableCpus);
+
+        if !opts.overrides(AdmissionOverride::SkipThreshold) {
+            self.check_cluster_capacity(
+                &tasks,
+                &avail,
+                &util,
+                opts.cpu_utilization_threshold,
+                opts.utilization_epsilon,
+            )?;
         }
 
+        clock.lap(Phase::Preconditions);
+
+        let mut pinned = if opts.reserve_pinned_cpus {
+            PinnedDemand::from_tasks(&tasks)
+        } else {
+            PinnedDemand::default()
+        };
+
+        // ── Algorithm dispatch ────────────────────────────────────────────────
+        let workloads = existing
+            .map(NodeWorkloads::from_schedule)
+            .unwrap_or_default();
+        let mut log = PlacementLog::new(
+            opts.log_policy,
+            tasks.len(),
+            self.sink.as_ref(),
+            workloads,
+            CacheGroups::new(&self.node_config_manager),
+        );
+        let run = match opts.algorithm {
+            SchedAlgorithm::TargetNodePriority => Self::schedule_target_node_priority,
+            SchedAlgorithm::LeastLoaded => Self::schedule_least_loaded,
+            SchedAlgorithm::BestFitDecreasing => Self::schedule_best_fit_decreasing,
+            SchedAlgorithm::RandomizedSpread => Self::schedule_randomized_spread,
+        };
+        let warm = if opts.warm_start.is_empty() {
+            Vec::new()
+        } else {
+            self.place_warm_started(&mut tasks, &avail, &mut util, &mut pinned, opts, &mut log)?
+        };
+        warm_start::place_rest(&mut tasks, &warm, |rest| {
+            run(self, rest, &avail, &mut util, &mut pinned, opts, &mut log)
+        })?;
+
+        clock.lap(Phase::Algorithm);
+
         // ── Post-schedule: Liu & Layland feasibility warning ──────────────────
-        self.run_liu_layland_check(&tasks);
+        self.run_liu_layland_check(&tasks, opts.utilization_epsilon);
+        clock.lap(Phase::Feasibility);
 
         // ── Collect results ───────────────────────────────────────────────────
-        let map = self.build_sched_map(tasks);
+        let mut map = self.build_sched_map(tasks, opts.max_task_duration)?;
+        if let Some(strategy) = opts.release_stagger {
+            stagger_releases(&mut map, strategy);
+        }
+        clock.lap(Phase::BuildSchedMap);
+        rta::log_report(&rta::analyse_schedule(&map));
+        if !opts.chains.is_empty() {
+            chain::log_report(&analyse_chains(&map, &opts.chains));
+        }
+
+        // ── Simulation gate for CPUs above the Liu & Layland bound ────────────
+        if opts.verify_with_simulation == Some(SimulationCheck::Strict) {
+            let mut combined = existing.cloned().unwrap_or_default();
+            for (node, node_tasks) in &map {
+                combined
+                    .entry(node.clone())
+                    .or_default()
+                    .extend(node_tasks.iter().cloned());
+            }
+            let misses = simulate::verify_schedule(
+                &combined,
+                opts.utilization_epsilon,
+                opts.priority_ordering,
+                opts.simulation_hyperperiod_limit,
+                opts.simulation_max_jobs,
+            )?;
+            if let Some(m) = misses.into_iter().next() {
+                return Err(SchedulerError::SimulatedDeadlineMiss {
+                    node: m.node,
+                    cpu: m.cpu,
+                    task: m.task,
+                    time_us: m.time_us,
+                });
+            }
+        }
+
+        clock.lap(Phase::Feasibility);
 
         info!(
             node_count = map.len(),
             total_tasks = map.values().map(|v| v.len()).sum::<usize>(),
             "=== Scheduling complete ==="
         );
+        for (node, node_tasks) in &map {
+            let cpus: BTreeSet<u32> = node_tasks.iter().map(|t| t.assigned_cpu).collect();
+            let total: Utilization = node_tasks.iter().map(|t| t.exact_utilization()).sum();
+            info!(
+                node        = %node,
+                tasks       = node_tasks.len(),
+                cpus        = ?cpus,
+                utilization = total.as_f64(),
+                "node summary"
+            );
+        }
 
         Ok(map)
     }
@@ -163,49 +582,36 @@ impl GlobalScheduler {
         tasks: &mut [Task],
         avail: &AvailCpus,
         util: &mut CpuUtil,
+        pinned: &mut PinnedDemand,
+        opts: &ScheduleOptions,
+        log: &mut PlacementLog,
     ) -> Result<(), SchedulerError> {
+        let threshold = opts.effective_threshold();
         info!("Executing target_node_priority algorithm");
-        let mut scheduled = 0usize;
-
+        // validate_tasks has checked every task has a workload_id and a
+        // target_node.
         for task in tasks.iter_mut() {
-            // workload_id is required by this algorithm
-            if task.workload_id.is_empty() {
-                return Err(SchedulerError::MissingWorkloadId {
-                    task: task.name.clone(),
-                });
-            }
-            // target_node is required by this algorithm
-            if task.target_node.is_empty() {
-                return Err(SchedulerError::MissingTargetNode {
-                    task: task.name.clone(),
-                });
-            }
-
-            let node = &task.target_node.clone();
-
-            // Admission control
-            match self.check_admission(task, node, util, avail) {
-                Ok(()) => {}
-                Err(reason) => {
-                    return Err(SchedulerError::AdmissionRejected {
-                        task: task.name.clone(),
-                        node: node.clone(),
-                        reason,
-                    });
-                }
-            }
+            // Admission control on the target (or fallback, if Preferred)
+            let node = &self.select_node(task, avail, util, log.workloads(), opts, |t| {
+                self.find_best_node_least_loaded(t, avail, util, log.workloads(), opts)
+            })?;
 
-            // Find the best CPU on the target node
-            match Self::find_best_cpu_for_task(task, node, avail, util) {
+            // Find the best CPU on the chosen node
+            let open = log.workloads().open_cpus(avail, task);
+            match log.cache_groups().find_cpu(task, node, &open, |cpus| {
+                Self::find_best_cpu_for_task(
+                    &self.node_config_manager,
+                    task,
+                    node,
+                    cpus,
+                    util,
+                    pinned,
+                    threshold,
+                )
+            }) {
                 Some(cpu) => {
-                    Self::assign_cpu_to_task(task, node, cpu, util);
-                    scheduled += 1;
-                    info!(
-                        task = %task.name,
-                        node = %node,
-                        cpu  = cpu,
-                        "✓ scheduled"
-                    );
+                    self.assign_cpu_to_task(task, node, cpu, util, pinned, opts)?;
+                    log.record(task, node, cpu);
                 }
                 None => {
                     return Err(SchedulerError::AdmissionRejected {
@@ -218,7 +624,7 @@ impl GlobalScheduler {
         }
 
         info!(
-            scheduled = scheduled,
+            scheduled = log.placed(),
             total = tasks.len(),
             "target_node_priority done"
         );
@@ -234,46 +640,46 @@ impl GlobalScheduler {
         tasks: &mut [Task],
         avail: &AvailCpus,
         util: &mut CpuUtil,
+        pinned: &mut PinnedDemand,
+        opts: &ScheduleOptions,
+        log: &mut PlacementLog,
     ) -> Result<(), SchedulerError> {
+        let threshold = opts.effective_threshold();
         info!("Executing least_loaded algorithm");
-        let mut scheduled = 0usize;
-
         for task in tasks.iter_mut() {
-            let best_node = self.find_best_node_least_loaded(task, avail, util);
-
-            match best_node {
-                Some(node) => {
-                    // find_best_node already validated admission; find the CPU
-                    match Self::find_best_cpu_for_task(task, &node, avail, util) {
-                        Some(cpu) => {
-                            Self::assign_cpu_to_task(task, &node, cpu, util);
-                            scheduled += 1;
-                            info!(
-                                task = %task.name,
-                                node = %node,
-                                cpu  = cpu,
-                                "✓ scheduled"
-                            );
-                        }
-                        None => {
-                            warn!(
-                                task = %task.name,
-                                node = %node,
-                                "✗ no suitable CPU despite node selection — skipping"
-                            );
-                        }
-                    }
+            let node = self.select_node(task, avail, util, log.workloads(), opts, |t| {
+                self.find_best_node_least_loaded(t, avail, util, log.workloads(), opts)
+            })?;
+
+            // select_node already validated admission; find the CPU
+            let open = log.workloads().open_cpus(avail, task);
+            match log.cache_groups().find_cpu(task, &node, &open, |cpus| {
+                Self::find_best_cpu_for_task(
+                    &self.node_config_manager,
+                    task,
+                    &node,
+                    cpus,
+                    util,
+                    pinned,
+                    threshold,
+                )
+            }) {
+                Some(cpu) => {
+                    self.assign_cpu_to_task(task, &node, cpu, util, pinned, opts)?;
+                    log.record(task, &node, cpu);
                 }
                 None => {
-                    return Err(SchedulerError::NoSchedulableNode {
-                        task: task.name.clone(),
-                    });
+                    self.sink.task_rejected(
+                        task,
+                        &node,
+                        "✗ no suitable CPU despite node selection — skipping",
+                    );
                 }
             }
         }
 
         info!(
-            scheduled = scheduled,
+            scheduled = log.placed(),
             total = tasks.len(),
             "least_loaded done"
         );
@@ -281,36 +687,42 @@ impl GlobalScheduler {
     }
 
     /// Find the node with the lowest current total utilisation that can also
-    /// admit `task`.  Returns `None` if no node qualifies.
+    /// admit `task`, or a near-tie closer to its zone (see [`proximity`]).
+    /// Returns `None` if no node qualifies.
     fn find_best_node_least_loaded(
         &self,
         task: &Task,
         avail: &AvailCpus,
         util: &CpuUtil,
+        workloads: &NodeWorkloads,
+        opts: &ScheduleOptions,
     ) -> Option<String> {
-        let mut best_node: Option<String> = None;
-        let mut lowest_util = f64::MAX;
+        let mut candidates = Vec::new();
 
         // BTreeMap iteration is alphabetically sorted — deterministic tie-breaking
         for (node_id, cpus) in avail {
             if cpus.is_empty() {
                 continue;
             }
-            if self.check_admission(task, node_id, util, avail).is_err() {
-                continue;
-            }
-            if Self::find_best_cpu_for_task(task, node_id, avail, util).is_none() {
+            if admission::node_verdict(
+                &self.node_config_manager,
+                task,
+                node_id,
+                avail,
+                util,
+                workloads,
+                opts,
+            )
+            .is_err()
+            {
                 continue;
             }
 
             let node_util = Self::calculate_node_utilization(util, node_id);
-            if node_util < lowest_util {
-                lowest_util = node_util;
-                best_node = Some(node_id.clone());
-            }
+            candidates.push((node_id.clone(), node_util));
         }
 
-        best_node
+        self.pick_candidate(task, candidates, Prefer::Lowest, opts)
     }
 
     // ─────────────────────────────────────────────────────────────────────────
@@ -322,48 +734,46 @@ impl GlobalScheduler {
         tasks: &mut [Task],
         avail: &AvailCpus,
         util: &mut CpuUtil,
+        pinned: &mut PinnedDemand,
+        opts: &ScheduleOptions,
+        log: &mut PlacementLog,
     ) -> Result<(), SchedulerError> {
+        let threshold = opts.effective_threshold();
         info!("Executing best_fit_decreasing algorithm");
 
         // Sort tasks largest WCET first — this is what "decreasing" means
-        tasks.sort_unstable_by(|a, b| b.runtime_us.cmp(&a.runtime_us));
-
-        let mut scheduled = 0usize;
+        tasks.sort_unstable_by_key(|t| std::cmp::Reverse(t.runtime_us));
 
         for task in tasks.iter_mut() {
-            let best_node = self.find_best_node_best_fit_decreasing(task, avail, util);
-
-            match best_node {
-                Some(node) => match Self::find_best_cpu_for_task(task, &node, avail, util) {
-                    Some(cpu) => {
-                        Self::assign_cpu_to_task(task, &node, cpu, util);
-                        scheduled += 1;
-                        info!(
-                            task    = %task.name,
-                            node    = %node,
-                            cpu     = cpu,
-                            wcet_us = task.runtime_us,
-                            "✓ scheduled"
-                        );
-                    }
-                    None => {
-                        warn!(
-                            task = %task.name,
-                            node = %node,
-                            "✗ no CPU on best-fit node — skipping"
-                        );
-                    }
-                },
+            let node = self.select_node(task, avail, util, log.workloads(), opts, |t| {
+                self.find_best_node_best_fit_decreasing(t, avail, util, log.workloads(), opts)
+            })?;
+
+            let open = log.workloads().open_cpus(avail, task);
+            match log.cache_groups().find_cpu(task, &node, &open, |cpus| {
+                Self::find_best_cpu_for_task(
+                    &self.node_config_manager,
+                    task,
+                    &node,
+                    cpus,
+                    util,
+                    pinned,
+                    threshold,
+                )
+            }) {
+                Some(cpu) => {
+                    self.assign_cpu_to_task(task, &node, cpu, util, pinned, opts)?;
+                    log.record(task, &node, cpu);
+                }
                 None => {
-                    return Err(SchedulerError::NoSchedulableNode {
-                        task: task.name.clone(),
-                    });
+                    self.sink
+                        .task_rejected(task, &node, "✗ no CPU on best-fit node — skipping");
                 }
             }
         }
 
         info!(
-            scheduled = scheduled,
+            scheduled = log.placed(),
             total = tasks.len(),
             "best_fit_decreasing done"
         );
@@ -371,107 +781,174 @@ impl GlobalScheduler {
     }
 
     /// Find the node that will have the highest utilisation after assignment
-    /// while still ≤ 1.0 (tightest fit = least wasted space).
-    /// Respects `task.target_node` if set (tries it first).
+    /// while still ≤ 1.0 (tightest fit = least wasted space), or a near-tie
+    /// closer to the task's zone (see [`proximity`]).
+    /// The `target_node` hint is handled by [`select_node`](Self::select_node).
     fn find_best_node_best_fit_decreasing(
         &self,
         task: &Task,
         avail: &AvailCpus,
         util: &CpuUtil,
+        workloads: &NodeWorkloads,
+        opts: &ScheduleOptions,
     ) -> Option<String> {
-        // If the task nominates a target node, try it first
-        if !task.target_node.is_empty() {
-            let node = &task.target_node;
-            if self.check_admission(task, node, util, avail).is_ok()
-                && Self::find_best_cpu_for_task(task, node, avail, util).is_some()
-            {
-                debug!(task = %task.name, node = %node, "using target_node hint in best_fit_decreasing");
-                return Some(node.clone());
-            } else {
-                warn!(
-                    task = %task.name,
-                    node = %node,
-                    "target_node not available in best_fit_decreasing, falling back to auto-select"
-                );
-            }
-        }
-
-        let task_util = task.utilization();
-        let mut best_node: Option<String> = None;
-        let mut best_after: f64 = -1.0;
+        let slack = Utilization::from_f64(opts.utilization_epsilon);
+        let mut candidates = Vec::new();
 
         for (node_id, cpus) in avail {
             if cpus.is_empty() {
                 continue;
             }
-            if self.check_admission(task, node_id, util, avail).is_err() {
-                continue;
-            }
-            if Self::find_best_cpu_for_task(task, node_id, avail, util).is_none() {
+            if admission::node_verdict(
+                &self.node_config_manager,
+                task,
+                node_id,
+                avail,
+                util,
+                workloads,
+                opts,
+            )
+            .is_err()
+            {
                 continue;
             }
 
-            let after = Self::calculate_node_utilization(util, node_id) + task_util;
-            // Best fit: highest projected utilisation that stays under the
-            // total CPU count (≤ 1.0 per CPU, measured as total / cpu_count,
-            // but we use raw sum ≤ cpu_count for simplicity)
-            let cpu_count = cpus.len() as f64;
-            if after <= cpu_count && after > best_after {
-                best_after = after;
-                best_node = Some(node_id.clone());
+            let after = Self::calculate_node_utilization(util, node_id)
+                + task.exact_utilization_on(self.architecture(node_id));
+            // Best fit: highest projected utilisation that stays within the
+            // node's capacity — its cpu_share of each available CPU (the
+            // whole CPU unless time-partitioned), plus ε slack.
+            let share = Utilization::from_f64(self.node_config_manager.cpu_share(node_id));
+            if after <= share.times(cpus.len()) + slack {
+                candidates.push((node_id.clone(), after));
             }
         }
 
-        best_node
+        self.pick_candidate(task, candidates, Prefer::Highest, opts)
     }
 
     // ─────────────────────────────────────────────────────────────────────────
     // Shared helpers
     // ─────────────────────────────────────────────────────────────────────────
 
-    /// Admission control gate: check whether `task` is eligible to run on
-    /// `node_id`.
+    /// Reject up front a task set that cannot fit even with perfect packing.
     ///
-    /// Checks (in order):
-    /// 1. Node exists in config.
-    /// 2. Memory budget (`task.memory_mb == 0` → skip; dormant until proto
-    ///    carries the field).
-    /// 3. If `CpuAffinity::Pinned`, the pinned CPU must be in the node's set.
-    fn check_admission(
+    /// Compares the total task utilisation with the sum of per-CPU headroom
+    /// below `threshold` (scaled by each node's
+    /// [`cpu_share`](NodeConfigManager::cpu_share)) across every configured
+    /// CPU.  Targets, pinning and per-CPU fragmentation are deliberately
+    /// ignored, so this never rejects a set the algorithms could place.  `epsilon` per CPU is tolerated but
+    /// not reported as available.  Each task counts at its cheapest
+    /// architecture among the candidate nodes.
+    fn check_cluster_capacity(
         &self,
-        task: &Task,
-        node_id: &str,
-        _util: &CpuUtil,
+        tasks: &[Task],
         avail: &AvailCpus,
-    ) -> Result<(), AdmissionReason> {
-        // 1. Node must exist in config
-        let node_cfg = self
-            .node_config_manager
-            .get_node_config(node_id)
-            .ok_or_else(|| AdmissionReason::NodeNotFound {
-                node: node_id.to_string(),
-            })?;
-
-        // 2. Memory (dormant while task.memory_mb == 0)
-        if task.memory_mb > 0 && task.memory_mb > node_cfg.max_memory_mb {
-            return Err(AdmissionReason::InsufficientMemory {
-                required_mb: task.memory_mb,
-                available_mb: node_cfg.max_memory_mb,
+        util: &CpuUtil,
+        threshold: f64,
+        epsilon: f64,
+    ) -> Result<(), SchedulerError> {
+        let node_limit = |node: &str| {
+            Utilization::from_f64(threshold * self.node_config_manager.cpu_share(node))
+        };
+        let cpu_count: usize = avail.values().map(Vec::len).sum();
+        let used: Utilization = avail
+            .iter()
+            .flat_map(|(node, cpus)| {
+                let limit = node_limit(node);
+                cpus.iter()
+                    .map(move |&cpu| Self::calculate_cpu_utilization(util, node, cpu).min(limit))
+            })
+            .sum();
+        let architectures: BTreeSet<&str> =
+            avail.keys().map(|node| self.architecture(node)).collect();
+        let required: Utilization = tasks
+            .iter()
+            .map(|t| {
+                architectures
+                    .iter()
+                    .map(|arch| t.exact_utilization_on(arch))
+                    .min()
+                    .unwrap_or_else(|| t.exact_utilization())
+            })
+            .sum();
+        let capacity: Utilization = avail
+            .iter()
+            .map(|(node, cpus)| node_limit(node).times(cpus.len()))
+            .sum();
+
+        if required + used > capacity + Utilization::from_f64(epsilon).times(cpu_count) {
+            let available = capacity.as_f64() - used.as_f64();
+            warn!(
+                required = required.as_f64(),
+                available,
+                cpu_count,
+                "workload exceeds aggregate cluster capacity — rejected before placement"
+            );
+            return Err(SchedulerError::ClusterCapacityExceeded {
+                required: required.as_f64(),
+                available,
             });
         }
+        Ok(())
+    }
 
-        // 3. Pinned CPU affinity must be in this node's CPU set
-        if let CpuAffinity::Pinned(mask) = task.affinity {
-            let required_cpu = mask.trailing_zeros();
-            let node_cpus = avail.get(node_id).map(|v| v.as_slice()).unwrap_or(&[]);
-            if !node_cpus.contains(&required_cpu) {
-                return Err(AdmissionReason::CpuAffinityUnavailable {
-                    requested_cpu: required_cpu,
-                });
+    /// Choose the node for `task`, honouring its `target_node` policy (the
+    /// algorithm's default when the task sets none).
+    ///
+    /// | `policy`          | target admits task | target cannot take task              |
+    /// |-------------------|--------------------|--------------------------------------|
+    /// | `Some(Hard)`      | target             | `AdmissionRejected`                  |
+    /// | `Some(Preferred)` | target             | `auto_select`, `target_fallback` set |
+    /// | `None`            | `auto_select`      | `auto_select`                        |
+    ///
+    /// An empty `target_node` always goes to `auto_select`.  Returns
+    /// `NoSchedulableNode` if `auto_select` finds nothing.
+    fn select_node(
+        &self,
+        task: &mut Task,
+        avail: &AvailCpus,
+        util: &CpuUtil,
+        workloads: &NodeWorkloads,
+        opts: &ScheduleOptions,
+        auto_select: impl FnOnce(&Task) -> Option<String>,
+    ) -> Result<String, SchedulerError> {
+        let policy = task
+            .target_node_policy
+            .or(opts.algorithm.default_target_policy());
+        if let Some(policy) = policy.filter(|_| !task.target_node.is_empty()) {
+            let node = task.target_node.clone();
+            let verdict = admission::node_verdict(
+                &self.node_config_manager,
+                task,
+                &node,
+                avail,
+                util,
+                workloads,
+                opts,
+            );
+            match (verdict, policy) {
+                (Ok(()), _) => {
+                    debug!(task = %task.name, node = %node, ?policy, "using target_node");
+                    return Ok(node);
+                }
+                (Err(reason), TargetNodePolicy::Hard) => {
+                    return Err(SchedulerError::AdmissionRejected {
+                        task: task.name.clone(),
+                        node,
+                        reason,
+                    });
+                }
+                (Err(reason), TargetNodePolicy::Preferred) => {
+                    self.sink.node_skipped(task, &node, &reason);
+                    task.target_fallback = true;
+                }
             }
         }
 
-        Ok(())
+        auto_select(task).ok_or_else(|| SchedulerError::NoSchedulableNode {
+            task: task.name.clone(),
+        })
     }
 
     /// Find the best CPU for `task` on `node_id`.
@@ -479,36 +956,48 @@ impl GlobalScheduler {
     /// Logic (mirrors C++ `find_best_cpu_for_task`):
     /// * If `CpuAffinity::Pinned`: try the lowest set bit first; fall through
     ///   to packing if that CPU would exceed the threshold.
+    /// * The threshold is scaled by the node's
+    ///   [`cpu_share`](NodeConfigManager::cpu_share) (time partitions).
     /// * For `Any` (or pinned-but-threshold-exceeded): sort CPUs
     ///   **highest-first** and return the first that fits under
-    ///   `CPU_UTILIZATION_THRESHOLD`.  Highest-first packs tasks onto the
+    ///   `threshold`.  Highest-first packs tasks onto the
     ///   upper CPUs, leaving lower CPUs free for new workloads.
+    /// * With pending [`PinnedDemand`], the first CPU that also leaves room
+    ///   for it wins; plain packing is the fallback.  So *whether* a CPU is
+    ///   found never depends on `pinned`, and node selection passes
+    ///   [`PinnedDemand::NONE`].
     ///
     /// Returns `None` if no CPU can accommodate the task.
-    fn find_best_cpu_for_task(
+    pub(crate) fn find_best_cpu_for_task(
+        config: &NodeConfigManager,
         task: &Task,
         node_id: &str,
         avail: &AvailCpus,
         util: &CpuUtil,
+        pinned: &PinnedDemand,
+        threshold: f64,
     ) -> Option<u32> {
         let cpus = avail.get(node_id)?;
         if cpus.is_empty() {
             return None;
         }
 
-        let task_util = task.utilization();
+        let task_util = task.exact_utilization_on(Self::architecture_in(config, node_id));
+        // A time-partitioned node only has its windows' share of each CPU.
+        let threshold = threshold * config.cpu_share(node_id);
+        let limit = Utilization::from_f64(threshold);
 
         // Try pinned CPU first
         if let CpuAffinity::Pinned(mask) = task.affinity {
             let pinned = mask.trailing_zeros();
             if cpus.contains(&pinned) {
                 let current = Self::calculate_cpu_utilization(util, node_id, pinned);
-                if current + task_util <= CPU_UTILIZATION_THRESHOLD {
+                if current + task_util <= limit {
                     debug!(
                         task = %task.name,
                         cpu  = pinned,
-                        current_pct = current * 100.0,
-                        added_pct   = task_util * 100.0,
+                        current_pct = current.as_f64() * 100.0,
+                        added_pct   = task_util.as_f64() * 100.0,
                         "using pinned CPU affinity"
                     );
                     return Some(pinned);
@@ -516,8 +1005,8 @@ impl GlobalScheduler {
                     warn!(
                         task     = %task.name,
                         cpu      = pinned,
-                        after_pct = (current + task_util) * 100.0,
-                        threshold_pct = CPU_UTILIZATION_THRESHOLD * 100.0,
+                        after_pct = (current + task_util).as_f64() * 100.0,
+                        threshold_pct = threshold * 100.0,
                         "pinned CPU would exceed threshold — falling back to packing"
                     );
                 }
@@ -528,14 +1017,34 @@ impl GlobalScheduler {
         let mut sorted: Vec<u32> = cpus.clone();
         sorted.sort_unstable_by(|a, b| b.cmp(a)); // descending
 
+        // Reservation: prefer a CPU that still fits the pinned demand pending
+        // there (empty unless `reserve_pinned_cpus` is set)
+        if !pinned.is_empty() {
+            let reserved = sorted.iter().copied().find(|&cpu| {
+                Self::calculate_cpu_utilization(util, node_id, cpu)
+                    + task_util
+                    + pinned.pending(node_id, cpu)
+                    <= limit
+            });
+            if let Some(cpu) = reserved {
+                debug!(
+                    task = %task.name,
+                    cpu  = cpu,
+                    pending_pct = pinned.pending(node_id, cpu).as_f64() * 100.0,
+                    "selected CPU (packing around pinned demand)"
+                );
+                return Some(cpu);
+            }
+        }
+
         for cpu in sorted {
             let current = Self::calculate_cpu_utilization(util, node_id, cpu);
-            if current + task_util <= CPU_UTILIZATION_THRESHOLD {
+            if current + task_util <= limit {
                 debug!(
                     task      = %task.name,
                     cpu       = cpu,
-                    before_pct = current * 100.0,
-                    after_pct  = (current + task_util) * 100.0,
+                    before_pct = current.as_f64() * 100.0,
+                    after_pct  = (current + task_util).as_f64() * 100.0,
                     "selected CPU (packing)"
                 );
                 return Some(cpu);
@@ -548,11 +1057,26 @@ impl GlobalScheduler {
     /// Assign `task` to `node_id:cpu_id`.
     ///
     /// Sets `task.assigned_node` and `task.assigned_cpu`, then increments the
-    /// CPU utilisation tracker.  The CPU is **not** removed from `avail` —
+    /// CPU utilisation tracker by the task's utilisation on that node's
+    /// architecture.  The CPU is **not** removed from `avail` —
     /// multiple tasks may share a core as long as total utilisation stays
-    /// under the threshold.
-    fn assign_cpu_to_task(task: &mut Task, node_id: &str, cpu_id: u32, util: &mut CpuUtil) {
-        let task_util = task.utilization();
+    /// under the threshold.  The task's entry in `pinned` is released.
+    ///
+    /// Fails with [`SchedulerError::InvalidTask`], changing nothing, if the
+    /// task needs more than one whole CPU on `node_id`'s architecture: the
+    /// threshold normally keeps such a task out, but not under
+    /// [`AdmissionOverride::SkipThreshold`].
+    fn assign_cpu_to_task(
+        &self,
+        task: &mut Task,
+        node_id: &str,
+        cpu_id: u32,
+        util: &mut CpuUtil,
+        pinned: &mut PinnedDemand,
+        opts: &ScheduleOptions,
+    ) -> Result<(), SchedulerError> {
+        let task_util = task.exact_utilization_on(self.architecture(node_id));
+        Self::check_whole_cpu(task, node_id, task_util, opts.utilization_epsilon)?;
         let prev = Self::calculate_cpu_utilization(util, node_id, cpu_id);
         let next = prev + task_util;
 
@@ -562,24 +1086,59 @@ impl GlobalScheduler {
         util.entry(node_id.to_string())
             .or_default()
             .insert(cpu_id, next);
+        pinned.release(task);
 
         debug!(
             task      = %task.name,
             node      = %node_id,
             cpu       = cpu_id,
-            before_pct = prev * 100.0,
-            after_pct  = next * 100.0,
+            before_pct = prev.as_f64() * 100.0,
+            after_pct  = next.as_f64() * 100.0,
             "CPU assigned"
         );
+        Ok(())
+    }
+
+    /// Reject `task` if `utilization` — its own, or on `node_id` after
+    /// `wcet_scaling` — is more than one whole CPU (plus `epsilon`, as in
+    /// every fit check).
+    fn check_whole_cpu(
+        task: &Task,
+        node_id: &str,
+        utilization: Utilization,
+        epsilon: f64,
+    ) -> Result<(), SchedulerError> {
+        if utilization <= Utilization::cpus(1) + Utilization::from_f64(epsilon) {
+            return Ok(());
+        }
+        Err(SchedTaskConversionError::OverUtilized {
+            task: task.name.clone(),
+            node: node_id.to_string(),
+            utilization,
+        }
+        .into())
+    }
+
+    /// `architecture` of `node_id`, or `""` (which no `wcet_scaling` entry
+    /// matches) if it is not configured.
+    fn architecture(&self, node_id: &str) -> &str {
+        Self::architecture_in(&self.node_config_manager, node_id)
+    }
+
+    /// [`architecture`](Self::architecture) of `node_id` in `config`.
+    fn architecture_in<'a>(config: &'a NodeConfigManager, node_id: &str) -> &'a str {
+        config
+            .get_node_config(node_id)
+            .map_or("", |cfg| cfg.architecture.as_str())
     }
 
-    /// Per-CPU utilisation for `(node_id, cpu_id)`.  Returns `0.0` if not
+    /// Per-CPU utilisation for `(node_id, cpu_id)`.  Returns zero if not
     /// tracked yet.
-    fn calculate_cpu_utilization(util: &CpuUtil, node_id: &str, cpu_id: u32) -> f64 {
+    fn calculate_cpu_utilization(util: &CpuUtil, node_id: &str, cpu_id: u32) -> Utilization {
         util.get(node_id)
             .and_then(|m| m.get(&cpu_id))
             .copied()
-            .unwrap_or(0.0)
+            .unwrap_or_default()
     }
 
     /// Total utilisation for `node_id` — sum of all per-CPU values.
@@ -587,10 +1146,10 @@ impl GlobalScheduler {
     /// **Does not** re-scan the task list; reads directly from the live
     /// utilisation map, eliminating the O(tasks × nodes) scan in the C++
     /// `calculate_node_utilization`.
-    fn calculate_node_utilization(util: &CpuUtil, node_id: &str) -> f64 {
+    fn calculate_node_utilization(util: &CpuUtil, node_id: &str) -> Utilization {
         util.get(node_id)
             .map(|m| m.values().copied().sum())
-            .unwrap_or(0.0)
+            .unwrap_or_default()
     }
 
     /// Sort CPUs for a node by utilisation.
@@ -617,11 +1176,10 @@ impl GlobalScheduler {
             let ub = Self::calculate_cpu_utilization(util, node_id, b);
             // Primary: utilisation order
             let util_ord = if prefer_high_util {
-                ub.partial_cmp(&ua)
+                ub.cmp(&ua)
             } else {
-                ua.partial_cmp(&ub)
-            }
-            .unwrap_or(std::cmp::Ordering::Equal);
+                ua.cmp(&ub)
+            };
             // Secondary: higher CPU number preferred
             if util_ord == std::cmp::Ordering::Equal {
                 b.cmp(&a)
@@ -636,38 +1194,77 @@ impl GlobalScheduler {
     // Initialisation helpers
     // ─────────────────────────────────────────────────────────────────────────
 
-    /// Build the initial available-CPU map from the loaded node configuration.
+    /// Build the initial available-CPU map from the loaded node configuration,
+    /// without the CPUs the nodes report offline (see
+    /// [`crate::config::NodeConfigManager::report_online_cpus`]).
     fn build_available_cpus(&self) -> AvailCpus {
         let mut avail = AvailCpus::new();
-        for (name, cfg) in self.node_config_manager.get_all_nodes() {
-            avail.insert(name.clone(), cfg.available_cpus.clone());
+        for name in self.node_config_manager.get_all_nodes().keys() {
+            let cpus = self
+                .node_config_manager
+                .usable_cpus(name)
+                .unwrap_or_default();
             info!(
                 node     = %name,
-                cpu_count = cfg.available_cpus.len(),
-                cpus     = ?cfg.available_cpus,
+                cpu_count = cpus.len(),
+                cpus     = ?cpus,
                 "node initialised"
             );
+            avail.insert(name.clone(), cpus);
         }
         avail
     }
 
-    /// Build the CPU utilisation map initialised to 0.0 for every CPU.
-    fn build_cpu_utilization(avail: &AvailCpus) -> CpuUtil {
+    /// Build the CPU utilisation map initialised to zero for every CPU.
+    pub(crate) fn build_cpu_utilization(avail: &AvailCpus) -> CpuUtil {
         let mut util = CpuUtil::new();
         for (node_id, cpus) in avail {
-            let cpu_map: BTreeMap<u32, f64> = cpus.iter().map(|&c| (c, 0.0)).collect();
+            let cpu_map: BTreeMap<u32, Utilization> =
+                cpus.iter().map(|&c| (c, Utilization::ZERO)).collect();
             util.insert(node_id.clone(), cpu_map);
         }
         util
     }
 
+    /// Add the utilisation of every task in `schedule` to `util`.
+    ///
+    /// Tasks on nodes or CPUs that are no longer configured are still
+    /// recorded so the totals stay honest, but they can never be selected
+    /// because selection iterates `AvailCpus`, not `CpuUtil`.
+    pub(crate) fn seed_cpu_utilization(util: &mut CpuUtil, schedule: &NodeSchedMap) {
+        for (node_id, node_tasks) in schedule {
+            let cpu_map = util.entry(node_id.clone()).or_default();
+            for t in node_tasks {
+                *cpu_map.entry(t.assigned_cpu).or_default() += t.exact_utilization();
+            }
+        }
+    }
+
+    /// Add the foreign load nodes reported to `util` when the manager
+    /// respects it (see [`crate::config`]'s foreign-load docs).  Only CPUs
+    /// already in `util` are charged.
+    pub(crate) fn seed_foreign_load(&self, util: &mut CpuUtil) {
+        if !self.node_config_manager.respects_foreign_load() {
+            return;
+        }
+        for (node_id, cpu_map) in util.iter_mut() {
+            for (cpu, load) in self.node_config_manager.foreign_load(node_id) {
+                if let Some(u) = cpu_map.get_mut(&cpu) {
+                    *u += Utilization::from_f64(load);
+                }
+            }
+        }
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // Post-schedule helpers
     // ─────────────────────────────────────────────────────────────────────────
 
     /// Group assigned tasks by node and run the Liu & Layland check on each
-    /// group.  Emits `warn!` if a node's task set may not be RM-schedulable.
-    fn run_liu_layland_check(&self, tasks: &[Task]) {
+    /// group.  Reports a `feasibility_warning` to the sink if a node's task set
+    /// may not be RM-schedulable.  The bound is scaled by the node's
+    /// [`cpu_share`](NodeConfigManager::cpu_share).
+    fn run_liu_layland_check(&self, tasks: &[Task], epsilon: f64) {
         // Group by assigned node
         let mut by_node: BTreeMap<&str, Vec<&Task>> = BTreeMap::new();
         for task in tasks {
@@ -677,15 +1274,23 @@ impl GlobalScheduler {
         }
 
         for (node_id, node_tasks) in &by_node {
-            let refs: Vec<&Task> = node_tasks.to_vec();
-            if let Some(total_u) = check_liu_layland(&refs) {
-                warn!(
-                    node       = %node_id,
-                    utilization = total_u,
-                    bound       = liu_layland_bound(refs.len()),
-                    task_count  = refs.len(),
-                    "task set may not be RM-schedulable (utilization exceeds Liu & Layland bound) \
-                     — manual Response Time Analysis required"
+            let arch = self.architecture(node_id);
+            let scaled: Vec<Task> = node_tasks
+                .iter()
+                .map(|t| Task {
+                    runtime_us: t.runtime_on(arch),
+                    ..(*t).clone()
+                })
+                .collect();
+            let refs: Vec<&Task> = scaled.iter().collect();
+            let share = self.node_config_manager.cpu_share(node_id);
+            if let Some(total_u) = check_liu_layland_scaled(&refs, share, epsilon) {
+                self.sink.feasibility_warning(
+                    node_id,
+                    total_u,
+                    liu_layland_bound(refs.len()) * share,
+                    share,
+                    refs.len(),
                 );
             }
         }
@@ -694,18 +1299,27 @@ impl GlobalScheduler {
     /// Consume the scheduled `tasks` and build the final [`NodeSchedMap`].
     ///
     /// Replaces C++ `generate_schedules()` (malloc / strncpy / free).
-    /// Unassigned tasks (no `assigned_node`) are silently dropped — the
-    /// algorithm is responsible for returning an error before reaching this
-    /// point if a required task could not be placed.
-    fn build_sched_map(&self, tasks: Vec<Task>) -> NodeSchedMap {
+    /// `runtime_ns` is the runtime scaled for the assigned node's
+    /// architecture.  Unassigned tasks (no `assigned_node`) are silently
+    /// dropped — the algorithm is responsible for returning an error before
+    /// reaching this point if a required task could not be placed.
+    ///
+    /// Fails with [`SchedulerError::InvalidTask`] if a task's timing is
+    /// longer than `max` (see [`SchedTask::from_task`]).
+    fn build_sched_map(
+        &self,
+        tasks: Vec<Task>,
+        max: Nanos,
+    ) -> Result<NodeSchedMap, SchedulerError> {
         let mut map: NodeSchedMap = NodeSchedMap::new();
-        for task in tasks {
+        for mut task in tasks {
             if task.is_assigned() {
-                let st = SchedTask::from_task(&task);
+                task.runtime_us = task.runtime_on(self.architecture(&task.assigned_node));
+                let st = SchedTask::from_task(&task, max)?;
                 map.entry(task.assigned_node).or_default().push(st);
             }
         }
-        map
+        Ok(map)
     }
 }
 
@@ -714,41 +1328,24 @@ impl GlobalScheduler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::NodeConfigManager;
-    use crate::task::{CpuAffinity, Task};
-    use std::io::Write;
-    use tempfile::NamedTempFile;
+    use crate::config::{NodeConfig, NodeConfigManager};
+    use crate::task::{CpuAffinity, Micros, Nanos, Task};
+    use crate::testing::{example_nodes, fake_node, fake_nodes, fake_task};
 
     // ── Test helpers ──────────────────────────────────────────────────────────
 
-    fn write_yaml(content: &str) -> NamedTempFile {
-        let mut f = NamedTempFile::new().unwrap();
-        f.write_all(content.as_bytes()).unwrap();
-        f
-    }
-
     /// Two-node config:
     ///   node01 – CPUs [2, 3]          – 4096 MB
     ///   node02 – CPUs [2, 3, 4, 5]   – 8192 MB
     fn two_node_scheduler() -> GlobalScheduler {
-        let yaml = r#"
-nodes:
-  node01:
-    available_cpus: [2, 3]
-    max_memory_mb: 4096
-  node02:
-    available_cpus: [2, 3, 4, 5]
-    max_memory_mb: 8192
-"#;
-        let f = write_yaml(yaml);
-        let mut mgr = NodeConfigManager::new();
-        mgr.load_from_file(f.path()).unwrap();
-        // Keep the tempfile alive for the test duration via a leak-and-forget
-        std::mem::forget(f);
-        GlobalScheduler::new(Arc::new(mgr))
-    }
-
-    /// Single task with a given target node, period, and runtime.
+        GlobalScheduler::new(Arc::new(fake_nodes(&[
+            ("node01", &[2, 3], 4096),
+            ("node02", &[2, 3, 4, 5], 8192),
+        ])))
+    }
+
+    /// Single task of `workload` with a given target node, period, and
+    /// runtime.
     fn make_task(
         name: &str,
         workload: &str,
@@ -757,13 +1354,8 @@ nodes:
         runtime_us: u64,
     ) -> Task {
         Task {
-            name: name.to_string(),
             workload_id: workload.to_string(),
-            target_node: target.to_string(),
-            period_us,
-            runtime_us,
-            deadline_us: period_us,
-            ..Default::default()
+            ..fake_task(name, target, period_us, runtime_us)
         }
     }
 
@@ -790,9 +1382,9 @@ nodes:
             workload_id: "wl1".to_string(),
             target_node: "node01".to_string(),
             affinity: CpuAffinity::Pinned(0b0100), // CPU 2
-            period_us: 10_000,
-            runtime_us: 1_000,
-            deadline_us: 10_000,
+            period_us: Micros(10_000),
+            runtime_us: Micros(1_000),
+            deadline_us: Micros(10_000),
             ..Default::default()
         };
         let map = sched.schedule(vec![task], "target_node_priority").unwrap();
@@ -806,14 +1398,16 @@ nodes:
             name: "no_target".to_string(),
             workload_id: "wl1".to_string(),
             target_node: String::new(), // intentionally empty
-            period_us: 10_000,
-            runtime_us: 1_000,
+            period_us: Micros(10_000),
+            runtime_us: Micros(1_000),
             ..Default::default()
         };
         let err = sched
             .schedule(vec![task], "target_node_priority")
             .unwrap_err();
-        assert!(matches!(err, SchedulerError::MissingTargetNode { .. }));
+        assert_eq!(err.code(), ErrorCode::InvalidTasks);
+        assert_eq!(err.task(), Some("no_target"));
+        assert!(err.to_string().contains("no target_node"), "{err}");
     }
 
     #[test]
@@ -823,14 +1417,16 @@ nodes:
             name: "no_wl".to_string(),
             workload_id: String::new(), // intentionally empty
             target_node: "node01".to_string(),
-            period_us: 10_000,
-            runtime_us: 1_000,
+            period_us: Micros(10_000),
+            runtime_us: Micros(1_000),
             ..Default::default()
         };
         let err = sched
             .schedule(vec![task], "target_node_priority")
             .unwrap_err();
-        assert!(matches!(err, SchedulerError::MissingWorkloadId { .. }));
+        assert_eq!(err.code(), ErrorCode::InvalidTasks);
+        assert_eq!(err.task(), Some("no_wl"));
+        assert!(err.to_string().contains("no workload_id"), "{err}");
     }
 
     // ── least_loaded ──────────────────────────────────────────────────────────
@@ -897,23 +1493,102 @@ nodes:
         }
     }
 
-    // ── Admission control ─────────────────────────────────────────────────────
+    // ── Target-node policy ────────────────────────────────────────────────────
+
+    /// Targets node01 but pins CPU 5, which only node02 has.
+    fn misfit_on_node01(policy: Option<TargetNodePolicy>) -> Task {
+        Task {
+            affinity: CpuAffinity::Pinned(1 << 5),
+            target_node_policy: policy,
+            ..make_task("pinned5", "wl1", "node01", 10_000, 1_000)
+        }
+    }
 
     #[test]
-    fn admission_rejects_over_memory() {
+    fn hard_target_is_rejected_when_it_cannot_take_task() {
+        let sched = two_node_scheduler();
+        for alg in [
+            "target_node_priority",
+            "least_loaded",
+            "best_fit_decreasing",
+        ] {
+            let task = misfit_on_node01(Some(TargetNodePolicy::Hard));
+            let err = sched.schedule(vec![task], alg).unwrap_err();
+            assert!(
+                matches!(
+                    &err,
+                    SchedulerError::AdmissionRejected { node, .. } if node == "node01"
+                ),
+                "{alg}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn preferred_target_falls_back_and_is_recorded() {
+        let sched = two_node_scheduler();
+        for alg in [
+            "target_node_priority",
+            "least_loaded",
+            "best_fit_decreasing",
+        ] {
+            let task = misfit_on_node01(Some(TargetNodePolicy::Preferred));
+            let map = sched.schedule(vec![task], alg).unwrap();
+            let placed = &map["node02"][0];
+            assert_eq!(placed.assigned_cpu, 5, "{alg}");
+            assert_eq!(placed.fallback_from.as_deref(), Some("node01"), "{alg}");
+        }
+    }
+
+    #[test]
+    fn preferred_target_wins_over_auto_select() {
+        // Both nodes empty: least_loaded alone would pick node01.
         let sched = two_node_scheduler();
-        // node01 max_memory_mb = 4096; task requires 5000
         let task = Task {
-            name: "mem_hog".to_string(),
-            workload_id: "wl1".to_string(),
-            target_node: "node01".to_string(),
-            memory_mb: 5_000, // exceeds node01's 4096 MB
-            period_us: 10_000,
-            runtime_us: 1_000,
-            ..Default::default()
+            target_node_policy: Some(TargetNodePolicy::Preferred),
+            ..make_task("t1", "wl1", "node02", 10_000, 1_000)
         };
-        let err = sched
-            .schedule(vec![task], "target_node_priority")
+        let map = sched.schedule(vec![task], "least_loaded").unwrap();
+        assert_eq!(map["node02"][0].fallback_from, None);
+    }
+
+    #[test]
+    fn algorithm_default_policies_are_unchanged() {
+        let sched = two_node_scheduler();
+        // target_node_priority: Hard
+        let err = sched
+            .schedule(vec![misfit_on_node01(None)], "target_node_priority")
+            .unwrap_err();
+        assert!(matches!(err, SchedulerError::AdmissionRejected { .. }));
+        // best_fit_decreasing: Preferred
+        let map = sched
+            .schedule(vec![misfit_on_node01(None)], "best_fit_decreasing")
+            .unwrap();
+        assert!(map["node02"][0].fallback_from.is_some());
+        // least_loaded: target ignored, so not a fallback either
+        let map = sched
+            .schedule(vec![misfit_on_node01(None)], "least_loaded")
+            .unwrap();
+        assert_eq!(map["node02"][0].fallback_from, None);
+    }
+
+    // ── Admission control ─────────────────────────────────────────────────────
+
+    #[test]
+    fn admission_rejects_over_memory() {
+        let sched = two_node_scheduler();
+        // node01 max_memory_mb = 4096; task requires 5000
+        let task = Task {
+            name: "mem_hog".to_string(),
+            workload_id: "wl1".to_string(),
+            target_node: "node01".to_string(),
+            memory_mb: 5_000, // exceeds node01's 4096 MB
+            period_us: Micros(10_000),
+            runtime_us: Micros(1_000),
+            ..Default::default()
+        };
+        let err = sched
+            .schedule(vec![task], "target_node_priority")
             .unwrap_err();
         assert!(
             matches!(
@@ -927,6 +1602,155 @@ nodes:
         );
     }
 
+    #[test]
+    fn admission_overrides_skip_only_the_named_checks() {
+        let sched = two_node_scheduler();
+        let mem_hog = Task {
+            name: "mem_hog".to_string(),
+            workload_id: "wl1".to_string(),
+            target_node: "node01".to_string(),
+            memory_mb: 5_000, // exceeds node01's 4096 MB
+            period_us: Micros(10_000),
+            runtime_us: Micros(1_000),
+            ..Default::default()
+        };
+        let skip = |o| ScheduleOptions::default().with_admission_override(o);
+        assert!(sched
+            .schedule_with_options(
+                vec![mem_hog.clone()],
+                &skip(AdmissionOverride::SkipThreshold)
+            )
+            .is_err());
+        let map = sched
+            .schedule_with_options(vec![mem_hog], &skip(AdmissionOverride::SkipMemory))
+            .unwrap();
+        assert_eq!(map["node01"][0].name, "mem_hog");
+
+        // 95 % is above the 90 % threshold: only skip_threshold admits it.
+        let heavy = Task {
+            name: "heavy".to_string(),
+            workload_id: "wl1".to_string(),
+            target_node: "node01".to_string(),
+            period_us: Micros(10_000),
+            runtime_us: Micros(9_500),
+            ..Default::default()
+        };
+        assert!(sched
+            .schedule_with_options(vec![heavy.clone()], &skip(AdmissionOverride::SkipMemory))
+            .is_err());
+        assert!(sched
+            .schedule_with_options(vec![heavy], &skip(AdmissionOverride::SkipThreshold))
+            .is_ok());
+    }
+
+    #[test]
+    fn task_needing_more_than_one_cpu_is_rejected_as_invalid() {
+        let sched = two_node_scheduler();
+        // runtime = 3 × period: 300 % of one CPU.
+        let impossible = make_task("impossible", "wl1", "node01", 10_000, 30_000);
+        for opts in [
+            ScheduleOptions::default(),
+            ScheduleOptions::default().with_admission_override(AdmissionOverride::SkipThreshold),
+        ] {
+            let err = sched
+                .schedule_with_options(vec![impossible.clone()], &opts)
+                .unwrap_err();
+            assert_eq!(err.code(), ErrorCode::InvalidTask);
+            match err {
+                SchedulerError::InvalidTask(SchedTaskConversionError::OverUtilized {
+                    task,
+                    node,
+                    utilization,
+                }) => {
+                    assert_eq!(task, "impossible");
+                    assert_eq!(node, "");
+                    assert_eq!(utilization, Utilization::cpus(3));
+                }
+                other => panic!("expected OverUtilized, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn wcet_scaling_past_one_cpu_is_rejected_on_that_node() {
+        let sched = two_node_scheduler();
+        // 60 % as declared, 120 % on aarch64: only skip_threshold gets it
+        // as far as assignment, which must still refuse it.
+        let slow = Task {
+            wcet_scaling: [("aarch64".into(), 2.0)].into(),
+            ..make_task("slow", "wl1", "node01", 10_000, 6_000)
+        };
+        let opts =
+            ScheduleOptions::default().with_admission_override(AdmissionOverride::SkipThreshold);
+        let err = sched
+            .schedule_with_options(vec![slow.clone()], &opts)
+            .unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                SchedulerError::InvalidTask(SchedTaskConversionError::OverUtilized { node, .. })
+                    if node == "node01"
+            ),
+            "{err:?}"
+        );
+        assert_eq!(
+            sched
+                .schedule_with_options(vec![slow], &ScheduleOptions::default())
+                .unwrap_err()
+                .code(),
+            ErrorCode::AdmissionRejected
+        );
+    }
+
+    #[test]
+    fn shrinking_free_memory_flips_admission() {
+        let mgr = NodeConfigManager::from_nodes(vec![NodeConfig {
+            max_memory_mb: 4096,
+            ..NodeConfig::default_config("node01")
+        }])
+        .with_live_memory(std::time::Duration::from_secs(30));
+        let sched = GlobalScheduler::new(Arc::new(mgr));
+        let task = Task {
+            name: "t".into(),
+            workload_id: "wl1".into(),
+            target_node: "node01".into(),
+            memory_mb: 1_000,
+            period_us: Micros(10_000),
+            runtime_us: Micros(1_000),
+            ..Default::default()
+        };
+        let admit = |free_mb: u64| {
+            sched
+                .node_config_manager
+                .report_free_memory("node01", free_mb, 0);
+            sched.schedule(vec![task.clone()], "target_node_priority")
+        };
+
+        // Successive heartbeats: 3000 → 1500 → 800 MB free.
+        assert!(admit(3_000).is_ok());
+        assert!(admit(1_500).is_ok());
+        let err = admit(800).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                SchedulerError::AdmissionRejected {
+                    reason: AdmissionReason::InsufficientMemory {
+                        required_mb: 1_000,
+                        available_mb: 800,
+                    },
+                    ..
+                }
+            ),
+            "expected InsufficientMemory rejection, got: {err}"
+        );
+
+        // Memory held by Timpani-O's own placements counts as available.
+        sched
+            .node_config_manager
+            .report_free_memory("node01", 800, 400);
+        assert!(sched.schedule(vec![task], "target_node_priority").is_ok());
+    }
+
     #[test]
     fn utilization_threshold_respected() {
         // Fill node01 CPU 3 to 85%, then try to add a 10% task (total 95% > 90%)
@@ -938,9 +1762,9 @@ nodes:
             workload_id: "wl1".to_string(),
             target_node: "node01".to_string(),
             affinity: CpuAffinity::Pinned(1 << 3), // CPU 3
-            period_us: 10_000,
-            runtime_us: 8_500, // 85%
-            deadline_us: 10_000,
+            period_us: Micros(10_000),
+            runtime_us: Micros(8_500), // 85%
+            deadline_us: Micros(10_000),
             ..Default::default()
         };
         // Schedules the filler first; result is dropped intentionally
@@ -953,9 +1777,9 @@ nodes:
             workload_id: "wl1".to_string(),
             target_node: "node01".to_string(),
             affinity: CpuAffinity::Pinned(1 << 3), // CPU 3
-            period_us: 10_000,
-            runtime_us: 8_500, // 85%
-            deadline_us: 10_000,
+            period_us: Micros(10_000),
+            runtime_us: Micros(8_500), // 85%
+            deadline_us: Micros(10_000),
             ..Default::default()
         };
         let over = Task {
@@ -963,9 +1787,9 @@ nodes:
             workload_id: "wl1".to_string(),
             target_node: "node01".to_string(),
             affinity: CpuAffinity::Pinned(1 << 3), // CPU 3
-            period_us: 10_000,
-            runtime_us: 1_000, // 10% — pushes total to 95%
-            deadline_us: 10_000,
+            period_us: Micros(10_000),
+            runtime_us: Micros(1_000), // 10% — pushes total to 95%
+            deadline_us: Micros(10_000),
             ..Default::default()
         };
         // The 85% filler takes CPU 3. The 10% task tries CPU 3 → 95% > 90%.
@@ -977,6 +1801,661 @@ nodes:
         assert!(result.is_ok() || matches!(result, Err(SchedulerError::AdmissionRejected { .. })));
     }
 
+    // ── Exact utilisation ─────────────────────────────────────────────────────
+
+    fn one_cpu_scheduler() -> GlobalScheduler {
+        GlobalScheduler::new(Arc::new(fake_nodes(&[("node01", &[0], 4096)])))
+    }
+
+    /// Every ordering of `tasks` must fill node01/cpu0 completely.
+    fn assert_fills_in_any_order(sched: &GlobalScheduler, tasks: Vec<Task>) {
+        let opts = ScheduleOptions::default().with_cpu_utilization_threshold(0.90);
+        let mut rng = spread::SplitMix64::new(tasks.len() as u64);
+        let mut order = tasks;
+        for _ in 0..24 {
+            rng.shuffle(&mut order);
+            let map = sched
+                .schedule_with_options(order.clone(), &opts)
+                .unwrap_or_else(|e| panic!("{e} for {:?}", names(&order)));
+            assert_eq!(map["node01"].len(), order.len());
+
+            // Full means full: not even 1 µs more fits.
+            let mut more = order.clone();
+            more.push(make_task("extra", "wl1", "node01", 10_000, 1));
+            assert!(sched.schedule_with_options(more, &opts).is_err());
+        }
+    }
+
+    fn names(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn three_thirty_percent_tasks_fit_ninety_percent_in_any_order() {
+        let sched = one_cpu_scheduler();
+        let tasks = vec![
+            make_task("a", "wl1", "node01", 10_000, 3_000),
+            make_task("b", "wl1", "node01", 1_000, 300),
+            make_task("c", "wl1", "node01", 20_000, 6_000),
+        ];
+        assert_fills_in_any_order(&sched, tasks);
+
+        // 0.1 + 0.2 + 0.6 overshoots 0.9 in f64 for some orders.
+        let tasks = vec![
+            make_task("a", "wl1", "node01", 10_000, 1_000),
+            make_task("b", "wl1", "node01", 10_000, 2_000),
+            make_task("c", "wl1", "node01", 10_000, 6_000),
+        ];
+        assert_fills_in_any_order(&sched, tasks);
+    }
+
+    #[test]
+    fn utilization_epsilon_is_applied_to_every_fit_check() {
+        let sched = one_cpu_scheduler();
+        // 0.900000001: one part per billion over the 90 % threshold.
+        let over = || vec![make_task("t", "wl1", "node01", 1_000_000_000, 900_000_001)];
+        for algorithm in [
+            SchedAlgorithm::TargetNodePriority,
+            SchedAlgorithm::LeastLoaded,
+            SchedAlgorithm::BestFitDecreasing,
+        ] {
+            let opts = ScheduleOptions::default().with_algorithm(algorithm);
+            assert!(
+                sched.schedule_with_options(over(), &opts).is_ok(),
+                "{algorithm}: within the default epsilon"
+            );
+            let strict = opts.clone().with_utilization_epsilon(0.0);
+            assert!(
+                sched.schedule_with_options(over(), &strict).is_err(),
+                "{algorithm}: over the limit with no epsilon"
+            );
+        }
+
+        // BFD's node cap (1.0 per CPU) gets the same slack.
+        let full = ScheduleOptions::default()
+            .with_algorithm(SchedAlgorithm::BestFitDecreasing)
+            .with_cpu_utilization_threshold(1.0);
+        let one_over = vec![make_task("t", "wl1", "", 1_000_000_000, 1_000_000_001)];
+        assert!(sched.schedule_with_options(one_over.clone(), &full).is_ok());
+        let strict = full.with_utilization_epsilon(0.0);
+        assert!(sched.schedule_with_options(one_over, &strict).is_err());
+    }
+
+    #[test]
+    fn random_exact_fills_are_admitted_in_any_order() {
+        let sched = one_cpu_scheduler();
+        let mut rng = spread::SplitMix64::new(1913);
+        for round in 0..20 {
+            // Split 90 % into random whole-percent shares on mixed periods.
+            let mut left = 90;
+            let mut tasks = Vec::new();
+            while left > 0 {
+                let pct = (rng.below(left.min(30)) + 1) as u64;
+                let period = [1_000, 2_500, 3_000, 7_000, 10_000][rng.below(5)];
+                tasks.push(make_task(
+                    &format!("r{round}_{}", tasks.len()),
+                    "wl1",
+                    "node01",
+                    period,
+                    period / 100 * pct,
+                ));
+                left -= pct as usize;
+            }
+            assert_fills_in_any_order(&sched, tasks);
+        }
+    }
+
+    #[test]
+    fn capacity_report_is_identical_for_permuted_schedules() {
+        let sched = two_node_scheduler();
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        let tasks: Vec<Task> = (0..12)
+            .map(|i| {
+                make_task(
+                    &format!("t{i}"),
+                    "wl1",
+                    "",
+                    7_000 + 1_000 * i,
+                    700 + 130 * i,
+                )
+            })
+            .collect();
+        let schedule = sched.schedule_with_options(tasks, &opts).unwrap();
+        let reference = sched.capacity_report(&schedule);
+
+        let mut rng = spread::SplitMix64::new(5);
+        for _ in 0..20 {
+            let mut permuted = schedule.clone();
+            for node_tasks in permuted.values_mut() {
+                rng.shuffle(node_tasks);
+            }
+            // Bit-identical, not merely close.
+            assert_eq!(sched.capacity_report(&permuted), reference);
+        }
+    }
+
+    // ── Cluster capacity pre-check ────────────────────────────────────────────
+
+    /// `count` untargeted tasks of `runtime_us / 10 ms` each.
+    fn uniform_tasks(count: usize, runtime_us: u64) -> Vec<Task> {
+        (0..count)
+            .map(|i| make_task(&format!("t{i}"), "wl1", "", 10_000, runtime_us))
+            .collect()
+    }
+
+    #[test]
+    fn oversized_workload_is_rejected_before_placement() {
+        // 6 CPUs × 0.9 = 5.4 CPUs available; 12 × 0.5 = 6.0 required.
+        let sched = two_node_scheduler();
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        let err = sched
+            .schedule_with_options(uniform_tasks(12, 5_000), &opts)
+            .unwrap_err();
+        match err {
+            SchedulerError::ClusterCapacityExceeded {
+                required,
+                available,
+            } => {
+                assert!((required - 6.0).abs() < 1e-9, "{required}");
+                assert!((available - 5.4).abs() < 1e-9, "{available}");
+            }
+            other => panic!("expected ClusterCapacityExceeded, got {other}"),
+        }
+    }
+
+    #[test]
+    fn workload_at_aggregate_limit_proceeds_to_placement() {
+        let sched = two_node_scheduler();
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        // 12 × 0.45 = 5.4: exactly the aggregate limit, and it packs.
+        let map = sched
+            .schedule_with_options(uniform_tasks(12, 4_500), &opts)
+            .unwrap();
+        assert_eq!(map.values().map(Vec::len).sum::<usize>(), 12);
+
+        // Under the aggregate limit but all targeted at node01 (2 CPUs): the
+        // pre-check passes and the algorithm reports the real failure.
+        let tasks: Vec<Task> = (0..5)
+            .map(|i| make_task(&format!("t{i}"), "wl1", "node01", 10_000, 4_500))
+            .collect();
+        let err = sched.schedule(tasks, "target_node_priority").unwrap_err();
+        assert!(
+            matches!(err, SchedulerError::AdmissionRejected { .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn existing_load_counts_against_cluster_capacity() {
+        let sched = two_node_scheduler();
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        let existing = sched
+            .schedule_with_options(uniform_tasks(6, 4_500), &opts)
+            .unwrap();
+        // 2.7 used, 2.7 left: 7 × 0.45 = 3.15 cannot fit.
+        let err = sched
+            .schedule_with_occupancy(&existing, uniform_tasks(7, 4_500), &opts)
+            .unwrap_err();
+        assert!(
+            matches!(err, SchedulerError::ClusterCapacityExceeded { .. }),
+            "{err}"
+        );
+    }
+
+    // ── Cordoned nodes ────────────────────────────────────────────────────────
+
+    #[test]
+    fn cordoned_nodes_take_no_new_placements() {
+        let sched = GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(vec![
+            fake_node("node01", &[2, 3], 4096),
+            NodeConfig {
+                enabled: false,
+                ..fake_node("node02", &[2, 3], 4096)
+            },
+        ])));
+        let tasks = || uniform_tasks(3, 1_000);
+
+        let map = sched.schedule(tasks(), "least_loaded").unwrap();
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["node01"]);
+        let err = sched
+            .schedule(
+                vec![make_task("t", "wl1", "node02", 10_000, 1_000)],
+                "target_node_priority",
+            )
+            .unwrap_err();
+        assert_eq!(err.reason(), Some(&AdmissionReason::NodeCordoned));
+
+        // Uncordoning takes effect on the next run.
+        sched.node_config_manager().set_cordoned("node02", false);
+        let map = sched.schedule(tasks(), "least_loaded").unwrap();
+        assert!(map.contains_key("node02"));
+        sched.node_config_manager().set_cordoned("node01", true);
+        let map = sched.schedule(tasks(), "least_loaded").unwrap();
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["node02"]);
+    }
+
+    // ── Time partitions ───────────────────────────────────────────────────────
+
+    /// `part`, running our tasks in a 5 ms window of a 10 ms frame, and
+    /// `plain`, each with CPUs 2 and 3.
+    fn partitioned_scheduler() -> GlobalScheduler {
+        use crate::config::{TimePartitions, TimeWindow};
+
+        let window = TimeWindow {
+            offset_us: 2_000,
+            duration_us: 5_000,
+        };
+        GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(vec![
+            NodeConfig {
+                time_partitions: Some(TimePartitions::new(10_000, vec![window]).unwrap()),
+                ..fake_node("part", &[2, 3], 4096)
+            },
+            fake_node("plain", &[2, 3], 4096),
+        ])))
+    }
+
+    #[test]
+    fn partition_windows_scale_per_cpu_capacity() {
+        let sched = partitioned_scheduler();
+        let sixty = |node: &str| vec![make_task("t", "wl1", node, 10_000, 6_000)];
+
+        assert!(sched
+            .schedule(sixty("plain"), "target_node_priority")
+            .is_ok());
+        let err = sched
+            .schedule(sixty("part"), "target_node_priority")
+            .unwrap_err();
+        assert_eq!(err.reason(), Some(&AdmissionReason::NoAvailableCpu));
+
+        // 90 % of a 50 % share: 45 % fits, and is all a CPU there has.
+        let map = sched
+            .schedule(
+                vec![make_task("t", "wl1", "part", 10_000, 4_500)],
+                "target_node_priority",
+            )
+            .unwrap();
+        let capacity = sched.capacity_report(&map);
+        assert!((capacity.node("part").unwrap().largest_placeable - 0.45).abs() < 1e-9);
+        assert!((capacity.node("plain").unwrap().largest_placeable - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cluster_precheck_counts_partitioned_capacity() {
+        // part holds 2 × 45 % and plain 4 × 45 %; a seventh task is over
+        // the aggregate capacity, which unscaled would still be 3.6 CPUs.
+        let sched = partitioned_scheduler();
+        let tasks = |n: usize| -> Vec<Task> {
+            (0..n)
+                .map(|i| make_task(&format!("t{i}"), "wl1", "", 10_000, 4_500))
+                .collect()
+        };
+        assert!(sched.schedule(tasks(6), "least_loaded").is_ok());
+        let err = sched.schedule(tasks(7), "least_loaded").unwrap_err();
+        assert!(
+            matches!(err, SchedulerError::ClusterCapacityExceeded { .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn feasibility_warning_carries_the_partitioned_bound() {
+        #[derive(Default)]
+        struct Warnings(std::sync::Mutex<Vec<(String, f64, f64)>>);
+        impl ScheduleEventSink for Warnings {
+            fn feasibility_warning(&self, node: &str, _: f64, bound: f64, share: f64, _: usize) {
+                self.0.lock().unwrap().push((node.into(), bound, share));
+            }
+        }
+
+        let warnings = Arc::new(Warnings::default());
+        let sched = partitioned_scheduler().with_sink(warnings.clone());
+        // 40 % + 30 %: under the two-task bound of 82.8 %, not under half of it.
+        let tasks = |node: &str| {
+            vec![
+                make_task("a", "wl1", node, 10_000, 4_000),
+                make_task("b", "wl1", node, 10_000, 3_000),
+            ]
+        };
+        sched
+            .schedule(tasks("plain"), "target_node_priority")
+            .unwrap();
+        assert!(warnings.0.lock().unwrap().is_empty());
+        sched
+            .schedule(tasks("part"), "target_node_priority")
+            .unwrap();
+        let got = warnings.0.lock().unwrap().clone();
+        assert_eq!(got.len(), 1);
+        assert_eq!((got[0].0.as_str(), got[0].2), ("part", 0.5));
+        assert!((got[0].1 - liu_layland_bound(2) * 0.5).abs() < 1e-12);
+    }
+
+    // ── Workload limit ────────────────────────────────────────────────────────
+
+    #[test]
+    fn node_at_its_workload_limit_only_takes_hosted_workloads() {
+        let sched = GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(vec![
+            NodeConfig {
+                max_workloads: Some(2),
+                ..fake_node("node01", &[2, 3], 4096)
+            },
+            fake_node("node02", &[2, 3], 4096),
+        ])));
+        let on_node01 =
+            |name: &str, workload: &str| make_task(name, workload, "node01", 10_000, 500);
+
+        let existing = sched
+            .schedule(
+                vec![on_node01("a", "wl1"), on_node01("b", "wl2")],
+                "target_node_priority",
+            )
+            .unwrap();
+        let report = sched.capacity_report(&existing);
+        let node01 = report.node("node01").unwrap();
+        assert_eq!((node01.workloads, node01.max_workloads), (2, Some(2)));
+        assert_eq!(report.node("node02").unwrap().max_workloads, None);
+
+        // A third workload is refused on node01, in one run or on top of
+        // existing occupancy …
+        let third = vec![
+            on_node01("a", "wl1"),
+            on_node01("b", "wl2"),
+            on_node01("c", "wl3"),
+        ];
+        for err in [
+            sched.schedule(third, "target_node_priority").unwrap_err(),
+            sched
+                .schedule_with_occupancy(
+                    &existing,
+                    vec![on_node01("c", "wl3")],
+                    &ScheduleOptions::default(),
+                )
+                .unwrap_err(),
+        ] {
+            assert!(
+                matches!(
+                    err,
+                    SchedulerError::AdmissionRejected {
+                        reason: AdmissionReason::WorkloadCountExceeded { limit: 2 },
+                        ..
+                    }
+                ),
+                "{err}"
+            );
+        }
+
+        // … while a hosted workload still gets in, and auto-selection
+        // routes the new one to node02.
+        let more = sched
+            .schedule_with_occupancy(
+                &existing,
+                vec![on_node01("a2", "wl1")],
+                &ScheduleOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(more["node01"][0].name, "a2");
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        let routed = sched
+            .schedule_with_occupancy(
+                &existing,
+                vec![make_task("c", "wl3", "", 10_000, 500)],
+                &opts,
+            )
+            .unwrap();
+        assert_eq!(routed["node02"][0].name, "c");
+    }
+
+    // ── Exclusive CPUs ────────────────────────────────────────────────────────
+
+    #[test]
+    fn exclusive_workload_keeps_its_cpus_to_itself() {
+        let sched = GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(vec![
+            fake_node("node01", &[2, 3], 4096),
+            fake_node("solo", &[2], 4096),
+        ])));
+        let exclusive = |name: &str, node: &str| Task {
+            exclusive_cpus: true,
+            ..make_task(name, "cert", node, 10_000, 1_000)
+        };
+        let opts = ScheduleOptions::default();
+
+        // In one run, the other workload is kept off the exclusive CPU.
+        let map = sched
+            .schedule(
+                vec![
+                    exclusive("c", "node01"),
+                    make_task("x", "wl2", "node01", 10_000, 1_000),
+                ],
+                "target_node_priority",
+            )
+            .unwrap();
+        assert_ne!(map["node01"][0].assigned_cpu, map["node01"][1].assigned_cpu);
+        let report = sched.capacity_report(&map);
+        let held = &report.node("node01").unwrap().exclusive_cpus;
+        assert_eq!(held.values().collect::<Vec<_>>(), ["cert"]);
+
+        // On top of existing occupancy, too; the workload itself may share.
+        let existing = sched
+            .schedule(vec![exclusive("c", "node01")], "target_node_priority")
+            .unwrap();
+        let cert_cpu = existing["node01"][0].assigned_cpu;
+        let more = sched
+            .schedule_with_occupancy(
+                &existing,
+                vec![
+                    make_task("x", "wl2", "node01", 10_000, 1_000),
+                    make_task("y", "wl2", "node01", 10_000, 1_000),
+                    exclusive("c2", "node01"),
+                ],
+                &opts,
+            )
+            .unwrap();
+        for t in &more["node01"] {
+            assert_eq!(t.assigned_cpu == cert_cpu, t.workload_id == "cert", "{t:?}");
+        }
+
+        // With no other CPU left, either order fails with the new reason.
+        let cert_on_solo = sched
+            .schedule(vec![exclusive("c", "solo")], "target_node_priority")
+            .unwrap();
+        let wl2_on_solo = sched
+            .schedule(
+                vec![make_task("x", "wl2", "solo", 10_000, 1_000)],
+                "target_node_priority",
+            )
+            .unwrap();
+        for (existing, task, holder) in [
+            (
+                &cert_on_solo,
+                make_task("x", "wl2", "solo", 10_000, 1_000),
+                "cert",
+            ),
+            (&wl2_on_solo, exclusive("c", "solo"), "wl2"),
+        ] {
+            let err = sched
+                .schedule_with_occupancy(existing, vec![task], &opts)
+                .unwrap_err();
+            assert!(
+                matches!(
+                    &err,
+                    SchedulerError::AdmissionRejected {
+                        reason: AdmissionReason::CpuExclusivelyReserved { workload },
+                        ..
+                    } if workload == holder
+                ),
+                "{err}"
+            );
+        }
+    }
+
+    // ── Allowed nodes ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn allowed_nodes_confine_placement() {
+        let sched = two_node_scheduler();
+        let opts = ScheduleOptions::default()
+            .with_algorithm(SchedAlgorithm::LeastLoaded)
+            .with_allowed_nodes(["node02"]);
+        let map = sched
+            .schedule_with_options(uniform_tasks(6, 1_000), &opts)
+            .unwrap();
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["node02"]);
+        assert_eq!(map["node02"].len(), 6);
+
+        // node02 alone (4 × 0.9 = 3.6) cannot take 8 × 0.5, although the
+        // whole cluster could.
+        let err = sched
+            .schedule_with_options(uniform_tasks(8, 5_000), &opts)
+            .unwrap_err();
+        assert!(
+            matches!(err, SchedulerError::ClusterCapacityExceeded { .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn target_outside_allowed_nodes_is_rejected() {
+        let sched = two_node_scheduler();
+        let opts = ScheduleOptions::default().with_allowed_nodes(["node02"]);
+        let tasks = vec![
+            make_task("ok", "wl1", "node02", 10_000, 1_000),
+            make_task("out", "wl1", "node01", 10_000, 1_000),
+        ];
+        match sched.schedule_with_options(tasks, &opts).unwrap_err() {
+            SchedulerError::TargetNodeNotAllowed {
+                task,
+                node,
+                allowed,
+            } => {
+                assert_eq!(task, "out");
+                assert_eq!(node, "node01");
+                assert_eq!(allowed, ["node02"]);
+            }
+            other => panic!("expected TargetNodeNotAllowed, got {other}"),
+        }
+    }
+
+    #[test]
+    fn allowed_nodes_without_configured_node_is_rejected() {
+        let sched = two_node_scheduler();
+        let opts = ScheduleOptions::default()
+            .with_algorithm(SchedAlgorithm::LeastLoaded)
+            .with_allowed_nodes(["rear01", "rear02"]);
+        let err = sched
+            .schedule_with_options(uniform_tasks(1, 1_000), &opts)
+            .unwrap_err();
+        assert!(err.to_string().contains("rear01"), "{err}");
+        assert!(matches!(err, SchedulerError::NoAllowedNodes { .. }));
+    }
+
+    // ── Architecture scaling ──────────────────────────────────────────────────
+
+    /// `examples/node_configurations.yaml`: node01/node02 are aarch64,
+    /// node03 is x86_64.
+    fn example_scheduler() -> GlobalScheduler {
+        GlobalScheduler::new(Arc::new(example_nodes()))
+    }
+
+    fn calibrated(name: &str, target: &str, runtime_us: u64) -> Task {
+        Task {
+            wcet_scaling: [("aarch64".into(), 1.0), ("x86_64".into(), 0.7)].into(),
+            ..make_task(name, "wl1", target, 10_000, runtime_us)
+        }
+    }
+
+    #[test]
+    fn node_receives_runtime_scaled_for_its_architecture() {
+        let sched = example_scheduler();
+        let tasks = vec![
+            calibrated("arm", "node01", 1_000),
+            calibrated("x86", "node03", 1_000),
+        ];
+        let map = sched.schedule(tasks, "target_node_priority").unwrap();
+        assert_eq!(map["node01"][0].runtime_ns, Nanos(1_000_000));
+        assert_eq!(map["node03"][0].runtime_ns, Nanos(700_000));
+    }
+
+    #[test]
+    fn admission_uses_the_scaled_runtime() {
+        // 95 % on the reference board, 66.5 % on x86_64.
+        let sched = example_scheduler();
+        let err = sched
+            .schedule(
+                vec![calibrated("t", "node01", 9_500)],
+                "target_node_priority",
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, SchedulerError::AdmissionRejected { .. }),
+            "{err}"
+        );
+
+        let map = sched
+            .schedule(
+                vec![calibrated("t", "node03", 9_500)],
+                "target_node_priority",
+            )
+            .unwrap();
+        assert_eq!(
+            map["node03"][0].exact_utilization(),
+            Utilization::of(665, 1_000)
+        );
+    }
+
+    #[test]
+    fn capacity_precheck_counts_the_cheapest_architecture() {
+        // 14 × 85 % exceeds the 9 CPUs available unscaled, but at 42.5 % two
+        // tasks share each x86_64 CPU: 8 on node03 plus one per aarch64 CPU.
+        let sched = example_scheduler();
+        let tasks = (0..14)
+            .map(|i| Task {
+                wcet_scaling: [("x86_64".into(), 0.5)].into(),
+                ..make_task(&format!("t{i}"), "wl1", "", 10_000, 8_500)
+            })
+            .collect();
+        let map = sched.schedule(tasks, "least_loaded").unwrap();
+        assert_eq!(map.values().map(Vec::len).sum::<usize>(), 14);
+        assert_eq!(map["node03"].len(), 8);
+        assert!(map["node03"]
+            .iter()
+            .all(|t| t.runtime_ns == Nanos(4_250_000)));
+    }
+
+    #[test]
+    fn non_positive_wcet_scaling_is_rejected() {
+        let sched = example_scheduler();
+        let task = Task {
+            wcet_scaling: [("x86_64".into(), -0.5)].into(),
+            ..make_task("t", "wl1", "node03", 10_000, 1_000)
+        };
+        let err = sched
+            .schedule(vec![task], "target_node_priority")
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidTasks);
+        assert_eq!(err.task(), Some("t"));
+        let SchedulerError::InvalidTasks(problems) = err else {
+            unreachable!()
+        };
+        assert_eq!(problems[0].code(), ErrorCode::InvalidWcetScaling);
+    }
+
+    #[test]
+    fn task_longer_than_the_maximum_duration_is_rejected() {
+        let sched = example_scheduler();
+        let opts = ScheduleOptions::default().with_max_task_duration(Nanos(5_000_000));
+        let err = sched
+            .schedule_with_options(vec![make_task("t", "wl1", "node03", 10_000, 1_000)], &opts)
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidTask);
+        assert_eq!(err.task(), Some("t"));
+        assert!(matches!(
+            err,
+            SchedulerError::InvalidTask(crate::task::SchedTaskConversionError::TooLong {
+                field: crate::task::TimingField::Period,
+                ..
+            })
+        ));
+    }
+
     // ── General ───────────────────────────────────────────────────────────────
 
     #[test]
@@ -1042,4 +2521,231 @@ nodes:
             .unwrap_err();
         assert!(matches!(err, SchedulerError::ConfigNotLoaded));
     }
+
+    // ── randomized_spread ─────────────────────────────────────────────────────
+
+    fn loose_tasks() -> Vec<Task> {
+        (0..8)
+            .map(|i| make_task(&format!("t{i}"), "wl1", "", 10_000, 500))
+            .collect()
+    }
+
+    fn spread(sched: &GlobalScheduler, seed: u64) -> String {
+        let opts = ScheduleOptions::default()
+            .with_algorithm(SchedAlgorithm::RandomizedSpread)
+            .with_seed(seed);
+        let map = sched.schedule_with_options(loose_tasks(), &opts).unwrap();
+        assert_schedule_invariants(sched, &map, &loose_tasks(), &opts);
+        let sorted: BTreeMap<_, _> = map.into_iter().collect();
+        format!("{sorted:?}")
+    }
+
+    /// Every task placed once, on a configured CPU, under the threshold.
+    fn assert_schedule_invariants(
+        sched: &GlobalScheduler,
+        map: &NodeSchedMap,
+        tasks: &[Task],
+        opts: &ScheduleOptions,
+    ) {
+        check_schedule(map, tasks, sched.node_config_manager(), opts).unwrap();
+        assert_eq!(map.values().map(Vec::len).sum::<usize>(), tasks.len());
+    }
+
+    #[test]
+    fn randomized_spread_same_seed_is_identical() {
+        let sched = two_node_scheduler();
+        assert_eq!(spread(&sched, 42), spread(&sched, 42));
+    }
+
+    #[test]
+    fn randomized_spread_different_seeds_differ() {
+        let sched = two_node_scheduler();
+        let reference = spread(&sched, 1);
+        assert!(
+            (2..10).any(|seed| spread(&sched, seed) != reference),
+            "no seed produced a different placement"
+        );
+    }
+
+    #[test]
+    fn randomized_spread_rejects_a_task_no_cpu_can_hold() {
+        let sched = partitioned_scheduler();
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::RandomizedSpread);
+        // 95 % fits neither the 45 % partition share nor a 90 % CPU.
+        let big = |target: &str, policy| Task {
+            target_node_policy: policy,
+            ..make_task("t", "wl1", target, 10_000, 9_500)
+        };
+
+        let err = sched
+            .schedule_with_options(vec![big("", None)], &opts)
+            .unwrap_err();
+        assert!(matches!(err, SchedulerError::NoSchedulableNode { .. }));
+
+        let err = sched
+            .schedule_with_options(vec![big("part", Some(TargetNodePolicy::Hard))], &opts)
+            .unwrap_err();
+        assert_eq!(err.reason(), Some(&AdmissionReason::NoAvailableCpu));
+    }
+
+    // ── pinned-CPU reservation ───────────────────────────────────────────────
+
+    /// Three unpinned tasks followed by one pinned to node01/cpu3.  Plain
+    /// packing fills cpu3 with the first two and leaves neither CPU room for
+    /// the pinned task; the batch fits exactly if cpu3 is kept for it.
+    fn stranding_batch() -> Vec<Task> {
+        let mut tasks = vec![
+            make_task("a", "wl1", "node01", 10_000, 5_000),
+            make_task("b", "wl1", "node01", 10_000, 3_000),
+            make_task("c", "wl1", "node01", 10_000, 4_000),
+            make_task("p", "wl1", "node01", 10_000, 6_000),
+        ];
+        tasks[3].affinity = CpuAffinity::Pinned(1 << 3);
+        tasks
+    }
+
+    #[test]
+    fn naive_packing_strands_a_pinned_task() {
+        let sched = two_node_scheduler();
+        let err = sched
+            .schedule(stranding_batch(), "target_node_priority")
+            .unwrap_err();
+        assert!(
+            matches!(err, SchedulerError::AdmissionRejected { ref task, .. } if task == "p"),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn pinned_reservation_places_the_stranded_batch() {
+        let sched = two_node_scheduler();
+        let opts = ScheduleOptions::default().with_reserve_pinned_cpus(true);
+        let map = sched
+            .schedule_with_options(stranding_batch(), &opts)
+            .unwrap();
+        assert_schedule_invariants(&sched, &map, &stranding_batch(), &opts);
+
+        let cpu = |name: &str| {
+            map["node01"]
+                .iter()
+                .find(|t| t.name == name)
+                .unwrap()
+                .assigned_cpu
+        };
+        assert_eq!(cpu("p"), 3);
+        assert_eq!((cpu("a"), cpu("b"), cpu("c")), (2, 3, 2));
+    }
+
+    #[test]
+    fn pinned_reservation_is_a_no_op_without_pinned_tasks() {
+        let sched = two_node_scheduler();
+        let opts = ScheduleOptions::default()
+            .with_algorithm(SchedAlgorithm::LeastLoaded)
+            .with_reserve_pinned_cpus(true);
+        let with = sched.schedule_with_options(loose_tasks(), &opts).unwrap();
+        let without = sched
+            .schedule_with_options(loose_tasks(), &opts.clone().with_reserve_pinned_cpus(false))
+            .unwrap();
+        let sorted = |m: NodeSchedMap| format!("{:?}", m.into_iter().collect::<BTreeMap<_, _>>());
+        assert_eq!(sorted(with), sorted(without));
+    }
+
+    #[test]
+    fn release_stagger_option_phases_placed_tasks() {
+        let sched = two_node_scheduler();
+        let opts = ScheduleOptions::default()
+            .with_algorithm(SchedAlgorithm::LeastLoaded)
+            .with_release_stagger(StaggerStrategy::EvenWithinPeriod);
+        let map = sched.schedule_with_options(loose_tasks(), &opts).unwrap();
+        let offsets: BTreeSet<i32> = map.values().flatten().map(|t| t.release_time_us).collect();
+        assert!(offsets.len() > 1, "{offsets:?}");
+        assert!(offsets.iter().all(|&o| (0..10_000).contains(&o)));
+    }
+
+    // ── Simulation gate ───────────────────────────────────────────────────────
+
+    /// 90 % on one CPU — within the threshold — but the 5 ms task has the
+    /// lower priority and waits behind the 10 ms job released with it.
+    fn inverted_priority_pair() -> Vec<Task> {
+        let mut long = make_task("long", "wl1", "node01", 20_000, 10_000);
+        long.priority = 90;
+        let mut short = make_task("short", "wl1", "node01", 5_000, 2_000);
+        short.priority = 10;
+        vec![long, short]
+    }
+
+    #[test]
+    fn strict_simulation_rejects_a_set_that_passes_utilisation() {
+        let sched = one_cpu_scheduler();
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::BestFitDecreasing);
+        let map = sched
+            .schedule_with_options(inverted_priority_pair(), &opts)
+            .unwrap();
+        assert_eq!(map["node01"].len(), 2);
+
+        let err = sched
+            .schedule_with_options(
+                inverted_priority_pair(),
+                &opts.clone().with_simulation_check(SimulationCheck::Strict),
+            )
+            .unwrap_err();
+        match err {
+            SchedulerError::SimulatedDeadlineMiss {
+                node,
+                cpu,
+                task,
+                time_us,
+            } => {
+                assert_eq!((node.as_str(), cpu, task.as_str()), ("node01", 0, "short"));
+                assert_eq!(time_us, 5_000);
+            }
+            other => panic!("expected SimulatedDeadlineMiss, got {other}"),
+        }
+
+        // Warn mode leaves the verdict to the caller.
+        let warn = opts.clone().with_simulation_check(SimulationCheck::Warn);
+        assert!(sched
+            .schedule_with_options(inverted_priority_pair(), &warn)
+            .is_ok());
+    }
+
+    #[test]
+    fn simulation_over_budget_is_skipped() {
+        let sched = one_cpu_scheduler();
+        let opts = ScheduleOptions::default()
+            .with_algorithm(SchedAlgorithm::BestFitDecreasing)
+            .with_simulation_check(SimulationCheck::Strict)
+            .with_simulation_hyperperiod_limit(Micros(10_000));
+        assert!(sched
+            .schedule_with_options(inverted_priority_pair(), &opts)
+            .is_ok());
+    }
+
+    #[test]
+    fn rt_task_rejected_on_node_without_rt_privileges() {
+        let sched = two_node_scheduler();
+        sched.node_config_manager.set_rt_capable("node01", false);
+
+        let mut rt = make_task("rt", "wl1", "node01", 10_000, 1_000);
+        rt.policy = crate::task::SchedPolicy::Fifo;
+        let err = sched
+            .schedule(vec![rt.clone()], "target_node_priority")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            SchedulerError::AdmissionRejected {
+                reason: AdmissionReason::RtPrivilegesMissing,
+                ..
+            }
+        ));
+
+        // Non-RT tasks are unaffected.
+        let normal = make_task("normal", "wl1", "node01", 10_000, 1_000);
+        assert!(sched.schedule(vec![normal], "target_node_priority").is_ok());
+
+        // Auto-placing algorithms route the RT task elsewhere.
+        rt.target_node.clear();
+        let map = sched.schedule(vec![rt], "least_loaded").unwrap();
+        assert!(map.contains_key("node02") && !map.contains_key("node01"));
+    }
 }
@@ -0,0 +1,543 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Per-call scheduling options.
+//!
+//! The C++ scheduler selected its algorithm with a raw string and used a
+//! compile-time 90 % threshold.  Here both are carried in
+//! [`ScheduleOptions`] so a caller (e.g. a single `AddSchedInfo` request) can
+//! override them without touching the shared [`GlobalScheduler`].
+//!
+//! [`GlobalScheduler`]: super::GlobalScheduler
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use super::chain::TaskChain;
+use super::log_policy::LogPolicy;
+use super::margin::MarginAnalysis;
+use super::priority::PriorityOrdering;
+use super::proximity::ProximityTable;
+use super::simulate::{
+    SimulationCheck, DEFAULT_MAX_SIMULATED_JOBS, DEFAULT_SIMULATION_HYPERPERIOD_LIMIT,
+};
+use super::warm_start::PriorPlacement;
+use super::{
+    SchedulerError, StaggerStrategy, CPU_UTILIZATION_THRESHOLD, DEFAULT_UTILIZATION_EPSILON,
+};
+use crate::limits::InputLimits;
+use crate::task::{Micros, Nanos, TargetNodePolicy, DEFAULT_MAX_TASK_DURATION};
+
+// ── SchedAlgorithm ────────────────────────────────────────────────────────────
+
+/// The scheduling algorithms understood by the global scheduler.
+///
+/// The string forms (used on the wire and on the command line) are the same
+/// names the C++ implementation accepted, plus `randomized_spread` which has
+/// no C++ counterpart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SchedAlgorithm {
+    /// Honour each task's `target_node`; fail if it cannot be placed there.
+    #[default]
+    TargetNodePriority,
+    /// Put each task on the node with the lowest total utilisation.
+    LeastLoaded,
+    /// Largest WCET first, each onto the tightest-fitting node.
+    BestFitDecreasing,
+    /// Seeded pseudo-random node order per task (soak testing).
+    RandomizedSpread,
+}
+
+impl SchedAlgorithm {
+    /// Every algorithm, in declaration order.
+    pub const ALL: [SchedAlgorithm; 4] = [
+        SchedAlgorithm::TargetNodePriority,
+        SchedAlgorithm::LeastLoaded,
+        SchedAlgorithm::BestFitDecreasing,
+        SchedAlgorithm::RandomizedSpread,
+    ];
+
+    /// Canonical string form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SchedAlgorithm::TargetNodePriority => "target_node_priority",
+            SchedAlgorithm::LeastLoaded => "least_loaded",
+            SchedAlgorithm::BestFitDecreasing => "best_fit_decreasing",
+            SchedAlgorithm::RandomizedSpread => "randomized_spread",
+        }
+    }
+
+    /// Target-node policy for tasks that do not set one.
+    ///
+    /// Preserves each algorithm's historical behaviour: `target_node_priority`
+    /// requires the target, `best_fit_decreasing` treats it as a hint, and the
+    /// other algorithms ignore it (`None`).
+    pub fn default_target_policy(self) -> Option<TargetNodePolicy> {
+        match self {
+            SchedAlgorithm::TargetNodePriority => Some(TargetNodePolicy::Hard),
+            SchedAlgorithm::BestFitDecreasing => Some(TargetNodePolicy::Preferred),
+            SchedAlgorithm::LeastLoaded | SchedAlgorithm::RandomizedSpread => None,
+        }
+    }
+}
+
+impl fmt::Display for SchedAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SchedAlgorithm {
+    type Err = SchedulerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "target_node_priority" => Ok(SchedAlgorithm::TargetNodePriority),
+            "least_loaded" => Ok(SchedAlgorithm::LeastLoaded),
+            "best_fit_decreasing" => Ok(SchedAlgorithm::BestFitDecreasing),
+            "randomized_spread" => Ok(SchedAlgorithm::RandomizedSpread),
+            other => Err(SchedulerError::UnknownAlgorithm(other.to_string())),
+        }
+    }
+}
+
+// ── AdmissionOverride ─────────────────────────────────────────────────────────
+
+/// An admission check a request may switch off for an experiment.
+///
+/// Only honoured when the service allows overrides; see
+/// [`ScheduleOptions::admission_overrides`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AdmissionOverride {
+    /// Ignore the node's memory budget.
+    SkipMemory,
+    /// Ignore the per-CPU utilisation threshold and the aggregate capacity
+    /// pre-check, so CPUs may be loaded past 100 %.
+    SkipThreshold,
+}
+
+impl AdmissionOverride {
+    /// Every override, in declaration order.
+    pub const ALL: [AdmissionOverride; 2] = [
+        AdmissionOverride::SkipMemory,
+        AdmissionOverride::SkipThreshold,
+    ];
+
+    /// Canonical string form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AdmissionOverride::SkipMemory => "skip_memory",
+            AdmissionOverride::SkipThreshold => "skip_threshold",
+        }
+    }
+}
+
+impl fmt::Display for AdmissionOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AdmissionOverride {
+    type Err = SchedulerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip_memory" => Ok(AdmissionOverride::SkipMemory),
+            "skip_threshold" => Ok(AdmissionOverride::SkipThreshold),
+            other => Err(SchedulerError::UnknownAdmissionOverride(other.to_string())),
+        }
+    }
+}
+
+// ── ScheduleOptions ───────────────────────────────────────────────────────────
+
+/// Knobs for a single scheduling run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleOptions {
+    /// Which placement algorithm to run.
+    pub algorithm: SchedAlgorithm,
+
+    /// Algorithms a caller tries in turn until one places the workload
+    /// (see [`fallback`](super::fallback)).  Empty = `algorithm` alone.
+    /// The scheduler itself only ever runs `algorithm`.
+    pub algorithm_chain: Vec<SchedAlgorithm>,
+
+    /// Maximum per-CPU utilisation fraction, in `(0, 1]`.
+    pub cpu_utilization_threshold: f64,
+
+    /// RNG seed for [`SchedAlgorithm::RandomizedSpread`]; ignored otherwise.
+    pub seed: u64,
+
+    /// Tolerance for every utilisation-versus-limit comparison, in
+    /// `[0, 1e-3]` (see [`DEFAULT_UTILIZATION_EPSILON`]).
+    pub utilization_epsilon: f64,
+
+    /// Restrict placement to these nodes (intersected with the configured
+    /// ones).  `None` allows every configured node.
+    pub allowed_nodes: Option<BTreeSet<String>>,
+
+    /// Keep unpinned tasks off CPUs that pinned tasks later in the batch
+    /// will need (see [`pinned`](super::pinned)).  Off by default.
+    pub reserve_pinned_cpus: bool,
+
+    /// Spread release offsets after placement (see
+    /// [`stagger`](super::stagger)).  `None` leaves them as submitted.
+    pub release_stagger: Option<StaggerStrategy>,
+
+    /// Simulate CPUs above their Liu & Layland bound after placement (see
+    /// [`simulate::verify_schedule`](super::simulate::verify_schedule)).
+    /// `Strict` fails the run on a miss; `Warn` is left to the caller, which
+    /// turns misses into advisories.  `None` skips the simulation.
+    pub verify_with_simulation: Option<SimulationCheck>,
+
+    /// CPUs whose hyperperiod exceeds this are not simulated.
+    pub simulation_hyperperiod_limit: Micros,
+
+    /// Most jobs one CPU's simulation window may release; a CPU over it
+    /// fails the check with [`SchedulerError::TooManyJobs`] instead of
+    /// being simulated.
+    pub simulation_max_jobs: u64,
+
+    /// Longest period, runtime, deadline or release time a placed task may
+    /// have; longer ones fail the run with [`SchedulerError::InvalidTask`].
+    pub max_task_duration: Nanos,
+
+    /// How much is logged per placed task (see
+    /// [`log_policy`](super::log_policy)).
+    pub log_policy: LogPolicy,
+
+    /// Admission checks skipped for this run.  Empty by default; callers
+    /// are expected to gate and audit any they set.
+    pub admission_overrides: BTreeSet<AdmissionOverride>,
+
+    /// Location costs that break near-ties between nodes (see
+    /// [`proximity`](super::proximity)).  `None` = node order only.
+    pub proximity: Option<Arc<ProximityTable>>,
+
+    /// Analysis the per-task runtime margins are computed against (see
+    /// [`margin`](super::margin)).
+    pub margin_analysis: MarginAnalysis,
+
+    /// How real-time tasks submitted with priority 0 are ranked, and what
+    /// the post-schedule bound check assumes (see
+    /// [`priority`](super::priority)).
+    pub priority_ordering: PriorityOrdering,
+
+    /// Tasks to keep where an earlier run placed them, by name, while that
+    /// still fits (see [`warm_start`](super::warm_start)).  Empty by default.
+    pub warm_start: BTreeMap<String, PriorPlacement>,
+
+    /// Chains checked against their end-to-end deadline after placement
+    /// (see [`chain`](super::chain)).  Empty by default.
+    pub chains: Vec<TaskChain>,
+
+    /// Caps on the workload's size (see [`limits`](crate::limits)): a run
+    /// with more tasks fails with [`SchedulerError::TooManyTasks`].
+    pub limits: InputLimits,
+}
+
+impl Default for ScheduleOptions {
+    fn default() -> Self {
+        Self {
+            algorithm: SchedAlgorithm::default(),
+            algorithm_chain: Vec::new(),
+            cpu_utilization_threshold: CPU_UTILIZATION_THRESHOLD,
+            seed: 0,
+            utilization_epsilon: DEFAULT_UTILIZATION_EPSILON,
+            allowed_nodes: None,
+            reserve_pinned_cpus: false,
+            release_stagger: None,
+            verify_with_simulation: None,
+            simulation_hyperperiod_limit: DEFAULT_SIMULATION_HYPERPERIOD_LIMIT,
+            simulation_max_jobs: DEFAULT_MAX_SIMULATED_JOBS,
+            max_task_duration: DEFAULT_MAX_TASK_DURATION,
+            log_policy: LogPolicy::default(),
+            admission_overrides: BTreeSet::new(),
+            proximity: None,
+            margin_analysis: MarginAnalysis::default(),
+            priority_ordering: PriorityOrdering::default(),
+            warm_start: BTreeMap::new(),
+            chains: Vec::new(),
+            limits: InputLimits::default(),
+        }
+    }
+}
+
+impl ScheduleOptions {
+    /// Default options with a different algorithm.
+    pub fn with_algorithm(mut self, algorithm: SchedAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Default options falling back through `chain` (see
+    /// [`fallback`](super::fallback)).
+    pub fn with_algorithm_chain(mut self, chain: impl IntoIterator<Item = SchedAlgorithm>) -> Self {
+        self.algorithm_chain = chain.into_iter().collect();
+        self
+    }
+
+    /// Default options with a different per-CPU threshold.
+    ///
+    /// Not validated here — see [`validate`](Self::validate).
+    pub fn with_cpu_utilization_threshold(mut self, threshold: f64) -> Self {
+        self.cpu_utilization_threshold = threshold;
+        self
+    }
+
+    /// Default options with a different RNG seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Default options with a different comparison tolerance.
+    ///
+    /// Not validated here — see [`validate`](Self::validate).
+    pub fn with_utilization_epsilon(mut self, epsilon: f64) -> Self {
+        self.utilization_epsilon = epsilon;
+        self
+    }
+
+    /// Default options restricted to `nodes`.
+    pub fn with_allowed_nodes<I, S>(mut self, nodes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_nodes = Some(nodes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Default options with pinned-CPU reservation switched on or off.
+    pub fn with_reserve_pinned_cpus(mut self, reserve: bool) -> Self {
+        self.reserve_pinned_cpus = reserve;
+        self
+    }
+
+    /// Default options with release offsets staggered by `strategy`.
+    pub fn with_release_stagger(mut self, strategy: StaggerStrategy) -> Self {
+        self.release_stagger = Some(strategy);
+        self
+    }
+
+    /// Default options with simulation-backed verification in `mode`.
+    pub fn with_simulation_check(mut self, mode: SimulationCheck) -> Self {
+        self.verify_with_simulation = Some(mode);
+        self
+    }
+
+    /// Default options with a different simulation budget.
+    pub fn with_simulation_hyperperiod_limit(mut self, limit: Micros) -> Self {
+        self.simulation_hyperperiod_limit = limit;
+        self
+    }
+
+    /// Default options with a different simulation job limit.
+    pub fn with_simulation_max_jobs(mut self, max_jobs: u64) -> Self {
+        self.simulation_max_jobs = max_jobs;
+        self
+    }
+
+    /// Default options with a different maximum task duration.
+    pub fn with_max_task_duration(mut self, max: Nanos) -> Self {
+        self.max_task_duration = max;
+        self
+    }
+
+    /// Default options with a different per-task logging policy.
+    pub fn with_log_policy(mut self, policy: LogPolicy) -> Self {
+        self.log_policy = policy;
+        self
+    }
+
+    /// Default options with the `skip` admission check switched off.
+    pub fn with_admission_override(mut self, skip: AdmissionOverride) -> Self {
+        self.admission_overrides.insert(skip);
+        self
+    }
+
+    /// Default options with near-ties broken by `table`.
+    pub fn with_proximity(mut self, table: ProximityTable) -> Self {
+        self.proximity = Some(Arc::new(table));
+        self
+    }
+
+    /// Default options with runtime margins computed against `analysis`.
+    pub fn with_margin_analysis(mut self, analysis: MarginAnalysis) -> Self {
+        self.margin_analysis = analysis;
+        self
+    }
+
+    /// Default options with priority-0 tasks ranked by `ordering`.
+    pub fn with_priority_ordering(mut self, ordering: PriorityOrdering) -> Self {
+        self.priority_ordering = ordering;
+        self
+    }
+
+    /// Default options with each named task warm-started at its prior
+    /// placement.
+    pub fn with_warm_start<I, S>(mut self, placements: I) -> Self
+    where
+        I: IntoIterator<Item = (S, PriorPlacement)>,
+        S: Into<String>,
+    {
+        self.warm_start
+            .extend(placements.into_iter().map(|(name, p)| (name.into(), p)));
+        self
+    }
+
+    /// Default options checking `chains` end to end.
+    pub fn with_chains(mut self, chains: impl IntoIterator<Item = TaskChain>) -> Self {
+        self.chains = chains.into_iter().collect();
+        self
+    }
+
+    /// Default options with different input caps.
+    pub fn with_limits(mut self, limits: InputLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// The algorithms a chained run tries, in order: `algorithm_chain`, or
+    /// `algorithm` alone when it is empty.  Never empty.
+    pub fn links(&self) -> Vec<SchedAlgorithm> {
+        if self.algorithm_chain.is_empty() {
+            vec![self.algorithm]
+        } else {
+            self.algorithm_chain.clone()
+        }
+    }
+
+    /// Whether the `check` admission check is switched off.
+    pub fn overrides(&self, check: AdmissionOverride) -> bool {
+        self.admission_overrides.contains(&check)
+    }
+
+    /// The per-CPU limit every admission check compares against: the
+    /// threshold plus the epsilon (see [`DEFAULT_UTILIZATION_EPSILON`]), or
+    /// unbounded under [`AdmissionOverride::SkipThreshold`].
+    pub fn effective_threshold(&self) -> f64 {
+        if self.overrides(AdmissionOverride::SkipThreshold) {
+            return f64::MAX;
+        }
+        self.cpu_utilization_threshold + self.utilization_epsilon
+    }
+
+    /// Whether `node` may receive tasks under these options.
+    pub fn allows_node(&self, node: &str) -> bool {
+        self.allowed_nodes
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(node))
+    }
+
+    /// Check that the threshold lies in `(0, 1]` and the epsilon in
+    /// `[0, 1e-3]`.
+    ///
+    /// `NaN` is rejected because it compares false against both bounds.
+    pub fn validate(&self) -> Result<(), SchedulerError> {
+        let t = self.cpu_utilization_threshold;
+        if !(t > 0.0 && t <= 1.0) {
+            return Err(SchedulerError::InvalidThreshold(t));
+        }
+        let e = self.utilization_epsilon;
+        if !(0.0..=1e-3).contains(&e) {
+            return Err(SchedulerError::InvalidEpsilon(e));
+        }
+        Ok(())
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithm_round_trips_through_strings() {
+        for alg in [
+            SchedAlgorithm::TargetNodePriority,
+            SchedAlgorithm::LeastLoaded,
+            SchedAlgorithm::BestFitDecreasing,
+            SchedAlgorithm::RandomizedSpread,
+        ] {
+            assert_eq!(alg.to_string().parse::<SchedAlgorithm>().unwrap(), alg);
+        }
+    }
+
+    #[test]
+    fn unknown_algorithm_is_rejected() {
+        let err = "round_robin".parse::<SchedAlgorithm>().unwrap_err();
+        assert!(matches!(err, SchedulerError::UnknownAlgorithm(s) if s == "round_robin"));
+    }
+
+    #[test]
+    fn default_options_match_legacy_behaviour() {
+        let opts = ScheduleOptions::default();
+        assert_eq!(opts.algorithm, SchedAlgorithm::TargetNodePriority);
+        assert_eq!(opts.cpu_utilization_threshold, 0.90);
+        assert!(opts.validate().is_ok());
+        assert!(opts.allows_node("anything"));
+        assert_eq!(opts.utilization_epsilon, DEFAULT_UTILIZATION_EPSILON);
+    }
+
+    #[test]
+    fn epsilon_must_be_small_and_non_negative() {
+        let with = |e| ScheduleOptions::default().with_utilization_epsilon(e);
+        assert!(with(0.0).validate().is_ok());
+        assert!(with(1e-3).validate().is_ok());
+        for bad in [-1e-9, 0.01, f64::NAN, f64::INFINITY] {
+            assert!(
+                matches!(with(bad).validate(), Err(SchedulerError::InvalidEpsilon(_))),
+                "{bad} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn admission_overrides_round_trip_and_lift_the_threshold() {
+        for o in AdmissionOverride::ALL {
+            assert_eq!(o.to_string().parse::<AdmissionOverride>().unwrap(), o);
+        }
+        assert!(matches!(
+            "skip_everything".parse::<AdmissionOverride>(),
+            Err(SchedulerError::UnknownAdmissionOverride(s)) if s == "skip_everything"
+        ));
+
+        let opts =
+            ScheduleOptions::default().with_admission_override(AdmissionOverride::SkipThreshold);
+        assert!(opts.overrides(AdmissionOverride::SkipThreshold));
+        assert!(!opts.overrides(AdmissionOverride::SkipMemory));
+        assert!(opts.effective_threshold() > 1e9);
+        assert!(opts.validate().is_ok());
+    }
+
+    #[test]
+    fn allowed_nodes_restrict_membership() {
+        let opts = ScheduleOptions::default().with_allowed_nodes(["front01", "front02"]);
+        assert!(opts.allows_node("front02"));
+        assert!(!opts.allows_node("rear01"));
+    }
+
+    #[test]
+    fn threshold_must_be_in_half_open_unit_interval() {
+        let with = |t| ScheduleOptions::default().with_cpu_utilization_threshold(t);
+        assert!(with(1.0).validate().is_ok());
+        assert!(with(0.01).validate().is_ok());
+        for bad in [0.0, -0.5, 1.01, f64::NAN] {
+            assert!(
+                matches!(
+                    with(bad).validate(),
+                    Err(SchedulerError::InvalidThreshold(_))
+                ),
+                "{bad} should be rejected"
+            );
+        }
+    }
+}
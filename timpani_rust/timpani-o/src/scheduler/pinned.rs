@@ -0,0 +1,231 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Pinned-CPU reservation: keep unpinned tasks off CPUs that pinned tasks
+//! later in the same batch will need.
+//!
+//! Legacy configurations pin many tasks to the same CPU (typically CPU 2).
+//! Plain packing puts `Any`-affinity tasks on the highest CPU with headroom,
+//! which can fill a pinned CPU before the pinned tasks reach it; they then
+//! fall back to packing or are rejected outright.
+//!
+//! With [`ScheduleOptions::reserve_pinned_cpus`] set, a pre-pass records the
+//! utilisation every pinned task in the batch will want on its CPU as
+//! [`PinnedDemand`].  When choosing a CPU for an unpinned task,
+//! [`find_best_cpu_for_task`] first looks for the highest CPU that fits the
+//! task *and* the demand still pending there, and only falls back to plain
+//! packing if none does.  Each pinned task's demand is released once it is
+//! placed.  Whether a node has a fitting CPU at all is unchanged, so node
+//! selection is unaffected.
+//!
+//! Demand is keyed by `(target_node, cpu)`; a pinned task without a target
+//! counts against that CPU on every node.  It is measured at the reference
+//! runtime (no [`wcet_scaling`](crate::task::Task::wcet_scaling)) — this is
+//! a heuristic, not an admission check.
+//!
+//! # Request validation
+//!
+//! [`GlobalScheduler::pinned_cpu_conflicts`] catches the obvious case before
+//! any placement: a task held to its `target_node` (hard policy, explicit or
+//! the algorithm's default) whose affinity mask shares no CPU with that
+//! node's configured CPUs.  All such tasks are reported together.  Tasks
+//! that may land elsewhere are left to admission, which still checks every
+//! placement.
+//!
+//! [`ScheduleOptions::reserve_pinned_cpus`]: super::ScheduleOptions::reserve_pinned_cpus
+//! [`find_best_cpu_for_task`]: super::GlobalScheduler::find_best_cpu_for_task
+
+use std::collections::BTreeMap;
+
+use super::{GlobalScheduler, PinnedCpuConflict, ScheduleOptions, Utilization};
+use crate::task::{CpuAffinity, TargetNodePolicy, Task};
+
+/// Utilisation still wanted by pinned tasks not yet placed (see the
+/// [module docs](self)).  Empty — and so without effect — unless built by
+/// [`from_tasks`](Self::from_tasks).
+#[derive(Debug, Clone, Default)]
+pub struct PinnedDemand {
+    /// `(target_node, cpu)` → task name → utilisation.  `""` is any node.
+    pending: BTreeMap<(String, u32), BTreeMap<String, Utilization>>,
+}
+
+impl PinnedDemand {
+    /// No demand: for callers that only ask whether *some* CPU fits.
+    pub const NONE: PinnedDemand = PinnedDemand {
+        pending: BTreeMap::new(),
+    };
+
+    /// Pre-pass over the batch: the demand of every pinned task in `tasks`.
+    pub fn from_tasks(tasks: &[Task]) -> Self {
+        let mut demand = Self::default();
+        for task in tasks {
+            if let Some(key) = Self::key(task) {
+                demand
+                    .pending
+                    .entry(key)
+                    .or_default()
+                    .insert(task.name.clone(), task.exact_utilization());
+            }
+        }
+        demand
+    }
+
+    /// Demand still pending on `node_id:cpu`.
+    pub fn pending(&self, node_id: &str, cpu: u32) -> Utilization {
+        [String::new(), node_id.to_string()]
+            .into_iter()
+            .filter_map(|node| self.pending.get(&(node, cpu)))
+            .flat_map(BTreeMap::values)
+            .copied()
+            .sum()
+    }
+
+    /// Drop `task`'s demand now that it has been placed.  No-op for an
+    /// unpinned task.
+    pub fn release(&mut self, task: &Task) {
+        if let Some(key) = Self::key(task) {
+            if let Some(tasks) = self.pending.get_mut(&key) {
+                tasks.remove(&task.name);
+                if tasks.is_empty() {
+                    self.pending.remove(&key);
+                }
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    fn key(task: &Task) -> Option<(String, u32)> {
+        match task.affinity {
+            CpuAffinity::Pinned(mask) => Some((task.target_node.clone(), mask.trailing_zeros())),
+            CpuAffinity::Any => None,
+        }
+    }
+}
+
+// ── Request validation ────────────────────────────────────────────────────────
+
+impl GlobalScheduler {
+    /// Tasks of `tasks` that cannot run on the node they are bound to (see
+    /// the [module docs](self)), in task order.  Unknown target nodes are
+    /// left to admission.
+    pub fn pinned_cpu_conflicts(
+        &self,
+        tasks: &[Task],
+        opts: &ScheduleOptions,
+    ) -> Vec<PinnedCpuConflict> {
+        let default_policy = opts.algorithm.default_target_policy();
+        tasks
+            .iter()
+            .filter(|t| {
+                !t.target_node.is_empty()
+                    && t.target_node_policy.or(default_policy) == Some(TargetNodePolicy::Hard)
+            })
+            .filter_map(|t| {
+                let CpuAffinity::Pinned(mask) = t.affinity else {
+                    return None;
+                };
+                let node = self.node_config_manager.get_node_config(&t.target_node)?;
+                if node
+                    .available_cpus
+                    .iter()
+                    .any(|&cpu| t.affinity.allows_cpu(cpu))
+                {
+                    return None;
+                }
+                Some(PinnedCpuConflict {
+                    task: t.name.clone(),
+                    node: t.target_node.clone(),
+                    requested: (0..u64::BITS).filter(|cpu| mask >> cpu & 1 == 1).collect(),
+                    node_cpus: node.available_cpus.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Micros;
+
+    fn task(name: &str, node: &str, affinity: CpuAffinity, runtime_us: u64) -> Task {
+        Task {
+            name: name.into(),
+            target_node: node.into(),
+            affinity,
+            period_us: Micros(10_000),
+            runtime_us: Micros(runtime_us),
+            deadline_us: Micros(10_000),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn demand_is_counted_per_cpu_and_released_on_placement() {
+        let tasks = vec![
+            task("a", "", CpuAffinity::Pinned(1 << 2), 2_000),
+            task("b", "n1", CpuAffinity::Pinned(1 << 2), 1_000),
+            task("c", "n2", CpuAffinity::Pinned(1 << 3), 1_000),
+            task("free", "", CpuAffinity::Any, 5_000),
+        ];
+        let mut demand = PinnedDemand::from_tasks(&tasks);
+        assert_eq!(demand.pending("n1", 2), Utilization::of(3, 10));
+        assert_eq!(demand.pending("n2", 2), Utilization::of(2, 10));
+        assert_eq!(demand.pending("n1", 3), Utilization::ZERO);
+
+        demand.release(&tasks[3]);
+        demand.release(&tasks[0]);
+        assert_eq!(demand.pending("n1", 2), Utilization::of(1, 10));
+        demand.release(&tasks[1]);
+        demand.release(&tasks[2]);
+        assert!(demand.is_empty());
+    }
+
+    #[test]
+    fn conflicts_list_only_tasks_bound_to_a_node_without_their_cpus() {
+        use std::sync::Arc;
+
+        use crate::config::{NodeConfig, NodeConfigManager};
+        use crate::scheduler::SchedAlgorithm;
+
+        let scheduler =
+            GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(vec![NodeConfig {
+                available_cpus: vec![0, 1],
+                ..NodeConfig::default_config("n1")
+            }])));
+        let hard = |mut t: Task| {
+            t.target_node_policy = Some(TargetNodePolicy::Hard);
+            t
+        };
+        let tasks = vec![
+            hard(task("bad", "n1", CpuAffinity::Pinned(0b1100), 1_000)),
+            hard(task("overlap", "n1", CpuAffinity::Pinned(0b0110), 1_000)),
+            hard(task(
+                "unknown_node",
+                "n9",
+                CpuAffinity::Pinned(0b1000),
+                1_000,
+            )),
+            task("preferred", "n1", CpuAffinity::Pinned(0b1000), 1_000),
+            hard(task("any", "n1", CpuAffinity::Any, 1_000)),
+        ];
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        let conflicts = scheduler.pinned_cpu_conflicts(&tasks, &opts);
+        assert_eq!(
+            conflicts,
+            [PinnedCpuConflict {
+                task: "bad".into(),
+                node: "n1".into(),
+                requested: vec![2, 3],
+                node_cpus: vec![0, 1],
+            }]
+        );
+    }
+}
@@ -0,0 +1,277 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Automatic priority assignment.
+//!
+//! A FIFO/RR task submitted with priority 0 — not a valid real-time
+//! priority — asks Timpani-O to choose one.  [`assign_priorities`] ranks
+//! those tasks of a workload by the workload's [`PriorityOrdering`] and
+//! gives them fixed priorities from the band
+//! [`AUTO_PRIORITY_HIGH`]`..=`[`AUTO_PRIORITY_LOW`]:
+//!
+//! | Ordering            | Rank key (shortest first)       | Optimal for              |
+//! |---------------------|---------------------------------|--------------------------|
+//! | `RateMonotonic`     | period, then effective deadline | implicit deadlines (D=T) |
+//! | `DeadlineMonotonic` | effective deadline, then period | constrained deadlines    |
+//!
+//! The effective deadline is `deadline_us`, or the period when that is
+//! unset.  The first rank gets [`AUTO_PRIORITY_HIGH`], each further rank one
+//! less, down to [`AUTO_PRIORITY_LOW`], which all remaining ranks share.
+//! Tasks with equal keys share a rank, so the result never depends on input
+//! order.  Aperiodic tasks (period 0) have no rank and get
+//! [`AUTO_PRIORITY_LOW`].  Tasks with a priority of their own, and
+//! `SCHED_NORMAL` tasks, are left alone.
+//!
+//! The ordering also decides what the post-schedule bound check assumes
+//! (see [`feasibility::check_schedule`](super::feasibility::check_schedule)).
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use super::error::SchedulerError;
+use crate::task::{Micros, Task};
+
+/// Highest priority the pass assigns.
+pub const AUTO_PRIORITY_HIGH: i32 = 90;
+
+/// Lowest priority the pass assigns.
+pub const AUTO_PRIORITY_LOW: i32 = 10;
+
+/// How [`assign_priorities`] ranks tasks (see the [module docs](self)).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriorityOrdering {
+    /// Shorter period → higher priority.
+    #[default]
+    RateMonotonic,
+    /// Shorter relative deadline → higher priority.
+    DeadlineMonotonic,
+}
+
+impl PriorityOrdering {
+    /// Canonical string form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PriorityOrdering::RateMonotonic => "rate_monotonic",
+            PriorityOrdering::DeadlineMonotonic => "deadline_monotonic",
+        }
+    }
+
+    /// Rank key of `task`; smaller ranks higher.
+    fn key(self, task: &Task) -> (Micros, Micros) {
+        let deadline = effective_deadline(task);
+        match self {
+            PriorityOrdering::RateMonotonic => (task.period_us, deadline),
+            PriorityOrdering::DeadlineMonotonic => (deadline, task.period_us),
+        }
+    }
+}
+
+impl fmt::Display for PriorityOrdering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PriorityOrdering {
+    type Err = SchedulerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rate_monotonic" => Ok(PriorityOrdering::RateMonotonic),
+            "deadline_monotonic" => Ok(PriorityOrdering::DeadlineMonotonic),
+            other => Err(SchedulerError::UnknownPriorityOrdering(other.to_string())),
+        }
+    }
+}
+
+/// `deadline_us`, or the period if unset.
+fn effective_deadline(task: &Task) -> Micros {
+    if task.deadline_us.is_zero() {
+        task.period_us
+    } else {
+        task.deadline_us
+    }
+}
+
+/// Whether `task` asks for an assigned priority.
+fn wants_priority(task: &Task) -> bool {
+    task.priority == 0 && task.policy.is_realtime()
+}
+
+/// Give every real-time task with priority 0 in `tasks` a priority ranked by
+/// `ordering` (see the [module docs](self)).  Returns how many were
+/// assigned.
+pub fn assign_priorities(tasks: &mut [Task], ordering: PriorityOrdering) -> usize {
+    let ranks: Vec<(Micros, Micros)> = tasks
+        .iter()
+        .filter(|t| wants_priority(t) && !t.period_us.is_zero())
+        .map(|t| ordering.key(t))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let mut assigned = 0;
+    for task in tasks.iter_mut().filter(|t| wants_priority(t)) {
+        task.priority = if task.period_us.is_zero() {
+            AUTO_PRIORITY_LOW
+        } else {
+            let rank = ranks.binary_search(&ordering.key(task)).unwrap_or(0);
+            let rank = i32::try_from(rank).unwrap_or(i32::MAX);
+            AUTO_PRIORITY_HIGH
+                .saturating_sub(rank)
+                .max(AUTO_PRIORITY_LOW)
+        };
+        assigned += 1;
+    }
+    assigned
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::feasibility::check_schedule;
+    use crate::scheduler::rta::analyse_schedule;
+    use crate::scheduler::DEFAULT_UTILIZATION_EPSILON;
+    use crate::task::{NodeSchedMap, SchedPolicy, SchedTask, DEFAULT_MAX_TASK_DURATION};
+
+    fn rt(name: &str, period_us: u64, runtime_us: u64, deadline_us: u64) -> Task {
+        Task {
+            name: name.into(),
+            policy: SchedPolicy::Fifo,
+            period_us: Micros(period_us),
+            runtime_us: Micros(runtime_us),
+            deadline_us: Micros(deadline_us),
+            assigned_node: "n1".into(),
+            assigned_cpu: Some(0),
+            ..Default::default()
+        }
+    }
+
+    fn priorities(tasks: &[Task]) -> Vec<(&str, i32)> {
+        tasks
+            .iter()
+            .map(|t| (t.name.as_str(), t.priority))
+            .collect()
+    }
+
+    fn on_one_cpu(tasks: &[Task]) -> NodeSchedMap {
+        let placed = tasks
+            .iter()
+            .map(|t| SchedTask::from_task(t, DEFAULT_MAX_TASK_DURATION).unwrap())
+            .collect();
+        [("n1".to_string(), placed)].into()
+    }
+
+    /// `ctl` has the longer period but must finish within 4 ms of release.
+    fn constrained() -> Vec<Task> {
+        vec![rt("ctl", 20_000, 2_000, 4_000), rt("io", 10_000, 4_000, 0)]
+    }
+
+    #[test]
+    fn rate_monotonic_ranks_by_period() {
+        let mut tasks = constrained();
+        assert_eq!(
+            assign_priorities(&mut tasks, PriorityOrdering::RateMonotonic),
+            2
+        );
+        assert_eq!(priorities(&tasks), [("ctl", 89), ("io", 90)]);
+    }
+
+    #[test]
+    fn deadline_monotonic_ranks_by_deadline() {
+        let mut tasks = constrained();
+        assign_priorities(&mut tasks, PriorityOrdering::DeadlineMonotonic);
+        assert_eq!(priorities(&tasks), [("ctl", 90), ("io", 89)]);
+    }
+
+    #[test]
+    fn constrained_deadlines_pass_rta_only_under_deadline_monotonic() {
+        let mut rm = constrained();
+        assign_priorities(&mut rm, PriorityOrdering::RateMonotonic);
+        let mut dm = constrained();
+        assign_priorities(&mut dm, PriorityOrdering::DeadlineMonotonic);
+        assert_ne!(priorities(&rm), priorities(&dm));
+
+        // RM: io (4 ms) runs first, so ctl responds at 6 ms > 4 ms.
+        let rm_report = analyse_schedule(&on_one_cpu(&rm));
+        let ctl = rm_report.results.iter().find(|r| r.task == "ctl").unwrap();
+        assert!(!ctl.schedulable());
+
+        // DM: ctl responds at 2 ms, io at 6 ms ≤ 10 ms.
+        let dm_report = analyse_schedule(&on_one_cpu(&dm));
+        assert!(dm_report.results.iter().all(|r| r.schedulable()));
+    }
+
+    #[test]
+    fn bound_check_follows_the_ordering() {
+        let map = on_one_cpu(&constrained());
+        let eps = DEFAULT_UTILIZATION_EPSILON;
+
+        // U = 0.5 is under the two-task bound; density 0.5 + 0.4 is not.
+        assert!(check_schedule(&map, eps, PriorityOrdering::RateMonotonic).is_empty());
+        let dm = check_schedule(&map, eps, PriorityOrdering::DeadlineMonotonic);
+        assert_eq!(dm.len(), 1);
+        assert_eq!(dm[0].analysis, "liu_layland_density");
+        assert!((dm[0].utilization - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn equal_keys_share_a_priority_and_the_band_floors() {
+        let mut tasks: Vec<Task> = (0..90)
+            .map(|i| rt(&format!("t{i:02}"), 1_000 * (i + 1), 10, 0))
+            .collect();
+        tasks.push(rt("twin", 1_000, 10, 0));
+        assign_priorities(&mut tasks, PriorityOrdering::RateMonotonic);
+
+        let prio = |name: &str| tasks.iter().find(|t| t.name == name).unwrap().priority;
+        assert_eq!(prio("t00"), AUTO_PRIORITY_HIGH);
+        assert_eq!(prio("twin"), AUTO_PRIORITY_HIGH);
+        assert_eq!(prio("t01"), AUTO_PRIORITY_HIGH - 1);
+        assert_eq!(prio("t80"), AUTO_PRIORITY_LOW);
+        assert_eq!(prio("t89"), AUTO_PRIORITY_LOW);
+    }
+
+    #[test]
+    fn explicit_priorities_normal_and_aperiodic_tasks() {
+        let mut tasks = vec![
+            Task {
+                priority: 42,
+                ..rt("fixed", 1_000, 10, 0)
+            },
+            Task {
+                policy: SchedPolicy::Normal,
+                ..rt("normal", 1_000, 10, 0)
+            },
+            rt("sporadic", 0, 10, 0),
+        ];
+        assert_eq!(
+            assign_priorities(&mut tasks, PriorityOrdering::DeadlineMonotonic),
+            1
+        );
+        assert_eq!(
+            priorities(&tasks),
+            [
+                ("fixed", 42),
+                ("normal", 0),
+                ("sporadic", AUTO_PRIORITY_LOW)
+            ]
+        );
+    }
+
+    #[test]
+    fn ordering_round_trips_through_str() {
+        for o in [
+            PriorityOrdering::RateMonotonic,
+            PriorityOrdering::DeadlineMonotonic,
+        ] {
+            assert_eq!(o.as_str().parse::<PriorityOrdering>().unwrap(), o);
+        }
+        let err = "edf".parse::<PriorityOrdering>().unwrap_err();
+        assert!(matches!(err, SchedulerError::UnknownPriorityOrdering(s) if s == "edf"));
+    }
+}
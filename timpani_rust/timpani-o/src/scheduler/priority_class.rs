@@ -0,0 +1,98 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Workload priority classes.
+//!
+//! Every workload belongs to one class (`SchedInfo.priority_class`):
+//!
+//! | Class         | Rank    | Typical workload                       |
+//! |---------------|---------|----------------------------------------|
+//! | `safety`      | highest | ASIL-rated control loops               |
+//! | `platform`    |         | vehicle services; the default if unset |
+//! | `best_effort` | lowest  | infotainment, diagnostics              |
+//!
+//! The class is the first ordering key wherever workloads compete: the
+//! pending queue retries higher classes first and a node drain moves lower
+//! classes first.  `importance` orders workloads within a class.
+//!
+//! Admission never evicts a running workload to make room; a workload that
+//! does not fit waits in the pending queue whatever its class.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::SchedulerError;
+
+/// A workload's priority class (see the module docs).  Ordered by rank:
+/// `BestEffort < Platform < Safety`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PriorityClass {
+    BestEffort,
+    #[default]
+    Platform,
+    Safety,
+}
+
+impl PriorityClass {
+    /// Every class, highest first.
+    pub const ALL: [PriorityClass; 3] = [
+        PriorityClass::Safety,
+        PriorityClass::Platform,
+        PriorityClass::BestEffort,
+    ];
+
+    /// Canonical string form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PriorityClass::Safety => "safety",
+            PriorityClass::Platform => "platform",
+            PriorityClass::BestEffort => "best_effort",
+        }
+    }
+
+    /// Parse an optional wire value; unset means [`Platform`](Self::Platform).
+    pub fn from_proto(value: Option<&str>) -> Result<Self, SchedulerError> {
+        value.map_or(Ok(PriorityClass::default()), str::parse)
+    }
+}
+
+impl fmt::Display for PriorityClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PriorityClass {
+    type Err = SchedulerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "safety" => Ok(PriorityClass::Safety),
+            "platform" => Ok(PriorityClass::Platform),
+            "best_effort" => Ok(PriorityClass::BestEffort),
+            other => Err(SchedulerError::UnknownPriorityClass(other.to_string())),
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_classes_and_rejects_the_rest() {
+        for class in PriorityClass::ALL {
+            assert_eq!(class.as_str().parse::<PriorityClass>().unwrap(), class);
+        }
+        assert_eq!(
+            PriorityClass::from_proto(None).unwrap(),
+            PriorityClass::Platform
+        );
+        let err = PriorityClass::from_proto(Some("best-effort")).unwrap_err();
+        assert!(matches!(err, SchedulerError::UnknownPriorityClass(s) if s == "best-effort"));
+    }
+}
@@ -0,0 +1,327 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Location proximity: break near-ties between nodes in favour of the one
+//! closest to a task's sensors.
+//!
+//! A task may name a `preferred_location` (its zone).  A [`ProximityTable`]
+//! in [`ScheduleOptions::proximity`] gives the cost of running a task from
+//! one zone on a node at a given `NodeConfig::location`:
+//!
+//! ```yaml
+//! tolerance: 0.05            # optional, default DEFAULT_PROXIMITY_TOLERANCE
+//! costs:
+//!   front_sensor_unit:       # task zone
+//!     front_sensor_unit: 0   # node location -> cost
+//!     vehicle_control_unit: 2
+//! ```
+//!
+//! `least_loaded` and `best_fit_decreasing` rank the nodes that admit the
+//! task as before.  Every candidate whose score is within `tolerance` of
+//! the winner's is a near-tie; if one of them costs strictly less than the
+//! winner, the cheapest (best score, then name order, among equal costs)
+//! is chosen instead and reported through
+//! [`ScheduleEventSink::location_preferred`](super::ScheduleEventSink::location_preferred).
+//! A location with no entry for the zone costs nothing if it *is* the zone
+//! and is never preferred otherwise, so an empty table or unmatched zones
+//! leave the deterministic order untouched.  A clearly better-scored node
+//! always wins.
+//!
+//! [`ScheduleOptions::proximity`]: super::ScheduleOptions::proximity
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{GlobalScheduler, ScheduleOptions, Utilization};
+use crate::task::Task;
+
+/// Default score difference (fraction of one CPU) within which candidates
+/// count as tied.
+pub const DEFAULT_PROXIMITY_TOLERANCE: f64 = 0.05;
+
+fn default_tolerance() -> f64 {
+    DEFAULT_PROXIMITY_TOLERANCE
+}
+
+/// Zone → node location → cost (see the module docs).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProximityTable {
+    #[serde(default = "default_tolerance")]
+    tolerance: f64,
+    #[serde(default)]
+    costs: BTreeMap<String, BTreeMap<String, u32>>,
+}
+
+impl Default for ProximityTable {
+    fn default() -> Self {
+        Self {
+            tolerance: DEFAULT_PROXIMITY_TOLERANCE,
+            costs: BTreeMap::new(),
+        }
+    }
+}
+
+impl ProximityTable {
+    /// An empty table with the default tolerance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the YAML layout shown in the module docs.
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+
+    /// The table with running a `zone` task at `location` costing `cost`.
+    pub fn with_cost(mut self, zone: &str, location: &str, cost: u32) -> Self {
+        self.costs
+            .entry(zone.to_string())
+            .or_default()
+            .insert(location.to_string(), cost);
+        self
+    }
+
+    /// The table with a different near-tie tolerance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    /// Cost of running a `zone` task at `location`; `None` if unknown.
+    pub fn cost(&self, zone: &str, location: &str) -> Option<u32> {
+        self.costs
+            .get(zone)
+            .and_then(|c| c.get(location))
+            .copied()
+            .or((zone == location).then_some(0))
+    }
+}
+
+/// Which end of a candidate score wins.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Prefer {
+    Lowest,
+    Highest,
+}
+
+impl Prefer {
+    /// `a` compared with `b`, better first.
+    fn cmp(self, a: Utilization, b: Utilization) -> Ordering {
+        match self {
+            Prefer::Lowest => a.cmp(&b),
+            Prefer::Highest => b.cmp(&a),
+        }
+    }
+}
+
+impl GlobalScheduler {
+    /// The node for `task` among `candidates` (node, score), which are in
+    /// name order: the best score, first in name order on a tie, unless
+    /// proximity picks a near-tie (see the module docs).
+    pub(super) fn pick_candidate(
+        &self,
+        task: &Task,
+        candidates: Vec<(String, Utilization)>,
+        prefer: Prefer,
+        opts: &ScheduleOptions,
+    ) -> Option<String> {
+        let winner = candidates
+            .iter()
+            .reduce(|best, c| {
+                if prefer.cmp(c.1, best.1).is_lt() {
+                    c
+                } else {
+                    best
+                }
+            })?
+            .clone();
+        let Some(table) = opts
+            .proximity
+            .as_deref()
+            .filter(|_| !task.preferred_location.is_empty())
+        else {
+            return Some(winner.0);
+        };
+
+        let zone = task.preferred_location.as_str();
+        let cost = |node: &str| {
+            let location = self
+                .node_config_manager
+                .get_node_config(node)
+                .map_or("", |c| c.location.as_str());
+            table.cost(zone, location).unwrap_or(u32::MAX)
+        };
+        let slack = Utilization::from_f64(table.tolerance());
+        let near_tie = |score: Utilization| match prefer {
+            Prefer::Lowest => score <= winner.1 + slack,
+            Prefer::Highest => score + slack >= winner.1,
+        };
+        let winner_cost = cost(&winner.0);
+        let closer = candidates
+            .iter()
+            .filter(|(node, score)| near_tie(*score) && cost(node) < winner_cost)
+            .min_by(|a, b| cost(&a.0).cmp(&cost(&b.0)).then(prefer.cmp(a.1, b.1)));
+        match closer {
+            Some((node, _)) => {
+                self.sink
+                    .location_preferred(task, node, &winner.0, cost(node));
+                Some(node.clone())
+            }
+            None => Some(winner.0),
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::config::{NodeConfig, NodeConfigManager};
+    use crate::scheduler::{SchedAlgorithm, ScheduleEventSink};
+    use crate::task::{Micros, Nanos, NodeSchedMap, SchedTask};
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl ScheduleEventSink for Recorder {
+        fn location_preferred(&self, task: &Task, node: &str, over: &str, cost: u32) {
+            let event = format!("{} {node} over {over} at cost {cost}", task.name);
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    /// `front` sits in the front zone, `rear` at the back; both have two
+    /// CPUs.
+    fn scheduler(sink: Arc<Recorder>) -> GlobalScheduler {
+        let node = |name: &str, location: &str| NodeConfig {
+            available_cpus: vec![0, 1],
+            location: location.into(),
+            ..NodeConfig::default_config(name)
+        };
+        GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(vec![
+            node("front", "front_sensor_unit"),
+            node("rear", "rear_unit"),
+        ])))
+        .with_sink(sink)
+    }
+
+    fn opts(algorithm: SchedAlgorithm) -> ScheduleOptions {
+        let table = ProximityTable::new()
+            .with_cost("front_sensor_unit", "front_sensor_unit", 0)
+            .with_cost("front_sensor_unit", "rear_unit", 3);
+        ScheduleOptions::default()
+            .with_algorithm(algorithm)
+            .with_proximity(table)
+    }
+
+    /// One 100 ms-period task on `node` CPU 0 using `util`.
+    fn load(node: &str, util: f64) -> SchedTask {
+        SchedTask {
+            name: format!("{node}_load"),
+            assigned_node: node.into(),
+            assigned_cpu: 0,
+            policy: Default::default(),
+            priority: 0,
+            period_ns: Nanos(100_000_000),
+            runtime_ns: Nanos((util * 100_000_000.0).round() as u64),
+            deadline_ns: Nanos(100_000_000),
+            release_time_us: 0,
+            max_dmiss: 0,
+            shared_resources: Vec::new(),
+            workload_id: "other".into(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    fn fusion() -> Task {
+        Task {
+            name: "fusion".into(),
+            workload_id: "wl".into(),
+            preferred_location: "front_sensor_unit".into(),
+            period_us: Micros(100_000),
+            runtime_us: Micros(10_000),
+            deadline_us: Micros(100_000),
+            ..Default::default()
+        }
+    }
+
+    fn place(sched: &GlobalScheduler, front: f64, rear: f64, opts: &ScheduleOptions) -> String {
+        let occupied: NodeSchedMap = [
+            ("front".to_string(), vec![load("front", front)]),
+            ("rear".to_string(), vec![load("rear", rear)]),
+        ]
+        .into();
+        let placed = sched
+            .schedule_with_occupancy(&occupied, vec![fusion()], opts)
+            .unwrap();
+        placed.into_keys().next().unwrap()
+    }
+
+    #[test]
+    fn least_loaded_prefers_the_nearby_node_only_on_a_near_tie() {
+        let sink = Arc::new(Recorder::default());
+        let sched = scheduler(Arc::clone(&sink));
+        let opts = opts(SchedAlgorithm::LeastLoaded);
+
+        // rear is 3 % emptier: a near-tie, the front node wins.
+        assert_eq!(place(&sched, 0.33, 0.30, &opts), "front");
+        assert_eq!(
+            sink.0.lock().unwrap().as_slice(),
+            ["fusion front over rear at cost 0"]
+        );
+        // rear is 30 % emptier: load wins.
+        assert_eq!(place(&sched, 0.60, 0.30, &opts), "rear");
+        // Without the table, or for a task without a zone, load wins.
+        let plain = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        assert_eq!(place(&sched, 0.33, 0.30, &plain), "rear");
+        assert_eq!(sink.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn best_fit_decreasing_prefers_the_nearby_node_only_on_a_near_tie() {
+        let sink = Arc::new(Recorder::default());
+        let sched = scheduler(Arc::clone(&sink));
+        let opts = opts(SchedAlgorithm::BestFitDecreasing);
+
+        // rear is 2 % fuller (the tighter fit): a near-tie, front wins.
+        assert_eq!(place(&sched, 0.50, 0.52, &opts), "front");
+        // rear is 40 % fuller: fit wins.
+        assert_eq!(place(&sched, 0.10, 0.50, &opts), "rear");
+        assert_eq!(sink.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn parses_yaml_and_treats_the_own_zone_as_free() {
+        let table = ProximityTable::from_yaml(
+            "costs:\n  front_sensor_unit:\n    vehicle_control_unit: 2\n",
+        )
+        .unwrap();
+        assert_eq!(table.tolerance(), DEFAULT_PROXIMITY_TOLERANCE);
+        assert_eq!(
+            table.cost("front_sensor_unit", "vehicle_control_unit"),
+            Some(2)
+        );
+        assert_eq!(
+            table.cost("front_sensor_unit", "front_sensor_unit"),
+            Some(0)
+        );
+        assert_eq!(table.cost("front_sensor_unit", "rear_unit"), None);
+        assert_eq!(table.cost("cabin", "vehicle_control_unit"), None);
+    }
+}
@@ -0,0 +1,269 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Placement quality: how close a schedule comes to the fewest CPUs.
+//!
+//! The algorithms are heuristics; [`evaluate`] measures what one produced:
+//!
+//! | Metric                 | Meaning                                                   |
+//! |------------------------|-----------------------------------------------------------|
+//! | `cpus_used`            | CPUs with at least one task (the "bins")                  |
+//! | `lower_bound`          | ⌈total utilisation⌉: no placement can use fewer CPUs      |
+//! | `utilization_variance` | population variance of the utilisation of the used CPUs   |
+//! | `near_threshold`       | used CPUs within `proximity` of the per-CPU threshold     |
+//!
+//! The lower bound is exact ([`Utilization`]) but can be loose: it ignores
+//! the threshold and that tasks do not split.  For tiny inputs —
+//! [`MAX_OPTIMAL_TASKS`] tasks on [`MAX_OPTIMAL_CPUS`] CPUs —
+//! `optimal_cpus` finds the true optimum by exhaustive search.  It is built
+//! for tests and with the `bench` feature only.
+//!
+//! Shadow comparisons record the metrics of both schedules (see
+//! [`crate::report::shadow`]).
+
+use std::collections::BTreeMap;
+
+use crate::task::NodeSchedMap;
+
+use super::{ScheduleOptions, Utilization, CPU_UTILIZATION_THRESHOLD};
+
+/// How close below the threshold a CPU counts as near it, unless
+/// configured otherwise.
+pub const DEFAULT_PROXIMITY: f64 = 0.05;
+
+/// Most tasks `optimal_cpus` accepts.
+pub const MAX_OPTIMAL_TASKS: usize = 12;
+
+/// Most CPUs `optimal_cpus` searches.
+pub const MAX_OPTIMAL_CPUS: usize = 8;
+
+// ── Metrics ───────────────────────────────────────────────────────────────────
+
+/// What [`evaluate`] measures against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QualityConfig {
+    /// Per-CPU utilisation threshold of the run.
+    pub cpu_utilization_threshold: f64,
+    /// A CPU at or above `threshold - proximity` is near the threshold.
+    pub proximity: f64,
+}
+
+impl Default for QualityConfig {
+    fn default() -> Self {
+        Self {
+            cpu_utilization_threshold: CPU_UTILIZATION_THRESHOLD,
+            proximity: DEFAULT_PROXIMITY,
+        }
+    }
+}
+
+impl QualityConfig {
+    /// The threshold `opts` scheduled with.
+    pub fn from_options(opts: &ScheduleOptions) -> Self {
+        Self {
+            cpu_utilization_threshold: opts.cpu_utilization_threshold,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_proximity(mut self, proximity: f64) -> Self {
+        self.proximity = proximity;
+        self
+    }
+}
+
+/// Placement quality of one schedule (see the module docs).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct QualityMetrics {
+    pub cpus_used: usize,
+    pub lower_bound: usize,
+    pub utilization_variance: f64,
+    pub near_threshold: usize,
+}
+
+impl QualityMetrics {
+    /// CPUs used beyond the lower bound.
+    pub fn excess_cpus(&self) -> usize {
+        self.cpus_used.saturating_sub(self.lower_bound)
+    }
+}
+
+/// Measure `result` against `cfg`.
+pub fn evaluate(result: &NodeSchedMap, cfg: &QualityConfig) -> QualityMetrics {
+    let mut per_cpu: BTreeMap<(&str, u32), Utilization> = BTreeMap::new();
+    for (node, tasks) in result {
+        for t in tasks {
+            *per_cpu.entry((node, t.assigned_cpu)).or_default() += t.exact_utilization();
+        }
+    }
+    let total: Utilization = per_cpu.values().copied().sum();
+    let loads: Vec<f64> = per_cpu.values().map(|u| u.as_f64()).collect();
+    let near = cfg.cpu_utilization_threshold - cfg.proximity;
+    QualityMetrics {
+        cpus_used: per_cpu.len(),
+        lower_bound: total.ceil_cpus(),
+        utilization_variance: variance(&loads),
+        near_threshold: loads.iter().filter(|&&u| u >= near).count(),
+    }
+}
+
+/// Population variance; zero for no values.
+fn variance(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n
+}
+
+// ── Exhaustive optimum ────────────────────────────────────────────────────────
+
+/// Fewest CPUs of capacity `threshold` that hold every task of `tasks`
+/// (utilisations), or `None` if more than `max_cpus` would be needed.
+///
+/// Tries every placement, up to CPU relabelling, so it is only usable on
+/// tiny inputs.  Node boundaries, memory and affinities are not modelled:
+/// the CPUs are one pool.
+///
+/// # Panics
+/// With more than [`MAX_OPTIMAL_TASKS`] tasks or [`MAX_OPTIMAL_CPUS`] CPUs.
+#[cfg(any(test, feature = "bench"))]
+pub fn optimal_cpus(tasks: &[Utilization], max_cpus: usize, threshold: f64) -> Option<usize> {
+    assert!(
+        tasks.len() <= MAX_OPTIMAL_TASKS && max_cpus <= MAX_OPTIMAL_CPUS,
+        "exhaustive search is limited to {MAX_OPTIMAL_TASKS} tasks on {MAX_OPTIMAL_CPUS} CPUs"
+    );
+    let mut sorted = tasks.to_vec();
+    sorted.sort_by(|a, b| b.cmp(a));
+    let capacity = Utilization::from_f64(threshold);
+    let mut best = None;
+    search(&sorted, capacity, max_cpus, &mut Vec::new(), &mut best);
+    best
+}
+
+/// Place `tasks[0]` on every CPU in use it fits on, or on one more CPU,
+/// and recurse; `best` is the fewest CPUs of any complete placement.
+#[cfg(any(test, feature = "bench"))]
+fn search(
+    tasks: &[Utilization],
+    capacity: Utilization,
+    max_cpus: usize,
+    cpus: &mut Vec<Utilization>,
+    best: &mut Option<usize>,
+) {
+    let Some((&task, rest)) = tasks.split_first() else {
+        *best = Some(best.map_or(cpus.len(), |b| b.min(cpus.len())));
+        return;
+    };
+    for i in 0..cpus.len() {
+        let before = cpus[i];
+        if before + task <= capacity {
+            cpus[i] = before + task;
+            search(rest, capacity, max_cpus, cpus, best);
+            cpus[i] = before;
+        }
+    }
+    // Only worth opening another CPU if that still beats the best so far.
+    let limit = best.map_or(max_cpus, |b| b - 1);
+    if cpus.len() < limit && task <= capacity {
+        cpus.push(task);
+        search(rest, capacity, max_cpus, cpus, best);
+        cpus.pop();
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fake_sched_task;
+
+    fn pct(values: &[u64]) -> Vec<Utilization> {
+        values.iter().map(|&p| Utilization::of(p, 100)).collect()
+    }
+
+    #[test]
+    fn lower_bound_is_the_total_rounded_up() {
+        assert_eq!(Utilization::ZERO.ceil_cpus(), 0);
+        assert_eq!(Utilization::of(1, 3).ceil_cpus(), 1);
+        assert_eq!(Utilization::cpus(2).ceil_cpus(), 2);
+        // 3 × 1/3 is exactly one CPU, not a rounding error above it.
+        let thirds: Utilization = [Utilization::of(1, 3); 3].into_iter().sum();
+        assert_eq!(thirds.ceil_cpus(), 1);
+        assert_eq!((thirds + Utilization::of(1, 1_000)).ceil_cpus(), 2);
+    }
+
+    #[test]
+    fn evaluate_measures_bins_spread_and_proximity() {
+        // n1: cpu0 = 60 % + 30 %, cpu1 = 20 %; n2: cpu0 = 50 %.
+        let map: NodeSchedMap = [
+            (
+                "n1".to_string(),
+                vec![
+                    fake_sched_task("a", "n1", 0, 10_000, 6_000),
+                    fake_sched_task("b", "n1", 0, 10_000, 3_000),
+                    fake_sched_task("c", "n1", 1, 10_000, 2_000),
+                ],
+            ),
+            (
+                "n2".to_string(),
+                vec![fake_sched_task("d", "n2", 0, 10_000, 5_000)],
+            ),
+        ]
+        .into();
+
+        let m = evaluate(&map, &QualityConfig::default());
+        assert_eq!(m.cpus_used, 3);
+        // 0.9 + 0.2 + 0.5 = 1.6
+        assert_eq!(m.lower_bound, 2);
+        assert_eq!(m.excess_cpus(), 1);
+        // Mean 1.6 / 3; deviations 11/30, -10/30, -1/30.
+        let expected = (121.0 + 100.0 + 1.0) / 900.0 / 3.0;
+        assert!((m.utilization_variance - expected).abs() < 1e-12);
+        assert_eq!(m.near_threshold, 1);
+
+        let wide = QualityConfig::default().with_proximity(0.45);
+        assert_eq!(evaluate(&map, &wide).near_threshold, 2);
+        assert_eq!(
+            evaluate(&NodeSchedMap::new(), &wide),
+            QualityMetrics::default()
+        );
+    }
+
+    #[test]
+    fn exhaustive_optimum_matches_hand_solved_fixtures() {
+        // (tasks in %, threshold, optimum)
+        let fixtures: [(&[u64], f64, Option<usize>); 6] = [
+            // 50+40 | 50+40: first-fit by size gets this too.
+            (&[50, 50, 40, 40], 0.9, Some(2)),
+            // 60+30 | 60+30 | 20: ⌈2.0⌉ = 2 is not reachable at 90 %.
+            (&[60, 60, 30, 30, 20], 0.9, Some(3)),
+            // 45+45 | 40+30+20 | 30+30+30: every CPU exactly full.
+            (&[45, 45, 40, 30, 30, 30, 30, 20], 0.9, Some(3)),
+            // Each task fills a CPU on its own.
+            (&[70, 70, 70], 0.9, Some(3)),
+            (&[], 0.9, Some(0)),
+            // A task above the threshold never fits.
+            (&[95], 0.9, None),
+        ];
+        for (tasks, threshold, optimum) in fixtures {
+            assert_eq!(
+                optimal_cpus(&pct(tasks), MAX_OPTIMAL_CPUS, threshold),
+                optimum,
+                "{tasks:?}"
+            );
+        }
+        // Three CPUs are needed, but only two are allowed.
+        assert_eq!(optimal_cpus(&pct(&[70, 70, 70]), 2, 0.9), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "exhaustive search is limited")]
+    fn exhaustive_optimum_refuses_large_inputs() {
+        optimal_cpus(&pct(&[1; MAX_OPTIMAL_TASKS + 1]), 1, 0.9);
+    }
+}
@@ -0,0 +1,360 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Response Time Analysis (RTA) with priority-ceiling blocking.
+//!
+//! Utilisation tests — the 90 % threshold and the Liu & Layland bound in
+//! [`feasibility`](super::feasibility) — assume independent tasks.  Tasks that
+//! share a lock ([`SharedResource`]) can additionally be *blocked* by a
+//! lower-priority task holding it.  This module adds that term.
+//!
+//! # Model
+//!
+//! Per `(node, cpu)`, fixed priorities (`SchedTask::priority`, higher value =
+//! higher priority, as for SCHED_FIFO/RR).  Under the Priority Ceiling
+//! Protocol a task is blocked at most once, for the longest critical section
+//! of any lower-priority task on a resource whose ceiling is at least its own
+//! priority:
+//!
+//! $$B_i = \max \{\, cs_{j,r} : \pi_j < \pi_i,\ \lceil r \rceil \ge \pi_i \,\}$$
+//!
+//! $$R_i = C_i + B_i + \sum_{j \in hp(i)} \left\lceil \frac{R_i}{T_j} \right\rceil C_j$$
+//!
+//! iterated to a fixed point, or until it exceeds the deadline.  Tasks of
+//! equal priority are counted as interference (conservative).
+//!
+//! Resources are scoped to one CPU.  Sharers placed on different CPUs are
+//! reported as [`BlockingWarning::CrossCpuResource`] — that blocking is not
+//! modelled.
+//!
+//! Like the Liu & Layland check, the result is **advisory**: warnings are
+//! logged, the schedule is still returned.
+
+use std::collections::BTreeMap;
+
+use tracing::warn;
+
+use crate::task::{Nanos, NodeSchedMap, SchedTask, SharedResource};
+use crate::units::fmt_duration_ns;
+
+// ── Results ───────────────────────────────────────────────────────────────────
+
+/// RTA outcome for one task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RtaResult {
+    pub node: String,
+    pub cpu: u32,
+    pub task: String,
+    /// Worst-case blocking term `B_i`.
+    pub blocking: Nanos,
+    /// Worst-case response time, or `None` if it exceeds `deadline`.
+    pub response: Option<Nanos>,
+    pub deadline: Nanos,
+}
+
+impl RtaResult {
+    pub fn schedulable(&self) -> bool {
+        self.response.is_some()
+    }
+}
+
+/// Problems found by [`analyse_schedule`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockingWarning {
+    /// The task meets its deadline without blocking but not with it.
+    BlockingCausesMiss {
+        node: String,
+        cpu: u32,
+        task: String,
+        blocking: Nanos,
+        deadline: Nanos,
+    },
+    /// Tasks sharing `resource` were placed on different CPUs.
+    /// `placements` is `(node, cpu, task)`, sorted.
+    CrossCpuResource {
+        resource: String,
+        placements: Vec<(String, u32, String)>,
+    },
+}
+
+/// Everything [`analyse_schedule`] produces.  Sorted by node, CPU, then task.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RtaReport {
+    pub results: Vec<RtaResult>,
+    pub warnings: Vec<BlockingWarning>,
+}
+
+// ── Per-CPU analysis ──────────────────────────────────────────────────────────
+
+fn cs_ns(r: &SharedResource) -> Nanos {
+    r.max_cs_us.saturating_to_nanos()
+}
+
+/// Priority ceiling of `resource` among `cpu_tasks` (`None` if unused).
+fn ceiling(resource: &str, cpu_tasks: &[&SchedTask]) -> Option<i32> {
+    cpu_tasks
+        .iter()
+        .filter(|t| t.shared_resources.iter().any(|r| r.name == resource))
+        .map(|t| t.priority)
+        .max()
+}
+
+/// Worst-case blocking of `task` by the lower-priority tasks in `cpu_tasks`.
+pub fn blocking_time(task: &SchedTask, cpu_tasks: &[&SchedTask]) -> Nanos {
+    cpu_tasks
+        .iter()
+        .filter(|j| j.priority < task.priority)
+        .flat_map(|j| j.shared_resources.iter())
+        .filter(|r| ceiling(&r.name, cpu_tasks).is_some_and(|c| c >= task.priority))
+        .map(cs_ns)
+        .max()
+        .unwrap_or(Nanos::ZERO)
+}
+
+/// Effective deadline: the relative deadline, or the period if unset.
+fn deadline_of(t: &SchedTask) -> Nanos {
+    if t.deadline_ns.is_zero() {
+        t.period_ns
+    } else {
+        t.deadline_ns
+    }
+}
+
+/// Fixed-point response time of `task` with blocking term `blocking`.
+///
+/// Returns `None` once the iteration exceeds the task's deadline.
+pub fn response_time(task: &SchedTask, cpu_tasks: &[&SchedTask], blocking: Nanos) -> Option<Nanos> {
+    let deadline = deadline_of(task).as_u64();
+    let hp: Vec<&&SchedTask> = cpu_tasks
+        .iter()
+        .filter(|j| j.name != task.name && j.priority >= task.priority && !j.period_ns.is_zero())
+        .collect();
+
+    let base = task.runtime_ns.as_u64().saturating_add(blocking.as_u64());
+    let mut r = base;
+    loop {
+        if r > deadline {
+            return None;
+        }
+        let next = hp.iter().fold(base, |acc, j| {
+            let releases = r.div_ceil(j.period_ns.as_u64());
+            acc.saturating_add(releases.saturating_mul(j.runtime_ns.as_u64()))
+        });
+        if next == r {
+            return Some(Nanos(r));
+        }
+        r = next;
+    }
+}
+
+// ── Whole-schedule analysis ───────────────────────────────────────────────────
+
+/// Run RTA (with blocking) on every `(node, cpu)` task set and check that
+/// resource sharers are co-located.
+pub fn analyse_schedule(schedule: &NodeSchedMap) -> RtaReport {
+    let mut by_cpu: BTreeMap<(&str, u32), Vec<&SchedTask>> = BTreeMap::new();
+    let mut sharers: BTreeMap<&str, Vec<(String, u32, String)>> = BTreeMap::new();
+    for (node, tasks) in schedule {
+        for t in tasks.iter().filter(|t| !t.period_ns.is_zero()) {
+            by_cpu.entry((node, t.assigned_cpu)).or_default().push(t);
+            for r in &t.shared_resources {
+                sharers.entry(&r.name).or_default().push((
+                    node.clone(),
+                    t.assigned_cpu,
+                    t.name.clone(),
+                ));
+            }
+        }
+    }
+
+    let mut report = RtaReport::default();
+    for ((node, cpu), mut tasks) in by_cpu {
+        tasks.sort_by(|a, b| a.name.cmp(&b.name));
+        for t in &tasks {
+            let blocking = blocking_time(t, &tasks);
+            let response = response_time(t, &tasks, blocking);
+            if response.is_none()
+                && !blocking.is_zero()
+                && response_time(t, &tasks, Nanos::ZERO).is_some()
+            {
+                report.warnings.push(BlockingWarning::BlockingCausesMiss {
+                    node: node.to_string(),
+                    cpu,
+                    task: t.name.clone(),
+                    blocking,
+                    deadline: deadline_of(t),
+                });
+            }
+            report.results.push(RtaResult {
+                node: node.to_string(),
+                cpu,
+                task: t.name.clone(),
+                blocking,
+                response,
+                deadline: deadline_of(t),
+            });
+        }
+    }
+
+    for (resource, mut placements) in sharers {
+        placements.sort();
+        let first = (&placements[0].0, placements[0].1);
+        if placements.iter().any(|(n, c, _)| (n, *c) != first) {
+            report.warnings.push(BlockingWarning::CrossCpuResource {
+                resource: resource.to_string(),
+                placements,
+            });
+        }
+    }
+    report
+}
+
+/// Log every unschedulable task and every [`BlockingWarning`].
+pub(super) fn log_report(report: &RtaReport) {
+    for r in report.results.iter().filter(|r| !r.schedulable()) {
+        warn!(
+            node     = %r.node,
+            cpu      = r.cpu,
+            task     = %r.task,
+            blocking = %fmt_duration_ns(r.blocking.as_u64()),
+            deadline = %fmt_duration_ns(r.deadline.as_u64()),
+            "RTA: worst-case response time exceeds deadline"
+        );
+    }
+    for w in &report.warnings {
+        match w {
+            BlockingWarning::BlockingCausesMiss {
+                node,
+                cpu,
+                task,
+                blocking,
+                deadline,
+            } => warn!(
+                node = %node,
+                cpu = cpu,
+                task = %task,
+                blocking = %fmt_duration_ns(blocking.as_u64()),
+                deadline = %fmt_duration_ns(deadline.as_u64()),
+                "RTA: priority-ceiling blocking alone pushes task past its deadline"
+            ),
+            BlockingWarning::CrossCpuResource {
+                resource,
+                placements,
+            } => warn!(
+                resource = %resource,
+                ?placements,
+                "shared resource used from several CPUs — cross-CPU blocking is not modelled"
+            ),
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Micros, SchedPolicy};
+
+    fn task(name: &str, cpu: u32, prio: i32, period_us: u64, runtime_us: u64) -> SchedTask {
+        SchedTask {
+            name: name.into(),
+            assigned_node: "n1".into(),
+            assigned_cpu: cpu,
+            policy: SchedPolicy::Fifo,
+            priority: prio,
+            period_ns: Micros(period_us).saturating_to_nanos(),
+            runtime_ns: Micros(runtime_us).saturating_to_nanos(),
+            deadline_ns: Micros(period_us).saturating_to_nanos(),
+            release_time_us: 0,
+            max_dmiss: 3,
+            shared_resources: Vec::new(),
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    fn uses(mut t: SchedTask, resource: &str, cs_us: u64) -> SchedTask {
+        t.shared_resources.push(SharedResource {
+            name: resource.into(),
+            max_cs_us: Micros(cs_us),
+        });
+        t
+    }
+
+    fn one_node(tasks: Vec<SchedTask>) -> NodeSchedMap {
+        [("n1".to_string(), tasks)].into()
+    }
+
+    #[test]
+    fn independent_tasks_classic_rta() {
+        // Textbook set: C/T = 1/4, 2/6, 3/10 → R = 1, 3, 10.
+        let map = one_node(vec![
+            task("a", 0, 30, 4_000, 1_000),
+            task("b", 0, 20, 6_000, 2_000),
+            task("c", 0, 10, 10_000, 3_000),
+        ]);
+        let report = analyse_schedule(&map);
+        let r: Vec<_> = report.results.iter().map(|r| r.response).collect();
+        assert_eq!(
+            r,
+            vec![
+                Some(Nanos(1_000_000)),
+                Some(Nanos(3_000_000)),
+                Some(Nanos(10_000_000))
+            ]
+        );
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn blocking_alone_causes_rta_failure() {
+        // hi: C=4 ms, D=5 ms.  lo holds "bus" for up to 2 ms → R_hi = 6 ms.
+        let mut hi = uses(task("hi", 0, 50, 10_000, 4_000), "bus", 1_000);
+        hi.deadline_ns = Micros(5_000).saturating_to_nanos();
+        let lo = uses(task("lo", 0, 10, 100_000, 20_000), "bus", 2_000);
+        let report = analyse_schedule(&one_node(vec![hi, lo]));
+
+        let hi = report.results.iter().find(|r| r.task == "hi").unwrap();
+        assert_eq!(hi.blocking, Nanos(2_000_000));
+        assert!(!hi.schedulable());
+        assert_eq!(
+            report.warnings,
+            vec![BlockingWarning::BlockingCausesMiss {
+                node: "n1".into(),
+                cpu: 0,
+                task: "hi".into(),
+                blocking: Nanos(2_000_000),
+                deadline: Nanos(5_000_000),
+            }]
+        );
+    }
+
+    #[test]
+    fn lowest_priority_task_is_never_blocked() {
+        let hi = uses(task("hi", 0, 50, 10_000, 1_000), "bus", 1_000);
+        let lo = uses(task("lo", 0, 10, 100_000, 1_000), "bus", 2_000);
+        let tasks = [&hi, &lo];
+        assert_eq!(blocking_time(&lo, &tasks), Nanos::ZERO);
+        assert_eq!(blocking_time(&hi, &tasks), Nanos(2_000_000));
+    }
+
+    #[test]
+    fn sharers_on_different_cpus_are_reported() {
+        let a = uses(task("a", 0, 50, 10_000, 1_000), "bus", 100);
+        let b = uses(task("b", 1, 10, 10_000, 1_000), "bus", 100);
+        let report = analyse_schedule(&one_node(vec![a, b]));
+        assert!(matches!(
+            report.warnings.as_slice(),
+            [BlockingWarning::CrossCpuResource { resource, placements }]
+                if resource == "bus" && placements.len() == 2
+        ));
+    }
+}
@@ -0,0 +1,855 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Per-task scheduling events, decoupled from `tracing`.
+//!
+//! The algorithms report what happens to each task through a
+//! [`ScheduleEventSink`] instead of logging directly:
+//!
+//! | Event                 | When                                                  |
+//! |-----------------------|-------------------------------------------------------|
+//! | `task_placed`         | a task was assigned a CPU                             |
+//! | `task_rejected`       | a task was left unplaced without failing the run      |
+//! | `node_skipped`        | a preferred `target_node` could not take the task     |
+//! | `location_preferred`  | proximity broke a near-tie between candidate nodes    |
+//! | `feasibility_warning` | a node's task set exceeds the Liu & Layland bound     |
+//! | `cache_hint`          | a task was placed on a node where cache mates run     |
+//! | `audit`               | the gRPC service changed or refused cluster state     |
+//!
+//! [`TracingSink`], the default, logs each event exactly as the scheduler
+//! did before sinks existed (same level, target, message and fields).  To
+//! collect events as well, install a [`FanOut`] of it and your own sink
+//! with [`GlobalScheduler::with_sink`](super::GlobalScheduler::with_sink).
+//! Run-level lines (algorithm start, progress, node summaries) and the RTA
+//! report still go to `tracing` directly.
+//!
+//! The gRPC service reports its `audit` log lines (admissions, removals,
+//! reloads, drains, failovers…) as [`AuditEvent`]s to the sink its
+//! schedulers share, so [`TracingSink`] logs them under the `audit` target
+//! as before.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Arc;
+
+use tracing::{debug, info, warn};
+
+use super::error::AdmissionReason;
+use super::{Phase, PhaseTimings, ScheduleOptions, UpdateLevel};
+use crate::config::ConfigError;
+use crate::metadata::Metadata;
+use crate::task::Task;
+use crate::units::fmt_duration_us;
+
+/// Target of every [`TracingSink`] event but placements, as before.
+const SCHEDULER_TARGET: &str = "timpani_o::scheduler";
+
+/// Target of the "✓ scheduled" lines, as before.
+const PLACEMENT_TARGET: &str = "timpani_o::scheduler::log_policy";
+
+/// Target of every [`AuditEvent`], as before.
+const AUDIT_TARGET: &str = "audit";
+
+// ── AuditEvent ────────────────────────────────────────────────────────────────
+
+/// A change to (or refused change of) cluster state, reported by the gRPC
+/// service.  One variant per `audit` log line; the fields are the line's.
+#[derive(Debug, Clone, Copy)]
+pub enum AuditEvent<'a> {
+    /// Options a workload is scheduled with; `overridden` if the request
+    /// set any of them.
+    SchedulingOptions {
+        tenant: &'a str,
+        workload_id: &'a str,
+        opts: &'a ScheduleOptions,
+        overridden: bool,
+    },
+    /// A revision changing tasks by more than the revision factor was
+    /// applied with `force`.
+    RevisionForced {
+        tenant: &'a str,
+        workload_id: &'a str,
+        changes: &'a str,
+    },
+    /// Time spent in each scheduling phase of an admission.
+    PhaseTimings {
+        tenant: &'a str,
+        workload_id: &'a str,
+        timings: &'a PhaseTimings,
+    },
+    /// Forwarded task metadata of an admitted workload, by task.
+    TaskMetadata {
+        tenant: &'a str,
+        workload_id: &'a str,
+        metadata: &'a BTreeMap<&'a str, Metadata>,
+    },
+    /// A workload was admitted with the `skipped` admission checks off.
+    OverridesApplied {
+        tenant: &'a str,
+        workload_id: &'a str,
+        skipped: &'a str,
+    },
+    /// A workload was queued behind `depth` others until capacity frees.
+    WorkloadQueued {
+        tenant: &'a str,
+        workload_id: &'a str,
+        depth: usize,
+    },
+    /// A queued workload was scheduled after `waited_ms`.
+    QueuedScheduled {
+        tenant: &'a str,
+        workload_id: &'a str,
+        waited_ms: u64,
+    },
+    /// A queued workload was removed before it was scheduled.
+    QueuedRemoved {
+        tenant: &'a str,
+        workload_id: &'a str,
+    },
+    /// A stored workload was removed.
+    WorkloadRemoved {
+        tenant: &'a str,
+        workload_id: &'a str,
+    },
+    /// A stored workload outlived its TTL and was removed.
+    WorkloadExpired {
+        tenant: &'a str,
+        workload_id: &'a str,
+        generation: u64,
+    },
+    /// `node` was cordoned or uncordoned.
+    CordonChanged { node: &'a str, cordoned: bool },
+    /// Tasks of a workload were moved off the draining `node`.
+    TasksDrained {
+        tenant: &'a str,
+        workload_id: &'a str,
+        node: &'a str,
+        generation: u64,
+    },
+    /// A task's timing was updated, at `level`.
+    TaskUpdated {
+        tenant: &'a str,
+        workload_id: &'a str,
+        task: &'a str,
+        level: UpdateLevel,
+        node: &'a str,
+        cpu: u32,
+        generation: u64,
+    },
+    /// A compaction was proposed; utilizations in percent.
+    CompactionProposed {
+        proposal_id: u64,
+        moves: usize,
+        nodes_freed: &'a [String],
+        nodes_before: usize,
+        nodes_after: usize,
+        peak_before_pct: f64,
+        peak_after_pct: f64,
+    },
+    /// A workload was re-placed by compaction `proposal_id`.
+    WorkloadCompacted {
+        tenant: &'a str,
+        workload_id: &'a str,
+        generation: u64,
+        proposal_id: u64,
+    },
+    /// The node configuration was replaced with one of `nodes`.
+    ConfigReloaded {
+        nodes: &'a BTreeSet<String>,
+        generation: u64,
+    },
+    /// A pushed node configuration failed to load.
+    ConfigRefused { error: &'a ConfigError },
+    /// `moved` orphaned tasks of a workload were re-placed after a reload.
+    OrphansEvacuated {
+        tenant: &'a str,
+        workload_id: &'a str,
+        generation: u64,
+        moved: usize,
+    },
+    /// `task` stays on `node`, which is no longer configured.
+    TaskOrphaned {
+        tenant: &'a str,
+        workload_id: &'a str,
+        node: &'a str,
+        task: &'a str,
+    },
+    /// `node` did not apply a schedule within `deadline_ms`, leaving
+    /// `degraded` tasks unapplied.
+    ApplyOverdue {
+        tenant: &'a str,
+        workload_id: &'a str,
+        node: &'a str,
+        generation: u64,
+        deadline_ms: u64,
+        degraded: usize,
+    },
+    /// `moved` tasks of a workload were failed over from the unresponsive
+    /// `node`.
+    FailedOver {
+        tenant: &'a str,
+        workload_id: &'a str,
+        node: &'a str,
+        generation: u64,
+        moved: usize,
+    },
+}
+
+// ── ScheduleEventSink ─────────────────────────────────────────────────────────
+
+/// Receiver of per-task scheduling events (see the module docs).
+///
+/// Every method defaults to doing nothing, so a sink implements only the
+/// events it cares about.  Events arrive on the scheduling thread, in
+/// placement order.
+pub trait ScheduleEventSink: Send + Sync {
+    /// `task` was assigned `cpu` on `node`.  `verbose` is whether the run's
+    /// [`LogPolicy`](super::LogPolicy) logs each placement at `info!`.
+    fn task_placed(&self, _task: &Task, _node: &str, _cpu: u32, _verbose: bool) {}
+
+    /// `task` was selected for `node` but left unplaced.
+    fn task_rejected(&self, _task: &Task, _node: &str, _reason: &str) {}
+
+    /// `task`'s preferred `node` could not take it; another node is chosen.
+    fn node_skipped(&self, _task: &Task, _node: &str, _reason: &AdmissionReason) {}
+
+    /// `node`, at location cost `cost` from the task's zone, was chosen
+    /// over the nearly equally scored `over` (see
+    /// [`proximity`](super::proximity)).
+    fn location_preferred(&self, _task: &Task, _node: &str, _over: &str, _cost: u32) {}
+
+    /// The `task_count` tasks on `node` total `utilization`, above the
+    /// Liu & Layland `bound`, already scaled by `cpu_share`: the share of
+    /// each CPU the node's tasks get (1.0 unless it is time-partitioned).
+    fn feasibility_warning(
+        &self,
+        _node: &str,
+        _utilization: f64,
+        _bound: f64,
+        _cpu_share: f64,
+        _task_count: usize,
+    ) {
+    }
+
+    /// `task` was placed on `cpu` of a `node` where members of its cache
+    /// affinity group already run; `honoured` is whether `cpu` shares a
+    /// cluster with one of them but runs none (see
+    /// [`cache_group`](super::cache_group)).  Follows its `task_placed`.
+    fn cache_hint(&self, _task: &Task, _node: &str, _cpu: u32, _honoured: bool) {}
+
+    /// The gRPC service changed, or refused to change, cluster state.
+    fn audit(&self, _event: &AuditEvent<'_>) {}
+}
+
+// ── TracingSink ───────────────────────────────────────────────────────────────
+
+/// Logs every event through `tracing`; the default sink.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingSink;
+
+impl ScheduleEventSink for TracingSink {
+    fn task_placed(&self, task: &Task, node: &str, cpu: u32, verbose: bool) {
+        if verbose {
+            info!(
+                target: PLACEMENT_TARGET,
+                task = %task.name,
+                node = %node,
+                cpu  = cpu,
+                wcet = %fmt_duration_us(task.runtime_us.as_u64()),
+                "✓ scheduled"
+            );
+        } else {
+            debug!(
+                target: PLACEMENT_TARGET,
+                task = %task.name,
+                node = %node,
+                cpu  = cpu,
+                wcet = %fmt_duration_us(task.runtime_us.as_u64()),
+                "✓ scheduled"
+            );
+        }
+    }
+
+    fn task_rejected(&self, task: &Task, node: &str, reason: &str) {
+        warn!(
+            target: SCHEDULER_TARGET,
+            task = %task.name,
+            node = %node,
+            "{reason}"
+        );
+    }
+
+    fn node_skipped(&self, task: &Task, node: &str, reason: &AdmissionReason) {
+        warn!(
+            target: SCHEDULER_TARGET,
+            task = %task.name,
+            node = %node,
+            %reason,
+            "preferred target_node cannot take task, falling back to auto-select"
+        );
+    }
+
+    fn location_preferred(&self, task: &Task, node: &str, over: &str, cost: u32) {
+        info!(
+            target: SCHEDULER_TARGET,
+            task = %task.name,
+            node = %node,
+            over = %over,
+            zone = %task.preferred_location,
+            cost,
+            "near-tie broken by location proximity"
+        );
+    }
+
+    fn feasibility_warning(
+        &self,
+        node: &str,
+        utilization: f64,
+        bound: f64,
+        cpu_share: f64,
+        task_count: usize,
+    ) {
+        if cpu_share < 1.0 {
+            warn!(
+                target: SCHEDULER_TARGET,
+                node       = %node,
+                utilization = utilization,
+                bound       = bound,
+                cpu_share   = cpu_share,
+                task_count  = task_count,
+                "task set may not be RM-schedulable (utilization exceeds the Liu & Layland \
+                 bound scaled to the node's time-partition capacity) \
+                 — manual Response Time Analysis required"
+            );
+            return;
+        }
+        warn!(
+            target: SCHEDULER_TARGET,
+            node       = %node,
+            utilization = utilization,
+            bound       = bound,
+            task_count  = task_count,
+            "task set may not be RM-schedulable (utilization exceeds Liu & Layland bound) \
+             — manual Response Time Analysis required"
+        );
+    }
+
+    fn cache_hint(&self, task: &Task, node: &str, cpu: u32, honoured: bool) {
+        let group = task.cache_affinity_group.as_deref().unwrap_or_default();
+        if honoured {
+            debug!(
+                target: SCHEDULER_TARGET,
+                task = %task.name,
+                node = %node,
+                cpu,
+                group = %group,
+                "placed in the cache cluster of its group"
+            );
+        } else {
+            info!(
+                target: SCHEDULER_TARGET,
+                task = %task.name,
+                node = %node,
+                cpu,
+                group = %group,
+                "no room in the cache cluster of its group, placed outside it"
+            );
+        }
+    }
+
+    fn audit(&self, event: &AuditEvent<'_>) {
+        match *event {
+            AuditEvent::SchedulingOptions {
+                tenant,
+                workload_id,
+                opts,
+                overridden,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant       = %tenant,
+                workload_id  = %workload_id,
+                algorithm    = %opts.algorithm,
+                algorithm_chain = ?opts.algorithm_chain,
+                threshold    = opts.cpu_utilization_threshold,
+                seed         = opts.seed,
+                allowed_nodes = ?opts.allowed_nodes,
+                overridden,
+                "scheduling options"
+            ),
+            AuditEvent::RevisionForced {
+                tenant,
+                workload_id,
+                changes,
+            } => warn!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                changes     = %changes,
+                "drastic revision forced"
+            ),
+            AuditEvent::PhaseTimings {
+                tenant,
+                workload_id,
+                timings,
+            } => {
+                let us = |phase| timings.get(phase).as_micros() as u64;
+                info!(
+                    target: AUDIT_TARGET,
+                    tenant             = %tenant,
+                    workload_id        = %workload_id,
+                    preconditions_us   = us(Phase::Preconditions),
+                    available_cpus_us  = us(Phase::AvailableCpus),
+                    algorithm_us       = us(Phase::Algorithm),
+                    feasibility_us     = us(Phase::Feasibility),
+                    build_sched_map_us = us(Phase::BuildSchedMap),
+                    total_us           = timings.total().as_micros() as u64,
+                    "schedule phase timings"
+                );
+            }
+            AuditEvent::TaskMetadata {
+                tenant,
+                workload_id,
+                metadata,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                metadata    = ?metadata,
+                "task metadata"
+            ),
+            AuditEvent::OverridesApplied {
+                tenant,
+                workload_id,
+                skipped,
+            } => warn!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                skipped     = %skipped,
+                "workload admitted with admission checks skipped"
+            ),
+            AuditEvent::WorkloadQueued {
+                tenant,
+                workload_id,
+                depth,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                depth       = depth,
+                "workload queued until capacity is released"
+            ),
+            AuditEvent::QueuedScheduled {
+                tenant,
+                workload_id,
+                waited_ms,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                waited_ms   = waited_ms,
+                "queued workload scheduled"
+            ),
+            AuditEvent::QueuedRemoved {
+                tenant,
+                workload_id,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                "queued workload removed"
+            ),
+            AuditEvent::WorkloadRemoved {
+                tenant,
+                workload_id,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                "workload removed"
+            ),
+            AuditEvent::WorkloadExpired {
+                tenant,
+                workload_id,
+                generation,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                generation  = generation,
+                "workload expired and removed"
+            ),
+            AuditEvent::CordonChanged { node, cordoned } => {
+                info!(target: AUDIT_TARGET, node = %node, cordoned, "node cordon state changed");
+            }
+            AuditEvent::TasksDrained {
+                tenant,
+                workload_id,
+                node,
+                generation,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                node        = %node,
+                generation  = generation,
+                "tasks drained from node"
+            ),
+            AuditEvent::TaskUpdated {
+                tenant,
+                workload_id,
+                task,
+                level,
+                node,
+                cpu,
+                generation,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                task        = %task,
+                level       = %level,
+                node        = %node,
+                cpu         = cpu,
+                generation  = generation,
+                "task timing updated"
+            ),
+            AuditEvent::CompactionProposed {
+                proposal_id,
+                moves,
+                nodes_freed,
+                nodes_before,
+                nodes_after,
+                peak_before_pct,
+                peak_after_pct,
+            } => info!(
+                target: AUDIT_TARGET,
+                proposal_id = proposal_id,
+                moves       = moves,
+                nodes_freed = ?nodes_freed,
+                nodes_before = nodes_before,
+                nodes_after = nodes_after,
+                peak_before_pct = peak_before_pct,
+                peak_after_pct = peak_after_pct,
+                "compaction proposed"
+            ),
+            AuditEvent::WorkloadCompacted {
+                tenant,
+                workload_id,
+                generation,
+                proposal_id,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                generation  = generation,
+                proposal_id,
+                "workload compacted"
+            ),
+            AuditEvent::ConfigReloaded { nodes, generation } => info!(
+                target: AUDIT_TARGET,
+                nodes = ?nodes,
+                generation,
+                "node configuration reloaded"
+            ),
+            AuditEvent::ConfigRefused { error } => {
+                warn!(target: AUDIT_TARGET, error = %error, "pushed node configuration refused");
+            }
+            AuditEvent::OrphansEvacuated {
+                tenant,
+                workload_id,
+                generation,
+                moved,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                generation  = generation,
+                moved       = moved,
+                "orphaned tasks evacuated"
+            ),
+            AuditEvent::TaskOrphaned {
+                tenant,
+                workload_id,
+                node,
+                task,
+            } => warn!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                node        = %node,
+                task        = %task,
+                "task orphaned: node is no longer configured"
+            ),
+            AuditEvent::ApplyOverdue {
+                tenant,
+                workload_id,
+                node,
+                generation,
+                deadline_ms,
+                degraded,
+            } => warn!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                node        = %node,
+                generation  = generation,
+                deadline_ms = deadline_ms,
+                degraded    = degraded,
+                "schedule not applied before the deadline"
+            ),
+            AuditEvent::FailedOver {
+                tenant,
+                workload_id,
+                node,
+                generation,
+                moved,
+            } => info!(
+                target: AUDIT_TARGET,
+                tenant      = %tenant,
+                workload_id = %workload_id,
+                node        = %node,
+                generation  = generation,
+                moved       = moved,
+                "tasks failed over from unresponsive node"
+            ),
+        }
+    }
+}
+
+// ── FanOut ────────────────────────────────────────────────────────────────────
+
+/// Forwards every event to each of its sinks, in the order they were added.
+#[derive(Clone, Default)]
+pub struct FanOut {
+    sinks: Vec<Arc<dyn ScheduleEventSink>>,
+}
+
+impl FanOut {
+    /// A fan-out with no sinks; events go nowhere until one is added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_sink(mut self, sink: Arc<dyn ScheduleEventSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+}
+
+impl fmt::Debug for FanOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FanOut")
+            .field("sinks", &self.sinks.len())
+            .finish()
+    }
+}
+
+impl ScheduleEventSink for FanOut {
+    fn task_placed(&self, task: &Task, node: &str, cpu: u32, verbose: bool) {
+        for sink in &self.sinks {
+            sink.task_placed(task, node, cpu, verbose);
+        }
+    }
+
+    fn task_rejected(&self, task: &Task, node: &str, reason: &str) {
+        for sink in &self.sinks {
+            sink.task_rejected(task, node, reason);
+        }
+    }
+
+    fn node_skipped(&self, task: &Task, node: &str, reason: &AdmissionReason) {
+        for sink in &self.sinks {
+            sink.node_skipped(task, node, reason);
+        }
+    }
+
+    fn location_preferred(&self, task: &Task, node: &str, over: &str, cost: u32) {
+        for sink in &self.sinks {
+            sink.location_preferred(task, node, over, cost);
+        }
+    }
+
+    fn feasibility_warning(
+        &self,
+        node: &str,
+        utilization: f64,
+        bound: f64,
+        cpu_share: f64,
+        task_count: usize,
+    ) {
+        for sink in &self.sinks {
+            sink.feasibility_warning(node, utilization, bound, cpu_share, task_count);
+        }
+    }
+
+    fn cache_hint(&self, task: &Task, node: &str, cpu: u32, honoured: bool) {
+        for sink in &self.sinks {
+            sink.cache_hint(task, node, cpu, honoured);
+        }
+    }
+
+    fn audit(&self, event: &AuditEvent<'_>) {
+        for sink in &self.sinks {
+            sink.audit(event);
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::config::{NodeConfig, NodeConfigManager, NodeProtocol};
+    use crate::scheduler::{GlobalScheduler, SchedAlgorithm, ScheduleOptions};
+    use crate::task::{Micros, TargetNodePolicy};
+
+    /// Shared buffer the test subscriber writes to.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Records the events it receives as short strings.
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl ScheduleEventSink for Recorder {
+        fn task_placed(&self, task: &Task, node: &str, cpu: u32, _verbose: bool) {
+            let event = format!("placed {} {node}:{cpu}", task.name);
+            self.0.lock().unwrap().push(event);
+        }
+
+        fn node_skipped(&self, task: &Task, node: &str, _reason: &AdmissionReason) {
+            let event = format!("skipped {} {node}", task.name);
+            self.0.lock().unwrap().push(event);
+        }
+
+        fn feasibility_warning(&self, node: &str, _: f64, _: f64, _: f64, task_count: usize) {
+            let event = format!("feasibility {node} {task_count}");
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    fn node(name: &str, cpus: u32) -> NodeConfig {
+        NodeConfig {
+            name: name.into(),
+            available_cpus: (0..cpus).collect(),
+            reserved_cpus: Vec::new(),
+            max_memory_mb: 4096,
+            architecture: "x86_64".into(),
+            location: String::new(),
+            description: String::new(),
+            endpoint: None,
+            max_workloads: None,
+            enabled: true,
+            time_partitions: None,
+            cpu_clusters: Vec::new(),
+            protocol: NodeProtocol::Grpc,
+        }
+    }
+
+    fn task(name: &str, runtime_us: u64, target: &str) -> Task {
+        Task {
+            name: name.into(),
+            workload_id: "wl".into(),
+            target_node: target.into(),
+            target_node_policy: (!target.is_empty()).then_some(TargetNodePolicy::Preferred),
+            period_us: Micros(10_000),
+            runtime_us: Micros(runtime_us),
+            deadline_us: Micros(10_000),
+            ..Default::default()
+        }
+    }
+
+    /// Run the fixture workload — one placement per task, one preferred
+    /// target fallback and one Liu & Layland warning — and return the log,
+    /// `debug!` included.
+    ///
+    /// "node initialised" and "node summary" lines are dropped: they follow
+    /// `HashMap` order.
+    fn fixture_log(sched: GlobalScheduler) -> String {
+        let tasks = vec![
+            task("a", 8_000, "n1"),
+            task("b", 5_000, "n1"),
+            task("c", 5_000, ""),
+        ];
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .without_time()
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            sched.schedule_with_options(tasks, &opts).unwrap();
+        });
+        let out = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        out.lines()
+            .filter(|l| !l.contains("node initialised") && !l.contains("node summary"))
+            .map(|l| format!("{l}\n"))
+            .collect()
+    }
+
+    fn scheduler() -> GlobalScheduler {
+        let nodes = NodeConfigManager::from_nodes(vec![node("n1", 1), node("n2", 2)]);
+        GlobalScheduler::new(Arc::new(nodes))
+    }
+
+    #[test]
+    fn default_sink_logs_as_before() {
+        // Captured from the scheduler before it reported through sinks.
+        let before = include_str!("../../tests/fixtures/schedule_log.txt");
+        assert_eq!(fixture_log(scheduler()), before);
+    }
+
+    #[test]
+    fn fan_out_keeps_the_log_and_feeds_every_sink() {
+        let recorder = Arc::new(Recorder::default());
+        let sink = FanOut::new()
+            .with_sink(Arc::new(TracingSink))
+            .with_sink(Arc::clone(&recorder) as Arc<dyn ScheduleEventSink>);
+        let sched = scheduler().with_sink(Arc::new(sink));
+
+        let before = include_str!("../../tests/fixtures/schedule_log.txt");
+        assert_eq!(fixture_log(sched), before);
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                "placed a n1:0",
+                "skipped b n1",
+                "placed b n2:1",
+                "placed c n2:0",
+                "feasibility n2 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn only_the_installed_sinks_see_events() {
+        let recorder = Arc::new(Recorder::default());
+        let sched = scheduler().with_sink(Arc::clone(&recorder) as Arc<dyn ScheduleEventSink>);
+        let log = fixture_log(sched);
+        assert!(!log.contains("✓ scheduled"));
+        assert!(!log.contains("Liu & Layland"));
+        assert!(log.contains("least_loaded done"));
+        assert_eq!(recorder.0.lock().unwrap().len(), 5);
+    }
+}
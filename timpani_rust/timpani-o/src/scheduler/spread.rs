@@ -0,0 +1,167 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Algorithm 4: `randomized_spread` — seeded pseudo-random placement.
+//!
+//! Intended for soak testing: every seed yields a different valid placement,
+//! and the same seed always yields the same one.  For each task the candidate
+//! node order is shuffled with [`SplitMix64`]; the first node that passes
+//! admission and has a CPU under the threshold wins.  CPU selection within the
+//! node is the standard [`find_best_cpu_for_task`] so only *node* choice is
+//! randomised.
+//!
+//! No external RNG crate: SplitMix64 is a few lines, has a fixed output
+//! sequence across platforms and crate versions, and is more than random
+//! enough for exploring placements.
+//!
+//! [`find_best_cpu_for_task`]: GlobalScheduler::find_best_cpu_for_task
+
+use tracing::info;
+
+use super::{
+    AdmissionReason, AvailCpus, CpuUtil, GlobalScheduler, PinnedDemand, PlacementLog,
+    ScheduleOptions, SchedulerError,
+};
+use crate::admission;
+use crate::task::Task;
+
+// ── SplitMix64 ────────────────────────────────────────────────────────────────
+
+/// SplitMix64 PRNG (Steele, Lea & Flood, 2014).
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..bound` (`bound > 0`).  The modulo bias is
+    /// negligible for the handful of nodes shuffled here.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Fisher–Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+// ── Algorithm ─────────────────────────────────────────────────────────────────
+
+impl GlobalScheduler {
+    pub(super) fn schedule_randomized_spread(
+        &self,
+        tasks: &mut [Task],
+        avail: &AvailCpus,
+        util: &mut CpuUtil,
+        pinned: &mut PinnedDemand,
+        opts: &ScheduleOptions,
+        log: &mut PlacementLog,
+    ) -> Result<(), SchedulerError> {
+        let threshold = opts.effective_threshold();
+        info!(seed = opts.seed, "Executing randomized_spread algorithm");
+        let mut rng = SplitMix64::new(opts.seed);
+        // BTreeMap order is the deterministic starting point for each shuffle.
+        let nodes: Vec<&String> = avail
+            .iter()
+            .filter(|(_, cpus)| !cpus.is_empty())
+            .map(|(n, _)| n)
+            .collect();
+
+        for task in tasks.iter_mut() {
+            // Target ignored unless the task sets an explicit policy.
+            let node = self.select_node(task, avail, util, log.workloads(), opts, |t| {
+                let mut order = nodes.clone();
+                rng.shuffle(&mut order);
+                order
+                    .into_iter()
+                    .find(|node| {
+                        admission::node_verdict(
+                            &self.node_config_manager,
+                            t,
+                            node,
+                            avail,
+                            util,
+                            log.workloads(),
+                            opts,
+                        )
+                        .is_ok()
+                    })
+                    .cloned()
+            })?;
+
+            // select_node already found a fitting CPU here; should the two
+            // checks ever disagree, refuse rather than drop the task.
+            let open = log.workloads().open_cpus(avail, task);
+            match log.cache_groups().find_cpu(task, &node, &open, |cpus| {
+                Self::find_best_cpu_for_task(
+                    &self.node_config_manager,
+                    task,
+                    &node,
+                    cpus,
+                    util,
+                    pinned,
+                    threshold,
+                )
+            }) {
+                Some(cpu) => {
+                    self.assign_cpu_to_task(task, &node, cpu, util, pinned, opts)?;
+                    log.record(task, &node, cpu);
+                }
+                None => {
+                    return Err(SchedulerError::AdmissionRejected {
+                        task: task.name.clone(),
+                        node,
+                        reason: AdmissionReason::NoAvailableCpu,
+                    });
+                }
+            }
+        }
+
+        info!(total = tasks.len(), "randomized_spread done");
+        Ok(())
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splitmix_reference_sequence() {
+        // First outputs for seed 0 from the reference implementation.
+        let mut rng = SplitMix64::new(0);
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+    }
+
+    #[test]
+    fn shuffle_is_a_permutation_and_seed_dependent() {
+        let mut a: Vec<u32> = (0..16).collect();
+        let mut b = a.clone();
+        SplitMix64::new(1).shuffle(&mut a);
+        SplitMix64::new(2).shuffle(&mut b);
+        assert_ne!(a, b);
+        a.sort_unstable();
+        assert_eq!(a, (0..16).collect::<Vec<_>>());
+    }
+}
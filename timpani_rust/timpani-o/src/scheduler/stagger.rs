@@ -0,0 +1,208 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Release-offset staggering.
+//!
+//! Tasks that all release at `t = 0` on one CPU queue behind each other at
+//! every hyperperiod start: the last of ten 1 ms tasks waits 9 ms.
+//! [`stagger_releases`] is an optional post-placement pass that gives each
+//! task on a CPU its own phase within its period instead.
+//!
+//! Per `(node, cpu)`, tasks whose `release_time_us` is still zero are sorted
+//! by period, then name, and the `k`-th of `n` is given an offset according
+//! to the [`StaggerStrategy`]:
+//!
+//! | Strategy           | Offset of task `k` of `n`        | Suits                        |
+//! |--------------------|----------------------------------|------------------------------|
+//! | `EvenWithinPeriod` | `⌊k × period / n⌋`               | one batch, equal periods     |
+//! | `GoldenRatio`      | `⌊frac(k / φ) × period⌋`         | sets that grow over time     |
+//!
+//! The first task keeps offset zero.  Offsets are always below the task's
+//! period, and the result depends only on the schedule, never on input
+//! order.  A task with a non-zero `release_time_us` keeps it and is not
+//! counted in `n`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::task::{NodeSchedMap, SchedTask};
+
+/// `2⁶⁴ / φ`: multiplying by it and keeping the low 64 bits is
+/// `frac(k / φ)` in fixed point.
+const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// How [`stagger_releases`] spreads offsets (see the [module docs](self)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaggerStrategy {
+    EvenWithinPeriod,
+    GoldenRatio,
+}
+
+impl StaggerStrategy {
+    /// Canonical string form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StaggerStrategy::EvenWithinPeriod => "even_within_period",
+            StaggerStrategy::GoldenRatio => "golden_ratio",
+        }
+    }
+
+    /// Offset of task `k` of `n` within `period_us`; always `< period_us`.
+    fn offset(self, k: usize, n: usize, period_us: u64) -> u64 {
+        let fraction = |num: u128, den: u128| (num * u128::from(period_us) / den) as u64;
+        match self {
+            StaggerStrategy::EvenWithinPeriod => fraction(k as u128, n as u128),
+            StaggerStrategy::GoldenRatio => {
+                fraction(u128::from((k as u64).wrapping_mul(GOLDEN_GAMMA)), 1 << 64)
+            }
+        }
+    }
+}
+
+impl fmt::Display for StaggerStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for StaggerStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "even_within_period" => Ok(StaggerStrategy::EvenWithinPeriod),
+            "golden_ratio" => Ok(StaggerStrategy::GoldenRatio),
+            other => Err(format!("unknown stagger strategy '{other}'")),
+        }
+    }
+}
+
+/// Give every unphased task in `schedule` a release offset within its
+/// period (see the [module docs](self)).
+pub fn stagger_releases(schedule: &mut NodeSchedMap, strategy: StaggerStrategy) {
+    for tasks in schedule.values_mut() {
+        let mut by_cpu: BTreeMap<u32, Vec<&mut SchedTask>> = BTreeMap::new();
+        for t in tasks.iter_mut() {
+            if t.release_time_us == 0 && !t.period_ns.is_zero() {
+                by_cpu.entry(t.assigned_cpu).or_default().push(t);
+            }
+        }
+        for mut free in by_cpu.into_values() {
+            free.sort_by(|a, b| (a.period_ns, &a.name).cmp(&(b.period_ns, &b.name)));
+            let n = free.len();
+            for (k, t) in free.into_iter().enumerate() {
+                let offset = strategy.offset(k, n, t.period_ns.to_micros().as_u64());
+                t.release_time_us = i32::try_from(offset).unwrap_or(i32::MAX);
+            }
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::simulate::{simulate_schedule, DEFAULT_MAX_SIMULATED_JOBS};
+    use crate::task::{Nanos, SchedPolicy};
+
+    fn st(name: &str, cpu: u32, period_us: u64, runtime_us: u64) -> SchedTask {
+        SchedTask {
+            name: name.into(),
+            assigned_node: "n1".into(),
+            assigned_cpu: cpu,
+            policy: SchedPolicy::Fifo,
+            priority: 50,
+            period_ns: Nanos(period_us * 1_000),
+            runtime_ns: Nanos(runtime_us * 1_000),
+            deadline_ns: Nanos(period_us * 1_000),
+            release_time_us: 0,
+            max_dmiss: 0,
+            shared_resources: vec![],
+            workload_id: String::new(),
+            fallback_from: None,
+            metadata: Default::default(),
+            placeholder: false,
+            exclusive_cpus: false,
+            fault_sink: Default::default(),
+            cache_affinity_group: None,
+        }
+    }
+
+    /// Ten 10 ms tasks of 500 µs each, all releasing at `t = 0` on one CPU.
+    fn bursty() -> NodeSchedMap {
+        let tasks = (0..10)
+            .map(|i| st(&format!("t{i}"), 2, 10_000, 500))
+            .collect();
+        [("n1".to_string(), tasks)].into()
+    }
+
+    fn worst_response(schedule: &NodeSchedMap) -> Nanos {
+        simulate_schedule(schedule, DEFAULT_MAX_SIMULATED_JOBS)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.max_response)
+            .max()
+            .unwrap()
+    }
+
+    fn offsets(schedule: &NodeSchedMap) -> Vec<i32> {
+        schedule["n1"].iter().map(|t| t.release_time_us).collect()
+    }
+
+    #[test]
+    fn staggering_reduces_worst_response_of_a_burst() {
+        let burst = bursty();
+        let unstaggered = worst_response(&burst);
+        assert_eq!(unstaggered, Nanos(5_000_000));
+
+        let mut even = bursty();
+        stagger_releases(&mut even, StaggerStrategy::EvenWithinPeriod);
+        assert_eq!(
+            offsets(&even),
+            (0..10).map(|i| i * 1_000).collect::<Vec<_>>()
+        );
+        assert_eq!(worst_response(&even), Nanos(500_000));
+
+        let mut golden = bursty();
+        stagger_releases(&mut golden, StaggerStrategy::GoldenRatio);
+        assert!(worst_response(&golden) < unstaggered);
+    }
+
+    #[test]
+    fn offsets_stay_below_the_period_and_ignore_input_order() {
+        for strategy in [
+            StaggerStrategy::EvenWithinPeriod,
+            StaggerStrategy::GoldenRatio,
+        ] {
+            let mut a = bursty();
+            a.get_mut("n1").unwrap().push(st("slow", 2, 7_000, 100));
+            let mut b = a.clone();
+            b.get_mut("n1").unwrap().reverse();
+
+            stagger_releases(&mut a, strategy);
+            stagger_releases(&mut b, strategy);
+            for t in &a["n1"] {
+                assert!((t.release_time_us as u64) < t.period_ns.to_micros().as_u64());
+                let twin = b["n1"].iter().find(|u| u.name == t.name).unwrap();
+                assert_eq!(twin.release_time_us, t.release_time_us, "{strategy}");
+            }
+        }
+    }
+
+    #[test]
+    fn explicit_offsets_are_kept_and_cpus_are_independent() {
+        let mut schedule = bursty();
+        let tasks = schedule.get_mut("n1").unwrap();
+        tasks.truncate(3);
+        tasks[1].release_time_us = 1_234;
+        tasks.push(st("other_cpu", 3, 10_000, 500));
+
+        stagger_releases(&mut schedule, StaggerStrategy::EvenWithinPeriod);
+        // t0 and t2 split the period; t1 is untouched; cpu3 starts at zero.
+        assert_eq!(offsets(&schedule), [0, 1_234, 5_000, 0]);
+    }
+}
@@ -0,0 +1,297 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Utilisation threshold sweep: how placement density follows the
+//! threshold for one workload.
+//!
+//! [`GlobalScheduler::sweep_thresholds`] schedules the same tasks once per
+//! threshold, each time on an empty cluster, and records per threshold:
+//!
+//! | Field                  | Meaning                                              |
+//! |------------------------|------------------------------------------------------|
+//! | `tasks_placed`         | tasks placed, best effort (see below)                |
+//! | `whole`                | the workload was placed by one ordinary run          |
+//! | `nodes_used`           | nodes that received at least one task                |
+//! | `feasibility_warnings` | [`check_schedule`] warnings                          |
+//! | `peak_utilization`     | highest per-CPU utilisation in the cluster           |
+//! | `node_peaks`           | highest per-CPU utilisation of each node used        |
+//! | `unplaced`             | tasks left out, in submission order                  |
+//!
+//! Best effort: when the workload does not fit as a whole, its tasks are
+//! placed one at a time, in submission order, on the headroom the earlier
+//! ones left; a task that fits nowhere is skipped.  The options' algorithm
+//! (or chain) is used throughout.  Nothing is applied anywhere — this is
+//! the engine of `timpani-o sweep`, which renders a sweep with
+//! [`to_csv`] and [`chart`] or as JSON.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use super::feasibility::check_schedule;
+use super::{run_chain, validate_tasks, GlobalScheduler, ScheduleOptions, SchedulerError};
+use crate::task::{NodeSchedMap, Task};
+
+/// Width of a full bar in [`chart`].
+const CHART_WIDTH: usize = 40;
+
+/// Output format of `timpani-o sweep`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum SweepFormat {
+    /// One row per threshold (see [`to_csv`]).
+    #[default]
+    Csv,
+    /// The [`SweepPoint`]s as a JSON array.
+    Json,
+}
+
+/// The result at one threshold (see the module docs).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SweepPoint {
+    pub threshold: f64,
+    pub tasks_total: usize,
+    pub tasks_placed: usize,
+    pub whole: bool,
+    pub nodes_used: usize,
+    pub feasibility_warnings: usize,
+    pub peak_utilization: f64,
+    pub node_peaks: BTreeMap<String, f64>,
+    pub unplaced: Vec<String>,
+}
+
+/// `from`, `from + step`, … up to and including `to` (within float noise),
+/// rounded to 1e-6.  Empty unless `step` is positive and `from <= to`.
+pub fn threshold_steps(from: f64, to: f64, step: f64) -> Vec<f64> {
+    if !(step > 0.0 && from <= to) {
+        return Vec::new();
+    }
+    let count = ((to - from) / step + 1e-9).floor() as usize;
+    (0..=count)
+        .map(|i| ((from + i as f64 * step) * 1e6).round() / 1e6)
+        .collect()
+}
+
+impl GlobalScheduler {
+    /// Schedule `tasks` with `opts` at each of `thresholds` (see the module
+    /// docs).  Fails if the tasks have structural problems or a threshold
+    /// is not valid; placement failures only leave tasks unplaced.
+    pub fn sweep_thresholds(
+        &self,
+        tasks: &[Task],
+        opts: &ScheduleOptions,
+        thresholds: &[f64],
+    ) -> Result<Vec<SweepPoint>, SchedulerError> {
+        validate_tasks(
+            tasks,
+            opts.algorithm,
+            opts.limits.max_task_problems,
+            &self.limit_hits,
+        )
+        .map_err(SchedulerError::InvalidTasks)?;
+        thresholds
+            .iter()
+            .map(|&threshold| {
+                let opts = opts.clone().with_cpu_utilization_threshold(threshold);
+                opts.validate()?;
+                Ok(self.sweep_point(tasks, &opts))
+            })
+            .collect()
+    }
+
+    /// One [`SweepPoint`] at `opts`' threshold.
+    fn sweep_point(&self, tasks: &[Task], opts: &ScheduleOptions) -> SweepPoint {
+        let (schedule, unplaced, whole) = match self.place_whole(tasks, opts) {
+            Some(schedule) => (schedule, Vec::new(), true),
+            None => {
+                let (schedule, unplaced) = self.place_best_effort(tasks, opts);
+                (schedule, unplaced, false)
+            }
+        };
+        let node_peaks = Self::peak_utilization(&schedule);
+        SweepPoint {
+            threshold: opts.cpu_utilization_threshold,
+            tasks_total: tasks.len(),
+            tasks_placed: schedule.values().map(Vec::len).sum(),
+            whole,
+            nodes_used: schedule.values().filter(|t| !t.is_empty()).count(),
+            feasibility_warnings: check_schedule(
+                &schedule,
+                opts.utilization_epsilon,
+                opts.priority_ordering,
+            )
+            .len(),
+            peak_utilization: node_peaks.values().copied().fold(0.0, f64::max),
+            node_peaks,
+            unplaced,
+        }
+    }
+
+    /// The workload placed by one ordinary run, or `None`.
+    fn place_whole(&self, tasks: &[Task], opts: &ScheduleOptions) -> Option<NodeSchedMap> {
+        run_chain(opts, |link| {
+            self.schedule_with_options(tasks.to_vec(), link)
+        })
+        .result
+        .ok()
+        .map(|(schedule, _)| schedule)
+    }
+
+    /// Each task placed on its own around the earlier ones; returns the
+    /// schedule and the tasks that fit nowhere.
+    fn place_best_effort(
+        &self,
+        tasks: &[Task],
+        opts: &ScheduleOptions,
+    ) -> (NodeSchedMap, Vec<String>) {
+        let mut schedule = NodeSchedMap::new();
+        let mut unplaced = Vec::new();
+        for task in tasks {
+            let run = run_chain(opts, |link| {
+                self.schedule_with_occupancy(&schedule, vec![task.clone()], link)
+            });
+            match run.result {
+                Ok((added, _)) => {
+                    for (node, node_tasks) in added {
+                        schedule.entry(node).or_default().extend(node_tasks);
+                    }
+                }
+                Err(_) => unplaced.push(task.name.clone()),
+            }
+        }
+        (schedule, unplaced)
+    }
+}
+
+/// One CSV row per point, with a header; unplaced tasks are joined by `;`.
+pub fn to_csv(points: &[SweepPoint]) -> String {
+    let mut out = String::from(
+        "threshold,tasks_total,tasks_placed,whole,nodes_used,feasibility_warnings,\
+         peak_utilization,unplaced\n",
+    );
+    for p in points {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{:.4},{}",
+            p.threshold,
+            p.tasks_total,
+            p.tasks_placed,
+            p.whole,
+            p.nodes_used,
+            p.feasibility_warnings,
+            p.peak_utilization,
+            p.unplaced.join(";")
+        );
+    }
+    out
+}
+
+/// A bar of tasks placed per threshold, e.g.
+///
+/// ```text
+/// 0.70 ##############################.......... 6/8 tasks, 2 nodes, peak 66.7%
+/// ```
+pub fn chart(points: &[SweepPoint]) -> String {
+    let mut out = String::new();
+    for p in points {
+        let filled = (p.tasks_placed * CHART_WIDTH)
+            .checked_div(p.tasks_total)
+            .unwrap_or(0);
+        let _ = writeln!(
+            out,
+            "{:.2} {}{} {}/{} tasks, {} nodes, peak {:.1}%",
+            p.threshold,
+            "#".repeat(filled),
+            ".".repeat(CHART_WIDTH - filled),
+            p.tasks_placed,
+            p.tasks_total,
+            p.nodes_used,
+            p.peak_utilization * 100.0
+        );
+    }
+    out
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::config::NodeConfigManager;
+    use crate::scheduler::SchedAlgorithm;
+    use crate::testing::{fake_node, fake_task};
+
+    /// Two nodes of two CPUs, and eight tasks of 30 % each: 2 per CPU fit
+    /// from a 60 % threshold, 3 only above 90 %.
+    fn fixture() -> (GlobalScheduler, Vec<Task>) {
+        let config = NodeConfigManager::from_nodes(vec![
+            fake_node("node01", &[0, 1], 4096),
+            fake_node("node02", &[0, 1], 4096),
+        ]);
+        let tasks = (0..8)
+            .map(|i| fake_task(&format!("t{i}"), "", 10_000, 3_000))
+            .collect();
+        (GlobalScheduler::new(Arc::new(config)), tasks)
+    }
+
+    #[test]
+    fn tasks_placed_never_falls_as_the_threshold_rises() {
+        let (sched, tasks) = fixture();
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        let thresholds = threshold_steps(0.25, 0.95, 0.05);
+        assert_eq!(thresholds.len(), 15);
+        assert_eq!(thresholds[1], 0.3);
+
+        let points = sched.sweep_thresholds(&tasks, &opts, &thresholds).unwrap();
+        let placed: Vec<_> = points.iter().map(|p| p.tasks_placed).collect();
+        assert!(placed.windows(2).all(|w| w[0] <= w[1]), "{placed:?}");
+        assert_eq!((placed[0], placed[14]), (0, 8));
+
+        let at = |t: f64| points.iter().find(|p| p.threshold == t).unwrap();
+        let low = at(0.35);
+        assert_eq!((low.tasks_placed, low.whole, low.nodes_used), (4, false, 2));
+        assert_eq!(low.unplaced, ["t4", "t5", "t6", "t7"]);
+        assert_eq!(at(0.6).tasks_placed, 8);
+        assert!(at(0.6).whole);
+        assert!((at(0.6).peak_utilization - 0.6).abs() < 1e-9);
+        assert_eq!(at(0.6).node_peaks.len(), 2);
+
+        let csv = to_csv(&points);
+        assert_eq!(csv.lines().count(), 16);
+        assert!(
+            csv.contains("\n0.35,8,4,false,2,0,0.3000,t4;t5;t6;t7\n"),
+            "{csv}"
+        );
+        let chart = chart(&points);
+        assert!(
+            chart.contains(&format!(
+                "0.35 {}{} 4/8 tasks",
+                "#".repeat(20),
+                ".".repeat(20)
+            )),
+            "{chart}"
+        );
+    }
+
+    #[test]
+    fn invalid_input_fails_the_whole_sweep() {
+        let (sched, mut tasks) = fixture();
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        assert!(matches!(
+            sched.sweep_thresholds(&tasks, &opts, &[0.5, 1.5]),
+            Err(SchedulerError::InvalidThreshold(_))
+        ));
+        tasks[1].name = "t0".into();
+        assert!(matches!(
+            sched.sweep_thresholds(&tasks, &opts, &[0.5]),
+            Err(SchedulerError::InvalidTasks(_))
+        ));
+        assert!(threshold_steps(0.9, 0.7, 0.05).is_empty());
+        assert!(threshold_steps(0.7, 0.9, 0.0).is_empty());
+    }
+}
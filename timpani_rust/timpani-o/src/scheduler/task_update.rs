@@ -0,0 +1,321 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Minimal-change update of one placed task's timing.
+//!
+//! [`GlobalScheduler::update_task`] gives a task of a placed workload a new
+//! period, runtime and deadline, changing as little as it can.  It tries
+//! three levels in turn and stops at the first that succeeds:
+//!
+//! | Level                          | What may change                                |
+//! |--------------------------------|------------------------------------------------|
+//! | [`InPlace`](UpdateLevel::InPlace)         | nothing moves: the task stays on its CPU |
+//! | [`Moved`](UpdateLevel::Moved)             | the task moves; every other task stays   |
+//! | [`Rescheduled`](UpdateLevel::Rescheduled) | the whole workload is placed again       |
+//!
+//! The first level is a [warm start](super::warm_start) on the task's
+//! current CPU, so it checks exactly what a revision would: node admission
+//! (memory included), the CPU's utilisation threshold and its exclusivity.
+//! The second places the task alone with the options' algorithm, around
+//! everything else placed; the third places the workload's tasks around
+//! the other workloads only.  When the third fails too, the error is
+//! returned and nothing changes.
+
+use std::fmt;
+
+use super::{GlobalScheduler, PriorPlacement, ScheduleOptions, SchedulerError};
+use crate::task::{Micros, NodeSchedMap, Task};
+
+/// The timing an update gives a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskTiming {
+    pub period_us: Micros,
+    pub runtime_us: Micros,
+    pub deadline_us: Micros,
+}
+
+impl TaskTiming {
+    /// The timing `task` has now.
+    pub fn of(task: &Task) -> Self {
+        Self {
+            period_us: task.period_us,
+            runtime_us: task.runtime_us,
+            deadline_us: task.deadline_us,
+        }
+    }
+
+    /// `task` with this timing.
+    pub fn applied_to(&self, task: &Task) -> Task {
+        Task {
+            period_us: self.period_us,
+            runtime_us: self.runtime_us,
+            deadline_us: self.deadline_us,
+            ..task.clone()
+        }
+    }
+}
+
+/// How far an update had to go (see the module docs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UpdateLevel {
+    InPlace,
+    Moved,
+    Rescheduled,
+}
+
+impl UpdateLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InPlace => "in_place",
+            Self::Moved => "moved",
+            Self::Rescheduled => "rescheduled",
+        }
+    }
+}
+
+impl fmt::Display for UpdateLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The workload after an update.
+#[derive(Debug, Clone)]
+pub struct TaskUpdate {
+    pub level: UpdateLevel,
+    /// The workload's new schedule.  Below
+    /// [`Rescheduled`](UpdateLevel::Rescheduled) only the updated task's
+    /// entry differs from the old one.
+    pub schedule: NodeSchedMap,
+}
+
+impl GlobalScheduler {
+    /// Give `task`, one of the workload's `tasks` placed as in `placed`, its
+    /// new timing (see the module docs).  `others` are the other workloads'
+    /// placements; `task` is matched by name.
+    pub fn update_task(
+        &self,
+        others: &NodeSchedMap,
+        placed: &NodeSchedMap,
+        tasks: &[Task],
+        task: &Task,
+        opts: &ScheduleOptions,
+    ) -> Result<TaskUpdate, SchedulerError> {
+        let at = placed.iter().find_map(|(node, on_node)| {
+            let i = on_node.iter().position(|t| t.name == task.name)?;
+            Some((node.clone(), i, on_node[i].assigned_cpu))
+        });
+
+        // ── 1–2. The task alone, around everything else ───────────────────────
+        let mut rest = placed.clone();
+        let mut occupied = others.clone();
+        if let Some((node, i, _)) = &at {
+            rest.get_mut(node).expect("found above").remove(*i);
+        }
+        for (node, on_node) in &rest {
+            occupied
+                .entry(node.clone())
+                .or_default()
+                .extend(on_node.iter().cloned());
+        }
+        let alone = match &at {
+            Some((node, _, cpu)) => opts.clone().with_warm_start([(
+                task.name.clone(),
+                PriorPlacement {
+                    node: node.clone(),
+                    cpu: *cpu,
+                },
+            )]),
+            None => opts.clone(),
+        };
+        if let Ok(map) = self.schedule_with_occupancy(&occupied, vec![task.clone()], &alone) {
+            let (node, updated) = map
+                .into_iter()
+                .find_map(|(node, mut on_node)| Some((node, on_node.pop()?)))
+                .expect("a placed task is in the schedule");
+            let level = match &at {
+                Some((n, i, cpu)) if *n == node && *cpu == updated.assigned_cpu => {
+                    // Keep its position, so only its entry differs.
+                    rest.get_mut(n).expect("found above").insert(*i, updated);
+                    UpdateLevel::InPlace
+                }
+                _ => {
+                    rest.entry(node).or_default().push(updated);
+                    UpdateLevel::Moved
+                }
+            };
+            rest.retain(|_, on_node| !on_node.is_empty());
+            return Ok(TaskUpdate {
+                level,
+                schedule: rest,
+            });
+        }
+
+        // ── 3. The whole workload, around the other workloads ────────────────
+        let all = tasks
+            .iter()
+            .map(|t| {
+                if t.name == task.name {
+                    task.clone()
+                } else {
+                    t.clone()
+                }
+            })
+            .collect();
+        Ok(TaskUpdate {
+            level: UpdateLevel::Rescheduled,
+            schedule: self.schedule_with_occupancy(others, all, opts)?,
+        })
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::config::NodeConfigManager;
+    use crate::scheduler::SchedAlgorithm;
+    use crate::testing::{fake_node, fake_task};
+
+    fn scheduler() -> GlobalScheduler {
+        GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(vec![fake_node(
+            "node01",
+            &[0, 1],
+            4096,
+        )])))
+    }
+
+    fn opts() -> ScheduleOptions {
+        ScheduleOptions::default().with_algorithm(SchedAlgorithm::TargetNodePriority)
+    }
+
+    /// Where each task of `map` runs.
+    fn cpus(map: &NodeSchedMap) -> Vec<(&str, u32)> {
+        let mut cpus: Vec<_> = map
+            .values()
+            .flatten()
+            .map(|t| (t.name.as_str(), t.assigned_cpu))
+            .collect();
+        cpus.sort();
+        cpus
+    }
+
+    fn slower(task: &Task, runtime_us: u64) -> Task {
+        TaskTiming {
+            runtime_us: Micros(runtime_us),
+            ..TaskTiming::of(task)
+        }
+        .applied_to(task)
+    }
+
+    #[test]
+    fn task_that_still_fits_stays_on_its_cpu() {
+        let sched = scheduler();
+        let tasks = vec![
+            fake_task("a", "node01", 10_000, 4_000),
+            fake_task("b", "node01", 10_000, 4_000),
+        ];
+        let placed = sched.schedule_with_options(tasks.clone(), &opts()).unwrap();
+        let before = cpus(&placed);
+
+        let update = sched
+            .update_task(
+                &NodeSchedMap::new(),
+                &placed,
+                &tasks,
+                &slower(&tasks[0], 4_200),
+                &opts(),
+            )
+            .unwrap();
+        assert_eq!(update.level, UpdateLevel::InPlace);
+        assert_eq!(cpus(&update.schedule), before);
+        let a = &update.schedule["node01"]
+            .iter()
+            .find(|t| t.name == "a")
+            .unwrap();
+        assert_eq!(a.runtime_ns.to_micros(), Micros(4_200));
+        // Same position on the node, so a delta carries only this entry.
+        let names = |m: &NodeSchedMap| {
+            m["node01"]
+                .iter()
+                .map(|t| t.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&update.schedule), names(&placed));
+    }
+
+    #[test]
+    fn task_without_room_moves_and_nothing_else_does() {
+        let sched = scheduler();
+        let tasks = vec![
+            fake_task("a", "node01", 10_000, 4_000),
+            fake_task("b", "node01", 10_000, 4_000),
+            fake_task("c", "node01", 10_000, 3_000),
+        ];
+        let placed = sched.schedule_with_options(tasks.clone(), &opts()).unwrap();
+        assert_eq!(cpus(&placed), [("a", 1), ("b", 1), ("c", 0)]);
+
+        // 55 % beside b's 40 % is over the threshold; beside c's 30 % it is not.
+        let update = sched
+            .update_task(
+                &NodeSchedMap::new(),
+                &placed,
+                &tasks,
+                &slower(&tasks[0], 5_500),
+                &opts(),
+            )
+            .unwrap();
+        assert_eq!(update.level, UpdateLevel::Moved);
+        assert_eq!(cpus(&update.schedule), [("a", 0), ("b", 1), ("c", 0)]);
+    }
+
+    #[test]
+    fn task_fitting_nowhere_alone_reschedules_the_workload() {
+        let sched = scheduler();
+        let tasks = vec![
+            fake_task("a", "node01", 10_000, 3_000),
+            fake_task("b", "node01", 10_000, 3_000),
+            fake_task("c", "node01", 10_000, 5_000),
+        ];
+        let placed = sched.schedule_with_options(tasks.clone(), &opts()).unwrap();
+        assert_eq!(cpus(&placed), [("a", 1), ("b", 1), ("c", 0)]);
+
+        // 65 % of a fits beside neither b nor c as placed, but a fresh
+        // placement pairs b with c and gives a a CPU of its own.
+        let update = sched
+            .update_task(
+                &NodeSchedMap::new(),
+                &placed,
+                &tasks,
+                &slower(&tasks[0], 6_500),
+                &opts(),
+            )
+            .unwrap();
+        assert_eq!(update.level, UpdateLevel::Rescheduled);
+        let after = cpus(&update.schedule);
+        assert_eq!(after.len(), 3);
+        let cpu = |name| after.iter().find(|(n, _)| *n == name).unwrap().1;
+        assert_eq!(cpu("b"), cpu("c"));
+        assert_ne!(cpu("a"), cpu("b"));
+
+        // 95 % fits on no CPU, even alone: the update fails.
+        let err = sched
+            .update_task(
+                &NodeSchedMap::new(),
+                &placed,
+                &tasks,
+                &slower(&tasks[2], 9_500),
+                &opts(),
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, SchedulerError::AdmissionRejected { .. }),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Where a scheduling run spends its time.
+//!
+//! [`GlobalScheduler::schedule_profiled`](super::GlobalScheduler::schedule_profiled)
+//! times each phase of a run with [`Instant`]:
+//!
+//! | Phase             | Covers                                                    |
+//! |-------------------|-----------------------------------------------------------|
+//! | `preconditions`   | option and task validation, the aggregate capacity check  |
+//! | `available_cpus`  | CPU pool, node filters, utilisation seeded from occupancy |
+//! | `algorithm`       | pinned-CPU demand and the placement loop                  |
+//! | `feasibility`     | Liu & Layland, RTA and the simulation gate                |
+//! | `build_sched_map` | the output map and release staggering                     |
+//!
+//! On a large input `algorithm` should dominate; a bookkeeping phase that
+//! catches up with it has likely gone quadratic.
+//!
+//! # Allocation counts
+//!
+//! With the `alloc-stats` feature, each phase also counts heap allocations,
+//! read from a process-wide counter that only [`CountingAllocator`] feeds.
+//! A binary or test opts in by installing it:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOC: timpani_o::scheduler::timing::CountingAllocator =
+//!     timpani_o::scheduler::timing::CountingAllocator;
+//! ```
+//!
+//! Without the feature [`PhaseTimings::allocations`] is `None`; with it but
+//! without the allocator installed, every count is zero.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// ── Phase ─────────────────────────────────────────────────────────────────────
+
+/// A phase of a scheduling run (see the module docs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Preconditions,
+    AvailableCpus,
+    Algorithm,
+    Feasibility,
+    BuildSchedMap,
+}
+
+impl Phase {
+    /// Every phase, in run order.
+    pub const ALL: [Phase; 5] = [
+        Phase::Preconditions,
+        Phase::AvailableCpus,
+        Phase::Algorithm,
+        Phase::Feasibility,
+        Phase::BuildSchedMap,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Phase::Preconditions => "preconditions",
+            Phase::AvailableCpus => "available_cpus",
+            Phase::Algorithm => "algorithm",
+            Phase::Feasibility => "feasibility",
+            Phase::BuildSchedMap => "build_sched_map",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ── PhaseTimings ──────────────────────────────────────────────────────────────
+
+/// Wall-clock time (and, with `alloc-stats`, allocations) per phase of one
+/// run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    elapsed: [Duration; Phase::ALL.len()],
+    allocations: Option<[u64; Phase::ALL.len()]>,
+}
+
+impl PhaseTimings {
+    pub fn get(&self, phase: Phase) -> Duration {
+        self.elapsed[phase.index()]
+    }
+
+    /// Heap allocations during `phase`; `None` without `alloc-stats`.
+    pub fn allocations(&self, phase: Phase) -> Option<u64> {
+        self.allocations.map(|a| a[phase.index()])
+    }
+
+    /// The whole run.
+    pub fn total(&self) -> Duration {
+        self.elapsed.iter().sum()
+    }
+
+    /// The phase that took longest (the earliest on a tie).
+    pub fn dominant(&self) -> Phase {
+        Phase::ALL
+            .into_iter()
+            .rev()
+            .max_by_key(|&p| self.get(p))
+            .unwrap_or(Phase::Algorithm)
+    }
+
+    /// `(phase, time)` in run order.
+    pub fn iter(&self) -> impl Iterator<Item = (Phase, Duration)> + '_ {
+        Phase::ALL.into_iter().map(|p| (p, self.get(p)))
+    }
+}
+
+// ── PhaseClock ────────────────────────────────────────────────────────────────
+
+/// Accumulates [`PhaseTimings`] lap by lap.
+#[derive(Debug)]
+pub(crate) struct PhaseClock {
+    last: Instant,
+    last_allocations: u64,
+    timings: PhaseTimings,
+}
+
+impl PhaseClock {
+    pub(crate) fn start() -> Self {
+        Self {
+            last: Instant::now(),
+            last_allocations: allocation_count(),
+            timings: PhaseTimings {
+                allocations: cfg!(feature = "alloc-stats").then_some([0; Phase::ALL.len()]),
+                ..PhaseTimings::default()
+            },
+        }
+    }
+
+    /// Charge the time since the previous lap to `phase`.  A phase may be
+    /// lapped more than once; its laps add up.
+    pub(crate) fn lap(&mut self, phase: Phase) {
+        let now = Instant::now();
+        self.timings.elapsed[phase.index()] += now - self.last;
+        self.last = now;
+        if let Some(allocations) = &mut self.timings.allocations {
+            let count = allocation_count();
+            allocations[phase.index()] += count - self.last_allocations;
+            self.last_allocations = count;
+        }
+    }
+
+    pub(crate) fn finish(self) -> PhaseTimings {
+        self.timings
+    }
+}
+
+// ── Allocation counting ───────────────────────────────────────────────────────
+
+#[cfg(feature = "alloc-stats")]
+static ALLOCATIONS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// Allocations counted so far by [`CountingAllocator`].
+#[cfg(feature = "alloc-stats")]
+fn allocation_count() -> u64 {
+    ALLOCATIONS.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+#[cfg(not(feature = "alloc-stats"))]
+fn allocation_count() -> u64 {
+    0
+}
+
+/// The system allocator, counting every allocation for [`PhaseTimings`]
+/// (see the module docs).
+#[cfg(feature = "alloc-stats")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountingAllocator;
+
+#[cfg(feature = "alloc-stats")]
+// SAFETY: every call is forwarded unchanged to `System`.
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        std::alloc::System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        std::alloc::System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: std::alloc::Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        std::alloc::System.realloc(ptr, layout, new_size)
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::config::{NodeConfig, NodeConfigManager};
+    use crate::scheduler::{GlobalScheduler, SchedAlgorithm, ScheduleOptions};
+    use crate::task::{Micros, Task};
+
+    /// 32 nodes of 8 CPUs and 3000 tasks of 1 % each.
+    fn large_run() -> PhaseTimings {
+        let nodes = (0..32)
+            .map(|i| NodeConfig {
+                available_cpus: (0..8).collect(),
+                ..NodeConfig::default_config(format!("node{i:02}"))
+            })
+            .collect();
+        let scheduler = GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(nodes)));
+        let tasks = (0..3000)
+            .map(|i| Task {
+                name: format!("t{i:04}"),
+                workload_id: "bulk".into(),
+                period_us: Micros(10_000),
+                runtime_us: Micros(100),
+                deadline_us: Micros(10_000),
+                ..Default::default()
+            })
+            .collect();
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+        let (map, timings) = scheduler.schedule_profiled(None, tasks, &opts).unwrap();
+        assert_eq!(map.values().map(Vec::len).sum::<usize>(), 3000);
+        timings
+    }
+
+    #[test]
+    fn laps_accumulate_per_phase() {
+        let mut clock = PhaseClock::start();
+        std::thread::sleep(Duration::from_millis(2));
+        clock.lap(Phase::Feasibility);
+        clock.lap(Phase::Algorithm);
+        std::thread::sleep(Duration::from_millis(2));
+        clock.lap(Phase::Feasibility);
+        let timings = clock.finish();
+        assert!(timings.get(Phase::Feasibility) >= Duration::from_millis(4));
+        assert_eq!(timings.dominant(), Phase::Feasibility);
+        assert_eq!(timings.total(), timings.iter().map(|(_, d)| d).sum());
+        assert_eq!(PhaseTimings::default().dominant(), Phase::Preconditions);
+        if !cfg!(feature = "alloc-stats") {
+            assert_eq!(timings.allocations(Phase::Algorithm), None);
+        }
+    }
+
+    /// Catches a bookkeeping phase gone quadratic: on a large input the
+    /// placement loop must still take most of the run.  The bound is loose
+    /// enough for a loaded CI machine.
+    #[test]
+    fn algorithm_phase_dominates_a_large_run() {
+        let timings = large_run();
+        let algorithm = timings.get(Phase::Algorithm);
+        assert_eq!(timings.dominant(), Phase::Algorithm, "{timings:?}");
+        assert!(algorithm * 2 >= timings.total(), "{timings:?}");
+    }
+}
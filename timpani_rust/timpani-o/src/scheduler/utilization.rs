@@ -0,0 +1,316 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Exact per-CPU utilisation accounting.
+//!
+//! Admission compares `current + task ≤ threshold` once per candidate CPU.
+//! With `f64` the left-hand side depends on the order tasks were added
+//! (`0.3 + 0.3 + 0.3 > 0.9` in binary floating point), so a task that
+//! exactly fits could be accepted or rejected depending on input order.
+//!
+//! [`Utilization`] is a reduced fraction `num / den` of unsigned 128-bit
+//! integers.  Adding `runtime / period` terms keeps `den` at the LCM of the
+//! periods involved, so sums are exact and order-independent, and
+//! comparisons never round.  Thresholds are converted once with
+//! [`Utilization::from_f64`] at parts-per-billion resolution (`0.90` becomes
+//! exactly `9/10`).
+//!
+//! Reports convert back with [`Utilization::as_f64`] for display only.
+//!
+//! If the LCM of the periods ever overflows `u128` — which needs many large,
+//! pairwise coprime periods — the sum falls back to a fixed denominator of
+//! 10¹⁸, rounding each term **up** so admission stays conservative.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+/// Resolution of [`Utilization::from_f64`].
+const THRESHOLD_SCALE: u128 = 1_000_000_000;
+
+/// Denominator used when the exact one would overflow.
+const FALLBACK_DEN: u128 = 1_000_000_000_000_000_000;
+
+/// Exact CPU utilisation (see the [module docs](self)).
+#[derive(Debug, Clone, Copy)]
+pub struct Utilization {
+    /// Always reduced: `gcd(num, den) == 1` and `den > 0`.
+    num: u128,
+    den: u128,
+}
+
+impl Utilization {
+    pub const ZERO: Utilization = Utilization { num: 0, den: 1 };
+
+    /// `runtime / period`.  Zero when `period` is zero, like
+    /// [`Micros::ratio`](crate::task::Micros::ratio).
+    pub fn of(runtime: u64, period: u64) -> Self {
+        if period == 0 {
+            return Self::ZERO;
+        }
+        Self::reduced(runtime as u128, period as u128)
+    }
+
+    /// Whole CPUs.
+    pub fn cpus(n: usize) -> Self {
+        Self::reduced(n as u128, 1)
+    }
+
+    /// Nearest fraction with a denominator of 10⁹.  Negative and non-finite
+    /// inputs map to zero.
+    pub fn from_f64(value: f64) -> Self {
+        if !(value.is_finite() && value > 0.0) {
+            return Self::ZERO;
+        }
+        Self::reduced(
+            (value * THRESHOLD_SCALE as f64).round() as u128,
+            THRESHOLD_SCALE,
+        )
+    }
+
+    /// `self × n`, saturating on overflow.
+    pub fn times(self, n: usize) -> Self {
+        match self.num.checked_mul(n as u128) {
+            Some(num) => Self::reduced(num, self.den),
+            None => Self {
+                num: u128::MAX,
+                den: 1,
+            },
+        }
+    }
+
+    /// Whole CPUs needed to hold `self`: the value rounded up.
+    pub fn ceil_cpus(self) -> usize {
+        usize::try_from(self.num.div_ceil(self.den)).unwrap_or(usize::MAX)
+    }
+
+    /// Approximate value for logs and reports.
+    pub fn as_f64(self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    fn reduced(num: u128, den: u128) -> Self {
+        let g = gcd(num, den);
+        Self {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    /// `self` scaled to `den`, rounded up.
+    fn ceil_to(self, den: u128) -> u128 {
+        // num/self.den * den, split to avoid overflowing num * den.
+        let whole = self.num / self.den;
+        let rem = self.num % self.den;
+        let frac = mul_div_ceil(rem, den, self.den);
+        whole.saturating_mul(den).saturating_add(frac)
+    }
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        let den = lcm(self.den, rhs.den)?;
+        let lhs = self.num.checked_mul(den / self.den)?;
+        let rhs = rhs.num.checked_mul(den / rhs.den)?;
+        Some(Self::reduced(lhs.checked_add(rhs)?, den))
+    }
+}
+
+impl Default for Utilization {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Add for Utilization {
+    type Output = Utilization;
+
+    fn add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or_else(|| {
+            let num = self
+                .ceil_to(FALLBACK_DEN)
+                .saturating_add(rhs.ceil_to(FALLBACK_DEN));
+            Self::reduced(num, FALLBACK_DEN)
+        })
+    }
+}
+
+impl AddAssign for Utilization {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for Utilization {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Ord for Utilization {
+    /// Exact comparison by continued-fraction expansion — no products, so
+    /// no overflow.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut a, mut b) = ((self.num, self.den), (other.num, other.den));
+        let mut flipped = false;
+        loop {
+            let (qa, ra) = (a.0 / a.1, a.0 % a.1);
+            let (qb, rb) = (b.0 / b.1, b.0 % b.1);
+            let ord = match (qa.cmp(&qb), ra, rb) {
+                (Ordering::Equal, 0, 0) => Ordering::Equal,
+                (Ordering::Equal, 0, _) => Ordering::Less,
+                (Ordering::Equal, _, 0) => Ordering::Greater,
+                (Ordering::Equal, _, _) => {
+                    // ra/a.1 vs rb/b.1  ⇔  reversed  a.1/ra vs b.1/rb
+                    a = (a.1, ra);
+                    b = (b.1, rb);
+                    flipped = !flipped;
+                    continue;
+                }
+                (ord, _, _) => ord,
+            };
+            return if flipped { ord.reverse() } else { ord };
+        }
+    }
+}
+
+impl PartialOrd for Utilization {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Utilization {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Utilization {}
+
+impl fmt::Display for Utilization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1}%", self.as_f64() * 100.0)
+    }
+}
+
+// ── Integer helpers ───────────────────────────────────────────────────────────
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
+
+fn lcm(a: u128, b: u128) -> Option<u128> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// `ceil(a * b / c)` for `a < c`, without overflowing `a * b`.
+fn mul_div_ceil(a: u128, b: u128, c: u128) -> u128 {
+    if let Some(p) = a.checked_mul(b) {
+        return p.div_ceil(c);
+    }
+    // Long multiplication one bit of `b` at a time; `acc` stays below `c`.
+    let (mut quot, mut acc) = (0u128, 0u128);
+    for bit in (0..128).rev() {
+        // acc = 2 * acc (mod c), quot = 2 * quot
+        quot = quot.saturating_mul(2);
+        if acc >= c - acc {
+            acc -= c - acc;
+            quot = quot.saturating_add(1);
+        } else {
+            acc *= 2;
+        }
+        if (b >> bit) & 1 == 1 {
+            // acc += a (mod c)
+            if acc >= c - a {
+                acc -= c - a;
+                quot = quot.saturating_add(1);
+            } else {
+                acc += a;
+            }
+        }
+    }
+    quot + u128::from(acc > 0)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::spread::SplitMix64;
+
+    #[test]
+    fn three_thirty_percent_tasks_exactly_fill_ninety() {
+        let t = Utilization::of(3_000, 10_000);
+        let sum = t + t + t;
+        assert_eq!(sum, Utilization::from_f64(0.90));
+        assert!(sum <= Utilization::from_f64(0.90));
+        // The float sum is what used to go wrong.
+        assert_ne!(0.3_f64 + 0.3 + 0.3, 0.9);
+    }
+
+    #[test]
+    fn mixed_periods_sum_exactly() {
+        // 1/3 + 1/6 + 1/2 = 1
+        let sum: Utilization = [(1, 3), (1, 6), (1, 2)]
+            .into_iter()
+            .map(|(r, p)| Utilization::of(r, p))
+            .sum();
+        assert_eq!(sum, Utilization::cpus(1));
+        assert_eq!(Utilization::of(5, 0), Utilization::ZERO);
+        assert_eq!(Utilization::of(3, 10).times(3), Utilization::from_f64(0.9));
+    }
+
+    #[test]
+    fn ordering_matches_cross_multiplication() {
+        let mut rng = SplitMix64::new(1913);
+        for _ in 0..1_000 {
+            let (a, b, c, d) = (
+                rng.below(1_000) as u64,
+                rng.below(1_000) as u64 + 1,
+                rng.below(1_000) as u64,
+                rng.below(1_000) as u64 + 1,
+            );
+            let expected = (a as u128 * d as u128).cmp(&(c as u128 * b as u128));
+            assert_eq!(
+                Utilization::of(a, b).cmp(&Utilization::of(c, d)),
+                expected,
+                "{a}/{b} vs {c}/{d}"
+            );
+        }
+    }
+
+    #[test]
+    fn sums_are_order_independent() {
+        let mut rng = SplitMix64::new(7);
+        let mut terms: Vec<Utilization> = (0..20)
+            .map(|_| {
+                let period = [1_000, 2_500, 3_000, 7_000, 10_000][rng.below(5)];
+                Utilization::of(rng.below(period as usize / 10) as u64, period)
+            })
+            .collect();
+        let reference: Utilization = terms.iter().copied().sum();
+        for _ in 0..50 {
+            rng.shuffle(&mut terms);
+            assert_eq!(terms.iter().copied().sum::<Utilization>(), reference);
+        }
+    }
+
+    #[test]
+    fn overflowing_denominators_round_up() {
+        // Two coprime near-u64::MAX periods, then a third: the LCM overflows.
+        let big = [u64::MAX, u64::MAX - 2, u64::MAX - 4];
+        let exact_sum: Utilization = big.iter().map(|&p| Utilization::of(1, p)).sum();
+        assert!(exact_sum > Utilization::ZERO);
+        assert!(exact_sum < Utilization::from_f64(1e-9));
+        assert_eq!(
+            mul_div_ceil(u128::MAX - 1, u128::MAX - 1, u128::MAX),
+            u128::MAX - 1
+        );
+    }
+}
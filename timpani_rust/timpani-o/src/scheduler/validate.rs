@@ -0,0 +1,264 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Batch task validation: every structural problem of a workload at once.
+//!
+//! [`validate_tasks`] runs before any placement work, at the start of every
+//! `schedule*` call and in the gRPC request validator, and reports every
+//! [`TaskProblem`] it finds rather than the first:
+//!
+//! | Problem                                  | When                        |
+//! |------------------------------------------|-----------------------------|
+//! | `MissingWorkloadId` / `MissingTargetNode` | `target_node_priority` only |
+//! | `InvalidWcetScaling`                     | always                      |
+//! | `Invalid` (a field outside its range)    | always                      |
+//! | `DuplicateName`                          | always                      |
+//!
+//! Problems are listed in task order, each task's in the order of the
+//! table.  A duplicated name is reported once, at its second occurrence.
+//! Only the first `max_problems` (see
+//! [`InputLimits::max_task_problems`](crate::limits::InputLimits)) are
+//! listed; the rest are counted in [`TaskProblems::omitted`].
+//!
+//! Checks that depend on the cluster or the options (pinned CPUs, allowed
+//! nodes, a task needing more than one CPU) are not structural and stay
+//! where they were.
+
+use std::collections::BTreeMap;
+
+use super::{SchedAlgorithm, TaskProblem, TaskProblems};
+use crate::limits::{Limit, LimitHits};
+use crate::task::Task;
+
+/// Every structural problem of `tasks` for `algorithm`, at most
+/// `max_problems` of them listed (see the [module docs](self)); `Ok` when
+/// there is none.  Leaving problems out counts as a hit in `hits`.
+pub fn validate_tasks(
+    tasks: &[Task],
+    algorithm: SchedAlgorithm,
+    max_problems: usize,
+    hits: &LimitHits,
+) -> Result<(), TaskProblems> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for task in tasks {
+        *counts.entry(task.name.as_str()).or_default() += 1;
+    }
+
+    let mut seen: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut problems = TaskProblems::default();
+    let mut push = |problem| {
+        if problems.problems.len() < max_problems {
+            problems.problems.push(problem);
+        } else {
+            problems.omitted += 1;
+        }
+    };
+    for task in tasks {
+        let task_name = || task.name.clone();
+        if algorithm == SchedAlgorithm::TargetNodePriority {
+            if task.workload_id.is_empty() {
+                push(TaskProblem::MissingWorkloadId { task: task_name() });
+            }
+            if task.target_node.is_empty() {
+                push(TaskProblem::MissingTargetNode { task: task_name() });
+            }
+        }
+        if let Some((arch, factor)) = task.invalid_wcet_scaling() {
+            push(TaskProblem::InvalidWcetScaling {
+                task: task_name(),
+                architecture: arch.to_string(),
+                factor,
+            });
+        }
+        if let Err(e) = task.check_ranges() {
+            push(TaskProblem::Invalid(e));
+        }
+        let occurrence = seen.entry(task.name.as_str()).or_default();
+        *occurrence += 1;
+        if *occurrence == 2 {
+            push(TaskProblem::DuplicateName {
+                task: task_name(),
+                count: counts[task.name.as_str()],
+            });
+        }
+    }
+
+    if problems.total() == 0 {
+        return Ok(());
+    }
+    // Counts the hit when some were left out.
+    hits.exceeded(Limit::TaskProblems, problems.total(), max_problems);
+    Err(problems)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::config::NodeConfigManager;
+    use crate::limits::{self, InputLimits};
+    use crate::scheduler::{ErrorCode, GlobalScheduler, ScheduleOptions, SchedulerError};
+    use crate::task::{RangedField, SchedTaskConversionError};
+    use crate::testing::{fake_node, fake_task};
+
+    /// Four tasks, four different problems, plus one good task.
+    fn broken_workload() -> Vec<Task> {
+        let no_target = fake_task("no_target", "", 10_000, 1_000);
+        let mut bad_scaling = fake_task("bad_scaling", "node01", 10_000, 1_000);
+        bad_scaling.wcet_scaling.insert("aarch64".into(), 0.0);
+        let mut bad_priority = fake_task("bad_priority", "node01", 10_000, 1_000);
+        bad_priority.priority = 150;
+        vec![
+            no_target,
+            bad_scaling,
+            fake_task("twin", "node01", 20_000, 1_000),
+            bad_priority,
+            fake_task("twin", "node01", 20_000, 1_000),
+            fake_task("fine", "node01", 20_000, 1_000),
+        ]
+    }
+
+    #[test]
+    fn every_problem_is_reported_with_its_task() {
+        let max = limits::DEFAULT_MAX_TASK_PROBLEMS;
+        let problems = validate_tasks(
+            &broken_workload(),
+            SchedAlgorithm::TargetNodePriority,
+            max,
+            &LimitHits::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            problems
+                .iter()
+                .map(|p| (p.task(), p.code()))
+                .collect::<Vec<_>>(),
+            [
+                ("no_target", ErrorCode::MissingTargetNode),
+                ("bad_scaling", ErrorCode::InvalidWcetScaling),
+                ("bad_priority", ErrorCode::InvalidTask),
+                ("twin", ErrorCode::DuplicateTaskName),
+            ]
+        );
+        assert_eq!(
+            problems[2],
+            TaskProblem::Invalid(SchedTaskConversionError::OutOfRange {
+                task: "bad_priority".into(),
+                field: RangedField::Priority,
+                value: 150,
+            })
+        );
+        assert_eq!(
+            problems[3],
+            TaskProblem::DuplicateName {
+                task: "twin".into(),
+                count: 2,
+            }
+        );
+
+        // Only target_node_priority needs a target node.
+        let problems = validate_tasks(
+            &broken_workload(),
+            SchedAlgorithm::LeastLoaded,
+            max,
+            &LimitHits::default(),
+        )
+        .unwrap_err();
+        assert_eq!(problems.len(), 3);
+        assert!(problems.iter().all(|p| p.task() != "no_target"));
+        assert_eq!(
+            validate_tasks(
+                &broken_workload()[5..],
+                SchedAlgorithm::TargetNodePriority,
+                max,
+                &LimitHits::default(),
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn schedule_fails_with_all_problems_before_placing_anything() {
+        let sched = GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(vec![fake_node(
+            "node01",
+            &[0, 1],
+            4096,
+        )])));
+        let err = sched
+            .schedule(broken_workload(), "target_node_priority")
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidTasks);
+        assert_eq!(err.task(), Some("no_target"));
+        let message = err.to_string();
+        assert!(message.starts_with("4 task problem(s): "), "{message}");
+        for name in ["no_target", "bad_scaling", "bad_priority", "twin"] {
+            assert!(message.contains(&format!("'{name}'")), "{message}");
+        }
+        let SchedulerError::InvalidTasks(problems) = err else {
+            panic!("expected InvalidTasks");
+        };
+        assert_eq!(problems.len(), 4);
+    }
+
+    #[test]
+    fn problems_past_the_cap_are_counted_not_listed() {
+        let max = 10;
+        let tasks: Vec<Task> = (0..max + 50)
+            .map(|i| Task {
+                priority: 150,
+                ..fake_task(&format!("t{i}"), "node01", 10_000, 1_000)
+            })
+            .collect();
+        let hits = LimitHits::default();
+        let problems = validate_tasks(&tasks, SchedAlgorithm::LeastLoaded, max, &hits).unwrap_err();
+        assert_eq!((problems.len(), problems.omitted), (max, 50));
+        assert!(problems.is_truncated());
+        assert_eq!(problems.last().unwrap().task(), format!("t{}", max - 1));
+        assert_eq!(hits.get(Limit::TaskProblems), 1);
+
+        let message = SchedulerError::InvalidTasks(problems).to_string();
+        assert!(message.starts_with(&format!("{} task problem(s): ", max + 50)));
+        assert!(message.ends_with("; and 50 more"), "{message}");
+
+        // Within the cap nothing is left out, so nothing is counted.
+        let problems =
+            validate_tasks(&tasks[..max], SchedAlgorithm::LeastLoaded, max, &hits).unwrap_err();
+        assert!(!problems.is_truncated());
+        assert_eq!(hits.get(Limit::TaskProblems), 1);
+    }
+
+    #[test]
+    fn a_workload_over_the_task_cap_is_refused_before_validation() {
+        let sched = GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(vec![fake_node(
+            "node01",
+            &[0, 1],
+            4096,
+        )])));
+        let max = limits::DEFAULT_MAX_TASKS;
+        // Every task is broken too; the count is what fails.
+        let tasks = vec![fake_task("same", "node01", 10_000, 1_000); max + 1];
+        let err = sched.schedule(tasks, "least_loaded").unwrap_err();
+        assert_eq!(err.code(), ErrorCode::TooManyTasks);
+
+        // The cap comes with the options.
+        let max = 8;
+        let opts = ScheduleOptions::default()
+            .with_algorithm(SchedAlgorithm::LeastLoaded)
+            .with_limits(InputLimits::default().with_max_tasks(max));
+        let tasks = vec![fake_task("same", "node01", 10_000, 1_000); max + 1];
+        let err = sched.schedule_with_options(tasks, &opts).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::TooManyTasks);
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "{} tasks in one workload, more than the limit of {max}",
+                max + 1
+            )
+        );
+    }
+}
@@ -0,0 +1,215 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Warm start: keep a task where an earlier run placed it.
+//!
+//! [`ScheduleOptions::warm_start`] maps task names to a [`PriorPlacement`].
+//! Before the algorithm runs, each named task goes back to its node and CPU
+//! when all of these still hold:
+//!
+//! - the node admits the task ([`admission::node_verdict`]);
+//! - the CPU is still available there and the task's affinity allows it;
+//! - the CPU stays under the utilisation threshold with the task on it.
+//!
+//! A task that fails any of them, and every task without an entry, is
+//! placed by the algorithm as usual.  The service uses this when a workload
+//! revision turns a placeholder into the real task (see
+//! [`Task::placeholder`]), so the capacity it reserved is the capacity the
+//! task gets.
+
+use tracing::debug;
+
+use super::{
+    AvailCpus, CpuUtil, GlobalScheduler, PinnedDemand, PlacementLog, ScheduleOptions,
+    SchedulerError,
+};
+use crate::admission;
+use crate::task::{CpuAffinity, Task};
+
+/// Where an earlier run placed a task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorPlacement {
+    pub node: String,
+    pub cpu: u32,
+}
+
+impl GlobalScheduler {
+    /// Put every task with a warm-start entry that still fits back on its
+    /// prior CPU.  Returns whether each task was placed; fails like
+    /// `assign_cpu_to_task`.
+    pub(super) fn place_warm_started(
+        &self,
+        tasks: &mut [Task],
+        avail: &AvailCpus,
+        util: &mut CpuUtil,
+        pinned: &mut PinnedDemand,
+        opts: &ScheduleOptions,
+        log: &mut PlacementLog,
+    ) -> Result<Vec<bool>, SchedulerError> {
+        let limit = super::Utilization::from_f64(opts.effective_threshold());
+        let mut placed = vec![false; tasks.len()];
+        for (task, placed) in tasks.iter_mut().zip(&mut placed) {
+            let Some(prior) = opts.warm_start.get(&task.name) else {
+                continue;
+            };
+            let allowed = match task.affinity {
+                CpuAffinity::Pinned(mask) => prior.cpu < 64 && mask & (1 << prior.cpu) != 0,
+                _ => true,
+            };
+            let fits = allowed
+                && avail
+                    .get(&prior.node)
+                    .is_some_and(|cpus| cpus.contains(&prior.cpu))
+                && log
+                    .workloads()
+                    .conflict(&prior.node, prior.cpu, task)
+                    .is_none()
+                && admission::node_verdict(
+                    &self.node_config_manager,
+                    task,
+                    &prior.node,
+                    avail,
+                    util,
+                    log.workloads(),
+                    opts,
+                )
+                .is_ok()
+                && Self::calculate_cpu_utilization(util, &prior.node, prior.cpu)
+                    + task.exact_utilization_on(self.architecture(&prior.node))
+                    <= limit;
+            if !fits {
+                debug!(
+                    task = %task.name,
+                    node = %prior.node,
+                    cpu  = prior.cpu,
+                    "warm start: prior placement no longer fits"
+                );
+                continue;
+            }
+            self.assign_cpu_to_task(task, &prior.node, prior.cpu, util, pinned, opts)?;
+            log.record(task, &prior.node, prior.cpu);
+            *placed = true;
+        }
+        Ok(placed)
+    }
+}
+
+/// Run `place` over the tasks `placed` marks false, in their order, and
+/// put `tasks` back in its original order afterwards.
+pub(super) fn place_rest<E>(
+    tasks: &mut Vec<Task>,
+    placed: &[bool],
+    place: impl FnOnce(&mut [Task]) -> Result<(), E>,
+) -> Result<(), E> {
+    if !placed.contains(&true) {
+        return place(tasks);
+    }
+    let (mut done, mut rest): (Vec<_>, Vec<_>) = std::mem::take(tasks)
+        .into_iter()
+        .enumerate()
+        .partition(|(i, _)| placed[*i]);
+    let (order, mut remaining): (Vec<usize>, Vec<Task>) = rest.drain(..).unzip();
+    let result = place(&mut remaining);
+    done.extend(order.into_iter().zip(remaining));
+    done.sort_by_key(|(i, _)| *i);
+    *tasks = done.into_iter().map(|(_, t)| t).collect();
+    result
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::config::{NodeConfig, NodeConfigManager};
+    use crate::scheduler::SchedAlgorithm;
+    use crate::task::{Micros, NodeSchedMap};
+
+    fn scheduler() -> GlobalScheduler {
+        let nodes = ["n1", "n2"]
+            .map(|n| NodeConfig {
+                available_cpus: vec![0, 1],
+                ..NodeConfig::default_config(n)
+            })
+            .into();
+        GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(nodes)))
+    }
+
+    fn task(name: &str, runtime: u64) -> Task {
+        Task {
+            name: name.into(),
+            workload_id: "wl".into(),
+            period_us: Micros(10_000),
+            runtime_us: Micros(runtime),
+            deadline_us: Micros(10_000),
+            ..Default::default()
+        }
+    }
+
+    fn placement(map: &NodeSchedMap, name: &str) -> (String, u32) {
+        map.values()
+            .flatten()
+            .find(|t| t.name == name)
+            .map(|t| (t.assigned_node.clone(), t.assigned_cpu))
+            .unwrap()
+    }
+
+    #[test]
+    fn placeholder_converted_to_a_real_task_keeps_its_placement() {
+        let s = scheduler();
+        let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+
+        // Without a hint least_loaded puts the only task on n1.
+        let reservation = Task {
+            placeholder: true,
+            ..task("cam", 4_000)
+        };
+        let map = s.schedule_with_options(vec![reservation], &opts).unwrap();
+        assert_eq!(placement(&map, "cam"), ("n1".into(), 1));
+        assert!(map["n1"][0].placeholder);
+
+        // The conversion, from a prior run that put it on n2 cpu0.
+        let warm = opts.clone().with_warm_start([(
+            "cam",
+            PriorPlacement {
+                node: "n2".into(),
+                cpu: 0,
+            },
+        )]);
+        let tasks = vec![task("a", 1_000), task("cam", 4_000), task("b", 1_000)];
+        let map = s.schedule_with_options(tasks.clone(), &warm).unwrap();
+        assert_eq!(placement(&map, "cam"), ("n2".into(), 0));
+        assert!(!map["n2"].iter().any(|t| t.placeholder));
+
+        // Once cpu0 on n2 is taken by another tenant, the algorithm decides.
+        let other = Task {
+            target_node: "n2".into(),
+            affinity: CpuAffinity::Pinned(1),
+            ..task("other", 6_000)
+        };
+        let occupied = s
+            .schedule_with_options(vec![other], &ScheduleOptions::default())
+            .unwrap();
+        assert_eq!(placement(&occupied, "other"), ("n2".into(), 0));
+        let (map, _) = s.schedule_profiled(Some(&occupied), tasks, &warm).unwrap();
+        assert_ne!(placement(&map, "cam"), ("n2".into(), 0));
+    }
+
+    #[test]
+    fn place_rest_keeps_task_order() {
+        let mut tasks = vec![task("a", 1), task("b", 1), task("c", 1)];
+        let mut seen = Vec::new();
+        place_rest(&mut tasks, &[false, true, false], |rest| {
+            seen.extend(rest.iter().map(|t| t.name.clone()));
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+        assert_eq!(seen, ["a", "c"]);
+        let names: Vec<_> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+    }
+}
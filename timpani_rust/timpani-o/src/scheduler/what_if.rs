@@ -0,0 +1,336 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! What-if analysis for losing a node.
+//!
+//! [`GlobalScheduler::what_if_node_loss`] answers "could the cluster absorb
+//! this node going away?" without changing anything:
+//!
+//! | Section    | Contents                                                        |
+//! |------------|-----------------------------------------------------------------|
+//! | `moved`    | tasks of the node that fit elsewhere, and where                 |
+//! | `unplaced` | tasks that fit nowhere, with every other node's admission reason |
+//! | `peaks`    | per-node peak CPU utilisation, before and after                 |
+//!
+//! The node's tasks are re-placed one at a time, in the order given (the
+//! service uses the drain order), with `least_loaded` on the headroom the
+//! other placements leave.  Unlike [impact analysis](super::impact), the
+//! caller supplies each task's full spec, so affinity and a target node
+//! elsewhere are honoured.  A task with a hard `target_node` on the lost
+//! node cannot move and is reported unplaced without probing.
+
+use std::collections::BTreeMap;
+
+use super::{AdmissionReason, CpuUtil, GlobalScheduler, SchedAlgorithm, ScheduleOptions};
+use crate::task::{NodeSchedMap, TargetNodePolicy, Task};
+
+/// A task placed on the node under analysis.
+#[derive(Debug, Clone)]
+pub struct LostTask {
+    pub tenant: String,
+    /// The task's spec, as submitted.
+    pub task: Task,
+    /// The CPU it occupies on the node.
+    pub cpu: u32,
+}
+
+/// A lost task that fits elsewhere.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhatIfMove {
+    pub tenant: String,
+    pub task: String,
+    pub from_cpu: u32,
+    pub to_node: String,
+    pub to_cpu: u32,
+}
+
+/// Why one node would not admit a task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeRejection {
+    pub node: String,
+    pub reason: AdmissionReason,
+}
+
+/// A lost task that fits nowhere.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnplacedTask {
+    pub tenant: String,
+    pub task: String,
+    /// The scheduler's error for the re-placement.
+    pub reason: String,
+    /// Each remaining node's admission reason, sorted by node.  Empty for a
+    /// task hard-pinned to the lost node.
+    pub rejections: Vec<NodeRejection>,
+}
+
+/// Highest per-CPU utilisation on one node (1.0 = one full CPU).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeakUtilization {
+    pub node: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+/// Result of [`GlobalScheduler::what_if_node_loss`] (see the module docs).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WhatIfReport {
+    pub node: String,
+    /// In placement order.
+    pub moved: Vec<WhatIfMove>,
+    /// In placement order.
+    pub unplaced: Vec<UnplacedTask>,
+    /// Every configured node, sorted; the lost node ends at zero.
+    pub peaks: Vec<PeakUtilization>,
+}
+
+impl WhatIfReport {
+    /// `true` when every task of the node fits elsewhere.
+    pub fn survivable(&self) -> bool {
+        self.unplaced.is_empty()
+    }
+}
+
+impl GlobalScheduler {
+    /// What losing `node` would do: re-place `lost` (the tasks `placements`
+    /// has on it) on the other configured nodes around the rest of
+    /// `placements`.  Nothing is applied.
+    pub fn what_if_node_loss(
+        &self,
+        node: &str,
+        placements: &NodeSchedMap,
+        lost: Vec<LostTask>,
+        opts: &ScheduleOptions,
+    ) -> WhatIfReport {
+        let others: Vec<String> = self.node_ids().into_iter().filter(|n| n != node).collect();
+        let opts = opts
+            .clone()
+            .with_algorithm(SchedAlgorithm::LeastLoaded)
+            .with_allowed_nodes(others.iter().cloned());
+        let probe_opts = opts
+            .clone()
+            .with_algorithm(SchedAlgorithm::TargetNodePriority);
+
+        let mut occupied = placements.clone();
+        occupied.remove(node);
+        let mut moved = Vec::new();
+        let mut unplaced = Vec::new();
+        for LostTask {
+            tenant,
+            mut task,
+            cpu,
+        } in lost
+        {
+            if task.target_node == node {
+                if task.target_node_policy == Some(TargetNodePolicy::Hard) {
+                    unplaced.push(UnplacedTask {
+                        tenant,
+                        reason: format!("task '{}' has a hard target_node on {node}", task.name),
+                        task: task.name,
+                        rejections: Vec::new(),
+                    });
+                    continue;
+                }
+                task.target_node.clear();
+            }
+
+            match self.schedule_with_occupancy(&occupied, vec![task.clone()], &opts) {
+                Ok(placed) => {
+                    for (to_node, tasks) in placed {
+                        moved.extend(tasks.iter().map(|t| WhatIfMove {
+                            tenant: tenant.clone(),
+                            task: t.name.clone(),
+                            from_cpu: cpu,
+                            to_node: to_node.clone(),
+                            to_cpu: t.assigned_cpu,
+                        }));
+                        occupied.entry(to_node).or_default().extend(tasks);
+                    }
+                }
+                Err(e) => {
+                    // Ask each node in turn, as a hard target, for its reason.
+                    let rejections = others
+                        .iter()
+                        .filter_map(|n| {
+                            let probe = Task {
+                                target_node: n.clone(),
+                                target_node_policy: Some(TargetNodePolicy::Hard),
+                                ..task.clone()
+                            };
+                            let err = self
+                                .schedule_with_occupancy(&occupied, vec![probe], &probe_opts)
+                                .err()?;
+                            Some(NodeRejection {
+                                node: n.clone(),
+                                reason: err.reason()?.clone(),
+                            })
+                        })
+                        .collect();
+                    unplaced.push(UnplacedTask {
+                        tenant,
+                        task: task.name,
+                        reason: e.to_string(),
+                        rejections,
+                    });
+                }
+            }
+        }
+
+        let before = Self::peak_utilization(placements);
+        let after = Self::peak_utilization(&occupied);
+        let peaks = self
+            .node_ids()
+            .into_iter()
+            .map(|n| PeakUtilization {
+                before: before.get(&n).copied().unwrap_or_default(),
+                after: after.get(&n).copied().unwrap_or_default(),
+                node: n,
+            })
+            .collect();
+
+        WhatIfReport {
+            node: node.to_string(),
+            moved,
+            unplaced,
+            peaks,
+        }
+    }
+
+    /// Highest per-CPU utilisation of each node in `schedule`.
+    pub(super) fn peak_utilization(schedule: &NodeSchedMap) -> BTreeMap<String, f64> {
+        let mut util = CpuUtil::new();
+        Self::seed_cpu_utilization(&mut util, schedule);
+        util.into_iter()
+            .map(|(node, cpus)| {
+                let peak = cpus.values().map(|u| u.as_f64()).fold(0.0, f64::max);
+                (node, peak)
+            })
+            .collect()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::codec;
+    use crate::config::NodeConfigManager;
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    /// The impact fixtures: front01, rear01 and control01 with two CPUs
+    /// each and the placements of `impact_state.json`.
+    fn cluster() -> (GlobalScheduler, NodeSchedMap) {
+        let mut mgr = NodeConfigManager::new();
+        mgr.load_from_file(&fixture("impact_current.yaml")).unwrap();
+        let state = codec::load(&fixture("impact_state.json")).unwrap();
+        (GlobalScheduler::new(Arc::new(mgr)), state)
+    }
+
+    fn lost_on(state: &NodeSchedMap, node: &str) -> Vec<LostTask> {
+        state[node]
+            .iter()
+            .map(|t| LostTask {
+                tenant: t.workload_id.clone(),
+                task: Task {
+                    workload_id: t.workload_id.clone(),
+                    ..GlobalScheduler::replacement_task(t)
+                },
+                cpu: t.assigned_cpu,
+            })
+            .collect()
+    }
+
+    fn peak(report: &WhatIfReport, node: &str) -> (f64, f64) {
+        let p = report.peaks.iter().find(|p| p.node == node).unwrap();
+        (p.before, p.after)
+    }
+
+    #[test]
+    fn losing_rear01_is_survivable() {
+        let (scheduler, state) = cluster();
+        let before = state.clone();
+        let report = scheduler.what_if_node_loss(
+            "rear01",
+            &state,
+            lost_on(&state, "rear01"),
+            &ScheduleOptions::default(),
+        );
+
+        assert!(report.survivable(), "{report:?}");
+        // control01 runs 30 % on each CPU: both parking tasks fit there.
+        let moved: Vec<_> = report
+            .moved
+            .iter()
+            .map(|m| (m.task.as_str(), m.to_node.as_str()))
+            .collect();
+        assert_eq!(
+            moved,
+            [("park_assist", "control01"), ("rear_cam", "control01")]
+        );
+        let (control_before, control_after) = peak(&report, "control01");
+        assert!((control_before - 0.30).abs() < 1e-9);
+        assert!((control_after - 0.90).abs() < 1e-9);
+        assert_eq!(peak(&report, "rear01").1, 0.0);
+        assert_eq!(state, before, "the placements are not touched");
+    }
+
+    #[test]
+    fn losing_front01_strands_lidar_with_per_node_reasons() {
+        let (scheduler, state) = cluster();
+        let report = scheduler.what_if_node_loss(
+            "front01",
+            &state,
+            lost_on(&state, "front01"),
+            &ScheduleOptions::default(),
+        );
+
+        assert!(!report.survivable());
+        assert_eq!(report.moved.len(), 1);
+        assert_eq!(report.moved[0].task, "camera");
+        assert_eq!(report.unplaced.len(), 1);
+        let lidar = &report.unplaced[0];
+        assert_eq!(
+            (lidar.tenant.as_str(), lidar.task.as_str()),
+            ("perception", "lidar")
+        );
+        // 85 % fits on no CPU of either remaining node.
+        assert_eq!(
+            lidar.rejections,
+            [
+                NodeRejection {
+                    node: "control01".into(),
+                    reason: AdmissionReason::NoAvailableCpu,
+                },
+                NodeRejection {
+                    node: "rear01".into(),
+                    reason: AdmissionReason::NoAvailableCpu,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn hard_target_on_the_lost_node_is_not_probed() {
+        let (scheduler, state) = cluster();
+        let mut lost = lost_on(&state, "rear01");
+        lost[0].task.target_node = "rear01".into();
+        lost[0].task.target_node_policy = Some(TargetNodePolicy::Hard);
+        let report =
+            scheduler.what_if_node_loss("rear01", &state, lost, &ScheduleOptions::default());
+
+        assert_eq!(report.unplaced.len(), 1);
+        assert_eq!(report.unplaced[0].task, "park_assist");
+        assert!(report.unplaced[0].rejections.is_empty());
+        assert_eq!(report.moved.len(), 1);
+    }
+}
@@ -0,0 +1,217 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Per-node workload limit and exclusive CPUs.
+//!
+//! A node whose configuration sets
+//! [`max_workloads`](crate::config::NodeConfig::max_workloads) hosts at most
+//! that many distinct `workload_id`s at once.  [`NodeWorkloads`] tracks which
+//! workloads each node hosts during one scheduling run: seeded from the
+//! existing occupancy, then updated on every placement.
+//!
+//! Admission refuses a task of a workload the node does not host yet once
+//! the node is at its limit
+//! ([`AdmissionReason::WorkloadCountExceeded`](super::AdmissionReason::WorkloadCountExceeded));
+//! tasks of a workload already there are unaffected.  A task without a
+//! `workload_id` counts as belonging to the workload `""`.
+//!
+//! # Exclusive CPUs
+//!
+//! A workload submitted with `exclusive_cpus` (every task's
+//! [`Task::exclusive_cpus`]) holds each CPU one of its tasks goes to for as
+//! long as the task is placed there: no task of another workload joins that
+//! CPU, whatever its headroom, and the workload's own tasks only go to CPUs
+//! no other workload uses.  The holds are seeded from the existing
+//! placements ([`SchedTask::exclusive_cpus`](crate::task::SchedTask::exclusive_cpus)),
+//! so they outlast the run that made them and are part of a
+//! [`ClusterState`](crate::admission::ClusterState) snapshot.
+//!
+//! A node whose only CPUs with room are excluded this way rejects the task
+//! with
+//! [`AdmissionReason::CpuExclusivelyReserved`](super::AdmissionReason::CpuExclusivelyReserved),
+//! naming the workload it conflicts with.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::AvailCpus;
+use crate::task::{NodeSchedMap, Task};
+
+/// Distinct workloads hosted per node and per CPU, and the CPUs held
+/// exclusively (see the [module docs](self)).
+#[derive(Debug, Clone, Default)]
+pub struct NodeWorkloads {
+    /// Node → workload IDs with at least one task there.
+    hosted: BTreeMap<String, BTreeSet<String>>,
+    /// Node → CPU → workload IDs with at least one task there.
+    per_cpu: BTreeMap<String, BTreeMap<u32, BTreeSet<String>>>,
+    /// Node → CPU → the workload holding it exclusively.
+    exclusive: BTreeMap<String, BTreeMap<u32, String>>,
+}
+
+impl NodeWorkloads {
+    /// The workloads `schedule` already places on each node.
+    pub fn from_schedule(schedule: &NodeSchedMap) -> Self {
+        let mut workloads = Self::default();
+        for (node, tasks) in schedule {
+            for task in tasks {
+                workloads.record(
+                    node,
+                    task.assigned_cpu,
+                    &task.workload_id,
+                    task.exclusive_cpus,
+                );
+            }
+        }
+        workloads
+    }
+
+    /// Note that `workload_id` now has a task on `cpu` of `node`, holding
+    /// the CPU if `exclusive`.
+    pub fn record(&mut self, node: &str, cpu: u32, workload_id: &str, exclusive: bool) {
+        if !self.hosts(node, workload_id) {
+            self.hosted
+                .entry(node.to_string())
+                .or_default()
+                .insert(workload_id.to_string());
+        }
+        self.per_cpu
+            .entry(node.to_string())
+            .or_default()
+            .entry(cpu)
+            .or_default()
+            .insert(workload_id.to_string());
+        if exclusive {
+            self.exclusive
+                .entry(node.to_string())
+                .or_default()
+                .entry(cpu)
+                .or_insert_with(|| workload_id.to_string());
+        }
+    }
+
+    /// Whether `workload_id` already has a task on `node`.
+    pub fn hosts(&self, node: &str, workload_id: &str) -> bool {
+        self.hosted
+            .get(node)
+            .is_some_and(|ids| ids.contains(workload_id))
+    }
+
+    /// Distinct workloads on `node`.
+    pub fn count(&self, node: &str) -> usize {
+        self.hosted.get(node).map_or(0, BTreeSet::len)
+    }
+
+    /// Whether a task of `workload_id` may join `node` under `limit`.
+    pub fn admits(&self, node: &str, workload_id: &str, limit: Option<usize>) -> bool {
+        limit.is_none_or(|limit| self.hosts(node, workload_id) || self.count(node) < limit)
+    }
+
+    /// CPUs of `node` held exclusively, with the workload holding each.
+    pub fn exclusive_cpus(&self, node: &str) -> BTreeMap<u32, String> {
+        self.exclusive.get(node).cloned().unwrap_or_default()
+    }
+
+    /// The workload that keeps `task` off `cpu` of `node`: the CPU's
+    /// exclusive holder if that is another workload, or — for a task of an
+    /// exclusive workload — another workload already there.  `None` if the
+    /// CPU is open to the task.
+    pub fn conflict(&self, node: &str, cpu: u32, task: &Task) -> Option<&str> {
+        let holder = self.exclusive.get(node).and_then(|cpus| cpus.get(&cpu));
+        if let Some(holder) = holder.filter(|w| **w != task.workload_id) {
+            return Some(holder);
+        }
+        if !task.exclusive_cpus {
+            return None;
+        }
+        self.per_cpu
+            .get(node)
+            .and_then(|cpus| cpus.get(&cpu))
+            .and_then(|ids| ids.iter().find(|w| **w != task.workload_id))
+            .map(String::as_str)
+    }
+
+    /// `avail` without the CPUs [`conflict`](Self::conflict) keeps `task`
+    /// off; borrowed when there are none to remove.
+    pub fn open_cpus<'a>(&self, avail: &'a AvailCpus, task: &Task) -> Cow<'a, AvailCpus> {
+        if self.exclusive.is_empty() && !task.exclusive_cpus {
+            return Cow::Borrowed(avail);
+        }
+        Cow::Owned(
+            avail
+                .iter()
+                .map(|(node, cpus)| {
+                    let open = cpus
+                        .iter()
+                        .copied()
+                        .filter(|&cpu| self.conflict(node, cpu, task).is_none())
+                        .collect();
+                    (node.clone(), open)
+                })
+                .collect(),
+        )
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_new_workloads_count_against_the_limit() {
+        let mut workloads = NodeWorkloads::default();
+        workloads.record("n1", 0, "a", false);
+        workloads.record("n1", 1, "b", false);
+        workloads.record("n1", 1, "a", false);
+        assert_eq!(workloads.count("n1"), 2);
+
+        assert!(workloads.admits("n1", "a", Some(2)));
+        assert!(!workloads.admits("n1", "c", Some(2)));
+        assert!(workloads.admits("n1", "c", None));
+        assert!(workloads.admits("n2", "c", Some(1)));
+    }
+
+    #[test]
+    fn exclusive_cpus_are_closed_to_other_workloads_both_ways() {
+        let task = |workload: &str, exclusive_cpus| Task {
+            workload_id: workload.into(),
+            exclusive_cpus,
+            ..Default::default()
+        };
+        let mut workloads = NodeWorkloads::default();
+        workloads.record("n1", 3, "cert", true);
+        workloads.record("n1", 2, "infotainment", false);
+
+        // Another workload stays off the held CPU; its holder does not.
+        assert_eq!(
+            workloads.conflict("n1", 3, &task("other", false)),
+            Some("cert")
+        );
+        assert_eq!(workloads.conflict("n1", 3, &task("cert", true)), None);
+        assert_eq!(workloads.conflict("n1", 1, &task("other", false)), None);
+        // An exclusive workload stays off CPUs others use.
+        assert_eq!(
+            workloads.conflict("n1", 2, &task("cert", true)),
+            Some("infotainment")
+        );
+        assert_eq!(workloads.exclusive_cpus("n1"), [(3, "cert".into())].into());
+
+        let avail: AvailCpus = [("n1".to_string(), vec![1, 2, 3])].into();
+        assert_eq!(
+            workloads.open_cpus(&avail, &task("other", false))["n1"],
+            [1, 2]
+        );
+        assert_eq!(
+            workloads.open_cpus(&avail, &task("cert", true))["n1"],
+            [1, 3]
+        );
+        assert!(matches!(
+            NodeWorkloads::default().open_cpus(&avail, &task("other", false)),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -19,8 +19,129 @@ SPDX-License-Identifier: MIT
 //! compiler guarantees there is never more than one live copy.  The scheduler
 //! fills `assigned_node` / `assigned_cpu` in-place during the algorithm, then
 //! converts to `Vec<SchedTask>` (grouped by node) as the final step.
+//!
+//! # Time units
+//! `Task` timing is in microseconds ([`Micros`]) and `SchedTask` timing is in
+//! nanoseconds ([`Nanos`]).  The newtypes make a µs/ns mix-up a type error;
+//! the only conversions are the named methods on each type.  Both serialise
+//! as plain integers, so the wire format is unchanged.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::metadata::Metadata;
+use crate::naming::{NameKind, NamingPolicy};
+use crate::scheduler::utilization::Utilization;
+use crate::scheduler::SchedulerError;
+use crate::units::{fmt_duration_ns, fmt_duration_us};
+
+// ── Time units ────────────────────────────────────────────────────────────────
+
+/// A duration in microseconds.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Micros(pub u64);
+
+/// A duration in nanoseconds.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Nanos(pub u64);
+
+impl Micros {
+    pub const ZERO: Micros = Micros(0);
+
+    /// Raw microsecond count.
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Convert a proto `int32` µs field; negative values clamp to zero.
+    pub fn from_proto(v: i32) -> Self {
+        Micros(v.max(0) as u64)
+    }
+
+    /// Convert to a proto `int32` µs field, saturating at `i32::MAX`.
+    pub fn to_proto(self) -> i32 {
+        i32::try_from(self.0).unwrap_or(i32::MAX)
+    }
+
+    /// `None` if the nanosecond value would overflow `u64`.
+    pub fn checked_to_nanos(self) -> Option<Nanos> {
+        self.0.checked_mul(1_000).map(Nanos)
+    }
+
+    /// In nanoseconds, or `None` if that is above `max`.
+    pub fn to_nanos_within(self, max: Nanos) -> Option<Nanos> {
+        self.checked_to_nanos().filter(|ns| *ns <= max)
+    }
+
+    /// Like [`checked_to_nanos`](Self::checked_to_nanos) but clamps to
+    /// `u64::MAX` ns on overflow.
+    pub fn saturating_to_nanos(self) -> Nanos {
+        Nanos(self.0.saturating_mul(1_000))
+    }
+
+    /// `self / denom` as a fraction.  Returns `0.0` when `denom` is zero.
+    pub fn ratio(self, denom: Micros) -> f64 {
+        if denom.is_zero() {
+            0.0
+        } else {
+            self.0 as f64 / denom.0 as f64
+        }
+    }
+}
+
+impl Nanos {
+    pub const ZERO: Nanos = Nanos(0);
+
+    /// Raw nanosecond count.
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Convert to microseconds, truncating any sub-µs remainder.
+    pub const fn to_micros(self) -> Micros {
+        Micros(self.0 / 1_000)
+    }
 
-use std::collections::HashMap;
+    /// `self / denom` as a fraction.  Returns `0.0` when `denom` is zero.
+    pub fn ratio(self, denom: Nanos) -> f64 {
+        if denom.is_zero() {
+            0.0
+        } else {
+            self.0 as f64 / denom.0 as f64
+        }
+    }
+}
+
+impl fmt::Display for Micros {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}µs", self.0)
+    }
+}
+
+impl fmt::Display for Nanos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}ns", self.0)
+    }
+}
 
 // ── Scheduling policy ─────────────────────────────────────────────────────────
 
@@ -32,7 +153,7 @@ use std::collections::HashMap;
 /// Carrying the typed enum through the whole pipeline (instead of a raw `int`)
 /// makes it impossible to create an invalid policy value inside Timpani-O.  The
 /// conversion back to an integer only happens at the Timpani-N wire boundary.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum SchedPolicy {
     /// `SCHED_NORMAL` – standard Linux CFS scheduling.
     #[default]
@@ -45,6 +166,12 @@ pub enum SchedPolicy {
 
 impl SchedPolicy {
     /// Convert to the integer value expected by Timpani-N / the Linux kernel.
+    /// `true` for the real-time policies (FIFO, RR), which need
+    /// `CAP_SYS_NICE` or `RLIMIT_RTPRIO` on the node.
+    pub fn is_realtime(self) -> bool {
+        matches!(self, SchedPolicy::Fifo | SchedPolicy::RoundRobin)
+    }
+
     pub fn to_linux_int(self) -> i32 {
         match self {
             SchedPolicy::Normal => 0,
@@ -105,7 +232,7 @@ impl CpuAffinity {
     pub fn allows_cpu(&self, cpu_id: u32) -> bool {
         match self {
             CpuAffinity::Any => true,
-            CpuAffinity::Pinned(mask) => (mask >> cpu_id) & 1 == 1,
+            CpuAffinity::Pinned(mask) => mask.checked_shr(cpu_id).is_some_and(|m| m & 1 == 1),
         }
     }
 
@@ -127,6 +254,83 @@ impl CpuAffinity {
     }
 }
 
+// ── TargetNodePolicy ──────────────────────────────────────────────────────────
+
+/// How strictly a task's `target_node` is honoured.
+///
+/// When a task does not set one, the algorithm's default applies (see
+/// `SchedAlgorithm::default_target_policy`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetNodePolicy {
+    /// Place on `target_node` or fail with `AdmissionRejected`.
+    Hard,
+    /// Try `target_node` first; auto-select another node if it cannot take
+    /// the task.
+    Preferred,
+}
+
+impl TargetNodePolicy {
+    /// Parse the proto `TargetNodePolicy` enum integer.
+    /// Unknown values map to `Hard` (the stricter reading).
+    pub fn from_proto_int(v: i32) -> Self {
+        match v {
+            1 => TargetNodePolicy::Preferred,
+            _ => TargetNodePolicy::Hard,
+        }
+    }
+}
+
+// ── FaultSink ─────────────────────────────────────────────────────────────────
+
+/// Where a task's deadline misses are reported.
+///
+/// Mirrors the `FaultSink` proto enum.  Timpani-N routes each miss by it;
+/// Timpani-O forwards a reported miss to Pullpiri only if the sink in effect
+/// on the node [forwards upstream](Self::forwards_upstream).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FaultSink {
+    /// `ReportDMiss` to Timpani-O, which forwards it to Pullpiri.
+    #[default]
+    Upstream,
+    /// The node's local sink only; Timpani-O does not forward the miss.
+    LocalOnly,
+    /// Both the node's local sink and Timpani-O.
+    Both,
+}
+
+impl FaultSink {
+    /// Parse the proto `FaultSink` enum integer.
+    /// Unknown values map to `Upstream`, which never loses a miss.
+    pub fn from_proto_int(v: i32) -> Self {
+        match v {
+            1 => FaultSink::LocalOnly,
+            2 => FaultSink::Both,
+            _ => FaultSink::Upstream,
+        }
+    }
+
+    pub fn to_proto_int(self) -> i32 {
+        match self {
+            FaultSink::Upstream => 0,
+            FaultSink::LocalOnly => 1,
+            FaultSink::Both => 2,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FaultSink::Upstream => "upstream",
+            FaultSink::LocalOnly => "local_only",
+            FaultSink::Both => "both",
+        }
+    }
+
+    /// `true` if Timpani-O forwards the task's misses to Pullpiri.
+    pub fn forwards_upstream(self) -> bool {
+        matches!(self, FaultSink::Upstream | FaultSink::Both)
+    }
+}
+
 // ── Task (input / working copy) ───────────────────────────────────────────────
 
 /// Internal task representation used during scheduling.
@@ -139,7 +343,7 @@ impl CpuAffinity {
 ///   representations.
 /// * `assigned_cpu` is `Option<u32>` instead of `-1` sentinel.
 /// * Dead fields (`dependencies`, `cluster_requirement`) are removed.
-/// * `memory_mb` is reinstated as `u64` (zero = unconstrained / not yet set by proto).
+/// * `memory_mb` is reinstated as `u64` (zero = unconstrained).
 ///
 /// # Lifecycle
 /// Created by the gRPC handler from a proto `TaskInfo`, **moved** into
@@ -160,11 +364,20 @@ pub struct Task {
     /// `best_fit_decreasing` and `least_loaded` algorithms).
     pub target_node: String,
 
+    /// How strictly `target_node` is honoured.  `None` = algorithm default.
+    pub target_node_policy: Option<TargetNodePolicy>,
+
+    /// Zone the task would rather run near (e.g. its sensors), matched
+    /// against node `location`s through the run's proximity table (see
+    /// [`proximity`](crate::scheduler::proximity)).  Empty = no preference.
+    pub preferred_location: String,
+
     // ── Scheduling parameters ─────────────────────────────────────────────────
     /// Linux scheduling policy.
     pub policy: SchedPolicy,
 
-    /// Real-time priority (1–99 for FIFO/RR, 0 for Normal).
+    /// Real-time priority (1–99 for FIFO/RR, 0 for Normal).  Outside
+    /// [`RangedField::Priority`]'s range the task is invalid.
     pub priority: i32,
 
     /// CPU affinity constraint.
@@ -173,22 +386,21 @@ pub struct Task {
     // ── Resource requirements ─────────────────────────────────────────────────
     /// Memory budget for this task in megabytes.
     ///
-    /// Checked against `NodeConfig::max_memory_mb` during admission control.
-    /// A value of `0` means "no constraint" — used when Pullpiri does not yet
-    /// populate this field (the proto `TaskInfo` does not carry it yet).
-    /// This is **dormant** until the proto is extended; the field exists now so
-    /// the pipeline is ready without a breaking change later.
+    /// Checked against `NodeConfig::max_memory_mb` (or the node's live
+    /// ceiling) during admission control.  Set from `TaskInfo.memory_mb`
+    /// (schema rev 24); a value of `0` means "no constraint", which is what
+    /// older Pullpiri builds send.
     pub memory_mb: u64,
 
     // ── Timing (all in microseconds) ──────────────────────────────────────────
     /// Task period in µs.
-    pub period_us: u64,
+    pub period_us: Micros,
 
     /// Worst-case execution time (runtime) in µs.
-    pub runtime_us: u64,
+    pub runtime_us: Micros,
 
     /// Relative deadline in µs (typically equals `period_us`).
-    pub deadline_us: u64,
+    pub deadline_us: Micros,
 
     /// Release time offset from the start of the hyperperiod, in µs.
     pub release_time_us: u32,
@@ -197,6 +409,38 @@ pub struct Task {
     /// reported to Pullpiri.
     pub max_dmiss: i32,
 
+    /// Resources this task locks.  Empty for independent tasks.
+    pub shared_resources: Vec<SharedResource>,
+
+    /// WCET calibration per node `architecture`: `runtime_us` was measured
+    /// on a reference board and is multiplied by the matching factor when
+    /// the task is considered for (and placed on) a node of that
+    /// architecture.  Architectures not listed run `runtime_us` unscaled.
+    pub wcet_scaling: BTreeMap<String, f64>,
+
+    /// Opaque key/value pairs from Piccolo, carried through unchanged (see
+    /// [`metadata`](crate::metadata)).
+    pub metadata: Metadata,
+
+    /// Capacity reserved for a task that arrives later: admitted, counted
+    /// and checked for feasibility like any task, but never applied on its
+    /// node nor monitored for deadline misses.  Replacing it with a real
+    /// task of the same name keeps its placement while that still fits.
+    pub placeholder: bool,
+
+    /// The task's workload holds its CPUs exclusively
+    /// (`SchedInfo.exclusive_cpus`): the CPU the task goes to is shared with
+    /// no other workload (see [`crate::scheduler::workloads`]).
+    pub exclusive_cpus: bool,
+
+    /// Where the task's deadline misses are reported.
+    pub fault_sink: FaultSink,
+
+    /// Tasks of one workload naming the same group would rather share a
+    /// cache cluster on different CPUs (see
+    /// [`crate::scheduler::cache_group`]).  A hint only: `None` = no group.
+    pub cache_affinity_group: Option<String>,
+
     // ── Assignment (filled by GlobalScheduler) ────────────────────────────────
     /// Node the scheduler assigned this task to.  Empty until the algorithm
     /// runs.
@@ -205,6 +449,10 @@ pub struct Task {
     /// CPU the scheduler assigned this task to.  `None` until the algorithm
     /// runs.
     pub assigned_cpu: Option<u32>,
+
+    /// `true` if a `Preferred` `target_node` could not take the task and
+    /// another node was auto-selected.
+    pub target_fallback: bool,
 }
 
 impl Task {
@@ -212,19 +460,111 @@ impl Task {
     ///
     /// Returns `0.0` when `period_us` is zero to avoid division by zero.
     pub fn utilization(&self) -> f64 {
-        if self.period_us == 0 {
-            0.0
-        } else {
-            self.runtime_us as f64 / self.period_us as f64
+        self.runtime_us.ratio(self.period_us)
+    }
+
+    /// Exact `runtime_us / period_us`, as used for admission control.
+    pub fn exact_utilization(&self) -> Utilization {
+        Utilization::of(self.runtime_us.as_u64(), self.period_us.as_u64())
+    }
+
+    /// WCET on a node of `architecture`: `runtime_us` times the matching
+    /// [`wcet_scaling`](Self::wcet_scaling) factor, rounded to the nearest
+    /// µs, or `runtime_us` itself when the architecture is not listed.
+    pub fn runtime_on(&self, architecture: &str) -> Micros {
+        match self.wcet_scaling.get(architecture) {
+            Some(&factor) => Micros((self.runtime_us.as_u64() as f64 * factor).round() as u64),
+            None => self.runtime_us,
         }
     }
 
+    /// [`exact_utilization`](Self::exact_utilization) with the runtime
+    /// scaled for `architecture` (see [`runtime_on`](Self::runtime_on)).
+    pub fn exact_utilization_on(&self, architecture: &str) -> Utilization {
+        Utilization::of(
+            self.runtime_on(architecture).as_u64(),
+            self.period_us.as_u64(),
+        )
+    }
+
+    /// First `wcet_scaling` entry whose factor is not a positive finite
+    /// number.
+    pub fn invalid_wcet_scaling(&self) -> Option<(&str, f64)> {
+        self.wcet_scaling
+            .iter()
+            .find(|(_, f)| !(f.is_finite() && **f > 0.0))
+            .map(|(arch, &f)| (arch.as_str(), f))
+    }
+
+    /// Check `workload_id` and `name` against `policy`, then the
+    /// [`RangedField`]s.  Fails with `InvalidName` or `InvalidTask`.
+    pub fn validate(&self, policy: &NamingPolicy) -> Result<(), SchedulerError> {
+        policy.check(NameKind::WorkloadId, &self.workload_id)?;
+        policy.check(NameKind::TaskName, &self.name)?;
+        self.check_ranges()?;
+        Ok(())
+    }
+
+    /// Fail with `OutOfRange` for the first [`RangedField`] outside its
+    /// range.
+    pub fn check_ranges(&self) -> Result<(), SchedTaskConversionError> {
+        match self.out_of_range() {
+            Some((field, value)) => Err(SchedTaskConversionError::OutOfRange {
+                task: self.name.clone(),
+                field,
+                value,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Apply `check` to the [`RangedField`]s: `Strict` is
+    /// [`check_ranges`](Self::check_ranges); `Clamp` moves every value
+    /// outside its range to the nearest end of it and returns each field
+    /// changed with its original value.
+    pub fn enforce_ranges(
+        &mut self,
+        check: RangeCheck,
+    ) -> Result<Vec<(RangedField, i32)>, SchedTaskConversionError> {
+        if check == RangeCheck::Strict {
+            return self.check_ranges().map(|()| Vec::new());
+        }
+        let mut clamped = Vec::new();
+        while let Some((field, value)) = self.out_of_range() {
+            let range = field.range();
+            field.set(self, value.clamp(*range.start(), *range.end()));
+            clamped.push((field, value));
+        }
+        Ok(clamped)
+    }
+
+    fn out_of_range(&self) -> Option<(RangedField, i32)> {
+        RangedField::ALL
+            .into_iter()
+            .map(|field| (field, field.get(self)))
+            .find(|(field, value)| !field.range().contains(value))
+    }
+
     /// Returns `true` if the scheduler has assigned a node to this task.
     pub fn is_assigned(&self) -> bool {
         !self.assigned_node.is_empty() && self.assigned_cpu.is_some()
     }
 }
 
+// ── SharedResource ────────────────────────────────────────────────────────────
+
+/// A mutually exclusive resource (typically a lock) used by a task.
+///
+/// Resources are matched by `name` among tasks on the same CPU; see
+/// `scheduler::rta` for how the critical section feeds into blocking.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SharedResource {
+    pub name: String,
+    /// Longest single critical section this task executes while holding the
+    /// resource, in µs.
+    pub max_cs_us: Micros,
+}
+
 // ── SchedTask (output / wire-ready) ──────────────────────────────────────────
 
 /// Per-task scheduling result sent to Timpani-N.
@@ -234,7 +574,7 @@ impl Task {
 /// risk) and nanosecond timing as required by the Timpani-N protocol.
 ///
 /// Produced from a fully-assigned [`Task`] via [`SchedTask::from_task`].
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SchedTask {
     /// Task name (no length limit — Rust `String` replaces the 16-byte C array).
     pub name: String,
@@ -252,47 +592,321 @@ pub struct SchedTask {
     pub priority: i32,
 
     /// Period in nanoseconds (converted from `Task::period_us`).
-    pub period_ns: u64,
+    pub period_ns: Nanos,
 
     /// Runtime (WCET) in nanoseconds.
-    pub runtime_ns: u64,
+    pub runtime_ns: Nanos,
 
     /// Deadline in nanoseconds.
-    pub deadline_ns: u64,
+    pub deadline_ns: Nanos,
 
     /// Release time in microseconds (kept as-is from the proto field).
     pub release_time_us: i32,
 
     /// Maximum deadline misses allowed.
     pub max_dmiss: i32,
+
+    /// Resources this task locks (carried for post-schedule blocking analysis;
+    /// not sent to Timpani-N).
+    pub shared_resources: Vec<SharedResource>,
+
+    /// Workload the task belongs to; empty if the request gave none.
+    #[serde(default)]
+    pub workload_id: String,
+
+    /// The preferred target node that was skipped, if the scheduler fell
+    /// back to another node.
+    pub fallback_from: Option<String>,
+
+    /// The source task's metadata; Timpani-N resolves PIDs by it.
+    #[serde(default)]
+    pub metadata: Metadata,
+
+    /// Reserved capacity only (see [`Task::placeholder`]); Timpani-N stores
+    /// the task without applying it.
+    #[serde(default)]
+    pub placeholder: bool,
+
+    /// The task's workload holds `assigned_cpu` exclusively (see
+    /// [`Task::exclusive_cpus`]).
+    #[serde(default)]
+    pub exclusive_cpus: bool,
+
+    /// Where the task's deadline misses are reported.
+    #[serde(default)]
+    pub fault_sink: FaultSink,
+
+    /// See [`Task::cache_affinity_group`].
+    #[serde(default)]
+    pub cache_affinity_group: Option<String>,
+}
+
+/// Longest period, runtime, deadline or release time a [`SchedTask`] may
+/// carry unless configured otherwise: one hour, like
+/// [`DEFAULT_HYPERPERIOD_LIMIT_US`](crate::hyperperiod::DEFAULT_HYPERPERIOD_LIMIT_US).
+pub const DEFAULT_MAX_TASK_DURATION: Nanos = Nanos(3_600_000_000_000);
+
+/// Longest duration the µs `int32` fields of `ScheduledTask` can carry
+/// (about 35 min).
+pub const WIRE_MAX_DURATION: Nanos = Nanos(i32::MAX as u64 * 1_000);
+
+/// A timing parameter of a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingField {
+    Period,
+    Runtime,
+    Deadline,
+    ReleaseTime,
+}
+
+impl TimingField {
+    /// Field name, as in `TaskInfo`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TimingField::Period => "period",
+            TimingField::Runtime => "runtime",
+            TimingField::Deadline => "deadline",
+            TimingField::ReleaseTime => "release_time",
+        }
+    }
+}
+
+impl fmt::Display for TimingField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Highest `priority` a task may carry: the top Linux RT priority.
+pub const MAX_PRIORITY: i32 = 99;
+
+/// A task parameter with a bounded range: too large or negative, it would
+/// be truncated or rejected once it reaches the kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangedField {
+    /// `0..=MAX_PRIORITY`; 0 for Normal tasks and for auto-assignment.
+    Priority,
+    /// Not negative.
+    MaxDmiss,
+}
+
+impl RangedField {
+    pub const ALL: [RangedField; 2] = [RangedField::Priority, RangedField::MaxDmiss];
+
+    /// Field name, as in `TaskInfo`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RangedField::Priority => "priority",
+            RangedField::MaxDmiss => "max_dmiss",
+        }
+    }
+
+    /// The values the field may take.
+    pub fn range(self) -> RangeInclusive<i32> {
+        match self {
+            RangedField::Priority => 0..=MAX_PRIORITY,
+            RangedField::MaxDmiss => 0..=i32::MAX,
+        }
+    }
+
+    fn get(self, task: &Task) -> i32 {
+        match self {
+            RangedField::Priority => task.priority,
+            RangedField::MaxDmiss => task.max_dmiss,
+        }
+    }
+
+    fn set(self, task: &mut Task, value: i32) {
+        match self {
+            RangedField::Priority => task.priority = value,
+            RangedField::MaxDmiss => task.max_dmiss = value,
+        }
+    }
+}
+
+impl fmt::Display for RangedField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What [`Task::enforce_ranges`] does with a [`RangedField`] outside its
+/// range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RangeCheck {
+    /// Refuse the task with `SchedTaskConversionError::OutOfRange`.
+    #[default]
+    Strict,
+    /// Move the value to the nearest end of the range.
+    Clamp,
+}
+
+impl RangeCheck {
+    /// Canonical string form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RangeCheck::Strict => "strict",
+            RangeCheck::Clamp => "clamp",
+        }
+    }
+}
+
+impl fmt::Display for RangeCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RangeCheck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(RangeCheck::Strict),
+            "clamp" => Ok(RangeCheck::Clamp),
+            other => Err(format!("unknown range check '{other}'")),
+        }
+    }
+}
+
+/// Why a task was refused as invalid: by [`SchedTask::from_task`], or by
+/// the scheduler before assigning it (`OverUtilized`).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum SchedTaskConversionError {
+    /// A timing value is above the maximum.
+    #[error(
+        "task '{task}' has {field} {}, above the maximum of {}",
+        fmt_duration_us(value.as_u64()),
+        fmt_duration_ns(max.as_u64())
+    )]
+    TooLong {
+        task: String,
+        field: TimingField,
+        value: Micros,
+        max: Nanos,
+    },
+    /// The task needs more than one whole CPU (`runtime > period`), on its
+    /// own or after `wcet_scaling` for `node` (empty = before placement).
+    /// A task runs on one CPU, so it can never keep up.
+    #[error("task '{task}' needs {utilization} of one CPU{}", on_node(node))]
+    OverUtilized {
+        task: String,
+        node: String,
+        utilization: Utilization,
+    },
+    /// A [`RangedField`] is outside its range.
+    #[error("task '{task}' has {field} {value}, outside {}", fmt_range(*field))]
+    OutOfRange {
+        task: String,
+        field: RangedField,
+        value: i32,
+    },
+}
+
+fn fmt_range(field: RangedField) -> String {
+    let range = field.range();
+    if *range.end() == i32::MAX {
+        format!("{} or more", range.start())
+    } else {
+        format!("{}–{}", range.start(), range.end())
+    }
+}
+
+fn on_node(node: &str) -> String {
+    if node.is_empty() {
+        String::new()
+    } else {
+        format!(" on node '{node}'")
+    }
+}
+
+impl SchedTaskConversionError {
+    /// The task that was refused.
+    pub fn task(&self) -> &str {
+        match self {
+            SchedTaskConversionError::TooLong { task, .. }
+            | SchedTaskConversionError::OverUtilized { task, .. }
+            | SchedTaskConversionError::OutOfRange { task, .. } => task,
+        }
+    }
 }
 
 impl SchedTask {
     /// Convert a fully-assigned [`Task`] into a wire-ready [`SchedTask`].
     ///
+    /// Fails if the period, runtime, deadline or release time is above
+    /// `max` (see [`DEFAULT_MAX_TASK_DURATION`]) or does not fit the µs
+    /// `int32` fields of the `ScheduledTask` sent to Timpani-N
+    /// ([`WIRE_MAX_DURATION`]), instead of saturating.
+    ///
     /// # Panics
     /// Panics in debug builds if the task has not been assigned (i.e.
-    /// `assigned_node` is empty or `assigned_cpu` is `None`).  In release
-    /// builds the values default to empty / 0 rather than panicking.
-    pub fn from_task(task: &Task) -> Self {
+    /// `assigned_node` is empty or `assigned_cpu` is `None`), or if a
+    /// [`RangedField`] is outside its range: callers validate tasks as they
+    /// arrive.  In release builds the values default to empty / 0, and
+    /// ranged values pass through, rather than panicking.
+    pub fn from_task(task: &Task, max: Nanos) -> Result<Self, SchedTaskConversionError> {
         debug_assert!(
             task.is_assigned(),
             "SchedTask::from_task called on unassigned task '{}'",
             task.name
         );
+        debug_assert!(
+            task.check_ranges().is_ok(),
+            "SchedTask::from_task called on invalid task: {}",
+            task.check_ranges().unwrap_err()
+        );
+
+        let max = max.min(WIRE_MAX_DURATION);
+        let within = |field, value: Micros| {
+            value
+                .to_nanos_within(max)
+                .ok_or_else(|| SchedTaskConversionError::TooLong {
+                    task: task.name.clone(),
+                    field,
+                    value,
+                    max,
+                })
+        };
+        let release = Micros(u64::from(task.release_time_us));
+        within(TimingField::ReleaseTime, release)?;
 
-        SchedTask {
+        Ok(SchedTask {
             name: task.name.clone(),
             assigned_node: task.assigned_node.clone(),
             assigned_cpu: task.assigned_cpu.unwrap_or(0),
             policy: task.policy,
             priority: task.priority,
-            period_ns: task.period_us.saturating_mul(1_000),
-            runtime_ns: task.runtime_us.saturating_mul(1_000),
-            deadline_ns: task.deadline_us.saturating_mul(1_000),
-            release_time_us: task.release_time_us as i32,
+            period_ns: within(TimingField::Period, task.period_us)?,
+            runtime_ns: within(TimingField::Runtime, task.runtime_us)?,
+            deadline_ns: within(TimingField::Deadline, task.deadline_us)?,
+            // Within WIRE_MAX_DURATION, so it fits.
+            release_time_us: i32::try_from(task.release_time_us).unwrap_or(i32::MAX),
             max_dmiss: task.max_dmiss,
-        }
+            shared_resources: task.shared_resources.clone(),
+            workload_id: task.workload_id.clone(),
+            fallback_from: task.target_fallback.then(|| task.target_node.clone()),
+            metadata: task.metadata.clone(),
+            placeholder: task.placeholder,
+            exclusive_cpus: task.exclusive_cpus,
+            fault_sink: task.fault_sink,
+            cache_affinity_group: task.cache_affinity_group.clone(),
+        })
+    }
+
+    /// CPU utilisation fraction: `runtime_ns / period_ns`.
+    ///
+    /// Same as [`Task::utilization`] on the source task.  Returns `0.0` when
+    /// `period_ns` is zero.
+    pub fn utilization(&self) -> f64 {
+        self.runtime_ns.ratio(self.period_ns)
+    }
+
+    /// Exact `runtime_ns / period_ns`; equal to the source task's
+    /// [`Task::exact_utilization`].
+    pub fn exact_utilization(&self) -> Utilization {
+        Utilization::of(self.runtime_ns.as_u64(), self.period_ns.as_u64())
     }
 }
 
@@ -362,6 +976,7 @@ mod tests {
         assert!(aff.allows_cpu(2));
         assert!(aff.allows_cpu(3));
         assert!(!aff.allows_cpu(4));
+        assert!(!aff.allows_cpu(64));
     }
 
     #[test]
@@ -390,8 +1005,8 @@ mod tests {
     #[test]
     fn task_utilization_is_correct() {
         let task = Task {
-            period_us: 1_000_000,
-            runtime_us: 100_000,
+            period_us: Micros(1_000_000),
+            runtime_us: Micros(100_000),
             ..Default::default()
         };
         assert!((task.utilization() - 0.1).abs() < 1e-9);
@@ -400,13 +1015,38 @@ mod tests {
     #[test]
     fn task_utilization_zero_period_returns_zero() {
         let task = Task {
-            period_us: 0,
-            runtime_us: 100,
+            period_us: Micros(0),
+            runtime_us: Micros(100),
             ..Default::default()
         };
         assert_eq!(task.utilization(), 0.0);
     }
 
+    #[test]
+    fn runtime_scales_per_architecture() {
+        let task = Task {
+            period_us: Micros(10_000),
+            runtime_us: Micros(1_000),
+            wcet_scaling: [("aarch64".into(), 1.0), ("x86_64".into(), 0.7)].into(),
+            ..Default::default()
+        };
+        assert_eq!(task.runtime_on("aarch64"), Micros(1_000));
+        assert_eq!(task.runtime_on("x86_64"), Micros(700));
+        assert_eq!(
+            task.runtime_on("riscv64"),
+            Micros(1_000),
+            "unlisted: unscaled"
+        );
+        assert_eq!(task.exact_utilization_on("x86_64"), Utilization::of(7, 100));
+        assert_eq!(task.invalid_wcet_scaling(), None);
+
+        let bad = Task {
+            wcet_scaling: [("x86_64".into(), 0.0)].into(),
+            ..task
+        };
+        assert_eq!(bad.invalid_wcet_scaling(), Some(("x86_64", 0.0)));
+    }
+
     #[test]
     fn task_is_assigned_requires_both_node_and_cpu() {
         let mut task = Task::default();
@@ -422,6 +1062,133 @@ mod tests {
         assert!(task.is_assigned());
     }
 
+    #[test]
+    fn validate_checks_workload_id_then_name() {
+        let policy = NamingPolicy::default();
+        let task = Task {
+            name: "t/1".into(),
+            workload_id: "wl 1".into(),
+            ..Task::default()
+        };
+        assert!(matches!(
+            task.validate(&policy),
+            Err(SchedulerError::InvalidName(e)) if e.kind == NameKind::WorkloadId
+        ));
+
+        let task = Task {
+            workload_id: "wl1".into(),
+            ..task
+        };
+        assert!(matches!(
+            task.validate(&policy),
+            Err(SchedulerError::InvalidName(e)) if e.kind == NameKind::TaskName
+        ));
+
+        let task = Task {
+            name: "t1".into(),
+            ..task
+        };
+        assert!(task.validate(&policy).is_ok());
+
+        let task = Task {
+            priority: 100,
+            ..task
+        };
+        assert!(matches!(
+            task.validate(&policy),
+            Err(SchedulerError::InvalidTask(
+                SchedTaskConversionError::OutOfRange {
+                    field: RangedField::Priority,
+                    value: 100,
+                    ..
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    fn strict_range_check_refuses_each_boundary() {
+        let task = |priority, max_dmiss| Task {
+            name: "t1".into(),
+            priority,
+            max_dmiss,
+            ..Task::default()
+        };
+        for (priority, max_dmiss) in [(0, 0), (MAX_PRIORITY, i32::MAX)] {
+            let mut ok = task(priority, max_dmiss);
+            assert_eq!(ok.enforce_ranges(RangeCheck::Strict), Ok(Vec::new()));
+        }
+        for (priority, max_dmiss, field, value) in [
+            (-1, 0, RangedField::Priority, -1),
+            (MAX_PRIORITY + 1, 0, RangedField::Priority, 100),
+            (i32::MAX, 0, RangedField::Priority, i32::MAX),
+            (50, -1, RangedField::MaxDmiss, -1),
+        ] {
+            let mut bad = task(priority, max_dmiss);
+            let err = bad.enforce_ranges(RangeCheck::Strict).unwrap_err();
+            assert_eq!(
+                err,
+                SchedTaskConversionError::OutOfRange {
+                    task: "t1".into(),
+                    field,
+                    value
+                }
+            );
+            // Refused, not changed.
+            assert_eq!((bad.priority, bad.max_dmiss), (priority, max_dmiss));
+        }
+        assert_eq!(
+            task(150, 0).check_ranges().unwrap_err().to_string(),
+            "task 't1' has priority 150, outside 0–99"
+        );
+        assert_eq!(
+            task(0, -3).check_ranges().unwrap_err().to_string(),
+            "task 't1' has max_dmiss -3, outside 0 or more"
+        );
+    }
+
+    #[test]
+    fn clamp_range_check_moves_values_to_the_nearest_end() {
+        let mut task = Task {
+            priority: 150,
+            max_dmiss: -3,
+            ..Task::default()
+        };
+        assert_eq!(
+            task.enforce_ranges(RangeCheck::Clamp),
+            Ok(vec![
+                (RangedField::Priority, 150),
+                (RangedField::MaxDmiss, -3)
+            ])
+        );
+        assert_eq!((task.priority, task.max_dmiss), (MAX_PRIORITY, 0));
+
+        task.priority = -1;
+        assert_eq!(
+            task.enforce_ranges(RangeCheck::Clamp),
+            Ok(vec![(RangedField::Priority, -1)])
+        );
+        assert_eq!(task.priority, 0);
+        // In range: nothing to clamp.
+        assert_eq!(task.enforce_ranges(RangeCheck::Clamp), Ok(Vec::new()));
+        assert_eq!("clamp".parse(), Ok(RangeCheck::Clamp));
+        assert!("lenient".parse::<RangeCheck>().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "priority 100, outside 0–99")]
+    #[cfg(debug_assertions)]
+    fn sched_task_from_task_asserts_ranges_in_debug_builds() {
+        let task = Task {
+            name: "t1".into(),
+            assigned_node: "node01".into(),
+            assigned_cpu: Some(0),
+            priority: 100,
+            ..Task::default()
+        };
+        let _ = SchedTask::from_task(&task, DEFAULT_MAX_TASK_DURATION);
+    }
+
     // ── SchedTask ─────────────────────────────────────────────────────────────
 
     #[test]
@@ -432,38 +1199,131 @@ mod tests {
             assigned_cpu: Some(3),
             policy: SchedPolicy::Fifo,
             priority: 50,
-            period_us: 1_000, // 1 ms
-            runtime_us: 100,  // 0.1 ms
-            deadline_us: 1_000,
+            period_us: Micros(1_000), // 1 ms
+            runtime_us: Micros(100),  // 0.1 ms
+            deadline_us: Micros(1_000),
             release_time_us: 0,
             max_dmiss: 3,
+            metadata: [("image".to_string(), "cam:1".to_string())].into(),
             ..Default::default()
         };
-        let st = SchedTask::from_task(&task);
+        let st = SchedTask::from_task(&task, DEFAULT_MAX_TASK_DURATION).unwrap();
 
         assert_eq!(st.name, "t1");
         assert_eq!(st.assigned_node, "node01");
         assert_eq!(st.assigned_cpu, 3);
-        assert_eq!(st.period_ns, 1_000_000); // µs → ns
-        assert_eq!(st.runtime_ns, 100_000);
-        assert_eq!(st.deadline_ns, 1_000_000);
+        assert_eq!(st.period_ns, Nanos(1_000_000)); // µs → ns
+        assert_eq!(st.runtime_ns, Nanos(100_000));
+        assert_eq!(st.deadline_ns, Nanos(1_000_000));
         assert_eq!(st.policy, SchedPolicy::Fifo);
         assert_eq!(st.priority, 50);
         assert_eq!(st.max_dmiss, 3);
+        assert_eq!(st.metadata, task.metadata);
     }
 
     #[test]
-    fn sched_task_period_ns_does_not_overflow_on_large_values() {
-        // u64::MAX / 1000 = ~1.8 × 10^16 µs — saturating_mul should handle it
-        let task = Task {
+    fn sched_task_rejects_values_above_the_maximum() {
+        // u64::MAX / 1000 + 1 µs overflows u64 in ns.
+        let mut task = Task {
             name: "big".into(),
             assigned_node: "n".into(),
             assigned_cpu: Some(0),
-            period_us: u64::MAX / 1_000 + 1, // would overflow without saturation
+            period_us: Micros(u64::MAX / 1_000 + 1),
+            runtime_us: Micros(100),
+            deadline_us: Micros(1_000),
             ..Default::default()
         };
-        // Should not panic
-        let st = SchedTask::from_task(&task);
-        assert_eq!(st.period_ns, u64::MAX); // saturated
+        let err = SchedTask::from_task(&task, DEFAULT_MAX_TASK_DURATION).unwrap_err();
+        assert_eq!(
+            err,
+            SchedTaskConversionError::TooLong {
+                task: "big".into(),
+                field: TimingField::Period,
+                value: task.period_us,
+                max: WIRE_MAX_DURATION,
+            }
+        );
+        assert_eq!(err.task(), "big");
+
+        // A configured maximum below the wire limit applies as is.
+        task.period_us = Micros(900);
+        let max = Nanos(999_000);
+        assert!(matches!(
+            SchedTask::from_task(&task, max),
+            Err(SchedTaskConversionError::TooLong {
+                field: TimingField::Deadline,
+                max: Nanos(999_000),
+                ..
+            })
+        ));
+        assert!(SchedTask::from_task(&task, Nanos(1_000_000)).is_ok());
+
+        // So does the release time, which goes out as int32 µs.
+        task.release_time_us = u32::MAX;
+        assert!(matches!(
+            SchedTask::from_task(&task, DEFAULT_MAX_TASK_DURATION),
+            Err(SchedTaskConversionError::TooLong {
+                field: TimingField::ReleaseTime,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn default_max_task_duration_matches_the_hyperperiod_limit() {
+        assert_eq!(
+            DEFAULT_MAX_TASK_DURATION,
+            crate::hyperperiod::DEFAULT_HYPERPERIOD_LIMIT_US.saturating_to_nanos()
+        );
+    }
+
+    #[test]
+    fn sched_task_utilization_matches_source_task() {
+        let task = Task {
+            name: "t1".into(),
+            assigned_node: "node01".into(),
+            assigned_cpu: Some(0),
+            period_us: Micros(10_000),
+            runtime_us: Micros(2_500),
+            ..Default::default()
+        };
+        let st = SchedTask::from_task(&task, DEFAULT_MAX_TASK_DURATION).unwrap();
+        assert!((st.utilization() - task.utilization()).abs() < 1e-12);
+    }
+
+    // ── Time units ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn micros_to_nanos_checked_and_saturating() {
+        assert_eq!(Micros(1_500).checked_to_nanos(), Some(Nanos(1_500_000)));
+        let huge = Micros(u64::MAX / 1_000 + 1);
+        assert_eq!(huge.checked_to_nanos(), None);
+        assert_eq!(huge.saturating_to_nanos(), Nanos(u64::MAX));
+    }
+
+    #[test]
+    fn nanos_to_micros_truncates() {
+        assert_eq!(Nanos(1_999).to_micros(), Micros(1));
+        assert_eq!(Nanos(2_000).to_micros(), Micros(2));
+    }
+
+    #[test]
+    fn micros_proto_boundary_clamps() {
+        assert_eq!(Micros::from_proto(-5), Micros::ZERO);
+        assert_eq!(Micros::from_proto(10_000), Micros(10_000));
+        assert_eq!(Micros(10_000).to_proto(), 10_000);
+        assert_eq!(Micros(u64::MAX).to_proto(), i32::MAX);
+    }
+
+    #[test]
+    fn ratio_handles_zero_denominator() {
+        assert_eq!(Micros(5).ratio(Micros::ZERO), 0.0);
+        assert_eq!(Nanos(1).ratio(Nanos(4)), 0.25);
+    }
+
+    #[test]
+    fn time_units_serialize_as_plain_integers() {
+        assert_eq!(serde_yaml::to_string(&Micros(42)).unwrap().trim(), "42");
+        assert_eq!(serde_yaml::from_str::<Nanos>("7").unwrap(), Nanos(7));
     }
 }
@@ -0,0 +1,462 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Workload task files: a `SchedInfo` in YAML, with task templates.
+//!
+//! Tasks that share a policy/priority/period block can name it once under
+//! `templates:` and reference it with `template:`:
+//!
+//! ```yaml
+//! templates:
+//!   camera_task:
+//!     policy: 1
+//!     priority: 70
+//!     period: 33333
+//!     deadline: 33333
+//!     metadata: { pipeline: camera }
+//!   front_camera:
+//!     template: camera_task   # templates may build on each other
+//!     node_id: node01
+//!
+//! tasks:
+//!   - name: cam_front_isp
+//!     template: front_camera
+//!     runtime: 4000
+//!   - name: cam_front_enc
+//!     template: front_camera
+//!     runtime: 6000
+//!     priority: 75            # overrides camera_task's 70
+//! ```
+//!
+//! A template sets any `TaskInfo` field except `name`.  [`parse`] expands
+//! the templates before the file is read as a `SchedInfo`, so the result is
+//! the same as writing every field inline, and validation sees no
+//! difference.  Precedence, highest first:
+//!
+//! | Source                              | Example above           |
+//! |-------------------------------------|-------------------------|
+//! | a field set on the task             | `priority: 75`          |
+//! | the task's template                 | `node_id: node01`       |
+//! | that template's template, and so on | `period: 33333`         |
+//! | the `TaskInfo` default              | `release_time: 0`       |
+//!
+//! A field replaces the template's value as a whole: a task that sets
+//! `metadata` does not inherit any of the template's keys.  An unknown
+//! template name or a template that (indirectly) references itself fails
+//! the file, and so does a file with more templates or tasks than the
+//! [input limits](crate::limits) allow.
+//!
+//! Processing chains are declared next to the tasks, each checked against
+//! its end-to-end deadline after placement (see
+//! [`chain`](crate::scheduler::chain)):
+//!
+//! ```yaml
+//! chains:
+//!   - name: front_camera
+//!     tasks: [cam_front_isp, cam_front_enc]   # precedence order
+//!     end_to_end_deadline_us: 20000
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde_yaml::{Mapping, Value};
+use thiserror::Error;
+
+use crate::limits::{InputLimits, Limit, LimitHits};
+use crate::proto::schedinfo_v1::SchedInfo;
+
+/// Top-level key holding the templates.
+pub const TEMPLATES_KEY: &str = "templates";
+
+/// Task (or template) key naming the template it builds on.
+pub const TEMPLATE_KEY: &str = "template";
+
+/// Why a task file could not be read.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum TaskfileError {
+    #[error("invalid task file: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    /// `referrer` is `task <name>` or `template <name>`.
+    #[error("{referrer} references unknown template '{template}'")]
+    UnknownTemplate { referrer: String, template: String },
+
+    /// Each template references the next; the last is the first again.
+    #[error("circular template reference: {}", chain.join(" -> "))]
+    CircularTemplate { chain: Vec<String> },
+
+    #[error("template '{name}' {reason}")]
+    InvalidTemplate { name: String, reason: String },
+
+    #[error("task file has {count} templates, more than the limit of {max}")]
+    TooManyTemplates { count: usize, max: usize },
+
+    #[error("task file has {count} tasks, more than the limit of {max}")]
+    TooManyTasks { count: usize, max: usize },
+}
+
+/// Read a task file, expanding its templates (see the module docs), with
+/// no more templates or tasks than `caps` allow; going over is counted in
+/// `hits`.
+pub fn parse(yaml: &str, caps: &InputLimits, hits: &LimitHits) -> Result<SchedInfo, TaskfileError> {
+    let mut doc: Value = serde_yaml::from_str(yaml)?;
+    check_sizes(&doc, caps, hits)?;
+    expand_templates(&mut doc)?;
+    Ok(serde_yaml::from_value(doc)?)
+}
+
+/// Refuse a file with more templates or tasks than `caps` allow, before
+/// anything is expanded.
+fn check_sizes(doc: &Value, caps: &InputLimits, hits: &LimitHits) -> Result<(), TaskfileError> {
+    let len = |key: &str| match doc.get(key) {
+        Some(Value::Mapping(m)) => m.len(),
+        Some(Value::Sequence(s)) => s.len(),
+        _ => 0,
+    };
+    let templates = len(TEMPLATES_KEY);
+    if hits.exceeded(Limit::Templates, templates, caps.max_templates) {
+        return Err(TaskfileError::TooManyTemplates {
+            count: templates,
+            max: caps.max_templates,
+        });
+    }
+    let tasks = len("tasks");
+    if hits.exceeded(Limit::Tasks, tasks, caps.max_tasks) {
+        return Err(TaskfileError::TooManyTasks {
+            count: tasks,
+            max: caps.max_tasks,
+        });
+    }
+    Ok(())
+}
+
+/// Replace every task's `template` reference in `doc` with the template's
+/// fields, and drop the `templates` section.
+pub fn expand_templates(doc: &mut Value) -> Result<(), TaskfileError> {
+    let Some(root) = doc.as_mapping_mut() else {
+        return Ok(());
+    };
+    let templates = match root.remove(TEMPLATES_KEY) {
+        None | Some(Value::Null) => Mapping::new(),
+        Some(Value::Mapping(m)) => m,
+        Some(_) => {
+            return Err(TaskfileError::InvalidTemplate {
+                name: TEMPLATES_KEY.to_string(),
+                reason: "section must be a mapping of names to fields".to_string(),
+            })
+        }
+    };
+
+    let mut resolved = BTreeMap::new();
+    for name in templates.keys() {
+        let name = key_name(name)?;
+        resolve(name, &templates, &mut resolved, &mut Vec::new())?;
+    }
+
+    let Some(Value::Sequence(tasks)) = root.get_mut("tasks") else {
+        return Ok(());
+    };
+    for task in tasks {
+        let Value::Mapping(fields) = task else {
+            continue;
+        };
+        let Some(reference) = fields.remove(TEMPLATE_KEY) else {
+            continue;
+        };
+        let referrer = format!(
+            "task {}",
+            fields
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or("<unnamed>")
+        );
+        let base = lookup(&reference, &referrer, &resolved)?;
+        *fields = overlay(base, fields);
+    }
+    Ok(())
+}
+
+/// Fully expanded fields of template `name`, memoised in `resolved`.
+/// `stack` holds the templates being resolved, outermost first.
+fn resolve<'a>(
+    name: &'a str,
+    templates: &'a Mapping,
+    resolved: &mut BTreeMap<String, Mapping>,
+    stack: &mut Vec<&'a str>,
+) -> Result<(), TaskfileError> {
+    if resolved.contains_key(name) {
+        return Ok(());
+    }
+    if let Some(start) = stack.iter().position(|&n| n == name) {
+        let mut chain: Vec<String> = stack[start..].iter().map(|n| n.to_string()).collect();
+        chain.push(name.to_string());
+        return Err(TaskfileError::CircularTemplate { chain });
+    }
+    let invalid = |reason: &str| TaskfileError::InvalidTemplate {
+        name: name.to_string(),
+        reason: reason.to_string(),
+    };
+    let empty = Mapping::new();
+    let fields = match templates.get(name) {
+        Some(Value::Mapping(m)) => m,
+        Some(Value::Null) | None => &empty,
+        Some(_) => return Err(invalid("must be a mapping of task fields")),
+    };
+    if fields.contains_key("name") {
+        return Err(invalid("cannot set a task name"));
+    }
+
+    let mut own = fields.clone();
+    own.remove(TEMPLATE_KEY);
+    let expanded = match templates.get(name).and_then(|t| t.get(TEMPLATE_KEY)) {
+        Some(parent) => {
+            let parent_name = parent
+                .as_str()
+                .ok_or_else(|| invalid("references a template by a non-string"))?;
+            stack.push(name);
+            // A missing parent is reported by lookup below.
+            if templates.contains_key(parent_name) {
+                resolve(parent_name, templates, resolved, stack)?;
+            }
+            stack.pop();
+            let base = lookup(parent, &format!("template {name}"), resolved)?;
+            overlay(base, &own)
+        }
+        None => own,
+    };
+    resolved.insert(name.to_string(), expanded);
+    Ok(())
+}
+
+/// The expanded template `reference` names.
+fn lookup<'a>(
+    reference: &Value,
+    referrer: &str,
+    resolved: &'a BTreeMap<String, Mapping>,
+) -> Result<&'a Mapping, TaskfileError> {
+    let name = reference.as_str().unwrap_or_default();
+    resolved
+        .get(name)
+        .ok_or_else(|| TaskfileError::UnknownTemplate {
+            referrer: referrer.to_string(),
+            template: name.to_string(),
+        })
+}
+
+/// `base` with every field of `fields` set over it.
+fn overlay(base: &Mapping, fields: &Mapping) -> Mapping {
+    let mut out = base.clone();
+    for (k, v) in fields {
+        out.insert(k.clone(), v.clone());
+    }
+    out
+}
+
+fn key_name(key: &Value) -> Result<&str, TaskfileError> {
+    key.as_str().ok_or_else(|| TaskfileError::InvalidTemplate {
+        name: format!("{key:?}"),
+        reason: "must be named by a string".to_string(),
+    })
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATED: &str = r#"
+workload_id: cameras
+templates:
+  camera_task:
+    policy: 1
+    priority: 70
+    period: 33333
+    deadline: 33333
+    max_dmiss: 2
+    metadata: { pipeline: camera, tier: vision }
+  front_camera:
+    template: camera_task
+    node_id: node01
+    cpu_affinity: 2
+tasks:
+  - name: cam_front_isp
+    template: front_camera
+    runtime: 4000
+  - name: cam_front_enc
+    template: front_camera
+    runtime: 6000
+    priority: 75
+    metadata: { pipeline: encoder }
+  - name: cam_rear_isp
+    template: camera_task
+    node_id: node02
+    runtime: 4000
+  - name: logger
+    policy: 0
+    period: 100000
+    runtime: 1000
+    deadline: 100000
+    node_id: node02
+"#;
+
+    const INLINE: &str = r#"
+workload_id: cameras
+tasks:
+  - name: cam_front_isp
+    policy: 1
+    priority: 70
+    period: 33333
+    deadline: 33333
+    max_dmiss: 2
+    metadata: { pipeline: camera, tier: vision }
+    node_id: node01
+    cpu_affinity: 2
+    runtime: 4000
+  - name: cam_front_enc
+    policy: 1
+    priority: 75
+    period: 33333
+    deadline: 33333
+    max_dmiss: 2
+    metadata: { pipeline: encoder }
+    node_id: node01
+    cpu_affinity: 2
+    runtime: 6000
+  - name: cam_rear_isp
+    policy: 1
+    priority: 70
+    period: 33333
+    deadline: 33333
+    max_dmiss: 2
+    metadata: { pipeline: camera, tier: vision }
+    node_id: node02
+    runtime: 4000
+  - name: logger
+    policy: 0
+    period: 100000
+    runtime: 1000
+    deadline: 100000
+    node_id: node02
+"#;
+
+    #[test]
+    fn templates_expand_to_the_inline_fields() {
+        let templated = parse(TEMPLATED, &InputLimits::default(), &LimitHits::default()).unwrap();
+        assert_eq!(
+            templated,
+            parse(INLINE, &InputLimits::default(), &LimitHits::default()).unwrap()
+        );
+        // And plain serde agrees on the inline file.
+        assert_eq!(
+            templated,
+            serde_yaml::from_str::<SchedInfo>(INLINE).unwrap()
+        );
+        assert_eq!(templated.tasks[1].priority, 75);
+        assert_eq!(templated.tasks[2].cpu_affinity, 0);
+    }
+
+    #[test]
+    fn chains_are_read_with_their_deadline() {
+        let req = parse(
+            r#"
+workload_id: perception
+tasks:
+  - { name: sensor, template: t }
+  - { name: fusion, template: t }
+templates:
+  t: { policy: 1, period: 10000, runtime: 2000, deadline: 10000 }
+chains:
+  - name: sense_to_fuse
+    tasks: [sensor, fusion]
+    end_to_end_deadline_us: 12000
+"#,
+            &InputLimits::default(),
+            &LimitHits::default(),
+        )
+        .unwrap();
+        let [chain] = req.chains.as_slice() else {
+            panic!("{:?}", req.chains)
+        };
+        assert_eq!(chain.name, "sense_to_fuse");
+        assert_eq!(chain.tasks, ["sensor", "fusion"]);
+        assert_eq!(chain.end_to_end_deadline_us, 12_000);
+    }
+
+    #[test]
+    fn unknown_and_circular_templates_are_refused() {
+        let unknown = "tasks:\n  - { name: t1, template: nope }\n";
+        assert_eq!(
+            parse(unknown, &InputLimits::default(), &LimitHits::default())
+                .unwrap_err()
+                .to_string(),
+            "task t1 references unknown template 'nope'"
+        );
+
+        let parent = "templates:\n  a: { template: b }\ntasks: []\n";
+        assert_eq!(
+            parse(parent, &InputLimits::default(), &LimitHits::default())
+                .unwrap_err()
+                .to_string(),
+            "template a references unknown template 'b'"
+        );
+
+        let circular = "
+templates:
+  a: { template: b, priority: 1 }
+  b: { template: c }
+  c: { template: a }
+tasks: []
+";
+        match parse(circular, &InputLimits::default(), &LimitHits::default()).unwrap_err() {
+            TaskfileError::CircularTemplate { chain } => {
+                assert_eq!(chain, ["a", "b", "c", "a"]);
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+
+        let named = "templates:\n  a: { name: x }\ntasks: []\n";
+        assert!(matches!(
+            parse(named, &InputLimits::default(), &LimitHits::default()).unwrap_err(),
+            TaskfileError::InvalidTemplate { .. }
+        ));
+    }
+
+    #[test]
+    fn oversized_files_are_refused_before_expansion() {
+        let caps = InputLimits::default()
+            .with_max_templates(2)
+            .with_max_tasks(3);
+        let doc = |yaml: &str| serde_yaml::from_str::<Value>(yaml).unwrap();
+        let hits = LimitHits::default();
+
+        // A chain of three templates, which expansion would recurse through.
+        let templates = "templates:\n  a: { template: b }\n  b: { template: c }\n  c: {}\n";
+        assert!(matches!(
+            check_sizes(&doc(templates), &caps, &hits),
+            Err(TaskfileError::TooManyTemplates { count: 3, max: 2 })
+        ));
+        let tasks = "tasks: [{ name: a }, { name: b }, { name: c }, { name: d }]\n";
+        assert!(matches!(
+            check_sizes(&doc(tasks), &caps, &hits),
+            Err(TaskfileError::TooManyTasks { count: 4, max: 3 })
+        ));
+        assert!(check_sizes(&doc(TEMPLATED), &caps.with_max_tasks(4), &hits).is_ok());
+        assert_eq!((hits.get(Limit::Templates), hits.get(Limit::Tasks)), (1, 1));
+
+        // parse applies the caps it is given.
+        let max = 3;
+        let huge = format!("tasks:\n{}", "  - { name: t }\n".repeat(max + 1));
+        assert_eq!(
+            parse(&huge, &caps, &hits).unwrap_err().to_string(),
+            format!(
+                "task file has {} tasks, more than the limit of {max}",
+                max + 1
+            )
+        );
+        assert_eq!(hits.get(Limit::Tasks), 2);
+    }
+}
@@ -0,0 +1,206 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Fixtures for tests written against the library (`testing` feature).
+//!
+//! | Helper                  | Replaces                                          |
+//! |-------------------------|---------------------------------------------------|
+//! | [`fake_nodes`]          | a temp-file YAML node configuration               |
+//! | [`example_nodes`]       | loading `examples/node_configurations.yaml`       |
+//! | [`fake_task`]           | a hand-written [`Task`] literal                   |
+//! | [`fake_sched_task`]     | a hand-written placed [`SchedTask`] literal       |
+//! | [`MockClock`]           | `std::thread::sleep` around a time window         |
+//!
+//! ```
+//! use std::sync::Arc;
+//! use timpani_o::scheduler::GlobalScheduler;
+//! use timpani_o::testing::{fake_nodes, fake_task};
+//!
+//! let nodes = fake_nodes(&[("n1", &[0, 1], 4096), ("n2", &[2, 3], 8192)]);
+//! let tasks = vec![fake_task("cam", "n2", 10_000, 2_000)];
+//! let map = GlobalScheduler::new(Arc::new(nodes))
+//!     .schedule(tasks, "target_node_priority")
+//!     .unwrap();
+//! assert_eq!(map["n2"][0].name, "cam");
+//! ```
+//!
+//! Time-dependent parts of Timpani-O take the current instant as an
+//! argument in an `_at` variant of each call — e.g.
+//! [`Debouncer::should_send_at`](crate::fault::debounce::Debouncer::should_send_at),
+//! [`ApplyWatchdog::expired_at`](crate::grpc::watchdog::ApplyWatchdog::expired_at)
+//! and
+//! [`SchedInfoServiceImpl::expire_workloads_at`](crate::grpc::schedinfo_service::SchedInfoServiceImpl::expire_workloads_at).
+//! [`MockClock`] supplies those instants.
+//!
+//! The crate's own unit tests use the same helpers.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::{NodeConfig, NodeConfigManager};
+use crate::task::{Micros, SchedTask, Task, DEFAULT_MAX_TASK_DURATION};
+
+/// `examples/node_configurations.yaml`: node01 (CPUs 2–3, 4096 MB,
+/// aarch64), node02 (CPUs 2–5, 8192 MB, aarch64) and node03 (CPUs 2, 3, 6,
+/// 7, 4096 MB, x86_64).
+pub const EXAMPLE_NODE_CONFIG: &str = include_str!("../examples/node_configurations.yaml");
+
+/// Workload ID [`fake_task`] gives its tasks.
+pub const FAKE_WORKLOAD: &str = "wl1";
+
+// ── Nodes ─────────────────────────────────────────────────────────────────────
+
+/// [`NodeConfig::default_config`] with the given CPUs and memory.
+pub fn fake_node(name: &str, cpus: &[u32], max_memory_mb: u64) -> NodeConfig {
+    NodeConfig {
+        available_cpus: cpus.to_vec(),
+        max_memory_mb,
+        ..NodeConfig::default_config(name)
+    }
+}
+
+/// A loaded configuration with one [`fake_node`] per
+/// `(name, cpus, max_memory_mb)`.
+pub fn fake_nodes(spec: &[(&str, &[u32], u64)]) -> NodeConfigManager {
+    NodeConfigManager::from_nodes(
+        spec.iter()
+            .map(|&(name, cpus, memory)| fake_node(name, cpus, memory))
+            .collect(),
+    )
+}
+
+/// [`EXAMPLE_NODE_CONFIG`], loaded and validated like a file.
+///
+/// ```
+/// let nodes = timpani_o::testing::example_nodes();
+/// assert_eq!(nodes.get_available_cpus("node03"), [2, 3, 6, 7]);
+/// ```
+pub fn example_nodes() -> NodeConfigManager {
+    let mut mgr = NodeConfigManager::new();
+    mgr.load_from_str(EXAMPLE_NODE_CONFIG, Path::new("node_configurations.yaml"))
+        .expect("example node configuration is valid");
+    mgr
+}
+
+// ── Tasks ─────────────────────────────────────────────────────────────────────
+
+/// An unplaced task of [`FAKE_WORKLOAD`] with its deadline at its period;
+/// `target` may be empty.  Everything else is [`Task::default`].
+///
+/// ```
+/// use timpani_o::task::{Micros, SchedPolicy, Task};
+/// use timpani_o::testing::fake_task;
+///
+/// let rt = Task {
+///     policy: SchedPolicy::Fifo,
+///     priority: 70,
+///     ..fake_task("ctl", "node01", 5_000, 500)
+/// };
+/// assert_eq!(rt.deadline_us, Micros(5_000));
+/// ```
+pub fn fake_task(name: &str, target: &str, period_us: u64, runtime_us: u64) -> Task {
+    Task {
+        name: name.to_string(),
+        workload_id: FAKE_WORKLOAD.to_string(),
+        target_node: target.to_string(),
+        period_us: Micros(period_us),
+        runtime_us: Micros(runtime_us),
+        deadline_us: Micros(period_us),
+        ..Default::default()
+    }
+}
+
+/// `task` as the scheduler would hand it to `node`, on `cpu`.
+pub fn placed(task: &Task, node: &str, cpu: u32) -> SchedTask {
+    let mut task = task.clone();
+    task.assigned_node = node.to_string();
+    task.assigned_cpu = Some(cpu);
+    SchedTask::from_task(&task, DEFAULT_MAX_TASK_DURATION).expect("fake task fits the wire limits")
+}
+
+/// A [`fake_task`] already [`placed`] on `node:cpu`.
+///
+/// ```
+/// use timpani_o::scheduler::feasibility::check_schedule;
+/// use timpani_o::scheduler::{PriorityOrdering, DEFAULT_UTILIZATION_EPSILON};
+/// use timpani_o::task::NodeSchedMap;
+/// use timpani_o::testing::fake_sched_task;
+///
+/// // 50 % + 40 % on one CPU is above the two-task Liu & Layland bound.
+/// let map: NodeSchedMap = [(
+///     "n1".to_string(),
+///     vec![
+///         fake_sched_task("a", "n1", 0, 10_000, 5_000),
+///         fake_sched_task("b", "n1", 0, 20_000, 8_000),
+///     ],
+/// )]
+/// .into();
+/// let warnings = check_schedule(&map, DEFAULT_UTILIZATION_EPSILON, PriorityOrdering::default());
+/// assert_eq!(warnings.len(), 1);
+/// ```
+pub fn fake_sched_task(
+    name: &str,
+    node: &str,
+    cpu: u32,
+    period_us: u64,
+    runtime_us: u64,
+) -> SchedTask {
+    placed(&fake_task(name, node, period_us, runtime_us), node, cpu)
+}
+
+// ── Clock ─────────────────────────────────────────────────────────────────────
+
+/// A clock that only moves when told to.  Clones share the same time.
+///
+/// ```
+/// use std::time::Duration;
+/// use timpani_o::fault::debounce::Debouncer;
+/// use timpani_o::testing::MockClock;
+///
+/// let clock = MockClock::new();
+/// let debouncer = Debouncer::new(Duration::from_secs(60));
+/// assert!(debouncer.should_send_at("k", clock.now()));
+/// clock.advance(Duration::from_secs(59));
+/// assert!(!debouncer.should_send_at("k", clock.now()));
+/// clock.advance(Duration::from_secs(1));
+/// assert!(debouncer.should_send_at("k", clock.now()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl MockClock {
+    /// A clock stopped at the real current instant.
+    pub fn new() -> Self {
+        Self::starting_at(Instant::now())
+    }
+
+    /// A clock stopped at `start`.
+    pub fn starting_at(start: Instant) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(start)),
+        }
+    }
+
+    /// The clock's current instant.
+    pub fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+
+    /// Move the clock forward by `by` and return the new instant.
+    pub fn advance(&self, by: Duration) -> Instant {
+        let mut now = self.now.lock().unwrap();
+        *now += by;
+        *now
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,552 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! In-process end-to-end harness (`testing` feature).
+//!
+//! [`TestCluster::start`] runs a real Timpani-O in the calling process —
+//! both gRPC servers on ephemeral loopback ports, node configuration built
+//! in memory — plus a recording Pullpiri `FaultService` on a third port that
+//! Timpani-O's production [`FaultClient`] talks to.  [`SimNode`] plays one
+//! Timpani-N against it over the real `NodeService` wire protocol:
+//!
+//! | Step           | RPC                                   | Effect                                       |
+//! |----------------|---------------------------------------|----------------------------------------------|
+//! | fetch          | `GetSchedInfo`, then `ReportApply`    | taken by the node's store, dry-run applied   |
+//! | fetch_streamed | `StreamSchedInfo`, then `ReportApply` | the same, only on a verified commit          |
+//! | sync           | `SyncTimer`                           | joins the start-time barrier                 |
+//! | dmiss          | `ReportDMiss`                         | forwarded by Timpani-O to the fault recorder |
+//!
+//! ```ignore
+//! let cluster = TestCluster::start(vec![NodeConfig::default_config("n1")]).await?;
+//! cluster.submit(sched_info).await?;
+//! let mut n1 = cluster.node("n1").await?;
+//! n1.fetch().await?;
+//! n1.sync().await?;
+//! ```
+//!
+//! A [`SimNode`] runs Timpani-N's own schedule handling: every answer goes
+//! into a `timpani_n` [`ScheduleStore`], which decides whether it is
+//! applied, a duplicate or stale, and an applied generation goes through
+//! the node's [`Applier`] in dry-run mode (every task `DRY_RUN`, nothing
+//! touched on the host).  The resulting report is sent back with
+//! `ReportApply`, as the real node does; a stale push is acknowledged.
+//! Only the transport is the harness's own: the steps are async and
+//! driven by the test instead of the node's polling loop (`tests/e2e.rs`
+//! also runs that loop).  The node announces deltas and placeholders but
+//! cannot stage a transactional push; [`SimNode::set_features`] plays an
+//! older build.
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use prost::Message;
+use timpani_n::apply::{errno, Applier, ApplyReport, NodeApplyInfo, SchedBackend};
+use timpani_n::capability::Capabilities;
+use timpani_n::proto::node_v1;
+use timpani_n::schedule::{PushStatus, SchedulePush, ScheduleStore};
+use tokio::net::TcpListener;
+use tokio::sync::{watch, Notify};
+use tonic::transport::server::TcpIncoming;
+use tonic::transport::{Channel, Server};
+use tonic::{Code, Request, Response, Status};
+
+use crate::config::{NodeConfig, NodeConfigManager};
+use crate::fault::FaultClient;
+use crate::grpc::events::EventLog;
+use crate::grpc::node_service::NodeServiceImpl;
+use crate::grpc::protocol::NodeFeatures;
+use crate::grpc::schedinfo_service::SchedInfoServiceImpl;
+use crate::grpc::stream::ScheduleAssembler;
+use crate::grpc::{new_workload_store, WorkloadStore};
+use crate::inject::FailureInjector;
+use crate::proto::schedinfo_v1::{
+    fault_service_server::{FaultService, FaultServiceServer},
+    node_service_client::NodeServiceClient,
+    node_service_server::NodeServiceServer,
+    sched_info_service_client::SchedInfoServiceClient,
+    sched_info_service_server::SchedInfoServiceServer,
+    ApplyReport as ProtoApplyReport, ClockSync, CpuSet, DeadlineMissInfo, FaultInfo,
+    ForeignLoadReport, NodeFeature, NodeResponse, NodeSchedRequest, NodeSchedResponse,
+    Response as ProtoResponse, SchedInfo, ScheduledTask, SyncRequest, SyncResponse, WorkloadRef,
+};
+
+/// `SyncTimer` barrier timeout used by the harness.
+pub const SYNC_TIMEOUT: Duration = Duration::from_secs(5);
+
+// ── Fault recorder ────────────────────────────────────────────────────────────
+
+/// Pullpiri stand-in: records every `NotifyFault` it receives.
+#[derive(Default)]
+struct FaultRecorder {
+    faults: Mutex<Vec<FaultInfo>>,
+    arrived: Notify,
+}
+
+#[tonic::async_trait]
+impl FaultService for Arc<FaultRecorder> {
+    async fn notify_fault(
+        &self,
+        request: Request<FaultInfo>,
+    ) -> Result<Response<ProtoResponse>, Status> {
+        self.faults.lock().unwrap().push(request.into_inner());
+        self.arrived.notify_waiters();
+        Ok(Response::new(ProtoResponse::default()))
+    }
+}
+
+// ── TestCluster ───────────────────────────────────────────────────────────────
+
+/// A running Timpani-O with a recording Pullpiri (see the module docs).
+///
+/// The servers shut down when the cluster is dropped.
+pub struct TestCluster {
+    sinfo_addr: SocketAddr,
+    node_addr: SocketAddr,
+    store: WorkloadStore,
+    faults: Arc<FaultRecorder>,
+    injector: Arc<FailureInjector>,
+    shutdown: watch::Sender<bool>,
+}
+
+impl TestCluster {
+    /// Start Timpani-O with `nodes` as its node configuration.
+    pub async fn start(nodes: Vec<NodeConfig>) -> Result<Self> {
+        let (shutdown, shutdown_rx) = watch::channel(false);
+        let faults = Arc::new(FaultRecorder::default());
+
+        let (fault_addr, fault_incoming) = bind().await?;
+        spawn_server(
+            Server::builder().add_service(FaultServiceServer::new(Arc::clone(&faults))),
+            fault_incoming,
+            shutdown_rx.clone(),
+        );
+
+        let config = Arc::new(NodeConfigManager::from_nodes(nodes));
+        let store = new_workload_store();
+        let notifier = FaultClient::connect_lazy(format!("http://{fault_addr}"))?;
+        let events = Arc::new(EventLog::default());
+        let sinfo = SchedInfoServiceImpl::new(
+            Arc::clone(&config),
+            Arc::clone(&store),
+            Arc::clone(&notifier),
+        )
+        .with_event_log(Arc::clone(&events));
+        let injector = Arc::new(FailureInjector::new());
+        let node = NodeServiceImpl::new(Arc::clone(&store), notifier, SYNC_TIMEOUT)
+            .with_failure_injector(Arc::clone(&injector))
+            .with_node_config(config)
+            .with_event_log(events);
+
+        let (sinfo_addr, sinfo_incoming) = bind().await?;
+        spawn_server(
+            Server::builder().add_service(SchedInfoServiceServer::new(sinfo)),
+            sinfo_incoming,
+            shutdown_rx.clone(),
+        );
+        let (node_addr, node_incoming) = bind().await?;
+        spawn_server(
+            Server::builder().add_service(NodeServiceServer::new(node)),
+            node_incoming,
+            shutdown_rx,
+        );
+
+        Ok(Self {
+            sinfo_addr,
+            node_addr,
+            store,
+            faults,
+            injector,
+            shutdown,
+        })
+    }
+
+    /// Address of the Pullpiri-facing `SchedInfoService`.
+    pub fn sinfo_addr(&self) -> SocketAddr {
+        self.sinfo_addr
+    }
+
+    /// Address of the node-facing `NodeService`.
+    pub fn node_addr(&self) -> SocketAddr {
+        self.node_addr
+    }
+
+    /// The server's workload store, for assertions on internal state.
+    pub fn store(&self) -> &WorkloadStore {
+        &self.store
+    }
+
+    /// The injector wired into the `NodeService` (see [`crate::inject`]).
+    pub fn injector(&self) -> &FailureInjector {
+        &self.injector
+    }
+
+    /// A fresh `SchedInfoService` client.
+    pub async fn client(&self) -> Result<SchedInfoServiceClient<Channel>> {
+        Ok(SchedInfoServiceClient::connect(format!("http://{}", self.sinfo_addr)).await?)
+    }
+
+    /// `AddSchedInfo`.
+    pub async fn submit(&self, info: SchedInfo) -> Result<ProtoResponse> {
+        Ok(self
+            .client()
+            .await?
+            .add_sched_info(info)
+            .await?
+            .into_inner())
+    }
+
+    /// `RemoveWorkload`.
+    pub async fn remove(&self, workload_id: &str) -> Result<ProtoResponse> {
+        let req = WorkloadRef {
+            workload_id: workload_id.to_string(),
+        };
+        Ok(self
+            .client()
+            .await?
+            .remove_workload(req)
+            .await?
+            .into_inner())
+    }
+
+    /// A simulated Timpani-N for `node_id`, connected but not yet fetched.
+    pub async fn node(&self, node_id: &str) -> Result<SimNode> {
+        let client = NodeServiceClient::connect(format!("http://{}", self.node_addr)).await?;
+        Ok(SimNode {
+            node_id: node_id.to_string(),
+            client,
+            store: ScheduleStore::new(),
+            workload_id: String::new(),
+            tasks: BTreeMap::new(),
+            report: None,
+            free_memory_mb: None,
+            online_cpus: None,
+            clock: None,
+            foreign_load: None,
+            features: NodeFeatures::LEGACY
+                .with(NodeFeature::Delta)
+                .with(NodeFeature::Placeholder),
+        })
+    }
+
+    /// Faults Pullpiri has received so far.
+    pub fn faults(&self) -> Vec<FaultInfo> {
+        self.faults.faults.lock().unwrap().clone()
+    }
+
+    /// Wait until Pullpiri has received at least `n` faults.
+    pub async fn wait_for_faults(&self, n: usize, timeout: Duration) -> Result<Vec<FaultInfo>> {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let arrived = self.faults.arrived.notified();
+                let faults = self.faults();
+                if faults.len() >= n {
+                    return faults;
+                }
+                arrived.await;
+            }
+        })
+        .await
+        .with_context(|| format!("fewer than {n} fault(s) within {timeout:?}"))
+    }
+}
+
+impl Drop for TestCluster {
+    fn drop(&mut self) {
+        let _ = self.shutdown.send(true);
+    }
+}
+
+/// Bind an ephemeral loopback port.
+async fn bind() -> Result<(SocketAddr, TcpIncoming)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let incoming =
+        TcpIncoming::from_listener(listener, true, None).map_err(|e| anyhow::anyhow!("{e}"))?;
+    Ok((addr, incoming))
+}
+
+fn spawn_server(
+    router: tonic::transport::server::Router,
+    incoming: TcpIncoming,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    tokio::spawn(async move {
+        let signal = async move {
+            while !*shutdown.borrow() {
+                if shutdown.changed().await.is_err() {
+                    break;
+                }
+            }
+        };
+        if let Err(e) = router.serve_with_incoming_shutdown(incoming, signal).await {
+            tracing::error!("testkit server error: {e}");
+        }
+    });
+}
+
+// ── SimNode ───────────────────────────────────────────────────────────────────
+
+/// Capabilities of the simulated node: RT-ready.
+const SIM_CAPS: Capabilities = Capabilities {
+    cap_sys_nice: true,
+    rtprio_limit: 99,
+    cpuset_writable: true,
+    required_prio: 1,
+};
+
+/// The backend behind the dry-run [`Applier`], which never calls it.
+struct NoBackend;
+
+impl SchedBackend for NoBackend {
+    fn join_cpuset(&self, _pid: i32, _cpus: u64) -> Result<(), i32> {
+        Err(errno::EPERM)
+    }
+    fn set_affinity(&self, _pid: i32, _cpus: u64) -> Result<(), i32> {
+        Err(errno::EPERM)
+    }
+    fn set_scheduler(&self, _pid: i32, _policy: i32, _priority: i32) -> Result<(), i32> {
+        Err(errno::EPERM)
+    }
+    fn get_affinity(&self, _pid: i32) -> Result<u64, i32> {
+        Err(errno::EPERM)
+    }
+    fn get_scheduler(&self, _pid: i32) -> Result<(i32, i32), i32> {
+        Err(errno::EPERM)
+    }
+}
+
+/// The same message in another crate's generated types: both are built
+/// from `node_service.proto`, so the encoding is shared.
+fn recode<T: Message, U: Message + Default>(msg: &T) -> U {
+    U::decode(msg.encode_to_vec().as_slice()).expect("same proto on both sides")
+}
+
+/// A dry-run Timpani-N (see the module docs).
+pub struct SimNode {
+    node_id: String,
+    client: NodeServiceClient<Channel>,
+    /// The node's schedule.
+    store: ScheduleStore,
+    workload_id: String,
+    /// The stored tasks as last delivered, with the fields the node does
+    /// not keep.
+    tasks: BTreeMap<String, ScheduledTask>,
+    /// The last report sent.
+    report: Option<ProtoApplyReport>,
+    free_memory_mb: Option<u64>,
+    online_cpus: Option<Vec<u32>>,
+    clock: Option<ClockSync>,
+    foreign_load: Option<ForeignLoadReport>,
+    features: NodeFeatures,
+}
+
+impl SimNode {
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// Generation of the last applied schedule (`None` before the first
+    /// fetch or after the workload was removed).
+    pub fn generation(&self) -> Option<u64> {
+        Some(self.store.generation()).filter(|&g| g > 0)
+    }
+
+    /// Instance epoch of the Timpani-O that numbered [`generation`](Self::generation).
+    pub fn instance_epoch(&self) -> Option<u64> {
+        Some(self.store.instance_epoch()).filter(|&e| e > 0)
+    }
+
+    /// The last `ReportApply` sent (`None` before the first apply).
+    pub fn last_report(&self) -> Option<&ProtoApplyReport> {
+        self.report.as_ref()
+    }
+
+    pub fn workload_id(&self) -> &str {
+        &self.workload_id
+    }
+
+    /// Talk to `cluster` from now on, keeping the applied schedule — a
+    /// Timpani-O restart as the node sees it.
+    pub async fn reconnect(&mut self, cluster: &TestCluster) -> Result<()> {
+        self.client = NodeServiceClient::connect(format!("http://{}", cluster.node_addr)).await?;
+        Ok(())
+    }
+
+    /// Applied task names, sorted.
+    pub fn task_names(&self) -> Vec<&str> {
+        self.tasks.keys().map(String::as_str).collect()
+    }
+
+    /// An applied task.
+    pub fn task(&self, name: &str) -> Option<&ScheduledTask> {
+        self.tasks.get(name)
+    }
+
+    /// Free memory sent with every later fetch (`None` = no report).
+    pub fn set_free_memory_mb(&mut self, free_mb: Option<u64>) {
+        self.free_memory_mb = free_mb;
+    }
+
+    /// Online CPUs sent with every later fetch (`None` = no report).
+    pub fn set_online_cpus(&mut self, cpus: Option<Vec<u32>>) {
+        self.online_cpus = cpus;
+    }
+
+    /// Clock report sent with every later fetch (`None` = no report).
+    pub fn set_clock(&mut self, clock: Option<ClockSync>) {
+        self.clock = clock;
+    }
+
+    /// Foreign-load report sent with every later fetch (`None` = no
+    /// report).
+    pub fn set_foreign_load(&mut self, report: Option<ForeignLoadReport>) {
+        self.foreign_load = report;
+    }
+
+    /// Protocol features announced with every later fetch
+    /// ([`NodeFeatures::LEGACY`] = a build older than the field).
+    pub fn set_features(&mut self, features: NodeFeatures) {
+        self.features = features;
+    }
+
+    /// `GetSchedInfo`, apply the answer and report it.
+    ///
+    /// Returns the raw response, or `None` when Timpani-O has no workload
+    /// for this node's tenant — the node then stops everything.
+    pub async fn fetch(&mut self) -> Result<Option<NodeSchedResponse>> {
+        let req = NodeSchedRequest {
+            node_id: self.node_id.clone(),
+            known_generation: self.generation(),
+            known_instance_epoch: self.instance_epoch(),
+            known_tasks: self.known_tasks(),
+            free_memory_mb: self.free_memory_mb,
+            online_cpus: self.online_cpus.clone().map(|cpus| CpuSet { cpus }),
+            clock: self.clock.clone(),
+            foreign_load: self.foreign_load.clone(),
+            features: self.features.bits(),
+        };
+        let resp = match self.client.get_sched_info(req).await {
+            Ok(resp) => resp.into_inner(),
+            Err(status) if status.code() == Code::NotFound => {
+                self.clear();
+                return Ok(None);
+            }
+            Err(status) => return Err(status.into()),
+        };
+
+        self.apply(&resp).await?;
+        Ok(Some(resp))
+    }
+
+    /// `StreamSchedInfo`, and apply and report the answer once its commit
+    /// verifies.
+    ///
+    /// A dropped or corrupted batch, or a stream that does not commit within
+    /// `timeout`, returns an error and leaves the applied schedule untouched.
+    pub async fn fetch_streamed(&mut self, timeout: Duration) -> Result<Option<NodeSchedResponse>> {
+        let req = NodeSchedRequest {
+            node_id: self.node_id.clone(),
+            known_generation: self.generation(),
+            known_instance_epoch: self.instance_epoch(),
+            known_tasks: self.known_tasks(),
+            free_memory_mb: self.free_memory_mb,
+            online_cpus: self.online_cpus.clone().map(|cpus| CpuSet { cpus }),
+            clock: self.clock.clone(),
+            foreign_load: self.foreign_load.clone(),
+            features: self.features.bits(),
+        };
+        let mut stream = match self.client.stream_sched_info(req).await {
+            Ok(resp) => resp.into_inner(),
+            Err(status) if status.code() == Code::NotFound => {
+                self.clear();
+                return Ok(None);
+            }
+            Err(status) => return Err(status.into()),
+        };
+
+        let mut assembler = ScheduleAssembler::new();
+        let resp = tokio::time::timeout(timeout, async {
+            while let Some(chunk) = stream.message().await? {
+                if let Some(resp) = assembler.push(chunk)? {
+                    return Ok::<_, anyhow::Error>(resp);
+                }
+            }
+            Err(assembler.incomplete().into())
+        })
+        .await
+        .with_context(|| format!("[{}] no commit within {timeout:?}", self.node_id))?
+        .with_context(|| format!("[{}] streamed schedule discarded", self.node_id))?;
+
+        self.apply(&resp).await?;
+        Ok(Some(resp))
+    }
+
+    /// `SyncTimer` — blocks until every active node has synced.
+    pub async fn sync(&mut self) -> Result<SyncResponse> {
+        let req = SyncRequest {
+            node_id: self.node_id.clone(),
+        };
+        Ok(self.client.sync_timer(req).await?.into_inner())
+    }
+
+    fn known_tasks(&self) -> Vec<String> {
+        self.store.tasks().iter().map(|t| t.name.clone()).collect()
+    }
+
+    /// Take `resp` into the store as Timpani-N does, dry-run apply it if
+    /// applied, and send the report.
+    async fn apply(&mut self, resp: &NodeSchedResponse) -> Result<()> {
+        if !resp.full && !resp.resync && self.generation().is_none() {
+            bail!(
+                "[{}] delta received without a base generation",
+                self.node_id
+            );
+        }
+        let push = SchedulePush::from(recode::<_, node_v1::NodeSchedResponse>(resp));
+        let report = match self.store.push(push) {
+            PushStatus::Applied => {
+                let stored = self.known_tasks();
+                self.tasks.retain(|name, _| stored.contains(name));
+                for t in resp.tasks.iter().chain(&resp.modified_tasks) {
+                    self.tasks.insert(t.name.clone(), t.clone());
+                }
+                self.workload_id.clone_from(&resp.workload_id);
+                Applier::new(&NoBackend, SIM_CAPS)
+                    .with_dry_run(true)
+                    .apply_all(
+                        &self.node_id,
+                        resp.generation,
+                        self.store.tasks(),
+                        NodeApplyInfo::from_capabilities(&SIM_CAPS),
+                    )
+            }
+            PushStatus::Duplicate => return Ok(()),
+            status => ApplyReport::ack(&self.node_id, resp.generation, status),
+        };
+        let report: ProtoApplyReport = recode(&node_v1::ApplyReport::from(&report));
+        self.client.report_apply(report.clone()).await?;
+        self.report = Some(report);
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.store = ScheduleStore::new();
+        self.tasks.clear();
+        self.workload_id.clear();
+    }
+
+    /// `ReportDMiss` for one of this node's tasks.
+    pub async fn report_dmiss(&mut self, task_name: &str) -> Result<NodeResponse> {
+        let req = DeadlineMissInfo {
+            node_id: self.node_id.clone(),
+            task_name: task_name.to_string(),
+            drift: None,
+        };
+        Ok(self.client.report_d_miss(req).await?.into_inner())
+    }
+}
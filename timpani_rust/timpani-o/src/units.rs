@@ -0,0 +1,147 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Human-readable durations for logs, reports, error messages and the CLI.
+//!
+//! Tasks carry µs and the scheduler works in ns; neither is pleasant to
+//! read once a period reaches seconds.  [`fmt_duration_us`] picks the
+//! largest unit that keeps the value at or above one:
+//!
+//! | Value (µs)  | Output    |
+//! |-------------|-----------|
+//! | `100`       | `100 µs`  |
+//! | `999`       | `999 µs`  |
+//! | `1_000`     | `1 ms`    |
+//! | `1_500`     | `1.5 ms`  |
+//! | `2_000_000` | `2 s`     |
+//!
+//! Fractions are rounded to the [`DurationStyle::precision`] and trailing
+//! zeros dropped.  With [`DurationStyle::raw`] (`timpani-o --raw-units`)
+//! every duration is the plain integer number of microseconds instead, for
+//! scripts that parse the output.
+//!
+//! Reports and CLI output take the [`DurationStyle`] from their caller.  Log
+//! fields and error `Display` impls have no caller to ask, so they always
+//! use the default style through [`fmt_duration_us`] / [`fmt_duration_ns`].
+
+/// Fraction digits when none is configured.
+pub const DEFAULT_PRECISION: usize = 1;
+
+const US_PER_MS: u64 = 1_000;
+const US_PER_S: u64 = 1_000_000;
+
+/// How durations are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurationStyle {
+    /// At most this many fraction digits (`1` → `1.5 ms`).
+    pub precision: usize,
+    /// Integer microseconds, no unit.
+    pub raw: bool,
+}
+
+impl Default for DurationStyle {
+    fn default() -> Self {
+        Self {
+            precision: DEFAULT_PRECISION,
+            raw: false,
+        }
+    }
+}
+
+impl DurationStyle {
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    pub fn with_raw(mut self, raw: bool) -> Self {
+        self.raw = raw;
+        self
+    }
+
+    /// `us` microseconds in this style.
+    pub fn format_us(self, us: u64) -> String {
+        if self.raw {
+            return us.to_string();
+        }
+        if us < US_PER_MS {
+            return format!("{us} µs");
+        }
+        let ms = self.round(us, US_PER_MS);
+        // 999.96 ms rounds to "1000 ms"; say "1 s" instead.
+        if us < US_PER_S && ms != "1000" {
+            return format!("{ms} ms");
+        }
+        format!("{} s", self.round(us, US_PER_S))
+    }
+
+    /// `ns` nanoseconds, truncated to whole microseconds, in this style.
+    pub fn format_ns(self, ns: u64) -> String {
+        self.format_us(ns / 1_000)
+    }
+
+    /// `value / unit` rounded to `precision` digits, trailing zeros dropped.
+    fn round(self, value: u64, unit: u64) -> String {
+        let s = format!("{:.*}", self.precision, value as f64 / unit as f64);
+        if s.contains('.') {
+            s.trim_end_matches('0').trim_end_matches('.').to_string()
+        } else {
+            s
+        }
+    }
+}
+
+/// `us` microseconds in the default style, e.g. `1.5 ms`.
+pub fn fmt_duration_us(us: u64) -> String {
+    DurationStyle::default().format_us(us)
+}
+
+/// `ns` nanoseconds, truncated to whole microseconds, in the default style.
+pub fn fmt_duration_ns(ns: u64) -> String {
+    DurationStyle::default().format_ns(ns)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_largest_unit_at_or_above_one() {
+        let human = DurationStyle::default();
+        for (us, expected) in [
+            (0, "0 µs"),
+            (100, "100 µs"),
+            (999, "999 µs"),
+            (1_000, "1 ms"),
+            (1_500, "1.5 ms"),
+            (10_000, "10 ms"),
+            (999_000, "999 ms"),
+            (999_999, "1 s"),
+            (1_000_000, "1 s"),
+            (2_000_000, "2 s"),
+            (2_240_000, "2.2 s"),
+        ] {
+            assert_eq!(human.format_us(us), expected, "{us} µs");
+        }
+    }
+
+    #[test]
+    fn precision_is_configurable() {
+        let style = DurationStyle::default().with_precision(3);
+        assert_eq!(style.format_us(1_234), "1.234 ms");
+        assert_eq!(style.format_us(1_200), "1.2 ms");
+        assert_eq!(style.with_precision(0).format_us(1_500), "2 ms");
+    }
+
+    #[test]
+    fn raw_mode_passes_integer_microseconds_through() {
+        let raw = DurationStyle::default().with_raw(true);
+        for us in [0, 999, 1_000, 1_500, 2_000_000] {
+            assert_eq!(raw.format_us(us), us.to_string());
+        }
+    }
+}
@@ -0,0 +1,48 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Per-phase allocation counts, with `CountingAllocator` installed.
+//!
+//! `cargo test -p timpani-o --features alloc-stats --test alloc_profile`
+
+use std::sync::Arc;
+
+use timpani_o::prelude::*;
+use timpani_o::scheduler::timing::CountingAllocator;
+use timpani_o::scheduler::Phase;
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+#[test]
+fn every_phase_reports_its_allocations() {
+    let nodes = (0..8)
+        .map(|i| NodeConfig {
+            available_cpus: (0..4).collect(),
+            ..NodeConfig::default_config(format!("node{i}"))
+        })
+        .collect();
+    let scheduler = GlobalScheduler::new(Arc::new(NodeConfigManager::from_nodes(nodes)));
+    let tasks: Vec<Task> = (0..500)
+        .map(|i| Task {
+            name: format!("t{i:03}"),
+            workload_id: "bulk".into(),
+            period_us: Micros(10_000),
+            runtime_us: Micros(100),
+            deadline_us: Micros(10_000),
+            ..Default::default()
+        })
+        .collect();
+    let opts = ScheduleOptions::default().with_algorithm(SchedAlgorithm::LeastLoaded);
+    let (_, timings) = scheduler.schedule_profiled(None, tasks, &opts).unwrap();
+
+    for phase in Phase::ALL {
+        assert!(timings.allocations(phase).is_some(), "{phase}");
+    }
+    // The placement loop allocates per task; building the map once per
+    // task as well.
+    assert!(timings.allocations(Phase::Algorithm).unwrap() >= 500);
+    assert!(timings.allocations(Phase::BuildSchedMap).unwrap() >= 500);
+}
@@ -0,0 +1,76 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! The scheduler core used as a library, without gRPC.
+//!
+//! Only touches `core` modules, so it also builds — without protoc — as
+//! `cargo test -p timpani-o --no-default-features --features core --test core_only`.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use timpani_o::prelude::*;
+use timpani_o::report::to_dot;
+use timpani_o::scheduler::feasibility::check_schedule;
+use timpani_o::scheduler::{StaggerStrategy, DEFAULT_UTILIZATION_EPSILON};
+use timpani_o::units::DurationStyle;
+
+fn task(name: &str, period_us: u64, runtime_us: u64) -> Task {
+    Task {
+        name: name.into(),
+        workload_id: "plan".into(),
+        period_us: Micros(period_us),
+        runtime_us: Micros(runtime_us),
+        deadline_us: Micros(period_us),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn plan_a_workload_offline() {
+    let mut config = NodeConfigManager::new();
+    config
+        .load_from_file(Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/node_configurations.yaml"
+        )))
+        .unwrap();
+    let config = Arc::new(config);
+    let tasks = vec![
+        task("sensor", 10_000, 2_000),
+        task("fusion", 20_000, 6_000),
+        task("logger", 100_000, 5_000),
+    ];
+
+    let mut hyper = HyperperiodManager::new();
+    let info = hyper.calculate_hyperperiod("plan", &tasks).unwrap();
+    assert_eq!(info.hyperperiod_us, Micros(100_000));
+
+    let opts = ScheduleOptions::default()
+        .with_algorithm(SchedAlgorithm::LeastLoaded)
+        .with_release_stagger(StaggerStrategy::EvenWithinPeriod);
+    let map = GlobalScheduler::new(Arc::clone(&config))
+        .schedule_with_options(tasks, &opts)
+        .unwrap();
+    assert_eq!(map.values().map(Vec::len).sum::<usize>(), 3);
+    assert!(check_schedule(&map, DEFAULT_UTILIZATION_EPSILON, opts.priority_ordering).is_empty());
+    assert!(to_dot(&map, &config, DurationStyle::default()).starts_with("digraph timpani {"));
+}
+
+#[test]
+fn errors_from_the_prelude_need_a_wildcard_arm() {
+    let config = Arc::new(NodeConfigManager::new());
+    let err = GlobalScheduler::new(config)
+        .schedule(vec![task("sensor", 10_000, 2_000)], "round_robin")
+        .unwrap_err();
+    let code = match &err {
+        SchedulerError::UnknownAlgorithm(name) => {
+            assert_eq!(name, "round_robin");
+            err.code()
+        }
+        _ => panic!("unexpected error: {err}"),
+    };
+    assert_eq!(code, ErrorCode::UnknownAlgorithm);
+}
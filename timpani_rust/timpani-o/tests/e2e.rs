@@ -0,0 +1,370 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Whole-pipeline scenario over real gRPC, driven by `timpani_o::testkit`:
+//! request → schedule → node fetch (dry-run apply, reported back) → sync →
+//! fault back.
+//!
+//! Requires the `testing` feature:
+//! `cargo test -p timpani-o --features testing --test e2e`
+
+use std::time::Duration;
+
+use timpani_o::config::NodeConfig;
+use timpani_o::grpc::protocol::NodeFeatures;
+use timpani_o::grpc::DEFAULT_TENANT;
+use timpani_o::inject::InjectionPoint;
+use timpani_o::proto::schedinfo_v1::{FaultType, SchedInfo, TaskInfo};
+use timpani_o::testkit::TestCluster;
+
+use timpani_n::agent::NodeAgent;
+use timpani_n::backend::LinuxBackend;
+use timpani_n::capability::Capabilities;
+use timpani_n::resolve::NoSystemd;
+use timpani_n::upstream::GrpcUpstream;
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+fn task(name: &str, node: &str, runtime: i32) -> TaskInfo {
+    TaskInfo {
+        name: name.into(),
+        node_id: node.into(),
+        priority: 50,
+        policy: 1,
+        period: 10_000,
+        runtime,
+        deadline: 10_000,
+        max_dmiss: 3,
+        ..Default::default()
+    }
+}
+
+fn workload(tasks: Vec<TaskInfo>) -> SchedInfo {
+    SchedInfo {
+        workload_id: "wl_e2e".into(),
+        tasks,
+        ..Default::default()
+    }
+}
+
+// ── Scenario ──────────────────────────────────────────────────────────────────
+
+/// Deploy, update, deadline-miss fault, removal — each observed from the
+/// nodes' side of the wire.
+#[tokio::test]
+async fn deploy_update_fault_and_remove() {
+    let cluster = TestCluster::start(vec![
+        NodeConfig::default_config("n1"),
+        NodeConfig::default_config("n2"),
+    ])
+    .await
+    .unwrap();
+    let mut n1 = cluster.node("n1").await.unwrap();
+    let mut n2 = cluster.node("n2").await.unwrap();
+
+    // ── Deploy ────────────────────────────────────────────────────────────────
+    let resp = cluster
+        .submit(workload(vec![
+            task("t1", "n1", 1_000),
+            task("t2", "n2", 1_000),
+        ]))
+        .await
+        .unwrap();
+    assert_eq!(resp.status, 0, "{resp:?}");
+
+    let first = n1.fetch().await.unwrap().unwrap();
+    assert!(first.full);
+    n2.fetch().await.unwrap();
+    assert_eq!(n1.task_names(), ["t1"]);
+    assert_eq!(n2.task_names(), ["t2"]);
+    assert_eq!(n1.generation(), Some(1));
+
+    let (s1, s2) = tokio::join!(n1.sync(), n2.sync());
+    let (s1, s2) = (s1.unwrap(), s2.unwrap());
+    assert!(s1.ack && s2.ack);
+    assert_eq!(
+        (s1.start_time_sec, s1.start_time_nsec),
+        (s2.start_time_sec, s2.start_time_nsec)
+    );
+
+    // ── Update: t1 changes, t3 joins n1, t2 leaves n2 ─────────────────────────
+    let resp = cluster
+        .submit(workload(vec![
+            task("t1", "n1", 2_000),
+            task("t3", "n1", 500),
+        ]))
+        .await
+        .unwrap();
+    assert_eq!(resp.status, 0, "{resp:?}");
+
+    let delta = n1.fetch().await.unwrap().unwrap();
+    assert!(!delta.full);
+    assert_eq!(delta.generation, 2);
+    assert_eq!(n1.task_names(), ["t1", "t3"]);
+    assert_eq!(n1.task("t1").unwrap().runtime_us, 2_000);
+    {
+        // n1 applied generation 2 as the node does, and reported it.
+        let store = cluster.store().lock().await;
+        let report = &store[DEFAULT_TENANT].apply_reports["n1"];
+        assert_eq!(Some(report), n1.last_report());
+        assert_eq!(report.generation, 2);
+        let dry_run = timpani_o::proto::schedinfo_v1::ApplyStatus::DryRun as i32;
+        let statuses: Vec<(&str, i32)> = report
+            .tasks
+            .iter()
+            .map(|t| (t.task_name.as_str(), t.status))
+            .collect();
+        assert_eq!(statuses, [("t1", dry_run), ("t3", dry_run)]);
+    }
+
+    let delta = n2.fetch().await.unwrap().unwrap();
+    assert!(!delta.full);
+    assert_eq!(delta.removed_tasks, ["t2"]);
+    assert!(n2.task_names().is_empty());
+
+    // Only n1 is active now, so it releases the new barrier alone.
+    assert!(n1.sync().await.unwrap().ack);
+
+    // ── Deadline miss → Pullpiri ──────────────────────────────────────────────
+    let resp = n1.report_dmiss("t3").await.unwrap();
+    assert_eq!(resp.status, 0, "{}", resp.error_message);
+
+    let faults = cluster
+        .wait_for_faults(1, Duration::from_secs(5))
+        .await
+        .unwrap();
+    let dmiss = faults
+        .iter()
+        .find(|f| f.r#type == FaultType::Dmiss as i32)
+        .expect("deadline miss forwarded to Pullpiri");
+    assert_eq!(dmiss.workload_id, "wl_e2e");
+    assert_eq!(dmiss.node_id, "n1");
+    assert_eq!(dmiss.task_name, "t3");
+
+    // ── Removal ───────────────────────────────────────────────────────────────
+    let resp = cluster.remove("wl_e2e").await.unwrap();
+    assert_eq!(resp.status, 0, "{resp:?}");
+
+    assert!(n1.fetch().await.unwrap().is_none());
+    assert!(n1.task_names().is_empty());
+    assert_eq!(n1.generation(), None);
+    assert!(cluster.store().lock().await.is_empty());
+}
+
+/// A 1,000-task node pulls its schedule in batches; when one batch of the
+/// next generation is lost, the node keeps running the old schedule.
+#[tokio::test]
+async fn streamed_schedule_survives_a_dropped_batch() {
+    let cluster = TestCluster::start(vec![NodeConfig::default_config("n1")])
+        .await
+        .unwrap();
+    let mut n1 = cluster.node("n1").await.unwrap();
+    let many = |runtime| {
+        (0..1_000)
+            .map(|i| task(&format!("t{i:04}"), "n1", runtime))
+            .collect()
+    };
+
+    let resp = cluster.submit(workload(many(1))).await.unwrap();
+    assert_eq!(resp.status, 0, "{resp:?}");
+    let first = n1
+        .fetch_streamed(Duration::from_secs(5))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(first.full);
+    assert_eq!(n1.task_names().len(), 1_000);
+    assert_eq!(n1.generation(), Some(1));
+
+    // Generation 2 changes every task; the third of its five batches is lost.
+    let resp = cluster.submit(workload(many(2))).await.unwrap();
+    assert_eq!(resp.status, 0, "{resp:?}");
+    let sent = cluster.injector().calls(InjectionPoint::NodeStreamBatch);
+    cluster
+        .injector()
+        .fail_nth(InjectionPoint::NodeStreamBatch, sent + 3);
+    let err = n1.fetch_streamed(Duration::from_secs(5)).await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("batch 3 arrived where batch 2"),
+        "{err:#}"
+    );
+    assert_eq!(n1.generation(), Some(1));
+    assert_eq!(n1.task_names().len(), 1_000);
+    assert!(n1
+        .task_names()
+        .iter()
+        .all(|n| n1.task(n).unwrap().runtime_us == 1));
+
+    // A clean retry gets the delta.
+    let delta = n1
+        .fetch_streamed(Duration::from_secs(5))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!delta.full);
+    assert_eq!(delta.modified_tasks.len(), 1_000);
+    assert_eq!(n1.generation(), Some(2));
+    assert!(n1
+        .task_names()
+        .iter()
+        .all(|n| n1.task(n).unwrap().runtime_us == 2));
+}
+
+/// A current and a legacy node in one delivery: each gets the payload its
+/// build understands.
+#[tokio::test]
+async fn legacy_node_gets_full_pushes_without_placeholders() {
+    let cluster = TestCluster::start(vec![
+        NodeConfig::default_config("n1"),
+        NodeConfig::default_config("n2"),
+    ])
+    .await
+    .unwrap();
+    let mut n1 = cluster.node("n1").await.unwrap();
+    let mut n2 = cluster.node("n2").await.unwrap();
+    n2.set_features(NodeFeatures::LEGACY);
+    let spare = |node: &str| TaskInfo {
+        placeholder: true,
+        ..task(&format!("spare_{node}"), node, 1_000)
+    };
+
+    let resp = cluster
+        .submit(workload(vec![
+            task("t1", "n1", 1_000),
+            spare("n1"),
+            task("t2", "n2", 1_000),
+            spare("n2"),
+        ]))
+        .await
+        .unwrap();
+    assert_eq!(resp.status, 0, "{resp:?}");
+    n1.fetch().await.unwrap();
+    let first = n2.fetch().await.unwrap().unwrap();
+    assert!(first.full);
+    assert_eq!(n1.task_names(), ["spare_n1", "t1"]);
+    assert_eq!(n2.task_names(), ["t2"]);
+
+    let resp = cluster
+        .submit(workload(vec![
+            task("t1", "n1", 2_000),
+            spare("n1"),
+            task("t2", "n2", 2_000),
+            spare("n2"),
+        ]))
+        .await
+        .unwrap();
+    assert_eq!(resp.status, 0, "{resp:?}");
+    let current = n1.fetch().await.unwrap().unwrap();
+    assert!(!current.full);
+    assert_eq!(current.modified_tasks.len(), 1);
+    let legacy = n2.fetch().await.unwrap().unwrap();
+    assert!(legacy.full);
+    assert_eq!(legacy.generation, 2);
+    assert_eq!(n2.task_names(), ["t2"]);
+    assert_eq!(n2.task("t2").unwrap().runtime_us, 2_000);
+
+    let store = cluster.store().lock().await;
+    let protocols = &store[DEFAULT_TENANT].protocols;
+    assert_eq!(protocols["n1"].to_string(), "delta,placeholder");
+    assert_eq!(protocols["n2"], NodeFeatures::LEGACY);
+}
+
+/// A node keeps its schedule across a Timpani-O restart; the new instance
+/// numbers its generations from 1 again, and although the node's generation
+/// matches, it gets the resubmitted schedule in full.
+#[tokio::test]
+async fn restarted_timpani_o_resyncs_a_node_whose_generation_matches() {
+    let nodes = || vec![NodeConfig::default_config("n1")];
+    let before = TestCluster::start(nodes()).await.unwrap();
+    let mut n1 = before.node("n1").await.unwrap();
+    before
+        .submit(workload(vec![task("t1", "n1", 1_000)]))
+        .await
+        .unwrap();
+    before
+        .submit(workload(vec![
+            task("t1", "n1", 2_000),
+            task("t2", "n1", 500),
+        ]))
+        .await
+        .unwrap();
+    n1.fetch().await.unwrap();
+    assert_eq!(n1.generation(), Some(2));
+    let old_epoch = n1.instance_epoch().unwrap();
+    drop(before);
+
+    // ── Restart; Pullpiri resubmits a newer workload ──────────────────────────
+    let after = TestCluster::start(nodes()).await.unwrap();
+    after
+        .submit(workload(vec![task("t1", "n1", 3_000)]))
+        .await
+        .unwrap();
+    after
+        .submit(workload(vec![
+            task("t1", "n1", 3_000),
+            task("t3", "n1", 500),
+        ]))
+        .await
+        .unwrap();
+    n1.reconnect(&after).await.unwrap();
+
+    let resync = n1.fetch().await.unwrap().unwrap();
+    assert!(resync.resync && resync.full);
+    assert_eq!(resync.generation, 2);
+    assert!(resync.instance_epoch > old_epoch);
+    assert_eq!(n1.task_names(), ["t1", "t3"]);
+    assert_eq!(n1.task("t1").unwrap().runtime_us, 3_000);
+
+    // Back to deltas against the new instance.
+    let current = n1.fetch().await.unwrap().unwrap();
+    assert!(!current.resync && !current.full);
+    assert_eq!(n1.instance_epoch(), Some(resync.instance_epoch));
+}
+
+/// The Timpani-N agent over its own gRPC client: it pulls its schedule,
+/// applies it and reports the outcome, which Timpani-O keeps.  No process
+/// here runs the tasks, so each is reported as not found.
+#[tokio::test]
+async fn timpani_n_reports_what_it_applied() {
+    let cluster = TestCluster::start(vec![NodeConfig::default_config("n1")])
+        .await
+        .unwrap();
+    cluster
+        .submit(workload(vec![
+            task("t1", "n1", 1_000),
+            task("t2", "n1", 500),
+        ]))
+        .await
+        .unwrap();
+
+    let addr = cluster.node_addr();
+    let (generation, known) = tokio::task::spawn_blocking(move || {
+        let config = timpani_n::config::Config {
+            node_id: "n1".into(),
+            ..Default::default()
+        };
+        let backend = LinuxBackend::new();
+        let mut agent = NodeAgent::new(&config, Capabilities::default(), &backend, &NoSystemd)
+            .with_processes(Vec::new);
+        let mut upstream = GrpcUpstream::new(&addr.ip().to_string(), addr.port()).unwrap();
+        agent.poll(&mut upstream).unwrap();
+        (agent.store().generation(), agent.request().known_tasks)
+    })
+    .await
+    .unwrap();
+    assert_eq!(generation, 1);
+    assert_eq!(known, ["t1", "t2"]);
+
+    let store = cluster.store().lock().await;
+    let report = &store[DEFAULT_TENANT].apply_reports["n1"];
+    assert_eq!(report.generation, 1);
+    let statuses: Vec<(&str, i32)> = report
+        .tasks
+        .iter()
+        .map(|t| (t.task_name.as_str(), t.status))
+        .collect();
+    let not_found = timpani_o::proto::schedinfo_v1::ApplyStatus::PidNotFound as i32;
+    assert_eq!(statuses, [("t1", not_found), ("t2", not_found)]);
+}
@@ -0,0 +1,182 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Pipeline failure scenarios driven by `timpani_o::inject`.
+//!
+//! Requires the `testing` feature:
+//! `cargo test -p timpani-o --features testing --test failure_injection`
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tempfile::NamedTempFile;
+use tonic::Request;
+
+use timpani_o::config::NodeConfigManager;
+use timpani_o::fault::{FaultError, FaultNotification, FaultNotifier};
+use timpani_o::grpc::{
+    new_workload_store, node_service::NodeServiceImpl, schedinfo_service::SchedInfoServiceImpl,
+};
+use timpani_o::inject::{FailureInjector, InjectingNotifier, InjectionPoint};
+use timpani_o::proto::schedinfo_v1::{
+    node_service_server::NodeService, sched_info_service_server::SchedInfoService,
+    DeadlineMissInfo, NodeSchedRequest, SchedInfo, TaskInfo,
+};
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+const NODES_YAML: &str = r#"
+nodes:
+  n1:
+    available_cpus: [0, 1]
+    max_memory_mb: 4096
+"#;
+
+/// Records every notification it is asked to deliver.
+#[derive(Default)]
+struct RecordingNotifier {
+    delivered: Mutex<Vec<FaultNotification>>,
+}
+
+#[tonic::async_trait]
+impl FaultNotifier for RecordingNotifier {
+    async fn notify_fault(&self, info: FaultNotification) -> Result<(), FaultError> {
+        self.delivered.lock().unwrap().push(info);
+        Ok(())
+    }
+}
+
+fn config_file() -> NamedTempFile {
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(NODES_YAML.as_bytes()).unwrap();
+    f
+}
+
+fn task(name: &str) -> TaskInfo {
+    TaskInfo {
+        name: name.into(),
+        node_id: "n1".into(),
+        priority: 50,
+        policy: 1,
+        period: 10_000,
+        runtime: 1_000,
+        deadline: 10_000,
+        max_dmiss: 3,
+        ..Default::default()
+    }
+}
+
+/// Both services sharing one store, with `injector` wired into the node
+/// service and in front of `notifier`.
+fn services(
+    injector: &Arc<FailureInjector>,
+    notifier: Arc<RecordingNotifier>,
+) -> (SchedInfoServiceImpl, NodeServiceImpl) {
+    let file = config_file();
+    let mut config = NodeConfigManager::new();
+    config.load_from_file(file.path()).unwrap();
+    let config = Arc::new(config);
+
+    let notifier = InjectingNotifier::wrap(notifier, Arc::clone(injector));
+    let store = new_workload_store();
+    let sched = SchedInfoServiceImpl::new(config, Arc::clone(&store), Arc::clone(&notifier));
+    let node = NodeServiceImpl::new(store, notifier, Duration::from_secs(5))
+        .with_failure_injector(Arc::clone(injector));
+    (sched, node)
+}
+
+async fn submit(sched: &SchedInfoServiceImpl) {
+    let resp = sched
+        .add_sched_info(Request::new(SchedInfo {
+            workload_id: "wl".into(),
+            tasks: vec![task("t1")],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    assert_eq!(resp.into_inner().status, 0);
+}
+
+// ── Scenarios ─────────────────────────────────────────────────────────────────
+
+/// A node whose first fetch exceeds its deadline retries and gets the same
+/// schedule.
+#[tokio::test]
+async fn delayed_node_serve_is_recovered_by_retry() {
+    let injector = Arc::new(FailureInjector::new());
+    injector.delay_nth(InjectionPoint::NodeServe, 1, Duration::from_millis(500));
+    let (sched, node) = services(&injector, Arc::default());
+    submit(&sched).await;
+
+    let fetch = || {
+        node.get_sched_info(Request::new(NodeSchedRequest {
+            node_id: "n1".into(),
+            ..Default::default()
+        }))
+    };
+    let deadline = Duration::from_millis(100);
+
+    assert!(
+        tokio::time::timeout(deadline, fetch()).await.is_err(),
+        "first fetch should exceed the node's deadline"
+    );
+    let resp = tokio::time::timeout(deadline, fetch())
+        .await
+        .expect("retry should be served promptly")
+        .unwrap()
+        .into_inner();
+    assert_eq!(resp.tasks.len(), 1);
+    assert_eq!(injector.calls(InjectionPoint::NodeServe), 2);
+}
+
+/// An undeliverable deadline-miss report is flagged to the node instead of
+/// being dropped silently; the next report goes through.
+#[tokio::test]
+async fn fault_send_failure_is_reported_to_the_node() {
+    let injector = Arc::new(FailureInjector::new());
+    injector.fail_nth(InjectionPoint::FaultSend, 1);
+    let notifier = Arc::new(RecordingNotifier::default());
+    let (sched, node) = services(&injector, Arc::clone(&notifier));
+    submit(&sched).await;
+
+    let report = || {
+        node.report_d_miss(Request::new(DeadlineMissInfo {
+            node_id: "n1".into(),
+            task_name: "t1".into(),
+            drift: None,
+        }))
+    };
+
+    let first = report().await.unwrap().into_inner();
+    assert_ne!(first.status, 0);
+    assert!(first.error_message.contains("injected failure"));
+    assert!(notifier.delivered.lock().unwrap().is_empty());
+
+    let second = report().await.unwrap().into_inner();
+    assert_eq!(second.status, 0);
+    assert_eq!(notifier.delivered.lock().unwrap().len(), 1);
+}
+
+/// A reload that fails part-way leaves the manager marked unloaded, and a
+/// later reload recovers.
+#[tokio::test]
+async fn config_reload_failure_marks_config_unloaded() {
+    let injector = Arc::new(FailureInjector::new());
+    injector.fail_nth(InjectionPoint::ConfigReload, 2);
+    let file = config_file();
+    let mut config = NodeConfigManager::new().with_failure_injector(Arc::clone(&injector));
+
+    config.load_from_file(file.path()).unwrap();
+    assert!(config.is_loaded());
+
+    let err = config.load_from_file(file.path()).unwrap_err();
+    assert!(err.to_string().contains("config_reload"), "{err}");
+    assert!(!config.is_loaded());
+    assert!(config.get_node_config("n1").is_none());
+
+    config.load_from_file(file.path()).unwrap();
+    assert!(config.get_node_config("n1").is_some());
+}
@@ -0,0 +1,162 @@
+/*
+ * SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+ * SPDX-License-Identifier: MIT
+ */
+
+/*
+ * Captures the schedule payload the C++ Timpani-O hands a legacy Timpani-N
+ * (`DBusServer::SerializeSchedInfo`) for the fixture schedules of
+ * `timpani_o::compat::cxx`, one `<fixture>.bin` per schedule.
+ *
+ * The two loops below are copied from `GlobalScheduler` (filling
+ * `sched_task_t`) and `DBusServer::SerializeSchedInfo` (packing it) and run
+ * against the real libtrpc serializer; the rest of `DBusServer` needs a
+ * D-Bus session, so it is left out.  From this directory:
+ *
+ *   gcc -Istub -I../../../../../libtrpc/src \
+ *       -c ../../../../../libtrpc/src/serialize.c -o /tmp/serialize.o
+ *   g++ -Istub -I../../../../../timpani-o/src -I../../../../../libtrpc/src \
+ *       capture.cpp /tmp/serialize.o -o /tmp/capture
+ *   /tmp/capture .
+ *
+ * `stub/` stands in for the libsystemd headers libtrpc.h includes.
+ *
+ * Keep the fixtures here in step with the `fixtures` module of the tests in
+ * `src/compat/cxx.rs`.
+ */
+
+#include <cstdio>
+#include <cstring>
+#include <map>
+#include <string>
+#include <vector>
+
+#include "libtrpc.h"
+#include "sched_info.h"
+
+constexpr int kNsToUs = 1000;
+
+struct Task {
+    std::string name;
+    std::string assigned_node;
+    int assigned_cpu;
+    int policy;
+    int priority;
+    uint64_t period_us;
+    uint64_t runtime_us;
+    uint64_t deadline_us;
+    int release_time;
+    int max_dmiss;
+};
+
+struct Fixture {
+    const char* file;
+    std::string workload_id;
+    uint64_t hyperperiod_us;
+    std::vector<Task> tasks;
+};
+
+// GlobalScheduler: tasks_ → sched_info_map_
+static std::map<std::string, std::vector<sched_task_t>> Fill(const std::vector<Task>& tasks_)
+{
+    std::map<std::string, std::vector<sched_task_t>> sched_info_map_;
+    for (const auto& task : tasks_) {
+        sched_task_t sched_task;
+        memset(&sched_task, 0, sizeof(sched_task));
+
+        strncpy(sched_task.task_name, task.name.c_str(),
+                sizeof(sched_task.task_name) - 1);
+        sched_task.task_name[sizeof(sched_task.task_name) - 1] = '\0';
+
+        sched_task.period_ns = task.period_us * 1000;      // Convert to nanoseconds
+        sched_task.runtime_ns = task.runtime_us * 1000;    // Convert to nanoseconds
+        sched_task.deadline_ns = task.deadline_us * 1000;  // Convert to nanoseconds
+        sched_task.cpu_affinity = task.assigned_cpu;
+        sched_task.sched_policy = task.policy;
+        sched_task.sched_priority = task.priority;
+        sched_task.release_time = task.release_time; // Release time in microseconds
+        sched_task.max_dmiss = task.max_dmiss;
+
+        strncpy(sched_task.assigned_node, task.assigned_node.c_str(),
+                sizeof(sched_task.assigned_node) - 1);
+        sched_task.assigned_node[sizeof(sched_task.assigned_node) - 1] = '\0';
+
+        sched_info_map_[task.assigned_node].push_back(sched_task);
+    }
+    return sched_info_map_;
+}
+
+// DBusServer::SerializeSchedInfo
+static serial_buf_t* Serialize(const Fixture& f)
+{
+    const auto node_sched_info = Fill(f.tasks);
+    const std::string& workload_id = f.workload_id;
+
+    serial_buf_t* sched_info_buf_ = new_serial_buf(1024 + 256);
+
+    uint64_t hyperperiod_us = f.hyperperiod_us;
+    serialize_int64_t(sched_info_buf_, hyperperiod_us);
+    serialize_str(sched_info_buf_, workload_id.substr(0, 64 - 1).c_str());
+
+    int nr_tasks = 0;
+    for (const auto& sinfo : node_sched_info) {
+        const std::vector<sched_task_t>& tasks = sinfo.second;
+
+        for (size_t i = 0; i < tasks.size(); i++) {
+            const sched_task_t& task = tasks[i];
+            std::string task_name = task.task_name;
+            serialize_str(sched_info_buf_,
+                          task_name.substr(0, 16 - 1).c_str());
+            serialize_int32_t(sched_info_buf_, task.sched_priority);
+            serialize_int32_t(sched_info_buf_, task.sched_policy);
+            serialize_int32_t(sched_info_buf_, task.period_ns / kNsToUs);
+            serialize_int32_t(sched_info_buf_, task.release_time);
+            serialize_int32_t(sched_info_buf_, task.runtime_ns / kNsToUs);
+            serialize_int32_t(sched_info_buf_, task.deadline_ns / kNsToUs);
+            serialize_int64_t(sched_info_buf_, task.cpu_affinity);
+            serialize_int32_t(sched_info_buf_, task.max_dmiss);
+            std::string task_assigned_node = task.assigned_node;
+            serialize_str(sched_info_buf_,
+                        task_assigned_node.substr(0, 64 - 1).c_str());
+        }
+        nr_tasks += tasks.size();
+    }
+    serialize_int32_t(sched_info_buf_, nr_tasks);
+    return sched_info_buf_;
+}
+
+int main(int argc, char** argv)
+{
+    const std::string dir = argc > 1 ? argv[1] : ".";
+    const std::string long_node =
+        "ecu_front_perception_and_sensor_fusion_compute_node_with_a_long_hostname";
+
+    const std::vector<Fixture> fixtures = {
+        {"basic.bin", "wl_basic", 20000, {
+            {"lidar",     "node01", 3, SCHED_RR,    70, 20000, 5000, 20000, 500, 0},
+            {"planner",   "node02", 1, SCHED_OTHER,  0,  5000, 1000,  5000,   0, 3},
+            {"cam_front", "node01", 2, SCHED_FIFO,  80, 10000, 2000,  8000,   0, 1},
+        }},
+        {"truncated.bin",
+         "vehicle_perception_stack_with_a_really_long_workload_identifier_v2", 100000, {
+            {"sensor_fusion_main", long_node, 4, SCHED_FIFO, 90, 50000, 10000, 40000, 0, 2},
+            {"object_tracker_v2",  long_node, 5, SCHED_FIFO, 85, 100000, 20000, 100000, 250, 0},
+            {"ctl",                "node01",  0, SCHED_RR,   60, 10000, 1000, 10000, 0, 0},
+        }},
+        {"empty.bin", "wl_empty", 0, {}},
+    };
+
+    for (const auto& f : fixtures) {
+        serial_buf_t* buf = Serialize(f);
+        const std::string path = dir + "/" + f.file;
+        FILE* out = fopen(path.c_str(), "wb");
+        if (!out || fwrite(buf->data, 1, buf->pos, out) != buf->pos) {
+            perror(path.c_str());
+            return 1;
+        }
+        fclose(out);
+        printf("%s: %zu bytes\n", path.c_str(), buf->pos);
+        free_serial_buf(buf);
+    }
+    return 0;
+}
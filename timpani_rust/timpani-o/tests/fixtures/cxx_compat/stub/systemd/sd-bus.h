@@ -0,0 +1,2 @@
+/* Opaque stand-in so libtrpc.h compiles without libsystemd headers. */
+typedef struct sd_bus sd_bus;
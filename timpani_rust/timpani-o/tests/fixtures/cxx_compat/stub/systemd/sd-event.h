@@ -0,0 +1,6 @@
+/* Opaque stand-ins so libtrpc.h compiles without libsystemd headers. */
+#include <stddef.h>
+#include <stdint.h>
+#include <time.h>
+typedef struct sd_event sd_event;
+typedef struct sd_event_source sd_event_source;
@@ -0,0 +1,399 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+//! Wire compatibility of `AddSchedInfo` across schema revisions (see
+//! `timpani_o::proto`).
+//!
+//! `proto_compat/rNN.sched_info.bin` and `rNN.response.bin` are the same
+//! workload and answer as encoded at revision NN, with every field that
+//! revision had set.  They are never regenerated: a golden that no longer
+//! decodes to what its revision meant is a break for that Pullpiri build.
+//! `proto_compat/previous.rs` is the code generated for the previous
+//! revision, which must still read what the current code writes.
+//!
+//! The golden for a new revision is recorded with
+//! `TIMPANI_BLESS_PROTO_COMPAT=1 cargo test -p timpani-o --test proto_compat`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use prost::Message;
+
+use timpani_o::grpc::schedinfo_service::task_from_proto;
+use timpani_o::proto::schedinfo_v1::{
+    ChainAttempt, ChainLatency, Response, SchedInfo, SharedResource as ProtoSharedResource,
+    TaskChain, TaskInfo, TaskPlacement, WorkloadSummary,
+};
+use timpani_o::proto::SCHEMA_REVISION;
+use timpani_o::task::{
+    CpuAffinity, FaultSink, Micros, SchedPolicy, SharedResource, TargetNodePolicy, Task,
+};
+
+/// Generated code of revision `SCHEMA_REVISION - 1`; only the messages are
+/// exercised.
+#[allow(dead_code)]
+#[path = "proto_compat/previous.rs"]
+mod previous;
+
+const BLESS_VAR: &str = "TIMPANI_BLESS_PROTO_COMPAT";
+
+// ── Goldens ───────────────────────────────────────────────────────────────────
+
+fn golden_path(rev: u32, kind: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("tests/proto_compat/r{rev:02}.{kind}.bin"))
+}
+
+fn golden(rev: u32, kind: &str) -> Vec<u8> {
+    let path = golden_path(rev, kind);
+    fs::read(&path).unwrap_or_else(|e| {
+        panic!(
+            "{}: {e}; record it with `{BLESS_VAR}=1 cargo test -p timpani-o --test proto_compat`",
+            path.display()
+        )
+    })
+}
+
+/// The golden workload's tasks as sent at `rev`.
+fn task_infos(rev: u32) -> Vec<TaskInfo> {
+    let mut cam = TaskInfo {
+        name: "cam".into(),
+        priority: 80,
+        policy: 1,
+        cpu_affinity: 0b10,
+        period: 10_000,
+        release_time: 500,
+        runtime: 2_000,
+        deadline: 8_000,
+        node_id: "node01".into(),
+        max_dmiss: 3,
+        ..Default::default()
+    };
+    let mut log = TaskInfo {
+        name: "log".into(),
+        priority: 10,
+        policy: 2,
+        period: 50_000,
+        runtime: 5_000,
+        deadline: 50_000,
+        ..Default::default()
+    };
+    if rev >= 4 {
+        let bus = |max_cs_us| ProtoSharedResource {
+            name: "bus".into(),
+            max_cs_us,
+        };
+        cam.shared_resources = vec![bus(100)];
+        log.shared_resources = vec![bus(50)];
+    }
+    if rev >= 5 {
+        cam.target_node_policy = Some(1);
+    }
+    if rev >= 10 {
+        cam.wcet_scaling = [("x86_64".to_string(), 0.7)].into();
+    }
+    if rev >= 11 {
+        cam.metadata = [("image".to_string(), "cam:1.2".to_string())].into();
+    }
+    if rev >= 16 {
+        cam.preferred_location = "front".into();
+    }
+    if rev >= 18 {
+        log.placeholder = true;
+    }
+    if rev >= 20 {
+        cam.fault_sink = 1; // LOCAL_ONLY
+    }
+    if rev >= 23 {
+        cam.cache_affinity_group = "vision".into();
+        log.cache_affinity_group = "vision".into();
+    }
+    if rev >= 24 {
+        cam.memory_mb = 256;
+    }
+    vec![cam, log]
+}
+
+/// The golden request as sent at `rev`.
+fn request(rev: u32) -> SchedInfo {
+    let mut req = SchedInfo {
+        workload_id: "compat".into(),
+        tasks: task_infos(rev),
+        ..Default::default()
+    };
+    if rev >= 2 {
+        req.algorithm = Some("least_loaded".into());
+        req.cpu_utilization_threshold = Some(0.8);
+    }
+    if rev >= 3 {
+        req.seed = Some(42);
+    }
+    if rev >= 6 {
+        req.queue_if_full = Some(true);
+        req.importance = 5;
+    }
+    if rev >= 9 {
+        req.allowed_nodes = vec!["node01".into(), "node02".into()];
+    }
+    if rev >= 12 {
+        req.force = Some(true);
+    }
+    if rev >= 13 {
+        req.admission_overrides = vec!["skip_memory".into()];
+    }
+    if rev >= 14 {
+        req.priority_class = Some("safety".into());
+    }
+    if rev >= 15 {
+        req.ttl_seconds = Some(3600);
+    }
+    if rev >= 19 {
+        req.priority_ordering = Some("deadline_monotonic".into());
+    }
+    if rev >= 21 {
+        req.exclusive_cpus = Some(true);
+    }
+    if rev >= 22 {
+        req.algorithm_chain = vec!["target_node_priority".into(), "best_fit_decreasing".into()];
+    }
+    if rev >= 25 {
+        req.chains = vec![TaskChain {
+            name: "sense".into(),
+            tasks: vec!["cam".into()],
+            end_to_end_deadline_us: 5_000,
+        }];
+    }
+    req
+}
+
+/// The golden (successful) response as sent at `rev`.
+fn response(rev: u32) -> Response {
+    let mut resp = Response::default();
+    if rev >= 5 {
+        let placement = |task: &str, node: &str, cpu, requested: &str, margin| TaskPlacement {
+            task: task.into(),
+            node: node.into(),
+            cpu,
+            requested_node: requested.into(),
+            runtime_margin: (rev >= 17).then_some(margin),
+            // On different nodes, so not honoured.
+            cache_group_honoured: (rev >= 23).then_some(false),
+            ..Default::default()
+        };
+        resp.placements = vec![
+            placement("cam", "node01", 1, "node01", 4.0),
+            placement("log", "node02", 0, "", 10.0),
+        ];
+    }
+    if rev >= 8 {
+        let mut summary = WorkloadSummary {
+            workload_id: "compat".into(),
+            task_count: 2,
+            nodes: vec!["node01".into(), "node02".into()],
+            total_utilization: 0.3,
+            peak_cpu_utilization: 0.2,
+            hyperperiod_us: 50_000,
+            ..Default::default()
+        };
+        if rev >= 13 {
+            summary.admission_overrides = vec!["skip_memory".into()];
+        }
+        if rev >= 17 {
+            summary.min_runtime_margin = Some(4.0);
+        }
+        if rev >= 19 {
+            summary.priority_ordering = "deadline_monotonic".into();
+        }
+        if rev >= 25 {
+            summary.chains = vec![ChainLatency {
+                name: "sense".into(),
+                node: "node01".into(),
+                cpu: 1,
+                latency_us: Some(2_000),
+                deadline_us: 5_000,
+                met: true,
+                ..Default::default()
+            }];
+        }
+        resp.summary = Some(summary);
+    }
+    if rev >= 22 {
+        resp.chain_attempts = vec![
+            ChainAttempt {
+                algorithm: "target_node_priority".into(),
+                error_code: 1006,
+                error: "task 'log' has no target_node — required by target_node_priority \
+                        algorithm"
+                    .into(),
+            },
+            ChainAttempt {
+                algorithm: "best_fit_decreasing".into(),
+                ..Default::default()
+            },
+        ];
+    }
+    resp
+}
+
+/// What the golden tasks of `rev` mean, written against `Task` rather than
+/// the proto: a field the revision lacks has the meaning it had then.
+fn expected_tasks(rev: u32) -> Vec<Task> {
+    let bus = |max_cs_us| SharedResource {
+        name: "bus".into(),
+        max_cs_us: Micros(max_cs_us),
+    };
+    let cam = Task {
+        name: "cam".into(),
+        workload_id: "compat".into(),
+        target_node: "node01".into(),
+        target_node_policy: (rev >= 5).then_some(TargetNodePolicy::Preferred),
+        preferred_location: if rev >= 16 {
+            "front".into()
+        } else {
+            String::new()
+        },
+        policy: SchedPolicy::Fifo,
+        priority: 80,
+        affinity: CpuAffinity::Pinned(0b10),
+        period_us: Micros(10_000),
+        runtime_us: Micros(2_000),
+        deadline_us: Micros(8_000),
+        release_time_us: 500,
+        max_dmiss: 3,
+        shared_resources: if rev >= 4 { vec![bus(100)] } else { vec![] },
+        wcet_scaling: if rev >= 10 {
+            [("x86_64".to_string(), 0.7)].into()
+        } else {
+            Default::default()
+        },
+        metadata: if rev >= 11 {
+            [("image".to_string(), "cam:1.2".to_string())].into()
+        } else {
+            Default::default()
+        },
+        fault_sink: if rev >= 20 {
+            FaultSink::LocalOnly
+        } else {
+            FaultSink::Upstream
+        },
+        cache_affinity_group: (rev >= 23).then(|| "vision".into()),
+        memory_mb: if rev >= 24 { 256 } else { 0 },
+        ..Default::default()
+    };
+    let log = Task {
+        name: "log".into(),
+        workload_id: "compat".into(),
+        policy: SchedPolicy::RoundRobin,
+        priority: 10,
+        affinity: CpuAffinity::Any,
+        period_us: Micros(50_000),
+        runtime_us: Micros(5_000),
+        deadline_us: Micros(50_000),
+        shared_resources: if rev >= 4 { vec![bus(50)] } else { vec![] },
+        placeholder: rev >= 18,
+        cache_affinity_group: (rev >= 23).then(|| "vision".into()),
+        ..Default::default()
+    };
+    vec![cam, log]
+}
+
+// ── Old messages, current code ────────────────────────────────────────────────
+
+#[test]
+fn every_revision_has_goldens() {
+    if std::env::var_os(BLESS_VAR).is_some() {
+        for (kind, bytes) in [
+            ("sched_info", request(SCHEMA_REVISION).encode_to_vec()),
+            ("response", response(SCHEMA_REVISION).encode_to_vec()),
+        ] {
+            let path = golden_path(SCHEMA_REVISION, kind);
+            if !path.exists() {
+                fs::write(&path, bytes).unwrap();
+            }
+        }
+    }
+    for rev in 1..=SCHEMA_REVISION {
+        golden(rev, "sched_info");
+        golden(rev, "response");
+    }
+    assert!(
+        !golden_path(SCHEMA_REVISION + 1, "sched_info").exists(),
+        "a golden is newer than SCHEMA_REVISION"
+    );
+}
+
+#[test]
+fn old_requests_decode_with_defaults_for_later_fields() {
+    for rev in 1..=SCHEMA_REVISION {
+        let decoded = SchedInfo::decode(golden(rev, "sched_info").as_slice())
+            .unwrap_or_else(|e| panic!("r{rev:02}: {e}"));
+        assert_eq!(decoded, request(rev), "r{rev:02}");
+    }
+}
+
+#[test]
+fn old_requests_map_to_the_tasks_they_meant() {
+    for rev in 1..=SCHEMA_REVISION {
+        let req = SchedInfo::decode(golden(rev, "sched_info").as_slice()).unwrap();
+        let tasks: Vec<Task> = req
+            .tasks
+            .iter()
+            .map(|t| task_from_proto(t, &req.workload_id))
+            .collect();
+        // `Task` has no `PartialEq`; its `Debug` output lists every field.
+        assert_eq!(
+            format!("{tasks:#?}"),
+            format!("{:#?}", expected_tasks(rev)),
+            "r{rev:02}"
+        );
+    }
+}
+
+#[test]
+fn old_responses_decode_with_defaults_for_later_fields() {
+    for rev in 1..=SCHEMA_REVISION {
+        let decoded = Response::decode(golden(rev, "response").as_slice())
+            .unwrap_or_else(|e| panic!("r{rev:02}: {e}"));
+        assert_eq!(decoded, response(rev), "r{rev:02}");
+    }
+}
+
+// ── Current messages, previous code ───────────────────────────────────────────
+
+/// Decode `bytes` with the previous revision's `P`, encode that again and
+/// decode it with the current `C`: what the previous revision understood of
+/// a current message.  Fields it does not know are dropped.
+fn through_previous<P: Message + Default, C: Message + Default>(bytes: &[u8]) -> C {
+    let old = P::decode(bytes).expect("previous revision cannot decode current message");
+    C::decode(old.encode_to_vec().as_slice()).unwrap()
+}
+
+#[test]
+fn previous_revision_reads_current_requests() {
+    let bytes = request(SCHEMA_REVISION).encode_to_vec();
+    let seen: SchedInfo = through_previous::<previous::SchedInfo, _>(&bytes);
+    assert_eq!(seen, request(SCHEMA_REVISION - 1));
+}
+
+#[test]
+fn previous_revision_reads_current_responses() {
+    let bytes = response(SCHEMA_REVISION).encode_to_vec();
+    let seen: Response = through_previous::<previous::Response, _>(&bytes);
+    assert_eq!(seen, response(SCHEMA_REVISION - 1));
+}
+
+#[test]
+fn current_revision_reads_what_the_previous_one_wrote() {
+    let rev = SCHEMA_REVISION - 1;
+    let req = previous::SchedInfo::decode(golden(rev, "sched_info").as_slice()).unwrap();
+    assert_eq!(
+        SchedInfo::decode(req.encode_to_vec().as_slice()).unwrap(),
+        request(rev)
+    );
+    let resp = previous::Response::decode(golden(rev, "response").as_slice()).unwrap();
+    assert_eq!(
+        Response::decode(resp.encode_to_vec().as_slice()).unwrap(),
+        response(rev)
+    );
+}
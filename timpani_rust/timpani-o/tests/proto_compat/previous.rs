@@ -0,0 +1,414 @@
+/*
+SPDX-FileCopyrightText: Copyright 2026 LG Electronics Inc.
+SPDX-License-Identifier: MIT
+*/
+
+// `schedinfo.v1` as generated by tonic-build for schema revision 24 (the
+// revision before `timpani_o::proto::SCHEMA_REVISION`), trimmed to the
+// `AddSchedInfo` request and response messages.  Do not edit by hand: when
+// the schema revision is bumped, replace the messages below with the ones
+// generated from the proto *before* the change (`OUT_DIR/schedinfo.v1.rs`).
+
+// This file is @generated by prost-build.
+/// Common response message for SchedInfoService and FaultService
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Response {
+    /// Status code: 0 for success, non-zero for error
+    #[prost(int32, tag = "1")]
+    pub status: i32,
+    /// Where each task was placed (AddSchedInfo success only)
+    #[prost(message, repeated, tag = "2")]
+    pub placements: ::prost::alloc::vec::Vec<TaskPlacement>,
+    /// True if the workload was parked in the pending queue (status 1)
+    #[prost(bool, tag = "3")]
+    pub queued: bool,
+    /// Stable TIMPANI_E_* code when status is -1 (0 = none, see ErrorCode)
+    #[prost(uint32, tag = "4")]
+    pub error_code: u32,
+    /// Workload-level summary (AddSchedInfo success only)
+    #[prost(message, optional, tag = "5")]
+    pub summary: ::core::option::Option<WorkloadSummary>,
+    /// Every algorithm tried, in order, when an algorithm chain was used; on
+    /// success the last one placed the workload
+    #[prost(message, repeated, tag = "6")]
+    pub chain_attempts: ::prost::alloc::vec::Vec<ChainAttempt>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainAttempt {
+    #[prost(string, tag = "1")]
+    pub algorithm: ::prost::alloc::string::String,
+    /// TIMPANI_E_* code of the link's error; 0 for the link that succeeded
+    #[prost(uint32, tag = "2")]
+    pub error_code: u32,
+    #[prost(string, tag = "3")]
+    pub error: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WorkloadSummary {
+    #[prost(string, tag = "1")]
+    pub workload_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub task_count: u32,
+    /// Nodes that received at least one task, sorted
+    #[prost(string, repeated, tag = "3")]
+    pub nodes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Sum of task utilisations, in CPU-equivalents
+    #[prost(double, tag = "4")]
+    pub total_utilization: f64,
+    /// Highest per-CPU utilisation (0.0 - 1.0)
+    #[prost(double, tag = "5")]
+    pub peak_cpu_utilization: f64,
+    #[prost(uint64, tag = "6")]
+    pub hyperperiod_us: u64,
+    /// Feasibility warnings raised for the schedule
+    #[prost(uint32, tag = "7")]
+    pub warning_count: u32,
+    /// Admission checks skipped for this workload (SchedInfo.admission_overrides)
+    #[prost(string, repeated, tag = "8")]
+    pub admission_overrides: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Smallest TaskPlacement.runtime_margin of the workload; unset if no
+    /// task has one
+    #[prost(double, optional, tag = "9")]
+    pub min_runtime_margin: ::core::option::Option<f64>,
+    /// Ordering priority-0 tasks were ranked by (SchedInfo.priority_ordering)
+    #[prost(string, tag = "10")]
+    pub priority_ordering: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskPlacement {
+    #[prost(string, tag = "1")]
+    pub task: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub node: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub cpu: u32,
+    /// True if the preferred node_id could not take the task and another
+    /// node was auto-selected
+    #[prost(bool, tag = "4")]
+    pub target_fallback: bool,
+    /// The node_id the task asked for (empty if none)
+    #[prost(string, tag = "5")]
+    pub requested_node: ::prost::alloc::string::String,
+    /// Non-zero if this task could not be placed: the admission reason's
+    /// TIMPANI_E_* code when known, else the scheduler error's
+    #[prost(uint32, tag = "6")]
+    pub error_code: u32,
+    /// Largest factor the task's runtime could be multiplied by before its
+    /// CPU fails the configured analysis, rounded down to 0.01; unset for
+    /// aperiodic and unplaced tasks
+    #[prost(double, optional, tag = "7")]
+    pub runtime_margin: ::core::option::Option<f64>,
+    /// Whether the task shares a cpu_clusters entry of its node with a member
+    /// of its cache_affinity_group on another CPU, and its own CPU with none;
+    /// unset unless another member of the group was placed
+    #[prost(bool, optional, tag = "8")]
+    pub cache_group_honoured: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskInfo {
+    /// Unique task name
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// Task priority; 0 on a FIFO/RR task = assigned by Timpani-O from the
+    /// task's timing (SchedInfo.priority_ordering)
+    #[prost(int32, tag = "2")]
+    pub priority: i32,
+    /// Scheduling policy
+    #[prost(enumeration = "SchedPolicy", tag = "3")]
+    pub policy: i32,
+    /// CPU affinity
+    #[prost(uint64, tag = "4")]
+    pub cpu_affinity: u64,
+    /// Period in us
+    #[prost(int32, tag = "5")]
+    pub period: i32,
+    /// Release time in us
+    #[prost(int32, tag = "6")]
+    pub release_time: i32,
+    /// Runtime in us
+    #[prost(int32, tag = "7")]
+    pub runtime: i32,
+    /// Deadline in us
+    #[prost(int32, tag = "8")]
+    pub deadline: i32,
+    /// Node ID where the task is running
+    #[prost(string, tag = "9")]
+    pub node_id: ::prost::alloc::string::String,
+    /// Maximum number of deadline misses allowed
+    #[prost(int32, tag = "10")]
+    pub max_dmiss: i32,
+    /// Locks shared with other tasks (for priority-ceiling blocking analysis)
+    #[prost(message, repeated, tag = "11")]
+    pub shared_resources: ::prost::alloc::vec::Vec<SharedResource>,
+    /// How strictly node_id is honoured. Algorithm default when unset
+    /// (HARD for target_node_priority, PREFERRED for best_fit_decreasing,
+    /// ignored by least_loaded and randomized_spread).
+    #[prost(enumeration = "TargetNodePolicy", optional, tag = "12")]
+    pub target_node_policy: ::core::option::Option<i32>,
+    /// Runtime multiplier per node architecture (e.g. {aarch64: 1.0,
+    /// x86_64: 0.7}); runtime is measured on a reference board. Architectures
+    /// not listed run the runtime unscaled.
+    #[prost(map = "string, double", tag = "13")]
+    pub wcet_scaling: ::std::collections::HashMap<::prost::alloc::string::String, f64>,
+    /// Opaque key/value pairs (container image, service version, trace ids),
+    /// carried through to Timpani-N and status unchanged. Size-limited by
+    /// Timpani-O (--metadata-max-keys, --metadata-max-value-len).
+    #[prost(map = "string, string", tag = "14")]
+    pub metadata:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Zone the task would rather run near (e.g. its sensors); breaks
+    /// near-ties between nodes by location under least_loaded and
+    /// best_fit_decreasing when Timpani-O has a proximity table. Empty = none.
+    #[prost(string, tag = "15")]
+    pub preferred_location: ::prost::alloc::string::String,
+    /// Reserves capacity for a task that arrives later: admitted and counted
+    /// like any task, but never applied by Timpani-N nor monitored for
+    /// deadline misses. Resubmitting it as a real task under the same name
+    /// keeps its node and CPU while they still fit.
+    #[prost(bool, tag = "16")]
+    pub placeholder: bool,
+    /// Where the task's deadline misses are reported. UPSTREAM (to Piccolo
+    /// through Timpani-O) when unset.
+    #[prost(enumeration = "FaultSink", tag = "17")]
+    pub fault_sink: i32,
+    /// Tasks of the workload naming the same group exchange data through a
+    /// shared cache: each is placed on a CPU of its node's cluster where a
+    /// member runs, but on a different CPU, when one has room. A hint only;
+    /// never fails a placement. Empty = none.
+    #[prost(string, tag = "18")]
+    pub cache_affinity_group: ::prost::alloc::string::String,
+    /// Memory the task needs on its node, in MB; admitted against the node's
+    /// max_memory_mb (or its live free memory, when enabled). 0 = none.
+    #[prost(uint64, tag = "19")]
+    pub memory_mb: u64,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SharedResource {
+    /// Resource name; tasks naming the same resource share it
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// Longest critical section held by this task, in us
+    #[prost(int32, tag = "2")]
+    pub max_cs_us: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchedInfo {
+    #[prost(string, tag = "1")]
+    pub workload_id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub tasks: ::prost::alloc::vec::Vec<TaskInfo>,
+    /// Scheduling algorithm override (target_node_priority, least_loaded,
+    /// best_fit_decreasing, randomized_spread). Timpani-O's configured default
+    /// when unset.
+    #[prost(string, optional, tag = "3")]
+    pub algorithm: ::core::option::Option<::prost::alloc::string::String>,
+    /// Per-CPU utilization threshold override, in (0, 1].
+    /// Timpani-O's configured default when unset.
+    #[prost(double, optional, tag = "4")]
+    pub cpu_utilization_threshold: ::core::option::Option<f64>,
+    /// RNG seed for randomized_spread; ignored by the other algorithms.
+    /// Timpani-O's configured default when unset.
+    #[prost(uint64, optional, tag = "5")]
+    pub seed: ::core::option::Option<u64>,
+    /// Park the workload in the pending queue instead of rejecting it when
+    /// it only fails for lack of capacity. Scheduled automatically once
+    /// capacity is released; a WORKLOAD_SCHEDULED advisory follows.
+    #[prost(bool, optional, tag = "6")]
+    pub queue_if_full: ::core::option::Option<bool>,
+    /// Pending-queue and drain priority within the priority class; higher is
+    /// retried first and drained last
+    #[prost(int32, tag = "7")]
+    pub importance: i32,
+    /// Restrict placement to these nodes (empty = every configured node).
+    /// A task whose node_id is outside the set is rejected.
+    #[prost(string, repeated, tag = "8")]
+    pub allowed_nodes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Accept a revision of the stored workload even if it changes a task's
+    /// period or runtime by more than Timpani-O's revision factor (default 10x);
+    /// without it such a revision fails with FAILED_PRECONDITION.
+    #[prost(bool, optional, tag = "9")]
+    pub force: ::core::option::Option<bool>,
+    /// Admission checks to skip for a lab experiment: "skip_memory",
+    /// "skip_threshold". Rejected with INVALID_ARGUMENT unless Timpani-O allows
+    /// overrides; when honoured, each is audited and reported to Pullpiri as an
+    /// ADMISSION_OVERRIDE advisory.
+    #[prost(string, repeated, tag = "10")]
+    pub admission_overrides: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Priority class: "safety", "platform" or "best_effort" (highest first).
+    /// Orders the pending queue and node drains ahead of importance; only a
+    /// strictly higher class may evict. "platform" when unset; any other value
+    /// is rejected with INVALID_ARGUMENT.
+    #[prost(string, optional, tag = "11")]
+    pub priority_class: ::core::option::Option<::prost::alloc::string::String>,
+    /// Remove the workload automatically this many seconds after it is
+    /// admitted, as if by RemoveWorkload (WORKLOAD_EXPIRED advisory). Renew by
+    /// resubmitting with a new ttl_seconds; unset = never expires, 0 is
+    /// rejected with INVALID_ARGUMENT. A queued workload's TTL starts when it
+    /// is scheduled.
+    #[prost(uint64, optional, tag = "12")]
+    pub ttl_seconds: ::core::option::Option<u64>,
+    /// How tasks sent with priority 0 are ranked: "rate_monotonic" (shorter
+    /// period first) or "deadline_monotonic" (shorter deadline first, for
+    /// deadlines below the period). "rate_monotonic" when unset; any other
+    /// value is rejected with INVALID_ARGUMENT.
+    #[prost(string, optional, tag = "13")]
+    pub priority_ordering: ::core::option::Option<::prost::alloc::string::String>,
+    /// Dedicate every CPU that receives one of the workload's tasks to it: no
+    /// other workload's task is placed there, now or later, whatever the
+    /// headroom, and its own tasks avoid CPUs other workloads use. A task
+    /// that could only go to such a CPU is rejected with reason
+    /// TIMPANI_E_CPU_EXCLUSIVELY_RESERVED.
+    #[prost(bool, optional, tag = "14")]
+    pub exclusive_cpus: ::core::option::Option<bool>,
+    /// Algorithms to try in order until one places the whole workload, each
+    /// against the cluster as it was before the first (e.g.
+    /// \["target_node_priority", "best_fit_decreasing"\]). Overrides algorithm
+    /// and Timpani-O's configured chain; empty = algorithm alone, or the
+    /// configured chain when algorithm is unset too. Response.chain_attempts
+    /// reports each link tried.
+    #[prost(string, repeated, tag = "15")]
+    pub algorithm_chain: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::prost::Enumeration,
+)]
+#[repr(i32)]
+pub enum SchedPolicy {
+    /// SCHED_NORMAL
+    Normal = 0,
+    /// SCHED_FIFO
+    Fifo = 1,
+    /// SCHED_RR
+    Rr = 2,
+}
+impl SchedPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Normal => "NORMAL",
+            Self::Fifo => "FIFO",
+            Self::Rr => "RR",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "NORMAL" => Some(Self::Normal),
+            "FIFO" => Some(Self::Fifo),
+            "RR" => Some(Self::Rr),
+            _ => None,
+        }
+    }
+}
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::prost::Enumeration,
+)]
+#[repr(i32)]
+pub enum FaultSink {
+    /// ReportDMiss to Timpani-O, forwarded to Piccolo
+    Upstream = 0,
+    /// Only the node's local fault sink (e.g. a vehicle-local safety monitor);
+    /// not forwarded to Piccolo
+    LocalOnly = 1,
+    /// Both
+    Both = 2,
+}
+impl FaultSink {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Upstream => "FAULT_SINK_UPSTREAM",
+            Self::LocalOnly => "FAULT_SINK_LOCAL_ONLY",
+            Self::Both => "FAULT_SINK_BOTH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "FAULT_SINK_UPSTREAM" => Some(Self::Upstream),
+            "FAULT_SINK_LOCAL_ONLY" => Some(Self::LocalOnly),
+            "FAULT_SINK_BOTH" => Some(Self::Both),
+            _ => None,
+        }
+    }
+}
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::prost::Enumeration,
+)]
+#[repr(i32)]
+pub enum TargetNodePolicy {
+    /// Place on node_id or reject the workload
+    Hard = 0,
+    /// Try node_id first, fall back to auto-select
+    Preferred = 1,
+}
+impl TargetNodePolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Hard => "HARD",
+            Self::Preferred => "PREFERRED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "HARD" => Some(Self::Hard),
+            "PREFERRED" => Some(Self::Preferred),
+            _ => None,
+        }
+    }
+}